@@ -23,7 +23,7 @@ import { loadSecurityConfig, loadAgentConfig, loadIntegrationsConfig } from "./s
 import { subscribeIncomingDeviceEvents } from "./src/native/incomingCalls";
 import { getAndroidRuntimeBridgeStatus } from "./src/native/androidAgentBridge";
 import { applyRuntimeSupervisorConfig, reportRuntimeHookEvent, startRuntimeSupervisor } from "./src/runtime/supervisor";
-import { startDaemon, restartDaemon, isDaemonRunning, waitForDaemonReady } from "./src/native/zeroClawDaemon";
+import { startDaemon, restartDaemon, isDaemonRunning, waitForDaemonReady, gatewayFetch } from "./src/native/zeroClawDaemon";
 import { startLocalLlmServer, stopLocalLlmServer, LOCAL_LLM_URL } from "./src/native/localLlmServer";
 
 // Dev-only: prepopulate credentials for faster local testing
@@ -219,7 +219,7 @@ export default function App() {
           detail: `${event.state} from ${phone}`,
         });
         try {
-          await fetch('http://127.0.0.1:8000/agent/event', {
+          await gatewayFetch('http://127.0.0.1:8000/agent/event', {
             method: 'POST',
             headers: { 'Content-Type': 'application/json' },
             body: JSON.stringify({
@@ -244,7 +244,7 @@ export default function App() {
           detail: `from ${address}`,
         });
         try {
-          await fetch('http://127.0.0.1:8000/agent/event', {
+          await gatewayFetch('http://127.0.0.1:8000/agent/event', {
             method: 'POST',
             headers: { 'Content-Type': 'application/json' },
             body: JSON.stringify({
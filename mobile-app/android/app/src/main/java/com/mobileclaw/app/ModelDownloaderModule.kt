@@ -43,6 +43,15 @@ class ModelDownloaderModule(reactContext: ReactApplicationContext) :
 
     @ReactMethod
     fun downloadModel(urlStr: String, destPath: String, promise: Promise) {
+        val allowed = runCatching { ZeroClawBackend.isTransferAllowed("model_download") }
+            .getOrDefault(true)
+        if (!allowed) {
+            promise.reject(
+                "DOWNLOAD_DEFERRED",
+                "Model download deferred until an unmetered network is available"
+            )
+            return
+        }
         Thread {
             try {
                 val url = URL(urlStr)
@@ -722,6 +722,9 @@ object RuntimeBridge {
             connection.readTimeout = 25_000
             connection.doOutput = true
             connection.setRequestProperty("Content-Type", "application/json")
+            if (endpoint.startsWith(LOCAL_GATEWAY)) {
+                gatewayApiToken()?.let { connection.setRequestProperty("Authorization", "Bearer $it") }
+            }
             connection.outputStream.use { out ->
                 out.write(body.toByteArray(Charsets.UTF_8))
             }
@@ -733,6 +736,12 @@ object RuntimeBridge {
         }
     }
 
+    private fun gatewayApiToken(): String? {
+        val handle = ZeroClawDaemonService.getAgentHandle()
+        if (handle == 0L) return null
+        return runCatching { ZeroClawBackend.getGatewayApiToken(handle) }.getOrNull()
+    }
+
     private fun readAll(connection: HttpURLConnection): String {
         val stream = connection.inputStream
         BufferedReader(InputStreamReader(stream)).use { reader ->
@@ -12,6 +12,34 @@ import android.util.Log
  * in the same process as the Android app.
  */
 class ZeroClawBackend {
+    /**
+     * Host-side handler for platform calls issued by the Rust runtime
+     * (e.g. Storage Access Framework document reads/writes, Android tool
+     * actions such as `post_notification` when `android.bridge.mode = "jni"`, or
+     * `shizuku_exec` with an `argv` array to run through a Shizuku
+     * binder process and reply with `exit_code`/`stdout`/`stderr`).
+     * Daemon watchdog events arrive as `daemon_event` with an `event` of
+     * `started` or `exited` (plus `attempt`, `error`, `restart_in_secs`).
+     * Gateway agent sessions report progress as `agent_event` with
+     * `session_id` and an `event` frame (`chunk`, `tool_call`, `tool_result`,
+     * `done` or `error`), the same frames `/v1/stream/{session}` sends.
+     * A gateway bound beyond loopback asks to be advertised on the LAN with
+     * `nsd_register` (`service_type`, `instance_name`, `port` and a `txt`
+     * map, for `NsdManager.registerService`) and withdraws it with
+     * `nsd_unregister` (`service_type`, `instance_name`) when it stops.
+     *
+     * With `secrets.backend = "keystore"`, config secrets are sealed by the
+     * host: `keystore_encrypt` gets `alias` and `plaintext` and replies with
+     * an opaque `ciphertext` string (e.g. Base64 of IV + AES-GCM output from
+     * a non-exportable Android Keystore key); `keystore_decrypt` gets `alias`
+     * and `ciphertext` and replies with `plaintext`. Register the callback
+     * before [startAgent] so stored secrets can be decrypted at load.
+     */
+    fun interface HostCallback {
+        /** Handle [action] with a JSON payload and return a JSON reply. */
+        fun onHostCall(action: String, payloadJson: String): String
+    }
+
     companion object {
         private const val TAG = "ZeroClawBackend"
 
@@ -80,6 +108,106 @@ class ZeroClawBackend {
         @JvmStatic
         external fun isHealthy(handleId: Long): Boolean
 
+        /**
+         * Get daemon supervision status
+         *
+         * @param handleId Handle from startAgent()
+         * @return JSON with running, daemon_status, restart_count, last_error,
+         *   providers (per-provider status and probe latency), health
+         */
+        @JvmStatic
+        external fun getStatus(handleId: Long): String
+
+        /**
+         * Get LLM spend from the cost ledger
+         *
+         * @param handleId Handle from startAgent()
+         * @param days Number of days to report, newest first
+         * @return JSON with enabled, daily_limit_usd, monthly_limit_usd and per-day
+         *         totals broken down by provider and model
+         */
+        @JvmStatic
+        external fun getCostStats(handleId: Long, days: Int): String
+
+        /**
+         * Re-read config.toml and apply it without restarting the agent
+         *
+         * Only the daemon components whose settings changed are restarted.
+         *
+         * @param handleId Handle from startAgent()
+         * @return false if the config failed to load; the running config is kept
+         */
+        @JvmStatic
+        external fun reloadConfig(handleId: Long): Boolean
+
+        /**
+         * List named config profiles
+         *
+         * @param handleId Handle from startAgent()
+         * @return JSON with active (name or null) and profiles (sorted names)
+         */
+        @JvmStatic
+        external fun listProfiles(handleId: Long): String
+
+        /**
+         * Switch to a named config profile (e.g. work, personal, travel)
+         *
+         * The switch is saved to config.toml and applied by hot reload.
+         *
+         * @param handleId Handle from startAgent()
+         * @param name Profile name from listProfiles()
+         * @return false if the config could not be saved
+         * @throws IllegalArgumentException for unknown or invalid profiles
+         */
+        @JvmStatic
+        external fun switchProfile(handleId: Long, name: String): Boolean
+
+        /**
+         * Check config TOML without applying it
+         *
+         * @param handleId Handle from startAgent()
+         * @param toml Config to check; empty checks config.toml on disk
+         * @return JSON with valid (no errors) and diagnostics, each with
+         *   severity, kind, path, line, column and message
+         */
+        @JvmStatic
+        external fun validateConfig(handleId: Long, toml: String): String
+
+        /**
+         * Pull the signed remote config ([config_sync]) now
+         *
+         * @param handleId Handle from startAgent()
+         * @return JSON with applied, sha256, sections and dropped_secrets
+         * @throws RuntimeException if sync is disabled or the pull or
+         *   signature check fails
+         */
+        @JvmStatic
+        external fun syncConfig(handleId: Long): String
+
+        /**
+         * Export the config as portable TOML
+         *
+         * @param handleId Handle from startAgent()
+         * @param passphrase Empty strips credentials; otherwise they are
+         *   wrapped and need the same passphrase on import
+         * @return TOML document
+         */
+        @JvmStatic
+        external fun exportConfig(handleId: Long, passphrase: String): String
+
+        /**
+         * Replace the config with a document from exportConfig()
+         *
+         * @param handleId Handle from startAgent()
+         * @param toml Exported document
+         * @param passphrase Export passphrase; empty for a stripped export
+         * @return JSON with sections, unwrapped_secrets and kept_secrets
+         * @throws RuntimeException if the document is invalid or the
+         *   passphrase is wrong
+         */
+        @JvmStatic
+        external fun importConfig(handleId: Long, toml: String, passphrase: String): String
+
         /**
          * Stop the agent and release resources
          *
@@ -92,11 +220,50 @@ class ZeroClawBackend {
          * Get the gateway URL for this agent instance
          *
          * @param handleId Handle from startAgent()
-         * @return Gateway URL (e.g., "http://127.0.0.1:8000")
+         * @return Gateway URL (e.g., "http://127.0.0.1:8000", or https
+         *   when [gateway.tls] is enabled)
          */
         @JvmStatic
         external fun getGatewayUrl(handleId: Long): String
 
+        /**
+         * Get the SHA-256 fingerprint of the gateway's TLS certificate
+         *
+         * Companion apps pin this instead of trusting a CA.
+         *
+         * @param handleId Handle from startAgent()
+         * @return Colon-separated hex, or null when [gateway.tls] is disabled
+         */
+        @JvmStatic
+        external fun getGatewayTlsFingerprint(handleId: Long): String?
+
+        /**
+         * Get the gateway's per-install API token
+         *
+         * Every gateway request except /health and /pair must send it as
+         * `Authorization: Bearer <token>`. Generated on first call.
+         *
+         * @param handleId Handle from startAgent()
+         * @return API token
+         */
+        @JvmStatic
+        external fun getGatewayApiToken(handleId: Long): String
+
+        /**
+         * Issue a short-lived pairing code for companion apps to scan
+         *
+         * The code is valid for [gateway] pairing_code_ttl_secs and pairs one
+         * client through POST /pair, which returns a long-lived token.
+         *
+         * @param handleId Handle from startAgent()
+         * @param url Address companions should pair with, e.g. the device's
+         *   LAN address; empty uses the configured gateway host
+         * @return JSON {code, expires_in_secs, url, fingerprint?, uri}; render
+         *   `uri` as the QR code
+         */
+        @JvmStatic
+        external fun createGatewayPairingOffer(handleId: Long, url: String): String
+
         /**
          * Execute a tool directly without going through agent loop
          *
@@ -109,5 +276,49 @@ class ZeroClawBackend {
          */
         @JvmStatic
         external fun executeTool(handleId: Long, toolName: String, paramsJson: String): String
+
+        /**
+         * Register the host callback used by the runtime for platform APIs
+         *
+         * @param callback Handler implementation, or null to unregister
+         */
+        @JvmStatic
+        external fun registerHostBridge(callback: HostCallback?)
+
+        /**
+         * Report Doze / App Standby state so the runtime can defer network work
+         *
+         * @param restricted true while background work is restricted
+         * @param nextWakeupEpochMs Next maintenance window (epoch ms), or 0 if unknown
+         */
+        @JvmStatic
+        external fun updateBackgroundState(restricted: Boolean, nextWakeupEpochMs: Long)
+
+        /**
+         * Report the active network so large transfers can be deferred on metered links
+         *
+         * @param connectionType wifi, cellular, ethernet, or none
+         * @param metered true when ConnectivityManager reports the network as metered
+         */
+        @JvmStatic
+        external fun updateNetworkState(connectionType: String, metered: Boolean)
+
+        /**
+         * Report the battery level so heartbeat runs can respect heartbeat.min_battery_percent
+         *
+         * @param percent charge level 0-100, or -1 when unknown
+         * @param charging true while the device is plugged in
+         */
+        @JvmStatic
+        external fun updateBatteryState(percent: Int, charging: Boolean)
+
+        /**
+         * Check a large transfer against the runtime.network policy
+         *
+         * @param kind model_download or image_upload
+         * @return false when the transfer must wait for an unmetered network
+         */
+        @JvmStatic
+        external fun isTransferAllowed(kind: String): Boolean
     }
 }
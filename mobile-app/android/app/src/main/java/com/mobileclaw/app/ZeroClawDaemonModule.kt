@@ -191,6 +191,27 @@ class ZeroClawDaemonModule(private val reactContext: ReactApplicationContext) :
         }
     }
 
+    /**
+     * Get the gateway API token, required on gateway requests as
+     * `Authorization: Bearer <token>`
+     */
+    @ReactMethod
+    fun getGatewayApiToken(promise: Promise) {
+        try {
+            val handle = ZeroClawDaemonService.getAgentHandle()
+            if (handle == 0L) {
+                promise.reject("AGENT_NOT_RUNNING", "Agent is not running")
+                return
+            }
+
+            promise.resolve(ZeroClawBackend.getGatewayApiToken(handle))
+
+        } catch (e: Exception) {
+            Log.e(TAG, "Error getting gateway API token", e)
+            promise.reject("TOKEN_FAILED", "Failed to get gateway API token: ${e.message}", e)
+        }
+    }
+
     /**
      * Get comprehensive daemon status
      */
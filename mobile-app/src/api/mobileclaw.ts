@@ -1,6 +1,7 @@
 import type { AgentRuntimeConfig } from "../state/mobileclaw";
 import * as FileSystem from "expo-file-system/legacy";
 import { Buffer } from "buffer";
+import { gatewayFetch } from "../native/zeroClawDaemon";
 
 export type ChatCompletionMessage = {
   role: "system" | "user" | "assistant";
@@ -348,7 +349,7 @@ export async function* runZeroClawAgentStream(
   gatewayUrl: string,
   sessionId?: string,
 ): AsyncGenerator<string> {
-  const res = await fetch(`${gatewayUrl}/agent/stream`, {
+  const res = await gatewayFetch(`${gatewayUrl}/agent/stream`, {
     method: 'POST',
     headers: { 'Content-Type': 'application/json' },
     body: JSON.stringify({ message, ...(sessionId ? { session_id: sessionId } : {}) }),
@@ -395,7 +396,7 @@ export async function runZeroClawAgent(
     headers.Authorization = `Bearer ${bearerToken}`;
   }
 
-  const res = await fetch(`${gatewayUrl}/agent/message`, {
+  const res = await gatewayFetch(`${gatewayUrl}/agent/message`, {
     method: "POST",
     headers,
     body: JSON.stringify({ message, ...(sessionId ? { session_id: sessionId } : {}) }),
@@ -420,7 +421,7 @@ export async function pairTelegramIdentity(
     throw new Error("Telegram identity is empty.");
   }
 
-  const res = await fetch(`${gatewayUrl}/agent/telegram/pair`, {
+  const res = await gatewayFetch(`${gatewayUrl}/agent/telegram/pair`, {
     method: "POST",
     headers: {
       "Content-Type": "application/json",
@@ -486,7 +487,7 @@ export async function fetchMemories(
     ? `${gatewayUrl}/memory?category=${encodeURIComponent(category)}`
     : `${gatewayUrl}/memory`;
 
-  const res = await fetch(url);
+  const res = await gatewayFetch(url);
   if (!res.ok) {
     const data = await readJsonResponse(res);
     throw new Error(data?.error || `Memory list error: ${res.status}`);
@@ -504,7 +505,7 @@ export async function recallMemories(
   const gatewayUrl = platformUrl.trim() || "http://127.0.0.1:8000";
   const url = `${gatewayUrl}/memory/recall?query=${encodeURIComponent(query)}&limit=${limit}`;
 
-  const res = await fetch(url);
+  const res = await gatewayFetch(url);
   if (!res.ok) {
     const data = await readJsonResponse(res);
     throw new Error(data?.error || `Memory recall error: ${res.status}`);
@@ -516,7 +517,7 @@ export async function recallMemories(
 
 export async function fetchMemoryCount(platformUrl: string): Promise<number> {
   const gatewayUrl = platformUrl.trim() || "http://127.0.0.1:8000";
-  const res = await fetch(`${gatewayUrl}/memory/count`);
+  const res = await gatewayFetch(`${gatewayUrl}/memory/count`);
   if (!res.ok) {
     const data = await readJsonResponse(res);
     throw new Error(data?.error || `Memory count error: ${res.status}`);
@@ -528,7 +529,7 @@ export async function fetchMemoryCount(platformUrl: string): Promise<number> {
 
 export async function forgetMemory(platformUrl: string, key: string): Promise<boolean> {
   const gatewayUrl = platformUrl.trim() || "http://127.0.0.1:8000";
-  const res = await fetch(`${gatewayUrl}/memory?key=${encodeURIComponent(key)}`, {
+  const res = await gatewayFetch(`${gatewayUrl}/memory?key=${encodeURIComponent(key)}`, {
     method: "DELETE",
   });
 
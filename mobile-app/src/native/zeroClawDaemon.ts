@@ -198,6 +198,43 @@ export async function getDaemonUrl(): Promise<string> {
   }
 }
 
+let cachedApiToken: string | null = null;
+
+/**
+ * Get the gateway API token
+ *
+ * Every gateway request except /health and /pair must send it as
+ * `Authorization: Bearer <token>`.
+ *
+ * @returns The token, or null when the embedded daemon is unavailable
+ */
+export async function getGatewayApiToken(): Promise<string | null> {
+  if (cachedApiToken) return cachedApiToken;
+  if (Platform.OS !== 'android' || !ZeroClawDaemon?.getGatewayApiToken) {
+    return null;
+  }
+
+  try {
+    cachedApiToken = await ZeroClawDaemon.getGatewayApiToken();
+    return cachedApiToken;
+  } catch (error) {
+    console.error('[ZeroClawDaemon] Failed to get API token:', error);
+    return null;
+  }
+}
+
+/**
+ * fetch() against the gateway with the API token attached
+ */
+export async function gatewayFetch(url: string, init: RequestInit = {}): Promise<Response> {
+  const token = await getGatewayApiToken();
+  const headers = new Headers(init.headers);
+  if (token && !headers.has('Authorization')) {
+    headers.set('Authorization', `Bearer ${token}`);
+  }
+  return fetch(url, { ...init, headers });
+}
+
 /**
  * Get comprehensive daemon status
  *
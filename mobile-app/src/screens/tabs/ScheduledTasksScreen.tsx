@@ -6,6 +6,7 @@ import { Screen } from "../../../ui/primitives/Screen";
 import { Text } from "../../../ui/primitives/Text";
 import { theme } from "../../../ui/theme";
 import { loadSecurityConfig, loadAgentConfig } from "../../state/mobileclaw";
+import { gatewayFetch } from "../../native/zeroClawDaemon";
 import { useActivity, type ActivityItem } from "../../state/activity";
 
 type GatewayHealth = "checking" | "ok" | "error";
@@ -72,7 +73,7 @@ export function ScheduledTasksScreen() {
     (async () => {
       try {
         const res = await Promise.race([
-          fetch(`${gatewayUrl}/cron/jobs`),
+          gatewayFetch(`${gatewayUrl}/cron/jobs`),
           new Promise<never>((_, reject) => setTimeout(() => reject(new Error("timeout")), 5000)),
         ]);
         if (res.ok) {
@@ -112,7 +113,7 @@ export function ScheduledTasksScreen() {
           onPress: () => {
             (async () => {
               try {
-                await fetch(`${gatewayUrl}/cron/jobs?id=${job.id}`, { method: "DELETE" });
+                await gatewayFetch(`${gatewayUrl}/cron/jobs?id=${job.id}`, { method: "DELETE" });
                 setCronJobs((prev) => prev.filter((j) => j.id !== job.id));
               } catch {
                 Alert.alert("Error", "Failed to delete task. Is the gateway online?");
@@ -1,3 +1,4 @@
+use crate::agent::context_window;
 use crate::agent::dispatcher::{
     NativeToolDispatcher, ParsedToolCall, ToolDispatcher, ToolExecutionResult, XmlToolDispatcher,
 };
@@ -11,10 +12,28 @@ use crate::runtime;
 use crate::security::SecurityPolicy;
 use crate::tools::{self, Tool, ToolSpec};
 use anyhow::Result;
+use serde::Serialize;
 use std::collections::HashMap;
 use std::io::Write as IoWrite;
 use std::sync::Arc;
 use std::time::Instant;
+use tokio::sync::mpsc::UnboundedSender;
+
+/// Progress of a turn run with [`Agent::turn_with_events`]. Serializes as a
+/// `{"type": ...}` frame for streaming clients.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum TurnEvent {
+    /// Reply text, as each model response arrives.
+    Chunk { content: String },
+    /// A tool is about to run.
+    ToolCall {
+        name: String,
+        args: serde_json::Value,
+    },
+    /// A tool finished.
+    ToolResult { name: String, output: String },
+}
 
 pub struct Agent {
     provider: Box<dyn Provider>,
@@ -241,6 +260,15 @@ impl Agent {
     }
 
     pub fn from_config(config: &Config) -> Result<Self> {
+        Self::from_config_with_memory_namespace(config, None)
+    }
+
+    /// Like [`Self::from_config`], but with memory confined to `namespace`
+    /// (see [`memory::ScopedMemory`]) when one is given.
+    pub fn from_config_with_memory_namespace(
+        config: &Config,
+        namespace: Option<&str>,
+    ) -> Result<Self> {
         let observer: Arc<dyn Observer> =
             Arc::from(observability::create_observer(&config.observability));
         let runtime: Arc<dyn runtime::RuntimeAdapter> =
@@ -257,6 +285,10 @@ impl Agent {
             &config.workspace_dir,
             config.api_key.as_deref(),
         )?);
+        let memory: Arc<dyn Memory> = match namespace {
+            Some(namespace) => Arc::new(memory::ScopedMemory::new(memory, namespace)),
+            None => memory,
+        };
 
         let composio_key = if config.composio.enabled {
             config.composio.api_key.as_deref()
@@ -287,11 +319,12 @@ impl Agent {
 
         let provider_name = config.default_provider.as_deref().unwrap_or("openrouter");
 
-        let model_name = config
-            .default_model
-            .as_deref()
-            .unwrap_or("anthropic/claude-sonnet-4-20250514")
-            .to_string();
+        let model_name = config.resolve_model_alias(
+            config
+                .default_model
+                .as_deref()
+                .unwrap_or("anthropic/claude-sonnet-4-20250514"),
+        );
 
         let provider_options = providers::ProviderRuntimeOptions::default();
 
@@ -309,7 +342,9 @@ impl Agent {
         let tool_dispatcher: Box<dyn ToolDispatcher> = match dispatcher_choice {
             "native" => Box::new(NativeToolDispatcher),
             "xml" => Box::new(XmlToolDispatcher),
-            _ if provider.supports_native_tools() => Box::new(NativeToolDispatcher),
+            _ if provider.supports_native_tools_for_model(&model_name) => {
+                Box::new(NativeToolDispatcher)
+            }
             _ => Box::new(XmlToolDispatcher),
         };
 
@@ -469,6 +504,30 @@ impl Agent {
     }
 
     pub async fn turn(&mut self, user_message: &str) -> Result<String> {
+        self.run_turn(user_message, None).await
+    }
+
+    /// Like [`Self::turn`], but reports reply text and tool activity on
+    /// `events` while the turn runs.
+    pub async fn turn_with_events(
+        &mut self,
+        user_message: &str,
+        events: UnboundedSender<TurnEvent>,
+    ) -> Result<String> {
+        self.run_turn(user_message, Some(&events)).await
+    }
+
+    async fn run_turn(
+        &mut self,
+        user_message: &str,
+        events: Option<&UnboundedSender<TurnEvent>>,
+    ) -> Result<String> {
+        let emit = |event: TurnEvent| {
+            if let Some(events) = events {
+                let _ = events.send(event);
+            }
+        };
+
         if self.history.is_empty() {
             let system_prompt = self.build_system_prompt()?;
             self.history
@@ -502,14 +561,28 @@ impl Agent {
 
         let effective_model = self.classify_model(user_message);
 
+        let send_tool_specs = self.tool_dispatcher.should_send_tool_specs();
+        let tool_specs_tokens = if send_tool_specs {
+            serde_json::to_string(&self.tool_specs)
+                .map_or(0, |json| context_window::estimate_tokens(&json))
+        } else {
+            0
+        };
+        let budget = context_window::input_budget(context_window::resolve_context_window(
+            &effective_model,
+            &self.config.context_windows,
+        ))
+        .saturating_sub(tool_specs_tokens);
+
         for _ in 0..self.config.max_tool_iterations {
-            let messages = self.tool_dispatcher.to_provider_messages(&self.history);
+            let mut messages = self.tool_dispatcher.to_provider_messages(&self.history);
+            context_window::fit_history(&mut messages, budget);
             let response = match self
                 .provider
                 .chat(
                     ChatRequest {
                         messages: &messages,
-                        tools: if self.tool_dispatcher.should_send_tool_specs() {
+                        tools: if send_tool_specs {
                             Some(&self.tool_specs)
                         } else {
                             None
@@ -538,6 +611,9 @@ impl Agent {
                     )));
                 self.trim_history();
 
+                emit(TurnEvent::Chunk {
+                    content: final_text.clone(),
+                });
                 return Ok(final_text);
             }
 
@@ -548,6 +624,9 @@ impl Agent {
                     )));
                 print!("{text}");
                 let _ = std::io::stdout().flush();
+                emit(TurnEvent::Chunk {
+                    content: text.clone(),
+                });
             }
 
             self.history.push(ConversationMessage::AssistantToolCalls {
@@ -556,7 +635,19 @@ impl Agent {
                 reasoning_content: response.reasoning_content.clone(),
             });
 
+            for call in &calls {
+                emit(TurnEvent::ToolCall {
+                    name: call.name.clone(),
+                    args: call.arguments.clone(),
+                });
+            }
             let results = self.execute_tools(&calls).await;
+            for result in &results {
+                emit(TurnEvent::ToolResult {
+                    name: result.name.clone(),
+                    output: result.output.clone(),
+                });
+            }
             let formatted = self.tool_dispatcher.format_results(&results);
             self.history.push(formatted);
             self.trim_history();
@@ -624,11 +715,12 @@ pub async fn run(
         .as_deref()
         .unwrap_or("openrouter")
         .to_string();
-    let model_name = effective_config
-        .default_model
-        .as_deref()
-        .unwrap_or("anthropic/claude-sonnet-4-20250514")
-        .to_string();
+    let model_name = effective_config.resolve_model_alias(
+        effective_config
+            .default_model
+            .as_deref()
+            .unwrap_or("anthropic/claude-sonnet-4-20250514"),
+    );
 
     agent.observer.record_event(&ObserverEvent::AgentStart {
         provider: provider_name.clone(),
@@ -843,6 +935,78 @@ mod tests {
             .any(|msg| matches!(msg, ConversationMessage::ToolResults(_))));
     }
 
+    #[tokio::test]
+    async fn turn_with_events_reports_tool_activity_and_reply() {
+        let provider = Box::new(MockProvider {
+            responses: Mutex::new(vec![
+                crate::providers::ChatResponse {
+                    text: Some(String::new()),
+                    tool_calls: vec![crate::providers::ToolCall {
+                        id: "tc1".into(),
+                        name: "echo".into(),
+                        arguments: r#"{"text":"hi"}"#.into(),
+                    }],
+                    usage: None,
+                    reasoning_content: None,
+                },
+                crate::providers::ChatResponse {
+                    text: Some("done".into()),
+                    tool_calls: vec![],
+                    usage: None,
+                    reasoning_content: None,
+                },
+            ]),
+        });
+
+        let memory_cfg = crate::config::MemoryConfig {
+            backend: "none".into(),
+            ..crate::config::MemoryConfig::default()
+        };
+        let mem: Arc<dyn Memory> = Arc::from(
+            crate::memory::create_memory(&memory_cfg, std::path::Path::new("/tmp"), None).unwrap(),
+        );
+
+        let observer: Arc<dyn Observer> = Arc::from(crate::observability::NoopObserver {});
+        let mut agent = Agent::builder()
+            .provider(provider)
+            .tools(vec![Box::new(MockTool)])
+            .memory(mem)
+            .observer(observer)
+            .tool_dispatcher(Box::new(NativeToolDispatcher))
+            .workspace_dir(std::path::PathBuf::from("/tmp"))
+            .build()
+            .unwrap();
+
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        let response = agent.turn_with_events("hi", tx).await.unwrap();
+        assert_eq!(response, "done");
+
+        let mut events = Vec::new();
+        while let Some(event) = rx.recv().await {
+            events.push(event);
+        }
+        assert_eq!(
+            events,
+            vec![
+                TurnEvent::ToolCall {
+                    name: "echo".into(),
+                    args: serde_json::json!({"text": "hi"}),
+                },
+                TurnEvent::ToolResult {
+                    name: "echo".into(),
+                    output: "tool-out".into(),
+                },
+                TurnEvent::Chunk {
+                    content: "done".into(),
+                },
+            ]
+        );
+        assert_eq!(
+            serde_json::to_value(&events[0]).unwrap(),
+            serde_json::json!({"type": "tool_call", "name": "echo", "args": {"text": "hi"}})
+        );
+    }
+
     #[tokio::test]
     async fn turn_with_android_device_tool_executes_tool_call() {
         let provider = Box::new(MockProvider {
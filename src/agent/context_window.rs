@@ -0,0 +1,187 @@
+//! Token estimates and per-model context windows.
+//!
+//! Providers reject requests that exceed the model's context window, often
+//! with a bare 400. Before each call the agent estimates the size of the
+//! conversation and drops (or, between turns, summarizes) the oldest messages
+//! so the request fits with room left for the reply.
+
+use crate::providers::ChatMessage;
+use std::collections::HashMap;
+use std::hash::BuildHasher;
+
+/// Context window assumed for models missing from [`CONTEXT_WINDOWS`].
+pub const DEFAULT_CONTEXT_WINDOW: usize = 32_768;
+
+/// Upper bound on the tokens held back for the model's reply.
+const MAX_RESPONSE_RESERVE: usize = 8_192;
+
+/// Fixed cost of role markers and message framing.
+const MESSAGE_OVERHEAD_TOKENS: usize = 4;
+
+/// Known context windows, matched as prefixes of the normalized model name.
+/// More specific prefixes must come first.
+const CONTEXT_WINDOWS: &[(&str, usize)] = &[
+    ("claude-", 200_000),
+    ("gpt-5", 400_000),
+    ("gpt-4.1", 1_047_576),
+    ("gpt-4o", 128_000),
+    ("gpt-4-turbo", 128_000),
+    ("gpt-4", 8_192),
+    ("gpt-3.5", 16_385),
+    ("gpt-oss", 131_072),
+    ("o1-mini", 128_000),
+    ("o1", 200_000),
+    ("o3", 200_000),
+    ("o4", 200_000),
+    ("gemini-", 1_048_576),
+    ("gemma3", 128_000),
+    ("gemma-3", 128_000),
+    ("gemma", 8_192),
+    ("llama3.1", 128_000),
+    ("llama3.2", 128_000),
+    ("llama3.3", 128_000),
+    ("llama-3.1", 128_000),
+    ("llama-3.2", 128_000),
+    ("llama-3.3", 128_000),
+    ("llama4", 128_000),
+    ("llama-4", 128_000),
+    ("llama", 8_192),
+    ("qwen", 32_768),
+    ("mistral-large", 128_000),
+    ("mistral", 32_768),
+    ("deepseek", 64_000),
+    ("phi4", 16_384),
+    ("phi-4", 16_384),
+    ("phi3", 4_096),
+    ("phi-3", 4_096),
+    ("glm-4", 128_000),
+    ("grok", 131_072),
+    ("kimi-k2", 131_072),
+];
+
+/// Heuristic token count: roughly four ASCII characters per token, and one
+/// token per other character (CJK text tokenizes close to that).
+pub fn estimate_tokens(text: &str) -> usize {
+    let ascii = text.bytes().filter(u8::is_ascii).count();
+    let other = text.chars().filter(|c| !c.is_ascii()).count();
+    ascii.div_ceil(4) + other
+}
+
+pub fn estimate_message_tokens(message: &ChatMessage) -> usize {
+    estimate_tokens(&message.content) + MESSAGE_OVERHEAD_TOKENS
+}
+
+pub fn estimate_history_tokens(messages: &[ChatMessage]) -> usize {
+    messages.iter().map(estimate_message_tokens).sum()
+}
+
+/// Context window of `model` from the built-in registry. Provider prefixes
+/// (`anthropic/claude-...`) and case are ignored.
+pub fn model_context_window(model: &str) -> usize {
+    let name = model.rsplit('/').next().unwrap_or(model).to_lowercase();
+    CONTEXT_WINDOWS
+        .iter()
+        .find(|(prefix, _)| name.starts_with(prefix))
+        .map_or(DEFAULT_CONTEXT_WINDOW, |(_, tokens)| *tokens)
+}
+
+/// Context window of `model`, preferring `[agent].context_windows` entries.
+pub fn resolve_context_window<S: BuildHasher>(
+    model: &str,
+    overrides: &HashMap<String, usize, S>,
+) -> usize {
+    overrides
+        .get(model)
+        .copied()
+        .filter(|tokens| *tokens > 0)
+        .unwrap_or_else(|| model_context_window(model))
+}
+
+/// Tokens available for the request once the reply reserve is held back.
+pub fn input_budget(context_window: usize) -> usize {
+    context_window - (context_window / 4).min(MAX_RESPONSE_RESERVE)
+}
+
+/// Drop the oldest non-system messages until the history fits `budget`
+/// tokens. The newest message is always kept, and tool results left without
+/// their assistant turn are dropped with it. Returns the number removed.
+pub fn fit_history(history: &mut Vec<ChatMessage>, budget: usize) -> usize {
+    let start = usize::from(history.first().is_some_and(|m| m.role == "system"));
+    let mut total = estimate_history_tokens(history);
+    let mut end = start;
+    while total > budget && end + 1 < history.len() {
+        total -= estimate_message_tokens(&history[end]);
+        end += 1;
+    }
+    if end > start {
+        while end + 1 < history.len() && history[end].role == "tool" {
+            end += 1;
+        }
+    }
+    history.drain(start..end);
+    end - start
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn estimates_ascii_and_wide_text() {
+        assert_eq!(estimate_tokens(""), 0);
+        assert_eq!(estimate_tokens("hello world!"), 3);
+        assert_eq!(estimate_tokens("你好"), 2);
+        assert_eq!(
+            estimate_message_tokens(&ChatMessage::user("abcd")),
+            1 + MESSAGE_OVERHEAD_TOKENS
+        );
+    }
+
+    #[test]
+    fn registry_matches_known_models_and_overrides() {
+        assert_eq!(model_context_window("anthropic/claude-sonnet-4"), 200_000);
+        assert_eq!(model_context_window("gpt-4o-mini"), 128_000);
+        assert_eq!(model_context_window("gpt-4"), 8_192);
+        assert_eq!(model_context_window("llama3.2:3b"), 128_000);
+        assert_eq!(model_context_window("Llama3:8b"), 8_192);
+        assert_eq!(model_context_window("openai/gpt-oss-120b"), 131_072);
+        assert_eq!(model_context_window("my-finetune"), DEFAULT_CONTEXT_WINDOW);
+
+        let overrides = HashMap::from([("my-finetune".to_string(), 4_096)]);
+        assert_eq!(resolve_context_window("my-finetune", &overrides), 4_096);
+        assert_eq!(resolve_context_window("gpt-4", &overrides), 8_192);
+    }
+
+    #[test]
+    fn input_budget_reserves_reply_room() {
+        assert_eq!(input_budget(4_096), 3_072);
+        assert_eq!(input_budget(200_000), 200_000 - MAX_RESPONSE_RESERVE);
+    }
+
+    #[test]
+    fn fit_history_drops_oldest_and_orphaned_tool_results() {
+        let filler = "x".repeat(400);
+        let mut history = vec![
+            ChatMessage::system("sys"),
+            ChatMessage::user(filler.clone()),
+            ChatMessage::assistant(filler.clone()),
+            ChatMessage::tool(filler.clone()),
+            ChatMessage::user("latest"),
+        ];
+        let removed = fit_history(&mut history, 150);
+        assert_eq!(removed, 3);
+        assert_eq!(history.len(), 2);
+        assert_eq!(history[0].role, "system");
+        assert_eq!(history[1].content, "latest");
+
+        assert_eq!(fit_history(&mut history, 150), 0);
+        assert_eq!(history.len(), 2);
+    }
+
+    #[test]
+    fn fit_history_keeps_newest_message_even_when_oversized() {
+        let mut history = vec![ChatMessage::user("x".repeat(4_000))];
+        assert_eq!(fit_history(&mut history, 10), 0);
+        assert_eq!(history.len(), 1);
+    }
+}
@@ -314,6 +314,8 @@ mod tests {
                     .into(),
             ),
             tool_calls: vec![],
+            usage: None,
+            reasoning_content: None,
         };
         let dispatcher = XmlToolDispatcher;
         let (_, calls) = dispatcher.parse_response(&response);
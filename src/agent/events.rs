@@ -0,0 +1,74 @@
+//! Process-wide bus of agent turn events for gateway sessions.
+//!
+//! Every turn the gateway runs on a session publishes its progress here as
+//! `{"type": ...}` frames: `chunk`, `tool_call` and `tool_result` while it
+//! runs (see [`TurnEvent`]), then `done` or `error`. `GET /v1/stream/{session}`
+//! relays one session's frames over SSE, and the Android bridge forwards all
+//! of them to the host callback.
+
+use super::TurnEvent;
+use serde::Serialize;
+use std::sync::OnceLock;
+use tokio::sync::broadcast;
+
+/// Buffered events per subscriber before the oldest are dropped.
+const EVENT_CAPACITY: usize = 256;
+
+static EVENTS: OnceLock<broadcast::Sender<SessionEvent>> = OnceLock::new();
+
+/// One frame of a session's turn.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SessionEvent {
+    pub session_id: String,
+    pub event: serde_json::Value,
+}
+
+fn events() -> &'static broadcast::Sender<SessionEvent> {
+    EVENTS.get_or_init(|| broadcast::channel(EVENT_CAPACITY).0)
+}
+
+/// Publish a frame for `session_id`. Returns how many subscribers received
+/// it.
+pub fn publish(session_id: &str, event: serde_json::Value) -> usize {
+    events()
+        .send(SessionEvent {
+            session_id: session_id.to_string(),
+            event,
+        })
+        .unwrap_or(0)
+}
+
+/// Publish a [`TurnEvent`] for `session_id`.
+pub fn publish_turn_event(session_id: &str, event: &TurnEvent) -> usize {
+    serde_json::to_value(event).map_or(0, |event| publish(session_id, event))
+}
+
+/// Receive events published from now on, for all sessions.
+pub fn subscribe() -> broadcast::Receiver<SessionEvent> {
+    events().subscribe()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn published_turn_events_carry_session_and_frame() {
+        let mut rx = subscribe();
+        let chunk = TurnEvent::Chunk {
+            content: "hello".into(),
+        };
+        assert!(publish_turn_event("events-test", &chunk) >= 1);
+
+        loop {
+            let event = rx.recv().await.unwrap();
+            if event.session_id == "events-test" {
+                assert_eq!(
+                    event.event,
+                    serde_json::json!({"type": "chunk", "content": "hello"})
+                );
+                break;
+            }
+        }
+    }
+}
@@ -1,3 +1,4 @@
+use super::context_window;
 use crate::approval::{ApprovalManager, ApprovalRequest, ApprovalResponse};
 use crate::config::Config;
 use crate::memory::{self, Memory, MemoryCategory};
@@ -110,7 +111,9 @@ pub(crate) const DRAFT_CLEAR_SENTINEL: &str = "\x00CLEAR\x00";
 fn truncate_tool_args_for_progress(name: &str, args: &serde_json::Value, max_len: usize) -> String {
     let hint = match name {
         "shell" => args.get("command").and_then(|v| v.as_str()),
-        "file_read" | "file_write" => args.get("path").and_then(|v| v.as_str()),
+        "file_read" | "file_write" | "file_append" | "file_delete" | "file_list" => {
+            args.get("path").and_then(|v| v.as_str())
+        }
         _ => args
             .get("action")
             .and_then(|v| v.as_str())
@@ -192,6 +195,7 @@ async fn auto_compact_history(
     provider: &dyn Provider,
     model: &str,
     max_history: usize,
+    context_window_tokens: usize,
 ) -> Result<bool> {
     let has_system = history.first().map_or(false, |m| m.role == "system");
     let non_system_count = if has_system {
@@ -200,7 +204,10 @@ async fn auto_compact_history(
         history.len()
     };
 
-    if non_system_count <= max_history {
+    let over_budget = context_window_tokens > 0
+        && context_window::estimate_history_tokens(history)
+            > context_window::input_budget(context_window_tokens);
+    if non_system_count <= max_history && !over_budget {
         return Ok(false);
     }
 
@@ -1120,7 +1127,8 @@ fn default_param_for_tool(tool: &str) -> &'static str {
         // All file tools default to "path"
         "file_read" | "fileread" | "readfile" | "read_file" | "file" | "file_write"
         | "filewrite" | "writefile" | "write_file" | "file_edit" | "fileedit" | "editfile"
-        | "edit_file" | "file_list" | "filelist" | "listfiles" | "list_files" => "path",
+        | "edit_file" | "file_list" | "filelist" | "listfiles" | "list_files" | "file_append"
+        | "file_delete" => "path",
         // Memory recall and forget both default to "query"
         "memory_recall" | "memoryrecall" | "recall" | "memrecall" | "memory_forget"
         | "memoryforget" | "forget" | "memforget" => "query",
@@ -1873,6 +1881,7 @@ pub(crate) async fn agent_turn(
     silent: bool,
     multimodal_config: &crate::config::MultimodalConfig,
     max_tool_iterations: usize,
+    context_window_tokens: usize,
 ) -> Result<String> {
     run_tool_call_loop(
         provider,
@@ -1887,6 +1896,7 @@ pub(crate) async fn agent_turn(
         "channel",
         multimodal_config,
         max_tool_iterations,
+        context_window_tokens,
         None,
         None,
         None,
@@ -2077,6 +2087,7 @@ pub(crate) async fn run_tool_call_loop(
     channel_name: &str,
     multimodal_config: &crate::config::MultimodalConfig,
     max_tool_iterations: usize,
+    context_window_tokens: usize,
     cancellation_token: Option<CancellationToken>,
     on_delta: Option<tokio::sync::mpsc::Sender<String>>,
     hooks: Option<&crate::hooks::HookRunner>,
@@ -2096,7 +2107,14 @@ pub(crate) async fn run_tool_call_loop(
         .filter(|tool| !excluded_tools.iter().any(|ex| ex == tool.name()))
         .map(|tool| tool.spec())
         .collect();
-    let use_native_tools = provider.supports_native_tools() && !tool_specs.is_empty();
+    let use_native_tools =
+        provider.supports_native_tools_for_model(model) && !tool_specs.is_empty();
+    // Native tool schemas travel alongside the messages and count against the window.
+    let tool_specs_tokens = if use_native_tools {
+        serde_json::to_string(&tool_specs).map_or(0, |json| context_window::estimate_tokens(&json))
+    } else {
+        0
+    };
     let turn_id = Uuid::new_v4().to_string();
     let mut seen_tool_signatures: HashSet<(String, String)> = HashSet::new();
 
@@ -2120,6 +2138,20 @@ pub(crate) async fn run_tool_call_loop(
             .into());
         }
 
+        if context_window_tokens > 0 {
+            let budget = context_window::input_budget(context_window_tokens)
+                .saturating_sub(tool_specs_tokens);
+            let dropped = context_window::fit_history(history, budget);
+            if dropped > 0 {
+                tracing::info!(
+                    model,
+                    dropped,
+                    context_window_tokens,
+                    "Dropped oldest messages to fit the context window"
+                );
+            }
+        }
+
         let prepared_messages =
             multimodal::prepare_messages_for_provider(history, multimodal_config).await?;
 
@@ -2779,7 +2811,10 @@ pub async fn run(
     interactive: bool,
 ) -> Result<String> {
     // ── Wire up agnostic subsystems ──────────────────────────────
-    let base_observer = observability::create_observer(&config.observability);
+    let base_observer = crate::cost::with_cost_tracking(
+        observability::create_observer(&config.observability),
+        &config,
+    );
     let observer: Arc<dyn Observer> = Arc::from(base_observer);
     let runtime: Arc<dyn runtime::RuntimeAdapter> =
         Arc::from(runtime::create_runtime(&config.runtime)?);
@@ -2836,6 +2871,10 @@ pub async fn run(
         tracing::info!(count = peripheral_tools.len(), "Peripheral tools added");
         tools_registry.extend(peripheral_tools);
     }
+    tools_registry.extend(tools::policy::apply_boxed(
+        tools::mcp::create_mcp_tools(&config.mcp, &security).await,
+        &config.tools,
+    ));
 
     // ── Resolve provider ─────────────────────────────────────────
     let provider_name = provider_override
@@ -2843,10 +2882,15 @@ pub async fn run(
         .or(config.default_provider.as_deref())
         .unwrap_or("openrouter");
 
-    let model_name = model_override
-        .as_deref()
-        .or(config.default_model.as_deref())
-        .unwrap_or("anthropic/claude-sonnet-4");
+    let model_name = config.resolve_model_alias(
+        model_override
+            .as_deref()
+            .or(config.default_model.as_deref())
+            .unwrap_or("anthropic/claude-sonnet-4"),
+    );
+    let model_name = model_name.as_str();
+    let context_window_tokens =
+        context_window::resolve_context_window(model_name, &config.agent.context_windows);
 
     let provider_runtime_options = providers::ProviderRuntimeOptions {
         auth_profile_override: None,
@@ -2854,6 +2898,13 @@ pub async fn run(
         zeroclaw_dir: config.config_path.parent().map(std::path::PathBuf::from),
         secrets_encrypt: config.secrets.encrypt,
         reasoning_enabled: config.runtime.reasoning_enabled,
+        gemini_safety_settings: config.gemini.safety_settings.clone(),
+        azure_openai: config.azure_openai.clone(),
+        openrouter: config.openrouter.clone(),
+        local_llm: config.local_llm.clone(),
+        workspace_dir: Some(config.workspace_dir.clone()),
+        response_cache: memory::create_response_cache(&config.memory, &config.workspace_dir)
+            .map(Arc::new),
     };
 
     let provider: Box<dyn Provider> = providers::create_routed_provider_with_options(
@@ -2914,6 +2965,10 @@ pub async fn run(
             "memory_recall",
             "Search memory. Use when: retrieving prior decisions, user preferences, historical context. Don't use when: answer is already in current context.",
         ),
+        (
+            "memory_search",
+            "Semantic search over memory and indexed workspace notes. Use when: keyword recall misses, or the answer may live in the user's notes. Don't use when: the exact memory key is known.",
+        ),
         (
             "memory_forget",
             "Delete a memory entry. Use when: memory is incorrect/stale or explicitly requested for removal. Don't use when: impact is uncertain.",
@@ -2937,6 +2992,12 @@ pub async fn run(
         "Force-run a cron job immediately and record a run history entry.",
     ));
     tool_descs.push(("cron_runs", "Show recent run history for a cron job."));
+    if config.cost.enabled {
+        tool_descs.push((
+            "cost_stats",
+            "Show LLM spend per day and per provider/model from the cost ledger. Use when: the user asks what the agent costs or how many tokens it used.",
+        ));
+    }
     tool_descs.push((
         "screenshot",
         "Capture a screenshot of the current screen. Returns file path and base64-encoded PNG. Use when: visual verification, UI inspection, debugging displays.",
@@ -3006,7 +3067,7 @@ pub async fn run(
     } else {
         None
     };
-    let native_tools = provider.supports_native_tools();
+    let native_tools = provider.supports_native_tools_for_model(model_name);
     let mut system_prompt = crate::channels::build_system_prompt_with_mode(
         &config.workspace_dir,
         model_name,
@@ -3080,6 +3141,7 @@ pub async fn run(
             channel_name,
             &config.multimodal,
             config.agent.max_tool_iterations,
+            context_window_tokens,
             None,
             None,
             None,
@@ -3202,6 +3264,7 @@ pub async fn run(
                 channel_name,
                 &config.multimodal,
                 config.agent.max_tool_iterations,
+                context_window_tokens,
                 None,
                 None,
                 None,
@@ -3232,6 +3295,7 @@ pub async fn run(
                 provider.as_ref(),
                 model_name,
                 config.agent.max_history_messages,
+                context_window_tokens,
             )
             .await
             {
@@ -3260,8 +3324,10 @@ pub async fn run(
 /// Process a single message through the full agent (with tools, peripherals, memory).
 /// Used by channels (Telegram, Discord, etc.) to enable hardware and tool use.
 pub async fn process_message(config: Config, message: &str) -> Result<String> {
-    let observer: Arc<dyn Observer> =
-        Arc::from(observability::create_observer(&config.observability));
+    let observer: Arc<dyn Observer> = Arc::from(crate::cost::with_cost_tracking(
+        observability::create_observer(&config.observability),
+        &config,
+    ));
     let runtime: Arc<dyn runtime::RuntimeAdapter> =
         Arc::from(runtime::create_runtime(&config.runtime)?);
     let security = Arc::new(SecurityPolicy::from_config(
@@ -3301,18 +3367,31 @@ pub async fn process_message(config: Config, message: &str) -> Result<String> {
     let peripheral_tools: Vec<Box<dyn Tool>> =
         crate::peripherals::create_peripheral_tools(&config.peripherals).await?;
     tools_registry.extend(peripheral_tools);
+    tools_registry.extend(tools::policy::apply_boxed(
+        tools::mcp::create_mcp_tools(&config.mcp, &security).await,
+        &config.tools,
+    ));
 
     let provider_name = config.default_provider.as_deref().unwrap_or("openrouter");
-    let model_name = config
-        .default_model
-        .clone()
-        .unwrap_or_else(|| "anthropic/claude-sonnet-4-20250514".into());
+    let model_name = config.resolve_model_alias(
+        config
+            .default_model
+            .as_deref()
+            .unwrap_or("anthropic/claude-sonnet-4-20250514"),
+    );
     let provider_runtime_options = providers::ProviderRuntimeOptions {
         auth_profile_override: None,
         provider_api_url: config.api_url.clone(),
         zeroclaw_dir: config.config_path.parent().map(std::path::PathBuf::from),
         secrets_encrypt: config.secrets.encrypt,
         reasoning_enabled: config.runtime.reasoning_enabled,
+        gemini_safety_settings: config.gemini.safety_settings.clone(),
+        azure_openai: config.azure_openai.clone(),
+        openrouter: config.openrouter.clone(),
+        local_llm: config.local_llm.clone(),
+        workspace_dir: Some(config.workspace_dir.clone()),
+        response_cache: memory::create_response_cache(&config.memory, &config.workspace_dir)
+            .map(Arc::new),
     };
     let provider: Box<dyn Provider> = providers::create_routed_provider_with_options(
         provider_name,
@@ -3392,7 +3471,7 @@ pub async fn process_message(config: Config, message: &str) -> Result<String> {
     } else {
         None
     };
-    let native_tools = provider.supports_native_tools();
+    let native_tools = provider.supports_native_tools_for_model(&model_name);
     let mut system_prompt = crate::channels::build_system_prompt_with_mode(
         &config.workspace_dir,
         &model_name,
@@ -3437,6 +3516,7 @@ pub async fn process_message(config: Config, message: &str) -> Result<String> {
         true,
         &config.multimodal,
         config.agent.max_tool_iterations,
+        context_window::resolve_context_window(&model_name, &config.agent.context_windows),
     )
     .await
 }
@@ -3746,6 +3826,7 @@ mod tests {
             "cli",
             &crate::config::MultimodalConfig::default(),
             3,
+            0,
             None,
             None,
             None,
@@ -3792,6 +3873,7 @@ mod tests {
             "cli",
             &multimodal,
             3,
+            0,
             None,
             None,
             None,
@@ -3832,6 +3914,7 @@ mod tests {
             "cli",
             &crate::config::MultimodalConfig::default(),
             3,
+            0,
             None,
             None,
             None,
@@ -3958,6 +4041,7 @@ mod tests {
             "telegram",
             &crate::config::MultimodalConfig::default(),
             4,
+            0,
             None,
             None,
             None,
@@ -4027,6 +4111,7 @@ mod tests {
             "cli",
             &crate::config::MultimodalConfig::default(),
             4,
+            0,
             None,
             None,
             None,
@@ -4083,6 +4168,7 @@ mod tests {
             "cli",
             &crate::config::MultimodalConfig::default(),
             4,
+            0,
             None,
             None,
             None,
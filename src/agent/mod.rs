@@ -1,7 +1,9 @@
 #[allow(clippy::module_inception)]
 pub mod agent;
 pub mod classifier;
+pub mod context_window;
 pub mod dispatcher;
+pub mod events;
 pub mod loop_;
 pub mod memory_loader;
 pub mod prompt;
@@ -10,6 +12,6 @@ pub mod prompt;
 mod tests;
 
 #[allow(unused_imports)]
-pub use agent::{Agent, AgentBuilder};
+pub use agent::{Agent, AgentBuilder, TurnEvent};
 #[allow(unused_imports)]
 pub use loop_::{process_message, run};
@@ -10,7 +10,7 @@
 
 use anyhow::{anyhow, Result};
 use async_imap::extensions::idle::IdleResponse;
-use async_imap::types::Fetch;
+use async_imap::types::{Fetch, Flag};
 use async_imap::Session;
 use async_trait::async_trait;
 use futures_util::TryStreamExt;
@@ -67,6 +67,9 @@ pub struct EmailConfig {
     /// Allowed sender addresses/domains (empty = deny all, ["*"] = allow all)
     #[serde(default)]
     pub allowed_senders: Vec<String>,
+    /// Addresses/domains the `email_send` tool may send to (empty = deny all, ["*"] = allow all)
+    #[serde(default)]
+    pub allowed_recipients: Vec<String>,
 }
 
 impl crate::config::traits::ChannelConfig for EmailConfig {
@@ -108,12 +111,37 @@ impl Default for EmailConfig {
             from_address: String::new(),
             idle_timeout_secs: default_idle_timeout(),
             allowed_senders: Vec::new(),
+            allowed_recipients: Vec::new(),
         }
     }
 }
 
 type ImapSession = Session<TlsStream<TcpStream>>;
 
+/// Match an address against an allowlist of addresses and domains
+/// (empty = deny all, "*" = allow all).
+fn address_allowed(allowlist: &[String], email: &str) -> bool {
+    if allowlist.is_empty() {
+        return false; // Empty = deny all
+    }
+    if allowlist.iter().any(|a| a == "*") {
+        return true; // Wildcard = allow all
+    }
+    let email_lower = email.to_lowercase();
+    allowlist.iter().any(|allowed| {
+        if allowed.starts_with('@') {
+            // Domain match with @ prefix: "@example.com"
+            email_lower.ends_with(&allowed.to_lowercase())
+        } else if allowed.contains('@') {
+            // Full email address match
+            allowed.eq_ignore_ascii_case(email)
+        } else {
+            // Domain match without @ prefix: "example.com"
+            email_lower.ends_with(&format!("@{}", allowed.to_lowercase()))
+        }
+    })
+}
+
 /// Email channel — IMAP IDLE for instant push notifications, SMTP for outbound
 pub struct EmailChannel {
     pub config: EmailConfig,
@@ -130,25 +158,12 @@ impl EmailChannel {
 
     /// Check if a sender email is in the allowlist
     pub fn is_sender_allowed(&self, email: &str) -> bool {
-        if self.config.allowed_senders.is_empty() {
-            return false; // Empty = deny all
-        }
-        if self.config.allowed_senders.iter().any(|a| a == "*") {
-            return true; // Wildcard = allow all
-        }
-        let email_lower = email.to_lowercase();
-        self.config.allowed_senders.iter().any(|allowed| {
-            if allowed.starts_with('@') {
-                // Domain match with @ prefix: "@example.com"
-                email_lower.ends_with(&allowed.to_lowercase())
-            } else if allowed.contains('@') {
-                // Full email address match
-                allowed.eq_ignore_ascii_case(email)
-            } else {
-                // Domain match without @ prefix: "example.com"
-                email_lower.ends_with(&format!("@{}", allowed.to_lowercase()))
-            }
-        })
+        address_allowed(&self.config.allowed_senders, email)
+    }
+
+    /// Check if an outbound recipient is in the `email_send` allowlist
+    pub fn is_recipient_allowed(&self, email: &str) -> bool {
+        address_allowed(&self.config.allowed_recipients, email)
     }
 
     /// Strip HTML tags from content (basic)
@@ -486,6 +501,120 @@ impl EmailChannel {
         };
         Ok(transport)
     }
+
+    /// Build a [`MailboxMessage`] from raw RFC822 bytes (full message or headers only)
+    pub fn summarize_message(
+        raw: &[u8],
+        uid: u32,
+        unread: bool,
+        include_body: bool,
+    ) -> Option<MailboxMessage> {
+        let parsed = MessageParser::default().parse(raw)?;
+        Some(MailboxMessage {
+            uid,
+            from: Self::extract_sender(&parsed),
+            subject: parsed.subject().unwrap_or("(no subject)").to_string(),
+            date: parsed.date().map(|d| d.to_rfc3339()),
+            unread,
+            body: include_body.then(|| Self::extract_text(&parsed)),
+        })
+    }
+
+    /// List the newest messages in `folder` without changing any flags
+    pub async fn list_messages(
+        &self,
+        folder: &str,
+        unread_only: bool,
+        limit: usize,
+    ) -> Result<Vec<MailboxMessage>> {
+        let mut session = self.connect_imap().await?;
+        // EXAMINE opens the mailbox read-only, so nothing is marked as seen
+        session.examine(folder).await?;
+
+        let mut uids: Vec<u32> = session
+            .uid_search(if unread_only { "UNSEEN" } else { "ALL" })
+            .await?
+            .into_iter()
+            .collect();
+        uids.sort_unstable_by(|a, b| b.cmp(a));
+        uids.truncate(limit);
+
+        let mut results = Vec::with_capacity(uids.len());
+        if !uids.is_empty() {
+            let uid_set = uids
+                .iter()
+                .map(|u| u.to_string())
+                .collect::<Vec<_>>()
+                .join(",");
+            let messages: Vec<Fetch> = session
+                .uid_fetch(&uid_set, "(FLAGS BODY.PEEK[HEADER])")
+                .await?
+                .try_collect()
+                .await?;
+            for msg in &messages {
+                let unread = !msg.flags().any(|f| f == Flag::Seen);
+                if let Some(summary) = msg
+                    .header()
+                    .and_then(|h| Self::summarize_message(h, msg.uid.unwrap_or(0), unread, false))
+                {
+                    results.push(summary);
+                }
+            }
+            results.sort_by_key(|summary| std::cmp::Reverse(summary.uid));
+        }
+
+        let _ = session.logout().await;
+        Ok(results)
+    }
+
+    /// Read one message by UID; only marks it as seen when `mark_seen` is set
+    pub async fn read_message(
+        &self,
+        folder: &str,
+        uid: u32,
+        mark_seen: bool,
+    ) -> Result<Option<MailboxMessage>> {
+        let mut session = self.connect_imap().await?;
+        if mark_seen {
+            session.select(folder).await?;
+        } else {
+            session.examine(folder).await?;
+        }
+
+        let messages: Vec<Fetch> = session
+            .uid_fetch(uid.to_string(), "(FLAGS BODY.PEEK[])")
+            .await?
+            .try_collect()
+            .await?;
+        let message = messages.iter().find_map(|msg| {
+            let unread = !msg.flags().any(|f| f == Flag::Seen);
+            msg.body()
+                .and_then(|b| Self::summarize_message(b, uid, unread, true))
+        });
+
+        if mark_seen && message.is_some() {
+            let _ = session
+                .uid_store(uid.to_string(), "+FLAGS (\\Seen)")
+                .await?
+                .try_collect::<Vec<_>>()
+                .await;
+        }
+
+        let _ = session.logout().await;
+        Ok(message)
+    }
+}
+
+/// A mailbox message as exposed to tools
+#[derive(Debug, Clone, Serialize)]
+pub struct MailboxMessage {
+    pub uid: u32,
+    pub from: String,
+    pub subject: String,
+    pub date: Option<String>,
+    pub unread: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub body: Option<String>,
 }
 
 /// Internal struct for parsed email data
@@ -635,6 +764,7 @@ mod tests {
             from_address: "bot@example.com".to_string(),
             idle_timeout_secs: 1200,
             allowed_senders: vec!["allowed@example.com".to_string()],
+            allowed_recipients: Vec::new(),
         };
         assert_eq!(config.imap_host, "imap.example.com");
         assert_eq!(config.imap_folder, "Archive");
@@ -655,6 +785,7 @@ mod tests {
             from_address: "bot@test.com".to_string(),
             idle_timeout_secs: 1740,
             allowed_senders: vec!["*".to_string()],
+            allowed_recipients: Vec::new(),
         };
         let cloned = config.clone();
         assert_eq!(cloned.imap_host, config.imap_host);
@@ -781,6 +912,19 @@ mod tests {
         assert!(channel.is_sender_allowed("specific@example.com"));
     }
 
+    #[test]
+    fn is_recipient_allowed_uses_separate_allowlist() {
+        let config = EmailConfig {
+            allowed_senders: vec!["*".to_string()],
+            allowed_recipients: vec!["@example.com".to_string()],
+            ..Default::default()
+        };
+        let channel = EmailChannel::new(config);
+        assert!(channel.is_recipient_allowed("me@example.com"));
+        assert!(!channel.is_recipient_allowed("me@other.com"));
+        assert!(!EmailChannel::new(EmailConfig::default()).is_recipient_allowed("me@example.com"));
+    }
+
     #[test]
     fn is_sender_allowed_empty_sender() {
         let config = EmailConfig {
@@ -900,6 +1044,7 @@ mod tests {
             from_address: "bot@example.com".to_string(),
             idle_timeout_secs: 1740,
             allowed_senders: vec!["allowed@example.com".to_string()],
+            allowed_recipients: Vec::new(),
         };
 
         let json = serde_json::to_string(&config).unwrap();
@@ -125,6 +125,7 @@ const CHANNEL_HOOK_MAX_OUTBOUND_CHARS: usize = 20_000;
 
 type ProviderCacheMap = Arc<Mutex<HashMap<String, Arc<dyn Provider>>>>;
 type RouteSelectionMap = Arc<Mutex<HashMap<String, ChannelRouteSelection>>>;
+type ToolRegistry = Arc<Vec<Box<dyn Tool>>>;
 
 fn effective_channel_message_timeout_secs(configured: u64) -> u64 {
     configured.max(MIN_CHANNEL_MESSAGE_TIMEOUT_SECS)
@@ -152,6 +153,8 @@ enum ChannelRuntimeCommand {
     ShowModel,
     SetModel(String),
     NewSession,
+    ShowProfiles,
+    SetProfile(String),
 }
 
 #[derive(Debug, Clone, Default, Deserialize)]
@@ -169,6 +172,7 @@ struct ModelCacheEntry {
 struct ChannelRuntimeDefaults {
     default_provider: String,
     model: String,
+    model_aliases: HashMap<String, String>,
     temperature: f64,
     api_key: Option<String>,
     api_url: Option<String>,
@@ -203,10 +207,14 @@ struct ChannelRuntimeContext {
     provider: Arc<dyn Provider>,
     default_provider: Arc<String>,
     memory: Arc<dyn Memory>,
-    tools_registry: Arc<Vec<Box<dyn Tool>>>,
+    tools_registry: ToolRegistry,
+    /// Registries for `[channels_config.overrides]`, keyed like the config.
+    channel_tools: Arc<HashMap<String, ToolRegistry>>,
     observer: Arc<dyn Observer>,
     system_prompt: Arc<String>,
     model: Arc<String>,
+    /// `[model_aliases]` at startup; hot reloads carry their own copy.
+    model_aliases: Arc<HashMap<String, String>>,
     temperature: f64,
     auto_save_memory: bool,
     max_tool_iterations: usize,
@@ -224,6 +232,8 @@ struct ChannelRuntimeContext {
     multimodal: crate::config::MultimodalConfig,
     hooks: Option<Arc<crate::hooks::HookRunner>>,
     non_cli_excluded_tools: Arc<Vec<String>>,
+    /// `[agent].context_windows` overrides for the per-model token budget.
+    context_windows: Arc<HashMap<String, usize>>,
 }
 
 #[derive(Clone)]
@@ -589,6 +599,12 @@ fn parse_runtime_command(channel_name: &str, content: &str) -> Option<ChannelRun
             }
         }
         "/new" => Some(ChannelRuntimeCommand::NewSession),
+        "/profile" => match parts.next() {
+            Some(profile) => Some(ChannelRuntimeCommand::SetProfile(
+                profile.trim().to_string(),
+            )),
+            None => Some(ChannelRuntimeCommand::ShowProfiles),
+        },
         _ => None,
     }
 }
@@ -622,16 +638,19 @@ fn resolved_default_provider(config: &Config) -> String {
 }
 
 fn resolved_default_model(config: &Config) -> String {
-    config
-        .default_model
-        .clone()
-        .unwrap_or_else(|| "anthropic/claude-sonnet-4.6".to_string())
+    config.resolve_model_alias(
+        config
+            .default_model
+            .as_deref()
+            .unwrap_or("anthropic/claude-sonnet-4.6"),
+    )
 }
 
 fn runtime_defaults_from_config(config: &Config) -> ChannelRuntimeDefaults {
     ChannelRuntimeDefaults {
         default_provider: resolved_default_provider(config),
         model: resolved_default_model(config),
+        model_aliases: config.model_aliases.clone(),
         temperature: config.default_temperature,
         api_key: config.api_key.clone(),
         api_url: config.api_url.clone(),
@@ -659,6 +678,7 @@ fn runtime_defaults_snapshot(ctx: &ChannelRuntimeContext) -> ChannelRuntimeDefau
     ChannelRuntimeDefaults {
         default_provider: ctx.default_provider.as_str().to_string(),
         model: ctx.model.as_str().to_string(),
+        model_aliases: (*ctx.model_aliases).clone(),
         temperature: ctx.temperature,
         api_key: ctx.api_key.clone(),
         api_url: ctx.api_url.clone(),
@@ -706,6 +726,7 @@ async fn load_runtime_defaults_from_config_file(path: &Path) -> Result<ChannelRu
     }
 
     parsed.apply_env_overrides();
+    crate::config::low_resource::apply(&mut parsed);
     Ok(runtime_defaults_from_config(&parsed))
 }
 
@@ -1008,7 +1029,11 @@ async fn create_resilient_provider_nonblocking(
     .context("failed to join provider initialization task")?
 }
 
-fn build_models_help_response(current: &ChannelRouteSelection, workspace_dir: &Path) -> String {
+fn build_models_help_response(
+    current: &ChannelRouteSelection,
+    workspace_dir: &Path,
+    model_aliases: &HashMap<String, String>,
+) -> String {
     let mut response = String::new();
     let _ = writeln!(
         response,
@@ -1017,6 +1042,15 @@ fn build_models_help_response(current: &ChannelRouteSelection, workspace_dir: &P
     );
     response.push_str("\nSwitch model with `/model <model-id>`.\n");
 
+    if !model_aliases.is_empty() {
+        let mut aliases: Vec<_> = model_aliases.iter().collect();
+        aliases.sort();
+        response.push_str("\nModel aliases:\n");
+        for (alias, model) in aliases {
+            let _ = writeln!(response, "- `{alias}` → `{model}`");
+        }
+    }
+
     let cached_models = load_cached_model_preview(workspace_dir, &current.provider);
     if cached_models.is_empty() {
         let _ = writeln!(
@@ -1063,6 +1097,46 @@ fn build_providers_help_response(current: &ChannelRouteSelection) -> String {
     response
 }
 
+/// `/profile` lists config profiles; `/profile <name>` switches to one,
+/// saves it, and asks the daemon to hot-reload the affected components.
+async fn handle_profile_command(ctx: &ChannelRuntimeContext, profile: Option<&str>) -> String {
+    let Some(config_path) = runtime_config_path(ctx) else {
+        return "Profiles are unavailable: config file location is unknown.".to_string();
+    };
+    let mut config = match Config::load_from(&config_path, ctx.workspace_dir.as_path()).await {
+        Ok(config) => config,
+        Err(err) => {
+            let safe_err = providers::sanitize_api_error(&format!("{err:#}"));
+            return format!("Failed to load config.\nDetails: {safe_err}");
+        }
+    };
+
+    let Some(profile) = profile else {
+        let names = config.profile_names();
+        if names.is_empty() {
+            return "No profiles configured. Add `[profiles.<name>]` sections to config.toml."
+                .to_string();
+        }
+        let available = names
+            .iter()
+            .map(|name| format!("`{name}`"))
+            .collect::<Vec<_>>()
+            .join(", ");
+        return format!(
+            "Active profile: `{}`.\nAvailable: {available}\nSwitch with `/profile <name>`.",
+            config.active_profile.as_deref().unwrap_or("none")
+        );
+    };
+
+    match Box::pin(config.activate_profile(profile, crate::config::ChangeSource::Chat)).await {
+        Ok(()) => {
+            crate::daemon::request_reload();
+            format!("Switched to profile `{profile}`. Settings apply in a few seconds.")
+        }
+        Err(err) => format!("Profile unchanged: {err:#}"),
+    }
+}
+
 async fn handle_runtime_command_if_needed(
     ctx: &ChannelRuntimeContext,
     msg: &traits::ChannelMessage,
@@ -1108,20 +1182,32 @@ async fn handle_runtime_command_if_needed(
                 ),
             }
         }
-        ChannelRuntimeCommand::ShowModel => {
-            build_models_help_response(&current, ctx.workspace_dir.as_path())
-        }
+        ChannelRuntimeCommand::ShowModel => build_models_help_response(
+            &current,
+            ctx.workspace_dir.as_path(),
+            &runtime_defaults_snapshot(ctx).model_aliases,
+        ),
         ChannelRuntimeCommand::SetModel(raw_model) => {
-            let model = raw_model.trim().trim_matches('`').to_string();
-            if model.is_empty() {
+            let requested = raw_model.trim().trim_matches('`');
+            if requested.is_empty() {
                 "Model ID cannot be empty. Use `/model <model-id>`.".to_string()
             } else {
+                let model = runtime_defaults_snapshot(ctx)
+                    .model_aliases
+                    .get(requested)
+                    .map_or(requested, |target| target.trim())
+                    .to_string();
                 current.model = model.clone();
                 set_route_selection(ctx, &sender_key, current.clone());
                 clear_sender_history(ctx, &sender_key);
 
+                let shown = if model == requested {
+                    format!("`{model}`")
+                } else {
+                    format!("`{requested}` (`{model}`)")
+                };
                 format!(
-                    "Model switched to `{model}` for provider `{}` in this sender session.",
+                    "Model switched to {shown} for provider `{}` in this sender session.",
                     current.provider
                 )
             }
@@ -1130,6 +1216,10 @@ async fn handle_runtime_command_if_needed(
             clear_sender_history(ctx, &sender_key);
             "Conversation history cleared. Starting fresh.".to_string()
         }
+        ChannelRuntimeCommand::ShowProfiles => Box::pin(handle_profile_command(ctx, None)).await,
+        ChannelRuntimeCommand::SetProfile(profile) => {
+            Box::pin(handle_profile_command(ctx, Some(&profile))).await
+        }
     };
 
     if let Err(err) = channel
@@ -1455,6 +1545,75 @@ fn strip_isolated_tool_json_artifacts(message: &str, known_tool_names: &HashSet<
     result.trim().to_string()
 }
 
+/// Restart signals of running channel listeners, by channel name.
+fn channel_restart_signals() -> &'static Mutex<HashMap<String, Arc<tokio::sync::Notify>>> {
+    static SIGNALS: OnceLock<Mutex<HashMap<String, Arc<tokio::sync::Notify>>>> = OnceLock::new();
+    SIGNALS.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// Registration of a running listener's restart signal; removed on drop,
+/// including when the listener task is aborted.
+struct ChannelRestartSignal {
+    name: String,
+    notify: Arc<tokio::sync::Notify>,
+}
+
+impl ChannelRestartSignal {
+    fn register(name: &str) -> Self {
+        let notify = Arc::new(tokio::sync::Notify::new());
+        channel_restart_signals()
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(name.to_string(), Arc::clone(&notify));
+        Self {
+            name: name.to_string(),
+            notify,
+        }
+    }
+}
+
+impl Drop for ChannelRestartSignal {
+    fn drop(&mut self) {
+        let mut signals = channel_restart_signals()
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        // A newer listener may have taken over the name.
+        if signals
+            .get(&self.name)
+            .is_some_and(|notify| Arc::ptr_eq(notify, &self.notify))
+        {
+            signals.remove(&self.name);
+        }
+    }
+}
+
+/// Ask a running channel listener to reconnect now, without waiting for a
+/// failure. Returns false when no channel by that name is running.
+pub fn request_channel_restart(name: &str) -> bool {
+    let signals = channel_restart_signals()
+        .lock()
+        .unwrap_or_else(|e| e.into_inner());
+    match signals.get(name) {
+        Some(notify) => {
+            notify.notify_one();
+            true
+        }
+        None => false,
+    }
+}
+
+/// Names of the running channel listeners, sorted.
+pub fn running_channels() -> Vec<String> {
+    let mut names: Vec<String> = channel_restart_signals()
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .keys()
+        .cloned()
+        .collect();
+    names.sort();
+    names
+}
+
 fn spawn_supervised_listener(
     ch: Arc<dyn Channel>,
     tx: tokio::sync::mpsc::Sender<traits::ChannelMessage>,
@@ -1485,6 +1644,7 @@ fn spawn_supervised_listener_with_health_interval(
 
     tokio::spawn(async move {
         let component = format!("channel:{}", ch.name());
+        let restart = ChannelRestartSignal::register(ch.name());
         let mut backoff = initial_backoff_secs.max(1);
         let max_backoff = max_backoff_secs.max(backoff);
 
@@ -1492,6 +1652,7 @@ fn spawn_supervised_listener_with_health_interval(
             crate::health::mark_component_ok(&component);
             let mut health = tokio::time::interval(health_interval);
             health.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+            let mut restart_requested = false;
             let result = {
                 let listen_future = ch.listen(tx.clone());
                 tokio::pin!(listen_future);
@@ -1501,6 +1662,10 @@ fn spawn_supervised_listener_with_health_interval(
                         _ = health.tick() => {
                             crate::health::mark_component_ok(&component);
                         }
+                        () = restart.notify.notified() => {
+                            restart_requested = true;
+                            break Ok(());
+                        }
                         result = &mut listen_future => break result,
                     }
                 }
@@ -1510,6 +1675,13 @@ fn spawn_supervised_listener_with_health_interval(
                 break;
             }
 
+            if restart_requested {
+                tracing::info!("Channel {} restart requested; reconnecting", ch.name());
+                crate::health::bump_component_restart(&component);
+                backoff = initial_backoff_secs.max(1);
+                continue;
+            }
+
             match result {
                 Ok(()) => {
                     tracing::warn!("Channel {} exited unexpectedly; restarting", ch.name());
@@ -1525,6 +1697,9 @@ fn spawn_supervised_listener_with_health_interval(
 
             crate::health::bump_component_restart(&component);
             tokio::time::sleep(Duration::from_secs(backoff)).await;
+            // Reconnect in the next Doze maintenance window rather than burning
+            // restarts against a network the OS is blocking.
+            crate::runtime::power::wait_until_unrestricted().await;
             // Double backoff AFTER sleeping so first error uses initial_backoff
             backoff = backoff.saturating_mul(2).min(max_backoff);
         }
@@ -1576,6 +1751,38 @@ fn spawn_scoped_typing_task(
     handle
 }
 
+/// Tools for a message: the `channel:chat` override, then `channel`, then
+/// the shared registry. Telegram forum replies (`chat:thread`) also match
+/// their chat.
+fn tools_for_message<'a>(
+    ctx: &'a ChannelRuntimeContext,
+    msg: &traits::ChannelMessage,
+) -> &'a [Box<dyn Tool>] {
+    let chat = msg
+        .reply_target
+        .split_once(':')
+        .map_or(msg.reply_target.as_str(), |(chat, _)| chat);
+    [
+        format!("{}:{}", msg.channel, msg.reply_target),
+        format!("{}:{chat}", msg.channel),
+        msg.channel.clone(),
+    ]
+    .iter()
+    .find_map(|key| ctx.channel_tools.get(key))
+    .unwrap_or(&ctx.tools_registry)
+    .as_slice()
+}
+
+/// How long ago the platform says `msg` was sent. `None` for messages
+/// without a timestamp.
+fn channel_message_lag(msg: &traits::ChannelMessage, now: SystemTime) -> Option<Duration> {
+    if msg.timestamp == 0 {
+        return None;
+    }
+    let sent = SystemTime::UNIX_EPOCH + Duration::from_secs(msg.timestamp);
+    Some(now.duration_since(sent).unwrap_or(Duration::ZERO))
+}
+
 async fn process_channel_message(
     ctx: Arc<ChannelRuntimeContext>,
     msg: traits::ChannelMessage,
@@ -1606,6 +1813,18 @@ async fn process_channel_message(
             "content_preview": truncate_with_ellipsis(&msg.content, 160),
         }),
     );
+    ctx.observer
+        .record_event(&observability::ObserverEvent::ChannelMessage {
+            channel: msg.channel.clone(),
+            direction: "inbound".to_string(),
+        });
+    if let Some(lag) = channel_message_lag(&msg, SystemTime::now()) {
+        ctx.observer
+            .record_metric(&observability::traits::ObserverMetric::ChannelLag {
+                channel: msg.channel.clone(),
+                lag,
+            });
+    }
 
     // ── Hook: on_message_received (modifying) ────────────
     let msg = if let Some(hooks) = &ctx.hooks {
@@ -1807,7 +2026,7 @@ async fn process_channel_message(
             run_tool_call_loop(
                 active_provider.as_ref(),
                 &mut history,
-                ctx.tools_registry.as_ref(),
+                tools_for_message(&ctx, &msg),
                 ctx.observer.as_ref(),
                 route.provider.as_str(),
                 route.model.as_str(),
@@ -1817,6 +2036,10 @@ async fn process_channel_message(
                 msg.channel.as_str(),
                 &ctx.multimodal,
                 ctx.max_tool_iterations,
+                crate::agent::context_window::resolve_context_window(
+                    route.model.as_str(),
+                    &ctx.context_windows,
+                ),
                 Some(cancellation_token.clone()),
                 delta_tx,
                 ctx.hooks.as_deref(),
@@ -2574,7 +2797,7 @@ async fn bind_telegram_identity(config: &Config, identity: &str) -> Result<()> {
     }
 
     telegram.allowed_users.push(normalized.clone());
-    updated.save().await?;
+    updated.save_from(crate::config::ChangeSource::Cli).await?;
     println!("✅ Bound Telegram identity: {normalized}");
     println!("   Saved to {}", updated.config_path.display());
     match maybe_restart_managed_daemon_service() {
@@ -3109,6 +3332,43 @@ pub async fn doctor_channels(config: Config) -> Result<()> {
 
 /// Start all configured channels and route messages to the agent
 #[allow(clippy::too_many_lines)]
+/// Tools an agent gets on channels, bound to `security`.
+async fn build_tools_registry(
+    config: &Config,
+    security: &Arc<SecurityPolicy>,
+    runtime: Arc<dyn runtime::RuntimeAdapter>,
+    mem: Arc<dyn Memory>,
+) -> Vec<Box<dyn Tool>> {
+    let (composio_key, composio_entity_id) = if config.composio.enabled {
+        (
+            config.composio.api_key.as_deref(),
+            Some(config.composio.entity_id.as_str()),
+        )
+    } else {
+        (None, None)
+    };
+    let mut tools_registry = tools::all_tools_with_runtime(
+        Arc::new(config.clone()),
+        security,
+        runtime,
+        mem,
+        composio_key,
+        composio_entity_id,
+        &config.browser,
+        &config.http_request,
+        &config.web_fetch,
+        &config.workspace_dir,
+        &config.agents,
+        config.api_key.as_deref(),
+        config,
+    );
+    tools_registry.extend(tools::policy::apply_boxed(
+        tools::mcp::create_mcp_tools(&config.mcp, security).await,
+        &config.tools,
+    ));
+    tools_registry
+}
+
 pub async fn start_channels(config: Config) -> Result<()> {
     let provider_name = resolved_default_provider(&config);
     let provider_runtime_options = providers::ProviderRuntimeOptions {
@@ -3117,6 +3377,13 @@ pub async fn start_channels(config: Config) -> Result<()> {
         zeroclaw_dir: config.config_path.parent().map(std::path::PathBuf::from),
         secrets_encrypt: config.secrets.encrypt,
         reasoning_enabled: config.runtime.reasoning_enabled,
+        gemini_safety_settings: config.gemini.safety_settings.clone(),
+        azure_openai: config.azure_openai.clone(),
+        openrouter: config.openrouter.clone(),
+        local_llm: config.local_llm.clone(),
+        workspace_dir: Some(config.workspace_dir.clone()),
+        response_cache: memory::create_response_cache(&config.memory, &config.workspace_dir)
+            .map(Arc::new),
     };
     let provider: Arc<dyn Provider> = Arc::from(
         create_resilient_provider_nonblocking(
@@ -3149,8 +3416,10 @@ pub async fn start_channels(config: Config) -> Result<()> {
         );
     }
 
-    let observer: Arc<dyn Observer> =
-        Arc::from(observability::create_observer(&config.observability));
+    let observer: Arc<dyn Observer> = Arc::from(crate::cost::with_cost_tracking(
+        observability::create_observer(&config.observability),
+        &config,
+    ));
     let runtime: Arc<dyn runtime::RuntimeAdapter> =
         Arc::from(runtime::create_runtime(&config.runtime)?);
     let security = Arc::new(SecurityPolicy::from_config(
@@ -3165,31 +3434,28 @@ pub async fn start_channels(config: Config) -> Result<()> {
         &config.workspace_dir,
         config.api_key.as_deref(),
     )?);
-    let (composio_key, composio_entity_id) = if config.composio.enabled {
-        (
-            config.composio.api_key.as_deref(),
-            Some(config.composio.entity_id.as_str()),
-        )
-    } else {
-        (None, None)
-    };
     // Build system prompt from workspace identity files + skills
     let workspace = config.workspace_dir.clone();
-    let tools_registry = Arc::new(tools::all_tools_with_runtime(
-        Arc::new(config.clone()),
-        &security,
-        runtime,
-        Arc::clone(&mem),
-        composio_key,
-        composio_entity_id,
-        &config.browser,
-        &config.http_request,
-        &config.web_fetch,
-        &workspace,
-        &config.agents,
-        config.api_key.as_deref(),
-        &config,
-    ));
+    let tools_registry = Arc::new(
+        build_tools_registry(&config, &security, Arc::clone(&runtime), Arc::clone(&mem)).await,
+    );
+    let mut channel_tools = HashMap::new();
+    for (key, channel_override) in &config.channels_config.overrides {
+        let mut scoped = config.clone();
+        if let Some(level) = channel_override.autonomy {
+            scoped.autonomy.level = level;
+        }
+        let security = Arc::new(SecurityPolicy::from_config(
+            &scoped.autonomy,
+            &scoped.workspace_dir,
+        ));
+        let mut tools =
+            build_tools_registry(&scoped, &security, Arc::clone(&runtime), Arc::clone(&mem)).await;
+        if let Some(allowed) = &channel_override.allowed_tools {
+            tools.retain(|tool| allowed.iter().any(|name| name == tool.name()));
+        }
+        channel_tools.insert(key.clone(), Arc::new(tools));
+    }
 
     let skills = crate::skills::load_skills_with_config(&workspace, &config);
 
@@ -3260,7 +3526,7 @@ pub async fn start_channels(config: Config) -> Result<()> {
     } else {
         None
     };
-    let native_tools = provider.supports_native_tools();
+    let native_tools = provider.supports_native_tools_for_model(&model);
     let mut system_prompt = build_system_prompt_with_mode(
         &workspace,
         &model,
@@ -3358,7 +3624,11 @@ pub async fn start_channels(config: Config) -> Result<()> {
             .map(|ch| (ch.name().to_string(), Arc::clone(ch)))
             .collect::<HashMap<_, _>>(),
     );
-    let max_in_flight_messages = compute_max_in_flight_messages(channels.len());
+    let mut max_in_flight_messages = compute_max_in_flight_messages(channels.len());
+    if config.low_resource_overrides.is_some() {
+        max_in_flight_messages =
+            max_in_flight_messages.min(config.runtime.low_resource.max_in_flight_messages);
+    }
 
     println!("  🚦 In-flight message limit: {max_in_flight_messages}");
 
@@ -3378,9 +3648,11 @@ pub async fn start_channels(config: Config) -> Result<()> {
         default_provider: Arc::new(provider_name),
         memory: Arc::clone(&mem),
         tools_registry: Arc::clone(&tools_registry),
+        channel_tools: Arc::new(channel_tools),
         observer,
         system_prompt: Arc::new(system_prompt),
         model: Arc::new(model.clone()),
+        model_aliases: Arc::new(config.model_aliases.clone()),
         temperature,
         auto_save_memory: config.memory.auto_save,
         max_tool_iterations: config.agent.max_tool_iterations,
@@ -3406,6 +3678,7 @@ pub async fn start_channels(config: Config) -> Result<()> {
             None
         },
         non_cli_excluded_tools: Arc::new(config.autonomy.non_cli_excluded_tools.clone()),
+        context_windows: Arc::new(config.agent.context_windows.clone()),
     });
 
     run_message_dispatch_loop(rx, runtime_ctx, max_in_flight_messages).await;
@@ -3464,6 +3737,19 @@ mod tests {
         assert_eq!(effective_channel_message_timeout_secs(300), 300);
     }
 
+    #[test]
+    fn parses_profile_runtime_commands() {
+        assert_eq!(
+            parse_runtime_command("telegram", "/profile"),
+            Some(ChannelRuntimeCommand::ShowProfiles)
+        );
+        assert_eq!(
+            parse_runtime_command("telegram", "/profile@mybot travel"),
+            Some(ChannelRuntimeCommand::SetProfile("travel".to_string()))
+        );
+        assert_eq!(parse_runtime_command("slack", "/profile work"), None);
+    }
+
     #[test]
     fn channel_message_timeout_budget_scales_with_tool_iterations() {
         assert_eq!(channel_message_timeout_budget_secs(300, 1), 300);
@@ -3599,6 +3885,8 @@ mod tests {
             default_provider: Arc::new("test-provider".to_string()),
             memory: Arc::new(NoopMemory),
             tools_registry: Arc::new(vec![]),
+            channel_tools: Arc::new(HashMap::new()),
+            model_aliases: Arc::new(HashMap::new()),
             observer: Arc::new(NoopObserver),
             system_prompt: Arc::new("system".to_string()),
             model: Arc::new("test-model".to_string()),
@@ -3619,6 +3907,7 @@ mod tests {
             workspace_dir: Arc::new(std::env::temp_dir()),
             message_timeout_secs: CHANNEL_MESSAGE_TIMEOUT_SECS,
             non_cli_excluded_tools: Arc::new(Vec::new()),
+            context_windows: Arc::new(HashMap::new()),
         };
 
         assert!(compact_sender_history(&ctx, &sender));
@@ -3639,6 +3928,63 @@ mod tests {
         }));
     }
 
+    #[test]
+    fn tools_for_message_prefers_chat_then_channel_overrides() {
+        let mut channel_tools: HashMap<String, ToolRegistry> = HashMap::new();
+        channel_tools.insert("telegram:-100".into(), Arc::new(vec![]));
+        channel_tools.insert(
+            "telegram".into(),
+            Arc::new(vec![Box::new(MockPriceTool) as Box<dyn Tool>]),
+        );
+        let ctx = ChannelRuntimeContext {
+            channels_by_name: Arc::new(HashMap::new()),
+            provider: Arc::new(DummyProvider),
+            default_provider: Arc::new("test-provider".to_string()),
+            memory: Arc::new(NoopMemory),
+            tools_registry: Arc::new(vec![Box::new(MockPriceTool), Box::new(MockPriceTool)]),
+            channel_tools: Arc::new(channel_tools),
+            observer: Arc::new(NoopObserver),
+            system_prompt: Arc::new("system".to_string()),
+            model: Arc::new("test-model".to_string()),
+            model_aliases: Arc::new(HashMap::new()),
+            temperature: 0.0,
+            auto_save_memory: false,
+            max_tool_iterations: 5,
+            min_relevance_score: 0.0,
+            conversation_histories: Arc::new(Mutex::new(HashMap::new())),
+            provider_cache: Arc::new(Mutex::new(HashMap::new())),
+            route_overrides: Arc::new(Mutex::new(HashMap::new())),
+            api_key: None,
+            api_url: None,
+            reliability: Arc::new(crate::config::ReliabilityConfig::default()),
+            interrupt_on_new_message: false,
+            multimodal: crate::config::MultimodalConfig::default(),
+            hooks: None,
+            provider_runtime_options: providers::ProviderRuntimeOptions::default(),
+            workspace_dir: Arc::new(std::env::temp_dir()),
+            message_timeout_secs: CHANNEL_MESSAGE_TIMEOUT_SECS,
+            non_cli_excluded_tools: Arc::new(Vec::new()),
+            context_windows: Arc::new(HashMap::new()),
+        };
+        let message = |channel: &str, reply_target: &str| traits::ChannelMessage {
+            id: "msg-1".into(),
+            sender: "alice".into(),
+            reply_target: reply_target.into(),
+            content: "hi".into(),
+            channel: channel.into(),
+            timestamp: 0,
+            thread_ts: None,
+        };
+
+        assert!(tools_for_message(&ctx, &message("telegram", "-100")).is_empty());
+        assert!(tools_for_message(&ctx, &message("telegram", "-100:7")).is_empty());
+        assert_eq!(tools_for_message(&ctx, &message("telegram", "42")).len(), 1);
+        assert_eq!(
+            tools_for_message(&ctx, &message("discord", "-100")).len(),
+            2
+        );
+    }
+
     #[test]
     fn append_sender_turn_stores_single_turn_per_call() {
         let sender = "telegram_u2".to_string();
@@ -3648,6 +3994,8 @@ mod tests {
             default_provider: Arc::new("test-provider".to_string()),
             memory: Arc::new(NoopMemory),
             tools_registry: Arc::new(vec![]),
+            channel_tools: Arc::new(HashMap::new()),
+            model_aliases: Arc::new(HashMap::new()),
             observer: Arc::new(NoopObserver),
             system_prompt: Arc::new("system".to_string()),
             model: Arc::new("test-model".to_string()),
@@ -3668,6 +4016,7 @@ mod tests {
             workspace_dir: Arc::new(std::env::temp_dir()),
             message_timeout_secs: CHANNEL_MESSAGE_TIMEOUT_SECS,
             non_cli_excluded_tools: Arc::new(Vec::new()),
+            context_windows: Arc::new(HashMap::new()),
         };
 
         append_sender_turn(&ctx, &sender, ChatMessage::user("hello"));
@@ -3700,6 +4049,8 @@ mod tests {
             default_provider: Arc::new("test-provider".to_string()),
             memory: Arc::new(NoopMemory),
             tools_registry: Arc::new(vec![]),
+            channel_tools: Arc::new(HashMap::new()),
+            model_aliases: Arc::new(HashMap::new()),
             observer: Arc::new(NoopObserver),
             system_prompt: Arc::new("system".to_string()),
             model: Arc::new("test-model".to_string()),
@@ -3720,6 +4071,7 @@ mod tests {
             workspace_dir: Arc::new(std::env::temp_dir()),
             message_timeout_secs: CHANNEL_MESSAGE_TIMEOUT_SECS,
             non_cli_excluded_tools: Arc::new(Vec::new()),
+            context_windows: Arc::new(HashMap::new()),
         };
 
         assert!(rollback_orphan_user_turn(&ctx, &sender, "pending"));
@@ -4175,6 +4527,8 @@ BTC is currently around $65,000 based on latest tool output."#
             default_provider: Arc::new("test-provider".to_string()),
             memory: Arc::new(NoopMemory),
             tools_registry: Arc::new(vec![Box::new(MockPriceTool)]),
+            channel_tools: Arc::new(HashMap::new()),
+            model_aliases: Arc::new(HashMap::new()),
             observer: Arc::new(NoopObserver),
             system_prompt: Arc::new("test-system-prompt".to_string()),
             model: Arc::new("test-model".to_string()),
@@ -4193,6 +4547,7 @@ BTC is currently around $65,000 based on latest tool output."#
             message_timeout_secs: CHANNEL_MESSAGE_TIMEOUT_SECS,
             interrupt_on_new_message: false,
             non_cli_excluded_tools: Arc::new(Vec::new()),
+            context_windows: Arc::new(HashMap::new()),
             multimodal: crate::config::MultimodalConfig::default(),
             hooks: None,
         });
@@ -4234,6 +4589,8 @@ BTC is currently around $65,000 based on latest tool output."#
             default_provider: Arc::new("test-provider".to_string()),
             memory: Arc::new(NoopMemory),
             tools_registry: Arc::new(vec![Box::new(MockPriceTool)]),
+            channel_tools: Arc::new(HashMap::new()),
+            model_aliases: Arc::new(HashMap::new()),
             observer: Arc::new(NoopObserver),
             system_prompt: Arc::new("test-system-prompt".to_string()),
             model: Arc::new("test-model".to_string()),
@@ -4252,6 +4609,7 @@ BTC is currently around $65,000 based on latest tool output."#
             message_timeout_secs: CHANNEL_MESSAGE_TIMEOUT_SECS,
             interrupt_on_new_message: false,
             non_cli_excluded_tools: Arc::new(Vec::new()),
+            context_windows: Arc::new(HashMap::new()),
             multimodal: crate::config::MultimodalConfig::default(),
             hooks: None,
         });
@@ -4307,6 +4665,8 @@ BTC is currently around $65,000 based on latest tool output."#
             default_provider: Arc::new("test-provider".to_string()),
             memory: Arc::new(NoopMemory),
             tools_registry: Arc::new(vec![Box::new(MockPriceTool)]),
+            channel_tools: Arc::new(HashMap::new()),
+            model_aliases: Arc::new(HashMap::new()),
             observer: Arc::new(NoopObserver),
             system_prompt: Arc::new("test-system-prompt".to_string()),
             model: Arc::new("test-model".to_string()),
@@ -4327,6 +4687,7 @@ BTC is currently around $65,000 based on latest tool output."#
             multimodal: crate::config::MultimodalConfig::default(),
             hooks: None,
             non_cli_excluded_tools: Arc::new(Vec::new()),
+            context_windows: Arc::new(HashMap::new()),
         });
 
         process_channel_message(
@@ -4366,6 +4727,8 @@ BTC is currently around $65,000 based on latest tool output."#
             default_provider: Arc::new("test-provider".to_string()),
             memory: Arc::new(NoopMemory),
             tools_registry: Arc::new(vec![Box::new(MockPriceTool)]),
+            channel_tools: Arc::new(HashMap::new()),
+            model_aliases: Arc::new(HashMap::new()),
             observer: Arc::new(NoopObserver),
             system_prompt: Arc::new("test-system-prompt".to_string()),
             model: Arc::new("test-model".to_string()),
@@ -4386,6 +4749,7 @@ BTC is currently around $65,000 based on latest tool output."#
             multimodal: crate::config::MultimodalConfig::default(),
             hooks: None,
             non_cli_excluded_tools: Arc::new(Vec::new()),
+            context_windows: Arc::new(HashMap::new()),
         });
 
         process_channel_message(
@@ -4434,6 +4798,8 @@ BTC is currently around $65,000 based on latest tool output."#
             default_provider: Arc::new("test-provider".to_string()),
             memory: Arc::new(NoopMemory),
             tools_registry: Arc::new(vec![]),
+            channel_tools: Arc::new(HashMap::new()),
+            model_aliases: Arc::new(HashMap::new()),
             observer: Arc::new(NoopObserver),
             system_prompt: Arc::new("test-system-prompt".to_string()),
             model: Arc::new("default-model".to_string()),
@@ -4454,6 +4820,7 @@ BTC is currently around $65,000 based on latest tool output."#
             multimodal: crate::config::MultimodalConfig::default(),
             hooks: None,
             non_cli_excluded_tools: Arc::new(Vec::new()),
+            context_windows: Arc::new(HashMap::new()),
         });
 
         process_channel_message(
@@ -4523,6 +4890,8 @@ BTC is currently around $65,000 based on latest tool output."#
             default_provider: Arc::new("test-provider".to_string()),
             memory: Arc::new(NoopMemory),
             tools_registry: Arc::new(vec![]),
+            channel_tools: Arc::new(HashMap::new()),
+            model_aliases: Arc::new(HashMap::new()),
             observer: Arc::new(NoopObserver),
             system_prompt: Arc::new("test-system-prompt".to_string()),
             model: Arc::new("default-model".to_string()),
@@ -4543,6 +4912,7 @@ BTC is currently around $65,000 based on latest tool output."#
             multimodal: crate::config::MultimodalConfig::default(),
             hooks: None,
             non_cli_excluded_tools: Arc::new(Vec::new()),
+            context_windows: Arc::new(HashMap::new()),
         });
 
         process_channel_message(
@@ -4594,6 +4964,8 @@ BTC is currently around $65,000 based on latest tool output."#
             default_provider: Arc::new("test-provider".to_string()),
             memory: Arc::new(NoopMemory),
             tools_registry: Arc::new(vec![]),
+            channel_tools: Arc::new(HashMap::new()),
+            model_aliases: Arc::new(HashMap::new()),
             observer: Arc::new(NoopObserver),
             system_prompt: Arc::new("test-system-prompt".to_string()),
             model: Arc::new("default-model".to_string()),
@@ -4614,6 +4986,7 @@ BTC is currently around $65,000 based on latest tool output."#
             multimodal: crate::config::MultimodalConfig::default(),
             hooks: None,
             non_cli_excluded_tools: Arc::new(Vec::new()),
+            context_windows: Arc::new(HashMap::new()),
         });
 
         process_channel_message(
@@ -4661,6 +5034,7 @@ BTC is currently around $65,000 based on latest tool output."#
                     defaults: ChannelRuntimeDefaults {
                         default_provider: "test-provider".to_string(),
                         model: "hot-reloaded-model".to_string(),
+                        model_aliases: HashMap::new(),
                         temperature: 0.5,
                         api_key: None,
                         api_url: None,
@@ -4677,6 +5051,8 @@ BTC is currently around $65,000 based on latest tool output."#
             default_provider: Arc::new("test-provider".to_string()),
             memory: Arc::new(NoopMemory),
             tools_registry: Arc::new(vec![]),
+            channel_tools: Arc::new(HashMap::new()),
+            model_aliases: Arc::new(HashMap::new()),
             observer: Arc::new(NoopObserver),
             system_prompt: Arc::new("test-system-prompt".to_string()),
             model: Arc::new("startup-model".to_string()),
@@ -4700,6 +5076,7 @@ BTC is currently around $65,000 based on latest tool output."#
             multimodal: crate::config::MultimodalConfig::default(),
             hooks: None,
             non_cli_excluded_tools: Arc::new(Vec::new()),
+            context_windows: Arc::new(HashMap::new()),
         });
 
         process_channel_message(
@@ -4751,6 +5128,8 @@ BTC is currently around $65,000 based on latest tool output."#
             default_provider: Arc::new("test-provider".to_string()),
             memory: Arc::new(NoopMemory),
             tools_registry: Arc::new(vec![Box::new(MockPriceTool)]),
+            channel_tools: Arc::new(HashMap::new()),
+            model_aliases: Arc::new(HashMap::new()),
             observer: Arc::new(NoopObserver),
             system_prompt: Arc::new("test-system-prompt".to_string()),
             model: Arc::new("test-model".to_string()),
@@ -4771,6 +5150,7 @@ BTC is currently around $65,000 based on latest tool output."#
             multimodal: crate::config::MultimodalConfig::default(),
             hooks: None,
             non_cli_excluded_tools: Arc::new(Vec::new()),
+            context_windows: Arc::new(HashMap::new()),
         });
 
         process_channel_message(
@@ -4811,6 +5191,8 @@ BTC is currently around $65,000 based on latest tool output."#
             default_provider: Arc::new("test-provider".to_string()),
             memory: Arc::new(NoopMemory),
             tools_registry: Arc::new(vec![Box::new(MockPriceTool)]),
+            channel_tools: Arc::new(HashMap::new()),
+            model_aliases: Arc::new(HashMap::new()),
             observer: Arc::new(NoopObserver),
             system_prompt: Arc::new("test-system-prompt".to_string()),
             model: Arc::new("test-model".to_string()),
@@ -4831,6 +5213,7 @@ BTC is currently around $65,000 based on latest tool output."#
             multimodal: crate::config::MultimodalConfig::default(),
             hooks: None,
             non_cli_excluded_tools: Arc::new(Vec::new()),
+            context_windows: Arc::new(HashMap::new()),
         });
 
         process_channel_message(
@@ -4982,6 +5365,8 @@ BTC is currently around $65,000 based on latest tool output."#
             default_provider: Arc::new("test-provider".to_string()),
             memory: Arc::new(NoopMemory),
             tools_registry: Arc::new(vec![]),
+            channel_tools: Arc::new(HashMap::new()),
+            model_aliases: Arc::new(HashMap::new()),
             observer: Arc::new(NoopObserver),
             system_prompt: Arc::new("test-system-prompt".to_string()),
             model: Arc::new("test-model".to_string()),
@@ -5002,6 +5387,7 @@ BTC is currently around $65,000 based on latest tool output."#
             multimodal: crate::config::MultimodalConfig::default(),
             hooks: None,
             non_cli_excluded_tools: Arc::new(Vec::new()),
+            context_windows: Arc::new(HashMap::new()),
         });
 
         let (tx, rx) = tokio::sync::mpsc::channel::<traits::ChannelMessage>(4);
@@ -5062,6 +5448,8 @@ BTC is currently around $65,000 based on latest tool output."#
             default_provider: Arc::new("test-provider".to_string()),
             memory: Arc::new(NoopMemory),
             tools_registry: Arc::new(vec![]),
+            channel_tools: Arc::new(HashMap::new()),
+            model_aliases: Arc::new(HashMap::new()),
             observer: Arc::new(NoopObserver),
             system_prompt: Arc::new("test-system-prompt".to_string()),
             model: Arc::new("test-model".to_string()),
@@ -5082,6 +5470,7 @@ BTC is currently around $65,000 based on latest tool output."#
             multimodal: crate::config::MultimodalConfig::default(),
             hooks: None,
             non_cli_excluded_tools: Arc::new(Vec::new()),
+            context_windows: Arc::new(HashMap::new()),
         });
 
         let (tx, rx) = tokio::sync::mpsc::channel::<traits::ChannelMessage>(8);
@@ -5154,6 +5543,8 @@ BTC is currently around $65,000 based on latest tool output."#
             default_provider: Arc::new("test-provider".to_string()),
             memory: Arc::new(NoopMemory),
             tools_registry: Arc::new(vec![]),
+            channel_tools: Arc::new(HashMap::new()),
+            model_aliases: Arc::new(HashMap::new()),
             observer: Arc::new(NoopObserver),
             system_prompt: Arc::new("test-system-prompt".to_string()),
             model: Arc::new("test-model".to_string()),
@@ -5174,6 +5565,7 @@ BTC is currently around $65,000 based on latest tool output."#
             multimodal: crate::config::MultimodalConfig::default(),
             hooks: None,
             non_cli_excluded_tools: Arc::new(Vec::new()),
+            context_windows: Arc::new(HashMap::new()),
         });
 
         let (tx, rx) = tokio::sync::mpsc::channel::<traits::ChannelMessage>(8);
@@ -5228,6 +5620,8 @@ BTC is currently around $65,000 based on latest tool output."#
             default_provider: Arc::new("test-provider".to_string()),
             memory: Arc::new(NoopMemory),
             tools_registry: Arc::new(vec![]),
+            channel_tools: Arc::new(HashMap::new()),
+            model_aliases: Arc::new(HashMap::new()),
             observer: Arc::new(NoopObserver),
             system_prompt: Arc::new("test-system-prompt".to_string()),
             model: Arc::new("test-model".to_string()),
@@ -5248,6 +5642,7 @@ BTC is currently around $65,000 based on latest tool output."#
             multimodal: crate::config::MultimodalConfig::default(),
             hooks: None,
             non_cli_excluded_tools: Arc::new(Vec::new()),
+            context_windows: Arc::new(HashMap::new()),
         });
 
         process_channel_message(
@@ -5287,6 +5682,8 @@ BTC is currently around $65,000 based on latest tool output."#
             default_provider: Arc::new("test-provider".to_string()),
             memory: Arc::new(NoopMemory),
             tools_registry: Arc::new(vec![]),
+            channel_tools: Arc::new(HashMap::new()),
+            model_aliases: Arc::new(HashMap::new()),
             observer: Arc::new(NoopObserver),
             system_prompt: Arc::new("test-system-prompt".to_string()),
             model: Arc::new("test-model".to_string()),
@@ -5307,6 +5704,7 @@ BTC is currently around $65,000 based on latest tool output."#
             multimodal: crate::config::MultimodalConfig::default(),
             hooks: None,
             non_cli_excluded_tools: Arc::new(Vec::new()),
+            context_windows: Arc::new(HashMap::new()),
         });
 
         process_channel_message(
@@ -5803,6 +6201,8 @@ BTC is currently around $65,000 based on latest tool output."#
             default_provider: Arc::new("test-provider".to_string()),
             memory: Arc::new(NoopMemory),
             tools_registry: Arc::new(vec![]),
+            channel_tools: Arc::new(HashMap::new()),
+            model_aliases: Arc::new(HashMap::new()),
             observer: Arc::new(NoopObserver),
             system_prompt: Arc::new("test-system-prompt".to_string()),
             model: Arc::new("test-model".to_string()),
@@ -5823,6 +6223,7 @@ BTC is currently around $65,000 based on latest tool output."#
             multimodal: crate::config::MultimodalConfig::default(),
             hooks: None,
             non_cli_excluded_tools: Arc::new(Vec::new()),
+            context_windows: Arc::new(HashMap::new()),
         });
 
         process_channel_message(
@@ -5888,6 +6289,8 @@ BTC is currently around $65,000 based on latest tool output."#
             default_provider: Arc::new("test-provider".to_string()),
             memory: Arc::new(RecallMemory),
             tools_registry: Arc::new(vec![]),
+            channel_tools: Arc::new(HashMap::new()),
+            model_aliases: Arc::new(HashMap::new()),
             observer: Arc::new(NoopObserver),
             system_prompt: Arc::new("test-system-prompt".to_string()),
             model: Arc::new("test-model".to_string()),
@@ -5908,6 +6311,7 @@ BTC is currently around $65,000 based on latest tool output."#
             multimodal: crate::config::MultimodalConfig::default(),
             hooks: None,
             non_cli_excluded_tools: Arc::new(Vec::new()),
+            context_windows: Arc::new(HashMap::new()),
         });
 
         process_channel_message(
@@ -5973,6 +6377,8 @@ BTC is currently around $65,000 based on latest tool output."#
             default_provider: Arc::new("test-provider".to_string()),
             memory: Arc::new(NoopMemory),
             tools_registry: Arc::new(vec![]),
+            channel_tools: Arc::new(HashMap::new()),
+            model_aliases: Arc::new(HashMap::new()),
             observer: Arc::new(NoopObserver),
             system_prompt: Arc::new("test-system-prompt".to_string()),
             model: Arc::new("test-model".to_string()),
@@ -5993,6 +6399,7 @@ BTC is currently around $65,000 based on latest tool output."#
             multimodal: crate::config::MultimodalConfig::default(),
             hooks: None,
             non_cli_excluded_tools: Arc::new(Vec::new()),
+            context_windows: Arc::new(HashMap::new()),
         });
 
         process_channel_message(
@@ -6441,6 +6848,55 @@ This is an example JSON object for profile settings."#;
         assert!(calls.load(Ordering::SeqCst) >= 1);
     }
 
+    #[test]
+    fn channel_message_lag_is_measured_from_platform_timestamp() {
+        let mut msg = traits::ChannelMessage {
+            id: "1".into(),
+            sender: "alice".into(),
+            reply_target: "alice".into(),
+            content: "hi".into(),
+            channel: "telegram".into(),
+            timestamp: 1_700_000_000,
+            thread_ts: None,
+        };
+        let now = SystemTime::UNIX_EPOCH + Duration::from_secs(1_700_000_007);
+        assert_eq!(channel_message_lag(&msg, now), Some(Duration::from_secs(7)));
+
+        // Clock skew never reports negative lag
+        let earlier = SystemTime::UNIX_EPOCH + Duration::from_secs(1_699_999_990);
+        assert_eq!(channel_message_lag(&msg, earlier), Some(Duration::ZERO));
+
+        msg.timestamp = 0;
+        assert_eq!(channel_message_lag(&msg, now), None);
+    }
+
+    #[tokio::test]
+    async fn supervised_listener_restarts_on_request_and_unregisters() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let channel_name = format!("test-supervised-restart-{}", uuid::Uuid::new_v4());
+        let channel: Arc<dyn Channel> = Arc::new(BlockUntilClosedChannel {
+            name: channel_name.clone(),
+            calls: Arc::clone(&calls),
+        });
+
+        let (tx, rx) = tokio::sync::mpsc::channel::<traits::ChannelMessage>(1);
+        let handle = spawn_supervised_listener(channel, tx, 1, 1);
+
+        tokio::time::sleep(Duration::from_millis(30)).await;
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+        assert!(running_channels().contains(&channel_name));
+
+        assert!(request_channel_restart(&channel_name));
+        tokio::time::sleep(Duration::from_millis(30)).await;
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+
+        drop(rx);
+        handle.abort();
+        let _ = handle.await;
+        assert!(!request_channel_restart(&channel_name));
+        assert!(!running_channels().contains(&channel_name));
+    }
+
     #[test]
     fn maybe_restart_daemon_systemd_args_regression() {
         assert_eq!(
@@ -6522,6 +6978,8 @@ This is an example JSON object for profile settings."#;
             default_provider: Arc::new("dummy".to_string()),
             memory: Arc::new(NoopMemory),
             tools_registry: Arc::new(vec![]),
+            channel_tools: Arc::new(HashMap::new()),
+            model_aliases: Arc::new(HashMap::new()),
             observer: Arc::new(NoopObserver),
             system_prompt: Arc::new("You are a helpful assistant.".to_string()),
             model: Arc::new("test-model".to_string()),
@@ -6542,6 +7000,7 @@ This is an example JSON object for profile settings."#;
             multimodal: crate::config::MultimodalConfig::default(),
             hooks: None,
             non_cli_excluded_tools: Arc::new(Vec::new()),
+            context_windows: Arc::new(HashMap::new()),
         });
 
         // Simulate a photo attachment message with [IMAGE:] marker.
@@ -6588,6 +7047,8 @@ This is an example JSON object for profile settings."#;
             default_provider: Arc::new("dummy".to_string()),
             memory: Arc::new(NoopMemory),
             tools_registry: Arc::new(vec![]),
+            channel_tools: Arc::new(HashMap::new()),
+            model_aliases: Arc::new(HashMap::new()),
             observer: Arc::new(NoopObserver),
             system_prompt: Arc::new("You are a helpful assistant.".to_string()),
             model: Arc::new("test-model".to_string()),
@@ -6608,6 +7069,7 @@ This is an example JSON object for profile settings."#;
             multimodal: crate::config::MultimodalConfig::default(),
             hooks: None,
             non_cli_excluded_tools: Arc::new(Vec::new()),
+            context_windows: Arc::new(HashMap::new()),
         });
 
         process_channel_message(
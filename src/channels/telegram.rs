@@ -478,7 +478,7 @@ impl TelegramChannel {
         if !telegram.allowed_users.iter().any(|u| u == &normalized) {
             telegram.allowed_users.push(normalized);
             config
-                .save()
+                .save_from(crate::config::ChangeSource::Chat)
                 .await
                 .context("Failed to persist Telegram allowlist to config.toml")?;
         }
@@ -1308,6 +1308,85 @@ Allowlist Telegram username (without '@') or numeric user ID.",
         })
     }
 
+    /// Turn an inline keyboard button press into a message for the agent.
+    /// The pressed button's callback data is quoted with the message it was attached to.
+    fn parse_callback_query(&self, update: &serde_json::Value) -> Option<ChannelMessage> {
+        let query = update.get("callback_query")?;
+        let data = query.get("data").and_then(serde_json::Value::as_str)?;
+        let query_id = query.get("id").and_then(serde_json::Value::as_str)?;
+
+        let (username, sender_id, sender_identity) = Self::extract_sender_info(query);
+        let mut identities = vec![username.as_str()];
+        if let Some(id) = sender_id.as_deref() {
+            identities.push(id);
+        }
+        if !self.is_any_user_allowed(identities.iter().copied()) {
+            return None;
+        }
+
+        let message = query.get("message")?;
+        let chat_id = message
+            .get("chat")
+            .and_then(|chat| chat.get("id"))
+            .and_then(serde_json::Value::as_i64)
+            .map(|id| id.to_string())?;
+        let message_id = message
+            .get("message_id")
+            .and_then(serde_json::Value::as_i64)
+            .unwrap_or(0);
+        let thread_id = message
+            .get("message_thread_id")
+            .and_then(serde_json::Value::as_i64)
+            .map(|id| id.to_string());
+        let reply_target = if let Some(ref tid) = thread_id {
+            format!("{}:{}", chat_id, tid)
+        } else {
+            chat_id.clone()
+        };
+
+        let original = message
+            .get("text")
+            .or_else(|| message.get("caption"))
+            .and_then(serde_json::Value::as_str)
+            .unwrap_or_default();
+        let content = if original.is_empty() {
+            format!("[Button pressed] {data}")
+        } else {
+            let quote = original
+                .lines()
+                .map(|line| format!("> {line}"))
+                .collect::<Vec<_>>()
+                .join("\n");
+            format!("{quote}\n\n[Button pressed] {data}")
+        };
+
+        Some(ChannelMessage {
+            id: format!("telegram_{chat_id}_{message_id}_cb_{query_id}"),
+            sender: sender_identity,
+            reply_target,
+            content,
+            channel: "telegram".to_string(),
+            timestamp: std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs(),
+            thread_ts: thread_id,
+        })
+    }
+
+    /// Acknowledge a button press so the client stops showing a loading spinner.
+    fn answer_callback_query_nonblocking(&self, query_id: String) {
+        let client = self.http_client();
+        let url = self.api_url("answerCallbackQuery");
+
+        tokio::spawn(async move {
+            let body = serde_json::json!({ "callback_query_id": query_id });
+            if let Err(err) = client.post(&url).json(&body).send().await {
+                tracing::warn!("Telegram: failed to answer callback query {query_id}: {err}");
+            }
+        });
+    }
+
     /// Download a Telegram photo by file_id, resize to fit within 1024px, and return as base64 data URI.
     async fn resolve_photo_data_uri(&self, file_id: &str) -> anyhow::Result<String> {
         use base64::Engine as _;
@@ -2479,7 +2558,7 @@ impl Channel for TelegramChannel {
             let probe = serde_json::json!({
                 "offset": offset,
                 "timeout": 0,
-                "allowed_updates": ["message"]
+                "allowed_updates": ["message", "callback_query"]
             });
             match self.http_client().post(&url).json(&probe).send().await {
                 Err(e) => {
@@ -2541,6 +2620,10 @@ impl Channel for TelegramChannel {
         tracing::debug!("Startup probe succeeded; entering main long-poll loop.");
 
         loop {
+            if let Some(delay) = crate::runtime::network::metered_poll_delay() {
+                tokio::time::sleep(delay).await;
+            }
+
             if self.mention_only {
                 let missing_username = self.bot_username.lock().is_none();
                 if missing_username {
@@ -2552,7 +2635,7 @@ impl Channel for TelegramChannel {
             let body = serde_json::json!({
                 "offset": offset,
                 "timeout": 30,
-                "allowed_updates": ["message"]
+                "allowed_updates": ["message", "callback_query"]
             });
 
             let resp = match self.http_client().post(&url).json(&body).send().await {
@@ -2613,6 +2696,20 @@ Ensure only one `zeroclaw` process is using this bot token."
                         offset = uid + 1;
                     }
 
+                    if let Some(query_id) = update
+                        .get("callback_query")
+                        .and_then(|query| query.get("id"))
+                        .and_then(serde_json::Value::as_str)
+                    {
+                        self.answer_callback_query_nonblocking(query_id.to_string());
+                        if let Some(msg) = self.parse_callback_query(update) {
+                            if tx.send(msg).await.is_err() {
+                                return Ok(());
+                            }
+                        }
+                        continue;
+                    }
+
                     let msg = if let Some(m) = self.parse_update_message(update) {
                         m
                     } else if let Some(m) = self.try_parse_voice_message(update).await {
@@ -3925,6 +4022,47 @@ mod tests {
     // extract_sender_info tests
     // ─────────────────────────────────────────────────────────────────────
 
+    #[test]
+    fn parse_callback_query_quotes_original_message() {
+        let ch = TelegramChannel::new("t".into(), vec!["alice".into()], false);
+        let update = serde_json::json!({
+            "update_id": 7,
+            "callback_query": {
+                "id": "cbq-1",
+                "from": { "id": 555, "username": "alice" },
+                "data": "approve",
+                "message": {
+                    "message_id": 33,
+                    "chat": { "id": -100200300 },
+                    "text": "Deploy v2 to prod?"
+                }
+            }
+        });
+
+        let msg = ch.parse_callback_query(&update).unwrap();
+        assert_eq!(msg.reply_target, "-100200300");
+        assert_eq!(msg.sender, "alice");
+        assert_eq!(msg.id, "telegram_-100200300_33_cb_cbq-1");
+        assert_eq!(
+            msg.content,
+            "> Deploy v2 to prod?\n\n[Button pressed] approve"
+        );
+    }
+
+    #[test]
+    fn parse_callback_query_respects_allowlist() {
+        let ch = TelegramChannel::new("t".into(), vec!["alice".into()], false);
+        let update = serde_json::json!({
+            "callback_query": {
+                "id": "cbq-2",
+                "from": { "id": 999, "username": "mallory" },
+                "data": "approve",
+                "message": { "message_id": 1, "chat": { "id": 1 } }
+            }
+        });
+        assert!(ch.parse_callback_query(&update).is_none());
+    }
+
     #[test]
     fn extract_sender_info_with_username() {
         let msg = serde_json::json!({
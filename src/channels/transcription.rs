@@ -1,10 +1,13 @@
 use anyhow::{bail, Context, Result};
 use reqwest::multipart::{Form, Part};
+use serde_json::json;
+use std::path::Path;
 
 use crate::config::TranscriptionConfig;
+use crate::runtime::host_bridge;
 
 /// Maximum upload size accepted by the Groq Whisper API (25 MB).
-const MAX_AUDIO_BYTES: usize = 25 * 1024 * 1024;
+pub(crate) const MAX_AUDIO_BYTES: usize = 25 * 1024 * 1024;
 
 /// Map file extension to MIME type for Whisper-compatible transcription APIs.
 fn mime_for_audio(extension: &str) -> Option<&'static str> {
@@ -105,6 +108,38 @@ pub async fn transcribe_audio(
     Ok(text)
 }
 
+/// Transcribe an audio file with the host app's on-device recognizer.
+///
+/// Only available inside the mobile app, where the host registers a bridge
+/// handling the `transcribe_audio` action with a file `path`.
+pub async fn transcribe_on_device(path: &Path, config: &TranscriptionConfig) -> Result<String> {
+    let payload = json!({
+        "path": path.to_string_lossy(),
+        "language": config.language,
+    });
+    let reply =
+        tokio::task::spawn_blocking(move || host_bridge::call_host("transcribe_audio", payload))
+            .await??;
+
+    if reply
+        .get("ok")
+        .and_then(serde_json::Value::as_bool)
+        .is_some_and(|ok| !ok)
+    {
+        let detail = reply
+            .get("error")
+            .and_then(serde_json::Value::as_str)
+            .unwrap_or("unknown error");
+        bail!("On-device transcription failed: {detail}");
+    }
+
+    Ok(reply
+        .get("text")
+        .and_then(serde_json::Value::as_str)
+        .context("On-device transcription reply missing 'text' field")?
+        .to_string())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,296 @@
+//! Audit trail of runtime config changes.
+//!
+//! Saves made for the Android app, the gateway, a chat command, the agent or
+//! `[config_sync]` go through [`Config::save_from`], which compares the file
+//! before and after and appends the changed keys to
+//! `state/config-changes.jsonl` in the workspace. Credentials are never
+//! written: their values show up as `"[redacted]"`. The log is append-only;
+//! [`load`] reads it back for `GET /api/config/changes`.
+
+use super::schema::Config;
+use super::sync::SECRET_KEYS;
+use anyhow::{Context, Result};
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use toml::{Table, Value};
+
+pub const LOG_REL_PATH: &str = "state/config-changes.jsonl";
+
+const REDACTED: &str = "[redacted]";
+
+static WRITE_LOCK: Mutex<()> = Mutex::new(());
+
+/// Who made a config change.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChangeSource {
+    /// The Android app, through the JNI bridge.
+    Jni,
+    /// A gateway API call.
+    Gateway,
+    /// A chat command such as `/profile` or `/bind`.
+    Chat,
+    /// A config tool called by the agent.
+    Agent,
+    /// A `[config_sync]` pull.
+    Sync,
+    /// A `zeroclaw` CLI command.
+    Cli,
+}
+
+impl std::str::FromStr for ChangeSource {
+    type Err = anyhow::Error;
+
+    fn from_str(value: &str) -> Result<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "jni" => Ok(Self::Jni),
+            "gateway" => Ok(Self::Gateway),
+            "chat" => Ok(Self::Chat),
+            "agent" => Ok(Self::Agent),
+            "sync" => Ok(Self::Sync),
+            "cli" => Ok(Self::Cli),
+            other => anyhow::bail!(
+                "Unknown change source '{other}' (expected jni, gateway, chat, agent, sync or cli)"
+            ),
+        }
+    }
+}
+
+/// One changed key. `old` is absent for added keys, `new` for removed ones.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ConfigChange {
+    /// Dotted key, e.g. `gateway.port`.
+    pub path: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub old: Option<serde_json::Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub new: Option<serde_json::Value>,
+}
+
+/// One save, as stored in the log.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ConfigChangeEntry {
+    pub timestamp: String,
+    pub source: ChangeSource,
+    pub changes: Vec<ConfigChange>,
+}
+
+pub fn log_path(workspace_dir: &Path) -> PathBuf {
+    workspace_dir.join(LOG_REL_PATH)
+}
+
+/// Keys that differ between `old` and `new`, in key order.
+pub fn diff(old: &Config, new: &Config) -> Result<Vec<ConfigChange>> {
+    let old = Table::try_from(old).context("Failed to serialize previous config")?;
+    let new = Table::try_from(new).context("Failed to serialize new config")?;
+    let mut changes = Vec::new();
+    diff_tables(&old, &new, "", &mut changes);
+    Ok(changes)
+}
+
+fn diff_tables(old: &Table, new: &Table, prefix: &str, changes: &mut Vec<ConfigChange>) {
+    let keys: BTreeSet<&String> = old.keys().chain(new.keys()).collect();
+    for key in keys {
+        let path = if prefix.is_empty() {
+            key.clone()
+        } else {
+            format!("{prefix}.{key}")
+        };
+        let secret = SECRET_KEYS.contains(&key.as_str());
+        match (old.get(key), new.get(key)) {
+            (Some(Value::Table(old)), Some(Value::Table(new))) if !secret => {
+                diff_tables(old, new, &path, changes);
+            }
+            (old, new) if old != new => changes.push(ConfigChange {
+                path,
+                old: old.map(|value| redacted(value, secret)),
+                new: new.map(|value| redacted(value, secret)),
+            }),
+            _ => {}
+        }
+    }
+}
+
+/// `value` as JSON with credentials below it replaced by [`REDACTED`].
+fn redacted(value: &Value, secret: bool) -> serde_json::Value {
+    if secret {
+        return serde_json::Value::String(REDACTED.into());
+    }
+    match value {
+        Value::Table(table) => serde_json::Value::Object(
+            table
+                .iter()
+                .map(|(key, value)| {
+                    let secret = SECRET_KEYS.contains(&key.as_str());
+                    (key.clone(), redacted(value, secret))
+                })
+                .collect(),
+        ),
+        Value::Array(items) => {
+            serde_json::Value::Array(items.iter().map(|item| redacted(item, false)).collect())
+        }
+        other => serde_json::to_value(other).unwrap_or(serde_json::Value::Null),
+    }
+}
+
+/// Append `changes` to the log. Saves that changed nothing are not logged.
+pub fn record(
+    workspace_dir: &Path,
+    source: ChangeSource,
+    changes: Vec<ConfigChange>,
+) -> Result<()> {
+    if changes.is_empty() {
+        return Ok(());
+    }
+    let entry = ConfigChangeEntry {
+        timestamp: Utc::now().to_rfc3339(),
+        source,
+        changes,
+    };
+    let line = serde_json::to_string(&entry)?;
+    let path = log_path(workspace_dir);
+
+    let _guard = WRITE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let mut options = OpenOptions::new();
+    options.create(true).append(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    let mut file = options
+        .open(&path)
+        .with_context(|| format!("Failed to open {}", path.display()))?;
+    writeln!(file, "{line}")?;
+    file.sync_data()?;
+
+    tracing::info!(
+        source = ?entry.source,
+        keys = entry.changes.len(),
+        "Config change recorded"
+    );
+    Ok(())
+}
+
+/// Up to `limit` entries, newest first. `source` keeps entries from one
+/// source; `path` keeps changes at or below a dotted key.
+pub fn load(
+    workspace_dir: &Path,
+    limit: usize,
+    source: Option<ChangeSource>,
+    path: Option<&str>,
+) -> Result<Vec<ConfigChangeEntry>> {
+    let log = log_path(workspace_dir);
+    if !log.exists() {
+        return Ok(Vec::new());
+    }
+    let raw =
+        fs::read_to_string(&log).with_context(|| format!("Failed to read {}", log.display()))?;
+    let path = path.map(str::trim).filter(|path| !path.is_empty());
+
+    let mut entries = Vec::new();
+    for line in raw.lines().rev() {
+        if entries.len() >= limit {
+            break;
+        }
+        let Ok(mut entry) = serde_json::from_str::<ConfigChangeEntry>(line) else {
+            continue;
+        };
+        if source.is_some_and(|source| source != entry.source) {
+            continue;
+        }
+        if let Some(path) = path {
+            entry.changes.retain(|change| {
+                change.path == path
+                    || change
+                        .path
+                        .strip_prefix(path)
+                        .is_some_and(|rest| rest.starts_with('.'))
+            });
+            if entry.changes.is_empty() {
+                continue;
+            }
+        }
+        entries.push(entry);
+    }
+    Ok(entries)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn diff_lists_changed_keys_and_redacts_credentials() {
+        let old = Config::default();
+        let mut new = old.clone();
+        new.gateway.port = 4242;
+        new.api_key = Some("sk-secret".into());
+        new.channels_config.telegram = Some(
+            toml::from_str("bot_token = \"123:secret\"\nallowed_users = [\"alice\"]\n").unwrap(),
+        );
+
+        let changes = diff(&old, &new).unwrap();
+        let paths: Vec<&str> = changes.iter().map(|c| c.path.as_str()).collect();
+        assert_eq!(
+            paths,
+            vec!["api_key", "channels_config.telegram", "gateway.port"]
+        );
+        assert_eq!(changes[0].old, None);
+        assert_eq!(changes[0].new, Some(serde_json::json!(REDACTED)));
+        let telegram = changes[1].new.as_ref().unwrap();
+        assert_eq!(telegram["bot_token"], REDACTED);
+        assert_eq!(telegram["allowed_users"], serde_json::json!(["alice"]));
+        assert_eq!(changes[2].old, Some(serde_json::json!(42617)));
+        assert_eq!(changes[2].new, Some(serde_json::json!(4242)));
+        assert!(!serde_json::to_string(&changes).unwrap().contains("secret"));
+    }
+
+    #[test]
+    fn log_is_appended_and_filtered_newest_first() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let change = |path: &str, value: i64| ConfigChange {
+            path: path.into(),
+            old: None,
+            new: Some(serde_json::json!(value)),
+        };
+        record(
+            tmp.path(),
+            ChangeSource::Jni,
+            vec![change("gateway.port", 1)],
+        )
+        .unwrap();
+        record(tmp.path(), ChangeSource::Gateway, Vec::new()).unwrap();
+        record(
+            tmp.path(),
+            ChangeSource::Chat,
+            vec![
+                change("agent.max_history_messages", 2),
+                change("gateway.host", 3),
+            ],
+        )
+        .unwrap();
+
+        let all = load(tmp.path(), 10, None, None).unwrap();
+        assert_eq!(all.len(), 2);
+        assert_eq!(all[0].source, ChangeSource::Chat);
+
+        let gateway = load(tmp.path(), 10, None, Some("gateway")).unwrap();
+        assert_eq!(gateway.len(), 2);
+        assert_eq!(gateway[0].changes.len(), 1);
+        assert_eq!(gateway[0].changes[0].path, "gateway.host");
+
+        let jni = load(tmp.path(), 10, Some(ChangeSource::Jni), None).unwrap();
+        assert_eq!(jni.len(), 1);
+        assert_eq!(load(tmp.path(), 1, None, None).unwrap().len(), 1);
+        assert!(load(tmp.path(), 10, None, Some("gate")).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,489 @@
+//! Config file diagnostics.
+//!
+//! [`check_config_str`] reports everything wrong with a `config.toml` in one
+//! pass — syntax errors, unknown keys, type errors, missing required fields,
+//! invalid values and conflicting settings — each with the line and column
+//! it points at. Loading uses the same parser, so a bad edit fails with the
+//! offending location instead of surfacing later inside whichever component
+//! first reads the value.
+
+use super::schema::Config;
+use crate::security::pairing::is_public_bind;
+use anyhow::{Context, Result};
+use serde::Serialize;
+use std::fmt;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use toml::de::{DeTable, DeValue};
+use toml::Spanned;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    /// The file cannot be loaded, or a component will refuse to start.
+    Error,
+    /// The file loads, but a setting is ignored or has no effect.
+    Warning,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DiagnosticKind {
+    Syntax,
+    UnknownKey,
+    TypeError,
+    MissingField,
+    InvalidValue,
+    Conflict,
+}
+
+/// One problem found in a config file.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ConfigDiagnostic {
+    pub severity: Severity,
+    pub kind: DiagnosticKind,
+    /// Dotted config path, e.g. `gateway.port` or `model_routes.0.hint`.
+    pub path: Option<String>,
+    /// 1-based line in the file.
+    pub line: Option<usize>,
+    /// 1-based column (in characters) in the file.
+    pub column: Option<usize>,
+    pub message: String,
+}
+
+impl fmt::Display for ConfigDiagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let (Some(line), Some(column)) = (self.line, self.column) {
+            write!(f, "{line}:{column}: ")?;
+        }
+        let severity = match self.severity {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+        };
+        write!(f, "{severity}: {}", self.message)
+    }
+}
+
+/// Result of checking a config file.
+#[derive(Debug, Clone, Serialize)]
+pub struct ConfigReport {
+    pub file: PathBuf,
+    pub valid: bool,
+    pub diagnostics: Vec<ConfigDiagnostic>,
+}
+
+impl ConfigReport {
+    fn new(file: &Path, diagnostics: Vec<ConfigDiagnostic>) -> Self {
+        let valid = !diagnostics
+            .iter()
+            .any(|diagnostic| diagnostic.severity == Severity::Error);
+        Self {
+            file: file.to_path_buf(),
+            valid,
+            diagnostics,
+        }
+    }
+
+    pub fn errors(&self) -> impl Iterator<Item = &ConfigDiagnostic> {
+        self.diagnostics
+            .iter()
+            .filter(|diagnostic| diagnostic.severity == Severity::Error)
+    }
+
+    pub fn warnings(&self) -> impl Iterator<Item = &ConfigDiagnostic> {
+        self.diagnostics
+            .iter()
+            .filter(|diagnostic| diagnostic.severity == Severity::Warning)
+    }
+}
+
+/// One diagnostic per line, each prefixed with `file:line:column`.
+impl fmt::Display for ConfigReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, diagnostic) in self.diagnostics.iter().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            write!(f, "{}:{diagnostic}", self.file.display())?;
+        }
+        Ok(())
+    }
+}
+
+/// Check config file contents without loading secrets or touching disk.
+pub fn check_config_str(contents: &str, file: &Path) -> ConfigReport {
+    let (config, mut diagnostics) = parse(contents);
+    if let Some(config) = config {
+        let doc = DeTable::parse_recoverable(contents).0;
+        let source = Source {
+            contents,
+            doc: doc.get_ref(),
+        };
+        if let Err(e) = config.validate() {
+            let message = format!("{e:#}");
+            let path = leading_path(&message);
+            diagnostics.push(source.diagnostic(
+                Severity::Error,
+                DiagnosticKind::InvalidValue,
+                path.filter(|path| source.locate(path).is_some()),
+                message,
+            ));
+        }
+        diagnostics.extend(conflicts(&config, &source));
+    }
+    ConfigReport::new(file, diagnostics)
+}
+
+/// Read and check the config file at `path`.
+pub async fn check_config_file(path: &Path) -> Result<ConfigReport> {
+    let contents = tokio::fs::read_to_string(path)
+        .await
+        .with_context(|| format!("Failed to read config file {}", path.display()))?;
+    Ok(check_config_str(&contents, path))
+}
+
+/// Parse config file contents, reporting syntax errors, type errors, missing
+/// fields and unknown keys. Returns the config when it deserialized.
+pub(crate) fn parse(contents: &str) -> (Option<Config>, Vec<ConfigDiagnostic>) {
+    let (doc, syntax_errors) = DeTable::parse_recoverable(contents);
+    let source = Source {
+        contents,
+        doc: doc.get_ref(),
+    };
+    if !syntax_errors.is_empty() {
+        let diagnostics = syntax_errors
+            .iter()
+            .map(|e| source.error_diagnostic(DiagnosticKind::Syntax, e))
+            .collect();
+        return (None, diagnostics);
+    }
+
+    let deserializer = match toml::de::Deserializer::parse(contents) {
+        Ok(deserializer) => deserializer,
+        Err(e) => {
+            return (
+                None,
+                vec![source.error_diagnostic(DiagnosticKind::Syntax, &e)],
+            )
+        }
+    };
+    let mut ignored = Vec::new();
+    let result: Result<Config, toml::de::Error> =
+        serde_ignored::deserialize(deserializer, |path| ignored.push(path.to_string()));
+
+    let mut diagnostics: Vec<ConfigDiagnostic> = ignored
+        .into_iter()
+        .map(|path| {
+            let message = format!(
+                "Unknown config key \"{path}\" is ignored; check for typos or deprecated options"
+            );
+            source.diagnostic(
+                Severity::Warning,
+                DiagnosticKind::UnknownKey,
+                Some(path),
+                message,
+            )
+        })
+        .collect();
+
+    match result {
+        Ok(config) => (Some(config), diagnostics),
+        Err(e) => {
+            diagnostics.push(source.error_diagnostic(error_kind(e.message()), &e));
+            (None, diagnostics)
+        }
+    }
+}
+
+fn error_kind(message: &str) -> DiagnosticKind {
+    if message.starts_with("missing field") {
+        DiagnosticKind::MissingField
+    } else if message.starts_with("unknown field") {
+        DiagnosticKind::UnknownKey
+    } else if message.starts_with("invalid type")
+        || message.starts_with("invalid length")
+        || message.starts_with("unknown variant")
+    {
+        DiagnosticKind::TypeError
+    } else {
+        DiagnosticKind::InvalidValue
+    }
+}
+
+/// Settings that are individually valid but do not work together.
+fn conflicts(config: &Config, source: &Source<'_>) -> Vec<ConfigDiagnostic> {
+    let mut diagnostics = Vec::new();
+
+    // With `bind_interface` set, the interface address replaces `host` and is
+    // checked when the gateway starts.
+    if is_public_bind(&config.gateway.host)
+        && config.gateway.bind_interface.is_none()
+        && config.tunnel.provider == "none"
+        && !config.gateway.allow_public_bind
+    {
+        diagnostics.push(source.diagnostic(
+            Severity::Error,
+            DiagnosticKind::Conflict,
+            Some("gateway.host".into()),
+            format!(
+                "gateway.host = \"{}\" is a public address, but no tunnel is configured and \
+                 gateway.allow_public_bind is false; the gateway will refuse to start",
+                config.gateway.host
+            ),
+        ));
+    }
+
+    if !config.secrets.encrypt && config.secrets.backend != super::SecretsBackend::Local {
+        diagnostics.push(source.diagnostic(
+            Severity::Warning,
+            DiagnosticKind::Conflict,
+            Some("secrets.backend".into()),
+            "secrets.backend has no effect while secrets.encrypt is false".into(),
+        ));
+    }
+
+    if let Some(active) = config.active_profile.as_deref() {
+        if !config.profiles.contains_key(active) {
+            diagnostics.push(source.diagnostic(
+                Severity::Warning,
+                DiagnosticKind::Conflict,
+                Some("active_profile".into()),
+                format!("active_profile \"{active}\" is not defined under [profiles]"),
+            ));
+        }
+    }
+
+    diagnostics
+}
+
+/// Leading config path of a validation message, e.g. `gateway.host` in
+/// "gateway.host must not be empty".
+fn leading_path(message: &str) -> Option<String> {
+    let path: String = message
+        .chars()
+        .take_while(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '.' | '[' | ']'))
+        .collect();
+    let path = path.trim_end_matches('.');
+    (!path.is_empty()).then(|| path.replace('[', ".").replace(']', ""))
+}
+
+type Entry<'a, 'i> = (String, &'a Spanned<DeValue<'i>>);
+
+fn children_of_table<'a, 'i>(table: &'a DeTable<'i>) -> Vec<Entry<'a, 'i>> {
+    table
+        .iter()
+        .map(|(key, value)| (key.get_ref().to_string(), value))
+        .collect()
+}
+
+/// Whether `value` or anything nested in it spans `offset`. A table's own
+/// span covers only its header, not the keys below it.
+fn spans_offset(value: &Spanned<DeValue<'_>>, offset: usize) -> bool {
+    if value.span().contains(&offset) {
+        return true;
+    }
+    match value.get_ref() {
+        DeValue::Table(table) => table
+            .iter()
+            .any(|(key, value)| key.span().contains(&offset) || spans_offset(value, offset)),
+        DeValue::Array(items) => items.iter().any(|item| spans_offset(item, offset)),
+        _ => false,
+    }
+}
+
+struct Source<'a> {
+    contents: &'a str,
+    doc: &'a DeTable<'a>,
+}
+
+impl Source<'_> {
+    fn diagnostic(
+        &self,
+        severity: Severity,
+        kind: DiagnosticKind,
+        path: Option<String>,
+        message: String,
+    ) -> ConfigDiagnostic {
+        let span = path.as_deref().and_then(|path| self.locate(path));
+        let (line, column) = span.map_or((None, None), |span| {
+            let (line, column) = self.line_column(span.start);
+            (Some(line), Some(column))
+        });
+        ConfigDiagnostic {
+            severity,
+            kind,
+            path,
+            line,
+            column,
+            message,
+        }
+    }
+
+    fn error_diagnostic(&self, kind: DiagnosticKind, error: &toml::de::Error) -> ConfigDiagnostic {
+        let span = error.span();
+        let (line, column) = span.clone().map_or((None, None), |span| {
+            let (line, column) = self.line_column(span.start);
+            (Some(line), Some(column))
+        });
+        ConfigDiagnostic {
+            severity: Severity::Error,
+            kind,
+            path: span.and_then(|span| self.path_at(span.start)),
+            line,
+            column,
+            message: error.message().to_string(),
+        }
+    }
+
+    /// Span of the deepest existing key along a dotted `path`.
+    fn locate(&self, path: &str) -> Option<Range<usize>> {
+        let mut value: Option<&DeValue<'_>> = None;
+        let mut table = Some(self.doc);
+        let mut found = None;
+        for segment in path.split('.') {
+            let next = if let Some(table) = table {
+                table
+                    .iter()
+                    .find(|(key, _)| key.get_ref() == segment)
+                    .map(|(key, value)| (key.span(), value))
+            } else if let Some(DeValue::Array(items)) = value {
+                segment
+                    .parse::<usize>()
+                    .ok()
+                    .and_then(|index| items.get(index))
+                    .map(|item| (item.span(), item))
+            } else {
+                None
+            };
+            let Some((span, next)) = next else {
+                break;
+            };
+            found = Some(span);
+            value = Some(next.get_ref());
+            table = next.get_ref().as_table();
+        }
+        found
+    }
+
+    /// Dotted path of the deepest key or value whose span contains `offset`.
+    fn path_at(&self, offset: usize) -> Option<String> {
+        let mut path = Vec::new();
+        let mut entries = children_of_table(self.doc);
+        while let Some((name, value)) = entries
+            .into_iter()
+            .find(|(_, value)| spans_offset(value, offset))
+        {
+            path.push(name);
+            entries = match value.get_ref() {
+                DeValue::Table(table) => children_of_table(table),
+                DeValue::Array(items) => items
+                    .iter()
+                    .enumerate()
+                    .map(|(i, item)| (i.to_string(), item))
+                    .collect(),
+                _ => break,
+            };
+        }
+        (!path.is_empty()).then(|| path.join("."))
+    }
+
+    fn line_column(&self, offset: usize) -> (usize, usize) {
+        let before = &self.contents[..offset.min(self.contents.len())];
+        let line = before.matches('\n').count() + 1;
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+        (line, before[line_start..].chars().count() + 1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check(contents: &str) -> ConfigReport {
+        check_config_str(contents, Path::new("config.toml"))
+    }
+
+    #[test]
+    fn reports_syntax_errors_with_location() {
+        let report = check("default_temperature = 0.5\n[gateway]\nport = \n");
+        assert!(!report.valid);
+        let error = report.errors().next().unwrap();
+        assert_eq!(error.kind, DiagnosticKind::Syntax);
+        assert_eq!(error.line, Some(3));
+    }
+
+    #[test]
+    fn reports_type_errors_and_missing_fields_with_path() {
+        let report = check("[gateway]\nhost = \"127.0.0.1\"\nport = \"eighty\"\n");
+        let error = report.errors().next().unwrap();
+        assert_eq!(error.kind, DiagnosticKind::TypeError);
+        assert_eq!(error.path.as_deref(), Some("gateway.port"));
+        assert_eq!((error.line, error.column), (Some(3), Some(8)));
+
+        let report = check("[[model_routes]]\nhint = \"fast\"\nprovider = \"groq\"\n");
+        let error = report.errors().next().unwrap();
+        assert_eq!(error.kind, DiagnosticKind::MissingField);
+        assert!(error.message.contains("model"), "{}", error.message);
+        assert_eq!(error.line, Some(1));
+    }
+
+    #[test]
+    fn unknown_keys_are_warnings_pointing_at_the_key() {
+        let report = check("default_temperature = 0.5\n\n[gateway]\nprot = 8080\n");
+        assert!(report.valid);
+        let warning = report.warnings().next().unwrap();
+        assert_eq!(warning.kind, DiagnosticKind::UnknownKey);
+        assert_eq!(warning.path.as_deref(), Some("gateway.prot"));
+        assert_eq!((warning.line, warning.column), (Some(4), Some(1)));
+        assert_eq!(
+            report.to_string(),
+            "config.toml:4:1: warning: Unknown config key \"gateway.prot\" is ignored; \
+             check for typos or deprecated options"
+        );
+    }
+
+    #[test]
+    fn reports_invalid_values_and_conflicts() {
+        let report = check("default_temperature = 0.7\n\n[scheduler]\nmax_tasks = 0\n");
+        let error = report.errors().next().unwrap();
+        assert_eq!(error.kind, DiagnosticKind::InvalidValue);
+        assert_eq!(error.path.as_deref(), Some("scheduler.max_tasks"));
+        assert_eq!(error.line, Some(4));
+
+        let report = check(
+            "default_temperature = 0.7\n\n[gateway]\nhost = \"0.0.0.0\"\n\n[secrets]\nencrypt = false\nbackend = \"keystore\"\n",
+        );
+        let kinds: Vec<_> = report
+            .diagnostics
+            .iter()
+            .map(|diagnostic| {
+                (
+                    diagnostic.severity,
+                    diagnostic.path.as_deref(),
+                    diagnostic.line,
+                )
+            })
+            .collect();
+        assert_eq!(
+            kinds,
+            vec![
+                (Severity::Error, Some("gateway.host"), Some(4)),
+                (Severity::Warning, Some("secrets.backend"), Some(8)),
+            ]
+        );
+        assert!(!report.valid);
+
+        let report = check(
+            "default_temperature = 0.7\n\n[gateway]\nhost = \"0.0.0.0\"\nbind_interface = \"tailnet\"\n",
+        );
+        assert!(report.valid, "{report}");
+    }
+
+    #[test]
+    fn default_config_is_valid() {
+        let contents = toml::to_string(&Config::default()).unwrap();
+        let report = check(&contents);
+        assert!(report.valid, "{report}");
+        assert!(report.diagnostics.is_empty(), "{report}");
+    }
+}
@@ -0,0 +1,124 @@
+//! JSON as an alternative to TOML for the config file.
+//!
+//! Host apps that generate the config programmatically can write
+//! `config.json` (or JSON content in `config.toml`). Loading converts JSON to
+//! the TOML layout first, so migrations, diagnostics and parsing behave the
+//! same; `save()` writes the file back in the format it was found in.
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+use std::path::Path;
+use toml::Table;
+
+/// File name looked up when `config.toml` does not exist.
+pub const JSON_FILE_NAME: &str = "config.json";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfigFormat {
+    Toml,
+    Json,
+}
+
+impl ConfigFormat {
+    /// Format of `contents` read from `path`: a `.json` extension or a
+    /// leading `{` means JSON.
+    pub fn detect(path: &Path, contents: &str) -> Self {
+        let json_extension = path
+            .extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("json"));
+        if json_extension || contents.trim_start().starts_with('{') {
+            Self::Json
+        } else {
+            Self::Toml
+        }
+    }
+
+    /// Format to save `path` in: that of the existing file, otherwise the
+    /// one its extension implies.
+    pub async fn for_existing(path: &Path) -> Self {
+        let contents = tokio::fs::read_to_string(path).await.unwrap_or_default();
+        Self::detect(path, &contents)
+    }
+
+    pub fn serialize<T: Serialize>(self, value: &T) -> Result<String> {
+        match self {
+            Self::Toml => toml::to_string_pretty(value).context("Failed to serialize config"),
+            Self::Json => {
+                let mut json =
+                    serde_json::to_string_pretty(value).context("Failed to serialize config")?;
+                json.push('\n');
+                Ok(json)
+            }
+        }
+    }
+}
+
+/// Convert a JSON config document to a TOML table. `null` values are
+/// dropped, as TOML has no equivalent and they mean "unset" in the config.
+pub fn json_to_table(contents: &str) -> Result<Table> {
+    let mut value: serde_json::Value =
+        serde_json::from_str(contents).context("Failed to parse JSON config")?;
+    strip_nulls(&mut value);
+    if !value.is_object() {
+        anyhow::bail!("JSON config must be an object");
+    }
+    Table::try_from(value).context("JSON config cannot be represented as TOML")
+}
+
+fn strip_nulls(value: &mut serde_json::Value) {
+    match value {
+        serde_json::Value::Object(map) => {
+            map.retain(|_, value| !value.is_null());
+            map.values_mut().for_each(strip_nulls);
+        }
+        serde_json::Value::Array(items) => {
+            items.retain(|value| !value.is_null());
+            items.iter_mut().for_each(strip_nulls);
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_format_by_extension_or_content() {
+        assert_eq!(
+            ConfigFormat::detect(Path::new("config.json"), ""),
+            ConfigFormat::Json
+        );
+        assert_eq!(
+            ConfigFormat::detect(Path::new("config.toml"), "  {\"a\": 1}"),
+            ConfigFormat::Json
+        );
+        assert_eq!(
+            ConfigFormat::detect(Path::new("config.toml"), "default_temperature = 0.7\n"),
+            ConfigFormat::Toml
+        );
+    }
+
+    #[test]
+    fn json_converts_to_toml_without_nulls() {
+        let table = json_to_table(
+            r#"{
+  "default_temperature": 1,
+  "api_key": null,
+  "autonomy": { "level": "full", "allowed_commands": ["ls", null] }
+}"#,
+        )
+        .unwrap();
+        assert_eq!(table["default_temperature"].as_integer(), Some(1));
+        assert!(!table.contains_key("api_key"));
+        assert_eq!(
+            table["autonomy"]["allowed_commands"]
+                .as_array()
+                .unwrap()
+                .len(),
+            1
+        );
+        assert!(json_to_table("[1, 2]").is_err());
+        assert!(json_to_table("{ not json").is_err());
+    }
+}
@@ -0,0 +1,239 @@
+//! `[runtime.low_resource]` preset for phones with 3-4 GB of RAM.
+//!
+//! On load, [`apply`] decides whether the preset is active (`mode = "on"`, or
+//! `"auto"` with total RAM at or below `max_ram_mb`) and then shrinks history,
+//! concurrency and the local model context, turns off heavy tools and swaps in
+//! the smaller default model. The replaced values are remembered in
+//! [`Config::low_resource_overrides`] so [`restore`] can put them back before
+//! the config is saved.
+
+use super::schema::{Config, LowResourceConfig, LowResourceMode, ToolPolicyConfig};
+
+/// Settings the preset changes, before and after it ran.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PresetOverrides {
+    original: Tuned,
+    applied: Tuned,
+    /// `[tools.<name>]` entries the preset added.
+    disabled_tools: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Tuned {
+    default_model: Option<String>,
+    max_history_messages: usize,
+    compact_context: bool,
+    parallel_tools: bool,
+    scheduler_max_concurrent: usize,
+    local_llm_context_size: u32,
+}
+
+impl Tuned {
+    fn of(config: &Config) -> Self {
+        Self {
+            default_model: config.default_model.clone(),
+            max_history_messages: config.agent.max_history_messages,
+            compact_context: config.agent.compact_context,
+            parallel_tools: config.agent.parallel_tools,
+            scheduler_max_concurrent: config.scheduler.max_concurrent,
+            local_llm_context_size: config.local_llm.context_size,
+        }
+    }
+}
+
+/// Apply the preset to a freshly loaded config when it is active on this device.
+pub fn apply(config: &mut Config) {
+    let settings = config.runtime.low_resource.clone();
+    let ram_mb = total_ram_mb();
+    if !active_for(&settings, ram_mb) {
+        return;
+    }
+
+    let original = Tuned::of(config);
+    if let Some(model) = &settings.default_model {
+        config.default_model = Some(model.clone());
+    }
+    let agent = &mut config.agent;
+    agent.max_history_messages = agent
+        .max_history_messages
+        .min(settings.max_history_messages);
+    agent.compact_context = true;
+    agent.parallel_tools = false;
+    config.scheduler.max_concurrent = config
+        .scheduler
+        .max_concurrent
+        .min(settings.max_concurrent_tasks);
+    config.local_llm.context_size = config
+        .local_llm
+        .context_size
+        .min(settings.local_llm_context_size);
+
+    let mut disabled_tools = Vec::new();
+    for tool in &settings.disabled_tools {
+        if !config.tools.contains_key(tool) {
+            config.tools.insert(tool.clone(), disabled_policy());
+            disabled_tools.push(tool.clone());
+        }
+    }
+
+    tracing::info!(
+        ram_mb,
+        "Low-resource preset active; disabled tools: {}",
+        disabled_tools.join(", ")
+    );
+    config.low_resource_overrides = Some(PresetOverrides {
+        applied: Tuned::of(config),
+        original,
+        disabled_tools,
+    });
+}
+
+/// Undo [`apply`] on a config about to be saved. Settings changed since the
+/// preset ran keep their new value.
+pub fn restore(config: &mut Config) {
+    let Some(overrides) = config.low_resource_overrides.take() else {
+        return;
+    };
+    let PresetOverrides {
+        original,
+        applied,
+        disabled_tools,
+    } = overrides;
+
+    if config.default_model == applied.default_model {
+        config.default_model = original.default_model;
+    }
+    let agent = &mut config.agent;
+    if agent.max_history_messages == applied.max_history_messages {
+        agent.max_history_messages = original.max_history_messages;
+    }
+    if agent.compact_context == applied.compact_context {
+        agent.compact_context = original.compact_context;
+    }
+    if agent.parallel_tools == applied.parallel_tools {
+        agent.parallel_tools = original.parallel_tools;
+    }
+    if config.scheduler.max_concurrent == applied.scheduler_max_concurrent {
+        config.scheduler.max_concurrent = original.scheduler_max_concurrent;
+    }
+    if config.local_llm.context_size == applied.local_llm_context_size {
+        config.local_llm.context_size = original.local_llm_context_size;
+    }
+    let untouched = disabled_policy();
+    for tool in disabled_tools {
+        if config.tools.get(&tool).is_some_and(|policy| {
+            !policy.enabled
+                && policy.name == untouched.name
+                && policy.description == untouched.description
+                && policy.params.is_empty()
+        }) {
+            config.tools.remove(&tool);
+        }
+    }
+}
+
+fn disabled_policy() -> ToolPolicyConfig {
+    ToolPolicyConfig {
+        enabled: false,
+        ..ToolPolicyConfig::default()
+    }
+}
+
+fn active_for(settings: &LowResourceConfig, total_ram_mb: Option<u64>) -> bool {
+    match settings.mode {
+        LowResourceMode::On => true,
+        LowResourceMode::Off => false,
+        LowResourceMode::Auto => total_ram_mb.is_some_and(|ram| ram <= settings.max_ram_mb),
+    }
+}
+
+/// Total RAM from `/proc/meminfo` (Linux and Android); `None` elsewhere.
+fn total_ram_mb() -> Option<u64> {
+    let meminfo = std::fs::read_to_string("/proc/meminfo").ok()?;
+    parse_mem_total_mb(&meminfo)
+}
+
+fn parse_mem_total_mb(meminfo: &str) -> Option<u64> {
+    let kib = meminfo
+        .lines()
+        .find_map(|line| line.strip_prefix("MemTotal:"))?
+        .trim()
+        .trim_end_matches("kB")
+        .trim()
+        .parse::<u64>()
+        .ok()?;
+    Some(kib / 1024)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn preset(mode: LowResourceMode) -> LowResourceConfig {
+        LowResourceConfig {
+            mode,
+            default_model: Some("fast".into()),
+            ..LowResourceConfig::default()
+        }
+    }
+
+    #[test]
+    fn auto_mode_follows_total_ram() {
+        let auto = preset(LowResourceMode::Auto);
+        assert!(active_for(&auto, Some(3_700)));
+        assert!(!active_for(&auto, Some(7_800)));
+        assert!(!active_for(&auto, None));
+        assert!(active_for(&preset(LowResourceMode::On), Some(16_000)));
+        assert!(!active_for(&preset(LowResourceMode::Off), Some(2_000)));
+
+        let meminfo = "MemTotal:        3809476 kB\nMemFree:          180744 kB\n";
+        assert_eq!(parse_mem_total_mb(meminfo), Some(3_720));
+        assert_eq!(parse_mem_total_mb("MemFree: 1 kB\n"), None);
+    }
+
+    #[test]
+    fn apply_caps_settings_and_restore_undoes_them() {
+        let mut config = Config::default();
+        config.runtime.low_resource = preset(LowResourceMode::On);
+        config.default_model = Some("smart".into());
+        config.agent.parallel_tools = true;
+        config.tools.insert(
+            "delegate".into(),
+            ToolPolicyConfig {
+                name: Some("ask_expert".into()),
+                ..ToolPolicyConfig::default()
+            },
+        );
+        let before = config.clone();
+
+        apply(&mut config);
+        assert_eq!(config.default_model.as_deref(), Some("fast"));
+        assert_eq!(config.agent.max_history_messages, 20);
+        assert!(config.agent.compact_context);
+        assert!(!config.agent.parallel_tools);
+        assert_eq!(config.scheduler.max_concurrent, 1);
+        assert_eq!(config.local_llm.context_size, 2048);
+        assert!(!config.tools["browser"].enabled);
+        // An explicit [tools.delegate] entry wins over the preset.
+        assert!(config.tools["delegate"].enabled);
+
+        // A change made while the preset is active survives the restore.
+        config.agent.max_tool_iterations = 4;
+        restore(&mut config);
+        assert!(config.low_resource_overrides.is_none());
+        assert_eq!(config.default_model, before.default_model);
+        assert_eq!(
+            config.agent.max_history_messages,
+            before.agent.max_history_messages
+        );
+        assert!(config.agent.parallel_tools);
+        assert_eq!(
+            config.scheduler.max_concurrent,
+            before.scheduler.max_concurrent
+        );
+        assert_eq!(config.local_llm.context_size, before.local_llm.context_size);
+        assert!(!config.tools.contains_key("browser"));
+        assert!(config.tools.contains_key("delegate"));
+        assert_eq!(config.agent.max_tool_iterations, 4);
+    }
+}
@@ -0,0 +1,288 @@
+//! Versioned upgrades for `config.toml`.
+//!
+//! Every config carries a top-level `schema_version` (files written before it
+//! existed are version 0). On load, [`upgrade_file`] runs the migrations
+//! between the file's version and [`CURRENT_SCHEMA_VERSION`] over the raw TOML
+//! table, so renamed keys and moved sections keep working after an app
+//! update. Before rewriting the file it copies the original to
+//! `config.toml.v<old>.bak`.
+
+use anyhow::{Context, Result};
+use std::path::{Path, PathBuf};
+use tokio::fs;
+use toml::{Table, Value};
+
+/// Schema version written by this build.
+pub const CURRENT_SCHEMA_VERSION: u32 = 1;
+
+/// One change within a migration; returns whether it modified the table.
+type Step = (&'static str, fn(&mut Table) -> bool);
+
+struct Migration {
+    /// Version the config has after this migration.
+    to: u32,
+    steps: &'static [Step],
+}
+
+const MIGRATIONS: &[Migration] = &[Migration {
+    to: 1,
+    steps: &[
+        ("model_provider -> default_provider", |config| {
+            rename_key(config, "model_provider", "default_provider")
+        }),
+        ("model -> default_model", |config| {
+            rename_key(config, "model", "default_model")
+        }),
+        ("[channels] -> [channels_config]", |config| {
+            move_section(config, "channels", "channels_config")
+        }),
+        ("composio.enable -> composio.enabled", |config| {
+            table_at(config, &["composio"])
+                .is_some_and(|composio| rename_key(composio, "enable", "enabled"))
+        }),
+        (
+            "heartbeat.channel/recipient -> heartbeat.target/to",
+            |config| {
+                table_at(config, &["heartbeat"]).is_some_and(|heartbeat| {
+                    rename_key(heartbeat, "channel", "target")
+                        | rename_key(heartbeat, "recipient", "to")
+                })
+            },
+        ),
+        ("storage.provider.config.dbURL -> db_url", |config| {
+            table_at(config, &["storage", "provider", "config"]).is_some_and(|storage| {
+                ["dbURL", "database_url", "databaseUrl"]
+                    .map(|key| rename_key(storage, key, "db_url"))
+                    .contains(&true)
+            })
+        }),
+    ],
+}];
+
+/// Version recorded in a raw config table; 0 when the key is missing.
+pub fn schema_version(config: &Table) -> u32 {
+    config
+        .get("schema_version")
+        .and_then(Value::as_integer)
+        .and_then(|version| u32::try_from(version).ok())
+        .unwrap_or(0)
+}
+
+/// Upgrade a raw config table to [`CURRENT_SCHEMA_VERSION`] in place.
+///
+/// Returns the steps that changed something. Tables from a newer build are
+/// left untouched.
+pub fn migrate(config: &mut Table) -> Vec<&'static str> {
+    let version = schema_version(config);
+    if version >= CURRENT_SCHEMA_VERSION {
+        return Vec::new();
+    }
+
+    let mut applied = Vec::new();
+    for migration in MIGRATIONS.iter().filter(|m| m.to > version) {
+        for (name, step) in migration.steps {
+            if step(config) {
+                applied.push(*name);
+            }
+        }
+    }
+    config.insert(
+        "schema_version".into(),
+        Value::Integer(CURRENT_SCHEMA_VERSION.into()),
+    );
+    applied
+}
+
+/// Where the pre-migration copy of `config_path` at `version` is kept.
+pub fn backup_path(config_path: &Path, version: u32) -> PathBuf {
+    let file_name = config_path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or("config.toml");
+    config_path.with_file_name(format!("{file_name}.v{version}.bak"))
+}
+
+/// Migrate the contents of `config_path` and return the upgraded TOML.
+///
+/// The file is only rewritten when a migration changed a key, after the
+/// original is copied to [`backup_path`]. If the rewrite fails (read-only
+/// storage, for instance) the upgraded config is still returned so the load
+/// goes ahead. Contents that are not valid TOML are returned as-is for the
+/// normal parser to report.
+pub async fn upgrade_file(config_path: &Path, contents: String) -> Result<String> {
+    let Ok(mut config) = toml::from_str::<Table>(&contents) else {
+        return Ok(contents);
+    };
+    let version = schema_version(&config);
+    if version > CURRENT_SCHEMA_VERSION {
+        tracing::warn!(
+            "{} has schema_version {version}, newer than this build supports \
+             ({CURRENT_SCHEMA_VERSION}); loading it without migration",
+            config_path.display()
+        );
+        return Ok(contents);
+    }
+
+    let applied = migrate(&mut config);
+    if applied.is_empty() {
+        return Ok(contents);
+    }
+    let upgraded = toml::to_string(&config).context("Failed to serialize migrated config")?;
+
+    let backup = backup_path(config_path, version);
+    if let Err(e) = write_upgraded(config_path, &backup, &upgraded).await {
+        tracing::warn!(
+            "Config migrated in memory but {} could not be rewritten: {e:#}",
+            config_path.display()
+        );
+    } else {
+        tracing::info!(
+            "Migrated {} from schema_version {version} to {CURRENT_SCHEMA_VERSION} ({}); \
+             previous file saved as {}",
+            config_path.display(),
+            applied.join(", "),
+            backup.display()
+        );
+    }
+    Ok(upgraded)
+}
+
+async fn write_upgraded(config_path: &Path, backup: &Path, upgraded: &str) -> Result<()> {
+    // Keep the oldest copy if an earlier upgrade already left one behind.
+    if !backup.exists() {
+        fs::copy(config_path, backup)
+            .await
+            .with_context(|| format!("Failed to back up config to {}", backup.display()))?;
+    }
+
+    // Copy first so the temporary file keeps the config's permissions.
+    let temp_path = config_path.with_extension("toml.migrate.tmp");
+    fs::copy(config_path, &temp_path)
+        .await
+        .context("Failed to create temporary config file")?;
+    fs::write(&temp_path, upgraded)
+        .await
+        .context("Failed to write migrated config")?;
+    if let Err(e) = fs::rename(&temp_path, config_path).await {
+        let _ = fs::remove_file(&temp_path).await;
+        anyhow::bail!("Failed to replace config file: {e}");
+    }
+    Ok(())
+}
+
+fn rename_key(table: &mut Table, from: &str, to: &str) -> bool {
+    let Some(value) = table.remove(from) else {
+        return false;
+    };
+    // An explicit new-style key wins over its legacy spelling.
+    table.entry(to).or_insert(value);
+    true
+}
+
+/// Move the entries of table `from` under `to`, keeping existing ones.
+fn move_section(config: &mut Table, from: &str, to: &str) -> bool {
+    if !config.get(from).is_some_and(Value::is_table) {
+        return false;
+    }
+    let Some(Value::Table(section)) = config.remove(from) else {
+        return false;
+    };
+    let target = config
+        .entry(to)
+        .or_insert_with(|| Value::Table(Table::new()));
+    if let Value::Table(target) = target {
+        for (key, value) in section {
+            target.entry(key).or_insert(value);
+        }
+    }
+    true
+}
+
+fn table_at<'a>(config: &'a mut Table, path: &[&str]) -> Option<&'a mut Table> {
+    path.iter()
+        .try_fold(config, |table, key| table.get_mut(*key)?.as_table_mut())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LEGACY: &str = r#"
+model_provider = "ollama"
+model = "llama3.2"
+default_temperature = 0.5
+
+[channels.telegram]
+bot_token = "123:abc"
+allowed_users = ["alice"]
+
+[composio]
+enable = true
+
+[heartbeat]
+enabled = true
+interval_minutes = 30
+channel = "telegram"
+recipient = "42"
+"#;
+
+    #[test]
+    fn migrates_legacy_keys_and_sections() {
+        let mut config: Table = toml::from_str(LEGACY).unwrap();
+        assert_eq!(schema_version(&config), 0);
+
+        let applied = migrate(&mut config);
+        assert_eq!(applied.len(), 5);
+        assert_eq!(schema_version(&config), CURRENT_SCHEMA_VERSION);
+        assert_eq!(config["default_provider"].as_str(), Some("ollama"));
+        assert_eq!(config["default_model"].as_str(), Some("llama3.2"));
+        assert!(config.get("channels").is_none());
+        assert_eq!(
+            config["channels_config"]["telegram"]["bot_token"].as_str(),
+            Some("123:abc")
+        );
+        assert_eq!(config["composio"]["enabled"].as_bool(), Some(true));
+        assert_eq!(config["heartbeat"]["target"].as_str(), Some("telegram"));
+        assert_eq!(config["heartbeat"]["to"].as_str(), Some("42"));
+
+        // Already current: nothing to do.
+        assert!(migrate(&mut config).is_empty());
+    }
+
+    #[test]
+    fn new_style_keys_win_and_newer_files_are_left_alone() {
+        let mut config: Table =
+            toml::from_str("model = \"old\"\ndefault_model = \"new\"\n").unwrap();
+        assert_eq!(migrate(&mut config), vec!["model -> default_model"]);
+        assert_eq!(config["default_model"].as_str(), Some("new"));
+
+        let mut config: Table = toml::from_str("schema_version = 99\nmodel = \"x\"\n").unwrap();
+        assert!(migrate(&mut config).is_empty());
+        assert!(config.contains_key("model"));
+    }
+
+    #[tokio::test]
+    async fn upgrade_file_backs_up_and_rewrites_only_when_needed() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.toml");
+
+        fs::write(&path, "default_temperature = 0.5\n")
+            .await
+            .unwrap();
+        let contents = fs::read_to_string(&path).await.unwrap();
+        assert_eq!(
+            upgrade_file(&path, contents.clone()).await.unwrap(),
+            contents
+        );
+        assert!(!backup_path(&path, 0).exists());
+
+        fs::write(&path, LEGACY).await.unwrap();
+        let upgraded = upgrade_file(&path, LEGACY.to_string()).await.unwrap();
+        assert_eq!(fs::read_to_string(&path).await.unwrap(), upgraded);
+        assert!(upgraded.contains("schema_version = 1"));
+        assert_eq!(
+            fs::read_to_string(backup_path(&path, 0)).await.unwrap(),
+            LEGACY
+        );
+    }
+}
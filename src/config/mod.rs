@@ -1,24 +1,42 @@
+pub mod audit;
+pub mod diagnostics;
+pub mod format;
+pub mod low_resource;
+pub mod migrations;
 pub mod schema;
+pub mod secret_ref;
+pub mod sync;
 pub mod traits;
+pub mod transfer;
 
+pub use audit::ChangeSource;
+pub use diagnostics::{check_config_file, check_config_str, ConfigDiagnostic, ConfigReport};
 #[allow(unused_imports)]
 pub use schema::{
-    apply_runtime_proxy_to_builder, build_runtime_proxy_client,
-    build_runtime_proxy_client_with_timeouts, runtime_proxy_config, set_runtime_proxy_config,
-    AgentConfig, AndroidBridgeConfig, AndroidCapabilitiesConfig, AndroidConfig,
-    AndroidDistribution, AndroidPolicyConfig, AndroidRuntimeConfig, AuditConfig, AutonomyConfig,
-    BrowserComputerUseConfig, BrowserConfig, BuiltinHooksConfig, ChannelsConfig,
-    ClassificationRule, ComposioConfig, Config, CostConfig, CronConfig, DelegateAgentConfig,
-    DiscordConfig, DockerRuntimeConfig, EmbeddingRouteConfig, EstopConfig, FeishuConfig,
-    GatewayConfig, HardwareConfig, HardwareTransport, HeartbeatConfig, HooksConfig,
-    HttpRequestConfig, IMessageConfig, IdentityConfig, LarkConfig, MatrixConfig, MemoryConfig,
-    ModelRouteConfig, MultimodalConfig, NextcloudTalkConfig, ObservabilityConfig, OtpConfig,
-    OtpMethod, PeripheralBoardConfig, PeripheralsConfig, ProxyConfig, ProxyScope, QdrantConfig,
-    QueryClassificationConfig, ReliabilityConfig, ResourceLimitsConfig, RuntimeConfig,
-    SandboxBackend, SandboxConfig, SchedulerConfig, SecretsConfig, SecurityConfig, SkillsConfig,
+    apply_runtime_provider_http_to_builder, apply_runtime_proxy_to_builder,
+    build_provider_client_with_timeouts, build_runtime_proxy_client,
+    build_runtime_proxy_client_with_timeouts, runtime_provider_http_config, runtime_proxy_config,
+    set_runtime_provider_http_config, set_runtime_proxy_config, AgentConfig, AndroidBridgeConfig,
+    AndroidCapabilitiesConfig, AndroidConfig, AndroidDistribution, AndroidPolicyConfig,
+    AndroidRuntimeConfig, AndroidShizukuConfig, AndroidStorageLocation, AuditConfig,
+    AutonomyConfig, AzureOpenAiConfig, BrowserComputerUseConfig, BrowserConfig, BuiltinHooksConfig,
+    ChannelOverrideConfig, ChannelsConfig, ClassificationRule, ComposioConfig, Config,
+    ConfigProfile, ConfigSyncConfig, CostConfig, CronConfig, DelegateAgentConfig, DiscordConfig,
+    DockerRuntimeConfig, EmbeddingRouteConfig, EstopConfig, EvalConfig, FeishuConfig,
+    GatewayConfig, GatewayHookAction, GatewayHookConfig, GatewayTlsConfig, GatewayUserConfig,
+    GeminiConfig, GeminiSafetySetting, HardwareConfig, HardwareTransport, HeartbeatConfig,
+    HomeAssistantConfig, HooksConfig, HttpRequestConfig, IMessageConfig, IdentityConfig,
+    LarkConfig, LocalLlmConfig, LowResourceConfig, LowResourceMode, MatrixConfig, McpConfig,
+    McpServerConfig, MemoryConfig, ModelRouteConfig, MultimodalConfig, NextcloudTalkConfig,
+    ObservabilityConfig, OpenRouterConfig, OtpConfig, OtpMethod, PeripheralBoardConfig,
+    PeripheralsConfig, PluginsConfig, ProviderHttpConfig, ProviderRateLimitConfig,
+    ProviderRetryPolicyConfig, ProxyConfig, ProxyScope, QdrantConfig, QueryClassificationConfig,
+    ReliabilityConfig, ResourceLimitsConfig, RuntimeConfig, RuntimeNetworkConfig, SandboxBackend,
+    SandboxConfig, SchedulerConfig, SecretsBackend, SecretsConfig, SecurityConfig, SkillsConfig,
     SkillsPromptInjectionMode, SlackConfig, StorageConfig, StorageProviderConfig,
-    StorageProviderSection, StreamMode, TelegramConfig, TranscriptionConfig, TunnelConfig,
-    WebFetchConfig, WebSearchConfig, WebhookConfig,
+    StorageProviderSection, StreamMode, TelegramConfig, ToolParamConstraint, ToolPolicyConfig,
+    TranscriptionConfig, TunnelConfig, WebFetchConfig, WebSearchConfig, WebhookConfig,
+    DEFAULT_PROFILE_NAME,
 };
 
 pub fn name_and_presence<T: traits::ChannelConfig>(channel: &Option<T>) -> (&'static str, bool) {
@@ -47,6 +65,8 @@ mod tests {
             draft_update_interval_ms: 1000,
             interrupt_on_new_message: false,
             mention_only: false,
+            notify_chat_id: None,
+            notify_targets: Default::default(),
         };
 
         let discord = DiscordConfig {
@@ -55,6 +75,8 @@ mod tests {
             allowed_users: vec![],
             listen_to_bots: false,
             mention_only: false,
+            notify_chat_id: None,
+            notify_webhook_url: None,
         };
 
         let lark = LarkConfig {
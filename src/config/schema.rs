@@ -1,3 +1,5 @@
+use crate::config::audit::ChangeSource;
+use crate::config::format::ConfigFormat;
 use crate::config::traits::ChannelConfig;
 use crate::providers::{is_glm_alias, is_zai_alias};
 use crate::security::{AutonomyLevel, DomainMatcher};
@@ -15,10 +17,12 @@ use tokio::io::AsyncWriteExt;
 
 const SUPPORTED_PROXY_SERVICE_KEYS: &[&str] = &[
     "provider.anthropic",
+    "provider.azure_openai",
     "provider.compatible",
     "provider.copilot",
     "provider.gemini",
     "provider.glm",
+    "provider.mistral",
     "provider.ollama",
     "provider.openai",
     "provider.openrouter",
@@ -38,6 +42,7 @@ const SUPPORTED_PROXY_SERVICE_KEYS: &[&str] = &[
     "tool.browser",
     "tool.composio",
     "tool.http_request",
+    "tool.ntfy",
     "tool.pushover",
     "memory.embeddings",
     "tunnel.custom",
@@ -56,6 +61,8 @@ const SUPPORTED_PROXY_SERVICE_SELECTORS: &[&str] = &[
 static RUNTIME_PROXY_CONFIG: OnceLock<RwLock<ProxyConfig>> = OnceLock::new();
 static RUNTIME_PROXY_CLIENT_CACHE: OnceLock<RwLock<HashMap<String, reqwest::Client>>> =
     OnceLock::new();
+static RUNTIME_PROVIDER_HTTP_CONFIG: OnceLock<RwLock<HashMap<String, ProviderHttpConfig>>> =
+    OnceLock::new();
 
 // ── Top-level config ──────────────────────────────────────────────
 
@@ -70,6 +77,18 @@ pub struct Config {
     /// Path to config.toml - computed from home, not serialized
     #[serde(skip)]
     pub config_path: PathBuf,
+    /// `${VAR}` / `file://` references credentials were loaded from, keyed by
+    /// the resolved value, so `save()` writes the reference back - not serialized
+    #[serde(skip)]
+    pub secret_refs: HashMap<String, String>,
+    /// Settings the active `[runtime.low_resource]` preset replaced, so
+    /// `save()` writes the user's values back - not serialized
+    #[serde(skip)]
+    pub low_resource_overrides: Option<super::low_resource::PresetOverrides>,
+    /// Config layout version. Older files are upgraded on load by
+    /// [`super::migrations`]; missing means a file from before versioning.
+    #[serde(default = "default_schema_version")]
+    pub schema_version: u32,
     /// API key for the selected provider. Overridden by `ZEROCLAW_API_KEY` or `API_KEY` env vars.
     pub api_key: Option<String>,
     /// Base URL override for provider API (e.g. "http://10.0.0.1:11434" for remote Ollama)
@@ -83,6 +102,10 @@ pub struct Config {
     /// Optional named provider profiles keyed by id (Codex app-server compatible layout).
     #[serde(default)]
     pub model_providers: HashMap<String, ModelProviderConfig>,
+    /// Short names for models (`fast = "llama-3.1-8b"`), accepted wherever a model
+    /// name is: `default_model`, `/model`, cron jobs, delegate agents and the mobile app.
+    #[serde(default)]
+    pub model_aliases: HashMap<String, String>,
     /// Default model temperature (0.0–2.0). Default: `0.7`.
     pub default_temperature: f64,
 
@@ -171,6 +194,10 @@ pub struct Config {
     #[serde(default)]
     pub secrets: SecretsConfig,
 
+    /// Remote config sync (`[config_sync]`).
+    #[serde(default)]
+    pub config_sync: ConfigSyncConfig,
+
     /// Browser automation configuration (`[browser]`).
     #[serde(default)]
     pub browser: BrowserConfig,
@@ -195,6 +222,10 @@ pub struct Config {
     #[serde(default)]
     pub proxy: ProxyConfig,
 
+    /// Per-provider extra headers and proxy (`[provider_http.<provider>]`).
+    #[serde(default)]
+    pub provider_http: HashMap<String, ProviderHttpConfig>,
+
     /// Identity format configuration: OpenClaw or AIEOS (`[identity]`).
     #[serde(default)]
     pub identity: IdentityConfig,
@@ -222,6 +253,50 @@ pub struct Config {
     /// Voice transcription configuration (Whisper API via Groq).
     #[serde(default)]
     pub transcription: TranscriptionConfig,
+
+    /// MCP (Model Context Protocol) servers whose tools are imported (`[mcp]`).
+    #[serde(default)]
+    pub mcp: McpConfig,
+
+    /// Per-tool enable/rename/parameter policies (`[tools.<name>]`).
+    #[serde(default)]
+    pub tools: HashMap<String, ToolPolicyConfig>,
+
+    /// Third-party WASM tool plugins loaded from the workspace (`[plugins]`).
+    #[serde(default)]
+    pub plugins: PluginsConfig,
+
+    /// Home Assistant smart-home integration (`[home_assistant]`).
+    #[serde(default)]
+    pub home_assistant: HomeAssistantConfig,
+
+    /// Sandboxed script evaluation tool limits (`[eval]`).
+    #[serde(default)]
+    pub eval: EvalConfig,
+
+    /// Google Gemini provider options (`[gemini]`).
+    #[serde(default)]
+    pub gemini: GeminiConfig,
+
+    /// Azure OpenAI provider options (`[azure_openai]`).
+    #[serde(default)]
+    pub azure_openai: AzureOpenAiConfig,
+
+    /// OpenRouter provider routing preferences (`[openrouter]`).
+    #[serde(default)]
+    pub openrouter: OpenRouterConfig,
+
+    /// On-device llama.cpp inference (`[local_llm]`).
+    #[serde(default)]
+    pub local_llm: LocalLlmConfig,
+
+    /// Named profiles (`[profiles.<name>]`) that can be switched at runtime.
+    #[serde(default)]
+    pub profiles: HashMap<String, ConfigProfile>,
+
+    /// Profile whose settings are currently applied at the top level.
+    #[serde(default)]
+    pub active_profile: Option<String>,
 }
 
 /// Named provider profile definition compatible with Codex app-server style config.
@@ -361,6 +436,10 @@ fn default_transcription_model() -> String {
     "whisper-large-v3-turbo".into()
 }
 
+fn default_transcription_backend() -> String {
+    "api".into()
+}
+
 fn default_transcription_max_duration_secs() -> u64 {
     120
 }
@@ -383,6 +462,10 @@ pub struct TranscriptionConfig {
     /// Maximum voice duration in seconds (messages longer than this are skipped).
     #[serde(default = "default_transcription_max_duration_secs")]
     pub max_duration_secs: u64,
+    /// Where audio is transcribed: `api` (Whisper-compatible endpoint above)
+    /// or `on_device` (the mobile host app's recognizer via the host bridge).
+    #[serde(default = "default_transcription_backend")]
+    pub backend: String,
 }
 
 impl Default for TranscriptionConfig {
@@ -393,6 +476,7 @@ impl Default for TranscriptionConfig {
             model: default_transcription_model(),
             language: None,
             max_duration_secs: default_transcription_max_duration_secs(),
+            backend: default_transcription_backend(),
         }
     }
 }
@@ -416,6 +500,10 @@ pub struct AgentConfig {
     /// Tool dispatch strategy (e.g. `"auto"`). Default: `"auto"`.
     #[serde(default = "default_agent_tool_dispatcher")]
     pub tool_dispatcher: String,
+    /// Context window sizes in tokens, keyed by model name. Overrides the
+    /// built-in registry for custom, fine-tuned, or locally served models.
+    #[serde(default)]
+    pub context_windows: std::collections::HashMap<String, usize>,
 }
 
 fn default_agent_max_tool_iterations() -> usize {
@@ -438,6 +526,7 @@ impl Default for AgentConfig {
             max_history_messages: default_agent_max_history_messages(),
             parallel_tools: false,
             tool_dispatcher: default_agent_tool_dispatcher(),
+            context_windows: std::collections::HashMap::new(),
         }
     }
 }
@@ -781,6 +870,10 @@ pub struct GatewayConfig {
     #[serde(default)]
     pub paired_tokens: Vec<String>,
 
+    /// Lifetime of pairing codes issued on demand for QR pairing, in seconds.
+    #[serde(default = "default_pairing_code_ttl_secs")]
+    pub pairing_code_ttl_secs: u64,
+
     /// Max `/pair` requests per minute per client key.
     #[serde(default = "default_pair_rate_limit")]
     pub pair_rate_limit_per_minute: u32,
@@ -789,6 +882,15 @@ pub struct GatewayConfig {
     #[serde(default = "default_webhook_rate_limit")]
     pub webhook_rate_limit_per_minute: u32,
 
+    /// Max authenticated requests per minute per bearer token (0 = unlimited).
+    #[serde(default = "default_token_rate_limit")]
+    pub token_rate_limit_per_minute: u32,
+
+    /// Max authenticated requests per minute per client IP (0 = unlimited).
+    /// Loopback clients, such as the app on the phone itself, are exempt.
+    #[serde(default = "default_ip_rate_limit")]
+    pub ip_rate_limit_per_minute: u32,
+
     /// Trust proxy-forwarded client IP headers (`X-Forwarded-For`, `X-Real-IP`).
     /// Disabled by default; enable only behind a trusted reverse proxy.
     #[serde(default)]
@@ -805,6 +907,103 @@ pub struct GatewayConfig {
     /// Maximum distinct idempotency keys retained in memory.
     #[serde(default = "default_gateway_idempotency_max_keys")]
     pub idempotency_max_keys: usize,
+
+    /// Bind to the address of this network interface instead of `host`:
+    /// an interface name such as `tailscale0` or `wg0`, or `tailnet` for the
+    /// first interface holding a Tailscale address.
+    #[serde(default)]
+    pub bind_interface: Option<String>,
+
+    /// Name clients reach this node by on the tailnet or VPN (e.g. a MagicDNS
+    /// name), used in gateway URLs and pairing offers instead of the address.
+    #[serde(default)]
+    pub node_name: Option<String>,
+
+    /// Advertise the gateway on the LAN as `_mobileclaw._tcp` (mDNS/NSD) so
+    /// companion apps can find it. Only applies when bound beyond loopback.
+    #[serde(default = "default_true")]
+    pub advertise: bool,
+
+    /// Service instance name shown to discovering clients (default: `MobileClaw`).
+    #[serde(default)]
+    pub instance_name: Option<String>,
+
+    /// Named users sharing this gateway (`[gateway.users.<name>]`). Each gets
+    /// its own agent sessions, memory namespace and request quota.
+    #[serde(default)]
+    pub users: HashMap<String, GatewayUserConfig>,
+
+    /// Inbound webhooks served at `POST /hooks/<name>`
+    /// (`[gateway.hooks.<name>]`).
+    #[serde(default)]
+    pub hooks: HashMap<String, GatewayHookConfig>,
+
+    /// HTTPS for LAN clients (`[gateway.tls]`).
+    #[serde(default)]
+    pub tls: GatewayTlsConfig,
+}
+
+/// Gateway HTTPS configuration (`[gateway.tls]`).
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct GatewayTlsConfig {
+    /// Serve HTTPS instead of plain HTTP (default: false)
+    #[serde(default)]
+    pub enabled: bool,
+    /// PEM certificate chain to serve. When unset, a self-signed certificate
+    /// is generated on first start and reused, so clients can pin it.
+    #[serde(default)]
+    pub cert_path: Option<String>,
+    /// PEM private key matching `cert_path`.
+    #[serde(default)]
+    pub key_path: Option<String>,
+}
+
+/// A gateway user (`[gateway.users.<name>]`).
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct GatewayUserConfig {
+    /// Ids of the paired tokens that act as this user, as listed by
+    /// `GET /admin/tokens` and returned by `POST /pair`.
+    #[serde(default)]
+    pub tokens: Vec<String>,
+    /// Max requests per minute across this user's tokens. Overrides
+    /// `token_rate_limit_per_minute` (0 = unlimited).
+    #[serde(default)]
+    pub rate_limit_per_minute: Option<u32>,
+}
+
+/// What an inbound webhook does with its payload.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum GatewayHookAction {
+    /// Run an agent turn with the rendered template as the prompt.
+    #[default]
+    Agent,
+    /// Push the rendered template to `/ws` clients as a notification.
+    Notify,
+}
+
+/// An inbound webhook (`[gateway.hooks.<name>]`).
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct GatewayHookConfig {
+    /// Shared secret callers prove with a GitHub-style `X-Hub-Signature-256`,
+    /// an `X-Hook-Secret` header or a `?secret=` query parameter. Without
+    /// one, callers need a gateway token.
+    #[serde(default)]
+    pub secret: Option<String>,
+    /// `agent` (default) or `notify`.
+    #[serde(default)]
+    pub action: GatewayHookAction,
+    /// Prompt or notification text. `{{path.to.field}}` is replaced from the
+    /// JSON payload and `{{.}}` by the whole payload. Empty = the hook name
+    /// and the whole payload.
+    #[serde(default)]
+    pub template: String,
+    /// Agent session for `agent` hooks (default: `hook:<name>`).
+    #[serde(default)]
+    pub session: Option<String>,
+    /// Notification topic (default: the hook name).
+    #[serde(default)]
+    pub topic: Option<String>,
 }
 
 fn default_gateway_port() -> u16 {
@@ -815,6 +1014,10 @@ fn default_gateway_host() -> String {
     "127.0.0.1".into()
 }
 
+fn default_pairing_code_ttl_secs() -> u64 {
+    300
+}
+
 fn default_pair_rate_limit() -> u32 {
     10
 }
@@ -823,6 +1026,14 @@ fn default_webhook_rate_limit() -> u32 {
     60
 }
 
+fn default_token_rate_limit() -> u32 {
+    120
+}
+
+fn default_ip_rate_limit() -> u32 {
+    300
+}
+
 fn default_idempotency_ttl_secs() -> u64 {
     300
 }
@@ -847,12 +1058,22 @@ impl Default for GatewayConfig {
             require_pairing: true,
             allow_public_bind: false,
             paired_tokens: Vec::new(),
+            pairing_code_ttl_secs: default_pairing_code_ttl_secs(),
             pair_rate_limit_per_minute: default_pair_rate_limit(),
             webhook_rate_limit_per_minute: default_webhook_rate_limit(),
+            token_rate_limit_per_minute: default_token_rate_limit(),
+            ip_rate_limit_per_minute: default_ip_rate_limit(),
             trust_forwarded_headers: false,
             rate_limit_max_keys: default_gateway_rate_limit_max_keys(),
             idempotency_ttl_secs: default_idempotency_ttl_secs(),
             idempotency_max_keys: default_gateway_idempotency_max_keys(),
+            bind_interface: None,
+            node_name: None,
+            advertise: true,
+            instance_name: None,
+            users: HashMap::new(),
+            hooks: HashMap::new(),
+            tls: GatewayTlsConfig::default(),
         }
     }
 }
@@ -889,6 +1110,475 @@ impl Default for ComposioConfig {
     }
 }
 
+// ── Tool policies ────────────────────────────────────────────────
+
+/// Per-tool settings (`[tools.<name>]`), applied when the tool registry is built.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ToolPolicyConfig {
+    /// Register the tool at all. Default: `true`.
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    /// Name the model sees instead of the built-in one.
+    #[serde(default)]
+    pub name: Option<String>,
+    /// Description the model sees instead of the built-in one.
+    #[serde(default)]
+    pub description: Option<String>,
+    /// Constraints on call arguments, keyed by parameter name (`[tools.<name>.params.<param>]`).
+    #[serde(default)]
+    pub params: HashMap<String, ToolParamConstraint>,
+}
+
+impl Default for ToolPolicyConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            name: None,
+            description: None,
+            params: HashMap::new(),
+        }
+    }
+}
+
+/// Limits on one tool parameter. Array values are checked element by element.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+pub struct ToolParamConstraint {
+    /// Values the parameter may take (case-insensitive). Comma-separated
+    /// strings are checked item by item. Empty allows any value.
+    #[serde(default)]
+    pub allowed_values: Vec<String>,
+    /// Maximum size of a string value in bytes (for example file content).
+    #[serde(default)]
+    pub max_bytes: Option<usize>,
+    /// Minimum numeric value.
+    #[serde(default)]
+    pub min: Option<f64>,
+    /// Maximum numeric value.
+    #[serde(default)]
+    pub max: Option<f64>,
+}
+
+// ── MCP (Model Context Protocol client) ─────────────────────────
+
+/// MCP client configuration (`[mcp]` section).
+///
+/// Each enabled server's tools are discovered at startup and registered as
+/// `mcp_<server>_<tool>`.
+#[derive(Debug, Clone, Serialize, Deserialize, Default, JsonSchema)]
+pub struct McpConfig {
+    /// Enable MCP tool import
+    #[serde(default)]
+    pub enabled: bool,
+    /// MCP servers to connect to (`[[mcp.servers]]`)
+    #[serde(default)]
+    pub servers: Vec<McpServerConfig>,
+}
+
+/// Configuration for a single MCP server.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct McpServerConfig {
+    /// Short server name used to prefix imported tool names
+    pub name: String,
+    /// Transport: "stdio" (spawn `command`) or "http" (streamable HTTP/SSE at `url`)
+    #[serde(default = "default_mcp_transport")]
+    pub transport: String,
+    /// Executable to spawn for stdio servers (e.g. "npx")
+    #[serde(default)]
+    pub command: Option<String>,
+    /// Arguments for the stdio command
+    #[serde(default)]
+    pub args: Vec<String>,
+    /// Extra environment variables for the stdio command
+    #[serde(default)]
+    pub env: HashMap<String, String>,
+    /// Endpoint URL for http servers (e.g. "http://127.0.0.1:8931/mcp")
+    #[serde(default)]
+    pub url: Option<String>,
+    /// Extra HTTP headers for http servers (e.g. Authorization)
+    #[serde(default)]
+    pub headers: HashMap<String, String>,
+    /// Per-request timeout in seconds (default: 60)
+    #[serde(default = "default_mcp_timeout_secs")]
+    pub timeout_secs: u64,
+    /// Optional allowlist of remote tool names to import (empty = all)
+    #[serde(default)]
+    pub allowed_tools: Vec<String>,
+}
+
+fn default_mcp_transport() -> String {
+    "stdio".into()
+}
+
+fn default_mcp_timeout_secs() -> u64 {
+    60
+}
+
+impl Default for McpServerConfig {
+    fn default() -> Self {
+        Self {
+            name: String::new(),
+            transport: default_mcp_transport(),
+            command: None,
+            args: Vec::new(),
+            env: HashMap::new(),
+            url: None,
+            headers: HashMap::new(),
+            timeout_secs: default_mcp_timeout_secs(),
+            allowed_tools: Vec::new(),
+        }
+    }
+}
+
+// ── WASM plugins (third-party tools) ────────────────────────────
+
+/// WASM tool plugin configuration (`[plugins]` section).
+///
+/// Each plugin lives in `<workspace>/<dir>/<name>/` as `plugin.json` plus
+/// `plugin.wasm`. Manifests may request capabilities and limits; the values
+/// here are the ceiling for what any plugin is granted.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct PluginsConfig {
+    /// Load WASM plugins (requires the `plugins-wasm` build feature)
+    #[serde(default)]
+    pub enabled: bool,
+    /// Plugin directory, relative to the workspace
+    #[serde(default = "default_plugins_dir")]
+    pub dir: String,
+    /// Allow plugins that request read-only workspace access
+    #[serde(default)]
+    pub allow_workspace_read: bool,
+    /// Allow plugins that request read-write workspace access
+    #[serde(default)]
+    pub allow_workspace_write: bool,
+    /// Maximum fuel (roughly, executed instructions) per call
+    #[serde(default = "default_plugins_max_fuel")]
+    pub max_fuel: u64,
+    /// Maximum linear memory per call, in MB
+    #[serde(default = "default_plugins_max_memory_mb")]
+    pub max_memory_mb: u64,
+    /// Maximum wall-clock time per call, in seconds
+    #[serde(default = "default_plugins_max_timeout_secs")]
+    pub max_timeout_secs: u64,
+}
+
+fn default_plugins_dir() -> String {
+    "plugins".into()
+}
+
+fn default_plugins_max_fuel() -> u64 {
+    5_000_000_000
+}
+
+fn default_plugins_max_memory_mb() -> u64 {
+    64
+}
+
+fn default_plugins_max_timeout_secs() -> u64 {
+    30
+}
+
+impl Default for PluginsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            dir: default_plugins_dir(),
+            allow_workspace_read: false,
+            allow_workspace_write: false,
+            max_fuel: default_plugins_max_fuel(),
+            max_memory_mb: default_plugins_max_memory_mb(),
+            max_timeout_secs: default_plugins_max_timeout_secs(),
+        }
+    }
+}
+
+// ── Eval (sandboxed scripting) ──────────────────────────────────
+
+/// Sandboxed script evaluation configuration (`[eval]` section).
+///
+/// The `eval` tool runs Rhai scripts with no filesystem, network, or module
+/// access; these limits bound CPU time and memory per call.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct EvalConfig {
+    /// Register the `eval` tool (requires the `eval-rhai` build feature)
+    #[serde(default)]
+    pub enabled: bool,
+    /// Maximum script operations per call
+    #[serde(default = "default_eval_max_operations")]
+    pub max_operations: u64,
+    /// Maximum wall-clock time per call, in milliseconds
+    #[serde(default = "default_eval_timeout_ms")]
+    pub timeout_ms: u64,
+    /// Maximum length of any string value, in bytes
+    #[serde(default = "default_eval_max_string_bytes")]
+    pub max_string_bytes: usize,
+    /// Maximum number of elements in any array or map
+    #[serde(default = "default_eval_max_collection_len")]
+    pub max_collection_len: usize,
+}
+
+fn default_eval_max_operations() -> u64 {
+    10_000_000
+}
+
+fn default_eval_timeout_ms() -> u64 {
+    5_000
+}
+
+fn default_eval_max_string_bytes() -> usize {
+    1024 * 1024
+}
+
+fn default_eval_max_collection_len() -> usize {
+    100_000
+}
+
+impl Default for EvalConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            max_operations: default_eval_max_operations(),
+            timeout_ms: default_eval_timeout_ms(),
+            max_string_bytes: default_eval_max_string_bytes(),
+            max_collection_len: default_eval_max_collection_len(),
+        }
+    }
+}
+
+// ── Home Assistant (smart home) ─────────────────────────────────
+
+/// Home Assistant integration (`[home_assistant]` section).
+///
+/// Enables the `home_assistant` tool against a Home Assistant instance using
+/// a long-lived access token.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct HomeAssistantConfig {
+    /// Enable the `home_assistant` tool
+    #[serde(default)]
+    pub enabled: bool,
+    /// Instance base URL, e.g. "http://homeassistant.local:8123"
+    #[serde(default)]
+    pub base_url: Option<String>,
+    /// Long-lived access token (stored encrypted when secrets.encrypt = true)
+    #[serde(default)]
+    pub token: Option<String>,
+    /// Service domains the agent may call (e.g. "light", "switch"; empty = all)
+    #[serde(default)]
+    pub allowed_domains: Vec<String>,
+    /// Request timeout in seconds
+    #[serde(default = "default_home_assistant_timeout_secs")]
+    pub timeout_secs: u64,
+}
+
+fn default_home_assistant_timeout_secs() -> u64 {
+    15
+}
+
+impl Default for HomeAssistantConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            base_url: None,
+            token: None,
+            allowed_domains: Vec::new(),
+            timeout_secs: default_home_assistant_timeout_secs(),
+        }
+    }
+}
+
+// ── Gemini provider ─────────────────────────────────────────────
+
+/// Gemini safety categories accepted in `[[gemini.safety_settings]]`.
+pub const GEMINI_SAFETY_CATEGORIES: &[&str] = &[
+    "HARM_CATEGORY_HARASSMENT",
+    "HARM_CATEGORY_HATE_SPEECH",
+    "HARM_CATEGORY_SEXUALLY_EXPLICIT",
+    "HARM_CATEGORY_DANGEROUS_CONTENT",
+    "HARM_CATEGORY_CIVIC_INTEGRITY",
+];
+
+/// Gemini block thresholds accepted in `[[gemini.safety_settings]]`.
+pub const GEMINI_SAFETY_THRESHOLDS: &[&str] = &[
+    "BLOCK_NONE",
+    "BLOCK_ONLY_HIGH",
+    "BLOCK_MEDIUM_AND_ABOVE",
+    "BLOCK_LOW_AND_ABOVE",
+    "OFF",
+];
+
+/// Google Gemini provider configuration (`[gemini]` section).
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+pub struct GeminiConfig {
+    /// Per-category safety thresholds sent as `safetySettings` with every
+    /// request. Empty = Google's defaults.
+    #[serde(default)]
+    pub safety_settings: Vec<GeminiSafetySetting>,
+}
+
+/// One Gemini safety setting, e.g.
+/// `{ category = "HARM_CATEGORY_DANGEROUS_CONTENT", threshold = "BLOCK_ONLY_HIGH" }`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct GeminiSafetySetting {
+    pub category: String,
+    pub threshold: String,
+}
+
+// ── Azure OpenAI provider ───────────────────────────────────────
+
+/// Azure OpenAI configuration (`[azure_openai]` section).
+///
+/// Used by the `azure-openai` provider. The configured model is the Azure
+/// deployment name. Requests authenticate with Entra ID (AAD) client
+/// credentials when `tenant_id`, `client_id` and `client_secret` are all
+/// set, and with the `api-key` header otherwise.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct AzureOpenAiConfig {
+    /// Resource endpoint, e.g. `https://my-resource.openai.azure.com`.
+    /// Falls back to `api_url`, then `AZURE_OPENAI_ENDPOINT`.
+    #[serde(default)]
+    pub endpoint: Option<String>,
+    /// `api-version` query parameter sent with every request.
+    #[serde(default = "default_azure_openai_api_version")]
+    pub api_version: String,
+    /// Entra ID tenant for client-credential token auth.
+    #[serde(default)]
+    pub tenant_id: Option<String>,
+    /// App registration (client) ID for client-credential token auth.
+    #[serde(default)]
+    pub client_id: Option<String>,
+    /// Client secret for client-credential token auth.
+    #[serde(default)]
+    pub client_secret: Option<String>,
+}
+
+fn default_azure_openai_api_version() -> String {
+    "2024-10-21".into()
+}
+
+impl Default for AzureOpenAiConfig {
+    fn default() -> Self {
+        Self {
+            endpoint: None,
+            api_version: default_azure_openai_api_version(),
+            tenant_id: None,
+            client_id: None,
+            client_secret: None,
+        }
+    }
+}
+
+// ── OpenRouter provider routing ─────────────────────────────────
+
+/// OpenRouter provider routing preferences (`[openrouter]` section).
+///
+/// Sent as the `provider` object with every OpenRouter request. Defaults
+/// leave routing to OpenRouter.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct OpenRouterConfig {
+    /// Upstream provider slugs to try first, in order (e.g. `"deepinfra"`,
+    /// `"together"`).
+    #[serde(default)]
+    pub order: Vec<String>,
+    /// Allow upstreams outside `order` when those in it are unavailable.
+    #[serde(default = "default_true")]
+    pub allow_fallbacks: bool,
+    /// Maximum prompt price in USD per million tokens.
+    #[serde(default)]
+    pub max_prompt_price: Option<f64>,
+    /// Maximum completion price in USD per million tokens.
+    #[serde(default)]
+    pub max_completion_price: Option<f64>,
+    /// Only route to upstreams that do not store or train on prompts.
+    #[serde(default)]
+    pub deny_data_collection: bool,
+}
+
+impl Default for OpenRouterConfig {
+    fn default() -> Self {
+        Self {
+            order: Vec::new(),
+            allow_fallbacks: true,
+            max_prompt_price: None,
+            max_completion_price: None,
+            deny_data_collection: false,
+        }
+    }
+}
+
+// ── Local LLM (on-device llama.cpp) ─────────────────────────────
+
+/// On-device llama.cpp inference configuration (`[local_llm]` section).
+///
+/// Used by the `local-llm` provider (and, for `max_tokens` and the
+/// `accelerator*` keys, the `accelerated` provider). Defaults are sized for
+/// phones: a small context window and at most four threads so generation
+/// stays on the performance cores of big.LITTLE SoCs.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct LocalLlmConfig {
+    /// Workspace-relative path to the GGUF model file. When unset, the
+    /// configured model name is used as the path.
+    #[serde(default)]
+    pub model_path: Option<String>,
+    /// Inference threads; `0` = number of CPU cores, capped at 4
+    #[serde(default)]
+    pub threads: usize,
+    /// Context window in tokens (prompt + reply)
+    #[serde(default = "default_local_llm_context_size")]
+    pub context_size: u32,
+    /// Prompt tokens evaluated per batch
+    #[serde(default = "default_local_llm_batch_size")]
+    pub batch_size: u32,
+    /// Maximum tokens generated per reply
+    #[serde(default = "default_local_llm_max_tokens")]
+    pub max_tokens: u32,
+    /// Model layers offloaded to the GPU (`0` = CPU only)
+    #[serde(default)]
+    pub gpu_layers: u32,
+    /// Host runtime used by the `accelerated` provider (`mlc`, `executorch`)
+    #[serde(default = "default_local_llm_accelerator")]
+    pub accelerator: String,
+    /// Maximum time the host runtime may take per reply, in seconds
+    #[serde(default = "default_local_llm_accelerator_timeout_secs")]
+    pub accelerator_timeout_secs: u64,
+}
+
+/// Host runtimes accepted in `local_llm.accelerator`.
+pub const LOCAL_LLM_ACCELERATORS: &[&str] = &["mlc", "executorch"];
+
+fn default_local_llm_accelerator() -> String {
+    "mlc".into()
+}
+
+fn default_local_llm_accelerator_timeout_secs() -> u64 {
+    120
+}
+
+fn default_local_llm_context_size() -> u32 {
+    4096
+}
+
+fn default_local_llm_batch_size() -> u32 {
+    512
+}
+
+fn default_local_llm_max_tokens() -> u32 {
+    1024
+}
+
+impl Default for LocalLlmConfig {
+    fn default() -> Self {
+        Self {
+            model_path: None,
+            threads: 0,
+            context_size: default_local_llm_context_size(),
+            batch_size: default_local_llm_batch_size(),
+            max_tokens: default_local_llm_max_tokens(),
+            gpu_layers: 0,
+            accelerator: default_local_llm_accelerator(),
+            accelerator_timeout_secs: default_local_llm_accelerator_timeout_secs(),
+        }
+    }
+}
+
 // ── Secrets (encrypted credential store) ────────────────────────
 
 /// Secrets encryption configuration (`[secrets]` section).
@@ -897,14 +1587,125 @@ pub struct SecretsConfig {
     /// Enable encryption for API keys and tokens in config.toml
     #[serde(default = "default_true")]
     pub encrypt: bool,
+    /// Where the encryption key lives: `local` (key file next to the config)
+    /// or `keystore` (the host app's keystore, e.g. Android Keystore, reached
+    /// through the host bridge)
+    #[serde(default)]
+    pub backend: SecretsBackend,
 }
 
 impl Default for SecretsConfig {
     fn default() -> Self {
-        Self { encrypt: true }
+        Self {
+            encrypt: true,
+            backend: SecretsBackend::default(),
+        }
+    }
+}
+
+/// Key storage for encrypted config secrets.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum SecretsBackend {
+    /// ChaCha20-Poly1305 with a key file in the config directory.
+    #[default]
+    Local,
+    /// Encrypted by the host app with a non-exportable keystore key; values
+    /// are stored as `ks1:` ciphertext.
+    Keystore,
+}
+
+// ── Remote config sync ───────────────────────────────────────────
+
+/// Remote config sync (`[config_sync]` section).
+///
+/// Pulls a signed TOML document from an HTTPS URL or a git repository and
+/// merges it over the local config, so several devices share the same agent
+/// settings. Credentials are never taken from the document.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ConfigSyncConfig {
+    /// Enable syncing. Default: `false`.
+    #[serde(default)]
+    pub enabled: bool,
+    /// HTTPS URL of the shared TOML document.
+    #[serde(default)]
+    pub url: Option<String>,
+    /// URL of the detached signature. Default: `url` with `.sig` appended.
+    #[serde(default)]
+    pub signature_url: Option<String>,
+    /// Git repository to pull the document from instead of `url`.
+    #[serde(default)]
+    pub git_repo: Option<String>,
+    /// Branch or tag to pull. Default: the repository's default branch.
+    #[serde(default)]
+    pub git_ref: Option<String>,
+    /// Path of the document inside the repository; the signature is read
+    /// from the same path with `.sig` appended. Default: `zeroclaw.toml`.
+    #[serde(default = "default_config_sync_git_path")]
+    pub git_path: String,
+    /// Base64 Ed25519 public key the document must be signed with.
+    #[serde(default)]
+    pub public_key: String,
+    /// Minutes between pulls while the daemon runs; `0` pulls only on
+    /// request. Default: `60`.
+    #[serde(default = "default_config_sync_interval_minutes")]
+    pub interval_minutes: u64,
+    /// Top-level sections to take from the document, e.g. `["autonomy",
+    /// "agent"]`. Empty takes every section it contains.
+    #[serde(default)]
+    pub sections: Vec<String>,
+}
+
+fn default_config_sync_git_path() -> String {
+    "zeroclaw.toml".into()
+}
+
+fn default_config_sync_interval_minutes() -> u64 {
+    60
+}
+
+impl Default for ConfigSyncConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            url: None,
+            signature_url: None,
+            git_repo: None,
+            git_ref: None,
+            git_path: default_config_sync_git_path(),
+            public_key: String::new(),
+            interval_minutes: default_config_sync_interval_minutes(),
+            sections: Vec::new(),
+        }
     }
 }
 
+// ── Profiles ─────────────────────────────────────────────────────
+
+/// Profile the current top-level settings are stored under when switching
+/// away before any profile was activated.
+pub const DEFAULT_PROFILE_NAME: &str = "default";
+
+/// Named settings profile (`[profiles.<name>]`), e.g. work / personal / travel.
+///
+/// Switching to a profile copies its set values over the top-level keys and
+/// stores the previous values under the profile being left, so the file's
+/// top level always reflects the active profile. Unset keys keep their
+/// current value; `autonomy` and `channels_config` replace the whole section.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+pub struct ConfigProfile {
+    #[serde(default)]
+    pub default_provider: Option<String>,
+    #[serde(default)]
+    pub default_model: Option<String>,
+    #[serde(default)]
+    pub default_temperature: Option<f64>,
+    #[serde(default)]
+    pub autonomy: Option<AutonomyConfig>,
+    #[serde(default)]
+    pub channels_config: Option<ChannelsConfig>,
+}
+
 // ── Browser (friendly-service browsing only) ───────────────────
 
 /// Computer-use sidecar configuration (`[browser.computer_use]` section).
@@ -1076,6 +1877,14 @@ pub struct WebFetchConfig {
     /// Request timeout in seconds (default: 30)
     #[serde(default = "default_web_fetch_timeout_secs")]
     pub timeout_secs: u64,
+    /// Reduce HTML pages to their main content as Markdown (default: true);
+    /// `false` returns the whole page as plain text
+    #[serde(default = "default_true")]
+    pub readability: bool,
+    /// Reuse fetched pages cached in the workspace for this many seconds
+    /// (default: 3600, 0 = no caching)
+    #[serde(default = "default_web_fetch_cache_ttl_secs")]
+    pub cache_ttl_secs: u64,
 }
 
 fn default_web_fetch_max_response_size() -> usize {
@@ -1086,6 +1895,10 @@ fn default_web_fetch_timeout_secs() -> u64 {
     30
 }
 
+fn default_web_fetch_cache_ttl_secs() -> u64 {
+    3600
+}
+
 impl Default for WebFetchConfig {
     fn default() -> Self {
         Self {
@@ -1094,6 +1907,8 @@ impl Default for WebFetchConfig {
             blocked_domains: vec![],
             max_response_size: default_web_fetch_max_response_size(),
             timeout_secs: default_web_fetch_timeout_secs(),
+            readability: true,
+            cache_ttl_secs: default_web_fetch_cache_ttl_secs(),
         }
     }
 }
@@ -1106,12 +1921,20 @@ pub struct WebSearchConfig {
     /// Enable `web_search_tool` for web searches
     #[serde(default)]
     pub enabled: bool,
-    /// Search provider: "duckduckgo" (free, no API key) or "brave" (requires API key)
+    /// Search provider: "duckduckgo" (free, no API key), "brave" or "tavily"
+    /// (require API keys), or "searxng" (self-hosted instance)
     #[serde(default = "default_web_search_provider")]
     pub provider: String,
     /// Brave Search API key (required if provider is "brave")
     #[serde(default)]
     pub brave_api_key: Option<String>,
+    /// SearXNG instance base URL (required if provider is "searxng"); the
+    /// instance must have the JSON output format enabled
+    #[serde(default)]
+    pub searxng_url: Option<String>,
+    /// Tavily API key (required if provider is "tavily")
+    #[serde(default)]
+    pub tavily_api_key: Option<String>,
     /// Maximum results per search (1-10)
     #[serde(default = "default_web_search_max_results")]
     pub max_results: usize,
@@ -1138,6 +1961,8 @@ impl Default for WebSearchConfig {
             enabled: false,
             provider: default_web_search_provider(),
             brave_api_key: None,
+            searxng_url: None,
+            tavily_api_key: None,
             max_results: default_web_search_max_results(),
             timeout_secs: default_web_search_timeout_secs(),
         }
@@ -1367,6 +2192,75 @@ impl ProxyConfig {
     }
 }
 
+/// Extra headers and proxy for one provider (`[provider_http.<provider>]`).
+///
+/// Keys are provider IDs (`openai`, `anthropic`, `openrouter`, ...).
+/// OpenAI-compatible providers match on their name in kebab-case, e.g.
+/// `custom` for `custom:` endpoints or `together-ai`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct ProviderHttpConfig {
+    /// Headers added to every request, e.g. gateway auth headers.
+    #[serde(default)]
+    pub headers: HashMap<String, String>,
+    /// Proxy URL for this provider only (http, https, socks5, socks5h).
+    /// Takes precedence over `[proxy]`.
+    #[serde(default)]
+    pub proxy: Option<String>,
+}
+
+impl ProviderHttpConfig {
+    pub fn validate(&self, provider: &str) -> Result<()> {
+        for (name, value) in &self.headers {
+            reqwest::header::HeaderName::from_bytes(name.as_bytes()).with_context(|| {
+                format!("provider_http.{provider}.headers has an invalid header name '{name}'")
+            })?;
+            reqwest::header::HeaderValue::from_str(value).with_context(|| {
+                format!("provider_http.{provider}.headers.{name} has an invalid value")
+            })?;
+        }
+        if let Some(url) = normalize_proxy_url_option(self.proxy.as_deref()) {
+            validate_proxy_url(&format!("provider_http.{provider}.proxy"), &url)?;
+        }
+        Ok(())
+    }
+
+    fn apply_to_reqwest_builder(
+        &self,
+        mut builder: reqwest::ClientBuilder,
+        provider: &str,
+    ) -> reqwest::ClientBuilder {
+        let mut headers = reqwest::header::HeaderMap::new();
+        for (name, value) in &self.headers {
+            match (
+                reqwest::header::HeaderName::from_bytes(name.as_bytes()),
+                reqwest::header::HeaderValue::from_str(value),
+            ) {
+                (Ok(name), Ok(value)) => {
+                    headers.insert(name, value);
+                }
+                _ => tracing::warn!(provider, header = %name, "Ignoring invalid provider header"),
+            }
+        }
+        if !headers.is_empty() {
+            builder = builder.default_headers(headers);
+        }
+
+        if let Some(url) = normalize_proxy_url_option(self.proxy.as_deref()) {
+            match reqwest::Proxy::all(&url) {
+                Ok(proxy) => builder = builder.proxy(proxy),
+                Err(error) => {
+                    tracing::warn!(
+                        proxy_url = %url,
+                        provider,
+                        "Ignoring invalid provider proxy URL: {error}"
+                    );
+                }
+            }
+        }
+        builder
+    }
+}
+
 fn apply_no_proxy(proxy: reqwest::Proxy, no_proxy: Option<reqwest::NoProxy>) -> reqwest::Proxy {
     proxy.no_proxy(no_proxy)
 }
@@ -1474,6 +2368,10 @@ fn runtime_proxy_state() -> &'static RwLock<ProxyConfig> {
     RUNTIME_PROXY_CONFIG.get_or_init(|| RwLock::new(ProxyConfig::default()))
 }
 
+fn runtime_provider_http_state() -> &'static RwLock<HashMap<String, ProviderHttpConfig>> {
+    RUNTIME_PROVIDER_HTTP_CONFIG.get_or_init(|| RwLock::new(HashMap::new()))
+}
+
 fn runtime_proxy_client_cache() -> &'static RwLock<HashMap<String, reqwest::Client>> {
     RUNTIME_PROXY_CLIENT_CACHE.get_or_init(|| RwLock::new(HashMap::new()))
 }
@@ -1537,6 +2435,35 @@ pub fn set_runtime_proxy_config(config: ProxyConfig) {
     clear_runtime_proxy_client_cache();
 }
 
+/// Replace the `[provider_http]` overrides used when building provider clients.
+pub fn set_runtime_provider_http_config<S: std::hash::BuildHasher>(
+    config: HashMap<String, ProviderHttpConfig, S>,
+) {
+    let config = config
+        .into_iter()
+        .map(|(provider, http)| (provider.trim().to_ascii_lowercase(), http))
+        .collect();
+    match runtime_provider_http_state().write() {
+        Ok(mut guard) => {
+            *guard = config;
+        }
+        Err(poisoned) => {
+            *poisoned.into_inner() = config;
+        }
+    }
+
+    clear_runtime_proxy_client_cache();
+}
+
+/// `[provider_http]` override for `provider`, if any.
+pub fn runtime_provider_http_config(provider: &str) -> Option<ProviderHttpConfig> {
+    let provider = provider.trim().to_ascii_lowercase();
+    match runtime_provider_http_state().read() {
+        Ok(guard) => guard.get(&provider).cloned(),
+        Err(poisoned) => poisoned.into_inner().get(&provider).cloned(),
+    }
+}
+
 pub fn runtime_proxy_config() -> ProxyConfig {
     match runtime_proxy_state().read() {
         Ok(guard) => guard.clone(),
@@ -1548,7 +2475,28 @@ pub fn apply_runtime_proxy_to_builder(
     builder: reqwest::ClientBuilder,
     service_key: &str,
 ) -> reqwest::ClientBuilder {
-    runtime_proxy_config().apply_to_reqwest_builder(builder, service_key)
+    let provider = service_key.trim().strip_prefix("provider.").unwrap_or("");
+    apply_runtime_provider_http_to_builder(builder, provider, service_key)
+}
+
+/// Apply `[provider_http.<provider>]` headers and proxy, falling back to the
+/// `[proxy]` settings for `service_key` when the provider has no own proxy.
+pub fn apply_runtime_provider_http_to_builder(
+    builder: reqwest::ClientBuilder,
+    provider: &str,
+    service_key: &str,
+) -> reqwest::ClientBuilder {
+    match runtime_provider_http_config(provider) {
+        Some(http) => {
+            let builder = if normalize_proxy_url_option(http.proxy.as_deref()).is_some() {
+                builder
+            } else {
+                runtime_proxy_config().apply_to_reqwest_builder(builder, service_key)
+            };
+            http.apply_to_reqwest_builder(builder, provider)
+        }
+        None => runtime_proxy_config().apply_to_reqwest_builder(builder, service_key),
+    }
 }
 
 pub fn build_runtime_proxy_client(service_key: &str) -> reqwest::Client {
@@ -1573,6 +2521,45 @@ pub fn build_runtime_proxy_client_with_timeouts(
 ) -> reqwest::Client {
     let cache_key =
         runtime_proxy_cache_key(service_key, Some(timeout_secs), Some(connect_timeout_secs));
+    let provider = service_key.trim().strip_prefix("provider.").unwrap_or("");
+    build_timeout_client(
+        cache_key,
+        provider,
+        service_key,
+        timeout_secs,
+        connect_timeout_secs,
+    )
+}
+
+/// Client for `provider` with `[provider_http]` overrides applied. Providers
+/// sharing a service key (OpenAI-compatible endpoints) pass their own name.
+pub fn build_provider_client_with_timeouts(
+    provider: &str,
+    service_key: &str,
+    timeout_secs: u64,
+    connect_timeout_secs: u64,
+) -> reqwest::Client {
+    let cache_key = format!(
+        "{}|provider={}",
+        runtime_proxy_cache_key(service_key, Some(timeout_secs), Some(connect_timeout_secs)),
+        provider.trim().to_ascii_lowercase()
+    );
+    build_timeout_client(
+        cache_key,
+        provider,
+        service_key,
+        timeout_secs,
+        connect_timeout_secs,
+    )
+}
+
+fn build_timeout_client(
+    cache_key: String,
+    provider: &str,
+    service_key: &str,
+    timeout_secs: u64,
+    connect_timeout_secs: u64,
+) -> reqwest::Client {
     if let Some(client) = runtime_proxy_cached_client(&cache_key) {
         return client;
     }
@@ -1580,7 +2567,7 @@ pub fn build_runtime_proxy_client_with_timeouts(
     let builder = reqwest::Client::builder()
         .timeout(std::time::Duration::from_secs(timeout_secs))
         .connect_timeout(std::time::Duration::from_secs(connect_timeout_secs));
-    let builder = apply_runtime_proxy_to_builder(builder, service_key);
+    let builder = apply_runtime_provider_http_to_builder(builder, provider, service_key);
     let client = builder.build().unwrap_or_else(|error| {
         tracing::warn!(
             service_key,
@@ -1734,7 +2721,7 @@ pub struct MemoryConfig {
     /// For sqlite backend: prune conversation rows older than this many days
     #[serde(default = "default_conversation_retention_days")]
     pub conversation_retention_days: u32,
-    /// Embedding provider: "none" | "openai" | "custom:URL"
+    /// Embedding provider: "none" | "openai" | "on_device" | "custom:URL"
     #[serde(default = "default_embedding_provider")]
     pub embedding_provider: String,
     /// Embedding model name (e.g. "text-embedding-3-small")
@@ -1771,6 +2758,9 @@ pub struct MemoryConfig {
     /// Max number of cached responses before LRU eviction (default: 5000)
     #[serde(default = "default_response_cache_max")]
     pub response_cache_max_entries: usize,
+    /// Only calls at or below this temperature are cached (default: 0.2)
+    #[serde(default = "default_response_cache_max_temperature")]
+    pub response_cache_max_temperature: f64,
 
     // ── Memory Snapshot (soul backup to Markdown) ─────────────
     /// Enable periodic export of core memories to MEMORY_SNAPSHOT.md
@@ -1838,6 +2828,9 @@ fn default_response_cache_ttl() -> u32 {
 fn default_response_cache_max() -> usize {
     5_000
 }
+fn default_response_cache_max_temperature() -> f64 {
+    0.2
+}
 
 impl Default for MemoryConfig {
     fn default() -> Self {
@@ -1859,6 +2852,7 @@ impl Default for MemoryConfig {
             response_cache_enabled: false,
             response_cache_ttl_minutes: default_response_cache_ttl(),
             response_cache_max_entries: default_response_cache_max(),
+            response_cache_max_temperature: default_response_cache_max_temperature(),
             snapshot_enabled: false,
             snapshot_on_hygiene: false,
             auto_hydrate: true,
@@ -1896,6 +2890,18 @@ pub struct ObservabilityConfig {
     /// Maximum entries retained when runtime_trace_mode = "rolling".
     #[serde(default = "default_runtime_trace_max_entries")]
     pub runtime_trace_max_entries: usize,
+
+    /// Record provider requests and responses (redacted) to daily JSONL files.
+    #[serde(default)]
+    pub wire_log: bool,
+
+    /// Wire log directory. Relative paths are resolved under workspace_dir.
+    #[serde(default = "default_wire_log_path")]
+    pub wire_log_path: String,
+
+    /// Days of wire log files kept, including today.
+    #[serde(default = "default_wire_log_retention_days")]
+    pub wire_log_retention_days: u32,
 }
 
 impl Default for ObservabilityConfig {
@@ -1907,6 +2913,9 @@ impl Default for ObservabilityConfig {
             runtime_trace_mode: default_runtime_trace_mode(),
             runtime_trace_path: default_runtime_trace_path(),
             runtime_trace_max_entries: default_runtime_trace_max_entries(),
+            wire_log: false,
+            wire_log_path: default_wire_log_path(),
+            wire_log_retention_days: default_wire_log_retention_days(),
         }
     }
 }
@@ -1923,6 +2932,14 @@ fn default_runtime_trace_max_entries() -> usize {
     200
 }
 
+fn default_wire_log_path() -> String {
+    "state/wire-log".to_string()
+}
+
+fn default_wire_log_retention_days() -> u32 {
+    3
+}
+
 // ── Hooks ────────────────────────────────────────────────────────
 
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
@@ -2016,6 +3033,29 @@ pub struct AutonomyConfig {
     /// model in tool specs.
     #[serde(default)]
     pub non_cli_excluded_tools: Vec<String>,
+
+    /// Phone numbers the `android_sms` tool may send to and read from.
+    /// Empty = SMS access disabled.
+    #[serde(default)]
+    pub allowed_sms_numbers: Vec<String>,
+
+    /// Allow the `android_location` tool to read the device location.
+    #[serde(default)]
+    pub allow_location: bool,
+
+    /// Domains outbound network tools must never contact (exact or subdomain
+    /// match). Takes precedence over per-tool allowlists.
+    #[serde(default)]
+    pub denied_domains: Vec<String>,
+
+    /// Maximum number of scheduled jobs (cron and one-shot) that agent tools
+    /// may keep registered at once. `0` disables agent-created jobs.
+    #[serde(default = "default_max_scheduled_jobs")]
+    pub max_scheduled_jobs: usize,
+}
+
+fn default_max_scheduled_jobs() -> usize {
+    20
 }
 
 fn default_auto_approve() -> Vec<String> {
@@ -2084,6 +3124,10 @@ impl Default for AutonomyConfig {
             always_ask: default_always_ask(),
             allowed_roots: Vec::new(),
             non_cli_excluded_tools: Vec::new(),
+            allowed_sms_numbers: Vec::new(),
+            allow_location: false,
+            denied_domains: Vec::new(),
+            max_scheduled_jobs: default_max_scheduled_jobs(),
         }
     }
 }
@@ -2093,7 +3137,8 @@ impl Default for AutonomyConfig {
 /// Runtime adapter configuration (`[runtime]` section).
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct RuntimeConfig {
-    /// Runtime kind (`native` | `docker` | `android`).
+    /// Runtime kind (`auto` | `native` | `docker` | `android` | `desktop`).
+    /// `auto` (default) detects the host environment at startup.
     #[serde(default = "default_runtime_kind")]
     pub kind: String,
 
@@ -2105,6 +3150,14 @@ pub struct RuntimeConfig {
     #[serde(default)]
     pub android: AndroidRuntimeConfig,
 
+    /// Metered-network policy for large transfers (`[runtime.network]`).
+    #[serde(default)]
+    pub network: RuntimeNetworkConfig,
+
+    /// Preset for devices with little RAM (`[runtime.low_resource]`).
+    #[serde(default)]
+    pub low_resource: LowResourceConfig,
+
     /// Global reasoning override for providers that expose explicit controls.
     /// - `None`: provider default behavior
     /// - `Some(true)`: request reasoning/thinking when supported
@@ -2162,6 +3215,21 @@ pub struct AndroidCapabilitiesConfig {
     pub file_system_access: bool,
     #[serde(default)]
     pub event_hooks: bool,
+    /// Read other apps' notifications fed by the host's notification listener
+    #[serde(default)]
+    pub notification_access: bool,
+    /// Read per-app usage statistics (UsageStatsManager)
+    #[serde(default)]
+    pub usage_stats: bool,
+    /// Run Tasker tasks and send custom broadcast intents
+    #[serde(default)]
+    pub automation: bool,
+    /// Play audio files and spoken text through the device speaker
+    #[serde(default = "default_true")]
+    pub audio_playback: bool,
+    /// Register enter/exit geofences that trigger agent prompts
+    #[serde(default)]
+    pub geofencing: bool,
 }
 
 impl Default for AndroidCapabilitiesConfig {
@@ -2184,6 +3252,11 @@ impl Default for AndroidCapabilitiesConfig {
             browser_automation: false,
             file_system_access: false,
             event_hooks: false,
+            notification_access: false,
+            usage_stats: false,
+            automation: false,
+            audio_playback: true,
+            geofencing: false,
         }
     }
 }
@@ -2248,6 +3321,26 @@ pub struct AndroidPolicyConfig {
     /// Maximum calls per hour.
     #[serde(default = "default_android_max_calls_per_hour")]
     pub max_calls_per_hour: u32,
+    /// Intent kinds the `android_intent` tool may fire
+    /// (`open_app`, `view_url`, `navigate`, `share_text`, `dial`).
+    #[serde(default = "default_android_allowed_intents")]
+    pub allowed_intents: Vec<String>,
+    /// Tasker tasks the `android_automation` tool may run (empty = any task).
+    #[serde(default)]
+    pub allowed_tasker_tasks: Vec<String>,
+    /// Broadcast intent actions the `android_automation` tool may send
+    /// (empty = none).
+    #[serde(default)]
+    pub allowed_broadcast_actions: Vec<String>,
+}
+
+fn default_android_allowed_intents() -> Vec<String> {
+    vec![
+        "open_app".into(),
+        "view_url".into(),
+        "navigate".into(),
+        "share_text".into(),
+    ]
 }
 
 fn default_android_max_sms_per_hour() -> u32 {
@@ -2266,16 +3359,149 @@ impl Default for AndroidPolicyConfig {
             allowed_phone_numbers: Vec::new(),
             max_sms_per_hour: default_android_max_sms_per_hour(),
             max_calls_per_hour: default_android_max_calls_per_hour(),
+            allowed_intents: default_android_allowed_intents(),
+            allowed_tasker_tasks: Vec::new(),
+            allowed_broadcast_actions: Vec::new(),
         }
     }
 }
 
+/// Preferred storage location for the Android runtime.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum AndroidStorageLocation {
+    /// App-private internal storage (`/data/user/0/<package>/files`).
+    #[default]
+    Internal,
+    /// App-specific external files directory (`Context.getExternalFilesDir`).
+    External,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct AndroidRuntimeConfig {
     #[serde(default)]
     pub app_data_dir: Option<String>,
+    /// Preferred storage location. Falls back to internal storage when the
+    /// external directory is unmounted or not writable.
+    #[serde(default)]
+    pub storage_location: AndroidStorageLocation,
+    /// App-specific external files directory reported by the host app.
+    #[serde(default)]
+    pub external_files_dir: Option<String>,
+    /// Shared media collections exposed to the agent
+    /// (`pictures`, `music`, `movies`, `documents`, `download`).
+    /// Collections the app lacks permission to read are skipped.
+    #[serde(default)]
+    pub media_collections: Vec<String>,
     #[serde(default = "default_android_foreground_service")]
     pub use_foreground_service: bool,
+    /// Storage Access Framework tree URIs granted by the user through the
+    /// document picker. `content://` paths in file tools must fall inside one
+    /// of these trees. Empty = SAF access disabled.
+    #[serde(default)]
+    pub saf_tree_uris: Vec<String>,
+}
+
+/// Metered-network policy (`[runtime.network]` section).
+///
+/// Connection type and metered status are reported by the host app; on
+/// platforms without a report the network is treated as unmetered.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+pub struct RuntimeNetworkConfig {
+    /// Defer model downloads until the device is on an unmetered network.
+    #[serde(default)]
+    pub unmetered_only_downloads: bool,
+    /// Reject image attachments for providers while on a metered network.
+    #[serde(default)]
+    pub unmetered_only_image_uploads: bool,
+    /// Extra seconds to wait between channel long-poll cycles on a metered
+    /// network. `0` = poll at the normal rate.
+    #[serde(default)]
+    pub metered_poll_interval_secs: u64,
+}
+
+/// When the low-resource preset applies.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum LowResourceMode {
+    /// Apply when total RAM is at most `max_ram_mb`.
+    #[default]
+    Auto,
+    On,
+    Off,
+}
+
+/// Low-resource preset (`[runtime.low_resource]`) for 3-4 GB phones.
+///
+/// While active, the limits below cap the matching settings (they never
+/// raise them), `disabled_tools` are turned off unless `[tools.<name>]`
+/// says otherwise, and `default_model` replaces the top-level one. The
+/// preset only changes the loaded config; `config.toml` keeps its values.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct LowResourceConfig {
+    /// `auto` (default), `on` or `off`.
+    #[serde(default)]
+    pub mode: LowResourceMode,
+    /// RAM threshold for `auto`, in MB. Default: `4096`.
+    #[serde(default = "default_low_resource_max_ram_mb")]
+    pub max_ram_mb: u64,
+    /// Cap for `agent.max_history_messages`. Default: `20`.
+    #[serde(default = "default_low_resource_max_history_messages")]
+    pub max_history_messages: usize,
+    /// Cap for `scheduler.max_concurrent`. Default: `1`.
+    #[serde(default = "default_low_resource_max_concurrent_tasks")]
+    pub max_concurrent_tasks: usize,
+    /// Cap for channel messages handled at once. Default: `2`.
+    #[serde(default = "default_low_resource_max_in_flight_messages")]
+    pub max_in_flight_messages: usize,
+    /// Cap for `local_llm.context_size`. Default: `2048`.
+    #[serde(default = "default_low_resource_local_llm_context_size")]
+    pub local_llm_context_size: u32,
+    /// Tools to turn off. Default: `["browser", "delegate"]`.
+    #[serde(default = "default_low_resource_disabled_tools")]
+    pub disabled_tools: Vec<String>,
+    /// Smaller model (or `[model_aliases]` name) to use instead of `default_model`.
+    #[serde(default)]
+    pub default_model: Option<String>,
+}
+
+fn default_low_resource_max_ram_mb() -> u64 {
+    4096
+}
+
+fn default_low_resource_max_history_messages() -> usize {
+    20
+}
+
+fn default_low_resource_max_concurrent_tasks() -> usize {
+    1
+}
+
+fn default_low_resource_max_in_flight_messages() -> usize {
+    2
+}
+
+fn default_low_resource_local_llm_context_size() -> u32 {
+    2048
+}
+
+fn default_low_resource_disabled_tools() -> Vec<String> {
+    vec!["browser".into(), "delegate".into()]
+}
+
+impl Default for LowResourceConfig {
+    fn default() -> Self {
+        Self {
+            mode: LowResourceMode::default(),
+            max_ram_mb: default_low_resource_max_ram_mb(),
+            max_history_messages: default_low_resource_max_history_messages(),
+            max_concurrent_tasks: default_low_resource_max_concurrent_tasks(),
+            max_in_flight_messages: default_low_resource_max_in_flight_messages(),
+            local_llm_context_size: default_low_resource_local_llm_context_size(),
+            disabled_tools: default_low_resource_disabled_tools(),
+            default_model: None,
+        }
+    }
 }
 
 fn default_android_foreground_service() -> bool {
@@ -2286,7 +3512,11 @@ impl Default for AndroidRuntimeConfig {
     fn default() -> Self {
         Self {
             app_data_dir: None,
+            storage_location: AndroidStorageLocation::default(),
+            external_files_dir: None,
+            media_collections: Vec::new(),
             use_foreground_service: default_android_foreground_service(),
+            saf_tree_uris: Vec::new(),
         }
     }
 }
@@ -2303,6 +3533,22 @@ pub struct AndroidConfig {
     pub bridge: AndroidBridgeConfig,
     #[serde(default)]
     pub policy: AndroidPolicyConfig,
+    #[serde(default)]
+    pub shizuku: AndroidShizukuConfig,
+}
+
+/// Shizuku-backed privileged operations (`[android.shizuku]`).
+///
+/// Requires the Shizuku app to be running and to have granted this app
+/// permission. Disabled by default.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+pub struct AndroidShizukuConfig {
+    /// Enable `settings_put` and `input_inject` actions through Shizuku.
+    #[serde(default)]
+    pub enabled: bool,
+    /// Settings keys `settings_put` may write. Empty = deny all writes.
+    #[serde(default)]
+    pub allowed_settings_keys: Vec<String>,
 }
 
 impl Default for AndroidConfig {
@@ -2313,6 +3559,7 @@ impl Default for AndroidConfig {
             capabilities: AndroidCapabilitiesConfig::default(),
             bridge: AndroidBridgeConfig::default(),
             policy: AndroidPolicyConfig::default(),
+            shizuku: AndroidShizukuConfig::default(),
         }
     }
 }
@@ -2350,7 +3597,7 @@ pub struct DockerRuntimeConfig {
 }
 
 fn default_runtime_kind() -> String {
-    "native".into()
+    "auto".into()
 }
 
 fn default_docker_image() -> String {
@@ -2389,6 +3636,8 @@ impl Default for RuntimeConfig {
             kind: default_runtime_kind(),
             docker: DockerRuntimeConfig::default(),
             android: AndroidRuntimeConfig::default(),
+            network: RuntimeNetworkConfig::default(),
+            low_resource: LowResourceConfig::default(),
             reasoning_enabled: None,
         }
     }
@@ -2407,6 +3656,19 @@ pub struct ReliabilityConfig {
     /// Base backoff (ms) for provider retry delay.
     #[serde(default = "default_provider_backoff_ms")]
     pub provider_backoff_ms: u64,
+    /// Upper bound (ms) for the exponential provider retry delay.
+    #[serde(default = "default_provider_max_backoff_ms")]
+    pub provider_max_backoff_ms: u64,
+    /// Per-provider retry overrides, keyed by provider name (as written in
+    /// `default_provider` or `fallback_providers`).
+    /// Example: `{ "ollama" = { max_retries = 0 } }`
+    #[serde(default)]
+    pub provider_retry_policies: std::collections::HashMap<String, ProviderRetryPolicyConfig>,
+    /// Client-side request rate per provider, keyed like
+    /// `provider_retry_policies`.
+    /// Example: `{ "groq" = { requests_per_minute = 30 } }`
+    #[serde(default)]
+    pub provider_rate_limits: std::collections::HashMap<String, ProviderRateLimitConfig>,
     /// Fallback provider chain (e.g. `["anthropic", "openai"]`).
     #[serde(default)]
     pub fallback_providers: Vec<String>,
@@ -2418,6 +3680,12 @@ pub struct ReliabilityConfig {
     /// Example: `{ "claude-opus-4-20250514" = ["claude-sonnet-4-20250514", "gpt-4o"] }`
     #[serde(default)]
     pub model_fallbacks: std::collections::HashMap<String, Vec<String>>,
+    /// Model to request from each fallback provider, keyed by its
+    /// `fallback_providers` entry. Fallbacks without an entry receive the
+    /// primary model name.
+    /// Example: `{ "ollama" = "llama3.2:3b", "local-llm" = "qwen2.5-1.5b-instruct-q4_k_m.gguf" }`
+    #[serde(default)]
+    pub fallback_models: std::collections::HashMap<String, String>,
     /// Initial backoff for channel/daemon restarts.
     #[serde(default = "default_channel_backoff_secs")]
     pub channel_initial_backoff_secs: u64,
@@ -2430,6 +3698,10 @@ pub struct ReliabilityConfig {
     /// Max retries for cron job execution attempts.
     #[serde(default = "default_scheduler_retries")]
     pub scheduler_retries: u32,
+    /// How often the daemon probes provider reachability and latency, in
+    /// seconds. `0` disables probing.
+    #[serde(default = "default_provider_probe_interval_secs")]
+    pub provider_probe_interval_secs: u64,
 }
 
 fn default_provider_retries() -> u32 {
@@ -2440,6 +3712,10 @@ fn default_provider_backoff_ms() -> u64 {
     500
 }
 
+fn default_provider_max_backoff_ms() -> u64 {
+    10_000
+}
+
 fn default_channel_backoff_secs() -> u64 {
     2
 }
@@ -2456,22 +3732,58 @@ fn default_scheduler_retries() -> u32 {
     2
 }
 
+fn default_provider_probe_interval_secs() -> u64 {
+    300
+}
+
 impl Default for ReliabilityConfig {
     fn default() -> Self {
         Self {
             provider_retries: default_provider_retries(),
             provider_backoff_ms: default_provider_backoff_ms(),
+            provider_max_backoff_ms: default_provider_max_backoff_ms(),
+            provider_retry_policies: std::collections::HashMap::new(),
+            provider_rate_limits: std::collections::HashMap::new(),
             fallback_providers: Vec::new(),
             api_keys: Vec::new(),
             model_fallbacks: std::collections::HashMap::new(),
+            fallback_models: std::collections::HashMap::new(),
             channel_initial_backoff_secs: default_channel_backoff_secs(),
             channel_max_backoff_secs: default_channel_backoff_max_secs(),
             scheduler_poll_secs: default_scheduler_poll_secs(),
             scheduler_retries: default_scheduler_retries(),
+            provider_probe_interval_secs: default_provider_probe_interval_secs(),
         }
     }
 }
 
+/// Retry override for a single provider. Unset fields inherit the global
+/// `[reliability]` values.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct ProviderRetryPolicyConfig {
+    /// Retries before failing over (overrides `provider_retries`).
+    #[serde(default)]
+    pub max_retries: Option<u32>,
+    /// Base retry delay in ms (overrides `provider_backoff_ms`).
+    #[serde(default)]
+    pub backoff_ms: Option<u64>,
+    /// Retry delay cap in ms (overrides `provider_max_backoff_ms`).
+    #[serde(default)]
+    pub max_backoff_ms: Option<u64>,
+}
+
+/// Token bucket pacing requests to a single provider.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct ProviderRateLimitConfig {
+    /// Sustained requests per minute. `0` disables pacing.
+    #[serde(default)]
+    pub requests_per_minute: u32,
+    /// Requests allowed back to back before pacing starts. Defaults to ten
+    /// seconds' worth of `requests_per_minute`.
+    #[serde(default)]
+    pub burst: Option<u32>,
+}
+
 // ── Scheduler ────────────────────────────────────────────────────
 
 /// Scheduler configuration for periodic task execution (`[scheduler]` section).
@@ -2624,6 +3936,27 @@ pub struct HeartbeatConfig {
     /// Optional delivery recipient/chat identifier (required when `target` is set).
     #[serde(default, alias = "recipient")]
     pub to: Option<String>,
+    /// Cron expression for run times (for example `"0 8 * * *"` for every
+    /// morning at 8). Replaces `interval_minutes` when set.
+    #[serde(default)]
+    pub schedule: Option<String>,
+    /// IANA timezone for `schedule` and `quiet_hours`. Default: device local time.
+    #[serde(default)]
+    pub timezone: Option<String>,
+    /// Prompt sent for each task; `{task}`, `{date}` and `{time}` are filled in.
+    /// Default: `"[Heartbeat Task] {task}"`.
+    #[serde(default = "default_heartbeat_prompt_template")]
+    pub prompt_template: String,
+    /// Local time window without runs, for example `"22:00-07:00"`.
+    #[serde(default)]
+    pub quiet_hours: Option<String>,
+    /// Skip runs while the battery is below this percentage and not charging.
+    #[serde(default)]
+    pub min_battery_percent: Option<u8>,
+}
+
+fn default_heartbeat_prompt_template() -> String {
+    crate::heartbeat::policy::DEFAULT_PROMPT_TEMPLATE.into()
 }
 
 impl Default for HeartbeatConfig {
@@ -2634,6 +3967,11 @@ impl Default for HeartbeatConfig {
             message: None,
             target: None,
             to: None,
+            schedule: None,
+            timezone: None,
+            prompt_template: default_heartbeat_prompt_template(),
+            quiet_hours: None,
+            min_battery_percent: None,
         }
     }
 }
@@ -2671,7 +4009,7 @@ impl Default for CronConfig {
 /// Supported providers: `"none"` (default), `"cloudflare"`, `"tailscale"`, `"ngrok"`, `"custom"`.
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct TunnelConfig {
-    /// Tunnel provider: `"none"`, `"cloudflare"`, `"tailscale"`, `"ngrok"`, or `"custom"`. Default: `"none"`.
+    /// Tunnel provider: `"none"`, `"cloudflare"`, `"tailscale"`, `"ngrok"`, `"relay"`, or `"custom"`. Default: `"none"`.
     pub provider: String,
 
     /// Cloudflare Tunnel configuration (used when `provider = "cloudflare"`).
@@ -2686,6 +4024,10 @@ pub struct TunnelConfig {
     #[serde(default)]
     pub ngrok: Option<NgrokTunnelConfig>,
 
+    /// Outbound relay configuration (used when `provider = "relay"`).
+    #[serde(default)]
+    pub relay: Option<RelayTunnelConfig>,
+
     /// Custom tunnel command configuration (used when `provider = "custom"`).
     #[serde(default)]
     pub custom: Option<CustomTunnelConfig>,
@@ -2698,6 +4040,7 @@ impl Default for TunnelConfig {
             cloudflare: None,
             tailscale: None,
             ngrok: None,
+            relay: None,
             custom: None,
         }
     }
@@ -2726,6 +4069,17 @@ pub struct NgrokTunnelConfig {
     pub domain: Option<String>,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct RelayTunnelConfig {
+    /// WebSocket URL of the relay server, e.g. `wss://relay.example.com/connect`
+    pub url: String,
+    /// Bearer token the relay expects from this phone
+    pub token: String,
+    /// Public URL clients use, if the relay does not report one in its hello
+    #[serde(default)]
+    pub public_url: Option<String>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct CustomTunnelConfig {
     /// Command template to start the tunnel. Use {port} and {host} placeholders.
@@ -2810,6 +4164,22 @@ pub struct ChannelsConfig {
     /// Default: 300s for on-device LLMs (Ollama) which are slower than cloud APIs.
     #[serde(default = "default_channel_message_timeout_secs")]
     pub message_timeout_secs: u64,
+    /// Per-channel autonomy and tool overrides (`[channels_config.overrides.<key>]`).
+    /// Keys are a channel name (`telegram`) or a channel and chat
+    /// (`"telegram:-1001234567890"`); the chat entry wins over the channel one.
+    #[serde(default)]
+    pub overrides: HashMap<String, ChannelOverrideConfig>,
+}
+
+/// Security overrides for one channel or chat (`[channels_config.overrides.<key>]`).
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct ChannelOverrideConfig {
+    /// Autonomy level for messages here; the rest of `[autonomy]` still applies.
+    #[serde(default)]
+    pub autonomy: Option<AutonomyLevel>,
+    /// Tools the agent may use here. Unset keeps every tool; empty allows none.
+    #[serde(default)]
+    pub allowed_tools: Option<Vec<String>>,
 }
 
 impl ChannelsConfig {
@@ -2935,6 +4305,7 @@ impl Default for ChannelsConfig {
             nostr: None,
             clawdtalk: None,
             message_timeout_secs: default_channel_message_timeout_secs(),
+            overrides: HashMap::new(),
         }
     }
 }
@@ -2978,6 +4349,10 @@ pub struct TelegramConfig {
     /// Chat ID for proactive notifications (TelegramNotifyTool). Optional.
     #[serde(default)]
     pub notify_chat_id: Option<String>,
+    /// Named extra notification chats (e.g. `family = "-1001234567890"`).
+    /// TelegramNotifyTool may only target these and `notify_chat_id`.
+    #[serde(default)]
+    pub notify_targets: HashMap<String, String>,
 }
 
 impl ChannelConfig for TelegramConfig {
@@ -3007,6 +4382,13 @@ pub struct DiscordConfig {
     /// Other messages in the guild are silently ignored.
     #[serde(default)]
     pub mention_only: bool,
+    /// Channel ID for proactive notifications sent with the bot token (DiscordNotifyTool). Optional.
+    #[serde(default)]
+    pub notify_chat_id: Option<String>,
+    /// Incoming webhook URL for proactive notifications; takes precedence over
+    /// `notify_chat_id` and does not need the bot to join the channel. Optional.
+    #[serde(default)]
+    pub notify_webhook_url: Option<String>,
 }
 
 impl ChannelConfig for DiscordConfig {
@@ -3031,6 +4413,13 @@ pub struct SlackConfig {
     /// Allowed Slack user IDs. Empty = deny all.
     #[serde(default)]
     pub allowed_users: Vec<String>,
+    /// Channel ID for proactive notifications posted with the bot token (SlackNotifyTool). Optional.
+    #[serde(default)]
+    pub notify_channel_id: Option<String>,
+    /// Incoming webhook URL for proactive notifications; takes precedence over
+    /// `notify_channel_id`. Optional.
+    #[serde(default)]
+    pub notify_webhook_url: Option<String>,
 }
 
 impl ChannelConfig for SlackConfig {
@@ -3124,6 +4513,13 @@ pub struct MatrixConfig {
     pub room_id: String,
     /// Allowed Matrix user IDs. Empty = deny all.
     pub allowed_users: Vec<String>,
+    /// Room ID or alias for proactive notifications (MatrixNotifyTool). Optional.
+    #[serde(default)]
+    pub notify_room_id: Option<String>,
+    /// Pantalaimon (or compatible E2EE proxy) URL used for notifications instead of
+    /// `homeserver`, so messages to encrypted rooms are encrypted. Optional.
+    #[serde(default)]
+    pub notify_proxy_url: Option<String>,
 }
 
 impl ChannelConfig for MatrixConfig {
@@ -3798,6 +5194,10 @@ pub fn default_nostr_relays() -> Vec<String> {
 
 // ── Config impl ──────────────────────────────────────────────────
 
+fn default_schema_version() -> u32 {
+    super::migrations::CURRENT_SCHEMA_VERSION
+}
+
 impl Default for Config {
     fn default() -> Self {
         let home =
@@ -3807,11 +5207,14 @@ impl Default for Config {
         Self {
             workspace_dir: zeroclaw_dir.join("workspace"),
             config_path: zeroclaw_dir.join("config.toml"),
+            secret_refs: HashMap::new(),
+            low_resource_overrides: None,
             api_key: None,
             api_url: None,
             default_provider: Some("openrouter".to_string()),
             default_model: Some("anthropic/claude-sonnet-4.6".to_string()),
             model_providers: HashMap::new(),
+            model_aliases: HashMap::new(),
             default_temperature: 0.7,
             observability: ObservabilityConfig::default(),
             autonomy: AutonomyConfig::default(),
@@ -3839,6 +5242,7 @@ impl Default for Config {
             web_fetch: WebFetchConfig::default(),
             web_search: WebSearchConfig::default(),
             proxy: ProxyConfig::default(),
+            provider_http: HashMap::new(),
             identity: IdentityConfig::default(),
             cost: CostConfig::default(),
             peripherals: PeripheralsConfig::default(),
@@ -3847,6 +5251,19 @@ impl Default for Config {
             hardware: HardwareConfig::default(),
             query_classification: QueryClassificationConfig::default(),
             transcription: TranscriptionConfig::default(),
+            mcp: McpConfig::default(),
+            tools: HashMap::new(),
+            plugins: PluginsConfig::default(),
+            home_assistant: HomeAssistantConfig::default(),
+            eval: EvalConfig::default(),
+            gemini: GeminiConfig::default(),
+            azure_openai: AzureOpenAiConfig::default(),
+            openrouter: OpenRouterConfig::default(),
+            local_llm: LocalLlmConfig::default(),
+            profiles: HashMap::new(),
+            active_profile: None,
+            config_sync: ConfigSyncConfig::default(),
+            schema_version: super::migrations::CURRENT_SCHEMA_VERSION,
         }
     }
 }
@@ -3997,9 +5414,13 @@ pub(crate) async fn persist_active_workspace_config_dir(config_dir: &Path) -> Re
     Ok(())
 }
 
+fn has_config_file(dir: &Path) -> bool {
+    dir.join("config.toml").exists() || dir.join(super::format::JSON_FILE_NAME).exists()
+}
+
 pub(crate) fn resolve_config_dir_for_workspace(workspace_dir: &Path) -> (PathBuf, PathBuf) {
     let workspace_config_dir = workspace_dir.to_path_buf();
-    if workspace_config_dir.join("config.toml").exists() {
+    if has_config_file(&workspace_config_dir) {
         return (
             workspace_config_dir.clone(),
             workspace_config_dir.join("workspace"),
@@ -4010,7 +5431,7 @@ pub(crate) fn resolve_config_dir_for_workspace(workspace_dir: &Path) -> (PathBuf
         .parent()
         .map(|parent| parent.join(".zeroclaw"));
     if let Some(legacy_dir) = legacy_config_dir {
-        if legacy_dir.join("config.toml").exists() {
+        if has_config_file(&legacy_dir) {
             return (legacy_dir, workspace_config_dir);
         }
 
@@ -4103,63 +5524,179 @@ async fn resolve_runtime_config_dirs(
     ))
 }
 
+/// Secret store plus the credential references seen on load.
+struct SecretFields {
+    store: crate::security::SecretStore,
+    /// Resolved value → the `${VAR}` or `file://` reference it came from.
+    refs: HashMap<String, String>,
+}
+
 fn decrypt_optional_secret(
-    store: &crate::security::SecretStore,
+    fields: &mut SecretFields,
     value: &mut Option<String>,
     field_name: &str,
 ) -> Result<()> {
-    if let Some(raw) = value.clone() {
-        if crate::security::SecretStore::is_encrypted(&raw) {
-            *value = Some(
-                store
-                    .decrypt(&raw)
-                    .with_context(|| format!("Failed to decrypt {field_name}"))?,
-            );
-        }
+    if let Some(raw) = value.as_mut() {
+        decrypt_secret(fields, raw, field_name)?;
+    }
+    Ok(())
+}
+
+fn decrypt_secret(fields: &mut SecretFields, value: &mut String, field_name: &str) -> Result<()> {
+    if crate::security::SecretStore::is_encrypted(value) {
+        *value = fields
+            .store
+            .decrypt(value)
+            .with_context(|| format!("Failed to decrypt {field_name}"))?;
+    } else if let Some(resolved) = super::secret_ref::resolve(value)
+        .with_context(|| format!("Failed to resolve {field_name}"))?
+    {
+        let reference = std::mem::replace(value, resolved.clone());
+        fields.refs.insert(resolved, reference);
+    }
+    Ok(())
+}
+
+fn encrypt_optional_secret(
+    fields: &SecretFields,
+    value: &mut Option<String>,
+    field_name: &str,
+) -> Result<()> {
+    if let Some(raw) = value.as_mut() {
+        encrypt_secret(fields, raw, field_name)?;
+    }
+    Ok(())
+}
+
+fn encrypt_secret(fields: &SecretFields, value: &mut String, field_name: &str) -> Result<()> {
+    if let Some(reference) = fields.refs.get(value.as_str()) {
+        value.clone_from(reference);
+    } else if !crate::security::SecretStore::is_encrypted(value)
+        && !super::secret_ref::is_reference(value)
+    {
+        *value = fields
+            .store
+            .encrypt(value)
+            .with_context(|| format!("Failed to encrypt {field_name}"))?;
+    }
+    Ok(())
+}
+
+fn decrypt_channel_secrets(fields: &mut SecretFields, channels: &mut ChannelsConfig) -> Result<()> {
+    if let Some(ref mut tg) = channels.telegram {
+        decrypt_secret(
+            fields,
+            &mut tg.bot_token,
+            "channels_config.telegram.bot_token",
+        )?;
+    }
+
+    if let Some(ref mut dc) = channels.discord {
+        decrypt_secret(
+            fields,
+            &mut dc.bot_token,
+            "channels_config.discord.bot_token",
+        )?;
+        decrypt_optional_secret(
+            fields,
+            &mut dc.notify_webhook_url,
+            "channels_config.discord.notify_webhook_url",
+        )?;
+    }
+
+    if let Some(ref mut sl) = channels.slack {
+        decrypt_secret(fields, &mut sl.bot_token, "channels_config.slack.bot_token")?;
+        decrypt_optional_secret(fields, &mut sl.app_token, "channels_config.slack.app_token")?;
+        decrypt_optional_secret(
+            fields,
+            &mut sl.notify_webhook_url,
+            "channels_config.slack.notify_webhook_url",
+        )?;
+    }
+
+    if let Some(ref mut mm) = channels.mattermost {
+        decrypt_secret(
+            fields,
+            &mut mm.bot_token,
+            "channels_config.mattermost.bot_token",
+        )?;
+    }
+
+    if let Some(ref mut mx) = channels.matrix {
+        decrypt_secret(
+            fields,
+            &mut mx.access_token,
+            "channels_config.matrix.access_token",
+        )?;
     }
-    Ok(())
-}
 
-fn decrypt_secret(
-    store: &crate::security::SecretStore,
-    value: &mut String,
-    field_name: &str,
-) -> Result<()> {
-    if crate::security::SecretStore::is_encrypted(value) {
-        *value = store
-            .decrypt(value)
-            .with_context(|| format!("Failed to decrypt {field_name}"))?;
+    if let Some(ref mut ns) = channels.nostr {
+        decrypt_secret(
+            fields,
+            &mut ns.private_key,
+            "channels_config.nostr.private_key",
+        )?;
     }
+
     Ok(())
 }
 
-fn encrypt_optional_secret(
-    store: &crate::security::SecretStore,
-    value: &mut Option<String>,
-    field_name: &str,
-) -> Result<()> {
-    if let Some(raw) = value.clone() {
-        if !crate::security::SecretStore::is_encrypted(&raw) {
-            *value = Some(
-                store
-                    .encrypt(&raw)
-                    .with_context(|| format!("Failed to encrypt {field_name}"))?,
-            );
-        }
+fn encrypt_channel_secrets(fields: &SecretFields, channels: &mut ChannelsConfig) -> Result<()> {
+    if let Some(ref mut tg) = channels.telegram {
+        encrypt_secret(
+            fields,
+            &mut tg.bot_token,
+            "channels_config.telegram.bot_token",
+        )?;
     }
-    Ok(())
-}
 
-fn encrypt_secret(
-    store: &crate::security::SecretStore,
-    value: &mut String,
-    field_name: &str,
-) -> Result<()> {
-    if !crate::security::SecretStore::is_encrypted(value) {
-        *value = store
-            .encrypt(value)
-            .with_context(|| format!("Failed to encrypt {field_name}"))?;
+    if let Some(ref mut dc) = channels.discord {
+        encrypt_secret(
+            fields,
+            &mut dc.bot_token,
+            "channels_config.discord.bot_token",
+        )?;
+        encrypt_optional_secret(
+            fields,
+            &mut dc.notify_webhook_url,
+            "channels_config.discord.notify_webhook_url",
+        )?;
+    }
+
+    if let Some(ref mut sl) = channels.slack {
+        encrypt_secret(fields, &mut sl.bot_token, "channels_config.slack.bot_token")?;
+        encrypt_optional_secret(fields, &mut sl.app_token, "channels_config.slack.app_token")?;
+        encrypt_optional_secret(
+            fields,
+            &mut sl.notify_webhook_url,
+            "channels_config.slack.notify_webhook_url",
+        )?;
+    }
+
+    if let Some(ref mut mm) = channels.mattermost {
+        encrypt_secret(
+            fields,
+            &mut mm.bot_token,
+            "channels_config.mattermost.bot_token",
+        )?;
+    }
+
+    if let Some(ref mut mx) = channels.matrix {
+        encrypt_secret(
+            fields,
+            &mut mx.access_token,
+            "channels_config.matrix.access_token",
+        )?;
+    }
+
+    if let Some(ref mut ns) = channels.nostr {
+        encrypt_secret(
+            fields,
+            &mut ns.private_key,
+            "channels_config.nostr.private_key",
+        )?;
     }
+
     Ok(())
 }
 
@@ -4230,7 +5767,11 @@ impl Config {
         let (zeroclaw_dir, workspace_dir, resolution_source) =
             resolve_runtime_config_dirs(&default_zeroclaw_dir, &default_workspace_dir).await?;
 
-        let config_path = zeroclaw_dir.join("config.toml");
+        let mut config_path = zeroclaw_dir.join("config.toml");
+        let json_path = zeroclaw_dir.join(super::format::JSON_FILE_NAME);
+        if !config_path.exists() && json_path.exists() {
+            config_path = json_path;
+        }
 
         fs::create_dir_all(&zeroclaw_dir)
             .await
@@ -4257,71 +5798,10 @@ impl Config {
                 }
             }
 
-            let contents = fs::read_to_string(&config_path)
-                .await
-                .context("Failed to read config file")?;
-
-            // Track ignored/unknown config keys to warn users about silent misconfigurations
-            // (e.g., using [providers.ollama] which doesn't exist instead of top-level api_url)
-            let mut ignored_paths: Vec<String> = Vec::new();
-            let mut config: Config = serde_ignored::deserialize(
-                toml::de::Deserializer::parse(&contents).context("Failed to parse config file")?,
-                |path| {
-                    ignored_paths.push(path.to_string());
-                },
-            )
-            .context("Failed to deserialize config file")?;
-
-            // Warn about each unknown config key
-            for path in ignored_paths {
-                tracing::warn!(
-                    "Unknown config key ignored: \"{}\". Check config.toml for typos or deprecated options.",
-                    path
-                );
-            }
-            // Set computed paths that are skipped during serialization
-            config.config_path = config_path.clone();
-            config.workspace_dir = workspace_dir;
-            let store = crate::security::SecretStore::new(&zeroclaw_dir, config.secrets.encrypt);
-            decrypt_optional_secret(&store, &mut config.api_key, "config.api_key")?;
-            decrypt_optional_secret(
-                &store,
-                &mut config.composio.api_key,
-                "config.composio.api_key",
-            )?;
-
-            decrypt_optional_secret(
-                &store,
-                &mut config.browser.computer_use.api_key,
-                "config.browser.computer_use.api_key",
-            )?;
-
-            decrypt_optional_secret(
-                &store,
-                &mut config.web_search.brave_api_key,
-                "config.web_search.brave_api_key",
-            )?;
-
-            decrypt_optional_secret(
-                &store,
-                &mut config.storage.provider.config.db_url,
-                "config.storage.provider.config.db_url",
-            )?;
-
-            for agent in config.agents.values_mut() {
-                decrypt_optional_secret(&store, &mut agent.api_key, "config.agents.*.api_key")?;
-            }
-
-            if let Some(ref mut ns) = config.channels_config.nostr {
-                decrypt_secret(
-                    &store,
-                    &mut ns.private_key,
-                    "config.channels_config.nostr.private_key",
-                )?;
-            }
-
+            let mut config = Self::read_config_file(&config_path, workspace_dir).await?;
             config.apply_env_overrides();
             config.validate()?;
+            super::low_resource::apply(&mut config);
             tracing::info!(
                 path = %config.config_path.display(),
                 workspace = %config.workspace_dir.display(),
@@ -4345,6 +5825,7 @@ impl Config {
 
             config.apply_env_overrides();
             config.validate()?;
+            super::low_resource::apply(&mut config);
             tracing::info!(
                 path = %config.config_path.display(),
                 workspace = %config.workspace_dir.display(),
@@ -4356,6 +5837,218 @@ impl Config {
         }
     }
 
+    /// Re-read the config file at this config's path, keeping its workspace.
+    ///
+    /// Used by the daemon to hot-reload settings; env overrides are applied
+    /// and the result is validated just like at startup.
+    pub async fn reload(&self) -> Result<Self> {
+        Self::load_from(&self.config_path, &self.workspace_dir).await
+    }
+
+    /// Load, decrypt and validate the config file at `config_path`.
+    pub async fn load_from(config_path: &Path, workspace_dir: &Path) -> Result<Self> {
+        let mut config = Self::read_config_file(config_path, workspace_dir.to_path_buf()).await?;
+        config.apply_env_overrides();
+        config.validate()?;
+        super::low_resource::apply(&mut config);
+        Ok(config)
+    }
+
+    /// Names of the configured profiles, sorted.
+    pub fn profile_names(&self) -> Vec<String> {
+        let mut names: Vec<String> = self.profiles.keys().cloned().collect();
+        names.sort();
+        names
+    }
+
+    /// Apply profile `name` to the top-level settings.
+    ///
+    /// The settings being replaced are kept under the previously active
+    /// profile (or [`DEFAULT_PROFILE_NAME`]) so switching back restores them.
+    /// Only updates memory; see [`Config::activate_profile`] to persist.
+    pub fn switch_profile(&mut self, name: &str) -> Result<()> {
+        let name = name.trim();
+        let Some(target) = self.profiles.get(name).cloned() else {
+            let available = self.profile_names();
+            if available.is_empty() {
+                anyhow::bail!("Unknown profile '{name}': no [profiles] are configured");
+            }
+            anyhow::bail!(
+                "Unknown profile '{name}'. Available: {}",
+                available.join(", ")
+            );
+        };
+
+        let mut next = self.clone();
+        let previous = next
+            .active_profile
+            .clone()
+            .unwrap_or_else(|| DEFAULT_PROFILE_NAME.to_string());
+        if previous != name {
+            let snapshot = ConfigProfile {
+                default_provider: next.default_provider.clone(),
+                default_model: next.default_model.clone(),
+                default_temperature: Some(next.default_temperature),
+                autonomy: Some(next.autonomy.clone()),
+                channels_config: Some(next.channels_config.clone()),
+            };
+            next.profiles.insert(previous, snapshot);
+        }
+
+        if let Some(provider) = target.default_provider {
+            next.default_provider = Some(provider);
+        }
+        if let Some(model) = target.default_model {
+            next.default_model = Some(model);
+        }
+        if let Some(temperature) = target.default_temperature {
+            next.default_temperature = temperature;
+        }
+        if let Some(autonomy) = target.autonomy {
+            next.autonomy = autonomy;
+        }
+        if let Some(channels) = target.channels_config {
+            next.channels_config = channels;
+        }
+        next.active_profile = Some(name.to_string());
+        next.validate()
+            .with_context(|| format!("Profile '{name}' is invalid"))?;
+
+        *self = next;
+        Ok(())
+    }
+
+    /// Switch to profile `name` and save the result to disk.
+    pub async fn activate_profile(&mut self, name: &str, source: ChangeSource) -> Result<()> {
+        let mut next = self.clone();
+        next.switch_profile(name)?;
+        next.save_from(source).await?;
+        *self = next;
+        Ok(())
+    }
+
+    /// Parse `config_path` and decrypt its stored secrets.
+    pub(super) async fn read_config_file(
+        config_path: &Path,
+        workspace_dir: PathBuf,
+    ) -> Result<Self> {
+        let zeroclaw_dir = config_path.parent().unwrap_or_else(|| Path::new("."));
+        let contents = fs::read_to_string(config_path)
+            .await
+            .context("Failed to read config file")?;
+        let format = ConfigFormat::detect(config_path, &contents);
+        let contents = match format {
+            ConfigFormat::Toml => super::migrations::upgrade_file(config_path, contents).await?,
+            ConfigFormat::Json => {
+                // Migrated in memory only; the next save() writes JSON again.
+                let mut table = super::format::json_to_table(&contents)?;
+                super::migrations::migrate(&mut table);
+                toml::to_string(&table).context("Failed to convert JSON config")?
+            }
+        };
+
+        // Unknown keys are warned about and ignored (e.g. [providers.ollama],
+        // which doesn't exist, instead of top-level api_url); syntax and type
+        // errors fail the load with their line and column.
+        let (config, mut diagnostics) = super::diagnostics::parse(&contents);
+        if format == ConfigFormat::Json {
+            // Positions point into the converted TOML, not the JSON file.
+            for diagnostic in &mut diagnostics {
+                diagnostic.line = None;
+                diagnostic.column = None;
+            }
+        }
+        let file = config_path.display();
+        for diagnostic in &diagnostics {
+            if diagnostic.severity == super::diagnostics::Severity::Warning {
+                tracing::warn!("{file}:{diagnostic}");
+            }
+        }
+        let Some(mut config) = config else {
+            let errors: Vec<String> = diagnostics
+                .iter()
+                .filter(|diagnostic| diagnostic.severity == super::diagnostics::Severity::Error)
+                .map(|diagnostic| format!("{file}:{diagnostic}"))
+                .collect();
+            anyhow::bail!("Failed to parse config file:\n{}", errors.join("\n"));
+        };
+
+        // Set computed paths that are skipped during serialization
+        config.config_path = config_path.to_path_buf();
+        config.workspace_dir = workspace_dir;
+        let mut fields = SecretFields {
+            store: crate::security::SecretStore::from_config(zeroclaw_dir, &config.secrets),
+            refs: HashMap::new(),
+        };
+        decrypt_optional_secret(&mut fields, &mut config.api_key, "config.api_key")?;
+        decrypt_optional_secret(
+            &mut fields,
+            &mut config.composio.api_key,
+            "config.composio.api_key",
+        )?;
+
+        decrypt_optional_secret(
+            &mut fields,
+            &mut config.home_assistant.token,
+            "config.home_assistant.token",
+        )?;
+
+        decrypt_optional_secret(
+            &mut fields,
+            &mut config.azure_openai.client_secret,
+            "config.azure_openai.client_secret",
+        )?;
+
+        decrypt_optional_secret(
+            &mut fields,
+            &mut config.browser.computer_use.api_key,
+            "config.browser.computer_use.api_key",
+        )?;
+
+        decrypt_optional_secret(
+            &mut fields,
+            &mut config.web_search.brave_api_key,
+            "config.web_search.brave_api_key",
+        )?;
+
+        decrypt_optional_secret(
+            &mut fields,
+            &mut config.web_search.tavily_api_key,
+            "config.web_search.tavily_api_key",
+        )?;
+
+        decrypt_optional_secret(
+            &mut fields,
+            &mut config.storage.provider.config.db_url,
+            "config.storage.provider.config.db_url",
+        )?;
+
+        if let Some(relay) = config.tunnel.relay.as_mut() {
+            decrypt_secret(&mut fields, &mut relay.token, "config.tunnel.relay.token")?;
+        }
+
+        for hook in config.gateway.hooks.values_mut() {
+            decrypt_optional_secret(
+                &mut fields,
+                &mut hook.secret,
+                "config.gateway.hooks.*.secret",
+            )?;
+        }
+
+        for agent in config.agents.values_mut() {
+            decrypt_optional_secret(&mut fields, &mut agent.api_key, "config.agents.*.api_key")?;
+        }
+
+        decrypt_channel_secrets(&mut fields, &mut config.channels_config)?;
+        for profile in config.profiles.values_mut() {
+            if let Some(channels) = profile.channels_config.as_mut() {
+                decrypt_channel_secrets(&mut fields, channels)?;
+            }
+        }
+        config.secret_refs = fields.refs;
+        Ok(config)
+    }
+
     fn lookup_model_provider_profile(
         &self,
         provider_name: &str,
@@ -4440,6 +6133,16 @@ impl Config {
         }
     }
 
+    /// Model name for `model`, following `[model_aliases]`. Names that are not
+    /// aliases are returned unchanged.
+    pub fn resolve_model_alias(&self, model: &str) -> String {
+        let model = model.trim();
+        self.model_aliases
+            .get(model)
+            .map_or(model, |target| target.trim())
+            .to_string()
+    }
+
     /// Validate configuration values that would cause runtime failures.
     ///
     /// Called after TOML deserialization and env-override application to catch
@@ -4449,6 +6152,21 @@ impl Config {
         if self.gateway.host.trim().is_empty() {
             anyhow::bail!("gateway.host must not be empty");
         }
+        let tls = &self.gateway.tls;
+        if tls.cert_path.is_some() != tls.key_path.is_some() {
+            anyhow::bail!("gateway.tls.cert_path and gateway.tls.key_path must be set together");
+        }
+        let mut user_tokens: HashMap<String, &str> = HashMap::new();
+        for (name, user) in &self.gateway.users {
+            for id in &user.tokens {
+                let id = id.trim().to_ascii_lowercase();
+                if let Some(other) = user_tokens.insert(id.clone(), name) {
+                    anyhow::bail!(
+                        "gateway.users: token {id} is assigned to both '{other}' and '{name}'"
+                    );
+                }
+            }
+        }
 
         // Autonomy
         if self.autonomy.max_actions_per_hour == 0 {
@@ -4499,6 +6217,25 @@ impl Config {
             anyhow::bail!("security.estop.state_file must not be empty");
         }
 
+        // Channel overrides
+        for (key, channel_override) in &self.channels_config.overrides {
+            let channel = key
+                .split_once(':')
+                .map_or(key.as_str(), |(channel, _)| channel);
+            if channel.trim().is_empty() {
+                anyhow::bail!(
+                    "channels_config.overrides key {key:?} must start with a channel name"
+                );
+            }
+            if let Some(tools) = &channel_override.allowed_tools {
+                if tools.iter().any(|tool| tool.trim().is_empty()) {
+                    anyhow::bail!(
+                        "channels_config.overrides.{key}.allowed_tools must not contain empty names"
+                    );
+                }
+            }
+        }
+
         // Scheduler
         if self.scheduler.max_concurrent == 0 {
             anyhow::bail!("scheduler.max_concurrent must be greater than 0");
@@ -4507,6 +6244,137 @@ impl Config {
             anyhow::bail!("scheduler.max_tasks must be greater than 0");
         }
 
+        // Low-resource preset
+        let low_resource = &self.runtime.low_resource;
+        if low_resource.max_history_messages == 0
+            || low_resource.max_concurrent_tasks == 0
+            || low_resource.max_in_flight_messages == 0
+            || low_resource.local_llm_context_size == 0
+        {
+            anyhow::bail!("runtime.low_resource limits must be greater than 0");
+        }
+
+        // Heartbeat
+        crate::heartbeat::policy::HeartbeatPlan::from_config(&self.heartbeat)?;
+
+        // Model aliases
+        for (alias, target) in &self.model_aliases {
+            if alias.trim().is_empty() || target.trim().is_empty() {
+                anyhow::bail!("model_aliases entries must have a name and a model ({alias:?})");
+            }
+            if self.model_aliases.contains_key(target.trim()) {
+                anyhow::bail!(
+                    "model_aliases.{alias} points at another alias ({target}); use the model name"
+                );
+            }
+        }
+
+        // Tool policies
+        let mut prompt_names = HashMap::new();
+        for (tool, policy) in &self.tools {
+            if tool.trim().is_empty() {
+                anyhow::bail!("tools keys must be tool names");
+            }
+            if let Some(name) = &policy.name {
+                if name.is_empty()
+                    || name.len() > 64
+                    || !name
+                        .chars()
+                        .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+                {
+                    anyhow::bail!(
+                        "tools.{tool}.name is invalid ({name}); expected 1-64 characters of [A-Za-z0-9_-]"
+                    );
+                }
+                if let Some(other) = prompt_names.insert(name.as_str(), tool.as_str()) {
+                    anyhow::bail!("tools.{tool}.name and tools.{other}.name are both {name:?}");
+                }
+            }
+            for (param, constraint) in &policy.params {
+                if let (Some(min), Some(max)) = (constraint.min, constraint.max) {
+                    if min > max {
+                        anyhow::bail!("tools.{tool}.params.{param}.min must not exceed max");
+                    }
+                }
+            }
+        }
+
+        // Gemini safety settings
+        for (i, setting) in self.gemini.safety_settings.iter().enumerate() {
+            if !GEMINI_SAFETY_CATEGORIES.contains(&setting.category.as_str()) {
+                anyhow::bail!(
+                    "gemini.safety_settings[{i}].category is invalid ({}); expected one of: {}",
+                    setting.category,
+                    GEMINI_SAFETY_CATEGORIES.join(", ")
+                );
+            }
+            if !GEMINI_SAFETY_THRESHOLDS.contains(&setting.threshold.as_str()) {
+                anyhow::bail!(
+                    "gemini.safety_settings[{i}].threshold is invalid ({}); expected one of: {}",
+                    setting.threshold,
+                    GEMINI_SAFETY_THRESHOLDS.join(", ")
+                );
+            }
+        }
+
+        // Azure OpenAI
+        if self.azure_openai.api_version.trim().is_empty() {
+            anyhow::bail!("azure_openai.api_version must not be empty");
+        }
+        let aad_fields = [
+            &self.azure_openai.tenant_id,
+            &self.azure_openai.client_id,
+            &self.azure_openai.client_secret,
+        ];
+        let aad_set = aad_fields
+            .iter()
+            .filter(|field| field.as_deref().is_some_and(|v| !v.trim().is_empty()))
+            .count();
+        if aad_set != 0 && aad_set != aad_fields.len() {
+            anyhow::bail!(
+                "azure_openai.tenant_id, client_id and client_secret must be set together"
+            );
+        }
+
+        // OpenRouter routing
+        if self
+            .openrouter
+            .order
+            .iter()
+            .any(|slug| slug.trim().is_empty())
+        {
+            anyhow::bail!("openrouter.order must not contain empty provider names");
+        }
+        for (key, price) in [
+            ("max_prompt_price", self.openrouter.max_prompt_price),
+            ("max_completion_price", self.openrouter.max_completion_price),
+        ] {
+            if price.is_some_and(|p| !p.is_finite() || p < 0.0) {
+                anyhow::bail!("openrouter.{key} must be a non-negative number");
+            }
+        }
+
+        // Local LLM
+        if self.local_llm.context_size == 0 {
+            anyhow::bail!("local_llm.context_size must be greater than 0");
+        }
+        if self.local_llm.batch_size == 0 {
+            anyhow::bail!("local_llm.batch_size must be greater than 0");
+        }
+        if self.local_llm.max_tokens >= self.local_llm.context_size {
+            anyhow::bail!("local_llm.max_tokens must be smaller than local_llm.context_size");
+        }
+        if !LOCAL_LLM_ACCELERATORS.contains(&self.local_llm.accelerator.as_str()) {
+            anyhow::bail!(
+                "local_llm.accelerator is invalid ({}); expected one of: {}",
+                self.local_llm.accelerator,
+                LOCAL_LLM_ACCELERATORS.join(", ")
+            );
+        }
+        if self.local_llm.accelerator_timeout_secs == 0 {
+            anyhow::bail!("local_llm.accelerator_timeout_secs must be greater than 0");
+        }
+
         // Model routes
         for (i, route) in self.model_routes.iter().enumerate() {
             if route.hint.trim().is_empty() {
@@ -4601,8 +6469,47 @@ impl Config {
             }
         }
 
+        // Config sync
+        if self.config_sync.enabled {
+            let sync = &self.config_sync;
+            let url = sync.url.as_deref().map(str::trim).filter(|v| !v.is_empty());
+            let repo = sync
+                .git_repo
+                .as_deref()
+                .map(str::trim)
+                .filter(|v| !v.is_empty());
+            match (url, repo) {
+                (Some(_), Some(_)) => {
+                    anyhow::bail!("config_sync.url and config_sync.git_repo cannot both be set")
+                }
+                (None, None) => {
+                    anyhow::bail!(
+                        "config_sync.enabled requires config_sync.url or config_sync.git_repo"
+                    )
+                }
+                (Some(url), None) if !url.starts_with("https://") => {
+                    anyhow::bail!("config_sync.url must use https")
+                }
+                _ => {}
+            }
+            let git_path = Path::new(sync.git_path.trim());
+            if sync.git_path.trim().is_empty()
+                || git_path.is_absolute()
+                || git_path
+                    .components()
+                    .any(|part| matches!(part, std::path::Component::ParentDir))
+            {
+                anyhow::bail!("config_sync.git_path must be a relative path inside the repository");
+            }
+            super::sync::decode_public_key(&sync.public_key)
+                .context("config_sync.public_key is invalid")?;
+        }
+
         // Proxy (delegate to existing validation)
         self.proxy.validate()?;
+        for (provider, http) in &self.provider_http {
+            http.validate(provider)?;
+        }
 
         Ok(())
     }
@@ -4780,6 +6687,16 @@ impl Config {
             }
         }
 
+        // Tavily API key: ZEROCLAW_TAVILY_API_KEY or TAVILY_API_KEY
+        if let Ok(api_key) =
+            std::env::var("ZEROCLAW_TAVILY_API_KEY").or_else(|_| std::env::var("TAVILY_API_KEY"))
+        {
+            let api_key = api_key.trim();
+            if !api_key.is_empty() {
+                self.web_search.tavily_api_key = Some(api_key.to_string());
+            }
+        }
+
         // Web search max results: ZEROCLAW_WEB_SEARCH_MAX_RESULTS or WEB_SEARCH_MAX_RESULTS
         if let Ok(max_results) = std::env::var("ZEROCLAW_WEB_SEARCH_MAX_RESULTS")
             .or_else(|_| std::env::var("WEB_SEARCH_MAX_RESULTS"))
@@ -4894,56 +6811,113 @@ impl Config {
         }
 
         set_runtime_proxy_config(self.proxy.clone());
+        set_runtime_provider_http_config(self.provider_http.clone());
+        crate::runtime::network::set_network_policy(self.runtime.network.clone());
+    }
+
+    /// [`Config::save`] on behalf of `source`, recording the keys that
+    /// changed on disk in the config audit log.
+    pub async fn save_from(&self, source: ChangeSource) -> Result<()> {
+        let before = self.read_saved().await;
+        self.save().await?;
+        let (Some(before), Some(after)) = (before, self.read_saved().await) else {
+            return Ok(());
+        };
+        if let Err(e) = super::audit::diff(&before, &after)
+            .and_then(|changes| super::audit::record(&self.workspace_dir, source, changes))
+        {
+            tracing::warn!("Failed to record config change: {e:#}");
+        }
+        Ok(())
+    }
+
+    /// The config file as it is on disk, if it exists and can be read.
+    async fn read_saved(&self) -> Option<Self> {
+        if !self.config_path.exists() {
+            return None;
+        }
+        Self::read_config_file(&self.config_path, self.workspace_dir.clone())
+            .await
+            .ok()
     }
 
     pub async fn save(&self) -> Result<()> {
         // Encrypt secrets before serialization
         let mut config_to_save = self.clone();
+        super::low_resource::restore(&mut config_to_save);
         let zeroclaw_dir = self
             .config_path
             .parent()
             .context("Config path must have a parent directory")?;
-        let store = crate::security::SecretStore::new(zeroclaw_dir, self.secrets.encrypt);
+        let fields = SecretFields {
+            store: crate::security::SecretStore::from_config(zeroclaw_dir, &self.secrets),
+            refs: self.secret_refs.clone(),
+        };
 
-        encrypt_optional_secret(&store, &mut config_to_save.api_key, "config.api_key")?;
+        encrypt_optional_secret(&fields, &mut config_to_save.api_key, "config.api_key")?;
         encrypt_optional_secret(
-            &store,
+            &fields,
             &mut config_to_save.composio.api_key,
             "config.composio.api_key",
         )?;
 
         encrypt_optional_secret(
-            &store,
+            &fields,
+            &mut config_to_save.home_assistant.token,
+            "config.home_assistant.token",
+        )?;
+
+        encrypt_optional_secret(
+            &fields,
+            &mut config_to_save.azure_openai.client_secret,
+            "config.azure_openai.client_secret",
+        )?;
+
+        encrypt_optional_secret(
+            &fields,
             &mut config_to_save.browser.computer_use.api_key,
             "config.browser.computer_use.api_key",
         )?;
 
         encrypt_optional_secret(
-            &store,
+            &fields,
             &mut config_to_save.web_search.brave_api_key,
             "config.web_search.brave_api_key",
         )?;
 
         encrypt_optional_secret(
-            &store,
+            &fields,
+            &mut config_to_save.web_search.tavily_api_key,
+            "config.web_search.tavily_api_key",
+        )?;
+
+        encrypt_optional_secret(
+            &fields,
             &mut config_to_save.storage.provider.config.db_url,
             "config.storage.provider.config.db_url",
         )?;
 
+        if let Some(relay) = config_to_save.tunnel.relay.as_mut() {
+            encrypt_secret(&fields, &mut relay.token, "config.tunnel.relay.token")?;
+        }
+
+        for hook in config_to_save.gateway.hooks.values_mut() {
+            encrypt_optional_secret(&fields, &mut hook.secret, "config.gateway.hooks.*.secret")?;
+        }
+
         for agent in config_to_save.agents.values_mut() {
-            encrypt_optional_secret(&store, &mut agent.api_key, "config.agents.*.api_key")?;
+            encrypt_optional_secret(&fields, &mut agent.api_key, "config.agents.*.api_key")?;
         }
 
-        if let Some(ref mut ns) = config_to_save.channels_config.nostr {
-            encrypt_secret(
-                &store,
-                &mut ns.private_key,
-                "config.channels_config.nostr.private_key",
-            )?;
+        encrypt_channel_secrets(&fields, &mut config_to_save.channels_config)?;
+        for profile in config_to_save.profiles.values_mut() {
+            if let Some(channels) = profile.channels_config.as_mut() {
+                encrypt_channel_secrets(&fields, channels)?;
+            }
         }
 
-        let toml_str =
-            toml::to_string_pretty(&config_to_save).context("Failed to serialize config")?;
+        let format = ConfigFormat::for_existing(&self.config_path).await;
+        let serialized = format.serialize(&config_to_save)?;
 
         let parent_dir = self
             .config_path
@@ -4977,7 +6951,7 @@ impl Config {
                 )
             })?;
         temp_file
-            .write_all(toml_str.as_bytes())
+            .write_all(serialized.as_bytes())
             .await
             .context("Failed to write temporary config contents")?;
         temp_file
@@ -5179,7 +7153,7 @@ mod tests {
     #[test]
     async fn runtime_config_default() {
         let r = RuntimeConfig::default();
-        assert_eq!(r.kind, "native");
+        assert_eq!(r.kind, "auto");
         assert_eq!(r.docker.image, "alpine:3.20");
         assert_eq!(r.docker.network, "none");
         assert_eq!(r.docker.memory_limit_mb, Some(512));
@@ -5284,11 +7258,14 @@ default_temperature = 0.7
         let config = Config {
             workspace_dir: PathBuf::from("/tmp/test/workspace"),
             config_path: PathBuf::from("/tmp/test/config.toml"),
+            secret_refs: HashMap::new(),
+            low_resource_overrides: None,
             api_key: Some("sk-test-key".into()),
             api_url: None,
             default_provider: Some("openrouter".into()),
             default_model: Some("gpt-4o".into()),
             model_providers: HashMap::new(),
+            model_aliases: HashMap::new(),
             default_temperature: 0.5,
             observability: ObservabilityConfig {
                 backend: "log".into(),
@@ -5308,6 +7285,10 @@ default_temperature = 0.7
                 always_ask: vec![],
                 allowed_roots: vec![],
                 non_cli_excluded_tools: vec![],
+                allowed_sms_numbers: vec![],
+                allow_location: false,
+                denied_domains: vec![],
+                max_scheduled_jobs: 20,
             },
             security: SecurityConfig::default(),
             runtime: RuntimeConfig {
@@ -5331,6 +7312,7 @@ default_temperature = 0.7
                 message: Some("Check London time".into()),
                 target: Some("telegram".into()),
                 to: Some("123456".into()),
+                ..HeartbeatConfig::default()
             },
             cron: CronConfig::default(),
             channels_config: ChannelsConfig {
@@ -5342,8 +7324,8 @@ default_temperature = 0.7
                     draft_update_interval_ms: default_draft_update_interval_ms(),
                     interrupt_on_new_message: false,
                     mention_only: false,
-            notify_chat_id: None,
                     notify_chat_id: None,
+                    notify_targets: HashMap::new(),
                 }),
                 discord: None,
                 slack: None,
@@ -5365,6 +7347,7 @@ default_temperature = 0.7
                 nostr: None,
                 clawdtalk: None,
                 message_timeout_secs: 300,
+                overrides: HashMap::new(),
             },
             memory: MemoryConfig::default(),
             storage: StorageConfig::default(),
@@ -5378,6 +7361,7 @@ default_temperature = 0.7
             web_fetch: WebFetchConfig::default(),
             web_search: WebSearchConfig::default(),
             proxy: ProxyConfig::default(),
+            provider_http: HashMap::new(),
             agent: AgentConfig::default(),
             identity: IdentityConfig::default(),
             cost: CostConfig::default(),
@@ -5386,6 +7370,19 @@ default_temperature = 0.7
             hooks: HooksConfig::default(),
             hardware: HardwareConfig::default(),
             transcription: TranscriptionConfig::default(),
+            mcp: McpConfig::default(),
+            tools: HashMap::new(),
+            plugins: PluginsConfig::default(),
+            home_assistant: HomeAssistantConfig::default(),
+            eval: EvalConfig::default(),
+            gemini: GeminiConfig::default(),
+            azure_openai: AzureOpenAiConfig::default(),
+            openrouter: OpenRouterConfig::default(),
+            local_llm: LocalLlmConfig::default(),
+            profiles: HashMap::new(),
+            active_profile: None,
+            config_sync: ConfigSyncConfig::default(),
+            schema_version: crate::config::migrations::CURRENT_SCHEMA_VERSION,
         };
 
         let toml_str = toml::to_string_pretty(&config).unwrap();
@@ -5428,7 +7425,7 @@ default_temperature = 0.7
         assert_eq!(parsed.observability.backend, "none");
         assert_eq!(parsed.observability.runtime_trace_mode, "none");
         assert_eq!(parsed.autonomy.level, AutonomyLevel::Supervised);
-        assert_eq!(parsed.runtime.kind, "native");
+        assert_eq!(parsed.runtime.kind, "auto");
         assert!(!parsed.heartbeat.enabled);
         assert!(parsed.channels_config.cli);
         assert!(parsed.memory.hygiene_enabled);
@@ -5529,11 +7526,14 @@ tool_dispatcher = "xml"
         let config = Config {
             workspace_dir: dir.join("workspace"),
             config_path: config_path.clone(),
+            secret_refs: HashMap::new(),
+            low_resource_overrides: None,
             api_key: Some("sk-roundtrip".into()),
             api_url: None,
             default_provider: Some("openrouter".into()),
             default_model: Some("test-model".into()),
             model_providers: HashMap::new(),
+            model_aliases: HashMap::new(),
             default_temperature: 0.9,
             observability: ObservabilityConfig::default(),
             autonomy: AutonomyConfig::default(),
@@ -5561,6 +7561,7 @@ tool_dispatcher = "xml"
             web_fetch: WebFetchConfig::default(),
             web_search: WebSearchConfig::default(),
             proxy: ProxyConfig::default(),
+            provider_http: HashMap::new(),
             agent: AgentConfig::default(),
             identity: IdentityConfig::default(),
             cost: CostConfig::default(),
@@ -5569,6 +7570,19 @@ tool_dispatcher = "xml"
             hooks: HooksConfig::default(),
             hardware: HardwareConfig::default(),
             transcription: TranscriptionConfig::default(),
+            mcp: McpConfig::default(),
+            tools: HashMap::new(),
+            plugins: PluginsConfig::default(),
+            home_assistant: HomeAssistantConfig::default(),
+            eval: EvalConfig::default(),
+            gemini: GeminiConfig::default(),
+            azure_openai: AzureOpenAiConfig::default(),
+            openrouter: OpenRouterConfig::default(),
+            local_llm: LocalLlmConfig::default(),
+            profiles: HashMap::new(),
+            active_profile: None,
+            config_sync: ConfigSyncConfig::default(),
+            schema_version: crate::config::migrations::CURRENT_SCHEMA_VERSION,
         };
 
         config.save().await.unwrap();
@@ -5605,6 +7619,16 @@ tool_dispatcher = "xml"
         config.browser.computer_use.api_key = Some("browser-credential".into());
         config.web_search.brave_api_key = Some("brave-credential".into());
         config.storage.provider.config.db_url = Some("postgres://user:pw@host/db".into());
+        config.channels_config.telegram = Some(TelegramConfig {
+            bot_token: "123:telegram-credential".into(),
+            allowed_users: vec![],
+            stream_mode: StreamMode::Off,
+            draft_update_interval_ms: 1000,
+            interrupt_on_new_message: false,
+            mention_only: false,
+            notify_chat_id: None,
+            notify_targets: HashMap::new(),
+        });
 
         config.agents.insert(
             "worker".into(),
@@ -5665,6 +7689,15 @@ tool_dispatcher = "xml"
         assert!(crate::security::SecretStore::is_encrypted(worker_encrypted));
         assert_eq!(store.decrypt(worker_encrypted).unwrap(), "agent-credential");
 
+        let telegram_encrypted = &stored.channels_config.telegram.as_ref().unwrap().bot_token;
+        assert!(crate::security::SecretStore::is_encrypted(
+            telegram_encrypted
+        ));
+        assert_eq!(
+            store.decrypt(telegram_encrypted).unwrap(),
+            "123:telegram-credential"
+        );
+
         let storage_db_url = stored.storage.provider.config.db_url.as_deref().unwrap();
         assert!(crate::security::SecretStore::is_encrypted(storage_db_url));
         assert_eq!(
@@ -5675,6 +7708,122 @@ tool_dispatcher = "xml"
         let _ = fs::remove_dir_all(&dir).await;
     }
 
+    #[tokio::test]
+    async fn relay_token_is_encrypted_on_save_and_decrypted_on_load() {
+        let dir = TempDir::new().unwrap();
+        let mut config = Config {
+            config_path: dir.path().join("config.toml"),
+            workspace_dir: dir.path().join("workspace"),
+            ..Config::default()
+        };
+        config.tunnel.relay = Some(RelayTunnelConfig {
+            url: "wss://relay.example.com/connect".into(),
+            token: "relay-credential".into(),
+            public_url: None,
+        });
+        config.save().await.unwrap();
+
+        let contents = fs::read_to_string(&config.config_path).await.unwrap();
+        assert!(!contents.contains("relay-credential"));
+        let stored: Config = toml::from_str(&contents).unwrap();
+        assert!(crate::security::SecretStore::is_encrypted(
+            &stored.tunnel.relay.unwrap().token
+        ));
+
+        let loaded = Config::load_from(&config.config_path, &config.workspace_dir)
+            .await
+            .unwrap();
+        assert_eq!(loaded.tunnel.relay.unwrap().token, "relay-credential");
+    }
+
+    #[tokio::test]
+    async fn hook_secrets_are_encrypted_on_save_and_decrypted_on_load() {
+        let dir = TempDir::new().unwrap();
+        let mut config = Config {
+            config_path: dir.path().join("config.toml"),
+            workspace_dir: dir.path().join("workspace"),
+            ..Config::default()
+        };
+        config.gateway.hooks.insert(
+            "github".into(),
+            GatewayHookConfig {
+                secret: Some("hook-credential".into()),
+                ..GatewayHookConfig::default()
+            },
+        );
+        config.save().await.unwrap();
+
+        let contents = fs::read_to_string(&config.config_path).await.unwrap();
+        assert!(!contents.contains("hook-credential"));
+
+        let loaded = Config::load_from(&config.config_path, &config.workspace_dir)
+            .await
+            .unwrap();
+        assert_eq!(
+            loaded.gateway.hooks["github"].secret.as_deref(),
+            Some("hook-credential")
+        );
+    }
+
+    #[tokio::test]
+    async fn discord_notify_webhook_url_is_encrypted_like_the_bot_token() {
+        let dir = TempDir::new().unwrap();
+        let mut config = Config {
+            config_path: dir.path().join("config.toml"),
+            workspace_dir: dir.path().join("workspace"),
+            ..Config::default()
+        };
+        let webhook = "https://discord.com/api/webhooks/1/webhook-credential";
+        config.channels_config.discord = Some(DiscordConfig {
+            bot_token: "discord-token".into(),
+            guild_id: None,
+            allowed_users: vec![],
+            listen_to_bots: false,
+            mention_only: false,
+            notify_chat_id: None,
+            notify_webhook_url: Some(webhook.into()),
+        });
+        config.save().await.unwrap();
+
+        let contents = fs::read_to_string(&config.config_path).await.unwrap();
+        assert!(!contents.contains("webhook-credential"));
+
+        let loaded = Config::load_from(&config.config_path, &config.workspace_dir)
+            .await
+            .unwrap();
+        let discord = loaded.channels_config.discord.unwrap();
+        assert_eq!(discord.notify_webhook_url.as_deref(), Some(webhook));
+    }
+
+    #[tokio::test]
+    async fn slack_notify_webhook_url_is_encrypted_like_the_bot_token() {
+        let dir = TempDir::new().unwrap();
+        let mut config = Config {
+            config_path: dir.path().join("config.toml"),
+            workspace_dir: dir.path().join("workspace"),
+            ..Config::default()
+        };
+        let webhook = "https://hooks.slack.com/services/T0/B0/webhook-credential";
+        config.channels_config.slack = Some(SlackConfig {
+            bot_token: "xoxb-slack".into(),
+            app_token: None,
+            channel_id: None,
+            allowed_users: vec![],
+            notify_channel_id: None,
+            notify_webhook_url: Some(webhook.into()),
+        });
+        config.save().await.unwrap();
+
+        let contents = fs::read_to_string(&config.config_path).await.unwrap();
+        assert!(!contents.contains("webhook-credential"));
+
+        let loaded = Config::load_from(&config.config_path, &config.workspace_dir)
+            .await
+            .unwrap();
+        let slack = loaded.channels_config.slack.unwrap();
+        assert_eq!(slack.notify_webhook_url.as_deref(), Some(webhook));
+    }
+
     #[tokio::test]
     async fn config_save_atomic_cleanup() {
         let dir =
@@ -5705,6 +7854,238 @@ tool_dispatcher = "xml"
         let _ = fs::remove_dir_all(&dir).await;
     }
 
+    #[tokio::test]
+    async fn config_reload_picks_up_saved_changes_and_decrypts_secrets() {
+        let dir =
+            std::env::temp_dir().join(format!("zeroclaw_test_config_{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).await.unwrap();
+
+        let mut config = Config::default();
+        config.workspace_dir = dir.join("workspace");
+        config.config_path = dir.join("config.toml");
+        config.secrets.encrypt = true;
+        config.api_key = Some("root-credential".into());
+        config.default_model = Some("model-a".into());
+        config.save().await.unwrap();
+
+        let mut edited = config.clone();
+        edited.default_model = Some("model-b".into());
+        edited.save().await.unwrap();
+
+        let reloaded = config.reload().await.unwrap();
+        assert_eq!(reloaded.default_model.as_deref(), Some("model-b"));
+        assert_eq!(reloaded.api_key.as_deref(), Some("root-credential"));
+        assert_eq!(reloaded.workspace_dir, config.workspace_dir);
+
+        let _ = fs::remove_dir_all(&dir).await;
+    }
+
+    #[test]
+    async fn config_resolves_secret_references_and_saves_them_back() {
+        let dir =
+            std::env::temp_dir().join(format!("zeroclaw_test_config_{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).await.unwrap();
+        let token_path = dir.join("telegram-token");
+        fs::write(&token_path, "123:file-token\n").await.unwrap();
+        std::env::set_var("ZEROCLAW_TEST_CONFIG_REF_KEY", "env-credential");
+
+        let config_path = dir.join("config.toml");
+        let contents = format!(
+            "default_temperature = 0.7\n\
+             api_key = \"${{ZEROCLAW_TEST_CONFIG_REF_KEY}}\"\n\n\
+             [channels_config]\ncli = true\n\n\
+             [channels_config.telegram]\nbot_token = \"file://{}\"\nallowed_users = []\n",
+            token_path.display()
+        );
+        fs::write(&config_path, contents).await.unwrap();
+
+        let mut config = Config::load_from(&config_path, &dir.join("workspace"))
+            .await
+            .unwrap();
+        assert_eq!(config.api_key.as_deref(), Some("env-credential"));
+        let telegram = config.channels_config.telegram.as_ref().unwrap();
+        assert_eq!(telegram.bot_token, "123:file-token");
+
+        config.default_model = Some("edited-model".into());
+        config.save().await.unwrap();
+        let saved = fs::read_to_string(&config_path).await.unwrap();
+        assert!(saved.contains("${ZEROCLAW_TEST_CONFIG_REF_KEY}"), "{saved}");
+        assert!(saved.contains(&format!("file://{}", token_path.display())));
+        assert!(!saved.contains("env-credential"));
+
+        let _ = fs::remove_dir_all(&dir).await;
+    }
+
+    #[test]
+    async fn config_load_reports_file_line_and_column_of_bad_values() {
+        let dir =
+            std::env::temp_dir().join(format!("zeroclaw_test_config_{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).await.unwrap();
+        let config_path = dir.join("config.toml");
+        fs::write(&config_path, "[gateway]\nport = \"eighty\"\n")
+            .await
+            .unwrap();
+
+        let err = Config::load_from(&config_path, &dir.join("workspace"))
+            .await
+            .unwrap_err()
+            .to_string();
+        let location = format!("{}:2:8: error: invalid type", config_path.display());
+        assert!(err.contains(&location), "{err}");
+
+        let _ = fs::remove_dir_all(&dir).await;
+    }
+
+    #[test]
+    async fn config_load_migrates_legacy_layout_and_keeps_backup() {
+        let dir =
+            std::env::temp_dir().join(format!("zeroclaw_test_config_{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).await.unwrap();
+        let config_path = dir.join("config.toml");
+        let legacy = r#"model = "legacy-model"
+default_temperature = 0.7
+
+[channels]
+cli = true
+
+[channels.telegram]
+bot_token = "123:abc"
+allowed_users = ["alice"]
+"#;
+        fs::write(&config_path, legacy).await.unwrap();
+
+        let config = Config::load_from(&config_path, &dir.join("workspace"))
+            .await
+            .unwrap();
+        assert_eq!(config.default_model.as_deref(), Some("legacy-model"));
+        assert_eq!(
+            config.schema_version,
+            crate::config::migrations::CURRENT_SCHEMA_VERSION
+        );
+        let telegram = config.channels_config.telegram.as_ref().unwrap();
+        assert_eq!(telegram.allowed_users, vec!["alice".to_string()]);
+
+        let backup = crate::config::migrations::backup_path(&config_path, 0);
+        assert_eq!(fs::read_to_string(&backup).await.unwrap(), legacy);
+        let upgraded = fs::read_to_string(&config_path).await.unwrap();
+        assert!(
+            upgraded.contains("[channels_config.telegram]"),
+            "{upgraded}"
+        );
+
+        let _ = fs::remove_dir_all(&dir).await;
+    }
+
+    #[test]
+    async fn json_config_loads_and_saves_back_as_json() {
+        let dir =
+            std::env::temp_dir().join(format!("zeroclaw_test_config_{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).await.unwrap();
+        let config_path = dir.join("config.json");
+        let json = r#"{
+  "default_model": "json-model",
+  "default_temperature": 0.7,
+  "api_key": null,
+  "gateway": { "port": 4242 }
+}"#;
+        fs::write(&config_path, json).await.unwrap();
+
+        let mut config = Config::load_from(&config_path, &dir.join("workspace"))
+            .await
+            .unwrap();
+        assert_eq!(config.default_model.as_deref(), Some("json-model"));
+        assert_eq!(config.gateway.port, 4242);
+        assert!(config.api_key.is_none());
+
+        config.default_temperature = 0.3;
+        config.save().await.unwrap();
+        let saved = fs::read_to_string(&config_path).await.unwrap();
+        let value: serde_json::Value = serde_json::from_str(&saved).unwrap();
+        assert_eq!(value["default_model"], "json-model");
+        assert_eq!(value["default_temperature"], 0.3);
+
+        let reloaded = config.reload().await.unwrap();
+        assert_eq!(reloaded.gateway.port, 4242);
+
+        let _ = fs::remove_dir_all(&dir).await;
+    }
+
+    #[test]
+    async fn save_from_records_changed_keys_in_workspace_log() {
+        let dir =
+            std::env::temp_dir().join(format!("zeroclaw_test_config_{}", uuid::Uuid::new_v4()));
+        let mut config = Config {
+            config_path: dir.join("config.toml"),
+            workspace_dir: dir.join("workspace"),
+            ..Config::default()
+        };
+        config.save_from(ChangeSource::Jni).await.unwrap();
+        assert!(!crate::config::audit::log_path(&config.workspace_dir).exists());
+
+        config.gateway.port = 4242;
+        config.api_key = Some("sk-new".into());
+        config.save_from(ChangeSource::Gateway).await.unwrap();
+        config.save_from(ChangeSource::Gateway).await.unwrap();
+
+        let entries = crate::config::audit::load(&config.workspace_dir, 10, None, None).unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].source, ChangeSource::Gateway);
+        let paths: Vec<&str> = entries[0].changes.iter().map(|c| c.path.as_str()).collect();
+        assert_eq!(paths, vec!["api_key", "gateway.port"]);
+        let log = fs::read_to_string(crate::config::audit::log_path(&config.workspace_dir))
+            .await
+            .unwrap();
+        assert!(!log.contains("sk-new"));
+
+        let _ = fs::remove_dir_all(&dir).await;
+    }
+
+    #[test]
+    async fn switch_profile_applies_overrides_and_remembers_previous_settings() {
+        let mut config = Config::default();
+        config.default_model = Some("personal-model".into());
+        config.profiles.insert(
+            "work".into(),
+            ConfigProfile {
+                default_model: Some("work-model".into()),
+                default_temperature: Some(0.2),
+                autonomy: Some(AutonomyConfig {
+                    level: AutonomyLevel::ReadOnly,
+                    ..AutonomyConfig::default()
+                }),
+                ..ConfigProfile::default()
+            },
+        );
+        let provider = config.default_provider.clone();
+
+        config.switch_profile("work").unwrap();
+        assert_eq!(config.active_profile.as_deref(), Some("work"));
+        assert_eq!(config.default_model.as_deref(), Some("work-model"));
+        assert_eq!(config.default_provider, provider);
+        assert_eq!(config.autonomy.level, AutonomyLevel::ReadOnly);
+        assert_eq!(config.profile_names(), vec!["default", "work"]);
+
+        config.switch_profile(DEFAULT_PROFILE_NAME).unwrap();
+        assert_eq!(config.default_model.as_deref(), Some("personal-model"));
+        assert_eq!(config.autonomy.level, AutonomyConfig::default().level);
+        assert!((config.default_temperature - 0.7).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    async fn switch_profile_rejects_unknown_names_without_changes() {
+        let mut config = Config::default();
+        let err = config.switch_profile("travel").unwrap_err();
+        assert!(err.to_string().contains("no [profiles] are configured"));
+
+        config
+            .profiles
+            .insert("work".into(), ConfigProfile::default());
+        let err = config.switch_profile("travel").unwrap_err();
+        assert!(err.to_string().contains("Available: work"));
+        assert!(config.active_profile.is_none());
+        assert_eq!(config.profile_names(), vec!["work"]);
+    }
+
     // ── Telegram / Discord config ────────────────────────────
 
     #[test]
@@ -5717,6 +8098,7 @@ tool_dispatcher = "xml"
             interrupt_on_new_message: true,
             mention_only: false,
             notify_chat_id: None,
+            notify_targets: HashMap::new(),
         };
         let json = serde_json::to_string(&tc).unwrap();
         let parsed: TelegramConfig = serde_json::from_str(&json).unwrap();
@@ -5745,6 +8127,7 @@ tool_dispatcher = "xml"
             listen_to_bots: false,
             mention_only: false,
             notify_chat_id: None,
+            notify_webhook_url: None,
         };
         let json = serde_json::to_string(&dc).unwrap();
         let parsed: DiscordConfig = serde_json::from_str(&json).unwrap();
@@ -5761,6 +8144,7 @@ tool_dispatcher = "xml"
             listen_to_bots: false,
             mention_only: false,
             notify_chat_id: None,
+            notify_webhook_url: None,
         };
         let json = serde_json::to_string(&dc).unwrap();
         let parsed: DiscordConfig = serde_json::from_str(&json).unwrap();
@@ -5809,6 +8193,8 @@ tool_dispatcher = "xml"
             device_id: Some("DEVICE123".into()),
             room_id: "!room123:matrix.org".into(),
             allowed_users: vec!["@user:matrix.org".into()],
+            notify_room_id: None,
+            notify_proxy_url: None,
         };
         let json = serde_json::to_string(&mc).unwrap();
         let parsed: MatrixConfig = serde_json::from_str(&json).unwrap();
@@ -5829,6 +8215,8 @@ tool_dispatcher = "xml"
             device_id: None,
             room_id: "!abc:synapse.local".into(),
             allowed_users: vec!["@admin:synapse.local".into(), "*".into()],
+            notify_room_id: None,
+            notify_proxy_url: None,
         };
         let toml_str = toml::to_string(&mc).unwrap();
         let parsed: MatrixConfig = toml::from_str(&toml_str).unwrap();
@@ -5918,6 +8306,8 @@ allowed_users = ["@ops:matrix.org"]
                 device_id: None,
                 room_id: "!r:m".into(),
                 allowed_users: vec!["@u:m".into()],
+                notify_room_id: None,
+                notify_proxy_url: None,
             }),
             signal: None,
             whatsapp: None,
@@ -5933,6 +8323,7 @@ allowed_users = ["@ops:matrix.org"]
             nostr: None,
             clawdtalk: None,
             message_timeout_secs: 300,
+            overrides: HashMap::new(),
         };
         let toml_str = toml::to_string_pretty(&c).unwrap();
         let parsed: ChannelsConfig = toml::from_str(&toml_str).unwrap();
@@ -5949,6 +8340,104 @@ allowed_users = ["@ops:matrix.org"]
         assert!(c.matrix.is_none());
     }
 
+    #[test]
+    async fn channels_config_parses_per_channel_overrides() {
+        let toml_str = r#"
+cli = true
+
+[overrides.telegram]
+autonomy = "read_only"
+allowed_tools = ["memory_recall"]
+
+[overrides."telegram:42"]
+autonomy = "full"
+"#;
+        let parsed: ChannelsConfig = toml::from_str(toml_str).unwrap();
+        let telegram = &parsed.overrides["telegram"];
+        assert_eq!(telegram.autonomy, Some(AutonomyLevel::ReadOnly));
+        assert_eq!(
+            telegram.allowed_tools.as_deref(),
+            Some(&["memory_recall".to_string()][..])
+        );
+        assert_eq!(
+            parsed.overrides["telegram:42"].autonomy,
+            Some(AutonomyLevel::Full)
+        );
+        assert!(parsed.overrides["telegram:42"].allowed_tools.is_none());
+
+        let mut config = Config::default();
+        config.channels_config.overrides = parsed.overrides;
+        assert!(config.validate().is_ok());
+        config
+            .channels_config
+            .overrides
+            .insert(":42".into(), ChannelOverrideConfig::default());
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    async fn model_aliases_resolve_and_reject_chains() {
+        let mut config: Config = toml::from_str(
+            r#"
+default_temperature = 0.7
+default_model = "smart"
+
+[model_aliases]
+fast = "llama-3.1-8b"
+smart = "anthropic/claude-sonnet-4-6"
+"#,
+        )
+        .unwrap();
+        assert_eq!(
+            config.resolve_model_alias("smart"),
+            "anthropic/claude-sonnet-4-6"
+        );
+        assert_eq!(config.resolve_model_alias(" fast "), "llama-3.1-8b");
+        assert_eq!(config.resolve_model_alias("gpt-4o"), "gpt-4o");
+        assert!(config.validate().is_ok());
+
+        config.model_aliases.insert("quick".into(), "fast".into());
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    async fn tool_policies_parse_and_validate_prompt_names() {
+        let mut config: Config = toml::from_str(
+            r#"
+default_temperature = 0.7
+
+[tools.shell]
+enabled = false
+
+[tools.file_write]
+name = "save_file"
+params.content.max_bytes = 1048576
+
+[tools.email_send.params.to]
+allowed_values = ["me@example.com"]
+"#,
+        )
+        .unwrap();
+        assert!(!config.tools["shell"].enabled);
+        assert_eq!(
+            config.tools["file_write"].params["content"].max_bytes,
+            Some(1_048_576)
+        );
+        assert!(config.tools["email_send"].enabled);
+        assert!(config.validate().is_ok());
+
+        config.tools.insert(
+            "file_read".into(),
+            ToolPolicyConfig {
+                name: Some("save_file".into()),
+                ..ToolPolicyConfig::default()
+            },
+        );
+        assert!(config.validate().is_err());
+        config.tools.get_mut("file_read").unwrap().name = Some("read file".into());
+        assert!(config.validate().is_err());
+    }
+
     // ── Edge cases: serde(default) for allowed_users ─────────
 
     #[test]
@@ -6147,6 +8636,7 @@ channel_id = "C123"
             nostr: None,
             clawdtalk: None,
             message_timeout_secs: 300,
+            overrides: HashMap::new(),
         };
         let toml_str = toml::to_string_pretty(&c).unwrap();
         let parsed: ChannelsConfig = toml::from_str(&toml_str).unwrap();
@@ -6196,10 +8686,18 @@ channel_id = "C123"
         );
         assert_eq!(g.pair_rate_limit_per_minute, 10);
         assert_eq!(g.webhook_rate_limit_per_minute, 60);
+        assert_eq!(g.token_rate_limit_per_minute, 120);
+        assert_eq!(g.ip_rate_limit_per_minute, 300);
         assert!(!g.trust_forwarded_headers);
         assert_eq!(g.rate_limit_max_keys, 10_000);
         assert_eq!(g.idempotency_ttl_secs, 300);
         assert_eq!(g.idempotency_max_keys, 10_000);
+        assert!(g.bind_interface.is_none());
+        assert!(g.node_name.is_none());
+        assert!(g.advertise);
+        assert!(g.instance_name.is_none());
+        assert!(g.users.is_empty());
+        assert!(g.hooks.is_empty());
     }
 
     #[test]
@@ -6225,24 +8723,66 @@ channel_id = "C123"
             require_pairing: true,
             allow_public_bind: false,
             paired_tokens: vec!["zc_test_token".into()],
+            pairing_code_ttl_secs: 120,
             pair_rate_limit_per_minute: 12,
             webhook_rate_limit_per_minute: 80,
+            token_rate_limit_per_minute: 30,
+            ip_rate_limit_per_minute: 0,
             trust_forwarded_headers: true,
             rate_limit_max_keys: 2048,
             idempotency_ttl_secs: 600,
             idempotency_max_keys: 4096,
+            bind_interface: Some("tailnet".into()),
+            node_name: Some("pixel.tail1234.ts.net".into()),
+            advertise: false,
+            instance_name: Some("Pixel in the kitchen".into()),
+            users: HashMap::from([(
+                "alice".into(),
+                GatewayUserConfig {
+                    tokens: vec!["0123456789ab".into()],
+                    rate_limit_per_minute: Some(20),
+                },
+            )]),
+            hooks: HashMap::from([(
+                "grafana".into(),
+                GatewayHookConfig {
+                    secret: Some("hook-secret".into()),
+                    action: GatewayHookAction::Notify,
+                    template: "{{title}}: {{message}}".into(),
+                    ..GatewayHookConfig::default()
+                },
+            )]),
+            tls: GatewayTlsConfig {
+                enabled: true,
+                ..GatewayTlsConfig::default()
+            },
         };
         let toml_str = toml::to_string(&g).unwrap();
         let parsed: GatewayConfig = toml::from_str(&toml_str).unwrap();
         assert!(parsed.require_pairing);
         assert!(!parsed.allow_public_bind);
         assert_eq!(parsed.paired_tokens, vec!["zc_test_token"]);
+        assert_eq!(parsed.pairing_code_ttl_secs, 120);
         assert_eq!(parsed.pair_rate_limit_per_minute, 12);
         assert_eq!(parsed.webhook_rate_limit_per_minute, 80);
+        assert_eq!(parsed.token_rate_limit_per_minute, 30);
+        assert_eq!(parsed.ip_rate_limit_per_minute, 0);
         assert!(parsed.trust_forwarded_headers);
         assert_eq!(parsed.rate_limit_max_keys, 2048);
         assert_eq!(parsed.idempotency_ttl_secs, 600);
         assert_eq!(parsed.idempotency_max_keys, 4096);
+        assert_eq!(parsed.bind_interface.as_deref(), Some("tailnet"));
+        assert_eq!(parsed.node_name.as_deref(), Some("pixel.tail1234.ts.net"));
+        assert!(!parsed.advertise);
+        assert_eq!(
+            parsed.instance_name.as_deref(),
+            Some("Pixel in the kitchen")
+        );
+        assert_eq!(parsed.users["alice"].tokens, vec!["0123456789ab"]);
+        assert_eq!(parsed.users["alice"].rate_limit_per_minute, Some(20));
+        assert_eq!(parsed.hooks["grafana"].action, GatewayHookAction::Notify);
+        assert_eq!(parsed.hooks["grafana"].template, "{{title}}: {{message}}");
+        assert!(parsed.tls.enabled);
     }
 
     #[test]
@@ -6357,10 +8897,14 @@ enable = true
 
     #[test]
     async fn secrets_config_serde_roundtrip() {
-        let s = SecretsConfig { encrypt: false };
+        let s = SecretsConfig {
+            encrypt: false,
+            backend: SecretsBackend::Keystore,
+        };
         let toml_str = toml::to_string(&s).unwrap();
         let parsed: SecretsConfig = toml::from_str(&toml_str).unwrap();
         assert!(!parsed.encrypt);
+        assert_eq!(parsed.backend, SecretsBackend::Keystore);
     }
 
     #[test]
@@ -6797,6 +9341,28 @@ requires_openai_auth = true
         assert!(result.is_ok(), "expected validation to pass: {result:?}");
     }
 
+    #[test]
+    async fn validate_rejects_token_shared_by_gateway_users() {
+        let _env_guard = env_override_lock().await;
+        let mut config = Config::default();
+        let user = |id: &str| GatewayUserConfig {
+            tokens: vec![id.to_string()],
+            rate_limit_per_minute: None,
+        };
+        config.gateway.users = HashMap::from([
+            ("alice".to_string(), user("3f2a9c01d4e7")),
+            ("sam".to_string(), user("b81c44e0a9f2")),
+        ]);
+        assert!(config.validate().is_ok());
+
+        config
+            .gateway
+            .users
+            .insert("kim".to_string(), user("3F2A9C01D4E7"));
+        let error = config.validate().expect_err("expected validation failure");
+        assert!(error.to_string().contains("3f2a9c01d4e7"));
+    }
+
     #[test]
     async fn validate_rejects_unknown_model_provider_wire_api() {
         let _env_guard = env_override_lock().await;
@@ -7484,6 +10050,63 @@ default_model = "legacy-model"
         assert!(!runtime_proxy_cache_contains(&cache_key));
     }
 
+    #[test]
+    async fn provider_http_config_validates_headers_and_proxy() {
+        let mut config = Config::default();
+        config.provider_http.insert(
+            "custom".into(),
+            ProviderHttpConfig {
+                headers: HashMap::from([("X-Gateway-Token".into(), "abc".into())]),
+                proxy: Some("socks5h://proxy.corp:1080".into()),
+            },
+        );
+        assert!(config.validate().is_ok());
+
+        config.provider_http.insert(
+            "openai".into(),
+            ProviderHttpConfig {
+                headers: HashMap::from([("Bad Header".into(), "x".into())]),
+                proxy: None,
+            },
+        );
+        let err = config.validate().unwrap_err();
+        assert!(err.to_string().contains("provider_http.openai.headers"));
+
+        config.provider_http.insert(
+            "openai".into(),
+            ProviderHttpConfig {
+                headers: HashMap::new(),
+                proxy: Some("ftp://proxy.corp".into()),
+            },
+        );
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    async fn set_runtime_provider_http_config_normalizes_keys() {
+        let provider = format!(
+            "Provider-Http-Test-{}",
+            std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .expect("system clock should be after unix epoch")
+                .as_nanos()
+        );
+        let http = ProviderHttpConfig {
+            headers: HashMap::from([("X-Api-Gateway".into(), "1".into())]),
+            proxy: None,
+        };
+        let mut config = HashMap::new();
+        config.insert(format!(" {provider} "), http.clone());
+        set_runtime_provider_http_config(config);
+
+        assert_eq!(
+            runtime_provider_http_config(&provider.to_ascii_lowercase()),
+            Some(http)
+        );
+        set_runtime_provider_http_config(HashMap::new());
+        assert!(runtime_provider_http_config(&provider).is_none());
+    }
+
     #[test]
     async fn gateway_config_default_values() {
         let g = GatewayConfig::default();
@@ -7544,7 +10167,6 @@ default_model = "legacy-model"
             verification_token: Some("verify_token".into()),
             allowed_users: vec!["user_123".into(), "user_456".into()],
             mention_only: false,
-            notify_chat_id: None,
             use_feishu: true,
             receive_mode: LarkReceiveMode::Websocket,
             port: None,
@@ -7568,7 +10190,6 @@ default_model = "legacy-model"
             verification_token: Some("verify_token".into()),
             allowed_users: vec!["*".into()],
             mention_only: false,
-            notify_chat_id: None,
             use_feishu: false,
             receive_mode: LarkReceiveMode::Webhook,
             port: Some(9898),
@@ -7768,6 +10389,7 @@ default_model = "legacy-model"
         assert_eq!(tc.model, "whisper-large-v3-turbo");
         assert!(tc.language.is_none());
         assert_eq!(tc.max_duration_secs, 120);
+        assert_eq!(tc.backend, "api");
     }
 
     #[test]
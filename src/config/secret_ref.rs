@@ -0,0 +1,90 @@
+//! `${VAR}` and `file://` references in credential config values.
+//!
+//! A credential field such as `api_key` or `bot_token` may hold a reference
+//! instead of the secret itself: `${OPENAI_API_KEY}` reads an environment
+//! variable and `file:///run/secrets/telegram` reads a file (trailing newline
+//! removed; `file://~/...` starts at the home directory). References are
+//! resolved on load and written back unchanged on save, so the secret never
+//! lands in `config.toml`.
+
+use anyhow::{Context, Result};
+use std::path::Path;
+
+const FILE_PREFIX: &str = "file://";
+
+/// Whether `value` is a `${VAR}` or `file://` reference.
+pub fn is_reference(value: &str) -> bool {
+    env_var_name(value).is_some() || value.trim().starts_with(FILE_PREFIX)
+}
+
+/// Resolve a reference to the secret it points at. Returns `None` when
+/// `value` is not a reference.
+pub fn resolve(value: &str) -> Result<Option<String>> {
+    let value = value.trim();
+    if let Some(name) = env_var_name(value) {
+        let secret = std::env::var(name)
+            .ok()
+            .filter(|secret| !secret.is_empty())
+            .with_context(|| format!("environment variable {name} is not set"))?;
+        return Ok(Some(secret));
+    }
+
+    let Some(path) = value.strip_prefix(FILE_PREFIX) else {
+        return Ok(None);
+    };
+    let expanded = shellexpand::tilde(path);
+    let path = Path::new(expanded.as_ref());
+    if !path.is_absolute() {
+        anyhow::bail!("file reference {value} must use an absolute path (file:///...)");
+    }
+    let contents = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    let secret = contents.trim_end_matches(['\r', '\n']);
+    if secret.is_empty() {
+        anyhow::bail!("{} is empty", path.display());
+    }
+    Ok(Some(secret.to_string()))
+}
+
+/// `NAME` in a `${NAME}` reference.
+fn env_var_name(value: &str) -> Option<&str> {
+    let name = value.trim().strip_prefix("${")?.strip_suffix('}')?;
+    let mut chars = name.chars();
+    let valid = chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_');
+    valid.then_some(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resolves_env_and_file_references() {
+        std::env::set_var("ZEROCLAW_TEST_SECRET_REF", "from-env");
+        assert_eq!(
+            resolve("${ZEROCLAW_TEST_SECRET_REF}").unwrap().as_deref(),
+            Some("from-env")
+        );
+        let err = resolve("${ZEROCLAW_TEST_SECRET_REF_UNSET}").unwrap_err();
+        assert!(err.to_string().contains("is not set"));
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("token");
+        std::fs::write(&path, "from-file\n").unwrap();
+        let reference = format!("file://{}", path.display());
+        assert!(is_reference(&reference));
+        assert_eq!(resolve(&reference).unwrap().as_deref(), Some("from-file"));
+        assert!(resolve("file://relative/token").is_err());
+    }
+
+    #[test]
+    fn plain_values_are_not_references() {
+        for value in ["sk-plain", "$HOME", "${NOT VALID}", "${}", "prefix-${VAR}"] {
+            assert!(!is_reference(value), "{value}");
+            assert_eq!(resolve(value).unwrap(), None);
+        }
+    }
+}
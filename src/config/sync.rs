@@ -0,0 +1,659 @@
+//! Remote config sync.
+//!
+//! When `[config_sync]` is enabled, the daemon pulls a TOML document from an
+//! HTTPS URL or a git repository, checks its detached Ed25519 signature and
+//! merges it over the local config, so several devices keep the same agent
+//! settings. Secrets never sync: credential keys in the document are dropped
+//! and the local values kept, and device-local settings (`[secrets]`,
+//! `[config_sync]`, `active_profile`) are never taken from it. A document is
+//! applied once; edits made locally afterwards stay until it changes again.
+//! Each document carries a `sync_version` under its signature, and one not
+//! newer than the last applied is refused, so an old signed document cannot
+//! be replayed to roll settings back.
+
+use super::audit::ChangeSource;
+use super::schema::{Config, ConfigSyncConfig};
+use anyhow::{Context, Result};
+use base64::Engine;
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use toml::{Table, Value};
+
+pub const SYNC_COMPONENT: &str = "config_sync";
+
+const STATE_FILE: &str = "state/config-sync.json";
+const GIT_CHECKOUT_DIR: &str = "state/config-sync/repo";
+
+/// Top-level integer numbering signed documents.
+const VERSION_KEY: &str = "sync_version";
+
+/// Top-level keys that stay local to each device.
+const LOCAL_ONLY_KEYS: &[&str] = &["secrets", "config_sync", "active_profile", "schema_version"];
+
+/// Credential fields, matched by key name at any depth. Header and
+/// environment maps (`provider_http`, `[[mcp.servers]]`) count as a whole,
+/// since they carry auth headers and API keys, and so do webhook URLs, which
+/// embed their token.
+pub(super) const SECRET_KEYS: &[&str] = &[
+    "access_token",
+    "api_key",
+    "api_keys",
+    "api_token",
+    "app_secret",
+    "app_token",
+    "auth_token",
+    "bot_token",
+    "brave_api_key",
+    "client_secret",
+    "db_url",
+    "encrypt_key",
+    "env",
+    "headers",
+    "nickserv_password",
+    "notify_webhook_url",
+    "paired_tokens",
+    "password",
+    "private_key",
+    "sasl_password",
+    "secret",
+    "server_password",
+    "signing_secret",
+    "tavily_api_key",
+    "token",
+    "verification_token",
+    "verify_token",
+    "webhook_secret",
+];
+
+/// Result of one sync.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct SyncReport {
+    /// Whether the document was merged and saved. `false` when it has not
+    /// changed since the last applied sync.
+    pub applied: bool,
+    /// SHA-256 of the document, hex encoded.
+    pub sha256: String,
+    /// `sync_version` of the document; 0 when it was not applied.
+    pub version: u64,
+    /// Top-level sections taken from the document.
+    pub sections: Vec<String>,
+    /// Credential keys found in the document and ignored.
+    pub dropped_secrets: Vec<String>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct SyncState {
+    sha256: String,
+    #[serde(default)]
+    version: u64,
+    applied_at: String,
+    source: String,
+}
+
+/// Decode a base64 Ed25519 public key.
+pub fn decode_public_key(public_key: &str) -> Result<Vec<u8>> {
+    let key = base64::engine::general_purpose::STANDARD
+        .decode(public_key.trim())
+        .context("expected a base64 Ed25519 public key")?;
+    if key.len() != 32 {
+        anyhow::bail!(
+            "expected a 32-byte Ed25519 public key, got {} bytes",
+            key.len()
+        );
+    }
+    Ok(key)
+}
+
+/// Check the base64 detached `signature` of `document`.
+pub fn verify_signature(public_key: &str, document: &[u8], signature: &str) -> Result<()> {
+    let key = decode_public_key(public_key).context("Invalid config_sync.public_key")?;
+    let signature = base64::engine::general_purpose::STANDARD
+        .decode(signature.trim())
+        .context("Config sync signature is not valid base64")?;
+    ring::signature::UnparsedPublicKey::new(&ring::signature::ED25519, key)
+        .verify(document, &signature)
+        .map_err(|_| anyhow::anyhow!("Config sync signature does not match config_sync.public_key"))
+}
+
+/// The `sync_version` of `document`, which must be newer than
+/// `last_applied`.
+pub fn check_version(document: &str, last_applied: u64) -> Result<u64> {
+    let table: Table = toml::from_str(document).context("Remote config is not valid TOML")?;
+    let version = table
+        .get(VERSION_KEY)
+        .and_then(Value::as_integer)
+        .and_then(|version| u64::try_from(version).ok())
+        .filter(|version| *version > 0)
+        .with_context(|| format!("Remote config must set a positive integer `{VERSION_KEY}`"))?;
+    if version <= last_applied {
+        anyhow::bail!(
+            "Remote config {VERSION_KEY} {version} is not newer than the applied {last_applied}; \
+             refusing to roll back"
+        );
+    }
+    Ok(version)
+}
+
+/// Merge a remote document over `local`. Returns the merged config and the
+/// sections and credential keys it took and dropped.
+pub fn merge_document(
+    local: &Config,
+    document: &str,
+    sections: &[String],
+) -> Result<(Config, Vec<String>, Vec<String>)> {
+    let mut remote: Table = toml::from_str(document).context("Remote config is not valid TOML")?;
+    super::migrations::migrate(&mut remote);
+    let local_table = Table::try_from(local).context("Failed to serialize local config")?;
+    let mut merged = local_table.clone();
+
+    let mut applied = Vec::new();
+    let mut dropped = Vec::new();
+    for (key, mut value) in remote {
+        if key == VERSION_KEY
+            || LOCAL_ONLY_KEYS.contains(&key.as_str())
+            || (!sections.is_empty() && !sections.contains(&key))
+        {
+            continue;
+        }
+        if SECRET_KEYS.contains(&key.as_str()) {
+            dropped.push(key);
+            continue;
+        }
+        strip_secrets(&mut value, &key, &mut dropped);
+        match merged.get_mut(&key) {
+            Some(existing) => merge_value(existing, value),
+            None => {
+                merged.insert(key.clone(), value);
+            }
+        }
+        applied.push(key);
+    }
+    // Arrays such as `[[mcp.servers]]` are replaced whole; keep their local
+    // credentials.
+    restore_secrets(&mut merged, &local_table, "", &mut Vec::new());
+
+    applied.sort();
+    dropped.sort();
+
+    let contents = toml::to_string(&merged).context("Failed to serialize merged config")?;
+    let (config, diagnostics) = super::diagnostics::parse(&contents);
+    let Some(mut config) = config else {
+        let errors: Vec<String> = diagnostics
+            .iter()
+            .filter(|diagnostic| diagnostic.severity == super::diagnostics::Severity::Error)
+            .map(|diagnostic| diagnostic.message.clone())
+            .collect();
+        anyhow::bail!(
+            "Remote config does not fit the local schema: {}",
+            errors.join("; ")
+        );
+    };
+    config.config_path.clone_from(&local.config_path);
+    config.workspace_dir.clone_from(&local.workspace_dir);
+    config.secret_refs.clone_from(&local.secret_refs);
+    Ok((config, applied, dropped))
+}
+
+/// Remove credential keys below `path`, recording their dotted paths.
+fn strip_secrets(value: &mut Value, path: &str, dropped: &mut Vec<String>) {
+    match value {
+        Value::Table(table) => {
+            table.retain(|key, _| {
+                let secret = SECRET_KEYS.contains(&key);
+                if secret {
+                    dropped.push(format!("{path}.{key}"));
+                }
+                !secret
+            });
+            for (key, value) in table.iter_mut() {
+                strip_secrets(value, &format!("{path}.{key}"), dropped);
+            }
+        }
+        Value::Array(items) => {
+            for (i, item) in items.iter_mut().enumerate() {
+                strip_secrets(item, &format!("{path}.{i}"), dropped);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Copy credentials from `local` into `merged` where the merged table exists
+/// but lacks them, recording their dotted paths in `kept`. Array items are
+/// paired by position, and only when they have the same `name`, so a
+/// reordered list never gets another entry's credentials.
+pub(super) fn restore_secrets(
+    merged: &mut Table,
+    local: &Table,
+    path: &str,
+    kept: &mut Vec<String>,
+) {
+    for (key, local_value) in local {
+        let dotted = if path.is_empty() {
+            key.clone()
+        } else {
+            format!("{path}.{key}")
+        };
+        if SECRET_KEYS.contains(&key.as_str()) {
+            if !merged.contains_key(key) && !is_empty(local_value) {
+                merged.insert(key.clone(), local_value.clone());
+                kept.push(dotted);
+            }
+            continue;
+        }
+        match (merged.get_mut(key), local_value) {
+            (Some(Value::Table(merged)), Value::Table(local)) => {
+                restore_secrets(merged, local, &dotted, kept);
+            }
+            (Some(Value::Array(merged)), Value::Array(local)) => {
+                for (i, items) in merged.iter_mut().zip(local).enumerate() {
+                    if let (Value::Table(merged), Value::Table(local)) = items {
+                        if merged.get("name") == local.get("name") {
+                            restore_secrets(merged, local, &format!("{dotted}.{i}"), kept);
+                        }
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+fn is_empty(value: &Value) -> bool {
+    match value {
+        Value::String(v) => v.is_empty(),
+        Value::Array(items) => items.is_empty(),
+        Value::Table(table) => table.is_empty(),
+        _ => false,
+    }
+}
+
+/// Deep-merge tables; any other value (including arrays) is replaced.
+fn merge_value(existing: &mut Value, incoming: Value) {
+    match (existing, incoming) {
+        (Value::Table(existing), Value::Table(incoming)) => {
+            for (key, value) in incoming {
+                match existing.get_mut(&key) {
+                    Some(current) => merge_value(current, value),
+                    None => {
+                        existing.insert(key, value);
+                    }
+                }
+            }
+        }
+        (existing, incoming) => *existing = incoming,
+    }
+}
+
+/// Pull, verify and apply the remote document once.
+///
+/// The merge starts from the config file on disk (without env overrides),
+/// so it never persists values that only exist in memory.
+pub async fn sync_once(config: &Config) -> Result<SyncReport> {
+    let sync = &config.config_sync;
+    if !sync.enabled {
+        anyhow::bail!("Config sync is disabled; set [config_sync] enabled = true");
+    }
+
+    let (document, signature, source) = fetch(sync, &config.workspace_dir).await?;
+    verify_signature(&sync.public_key, &document, &signature)?;
+    let sha256 = hex::encode(Sha256::digest(&document));
+
+    let state_path = config.workspace_dir.join(STATE_FILE);
+    let state: SyncState = tokio::fs::read(&state_path)
+        .await
+        .ok()
+        .and_then(|raw| serde_json::from_slice(&raw).ok())
+        .unwrap_or_default();
+    if state.sha256 == sha256 {
+        return Ok(SyncReport {
+            applied: false,
+            sha256,
+            ..SyncReport::default()
+        });
+    }
+
+    let document = String::from_utf8(document).context("Remote config is not UTF-8")?;
+    let version = check_version(&document, state.version)?;
+    let local = Config::read_config_file(&config.config_path, config.workspace_dir.clone()).await?;
+    let (merged, sections, dropped_secrets) = merge_document(&local, &document, &sync.sections)?;
+    merged
+        .validate()
+        .context("Remote config was rejected by validation")?;
+    merged.save_from(ChangeSource::Sync).await?;
+
+    let state = SyncState {
+        sha256: sha256.clone(),
+        version,
+        applied_at: Utc::now().to_rfc3339(),
+        source,
+    };
+    if let Some(parent) = state_path.parent() {
+        tokio::fs::create_dir_all(parent).await?;
+    }
+    tokio::fs::write(&state_path, serde_json::to_vec_pretty(&state)?).await?;
+    crate::daemon::request_reload();
+
+    if !dropped_secrets.is_empty() {
+        tracing::warn!(
+            keys = ?dropped_secrets,
+            "Remote config contained credentials; they were ignored"
+        );
+    }
+    tracing::info!(sections = ?sections, version, sha256 = %sha256, "Remote config applied");
+    Ok(SyncReport {
+        applied: true,
+        sha256,
+        version,
+        sections,
+        dropped_secrets,
+    })
+}
+
+/// Daemon worker: sync every `interval_minutes`, starting right away.
+pub async fn run(config: Config) -> Result<()> {
+    let minutes = config.config_sync.interval_minutes.max(1);
+    let mut interval = tokio::time::interval(Duration::from_secs(minutes * 60));
+    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+
+    loop {
+        interval.tick().await;
+        match Box::pin(sync_once(&config)).await {
+            Ok(_) => crate::health::mark_component_ok(SYNC_COMPONENT),
+            Err(e) => {
+                crate::health::mark_component_error(SYNC_COMPONENT, format!("{e:#}"));
+                tracing::warn!("Config sync failed: {e:#}");
+            }
+        }
+    }
+}
+
+/// Fetch the document, its signature and a description of the source.
+async fn fetch(sync: &ConfigSyncConfig, workspace_dir: &Path) -> Result<(Vec<u8>, String, String)> {
+    if let Some(url) = sync.url.as_deref().map(str::trim).filter(|v| !v.is_empty()) {
+        let signature_url = sync
+            .signature_url
+            .as_deref()
+            .map(str::trim)
+            .filter(|v| !v.is_empty())
+            .map_or_else(|| format!("{url}.sig"), str::to_string);
+        let client = crate::config::build_runtime_proxy_client_with_timeouts("config_sync", 30, 10);
+        let document = client
+            .get(url)
+            .send()
+            .await
+            .and_then(reqwest::Response::error_for_status)
+            .with_context(|| format!("Failed to fetch remote config from {url}"))?
+            .bytes()
+            .await?
+            .to_vec();
+        let signature = client
+            .get(&signature_url)
+            .send()
+            .await
+            .and_then(reqwest::Response::error_for_status)
+            .with_context(|| format!("Failed to fetch config signature from {signature_url}"))?
+            .text()
+            .await?;
+        return Ok((document, signature, url.to_string()));
+    }
+
+    let Some(repo) = sync
+        .git_repo
+        .as_deref()
+        .map(str::trim)
+        .filter(|v| !v.is_empty())
+    else {
+        anyhow::bail!("config_sync needs a url or git_repo");
+    };
+    let checkout = workspace_dir.join(GIT_CHECKOUT_DIR);
+    pull_git(repo, sync.git_ref.as_deref(), &checkout).await?;
+
+    let path = checkout.join(sync.git_path.trim());
+    let document = tokio::fs::read(&path)
+        .await
+        .with_context(|| format!("{} not found in {repo}", sync.git_path))?;
+    let mut signature_path = path.into_os_string();
+    signature_path.push(".sig");
+    let signature = tokio::fs::read_to_string(PathBuf::from(signature_path))
+        .await
+        .with_context(|| format!("{}.sig not found in {repo}", sync.git_path))?;
+    Ok((document, signature, format!("{repo}:{}", sync.git_path)))
+}
+
+/// Shallow-clone `repo`, or fetch and reset an existing checkout to it.
+async fn pull_git(repo: &str, git_ref: Option<&str>, checkout: &Path) -> Result<()> {
+    let git_ref = git_ref.map(str::trim).filter(|v| !v.is_empty());
+    if !checkout.join(".git").exists() {
+        if let Some(parent) = checkout.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+        let mut args = vec!["clone", "--depth", "1"];
+        if let Some(git_ref) = git_ref {
+            args.extend(["--branch", git_ref]);
+        }
+        // `--` keeps a configured URL from being read as an option.
+        args.extend(["--", repo]);
+        return run_git(None, &args, checkout).await;
+    }
+
+    run_git(
+        Some(checkout),
+        &["remote", "set-url", "--", "origin", repo],
+        checkout,
+    )
+    .await?;
+    run_git(
+        Some(checkout),
+        &[
+            "fetch",
+            "--depth",
+            "1",
+            "--",
+            "origin",
+            git_ref.unwrap_or("HEAD"),
+        ],
+        checkout,
+    )
+    .await?;
+    run_git(Some(checkout), &["reset", "--hard", "FETCH_HEAD"], checkout).await
+}
+
+async fn run_git(dir: Option<&Path>, args: &[&str], checkout: &Path) -> Result<()> {
+    let mut command = tokio::process::Command::new("git");
+    if let Some(dir) = dir {
+        command.arg("-C").arg(dir);
+    }
+    command.args(args);
+    if dir.is_none() {
+        command.arg(checkout);
+    }
+    let output = command
+        .output()
+        .await
+        .context("Failed to run git for config sync")?;
+    if !output.status.success() {
+        anyhow::bail!(
+            "git {} failed: {}",
+            args.first().copied().unwrap_or_default(),
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ring::signature::{Ed25519KeyPair, KeyPair};
+
+    fn key_pair() -> Ed25519KeyPair {
+        let pkcs8 = Ed25519KeyPair::generate_pkcs8(&ring::rand::SystemRandom::new()).unwrap();
+        Ed25519KeyPair::from_pkcs8(pkcs8.as_ref()).unwrap()
+    }
+
+    fn encode(bytes: &[u8]) -> String {
+        base64::engine::general_purpose::STANDARD.encode(bytes)
+    }
+
+    #[test]
+    fn verifies_detached_signatures() {
+        let pair = key_pair();
+        let public_key = encode(pair.public_key().as_ref());
+        let document = b"default_model = \"shared-model\"\n";
+        let signature = encode(pair.sign(document).as_ref());
+
+        verify_signature(&public_key, document, &signature).unwrap();
+        let err =
+            verify_signature(&public_key, b"default_model = \"evil\"\n", &signature).unwrap_err();
+        assert!(err.to_string().contains("does not match"));
+        assert!(decode_public_key("c2hvcnQ=").is_err());
+    }
+
+    #[test]
+    fn merge_keeps_local_secrets_and_device_settings() {
+        let mut local = Config::default();
+        local.api_key = Some("local-key".into());
+        local.default_model = Some("local-model".into());
+        local.autonomy.allowed_commands = vec!["git".into()];
+        local.autonomy.max_actions_per_hour = 10;
+        local.secrets.encrypt = true;
+        local.channels_config.telegram =
+            Some(toml::from_str("bot_token = \"local-token\"\nallowed_users = []\n").unwrap());
+
+        let document = r#"
+api_key = "remote-key"
+default_model = "shared-model"
+
+[autonomy]
+max_actions_per_hour = 50
+
+[secrets]
+encrypt = false
+
+[channels_config.telegram]
+bot_token = "remote-token"
+allowed_users = ["alice"]
+"#;
+        let (merged, sections, dropped) = merge_document(&local, document, &[]).unwrap();
+
+        assert_eq!(merged.api_key.as_deref(), Some("local-key"));
+        assert_eq!(merged.default_model.as_deref(), Some("shared-model"));
+        assert_eq!(merged.autonomy.max_actions_per_hour, 50);
+        assert_eq!(merged.autonomy.allowed_commands, vec!["git"]);
+        assert!(merged.secrets.encrypt);
+        let telegram = merged.channels_config.telegram.unwrap();
+        assert_eq!(telegram.bot_token, "local-token");
+        assert_eq!(telegram.allowed_users, vec!["alice"]);
+        assert_eq!(
+            sections,
+            vec!["autonomy", "channels_config", "default_model"]
+        );
+        assert_eq!(
+            dropped,
+            vec!["api_key", "channels_config.telegram.bot_token"]
+        );
+    }
+
+    #[test]
+    fn merge_drops_header_maps_and_webhook_urls_but_keeps_local_ones() {
+        let mut local: Config = toml::from_str(
+            r#"
+default_temperature = 0.7
+
+[provider_http.openrouter]
+headers = { "X-Gateway-Key" = "local-header" }
+
+[[mcp.servers]]
+name = "search"
+transport = "http"
+url = "https://mcp.example/mcp"
+headers = { Authorization = "Bearer local-mcp" }
+env = { SEARCH_KEY = "local-env" }
+"#,
+        )
+        .unwrap();
+        local.channels_config.slack = Some(
+            toml::from_str(
+                "bot_token = \"xoxb-local\"\nnotify_webhook_url = \"https://hooks.slack.com/local\"\n",
+            )
+            .unwrap(),
+        );
+
+        let document = r#"
+[provider_http.openrouter]
+headers = { "X-Gateway-Key" = "remote-header" }
+proxy = "socks5://10.0.0.1:1080"
+
+[[mcp.servers]]
+name = "search"
+transport = "http"
+url = "https://mcp.example/v2/mcp"
+headers = { Authorization = "Bearer remote-mcp" }
+env = { SEARCH_KEY = "remote-env" }
+
+[channels_config.slack]
+bot_token = "xoxb-remote"
+notify_webhook_url = "https://hooks.slack.com/remote"
+"#;
+        let (merged, _, dropped) = merge_document(&local, document, &[]).unwrap();
+
+        let http = &merged.provider_http["openrouter"];
+        assert_eq!(http.headers["X-Gateway-Key"], "local-header");
+        assert_eq!(http.proxy.as_deref(), Some("socks5://10.0.0.1:1080"));
+        let server = &merged.mcp.servers[0];
+        assert_eq!(server.url.as_deref(), Some("https://mcp.example/v2/mcp"));
+        assert_eq!(server.headers["Authorization"], "Bearer local-mcp");
+        assert_eq!(server.env["SEARCH_KEY"], "local-env");
+        assert_eq!(
+            merged
+                .channels_config
+                .slack
+                .unwrap()
+                .notify_webhook_url
+                .as_deref(),
+            Some("https://hooks.slack.com/local")
+        );
+        assert_eq!(
+            dropped,
+            vec![
+                "channels_config.slack.bot_token",
+                "channels_config.slack.notify_webhook_url",
+                "mcp.servers.0.env",
+                "mcp.servers.0.headers",
+                "provider_http.openrouter.headers",
+            ]
+        );
+    }
+
+    #[test]
+    fn versions_must_increase() {
+        let document = "sync_version = 3\ndefault_model = \"shared-model\"\n";
+        assert_eq!(check_version(document, 0).unwrap(), 3);
+        assert_eq!(check_version(document, 2).unwrap(), 3);
+        let err = check_version(document, 3).unwrap_err();
+        assert!(err.to_string().contains("refusing to roll back"));
+        assert!(check_version(document, 7).is_err());
+        assert!(check_version("default_model = \"shared-model\"\n", 0).is_err());
+        assert!(check_version("sync_version = -1\n", 0).is_err());
+
+        let (merged, sections, _) = merge_document(&Config::default(), document, &[]).unwrap();
+        assert_eq!(merged.default_model.as_deref(), Some("shared-model"));
+        assert_eq!(sections, vec!["default_model"]);
+    }
+
+    #[test]
+    fn merge_takes_only_listed_sections() {
+        let local = Config::default();
+        let document =
+            "default_model = \"shared-model\"\n\n[autonomy]\nmax_actions_per_hour = 50\n";
+        let (merged, sections, _) =
+            merge_document(&local, document, &["autonomy".to_string()]).unwrap();
+        assert_eq!(merged.default_model, local.default_model);
+        assert_eq!(merged.autonomy.max_actions_per_hour, 50);
+        assert_eq!(sections, vec!["autonomy"]);
+    }
+}
@@ -0,0 +1,508 @@
+//! Config export and import.
+//!
+//! [`export`] turns the config on disk into a portable TOML document for a
+//! new phone or a shared template. Credentials are either stripped or, with a
+//! passphrase, wrapped as `wrap1:` values (ChaCha20-Poly1305 under a
+//! PBKDF2-SHA256 key), since keys held by the device keystore cannot leave it.
+//! `${VAR}` and `file://` references are kept as they are. [`import`] unwraps
+//! the document, keeps local credentials the document does not carry, and
+//! saves it over the current config. `[secrets]` always stays device-local.
+
+use super::audit::ChangeSource;
+use super::schema::Config;
+use super::sync::{restore_secrets, SECRET_KEYS};
+use crate::security::SecretStore;
+use anyhow::{Context, Result};
+use base64::Engine;
+use chacha20poly1305::aead::{Aead, KeyInit, OsRng};
+use chacha20poly1305::{AeadCore, ChaCha20Poly1305, Key, Nonce};
+use ring::rand::SecureRandom;
+use serde::Serialize;
+use std::num::NonZeroU32;
+use toml::{Table, Value};
+
+const WRAP_PREFIX: &str = "wrap1:";
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 12;
+const PBKDF2_ITERATIONS: u32 = 100_000;
+
+/// Top-level keys that stay on the device.
+const LOCAL_ONLY_KEYS: &[&str] = &["secrets"];
+
+/// Result of an import.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct ImportReport {
+    /// Top-level sections taken from the document.
+    pub sections: Vec<String>,
+    /// Wrapped credentials that were decrypted with the passphrase.
+    pub unwrapped_secrets: Vec<String>,
+    /// Credentials missing from the document that kept their local value.
+    pub kept_secrets: Vec<String>,
+}
+
+/// Export the config file as a portable TOML document.
+///
+/// Without a passphrase credentials are stripped; with one they are wrapped
+/// and can only be restored by [`import`] with the same passphrase.
+pub async fn export(config: &Config, passphrase: Option<&str>) -> Result<String> {
+    let passphrase = passphrase.filter(|p| !p.is_empty());
+    let local = Config::read_config_file(&config.config_path, config.workspace_dir.clone()).await?;
+    let zeroclaw_dir = local
+        .config_path
+        .parent()
+        .context("Config path must have a parent directory")?;
+    let store = SecretStore::from_config(zeroclaw_dir, &local.secrets);
+
+    let mut document = Table::try_from(&local).context("Failed to serialize config")?;
+    document.retain(|key, _| !LOCAL_ONLY_KEYS.contains(&key));
+    let mut secrets = Vec::new();
+    for (key, value) in &document {
+        if SECRET_KEYS.contains(&key.as_str()) {
+            secrets.push((Vec::new(), key.clone()));
+        } else {
+            find_secrets(value, &mut vec![key.clone()], &mut secrets);
+        }
+    }
+
+    for (parent, key) in secrets {
+        let Some(table) = table_at(&mut document, &parent) else {
+            continue;
+        };
+        let Some(value) = table.get_mut(&key) else {
+            continue;
+        };
+        // References are not secrets and work on any device that sets them up.
+        if let Some(reference) = value.as_str().and_then(|v| local.secret_refs.get(v)) {
+            *value = Value::String(reference.clone());
+            continue;
+        }
+        match passphrase {
+            Some(passphrase) => map_strings(value, &mut |plain: &str| {
+                let plain = store.decrypt(plain)?;
+                wrap(&plain, passphrase)
+            })?,
+            None => {
+                table.remove(&key);
+            }
+        }
+    }
+
+    let mode = if passphrase.is_some() {
+        "wrapped with a passphrase"
+    } else {
+        "stripped"
+    };
+    let body = toml::to_string(&document).context("Failed to serialize export")?;
+    Ok(format!(
+        "# ZeroClaw config export (credentials {mode})\n{body}"
+    ))
+}
+
+/// Import an exported document over the config file and save it.
+///
+/// Wrapped credentials need the export passphrase. Credentials the document
+/// does not carry keep their local values, and `[secrets]` stays local.
+pub async fn import(
+    config: &Config,
+    document: &str,
+    passphrase: Option<&str>,
+    source: ChangeSource,
+) -> Result<ImportReport> {
+    let passphrase = passphrase.filter(|p| !p.is_empty());
+    let mut imported: Table = toml::from_str(document).context("Import is not valid TOML")?;
+    super::migrations::migrate(&mut imported);
+
+    let mut report = ImportReport::default();
+    let mut wrapped = Vec::new();
+    for (key, value) in &imported {
+        find_wrapped(value, key, &mut wrapped);
+    }
+    if !wrapped.is_empty() {
+        let passphrase = passphrase
+            .context("The import contains wrapped credentials; a passphrase is needed")?;
+        for (key, value) in &mut imported {
+            map_strings(value, &mut |v: &str| match v.strip_prefix(WRAP_PREFIX) {
+                Some(blob) => unwrap(blob, passphrase),
+                None => Ok(v.to_string()),
+            })
+            .with_context(|| format!("Failed to unwrap credentials in {key}"))?;
+        }
+        report.unwrapped_secrets = wrapped;
+    }
+
+    let local = Config::read_config_file(&config.config_path, config.workspace_dir.clone()).await?;
+    let mut local_table = Table::try_from(&local).context("Failed to serialize local config")?;
+    imported.retain(|key, _| !LOCAL_ONLY_KEYS.contains(&key));
+    report.sections = imported.keys().cloned().collect();
+    restore_secrets(&mut imported, &local_table, "", &mut report.kept_secrets);
+    for key in LOCAL_ONLY_KEYS {
+        if let Some(value) = local_table.remove(*key) {
+            imported.insert((*key).to_string(), value);
+        }
+    }
+
+    let contents = toml::to_string(&imported).context("Failed to serialize import")?;
+    let (parsed, diagnostics) = super::diagnostics::parse(&contents);
+    let Some(mut merged) = parsed else {
+        let errors: Vec<String> = diagnostics
+            .iter()
+            .filter(|diagnostic| diagnostic.severity == super::diagnostics::Severity::Error)
+            .map(|diagnostic| diagnostic.message.clone())
+            .collect();
+        anyhow::bail!(
+            "Import does not fit the config schema: {}",
+            errors.join("; ")
+        );
+    };
+    merged.config_path.clone_from(&local.config_path);
+    merged.workspace_dir.clone_from(&local.workspace_dir);
+    merged.secret_refs.clone_from(&local.secret_refs);
+    merged
+        .validate()
+        .context("Imported config was rejected by validation")?;
+    merged.save_from(source).await?;
+    crate::daemon::request_reload();
+
+    report.sections.sort();
+    report.kept_secrets.sort();
+    tracing::info!(sections = ?report.sections, "Config imported");
+    Ok(report)
+}
+
+/// Whether `value` is a credential wrapped by [`export`].
+pub fn is_wrapped(value: &str) -> bool {
+    value.starts_with(WRAP_PREFIX)
+}
+
+fn derive_key(passphrase: &str, salt: &[u8]) -> [u8; 32] {
+    let mut key = [0u8; 32];
+    ring::pbkdf2::derive(
+        ring::pbkdf2::PBKDF2_HMAC_SHA256,
+        NonZeroU32::new(PBKDF2_ITERATIONS).unwrap_or(NonZeroU32::MIN),
+        salt,
+        passphrase.as_bytes(),
+        &mut key,
+    );
+    key
+}
+
+/// `wrap1:<base64(salt ‖ nonce ‖ ciphertext ‖ tag)>`.
+fn wrap(plaintext: &str, passphrase: &str) -> Result<String> {
+    let mut salt = [0u8; SALT_LEN];
+    ring::rand::SystemRandom::new()
+        .fill(&mut salt)
+        .map_err(|_| anyhow::anyhow!("Failed to generate a random salt"))?;
+    let key = derive_key(passphrase, &salt);
+    let cipher = ChaCha20Poly1305::new(Key::from_slice(&key));
+    let nonce = ChaCha20Poly1305::generate_nonce(&mut OsRng);
+    let ciphertext = cipher
+        .encrypt(&nonce, plaintext.as_bytes())
+        .map_err(|e| anyhow::anyhow!("Encryption failed: {e}"))?;
+
+    let mut blob = Vec::with_capacity(SALT_LEN + NONCE_LEN + ciphertext.len());
+    blob.extend_from_slice(&salt);
+    blob.extend_from_slice(&nonce);
+    blob.extend_from_slice(&ciphertext);
+    Ok(format!(
+        "{WRAP_PREFIX}{}",
+        base64::engine::general_purpose::STANDARD.encode(blob)
+    ))
+}
+
+fn unwrap(blob: &str, passphrase: &str) -> Result<String> {
+    let blob = base64::engine::general_purpose::STANDARD
+        .decode(blob)
+        .context("Wrapped credential is not valid base64")?;
+    if blob.len() < SALT_LEN + NONCE_LEN {
+        anyhow::bail!("Wrapped credential is truncated");
+    }
+    let (salt, rest) = blob.split_at(SALT_LEN);
+    let (nonce, ciphertext) = rest.split_at(NONCE_LEN);
+    let key = derive_key(passphrase, salt);
+    let plaintext = ChaCha20Poly1305::new(Key::from_slice(&key))
+        .decrypt(Nonce::from_slice(nonce), ciphertext)
+        .map_err(|_| anyhow::anyhow!("Wrong passphrase or corrupted credential"))?;
+    String::from_utf8(plaintext).context("Unwrapped credential is not UTF-8")
+}
+
+/// Collect `(parent path, key)` of credential keys below `value`. Array
+/// items appear in the path by index.
+fn find_secrets(value: &Value, path: &mut Vec<String>, found: &mut Vec<(Vec<String>, String)>) {
+    match value {
+        Value::Table(table) => {
+            for (key, value) in table {
+                if SECRET_KEYS.contains(&key.as_str()) {
+                    found.push((path.clone(), key.clone()));
+                } else {
+                    path.push(key.clone());
+                    find_secrets(value, path, found);
+                    path.pop();
+                }
+            }
+        }
+        Value::Array(items) => {
+            for (i, item) in items.iter().enumerate() {
+                path.push(i.to_string());
+                find_secrets(item, path, found);
+                path.pop();
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Record dotted paths of wrapped string values below `value`.
+fn find_wrapped(value: &Value, path: &str, found: &mut Vec<String>) {
+    match value {
+        Value::String(v) if is_wrapped(v) => found.push(path.to_string()),
+        Value::Table(table) => {
+            for (key, value) in table {
+                find_wrapped(value, &format!("{path}.{key}"), found);
+            }
+        }
+        Value::Array(items) => {
+            for (i, item) in items.iter().enumerate() {
+                find_wrapped(item, &format!("{path}.{i}"), found);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Apply `f` to every string below `value`.
+fn map_strings(value: &mut Value, f: &mut impl FnMut(&str) -> Result<String>) -> Result<()> {
+    match value {
+        Value::String(v) => *v = f(v)?,
+        Value::Table(table) => {
+            for (_, value) in table.iter_mut() {
+                map_strings(value, f)?;
+            }
+        }
+        Value::Array(items) => {
+            for item in items {
+                map_strings(item, f)?;
+            }
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+/// The table at `path` below `table`, indexing into arrays where
+/// [`find_secrets`] did.
+fn table_at<'a>(table: &'a mut Table, path: &[String]) -> Option<&'a mut Table> {
+    let Some((first, rest)) = path.split_first() else {
+        return Some(table);
+    };
+    let mut value = table.get_mut(first)?;
+    for key in rest {
+        value = match value {
+            Value::Array(items) => items.get_mut(key.parse::<usize>().ok()?)?,
+            value => value.as_table_mut()?.get_mut(key)?,
+        };
+    }
+    value.as_table_mut()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::TelegramConfig;
+
+    #[test]
+    fn wrap_round_trips_and_rejects_wrong_passphrase() {
+        let wrapped = wrap("sk-secret", "correct horse").unwrap();
+        assert!(is_wrapped(&wrapped));
+        let blob = wrapped.strip_prefix(WRAP_PREFIX).unwrap();
+        assert_eq!(unwrap(blob, "correct horse").unwrap(), "sk-secret");
+        assert!(unwrap(blob, "battery staple").is_err());
+    }
+
+    #[tokio::test]
+    async fn header_maps_and_webhook_urls_are_credentials() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut config: Config = toml::from_str(
+            r#"
+default_temperature = 0.7
+
+[provider_http.openrouter]
+headers = { "X-Gateway-Key" = "gw-secret" }
+
+[[mcp.servers]]
+name = "search"
+transport = "http"
+url = "https://mcp.example/mcp"
+headers = { Authorization = "Bearer mcp-secret" }
+env = { SEARCH_KEY = "env-secret" }
+"#,
+        )
+        .unwrap();
+        config.config_path = dir.path().join("config.toml");
+        config.workspace_dir = dir.path().join("workspace");
+        config.channels_config.discord = Some(
+            toml::from_str(
+                "bot_token = \"discord-bot\"\nnotify_webhook_url = \"https://discord.com/api/webhooks/1/discord-secret\"\n",
+            )
+            .unwrap(),
+        );
+        config.channels_config.slack = Some(
+            toml::from_str(
+                "bot_token = \"xoxb-slack\"\nnotify_webhook_url = \"https://hooks.slack.com/services/slack-secret\"\n",
+            )
+            .unwrap(),
+        );
+        config.save().await.unwrap();
+
+        let secrets = [
+            "gw-secret",
+            "mcp-secret",
+            "env-secret",
+            "discord-secret",
+            "slack-secret",
+        ];
+        let stripped = export(&config, None).await.unwrap();
+        let wrapped = export(&config, Some("pass")).await.unwrap();
+        for secret in secrets {
+            assert!(!stripped.contains(secret), "{secret} in {stripped}");
+            assert!(!wrapped.contains(secret), "{secret} in {wrapped}");
+        }
+        assert!(stripped.contains("https://mcp.example/mcp"));
+
+        // Wrapped credentials come back on a fresh device.
+        let target = tempfile::tempdir().unwrap();
+        let fresh = Config {
+            config_path: target.path().join("config.toml"),
+            workspace_dir: target.path().join("workspace"),
+            ..Config::default()
+        };
+        fresh.save().await.unwrap();
+        import(&fresh, &wrapped, Some("pass"), ChangeSource::Cli)
+            .await
+            .unwrap();
+        let restored = Config::read_config_file(&fresh.config_path, fresh.workspace_dir.clone())
+            .await
+            .unwrap();
+        assert_eq!(
+            restored.provider_http["openrouter"].headers["X-Gateway-Key"],
+            "gw-secret"
+        );
+        assert_eq!(
+            restored.mcp.servers[0].headers["Authorization"],
+            "Bearer mcp-secret"
+        );
+        assert_eq!(restored.mcp.servers[0].env["SEARCH_KEY"], "env-secret");
+        assert_eq!(
+            restored
+                .channels_config
+                .discord
+                .unwrap()
+                .notify_webhook_url
+                .as_deref(),
+            Some("https://discord.com/api/webhooks/1/discord-secret")
+        );
+
+        // A stripped template keeps the ones already on the device.
+        let report = import(&fresh, &stripped, None, ChangeSource::Cli)
+            .await
+            .unwrap();
+        for kept in [
+            "channels_config.slack.notify_webhook_url",
+            "mcp.servers.0.env",
+            "mcp.servers.0.headers",
+            "provider_http.openrouter.headers",
+        ] {
+            assert!(report.kept_secrets.contains(&kept.to_string()), "{kept}");
+        }
+        let restored = Config::read_config_file(&fresh.config_path, fresh.workspace_dir.clone())
+            .await
+            .unwrap();
+        assert_eq!(
+            restored
+                .channels_config
+                .slack
+                .unwrap()
+                .notify_webhook_url
+                .as_deref(),
+            Some("https://hooks.slack.com/services/slack-secret")
+        );
+        assert_eq!(
+            restored.mcp.servers[0].headers["Authorization"],
+            "Bearer mcp-secret"
+        );
+    }
+
+    #[tokio::test]
+    async fn export_strips_or_wraps_and_import_restores() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut config = Config {
+            config_path: dir.path().join("config.toml"),
+            workspace_dir: dir.path().join("workspace"),
+            api_key: Some("sk-live".into()),
+            default_model: Some("exported-model".into()),
+            ..Config::default()
+        };
+        config.channels_config.telegram = Some(
+            toml::from_str::<TelegramConfig>(
+                "bot_token = \"123:abc\"\nallowed_users = [\"alice\"]",
+            )
+            .unwrap(),
+        );
+        config.save().await.unwrap();
+
+        let stripped = export(&config, None).await.unwrap();
+        assert!(!stripped.contains("sk-live"));
+        assert!(!stripped.contains("123:abc"));
+        assert!(stripped.contains("exported-model"));
+        assert!(!stripped.contains("[secrets]"));
+
+        let wrapped = export(&config, Some("pass")).await.unwrap();
+        assert!(!wrapped.contains("sk-live"));
+        assert!(wrapped.contains(WRAP_PREFIX));
+
+        // A fresh device: different model, no credentials.
+        let target = tempfile::tempdir().unwrap();
+        let fresh = Config {
+            config_path: target.path().join("config.toml"),
+            workspace_dir: target.path().join("workspace"),
+            api_key: Some("sk-local".into()),
+            ..Config::default()
+        };
+        fresh.save().await.unwrap();
+
+        assert!(import(&fresh, &wrapped, None, ChangeSource::Cli)
+            .await
+            .is_err());
+        assert!(import(&fresh, &wrapped, Some("wrong"), ChangeSource::Cli)
+            .await
+            .is_err());
+        let report = import(&fresh, &wrapped, Some("pass"), ChangeSource::Cli)
+            .await
+            .unwrap();
+        assert!(report.unwrapped_secrets.contains(&"api_key".to_string()));
+        let restored = Config::read_config_file(&fresh.config_path, fresh.workspace_dir.clone())
+            .await
+            .unwrap();
+        assert_eq!(restored.api_key.as_deref(), Some("sk-live"));
+        assert_eq!(restored.default_model.as_deref(), Some("exported-model"));
+        assert_eq!(
+            restored.channels_config.telegram.unwrap().bot_token,
+            "123:abc"
+        );
+
+        // A stripped template keeps the credentials already on the device.
+        let report = import(&fresh, &stripped, None, ChangeSource::Cli)
+            .await
+            .unwrap();
+        assert_eq!(
+            report.kept_secrets,
+            vec![
+                "api_key".to_string(),
+                "channels_config.telegram.bot_token".to_string()
+            ]
+        );
+        let restored = Config::read_config_file(&fresh.config_path, fresh.workspace_dir.clone())
+            .await
+            .unwrap();
+        assert_eq!(restored.api_key.as_deref(), Some("sk-live"));
+    }
+}
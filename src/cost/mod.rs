@@ -1,8 +1,13 @@
+pub mod observer;
 pub mod tracker;
 pub mod types;
 
 // Re-exported for potential external use (public API)
 #[allow(unused_imports)]
-pub use tracker::CostTracker;
+pub use observer::{with_cost_tracking, CostObserver};
 #[allow(unused_imports)]
-pub use types::{BudgetCheck, CostRecord, CostSummary, ModelStats, TokenUsage, UsagePeriod};
+pub use tracker::{CostTracker, MAX_USAGE_DAYS};
+#[allow(unused_imports)]
+pub use types::{
+    BudgetCheck, CostRecord, CostSummary, DailyUsage, ModelStats, TokenUsage, UsagePeriod,
+};
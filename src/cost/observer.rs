@@ -0,0 +1,136 @@
+//! Records provider token usage into the cost ledger.
+//!
+//! [`CostObserver`] wraps the configured observability backend and, for every
+//! successful `LlmResponse` that reports token counts, prices the usage and
+//! appends it to `state/costs.jsonl` through [`CostTracker`].
+
+use super::tracker::CostTracker;
+use crate::config::Config;
+use crate::observability::traits::ObserverMetric;
+use crate::observability::{Observer, ObserverEvent};
+use std::any::Any;
+use std::sync::Arc;
+
+pub struct CostObserver {
+    inner: Box<dyn Observer>,
+    tracker: Arc<CostTracker>,
+}
+
+impl CostObserver {
+    pub fn new(inner: Box<dyn Observer>, tracker: Arc<CostTracker>) -> Self {
+        Self { inner, tracker }
+    }
+}
+
+impl Observer for CostObserver {
+    fn record_event(&self, event: &ObserverEvent) {
+        self.inner.record_event(event);
+
+        if let ObserverEvent::LlmResponse {
+            provider,
+            model,
+            success: true,
+            input_tokens,
+            output_tokens,
+            ..
+        } = event
+        {
+            if input_tokens.is_none() && output_tokens.is_none() {
+                return;
+            }
+            let usage = self.tracker.price_usage(
+                provider,
+                model,
+                input_tokens.unwrap_or(0),
+                output_tokens.unwrap_or(0),
+            );
+            if let Err(e) = self.tracker.record_usage(usage) {
+                tracing::warn!("Failed to record token usage for {provider}/{model}: {e}");
+            }
+        }
+    }
+
+    fn record_metric(&self, metric: &ObserverMetric) {
+        self.inner.record_metric(metric);
+    }
+
+    fn flush(&self) {
+        self.inner.flush();
+    }
+
+    fn name(&self) -> &str {
+        "cost"
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+/// Wrap `observer` so token usage lands in the cost ledger when
+/// `[cost].enabled` is set. Returns `observer` unchanged otherwise, or when
+/// the ledger cannot be opened.
+pub fn with_cost_tracking(observer: Box<dyn Observer>, config: &Config) -> Box<dyn Observer> {
+    if !config.cost.enabled {
+        return observer;
+    }
+    match CostTracker::new(config.cost.clone(), &config.workspace_dir) {
+        Ok(tracker) => Box::new(CostObserver::new(observer, Arc::new(tracker))),
+        Err(e) => {
+            tracing::warn!("Failed to initialize cost tracker: {e}");
+            observer
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::schema::CostConfig;
+    use crate::observability::NoopObserver;
+    use std::time::Duration;
+
+    fn response(success: bool, input_tokens: Option<u64>) -> ObserverEvent {
+        ObserverEvent::LlmResponse {
+            provider: "openai".into(),
+            model: "gpt-4o".into(),
+            duration: Duration::from_millis(10),
+            success,
+            error_message: None,
+            input_tokens,
+            output_tokens: input_tokens.map(|_| 20),
+        }
+    }
+
+    #[test]
+    fn records_successful_responses_with_usage() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let config = CostConfig {
+            enabled: true,
+            ..CostConfig::default()
+        };
+        let tracker = Arc::new(CostTracker::new(config, tmp.path()).unwrap());
+        let observer = CostObserver::new(Box::new(NoopObserver), tracker.clone());
+
+        observer.record_event(&response(true, Some(100)));
+        observer.record_event(&response(true, None));
+        observer.record_event(&response(false, Some(100)));
+
+        let summary = tracker.get_summary().unwrap();
+        assert_eq!(summary.request_count, 1);
+        assert_eq!(summary.total_tokens, 120);
+        assert_eq!(summary.by_model["gpt-4o"].provider, "openai");
+    }
+
+    #[test]
+    fn with_cost_tracking_is_a_no_op_when_disabled() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let config = Config {
+            workspace_dir: tmp.path().to_path_buf(),
+            ..Config::default()
+        };
+        let observer = with_cost_tracking(Box::new(NoopObserver), &config);
+        assert_eq!(observer.name(), "noop");
+        assert!(!tmp.path().join("state").exists());
+    }
+}
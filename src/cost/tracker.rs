@@ -1,5 +1,7 @@
-use super::types::{BudgetCheck, CostRecord, CostSummary, ModelStats, TokenUsage, UsagePeriod};
-use crate::config::schema::CostConfig;
+use super::types::{
+    BudgetCheck, CostRecord, CostSummary, DailyUsage, ModelStats, TokenUsage, UsagePeriod,
+};
+use crate::config::schema::{CostConfig, ModelPricing};
 use anyhow::{anyhow, Context, Result};
 use chrono::{Datelike, NaiveDate, Utc};
 use parking_lot::{Mutex, MutexGuard};
@@ -9,6 +11,9 @@ use std::io::{BufRead, BufReader, Write};
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
+/// Longest window [`CostTracker::daily_usage`] reports.
+pub const MAX_USAGE_DAYS: u32 = 366;
+
 /// Cost tracker for API usage monitoring and budget enforcement.
 pub struct CostTracker {
     config: CostConfig,
@@ -34,6 +39,27 @@ impl CostTracker {
         })
     }
 
+    /// Build a usage record for `model` on `provider`, priced from
+    /// `[cost].prices`. Unpriced models (e.g. on-device ones) cost nothing.
+    pub fn price_usage(
+        &self,
+        provider: &str,
+        model: &str,
+        input_tokens: u64,
+        output_tokens: u64,
+    ) -> TokenUsage {
+        let (input_price, output_price) = lookup_pricing(&self.config.prices, provider, model)
+            .map_or((0.0, 0.0), |pricing| (pricing.input, pricing.output));
+        TokenUsage::new(
+            model,
+            input_tokens,
+            output_tokens,
+            input_price,
+            output_price,
+        )
+        .with_provider(provider)
+    }
+
     /// Get the session ID.
     pub fn session_id(&self) -> &str {
         &self.session_id
@@ -173,6 +199,65 @@ impl CostTracker {
         let storage = self.lock_storage();
         storage.get_cost_for_month(year, month)
     }
+
+    /// Per-day ledger totals for the last `days` days (UTC, at most
+    /// [`MAX_USAGE_DAYS`]), newest first. Days without requests are included
+    /// with zero totals.
+    pub fn daily_usage(&self, days: u32) -> Result<Vec<DailyUsage>> {
+        let today = Utc::now().date_naive();
+        let days = days.clamp(1, MAX_USAGE_DAYS);
+        let first_day = today - chrono::Duration::days(i64::from(days) - 1);
+
+        let mut by_day: HashMap<NaiveDate, Vec<CostRecord>> = HashMap::new();
+        {
+            let storage = self.lock_storage();
+            storage.for_each_record(|record| {
+                let date = record.usage.timestamp.naive_utc().date();
+                if date >= first_day && date <= today {
+                    by_day.entry(date).or_default().push(record);
+                }
+            })?;
+        }
+
+        let mut usage: Vec<DailyUsage> = first_day
+            .iter_days()
+            .take_while(|date| *date <= today)
+            .map(|date| {
+                let records = by_day.remove(&date).unwrap_or_default();
+                let mut by_model = build_provider_model_stats(&records);
+                by_model.sort_by(|a, b| b.cost_usd.total_cmp(&a.cost_usd));
+                DailyUsage {
+                    date,
+                    cost_usd: records.iter().map(|r| r.usage.cost_usd).sum(),
+                    total_tokens: records.iter().map(|r| r.usage.total_tokens).sum(),
+                    request_count: records.len(),
+                    by_model,
+                }
+            })
+            .collect();
+        usage.reverse();
+        Ok(usage)
+    }
+}
+
+/// Find pricing for `model`: an exact key, then `provider/model`, then a key
+/// whose model part (after the last `/`) matches.
+fn lookup_pricing<'a>(
+    prices: &'a HashMap<String, ModelPricing>,
+    provider: &str,
+    model: &str,
+) -> Option<&'a ModelPricing> {
+    let bare_model = model.rsplit('/').next().unwrap_or(model);
+    prices
+        .get(model)
+        .or_else(|| prices.get(&format!("{provider}/{model}")))
+        .or_else(|| {
+            prices
+                .iter()
+                .filter(|(key, _)| key.rsplit('/').next() == Some(bare_model))
+                .min_by_key(|(key, _)| *key)
+                .map(|(_, pricing)| pricing)
+        })
 }
 
 fn resolve_storage_path(workspace_dir: &Path) -> Result<PathBuf> {
@@ -212,6 +297,7 @@ fn build_session_model_stats(session_costs: &[CostRecord]) -> HashMap<String, Mo
             .entry(record.usage.model.clone())
             .or_insert_with(|| ModelStats {
                 model: record.usage.model.clone(),
+                provider: record.usage.provider.clone(),
                 cost_usd: 0.0,
                 total_tokens: 0,
                 request_count: 0,
@@ -225,6 +311,28 @@ fn build_session_model_stats(session_costs: &[CostRecord]) -> HashMap<String, Mo
     by_model
 }
 
+fn build_provider_model_stats(records: &[CostRecord]) -> Vec<ModelStats> {
+    let mut by_model: HashMap<(&str, &str), ModelStats> = HashMap::new();
+
+    for record in records {
+        let entry = by_model
+            .entry((record.usage.provider.as_str(), record.usage.model.as_str()))
+            .or_insert_with(|| ModelStats {
+                model: record.usage.model.clone(),
+                provider: record.usage.provider.clone(),
+                cost_usd: 0.0,
+                total_tokens: 0,
+                request_count: 0,
+            });
+
+        entry.cost_usd += record.usage.cost_usd;
+        entry.total_tokens += record.usage.total_tokens;
+        entry.request_count += 1;
+    }
+
+    by_model.into_values().collect()
+}
+
 /// Persistent storage for cost records.
 struct CostStorage {
     path: PathBuf,
@@ -523,6 +631,73 @@ mod tests {
         assert!((today_cost - valid_usage.cost_usd).abs() < f64::EPSILON);
     }
 
+    #[test]
+    fn price_usage_matches_provider_prefixed_and_bare_keys() {
+        let tmp = TempDir::new().unwrap();
+        let mut config = enabled_config();
+        config.prices = HashMap::from([(
+            "anthropic/claude-sonnet-4".to_string(),
+            ModelPricing {
+                input: 3.0,
+                output: 15.0,
+            },
+        )]);
+        let tracker = CostTracker::new(config, tmp.path()).unwrap();
+
+        for (provider, model) in [
+            ("openrouter", "anthropic/claude-sonnet-4"),
+            ("anthropic", "claude-sonnet-4"),
+        ] {
+            let usage = tracker.price_usage(provider, model, 1_000_000, 0);
+            assert_eq!(usage.provider, provider);
+            assert!(
+                (usage.cost_usd - 3.0).abs() < f64::EPSILON,
+                "{provider}/{model}"
+            );
+        }
+
+        let local = tracker.price_usage("local-llm", "qwen2.5-1.5b", 1_000_000, 1_000_000);
+        assert!(local.cost_usd.abs() < f64::EPSILON);
+        assert_eq!(local.total_tokens, 2_000_000);
+    }
+
+    #[test]
+    fn daily_usage_groups_ledger_by_day_and_provider_model() {
+        let tmp = TempDir::new().unwrap();
+        let storage_path = resolve_storage_path(tmp.path()).unwrap();
+        fs::create_dir_all(storage_path.parent().unwrap()).unwrap();
+
+        let mut old_usage = TokenUsage::new("gpt-4o", 100, 100, 1.0, 1.0).with_provider("openai");
+        old_usage.timestamp -= chrono::Duration::days(2);
+        let mut stale_usage = old_usage.clone();
+        stale_usage.timestamp -= chrono::Duration::days(30);
+        let mut file = File::create(&storage_path).unwrap();
+        for usage in [old_usage, stale_usage] {
+            let record = CostRecord::new("old-session", usage);
+            writeln!(file, "{}", serde_json::to_string(&record).unwrap()).unwrap();
+        }
+        drop(file);
+
+        let tracker = CostTracker::new(enabled_config(), tmp.path()).unwrap();
+        for (provider, input) in [("openai", 1_000), ("openai", 2_000), ("ollama", 500)] {
+            tracker
+                .record_usage(TokenUsage::new("m", input, 0, 1.0, 1.0).with_provider(provider))
+                .unwrap();
+        }
+
+        let days = tracker.daily_usage(3).unwrap();
+        assert_eq!(days.len(), 3);
+        assert_eq!(days[0].date, Utc::now().date_naive());
+        assert_eq!(days[0].request_count, 3);
+        assert_eq!(days[0].total_tokens, 3_500);
+        assert_eq!(days[0].by_model.len(), 2);
+        assert_eq!(days[0].by_model[0].provider, "openai");
+        assert_eq!(days[0].by_model[0].request_count, 2);
+        assert_eq!(days[1].request_count, 0);
+        assert_eq!(days[2].request_count, 1);
+        assert_eq!(days[2].by_model[0].model, "gpt-4o");
+    }
+
     #[test]
     fn invalid_budget_estimate_is_rejected() {
         let tmp = TempDir::new().unwrap();
@@ -5,6 +5,10 @@ use serde::{Deserialize, Serialize};
 pub struct TokenUsage {
     /// Model identifier (e.g., "anthropic/claude-sonnet-4-20250514")
     pub model: String,
+    /// Provider that served the request (empty for records written before
+    /// providers were tracked)
+    #[serde(default)]
+    pub provider: String,
     /// Input/prompt tokens
     pub input_tokens: u64,
     /// Output/completion tokens
@@ -46,6 +50,7 @@ impl TokenUsage {
 
         Self {
             model,
+            provider: String::new(),
             input_tokens,
             output_tokens,
             total_tokens,
@@ -54,6 +59,12 @@ impl TokenUsage {
         }
     }
 
+    /// Attribute the usage to a provider.
+    pub fn with_provider(mut self, provider: impl Into<String>) -> Self {
+        self.provider = provider.into();
+        self
+    }
+
     /// Get the total cost.
     pub fn cost(&self) -> f64 {
         self.cost_usd
@@ -131,6 +142,9 @@ pub struct CostSummary {
 pub struct ModelStats {
     /// Model name
     pub model: String,
+    /// Provider name
+    #[serde(default)]
+    pub provider: String,
     /// Total cost for this model
     pub cost_usd: f64,
     /// Total tokens for this model
@@ -139,6 +153,21 @@ pub struct ModelStats {
     pub request_count: usize,
 }
 
+/// Ledger totals for one UTC day.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DailyUsage {
+    /// Day (UTC)
+    pub date: chrono::NaiveDate,
+    /// Total cost for the day
+    pub cost_usd: f64,
+    /// Total tokens for the day
+    pub total_tokens: u64,
+    /// Number of requests for the day
+    pub request_count: usize,
+    /// Breakdown by provider and model, most expensive first
+    pub by_model: Vec<ModelStats>,
+}
+
 impl Default for CostSummary {
     fn default() -> Self {
         Self {
@@ -190,4 +219,16 @@ mod tests {
         assert!(!record.id.is_empty());
         assert_eq!(record.usage.model, "test/model");
     }
+
+    #[test]
+    fn legacy_records_without_provider_deserialize() {
+        let usage = TokenUsage::new("test/model", 10, 5, 1.0, 1.0).with_provider("openai");
+        assert_eq!(usage.provider, "openai");
+
+        let mut value = serde_json::to_value(&usage).unwrap();
+        value.as_object_mut().unwrap().remove("provider");
+        let legacy: TokenUsage = serde_json::from_value(value).unwrap();
+        assert!(legacy.provider.is_empty());
+        assert_eq!(legacy.total_tokens, 15);
+    }
 }
@@ -6,7 +6,9 @@ use crate::cron::{
     due_jobs, next_run_for_schedule, record_last_run, record_run, remove_job, reschedule_after_run,
     update_job, CronJob, CronJobPatch, DeliveryConfig, JobType, Schedule, SessionTarget,
 };
+use crate::runtime::{NativeRuntime, RuntimeAdapter};
 use crate::security::SecurityPolicy;
+use crate::tools::android_bridge::AndroidBridge;
 use anyhow::Result;
 use chrono::{DateTime, Utc};
 use futures_util::{stream, StreamExt};
@@ -27,6 +29,11 @@ pub async fn run(config: Config) -> Result<()> {
         &config.autonomy,
         &config.workspace_dir,
     ));
+    let runtime: Box<dyn RuntimeAdapter> = crate::runtime::create_runtime(&config.runtime)
+        .unwrap_or_else(|e| {
+            tracing::warn!("Scheduler runtime init failed ({e}); assuming native runtime");
+            Box::new(NativeRuntime::new())
+        });
 
     crate::health::mark_component_ok(SCHEDULER_COMPONENT);
 
@@ -35,6 +42,16 @@ pub async fn run(config: Config) -> Result<()> {
         // Keep scheduler liveness fresh even when there are no due jobs.
         crate::health::mark_component_ok(SCHEDULER_COMPONENT);
 
+        // Under Doze/App Standby, leave due jobs pending so they run together
+        // in the next maintenance window instead of failing one by one.
+        if runtime.is_background_restricted() {
+            tracing::debug!(
+                next_window = ?runtime.next_allowed_wakeup(),
+                "Scheduler deferring due jobs: background work restricted"
+            );
+            continue;
+        }
+
         let jobs = match due_jobs(&config, Utc::now()) {
             Ok(jobs) => jobs,
             Err(e) => {
@@ -50,7 +67,7 @@ pub async fn run(config: Config) -> Result<()> {
 
 pub async fn execute_job_now(config: &Config, job: &CronJob) -> (bool, String) {
     let security = SecurityPolicy::from_config(&config.autonomy, &config.workspace_dir);
-    execute_job_with_retry(config, &security, job).await
+    Box::pin(execute_job_with_retry(config, &security, job)).await
 }
 
 async fn execute_job_with_retry(
@@ -65,7 +82,8 @@ async fn execute_job_with_retry(
     for attempt in 0..=retries {
         let (success, output) = match job.job_type {
             JobType::Shell => run_job_command(config, security, job).await,
-            JobType::Agent => run_agent_job(config, security, job).await,
+            JobType::Agent => Box::pin(run_agent_job(config, security, job)).await,
+            JobType::Reminder => run_reminder_job(job),
         };
         last_output = output;
 
@@ -98,18 +116,21 @@ async fn process_due_jobs(
     crate::health::mark_component_ok(component);
 
     let max_concurrent = config.scheduler.max_concurrent.max(1);
-    let mut in_flight =
-        stream::iter(
-            jobs.into_iter().map(|job| {
-                let config = config.clone();
-                let security = Arc::clone(security);
-                let component = component.to_owned();
-                async move {
-                    execute_and_persist_job(&config, security.as_ref(), &job, &component).await
-                }
-            }),
-        )
-        .buffer_unordered(max_concurrent);
+    let mut in_flight = stream::iter(jobs.into_iter().map(|job| {
+        let config = config.clone();
+        let security = Arc::clone(security);
+        let component = component.to_owned();
+        async move {
+            Box::pin(execute_and_persist_job(
+                &config,
+                security.as_ref(),
+                &job,
+                &component,
+            ))
+            .await
+        }
+    }))
+    .buffer_unordered(max_concurrent);
 
     while let Some((job_id, success, output)) = in_flight.next().await {
         if !success {
@@ -128,7 +149,7 @@ async fn execute_and_persist_job(
     warn_if_high_frequency_agent_job(job);
 
     let started_at = Utc::now();
-    let (success, output) = execute_job_with_retry(config, security, job).await;
+    let (success, output) = Box::pin(execute_job_with_retry(config, security, job)).await;
     let finished_at = Utc::now();
     let success = persist_job_result(config, job, success, &output, started_at, finished_at).await;
 
@@ -173,7 +194,7 @@ async fn run_agent_job(
 
     let run_result = match job.session_target {
         SessionTarget::Main | SessionTarget::Isolated => {
-            crate::agent::run(
+            Box::pin(crate::agent::run(
                 config.clone(),
                 Some(prefixed_prompt),
                 None,
@@ -181,7 +202,7 @@ async fn run_agent_job(
                 config.default_temperature,
                 vec![],
                 false,
-            )
+            ))
             .await
         }
     };
@@ -199,6 +220,15 @@ async fn run_agent_job(
     }
 }
 
+/// Reminders have nothing to execute; their output is the text to deliver.
+fn run_reminder_job(job: &CronJob) -> (bool, String) {
+    let message = job.prompt.as_deref().map(str::trim).unwrap_or_default();
+    if message.is_empty() {
+        return (false, "reminder has no message".to_string());
+    }
+    (true, format!("Reminder\n{message}"))
+}
+
 async fn persist_job_result(
     config: &Config,
     job: &CronJob,
@@ -369,6 +399,44 @@ pub(crate) async fn deliver_announcement(
             );
             channel.send(&SendMessage::new(output, target)).await?;
         }
+        "android" => {
+            let android = &config.android;
+            if !android.enabled || !android.capabilities.notifications {
+                anyhow::bail!("android notifications are not enabled");
+            }
+            // The first line becomes the notification title; `target` is the
+            // Android notification channel id.
+            let (title, body) = output.split_once('\n').unwrap_or((output, ""));
+            let notification_channel = if target.trim().is_empty() {
+                "agent"
+            } else {
+                target.trim()
+            };
+            let result = AndroidBridge::new(android.bridge.clone())
+                .call(
+                    "post_notification",
+                    serde_json::json!({
+                        "title": title.trim().chars().take(120).collect::<String>(),
+                        "body": body.trim().chars().take(4000).collect::<String>(),
+                        "channel": notification_channel,
+                    }),
+                )
+                .await?;
+            if result.get("ok").and_then(serde_json::Value::as_bool) == Some(false) {
+                anyhow::bail!(
+                    "android notification failed: {}",
+                    result
+                        .get("error")
+                        .and_then(serde_json::Value::as_str)
+                        .unwrap_or("android_bridge_error")
+                );
+            }
+        }
+        "gateway" => {
+            // Pushed to connected `/ws` clients with `target` as the topic;
+            // dropped when none are connected.
+            crate::gateway::ws::notify(target, output);
+        }
         other => anyhow::bail!("unsupported delivery channel: {other}"),
     }
 
@@ -911,6 +979,7 @@ mod tests {
                 tz: None,
             },
             "deliver this",
+            None,
             SessionTarget::Isolated,
             None,
             Some(DeliveryConfig {
@@ -949,6 +1018,7 @@ mod tests {
                 tz: None,
             },
             "deliver this",
+            None,
             SessionTarget::Isolated,
             None,
             Some(DeliveryConfig {
@@ -985,6 +1055,7 @@ mod tests {
             Some("at-no-autodelete".into()),
             crate::cron::Schedule::At { at },
             "Hello",
+            None,
             SessionTarget::Isolated,
             None,
             None,
@@ -1020,4 +1091,33 @@ mod tests {
         let err = deliver_if_configured(&config, &job, "x").await.unwrap_err();
         assert!(err.to_string().contains("unsupported delivery channel"));
     }
+
+    #[tokio::test]
+    async fn reminder_job_delivers_through_android_notification() {
+        let tmp = TempDir::new().unwrap();
+        let mut config = test_config(&tmp).await;
+        let mut job = test_job("");
+        job.job_type = JobType::Reminder;
+        job.prompt = Some("Take the laundry out".into());
+        job.delivery = DeliveryConfig {
+            mode: "announce".into(),
+            channel: Some("android".into()),
+            to: Some("reminders".into()),
+            best_effort: false,
+        };
+
+        let security = SecurityPolicy::from_config(&config.autonomy, &config.workspace_dir);
+        let (success, output) = execute_job_with_retry(&config, &security, &job).await;
+        assert!(success);
+        assert_eq!(output, "Reminder\nTake the laundry out");
+
+        let err = deliver_if_configured(&config, &job, &output)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("not enabled"));
+
+        config.android.enabled = true;
+        config.android.capabilities.notifications = true;
+        assert!(deliver_if_configured(&config, &job, &output).await.is_ok());
+    }
 }
@@ -115,6 +115,48 @@ pub fn add_agent_job(
     get_job(config, &id)
 }
 
+/// Persist a reminder: at each run the scheduler delivers `message` verbatim
+/// through `delivery` instead of running a command or the agent.
+pub fn add_reminder_job(
+    config: &Config,
+    name: Option<String>,
+    schedule: Schedule,
+    message: &str,
+    delivery: DeliveryConfig,
+) -> Result<CronJob> {
+    let now = Utc::now();
+    validate_schedule(&schedule, now)?;
+    let next_run = next_run_for_schedule(&schedule, now)?;
+    let id = Uuid::new_v4().to_string();
+    let expression = schedule_cron_expression(&schedule).unwrap_or_default();
+    let schedule_json = serde_json::to_string(&schedule)?;
+    let delete_after_run = matches!(schedule, Schedule::At { .. });
+
+    with_connection(config, |conn| {
+        conn.execute(
+            "INSERT INTO cron_jobs (
+                id, expression, command, schedule, job_type, prompt, name, session_target, model,
+                enabled, delivery, delete_after_run, created_at, next_run
+             ) VALUES (?1, ?2, '', ?3, 'reminder', ?4, ?5, 'isolated', NULL, 1, ?6, ?7, ?8, ?9)",
+            params![
+                id,
+                expression,
+                schedule_json,
+                message,
+                name,
+                serde_json::to_string(&delivery)?,
+                if delete_after_run { 1 } else { 0 },
+                now.to_rfc3339(),
+                next_run.to_rfc3339(),
+            ],
+        )
+        .context("Failed to insert cron reminder job")?;
+        Ok(())
+    })?;
+
+    get_job(config, &id)
+}
+
 pub fn list_jobs(config: &Config) -> Result<Vec<CronJob>> {
     with_connection(config, |conn| {
         let mut stmt = conn.prepare(
@@ -741,6 +783,34 @@ mod tests {
         assert_eq!(job.job_type, JobType::Agent);
     }
 
+    #[test]
+    fn add_reminder_job_persists_message_and_delivery() {
+        let tmp = TempDir::new().unwrap();
+        let config = test_config(&tmp);
+        let at = Utc::now() + ChronoDuration::minutes(30);
+        let delivery = DeliveryConfig {
+            mode: "announce".into(),
+            channel: Some("android".into()),
+            to: Some("device".into()),
+            best_effort: false,
+        };
+
+        let job = add_reminder_job(
+            &config,
+            Some("Call mom".into()),
+            Schedule::At { at },
+            "Call mom back",
+            delivery.clone(),
+        )
+        .unwrap();
+
+        let stored = get_job(&config, &job.id).unwrap();
+        assert_eq!(stored.job_type, JobType::Reminder);
+        assert_eq!(stored.prompt.as_deref(), Some("Call mom back"));
+        assert_eq!(stored.delivery, delivery);
+        assert!(stored.delete_after_run);
+    }
+
     #[test]
     fn job_type_from_sql_rejects_invalid_value() {
         let tmp = TempDir::new().unwrap();
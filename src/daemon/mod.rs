@@ -1,6 +1,11 @@
+mod reload;
+
+pub use reload::{note_paired_tokens_saved, request_reload};
+
 use crate::config::Config;
 use anyhow::Result;
 use chrono::Utc;
+use std::collections::HashMap;
 use std::future::Future;
 use std::path::PathBuf;
 use tokio::task::JoinHandle;
@@ -8,13 +13,31 @@ use tokio::time::Duration;
 
 const STATUS_FLUSH_SECONDS: u64 = 5;
 
-pub async fn run(config: Config, host: String, port: u16) -> Result<()> {
-    let initial_backoff = config.reliability.channel_initial_backoff_secs.max(1);
-    let max_backoff = config
-        .reliability
-        .channel_max_backoff_secs
-        .max(initial_backoff);
+/// How often the config file is checked for edits.
+const CONFIG_POLL_SECONDS: u64 = 2;
+
+/// Delay between noticing a change and applying it.
+const RELOAD_DEBOUNCE_MS: u64 = 500;
+
+/// A daemon run at least this long is considered stable, so the watchdog
+/// resets its restart backoff after it exits.
+const WATCHDOG_STABLE_RUN_SECONDS: u64 = 300;
+
+/// Lifecycle event emitted by [`spawn_daemon_watchdog`].
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum DaemonEvent {
+    /// The daemon future was (re)started. `attempt` starts at 1.
+    Started { attempt: u64 },
+    /// The daemon future returned or panicked and will be restarted.
+    Exited {
+        attempt: u64,
+        error: String,
+        restart_in_secs: u64,
+    },
+}
 
+pub async fn run(config: Config, host: String, port: u16) -> Result<()> {
     crate::health::mark_component_ok("daemon");
 
     if config.heartbeat.enabled {
@@ -23,86 +46,228 @@ pub async fn run(config: Config, host: String, port: u16) -> Result<()> {
                 .await;
     }
 
-    let mut handles: Vec<JoinHandle<()>> = vec![spawn_state_writer(config.clone())];
+    let state_writer = spawn_state_writer(config.clone());
+    let mut components: HashMap<&'static str, JoinHandle<()>> = HashMap::new();
+    for name in reload::component_names() {
+        if let Some(handle) = spawn_component(name, &config, &host, port) {
+            components.insert(name, handle);
+        }
+    }
 
-    {
-        let gateway_cfg = config.clone();
-        let gateway_host = host.clone();
-        handles.push(spawn_component_supervisor(
-            "gateway",
-            initial_backoff,
-            max_backoff,
-            move || {
-                let cfg = gateway_cfg.clone();
-                let host = gateway_host.clone();
-                async move { crate::gateway::run_gateway(&host, port, cfg).await }
-            },
-        ));
+    println!("🧠 ZeroClaw daemon started");
+    println!("   Gateway:  http://{host}:{port}");
+    println!(
+        "   Components: {}",
+        reload::component_names().collect::<Vec<_>>().join(", ")
+    );
+    println!(
+        "   Config:   {} (watched for changes)",
+        config.config_path.display()
+    );
+    println!("   Ctrl+C to stop");
+
+    let mut config = config;
+    let mut config_mtime = reload::config_mtime(&config).await;
+    let mut poll = tokio::time::interval(Duration::from_secs(CONFIG_POLL_SECONDS));
+    poll.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+    loop {
+        tokio::select! {
+            result = tokio::signal::ctrl_c() => {
+                result?;
+                break;
+            }
+            () = reload::reload_requested() => {}
+            _ = poll.tick() => {
+                let mtime = reload::config_mtime(&config).await;
+                if mtime == config_mtime {
+                    continue;
+                }
+            }
+        }
+
+        // Let editors finish writing and in-flight gateway replies go out
+        // before components restart.
+        tokio::time::sleep(Duration::from_millis(RELOAD_DEBOUNCE_MS)).await;
+        config_mtime = reload::config_mtime(&config).await;
+        if let Some(reloaded) = reload_config(&config, &mut components, &host, port).await {
+            config = reloaded;
+        }
     }
 
-    {
-        if has_supervised_channels(&config) {
-            let channels_cfg = config.clone();
-            handles.push(spawn_component_supervisor(
-                "channels",
+    crate::health::mark_component_error("daemon", "shutdown requested");
+
+    state_writer.abort();
+    for handle in components.values() {
+        handle.abort();
+    }
+    let _ = state_writer.await;
+    for (_, handle) in components {
+        let _ = handle.await;
+    }
+
+    Ok(())
+}
+
+/// Re-read the config file and restart the components whose settings
+/// changed and were not applied live. Returns the new config, or `None` when
+/// it could not be loaded or nothing changed.
+async fn reload_config(
+    current: &Config,
+    components: &mut HashMap<&'static str, JoinHandle<()>>,
+    host: &str,
+    port: u16,
+) -> Option<Config> {
+    let config = match current.reload().await {
+        Ok(config) => config,
+        Err(e) => {
+            crate::health::mark_component_error("config", format!("reload failed: {e:#}"));
+            tracing::warn!("Config reload failed; keeping the running config: {e:#}");
+            return None;
+        }
+    };
+    crate::health::mark_component_ok("config");
+
+    let plan = reload::ReloadPlan::diff(current, &config);
+    if plan.is_empty() {
+        return None;
+    }
+    let plan = plan.without_applied(&config);
+    if plan.is_empty() {
+        return Some(config);
+    }
+
+    if plan.subsystems.contains(&reload::Subsystem::Observability) {
+        crate::observability::runtime_trace::init_from_config(
+            &config.observability,
+            &config.workspace_dir,
+        );
+        crate::observability::wire_log::init_from_config(
+            &config.observability,
+            &config.workspace_dir,
+        );
+    }
+    if plan.subsystems.contains(&reload::Subsystem::Heartbeat) && config.heartbeat.enabled {
+        let _ =
+            crate::heartbeat::engine::HeartbeatEngine::ensure_heartbeat_file(&config.workspace_dir)
+                .await;
+    }
+
+    let restarted = plan.components();
+    for &name in &restarted {
+        if let Some(handle) = components.remove(name) {
+            handle.abort();
+            let _ = handle.await;
+        }
+        if let Some(handle) = spawn_component(name, &config, host, port) {
+            components.insert(name, handle);
+        }
+    }
+
+    tracing::info!(
+        changed = %plan.summary(),
+        restarted = ?restarted,
+        "Config reloaded"
+    );
+    Some(config)
+}
+
+/// Start the supervisor for one daemon component, or mark it healthy and
+/// return `None` when the config leaves it disabled.
+fn spawn_component(
+    name: &'static str,
+    config: &Config,
+    host: &str,
+    port: u16,
+) -> Option<JoinHandle<()>> {
+    let initial_backoff = config.reliability.channel_initial_backoff_secs.max(1);
+    let max_backoff = config
+        .reliability
+        .channel_max_backoff_secs
+        .max(initial_backoff);
+    let cfg = config.clone();
+
+    match name {
+        "gateway" => {
+            let host = host.to_string();
+            Some(spawn_component_supervisor(
+                name,
                 initial_backoff,
                 max_backoff,
                 move || {
-                    let cfg = channels_cfg.clone();
-                    async move { crate::channels::start_channels(cfg).await }
+                    let cfg = cfg.clone();
+                    let host = host.clone();
+                    async move { Box::pin(crate::gateway::run_gateway(&host, port, cfg)).await }
                 },
-            ));
-        } else {
-            crate::health::mark_component_ok("channels");
+            ))
+        }
+        "channels" if has_supervised_channels(config) => Some(spawn_component_supervisor(
+            name,
+            initial_backoff,
+            max_backoff,
+            move || {
+                let cfg = cfg.clone();
+                async move { Box::pin(crate::channels::start_channels(cfg)).await }
+            },
+        )),
+        "channels" => {
+            crate::health::mark_component_ok(name);
             tracing::info!("No real-time channels configured; channel supervisor disabled");
+            None
         }
-    }
-
-    if config.heartbeat.enabled {
-        let heartbeat_cfg = config.clone();
-        handles.push(spawn_component_supervisor(
-            "heartbeat",
+        "heartbeat" if config.heartbeat.enabled => Some(spawn_component_supervisor(
+            name,
             initial_backoff,
             max_backoff,
             move || {
-                let cfg = heartbeat_cfg.clone();
+                let cfg = cfg.clone();
                 async move { Box::pin(run_heartbeat_worker(cfg)).await }
             },
-        ));
-    }
-
-    if config.cron.enabled {
-        let scheduler_cfg = config.clone();
-        handles.push(spawn_component_supervisor(
-            "scheduler",
+        )),
+        "scheduler" if config.cron.enabled => Some(spawn_component_supervisor(
+            name,
             initial_backoff,
             max_backoff,
             move || {
-                let cfg = scheduler_cfg.clone();
+                let cfg = cfg.clone();
                 async move { crate::cron::scheduler::run(cfg).await }
             },
-        ));
-    } else {
-        crate::health::mark_component_ok("scheduler");
-        tracing::info!("Cron disabled; scheduler supervisor not started");
-    }
-
-    println!("🧠 ZeroClaw daemon started");
-    println!("   Gateway:  http://{host}:{port}");
-    println!("   Components: gateway, channels, heartbeat, scheduler");
-    println!("   Ctrl+C to stop");
-
-    tokio::signal::ctrl_c().await?;
-    crate::health::mark_component_error("daemon", "shutdown requested");
-
-    for handle in &handles {
-        handle.abort();
-    }
-    for handle in handles {
-        let _ = handle.await;
+        )),
+        "scheduler" => {
+            crate::health::mark_component_ok(name);
+            tracing::info!("Cron disabled; scheduler supervisor not started");
+            None
+        }
+        "provider_probe" if config.reliability.provider_probe_interval_secs > 0 => Some(
+            spawn_component_supervisor(name, initial_backoff, max_backoff, move || {
+                let cfg = cfg.clone();
+                async move { crate::providers::probe::run(cfg).await }
+            }),
+        ),
+        "provider_probe" => {
+            crate::health::mark_component_ok(name);
+            tracing::info!(
+                "Provider probing disabled (reliability.provider_probe_interval_secs = 0)"
+            );
+            None
+        }
+        "config_sync" if config.config_sync.enabled && config.config_sync.interval_minutes > 0 => {
+            Some(spawn_component_supervisor(
+                name,
+                initial_backoff,
+                max_backoff,
+                move || {
+                    let cfg = cfg.clone();
+                    async move { Box::pin(crate::config::sync::run(cfg)).await }
+                },
+            ))
+        }
+        "heartbeat" | "config_sync" => {
+            crate::health::mark_component_ok(name);
+            None
+        }
+        _ => None,
     }
-
-    Ok(())
 }
 
 pub fn state_file_path(config: &Config) -> PathBuf {
@@ -173,6 +338,59 @@ where
     })
 }
 
+/// Keep an embedded daemon alive (used by the Android foreground service).
+///
+/// Runs `run_daemon` in its own task so panics are caught, records exits on
+/// the `daemon` health component, and restarts with exponential backoff.
+/// `on_event` is called for every start and exit.
+pub fn spawn_daemon_watchdog<F, Fut, E>(
+    initial_backoff_secs: u64,
+    max_backoff_secs: u64,
+    mut run_daemon: F,
+    on_event: E,
+) -> JoinHandle<()>
+where
+    F: FnMut() -> Fut + Send + 'static,
+    Fut: Future<Output = Result<()>> + Send + 'static,
+    E: Fn(DaemonEvent) + Send + Sync + 'static,
+{
+    tokio::spawn(async move {
+        let initial_backoff = initial_backoff_secs.max(1);
+        let max_backoff = max_backoff_secs.max(initial_backoff);
+        let mut backoff = initial_backoff;
+        let mut attempt: u64 = 0;
+
+        loop {
+            attempt += 1;
+            on_event(DaemonEvent::Started { attempt });
+            let started = std::time::Instant::now();
+
+            let error = match tokio::spawn(run_daemon()).await {
+                Ok(Ok(())) => "daemon exited unexpectedly".to_string(),
+                Ok(Err(e)) => e.to_string(),
+                Err(e) if e.is_panic() => "daemon panicked".to_string(),
+                Err(e) => e.to_string(),
+            };
+
+            if started.elapsed().as_secs() >= WATCHDOG_STABLE_RUN_SECONDS {
+                backoff = initial_backoff;
+            }
+
+            crate::health::mark_component_error("daemon", &error);
+            crate::health::bump_component_restart("daemon");
+            tracing::error!("Daemon exited (attempt {attempt}): {error}; restarting in {backoff}s");
+            on_event(DaemonEvent::Exited {
+                attempt,
+                error,
+                restart_in_secs: backoff,
+            });
+
+            tokio::time::sleep(Duration::from_secs(backoff)).await;
+            backoff = backoff.saturating_mul(2).min(max_backoff);
+        }
+    })
+}
+
 async fn run_heartbeat_worker(config: Config) -> Result<()> {
     let observer: std::sync::Arc<dyn crate::observability::Observer> =
         std::sync::Arc::from(crate::observability::create_observer(&config.observability));
@@ -183,11 +401,19 @@ async fn run_heartbeat_worker(config: Config) -> Result<()> {
     );
     let delivery = heartbeat_delivery_target(&config)?;
 
-    let interval_mins = config.heartbeat.interval_minutes.max(5);
-    let mut interval = tokio::time::interval(Duration::from_secs(u64::from(interval_mins) * 60));
+    let plan = crate::heartbeat::policy::HeartbeatPlan::from_config(&config.heartbeat)?;
+    let mut next_run = plan.first_run(Utc::now())?;
 
     loop {
-        interval.tick().await;
+        let wait = (next_run - Utc::now()).to_std().unwrap_or_default();
+        tokio::time::sleep(wait).await;
+        let now = Utc::now();
+        next_run = plan.next_run(now.max(next_run))?;
+
+        if let Some(reason) = plan.skip_reason(now, crate::runtime::power::battery_state()) {
+            tracing::info!("Skipping heartbeat run: {reason}");
+            continue;
+        }
 
         let file_tasks = engine.collect_tasks().await?;
         let tasks = heartbeat_tasks_for_tick(file_tasks, config.heartbeat.message.as_deref());
@@ -196,9 +422,9 @@ async fn run_heartbeat_worker(config: Config) -> Result<()> {
         }
 
         for task in tasks {
-            let prompt = format!("[Heartbeat Task] {task}");
+            let prompt = plan.prompt(&task, Utc::now());
             let temp = config.default_temperature;
-            match crate::agent::run(
+            match Box::pin(crate::agent::run(
                 config.clone(),
                 Some(prompt),
                 None,
@@ -206,7 +432,7 @@ async fn run_heartbeat_worker(config: Config) -> Result<()> {
                 temp,
                 vec![],
                 false,
-            )
+            ))
             .await
             {
                 Ok(output) => {
@@ -312,6 +538,7 @@ fn validate_heartbeat_channel_config(config: &Config, channel: &str) -> Result<(
                 );
             }
         }
+        "gateway" => {}
         other => anyhow::bail!("unsupported heartbeat.target channel: {other}"),
     }
 
@@ -388,6 +615,81 @@ mod tests {
             .contains("component exited unexpectedly"));
     }
 
+    #[tokio::test]
+    async fn reload_keeps_running_config_when_file_is_invalid() {
+        let tmp = TempDir::new().unwrap();
+        let config = test_config(&tmp);
+        std::fs::write(&config.config_path, "default_temperature = \"hot\"").unwrap();
+
+        let mut components = HashMap::new();
+        let reloaded = reload_config(&config, &mut components, "127.0.0.1", 0).await;
+
+        assert!(reloaded.is_none());
+        assert!(components.is_empty());
+        let snapshot = crate::health::snapshot_json();
+        assert_eq!(snapshot["components"]["config"]["status"], "error");
+        assert!(snapshot["components"]["config"]["last_error"]
+            .as_str()
+            .unwrap_or_default()
+            .contains("reload failed"));
+    }
+
+    #[tokio::test]
+    async fn watchdog_restarts_failed_daemon_and_reports_events() {
+        let events = std::sync::Arc::new(parking_lot::Mutex::new(Vec::new()));
+        let sink = events.clone();
+        let handle = spawn_daemon_watchdog(
+            1,
+            1,
+            || async { anyhow::bail!("bind failed") },
+            move |event| sink.lock().push(event),
+        );
+
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        handle.abort();
+        let _ = handle.await;
+
+        let events = events.lock().clone();
+        assert_eq!(events[0], DaemonEvent::Started { attempt: 1 });
+        assert_eq!(
+            events[1],
+            DaemonEvent::Exited {
+                attempt: 1,
+                error: "bind failed".into(),
+                restart_in_secs: 1,
+            }
+        );
+        let snapshot = crate::health::snapshot_json();
+        assert!(
+            snapshot["components"]["daemon"]["restart_count"]
+                .as_u64()
+                .unwrap_or(0)
+                >= 1
+        );
+    }
+
+    #[tokio::test]
+    async fn watchdog_survives_daemon_panic() {
+        let events = std::sync::Arc::new(parking_lot::Mutex::new(Vec::new()));
+        let sink = events.clone();
+        let handle = spawn_daemon_watchdog(
+            1,
+            1,
+            || async { panic!("boom") },
+            move |event| sink.lock().push(event),
+        );
+
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert!(!handle.is_finished());
+        handle.abort();
+        let _ = handle.await;
+
+        assert!(events.lock().iter().any(|event| matches!(
+            event,
+            DaemonEvent::Exited { error, .. } if error == "daemon panicked"
+        )));
+    }
+
     #[test]
     fn detects_no_supervised_channels() {
         let config = Config::default();
@@ -404,6 +706,8 @@ mod tests {
             draft_update_interval_ms: 1000,
             interrupt_on_new_message: false,
             mention_only: false,
+            notify_chat_id: None,
+            notify_targets: Default::default(),
         });
         assert!(has_supervised_channels(&config));
     }
@@ -513,6 +817,18 @@ mod tests {
             .contains("unsupported heartbeat.target channel"));
     }
 
+    #[test]
+    fn heartbeat_delivery_target_accepts_gateway_without_channel_config() {
+        let mut config = Config::default();
+        config.heartbeat.target = Some("gateway".into());
+        config.heartbeat.to = Some("heartbeat".into());
+        let target = heartbeat_delivery_target(&config).unwrap();
+        assert_eq!(
+            target,
+            Some(("gateway".to_string(), "heartbeat".to_string()))
+        );
+    }
+
     #[test]
     fn heartbeat_delivery_target_requires_channel_configuration() {
         let mut config = Config::default();
@@ -536,6 +852,8 @@ mod tests {
             draft_update_interval_ms: 1000,
             interrupt_on_new_message: false,
             mention_only: false,
+            notify_chat_id: None,
+            notify_targets: Default::default(),
         });
 
         let target = heartbeat_delivery_target(&config).unwrap();
@@ -0,0 +1,403 @@
+//! Config hot-reload for the daemon.
+//!
+//! The daemon polls `config.toml` for changes and also reloads on demand
+//! ([`request_reload`], used by the gateway and the Android bridge). A reload
+//! diffs the old and new config by top-level section and restarts only the
+//! components that read a changed section; the rest keep running. Sections
+//! every agent reads (model, tools, security, observability and the rest of
+//! [`Subsystem::Agent`]) restart every component that runs one.
+//!
+//! Paired tokens the gateway saved itself ([`note_paired_tokens_saved`]) are
+//! already live in the running gateway and do not restart it.
+
+use crate::config::Config;
+use parking_lot::Mutex;
+use std::collections::BTreeSet;
+use std::sync::OnceLock;
+use tokio::sync::Notify;
+
+/// Area of the config a top-level section belongs to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Subsystem {
+    Model,
+    Tools,
+    Security,
+    Channels,
+    Gateway,
+    Scheduler,
+    Heartbeat,
+    Observability,
+    /// Remote config sync settings.
+    ConfigSync,
+    /// Stored `[profiles]`; the active one is already copied to the top
+    /// level, so no component reads them.
+    Profiles,
+    /// Memory, identity, cost and other settings read when an agent is built.
+    Agent,
+}
+
+impl Subsystem {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Model => "model",
+            Self::Tools => "tools",
+            Self::Security => "security",
+            Self::Channels => "channels",
+            Self::Gateway => "gateway",
+            Self::Scheduler => "scheduler",
+            Self::Heartbeat => "heartbeat",
+            Self::Observability => "observability",
+            Self::ConfigSync => "config_sync",
+            Self::Profiles => "profiles",
+            Self::Agent => "agent",
+        }
+    }
+}
+
+/// Top-level config keys and their subsystem. Keys not listed are
+/// [`Subsystem::Agent`].
+const SECTION_SUBSYSTEMS: &[(&str, Subsystem)] = &[
+    ("api_key", Subsystem::Model),
+    ("api_url", Subsystem::Model),
+    ("default_provider", Subsystem::Model),
+    ("default_model", Subsystem::Model),
+    ("model_providers", Subsystem::Model),
+    ("default_temperature", Subsystem::Model),
+    ("reliability", Subsystem::Model),
+    ("model_routes", Subsystem::Model),
+    ("embedding_routes", Subsystem::Model),
+    ("query_classification", Subsystem::Model),
+    ("provider_http", Subsystem::Model),
+    ("proxy", Subsystem::Model),
+    ("gemini", Subsystem::Model),
+    ("azure_openai", Subsystem::Model),
+    ("openrouter", Subsystem::Model),
+    ("local_llm", Subsystem::Model),
+    ("browser", Subsystem::Tools),
+    ("http_request", Subsystem::Tools),
+    ("web_fetch", Subsystem::Tools),
+    ("web_search", Subsystem::Tools),
+    ("composio", Subsystem::Tools),
+    ("mcp", Subsystem::Tools),
+    ("plugins", Subsystem::Tools),
+    ("home_assistant", Subsystem::Tools),
+    ("hardware", Subsystem::Tools),
+    ("peripherals", Subsystem::Tools),
+    ("agents", Subsystem::Tools),
+    ("skills", Subsystem::Tools),
+    ("multimodal", Subsystem::Tools),
+    ("transcription", Subsystem::Tools),
+    ("hooks", Subsystem::Tools),
+    ("autonomy", Subsystem::Security),
+    ("security", Subsystem::Security),
+    ("secrets", Subsystem::Security),
+    ("runtime", Subsystem::Security),
+    ("android", Subsystem::Security),
+    ("channels_config", Subsystem::Channels),
+    ("gateway", Subsystem::Gateway),
+    (PAIRED_TOKENS_SECTION, Subsystem::Gateway),
+    ("tunnel", Subsystem::Gateway),
+    ("cron", Subsystem::Scheduler),
+    ("scheduler", Subsystem::Scheduler),
+    ("heartbeat", Subsystem::Heartbeat),
+    ("observability", Subsystem::Observability),
+    ("config_sync", Subsystem::ConfigSync),
+    ("profiles", Subsystem::Profiles),
+    ("active_profile", Subsystem::Profiles),
+];
+
+/// `gateway.paired_tokens`, diffed apart from the rest of `[gateway]` so
+/// tokens the gateway saved itself can be told apart from other edits.
+const PAIRED_TOKENS_SECTION: &str = "gateway.paired_tokens";
+
+/// Subsystems every component that builds an agent depends on.
+const AGENT_SUBSYSTEMS: &[Subsystem] = &[
+    Subsystem::Model,
+    Subsystem::Tools,
+    Subsystem::Security,
+    Subsystem::Observability,
+    Subsystem::Agent,
+];
+
+struct Component {
+    name: &'static str,
+    runs_agent: bool,
+    reads: &'static [Subsystem],
+}
+
+/// Daemon components in start order, with the subsystems they read.
+const COMPONENTS: &[Component] = &[
+    Component {
+        name: "gateway",
+        runs_agent: true,
+        reads: &[Subsystem::Gateway],
+    },
+    Component {
+        name: "channels",
+        runs_agent: true,
+        reads: &[Subsystem::Channels],
+    },
+    Component {
+        name: "heartbeat",
+        runs_agent: true,
+        reads: &[Subsystem::Heartbeat, Subsystem::Channels],
+    },
+    Component {
+        name: "scheduler",
+        runs_agent: true,
+        reads: &[Subsystem::Scheduler, Subsystem::Channels],
+    },
+    Component {
+        name: "provider_probe",
+        runs_agent: false,
+        reads: &[Subsystem::Model],
+    },
+    Component {
+        name: "config_sync",
+        runs_agent: false,
+        reads: &[Subsystem::ConfigSync],
+    },
+];
+
+/// Names of all daemon components, in start order.
+pub fn component_names() -> impl Iterator<Item = &'static str> {
+    COMPONENTS.iter().map(|component| component.name)
+}
+
+fn section_subsystem(key: &str) -> Subsystem {
+    SECTION_SUBSYSTEMS
+        .iter()
+        .find(|(section, _)| *section == key)
+        .map_or(Subsystem::Agent, |(_, subsystem)| *subsystem)
+}
+
+impl Component {
+    fn depends_on(&self, subsystem: Subsystem) -> bool {
+        self.reads.contains(&subsystem)
+            || (self.runs_agent && AGENT_SUBSYSTEMS.contains(&subsystem))
+    }
+}
+
+/// What changed between two configs.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ReloadPlan {
+    /// Changed top-level config keys, sorted.
+    pub sections: Vec<String>,
+    pub subsystems: BTreeSet<Subsystem>,
+}
+
+impl ReloadPlan {
+    /// Compare two configs section by section.
+    pub fn diff(old: &Config, new: &Config) -> Self {
+        let old = sections_of(old);
+        let new = sections_of(new);
+
+        let keys: BTreeSet<&String> = old.keys().chain(new.keys()).collect();
+        let sections: Vec<String> = keys
+            .into_iter()
+            .filter(|key| old.get(*key) != new.get(*key))
+            .cloned()
+            .collect();
+        Self::from_sections(sections)
+    }
+
+    fn from_sections(sections: Vec<String>) -> Self {
+        let subsystems = sections.iter().map(|key| section_subsystem(key)).collect();
+        Self {
+            sections,
+            subsystems,
+        }
+    }
+
+    /// The plan without changes the running gateway already applied: paired
+    /// tokens equal to those it last saved.
+    pub fn without_applied(self, new: &Config) -> Self {
+        let saved = saved_paired_tokens().lock().clone();
+        self.without_saved_tokens(saved.as_deref(), new)
+    }
+
+    fn without_saved_tokens(self, saved: Option<&[String]>, new: &Config) -> Self {
+        if saved != Some(new.gateway.paired_tokens.as_slice()) {
+            return self;
+        }
+        Self::from_sections(
+            self.sections
+                .into_iter()
+                .filter(|section| section != PAIRED_TOKENS_SECTION)
+                .collect(),
+        )
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.sections.is_empty()
+    }
+
+    /// Whether `component` reads any changed section and must restart.
+    pub fn restarts(&self, component: &str) -> bool {
+        COMPONENTS
+            .iter()
+            .find(|candidate| candidate.name == component)
+            .is_some_and(|component| {
+                self.subsystems
+                    .iter()
+                    .any(|subsystem| component.depends_on(*subsystem))
+            })
+    }
+
+    /// Components that must restart, in start order.
+    pub fn components(&self) -> Vec<&'static str> {
+        component_names()
+            .filter(|name| self.restarts(name))
+            .collect()
+    }
+
+    /// Human-readable summary for logs, e.g. `model (default_model)`.
+    pub fn summary(&self) -> String {
+        self.subsystems
+            .iter()
+            .map(|subsystem| {
+                let sections: Vec<&str> = self
+                    .sections
+                    .iter()
+                    .filter(|key| section_subsystem(key) == *subsystem)
+                    .map(String::as_str)
+                    .collect();
+                format!("{} ({})", subsystem.as_str(), sections.join(", "))
+            })
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+/// Top-level sections of `config` as JSON, with `gateway.paired_tokens`
+/// split out of `gateway`.
+fn sections_of(config: &Config) -> serde_json::Map<String, serde_json::Value> {
+    let mut sections = match serde_json::to_value(config) {
+        Ok(serde_json::Value::Object(sections)) => sections,
+        _ => serde_json::Map::new(),
+    };
+    let tokens = sections
+        .get_mut("gateway")
+        .and_then(serde_json::Value::as_object_mut)
+        .and_then(|gateway| gateway.remove("paired_tokens"));
+    if let Some(tokens) = tokens {
+        sections.insert(PAIRED_TOKENS_SECTION.to_string(), tokens);
+    }
+    sections
+}
+
+fn saved_paired_tokens() -> &'static Mutex<Option<Vec<String>>> {
+    static TOKENS: OnceLock<Mutex<Option<Vec<String>>>> = OnceLock::new();
+    TOKENS.get_or_init(|| Mutex::new(None))
+}
+
+/// Record the paired tokens the running gateway is saving to the config
+/// file. It holds them in memory already, so the reload their write triggers
+/// does not restart it.
+pub fn note_paired_tokens_saved(tokens: &[String]) {
+    *saved_paired_tokens().lock() = Some(tokens.to_vec());
+}
+
+fn reload_signal() -> &'static Notify {
+    static SIGNAL: OnceLock<Notify> = OnceLock::new();
+    SIGNAL.get_or_init(Notify::new)
+}
+
+/// Ask the running daemon to re-read its config file.
+///
+/// Safe to call when no daemon is running; the request is kept until one
+/// starts waiting.
+pub fn request_reload() {
+    reload_signal().notify_one();
+}
+
+/// Wait for the next [`request_reload`] call.
+pub async fn reload_requested() {
+    reload_signal().notified().await;
+}
+
+/// Modification time of the config file, if it can be read.
+pub async fn config_mtime(config: &Config) -> Option<std::time::SystemTime> {
+    tokio::fs::metadata(&config.config_path)
+        .await
+        .and_then(|meta| meta.modified())
+        .ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unchanged_config_needs_no_restart() {
+        let config = Config::default();
+        let plan = ReloadPlan::diff(&config, &config.clone());
+        assert!(plan.is_empty());
+        assert!(plan.components().is_empty());
+    }
+
+    #[test]
+    fn model_change_restarts_every_agent_component() {
+        let old = Config::default();
+        let mut new = old.clone();
+        new.default_model = Some("gpt-4o-mini".into());
+
+        let plan = ReloadPlan::diff(&old, &new);
+        assert_eq!(plan.sections, vec!["default_model".to_string()]);
+        assert_eq!(
+            plan.components(),
+            vec![
+                "gateway",
+                "channels",
+                "heartbeat",
+                "scheduler",
+                "provider_probe"
+            ]
+        );
+        assert_eq!(plan.summary(), "model (default_model)");
+    }
+
+    #[test]
+    fn section_changes_restart_only_their_components() {
+        let old = Config::default();
+
+        let mut new = old.clone();
+        new.gateway.require_pairing = !old.gateway.require_pairing;
+        assert_eq!(ReloadPlan::diff(&old, &new).components(), vec!["gateway"]);
+
+        let mut new = old.clone();
+        new.cron.enabled = !old.cron.enabled;
+        assert_eq!(ReloadPlan::diff(&old, &new).components(), vec!["scheduler"]);
+
+        let mut new = old.clone();
+        new.autonomy.max_actions_per_hour += 1;
+        let plan = ReloadPlan::diff(&old, &new);
+        assert_eq!(
+            plan.components(),
+            vec!["gateway", "channels", "heartbeat", "scheduler"]
+        );
+        assert!(plan.subsystems.contains(&Subsystem::Security));
+    }
+
+    #[test]
+    fn pairing_saved_by_the_gateway_does_not_restart_it() {
+        let old = Config::default();
+        let mut new = old.clone();
+        new.gateway.paired_tokens = vec!["a".repeat(64)];
+
+        let plan = ReloadPlan::diff(&old, &new);
+        assert_eq!(plan.sections, vec![PAIRED_TOKENS_SECTION.to_string()]);
+        assert_eq!(plan.components(), vec!["gateway"]);
+
+        let saved = Some(new.gateway.paired_tokens.as_slice());
+        let plan = ReloadPlan::diff(&old, &new).without_saved_tokens(saved, &new);
+        assert!(plan.is_empty());
+        assert!(plan.components().is_empty());
+
+        // Tokens edited by hand still reach the gateway.
+        let mut edited = new.clone();
+        edited.gateway.paired_tokens.push("b".repeat(64));
+        let plan = ReloadPlan::diff(&new, &edited).without_saved_tokens(saved, &edited);
+        assert_eq!(plan.components(), vec!["gateway"]);
+    }
+}
@@ -510,6 +510,16 @@ fn check_config_semantics(config: &Config, items: &mut Vec<DiagItem>) {
             ));
         }
     }
+    for provider in config.reliability.fallback_models.keys() {
+        if !config.reliability.fallback_providers.contains(provider) {
+            items.push(DiagItem::warn(
+                cat,
+                format!(
+                    "fallback model set for \"{provider}\", which is not in fallback_providers"
+                ),
+            ));
+        }
+    }
 
     // Model routes validation
     for route in &config.model_routes {
@@ -630,12 +640,15 @@ fn provider_validation_error(name: &str) -> Option<String> {
 
 fn embedding_provider_validation_error(name: &str) -> Option<String> {
     let normalized = name.trim();
-    if normalized.eq_ignore_ascii_case("none") || normalized.eq_ignore_ascii_case("openai") {
+    if ["none", "openai", "on_device"]
+        .iter()
+        .any(|known| normalized.eq_ignore_ascii_case(known))
+    {
         return None;
     }
 
     let Some(url) = normalized.strip_prefix("custom:") else {
-        return Some("supported values: none, openai, custom:<url>".into());
+        return Some("supported values: none, openai, on_device, custom:<url>".into());
     };
 
     let url = url.trim();
@@ -1143,6 +1156,21 @@ mod tests {
         assert_eq!(fb_item.unwrap().severity, Severity::Warn);
     }
 
+    #[test]
+    fn config_validation_warns_orphan_fallback_model() {
+        let mut config = Config::default();
+        config.reliability.fallback_providers = vec!["ollama".into()];
+        config.reliability.fallback_models =
+            std::collections::HashMap::from([("olama".to_string(), "llama3.2:3b".to_string())]);
+        let mut items = Vec::new();
+        check_config_semantics(&config, &mut items);
+        let fb_item = items
+            .iter()
+            .find(|i| i.message.contains("fallback model set for \"olama\""));
+        assert!(fb_item.is_some());
+        assert_eq!(fb_item.unwrap().severity, Severity::Warn);
+    }
+
     #[test]
     fn config_validation_warns_bad_custom_fallback() {
         let mut config = Config::default();
@@ -0,0 +1,150 @@
+//! Admin API for managing a remote phone agent without shell access.
+//!
+//! All `/admin/*` routes need the gateway API token or a paired token, which
+//! the auth middleware checks before these handlers run.
+
+use super::AppState;
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::{IntoResponse, Json},
+};
+use serde::Deserialize;
+
+#[derive(Deserialize, Default)]
+pub struct RotateTokensBody {
+    /// Also revoke every paired client token.
+    #[serde(default)]
+    pub revoke_paired: bool,
+}
+
+fn internal_error(message: String) -> axum::response::Response {
+    (
+        StatusCode::INTERNAL_SERVER_ERROR,
+        Json(serde_json::json!({"error": message})),
+    )
+        .into_response()
+}
+
+/// POST /admin/reload — re-read the config file and restart changed components
+pub async fn handle_admin_reload() -> impl IntoResponse {
+    crate::daemon::request_reload();
+    (
+        StatusCode::ACCEPTED,
+        Json(serde_json::json!({"status": "reload_requested"})),
+    )
+}
+
+/// POST /admin/channels/{name}/restart — reconnect one channel listener
+pub async fn handle_admin_channel_restart(Path(name): Path<String>) -> impl IntoResponse {
+    if !crate::channels::request_channel_restart(&name) {
+        return (
+            StatusCode::NOT_FOUND,
+            Json(serde_json::json!({
+                "error": format!("Channel '{name}' is not running"),
+                "running": crate::channels::running_channels(),
+            })),
+        );
+    }
+    tracing::info!(channel = %name, "Channel restart requested over the admin API");
+    (
+        StatusCode::ACCEPTED,
+        Json(serde_json::json!({"status": "restart_requested", "channel": name})),
+    )
+}
+
+/// POST /admin/cache/flush — clear the response cache and agent sessions
+pub async fn handle_admin_cache_flush(State(state): State<AppState>) -> impl IntoResponse {
+    let config = state.config.lock().clone();
+    let response_cache_entries =
+        match crate::memory::create_response_cache(&config.memory, &config.workspace_dir)
+            .map(|cache| cache.clear())
+            .transpose()
+        {
+            Ok(cleared) => cleared.unwrap_or(0),
+            Err(e) => return internal_error(format!("Failed to clear response cache: {e:#}")),
+        };
+    let agent_sessions = state.agent_sessions.clear();
+
+    tracing::info!(
+        response_cache_entries,
+        agent_sessions,
+        "Caches flushed over the admin API"
+    );
+    Json(serde_json::json!({
+        "status": "ok",
+        "response_cache_entries": response_cache_entries,
+        "agent_sessions": agent_sessions,
+    }))
+    .into_response()
+}
+
+/// POST /admin/tokens/rotate — replace the API token, optionally revoking
+/// paired tokens. The new API token is only returned here.
+pub async fn handle_admin_tokens_rotate(
+    State(state): State<AppState>,
+    body: Option<Json<RotateTokensBody>>,
+) -> impl IntoResponse {
+    let Json(body) = body.unwrap_or_default();
+    let config = state.config.lock().clone();
+
+    let api_token = match super::auth::rotate_api_token(&config) {
+        Ok(token) => token,
+        Err(e) => return internal_error(format!("Failed to rotate API token: {e:#}")),
+    };
+    state.pairing.set_api_token(&api_token);
+
+    let revoked_paired = if body.revoke_paired {
+        let revoked = state.pairing.revoke_all();
+        if let Err(e) = Box::pin(super::persist_pairing_tokens(
+            state.config.clone(),
+            &state.pairing,
+        ))
+        .await
+        {
+            return internal_error(format!(
+                "Paired tokens revoked for this process, but not persisted: {e:#}"
+            ));
+        }
+        revoked
+    } else {
+        0
+    };
+
+    tracing::info!(revoked_paired, "🔐 Gateway tokens rotated");
+    Json(serde_json::json!({
+        "status": "ok",
+        "api_token": api_token,
+        "revoked_paired": revoked_paired,
+    }))
+    .into_response()
+}
+
+/// GET /admin/diagnostics — one snapshot of runtime state for remote triage
+pub async fn handle_admin_diagnostics(State(state): State<AppState>) -> impl IntoResponse {
+    let config = state.config.lock().clone();
+    let doctor = crate::doctor::diagnose(&config);
+
+    Json(serde_json::json!({
+        "version": env!("CARGO_PKG_VERSION"),
+        "provider": config.default_provider,
+        "model": state.model,
+        "config_path": config.config_path,
+        "workspace_dir": config.workspace_dir,
+        "memory_backend": state.mem.name(),
+        "health": crate::health::snapshot(),
+        "channels": crate::channels::running_channels(),
+        "agent_sessions": state.agent_sessions.len(),
+        "paired_tokens": state.pairing.token_ids().len(),
+        "users": config.gateway.users.keys().collect::<std::collections::BTreeSet<_>>(),
+        "gateway": {
+            "tls": config.gateway.tls.enabled,
+            "require_pairing": config.gateway.require_pairing,
+            "bind_interface": config.gateway.bind_interface,
+            "node_name": config.gateway.node_name,
+            "token_rate_limit_per_minute": config.gateway.token_rate_limit_per_minute,
+            "ip_rate_limit_per_minute": config.gateway.ip_rate_limit_per_minute,
+        },
+        "doctor": doctor,
+    }))
+}
@@ -2,9 +2,11 @@
 //!
 //! All `/api/*` routes require bearer token authentication (PairingGuard).
 
+use super::users::{self, GatewayUser};
 use super::AppState;
+use crate::config::ChangeSource;
 use axum::{
-    extract::{Path, Query, State},
+    extract::{Extension, Path, Query, State},
     http::{header, HeaderMap, StatusCode},
     response::{IntoResponse, Json},
 };
@@ -27,7 +29,7 @@ fn require_auth(
     state: &AppState,
     headers: &HeaderMap,
 ) -> Result<(), (StatusCode, Json<serde_json::Value>)> {
-    if !state.pairing.require_pairing() {
+    if !state.pairing.requires_auth() {
         return Ok(());
     }
 
@@ -52,6 +54,23 @@ pub struct MemoryQuery {
     pub category: Option<String>,
 }
 
+#[derive(Deserialize)]
+pub struct CostDailyQuery {
+    pub days: Option<u32>,
+}
+
+#[derive(Deserialize)]
+pub struct ModelsQuery {
+    pub provider: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct ConfigChangesQuery {
+    pub limit: Option<usize>,
+    pub source: Option<String>,
+    pub path: Option<String>,
+}
+
 #[derive(Deserialize)]
 pub struct MemoryStoreBody {
     pub key: String,
@@ -66,6 +85,17 @@ pub struct CronAddBody {
     pub command: String,
 }
 
+#[derive(Deserialize)]
+pub struct ConfigExportBody {
+    pub passphrase: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct ConfigImportBody {
+    pub toml: String,
+    pub passphrase: Option<String>,
+}
+
 // ── Handlers ────────────────────────────────────────────────────
 
 /// GET /api/status — system status overview
@@ -147,9 +177,14 @@ pub async fn handle_api_config_put(
     let incoming: crate::config::Config = match toml::from_str(&body) {
         Ok(c) => c,
         Err(e) => {
+            let config_path = state.config.lock().config_path.clone();
+            let report = crate::config::check_config_str(&body, &config_path);
             return (
                 StatusCode::BAD_REQUEST,
-                Json(serde_json::json!({"error": format!("Invalid TOML: {e}")})),
+                Json(serde_json::json!({
+                    "error": format!("Invalid TOML: {e}"),
+                    "diagnostics": report.diagnostics,
+                })),
             )
                 .into_response();
         }
@@ -167,7 +202,7 @@ pub async fn handle_api_config_put(
     }
 
     // Save to disk
-    if let Err(e) = new_config.save().await {
+    if let Err(e) = new_config.save_from(ChangeSource::Gateway).await {
         return (
             StatusCode::INTERNAL_SERVER_ERROR,
             Json(serde_json::json!({"error": format!("Failed to save config: {e}")})),
@@ -177,10 +212,319 @@ pub async fn handle_api_config_put(
 
     // Update in-memory config
     *state.config.lock() = new_config;
+    // Let the daemon restart the components whose settings changed
+    crate::daemon::request_reload();
 
     Json(serde_json::json!({"status": "ok"})).into_response()
 }
 
+/// POST /api/config/reload — re-read config.toml and apply it without a
+/// daemon restart
+pub async fn handle_api_config_reload(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+) -> impl IntoResponse {
+    if let Err(e) = require_auth(&state, &headers) {
+        return e.into_response();
+    }
+
+    crate::daemon::request_reload();
+    (
+        StatusCode::ACCEPTED,
+        Json(serde_json::json!({"status": "reload_requested"})),
+    )
+        .into_response()
+}
+
+/// POST /api/config/validate — check a TOML body (or, when the body is
+/// empty, the config file on disk) and report every problem with its line
+pub async fn handle_api_config_validate(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    body: String,
+) -> impl IntoResponse {
+    if let Err(e) = require_auth(&state, &headers) {
+        return e.into_response();
+    }
+
+    let config_path = state.config.lock().config_path.clone();
+    let report = if body.trim().is_empty() {
+        match crate::config::check_config_file(&config_path).await {
+            Ok(report) => report,
+            Err(e) => {
+                return (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    Json(serde_json::json!({"error": format!("{e:#}")})),
+                )
+                    .into_response();
+            }
+        }
+    } else {
+        crate::config::check_config_str(&body, &config_path)
+    };
+
+    Json(report).into_response()
+}
+
+/// POST /api/config/sync — pull the signed remote config now
+pub async fn handle_api_config_sync(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+) -> impl IntoResponse {
+    if let Err(e) = require_auth(&state, &headers) {
+        return e.into_response();
+    }
+
+    let config = state.config.lock().clone();
+    if !config.config_sync.enabled {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(serde_json::json!({"error": "Config sync is disabled"})),
+        )
+            .into_response();
+    }
+
+    let report = match Box::pin(crate::config::sync::sync_once(&config)).await {
+        Ok(report) => report,
+        Err(e) => {
+            return (
+                StatusCode::BAD_GATEWAY,
+                Json(serde_json::json!({"error": format!("{e:#}")})),
+            )
+                .into_response();
+        }
+    };
+
+    if report.applied {
+        match config.reload().await {
+            Ok(reloaded) => *state.config.lock() = reloaded,
+            Err(e) => tracing::warn!("Failed to reload config after sync: {e:#}"),
+        }
+    }
+
+    Json(report).into_response()
+}
+
+/// GET /api/config/changes — recorded config changes, newest first
+pub async fn handle_api_config_changes(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Query(params): Query<ConfigChangesQuery>,
+) -> impl IntoResponse {
+    if let Err(e) = require_auth(&state, &headers) {
+        return e.into_response();
+    }
+
+    let source = match params
+        .source
+        .as_deref()
+        .map(str::parse::<ChangeSource>)
+        .transpose()
+    {
+        Ok(source) => source,
+        Err(e) => {
+            return (
+                StatusCode::BAD_REQUEST,
+                Json(serde_json::json!({"error": format!("{e}")})),
+            )
+                .into_response();
+        }
+    };
+    let workspace_dir = state.config.lock().workspace_dir.clone();
+    let limit = params.limit.unwrap_or(50).clamp(1, 1000);
+    match crate::config::audit::load(&workspace_dir, limit, source, params.path.as_deref()) {
+        Ok(changes) => Json(serde_json::json!({"changes": changes})).into_response(),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(serde_json::json!({"error": format!("Failed to read config changes: {e}")})),
+        )
+            .into_response(),
+    }
+}
+
+/// POST /api/config/export — the config as portable TOML, credentials
+/// stripped or wrapped with `passphrase`
+pub async fn handle_api_config_export(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Json(body): Json<ConfigExportBody>,
+) -> impl IntoResponse {
+    if let Err(e) = require_auth(&state, &headers) {
+        return e.into_response();
+    }
+
+    let config = state.config.lock().clone();
+    match crate::config::transfer::export(&config, body.passphrase.as_deref()).await {
+        Ok(toml) => Json(serde_json::json!({"toml": toml})).into_response(),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(serde_json::json!({"error": format!("{e:#}")})),
+        )
+            .into_response(),
+    }
+}
+
+/// POST /api/config/import — replace the config with an exported document
+pub async fn handle_api_config_import(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Json(body): Json<ConfigImportBody>,
+) -> impl IntoResponse {
+    if let Err(e) = require_auth(&state, &headers) {
+        return e.into_response();
+    }
+
+    let config = state.config.lock().clone();
+    let report = match Box::pin(crate::config::transfer::import(
+        &config,
+        &body.toml,
+        body.passphrase.as_deref(),
+        ChangeSource::Gateway,
+    ))
+    .await
+    {
+        Ok(report) => report,
+        Err(e) => {
+            return (
+                StatusCode::BAD_REQUEST,
+                Json(serde_json::json!({"error": format!("{e:#}")})),
+            )
+                .into_response();
+        }
+    };
+
+    match config.reload().await {
+        Ok(reloaded) => *state.config.lock() = reloaded,
+        Err(e) => tracing::warn!("Failed to reload config after import: {e:#}"),
+    }
+
+    Json(report).into_response()
+}
+
+/// GET /api/profiles — list config profiles and the active one
+pub async fn handle_api_profiles(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+) -> impl IntoResponse {
+    if let Err(e) = require_auth(&state, &headers) {
+        return e.into_response();
+    }
+
+    let config = state.config.lock();
+    Json(serde_json::json!({
+        "active": config.active_profile,
+        "profiles": config.profile_names(),
+    }))
+    .into_response()
+}
+
+/// POST /api/pairing/code — issue a short-lived pairing code for a QR code
+pub async fn handle_api_pairing_code(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+) -> impl IntoResponse {
+    if let Err(e) = require_auth(&state, &headers) {
+        return e.into_response();
+    }
+
+    let config = state.config.lock().clone();
+    // Clients reach the gateway the way this request did.
+    let url = match headers.get(header::HOST).and_then(|v| v.to_str().ok()) {
+        Some(host) if config.gateway.tls.enabled => format!("https://{host}"),
+        Some(host) => format!("http://{host}"),
+        None => super::pairing::gateway_url(&config),
+    };
+    match super::pairing::offer(&config, &state.pairing, &url) {
+        Ok(offer) => Json(offer).into_response(),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(serde_json::json!({"error": format!("{e:#}")})),
+        )
+            .into_response(),
+    }
+}
+
+/// GET /api/pairing/tokens — ids of paired client tokens
+pub async fn handle_api_pairing_tokens(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+) -> impl IntoResponse {
+    if let Err(e) = require_auth(&state, &headers) {
+        return e.into_response();
+    }
+
+    Json(serde_json::json!({"tokens": state.pairing.token_ids()})).into_response()
+}
+
+/// DELETE /api/pairing/tokens/{id} — revoke a paired client token
+pub async fn handle_api_pairing_revoke(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(id): Path<String>,
+) -> impl IntoResponse {
+    if let Err(e) = require_auth(&state, &headers) {
+        return e.into_response();
+    }
+
+    if !state.pairing.revoke(&id) {
+        return (
+            StatusCode::NOT_FOUND,
+            Json(serde_json::json!({"error": format!("No paired token with id '{id}'")})),
+        )
+            .into_response();
+    }
+    if let Err(e) = Box::pin(super::persist_pairing_tokens(
+        state.config.clone(),
+        &state.pairing,
+    ))
+    .await
+    {
+        return (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(serde_json::json!({
+                "error": format!("Token revoked for this process, but not persisted: {e:#}")
+            })),
+        )
+            .into_response();
+    }
+    tracing::info!(id = %id, "🔐 Paired token revoked");
+    Json(serde_json::json!({"status": "ok", "revoked": id})).into_response()
+}
+
+/// POST /api/profiles/{name}/activate — switch profile, save, and hot-reload
+pub async fn handle_api_profile_activate(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(name): Path<String>,
+) -> impl IntoResponse {
+    if let Err(e) = require_auth(&state, &headers) {
+        return e.into_response();
+    }
+
+    let mut config = state.config.lock().clone();
+    if let Err(e) = config.switch_profile(&name) {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(serde_json::json!({"error": format!("{e:#}")})),
+        )
+            .into_response();
+    }
+
+    if let Err(e) = config.save_from(ChangeSource::Gateway).await {
+        return (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(serde_json::json!({"error": format!("Failed to save config: {e}")})),
+        )
+            .into_response();
+    }
+
+    let active = config.active_profile.clone();
+    *state.config.lock() = config;
+    crate::daemon::request_reload();
+
+    Json(serde_json::json!({"status": "ok", "active": active})).into_response()
+}
+
 /// GET /api/tools — list registered tool specs
 pub async fn handle_api_tools(
     State(state): State<AppState>,
@@ -364,16 +708,18 @@ pub async fn handle_api_doctor(
 /// GET /api/memory — list or search memory entries
 pub async fn handle_api_memory_list(
     State(state): State<AppState>,
+    user: Option<Extension<GatewayUser>>,
     headers: HeaderMap,
     Query(params): Query<MemoryQuery>,
 ) -> impl IntoResponse {
     if let Err(e) = require_auth(&state, &headers) {
         return e.into_response();
     }
+    let mem = users::memory_for(&state.mem, user.as_deref());
 
     if let Some(ref query) = params.query {
         // Search mode
-        match state.mem.recall(query, 50, None).await {
+        match mem.recall(query, 50, None).await {
             Ok(entries) => Json(serde_json::json!({"entries": entries})).into_response(),
             Err(e) => (
                 StatusCode::INTERNAL_SERVER_ERROR,
@@ -390,7 +736,7 @@ pub async fn handle_api_memory_list(
             other => crate::memory::MemoryCategory::Custom(other.to_string()),
         });
 
-        match state.mem.list(category.as_ref(), None).await {
+        match mem.list(category.as_ref(), None).await {
             Ok(entries) => Json(serde_json::json!({"entries": entries})).into_response(),
             Err(e) => (
                 StatusCode::INTERNAL_SERVER_ERROR,
@@ -404,6 +750,7 @@ pub async fn handle_api_memory_list(
 /// POST /api/memory — store a memory entry
 pub async fn handle_api_memory_store(
     State(state): State<AppState>,
+    user: Option<Extension<GatewayUser>>,
     headers: HeaderMap,
     Json(body): Json<MemoryStoreBody>,
 ) -> impl IntoResponse {
@@ -422,11 +769,8 @@ pub async fn handle_api_memory_store(
         })
         .unwrap_or(crate::memory::MemoryCategory::Core);
 
-    match state
-        .mem
-        .store(&body.key, &body.content, category, None)
-        .await
-    {
+    let mem = users::memory_for(&state.mem, user.as_deref());
+    match mem.store(&body.key, &body.content, category, None).await {
         Ok(()) => Json(serde_json::json!({"status": "ok"})).into_response(),
         Err(e) => (
             StatusCode::INTERNAL_SERVER_ERROR,
@@ -439,6 +783,7 @@ pub async fn handle_api_memory_store(
 /// DELETE /api/memory/:key — delete a memory entry
 pub async fn handle_api_memory_delete(
     State(state): State<AppState>,
+    user: Option<Extension<GatewayUser>>,
     headers: HeaderMap,
     Path(key): Path<String>,
 ) -> impl IntoResponse {
@@ -446,7 +791,8 @@ pub async fn handle_api_memory_delete(
         return e.into_response();
     }
 
-    match state.mem.forget(&key).await {
+    let mem = users::memory_for(&state.mem, user.as_deref());
+    match mem.forget(&key).await {
         Ok(deleted) => {
             Json(serde_json::json!({"status": "ok", "deleted": deleted})).into_response()
         }
@@ -491,6 +837,30 @@ pub async fn handle_api_cost(
     }
 }
 
+/// GET /api/cost/daily — per-day ledger totals by provider/model
+pub async fn handle_api_cost_daily(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Query(params): Query<CostDailyQuery>,
+) -> impl IntoResponse {
+    if let Err(e) = require_auth(&state, &headers) {
+        return e.into_response();
+    }
+
+    let Some(ref tracker) = state.cost_tracker else {
+        return Json(serde_json::json!({"enabled": false, "days": []})).into_response();
+    };
+
+    match tracker.daily_usage(params.days.unwrap_or(7)) {
+        Ok(days) => Json(serde_json::json!({"enabled": true, "days": days})).into_response(),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(serde_json::json!({"error": format!("Cost ledger query failed: {e}")})),
+        )
+            .into_response(),
+    }
+}
+
 /// GET /api/cli-tools — discovered CLI tools
 pub async fn handle_api_cli_tools(
     State(state): State<AppState>,
@@ -518,6 +888,64 @@ pub async fn handle_api_health(
     Json(serde_json::json!({"health": snapshot})).into_response()
 }
 
+/// GET /api/models — model IDs offered by the active (or `?provider=`) provider
+pub async fn handle_api_models(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Query(params): Query<ModelsQuery>,
+) -> impl IntoResponse {
+    if let Err(e) = require_auth(&state, &headers) {
+        return e.into_response();
+    }
+
+    let config = state.config.lock().clone();
+    let default_provider = config
+        .default_provider
+        .clone()
+        .unwrap_or_else(|| "openrouter".to_string());
+    let requested = params
+        .provider
+        .as_deref()
+        .map(str::trim)
+        .filter(|name| !name.is_empty() && *name != default_provider);
+
+    let (name, result) = match requested {
+        None => (default_provider, state.provider.list_models().await),
+        Some(name) => {
+            let options = crate::providers::ProviderRuntimeOptions {
+                auth_profile_override: None,
+                provider_api_url: None,
+                zeroclaw_dir: config.config_path.parent().map(std::path::PathBuf::from),
+                secrets_encrypt: config.secrets.encrypt,
+                reasoning_enabled: config.runtime.reasoning_enabled,
+                gemini_safety_settings: config.gemini.safety_settings.clone(),
+                azure_openai: config.azure_openai.clone(),
+                openrouter: config.openrouter.clone(),
+                local_llm: config.local_llm.clone(),
+                workspace_dir: Some(config.workspace_dir.clone()),
+                response_cache: None,
+            };
+            let result = match crate::providers::create_provider_with_options(name, None, &options)
+            {
+                Ok(provider) => provider.list_models().await,
+                Err(e) => Err(e),
+            };
+            (name.to_string(), result)
+        }
+    };
+
+    match result {
+        Ok(models) => Json(serde_json::json!({"provider": name, "models": models})).into_response(),
+        Err(e) => (
+            StatusCode::BAD_GATEWAY,
+            Json(serde_json::json!({
+                "error": crate::providers::sanitize_api_error(&format!("{e:#}"))
+            })),
+        )
+            .into_response(),
+    }
+}
+
 // ── Helpers ─────────────────────────────────────────────────────
 
 fn is_masked_secret(value: &str) -> bool {
@@ -692,223 +1120,175 @@ fn restore_embedding_route_api_keys(
     }
 }
 
-fn mask_sensitive_fields(config: &crate::config::Config) -> crate::config::Config {
-    let mut masked = config.clone();
-
-    mask_optional_secret(&mut masked.api_key);
-    mask_vec_secrets(&mut masked.reliability.api_keys);
-    mask_vec_secrets(&mut masked.gateway.paired_tokens);
-    mask_optional_secret(&mut masked.composio.api_key);
-    mask_optional_secret(&mut masked.browser.computer_use.api_key);
-    mask_optional_secret(&mut masked.web_search.brave_api_key);
-    mask_optional_secret(&mut masked.storage.provider.config.db_url);
-    mask_optional_secret(&mut masked.memory.qdrant.api_key);
-    if let Some(cloudflare) = masked.tunnel.cloudflare.as_mut() {
-        mask_required_secret(&mut cloudflare.token);
-    }
-    if let Some(ngrok) = masked.tunnel.ngrok.as_mut() {
-        mask_required_secret(&mut ngrok.auth_token);
-    }
-
-    for agent in masked.agents.values_mut() {
-        mask_optional_secret(&mut agent.api_key);
-    }
-    for route in &mut masked.model_routes {
-        mask_optional_secret(&mut route.api_key);
-    }
-    for route in &mut masked.embedding_routes {
-        mask_optional_secret(&mut route.api_key);
-    }
-
-    if let Some(telegram) = masked.channels_config.telegram.as_mut() {
+fn mask_channel_secrets(channels: &mut crate::config::ChannelsConfig) {
+    if let Some(telegram) = channels.telegram.as_mut() {
         mask_required_secret(&mut telegram.bot_token);
     }
-    if let Some(discord) = masked.channels_config.discord.as_mut() {
+    if let Some(discord) = channels.discord.as_mut() {
         mask_required_secret(&mut discord.bot_token);
     }
-    if let Some(slack) = masked.channels_config.slack.as_mut() {
+    if let Some(slack) = channels.slack.as_mut() {
         mask_required_secret(&mut slack.bot_token);
         mask_optional_secret(&mut slack.app_token);
     }
-    if let Some(mattermost) = masked.channels_config.mattermost.as_mut() {
+    if let Some(mattermost) = channels.mattermost.as_mut() {
         mask_required_secret(&mut mattermost.bot_token);
     }
-    if let Some(webhook) = masked.channels_config.webhook.as_mut() {
+    if let Some(webhook) = channels.webhook.as_mut() {
         mask_optional_secret(&mut webhook.secret);
     }
-    if let Some(matrix) = masked.channels_config.matrix.as_mut() {
+    if let Some(matrix) = channels.matrix.as_mut() {
         mask_required_secret(&mut matrix.access_token);
     }
-    if let Some(whatsapp) = masked.channels_config.whatsapp.as_mut() {
+    if let Some(whatsapp) = channels.whatsapp.as_mut() {
         mask_optional_secret(&mut whatsapp.access_token);
         mask_optional_secret(&mut whatsapp.app_secret);
         mask_optional_secret(&mut whatsapp.verify_token);
     }
-    if let Some(linq) = masked.channels_config.linq.as_mut() {
+    if let Some(linq) = channels.linq.as_mut() {
         mask_required_secret(&mut linq.api_token);
         mask_optional_secret(&mut linq.signing_secret);
     }
-    if let Some(nextcloud) = masked.channels_config.nextcloud_talk.as_mut() {
+    if let Some(nextcloud) = channels.nextcloud_talk.as_mut() {
         mask_required_secret(&mut nextcloud.app_token);
         mask_optional_secret(&mut nextcloud.webhook_secret);
     }
-    if let Some(wati) = masked.channels_config.wati.as_mut() {
+    if let Some(wati) = channels.wati.as_mut() {
         mask_required_secret(&mut wati.api_token);
     }
-    if let Some(irc) = masked.channels_config.irc.as_mut() {
+    if let Some(irc) = channels.irc.as_mut() {
         mask_optional_secret(&mut irc.server_password);
         mask_optional_secret(&mut irc.nickserv_password);
         mask_optional_secret(&mut irc.sasl_password);
     }
-    if let Some(lark) = masked.channels_config.lark.as_mut() {
+    if let Some(lark) = channels.lark.as_mut() {
         mask_required_secret(&mut lark.app_secret);
         mask_optional_secret(&mut lark.encrypt_key);
         mask_optional_secret(&mut lark.verification_token);
     }
-    if let Some(feishu) = masked.channels_config.feishu.as_mut() {
+    if let Some(feishu) = channels.feishu.as_mut() {
         mask_required_secret(&mut feishu.app_secret);
         mask_optional_secret(&mut feishu.encrypt_key);
         mask_optional_secret(&mut feishu.verification_token);
     }
-    if let Some(dingtalk) = masked.channels_config.dingtalk.as_mut() {
+    if let Some(dingtalk) = channels.dingtalk.as_mut() {
         mask_required_secret(&mut dingtalk.client_secret);
     }
-    if let Some(qq) = masked.channels_config.qq.as_mut() {
+    if let Some(qq) = channels.qq.as_mut() {
         mask_required_secret(&mut qq.app_secret);
     }
-    if let Some(nostr) = masked.channels_config.nostr.as_mut() {
+    if let Some(nostr) = channels.nostr.as_mut() {
         mask_required_secret(&mut nostr.private_key);
     }
-    if let Some(clawdtalk) = masked.channels_config.clawdtalk.as_mut() {
+    if let Some(clawdtalk) = channels.clawdtalk.as_mut() {
         mask_required_secret(&mut clawdtalk.api_key);
         mask_optional_secret(&mut clawdtalk.webhook_secret);
     }
-    if let Some(email) = masked.channels_config.email.as_mut() {
+    if let Some(email) = channels.email.as_mut() {
         mask_required_secret(&mut email.password);
     }
-    masked
 }
 
-fn restore_masked_sensitive_fields(
-    incoming: &mut crate::config::Config,
-    current: &crate::config::Config,
-) {
-    restore_optional_secret(&mut incoming.api_key, &current.api_key);
-    restore_vec_secrets(
-        &mut incoming.gateway.paired_tokens,
-        &current.gateway.paired_tokens,
-    );
-    restore_vec_secrets(
-        &mut incoming.reliability.api_keys,
-        &current.reliability.api_keys,
-    );
-    restore_optional_secret(&mut incoming.composio.api_key, &current.composio.api_key);
-    restore_optional_secret(
-        &mut incoming.browser.computer_use.api_key,
-        &current.browser.computer_use.api_key,
-    );
-    restore_optional_secret(
-        &mut incoming.web_search.brave_api_key,
-        &current.web_search.brave_api_key,
-    );
-    restore_optional_secret(
-        &mut incoming.storage.provider.config.db_url,
-        &current.storage.provider.config.db_url,
-    );
-    restore_optional_secret(
-        &mut incoming.memory.qdrant.api_key,
-        &current.memory.qdrant.api_key,
-    );
-    if let (Some(incoming_tunnel), Some(current_tunnel)) = (
-        incoming.tunnel.cloudflare.as_mut(),
-        current.tunnel.cloudflare.as_ref(),
-    ) {
-        restore_required_secret(&mut incoming_tunnel.token, &current_tunnel.token);
+fn mask_sensitive_fields(config: &crate::config::Config) -> crate::config::Config {
+    let mut masked = config.clone();
+
+    mask_optional_secret(&mut masked.api_key);
+    mask_vec_secrets(&mut masked.reliability.api_keys);
+    mask_vec_secrets(&mut masked.gateway.paired_tokens);
+    mask_optional_secret(&mut masked.composio.api_key);
+    mask_optional_secret(&mut masked.browser.computer_use.api_key);
+    mask_optional_secret(&mut masked.web_search.brave_api_key);
+    mask_optional_secret(&mut masked.web_search.tavily_api_key);
+    mask_optional_secret(&mut masked.storage.provider.config.db_url);
+    mask_optional_secret(&mut masked.memory.qdrant.api_key);
+    if let Some(cloudflare) = masked.tunnel.cloudflare.as_mut() {
+        mask_required_secret(&mut cloudflare.token);
     }
-    if let (Some(incoming_tunnel), Some(current_tunnel)) = (
-        incoming.tunnel.ngrok.as_mut(),
-        current.tunnel.ngrok.as_ref(),
-    ) {
-        restore_required_secret(&mut incoming_tunnel.auth_token, &current_tunnel.auth_token);
+    if let Some(ngrok) = masked.tunnel.ngrok.as_mut() {
+        mask_required_secret(&mut ngrok.auth_token);
+    }
+    if let Some(relay) = masked.tunnel.relay.as_mut() {
+        mask_required_secret(&mut relay.token);
+    }
+    for hook in masked.gateway.hooks.values_mut() {
+        mask_optional_secret(&mut hook.secret);
     }
 
-    for (name, agent) in &mut incoming.agents {
-        if let Some(current_agent) = current.agents.get(name) {
-            restore_optional_secret(&mut agent.api_key, &current_agent.api_key);
+    for agent in masked.agents.values_mut() {
+        mask_optional_secret(&mut agent.api_key);
+    }
+    for route in &mut masked.model_routes {
+        mask_optional_secret(&mut route.api_key);
+    }
+    for route in &mut masked.embedding_routes {
+        mask_optional_secret(&mut route.api_key);
+    }
+
+    mask_channel_secrets(&mut masked.channels_config);
+    for profile in masked.profiles.values_mut() {
+        if let Some(channels) = profile.channels_config.as_mut() {
+            mask_channel_secrets(channels);
         }
     }
-    restore_model_route_api_keys(&mut incoming.model_routes, &current.model_routes);
-    restore_embedding_route_api_keys(&mut incoming.embedding_routes, &current.embedding_routes);
+    masked
+}
 
-    if let (Some(incoming_ch), Some(current_ch)) = (
-        incoming.channels_config.telegram.as_mut(),
-        current.channels_config.telegram.as_ref(),
-    ) {
+fn restore_channel_secrets(
+    incoming: &mut crate::config::ChannelsConfig,
+    current: &crate::config::ChannelsConfig,
+) {
+    if let (Some(incoming_ch), Some(current_ch)) =
+        (incoming.telegram.as_mut(), current.telegram.as_ref())
+    {
         restore_required_secret(&mut incoming_ch.bot_token, &current_ch.bot_token);
     }
-    if let (Some(incoming_ch), Some(current_ch)) = (
-        incoming.channels_config.discord.as_mut(),
-        current.channels_config.discord.as_ref(),
-    ) {
+    if let (Some(incoming_ch), Some(current_ch)) =
+        (incoming.discord.as_mut(), current.discord.as_ref())
+    {
         restore_required_secret(&mut incoming_ch.bot_token, &current_ch.bot_token);
     }
-    if let (Some(incoming_ch), Some(current_ch)) = (
-        incoming.channels_config.slack.as_mut(),
-        current.channels_config.slack.as_ref(),
-    ) {
+    if let (Some(incoming_ch), Some(current_ch)) = (incoming.slack.as_mut(), current.slack.as_ref())
+    {
         restore_required_secret(&mut incoming_ch.bot_token, &current_ch.bot_token);
         restore_optional_secret(&mut incoming_ch.app_token, &current_ch.app_token);
     }
-    if let (Some(incoming_ch), Some(current_ch)) = (
-        incoming.channels_config.mattermost.as_mut(),
-        current.channels_config.mattermost.as_ref(),
-    ) {
+    if let (Some(incoming_ch), Some(current_ch)) =
+        (incoming.mattermost.as_mut(), current.mattermost.as_ref())
+    {
         restore_required_secret(&mut incoming_ch.bot_token, &current_ch.bot_token);
     }
-    if let (Some(incoming_ch), Some(current_ch)) = (
-        incoming.channels_config.webhook.as_mut(),
-        current.channels_config.webhook.as_ref(),
-    ) {
+    if let (Some(incoming_ch), Some(current_ch)) =
+        (incoming.webhook.as_mut(), current.webhook.as_ref())
+    {
         restore_optional_secret(&mut incoming_ch.secret, &current_ch.secret);
     }
-    if let (Some(incoming_ch), Some(current_ch)) = (
-        incoming.channels_config.matrix.as_mut(),
-        current.channels_config.matrix.as_ref(),
-    ) {
+    if let (Some(incoming_ch), Some(current_ch)) =
+        (incoming.matrix.as_mut(), current.matrix.as_ref())
+    {
         restore_required_secret(&mut incoming_ch.access_token, &current_ch.access_token);
     }
-    if let (Some(incoming_ch), Some(current_ch)) = (
-        incoming.channels_config.whatsapp.as_mut(),
-        current.channels_config.whatsapp.as_ref(),
-    ) {
+    if let (Some(incoming_ch), Some(current_ch)) =
+        (incoming.whatsapp.as_mut(), current.whatsapp.as_ref())
+    {
         restore_optional_secret(&mut incoming_ch.access_token, &current_ch.access_token);
         restore_optional_secret(&mut incoming_ch.app_secret, &current_ch.app_secret);
         restore_optional_secret(&mut incoming_ch.verify_token, &current_ch.verify_token);
     }
-    if let (Some(incoming_ch), Some(current_ch)) = (
-        incoming.channels_config.linq.as_mut(),
-        current.channels_config.linq.as_ref(),
-    ) {
+    if let (Some(incoming_ch), Some(current_ch)) = (incoming.linq.as_mut(), current.linq.as_ref()) {
         restore_required_secret(&mut incoming_ch.api_token, &current_ch.api_token);
         restore_optional_secret(&mut incoming_ch.signing_secret, &current_ch.signing_secret);
     }
     if let (Some(incoming_ch), Some(current_ch)) = (
-        incoming.channels_config.nextcloud_talk.as_mut(),
-        current.channels_config.nextcloud_talk.as_ref(),
+        incoming.nextcloud_talk.as_mut(),
+        current.nextcloud_talk.as_ref(),
     ) {
         restore_required_secret(&mut incoming_ch.app_token, &current_ch.app_token);
         restore_optional_secret(&mut incoming_ch.webhook_secret, &current_ch.webhook_secret);
     }
-    if let (Some(incoming_ch), Some(current_ch)) = (
-        incoming.channels_config.wati.as_mut(),
-        current.channels_config.wati.as_ref(),
-    ) {
+    if let (Some(incoming_ch), Some(current_ch)) = (incoming.wati.as_mut(), current.wati.as_ref()) {
         restore_required_secret(&mut incoming_ch.api_token, &current_ch.api_token);
     }
-    if let (Some(incoming_ch), Some(current_ch)) = (
-        incoming.channels_config.irc.as_mut(),
-        current.channels_config.irc.as_ref(),
-    ) {
+    if let (Some(incoming_ch), Some(current_ch)) = (incoming.irc.as_mut(), current.irc.as_ref()) {
         restore_optional_secret(
             &mut incoming_ch.server_password,
             &current_ch.server_password,
@@ -919,10 +1299,7 @@ fn restore_masked_sensitive_fields(
         );
         restore_optional_secret(&mut incoming_ch.sasl_password, &current_ch.sasl_password);
     }
-    if let (Some(incoming_ch), Some(current_ch)) = (
-        incoming.channels_config.lark.as_mut(),
-        current.channels_config.lark.as_ref(),
-    ) {
+    if let (Some(incoming_ch), Some(current_ch)) = (incoming.lark.as_mut(), current.lark.as_ref()) {
         restore_required_secret(&mut incoming_ch.app_secret, &current_ch.app_secret);
         restore_optional_secret(&mut incoming_ch.encrypt_key, &current_ch.encrypt_key);
         restore_optional_secret(
@@ -930,10 +1307,9 @@ fn restore_masked_sensitive_fields(
             &current_ch.verification_token,
         );
     }
-    if let (Some(incoming_ch), Some(current_ch)) = (
-        incoming.channels_config.feishu.as_mut(),
-        current.channels_config.feishu.as_ref(),
-    ) {
+    if let (Some(incoming_ch), Some(current_ch)) =
+        (incoming.feishu.as_mut(), current.feishu.as_ref())
+    {
         restore_required_secret(&mut incoming_ch.app_secret, &current_ch.app_secret);
         restore_optional_secret(&mut incoming_ch.encrypt_key, &current_ch.encrypt_key);
         restore_optional_secret(
@@ -941,39 +1317,110 @@ fn restore_masked_sensitive_fields(
             &current_ch.verification_token,
         );
     }
-    if let (Some(incoming_ch), Some(current_ch)) = (
-        incoming.channels_config.dingtalk.as_mut(),
-        current.channels_config.dingtalk.as_ref(),
-    ) {
+    if let (Some(incoming_ch), Some(current_ch)) =
+        (incoming.dingtalk.as_mut(), current.dingtalk.as_ref())
+    {
         restore_required_secret(&mut incoming_ch.client_secret, &current_ch.client_secret);
     }
-    if let (Some(incoming_ch), Some(current_ch)) = (
-        incoming.channels_config.qq.as_mut(),
-        current.channels_config.qq.as_ref(),
-    ) {
+    if let (Some(incoming_ch), Some(current_ch)) = (incoming.qq.as_mut(), current.qq.as_ref()) {
         restore_required_secret(&mut incoming_ch.app_secret, &current_ch.app_secret);
     }
-    if let (Some(incoming_ch), Some(current_ch)) = (
-        incoming.channels_config.nostr.as_mut(),
-        current.channels_config.nostr.as_ref(),
-    ) {
+    if let (Some(incoming_ch), Some(current_ch)) = (incoming.nostr.as_mut(), current.nostr.as_ref())
+    {
         restore_required_secret(&mut incoming_ch.private_key, &current_ch.private_key);
     }
-    if let (Some(incoming_ch), Some(current_ch)) = (
-        incoming.channels_config.clawdtalk.as_mut(),
-        current.channels_config.clawdtalk.as_ref(),
-    ) {
+    if let (Some(incoming_ch), Some(current_ch)) =
+        (incoming.clawdtalk.as_mut(), current.clawdtalk.as_ref())
+    {
         restore_required_secret(&mut incoming_ch.api_key, &current_ch.api_key);
         restore_optional_secret(&mut incoming_ch.webhook_secret, &current_ch.webhook_secret);
     }
-    if let (Some(incoming_ch), Some(current_ch)) = (
-        incoming.channels_config.email.as_mut(),
-        current.channels_config.email.as_ref(),
-    ) {
+    if let (Some(incoming_ch), Some(current_ch)) = (incoming.email.as_mut(), current.email.as_ref())
+    {
         restore_required_secret(&mut incoming_ch.password, &current_ch.password);
     }
 }
 
+fn restore_masked_sensitive_fields(
+    incoming: &mut crate::config::Config,
+    current: &crate::config::Config,
+) {
+    restore_optional_secret(&mut incoming.api_key, &current.api_key);
+    restore_vec_secrets(
+        &mut incoming.gateway.paired_tokens,
+        &current.gateway.paired_tokens,
+    );
+    restore_vec_secrets(
+        &mut incoming.reliability.api_keys,
+        &current.reliability.api_keys,
+    );
+    restore_optional_secret(&mut incoming.composio.api_key, &current.composio.api_key);
+    restore_optional_secret(
+        &mut incoming.browser.computer_use.api_key,
+        &current.browser.computer_use.api_key,
+    );
+    restore_optional_secret(
+        &mut incoming.web_search.brave_api_key,
+        &current.web_search.brave_api_key,
+    );
+    restore_optional_secret(
+        &mut incoming.web_search.tavily_api_key,
+        &current.web_search.tavily_api_key,
+    );
+    restore_optional_secret(
+        &mut incoming.storage.provider.config.db_url,
+        &current.storage.provider.config.db_url,
+    );
+    restore_optional_secret(
+        &mut incoming.memory.qdrant.api_key,
+        &current.memory.qdrant.api_key,
+    );
+    if let (Some(incoming_tunnel), Some(current_tunnel)) = (
+        incoming.tunnel.cloudflare.as_mut(),
+        current.tunnel.cloudflare.as_ref(),
+    ) {
+        restore_required_secret(&mut incoming_tunnel.token, &current_tunnel.token);
+    }
+    if let (Some(incoming_tunnel), Some(current_tunnel)) = (
+        incoming.tunnel.ngrok.as_mut(),
+        current.tunnel.ngrok.as_ref(),
+    ) {
+        restore_required_secret(&mut incoming_tunnel.auth_token, &current_tunnel.auth_token);
+    }
+    if let (Some(incoming_tunnel), Some(current_tunnel)) = (
+        incoming.tunnel.relay.as_mut(),
+        current.tunnel.relay.as_ref(),
+    ) {
+        restore_required_secret(&mut incoming_tunnel.token, &current_tunnel.token);
+    }
+    for (name, hook) in &mut incoming.gateway.hooks {
+        if let Some(current_hook) = current.gateway.hooks.get(name) {
+            restore_optional_secret(&mut hook.secret, &current_hook.secret);
+        }
+    }
+
+    for (name, agent) in &mut incoming.agents {
+        if let Some(current_agent) = current.agents.get(name) {
+            restore_optional_secret(&mut agent.api_key, &current_agent.api_key);
+        }
+    }
+    restore_model_route_api_keys(&mut incoming.model_routes, &current.model_routes);
+    restore_embedding_route_api_keys(&mut incoming.embedding_routes, &current.embedding_routes);
+
+    restore_channel_secrets(&mut incoming.channels_config, &current.channels_config);
+    for (name, profile) in &mut incoming.profiles {
+        if let (Some(incoming_ch), Some(current_ch)) = (
+            profile.channels_config.as_mut(),
+            current
+                .profiles
+                .get(name)
+                .and_then(|profile| profile.channels_config.as_ref()),
+        ) {
+            restore_channel_secrets(incoming_ch, current_ch);
+        }
+    }
+}
+
 fn hydrate_config_for_save(
     mut incoming: crate::config::Config,
     current: &crate::config::Config,
@@ -989,6 +1436,44 @@ fn hydrate_config_for_save(
 mod tests {
     use super::*;
 
+    #[test]
+    fn masking_covers_profile_channel_tokens() {
+        let telegram = crate::config::TelegramConfig {
+            bot_token: "123:work-bot".to_string(),
+            allowed_users: vec![],
+            stream_mode: crate::config::StreamMode::Off,
+            draft_update_interval_ms: 1000,
+            interrupt_on_new_message: false,
+            mention_only: false,
+            notify_chat_id: None,
+            notify_targets: std::collections::HashMap::new(),
+        };
+        let mut cfg = crate::config::Config::default();
+        cfg.profiles.insert(
+            "work".to_string(),
+            crate::config::ConfigProfile {
+                channels_config: Some(crate::config::ChannelsConfig {
+                    telegram: Some(telegram),
+                    ..crate::config::ChannelsConfig::default()
+                }),
+                ..crate::config::ConfigProfile::default()
+            },
+        );
+
+        let masked = mask_sensitive_fields(&cfg);
+        let masked_token = |config: &crate::config::Config| {
+            config.profiles["work"]
+                .channels_config
+                .as_ref()
+                .and_then(|channels| channels.telegram.as_ref())
+                .map(|telegram| telegram.bot_token.clone())
+        };
+        assert_eq!(masked_token(&masked).as_deref(), Some(MASKED_SECRET));
+
+        let hydrated = hydrate_config_for_save(masked, &cfg);
+        assert_eq!(masked_token(&hydrated).as_deref(), Some("123:work-bot"));
+    }
+
     #[test]
     fn masking_keeps_toml_valid_and_preserves_api_keys_type() {
         let mut cfg = crate::config::Config::default();
@@ -1026,6 +1511,7 @@ mod tests {
             from_address: "agent@example.com".to_string(),
             idle_timeout_secs: 1740,
             allowed_senders: vec!["*".to_string()],
+            allowed_recipients: Vec::new(),
         });
         cfg.model_routes = vec![crate::config::schema::ModelRouteConfig {
             hint: "reasoning".to_string(),
@@ -1131,6 +1617,18 @@ mod tests {
             auth_token: "ngrok-token-real".to_string(),
             domain: None,
         });
+        current.tunnel.relay = Some(crate::config::schema::RelayTunnelConfig {
+            url: "wss://relay.example.com/connect".to_string(),
+            token: "relay-token-real".to_string(),
+            public_url: None,
+        });
+        current.gateway.hooks.insert(
+            "github".to_string(),
+            crate::config::schema::GatewayHookConfig {
+                secret: Some("hook-secret-real".to_string()),
+                ..Default::default()
+            },
+        );
         current.memory.qdrant.api_key = Some("qdrant-real".to_string());
         current.channels_config.wati = Some(crate::config::schema::WatiConfig {
             api_token: "wati-real".to_string(),
@@ -1159,6 +1657,7 @@ mod tests {
             from_address: "agent@example.com".to_string(),
             idle_timeout_secs: 1740,
             allowed_senders: vec!["*".to_string()],
+            allowed_recipients: Vec::new(),
         });
         current.model_routes = vec![
             crate::config::schema::ModelRouteConfig {
@@ -1247,6 +1746,14 @@ mod tests {
                 .map(|v| v.auth_token.as_str()),
             Some("ngrok-token-real")
         );
+        assert_eq!(
+            hydrated.tunnel.relay.as_ref().map(|v| v.token.as_str()),
+            Some("relay-token-real")
+        );
+        assert_eq!(
+            hydrated.gateway.hooks["github"].secret.as_deref(),
+            Some("hook-secret-real")
+        );
         assert_eq!(
             hydrated.memory.qdrant.api_key.as_deref(),
             Some("qdrant-real")
@@ -0,0 +1,285 @@
+//! Per-install API token for the gateway.
+//!
+//! On first start a random token is written next to the config in
+//! `gateway.token` and reused afterwards. Every request must carry it, or a
+//! token issued by `POST /pair`, as `Authorization: Bearer <token>` or as a
+//! `?token=` query parameter where clients cannot set headers (browser
+//! WebSockets and `EventSource`). The Android app reads it through the JNI
+//! bridge. Health checks, pairing, the dashboard's and web UI's static assets
+//! and the third-party webhooks and `/hooks/*`, which verify their own
+//! signatures or secrets, stay open.
+//!
+//! Paired tokens assigned to a `[gateway.users]` entry identify that user;
+//! the middleware attaches a [`GatewayUser`] to their requests so handlers
+//! can keep users apart.
+//!
+//! Authenticated requests also count against per-token and per-IP quotas
+//! (`[gateway] token_rate_limit_per_minute` and `ip_rate_limit_per_minute`).
+//! Over quota, requests get `429 Too Many Requests` with `Retry-After`.
+//! Loopback clients are exempt from the per-IP quota. Requests replayed by the
+//! relay tunnel arrive over loopback too, but are keyed by the client address
+//! the relay reports and count like any remote client.
+
+use super::users::{self, GatewayUser};
+use super::AppState;
+use crate::config::Config;
+use crate::security::pairing::generate_token;
+use anyhow::{Context, Result};
+use axum::{
+    extract::{ConnectInfo, Request, State},
+    http::{header, HeaderMap, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Json, Response},
+};
+use std::io::Write;
+use std::net::{IpAddr, SocketAddr};
+use std::path::{Path, PathBuf};
+
+const TOKEN_FILE: &str = "gateway.token";
+
+/// Routes reachable without a token.
+const PUBLIC_PATHS: &[&str] = &[
+    "/",
+    "/health",
+    "/pair",
+    "/whatsapp",
+    "/linq",
+    "/wati",
+    "/nextcloud-talk",
+];
+
+/// Path of the token file for `config`.
+pub fn token_path(config: &Config) -> Result<PathBuf> {
+    let dir = config
+        .config_path
+        .parent()
+        .context("Config path must have a parent directory")?;
+    Ok(dir.join(TOKEN_FILE))
+}
+
+/// The gateway's API token, generated and stored on first use.
+pub fn api_token(config: &Config) -> Result<String> {
+    let path = token_path(config)?;
+    if path.exists() {
+        let token = std::fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        let token = token.trim();
+        if !token.is_empty() {
+            return Ok(token.to_string());
+        }
+    }
+
+    let token = write_new_token(&path)?;
+    tracing::info!(path = %path.display(), "Generated gateway API token");
+    Ok(token)
+}
+
+/// Replace the stored API token with a fresh one and return it. The running
+/// gateway must be given the new token too.
+pub fn rotate_api_token(config: &Config) -> Result<String> {
+    let path = token_path(config)?;
+    let token = write_new_token(&path)?;
+    tracing::info!(path = %path.display(), "Rotated gateway API token");
+    Ok(token)
+}
+
+fn write_new_token(path: &Path) -> Result<String> {
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)
+            .with_context(|| format!("Failed to create {}", dir.display()))?;
+    }
+    let token = generate_token();
+    let mut options = std::fs::OpenOptions::new();
+    options.create(true).write(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    let mut file = options
+        .open(path)
+        .with_context(|| format!("Failed to write {}", path.display()))?;
+    writeln!(file, "{token}")?;
+    Ok(token)
+}
+
+fn is_public(path: &str) -> bool {
+    PUBLIC_PATHS.contains(&path)
+        || ["/_app/", "/ui/", "/hooks/"]
+            .iter()
+            .any(|prefix| path.starts_with(prefix))
+}
+
+/// Bearer token from the `Authorization` header, else the `token` query
+/// parameter.
+pub(super) fn request_token(headers: &HeaderMap, query: Option<&str>) -> Option<String> {
+    if let Some(token) = headers
+        .get(header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|auth| auth.strip_prefix("Bearer "))
+    {
+        return Some(token.trim().to_string());
+    }
+    query?
+        .split('&')
+        .find_map(|pair| pair.strip_prefix("token="))
+        .and_then(|token| urlencoding::decode(token).ok())
+        .map(|token| token.into_owned())
+}
+
+/// Middleware rejecting requests to non-public routes without a valid token,
+/// and attaching the [`GatewayUser`] of tokens assigned to one.
+pub async fn require_token(
+    State(state): State<AppState>,
+    mut request: Request,
+    next: Next,
+) -> Response {
+    if is_public(request.uri().path()) || !state.pairing.requires_auth() {
+        return next.run(request).await;
+    }
+    let token = request_token(request.headers(), request.uri().query()).unwrap_or_default();
+    if state.pairing.is_authenticated(&token) {
+        let user = state.pairing.paired_token_id(&token).and_then(|id| {
+            let config = state.config.lock();
+            users::resolve(&config.gateway, &id)
+        });
+        if let Some(user) = user {
+            if users::is_owner_only(request.uri().path()) {
+                return (
+                    StatusCode::FORBIDDEN,
+                    Json(serde_json::json!({
+                        "error": format!("Forbidden — user '{}' cannot manage the gateway", user.name)
+                    })),
+                )
+                    .into_response();
+            }
+            request.extensions_mut().insert(user);
+        }
+        return next.run(request).await;
+    }
+    (
+        StatusCode::UNAUTHORIZED,
+        Json(serde_json::json!({
+            "error": "Unauthorized — send Authorization: Bearer <token> with the gateway API token or a paired token"
+        })),
+    )
+        .into_response()
+}
+
+/// Middleware applying per-token (or per-user) and per-IP quotas to
+/// non-public routes. Runs after [`require_token`], so only valid tokens are
+/// counted.
+pub async fn enforce_quotas(
+    State(state): State<AppState>,
+    request: Request,
+    next: Next,
+) -> Response {
+    if is_public(request.uri().path()) {
+        return next.run(request).await;
+    }
+    let peer_addr = request
+        .extensions()
+        .get::<ConnectInfo<SocketAddr>>()
+        .map(|ConnectInfo(addr)| *addr);
+    let client_ip =
+        super::client_key_from_request(peer_addr, request.headers(), state.trust_forwarded_headers);
+    let ip_key = if client_ip.parse::<IpAddr>().is_ok_and(|ip| ip.is_loopback()) {
+        ""
+    } else {
+        client_ip.as_str()
+    };
+    let token = request_token(request.headers(), request.uri().query()).unwrap_or_default();
+
+    let user = request.extensions().get::<GatewayUser>();
+
+    match state.rate_limiter.check_client(&token, ip_key, user) {
+        None => next.run(request).await,
+        Some(retry_after) => {
+            let retry_after_secs =
+                (retry_after.as_secs() + u64::from(retry_after.subsec_nanos() > 0)).max(1);
+            tracing::warn!(
+                client = %client_ip,
+                path = %request.uri().path(),
+                "Gateway quota exceeded"
+            );
+            (
+                StatusCode::TOO_MANY_REQUESTS,
+                [(header::RETRY_AFTER, retry_after_secs.to_string())],
+                Json(serde_json::json!({
+                    "error": "Too many requests. Please retry later.",
+                    "retry_after": retry_after_secs,
+                })),
+            )
+                .into_response()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::HeaderValue;
+
+    #[test]
+    fn token_is_generated_once_and_reused() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let config = Config {
+            config_path: tmp.path().join("config.toml"),
+            workspace_dir: tmp.path().join("workspace"),
+            ..Config::default()
+        };
+        let token = api_token(&config).unwrap();
+        assert!(token.starts_with("zc_"));
+        assert_eq!(api_token(&config).unwrap(), token);
+
+        let rotated = rotate_api_token(&config).unwrap();
+        assert_ne!(rotated, token);
+        assert_eq!(api_token(&config).unwrap(), rotated);
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = std::fs::metadata(tmp.path().join(TOKEN_FILE))
+                .unwrap()
+                .permissions()
+                .mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+    }
+
+    #[test]
+    fn token_is_read_from_header_or_query() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(
+            request_token(&headers, Some("session=1&token=zc_q")),
+            Some("zc_q".into())
+        );
+        assert_eq!(request_token(&headers, Some("session=1")), None);
+        assert_eq!(request_token(&headers, None), None);
+
+        headers.insert(
+            header::AUTHORIZATION,
+            HeaderValue::from_static("Bearer zc_h"),
+        );
+        assert_eq!(
+            request_token(&headers, Some("token=zc_q")),
+            Some("zc_h".into())
+        );
+    }
+
+    #[test]
+    fn only_pairing_health_webhooks_and_assets_are_public() {
+        assert!(is_public("/health"));
+        assert!(is_public("/pair"));
+        assert!(is_public("/whatsapp"));
+        assert!(is_public("/_app/index.js"));
+        assert!(is_public("/"));
+        assert!(is_public("/ui/app.js"));
+        assert!(is_public("/hooks/github"));
+        assert!(!is_public("/webhook"));
+        assert!(!is_public("/agent/message"));
+        assert!(!is_public("/api/config"));
+        assert!(!is_public("/metrics"));
+        assert!(!is_public("/healthz"));
+    }
+}
@@ -0,0 +1,238 @@
+//! Binding the gateway to one network interface, such as a Tailscale or
+//! WireGuard tunnel.
+//!
+//! With `[gateway] bind_interface` set, the gateway listens only on that
+//! interface's address, so the phone agent is reachable over the tailnet or
+//! VPN but not from the Wi-Fi or hotspot network it happens to be on. Tunnel
+//! interfaces are private overlays, so binding to one does not need
+//! `allow_public_bind`.
+
+use crate::config::Config;
+use anyhow::Result;
+use std::net::IpAddr;
+
+/// `bind_interface` value that picks the interface holding a Tailscale address.
+pub const TAILNET: &str = "tailnet";
+
+/// Interface name prefixes used by Tailscale, WireGuard and VPN apps.
+const TUNNEL_PREFIXES: &[&str] = &["tailscale", "wg", "tun", "utun"];
+
+/// The interface address the gateway binds to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BoundInterface {
+    pub name: String,
+    pub addr: IpAddr,
+    /// Whether this is a tailnet or VPN tunnel rather than a LAN interface.
+    pub overlay: bool,
+}
+
+impl BoundInterface {
+    /// The address in the form `host:port` strings expect.
+    pub fn host(&self) -> String {
+        match self.addr {
+            IpAddr::V4(addr) => addr.to_string(),
+            IpAddr::V6(addr) => format!("[{addr}]"),
+        }
+    }
+}
+
+/// Tailscale hands out `100.64.0.0/10` and `fd7a:115c:a1e0::/48` addresses.
+pub fn is_tailnet_addr(addr: IpAddr) -> bool {
+    match addr {
+        IpAddr::V4(addr) => {
+            let [a, b, ..] = addr.octets();
+            a == 100 && (64..128).contains(&b)
+        }
+        IpAddr::V6(addr) => {
+            let segments = addr.segments();
+            segments[..3] == [0xfd7a, 0x115c, 0xa1e0]
+        }
+    }
+}
+
+fn is_tunnel_interface(name: &str) -> bool {
+    TUNNEL_PREFIXES
+        .iter()
+        .any(|prefix| name.starts_with(prefix))
+}
+
+/// Pick the address for `interface` from `addrs`, preferring IPv4. Link-local
+/// IPv6 addresses are skipped since they cannot be bound without a scope.
+pub fn select(interface: &str, addrs: &[(String, IpAddr)]) -> Result<BoundInterface> {
+    let interface = interface.trim();
+    let usable = |addr: &IpAddr| match addr {
+        IpAddr::V4(_) => true,
+        IpAddr::V6(addr) => (addr.segments()[0] & 0xffc0) != 0xfe80,
+    };
+    let candidates: Vec<&(String, IpAddr)> = addrs
+        .iter()
+        .filter(|(name, addr)| {
+            usable(addr)
+                && if interface == TAILNET {
+                    is_tailnet_addr(*addr)
+                } else {
+                    name == interface
+                }
+        })
+        .collect();
+
+    let Some((name, addr)) = candidates
+        .iter()
+        .find(|(_, addr)| addr.is_ipv4())
+        .or_else(|| candidates.first())
+    else {
+        let mut available: Vec<&str> = addrs.iter().map(|(name, _)| name.as_str()).collect();
+        available.sort_unstable();
+        available.dedup();
+        if interface == TAILNET {
+            anyhow::bail!(
+                "No interface has a Tailscale address; is Tailscale connected? \
+                 (interfaces: {})",
+                available.join(", ")
+            );
+        }
+        anyhow::bail!(
+            "Interface '{interface}' has no usable address (interfaces: {})",
+            available.join(", ")
+        );
+    };
+
+    Ok(BoundInterface {
+        name: name.clone(),
+        addr: *addr,
+        overlay: is_tailnet_addr(*addr) || is_tunnel_interface(name),
+    })
+}
+
+/// Resolve `[gateway] bind_interface`, if set, against the current interfaces.
+pub fn resolve(config: &Config) -> Result<Option<BoundInterface>> {
+    let Some(interface) = config
+        .gateway
+        .bind_interface
+        .as_deref()
+        .map(str::trim)
+        .filter(|interface| !interface.is_empty())
+    else {
+        return Ok(None);
+    };
+    select(interface, &interface_addrs()?).map(Some)
+}
+
+/// Every address of every network interface, with the interface's name.
+#[cfg(unix)]
+pub fn interface_addrs() -> Result<Vec<(String, IpAddr)>> {
+    use std::ffi::CStr;
+    use std::net::{Ipv4Addr, Ipv6Addr};
+
+    let mut head: *mut libc::ifaddrs = std::ptr::null_mut();
+    // SAFETY: on success `head` is a list owned by us until `freeifaddrs`.
+    if unsafe { libc::getifaddrs(&raw mut head) } != 0 {
+        return Err(anyhow::Error::new(std::io::Error::last_os_error())
+            .context("Failed to list network interfaces"));
+    }
+
+    let mut addrs = Vec::new();
+    let mut cursor = head;
+    while !cursor.is_null() {
+        // SAFETY: `cursor` is a node of the list returned by `getifaddrs`, and
+        // its address is read as the struct its family says it is.
+        unsafe {
+            let ifa = &*cursor;
+            cursor = ifa.ifa_next;
+            if ifa.ifa_addr.is_null() || ifa.ifa_name.is_null() {
+                continue;
+            }
+            let addr = match i32::from((*ifa.ifa_addr).sa_family) {
+                libc::AF_INET => {
+                    let sin = ifa.ifa_addr.cast::<libc::sockaddr_in>().read_unaligned();
+                    IpAddr::V4(Ipv4Addr::from(u32::from_be(sin.sin_addr.s_addr)))
+                }
+                libc::AF_INET6 => {
+                    let sin6 = ifa.ifa_addr.cast::<libc::sockaddr_in6>().read_unaligned();
+                    IpAddr::V6(Ipv6Addr::from(sin6.sin6_addr.s6_addr))
+                }
+                _ => continue,
+            };
+            let name = CStr::from_ptr(ifa.ifa_name).to_string_lossy().into_owned();
+            addrs.push((name, addr));
+        }
+    }
+    // SAFETY: `head` came from a successful `getifaddrs` and is freed once.
+    unsafe { libc::freeifaddrs(head) };
+    Ok(addrs)
+}
+
+#[cfg(not(unix))]
+pub fn interface_addrs() -> Result<Vec<(String, IpAddr)>> {
+    anyhow::bail!("[gateway] bind_interface is only supported on Unix and Android")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn addrs() -> Vec<(String, IpAddr)> {
+        [
+            ("lo", "127.0.0.1"),
+            ("wlan0", "192.168.43.10"),
+            ("wlan0", "fe80::1"),
+            ("tun0", "fd7a:115c:a1e0::12"),
+            ("tun0", "100.101.102.103"),
+            ("wg0", "fe80::2"),
+            ("wg0", "10.8.0.2"),
+        ]
+        .into_iter()
+        .map(|(name, addr)| (name.to_string(), addr.parse().unwrap()))
+        .collect()
+    }
+
+    #[test]
+    fn tailnet_picks_the_tailscale_ipv4_address() {
+        let bound = select("tailnet", &addrs()).unwrap();
+        assert_eq!(bound.name, "tun0");
+        assert_eq!(bound.host(), "100.101.102.103");
+        assert!(bound.overlay);
+    }
+
+    #[test]
+    fn named_interfaces_skip_link_local_and_mark_tunnels() {
+        let wg = select("wg0", &addrs()).unwrap();
+        assert_eq!(wg.host(), "10.8.0.2");
+        assert!(wg.overlay);
+
+        let wlan = select("wlan0", &addrs()).unwrap();
+        assert_eq!(wlan.host(), "192.168.43.10");
+        assert!(!wlan.overlay);
+
+        let v6_only = vec![("tun0".to_string(), "fd7a:115c:a1e0::12".parse().unwrap())];
+        assert_eq!(
+            select("tailnet", &v6_only).unwrap().host(),
+            "[fd7a:115c:a1e0::12]"
+        );
+    }
+
+    #[test]
+    fn missing_interface_lists_available_ones() {
+        let err = select("tailscale0", &addrs()).unwrap_err().to_string();
+        assert!(err.contains("'tailscale0'"));
+        assert!(err.contains("wlan0"));
+
+        let err = select("tailnet", &addrs()[..3]).unwrap_err().to_string();
+        assert!(err.contains("Tailscale"));
+    }
+
+    #[test]
+    fn tailnet_ranges() {
+        assert!(is_tailnet_addr("100.64.0.1".parse().unwrap()));
+        assert!(is_tailnet_addr("100.127.255.254".parse().unwrap()));
+        assert!(!is_tailnet_addr("100.128.0.1".parse().unwrap()));
+        assert!(!is_tailnet_addr("fd00::1".parse().unwrap()));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn interface_addrs_include_loopback() {
+        let addrs = interface_addrs().unwrap();
+        assert!(addrs.iter().any(|(_, addr)| addr.is_loopback()));
+    }
+}
@@ -0,0 +1,214 @@
+//! LAN discovery of the gateway over mDNS/DNS-SD.
+//!
+//! When the gateway listens beyond loopback and `[gateway] advertise` is on,
+//! it is advertised as `_mobileclaw._tcp` so desktop companion apps can find
+//! the phone without users typing its address. The TXT record tells clients
+//! how to connect: version, scheme, certificate fingerprint to pin, and
+//! whether they must pair first. It never carries a pairing code or token.
+//!
+//! Android only lets apps announce services through `NsdManager`, so the
+//! advertisement is handed to the host app as `nsd_register` and withdrawn
+//! with `nsd_unregister` when the gateway stops.
+
+use crate::config::Config;
+use crate::runtime::host_bridge;
+use crate::security::pairing::is_public_bind;
+use serde::Serialize;
+use serde_json::json;
+use std::collections::BTreeMap;
+
+/// DNS-SD service type companion apps browse for.
+pub const SERVICE_TYPE: &str = "_mobileclaw._tcp";
+
+const DEFAULT_INSTANCE_NAME: &str = "MobileClaw";
+
+/// DNS-SD labels, including instance names, are limited to 63 bytes.
+const MAX_INSTANCE_NAME_BYTES: usize = 63;
+
+/// One service advertisement, as sent to the host in `nsd_register`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Advertisement {
+    pub service_type: String,
+    pub instance_name: String,
+    pub port: u16,
+    pub txt: BTreeMap<String, String>,
+}
+
+/// Instance name from `[gateway] instance_name`, trimmed to a valid label.
+pub fn instance_name(config: &Config) -> String {
+    let name = config
+        .gateway
+        .instance_name
+        .as_deref()
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .unwrap_or(DEFAULT_INSTANCE_NAME);
+
+    let mut end = name.len().min(MAX_INSTANCE_NAME_BYTES);
+    while !name.is_char_boundary(end) {
+        end -= 1;
+    }
+    name[..end].trim_end().to_string()
+}
+
+/// Describe the gateway listening on `port`. `fingerprint` is the TLS
+/// certificate's SHA-256 when TLS is on.
+pub fn advertisement(config: &Config, port: u16, fingerprint: Option<&str>) -> Advertisement {
+    let mut txt = BTreeMap::new();
+    txt.insert("version".into(), env!("CARGO_PKG_VERSION").into());
+    txt.insert(
+        "scheme".into(),
+        if fingerprint.is_some() {
+            "https"
+        } else {
+            "http"
+        }
+        .into(),
+    );
+    if let Some(fingerprint) = fingerprint {
+        txt.insert("fingerprint".into(), fingerprint.into());
+    }
+    // How a new client gets a token: pair with a code, or be given the API token.
+    txt.insert(
+        "pairing".into(),
+        if config.gateway.require_pairing {
+            "required"
+        } else {
+            "token"
+        }
+        .into(),
+    );
+    txt.insert("pair_path".into(), "/pair".into());
+
+    Advertisement {
+        service_type: SERVICE_TYPE.into(),
+        instance_name: instance_name(config),
+        port,
+        txt,
+    }
+}
+
+/// A registered advertisement, withdrawn when dropped.
+pub struct Registration {
+    instance_name: String,
+}
+
+impl Drop for Registration {
+    fn drop(&mut self) {
+        let payload = json!({
+            "service_type": SERVICE_TYPE,
+            "instance_name": self.instance_name,
+        });
+        if let Err(e) = host_bridge::call_host("nsd_unregister", payload) {
+            tracing::debug!("Failed to withdraw gateway advertisement: {e:#}");
+        }
+    }
+}
+
+/// Advertise the gateway bound to `host:port` if the config allows it.
+/// Returns `None` when advertising is off, the gateway is loopback-only or
+/// bound to one interface, or no host app can register the service.
+pub async fn advertise(
+    config: &Config,
+    host: &str,
+    port: u16,
+    fingerprint: Option<&str>,
+) -> Option<Registration> {
+    // A gateway bound to one interface (e.g. a tailnet) is not on the LAN.
+    if !config.gateway.advertise || !is_public_bind(host) || config.gateway.bind_interface.is_some()
+    {
+        return None;
+    }
+    if host_bridge::host_bridge().is_none() {
+        tracing::info!(
+            "LAN advertisement of the gateway ({SERVICE_TYPE}) needs the mobile app; skipping"
+        );
+        return None;
+    }
+
+    let advertisement = advertisement(config, port, fingerprint);
+    let payload = match serde_json::to_value(&advertisement) {
+        Ok(payload) => payload,
+        Err(e) => {
+            tracing::warn!("Failed to encode gateway advertisement: {e}");
+            return None;
+        }
+    };
+    let registered =
+        tokio::task::spawn_blocking(move || host_bridge::call_host("nsd_register", payload)).await;
+    let reply = match registered {
+        Ok(Ok(reply)) => reply,
+        Ok(Err(e)) => {
+            tracing::warn!("Failed to advertise the gateway on the LAN: {e:#}");
+            return None;
+        }
+        Err(e) => {
+            tracing::warn!("Failed to advertise the gateway on the LAN: {e}");
+            return None;
+        }
+    };
+    if reply
+        .get("ok")
+        .and_then(serde_json::Value::as_bool)
+        .is_some_and(|ok| !ok)
+    {
+        let detail = reply
+            .get("error")
+            .and_then(serde_json::Value::as_str)
+            .unwrap_or("registration rejected");
+        tracing::warn!("Failed to advertise the gateway on the LAN: {detail}");
+        return None;
+    }
+
+    tracing::info!(
+        instance = %advertisement.instance_name,
+        port,
+        "Gateway advertised on the LAN as {SERVICE_TYPE}"
+    );
+    Some(Registration {
+        instance_name: advertisement.instance_name,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn advertisement_carries_pairing_hint_and_fingerprint() {
+        let mut config = Config::default();
+        config.gateway.instance_name = Some("  Kitchen phone ".into());
+
+        let ad = advertisement(&config, 42617, Some("ab:cd"));
+        assert_eq!(ad.service_type, "_mobileclaw._tcp");
+        assert_eq!(ad.instance_name, "Kitchen phone");
+        assert_eq!(ad.port, 42617);
+        assert_eq!(ad.txt["scheme"], "https");
+        assert_eq!(ad.txt["fingerprint"], "ab:cd");
+        assert_eq!(ad.txt["pairing"], "required");
+        assert_eq!(ad.txt["version"], env!("CARGO_PKG_VERSION"));
+
+        config.gateway.require_pairing = false;
+        let ad = advertisement(&config, 8080, None);
+        assert_eq!(ad.txt["scheme"], "http");
+        assert!(!ad.txt.contains_key("fingerprint"));
+        assert_eq!(ad.txt["pairing"], "token");
+    }
+
+    #[test]
+    fn instance_name_defaults_and_fits_one_label() {
+        let mut config = Config::default();
+        assert_eq!(instance_name(&config), "MobileClaw");
+
+        config.gateway.instance_name = Some("é".repeat(40));
+        let name = instance_name(&config);
+        assert!(name.len() <= MAX_INSTANCE_NAME_BYTES);
+        assert_eq!(name, "é".repeat(31));
+    }
+
+    #[tokio::test]
+    async fn loopback_gateway_is_not_advertised() {
+        let config = Config::default();
+        assert!(advertise(&config, "127.0.0.1", 42617, None).await.is_none());
+    }
+}
@@ -0,0 +1,396 @@
+//! Generic inbound webhooks at `POST /hooks/{name}`.
+//!
+//! Each `[gateway.hooks.<name>]` entry accepts arbitrary JSON from services
+//! such as GitHub, Grafana alerting or IFTTT, renders its `template` against
+//! the payload and either runs an agent turn with the result as the prompt or
+//! pushes it to `/ws` clients as a notification. Agent replies are pushed as
+//! notifications too, under the hook's topic.
+//!
+//! Hooks with a `secret` check it themselves, since most senders cannot
+//! carry a gateway token: a GitHub-style `X-Hub-Signature-256` HMAC of the
+//! body, an `X-Hook-Secret` header or a `?secret=` query parameter. Hooks
+//! without one need a gateway token like any other route, as a bearer header
+//! or `?token=`. Hooks run as the owner, so tokens of `[gateway.users]` do
+//! not count. Unknown hook names are only reported to authorized callers, so
+//! hook names cannot be probed.
+
+use super::{
+    client_key_from_request, process_agent_turn_with_session, verify_whatsapp_signature, AppState,
+    RATE_LIMIT_WINDOW_SECS,
+};
+use crate::config::{GatewayConfig, GatewayHookAction, GatewayHookConfig};
+use crate::security::pairing::{constant_time_eq, PairingGuard};
+use axum::{
+    body::Bytes,
+    extract::{ConnectInfo, Path, Query, RawQuery, State},
+    http::{HeaderMap, StatusCode},
+    response::{IntoResponse, Json, Response},
+};
+use serde::Deserialize;
+use std::net::SocketAddr;
+
+/// Headers carrying a per-delivery id, used to drop redelivered payloads.
+const DELIVERY_ID_HEADERS: &[&str] = &["X-Idempotency-Key", "X-GitHub-Delivery"];
+
+#[derive(Deserialize)]
+pub struct HookQuery {
+    pub secret: Option<String>,
+}
+
+/// Replace `{{path}}` placeholders in `template` with values from `payload`.
+///
+/// A path walks object fields and array indices separated by dots
+/// (`{{alerts.0.labels.alertname}}`); `{{.}}` is the whole payload. Strings
+/// are inserted as is, other values as JSON, and missing values as nothing.
+pub fn render(template: &str, payload: &serde_json::Value) -> String {
+    let mut out = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find("{{") {
+        let Some(len) = rest[start + 2..].find("}}") else {
+            break;
+        };
+        out.push_str(&rest[..start]);
+        let path = rest[start + 2..start + 2 + len].trim();
+        if let Some(value) = lookup(payload, path) {
+            match value {
+                serde_json::Value::String(s) => out.push_str(s),
+                serde_json::Value::Null => {}
+                other => out.push_str(&other.to_string()),
+            }
+        }
+        rest = &rest[start + 2 + len + 2..];
+    }
+    out.push_str(rest);
+    out
+}
+
+fn lookup<'a>(payload: &'a serde_json::Value, path: &str) -> Option<&'a serde_json::Value> {
+    if path == "." {
+        return Some(payload);
+    }
+    path.split('.')
+        .try_fold(payload, |value, segment| match value {
+            serde_json::Value::Array(items) => items.get(segment.parse::<usize>().ok()?),
+            _ => value.get(segment),
+        })
+}
+
+/// The prompt or notification text `hook` makes of `payload`.
+pub fn message(name: &str, hook: &GatewayHookConfig, payload: &serde_json::Value) -> String {
+    if hook.template.trim().is_empty() {
+        format!("Webhook {name} received: {payload}")
+    } else {
+        render(&hook.template, payload)
+    }
+}
+
+/// Whether the request carries an owner's gateway token, or none is
+/// required. Tokens assigned to a named user are refused.
+fn has_owner_token(
+    pairing: &PairingGuard,
+    gateway: &GatewayConfig,
+    headers: &HeaderMap,
+    query: Option<&str>,
+) -> bool {
+    if !pairing.requires_auth() {
+        return true;
+    }
+    let token = super::auth::request_token(headers, query).unwrap_or_default();
+    pairing.is_authenticated(&token)
+        && pairing
+            .paired_token_id(&token)
+            .and_then(|id| super::users::resolve(gateway, &id))
+            .is_none()
+}
+
+/// Whether the request proves `secret`, or carries an owner's gateway token
+/// when the hook has none.
+fn is_authorized(
+    pairing: &PairingGuard,
+    gateway: &GatewayConfig,
+    hook: &GatewayHookConfig,
+    headers: &HeaderMap,
+    query: Option<&str>,
+    query_secret: Option<&str>,
+    body: &[u8],
+) -> bool {
+    let header_value = |name: &str| headers.get(name).and_then(|v| v.to_str().ok());
+    let Some(secret) = hook.secret.as_deref().filter(|s| !s.is_empty()) else {
+        return has_owner_token(pairing, gateway, headers, query);
+    };
+    if let Some(signature) = header_value("X-Hub-Signature-256") {
+        return verify_whatsapp_signature(secret, body, signature);
+    }
+    header_value("X-Hook-Secret")
+        .or(query_secret)
+        .is_some_and(|given| constant_time_eq(given, secret))
+}
+
+fn error(status: StatusCode, message: &str) -> Response {
+    (status, Json(serde_json::json!({"error": message}))).into_response()
+}
+
+/// POST /hooks/{name} — run a configured hook on an arbitrary payload
+pub async fn handle_hook(
+    State(state): State<AppState>,
+    ConnectInfo(peer_addr): ConnectInfo<SocketAddr>,
+    Path(name): Path<String>,
+    Query(query): Query<HookQuery>,
+    RawQuery(raw_query): RawQuery,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Response {
+    let rate_key =
+        client_key_from_request(Some(peer_addr), &headers, state.trust_forwarded_headers);
+    if !state.rate_limiter.allow_webhook(&rate_key) {
+        tracing::warn!("/hooks/{name} rate limit exceeded");
+        return (
+            StatusCode::TOO_MANY_REQUESTS,
+            Json(serde_json::json!({
+                "error": "Too many webhook requests. Please retry later.",
+                "retry_after": RATE_LIMIT_WINDOW_SECS,
+            })),
+        )
+            .into_response();
+    }
+
+    let config = state.config.lock().clone();
+    let hook = config.gateway.hooks.get(&name);
+    // Unknown names get the same 401 as a wrong secret unless the caller holds
+    // an owner's gateway token.
+    let authorized = match hook {
+        Some(hook) => is_authorized(
+            &state.pairing,
+            &config.gateway,
+            hook,
+            &headers,
+            raw_query.as_deref(),
+            query.secret.as_deref(),
+            &body,
+        ),
+        None => has_owner_token(
+            &state.pairing,
+            &config.gateway,
+            &headers,
+            raw_query.as_deref(),
+        ),
+    };
+    if !authorized {
+        tracing::warn!("Hook {name}: rejected — invalid or missing secret");
+        return error(
+            StatusCode::UNAUTHORIZED,
+            "Unauthorized — invalid or missing hook secret",
+        );
+    }
+    let Some(hook) = hook else {
+        return error(StatusCode::NOT_FOUND, &format!("No hook named '{name}'"));
+    };
+
+    if let Some(delivery) = DELIVERY_ID_HEADERS.iter().find_map(|header_name| {
+        headers
+            .get(*header_name)
+            .and_then(|v| v.to_str().ok())
+            .map(str::trim)
+            .filter(|v| !v.is_empty())
+    }) {
+        if !state
+            .idempotency_store
+            .record_if_new(&format!("hook:{name}:{delivery}"))
+        {
+            tracing::info!("Hook {name}: duplicate delivery {delivery} ignored");
+            return Json(serde_json::json!({"status": "duplicate", "idempotent": true}))
+                .into_response();
+        }
+    }
+
+    // Senders that post plain text or forms still get their body through.
+    let payload = serde_json::from_slice(&body)
+        .unwrap_or_else(|_| serde_json::Value::String(String::from_utf8_lossy(&body).into_owned()));
+    let text = message(&name, hook, &payload);
+    let topic = hook.topic.clone().unwrap_or_else(|| name.clone());
+    let session_id = hook
+        .session
+        .clone()
+        .unwrap_or_else(|| format!("hook:{name}"));
+    let action = hook.action;
+    tracing::info!(hook = %name, ?action, "Webhook received");
+
+    match action {
+        GatewayHookAction::Notify => {
+            let delivered = super::ws::notify(&topic, &text);
+            Json(serde_json::json!({"status": "ok", "delivered": delivered})).into_response()
+        }
+        GatewayHookAction::Agent => {
+            let session = session_id.clone();
+            tokio::spawn(async move {
+                let turn = process_agent_turn_with_session(&state, &config, &text, Some(&session));
+                match turn.await {
+                    Ok(reply) => {
+                        super::ws::notify(&topic, &reply);
+                    }
+                    Err(e) => tracing::error!("Hook {name} agent turn failed: {e}"),
+                }
+            });
+            (
+                StatusCode::ACCEPTED,
+                Json(serde_json::json!({"status": "accepted", "session_id": session_id})),
+            )
+                .into_response()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn render_walks_fields_and_indices() {
+        let payload = serde_json::json!({
+            "action": "opened",
+            "pull_request": {"title": "Fix login", "number": 42},
+            "alerts": [{"labels": {"alertname": "DiskFull"}}],
+            "draft": false,
+        });
+        assert_eq!(
+            render(
+                "PR #{{pull_request.number}} {{ action }}: {{pull_request.title}}",
+                &payload
+            ),
+            "PR #42 opened: Fix login"
+        );
+        assert_eq!(
+            render("{{alerts.0.labels.alertname}} draft={{draft}}", &payload),
+            "DiskFull draft=false"
+        );
+        assert_eq!(render("[{{missing.field}}] {{alerts.9}}", &payload), "[] ");
+        assert_eq!(render("{{.}}", &serde_json::json!({"a": 1})), r#"{"a":1}"#);
+        assert_eq!(render("open {{ brace", &payload), "open {{ brace");
+    }
+
+    #[test]
+    fn hooks_without_secret_accept_header_or_query_token() {
+        let pairing = PairingGuard::new(true, &["zc_paired".to_string()]);
+        let gateway = GatewayConfig::default();
+        let hook = GatewayHookConfig::default();
+        let mut headers = HeaderMap::new();
+
+        assert!(!is_authorized(
+            &pairing, &gateway, &hook, &headers, None, None, b"{}"
+        ));
+        assert!(is_authorized(
+            &pairing,
+            &gateway,
+            &hook,
+            &headers,
+            Some("token=zc_paired"),
+            None,
+            b"{}"
+        ));
+        assert!(!is_authorized(
+            &pairing,
+            &gateway,
+            &hook,
+            &headers,
+            Some("token=zc_wrong"),
+            None,
+            b"{}"
+        ));
+
+        headers.insert(
+            axum::http::header::AUTHORIZATION,
+            "Bearer zc_paired".parse().unwrap(),
+        );
+        assert!(is_authorized(
+            &pairing, &gateway, &hook, &headers, None, None, b"{}"
+        ));
+    }
+
+    #[test]
+    fn hooks_without_secret_refuse_named_user_tokens() {
+        let pairing = PairingGuard::new(true, &["zc_owner".to_string(), "zc_alice".to_string()]);
+        let gateway = GatewayConfig {
+            users: std::collections::HashMap::from([(
+                "alice".to_string(),
+                crate::config::GatewayUserConfig {
+                    tokens: vec![pairing.paired_token_id("zc_alice").unwrap()],
+                    rate_limit_per_minute: None,
+                },
+            )]),
+            ..GatewayConfig::default()
+        };
+        let hook = GatewayHookConfig::default();
+        let headers = HeaderMap::new();
+
+        assert!(is_authorized(
+            &pairing,
+            &gateway,
+            &hook,
+            &headers,
+            Some("token=zc_owner"),
+            None,
+            b"{}"
+        ));
+        assert!(!is_authorized(
+            &pairing,
+            &gateway,
+            &hook,
+            &headers,
+            Some("token=zc_alice"),
+            None,
+            b"{}"
+        ));
+        assert!(!has_owner_token(
+            &pairing,
+            &gateway,
+            &headers,
+            Some("token=zc_alice")
+        ));
+    }
+
+    #[test]
+    fn hooks_with_secret_ignore_gateway_tokens() {
+        let pairing = PairingGuard::new(true, &["zc_paired".to_string()]);
+        let gateway = GatewayConfig::default();
+        let hook = GatewayHookConfig {
+            secret: Some("s3cret".into()),
+            ..GatewayHookConfig::default()
+        };
+        let headers = HeaderMap::new();
+
+        assert!(!is_authorized(
+            &pairing,
+            &gateway,
+            &hook,
+            &headers,
+            Some("token=zc_paired"),
+            None,
+            b"{}"
+        ));
+        assert!(is_authorized(
+            &pairing,
+            &gateway,
+            &hook,
+            &headers,
+            Some("secret=s3cret"),
+            Some("s3cret"),
+            b"{}"
+        ));
+    }
+
+    #[test]
+    fn empty_template_falls_back_to_payload() {
+        let hook = GatewayHookConfig::default();
+        let payload = serde_json::json!({"value1": "hello"});
+        assert_eq!(
+            message("ifttt", &hook, &payload),
+            r#"Webhook ifttt received: {"value1":"hello"}"#
+        );
+
+        let hook = GatewayHookConfig {
+            template: "IFTTT says {{value1}}".into(),
+            ..GatewayHookConfig::default()
+        };
+        assert_eq!(message("ifttt", &hook, &payload), "IFTTT says hello");
+    }
+}
@@ -7,9 +7,19 @@
 //! - Request timeouts (30s) to prevent slow-loris attacks
 //! - Header sanitization (handled by axum/hyper)
 
+pub mod admin;
 pub mod api;
+pub mod auth;
+pub mod bind;
+pub mod discovery;
+pub mod hooks;
+pub mod pairing;
 pub mod sse;
 pub mod static_files;
+pub mod tls;
+pub mod users;
+#[cfg(feature = "web-ui")]
+pub mod web_ui;
 pub mod ws;
 
 use crate::agent::Agent;
@@ -30,10 +40,12 @@ use crate::util::truncate_with_ellipsis;
 use anyhow::{Context, Result};
 use axum::{
     body::Bytes,
-    extract::{ConnectInfo, Query, State},
+    extract::{ConnectInfo, Extension, Query, State},
     http::{header, HeaderMap, StatusCode},
+    middleware,
     response::{IntoResponse, Json},
     routing::{delete, get, post, put},
+    serve::ListenerExt,
     Router,
 };
 use parking_lot::Mutex;
@@ -117,8 +129,19 @@ impl SlidingWindowRateLimiter {
     }
 
     fn allow(&self, key: &str) -> bool {
-        if self.limit_per_window == 0 {
-            return true;
+        self.check(key).is_none()
+    }
+
+    /// Record a request for `key`. Returns `None` when it is allowed, else
+    /// how long until the window has room again.
+    fn check(&self, key: &str) -> Option<Duration> {
+        self.check_with_limit(key, self.limit_per_window)
+    }
+
+    /// [`Self::check`] with a per-key `limit` in place of the limiter's own.
+    fn check_with_limit(&self, key: &str, limit: u32) -> Option<Duration> {
+        if limit == 0 {
+            return None;
         }
 
         let now = Instant::now();
@@ -152,12 +175,13 @@ impl SlidingWindowRateLimiter {
         let entry = requests.entry(key.to_owned()).or_default();
         entry.retain(|instant| *instant > cutoff);
 
-        if entry.len() >= self.limit_per_window as usize {
-            return false;
+        if entry.len() >= limit as usize {
+            let oldest = entry.first().copied().unwrap_or(now);
+            return Some((oldest + self.window).saturating_duration_since(now));
         }
 
         entry.push(now);
-        true
+        None
     }
 }
 
@@ -165,6 +189,9 @@ impl SlidingWindowRateLimiter {
 pub struct GatewayRateLimiter {
     pair: SlidingWindowRateLimiter,
     webhook: SlidingWindowRateLimiter,
+    token: SlidingWindowRateLimiter,
+    user: SlidingWindowRateLimiter,
+    ip: SlidingWindowRateLimiter,
 }
 
 impl GatewayRateLimiter {
@@ -173,9 +200,49 @@ impl GatewayRateLimiter {
         Self {
             pair: SlidingWindowRateLimiter::new(pair_per_minute, window, max_keys),
             webhook: SlidingWindowRateLimiter::new(webhook_per_minute, window, max_keys),
+            token: SlidingWindowRateLimiter::new(0, window, max_keys),
+            user: SlidingWindowRateLimiter::new(0, window, max_keys),
+            ip: SlidingWindowRateLimiter::new(0, window, max_keys),
         }
     }
 
+    /// Limit authenticated requests per bearer token and per client IP.
+    /// Named users get the token quota across all of their tokens unless
+    /// they have their own.
+    fn with_client_quotas(mut self, token_per_minute: u32, ip_per_minute: u32) -> Self {
+        let window = Duration::from_secs(RATE_LIMIT_WINDOW_SECS);
+        self.token = SlidingWindowRateLimiter::new(token_per_minute, window, self.token.max_keys);
+        self.user = SlidingWindowRateLimiter::new(token_per_minute, window, self.user.max_keys);
+        self.ip = SlidingWindowRateLimiter::new(ip_per_minute, window, self.ip.max_keys);
+        self
+    }
+
+    /// Count an authenticated request. Returns how long the client must wait
+    /// when its token, or `user` for a named user's token, or its IP is over
+    /// quota. An empty `token` or `ip` is not limited.
+    fn check_client(
+        &self,
+        token: &str,
+        ip: &str,
+        user: Option<&users::GatewayUser>,
+    ) -> Option<Duration> {
+        if !ip.is_empty() {
+            if let Some(retry_after) = self.ip.check(ip) {
+                return Some(retry_after);
+            }
+        }
+        if let Some(user) = user {
+            let limit = user
+                .rate_limit_per_minute
+                .unwrap_or(self.user.limit_per_window);
+            return self.user.check_with_limit(&user.name, limit);
+        }
+        if token.is_empty() {
+            return None;
+        }
+        self.token.check(&hash_webhook_secret(token))
+    }
+
     fn allow_pair(&self, key: &str) -> bool {
         self.pair.allow(key)
     }
@@ -260,11 +327,17 @@ fn forwarded_client_ip(headers: &HeaderMap) -> Option<IpAddr> {
         .and_then(parse_client_ip)
 }
 
+/// Rate-limit key of the client: the address the relay tunnel reported for
+/// relayed requests, which would otherwise all share the loopback peer, then
+/// the forwarded headers if trusted, then the peer address.
 fn client_key_from_request(
     peer_addr: Option<SocketAddr>,
     headers: &HeaderMap,
     trust_forwarded_headers: bool,
 ) -> String {
+    if let Some(key) = crate::tunnel::relayed_client_key(headers) {
+        return key;
+    }
     if trust_forwarded_headers {
         if let Some(ip) = forwarded_client_ip(headers) {
             return ip.to_string();
@@ -343,7 +416,14 @@ impl AgentSessionStore {
         }
     }
 
-    fn get_or_create(&self, session_id: &str, config: &Config) -> Result<Arc<AsyncMutex<Agent>>> {
+    /// The agent of `session_id`, created with its memory confined to
+    /// `memory_namespace` (when given) if the session is new.
+    fn get_or_create(
+        &self,
+        session_id: &str,
+        config: &Config,
+        memory_namespace: Option<&str>,
+    ) -> Result<Arc<AsyncMutex<Agent>>> {
         let now = Instant::now();
         let mut entries = self.entries.lock();
         entries.retain(|_, entry| now.duration_since(entry.last_used) < self.ttl);
@@ -363,7 +443,10 @@ impl AgentSessionStore {
             }
         }
 
-        let agent = Arc::new(AsyncMutex::new(Agent::from_config(config)?));
+        let agent = Arc::new(AsyncMutex::new(Agent::from_config_with_memory_namespace(
+            config,
+            memory_namespace,
+        )?));
         entries.insert(
             session_id.to_string(),
             AgentSessionEntry {
@@ -373,13 +456,34 @@ impl AgentSessionStore {
         );
         Ok(agent)
     }
+
+    fn len(&self) -> usize {
+        self.entries.lock().len()
+    }
+
+    /// Drop every session; the next message on a session starts afresh.
+    fn clear(&self) -> usize {
+        let mut entries = self.entries.lock();
+        let cleared = entries.len();
+        entries.clear();
+        cleared
+    }
 }
 
 /// Run the HTTP gateway using axum with proper HTTP/1.1 compliance.
 #[allow(clippy::too_many_lines)]
 pub async fn run_gateway(host: &str, port: u16, config: Config) -> Result<()> {
+    // ── Interface bind: `[gateway] bind_interface` replaces `host` ──
+    let bound_interface = bind::resolve(&config)?;
+    let bound_host = bound_interface.as_ref().map(bind::BoundInterface::host);
+    let host = bound_host.as_deref().unwrap_or(host);
+
     // ── Security: refuse public bind without tunnel or explicit opt-in ──
-    if is_public_bind(host) && config.tunnel.provider == "none" && !config.gateway.allow_public_bind
+    // A tailnet or VPN interface is a private overlay, not a public bind.
+    if is_public_bind(host)
+        && !bound_interface.as_ref().is_some_and(|bound| bound.overlay)
+        && config.tunnel.provider == "none"
+        && !config.gateway.allow_public_bind
     {
         anyhow::bail!(
             "🛑 Refusing to bind to {host} — gateway would be exposed to the internet.\n\
@@ -400,6 +504,17 @@ pub async fn run_gateway(host: &str, port: u16, config: Config) -> Result<()> {
     let listener = tokio::net::TcpListener::bind(addr).await?;
     let actual_port = listener.local_addr()?.port();
     let display_addr = format!("{host}:{actual_port}");
+    let gateway_tls = if config.gateway.tls.enabled {
+        // A new certificate names the node, so clients can verify it by name.
+        Some(tls::load(&config)?)
+    } else {
+        None
+    };
+    let scheme = if gateway_tls.is_some() {
+        "https"
+    } else {
+        "http"
+    };
 
     let provider: Arc<dyn Provider> = Arc::from(providers::create_resilient_provider_with_options(
         config.default_provider.as_deref().unwrap_or("openrouter"),
@@ -412,12 +527,21 @@ pub async fn run_gateway(host: &str, port: u16, config: Config) -> Result<()> {
             zeroclaw_dir: config.config_path.parent().map(std::path::PathBuf::from),
             secrets_encrypt: config.secrets.encrypt,
             reasoning_enabled: config.runtime.reasoning_enabled,
+            gemini_safety_settings: config.gemini.safety_settings.clone(),
+            azure_openai: config.azure_openai.clone(),
+            openrouter: config.openrouter.clone(),
+            local_llm: config.local_llm.clone(),
+            workspace_dir: Some(config.workspace_dir.clone()),
+            response_cache: memory::create_response_cache(&config.memory, &config.workspace_dir)
+                .map(Arc::new),
         },
     )?);
-    let model = config
-        .default_model
-        .clone()
-        .unwrap_or_else(|| "anthropic/claude-sonnet-4".into());
+    let model = config.resolve_model_alias(
+        config
+            .default_model
+            .as_deref()
+            .unwrap_or("anthropic/claude-sonnet-4"),
+    );
     let temperature = config.default_temperature;
     let mem: Arc<dyn Memory> = Arc::from(memory::create_memory_with_storage(
         &config.memory,
@@ -592,19 +716,30 @@ pub async fn run_gateway(host: &str, port: u16, config: Config) -> Result<()> {
             .map(Arc::from);
 
     // ── Pairing guard ──────────────────────────────────────
-    let pairing = Arc::new(PairingGuard::new(
-        config.gateway.require_pairing,
-        &config.gateway.paired_tokens,
-    ));
+    let api_token = auth::api_token(&config)?;
+    let pairing = Arc::new(
+        PairingGuard::new(
+            config.gateway.require_pairing,
+            &config.gateway.paired_tokens,
+        )
+        .with_api_token(&api_token),
+    );
+    pairing::register(Arc::clone(&pairing));
     let rate_limit_max_keys = normalize_max_keys(
         config.gateway.rate_limit_max_keys,
         RATE_LIMIT_MAX_KEYS_DEFAULT,
     );
-    let rate_limiter = Arc::new(GatewayRateLimiter::new(
-        config.gateway.pair_rate_limit_per_minute,
-        config.gateway.webhook_rate_limit_per_minute,
-        rate_limit_max_keys,
-    ));
+    let rate_limiter = Arc::new(
+        GatewayRateLimiter::new(
+            config.gateway.pair_rate_limit_per_minute,
+            config.gateway.webhook_rate_limit_per_minute,
+            rate_limit_max_keys,
+        )
+        .with_client_quotas(
+            config.gateway.token_rate_limit_per_minute,
+            config.gateway.ip_rate_limit_per_minute,
+        ),
+    );
     let idempotency_max_keys = normalize_max_keys(
         config.gateway.idempotency_max_keys,
         IDEMPOTENCY_MAX_KEYS_DEFAULT,
@@ -615,7 +750,10 @@ pub async fn run_gateway(host: &str, port: u16, config: Config) -> Result<()> {
     ));
 
     // ── Tunnel ────────────────────────────────────────────────
-    let tunnel = crate::tunnel::create_tunnel(&config.tunnel)?;
+    let tunnel = crate::tunnel::create_tunnel(
+        &config.tunnel,
+        gateway_tls.as_ref().map(|tls| tls.certificate.clone()),
+    )?;
     let mut tunnel_url: Option<String> = None;
 
     if let Some(ref tun) = tunnel {
@@ -632,11 +770,24 @@ pub async fn run_gateway(host: &str, port: u16, config: Config) -> Result<()> {
         }
     }
 
-    println!("🦀 ZeroClaw Gateway listening on http://{display_addr}");
+    println!("🦀 ZeroClaw Gateway listening on {scheme}://{display_addr}");
+    if let Some(ref bound) = bound_interface {
+        println!("  🔒 Bound to interface {} only", bound.name);
+    }
+    if let Some(ref node_name) = config.gateway.node_name {
+        println!("  🌐 Node URL: {scheme}://{node_name}:{actual_port}");
+    }
+    if let Some(ref tls) = gateway_tls {
+        println!("  🔏 TLS certificate SHA-256: {}", tls.fingerprint);
+    }
     if let Some(ref url) = tunnel_url {
         println!("  🌐 Public URL: {url}");
     }
-    println!("  🌐 Web Dashboard: http://{display_addr}/");
+    if cfg!(feature = "web-ui") {
+        println!("  💬 Web chat: {scheme}://{display_addr}/");
+    } else {
+        println!("  🌐 Web Dashboard: {scheme}://{display_addr}/");
+    }
     println!("  POST /pair          — pair a new client (X-Pairing-Code header)");
     println!("  POST /webhook       — {{\"message\": \"your prompt\"}} (simple chat)");
     println!("  POST /agent/message — {{\"message\": \"your prompt\"}} (full agent with tools)");
@@ -651,6 +802,14 @@ pub async fn run_gateway(host: &str, port: u16, config: Config) -> Result<()> {
         println!("  POST /whatsapp      — WhatsApp message webhook");
     }
     println!("  GET  /health        — health check");
+    if !config.gateway.hooks.is_empty() {
+        let mut names: Vec<&str> = config.gateway.hooks.keys().map(String::as_str).collect();
+        names.sort_unstable();
+        println!(
+            "  POST /hooks/{{name}} — inbound webhooks: {}",
+            names.join(", ")
+        );
+    }
     if linq_channel.is_some() {
         println!("  POST /linq      — Linq message webhook (iMessage/RCS/SMS)");
     }
@@ -662,7 +821,10 @@ pub async fn run_gateway(host: &str, port: u16, config: Config) -> Result<()> {
         println!("  POST /nextcloud-talk — Nextcloud Talk bot webhook");
     }
     println!("  GET  /api/*     — REST API (bearer token required)");
+    println!("  POST /admin/*   — admin API: reload, channel restart, cache flush, token rotation");
     println!("  GET  /ws/chat   — WebSocket agent chat");
+    println!("  GET  /ws        — WebSocket agent turns, tool events and notifications");
+    println!("  GET  /v1/stream/{{session}} — SSE stream of a session's agent events");
     println!("  GET  /metrics   — Prometheus metrics");
     if let Some(code) = pairing.pairing_code() {
         println!();
@@ -674,8 +836,12 @@ pub async fn run_gateway(host: &str, port: u16, config: Config) -> Result<()> {
     } else if pairing.require_pairing() {
         println!("  🔒 Pairing: ACTIVE (bearer token required)");
     } else {
-        println!("  ⚠️  Pairing: DISABLED (all requests accepted)");
+        println!("  🔒 Pairing: DISABLED (API token required)");
     }
+    println!(
+        "  🔑 API token: {} (send as Authorization: Bearer <token>)",
+        auth::token_path(&config)?.display()
+    );
     println!("  Press Ctrl+C to stop.\n");
 
     crate::health::mark_component_ok("gateway");
@@ -685,12 +851,26 @@ pub async fn run_gateway(host: &str, port: u16, config: Config) -> Result<()> {
         hooks.fire_gateway_start(host, actual_port).await;
     }
 
-    // Wrap observer with broadcast capability for SSE
-    let broadcast_observer: Arc<dyn crate::observability::Observer> =
-        Arc::new(sse::BroadcastObserver::new(
-            crate::observability::create_observer(&config.observability),
-            event_tx.clone(),
+    // ── LAN discovery (withdrawn when the gateway stops) ──
+    let _advertisement = discovery::advertise(
+        &config,
+        host,
+        actual_port,
+        gateway_tls.as_ref().map(|tls| tls.fingerprint.as_str()),
+    )
+    .await;
+
+    // Record token usage in the cost ledger, then wrap with broadcast
+    // capability for SSE
+    let mut base_observer = crate::observability::create_observer(&config.observability);
+    if let Some(ref tracker) = cost_tracker {
+        base_observer = Box::new(crate::cost::CostObserver::new(
+            base_observer,
+            Arc::clone(tracker),
         ));
+    }
+    let broadcast_observer: Arc<dyn crate::observability::Observer> =
+        Arc::new(sse::BroadcastObserver::new(base_observer, event_tx.clone()));
 
     let state = AppState {
         config: config_state,
@@ -727,12 +907,19 @@ pub async fn run_gateway(host: &str, port: u16, config: Config) -> Result<()> {
         .layer(RequestBodyLimitLayer::new(1_048_576));
 
     // Build router with middleware
-    let app = Router::new()
+    let app = Router::new();
+    // ── Embedded chat + status page ──
+    #[cfg(feature = "web-ui")]
+    let app = app
+        .route("/", get(web_ui::handle_index))
+        .route("/ui/{*path}", get(web_ui::handle_asset));
+    let app = app
         // ── Existing routes ──
         .route("/health", get(handle_health))
         .route("/metrics", get(handle_metrics))
         .route("/pair", post(handle_pair))
         .route("/webhook", post(handle_webhook))
+        .route("/hooks/{name}", post(hooks::handle_hook))
         .route("/agent/message", post(handle_agent_message))
         .route("/agent/stream", post(handle_agent_stream))
         .route("/agent/event", post(handle_agent_event))
@@ -762,6 +949,23 @@ pub async fn run_gateway(host: &str, port: u16, config: Config) -> Result<()> {
         // ── Web Dashboard API routes ──
         .route("/api/status", get(api::handle_api_status))
         .route("/api/config", get(api::handle_api_config_get))
+        .route("/api/config/reload", post(api::handle_api_config_reload))
+        .route("/api/config/validate", post(api::handle_api_config_validate))
+        .route("/api/config/sync", post(api::handle_api_config_sync))
+        .route("/api/config/changes", get(api::handle_api_config_changes))
+        .route("/api/config/export", post(api::handle_api_config_export))
+        .route("/api/config/import", post(api::handle_api_config_import))
+        .route("/api/pairing/code", post(api::handle_api_pairing_code))
+        .route("/api/pairing/tokens", get(api::handle_api_pairing_tokens))
+        .route(
+            "/api/pairing/tokens/{id}",
+            delete(api::handle_api_pairing_revoke),
+        )
+        .route("/api/profiles", get(api::handle_api_profiles))
+        .route(
+            "/api/profiles/{name}/activate",
+            post(api::handle_api_profile_activate),
+        )
         .route("/api/tools", get(api::handle_api_tools))
         .route("/api/cron", get(api::handle_api_cron_list))
         .route("/api/cron", post(api::handle_api_cron_add))
@@ -775,16 +979,46 @@ pub async fn run_gateway(host: &str, port: u16, config: Config) -> Result<()> {
         .route("/api/memory", post(api::handle_api_memory_store))
         .route("/api/memory/{key}", delete(api::handle_api_memory_delete))
         .route("/api/cost", get(api::handle_api_cost))
+        .route("/api/cost/daily", get(api::handle_api_cost_daily))
         .route("/api/cli-tools", get(api::handle_api_cli_tools))
         .route("/api/health", get(api::handle_api_health))
+        .route("/api/models", get(api::handle_api_models))
+        // ── Admin API for remote management ──
+        .route("/admin/reload", post(admin::handle_admin_reload))
+        .route(
+            "/admin/channels/{name}/restart",
+            post(admin::handle_admin_channel_restart),
+        )
+        .route("/admin/cache/flush", post(admin::handle_admin_cache_flush))
+        .route(
+            "/admin/tokens/rotate",
+            post(admin::handle_admin_tokens_rotate),
+        )
+        .route("/admin/diagnostics", get(admin::handle_admin_diagnostics))
         // ── SSE event stream ──
         .route("/api/events", get(sse::handle_sse_events))
+        .route("/v1/stream/{session}", get(sse::handle_session_stream))
         // ── WebSocket agent chat ──
         .route("/ws/chat", get(ws::handle_ws_chat))
+        .route("/ws", get(ws::handle_ws))
         // ── Static assets (web dashboard) ──
         .route("/_app/{*path}", get(static_files::handle_static))
         // ── Config PUT with larger body limit ──
         .merge(config_put_router)
+        // ── Per-token and per-IP quotas, then the bearer token check ──
+        .route_layer(middleware::from_fn_with_state(
+            state.clone(),
+            auth::enforce_quotas,
+        ))
+        .route_layer(middleware::from_fn_with_state(
+            state.clone(),
+            auth::require_token,
+        ))
+        // ── Request metrics, including rejected requests ──
+        .route_layer(middleware::from_fn_with_state(
+            state.clone(),
+            track_request_metrics,
+        ))
         .with_state(state)
         .layer(RequestBodyLimitLayer::new(MAX_BODY_SIZE))
         .layer(TimeoutLayer::with_status_code(
@@ -795,11 +1029,23 @@ pub async fn run_gateway(host: &str, port: u16, config: Config) -> Result<()> {
         .fallback(get(static_files::handle_spa_fallback));
 
     // Run the server
-    axum::serve(
-        listener,
-        app.into_make_service_with_connect_info::<SocketAddr>(),
-    )
-    .await?;
+    if let Some(gateway_tls) = gateway_tls {
+        // `tap_io` also makes the peer address available as `ConnectInfo`.
+        let listener = tls::TlsListener::new(listener, gateway_tls.acceptor)?.tap_io(|stream| {
+            let _ = stream.get_ref().0.set_nodelay(true);
+        });
+        axum::serve(
+            listener,
+            app.into_make_service_with_connect_info::<SocketAddr>(),
+        )
+        .await?;
+    } else {
+        axum::serve(
+            listener,
+            app.into_make_service_with_connect_info::<SocketAddr>(),
+        )
+        .await?;
+    }
 
     Ok(())
 }
@@ -824,12 +1070,11 @@ const PROMETHEUS_CONTENT_TYPE: &str = "text/plain; version=0.0.4; charset=utf-8"
 
 /// GET /metrics — Prometheus text exposition format
 async fn handle_metrics(State(state): State<AppState>) -> impl IntoResponse {
-    let body = if let Some(prom) = state
-        .observer
-        .as_ref()
-        .as_any()
-        .downcast_ref::<crate::observability::PrometheusObserver>()
-    {
+    let body = if let Some(prom) = prometheus_observer(&state) {
+        prom.update_process_metrics();
+        if let Ok(count) = state.mem.count().await {
+            prom.set_memory_entries(state.mem.name(), count);
+        }
         prom.encode()
     } else {
         String::from("# Prometheus backend not enabled. Set [observability] backend = \"prometheus\" in config.\n")
@@ -842,6 +1087,46 @@ async fn handle_metrics(State(state): State<AppState>) -> impl IntoResponse {
     )
 }
 
+/// The Prometheus registry `/metrics` serves: the gateway's own observer when
+/// it is one, else the process-wide registry when the `prometheus` backend is
+/// configured (the gateway's observer wraps it).
+fn prometheus_observer(state: &AppState) -> Option<&crate::observability::PrometheusObserver> {
+    if let Some(prom) = state
+        .observer
+        .as_ref()
+        .as_any()
+        .downcast_ref::<crate::observability::PrometheusObserver>()
+    {
+        return Some(prom);
+    }
+    (state.config.lock().observability.backend == "prometheus")
+        .then(crate::observability::PrometheusObserver::global)
+}
+
+/// Middleware counting requests per matched route for `/metrics`.
+async fn track_request_metrics(
+    State(state): State<AppState>,
+    request: axum::extract::Request,
+    next: middleware::Next,
+) -> axum::response::Response {
+    let method = request.method().to_string();
+    let route = request
+        .extensions()
+        .get::<axum::extract::MatchedPath>()
+        .map_or_else(|| "unmatched".to_string(), |path| path.as_str().to_string());
+    let started = Instant::now();
+    let response = next.run(request).await;
+    if let Some(prom) = prometheus_observer(&state) {
+        prom.record_gateway_request(
+            &method,
+            &route,
+            response.status().as_u16(),
+            started.elapsed(),
+        );
+    }
+    response
+}
+
 /// POST /pair — exchange one-time code for bearer token
 #[axum::debug_handler]
 async fn handle_pair(
@@ -868,12 +1153,14 @@ async fn handle_pair(
     match state.pairing.try_pair(code, &rate_key).await {
         Ok(Some(token)) => {
             tracing::info!("🔐 New client paired successfully");
+            let token_id = state.pairing.paired_token_id(&token);
             if let Err(err) = persist_pairing_tokens(state.config.clone(), &state.pairing).await {
                 tracing::error!("🔐 Pairing succeeded but token persistence failed: {err:#}");
                 let body = serde_json::json!({
                     "paired": true,
                     "persisted": false,
                     "token": token,
+                    "token_id": token_id,
                     "message": "Paired for this process, but failed to persist token to config.toml. Check config path and write permissions.",
                 });
                 return (StatusCode::OK, Json(body));
@@ -883,6 +1170,7 @@ async fn handle_pair(
                 "paired": true,
                 "persisted": true,
                 "token": token,
+                "token_id": token_id,
                 "message": "Save this token — use it as Authorization: Bearer <token>"
             });
             (StatusCode::OK, Json(body))
@@ -911,8 +1199,10 @@ async fn persist_pairing_tokens(config: Arc<Mutex<Config>>, pairing: &PairingGua
     // this should be removed once async mutexes are used everywhere
     let mut updated_cfg = { config.lock().clone() };
     updated_cfg.gateway.paired_tokens = paired_tokens;
+    // The guard already holds these; keep the daemon from restarting us.
+    crate::daemon::note_paired_tokens_saved(&updated_cfg.gateway.paired_tokens);
     updated_cfg
-        .save()
+        .save_from(crate::config::ChangeSource::Gateway)
         .await
         .context("Failed to persist paired tokens to config.toml")?;
 
@@ -935,10 +1225,50 @@ async fn process_agent_turn_with_session(
     message: &str,
     session_id: Option<&str>,
 ) -> Result<String> {
-    let session_id = normalize_session_id(session_id);
-    let agent = state.agent_sessions.get_or_create(&session_id, config)?;
-    let mut guard = agent.lock().await;
-    guard.turn(message).await
+    stream_agent_turn(state, config, message, session_id, None, None).await
+}
+
+/// Run a turn on a gateway session, publishing its progress on the agent
+/// event bus and, when given, to `forward`. A named `user` gets sessions and
+/// memory of its own (see [`users`]).
+async fn stream_agent_turn(
+    state: &AppState,
+    config: &Config,
+    message: &str,
+    session_id: Option<&str>,
+    user: Option<&users::GatewayUser>,
+    forward: Option<tokio::sync::mpsc::UnboundedSender<crate::agent::TurnEvent>>,
+) -> Result<String> {
+    let session_id = users::session_key(user, &normalize_session_id(session_id));
+    let namespace = user.map(users::GatewayUser::memory_namespace);
+    let agent = state
+        .agent_sessions
+        .get_or_create(&session_id, config, namespace.as_deref())?;
+
+    let (events_tx, mut events_rx) = tokio::sync::mpsc::unbounded_channel();
+    let turn = async move {
+        let mut guard = agent.lock().await;
+        guard.turn_with_events(message, events_tx).await
+    };
+    let relay = async {
+        while let Some(event) = events_rx.recv().await {
+            crate::agent::events::publish_turn_event(&session_id, &event);
+            if let Some(forward) = &forward {
+                let _ = forward.send(event);
+            }
+        }
+    };
+    let (result, ()) = tokio::join!(turn, relay);
+
+    let frame = match &result {
+        Ok(response) => serde_json::json!({"type": "done", "full_response": response}),
+        Err(e) => serde_json::json!({
+            "type": "error",
+            "message": providers::sanitize_api_error(&e.to_string()),
+        }),
+    };
+    crate::agent::events::publish(&session_id, frame);
+    result
 }
 
 fn normalize_telegram_identity(value: &str) -> String {
@@ -970,7 +1300,9 @@ async fn bind_telegram_identity_for_gateway(state: &AppState, identity: &str) ->
     }
 
     telegram.allowed_users.push(normalized);
-    updated.save().await?;
+    updated
+        .save_from(crate::config::ChangeSource::Gateway)
+        .await?;
     *state.config.lock() = updated;
     Ok(())
 }
@@ -1042,6 +1374,7 @@ pub struct DeviceEventRequest {
 async fn handle_webhook(
     State(state): State<AppState>,
     ConnectInfo(peer_addr): ConnectInfo<SocketAddr>,
+    user: Option<Extension<users::GatewayUser>>,
     headers: HeaderMap,
     body: Result<Json<WebhookBody>, axum::extract::rejection::JsonRejection>,
 ) -> impl IntoResponse {
@@ -1056,22 +1389,6 @@ async fn handle_webhook(
         return (StatusCode::TOO_MANY_REQUESTS, Json(err));
     }
 
-    // ── Bearer token auth (pairing) ──
-    if state.pairing.require_pairing() {
-        let auth = headers
-            .get(header::AUTHORIZATION)
-            .and_then(|v| v.to_str().ok())
-            .unwrap_or("");
-        let token = auth.strip_prefix("Bearer ").unwrap_or("");
-        if !state.pairing.is_authenticated(token) {
-            tracing::warn!("Webhook: rejected — not paired / invalid bearer token");
-            let err = serde_json::json!({
-                "error": "Unauthorized — pair first via POST /pair, then send Authorization: Bearer <token>"
-            });
-            return (StatusCode::UNAUTHORIZED, Json(err));
-        }
-    }
-
     // ── Webhook secret auth (optional, additional layer) ──
     if let Some(ref secret_hash) = state.webhook_secret_hash {
         let header_hash = headers
@@ -1109,7 +1426,8 @@ async fn handle_webhook(
         .map(str::trim)
         .filter(|value| !value.is_empty())
     {
-        if !state.idempotency_store.record_if_new(idempotency_key) {
+        let idempotency_key = users::session_key(user.as_deref(), idempotency_key);
+        if !state.idempotency_store.record_if_new(&idempotency_key) {
             tracing::info!("Webhook duplicate ignored (idempotency key: {idempotency_key})");
             let body = serde_json::json!({
                 "status": "duplicate",
@@ -1124,8 +1442,7 @@ async fn handle_webhook(
 
     if state.auto_save {
         let key = webhook_memory_key();
-        let _ = state
-            .mem
+        let _ = users::memory_for(&state.mem, user.as_deref())
             .store(&key, message, MemoryCategory::Conversation, None)
             .await;
     }
@@ -1227,6 +1544,7 @@ async fn handle_webhook(
 /// POST /agent/message — full agent runtime with tools, memory, and multi-step reasoning
 async fn handle_agent_message(
     State(state): State<AppState>,
+    user: Option<Extension<users::GatewayUser>>,
     headers: HeaderMap,
     body: Result<Json<AgentMessageRequest>, axum::extract::rejection::JsonRejection>,
 ) -> impl IntoResponse {
@@ -1240,22 +1558,6 @@ async fn handle_agent_message(
         return (StatusCode::TOO_MANY_REQUESTS, Json(err));
     }
 
-    // ── Bearer token auth (pairing) ──
-    if state.pairing.require_pairing() {
-        let auth = headers
-            .get(header::AUTHORIZATION)
-            .and_then(|v| v.to_str().ok())
-            .unwrap_or("");
-        let token = auth.strip_prefix("Bearer ").unwrap_or("");
-        if !state.pairing.is_authenticated(token) {
-            tracing::warn!("Agent: rejected — not paired / invalid bearer token");
-            let err = serde_json::json!({
-                "error": "Unauthorized — pair first via POST /pair, then send Authorization: Bearer <token>"
-            });
-            return (StatusCode::UNAUTHORIZED, Json(err));
-        }
-    }
-
     // ── Webhook secret auth (optional, additional layer) ──
     if let Some(ref secret_hash) = state.webhook_secret_hash {
         let header_hash = headers
@@ -1310,7 +1612,16 @@ async fn handle_agent_message(
 
     // Use full agent runtime with tools + memory + multi-step reasoning
     // This is the key difference from /webhook (which uses simple_chat)
-    match process_agent_turn_with_session(&state, &config, &message, Some(&session_id)).await {
+    match stream_agent_turn(
+        &state,
+        &config,
+        &message,
+        Some(&session_id),
+        user.as_deref(),
+        None,
+    )
+    .await
+    {
         Ok(response) => {
             let body = serde_json::json!({
                 "response": response,
@@ -1334,29 +1645,12 @@ async fn handle_agent_message(
 /// Format: `data: {"delta":"<chunk>"}\n\n` followed by `data: [DONE]\n\n`.
 async fn handle_agent_stream(
     State(state): State<AppState>,
-    headers: HeaderMap,
+    user: Option<Extension<users::GatewayUser>>,
     body: Result<Json<AgentMessageRequest>, axum::extract::rejection::JsonRejection>,
 ) -> impl IntoResponse {
     use axum::body::Body;
     use axum::response::Response;
 
-    // ── Bearer token auth (pairing) ──
-    if state.pairing.require_pairing() {
-        let auth = headers
-            .get(header::AUTHORIZATION)
-            .and_then(|v| v.to_str().ok())
-            .unwrap_or("");
-        let token = auth.strip_prefix("Bearer ").unwrap_or("");
-        if !state.pairing.is_authenticated(token) {
-            let err = serde_json::json!({"error": "Unauthorized"});
-            return Response::builder()
-                .status(StatusCode::UNAUTHORIZED)
-                .header(header::CONTENT_TYPE, "application/json")
-                .body(Body::from(err.to_string()))
-                .unwrap_or_default();
-        }
-    }
-
     // ── Parse body ──
     let Json(agent_body) = match body {
         Ok(b) => b,
@@ -1375,7 +1669,16 @@ async fn handle_agent_stream(
     let session_id = normalize_session_id(agent_body.session_id.as_deref());
     let config = state.config.lock().clone();
 
-    let response_text = match process_agent_turn_with_session(&state, &config, &message, Some(&session_id)).await {
+    let response_text = match stream_agent_turn(
+        &state,
+        &config,
+        &message,
+        Some(&session_id),
+        user.as_deref(),
+        None,
+    )
+    .await
+    {
         Ok(r) => r,
         Err(e) => {
             tracing::error!(
@@ -1407,21 +1710,8 @@ async fn handle_agent_stream(
 /// POST /agent/telegram/pair — bind Telegram identity from MobileClaw app flow
 async fn handle_agent_telegram_pair(
     State(state): State<AppState>,
-    headers: HeaderMap,
     body: Result<Json<TelegramPairRequest>, axum::extract::rejection::JsonRejection>,
 ) -> impl IntoResponse {
-    if state.pairing.require_pairing() {
-        let auth = headers
-            .get(header::AUTHORIZATION)
-            .and_then(|v| v.to_str().ok())
-            .unwrap_or("");
-        let token = auth.strip_prefix("Bearer ").unwrap_or("");
-        if !state.pairing.is_authenticated(token) {
-            let err = serde_json::json!({"error": "Unauthorized"});
-            return (StatusCode::UNAUTHORIZED, Json(err));
-        }
-    }
-
     let Json(payload) = match body {
         Ok(b) => b,
         Err(_) => {
@@ -1471,22 +1761,6 @@ async fn handle_agent_event(
         return (StatusCode::TOO_MANY_REQUESTS, Json(err));
     }
 
-    // ── Bearer token auth (pairing) ──
-    if state.pairing.require_pairing() {
-        let auth = headers
-            .get(header::AUTHORIZATION)
-            .and_then(|v| v.to_str().ok())
-            .unwrap_or("");
-        let token = auth.strip_prefix("Bearer ").unwrap_or("");
-        if !state.pairing.is_authenticated(token) {
-            tracing::warn!("Event: rejected — not paired / invalid bearer token");
-            let err = serde_json::json!({
-                "error": "Unauthorized — pair first via POST /pair, then send Authorization: Bearer <token>"
-            });
-            return (StatusCode::UNAUTHORIZED, Json(err));
-        }
-    }
-
     // ── Parse body ──
     let Json(event_body) = match body {
         Ok(b) => b,
@@ -2050,6 +2324,7 @@ pub struct MemoryForgetQuery {
 
 async fn handle_list_memories(
     State(state): State<AppState>,
+    user: Option<Extension<users::GatewayUser>>,
     Query(params): Query<MemoryListQuery>,
 ) -> impl IntoResponse {
     let category = params.category.and_then(|c| match c.as_str() {
@@ -2060,7 +2335,8 @@ async fn handle_list_memories(
         _ => None,
     });
 
-    match state.mem.list(category.as_ref(), None).await {
+    let mem = users::memory_for(&state.mem, user.as_deref());
+    match mem.list(category.as_ref(), None).await {
         Ok(entries) => {
             let items: Vec<serde_json::Value> = entries
                 .iter()
@@ -2086,6 +2362,7 @@ async fn handle_list_memories(
 
 async fn handle_recall_memory(
     State(state): State<AppState>,
+    user: Option<Extension<users::GatewayUser>>,
     Query(params): Query<MemoryRecallQuery>,
 ) -> impl IntoResponse {
     let query = params.query.trim();
@@ -2098,7 +2375,8 @@ async fn handle_recall_memory(
 
     let limit = params.limit.clamp(1, 100);
 
-    match state.mem.recall(query, limit, None).await {
+    let mem = users::memory_for(&state.mem, user.as_deref());
+    match mem.recall(query, limit, None).await {
         Ok(entries) => {
             let items: Vec<serde_json::Value> = entries
                 .iter()
@@ -2125,8 +2403,12 @@ async fn handle_recall_memory(
     }
 }
 
-async fn handle_memory_count(State(state): State<AppState>) -> impl IntoResponse {
-    match state.mem.count().await {
+async fn handle_memory_count(
+    State(state): State<AppState>,
+    user: Option<Extension<users::GatewayUser>>,
+) -> impl IntoResponse {
+    let mem = users::memory_for(&state.mem, user.as_deref());
+    match mem.count().await {
         Ok(count) => (StatusCode::OK, Json(serde_json::json!({"count": count}))),
         Err(e) => (
             StatusCode::INTERNAL_SERVER_ERROR,
@@ -2137,6 +2419,7 @@ async fn handle_memory_count(State(state): State<AppState>) -> impl IntoResponse
 
 async fn handle_forget_memory(
     State(state): State<AppState>,
+    user: Option<Extension<users::GatewayUser>>,
     Query(params): Query<MemoryForgetQuery>,
 ) -> impl IntoResponse {
     let key = params.key.trim();
@@ -2147,7 +2430,8 @@ async fn handle_forget_memory(
         );
     }
 
-    match state.mem.forget(key).await {
+    let mem = users::memory_for(&state.mem, user.as_deref());
+    match mem.forget(key).await {
         Ok(true) => (
             StatusCode::OK,
             Json(serde_json::json!({"status": "forgotten", "key": key})),
@@ -2852,6 +3136,7 @@ mod tests {
             tools_registry: Arc::new(Vec::new()),
             cost_tracker: None,
             event_tx: tokio::sync::broadcast::channel(16).0,
+            agent_sessions: Arc::new(AgentSessionStore::new(16, Duration::from_secs(60))),
         };
 
         let response = handle_metrics(State(state)).await.into_response();
@@ -2901,6 +3186,7 @@ mod tests {
             tools_registry: Arc::new(Vec::new()),
             cost_tracker: None,
             event_tx: tokio::sync::broadcast::channel(16).0,
+            agent_sessions: Arc::new(AgentSessionStore::new(16, Duration::from_secs(60))),
         };
 
         let response = handle_metrics(State(state)).await.into_response();
@@ -2919,6 +3205,51 @@ mod tests {
         assert!(!limiter.allow_pair("127.0.0.1"));
     }
 
+    #[test]
+    fn gateway_rate_limiter_client_quotas_report_retry_after() {
+        let limiter = GatewayRateLimiter::new(2, 2, 100).with_client_quotas(2, 3);
+        assert!(limiter.check_client("zc_a", "10.0.0.1", None).is_none());
+        assert!(limiter.check_client("zc_a", "10.0.0.1", None).is_none());
+        let retry_after = limiter.check_client("zc_a", "10.0.0.2", None).unwrap();
+        assert!(retry_after <= Duration::from_secs(RATE_LIMIT_WINDOW_SECS));
+        assert!(retry_after > Duration::from_secs(RATE_LIMIT_WINDOW_SECS - 5));
+
+        // Another token from the same IP hits the per-IP quota instead
+        assert!(limiter.check_client("zc_b", "10.0.0.1", None).is_none());
+        assert!(limiter.check_client("zc_b", "10.0.0.1", None).is_some());
+
+        // Unlimited without a token or IP key
+        for _ in 0..10 {
+            assert!(limiter.check_client("", "", None).is_none());
+        }
+        let unlimited = GatewayRateLimiter::new(2, 2, 100);
+        for _ in 0..10 {
+            assert!(unlimited.check_client("zc_a", "10.0.0.1", None).is_none());
+        }
+    }
+
+    #[test]
+    fn gateway_rate_limiter_user_quota_spans_tokens() {
+        let limiter = GatewayRateLimiter::new(2, 2, 100).with_client_quotas(3, 0);
+        let alice = users::GatewayUser {
+            name: "alice".into(),
+            rate_limit_per_minute: Some(2),
+        };
+        assert!(limiter.check_client("zc_a1", "", Some(&alice)).is_none());
+        assert!(limiter.check_client("zc_a2", "", Some(&alice)).is_none());
+        assert!(limiter.check_client("zc_a3", "", Some(&alice)).is_some());
+
+        // Without an override a user gets the token quota
+        let bob = users::GatewayUser {
+            name: "bob".into(),
+            rate_limit_per_minute: None,
+        };
+        for _ in 0..3 {
+            assert!(limiter.check_client("zc_b", "", Some(&bob)).is_none());
+        }
+        assert!(limiter.check_client("zc_b", "", Some(&bob)).is_some());
+    }
+
     #[test]
     fn rate_limiter_sweep_removes_stale_entries() {
         let limiter = SlidingWindowRateLimiter::new(10, Duration::from_secs(60), 100);
@@ -3258,7 +3589,6 @@ mod tests {
             idempotency_store: Arc::new(IdempotencyStore::new(Duration::from_secs(300), 1000)),
             whatsapp: None,
             whatsapp_app_secret: None,
-            config: Arc::new(crate::config::Config::default()),
             linq: None,
             linq_signing_secret: None,
             nextcloud_talk: None,
@@ -3268,6 +3598,7 @@ mod tests {
             tools_registry: Arc::new(Vec::new()),
             cost_tracker: None,
             event_tx: tokio::sync::broadcast::channel(16).0,
+            agent_sessions: Arc::new(AgentSessionStore::new(16, Duration::from_secs(60))),
         };
 
         let mut headers = HeaderMap::new();
@@ -3279,6 +3610,7 @@ mod tests {
         let first = handle_webhook(
             State(state.clone()),
             test_connect_info(),
+            None,
             headers.clone(),
             body,
         )
@@ -3289,7 +3621,7 @@ mod tests {
         let body = Ok(Json(WebhookBody {
             message: "hello".into(),
         }));
-        let second = handle_webhook(State(state), test_connect_info(), headers, body)
+        let second = handle_webhook(State(state), test_connect_info(), None, headers, body)
             .await
             .into_response();
         assert_eq!(second.status(), StatusCode::OK);
@@ -3301,6 +3633,64 @@ mod tests {
         assert_eq!(provider_impl.calls.load(Ordering::SeqCst), 1);
     }
 
+    #[tokio::test]
+    async fn webhook_idempotency_keys_are_per_user() {
+        let provider_impl = Arc::new(MockProvider::default());
+        let provider: Arc<dyn Provider> = provider_impl.clone();
+        let memory: Arc<dyn Memory> = Arc::new(MockMemory);
+
+        let state = AppState {
+            config: Arc::new(Mutex::new(Config::default())),
+            provider,
+            model: "test-model".into(),
+            temperature: 0.0,
+            mem: memory,
+            auto_save: false,
+            webhook_secret_hash: None,
+            pairing: Arc::new(PairingGuard::new(false, &[])),
+            trust_forwarded_headers: false,
+            rate_limiter: Arc::new(GatewayRateLimiter::new(100, 100, 100)),
+            idempotency_store: Arc::new(IdempotencyStore::new(Duration::from_secs(300), 1000)),
+            whatsapp: None,
+            whatsapp_app_secret: None,
+            linq: None,
+            linq_signing_secret: None,
+            nextcloud_talk: None,
+            nextcloud_talk_webhook_secret: None,
+            wati: None,
+            observer: Arc::new(crate::observability::NoopObserver),
+            tools_registry: Arc::new(Vec::new()),
+            cost_tracker: None,
+            event_tx: tokio::sync::broadcast::channel(16).0,
+            agent_sessions: Arc::new(AgentSessionStore::new(16, Duration::from_secs(60))),
+        };
+        let alice = users::GatewayUser {
+            name: "alice".into(),
+            rate_limit_per_minute: None,
+        };
+
+        let mut headers = HeaderMap::new();
+        headers.insert("X-Idempotency-Key", HeaderValue::from_static("abc-123"));
+
+        for user in [None, Some(Extension(alice))] {
+            let response = handle_webhook(
+                State(state.clone()),
+                test_connect_info(),
+                user,
+                headers.clone(),
+                Ok(Json(WebhookBody {
+                    message: "hello".into(),
+                })),
+            )
+            .await
+            .into_response();
+            let payload = response.into_body().collect().await.unwrap().to_bytes();
+            let parsed: serde_json::Value = serde_json::from_slice(&payload).unwrap();
+            assert!(parsed.get("status").is_none(), "{parsed}");
+        }
+        assert_eq!(provider_impl.calls.load(Ordering::SeqCst), 2);
+    }
+
     #[tokio::test]
     async fn webhook_autosave_stores_distinct_keys_per_request() {
         let provider_impl = Arc::new(MockProvider::default());
@@ -3323,7 +3713,6 @@ mod tests {
             idempotency_store: Arc::new(IdempotencyStore::new(Duration::from_secs(300), 1000)),
             whatsapp: None,
             whatsapp_app_secret: None,
-            config: Arc::new(crate::config::Config::default()),
             linq: None,
             linq_signing_secret: None,
             nextcloud_talk: None,
@@ -3333,6 +3722,7 @@ mod tests {
             tools_registry: Arc::new(Vec::new()),
             cost_tracker: None,
             event_tx: tokio::sync::broadcast::channel(16).0,
+            agent_sessions: Arc::new(AgentSessionStore::new(16, Duration::from_secs(60))),
         };
 
         let headers = HeaderMap::new();
@@ -3343,6 +3733,7 @@ mod tests {
         let first = handle_webhook(
             State(state.clone()),
             test_connect_info(),
+            None,
             headers.clone(),
             body1,
         )
@@ -3353,7 +3744,7 @@ mod tests {
         let body2 = Ok(Json(WebhookBody {
             message: "hello two".into(),
         }));
-        let second = handle_webhook(State(state), test_connect_info(), headers, body2)
+        let second = handle_webhook(State(state), test_connect_info(), None, headers, body2)
             .await
             .into_response();
         assert_eq!(second.status(), StatusCode::OK);
@@ -3400,7 +3791,6 @@ mod tests {
             idempotency_store: Arc::new(IdempotencyStore::new(Duration::from_secs(300), 1000)),
             whatsapp: None,
             whatsapp_app_secret: None,
-            config: Arc::new(crate::config::Config::default()),
             linq: None,
             linq_signing_secret: None,
             nextcloud_talk: None,
@@ -3410,11 +3800,13 @@ mod tests {
             tools_registry: Arc::new(Vec::new()),
             cost_tracker: None,
             event_tx: tokio::sync::broadcast::channel(16).0,
+            agent_sessions: Arc::new(AgentSessionStore::new(16, Duration::from_secs(60))),
         };
 
         let response = handle_webhook(
             State(state),
             test_connect_info(),
+            None,
             HeaderMap::new(),
             Ok(Json(WebhookBody {
                 message: "hello".into(),
@@ -3449,7 +3841,6 @@ mod tests {
             idempotency_store: Arc::new(IdempotencyStore::new(Duration::from_secs(300), 1000)),
             whatsapp: None,
             whatsapp_app_secret: None,
-            config: Arc::new(crate::config::Config::default()),
             linq: None,
             linq_signing_secret: None,
             nextcloud_talk: None,
@@ -3459,6 +3850,7 @@ mod tests {
             tools_registry: Arc::new(Vec::new()),
             cost_tracker: None,
             event_tx: tokio::sync::broadcast::channel(16).0,
+            agent_sessions: Arc::new(AgentSessionStore::new(16, Duration::from_secs(60))),
         };
 
         let mut headers = HeaderMap::new();
@@ -3470,6 +3862,7 @@ mod tests {
         let response = handle_webhook(
             State(state),
             test_connect_info(),
+            None,
             headers,
             Ok(Json(WebhookBody {
                 message: "hello".into(),
@@ -3503,7 +3896,6 @@ mod tests {
             idempotency_store: Arc::new(IdempotencyStore::new(Duration::from_secs(300), 1000)),
             whatsapp: None,
             whatsapp_app_secret: None,
-            config: Arc::new(crate::config::Config::default()),
             linq: None,
             linq_signing_secret: None,
             nextcloud_talk: None,
@@ -3513,6 +3905,7 @@ mod tests {
             tools_registry: Arc::new(Vec::new()),
             cost_tracker: None,
             event_tx: tokio::sync::broadcast::channel(16).0,
+            agent_sessions: Arc::new(AgentSessionStore::new(16, Duration::from_secs(60))),
         };
 
         let mut headers = HeaderMap::new();
@@ -3521,6 +3914,7 @@ mod tests {
         let response = handle_webhook(
             State(state),
             test_connect_info(),
+            None,
             headers,
             Ok(Json(WebhookBody {
                 message: "hello".into(),
@@ -3571,6 +3965,7 @@ mod tests {
             tools_registry: Arc::new(Vec::new()),
             cost_tracker: None,
             event_tx: tokio::sync::broadcast::channel(16).0,
+            agent_sessions: Arc::new(AgentSessionStore::new(16, Duration::from_secs(60))),
         };
 
         let response = handle_nextcloud_talk_webhook(
@@ -3625,6 +4020,7 @@ mod tests {
             tools_registry: Arc::new(Vec::new()),
             cost_tracker: None,
             event_tx: tokio::sync::broadcast::channel(16).0,
+            agent_sessions: Arc::new(AgentSessionStore::new(16, Duration::from_secs(60))),
         };
 
         let mut headers = HeaderMap::new();
@@ -4015,6 +4411,153 @@ mod tests {
         assert!(keys.len() <= 1000, "should respect max_keys");
     }
 
+    #[tokio::test]
+    async fn named_user_cannot_list_or_delete_owner_cron_jobs() {
+        use tower::Service;
+
+        let tmp = tempfile::TempDir::new().unwrap();
+        let pairing = Arc::new(PairingGuard::new(true, &["zc_alice".to_string()]));
+        let alice_id = pairing.paired_token_id("zc_alice").unwrap();
+        let mut config = Config {
+            workspace_dir: tmp.path().join("workspace"),
+            config_path: tmp.path().join("config.toml"),
+            ..Config::default()
+        };
+        config.gateway.users.insert(
+            "alice".into(),
+            crate::config::GatewayUserConfig {
+                tokens: vec![alice_id],
+                rate_limit_per_minute: None,
+            },
+        );
+        let job = crate::cron::add_shell_job(
+            &config,
+            Some("owner-job".into()),
+            crate::cron::Schedule::Cron {
+                expr: "*/5 * * * *".into(),
+                tz: None,
+            },
+            "echo owner",
+        )
+        .unwrap();
+
+        let state = AppState {
+            config: Arc::new(Mutex::new(config.clone())),
+            provider: Arc::new(MockProvider::default()),
+            model: "test-model".into(),
+            temperature: 0.0,
+            mem: Arc::new(MockMemory),
+            auto_save: false,
+            webhook_secret_hash: None,
+            pairing,
+            trust_forwarded_headers: false,
+            rate_limiter: Arc::new(GatewayRateLimiter::new(100, 100, 100)),
+            idempotency_store: Arc::new(IdempotencyStore::new(Duration::from_secs(300), 1000)),
+            whatsapp: None,
+            whatsapp_app_secret: None,
+            linq: None,
+            linq_signing_secret: None,
+            nextcloud_talk: None,
+            nextcloud_talk_webhook_secret: None,
+            wati: None,
+            observer: Arc::new(crate::observability::NoopObserver),
+            tools_registry: Arc::new(Vec::new()),
+            cost_tracker: None,
+            event_tx: tokio::sync::broadcast::channel(16).0,
+            agent_sessions: Arc::new(AgentSessionStore::new(16, Duration::from_secs(60))),
+        };
+        let mut app = Router::new()
+            .route(
+                "/cron/jobs",
+                get(handle_list_cron_jobs).delete(handle_delete_cron_job),
+            )
+            .route("/api/cron", get(api::handle_api_cron_list))
+            .route("/api/cron/{id}", delete(api::handle_api_cron_delete))
+            .route_layer(middleware::from_fn_with_state(
+                state.clone(),
+                auth::require_token,
+            ))
+            .with_state(state);
+
+        for (method, uri) in [
+            ("GET", "/cron/jobs".to_string()),
+            ("DELETE", format!("/cron/jobs?id={}", job.id)),
+            ("GET", "/api/cron".to_string()),
+            ("DELETE", format!("/api/cron/{}", job.id)),
+        ] {
+            let request = axum::http::Request::builder()
+                .method(method)
+                .uri(&uri)
+                .header(header::AUTHORIZATION, "Bearer zc_alice")
+                .body(axum::body::Body::empty())
+                .unwrap();
+            let response = app.call(request).await.unwrap();
+            assert_eq!(response.status(), StatusCode::FORBIDDEN, "{method} {uri}");
+        }
+
+        assert!(crate::cron::get_job(&config, &job.id).is_ok());
+    }
+
+    #[tokio::test]
+    async fn query_token_is_accepted_by_agent_and_event_routes() {
+        use tower::Service;
+
+        let state = AppState {
+            config: Arc::new(Mutex::new(Config::default())),
+            provider: Arc::new(MockProvider::default()),
+            model: "test-model".into(),
+            temperature: 0.0,
+            mem: Arc::new(MockMemory),
+            auto_save: false,
+            webhook_secret_hash: None,
+            pairing: Arc::new(PairingGuard::new(true, &["zc_owner".to_string()])),
+            trust_forwarded_headers: false,
+            rate_limiter: Arc::new(GatewayRateLimiter::new(100, 100, 100)),
+            idempotency_store: Arc::new(IdempotencyStore::new(Duration::from_secs(300), 1000)),
+            whatsapp: None,
+            whatsapp_app_secret: None,
+            linq: None,
+            linq_signing_secret: None,
+            nextcloud_talk: None,
+            nextcloud_talk_webhook_secret: None,
+            wati: None,
+            observer: Arc::new(crate::observability::NoopObserver),
+            tools_registry: Arc::new(Vec::new()),
+            cost_tracker: None,
+            event_tx: tokio::sync::broadcast::channel(16).0,
+            agent_sessions: Arc::new(AgentSessionStore::new(16, Duration::from_secs(60))),
+        };
+        let mut app = Router::new()
+            .route("/agent/message", post(handle_agent_message))
+            .route("/agent/stream", post(handle_agent_stream))
+            .route("/api/events", get(sse::handle_sse_events))
+            .route_layer(middleware::from_fn_with_state(
+                state.clone(),
+                auth::require_token,
+            ))
+            .with_state(state);
+
+        // Past the token check, an empty body is rejected as invalid JSON.
+        for (method, path, authorized) in [
+            ("POST", "/agent/message", StatusCode::BAD_REQUEST),
+            ("POST", "/agent/stream", StatusCode::BAD_REQUEST),
+            ("GET", "/api/events", StatusCode::OK),
+        ] {
+            for (query, expected) in [
+                ("?token=zc_owner", authorized),
+                ("?token=zc_wrong", StatusCode::UNAUTHORIZED),
+            ] {
+                let request = axum::http::Request::builder()
+                    .method(method)
+                    .uri(format!("{path}{query}"))
+                    .body(axum::body::Body::empty())
+                    .unwrap();
+                let response = app.call(request).await.unwrap();
+                assert_eq!(response.status(), expected, "{method} {path}{query}");
+            }
+        }
+    }
+
     #[test]
     fn rate_limiter_rapid_burst_then_cooldown() {
         let limiter = SlidingWindowRateLimiter::new(5, Duration::from_millis(50), 100);
@@ -0,0 +1,128 @@
+//! On-demand pairing codes for QR pairing.
+//!
+//! A code issued here is valid for `[gateway] pairing_code_ttl_secs` and pairs
+//! one client through `POST /pair`, which returns a long-lived bearer token.
+//! The offer bundles the code with the gateway URL and, when TLS is on, the
+//! certificate fingerprint to pin, plus the `zeroclaw://pair` URI a QR code
+//! should encode. The running gateway registers its guard here so the Android
+//! bridge can issue codes without a round trip through HTTP.
+
+use super::tls;
+use crate::config::Config;
+use crate::security::PairingGuard;
+use anyhow::Result;
+use serde::Serialize;
+use std::fmt::Write;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+static ACTIVE_GUARD: Mutex<Option<Arc<PairingGuard>>> = Mutex::new(None);
+
+/// Everything a client needs to pair. `uri` is what the QR code encodes.
+#[derive(Debug, Clone, Serialize)]
+pub struct PairingOffer {
+    pub code: String,
+    pub expires_in_secs: u64,
+    pub url: String,
+    /// SHA-256 of the gateway's TLS certificate, when TLS is on.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fingerprint: Option<String>,
+    pub uri: String,
+}
+
+/// Make `guard` the one [`active`] returns. Called when the gateway starts.
+pub fn register(guard: Arc<PairingGuard>) {
+    *ACTIVE_GUARD.lock().unwrap_or_else(|e| e.into_inner()) = Some(guard);
+}
+
+/// Pairing guard of the running gateway, if any.
+pub fn active() -> Option<Arc<PairingGuard>> {
+    ACTIVE_GUARD
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .clone()
+}
+
+/// The gateway's URL from its configured port and its node name, bound
+/// interface address or host, in that order.
+pub fn gateway_url(config: &Config) -> String {
+    let scheme = if config.gateway.tls.enabled {
+        "https"
+    } else {
+        "http"
+    };
+    let host = match config.gateway.node_name.as_deref().map(str::trim) {
+        Some(node_name) if !node_name.is_empty() => node_name.to_string(),
+        _ => super::bind::resolve(config)
+            .ok()
+            .flatten()
+            .map_or_else(|| config.gateway.host.clone(), |bound| bound.host()),
+    };
+    format!("{scheme}://{host}:{}", config.gateway.port)
+}
+
+/// Issue a pairing code on `guard` and describe how to use it. `url` is the
+/// address clients should pair with.
+pub fn offer(config: &Config, guard: &PairingGuard, url: &str) -> Result<PairingOffer> {
+    let fingerprint = if config.gateway.tls.enabled {
+        Some(tls::fingerprint(config)?)
+    } else {
+        None
+    };
+    let expires_in_secs = config.gateway.pairing_code_ttl_secs.max(1);
+    let code = guard.issue_code(Duration::from_secs(expires_in_secs));
+    let url = url.trim().trim_end_matches('/').to_string();
+
+    let mut uri = format!(
+        "zeroclaw://pair?url={}&code={code}",
+        urlencoding::encode(&url)
+    );
+    if let Some(ref fingerprint) = fingerprint {
+        let _ = write!(uri, "&fingerprint={}", urlencoding::encode(fingerprint));
+    }
+
+    Ok(PairingOffer {
+        code,
+        expires_in_secs,
+        url,
+        fingerprint,
+        uri,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn offer_code_pairs_and_uri_carries_url_and_code() {
+        let mut config = Config::default();
+        config.gateway.pairing_code_ttl_secs = 60;
+        let guard = PairingGuard::new(true, &["zc_existing".into()]);
+
+        let offer = offer(&config, &guard, "http://192.168.1.20:42617/").unwrap();
+        assert_eq!(offer.url, "http://192.168.1.20:42617");
+        assert_eq!(offer.expires_in_secs, 60);
+        assert!(offer.fingerprint.is_none());
+        assert_eq!(
+            offer.uri,
+            format!(
+                "zeroclaw://pair?url=http%3A%2F%2F192.168.1.20%3A42617&code={}",
+                offer.code
+            )
+        );
+
+        let token = guard.try_pair(&offer.code, "qr").await.unwrap().unwrap();
+        assert!(guard.is_authenticated(&token));
+    }
+
+    #[test]
+    fn gateway_url_follows_tls() {
+        let mut config = Config::default();
+        assert_eq!(gateway_url(&config), "http://127.0.0.1:42617");
+        config.gateway.tls.enabled = true;
+        assert_eq!(gateway_url(&config), "https://127.0.0.1:42617");
+        config.gateway.node_name = Some("pixel.tail1234.ts.net".into());
+        assert_eq!(gateway_url(&config), "https://pixel.tail1234.ts.net:42617");
+    }
+}
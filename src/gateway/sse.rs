@@ -1,11 +1,14 @@
-//! Server-Sent Events (SSE) stream for real-time event delivery.
+//! Server-Sent Events (SSE) streams for real-time event delivery.
 //!
-//! Wraps the broadcast channel in AppState to deliver events to web dashboard clients.
+//! `/api/events` wraps the broadcast channel in AppState to deliver events to
+//! web dashboard clients; named users only get the events of their own
+//! sessions. `/v1/stream/{session}` relays one agent session's
+//! turn events from the agent event bus, for clients that cannot use `/ws`.
 
+use super::users::{self, GatewayUser};
 use super::AppState;
 use axum::{
-    extract::State,
-    http::{header, HeaderMap, StatusCode},
+    extract::{Extension, Path, State},
     response::{
         sse::{Event, KeepAlive, Sse},
         IntoResponse,
@@ -18,36 +21,20 @@ use tokio_stream::StreamExt;
 /// GET /api/events — SSE event stream
 pub async fn handle_sse_events(
     State(state): State<AppState>,
-    headers: HeaderMap,
+    user: Option<Extension<GatewayUser>>,
 ) -> impl IntoResponse {
-    // Auth check
-    if state.pairing.require_pairing() {
-        let token = headers
-            .get(header::AUTHORIZATION)
-            .and_then(|v| v.to_str().ok())
-            .and_then(|auth| auth.strip_prefix("Bearer "))
-            .unwrap_or("");
-
-        if !state.pairing.is_authenticated(token) {
-            return (
-                StatusCode::UNAUTHORIZED,
-                "Unauthorized — provide Authorization: Bearer <token>",
-            )
-                .into_response();
-        }
-    }
-
+    let user = user.map(|Extension(user)| user);
     let rx = state.event_tx.subscribe();
     let stream = BroadcastStream::new(rx).filter_map(
-        |result: Result<
+        move |result: Result<
             serde_json::Value,
             tokio_stream::wrappers::errors::BroadcastStreamRecvError,
         >| {
             match result {
-                Ok(value) => Some(Ok::<_, Infallible>(
+                Ok(value) if is_visible_to(user.as_ref(), &value) => Some(Ok::<_, Infallible>(
                     Event::default().data(value.to_string()),
                 )),
-                Err(_) => None, // Skip lagged messages
+                _ => None, // Other users' events and lagged messages
             }
         },
     );
@@ -57,6 +44,38 @@ pub async fn handle_sse_events(
         .into_response()
 }
 
+/// Whether `event` may be relayed to `user`: the owner gets every event, a
+/// named user only those tagged with one of its sessions.
+fn is_visible_to(user: Option<&GatewayUser>, event: &serde_json::Value) -> bool {
+    user.is_none()
+        || event["session_id"]
+            .as_str()
+            .is_some_and(|session| users::owns_session(user, session))
+}
+
+/// GET /v1/stream/{session} — SSE stream of a session's turn events
+///
+/// Each event's data is a frame as published on the agent event bus:
+/// `chunk`, `tool_call` and `tool_result` during a turn, then `done` or
+/// `error`. Turns are started with `POST /agent/message` and the same
+/// `session_id`; the token is checked by the gateway's auth middleware. A
+/// named user only sees its own sessions.
+pub async fn handle_session_stream(
+    user: Option<Extension<GatewayUser>>,
+    Path(session): Path<String>,
+) -> impl IntoResponse {
+    let session = users::session_key(user.as_deref(), &session);
+    let rx = crate::agent::events::subscribe();
+    let stream = BroadcastStream::new(rx).filter_map(move |result| match result {
+        Ok(event) if event.session_id == session => Some(Ok::<_, Infallible>(
+            Event::default().data(event.event.to_string()),
+        )),
+        _ => None, // Other sessions and lagged messages
+    });
+
+    Sse::new(stream).keep_alive(KeepAlive::default())
+}
+
 /// Broadcast observer that forwards events to the SSE broadcast channel.
 pub struct BroadcastObserver {
     inner: Box<dyn crate::observability::Observer>,
@@ -156,3 +175,26 @@ impl crate::observability::Observer for BroadcastObserver {
         self
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn named_users_only_see_events_of_their_sessions() {
+        let alice = GatewayUser {
+            name: "alice".into(),
+            rate_limit_per_minute: None,
+        };
+        let own = serde_json::json!({"type": "agent_start", "session_id": "user:alice/web-1"});
+        let other = serde_json::json!({"type": "agent_start", "session_id": "user:bob/web-1"});
+        let untagged = serde_json::json!({"type": "tool_call", "tool": "shell"});
+
+        assert!(is_visible_to(Some(&alice), &own));
+        assert!(!is_visible_to(Some(&alice), &other));
+        assert!(!is_visible_to(Some(&alice), &untagged));
+        for event in [&own, &other, &untagged] {
+            assert!(is_visible_to(None, event));
+        }
+    }
+}
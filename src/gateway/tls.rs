@@ -0,0 +1,506 @@
+//! HTTPS for the gateway (`[gateway.tls]`).
+//!
+//! Without a configured certificate, a self-signed ECDSA P-256 certificate is
+//! generated on first start and kept next to the config in `tls/`. It is
+//! reused afterwards, so companion apps on the LAN can pin its SHA-256
+//! fingerprint (printed at startup and returned by the Android bridge)
+//! instead of trusting a CA, and is only replaced when the gateway's host
+//! name changes.
+
+use crate::config::Config;
+use anyhow::{Context, Result};
+use axum::serve::Listener;
+use chrono::{DateTime, Utc};
+use rcgen::{string::Ia5String, SanType};
+use rustls::pki_types::pem::PemObject;
+use rustls::pki_types::{CertificateDer, PrivateKeyDer};
+use sha2::{Digest, Sha256};
+use std::io::Write;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::mpsc;
+use tokio_rustls::server::TlsStream;
+use tokio_rustls::TlsAcceptor;
+use x509_parser::extensions::GeneralName;
+
+const CERT_FILE: &str = "tls/gateway.crt";
+const KEY_FILE: &str = "tls/gateway.key";
+const COMMON_NAME: &str = "ZeroClaw Gateway";
+const VALID_DAYS: i64 = 3650;
+const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(10);
+/// Handshaken connections waiting for the server to pick them up.
+const ACCEPT_BACKLOG: usize = 64;
+
+/// TLS acceptor for the gateway and the fingerprint of its certificate.
+pub struct GatewayTls {
+    pub acceptor: TlsAcceptor,
+    /// The leaf certificate served.
+    pub certificate: CertificateDer<'static>,
+    /// SHA-256 of the leaf certificate, as colon-separated hex.
+    pub fingerprint: String,
+}
+
+/// Load the configured certificate, or the generated one. A generated
+/// certificate names [`certificate_host`]; it is created on first use and
+/// replaced when it was made for another host.
+pub fn load(config: &Config) -> Result<GatewayTls> {
+    let (certs, key) = certificate(config)?;
+    let leaf = certs[0].clone();
+    let fingerprint = fingerprint_of(&leaf);
+    let provider = Arc::new(rustls::crypto::ring::default_provider());
+    let mut server = rustls::ServerConfig::builder_with_provider(provider)
+        .with_safe_default_protocol_versions()
+        .context("Failed to set up gateway TLS")?
+        .with_no_client_auth()
+        .with_single_cert(certs, key)
+        .context("Invalid gateway TLS certificate or key")?;
+    server.alpn_protocols = vec![b"http/1.1".to_vec()];
+
+    Ok(GatewayTls {
+        acceptor: TlsAcceptor::from(Arc::new(server)),
+        certificate: leaf,
+        fingerprint,
+    })
+}
+
+/// The name a generated certificate is made for: the node name clients
+/// reach the gateway by, else the bound interface's address, else the
+/// configured host.
+pub fn certificate_host(config: &Config) -> Result<String> {
+    if let Some(node_name) = config
+        .gateway
+        .node_name
+        .as_deref()
+        .map(str::trim)
+        .filter(|node_name| !node_name.is_empty())
+    {
+        return Ok(node_name.to_string());
+    }
+    Ok(super::bind::resolve(config)?.map_or_else(
+        || config.gateway.host.clone(),
+        |bound| bound.addr.to_string(),
+    ))
+}
+
+pub fn pinned_client_config(certificate: CertificateDer<'static>) -> Result<rustls::ClientConfig> {
+    let provider = Arc::new(rustls::crypto::ring::default_provider());
+    let verifier = PinnedCertificate {
+        certificate,
+        algorithms: provider.signature_verification_algorithms,
+    };
+    let mut client = rustls::ClientConfig::builder_with_provider(provider)
+        .with_safe_default_protocol_versions()
+        .context("Failed to set up gateway TLS client")?
+        .dangerous()
+        .with_custom_certificate_verifier(Arc::new(verifier))
+        .with_no_client_auth();
+    client.alpn_protocols = vec![b"http/1.1".to_vec()];
+    Ok(client)
+}
+
+/// Accepts exactly one server certificate, still checking that the server
+/// holds its key.
+#[derive(Debug)]
+struct PinnedCertificate {
+    certificate: CertificateDer<'static>,
+    algorithms: rustls::crypto::WebPkiSupportedAlgorithms,
+}
+
+impl rustls::client::danger::ServerCertVerifier for PinnedCertificate {
+    fn verify_server_cert(
+        &self,
+        end_entity: &CertificateDer<'_>,
+        _intermediates: &[CertificateDer<'_>],
+        _server_name: &rustls::pki_types::ServerName<'_>,
+        _ocsp_response: &[u8],
+        _now: rustls::pki_types::UnixTime,
+    ) -> Result<rustls::client::danger::ServerCertVerified, rustls::Error> {
+        if end_entity.as_ref() == self.certificate.as_ref() {
+            Ok(rustls::client::danger::ServerCertVerified::assertion())
+        } else {
+            Err(rustls::Error::InvalidCertificate(
+                rustls::CertificateError::UnknownIssuer,
+            ))
+        }
+    }
+
+    fn verify_tls12_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &rustls::DigitallySignedStruct,
+    ) -> Result<rustls::client::danger::HandshakeSignatureValid, rustls::Error> {
+        rustls::crypto::verify_tls12_signature(message, cert, dss, &self.algorithms)
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &rustls::DigitallySignedStruct,
+    ) -> Result<rustls::client::danger::HandshakeSignatureValid, rustls::Error> {
+        rustls::crypto::verify_tls13_signature(message, cert, dss, &self.algorithms)
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<rustls::SignatureScheme> {
+        self.algorithms.supported_schemes()
+    }
+}
+
+/// Fingerprint of the certificate the gateway serves (or will serve).
+pub fn fingerprint(config: &Config) -> Result<String> {
+    let (certs, _) = certificate(config)?;
+    Ok(fingerprint_of(&certs[0]))
+}
+
+fn fingerprint_of(cert: &CertificateDer<'_>) -> String {
+    Sha256::digest(cert.as_ref())
+        .iter()
+        .map(|byte| format!("{byte:02X}"))
+        .collect::<Vec<_>>()
+        .join(":")
+}
+
+/// The certificate chain and key to serve. A generated certificate that does
+/// not name [`certificate_host`] is replaced.
+fn certificate(config: &Config) -> Result<(Vec<CertificateDer<'static>>, PrivateKeyDer<'static>)> {
+    let tls = &config.gateway.tls;
+    let (cert_path, key_path) = match (&tls.cert_path, &tls.key_path) {
+        (Some(cert), Some(key)) => (expand(cert), expand(key)),
+        _ => {
+            let dir = config
+                .config_path
+                .parent()
+                .context("Config path must have a parent directory")?;
+            let paths = (dir.join(CERT_FILE), dir.join(KEY_FILE));
+            let host = certificate_host(config)?;
+            if !paths.0.exists() || !paths.1.exists() {
+                generate(&paths.0, &paths.1, &host)?;
+            } else if !names_host(&read_certs(&paths.0)?[0], &host) {
+                tracing::warn!(
+                    host,
+                    "Gateway TLS certificate was generated for another host; \
+                     replacing it, so pinned fingerprints must be updated"
+                );
+                generate(&paths.0, &paths.1, &host)?;
+            }
+            paths
+        }
+    };
+
+    let certs = read_certs(&cert_path)?;
+    let pem = std::fs::read(&key_path)
+        .with_context(|| format!("Failed to read {}", key_path.display()))?;
+    let key = PrivateKeyDer::from_pem_slice(&pem)
+        .with_context(|| format!("Invalid private key in {}", key_path.display()))?;
+    Ok((certs, key))
+}
+
+fn read_certs(path: &Path) -> Result<Vec<CertificateDer<'static>>> {
+    let pem = std::fs::read(path).with_context(|| format!("Failed to read {}", path.display()))?;
+    let certs = CertificateDer::pem_slice_iter(&pem)
+        .collect::<Result<Vec<_>, _>>()
+        .with_context(|| format!("Invalid certificate in {}", path.display()))?;
+    if certs.is_empty() {
+        anyhow::bail!("No certificate found in {}", path.display());
+    }
+    Ok(certs)
+}
+
+/// Whether `cert` carries exactly the subject names [`generate`] gives `host`.
+fn names_host(cert: &CertificateDer<'_>, host: &str) -> bool {
+    let Ok((_, cert)) = x509_parser::parse_x509_certificate(cert.as_ref()) else {
+        return false;
+    };
+    let Ok(Some(extension)) = cert.subject_alternative_name() else {
+        return false;
+    };
+    let names = extension
+        .value
+        .general_names
+        .iter()
+        .map(|name| match name {
+            GeneralName::DNSName(name) => Ia5String::try_from(*name).ok().map(SanType::DnsName),
+            GeneralName::IPAddress(octets) => <[u8; 4]>::try_from(*octets)
+                .map(IpAddr::from)
+                .or_else(|_| <[u8; 16]>::try_from(*octets).map(IpAddr::from))
+                .ok()
+                .map(SanType::IpAddress),
+            _ => None,
+        })
+        .collect::<Option<Vec<_>>>();
+    names.is_some() && names == subject_alt_names(host).ok()
+}
+
+fn expand(path: &str) -> PathBuf {
+    PathBuf::from(shellexpand::tilde(path).as_ref())
+}
+
+fn generate(cert_path: &Path, key_path: &Path, host: &str) -> Result<()> {
+    let key_pair = rcgen::KeyPair::generate_for(&rcgen::PKCS_ECDSA_P256_SHA256)
+        .context("Failed to generate gateway TLS key")?;
+    let cert = self_signed(&key_pair, host, Utc::now())?;
+
+    if let Some(dir) = cert_path.parent() {
+        std::fs::create_dir_all(dir)
+            .with_context(|| format!("Failed to create {}", dir.display()))?;
+    }
+    // Remove a replaced key, so the new one is created owner-only.
+    match std::fs::remove_file(key_path) {
+        Err(e) if e.kind() != std::io::ErrorKind::NotFound => {
+            return Err(e).with_context(|| format!("Failed to replace {}", key_path.display()));
+        }
+        _ => {}
+    }
+    let mut options = std::fs::OpenOptions::new();
+    options.create_new(true).write(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    options
+        .open(key_path)
+        .and_then(|mut file| file.write_all(key_pair.serialize_pem().as_bytes()))
+        .with_context(|| format!("Failed to write {}", key_path.display()))?;
+    std::fs::write(cert_path, cert.pem())
+        .with_context(|| format!("Failed to write {}", cert_path.display()))?;
+    tracing::info!(
+        cert = %cert_path.display(),
+        "Generated self-signed gateway TLS certificate"
+    );
+    Ok(())
+}
+
+/// Certificate for `key_pair`, signed by itself, valid for `localhost`, the
+/// loopback addresses and `host`.
+fn self_signed(
+    key_pair: &rcgen::KeyPair,
+    host: &str,
+    now: DateTime<Utc>,
+) -> Result<rcgen::Certificate> {
+    let mut params = rcgen::CertificateParams::default();
+    params.distinguished_name = rcgen::DistinguishedName::new();
+    params
+        .distinguished_name
+        .push(rcgen::DnType::CommonName, COMMON_NAME);
+    params.subject_alt_names = subject_alt_names(host)?;
+    params.not_before = validity(now - chrono::Duration::days(1))?;
+    params.not_after = validity(now + chrono::Duration::days(VALID_DAYS))?;
+    params
+        .self_signed(key_pair)
+        .context("Failed to sign gateway TLS certificate")
+}
+
+fn validity(at: DateTime<Utc>) -> Result<time::OffsetDateTime> {
+    time::OffsetDateTime::from_unix_timestamp(at.timestamp())
+        .context("Gateway TLS certificate validity out of range")
+}
+
+fn subject_alt_names(host: &str) -> Result<Vec<SanType>> {
+    let mut names = vec![
+        SanType::DnsName(Ia5String::try_from("localhost")?),
+        SanType::IpAddress(Ipv4Addr::LOCALHOST.into()),
+        SanType::IpAddress(Ipv6Addr::LOCALHOST.into()),
+    ];
+    let host = host.trim();
+    match host.parse::<IpAddr>() {
+        Ok(ip) if ip.is_loopback() || ip.is_unspecified() => {}
+        Ok(ip) => names.push(SanType::IpAddress(ip)),
+        Err(_) if !host.is_empty() && host != "localhost" => {
+            let name = Ia5String::try_from(host)
+                .with_context(|| format!("Gateway host {host:?} is not a valid DNS name"))?;
+            names.push(SanType::DnsName(name));
+        }
+        Err(_) => {}
+    }
+    Ok(names)
+}
+
+/// [`Listener`] that hands the server TLS connections. Handshakes run in
+/// their own tasks, so a slow client cannot hold up the others.
+pub struct TlsListener {
+    incoming: mpsc::Receiver<(TlsStream<TcpStream>, SocketAddr)>,
+    local_addr: SocketAddr,
+}
+
+impl TlsListener {
+    pub fn new(mut listener: TcpListener, acceptor: TlsAcceptor) -> std::io::Result<Self> {
+        let local_addr = listener.local_addr()?;
+        let (tx, incoming) = mpsc::channel(ACCEPT_BACKLOG);
+        tokio::spawn(async move {
+            loop {
+                let (stream, peer) = Listener::accept(&mut listener).await;
+                let acceptor = acceptor.clone();
+                let sender = tx.clone();
+                tokio::spawn(async move {
+                    match tokio::time::timeout(HANDSHAKE_TIMEOUT, acceptor.accept(stream)).await {
+                        Ok(Ok(tls)) => {
+                            let _ = sender.send((tls, peer)).await;
+                        }
+                        Ok(Err(e)) => tracing::debug!(%peer, "Gateway TLS handshake failed: {e}"),
+                        Err(_) => tracing::debug!(%peer, "Gateway TLS handshake timed out"),
+                    }
+                });
+                if tx.is_closed() {
+                    break;
+                }
+            }
+        });
+        Ok(Self {
+            incoming,
+            local_addr,
+        })
+    }
+}
+
+impl Listener for TlsListener {
+    type Io = TlsStream<TcpStream>;
+    type Addr = SocketAddr;
+
+    async fn accept(&mut self) -> (Self::Io, Self::Addr) {
+        match self.incoming.recv().await {
+            Some(connection) => connection,
+            // The accept task only stops once this listener is dropped.
+            None => std::future::pending().await,
+        }
+    }
+
+    fn local_addr(&self) -> std::io::Result<Self::Addr> {
+        Ok(self.local_addr)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    fn config(dir: &Path) -> Config {
+        let mut config = Config {
+            config_path: dir.join("config.toml"),
+            workspace_dir: dir.join("workspace"),
+            ..Config::default()
+        };
+        config.gateway.tls.enabled = true;
+        config
+    }
+
+    #[test]
+    fn generated_certificate_is_reused() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let config = config(tmp.path());
+        let first = fingerprint(&config).unwrap();
+        assert_eq!(first.len(), 32 * 3 - 1);
+        assert_eq!(fingerprint(&config).unwrap(), first);
+        assert_eq!(load(&config).unwrap().fingerprint, first);
+        assert!(tmp.path().join(CERT_FILE).exists());
+    }
+
+    fn verify(cert: &CertificateDer<'static>, name: &str) -> bool {
+        use rustls::client::danger::ServerCertVerifier;
+
+        let mut roots = rustls::RootCertStore::empty();
+        roots.add(cert.clone()).unwrap();
+        let provider = Arc::new(rustls::crypto::ring::default_provider());
+        let verifier =
+            rustls::client::WebPkiServerVerifier::builder_with_provider(Arc::new(roots), provider)
+                .build()
+                .unwrap();
+        let name = rustls::pki_types::ServerName::try_from(name.to_string()).unwrap();
+        verifier
+            .verify_server_cert(cert, &[], &name, &[], rustls::pki_types::UnixTime::now())
+            .is_ok()
+    }
+
+    #[test]
+    fn self_signed_certificate_names_loopback_and_host() {
+        let key_pair = rcgen::KeyPair::generate_for(&rcgen::PKCS_ECDSA_P256_SHA256).unwrap();
+
+        let cert = self_signed(&key_pair, "gateway.lan", Utc::now())
+            .unwrap()
+            .der()
+            .clone();
+        for name in ["gateway.lan", "localhost", "127.0.0.1", "::1"] {
+            assert!(verify(&cert, name), "{name}");
+        }
+        assert!(!verify(&cert, "other.lan"));
+        assert!(names_host(&cert, "gateway.lan"));
+        assert!(!names_host(&cert, "other.lan"));
+        assert!(!names_host(&cert, "127.0.0.1"));
+
+        let cert = self_signed(&key_pair, "192.168.1.20", Utc::now())
+            .unwrap()
+            .der()
+            .clone();
+        assert!(verify(&cert, "192.168.1.20"));
+        assert!(!verify(&cert, "192.168.1.21"));
+        assert!(names_host(&cert, "192.168.1.20"));
+        assert!(!names_host(&cert, "192.168.1.21"));
+    }
+
+    #[test]
+    fn generated_certificate_is_replaced_for_another_host() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let mut config = config(tmp.path());
+        config.gateway.host = "gateway.lan".into();
+        let first = load(&config).unwrap().fingerprint;
+        assert_eq!(load(&config).unwrap().fingerprint, first);
+        assert_eq!(fingerprint(&config).unwrap(), first);
+
+        // The node name wins over the host, for the fingerprint too.
+        config.gateway.node_name = Some("10.0.0.5".into());
+        let second = fingerprint(&config).unwrap();
+        assert_ne!(second, first);
+        assert_eq!(load(&config).unwrap().fingerprint, second);
+        let (certs, _) = certificate(&config).unwrap();
+        assert!(verify(&certs[0], "10.0.0.5"));
+        assert!(!verify(&certs[0], "gateway.lan"));
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = std::fs::metadata(tmp.path().join(KEY_FILE))
+                .unwrap()
+                .permissions()
+                .mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+    }
+
+    #[tokio::test]
+    async fn clients_trusting_the_certificate_can_connect() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let config = config(tmp.path());
+        let tls = load(&config).unwrap();
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let mut listener = TlsListener::new(listener, tls.acceptor).unwrap();
+        let addr = Listener::local_addr(&listener).unwrap();
+        tokio::spawn(async move {
+            let (mut stream, _) = Listener::accept(&mut listener).await;
+            stream.write_all(b"pong").await.unwrap();
+            stream.shutdown().await.unwrap();
+        });
+
+        let (certs, _) = certificate(&config).unwrap();
+        let mut roots = rustls::RootCertStore::empty();
+        roots.add(certs[0].clone()).unwrap();
+        let provider = Arc::new(rustls::crypto::ring::default_provider());
+        let client = rustls::ClientConfig::builder_with_provider(provider)
+            .with_safe_default_protocol_versions()
+            .unwrap()
+            .with_root_certificates(roots)
+            .with_no_client_auth();
+        let connector = tokio_rustls::TlsConnector::from(Arc::new(client));
+        let tcp = TcpStream::connect(addr).await.unwrap();
+        let name = rustls::pki_types::ServerName::try_from("localhost").unwrap();
+        let mut stream = connector.connect(name, tcp).await.unwrap();
+        let mut reply = String::new();
+        stream.read_to_string(&mut reply).await.unwrap();
+        assert_eq!(reply, "pong");
+        assert_eq!(fingerprint_of(&certs[0]), tls.fingerprint);
+    }
+}
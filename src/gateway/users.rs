@@ -0,0 +1,189 @@
+//! Named users sharing one gateway.
+//!
+//! `[gateway.users.<name>]` assigns paired tokens, by id, to a user. Requests
+//! made with those tokens carry a [`GatewayUser`] (set by the auth
+//! middleware) and are isolated from everyone else's:
+//!
+//! - agent sessions are keyed per user, so a user cannot continue, stream or
+//!   collide with another user's conversation even when reusing its id;
+//! - memory goes through a namespace of the user's own, both for the agent,
+//!   `/webhook` and the memory endpoints;
+//! - `/api/events` only relays events of the user's own sessions, and `/ws`
+//!   does not push the owner's notifications (cron, heartbeat, hooks);
+//! - requests count against one quota shared by all of the user's tokens.
+//!
+//! Users cannot reach the owner's management routes (config, pairing,
+//! profiles, Telegram pairing and `/admin/*`), through which they could
+//! reassign tokens or channels, nor the cron jobs, rules and device events
+//! that trigger them, which are not kept per user. The API token and
+//! paired tokens not assigned to a user act as the owner and keep the shared
+//! sessions and memory.
+
+use crate::config::GatewayConfig;
+use crate::memory::{Memory, ScopedMemory};
+use std::sync::Arc;
+
+/// The user a request was made as.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GatewayUser {
+    pub name: String,
+    /// Per-minute quota overriding `token_rate_limit_per_minute`.
+    pub rate_limit_per_minute: Option<u32>,
+}
+
+impl GatewayUser {
+    /// Namespace of the user's memories.
+    pub fn memory_namespace(&self) -> String {
+        format!("user:{}", self.name)
+    }
+}
+
+/// Route prefixes only the owner may use.
+const OWNER_ONLY_PREFIXES: &[&str] = &[
+    "/admin/",
+    "/api/config",
+    "/api/pairing/",
+    "/api/profiles",
+    "/agent/telegram/pair",
+    "/agent/event",
+    "/api/cron",
+    "/cron/",
+    "/rules",
+];
+
+/// Whether `path` is a management route closed to named users.
+pub fn is_owner_only(path: &str) -> bool {
+    OWNER_ONLY_PREFIXES
+        .iter()
+        .any(|prefix| path.starts_with(prefix))
+}
+
+/// The user the paired token with id `token_id` is assigned to, if any.
+pub fn resolve(config: &GatewayConfig, token_id: &str) -> Option<GatewayUser> {
+    config
+        .users
+        .iter()
+        .find(|(_, user)| {
+            user.tokens
+                .iter()
+                .any(|id| id.trim().eq_ignore_ascii_case(token_id))
+        })
+        .map(|(name, user)| GatewayUser {
+            name: name.clone(),
+            rate_limit_per_minute: user.rate_limit_per_minute,
+        })
+}
+
+/// Key of `session_id` in the session store and on the agent event bus.
+pub fn session_key(user: Option<&GatewayUser>, session_id: &str) -> String {
+    match user {
+        Some(user) => format!("user:{}/{session_id}", user.name),
+        None => session_id.to_string(),
+    }
+}
+
+/// Whether the session keyed `session_key` belongs to `user`. The owner
+/// sees every session.
+pub fn owns_session(user: Option<&GatewayUser>, session_key: &str) -> bool {
+    match user {
+        Some(user) => session_key
+            .strip_prefix("user:")
+            .and_then(|rest| rest.strip_prefix(user.name.as_str()))
+            .is_some_and(|rest| rest.starts_with('/')),
+        None => true,
+    }
+}
+
+/// `mem` as seen by `user`: its own namespace, or everything for the owner.
+pub fn memory_for(mem: &Arc<dyn Memory>, user: Option<&GatewayUser>) -> Arc<dyn Memory> {
+    match user {
+        Some(user) => Arc::new(ScopedMemory::new(Arc::clone(mem), &user.memory_namespace())),
+        None => Arc::clone(mem),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::GatewayUserConfig;
+    use std::collections::HashMap;
+
+    fn config() -> GatewayConfig {
+        GatewayConfig {
+            users: HashMap::from([
+                (
+                    "alice".to_string(),
+                    GatewayUserConfig {
+                        tokens: vec!["aaaaaaaaaaaa".into(), "AAAAAAAAAAAB".into()],
+                        rate_limit_per_minute: Some(10),
+                    },
+                ),
+                (
+                    "bob".to_string(),
+                    GatewayUserConfig {
+                        tokens: vec!["bbbbbbbbbbbb".into()],
+                        rate_limit_per_minute: None,
+                    },
+                ),
+            ]),
+            ..GatewayConfig::default()
+        }
+    }
+
+    #[test]
+    fn tokens_resolve_to_their_user() {
+        let config = config();
+        let alice = resolve(&config, "aaaaaaaaaaab").unwrap();
+        assert_eq!(alice.name, "alice");
+        assert_eq!(alice.rate_limit_per_minute, Some(10));
+        assert_eq!(resolve(&config, "bbbbbbbbbbbb").unwrap().name, "bob");
+        assert!(resolve(&config, "cccccccccccc").is_none());
+    }
+
+    #[test]
+    fn management_routes_are_owner_only() {
+        for path in [
+            "/admin/diagnostics",
+            "/api/config",
+            "/api/pairing/tokens",
+            "/agent/telegram/pair",
+            "/agent/event",
+            "/api/cron",
+            "/api/cron/job-1",
+            "/cron/jobs",
+            "/rules",
+            "/rules/rule-1/toggle",
+        ] {
+            assert!(is_owner_only(path), "{path}");
+        }
+        for path in [
+            "/ws",
+            "/webhook",
+            "/api/memory",
+            "/agent/message",
+            "/agent/stream",
+            "/api/status",
+        ] {
+            assert!(!is_owner_only(path), "{path}");
+        }
+    }
+
+    #[test]
+    fn sessions_are_keyed_per_user() {
+        let config = config();
+        let alice = resolve(&config, "aaaaaaaaaaaa").unwrap();
+        let bob = resolve(&config, "bbbbbbbbbbbb").unwrap();
+        assert_eq!(session_key(None, "web-1"), "web-1");
+        assert_eq!(session_key(Some(&alice), "web-1"), "user:alice/web-1");
+        assert_ne!(
+            session_key(Some(&alice), "web-1"),
+            session_key(Some(&bob), "web-1")
+        );
+
+        let alice_key = session_key(Some(&alice), "web-1");
+        assert!(owns_session(Some(&alice), &alice_key));
+        assert!(!owns_session(Some(&bob), &alice_key));
+        assert!(!owns_session(Some(&alice), "web-1"));
+        assert!(owns_session(None, &alice_key));
+    }
+}
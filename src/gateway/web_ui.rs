@@ -0,0 +1,63 @@
+//! Minimal chat and status UI, compiled in with the `web-ui` feature.
+//!
+//! Bundles the hand-written `web/ui/` assets, which need no Node build, and
+//! serves them at `/` and `/ui/*` so any browser on the LAN can pair and talk
+//! to the agent. The page pairs through `/pair` and uses the same token-gated
+//! `/api/status` and `/ws` endpoints as other clients.
+
+use axum::{
+    extract::Path,
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
+};
+use rust_embed::Embed;
+
+#[derive(Embed)]
+#[folder = "web/ui/"]
+struct UiAssets;
+
+/// GET / — the chat and status page
+pub async fn handle_index() -> Response {
+    serve_asset("index.html")
+}
+
+/// GET /ui/{*path} — the page's scripts and styles
+pub async fn handle_asset(Path(path): Path<String>) -> Response {
+    serve_asset(&path)
+}
+
+fn serve_asset(path: &str) -> Response {
+    let Some(content) = UiAssets::get(path) else {
+        return (StatusCode::NOT_FOUND, "Not found").into_response();
+    };
+    let mime = mime_guess::from_path(path).first_or_octet_stream();
+    (
+        StatusCode::OK,
+        [
+            (header::CONTENT_TYPE, mime.to_string()),
+            // Unhashed file names, so revalidate after upgrades.
+            (header::CACHE_CONTROL, "no-cache".to_string()),
+        ],
+        content.data.into_owned(),
+    )
+        .into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn index_and_assets_are_embedded() {
+        let index = handle_index().await;
+        assert_eq!(index.status(), StatusCode::OK);
+        assert_eq!(index.headers()[header::CONTENT_TYPE], "text/html");
+
+        for asset in ["app.js", "style.css"] {
+            let response = handle_asset(Path(asset.to_string())).await;
+            assert_eq!(response.status(), StatusCode::OK, "{asset}");
+        }
+        let missing = handle_asset(Path("missing.js".into())).await;
+        assert_eq!(missing.status(), StatusCode::NOT_FOUND);
+    }
+}
@@ -1,4 +1,10 @@
-//! WebSocket agent chat handler.
+//! WebSocket chat handlers.
+//!
+//! `/ws/chat` streams single-turn replies from the provider. `/ws` runs full
+//! agent turns against a gateway session, with tool activity, and also pushes
+//! proactive notifications (cron and heartbeat output delivered to the
+//! `gateway` channel) to every connected client. A turn in progress does not
+//! hold back notifications.
 //!
 //! Protocol:
 //! ```text
@@ -7,18 +13,77 @@
 //! Server -> Client: {"type":"tool_call","name":"shell","args":{...}}
 //! Server -> Client: {"type":"tool_result","name":"shell","output":"..."}
 //! Server -> Client: {"type":"done","full_response":"..."}
+//! Server -> Client: {"type":"notification","topic":"...","content":"...","timestamp":"..."}
+//! Client -> Server: {"type":"ping"}
+//! Server -> Client: {"type":"pong"}
 //! ```
+//!
+//! On `/ws` a message may carry `"session_id"` to pick the agent session;
+//! otherwise the `?session=` query parameter is used, and without one each
+//! connection gets its own session. Session ids are per user: a named
+//! `[gateway.users]` user only ever reaches its own sessions.
 
+use super::users::{self, GatewayUser};
 use super::AppState;
+use crate::providers::traits::StreamOptions;
 use axum::{
     extract::{
         ws::{Message, WebSocket},
-        Query, State, WebSocketUpgrade,
+        Extension, Query, State, WebSocketUpgrade,
     },
     response::IntoResponse,
 };
 use futures_util::{SinkExt, StreamExt};
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
+use std::sync::OnceLock;
+use tokio::sync::{broadcast, mpsc};
+
+/// Buffered notifications per client before the oldest are dropped.
+const NOTIFICATION_CAPACITY: usize = 64;
+
+static NOTIFICATIONS: OnceLock<broadcast::Sender<Notification>> = OnceLock::new();
+
+/// A proactive message pushed to `/ws` clients.
+#[derive(Debug, Clone, Serialize)]
+pub struct Notification {
+    pub topic: String,
+    pub content: String,
+    pub timestamp: String,
+    /// Named user the notification is for; `None` for the owner's.
+    #[serde(skip)]
+    pub user: Option<String>,
+}
+
+impl Notification {
+    /// Whether `user`'s sockets may receive this: the owner's only get the
+    /// owner's notifications, a named user's only its own.
+    fn is_visible_to(&self, user: Option<&GatewayUser>) -> bool {
+        self.user.as_deref() == user.map(|user| user.name.as_str())
+    }
+}
+
+fn notifications() -> &'static broadcast::Sender<Notification> {
+    NOTIFICATIONS.get_or_init(|| broadcast::channel(NOTIFICATION_CAPACITY).0)
+}
+
+/// Push an owner notification (cron, heartbeat, hooks) to the owner's `/ws`
+/// clients; named users' sockets do not receive it. Returns how many sockets
+/// it was offered to; with none connected the notification is dropped.
+pub fn notify(topic: &str, content: &str) -> usize {
+    notifications()
+        .send(Notification {
+            topic: topic.to_string(),
+            content: content.to_string(),
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            user: None,
+        })
+        .unwrap_or(0)
+}
+
+/// Receive notifications sent with [`notify`] from now on.
+pub fn subscribe() -> broadcast::Receiver<Notification> {
+    notifications().subscribe()
+}
 
 #[derive(Deserialize)]
 pub struct WsQuery {
@@ -32,7 +97,7 @@ pub async fn handle_ws_chat(
     ws: WebSocketUpgrade,
 ) -> impl IntoResponse {
     // Auth via query param (browser WebSocket limitation)
-    if state.pairing.require_pairing() {
+    if state.pairing.requires_auth() {
         let token = params.token.as_deref().unwrap_or("");
         if !state.pairing.is_authenticated(token) {
             return (
@@ -93,7 +158,7 @@ async fn handle_socket(socket: WebSocket, state: AppState) {
             "model": state.model,
         }));
 
-        // Simple single-turn chat (no streaming for now — use provider.chat_with_system)
+        // Single-turn chat, streamed to the client as chunk frames
         let system_prompt = {
             let config_guard = state.config.lock();
             crate::channels::build_system_prompt(
@@ -127,13 +192,38 @@ async fn handle_socket(socket: WebSocket, state: AppState) {
                 }
             };
 
-        match state
-            .provider
-            .chat_with_history(&prepared.messages, &state.model, state.temperature)
-            .await
-        {
+        let result = {
+            let mut stream = state.provider.chat_stream(
+                &prepared.messages,
+                &state.model,
+                state.temperature,
+                StreamOptions::new(true),
+            );
+            let mut full_response = String::new();
+            let mut failure = None;
+            while let Some(chunk) = stream.next().await {
+                match chunk {
+                    Ok(chunk) if !chunk.delta.is_empty() => {
+                        full_response.push_str(&chunk.delta);
+                        let frame = serde_json::json!({
+                            "type": "chunk",
+                            "content": chunk.delta,
+                        });
+                        let _ = sender.send(Message::Text(frame.to_string().into())).await;
+                    }
+                    Ok(_) => {}
+                    Err(e) => {
+                        failure = Some(e);
+                        break;
+                    }
+                }
+            }
+            failure.map_or(Ok(full_response), Err)
+        };
+
+        match result {
             Ok(response) => {
-                // Send the full response as a done message
+                // Close the turn with the assembled response
                 let done = serde_json::json!({
                     "type": "done",
                     "full_response": response,
@@ -165,3 +255,259 @@ async fn handle_socket(socket: WebSocket, state: AppState) {
         }
     }
 }
+
+#[derive(Deserialize)]
+pub struct WsSessionQuery {
+    pub session: Option<String>,
+}
+
+/// GET /ws — WebSocket upgrade for agent turns and notifications. The token
+/// is checked by the gateway's auth middleware (`?token=` for browsers).
+pub async fn handle_ws(
+    State(state): State<AppState>,
+    user: Option<Extension<GatewayUser>>,
+    Query(params): Query<WsSessionQuery>,
+    ws: WebSocketUpgrade,
+) -> impl IntoResponse {
+    let session_id = params
+        .session
+        .as_deref()
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map_or_else(|| format!("ws-{}", uuid::Uuid::new_v4()), str::to_string);
+
+    let user = user.map(|Extension(user)| user);
+
+    ws.on_upgrade(move |socket| handle_agent_socket(socket, state, user, session_id))
+        .into_response()
+}
+
+fn send_frame(out: &mpsc::UnboundedSender<Message>, frame: &serde_json::Value) -> bool {
+    out.send(Message::Text(frame.to_string().into())).is_ok()
+}
+
+/// Relay the notifications visible to `user` to its socket until the socket
+/// or the broadcast closes.
+async fn forward_notifications(
+    mut notifications: broadcast::Receiver<Notification>,
+    user: Option<GatewayUser>,
+    out: mpsc::UnboundedSender<Message>,
+) {
+    loop {
+        match notifications.recv().await {
+            Ok(notification) if notification.is_visible_to(user.as_ref()) => {
+                let frame = serde_json::json!({
+                    "type": "notification",
+                    "topic": notification.topic,
+                    "content": notification.content,
+                    "timestamp": notification.timestamp,
+                });
+                if !send_frame(&out, &frame) {
+                    break;
+                }
+            }
+            Ok(_) => {}
+            Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                tracing::warn!(skipped, "WebSocket client fell behind on notifications");
+            }
+            Err(broadcast::error::RecvError::Closed) => break,
+        }
+    }
+}
+
+async fn handle_agent_socket(
+    socket: WebSocket,
+    state: AppState,
+    user: Option<GatewayUser>,
+    session_id: String,
+) {
+    let (mut sender, mut receiver) = socket.split();
+
+    // A single writer lets turn output and notifications interleave.
+    let (out_tx, mut out_rx) = mpsc::unbounded_channel::<Message>();
+    let writer = tokio::spawn(async move {
+        while let Some(msg) = out_rx.recv().await {
+            if sender.send(msg).await.is_err() {
+                break;
+            }
+        }
+    });
+
+    let forwarder = tokio::spawn(forward_notifications(
+        subscribe(),
+        user.clone(),
+        out_tx.clone(),
+    ));
+
+    while let Some(msg) = receiver.next().await {
+        let msg = match msg {
+            Ok(Message::Text(text)) => text,
+            Ok(Message::Close(_)) | Err(_) => break,
+            _ => continue,
+        };
+
+        let parsed: serde_json::Value = match serde_json::from_str(&msg) {
+            Ok(v) => v,
+            Err(_) => {
+                send_frame(
+                    &out_tx,
+                    &serde_json::json!({"type": "error", "message": "Invalid JSON"}),
+                );
+                continue;
+            }
+        };
+
+        match parsed["type"].as_str().unwrap_or("") {
+            "ping" => {
+                send_frame(&out_tx, &serde_json::json!({"type": "pong"}));
+            }
+            "message" => {
+                let content = parsed["content"].as_str().unwrap_or("").trim();
+                if content.is_empty() {
+                    continue;
+                }
+                let session = parsed["session_id"]
+                    .as_str()
+                    .map(str::trim)
+                    .filter(|s| !s.is_empty())
+                    .unwrap_or(&session_id);
+                run_agent_turn(&state, user.as_ref(), session, content, &out_tx).await;
+            }
+            _ => {}
+        }
+    }
+
+    forwarder.abort();
+    drop(out_tx);
+    let _ = writer.await;
+}
+
+async fn run_agent_turn(
+    state: &AppState,
+    user: Option<&GatewayUser>,
+    session_id: &str,
+    content: &str,
+    out: &mpsc::UnboundedSender<Message>,
+) {
+    let config = state.config.lock().clone();
+    let provider_label = config
+        .default_provider
+        .clone()
+        .unwrap_or_else(|| "unknown".to_string());
+    // Tags the `/api/events` broadcasts so they only reach the session's user.
+    let session_key = users::session_key(user, session_id);
+
+    let _ = state.event_tx.send(serde_json::json!({
+        "type": "agent_start",
+        "provider": provider_label,
+        "model": state.model,
+        "session_id": session_key,
+    }));
+
+    let (events_tx, mut events_rx) = mpsc::unbounded_channel();
+    let turn = super::stream_agent_turn(
+        state,
+        &config,
+        content,
+        Some(session_id),
+        user,
+        Some(events_tx),
+    );
+    let forward = async {
+        while let Some(event) = events_rx.recv().await {
+            if let Ok(frame) = serde_json::to_value(&event) {
+                send_frame(out, &frame);
+            }
+        }
+    };
+    let (result, ()) = tokio::join!(turn, forward);
+
+    match result {
+        Ok(response) => {
+            send_frame(
+                out,
+                &serde_json::json!({
+                    "type": "done",
+                    "full_response": response,
+                }),
+            );
+
+            let _ = state.event_tx.send(serde_json::json!({
+                "type": "agent_end",
+                "provider": provider_label,
+                "model": state.model,
+                "session_id": session_key,
+            }));
+        }
+        Err(e) => {
+            let sanitized = crate::providers::sanitize_api_error(&e.to_string());
+            send_frame(
+                out,
+                &serde_json::json!({
+                    "type": "error",
+                    "message": sanitized,
+                }),
+            );
+
+            let _ = state.event_tx.send(serde_json::json!({
+                "type": "error",
+                "component": "ws",
+                "message": sanitized,
+                "session_id": session_key,
+            }));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn notify_reaches_subscribers() {
+        let mut rx = subscribe();
+        assert!(notify("ws-test", "battery low") >= 1);
+
+        loop {
+            let notification = rx.recv().await.unwrap();
+            if notification.topic == "ws-test" {
+                assert_eq!(notification.content, "battery low");
+                assert!(!notification.timestamp.is_empty());
+                break;
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn named_users_do_not_receive_owner_notifications() {
+        let notification = |topic: &str, user: Option<&str>| Notification {
+            topic: topic.into(),
+            content: "cron output".into(),
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            user: user.map(str::to_string),
+        };
+        let alice = GatewayUser {
+            name: "alice".into(),
+            rate_limit_per_minute: None,
+        };
+        let topics = |user: Option<GatewayUser>| async move {
+            let (tx, rx) = broadcast::channel(8);
+            let (out, mut frames) = mpsc::unbounded_channel();
+            tx.send(notification("owner", None)).unwrap();
+            tx.send(notification("alice", Some("alice"))).unwrap();
+            tx.send(notification("bob", Some("bob"))).unwrap();
+            drop(tx);
+            forward_notifications(rx, user, out).await;
+
+            let mut topics = Vec::new();
+            while let Ok(Message::Text(text)) = frames.try_recv() {
+                let frame: serde_json::Value = serde_json::from_str(&text).unwrap();
+                topics.push(frame["topic"].as_str().unwrap().to_string());
+            }
+            topics
+        };
+
+        assert_eq!(topics(None).await, vec!["owner"]);
+        assert_eq!(topics(Some(alice)).await, vec!["alice"]);
+    }
+}
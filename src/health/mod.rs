@@ -3,7 +3,7 @@ use parking_lot::Mutex;
 use serde::Serialize;
 use std::collections::BTreeMap;
 use std::sync::OnceLock;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 
 #[derive(Debug, Clone, Serialize)]
 pub struct ComponentHealth {
@@ -14,17 +14,31 @@ pub struct ComponentHealth {
     pub restart_count: u64,
 }
 
+/// Latest background probe of an LLM provider endpoint.
+#[derive(Debug, Clone, Serialize)]
+pub struct ProviderHealth {
+    /// `ok`, `unreachable` (network-level failure) or `error`
+    pub status: String,
+    pub latency_ms: Option<u64>,
+    pub checked_at: String,
+    pub last_ok: Option<String>,
+    pub last_error: Option<String>,
+    pub consecutive_failures: u32,
+}
+
 #[derive(Debug, Clone, Serialize)]
 pub struct HealthSnapshot {
     pub pid: u32,
     pub updated_at: String,
     pub uptime_seconds: u64,
     pub components: BTreeMap<String, ComponentHealth>,
+    pub providers: BTreeMap<String, ProviderHealth>,
 }
 
 struct HealthRegistry {
     started_at: Instant,
     components: Mutex<BTreeMap<String, ComponentHealth>>,
+    providers: Mutex<BTreeMap<String, ProviderHealth>>,
 }
 
 static REGISTRY: OnceLock<HealthRegistry> = OnceLock::new();
@@ -33,6 +47,7 @@ fn registry() -> &'static HealthRegistry {
     REGISTRY.get_or_init(|| HealthRegistry {
         started_at: Instant::now(),
         components: Mutex::new(BTreeMap::new()),
+        providers: Mutex::new(BTreeMap::new()),
     })
 }
 
@@ -82,14 +97,72 @@ pub fn bump_component_restart(component: &str) {
     });
 }
 
+fn upsert_provider<F>(provider: &str, update: F)
+where
+    F: FnOnce(&mut ProviderHealth),
+{
+    let mut map = registry().providers.lock();
+    let now = now_rfc3339();
+    let entry = map
+        .entry(provider.to_string())
+        .or_insert_with(|| ProviderHealth {
+            status: "unknown".into(),
+            latency_ms: None,
+            checked_at: now.clone(),
+            last_ok: None,
+            last_error: None,
+            consecutive_failures: 0,
+        });
+    update(entry);
+    entry.checked_at = now;
+}
+
+pub fn mark_provider_ok(provider: &str, latency: Duration) {
+    upsert_provider(provider, |entry| {
+        entry.status = "ok".into();
+        entry.latency_ms = Some(u64::try_from(latency.as_millis()).unwrap_or(u64::MAX));
+        entry.last_ok = Some(now_rfc3339());
+        entry.last_error = None;
+        entry.consecutive_failures = 0;
+    });
+}
+
+/// Record a failed probe. `unreachable` marks network-level failures (DNS,
+/// refused connection, timeout) as opposed to errors returned by the API.
+#[allow(clippy::needless_pass_by_value)]
+pub fn mark_provider_error(provider: &str, error: impl ToString, unreachable: bool) {
+    let err = error.to_string();
+    upsert_provider(provider, move |entry| {
+        entry.status = if unreachable { "unreachable" } else { "error" }.into();
+        entry.latency_ms = None;
+        entry.last_error = Some(err);
+        entry.consecutive_failures = entry.consecutive_failures.saturating_add(1);
+    });
+}
+
+pub fn provider_health(provider: &str) -> Option<ProviderHealth> {
+    registry().providers.lock().get(provider).cloned()
+}
+
+/// Whether the latest probe found `provider` unreachable.
+pub fn provider_unreachable(provider: &str) -> bool {
+    registry()
+        .providers
+        .lock()
+        .get(provider)
+        .is_some_and(|entry| entry.status == "unreachable")
+}
+
 pub fn snapshot() -> HealthSnapshot {
     let components = registry().components.lock().clone();
+    let providers = registry().providers.lock().clone();
 
     HealthSnapshot {
         pid: std::process::id(),
         updated_at: now_rfc3339(),
         uptime_seconds: registry().started_at.elapsed().as_secs(),
         components,
+        providers,
     }
 }
 
@@ -167,6 +240,28 @@ mod tests {
         assert_eq!(entry.restart_count, 2);
     }
 
+    #[test]
+    fn provider_probes_track_latency_and_failures() {
+        let provider = unique_component("provider");
+        assert!(provider_health(&provider).is_none());
+        assert!(!provider_unreachable(&provider));
+
+        mark_provider_error(&provider, "dns error", true);
+        mark_provider_error(&provider, "dns error", true);
+        let entry = provider_health(&provider).unwrap();
+        assert_eq!(entry.status, "unreachable");
+        assert_eq!(entry.consecutive_failures, 2);
+        assert!(provider_unreachable(&provider));
+
+        mark_provider_ok(&provider, Duration::from_millis(42));
+        let entry = provider_health(&provider).unwrap();
+        assert_eq!(entry.status, "ok");
+        assert_eq!(entry.latency_ms, Some(42));
+        assert_eq!(entry.consecutive_failures, 0);
+        assert!(!provider_unreachable(&provider));
+        assert_eq!(snapshot_json()["providers"][&provider]["latency_ms"], 42);
+    }
+
     #[test]
     fn snapshot_json_contains_registered_component_fields() {
         let component = unique_component("health-json");
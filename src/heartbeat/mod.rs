@@ -1,4 +1,5 @@
 pub mod engine;
+pub mod policy;
 
 #[cfg(test)]
 mod tests {
@@ -0,0 +1,278 @@
+//! When heartbeat runs happen and what they ask the agent.
+//!
+//! `[heartbeat]` runs every `interval_minutes`, or on a cron `schedule`
+//! (`"0 8 * * *"` is every morning at 8) evaluated in `timezone`, the
+//! device's local time by default. A due run is skipped inside
+//! `quiet_hours` and while the battery is below `min_battery_percent` and not
+//! charging. Each task reaches the agent through `prompt_template`.
+
+use crate::config::HeartbeatConfig;
+use crate::runtime::power::BatteryState;
+use anyhow::{Context, Result};
+use chrono::{DateTime, Local, NaiveDateTime, NaiveTime, TimeZone, Utc};
+use cron::Schedule as CronSchedule;
+use std::str::FromStr;
+
+pub const DEFAULT_PROMPT_TEMPLATE: &str = "[Heartbeat Task] {task}";
+
+/// Shortest allowed `interval_minutes`.
+const MIN_INTERVAL_MINUTES: u32 = 5;
+
+/// Daily `HH:MM-HH:MM` window of local time; may wrap past midnight.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct QuietHours {
+    start: NaiveTime,
+    end: NaiveTime,
+}
+
+impl QuietHours {
+    pub fn parse(range: &str) -> Result<Self> {
+        let (start, end) = range
+            .split_once('-')
+            .with_context(|| format!("quiet_hours {range:?} must look like \"22:00-07:00\""))?;
+        let parse = |value: &str| {
+            NaiveTime::parse_from_str(value.trim(), "%H:%M")
+                .with_context(|| format!("invalid time {value:?} in quiet_hours"))
+        };
+        let quiet = Self {
+            start: parse(start)?,
+            end: parse(end)?,
+        };
+        if quiet.start == quiet.end {
+            anyhow::bail!("quiet_hours start and end must differ");
+        }
+        Ok(quiet)
+    }
+
+    pub fn contains(self, time: NaiveTime) -> bool {
+        if self.start < self.end {
+            time >= self.start && time < self.end
+        } else {
+            time >= self.start || time < self.end
+        }
+    }
+}
+
+/// Parsed timing and prompt settings of `[heartbeat]`.
+#[derive(Debug, Clone)]
+pub struct HeartbeatPlan {
+    schedule: Option<CronSchedule>,
+    timezone: Option<chrono_tz::Tz>,
+    interval: chrono::Duration,
+    quiet_hours: Option<QuietHours>,
+    min_battery_percent: Option<u8>,
+    prompt_template: String,
+}
+
+impl HeartbeatPlan {
+    pub fn from_config(config: &HeartbeatConfig) -> Result<Self> {
+        let schedule = non_empty(config.schedule.as_deref())
+            .map(|expr| {
+                let normalized = crate::cron::normalize_expression(expr)?;
+                CronSchedule::from_str(&normalized)
+                    .with_context(|| format!("Invalid heartbeat.schedule: {expr}"))
+            })
+            .transpose()?;
+        let timezone = non_empty(config.timezone.as_deref())
+            .map(|name| {
+                chrono_tz::Tz::from_str(name)
+                    .map_err(|_| anyhow::anyhow!("Invalid heartbeat.timezone: {name}"))
+            })
+            .transpose()?;
+        let quiet_hours = non_empty(config.quiet_hours.as_deref())
+            .map(QuietHours::parse)
+            .transpose()?;
+        if config
+            .min_battery_percent
+            .is_some_and(|percent| percent > 100)
+        {
+            anyhow::bail!("heartbeat.min_battery_percent must be between 0 and 100");
+        }
+        if config.prompt_template.trim().is_empty() {
+            anyhow::bail!("heartbeat.prompt_template must not be empty");
+        }
+
+        Ok(Self {
+            schedule,
+            timezone,
+            interval: chrono::Duration::minutes(i64::from(
+                config.interval_minutes.max(MIN_INTERVAL_MINUTES),
+            )),
+            quiet_hours,
+            min_battery_percent: config.min_battery_percent,
+            prompt_template: config.prompt_template.clone(),
+        })
+    }
+
+    /// When the worker should run first: right away on an interval,
+    /// otherwise the next scheduled time.
+    pub fn first_run(&self, now: DateTime<Utc>) -> Result<DateTime<Utc>> {
+        if self.schedule.is_some() {
+            self.next_run(now)
+        } else {
+            Ok(now)
+        }
+    }
+
+    /// The run after `after`.
+    pub fn next_run(&self, after: DateTime<Utc>) -> Result<DateTime<Utc>> {
+        let Some(schedule) = &self.schedule else {
+            return Ok(after + self.interval);
+        };
+        let next = match self.timezone {
+            Some(tz) => next_after(schedule, &after.with_timezone(&tz)),
+            None => next_after(schedule, &after.with_timezone(&Local)),
+        };
+        next.context("heartbeat.schedule has no future occurrence")
+    }
+
+    /// Why a run due at `now` should be skipped, if it should.
+    pub fn skip_reason(&self, now: DateTime<Utc>, battery: BatteryState) -> Option<String> {
+        if let Some(quiet) = self.quiet_hours {
+            if quiet.contains(self.local_time(now).time()) {
+                return Some("inside quiet hours".into());
+            }
+        }
+        match (self.min_battery_percent, battery.percent) {
+            (Some(min), Some(percent)) if percent < min && !battery.charging => {
+                Some(format!("battery at {percent}% (below {min}%)"))
+            }
+            _ => None,
+        }
+    }
+
+    /// Prompt for `task`, filling `{task}`, `{date}` and `{time}`.
+    pub fn prompt(&self, task: &str, now: DateTime<Utc>) -> String {
+        let local = self.local_time(now);
+        self.prompt_template
+            .replace("{date}", &local.format("%Y-%m-%d").to_string())
+            .replace("{time}", &local.format("%H:%M").to_string())
+            .replace("{task}", task)
+    }
+
+    fn local_time(&self, now: DateTime<Utc>) -> NaiveDateTime {
+        match self.timezone {
+            Some(tz) => now.with_timezone(&tz).naive_local(),
+            None => now.with_timezone(&Local).naive_local(),
+        }
+    }
+}
+
+fn next_after<Z: TimeZone>(schedule: &CronSchedule, after: &DateTime<Z>) -> Option<DateTime<Utc>> {
+    schedule
+        .after(after)
+        .next()
+        .map(|next| next.with_timezone(&Utc))
+}
+
+fn non_empty(value: Option<&str>) -> Option<&str> {
+    value.map(str::trim).filter(|value| !value.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> HeartbeatConfig {
+        HeartbeatConfig {
+            enabled: true,
+            schedule: Some("0 8 * * *".into()),
+            timezone: Some("Europe/Berlin".into()),
+            quiet_hours: Some("22:00-07:00".into()),
+            min_battery_percent: Some(20),
+            prompt_template: "Morning check ({date} {time}): {task}".into(),
+            ..HeartbeatConfig::default()
+        }
+    }
+
+    fn utc(value: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(value)
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+
+    #[test]
+    fn schedule_runs_at_local_time_and_interval_runs_right_away() {
+        let plan = HeartbeatPlan::from_config(&config()).unwrap();
+        // 08:00 in Berlin (UTC+2 in summer).
+        let now = utc("2026-06-01T05:00:00Z");
+        assert_eq!(plan.first_run(now).unwrap(), utc("2026-06-01T06:00:00Z"));
+        assert_eq!(
+            plan.next_run(utc("2026-06-01T06:00:00Z")).unwrap(),
+            utc("2026-06-02T06:00:00Z")
+        );
+
+        let plan = HeartbeatPlan::from_config(&HeartbeatConfig::default()).unwrap();
+        assert_eq!(plan.first_run(now).unwrap(), now);
+        assert_eq!(
+            plan.next_run(now).unwrap(),
+            now + chrono::Duration::minutes(30)
+        );
+    }
+
+    #[test]
+    fn quiet_hours_and_low_battery_skip_runs() {
+        let plan = HeartbeatPlan::from_config(&config()).unwrap();
+        let charged = BatteryState {
+            percent: Some(80),
+            charging: false,
+        };
+        // 23:30 and 06:30 Berlin are quiet, 08:00 is not.
+        assert!(plan
+            .skip_reason(utc("2026-06-01T21:30:00Z"), charged)
+            .is_some());
+        assert!(plan
+            .skip_reason(utc("2026-06-01T04:30:00Z"), charged)
+            .is_some());
+        let morning = utc("2026-06-01T06:00:00Z");
+        assert_eq!(plan.skip_reason(morning, charged), None);
+
+        let low = BatteryState {
+            percent: Some(10),
+            charging: false,
+        };
+        assert_eq!(
+            plan.skip_reason(morning, low).as_deref(),
+            Some("battery at 10% (below 20%)")
+        );
+        let plugged_in = BatteryState {
+            charging: true,
+            ..low
+        };
+        assert_eq!(plan.skip_reason(morning, plugged_in), None);
+        assert_eq!(plan.skip_reason(morning, BatteryState::default()), None);
+    }
+
+    #[test]
+    fn prompt_template_fills_placeholders() {
+        let plan = HeartbeatPlan::from_config(&config()).unwrap();
+        assert_eq!(
+            plan.prompt("Check the weather", utc("2026-06-01T06:00:00Z")),
+            "Morning check (2026-06-01 08:00): Check the weather"
+        );
+    }
+
+    #[test]
+    fn invalid_settings_are_rejected() {
+        for bad in [
+            HeartbeatConfig {
+                schedule: Some("every morning".into()),
+                ..HeartbeatConfig::default()
+            },
+            HeartbeatConfig {
+                timezone: Some("Mars/Olympus".into()),
+                ..HeartbeatConfig::default()
+            },
+            HeartbeatConfig {
+                quiet_hours: Some("22:00".into()),
+                ..HeartbeatConfig::default()
+            },
+            HeartbeatConfig {
+                min_battery_percent: Some(120),
+                ..HeartbeatConfig::default()
+            },
+        ] {
+            assert!(HeartbeatPlan::from_config(&bad).is_err(), "{bad:?}");
+        }
+    }
+}
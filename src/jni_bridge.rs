@@ -10,11 +10,11 @@
 
 use crate::agent;
 use crate::config::Config;
-use jni::objects::{JClass, JObject, JString};
-use jni::sys::{jboolean, jlong, jstring};
-use jni::JNIEnv;
+use jni::objects::{GlobalRef, JClass, JObject, JString, JValue};
+use jni::sys::{jboolean, jint, jlong, jstring};
+use jni::{JNIEnv, JavaVM};
 use std::collections::HashMap;
-use std::sync::{Mutex, Once};
+use std::sync::{Arc, Mutex, Once};
 use tokio::runtime::Runtime;
 
 /// Global registry of agent handles
@@ -50,6 +50,256 @@ fn install_crypto_provider_once() {
     });
 }
 
+/// Host bridge backed by a Kotlin callback object.
+///
+/// The object must implement
+/// `fun onHostCall(action: String, payloadJson: String): String`
+/// and return a JSON document.
+struct JniHostBridge {
+    vm: JavaVM,
+    callback: GlobalRef,
+}
+
+impl crate::runtime::HostBridge for JniHostBridge {
+    fn call(&self, action: &str, payload: serde_json::Value) -> anyhow::Result<serde_json::Value> {
+        let mut env = self.vm.attach_current_thread()?;
+        let action = env.new_string(action)?;
+        let payload = env.new_string(payload.to_string())?;
+        let reply = env
+            .call_method(
+                self.callback.as_obj(),
+                "onHostCall",
+                "(Ljava/lang/String;Ljava/lang/String;)Ljava/lang/String;",
+                &[JValue::Object(&action), JValue::Object(&payload)],
+            )?
+            .l()?;
+        if reply.is_null() {
+            anyhow::bail!("host callback returned null");
+        }
+        let reply: String = env.get_string(&JString::from(reply))?.into();
+        Ok(serde_json::from_str(&reply)?)
+    }
+}
+
+/// Register (or clear, when `callback` is null) the host callback used for
+/// platform APIs such as Storage Access Framework documents.
+#[no_mangle]
+pub extern "C" fn Java_com_mobileclaw_app_ZeroClawBackend_registerHostBridge(
+    mut env: JNIEnv,
+    _class: JClass,
+    callback: JObject,
+) {
+    if callback.is_null() {
+        crate::runtime::host_bridge::clear_host_bridge();
+        return;
+    }
+
+    let bridge = env
+        .get_java_vm()
+        .and_then(|vm| env.new_global_ref(&callback).map(|callback| (vm, callback)));
+    match bridge {
+        Ok((vm, callback)) => {
+            crate::runtime::host_bridge::register_host_bridge(Arc::new(JniHostBridge {
+                vm,
+                callback,
+            }))
+        }
+        Err(e) => {
+            let _ = env.throw_new(
+                "java/lang/RuntimeException",
+                format!("Failed to register host bridge: {}", e),
+            );
+        }
+    }
+}
+
+/// Report Android Doze / App Standby state from the host app.
+///
+/// `next_wakeup_epoch_ms <= 0` means the next maintenance window is unknown.
+#[no_mangle]
+pub extern "C" fn Java_com_mobileclaw_app_ZeroClawBackend_updateBackgroundState(
+    _env: JNIEnv,
+    _class: JClass,
+    restricted: jboolean,
+    next_wakeup_epoch_ms: jlong,
+) {
+    let next_allowed_wakeup = (next_wakeup_epoch_ms > 0)
+        .then(|| chrono::DateTime::<chrono::Utc>::from_timestamp_millis(next_wakeup_epoch_ms))
+        .flatten();
+    crate::runtime::power::update_background_state(crate::runtime::power::BackgroundState {
+        restricted: restricted != 0,
+        next_allowed_wakeup,
+    });
+}
+
+/// Report the active network from the host app's `ConnectivityManager`.
+///
+/// `connection_type` is one of `wifi`, `cellular`, `ethernet`, `none`.
+#[no_mangle]
+pub extern "C" fn Java_com_mobileclaw_app_ZeroClawBackend_updateNetworkState(
+    mut env: JNIEnv,
+    _class: JClass,
+    connection_type: JString,
+    metered: jboolean,
+) {
+    let connection_type: String = env
+        .get_string(&connection_type)
+        .map(|s| s.into())
+        .unwrap_or_default();
+    crate::runtime::network::update_network_state(crate::runtime::network::NetworkState {
+        connection: crate::runtime::ConnectionType::parse(&connection_type),
+        metered: metered != 0,
+    });
+}
+
+/// Report the battery level from the host app's `BatteryManager`.
+///
+/// A negative `percent` means the level is unknown.
+#[no_mangle]
+pub extern "C" fn Java_com_mobileclaw_app_ZeroClawBackend_updateBatteryState(
+    _env: JNIEnv,
+    _class: JClass,
+    percent: jint,
+    charging: jboolean,
+) {
+    crate::runtime::power::update_battery_state(crate::runtime::power::BatteryState {
+        percent: u8::try_from(percent.min(100)).ok(),
+        charging: charging != 0,
+    });
+}
+
+/// Ask whether a large transfer (`model_download`, `image_upload`) may run
+/// on the current network under the `[runtime.network]` policy.
+#[no_mangle]
+pub extern "C" fn Java_com_mobileclaw_app_ZeroClawBackend_isTransferAllowed(
+    mut env: JNIEnv,
+    _class: JClass,
+    kind: JString,
+) -> jboolean {
+    let kind: String = env.get_string(&kind).map(|s| s.into()).unwrap_or_default();
+    let allowed = crate::runtime::LargeTransfer::parse(&kind)
+        .is_none_or(|transfer| crate::runtime::network::ensure_transfer_allowed(transfer).is_ok());
+    jboolean::from(allowed)
+}
+
+/// Feed a notification posted (or updated) on the device from the host's
+/// `NotificationListenerService`. `payload_json` carries `key`, `package`,
+/// `app_name`, `title`, `text`, `category`, `posted_at_ms` and `ongoing`.
+#[no_mangle]
+pub extern "C" fn Java_com_mobileclaw_app_ZeroClawBackend_onNotificationPosted(
+    mut env: JNIEnv,
+    _class: JClass,
+    payload_json: JString,
+) {
+    let payload: String = env
+        .get_string(&payload_json)
+        .map(|s| s.into())
+        .unwrap_or_default();
+    if let Err(e) = crate::runtime::notifications::record_posted(&payload) {
+        tracing::warn!("Ignoring malformed notification from host: {e}");
+    }
+}
+
+/// Mark a previously posted notification (by listener `key`) as removed.
+#[no_mangle]
+pub extern "C" fn Java_com_mobileclaw_app_ZeroClawBackend_onNotificationRemoved(
+    mut env: JNIEnv,
+    _class: JClass,
+    key: JString,
+) {
+    let key: String = env.get_string(&key).map(|s| s.into()).unwrap_or_default();
+    crate::runtime::notifications::record_removed(&key);
+}
+
+/// Report whether the notification listener is bound (access granted).
+#[no_mangle]
+pub extern "C" fn Java_com_mobileclaw_app_ZeroClawBackend_updateNotificationListenerState(
+    _env: JNIEnv,
+    _class: JClass,
+    connected: jboolean,
+) {
+    crate::runtime::notifications::set_listener_connected(connected != 0);
+}
+
+/// Report a geofence transition from the host's geofencing receiver.
+/// `payload_json` carries the fence `id` and `transition` (`enter`/`exit`);
+/// matching fences schedule their prompt as a one-shot agent job.
+#[no_mangle]
+pub extern "C" fn Java_com_mobileclaw_app_ZeroClawBackend_onGeofenceEvent(
+    mut env: JNIEnv,
+    _class: JClass,
+    handle_id: jlong,
+    payload_json: JString,
+) {
+    let payload: String = env
+        .get_string(&payload_json)
+        .map(|s| s.into())
+        .unwrap_or_default();
+    // Release the registry before running the event, so other calls are not
+    // blocked behind it.
+    let (runtime, config) = {
+        let Ok(handles) = AGENT_HANDLES.lock() else {
+            let _ = env.throw_new(
+                "java/lang/RuntimeException",
+                "Agent handle registry poisoned",
+            );
+            return;
+        };
+        match handles.as_ref().and_then(|m| m.get(&handle_id)) {
+            Some(h) => (h.runtime.handle().clone(), h.config.clone()),
+            None => {
+                let _ = env.throw_new("java/lang/RuntimeException", "Invalid handle ID");
+                return;
+            }
+        }
+    };
+    let outcome = runtime
+        .block_on(async move { crate::runtime::geofences::handle_event(&config, &payload).await });
+    if let Err(e) = outcome {
+        tracing::warn!("Failed to handle geofence event from host: {e}");
+    }
+}
+
+/// Forward daemon lifecycle events to the host callback (`daemon_event`
+/// action), if one is registered.
+fn report_daemon_event(event: crate::daemon::DaemonEvent) {
+    if let crate::daemon::DaemonEvent::Exited { error, .. } = &event {
+        eprintln!("[ZeroClaw] Daemon exited: {}", error);
+    }
+    if crate::runtime::host_bridge::host_bridge().is_none() {
+        return;
+    }
+    let payload = serde_json::to_value(&event).unwrap_or_default();
+    if let Err(e) = crate::runtime::host_bridge::call_host("daemon_event", payload) {
+        tracing::warn!("Failed to deliver daemon event to host: {e}");
+    }
+}
+
+/// Forward gateway session events from the agent event bus to the host
+/// callback (`agent_event` action) while one is registered. The payload is
+/// `{"session_id", "event"}`, with the same frames `/v1/stream/{session}`
+/// sends.
+async fn forward_agent_events() {
+    let mut events = crate::agent::events::subscribe();
+    loop {
+        match events.recv().await {
+            Ok(event) => {
+                if crate::runtime::host_bridge::host_bridge().is_none() {
+                    continue;
+                }
+                let payload = serde_json::to_value(&event).unwrap_or_default();
+                if let Err(e) = crate::runtime::host_bridge::call_host("agent_event", payload) {
+                    tracing::warn!("Failed to deliver agent event to host: {e}");
+                }
+            }
+            Err(tokio::sync::broadcast::error::RecvError::Lagged(skipped)) => {
+                tracing::warn!(skipped, "Host fell behind on agent events");
+            }
+            Err(tokio::sync::broadcast::error::RecvError::Closed) => break,
+        }
+    }
+}
+
 /// Start the ZeroClaw agent runtime
 ///
 /// Returns a handle (jlong) that must be passed to subsequent calls
@@ -88,10 +338,16 @@ pub extern "C" fn Java_com_mobileclaw_app_ZeroClawBackend_startAgent(
     };
 
     let api_key_str: String = env.get_string(&api_key).map(Into::into).unwrap_or_default();
-    let provider_str: String = env.get_string(&provider).map(Into::into).unwrap_or_default();
+    let provider_str: String = env
+        .get_string(&provider)
+        .map(Into::into)
+        .unwrap_or_default();
     let model_str: String = env.get_string(&model).map(Into::into).unwrap_or_default();
     let api_url_str: String = env.get_string(&api_url).map(Into::into).unwrap_or_default();
-    let temperature_str: String = env.get_string(&temperature).map(Into::into).unwrap_or_default();
+    let temperature_str: String = env
+        .get_string(&temperature)
+        .map(Into::into)
+        .unwrap_or_default();
     let telegram_token_str: String = env
         .get_string(&telegram_token)
         .map(Into::into)
@@ -167,7 +423,11 @@ pub extern "C" fn Java_com_mobileclaw_app_ZeroClawBackend_startAgent(
     if config.default_provider.is_none() {
         config.default_provider = Some("openrouter".into());
     }
+    crate::observability::wire_log::init_from_config(&config.observability, &config.workspace_dir);
     config.gateway.port = 8000;
+    // No on-device pairing code: the app authenticates with the per-install
+    // API token (getGatewayApiToken), which the gateway requires on every
+    // request.
     config.gateway.require_pairing = false;
     config.android.enabled = true;
     config.android.bridge.mode = "http".into();
@@ -193,12 +453,18 @@ pub extern "C" fn Java_com_mobileclaw_app_ZeroClawBackend_startAgent(
         browser_automation: true,
         file_system_access: true,
         event_hooks: true,
+        notification_access: true,
+        usage_stats: true,
+        automation: true,
+        audio_playback: true,
+        geofencing: true,
     };
     if !telegram_token_str.is_empty() {
         config.channels_config.telegram = Some(crate::config::schema::TelegramConfig {
             bot_token: telegram_token_str,
             allowed_users: vec![],
             notify_chat_id: None,
+            notify_targets: Default::default(),
             stream_mode: Default::default(),
             draft_update_interval_ms: 1500,
             interrupt_on_new_message: false,
@@ -219,6 +485,8 @@ pub extern "C" fn Java_com_mobileclaw_app_ZeroClawBackend_startAgent(
             allowed_users: vec![],
             listen_to_bots: false,
             mention_only: false,
+            notify_chat_id: None,
+            notify_webhook_url: None,
         });
     }
     // Slack
@@ -228,6 +496,8 @@ pub extern "C" fn Java_com_mobileclaw_app_ZeroClawBackend_startAgent(
             app_token: None,
             channel_id: None,
             allowed_users: vec![],
+            notify_channel_id: None,
+            notify_webhook_url: None,
         });
     }
     // Composio
@@ -243,7 +513,11 @@ pub extern "C" fn Java_com_mobileclaw_app_ZeroClawBackend_startAgent(
     // but ignored — local inference now runs via llama.rn in React Native.
 
     // Enable compact context for on-device local models (small context windows)
-    if config.default_model.as_deref() == Some("local") {
+    if config
+        .default_model
+        .as_deref()
+        .is_some_and(|model| config.resolve_model_alias(model) == "local")
+    {
         config.agent.compact_context = true;
     }
 
@@ -258,7 +532,7 @@ pub extern "C" fn Java_com_mobileclaw_app_ZeroClawBackend_startAgent(
     // (config.save() is async — block on it with a temporary runtime)
     if let Err(e) = tokio::runtime::Runtime::new()
         .expect("temp runtime")
-        .block_on(config.save())
+        .block_on(config.save_from(crate::config::ChangeSource::Jni))
     {
         // Non-fatal: daemon still works, agent just may read stale config.toml
         tracing::warn!("Failed to save android-overridden config: {e}");
@@ -276,17 +550,30 @@ pub extern "C" fn Java_com_mobileclaw_app_ZeroClawBackend_startAgent(
         }
     };
 
-    let daemon_config = config.clone();
-    runtime.spawn(async move {
-        // Set up panic hook to log instead of abort
-        std::panic::set_hook(Box::new(|info| {
-            eprintln!("[ZeroClaw] PANIC: {:?}", info);
-        }));
+    // Set up panic hook to log instead of abort
+    std::panic::set_hook(Box::new(|info| {
+        eprintln!("[ZeroClaw] PANIC: {:?}", info);
+    }));
 
-        if let Err(e) = crate::daemon::run(daemon_config, "127.0.0.1".into(), 8000).await {
-            eprintln!("[ZeroClaw] Daemon exited: {}", e);
-        }
-    });
+    // Supervise the daemon (gateway + channels + scheduler): restart it with
+    // backoff when it exits or panics and report lifecycle events to the host.
+    let daemon_config = config.clone();
+    let guard = runtime.enter();
+    crate::daemon::spawn_daemon_watchdog(
+        config.reliability.channel_initial_backoff_secs,
+        config.reliability.channel_max_backoff_secs,
+        move || {
+            let config = daemon_config.clone();
+            async move {
+                // Restarts pick up edits that were hot-reloaded since startup
+                let config = config.reload().await.unwrap_or(config);
+                crate::daemon::run(config, "127.0.0.1".into(), 8000).await
+            }
+        },
+        report_daemon_event,
+    );
+    drop(guard);
+    runtime.spawn(forward_agent_events());
 
     // Give the daemon a moment to start
     std::thread::sleep(std::time::Duration::from_millis(500));
@@ -372,6 +659,93 @@ pub extern "C" fn Java_com_mobileclaw_app_ZeroClawBackend_isHealthy(
     }
 }
 
+/// Report daemon supervision status as JSON
+///
+/// Includes the health snapshot (per-component status and restart counts),
+/// the latest provider reachability/latency probes, and whether the handle is
+/// still registered.
+#[no_mangle]
+pub extern "C" fn Java_com_mobileclaw_app_ZeroClawBackend_getStatus(
+    mut env: JNIEnv,
+    _class: JClass,
+    handle_id: jlong,
+) -> jstring {
+    let running = AGENT_HANDLES
+        .lock()
+        .unwrap()
+        .as_ref()
+        .is_some_and(|m| m.contains_key(&handle_id));
+
+    let health = crate::health::snapshot_json();
+    let daemon = &health["components"]["daemon"];
+    let status = serde_json::json!({
+        "running": running,
+        "daemon_status": daemon["status"].as_str().unwrap_or("starting"),
+        "restart_count": daemon["restart_count"].as_u64().unwrap_or(0),
+        "last_error": daemon["last_error"],
+        "providers": health["providers"],
+        "health": health,
+    });
+
+    match env.new_string(status.to_string()) {
+        Ok(s) => s.into_raw(),
+        Err(e) => {
+            let _ = env.throw_new(
+                "java/lang/RuntimeException",
+                format!("Failed to create status string: {}", e),
+            );
+            JObject::null().into_raw()
+        }
+    }
+}
+
+/// Report LLM spend from the cost ledger as JSON
+///
+/// Returns per-day totals for the last `days` days, newest first, each broken
+/// down by provider and model.
+#[no_mangle]
+pub extern "C" fn Java_com_mobileclaw_app_ZeroClawBackend_getCostStats(
+    mut env: JNIEnv,
+    _class: JClass,
+    handle_id: jlong,
+    days: jint,
+) -> jstring {
+    let config = {
+        let handles = AGENT_HANDLES.lock().unwrap();
+        match handles.as_ref().and_then(|m| m.get(&handle_id)) {
+            Some(h) => h.config.clone(),
+            None => {
+                let _ = env.throw_new("java/lang/RuntimeException", "Invalid handle ID");
+                return JObject::null().into_raw();
+            }
+        }
+    };
+
+    let days = u32::try_from(days).unwrap_or(1);
+    let stats = crate::cost::CostTracker::new(config.cost.clone(), &config.workspace_dir)
+        .and_then(|tracker| tracker.daily_usage(days))
+        .map(|days| {
+            serde_json::json!({
+                "enabled": config.cost.enabled,
+                "daily_limit_usd": config.cost.daily_limit_usd,
+                "monthly_limit_usd": config.cost.monthly_limit_usd,
+                "days": days,
+            })
+        })
+        .unwrap_or_else(|e| serde_json::json!({ "error": e.to_string() }));
+
+    match env.new_string(stats.to_string()) {
+        Ok(s) => s.into_raw(),
+        Err(e) => {
+            let _ = env.throw_new(
+                "java/lang/RuntimeException",
+                format!("Failed to create cost stats string: {}", e),
+            );
+            JObject::null().into_raw()
+        }
+    }
+}
+
 /// Stop the agent and release resources
 #[no_mangle]
 pub extern "C" fn Java_com_mobileclaw_app_ZeroClawBackend_stopAgent(
@@ -387,6 +761,370 @@ pub extern "C" fn Java_com_mobileclaw_app_ZeroClawBackend_stopAgent(
     }
 }
 
+/// Re-read config.toml and apply it without restarting the agent
+///
+/// Refreshes the config used by `processMessage` and asks the daemon to
+/// restart only the components whose settings changed. Returns false (and
+/// keeps the running config) when the file fails to load or validate.
+#[no_mangle]
+pub extern "C" fn Java_com_mobileclaw_app_ZeroClawBackend_reloadConfig(
+    mut env: JNIEnv,
+    _class: JClass,
+    handle_id: jlong,
+) -> jboolean {
+    let mut handles = AGENT_HANDLES.lock().unwrap();
+    let handle = match handles.as_mut().and_then(|m| m.get_mut(&handle_id)) {
+        Some(h) => h,
+        None => {
+            let _ = env.throw_new("java/lang/RuntimeException", "Invalid handle ID");
+            return 0;
+        }
+    };
+
+    match handle.runtime.block_on(handle.config.reload()) {
+        Ok(config) => {
+            handle.config = config;
+            crate::daemon::request_reload();
+            1
+        }
+        Err(e) => {
+            eprintln!("[ZeroClaw] Config reload failed: {e:#}");
+            0
+        }
+    }
+}
+
+/// List config profiles as JSON: `{"active": name|null, "profiles": [names]}`
+#[no_mangle]
+pub extern "C" fn Java_com_mobileclaw_app_ZeroClawBackend_listProfiles(
+    mut env: JNIEnv,
+    _class: JClass,
+    handle_id: jlong,
+) -> jstring {
+    let profiles = {
+        let handles = AGENT_HANDLES.lock().unwrap();
+        match handles.as_ref().and_then(|m| m.get(&handle_id)) {
+            Some(h) => serde_json::json!({
+                "active": h.config.active_profile,
+                "profiles": h.config.profile_names(),
+            }),
+            None => {
+                let _ = env.throw_new("java/lang/RuntimeException", "Invalid handle ID");
+                return JObject::null().into_raw();
+            }
+        }
+    };
+
+    match env.new_string(profiles.to_string()) {
+        Ok(s) => s.into_raw(),
+        Err(e) => {
+            let _ = env.throw_new(
+                "java/lang/RuntimeException",
+                format!("Failed to create profiles string: {}", e),
+            );
+            JObject::null().into_raw()
+        }
+    }
+}
+
+/// Switch to a named config profile and apply it without a restart
+///
+/// Saves the switch to config.toml, refreshes the config used by
+/// `processMessage`, and hot-reloads the affected daemon components.
+/// Throws IllegalArgumentException for unknown or invalid profiles.
+#[no_mangle]
+pub extern "C" fn Java_com_mobileclaw_app_ZeroClawBackend_switchProfile(
+    mut env: JNIEnv,
+    _class: JClass,
+    handle_id: jlong,
+    name: JString,
+) -> jboolean {
+    let name: String = match env.get_string(&name) {
+        Ok(s) => s.into(),
+        Err(e) => {
+            let _ = env.throw_new(
+                "java/lang/RuntimeException",
+                format!("Invalid profile name: {}", e),
+            );
+            return 0;
+        }
+    };
+
+    let mut handles = AGENT_HANDLES.lock().unwrap();
+    let handle = match handles.as_mut().and_then(|m| m.get_mut(&handle_id)) {
+        Some(h) => h,
+        None => {
+            let _ = env.throw_new("java/lang/RuntimeException", "Invalid handle ID");
+            return 0;
+        }
+    };
+
+    let mut config = handle.config.clone();
+    if let Err(e) = config.switch_profile(&name) {
+        let _ = env.throw_new("java/lang/IllegalArgumentException", format!("{e:#}"));
+        return 0;
+    }
+    if let Err(e) = handle
+        .runtime
+        .block_on(config.save_from(crate::config::ChangeSource::Jni))
+    {
+        eprintln!("[ZeroClaw] Failed to save profile switch: {e:#}");
+        return 0;
+    }
+
+    handle.config = config;
+    crate::daemon::request_reload();
+    1
+}
+
+/// Pull the signed remote config now and return the result as JSON
+///
+/// Returns `{"applied", "sha256", "sections", "dropped_secrets"}`; when the
+/// document was applied, the config used by `processMessage` is refreshed.
+/// Throws RuntimeException when sync is disabled or the pull or signature
+/// check fails.
+#[no_mangle]
+pub extern "C" fn Java_com_mobileclaw_app_ZeroClawBackend_syncConfig(
+    mut env: JNIEnv,
+    _class: JClass,
+    handle_id: jlong,
+) -> jstring {
+    let result = {
+        let mut handles = AGENT_HANDLES.lock().unwrap();
+        let handle = match handles.as_mut().and_then(|m| m.get_mut(&handle_id)) {
+            Some(h) => h,
+            None => {
+                let _ = env.throw_new("java/lang/RuntimeException", "Invalid handle ID");
+                return JObject::null().into_raw();
+            }
+        };
+        let config = &mut handle.config;
+        handle.runtime.block_on(async {
+            let report = crate::config::sync::sync_once(config).await?;
+            if report.applied {
+                *config = config.reload().await?;
+            }
+            anyhow::Ok(report)
+        })
+    };
+
+    let report = match result {
+        Ok(report) => report,
+        Err(e) => {
+            let _ = env.throw_new("java/lang/RuntimeException", format!("{e:#}"));
+            return JObject::null().into_raw();
+        }
+    };
+
+    match env.new_string(serde_json::to_string(&report).unwrap_or_default()) {
+        Ok(s) => s.into_raw(),
+        Err(e) => {
+            let _ = env.throw_new(
+                "java/lang/RuntimeException",
+                format!("Failed to create sync report string: {}", e),
+            );
+            JObject::null().into_raw()
+        }
+    }
+}
+
+/// Check config TOML and return the report as JSON
+///
+/// Validates `toml`, or the config file on disk when `toml` is empty, and
+/// returns `{"file", "valid", "diagnostics": [...]}` where each diagnostic
+/// carries its severity, kind, config path, line, column and message.
+#[no_mangle]
+pub extern "C" fn Java_com_mobileclaw_app_ZeroClawBackend_validateConfig(
+    mut env: JNIEnv,
+    _class: JClass,
+    handle_id: jlong,
+    toml: JString,
+) -> jstring {
+    let toml: String = match env.get_string(&toml) {
+        Ok(s) => s.into(),
+        Err(e) => {
+            let _ = env.throw_new(
+                "java/lang/RuntimeException",
+                format!("Invalid config string: {}", e),
+            );
+            return JObject::null().into_raw();
+        }
+    };
+
+    let report = {
+        let handles = AGENT_HANDLES.lock().unwrap();
+        let handle = match handles.as_ref().and_then(|m| m.get(&handle_id)) {
+            Some(h) => h,
+            None => {
+                let _ = env.throw_new("java/lang/RuntimeException", "Invalid handle ID");
+                return JObject::null().into_raw();
+            }
+        };
+        let config_path = &handle.config.config_path;
+        if toml.trim().is_empty() {
+            handle
+                .runtime
+                .block_on(crate::config::check_config_file(config_path))
+        } else {
+            Ok(crate::config::check_config_str(&toml, config_path))
+        }
+    };
+
+    let report = match report {
+        Ok(report) => report,
+        Err(e) => {
+            let _ = env.throw_new("java/lang/RuntimeException", format!("{e:#}"));
+            return JObject::null().into_raw();
+        }
+    };
+
+    match env.new_string(serde_json::to_string(&report).unwrap_or_default()) {
+        Ok(s) => s.into_raw(),
+        Err(e) => {
+            let _ = env.throw_new(
+                "java/lang/RuntimeException",
+                format!("Failed to create report string: {}", e),
+            );
+            JObject::null().into_raw()
+        }
+    }
+}
+
+/// Export the config as portable TOML
+///
+/// An empty `passphrase` strips credentials (a shareable template); otherwise
+/// they are wrapped and only `importConfig` with the same passphrase can
+/// restore them.
+#[no_mangle]
+pub extern "C" fn Java_com_mobileclaw_app_ZeroClawBackend_exportConfig(
+    mut env: JNIEnv,
+    _class: JClass,
+    handle_id: jlong,
+    passphrase: JString,
+) -> jstring {
+    let passphrase: String = match env.get_string(&passphrase) {
+        Ok(s) => s.into(),
+        Err(e) => {
+            let _ = env.throw_new(
+                "java/lang/RuntimeException",
+                format!("Invalid passphrase string: {}", e),
+            );
+            return JObject::null().into_raw();
+        }
+    };
+
+    let result = {
+        let handles = AGENT_HANDLES.lock().unwrap();
+        let handle = match handles.as_ref().and_then(|m| m.get(&handle_id)) {
+            Some(h) => h,
+            None => {
+                let _ = env.throw_new("java/lang/RuntimeException", "Invalid handle ID");
+                return JObject::null().into_raw();
+            }
+        };
+        handle.runtime.block_on(crate::config::transfer::export(
+            &handle.config,
+            Some(passphrase.as_str()),
+        ))
+    };
+
+    let toml = match result {
+        Ok(toml) => toml,
+        Err(e) => {
+            let _ = env.throw_new("java/lang/RuntimeException", format!("{e:#}"));
+            return JObject::null().into_raw();
+        }
+    };
+
+    match env.new_string(toml) {
+        Ok(s) => s.into_raw(),
+        Err(e) => {
+            let _ = env.throw_new(
+                "java/lang/RuntimeException",
+                format!("Failed to create export string: {}", e),
+            );
+            JObject::null().into_raw()
+        }
+    }
+}
+
+/// Import a config exported by `exportConfig` and return the result as JSON
+///
+/// Returns `{"sections", "unwrapped_secrets", "kept_secrets"}` and refreshes
+/// the config used by `processMessage`. Throws RuntimeException when the
+/// document is invalid or the passphrase is wrong.
+#[no_mangle]
+pub extern "C" fn Java_com_mobileclaw_app_ZeroClawBackend_importConfig(
+    mut env: JNIEnv,
+    _class: JClass,
+    handle_id: jlong,
+    toml: JString,
+    passphrase: JString,
+) -> jstring {
+    let toml: String = match env.get_string(&toml) {
+        Ok(s) => s.into(),
+        Err(e) => {
+            let _ = env.throw_new(
+                "java/lang/RuntimeException",
+                format!("Invalid config string: {}", e),
+            );
+            return JObject::null().into_raw();
+        }
+    };
+    let passphrase: String = match env.get_string(&passphrase) {
+        Ok(s) => s.into(),
+        Err(e) => {
+            let _ = env.throw_new(
+                "java/lang/RuntimeException",
+                format!("Invalid passphrase string: {}", e),
+            );
+            return JObject::null().into_raw();
+        }
+    };
+
+    let result = {
+        let mut handles = AGENT_HANDLES.lock().unwrap();
+        let handle = match handles.as_mut().and_then(|m| m.get_mut(&handle_id)) {
+            Some(h) => h,
+            None => {
+                let _ = env.throw_new("java/lang/RuntimeException", "Invalid handle ID");
+                return JObject::null().into_raw();
+            }
+        };
+        let config = &mut handle.config;
+        handle.runtime.block_on(async {
+            let report = crate::config::transfer::import(
+                config,
+                &toml,
+                Some(passphrase.as_str()),
+                crate::config::ChangeSource::Jni,
+            )
+            .await?;
+            *config = config.reload().await?;
+            anyhow::Ok(report)
+        })
+    };
+
+    let report = match result {
+        Ok(report) => report,
+        Err(e) => {
+            let _ = env.throw_new("java/lang/RuntimeException", format!("{e:#}"));
+            return JObject::null().into_raw();
+        }
+    };
+
+    match env.new_string(serde_json::to_string(&report).unwrap_or_default()) {
+        Ok(s) => s.into_raw(),
+        Err(e) => {
+            let _ = env.throw_new(
+                "java/lang/RuntimeException",
+                format!("Failed to create import report string: {}", e),
+            );
+            JObject::null().into_raw()
+        }
+    }
+}
+
 /// Get the gateway URL for this agent instance
 #[no_mangle]
 pub extern "C" fn Java_com_mobileclaw_app_ZeroClawBackend_getGatewayUrl(
@@ -403,10 +1141,7 @@ pub extern "C" fn Java_com_mobileclaw_app_ZeroClawBackend_getGatewayUrl(
         }
     };
 
-    let url = format!(
-        "http://{}:{}",
-        &handle.config.gateway.host, handle.config.gateway.port
-    );
+    let url = crate::gateway::pairing::gateway_url(&handle.config);
 
     match env.new_string(&url) {
         Ok(s) => s.into_raw(),
@@ -420,6 +1155,151 @@ pub extern "C" fn Java_com_mobileclaw_app_ZeroClawBackend_getGatewayUrl(
     }
 }
 
+/// SHA-256 fingerprint of the gateway's TLS certificate, for pinning
+///
+/// Returns colon-separated hex, or null when `[gateway.tls]` is disabled.
+/// The self-signed certificate is created on first call if needed.
+#[no_mangle]
+pub extern "C" fn Java_com_mobileclaw_app_ZeroClawBackend_getGatewayTlsFingerprint(
+    mut env: JNIEnv,
+    _class: JClass,
+    handle_id: jlong,
+) -> jstring {
+    let result = {
+        let handles = AGENT_HANDLES.lock().unwrap();
+        let handle = match handles.as_ref().and_then(|m| m.get(&handle_id)) {
+            Some(h) => h,
+            None => {
+                let _ = env.throw_new("java/lang/RuntimeException", "Invalid handle ID");
+                return JObject::null().into_raw();
+            }
+        };
+        if !handle.config.gateway.tls.enabled {
+            return JObject::null().into_raw();
+        }
+        crate::gateway::tls::fingerprint(&handle.config)
+    };
+
+    let fingerprint = match result {
+        Ok(fingerprint) => fingerprint,
+        Err(e) => {
+            let _ = env.throw_new("java/lang/RuntimeException", format!("{e:#}"));
+            return JObject::null().into_raw();
+        }
+    };
+
+    match env.new_string(fingerprint) {
+        Ok(s) => s.into_raw(),
+        Err(e) => {
+            let _ = env.throw_new(
+                "java/lang/RuntimeException",
+                format!("Failed to create fingerprint string: {}", e),
+            );
+            JObject::null().into_raw()
+        }
+    }
+}
+
+/// Get the gateway's per-install API token (generated on first use)
+#[no_mangle]
+pub extern "C" fn Java_com_mobileclaw_app_ZeroClawBackend_getGatewayApiToken(
+    mut env: JNIEnv,
+    _class: JClass,
+    handle_id: jlong,
+) -> jstring {
+    let result = {
+        let handles = AGENT_HANDLES.lock().unwrap();
+        let handle = match handles.as_ref().and_then(|m| m.get(&handle_id)) {
+            Some(h) => h,
+            None => {
+                let _ = env.throw_new("java/lang/RuntimeException", "Invalid handle ID");
+                return JObject::null().into_raw();
+            }
+        };
+        crate::gateway::auth::api_token(&handle.config)
+    };
+
+    let token = match result {
+        Ok(token) => token,
+        Err(e) => {
+            let _ = env.throw_new("java/lang/RuntimeException", format!("{e:#}"));
+            return JObject::null().into_raw();
+        }
+    };
+
+    match env.new_string(token) {
+        Ok(s) => s.into_raw(),
+        Err(e) => {
+            let _ = env.throw_new(
+                "java/lang/RuntimeException",
+                format!("Failed to create token string: {}", e),
+            );
+            JObject::null().into_raw()
+        }
+    }
+}
+
+/// Issue a short-lived gateway pairing code for a QR code
+///
+/// `url` is the address companion apps should pair with (e.g. the device's
+/// LAN address); empty uses the configured host. Returns the offer as JSON:
+/// `{code, expires_in_secs, url, fingerprint?, uri}`, where `uri` is what the
+/// QR code should encode.
+#[no_mangle]
+pub extern "C" fn Java_com_mobileclaw_app_ZeroClawBackend_createGatewayPairingOffer(
+    mut env: JNIEnv,
+    _class: JClass,
+    handle_id: jlong,
+    url: JString,
+) -> jstring {
+    let url: String = match env.get_string(&url) {
+        Ok(s) => s.into(),
+        Err(e) => {
+            let _ = env.throw_new("java/lang/RuntimeException", format!("Invalid URL: {}", e));
+            return JObject::null().into_raw();
+        }
+    };
+
+    let result = {
+        let handles = AGENT_HANDLES.lock().unwrap();
+        let handle = match handles.as_ref().and_then(|m| m.get(&handle_id)) {
+            Some(h) => h,
+            None => {
+                let _ = env.throw_new("java/lang/RuntimeException", "Invalid handle ID");
+                return JObject::null().into_raw();
+            }
+        };
+        let url = if url.trim().is_empty() {
+            crate::gateway::pairing::gateway_url(&handle.config)
+        } else {
+            url
+        };
+        crate::gateway::pairing::active()
+            .ok_or_else(|| anyhow::anyhow!("Gateway is not running"))
+            .and_then(|guard| crate::gateway::pairing::offer(&handle.config, &guard, &url))
+            .and_then(|offer| Ok(serde_json::to_string(&offer)?))
+    };
+
+    let offer = match result {
+        Ok(offer) => offer,
+        Err(e) => {
+            let _ = env.throw_new("java/lang/RuntimeException", format!("{e:#}"));
+            return JObject::null().into_raw();
+        }
+    };
+
+    match env.new_string(offer) {
+        Ok(s) => s.into_raw(),
+        Err(e) => {
+            let _ = env.throw_new(
+                "java/lang/RuntimeException",
+                format!("Failed to create offer string: {}", e),
+            );
+            JObject::null().into_raw()
+        }
+    }
+}
+
 /// Execute a tool action
 ///
 /// This allows direct tool execution through the agent runtime.
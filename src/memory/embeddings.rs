@@ -1,4 +1,7 @@
+use crate::providers::traits::parse_openai_embeddings;
+use crate::providers::{Provider, ProviderRuntimeOptions};
 use async_trait::async_trait;
+use std::path::Path;
 
 /// Trait for embedding providers — convert text to vectors
 #[async_trait]
@@ -129,28 +132,122 @@ impl EmbeddingProvider for OpenAiEmbedding {
         }
 
         let json: serde_json::Value = resp.json().await?;
-        let data = json
-            .get("data")
+        parse_openai_embeddings(&json)
+    }
+}
+
+// ── On-device provider (host app bridge) ─────────────────────
+
+/// Embeds through the mobile host app (`embed_text` host action), so vectors
+/// are computed by an on-device model without network access.
+pub struct HostEmbedding {
+    model: String,
+    dims: usize,
+}
+
+impl HostEmbedding {
+    pub fn new(model: &str, dims: usize) -> Self {
+        Self {
+            model: model.to_string(),
+            dims,
+        }
+    }
+}
+
+#[async_trait]
+impl EmbeddingProvider for HostEmbedding {
+    fn name(&self) -> &str {
+        "on_device"
+    }
+
+    fn dimensions(&self) -> usize {
+        self.dims
+    }
+
+    async fn embed(&self, texts: &[&str]) -> anyhow::Result<Vec<Vec<f32>>> {
+        if texts.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let payload = serde_json::json!({
+            "model": self.model,
+            "texts": texts,
+        });
+        let reply = tokio::task::spawn_blocking(move || {
+            crate::runtime::host_bridge::call_host("embed_text", payload)
+        })
+        .await??;
+
+        if reply.get("ok").and_then(serde_json::Value::as_bool) == Some(false) {
+            let detail = reply
+                .get("error")
+                .and_then(serde_json::Value::as_str)
+                .unwrap_or("unknown error");
+            anyhow::bail!("On-device embedding failed: {detail}");
+        }
+
+        let data = reply
+            .get("embeddings")
             .and_then(|d| d.as_array())
-            .ok_or_else(|| anyhow::anyhow!("Invalid embedding response: missing 'data'"))?;
-
-        let mut embeddings = Vec::with_capacity(data.len());
-        for item in data {
-            let embedding = item
-                .get("embedding")
-                .and_then(|e| e.as_array())
-                .ok_or_else(|| anyhow::anyhow!("Invalid embedding item"))?;
-
-            #[allow(clippy::cast_possible_truncation)]
-            let vec: Vec<f32> = embedding
-                .iter()
-                .filter_map(|v| v.as_f64().map(|f| f as f32))
-                .collect();
-
-            embeddings.push(vec);
+            .ok_or_else(|| anyhow::anyhow!("Invalid embedding reply: missing 'embeddings'"))?;
+        if data.len() != texts.len() {
+            anyhow::bail!(
+                "Invalid embedding reply: expected {} vectors, got {}",
+                texts.len(),
+                data.len()
+            );
+        }
+
+        data.iter()
+            .map(|item| {
+                let values = item
+                    .as_array()
+                    .ok_or_else(|| anyhow::anyhow!("Invalid embedding item"))?;
+                #[allow(clippy::cast_possible_truncation)]
+                Ok(values
+                    .iter()
+                    .filter_map(|v| v.as_f64().map(|f| f as f32))
+                    .collect())
+            })
+            .collect()
+    }
+}
+
+// ── Chat provider backend (Provider::embed) ──────────────────
+
+/// Embeds through a chat provider's [`Provider::embed`], so any backend
+/// with an embeddings API (Ollama, llama.cpp, OpenAI-compatible services)
+/// can power memory search.
+pub struct ProviderEmbedding {
+    name: String,
+    provider: Box<dyn Provider>,
+    model: String,
+    dims: usize,
+}
+
+impl ProviderEmbedding {
+    pub fn new(name: &str, provider: Box<dyn Provider>, model: &str, dims: usize) -> Self {
+        Self {
+            name: name.to_string(),
+            provider,
+            model: model.to_string(),
+            dims,
         }
+    }
+}
 
-        Ok(embeddings)
+#[async_trait]
+impl EmbeddingProvider for ProviderEmbedding {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn dimensions(&self) -> usize {
+        self.dims
+    }
+
+    async fn embed(&self, texts: &[&str]) -> anyhow::Result<Vec<Vec<f32>>> {
+        self.provider.embed(texts, &self.model).await
     }
 }
 
@@ -161,6 +258,19 @@ pub fn create_embedding_provider(
     api_key: Option<&str>,
     model: &str,
     dims: usize,
+) -> Box<dyn EmbeddingProvider> {
+    create_embedding_provider_in_workspace(provider, api_key, model, dims, None)
+}
+
+/// Like [`create_embedding_provider`], resolving local model paths
+/// (`local-llm`) against `workspace_dir`. Any chat provider name with an
+/// embeddings API is accepted; unknown names fall back to keyword-only search.
+pub fn create_embedding_provider_in_workspace(
+    provider: &str,
+    api_key: Option<&str>,
+    model: &str,
+    dims: usize,
+    workspace_dir: Option<&Path>,
 ) -> Box<dyn EmbeddingProvider> {
     match provider {
         "openai" => {
@@ -181,12 +291,32 @@ pub fn create_embedding_provider(
                 dims,
             ))
         }
+        "on_device" => Box::new(HostEmbedding::new(model, dims)),
         name if name.starts_with("custom:") => {
             let base_url = name.strip_prefix("custom:").unwrap_or("");
             let key = api_key.unwrap_or("");
             Box::new(OpenAiEmbedding::new(base_url, key, model, dims))
         }
-        _ => Box::new(NoopEmbedding),
+        "" | "none" => Box::new(NoopEmbedding),
+        name => {
+            let options = ProviderRuntimeOptions {
+                workspace_dir: workspace_dir.map(Path::to_path_buf),
+                ..ProviderRuntimeOptions::default()
+            };
+            match crate::providers::create_provider_with_options(name, api_key, &options) {
+                Ok(chat) if chat.supports_embeddings() => {
+                    Box::new(ProviderEmbedding::new(name, chat, model, dims))
+                }
+                Ok(_) => {
+                    tracing::warn!(
+                        provider = name,
+                        "Provider has no embeddings API; memory search falls back to keywords"
+                    );
+                    Box::new(NoopEmbedding)
+                }
+                Err(_) => Box::new(NoopEmbedding),
+            }
+        }
     }
 }
 
@@ -233,6 +363,21 @@ mod tests {
         assert_eq!(p.dimensions(), 1536);
     }
 
+    #[test]
+    fn factory_on_device() {
+        let p = create_embedding_provider("on_device", None, "minilm", 384);
+        assert_eq!(p.name(), "on_device");
+        assert_eq!(p.dimensions(), 384);
+    }
+
+    #[tokio::test]
+    async fn on_device_requires_host_bridge() {
+        let p = HostEmbedding::new("minilm", 384);
+        assert!(p.embed(&[]).await.unwrap().is_empty());
+        let err = p.embed(&["hello"]).await.unwrap_err();
+        assert!(err.to_string().contains("host bridge"));
+    }
+
     #[test]
     fn factory_custom_url() {
         let p = create_embedding_provider("custom:http://localhost:1234", None, "model", 768);
@@ -272,10 +417,39 @@ mod tests {
 
     #[test]
     fn factory_unknown_provider_returns_noop() {
-        let p = create_embedding_provider("cohere", None, "model", 1536);
+        let p = create_embedding_provider("not-a-provider", None, "model", 1536);
+        assert_eq!(p.name(), "none");
+    }
+
+    #[test]
+    fn factory_uses_chat_provider_embeddings() {
+        let p = create_embedding_provider("ollama", None, "nomic-embed-text", 768);
+        assert_eq!(p.name(), "ollama");
+        assert_eq!(p.dimensions(), 768);
+
+        let p = create_embedding_provider("cohere", Some("key"), "embed-english-v3.0", 1024);
+        assert_eq!(p.name(), "cohere");
+
+        // Chat-only providers keep keyword search.
+        let p = create_embedding_provider("anthropic", Some("key"), "model", 1536);
         assert_eq!(p.name(), "none");
     }
 
+    #[tokio::test]
+    async fn factory_resolves_local_models_in_workspace() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let p = create_embedding_provider_in_workspace(
+            "local-llm",
+            None,
+            "models/embed.gguf",
+            384,
+            Some(tmp.path()),
+        );
+        assert_eq!(p.name(), "local-llm");
+        let err = p.embed(&["hello"]).await.unwrap_err();
+        assert!(err.to_string().contains("not found"), "{err}");
+    }
+
     #[test]
     fn factory_custom_empty_url() {
         // "custom:" with no URL — should still construct without panic
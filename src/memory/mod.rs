@@ -10,6 +10,7 @@ pub mod none;
 pub mod postgres;
 pub mod qdrant;
 pub mod response_cache;
+pub mod scoped;
 pub mod snapshot;
 pub mod sqlite;
 pub mod traits;
@@ -27,6 +28,7 @@ pub use none::NoneMemory;
 pub use postgres::PostgresMemory;
 pub use qdrant::QdrantMemory;
 pub use response_cache::ResponseCache;
+pub use scoped::ScopedMemory;
 pub use sqlite::SqliteMemory;
 pub use traits::Memory;
 #[allow(unused_imports)]
@@ -246,11 +248,12 @@ pub fn create_memory_with_storage_and_routes(
         resolved_embedding: &ResolvedEmbeddingConfig,
     ) -> anyhow::Result<SqliteMemory> {
         let embedder: Arc<dyn embeddings::EmbeddingProvider> =
-            Arc::from(embeddings::create_embedding_provider(
+            Arc::from(embeddings::create_embedding_provider_in_workspace(
                 &resolved_embedding.provider,
                 resolved_embedding.api_key.as_deref(),
                 &resolved_embedding.model,
                 resolved_embedding.dimensions,
+                Some(workspace_dir),
             ));
 
         #[allow(clippy::cast_possible_truncation)]
@@ -320,11 +323,12 @@ pub fn create_memory_with_storage_and_routes(
             .or_else(|| std::env::var("QDRANT_API_KEY").ok())
             .filter(|s| !s.trim().is_empty());
         let embedder: Arc<dyn embeddings::EmbeddingProvider> =
-            Arc::from(embeddings::create_embedding_provider(
+            Arc::from(embeddings::create_embedding_provider_in_workspace(
                 &resolved_embedding.provider,
                 resolved_embedding.api_key.as_deref(),
                 &resolved_embedding.model,
                 resolved_embedding.dimensions,
+                Some(workspace_dir),
             ));
         tracing::info!(
             "📦 Qdrant memory backend configured (url: {}, collection: {})",
@@ -389,11 +393,12 @@ pub fn create_response_cache(config: &MemoryConfig, workspace_dir: &Path) -> Opt
     ) {
         Ok(cache) => {
             tracing::info!(
-                "💾 Response cache enabled (TTL: {}min, max: {} entries)",
+                "💾 Response cache enabled (TTL: {}min, max: {} entries, temperature <= {})",
                 config.response_cache_ttl_minutes,
-                config.response_cache_max_entries
+                config.response_cache_max_entries,
+                config.response_cache_max_temperature
             );
-            Some(cache)
+            Some(cache.with_max_temperature(config.response_cache_max_temperature))
         }
         Err(e) => {
             tracing::warn!("Response cache disabled due to error: {e}");
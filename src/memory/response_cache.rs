@@ -4,6 +4,9 @@
 //! `(model, system_prompt_hash, user_prompt)`. Entries expire after a
 //! configurable TTL (default: 1 hour). The cache is optional and disabled by
 //! default — users opt in via `[memory] response_cache_enabled = true`.
+//!
+//! Providers consult it through `providers::cached::CachedProvider`, and only
+//! for low-temperature calls whose output is (nearly) deterministic.
 
 use anyhow::Result;
 use chrono::{Duration, Local};
@@ -12,16 +15,30 @@ use rusqlite::{params, Connection};
 use sha2::{Digest, Sha256};
 use std::path::{Path, PathBuf};
 
+/// Highest sampling temperature whose responses are cached by default.
+const DEFAULT_MAX_TEMPERATURE: f64 = 0.2;
+
 /// Response cache backed by a dedicated SQLite database.
 ///
 /// Lives alongside `brain.db` as `response_cache.db` so it can be
 /// independently wiped without touching memories.
 pub struct ResponseCache {
     conn: Mutex<Connection>,
-    #[allow(dead_code)]
     db_path: PathBuf,
     ttl_minutes: i64,
     max_entries: usize,
+    max_temperature: f64,
+}
+
+impl std::fmt::Debug for ResponseCache {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ResponseCache")
+            .field("db_path", &self.db_path)
+            .field("ttl_minutes", &self.ttl_minutes)
+            .field("max_entries", &self.max_entries)
+            .field("max_temperature", &self.max_temperature)
+            .finish_non_exhaustive()
+    }
 }
 
 impl ResponseCache {
@@ -58,9 +75,21 @@ impl ResponseCache {
             db_path,
             ttl_minutes: i64::from(ttl_minutes),
             max_entries,
+            max_temperature: DEFAULT_MAX_TEMPERATURE,
         })
     }
 
+    /// Only cache calls made at or below `max_temperature`.
+    pub fn with_max_temperature(mut self, max_temperature: f64) -> Self {
+        self.max_temperature = max_temperature;
+        self
+    }
+
+    /// Whether a call at `temperature` is deterministic enough to cache.
+    pub fn accepts_temperature(&self, temperature: f64) -> bool {
+        temperature <= self.max_temperature
+    }
+
     /// Build a deterministic cache key from model + system prompt + user prompt.
     pub fn cache_key(model: &str, system_prompt: Option<&str>, user_prompt: &str) -> String {
         let mut hasher = Sha256::new();
@@ -0,0 +1,154 @@
+use super::traits::{Memory, MemoryCategory, MemoryEntry};
+use async_trait::async_trait;
+use std::sync::Arc;
+
+/// A namespace of another memory backend.
+///
+/// Keys are stored as `<namespace>/<key>` with the namespace as session id,
+/// and only entries of the namespace are visible, with the prefix stripped.
+/// The gateway gives each named user one, so people sharing an agent keep
+/// their memories apart.
+pub struct ScopedMemory {
+    inner: Arc<dyn Memory>,
+    namespace: String,
+    prefix: String,
+}
+
+impl ScopedMemory {
+    pub fn new(inner: Arc<dyn Memory>, namespace: &str) -> Self {
+        Self {
+            inner,
+            namespace: namespace.to_string(),
+            prefix: format!("{namespace}/"),
+        }
+    }
+
+    fn scoped_key(&self, key: &str) -> String {
+        format!("{}{key}", self.prefix)
+    }
+
+    /// The entry with its key unprefixed, or `None` if it is outside the
+    /// namespace.
+    fn unscoped(&self, mut entry: MemoryEntry) -> Option<MemoryEntry> {
+        let key = entry.key.strip_prefix(&self.prefix)?.to_string();
+        entry.key = key;
+        Some(entry)
+    }
+}
+
+#[async_trait]
+impl Memory for ScopedMemory {
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    async fn store(
+        &self,
+        key: &str,
+        content: &str,
+        category: MemoryCategory,
+        _session_id: Option<&str>,
+    ) -> anyhow::Result<()> {
+        self.inner
+            .store(
+                &self.scoped_key(key),
+                content,
+                category,
+                Some(&self.namespace),
+            )
+            .await
+    }
+
+    async fn recall(
+        &self,
+        query: &str,
+        limit: usize,
+        _session_id: Option<&str>,
+    ) -> anyhow::Result<Vec<MemoryEntry>> {
+        let entries = self
+            .inner
+            .recall(query, limit, Some(&self.namespace))
+            .await?;
+        Ok(entries
+            .into_iter()
+            .filter_map(|entry| self.unscoped(entry))
+            .take(limit)
+            .collect())
+    }
+
+    async fn get(&self, key: &str) -> anyhow::Result<Option<MemoryEntry>> {
+        let entry = self.inner.get(&self.scoped_key(key)).await?;
+        Ok(entry.and_then(|entry| self.unscoped(entry)))
+    }
+
+    async fn list(
+        &self,
+        category: Option<&MemoryCategory>,
+        _session_id: Option<&str>,
+    ) -> anyhow::Result<Vec<MemoryEntry>> {
+        let entries = self.inner.list(category, Some(&self.namespace)).await?;
+        Ok(entries
+            .into_iter()
+            .filter_map(|entry| self.unscoped(entry))
+            .collect())
+    }
+
+    async fn forget(&self, key: &str) -> anyhow::Result<bool> {
+        self.inner.forget(&self.scoped_key(key)).await
+    }
+
+    async fn count(&self) -> anyhow::Result<usize> {
+        Ok(self.list(None, None).await?.len())
+    }
+
+    async fn health_check(&self) -> bool {
+        self.inner.health_check().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::memory::SqliteMemory;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn namespaces_do_not_see_each_other() {
+        let tmp = TempDir::new().unwrap();
+        let inner: Arc<dyn Memory> = Arc::new(SqliteMemory::new(tmp.path()).unwrap());
+        let alice = ScopedMemory::new(inner.clone(), "user:alice");
+        let bob = ScopedMemory::new(inner.clone(), "user:bob");
+
+        alice
+            .store("diary", "Alice likes green tea", MemoryCategory::Core, None)
+            .await
+            .unwrap();
+        bob.store("diary", "Bob likes coffee", MemoryCategory::Core, None)
+            .await
+            .unwrap();
+        inner
+            .store(
+                "shared",
+                "The wifi password is taped to the fridge",
+                MemoryCategory::Core,
+                None,
+            )
+            .await
+            .unwrap();
+
+        let entry = alice.get("diary").await.unwrap().unwrap();
+        assert_eq!(entry.key, "diary");
+        assert_eq!(entry.content, "Alice likes green tea");
+        assert!(alice.get("shared").await.unwrap().is_none());
+
+        let recalled = bob.recall("likes", 10, None).await.unwrap();
+        assert_eq!(recalled.len(), 1);
+        assert_eq!(recalled[0].content, "Bob likes coffee");
+
+        assert_eq!(alice.count().await.unwrap(), 1);
+        assert_eq!(alice.list(None, None).await.unwrap().len(), 1);
+        assert!(alice.forget("diary").await.unwrap());
+        assert!(bob.get("diary").await.unwrap().is_some());
+        assert_eq!(inner.count().await.unwrap(), 2);
+    }
+}
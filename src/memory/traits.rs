@@ -40,6 +40,19 @@ pub enum MemoryCategory {
     Custom(String),
 }
 
+impl MemoryCategory {
+    /// Parse a category name as accepted by the memory tools. Unknown names
+    /// become [`MemoryCategory::Custom`].
+    pub fn from_name(name: &str) -> Self {
+        match name {
+            "core" => Self::Core,
+            "daily" => Self::Daily,
+            "conversation" => Self::Conversation,
+            other => Self::Custom(other.to_string()),
+        }
+    }
+}
+
 impl std::fmt::Display for MemoryCategory {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -109,6 +122,17 @@ mod tests {
         );
     }
 
+    #[test]
+    fn memory_category_from_name_roundtrips_display() {
+        for name in ["core", "daily", "conversation", "project_notes"] {
+            assert_eq!(MemoryCategory::from_name(name).to_string(), name);
+        }
+        assert_eq!(
+            MemoryCategory::from_name("project_notes"),
+            MemoryCategory::Custom("project_notes".into())
+        );
+    }
+
     #[test]
     fn memory_category_serde_uses_snake_case() {
         let core = serde_json::to_string(&MemoryCategory::Core).unwrap();
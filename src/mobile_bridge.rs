@@ -1,6 +1,13 @@
-use crate::providers::{create_provider_with_url, Provider};
+use crate::config::ReliabilityConfig;
+use crate::providers::traits::StreamOptions;
+use crate::providers::{create_resilient_provider, ChatMessage, Provider};
+use futures_util::StreamExt;
 use serde::{Deserialize, Serialize};
-use std::ffi::{c_char, CStr, CString};
+use std::ffi::{c_char, c_void, CStr, CString};
+
+/// Receives each streamed text delta as a NUL-terminated UTF-8 string, valid
+/// only for the duration of the call, together with the caller's user data.
+pub type MobileclawTokenCallback = extern "C" fn(delta: *const c_char, user_data: *mut c_void);
 
 #[derive(Debug, Deserialize)]
 struct MobileBridgeRequest {
@@ -26,6 +33,43 @@ struct MobileBridgeResponse {
     error: Option<String>,
 }
 
+#[derive(Debug, Deserialize)]
+struct MobileEmbedRequest {
+    texts: Vec<String>,
+    #[serde(default = "default_provider")]
+    provider: String,
+    #[serde(default = "default_embedding_model")]
+    model: String,
+    #[serde(default)]
+    api_url: Option<String>,
+    #[serde(default)]
+    api_key: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+struct MobileEmbedResponse {
+    ok: bool,
+    embeddings: Option<Vec<Vec<f32>>>,
+    error: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct MobileModelsRequest {
+    #[serde(default = "default_provider")]
+    provider: String,
+    #[serde(default)]
+    api_url: Option<String>,
+    #[serde(default)]
+    api_key: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+struct MobileModelsResponse {
+    ok: bool,
+    models: Option<Vec<String>>,
+    error: Option<String>,
+}
+
 fn default_provider() -> String {
     "ollama".to_string()
 }
@@ -38,6 +82,10 @@ fn default_temperature() -> f64 {
     0.2
 }
 
+fn default_embedding_model() -> String {
+    "nomic-embed-text".to_string()
+}
+
 fn to_c_string(value: &str) -> *mut c_char {
     match CString::new(value) {
         Ok(s) => s.into_raw(),
@@ -72,10 +120,11 @@ fn handle_request_json(request_json: &str) -> String {
 }
 
 fn run_chat(request: MobileBridgeRequest) -> anyhow::Result<String> {
-    let provider: Box<dyn Provider> = create_provider_with_url(
+    let provider: Box<dyn Provider> = create_resilient_provider(
         request.provider.trim(),
         request.api_key.as_deref().map(str::trim),
         request.api_url.as_deref().map(str::trim),
+        &ReliabilityConfig::default(),
     )?;
 
     let runtime = tokio::runtime::Builder::new_current_thread()
@@ -94,6 +143,151 @@ fn run_chat(request: MobileBridgeRequest) -> anyhow::Result<String> {
     })
 }
 
+fn handle_stream_request_json(request_json: &str, on_delta: impl FnMut(&str)) -> String {
+    let request: MobileBridgeRequest = match serde_json::from_str(request_json) {
+        Ok(req) => req,
+        Err(error) => {
+            return make_response(false, None, Some(format!("invalid request JSON: {error}")));
+        }
+    };
+
+    if request.prompt.trim().is_empty() {
+        return make_response(false, None, Some("prompt must not be empty".to_string()));
+    }
+
+    match run_chat_stream(request, on_delta) {
+        Ok(reply) => make_response(true, Some(reply), None),
+        Err(error) => make_response(false, None, Some(error.to_string())),
+    }
+}
+
+/// Like [`run_chat`], but hands each text delta to `on_delta` as it arrives
+/// and returns the assembled reply.
+fn run_chat_stream(
+    request: MobileBridgeRequest,
+    mut on_delta: impl FnMut(&str),
+) -> anyhow::Result<String> {
+    let provider: Box<dyn Provider> = create_resilient_provider(
+        request.provider.trim(),
+        request.api_key.as_deref().map(str::trim),
+        request.api_url.as_deref().map(str::trim),
+        &ReliabilityConfig::default(),
+    )?;
+
+    let mut messages = Vec::new();
+    if let Some(system_prompt) = request.system_prompt.as_deref() {
+        messages.push(ChatMessage::system(system_prompt));
+    }
+    messages.push(ChatMessage::user(request.prompt.trim()));
+
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()?;
+
+    runtime.block_on(async {
+        let mut stream = provider.chat_stream(
+            &messages,
+            request.model.trim(),
+            request.temperature,
+            StreamOptions::new(true),
+        );
+        let mut reply = String::new();
+        while let Some(chunk) = stream.next().await {
+            let chunk = chunk?;
+            if !chunk.delta.is_empty() {
+                on_delta(&chunk.delta);
+                reply.push_str(&chunk.delta);
+            }
+        }
+        Ok::<_, anyhow::Error>(reply)
+    })
+}
+
+fn make_embed_response(embeddings: Option<Vec<Vec<f32>>>, error: Option<String>) -> String {
+    let response = MobileEmbedResponse {
+        ok: error.is_none(),
+        embeddings,
+        error,
+    };
+    serde_json::to_string(&response).unwrap_or_else(|_| {
+        r#"{"ok":false,"embeddings":null,"error":"serialization failure"}"#.to_string()
+    })
+}
+
+fn handle_embed_request_json(request_json: &str) -> String {
+    let request: MobileEmbedRequest = match serde_json::from_str(request_json) {
+        Ok(req) => req,
+        Err(error) => {
+            return make_embed_response(None, Some(format!("invalid request JSON: {error}")))
+        }
+    };
+
+    if request.texts.is_empty() {
+        return make_embed_response(None, Some("texts must not be empty".to_string()));
+    }
+
+    match run_embed(request) {
+        Ok(embeddings) => make_embed_response(Some(embeddings), None),
+        Err(error) => make_embed_response(None, Some(error.to_string())),
+    }
+}
+
+fn run_embed(request: MobileEmbedRequest) -> anyhow::Result<Vec<Vec<f32>>> {
+    let provider: Box<dyn Provider> = create_resilient_provider(
+        request.provider.trim(),
+        request.api_key.as_deref().map(str::trim),
+        request.api_url.as_deref().map(str::trim),
+        &ReliabilityConfig::default(),
+    )?;
+
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()?;
+
+    let texts: Vec<&str> = request.texts.iter().map(String::as_str).collect();
+    runtime.block_on(provider.embed(&texts, request.model.trim()))
+}
+
+fn make_models_response(models: Option<Vec<String>>, error: Option<String>) -> String {
+    let response = MobileModelsResponse {
+        ok: error.is_none(),
+        models,
+        error,
+    };
+    serde_json::to_string(&response).unwrap_or_else(|_| {
+        r#"{"ok":false,"models":null,"error":"serialization failure"}"#.to_string()
+    })
+}
+
+fn handle_models_request_json(request_json: &str) -> String {
+    let request: MobileModelsRequest = match serde_json::from_str(request_json) {
+        Ok(req) => req,
+        Err(error) => {
+            return make_models_response(None, Some(format!("invalid request JSON: {error}")))
+        }
+    };
+
+    match run_list_models(&request) {
+        Ok(models) => make_models_response(Some(models), None),
+        Err(error) => make_models_response(None, Some(error.to_string())),
+    }
+}
+
+fn run_list_models(request: &MobileModelsRequest) -> anyhow::Result<Vec<String>> {
+    let provider: Box<dyn Provider> = create_resilient_provider(
+        request.provider.trim(),
+        request.api_key.as_deref().map(str::trim),
+        request.api_url.as_deref().map(str::trim),
+        &ReliabilityConfig::default(),
+    )?;
+
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()?;
+
+    runtime.block_on(provider.list_models())
+}
+
 #[no_mangle]
 pub extern "C" fn mobileclaw_chat_json(request_json_ptr: *const c_char) -> *mut c_char {
     if request_json_ptr.is_null() {
@@ -119,6 +313,107 @@ pub extern "C" fn mobileclaw_chat_json(request_json_ptr: *const c_char) -> *mut
     to_c_string(&handle_request_json(request_json))
 }
 
+/// Streaming variant of [`mobileclaw_chat_json`]: `callback` is invoked on
+/// the calling thread for every text delta, then the final JSON response
+/// (with the full reply) is returned. Free the result with
+/// [`mobileclaw_free_cstring`].
+///
+/// # Safety
+///
+/// `request_json_ptr` must be null or point to a NUL-terminated string that
+/// stays valid for the whole call. `callback`, when set, must be safe to call
+/// with `user_data` from the calling thread until this function returns.
+#[no_mangle]
+pub unsafe extern "C" fn mobileclaw_chat_stream_json(
+    request_json_ptr: *const c_char,
+    callback: Option<MobileclawTokenCallback>,
+    user_data: *mut c_void,
+) -> *mut c_char {
+    if request_json_ptr.is_null() {
+        return to_c_string(&make_response(
+            false,
+            None,
+            Some("null request pointer".to_string()),
+        ));
+    }
+
+    let request_raw = unsafe { CStr::from_ptr(request_json_ptr) };
+    let request_json = match request_raw.to_str() {
+        Ok(value) => value,
+        Err(_) => {
+            return to_c_string(&make_response(
+                false,
+                None,
+                Some("request is not valid UTF-8".to_string()),
+            ))
+        }
+    };
+
+    to_c_string(&handle_stream_request_json(request_json, |delta| {
+        if let (Some(callback), Ok(delta)) = (callback, CString::new(delta)) {
+            callback(delta.as_ptr(), user_data);
+        }
+    }))
+}
+
+/// Embed `texts` with the requested provider and return
+/// `{"ok", "embeddings", "error"}` JSON, one vector per input text. Free the
+/// result with [`mobileclaw_free_cstring`].
+///
+/// # Safety
+///
+/// `request_json_ptr` must be null or point to a NUL-terminated string that
+/// stays valid for the whole call.
+#[no_mangle]
+pub unsafe extern "C" fn mobileclaw_embed_json(request_json_ptr: *const c_char) -> *mut c_char {
+    if request_json_ptr.is_null() {
+        return to_c_string(&make_embed_response(
+            None,
+            Some("null request pointer".to_string()),
+        ));
+    }
+
+    let request_raw = unsafe { CStr::from_ptr(request_json_ptr) };
+    let Ok(request_json) = request_raw.to_str() else {
+        return to_c_string(&make_embed_response(
+            None,
+            Some("request is not valid UTF-8".to_string()),
+        ));
+    };
+
+    to_c_string(&handle_embed_request_json(request_json))
+}
+
+/// List the model IDs the requested provider offers and return
+/// `{"ok", "models", "error"}` JSON. Free the result with
+/// [`mobileclaw_free_cstring`].
+///
+/// # Safety
+///
+/// `request_json_ptr` must be null or point to a NUL-terminated string that
+/// stays valid for the whole call.
+#[no_mangle]
+pub unsafe extern "C" fn mobileclaw_list_models_json(
+    request_json_ptr: *const c_char,
+) -> *mut c_char {
+    if request_json_ptr.is_null() {
+        return to_c_string(&make_models_response(
+            None,
+            Some("null request pointer".to_string()),
+        ));
+    }
+
+    let request_raw = unsafe { CStr::from_ptr(request_json_ptr) };
+    let Ok(request_json) = request_raw.to_str() else {
+        return to_c_string(&make_models_response(
+            None,
+            Some("request is not valid UTF-8".to_string()),
+        ));
+    };
+
+    to_c_string(&handle_models_request_json(request_json))
+}
+
 #[no_mangle]
 pub extern "C" fn mobileclaw_free_cstring(ptr: *mut c_char) {
     if ptr.is_null() {
@@ -153,3 +448,82 @@ pub extern "system" fn Java_com_zeroclaw_app_NativeZeroClawBridge_mobileclawChat
         .map(|s| s.into_raw())
         .unwrap_or(std::ptr::null_mut())
 }
+
+/// Streaming JNI entry point. `listener` must implement
+/// `fun onToken(delta: String)`; it is called on the calling thread for each
+/// text delta before the final JSON response is returned.
+#[cfg(target_os = "android")]
+#[no_mangle]
+pub extern "system" fn Java_com_zeroclaw_app_NativeZeroClawBridge_mobileclawChatStreamJson(
+    mut env: jni::JNIEnv,
+    _class: jni::objects::JClass,
+    request_json: jni::objects::JString,
+    listener: jni::objects::JObject,
+) -> jni::sys::jstring {
+    let request = match env.get_string(&request_json) {
+        Ok(value) => value.to_string_lossy().into_owned(),
+        Err(error) => {
+            let message =
+                make_response(false, None, Some(format!("jni get_string failed: {error}")));
+            return env
+                .new_string(message)
+                .map(|s| s.into_raw())
+                .unwrap_or(std::ptr::null_mut());
+        }
+    };
+
+    let response = handle_stream_request_json(&request, |delta| {
+        if listener.is_null() {
+            return;
+        }
+        let Ok(delta) = env.new_string(delta) else {
+            return;
+        };
+        let delivered = env.call_method(
+            &listener,
+            "onToken",
+            "(Ljava/lang/String;)V",
+            &[jni::objects::JValue::Object(&delta)],
+        );
+        if delivered.is_err() {
+            // Keep streaming; a throwing listener must not poison later JNI calls.
+            let _ = env.exception_clear();
+        }
+        let _ = env.delete_local_ref(delta);
+    });
+    env.new_string(response)
+        .map(|s| s.into_raw())
+        .unwrap_or(std::ptr::null_mut())
+}
+
+#[cfg(target_os = "android")]
+#[no_mangle]
+pub extern "system" fn Java_com_zeroclaw_app_NativeZeroClawBridge_mobileclawEmbedJson(
+    mut env: jni::JNIEnv,
+    _class: jni::objects::JClass,
+    request_json: jni::objects::JString,
+) -> jni::sys::jstring {
+    let response = match env.get_string(&request_json) {
+        Ok(value) => handle_embed_request_json(&value.to_string_lossy()),
+        Err(error) => make_embed_response(None, Some(format!("jni get_string failed: {error}"))),
+    };
+    env.new_string(response)
+        .map(|s| s.into_raw())
+        .unwrap_or(std::ptr::null_mut())
+}
+
+#[cfg(target_os = "android")]
+#[no_mangle]
+pub extern "system" fn Java_com_zeroclaw_app_NativeZeroClawBridge_mobileclawListModelsJson(
+    mut env: jni::JNIEnv,
+    _class: jni::objects::JClass,
+    request_json: jni::objects::JString,
+) -> jni::sys::jstring {
+    let response = match env.get_string(&request_json) {
+        Ok(value) => handle_models_request_json(&value.to_string_lossy()),
+        Err(error) => make_models_response(None, Some(format!("jni get_string failed: {error}"))),
+    };
+    env.new_string(response)
+        .map(|s| s.into_raw())
+        .unwrap_or(std::ptr::null_mut())
+}
@@ -135,6 +135,8 @@ pub async fn prepare_messages_for_provider(
         });
     }
 
+    crate::runtime::network::ensure_transfer_allowed(crate::runtime::LargeTransfer::ImageUpload)?;
+
     let remote_client = build_runtime_proxy_client_with_timeouts("provider.ollama", 30, 10);
 
     let mut normalized_messages = Vec::with_capacity(messages.len());
@@ -170,7 +172,7 @@ pub async fn prepare_messages_for_provider(
     })
 }
 
-fn compose_multimodal_message(text: &str, data_uris: &[String]) -> String {
+pub fn compose_multimodal_message(text: &str, data_uris: &[String]) -> String {
     let mut content = String::new();
     let trimmed = text.trim();
 
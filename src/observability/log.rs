@@ -101,6 +101,10 @@ impl Observer for LogObserver {
             ObserverMetric::QueueDepth(d) => {
                 info!(depth = d, "metric.queue_depth");
             }
+            ObserverMetric::ChannelLag { channel, lag } => {
+                let ms = u64::try_from(lag.as_millis()).unwrap_or(u64::MAX);
+                info!(channel = %channel, lag_ms = ms, "metric.channel_lag");
+            }
         }
     }
 
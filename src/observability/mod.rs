@@ -7,6 +7,7 @@ pub mod prometheus;
 pub mod runtime_trace;
 pub mod traits;
 pub mod verbose;
+pub mod wire_log;
 
 #[allow(unused_imports)]
 pub use self::log::LogObserver;
@@ -26,7 +27,7 @@ use crate::config::ObservabilityConfig;
 pub fn create_observer(config: &ObservabilityConfig) -> Box<dyn Observer> {
     match config.backend.as_str() {
         "log" => Box::new(LogObserver::new()),
-        "prometheus" => Box::new(PrometheusObserver::new()),
+        "prometheus" => Box::new(PrometheusObserver::global().clone()),
         "otel" | "opentelemetry" | "otlp" => {
             #[cfg(feature = "observability-otel")]
             match OtelObserver::new(
@@ -27,6 +27,7 @@ pub struct OtelObserver {
     tokens_used: Counter<u64>,
     active_sessions: Gauge<u64>,
     queue_depth: Gauge<u64>,
+    channel_lag: Histogram<f64>,
 }
 
 impl OtelObserver {
@@ -152,6 +153,12 @@ impl OtelObserver {
             .with_description("Current message queue depth")
             .build();
 
+        let channel_lag = meter
+            .f64_histogram("zeroclaw.channel.lag")
+            .with_description("Delay between a channel message being sent and being processed")
+            .with_unit("s")
+            .build();
+
         Ok(Self {
             tracer_provider,
             meter_provider: meter_provider_clone,
@@ -168,6 +175,7 @@ impl OtelObserver {
             tokens_used,
             active_sessions,
             queue_depth,
+            channel_lag,
         })
     }
 }
@@ -352,6 +360,12 @@ impl Observer for OtelObserver {
             ObserverMetric::QueueDepth(d) => {
                 self.queue_depth.record(*d as u64, &[]);
             }
+            ObserverMetric::ChannelLag { channel, lag } => {
+                self.channel_lag.record(
+                    lag.as_secs_f64(),
+                    &[KeyValue::new("channel", channel.clone())],
+                );
+            }
         }
     }
 
@@ -1,9 +1,16 @@
 use super::traits::{Observer, ObserverEvent, ObserverMetric};
 use prometheus::{
-    Encoder, GaugeVec, Histogram, HistogramOpts, HistogramVec, IntCounterVec, Registry, TextEncoder,
+    Encoder, GaugeVec, Histogram, HistogramOpts, HistogramVec, IntCounterVec, IntGaugeVec,
+    Registry, TextEncoder,
 };
+use std::sync::OnceLock;
+use std::time::Duration;
 
 /// Prometheus-backed observer — exposes metrics for scraping via `/metrics`.
+///
+/// Clones share one registry. [`PrometheusObserver::global`] is the registry
+/// the `prometheus` backend records into and the gateway serves.
+#[derive(Clone)]
 pub struct PrometheusObserver {
     registry: Registry,
 
@@ -16,16 +23,22 @@ pub struct PrometheusObserver {
     channel_messages: IntCounterVec,
     heartbeat_ticks: prometheus::IntCounter,
     errors: IntCounterVec,
+    gateway_requests: IntCounterVec,
 
     // Histograms
     agent_duration: HistogramVec,
     tool_duration: HistogramVec,
     request_latency: Histogram,
+    provider_latency: HistogramVec,
+    gateway_request_duration: HistogramVec,
+    channel_lag: HistogramVec,
 
     // Gauges
     tokens_used: prometheus::IntGauge,
     active_sessions: GaugeVec,
     queue_depth: GaugeVec,
+    resident_memory: prometheus::IntGauge,
+    memory_entries: IntGaugeVec,
 }
 
 impl PrometheusObserver {
@@ -81,6 +94,15 @@ impl PrometheusObserver {
         )
         .expect("valid metric");
 
+        let gateway_requests = IntCounterVec::new(
+            prometheus::Opts::new(
+                "zeroclaw_gateway_requests_total",
+                "Total gateway HTTP requests",
+            ),
+            &["method", "route", "status"],
+        )
+        .expect("valid metric");
+
         let agent_duration = HistogramVec::new(
             HistogramOpts::new(
                 "zeroclaw_agent_duration_seconds",
@@ -110,6 +132,36 @@ impl PrometheusObserver {
         )
         .expect("valid metric");
 
+        let provider_latency = HistogramVec::new(
+            HistogramOpts::new(
+                "zeroclaw_provider_latency_seconds",
+                "LLM provider response latency in seconds",
+            )
+            .buckets(vec![0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 30.0, 60.0, 120.0]),
+            &["provider", "model"],
+        )
+        .expect("valid metric");
+
+        let gateway_request_duration = HistogramVec::new(
+            HistogramOpts::new(
+                "zeroclaw_gateway_request_duration_seconds",
+                "Gateway HTTP request duration in seconds",
+            )
+            .buckets(vec![0.005, 0.01, 0.05, 0.1, 0.5, 1.0, 5.0, 30.0, 120.0]),
+            &["route"],
+        )
+        .expect("valid metric");
+
+        let channel_lag = HistogramVec::new(
+            HistogramOpts::new(
+                "zeroclaw_channel_lag_seconds",
+                "Delay between a channel message being sent and being processed",
+            )
+            .buckets(vec![0.5, 1.0, 2.5, 5.0, 15.0, 60.0, 300.0, 1800.0]),
+            &["channel"],
+        )
+        .expect("valid metric");
+
         let tokens_used = prometheus::IntGauge::new(
             "zeroclaw_tokens_used_last",
             "Tokens used in the last request",
@@ -128,6 +180,21 @@ impl PrometheusObserver {
         )
         .expect("valid metric");
 
+        let resident_memory = prometheus::IntGauge::new(
+            "zeroclaw_process_resident_memory_bytes",
+            "Resident memory of the agent process in bytes",
+        )
+        .expect("valid metric");
+
+        let memory_entries = IntGaugeVec::new(
+            prometheus::Opts::new(
+                "zeroclaw_memory_entries",
+                "Entries in the agent memory store",
+            ),
+            &["backend"],
+        )
+        .expect("valid metric");
+
         // Register all metrics
         registry.register(Box::new(agent_starts.clone())).ok();
         registry.register(Box::new(llm_requests.clone())).ok();
@@ -145,6 +212,14 @@ impl PrometheusObserver {
         registry.register(Box::new(tokens_used.clone())).ok();
         registry.register(Box::new(active_sessions.clone())).ok();
         registry.register(Box::new(queue_depth.clone())).ok();
+        registry.register(Box::new(gateway_requests.clone())).ok();
+        registry.register(Box::new(provider_latency.clone())).ok();
+        registry
+            .register(Box::new(gateway_request_duration.clone()))
+            .ok();
+        registry.register(Box::new(channel_lag.clone())).ok();
+        registry.register(Box::new(resident_memory.clone())).ok();
+        registry.register(Box::new(memory_entries.clone())).ok();
 
         Self {
             registry,
@@ -156,12 +231,55 @@ impl PrometheusObserver {
             channel_messages,
             heartbeat_ticks,
             errors,
+            gateway_requests,
             agent_duration,
             tool_duration,
             request_latency,
+            provider_latency,
+            gateway_request_duration,
+            channel_lag,
             tokens_used,
             active_sessions,
             queue_depth,
+            resident_memory,
+            memory_entries,
+        }
+    }
+
+    /// The process-wide registry used by the `prometheus` backend.
+    pub fn global() -> &'static Self {
+        static GLOBAL: OnceLock<PrometheusObserver> = OnceLock::new();
+        GLOBAL.get_or_init(Self::new)
+    }
+
+    /// Count a gateway request by method, matched route and status code.
+    pub fn record_gateway_request(
+        &self,
+        method: &str,
+        route: &str,
+        status: u16,
+        duration: Duration,
+    ) {
+        self.gateway_requests
+            .with_label_values(&[method, route, &status.to_string()])
+            .inc();
+        self.gateway_request_duration
+            .with_label_values(&[route])
+            .observe(duration.as_secs_f64());
+    }
+
+    /// Set the number of entries in the memory store.
+    pub fn set_memory_entries(&self, backend: &str, count: usize) {
+        self.memory_entries
+            .with_label_values(&[backend])
+            .set(i64::try_from(count).unwrap_or(i64::MAX));
+    }
+
+    /// Refresh process gauges (resident memory) before a scrape.
+    pub fn update_process_metrics(&self) {
+        if let Some(bytes) = resident_memory_bytes() {
+            self.resident_memory
+                .set(i64::try_from(bytes).unwrap_or(i64::MAX));
         }
     }
 
@@ -201,6 +319,7 @@ impl Observer for PrometheusObserver {
             ObserverEvent::LlmResponse {
                 provider,
                 model,
+                duration,
                 success,
                 input_tokens,
                 output_tokens,
@@ -210,6 +329,9 @@ impl Observer for PrometheusObserver {
                 self.llm_requests
                     .with_label_values(&[provider.as_str(), model.as_str(), success_str])
                     .inc();
+                self.provider_latency
+                    .with_label_values(&[provider.as_str(), model.as_str()])
+                    .observe(duration.as_secs_f64());
                 if let Some(input) = input_tokens {
                     self.tokens_input_total
                         .with_label_values(&[provider.as_str(), model.as_str()])
@@ -272,6 +394,11 @@ impl Observer for PrometheusObserver {
                     .with_label_values(&[] as &[&str])
                     .set(*d as f64);
             }
+            ObserverMetric::ChannelLag { channel, lag } => {
+                self.channel_lag
+                    .with_label_values(&[channel.as_str()])
+                    .observe(lag.as_secs_f64());
+            }
         }
     }
 
@@ -284,10 +411,27 @@ impl Observer for PrometheusObserver {
     }
 }
 
+/// Resident set size from `/proc/self/status`, where available.
+fn resident_memory_bytes() -> Option<u64> {
+    let status = std::fs::read_to_string("/proc/self/status").ok()?;
+    parse_vm_rss(&status)
+}
+
+fn parse_vm_rss(status: &str) -> Option<u64> {
+    let kib = status
+        .lines()
+        .find_map(|line| line.strip_prefix("VmRSS:"))?
+        .trim()
+        .trim_end_matches("kB")
+        .trim()
+        .parse::<u64>()
+        .ok()?;
+    Some(kib * 1024)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::time::Duration;
 
     #[test]
     fn prometheus_observer_name() {
@@ -344,6 +488,47 @@ mod tests {
         obs.record_metric(&ObserverMetric::TokensUsed(0));
         obs.record_metric(&ObserverMetric::ActiveSessions(3));
         obs.record_metric(&ObserverMetric::QueueDepth(42));
+        obs.record_metric(&ObserverMetric::ChannelLag {
+            channel: "telegram".into(),
+            lag: Duration::from_secs(3),
+        });
+    }
+
+    #[test]
+    fn gateway_provider_and_memory_metrics_are_exported() {
+        let obs = PrometheusObserver::new();
+        obs.record_gateway_request("POST", "/agent/message", 200, Duration::from_millis(40));
+        obs.record_gateway_request("POST", "/agent/message", 429, Duration::from_millis(1));
+        obs.record_event(&ObserverEvent::LlmResponse {
+            provider: "openrouter".into(),
+            model: "claude-sonnet".into(),
+            duration: Duration::from_millis(1200),
+            success: true,
+            error_message: None,
+            input_tokens: None,
+            output_tokens: None,
+        });
+        obs.set_memory_entries("sqlite", 42);
+        obs.update_process_metrics();
+
+        let output = obs.encode();
+        assert!(output.contains(
+            r#"zeroclaw_gateway_requests_total{method="POST",route="/agent/message",status="200"} 1"#
+        ));
+        assert!(output.contains(
+            r#"zeroclaw_gateway_requests_total{method="POST",route="/agent/message",status="429"} 1"#
+        ));
+        assert!(output.contains(
+            r#"zeroclaw_provider_latency_seconds_count{model="claude-sonnet",provider="openrouter"} 1"#
+        ));
+        assert!(output.contains(r#"zeroclaw_memory_entries{backend="sqlite"} 42"#));
+    }
+
+    #[test]
+    fn vm_rss_is_parsed_from_proc_status() {
+        let status = "Name:\tzeroclaw\nVmRSS:\t  51200 kB\nThreads:\t8\n";
+        assert_eq!(parse_vm_rss(status), Some(51200 * 1024));
+        assert_eq!(parse_vm_rss("Name:\tzeroclaw\n"), None);
     }
 
     #[test]
@@ -326,6 +326,9 @@ mod tests {
             runtime_trace_mode: "rolling".to_string(),
             runtime_trace_path: "state/runtime-trace.jsonl".to_string(),
             runtime_trace_max_entries: 3,
+            wire_log: false,
+            wire_log_path: "state/wire-log".to_string(),
+            wire_log_retention_days: 3,
         }
     }
 
@@ -81,6 +81,8 @@ pub enum ObserverMetric {
     ActiveSessions(u64),
     /// Current depth of the inbound message queue.
     QueueDepth(u64),
+    /// Delay between a channel message being sent and the agent picking it up.
+    ChannelLag { channel: String, lag: Duration },
 }
 
 /// Core observability trait for recording agent runtime telemetry.
@@ -0,0 +1,263 @@
+//! Opt-in wire log of provider requests and responses.
+//!
+//! When `observability.wire_log = true`, every call that reaches a provider
+//! is appended to a daily JSONL file under `wire_log_path`, so a surprising
+//! reply can be traced back to the exact prompt, tool set and model output
+//! on device. Every string is redacted before it is written: credentials
+//! (known key prefixes, `key=value` secrets, private keys, JWTs, database
+//! URLs) and PII (email addresses, phone and card numbers); inline image data
+//! is reduced to its size.
+
+use crate::config::ObservabilityConfig;
+use crate::security::{LeakDetector, LeakResult};
+use anyhow::Result;
+use chrono::{NaiveDate, Utc};
+use regex::Regex;
+use serde_json::Value;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, LazyLock, RwLock};
+
+const DEFAULT_WIRE_LOG_REL_PATH: &str = "state/wire-log";
+const FILE_PREFIX: &str = "wire-";
+const FILE_SUFFIX: &str = ".jsonl";
+
+struct WireLogger {
+    dir: PathBuf,
+    retention_days: u32,
+    /// Serializes writes and remembers the day files were last pruned.
+    last_pruned: std::sync::Mutex<Option<NaiveDate>>,
+}
+
+impl WireLogger {
+    fn append(&self, entry: &Value) -> Result<()> {
+        let mut last_pruned = self.last_pruned.lock().unwrap_or_else(|e| e.into_inner());
+        fs::create_dir_all(&self.dir)?;
+
+        let today = Utc::now().date_naive();
+        if *last_pruned != Some(today) {
+            self.prune(today);
+            *last_pruned = Some(today);
+        }
+
+        let path = self.dir.join(format!(
+            "{FILE_PREFIX}{}{FILE_SUFFIX}",
+            today.format("%Y-%m-%d")
+        ));
+        let mut options = OpenOptions::new();
+        options.create(true).append(true);
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::OpenOptionsExt;
+            options.mode(0o600);
+        }
+
+        let mut file = options.open(&path)?;
+        writeln!(file, "{}", serde_json::to_string(entry)?)?;
+        Ok(())
+    }
+
+    /// Delete daily files older than the retention window.
+    fn prune(&self, today: NaiveDate) {
+        let Ok(entries) = fs::read_dir(&self.dir) else {
+            return;
+        };
+        let keep_days = i64::from(self.retention_days.max(1));
+        for entry in entries.flatten() {
+            let name = entry.file_name();
+            let Some(date) = name
+                .to_str()
+                .and_then(|name| name.strip_prefix(FILE_PREFIX))
+                .and_then(|name| name.strip_suffix(FILE_SUFFIX))
+                .and_then(|date| NaiveDate::parse_from_str(date, "%Y-%m-%d").ok())
+            else {
+                continue;
+            };
+            if (today - date).num_days() >= keep_days {
+                if let Err(e) = fs::remove_file(entry.path()) {
+                    tracing::warn!("Failed to prune wire log {}: {e}", entry.path().display());
+                }
+            }
+        }
+    }
+}
+
+static WIRE_LOGGER: LazyLock<RwLock<Option<Arc<WireLogger>>>> = LazyLock::new(|| RwLock::new(None));
+
+/// Resolve the wire log directory from config.
+pub fn resolve_wire_log_dir(config: &ObservabilityConfig, workspace_dir: &Path) -> PathBuf {
+    let raw = config.wire_log_path.trim();
+    if raw.is_empty() {
+        return workspace_dir.join(DEFAULT_WIRE_LOG_REL_PATH);
+    }
+
+    let configured = PathBuf::from(raw);
+    if configured.is_absolute() {
+        configured
+    } else {
+        workspace_dir.join(configured)
+    }
+}
+
+/// Initialize (or disable) the wire log.
+pub fn init_from_config(config: &ObservabilityConfig, workspace_dir: &Path) {
+    let logger = config.wire_log.then(|| {
+        let dir = resolve_wire_log_dir(config, workspace_dir);
+        tracing::warn!(
+            dir = %dir.display(),
+            "Provider wire log enabled; prompts and replies are written to disk (redacted)"
+        );
+        Arc::new(WireLogger {
+            dir,
+            retention_days: config.wire_log_retention_days,
+            last_pruned: std::sync::Mutex::new(None),
+        })
+    });
+
+    let mut guard = WIRE_LOGGER.write().unwrap_or_else(|e| e.into_inner());
+    *guard = logger;
+}
+
+/// Whether provider calls are currently being logged.
+pub fn enabled() -> bool {
+    WIRE_LOGGER
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .is_some()
+}
+
+/// Append one redacted entry. `direction` is `request`, `response` or
+/// `error`; `call_id` links a response to its request.
+pub fn record(call_id: &str, direction: &str, provider: &str, model: &str, mut payload: Value) {
+    let logger = WIRE_LOGGER
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .clone();
+    let Some(logger) = logger else {
+        return;
+    };
+
+    redact_value(&mut payload);
+    let entry = serde_json::json!({
+        "timestamp": Utc::now().to_rfc3339(),
+        "call_id": call_id,
+        "direction": direction,
+        "provider": provider,
+        "model": model,
+        "payload": payload,
+    });
+    if let Err(e) = logger.append(&entry) {
+        tracing::warn!("Failed to write provider wire log: {e}");
+    }
+}
+
+static IMAGE_DATA: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"data:([\w.+-]+/[\w.+-]+);base64,([A-Za-z0-9+/=]+)").unwrap());
+
+static PII_PATTERNS: LazyLock<Vec<(Regex, &'static str)>> = LazyLock::new(|| {
+    vec![
+        (
+            Regex::new(r"[A-Za-z0-9._%+-]+@[A-Za-z0-9.-]+\.[A-Za-z]{2,}").unwrap(),
+            "[REDACTED_EMAIL]",
+        ),
+        (
+            Regex::new(r"\b(?:\d[ -]?){12,18}\d\b").unwrap(),
+            "[REDACTED_CARD]",
+        ),
+        (
+            Regex::new(r"\+?\(?\d{1,4}\)?[ .-]?\(?\d{2,4}\)?[ .-]\d{3,4}[ .-]?\d{3,4}\b").unwrap(),
+            "[REDACTED_PHONE]",
+        ),
+    ]
+});
+
+/// Redact credentials and PII from free text.
+pub fn redact_text(input: &str) -> String {
+    let mut text = IMAGE_DATA
+        .replace_all(input, |caps: &regex::Captures| {
+            format!("data:{};base64,[{} bytes]", &caps[1], caps[2].len())
+        })
+        .into_owned();
+    text = crate::providers::scrub_secret_patterns(&text);
+    text = crate::agent::loop_::scrub_credentials(&text);
+    if let LeakResult::Detected { redacted, .. } = LeakDetector::new().scan(&text) {
+        text = redacted;
+    }
+    for (regex, replacement) in PII_PATTERNS.iter() {
+        text = regex.replace_all(&text, *replacement).into_owned();
+    }
+    text
+}
+
+/// Redact every string inside a JSON value.
+pub fn redact_value(value: &mut Value) {
+    match value {
+        Value::String(text) => *text = redact_text(text),
+        Value::Array(items) => items.iter_mut().for_each(redact_value),
+        Value::Object(map) => map.values_mut().for_each(redact_value),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn redacts_credentials_and_pii() {
+        let redacted = redact_text(
+            "key sk-abcdefghijklmnop1234, api_key=\"supersecretvalue\", mail jane.doe@example.com, \
+             call +1 415-555-0134, card 4111 1111 1111 1111, 3 apples",
+        );
+        for leaked in [
+            "sk-abcdefghijklmnop1234",
+            "supersecretvalue",
+            "jane.doe@example.com",
+            "555-0134",
+            "4111 1111",
+        ] {
+            assert!(!redacted.contains(leaked), "{leaked} leaked: {redacted}");
+        }
+        assert!(redacted.contains("[REDACTED_EMAIL]"));
+        assert!(redacted.contains("3 apples"));
+
+        let image = redact_text("[IMAGE:data:image/png;base64,iVBORw0KGgoAAAANSUhEUg==]");
+        assert_eq!(image, "[IMAGE:data:image/png;base64,[24 bytes]]");
+    }
+
+    #[test]
+    fn writes_redacted_entries_to_daily_files_and_prunes_old_ones() {
+        let workspace = tempfile::tempdir().unwrap();
+        let config = ObservabilityConfig {
+            wire_log: true,
+            wire_log_retention_days: 2,
+            ..ObservabilityConfig::default()
+        };
+        let dir = resolve_wire_log_dir(&config, workspace.path());
+        fs::create_dir_all(&dir).unwrap();
+        let stale = dir.join("wire-2000-01-01.jsonl");
+        fs::write(&stale, "{}\n").unwrap();
+
+        let logger = WireLogger {
+            dir: dir.clone(),
+            retention_days: config.wire_log_retention_days,
+            last_pruned: std::sync::Mutex::new(None),
+        };
+        let mut payload = serde_json::json!({
+            "messages": [{"role": "user", "content": "I am bob@example.org"}]
+        });
+        redact_value(&mut payload);
+        logger.append(&payload).unwrap();
+
+        assert!(!stale.exists());
+        let today = dir.join(format!(
+            "wire-{}.jsonl",
+            Utc::now().date_naive().format("%Y-%m-%d")
+        ));
+        let written = fs::read_to_string(today).unwrap();
+        assert!(written.contains("[REDACTED_EMAIL]"));
+        assert!(!written.contains("bob@example.org"));
+    }
+}
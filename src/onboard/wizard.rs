@@ -126,6 +126,8 @@ pub async fn run_wizard(force: bool) -> Result<Config> {
     let config = Config {
         workspace_dir: workspace_dir.clone(),
         config_path: config_path.clone(),
+        secret_refs: std::collections::HashMap::new(),
+        low_resource_overrides: None,
         api_key: if api_key.is_empty() {
             None
         } else {
@@ -135,6 +137,7 @@ pub async fn run_wizard(force: bool) -> Result<Config> {
         default_provider: Some(provider),
         default_model: Some(model),
         model_providers: std::collections::HashMap::new(),
+        model_aliases: std::collections::HashMap::new(),
         default_temperature: 0.7,
         observability: ObservabilityConfig::default(),
         autonomy: AutonomyConfig::default(),
@@ -162,6 +165,7 @@ pub async fn run_wizard(force: bool) -> Result<Config> {
         web_fetch: crate::config::WebFetchConfig::default(),
         web_search: crate::config::WebSearchConfig::default(),
         proxy: crate::config::ProxyConfig::default(),
+        provider_http: std::collections::HashMap::new(),
         identity: crate::config::IdentityConfig::default(),
         cost: crate::config::CostConfig::default(),
         peripherals: crate::config::PeripheralsConfig::default(),
@@ -170,6 +174,19 @@ pub async fn run_wizard(force: bool) -> Result<Config> {
         hardware: hardware_config,
         query_classification: crate::config::QueryClassificationConfig::default(),
         transcription: crate::config::TranscriptionConfig::default(),
+        mcp: crate::config::McpConfig::default(),
+        tools: std::collections::HashMap::new(),
+        plugins: crate::config::PluginsConfig::default(),
+        home_assistant: crate::config::HomeAssistantConfig::default(),
+        eval: crate::config::EvalConfig::default(),
+        gemini: crate::config::GeminiConfig::default(),
+        azure_openai: crate::config::AzureOpenAiConfig::default(),
+        openrouter: crate::config::OpenRouterConfig::default(),
+        local_llm: crate::config::LocalLlmConfig::default(),
+        profiles: std::collections::HashMap::new(),
+        active_profile: None,
+        config_sync: crate::config::ConfigSyncConfig::default(),
+        schema_version: crate::config::migrations::CURRENT_SCHEMA_VERSION,
     };
 
     println!(
@@ -385,6 +402,7 @@ fn memory_config_defaults_for_backend(backend: &str) -> MemoryConfig {
         response_cache_enabled: false,
         response_cache_ttl_minutes: 60,
         response_cache_max_entries: 5_000,
+        response_cache_max_temperature: 0.2,
         snapshot_enabled: false,
         snapshot_on_hygiene: false,
         auto_hydrate: true,
@@ -478,6 +496,8 @@ async fn run_quick_setup_with_home(
     let config = Config {
         workspace_dir: workspace_dir.clone(),
         config_path: config_path.clone(),
+        secret_refs: std::collections::HashMap::new(),
+        low_resource_overrides: None,
         api_key: credential_override.map(|c| {
             let mut s = String::with_capacity(c.len());
             s.push_str(c);
@@ -487,6 +507,7 @@ async fn run_quick_setup_with_home(
         default_provider: Some(provider_name.clone()),
         default_model: Some(model.clone()),
         model_providers: std::collections::HashMap::new(),
+        model_aliases: std::collections::HashMap::new(),
         default_temperature: 0.7,
         observability: ObservabilityConfig::default(),
         autonomy: AutonomyConfig::default(),
@@ -514,6 +535,7 @@ async fn run_quick_setup_with_home(
         web_fetch: crate::config::WebFetchConfig::default(),
         web_search: crate::config::WebSearchConfig::default(),
         proxy: crate::config::ProxyConfig::default(),
+        provider_http: std::collections::HashMap::new(),
         identity: crate::config::IdentityConfig::default(),
         cost: crate::config::CostConfig::default(),
         peripherals: crate::config::PeripheralsConfig::default(),
@@ -522,6 +544,19 @@ async fn run_quick_setup_with_home(
         hardware: crate::config::HardwareConfig::default(),
         query_classification: crate::config::QueryClassificationConfig::default(),
         transcription: crate::config::TranscriptionConfig::default(),
+        mcp: crate::config::McpConfig::default(),
+        tools: std::collections::HashMap::new(),
+        plugins: crate::config::PluginsConfig::default(),
+        home_assistant: crate::config::HomeAssistantConfig::default(),
+        eval: crate::config::EvalConfig::default(),
+        gemini: crate::config::GeminiConfig::default(),
+        azure_openai: crate::config::AzureOpenAiConfig::default(),
+        openrouter: crate::config::OpenRouterConfig::default(),
+        local_llm: crate::config::LocalLlmConfig::default(),
+        profiles: std::collections::HashMap::new(),
+        active_profile: None,
+        config_sync: crate::config::ConfigSyncConfig::default(),
+        schema_version: crate::config::migrations::CURRENT_SCHEMA_VERSION,
     };
 
     config.save().await?;
@@ -818,6 +853,10 @@ fn curated_models_for_provider(provider_name: &str) -> Vec<(String, String)> {
                 "llama-3.3-70b-versatile".to_string(),
                 "Llama 3.3 70B (fast, recommended)".to_string(),
             ),
+            (
+                "llama-3.1-8b-instant".to_string(),
+                "Llama 3.1 8B Instant (lowest latency)".to_string(),
+            ),
             (
                 "openai/gpt-oss-120b".to_string(),
                 "GPT-OSS 120B (strong open-weight)".to_string(),
@@ -2974,7 +3013,10 @@ fn setup_tool_mode() -> Result<(ComposioConfig, SecretsConfig)> {
         .default(true)
         .interact()?;
 
-    let secrets_config = SecretsConfig { encrypt };
+    let secrets_config = SecretsConfig {
+        encrypt,
+        ..SecretsConfig::default()
+    };
 
     if encrypt {
         println!(
@@ -3617,6 +3659,7 @@ fn setup_channels() -> Result<ChannelsConfig> {
                     bot_token: token,
                     allowed_users,
                     notify_chat_id: None,
+                    notify_targets: std::collections::HashMap::default(),
                     stream_mode: StreamMode::default(),
                     draft_update_interval_ms: 1000,
                     interrupt_on_new_message: false,
@@ -3720,6 +3763,8 @@ fn setup_channels() -> Result<ChannelsConfig> {
                     allowed_users,
                     listen_to_bots: false,
                     mention_only: false,
+                    notify_chat_id: None,
+                    notify_webhook_url: None,
                 });
             }
             ChannelMenuChoice::Slack => {
@@ -3847,6 +3892,8 @@ fn setup_channels() -> Result<ChannelsConfig> {
                         Some(channel)
                     },
                     allowed_users,
+                    notify_channel_id: None,
+                    notify_webhook_url: None,
                 });
             }
             ChannelMenuChoice::IMessage => {
@@ -4003,6 +4050,8 @@ fn setup_channels() -> Result<ChannelsConfig> {
                     device_id: detected_device_id,
                     room_id,
                     allowed_users,
+                    notify_room_id: None,
+                    notify_proxy_url: None,
                 });
             }
             ChannelMenuChoice::Signal => {
@@ -95,7 +95,7 @@ pub async fn handle_command(cmd: crate::PeripheralCommands, config: &Config) ->
                 path: path_opt,
                 baud: 115_200,
             });
-            cfg.save().await?;
+            cfg.save_from(crate::config::ChangeSource::Cli).await?;
             println!("Added {} at {}. Restart daemon to apply.", board, path);
         }
         #[cfg(feature = "hardware")]
@@ -0,0 +1,149 @@
+//! On-device inference on the phone's GPU/NPU through the host app.
+//!
+//! MLC-LLM and ExecuTorch ship as Android libraries, so generation is
+//! delegated over the JNI host bridge: the app receives an
+//! `accelerated_generate` action, runs the model on its accelerated runtime,
+//! and replies with the text. Outside the mobile app no host is registered
+//! and requests fail; pair this provider with `local-llm` in
+//! `reliability.fallback_providers` to fall back to CPU inference.
+
+use super::local_llm::{prompt_messages, PromptMessage};
+use crate::config::LocalLlmConfig;
+use crate::providers::traits::{ChatMessage, Provider};
+use crate::runtime::host_bridge;
+use async_trait::async_trait;
+use serde_json::json;
+use std::time::Duration;
+
+pub struct AcceleratedProvider {
+    settings: LocalLlmConfig,
+}
+
+impl AcceleratedProvider {
+    pub fn new(settings: LocalLlmConfig) -> Self {
+        Self { settings }
+    }
+
+    fn timeout(&self) -> Duration {
+        Duration::from_secs(self.settings.accelerator_timeout_secs.max(1))
+    }
+
+    fn build_payload(
+        &self,
+        messages: &[PromptMessage],
+        model: &str,
+        temperature: f64,
+    ) -> serde_json::Value {
+        json!({
+            "runtime": self.settings.accelerator,
+            "model": model,
+            "messages": messages,
+            "temperature": temperature,
+            "max_tokens": self.settings.max_tokens,
+        })
+    }
+
+    /// Extract the reply text, treating `"ok": false` as a host-side failure.
+    fn parse_reply(&self, reply: &serde_json::Value) -> anyhow::Result<String> {
+        if reply.get("ok").and_then(serde_json::Value::as_bool) == Some(false) {
+            let detail = reply
+                .get("error")
+                .and_then(serde_json::Value::as_str)
+                .unwrap_or("unknown error");
+            anyhow::bail!("{} runtime failed: {detail}", self.settings.accelerator);
+        }
+        reply
+            .get("text")
+            .and_then(serde_json::Value::as_str)
+            .map(|text| text.trim().to_string())
+            .ok_or_else(|| anyhow::anyhow!("Host reply is missing 'text'"))
+    }
+
+    async fn call_host(
+        &self,
+        action: &str,
+        payload: serde_json::Value,
+    ) -> anyhow::Result<serde_json::Value> {
+        let action_name = action.to_string();
+        let call =
+            tokio::task::spawn_blocking(move || host_bridge::call_host(&action_name, payload));
+        match tokio::time::timeout(self.timeout(), call).await {
+            Ok(joined) => joined?,
+            Err(_) => anyhow::bail!(
+                "{} runtime did not answer '{action}' within {}s",
+                self.settings.accelerator,
+                self.settings.accelerator_timeout_secs
+            ),
+        }
+    }
+}
+
+#[async_trait]
+impl Provider for AcceleratedProvider {
+    async fn chat_with_system(
+        &self,
+        system_prompt: Option<&str>,
+        message: &str,
+        model: &str,
+        temperature: f64,
+    ) -> anyhow::Result<String> {
+        let mut messages = Vec::new();
+        if let Some(system) = system_prompt {
+            messages.push(ChatMessage::system(system));
+        }
+        messages.push(ChatMessage::user(message));
+        self.chat_with_history(&messages, model, temperature).await
+    }
+
+    async fn chat_with_history(
+        &self,
+        messages: &[ChatMessage],
+        model: &str,
+        temperature: f64,
+    ) -> anyhow::Result<String> {
+        let payload = self.build_payload(&prompt_messages(messages), model, temperature);
+        let reply = self.call_host("accelerated_generate", payload).await?;
+        self.parse_reply(&reply)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn payload_carries_runtime_and_folded_history() {
+        let provider = AcceleratedProvider::new(LocalLlmConfig {
+            accelerator: "executorch".into(),
+            max_tokens: 256,
+            ..LocalLlmConfig::default()
+        });
+        let messages = prompt_messages(&[
+            ChatMessage::system("Be brief."),
+            ChatMessage::user("Hi"),
+            ChatMessage::tool("{}"),
+        ]);
+        let payload = provider.build_payload(&messages, "llama3_2-3b.pte", 0.3);
+        assert_eq!(payload["runtime"], "executorch");
+        assert_eq!(payload["model"], "llama3_2-3b.pte");
+        assert_eq!(payload["max_tokens"], 256);
+        assert_eq!(payload["messages"].as_array().unwrap().len(), 2);
+        assert_eq!(payload["messages"][1]["role"], "user");
+    }
+
+    #[test]
+    fn parse_reply_reports_host_failures() {
+        let provider = AcceleratedProvider::new(LocalLlmConfig::default());
+        assert_eq!(
+            provider
+                .parse_reply(&json!({ "ok": true, "text": " Hello \n" }))
+                .unwrap(),
+            "Hello"
+        );
+        let err = provider
+            .parse_reply(&json!({ "ok": false, "error": "no OpenCL device" }))
+            .unwrap_err();
+        assert_eq!(err.to_string(), "mlc runtime failed: no OpenCL device");
+        assert!(provider.parse_reply(&json!({ "ok": true })).is_err());
+    }
+}
@@ -1,12 +1,30 @@
+use crate::multimodal;
+use crate::providers::sse::{self, SseEvent};
 use crate::providers::traits::{
-    ChatMessage, ChatRequest as ProviderChatRequest, ChatResponse as ProviderChatResponse,
-    Provider, TokenUsage, ToolCall as ProviderToolCall,
+    parse_openai_model_ids, prompt_guided_messages, ChatMessage,
+    ChatRequest as ProviderChatRequest, ChatResponse as ProviderChatResponse, ImagePart, Provider,
+    ProviderCapabilities, StreamChunk, StreamError, StreamOptions, StreamResult, TokenUsage,
+    ToolCall as ProviderToolCall,
 };
 use crate::tools::ToolSpec;
 use async_trait::async_trait;
+use futures_util::stream;
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
 
+/// Output ceiling for Claude 3.0 and older models.
+const LEGACY_MAX_TOKENS: u32 = 4096;
+const LEGACY_MODEL_PREFIXES: &[&str] = &[
+    "claude-2",
+    "claude-instant",
+    "claude-3-opus",
+    "claude-3-sonnet",
+    "claude-3-haiku",
+];
+/// Output ceiling for Claude 3.5 and newer. Kept well under the models'
+/// limits so non-streaming requests finish inside the HTTP timeout.
+const DEFAULT_MAX_TOKENS: u32 = 8192;
+
 pub struct AnthropicProvider {
     credential: Option<String>,
     base_url: String,
@@ -17,7 +35,7 @@ struct ChatRequest {
     model: String,
     max_tokens: u32,
     #[serde(skip_serializing_if = "Option::is_none")]
-    system: Option<String>,
+    system: Option<SystemPrompt>,
     messages: Vec<Message>,
     temperature: f64,
 }
@@ -51,6 +69,8 @@ struct NativeChatRequest<'a> {
     temperature: f64,
     #[serde(skip_serializing_if = "Option::is_none")]
     tools: Option<Vec<NativeToolSpec<'a>>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    stream: Option<bool>,
 }
 
 #[derive(Debug, Serialize)]
@@ -83,6 +103,27 @@ enum NativeContentOut {
         #[serde(skip_serializing_if = "Option::is_none")]
         cache_control: Option<CacheControl>,
     },
+    #[serde(rename = "image")]
+    Image { source: ImageSource },
+}
+
+#[derive(Debug, Serialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+enum ImageSource {
+    Base64 { media_type: String, data: String },
+    Url { url: String },
+}
+
+impl From<ImagePart> for ImageSource {
+    fn from(image: ImagePart) -> Self {
+        match image {
+            ImagePart::Base64 { mime_type, data } => Self::Base64 {
+                media_type: mime_type,
+                data,
+            },
+            ImagePart::Url(url) => Self::Url { url },
+        }
+    }
 }
 
 #[derive(Debug, Serialize)]
@@ -129,6 +170,8 @@ struct NativeChatResponse {
     #[serde(default)]
     content: Vec<NativeContentIn>,
     #[serde(default)]
+    stop_reason: Option<String>,
+    #[serde(default)]
     usage: Option<AnthropicUsage>,
 }
 
@@ -138,6 +181,10 @@ struct AnthropicUsage {
     input_tokens: Option<u64>,
     #[serde(default)]
     output_tokens: Option<u64>,
+    #[serde(default)]
+    cache_creation_input_tokens: Option<u64>,
+    #[serde(default)]
+    cache_read_input_tokens: Option<u64>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -191,6 +238,26 @@ impl AnthropicProvider {
         }
     }
 
+    /// Output token budget for `model`; the Messages API requires one on
+    /// every request.
+    fn max_tokens_for_model(model: &str) -> u32 {
+        let model = model.to_ascii_lowercase();
+        let legacy = LEGACY_MODEL_PREFIXES
+            .iter()
+            .any(|prefix| model.starts_with(prefix));
+        if legacy {
+            LEGACY_MAX_TOKENS
+        } else {
+            DEFAULT_MAX_TOKENS
+        }
+    }
+
+    /// Tool use arrived with Claude 3; older models get the text protocol.
+    fn model_supports_tools(model: &str) -> bool {
+        let model = model.to_ascii_lowercase();
+        !(model.starts_with("claude-2") || model.starts_with("claude-instant"))
+    }
+
     /// Cache system prompts larger than ~1024 tokens (3KB of text)
     fn should_cache_system(text: &str) -> bool {
         text.len() > 3072
@@ -210,7 +277,7 @@ impl AnthropicProvider {
                     | NativeContentOut::ToolResult { cache_control, .. } => {
                         *cache_control = Some(CacheControl::ephemeral());
                     }
-                    NativeContentOut::ToolUse { .. } => {}
+                    NativeContentOut::ToolUse { .. } | NativeContentOut::Image { .. } => {}
                 }
             }
         }
@@ -291,15 +358,52 @@ impl AnthropicProvider {
         })
     }
 
+    /// Content blocks for a user turn: one image block per `[IMAGE:...]`
+    /// marker, followed by the remaining text. References that are not data
+    /// URIs or URLs stay in the text as-is.
+    fn convert_user_content(content: &str) -> Vec<NativeContentOut> {
+        let (mut text, references) = multimodal::parse_image_markers(content);
+        if references.is_empty() {
+            return vec![NativeContentOut::Text {
+                text: content.to_string(),
+                cache_control: None,
+            }];
+        }
+
+        let mut blocks = Vec::with_capacity(references.len() + 1);
+        for reference in references {
+            match ImagePart::from_reference(&reference) {
+                Some(image) => blocks.push(NativeContentOut::Image {
+                    source: image.into(),
+                }),
+                None => {
+                    if !text.is_empty() {
+                        text.push('\n');
+                    }
+                    text.push_str("[IMAGE:");
+                    text.push_str(&reference);
+                    text.push(']');
+                }
+            }
+        }
+        if !text.is_empty() {
+            blocks.push(NativeContentOut::Text {
+                text,
+                cache_control: None,
+            });
+        }
+        blocks
+    }
+
     fn convert_messages(messages: &[ChatMessage]) -> (Option<SystemPrompt>, Vec<NativeMessage>) {
-        let mut system_text = None;
-        let mut native_messages = Vec::new();
+        let mut system_parts: Vec<&str> = Vec::new();
+        let mut native_messages: Vec<NativeMessage> = Vec::new();
 
         for msg in messages {
             match msg.role.as_str() {
                 "system" => {
-                    if system_text.is_none() {
-                        system_text = Some(msg.content.clone());
+                    if !msg.content.trim().is_empty() {
+                        system_parts.push(&msg.content);
                     }
                 }
                 "assistant" => {
@@ -334,29 +438,39 @@ impl AnthropicProvider {
                 _ => {
                     native_messages.push(NativeMessage {
                         role: "user".to_string(),
-                        content: vec![NativeContentOut::Text {
-                            text: msg.content.clone(),
-                            cache_control: None,
-                        }],
+                        content: Self::convert_user_content(&msg.content),
                     });
                 }
             }
         }
 
-        // Convert system text to SystemPrompt with cache control if large
-        let system_prompt = system_text.map(|text| {
-            if Self::should_cache_system(&text) {
-                SystemPrompt::Blocks(vec![SystemBlock {
-                    block_type: "text".to_string(),
-                    text,
-                    cache_control: Some(CacheControl::ephemeral()),
-                }])
-            } else {
-                SystemPrompt::String(text)
+        // The Messages API expects alternating turns; fold consecutive
+        // same-role messages (e.g. parallel tool results) into one turn.
+        let mut merged: Vec<NativeMessage> = Vec::with_capacity(native_messages.len());
+        for msg in native_messages {
+            match merged.last_mut() {
+                Some(prev) if prev.role == msg.role => prev.content.extend(msg.content),
+                _ => merged.push(msg),
             }
-        });
+        }
+        let native_messages = merged;
 
-        (system_prompt, native_messages)
+        let system_text = (!system_parts.is_empty()).then(|| system_parts.join("\n\n"));
+        (system_text.map(Self::system_prompt), native_messages)
+    }
+
+    /// System prompt, marked as a cache breakpoint when it is large enough
+    /// to be cached.
+    fn system_prompt(text: String) -> SystemPrompt {
+        if Self::should_cache_system(&text) {
+            SystemPrompt::Blocks(vec![SystemBlock {
+                block_type: "text".to_string(),
+                text,
+                cache_control: Some(CacheControl::ephemeral()),
+            }])
+        } else {
+            SystemPrompt::String(text)
+        }
     }
 
     fn parse_text_response(response: ChatResponse) -> anyhow::Result<String> {
@@ -368,13 +482,46 @@ impl AnthropicProvider {
             .ok_or_else(|| anyhow::anyhow!("No response from Anthropic"))
     }
 
+    /// Text carried by a Messages API stream event. Only `text_delta`
+    /// content blocks produce output; `error` events abort the stream.
+    fn parse_stream_event(event: &SseEvent) -> StreamResult<Option<String>> {
+        let value: serde_json::Value =
+            serde_json::from_str(&event.data).map_err(StreamError::Json)?;
+        match value.get("type").and_then(serde_json::Value::as_str) {
+            Some("content_block_delta") => Ok(value
+                .pointer("/delta/text")
+                .and_then(serde_json::Value::as_str)
+                .map(ToString::to_string)),
+            Some("error") => {
+                let message = value
+                    .pointer("/error/message")
+                    .and_then(serde_json::Value::as_str)
+                    .unwrap_or("unknown stream error");
+                Err(StreamError::Provider(format!("Anthropic: {message}")))
+            }
+            _ => Ok(None),
+        }
+    }
+
     fn parse_native_response(response: NativeChatResponse) -> ProviderChatResponse {
+        if response.stop_reason.as_deref() == Some("max_tokens") {
+            tracing::warn!("Anthropic response was truncated at the max_tokens limit");
+        }
         let mut text_parts = Vec::new();
         let mut tool_calls = Vec::new();
 
-        let usage = response.usage.map(|u| TokenUsage {
-            input_tokens: u.input_tokens,
-            output_tokens: u.output_tokens,
+        let usage = response.usage.map(|u| {
+            if u.cache_read_input_tokens.is_some() || u.cache_creation_input_tokens.is_some() {
+                tracing::debug!(
+                    cache_read = u.cache_read_input_tokens.unwrap_or(0),
+                    cache_write = u.cache_creation_input_tokens.unwrap_or(0),
+                    "Anthropic prompt cache usage"
+                );
+            }
+            TokenUsage {
+                input_tokens: u.input_tokens,
+                output_tokens: u.output_tokens,
+            }
         });
 
         for block in response.content {
@@ -423,6 +570,13 @@ impl AnthropicProvider {
 
 #[async_trait]
 impl Provider for AnthropicProvider {
+    fn capabilities(&self) -> ProviderCapabilities {
+        ProviderCapabilities {
+            native_tool_calling: true,
+            vision: true,
+        }
+    }
+
     async fn chat_with_system(
         &self,
         system_prompt: Option<&str>,
@@ -436,10 +590,25 @@ impl Provider for AnthropicProvider {
             )
         })?;
 
+        // Image blocks only exist in the content-block message format.
+        if !multimodal::parse_image_markers(message).1.is_empty() {
+            let mut messages = Vec::new();
+            if let Some(sys) = system_prompt {
+                messages.push(ChatMessage::system(sys));
+            }
+            messages.push(ChatMessage::user(message));
+            let request = ProviderChatRequest {
+                messages: &messages,
+                tools: None,
+            };
+            let response = self.chat(request, model, temperature).await?;
+            return Ok(response.text_or_empty().to_string());
+        }
+
         let request = ChatRequest {
             model: model.to_string(),
-            max_tokens: 4096,
-            system: system_prompt.map(ToString::to_string),
+            max_tokens: Self::max_tokens_for_model(model),
+            system: system_prompt.map(|text| Self::system_prompt(text.to_string())),
             messages: vec![Message {
                 role: "user".to_string(),
                 content: message.to_string(),
@@ -478,6 +647,14 @@ impl Provider for AnthropicProvider {
             )
         })?;
 
+        if let Some(messages) = prompt_guided_messages(self, &request, model) {
+            let request = ProviderChatRequest {
+                messages: &messages,
+                tools: None,
+            };
+            return self.chat(request, model, temperature).await;
+        }
+
         let (system_prompt, mut messages) = Self::convert_messages(request.messages);
 
         // Auto-cache last message if conversation is long
@@ -487,11 +664,12 @@ impl Provider for AnthropicProvider {
 
         let native_request = NativeChatRequest {
             model: model.to_string(),
-            max_tokens: 4096,
+            max_tokens: Self::max_tokens_for_model(model),
             system: system_prompt,
             messages,
             temperature,
             tools: Self::convert_tools(request.tools),
+            stream: None,
         };
 
         let req = self
@@ -514,6 +692,10 @@ impl Provider for AnthropicProvider {
         true
     }
 
+    fn supports_native_tools_for_model(&self, model: &str) -> bool {
+        Self::model_supports_tools(model)
+    }
+
     async fn chat_with_tools(
         &self,
         messages: &[ChatMessage],
@@ -561,6 +743,83 @@ impl Provider for AnthropicProvider {
         self.chat(request, model, temperature).await
     }
 
+    fn supports_streaming(&self) -> bool {
+        true
+    }
+
+    fn stream_chat_with_system(
+        &self,
+        system_prompt: Option<&str>,
+        message: &str,
+        model: &str,
+        temperature: f64,
+        options: StreamOptions,
+    ) -> stream::BoxStream<'static, StreamResult<StreamChunk>> {
+        let mut messages = Vec::new();
+        if let Some(sys) = system_prompt {
+            messages.push(ChatMessage::system(sys));
+        }
+        messages.push(ChatMessage::user(message));
+        self.stream_chat_with_history(&messages, model, temperature, options)
+    }
+
+    fn stream_chat_with_history(
+        &self,
+        messages: &[ChatMessage],
+        model: &str,
+        temperature: f64,
+        options: StreamOptions,
+    ) -> stream::BoxStream<'static, StreamResult<StreamChunk>> {
+        let Some(credential) = self.credential.as_ref() else {
+            return sse::error_stream("Anthropic credentials not set");
+        };
+
+        let (system_prompt, mut native_messages) = Self::convert_messages(messages);
+        if Self::should_cache_conversation(messages) {
+            Self::apply_cache_to_last_message(&mut native_messages);
+        }
+
+        let native_request = NativeChatRequest {
+            model: model.to_string(),
+            max_tokens: Self::max_tokens_for_model(model),
+            system: system_prompt,
+            messages: native_messages,
+            temperature,
+            tools: None,
+            stream: Some(true),
+        };
+        let req = self
+            .http_client()
+            .post(format!("{}/v1/messages", self.base_url))
+            .header("anthropic-version", "2023-06-01")
+            .header("content-type", "application/json")
+            .json(&native_request);
+        sse::stream_request(
+            self.apply_auth(req, credential),
+            options.count_tokens,
+            Self::parse_stream_event,
+        )
+    }
+
+    async fn list_models(&self) -> anyhow::Result<Vec<String>> {
+        let credential = self.credential.as_ref().ok_or_else(|| {
+            anyhow::anyhow!(
+                "Anthropic credentials not set. Set ANTHROPIC_API_KEY or ANTHROPIC_OAUTH_TOKEN (setup-token)."
+            )
+        })?;
+        let request = self
+            .http_client()
+            .get(format!("{}/v1/models?limit=1000", self.base_url))
+            .header("anthropic-version", "2023-06-01");
+        let response = self.apply_auth(request, credential).send().await?;
+        if !response.status().is_success() {
+            return Err(super::api_error("Anthropic", response).await);
+        }
+
+        let body: serde_json::Value = response.json().await?;
+        Ok(parse_openai_model_ids(&body))
+    }
+
     async fn warmup(&self) -> anyhow::Result<()> {
         if let Some(credential) = self.credential.as_ref() {
             let mut request = self
@@ -740,7 +999,7 @@ mod tests {
         let req = ChatRequest {
             model: "claude-3-opus".to_string(),
             max_tokens: 4096,
-            system: Some("You are ZeroClaw".to_string()),
+            system: Some(SystemPrompt::String("You are ZeroClaw".to_string())),
             messages: vec![Message {
                 role: "user".to_string(),
                 content: "hello".to_string(),
@@ -751,6 +1010,24 @@ mod tests {
         assert!(json.contains("\"system\":\"You are ZeroClaw\""));
     }
 
+    #[test]
+    fn simple_chat_request_caches_large_system_prompt() {
+        let req = ChatRequest {
+            model: "claude-3-opus".to_string(),
+            max_tokens: 4096,
+            system: Some(AnthropicProvider::system_prompt(
+                "tool catalog ".repeat(400),
+            )),
+            messages: vec![Message {
+                role: "user".to_string(),
+                content: "hello".to_string(),
+            }],
+            temperature: 0.7,
+        };
+        let json = serde_json::to_value(&req).unwrap();
+        assert_eq!(json["system"][0]["cache_control"]["type"], "ephemeral");
+    }
+
     #[test]
     fn chat_response_deserializes() {
         let json = r#"{"content":[{"type":"text","text":"Hello there!"}]}"#;
@@ -1156,10 +1433,12 @@ mod tests {
             }],
             temperature: 0.7,
             tools: None,
+            stream: None,
         };
 
         let json = serde_json::to_string(&req).unwrap();
         assert!(!json.contains("cache_control"));
+        assert!(!json.contains("stream"));
         assert!(json.contains(r#""system":"System""#));
     }
 
@@ -1202,6 +1481,33 @@ mod tests {
         assert_eq!(native_msgs[2].role, "user");
     }
 
+    #[test]
+    fn convert_messages_emits_image_blocks_for_markers() {
+        let messages = vec![ChatMessage::user_with_images(
+            "What is in this photo?",
+            &[
+                ImagePart::base64("image/png", "iVBORw0KGgo="),
+                ImagePart::url("https://example.com/cat.jpg"),
+            ],
+        )];
+
+        let (_, native_msgs) = AnthropicProvider::convert_messages(&messages);
+        let json = serde_json::to_value(&native_msgs[0]).unwrap();
+        let content = json["content"].as_array().unwrap();
+        assert_eq!(content.len(), 3);
+        assert_eq!(content[0]["type"], "image");
+        assert_eq!(content[0]["source"]["type"], "base64");
+        assert_eq!(content[0]["source"]["media_type"], "image/png");
+        assert_eq!(content[0]["source"]["data"], "iVBORw0KGgo=");
+        assert_eq!(content[1]["source"]["type"], "url");
+        assert_eq!(content[1]["source"]["url"], "https://example.com/cat.jpg");
+        assert_eq!(content[2]["type"], "text");
+        assert_eq!(content[2]["text"], "What is in this photo?");
+
+        let provider = AnthropicProvider::new(None);
+        assert!(provider.supports_vision());
+    }
+
     /// Integration test: spin up a mock Anthropic API server, call chat_with_tools
     /// with a multi-turn conversation + tools, and verify the request body contains
     /// ALL conversation turns and native tool definitions.
@@ -1346,6 +1652,57 @@ mod tests {
         assert_eq!(usage.output_tokens, Some(75));
     }
 
+    #[test]
+    fn max_tokens_follows_model_generation() {
+        for model in [
+            "claude-3-opus-20240229",
+            "claude-3-haiku-20240307",
+            "claude-2.1",
+        ] {
+            assert_eq!(
+                AnthropicProvider::max_tokens_for_model(model),
+                LEGACY_MAX_TOKENS
+            );
+        }
+        for model in [
+            "claude-3-5-haiku-20241022",
+            "claude-3-7-sonnet-latest",
+            "claude-sonnet-4-20250514",
+            "claude-opus-4-6",
+        ] {
+            assert_eq!(
+                AnthropicProvider::max_tokens_for_model(model),
+                DEFAULT_MAX_TOKENS
+            );
+        }
+    }
+
+    #[test]
+    fn convert_messages_joins_system_prompts_and_merges_same_role_turns() {
+        let msg = |role: &str, content: &str| ChatMessage {
+            role: role.to_string(),
+            content: content.to_string(),
+        };
+        let messages = vec![
+            msg("system", "You are helpful."),
+            msg("user", "hi"),
+            msg("system", "Reply in Spanish."),
+            msg("user", "how are you?"),
+            msg("assistant", "Bien."),
+        ];
+
+        let (system, native_msgs) = AnthropicProvider::convert_messages(&messages);
+
+        match system.unwrap() {
+            SystemPrompt::String(s) => assert_eq!(s, "You are helpful.\n\nReply in Spanish."),
+            SystemPrompt::Blocks(_) => panic!("Expected String variant for small prompt"),
+        }
+        assert_eq!(native_msgs.len(), 2);
+        assert_eq!(native_msgs[0].role, "user");
+        assert_eq!(native_msgs[0].content.len(), 2);
+        assert_eq!(native_msgs[1].role, "assistant");
+    }
+
     #[test]
     fn native_response_parses_without_usage() {
         let json = r#"{"content": [{"type": "text", "text": "Hello"}]}"#;
@@ -1353,4 +1710,36 @@ mod tests {
         let result = AnthropicProvider::parse_native_response(resp);
         assert!(result.usage.is_none());
     }
+
+    #[test]
+    fn stream_events_yield_text_deltas_and_errors() {
+        let event = |data: &str| SseEvent {
+            event: None,
+            data: data.to_string(),
+        };
+        let delta = event(
+            r#"{"type":"content_block_delta","index":0,"delta":{"type":"text_delta","text":"Hi"}}"#,
+        );
+        assert_eq!(
+            AnthropicProvider::parse_stream_event(&delta).unwrap(),
+            Some("Hi".to_string())
+        );
+
+        let start = event(r#"{"type":"message_start","message":{"id":"msg_1"}}"#);
+        assert_eq!(AnthropicProvider::parse_stream_event(&start).unwrap(), None);
+
+        let error =
+            event(r#"{"type":"error","error":{"type":"overloaded_error","message":"Overloaded"}}"#);
+        let err = AnthropicProvider::parse_stream_event(&error).unwrap_err();
+        assert!(err.to_string().contains("Overloaded"));
+    }
+
+    #[test]
+    fn tool_support_is_selected_per_model() {
+        let provider = AnthropicProvider::new(Some("key"));
+        assert!(provider.supports_native_tools_for_model("claude-sonnet-4-5"));
+        assert!(provider.supports_native_tools_for_model("claude-3-haiku-20240307"));
+        assert!(!provider.supports_native_tools_for_model("claude-2.1"));
+        assert!(!provider.supports_native_tools_for_model("claude-instant-1.2"));
+    }
 }
@@ -0,0 +1,745 @@
+//! Azure OpenAI Service.
+//!
+//! Azure addresses models by deployment: requests go to
+//! `{endpoint}/openai/deployments/{deployment}/chat/completions?api-version=...`,
+//! with the configured model used as the deployment name. Requests carry an
+//! `api-key` header, or an Entra ID (AAD) bearer token obtained with the
+//! client-credentials flow when `[azure_openai]` has tenant and client
+//! credentials. The request and response bodies follow OpenAI's chat
+//! completions format.
+
+use crate::config::AzureOpenAiConfig;
+use crate::providers::sse;
+use crate::providers::structured::{JsonSchemaFormat, ResponseFormat};
+use crate::providers::traits::{
+    parse_openai_embeddings, prompt_guided_messages, ChatMessage,
+    ChatRequest as ProviderChatRequest, ChatResponse as ProviderChatResponse, Provider,
+    ProviderCapabilities, StreamChunk, StreamOptions, StreamResult, TokenUsage,
+    ToolCall as ProviderToolCall, ToolsPayload,
+};
+use crate::tools::ToolSpec;
+use async_trait::async_trait;
+use futures_util::{stream, StreamExt};
+use reqwest::{Client, RequestBuilder};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+const TOKEN_SCOPE: &str = "https://cognitiveservices.azure.com/.default";
+/// Refresh AAD tokens this long before they expire.
+const TOKEN_REFRESH_MARGIN: Duration = Duration::from_secs(120);
+
+/// How requests are authorized.
+enum Auth {
+    ApiKey(String),
+    EntraId(Arc<TokenSource>),
+}
+
+struct CachedToken {
+    value: String,
+    expires_at: Instant,
+}
+
+/// Entra ID client-credentials grant with a cached access token.
+struct TokenSource {
+    tenant_id: String,
+    client_id: String,
+    client_secret: String,
+    cached: tokio::sync::Mutex<Option<CachedToken>>,
+}
+
+impl TokenSource {
+    /// Access token for the Cognitive Services scope, reused until shortly
+    /// before it expires.
+    async fn token(&self, client: &Client) -> anyhow::Result<String> {
+        let mut cached = self.cached.lock().await;
+        if let Some(token) = cached.as_ref() {
+            if token.expires_at > Instant::now() + TOKEN_REFRESH_MARGIN {
+                return Ok(token.value.clone());
+            }
+        }
+
+        let response = client
+            .post(format!(
+                "https://login.microsoftonline.com/{}/oauth2/v2.0/token",
+                urlencoding::encode(&self.tenant_id)
+            ))
+            .form(&[
+                ("grant_type", "client_credentials"),
+                ("client_id", self.client_id.as_str()),
+                ("client_secret", self.client_secret.as_str()),
+                ("scope", TOKEN_SCOPE),
+            ])
+            .send()
+            .await?;
+        if !response.status().is_success() {
+            return Err(super::api_error("Azure AD", response).await);
+        }
+
+        let token: TokenResponse = response.json().await?;
+        let lifetime = Duration::from_secs(token.expires_in.unwrap_or(3600));
+        *cached = Some(CachedToken {
+            value: token.access_token.clone(),
+            expires_at: Instant::now() + lifetime,
+        });
+        Ok(token.access_token)
+    }
+}
+
+pub struct AzureOpenAiProvider {
+    endpoint: Option<String>,
+    api_version: String,
+    auth: Option<Auth>,
+}
+
+#[derive(Debug, Serialize)]
+struct ChatRequest {
+    messages: Vec<Message>,
+    temperature: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tools: Option<Vec<ToolSpecOut>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tool_choice: Option<&'static str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    response_format: Option<ResponseFormat>,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    stream: bool,
+}
+
+#[derive(Debug, Serialize)]
+struct Message {
+    role: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    content: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tool_calls: Option<Vec<ToolCallOut>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tool_call_id: Option<String>,
+}
+
+impl Message {
+    fn text(role: &str, content: impl Into<String>) -> Self {
+        Self {
+            role: role.to_string(),
+            content: Some(content.into()),
+            tool_calls: None,
+            tool_call_id: None,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct ToolSpecOut {
+    #[serde(rename = "type")]
+    kind: String,
+    function: FunctionSpec,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct FunctionSpec {
+    name: String,
+    description: String,
+    parameters: serde_json::Value,
+}
+
+#[derive(Debug, Serialize)]
+struct ToolCallOut {
+    id: String,
+    #[serde(rename = "type")]
+    kind: &'static str,
+    function: FunctionCall,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct FunctionCall {
+    name: String,
+    #[serde(default)]
+    arguments: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatResponse {
+    #[serde(default)]
+    choices: Vec<Choice>,
+    #[serde(default)]
+    usage: Option<UsageInfo>,
+}
+
+#[derive(Debug, Deserialize)]
+struct UsageInfo {
+    #[serde(default)]
+    prompt_tokens: Option<u64>,
+    #[serde(default)]
+    completion_tokens: Option<u64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Choice {
+    message: ResponseMessage,
+}
+
+#[derive(Debug, Deserialize)]
+struct ResponseMessage {
+    #[serde(default)]
+    content: Option<String>,
+    #[serde(default)]
+    tool_calls: Option<Vec<ResponseToolCall>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ResponseToolCall {
+    #[serde(default)]
+    id: Option<String>,
+    function: FunctionCall,
+}
+
+#[derive(Debug, Deserialize)]
+struct TokenResponse {
+    access_token: String,
+    #[serde(default)]
+    expires_in: Option<u64>,
+}
+
+fn non_empty(value: Option<&str>) -> Option<String> {
+    value
+        .map(str::trim)
+        .filter(|v| !v.is_empty())
+        .map(ToString::to_string)
+}
+
+fn normalize_arguments(arguments: String) -> String {
+    if arguments.trim().is_empty() {
+        "{}".to_string()
+    } else {
+        arguments
+    }
+}
+
+impl AzureOpenAiProvider {
+    /// Build from `[azure_openai]`. The endpoint falls back to `api_url`,
+    /// then `AZURE_OPENAI_ENDPOINT`; a missing endpoint or credential is
+    /// reported when a request is made.
+    pub fn new(
+        config: &AzureOpenAiConfig,
+        api_url: Option<&str>,
+        credential: Option<&str>,
+    ) -> Self {
+        let endpoint = non_empty(config.endpoint.as_deref())
+            .or_else(|| non_empty(api_url))
+            .or_else(|| non_empty(std::env::var("AZURE_OPENAI_ENDPOINT").ok().as_deref()))
+            .map(|url| url.trim_end_matches('/').to_string());
+
+        let client_credentials = (
+            non_empty(config.tenant_id.as_deref()),
+            non_empty(config.client_id.as_deref()),
+            non_empty(config.client_secret.as_deref()),
+        );
+        let auth = match client_credentials {
+            (Some(tenant_id), Some(client_id), Some(client_secret)) => {
+                Some(Auth::EntraId(Arc::new(TokenSource {
+                    tenant_id,
+                    client_id,
+                    client_secret,
+                    cached: tokio::sync::Mutex::new(None),
+                })))
+            }
+            _ => non_empty(credential).map(Auth::ApiKey),
+        };
+
+        Self {
+            endpoint,
+            api_version: config.api_version.trim().to_string(),
+            auth,
+        }
+    }
+
+    /// `{endpoint}/openai/deployments/{deployment}/{operation}?api-version=...`
+    fn deployment_url(&self, deployment: &str, operation: &str) -> anyhow::Result<String> {
+        let endpoint = self.endpoint.as_deref().ok_or_else(|| {
+            anyhow::anyhow!(
+                "Azure OpenAI endpoint not set. Set [azure_openai].endpoint, api_url or AZURE_OPENAI_ENDPOINT."
+            )
+        })?;
+        let deployment = deployment.trim();
+        if deployment.is_empty() {
+            anyhow::bail!(
+                "Azure OpenAI deployment name is empty; set the model to the deployment name"
+            );
+        }
+        Ok(format!(
+            "{endpoint}/openai/deployments/{}/{operation}?api-version={}",
+            urlencoding::encode(deployment),
+            urlencoding::encode(&self.api_version)
+        ))
+    }
+
+    fn auth(&self) -> anyhow::Result<&Auth> {
+        self.auth.as_ref().ok_or_else(|| {
+            anyhow::anyhow!(
+                "Azure OpenAI credentials not set. Set AZURE_OPENAI_API_KEY, or tenant_id, client_id and client_secret in [azure_openai]."
+            )
+        })
+    }
+
+    async fn authorize(
+        &self,
+        client: &Client,
+        request: RequestBuilder,
+    ) -> anyhow::Result<RequestBuilder> {
+        match self.auth()? {
+            Auth::ApiKey(key) => Ok(request.header("api-key", key)),
+            Auth::EntraId(source) => Ok(request.bearer_auth(source.token(client).await?)),
+        }
+    }
+
+    fn convert_tools(tools: Option<&[ToolSpec]>) -> Option<Vec<ToolSpecOut>> {
+        let items = tools?;
+        if items.is_empty() {
+            return None;
+        }
+        Some(
+            items
+                .iter()
+                .map(|tool| ToolSpecOut {
+                    kind: "function".to_string(),
+                    function: FunctionSpec {
+                        name: tool.name.clone(),
+                        description: tool.description.clone(),
+                        parameters: tool.parameters.clone(),
+                    },
+                })
+                .collect(),
+        )
+    }
+
+    fn convert_assistant(content: &str) -> Option<Message> {
+        let value = serde_json::from_str::<serde_json::Value>(content).ok()?;
+        let calls = value
+            .get("tool_calls")
+            .and_then(|v| serde_json::from_value::<Vec<ProviderToolCall>>(v.clone()).ok())?;
+        let tool_calls = calls
+            .into_iter()
+            .map(|call| ToolCallOut {
+                id: call.id,
+                kind: "function",
+                function: FunctionCall {
+                    name: call.name,
+                    arguments: normalize_arguments(call.arguments),
+                },
+            })
+            .collect();
+        Some(Message {
+            role: "assistant".to_string(),
+            content: value
+                .get("content")
+                .and_then(serde_json::Value::as_str)
+                .map(ToString::to_string),
+            tool_calls: Some(tool_calls),
+            tool_call_id: None,
+        })
+    }
+
+    fn convert_tool_result(content: &str) -> Option<Message> {
+        let value = serde_json::from_str::<serde_json::Value>(content).ok()?;
+        let tool_call_id = value.get("tool_call_id")?.as_str()?.to_string();
+        let result = match value.get("content") {
+            Some(serde_json::Value::String(text)) => text.clone(),
+            Some(serde_json::Value::Null) | None => String::new(),
+            Some(other) => other.to_string(),
+        };
+        Some(Message {
+            role: "tool".to_string(),
+            content: Some(result),
+            tool_calls: None,
+            tool_call_id: Some(tool_call_id),
+        })
+    }
+
+    fn convert_messages(messages: &[ChatMessage]) -> Vec<Message> {
+        messages
+            .iter()
+            .map(|m| match m.role.as_str() {
+                "assistant" => Self::convert_assistant(&m.content)
+                    .unwrap_or_else(|| Message::text("assistant", m.content.clone())),
+                "tool" => Self::convert_tool_result(&m.content)
+                    .unwrap_or_else(|| Message::text("tool", m.content.clone())),
+                role => Message::text(role, m.content.clone()),
+            })
+            .collect()
+    }
+
+    fn parse_response(response: ChatResponse) -> anyhow::Result<ProviderChatResponse> {
+        let usage = response.usage.map(|u| TokenUsage {
+            input_tokens: u.prompt_tokens,
+            output_tokens: u.completion_tokens,
+        });
+        let message = response
+            .choices
+            .into_iter()
+            .next()
+            .map(|c| c.message)
+            .ok_or_else(|| anyhow::anyhow!("No response from Azure OpenAI"))?;
+        let tool_calls = message
+            .tool_calls
+            .unwrap_or_default()
+            .into_iter()
+            .map(|call| ProviderToolCall {
+                id: call.id.unwrap_or_else(|| uuid::Uuid::new_v4().to_string()),
+                name: call.function.name,
+                arguments: normalize_arguments(call.function.arguments),
+            })
+            .collect();
+
+        Ok(ProviderChatResponse {
+            text: message.content.filter(|text| !text.is_empty()),
+            tool_calls,
+            usage,
+            reasoning_content: None,
+        })
+    }
+
+    async fn send(
+        &self,
+        deployment: &str,
+        request: &ChatRequest,
+    ) -> anyhow::Result<ProviderChatResponse> {
+        let url = self.deployment_url(deployment, "chat/completions")?;
+        let client = self.http_client();
+        let response = self
+            .authorize(&client, client.post(url).json(request))
+            .await?
+            .send()
+            .await?;
+        if !response.status().is_success() {
+            return Err(super::api_error("Azure OpenAI", response).await);
+        }
+        Self::parse_response(response.json().await?)
+    }
+
+    fn http_client(&self) -> Client {
+        crate::config::build_runtime_proxy_client_with_timeouts("provider.azure_openai", 120, 10)
+    }
+}
+
+#[async_trait]
+impl Provider for AzureOpenAiProvider {
+    fn capabilities(&self) -> ProviderCapabilities {
+        ProviderCapabilities {
+            native_tool_calling: true,
+            vision: false,
+        }
+    }
+
+    fn convert_tools(&self, tools: &[ToolSpec]) -> ToolsPayload {
+        ToolsPayload::OpenAI {
+            tools: Self::convert_tools(Some(tools))
+                .unwrap_or_default()
+                .into_iter()
+                .filter_map(|spec| serde_json::to_value(spec).ok())
+                .collect(),
+        }
+    }
+
+    async fn chat_with_system(
+        &self,
+        system_prompt: Option<&str>,
+        message: &str,
+        model: &str,
+        temperature: f64,
+    ) -> anyhow::Result<String> {
+        let mut messages = Vec::new();
+        if let Some(sys) = system_prompt {
+            messages.push(ChatMessage::system(sys));
+        }
+        messages.push(ChatMessage::user(message));
+        self.chat_with_history(&messages, model, temperature).await
+    }
+
+    async fn chat_with_history(
+        &self,
+        messages: &[ChatMessage],
+        model: &str,
+        temperature: f64,
+    ) -> anyhow::Result<String> {
+        let request = ChatRequest {
+            messages: Self::convert_messages(messages),
+            temperature,
+            tools: None,
+            tool_choice: None,
+            response_format: None,
+            stream: false,
+        };
+        let response = self.send(model, &request).await?;
+        Ok(response.text.unwrap_or_default())
+    }
+
+    async fn chat(
+        &self,
+        request: ProviderChatRequest<'_>,
+        model: &str,
+        temperature: f64,
+    ) -> anyhow::Result<ProviderChatResponse> {
+        if let Some(messages) = prompt_guided_messages(self, &request, model) {
+            let request = ProviderChatRequest {
+                messages: &messages,
+                tools: None,
+            };
+            return self.chat(request, model, temperature).await;
+        }
+
+        let tools = Self::convert_tools(request.tools);
+        let request = ChatRequest {
+            messages: Self::convert_messages(request.messages),
+            temperature,
+            tool_choice: tools.as_ref().map(|_| "auto"),
+            tools,
+            response_format: None,
+            stream: false,
+        };
+        self.send(model, &request).await
+    }
+
+    async fn chat_with_tools(
+        &self,
+        messages: &[ChatMessage],
+        tools: &[serde_json::Value],
+        model: &str,
+        temperature: f64,
+    ) -> anyhow::Result<ProviderChatResponse> {
+        let tools = tools
+            .iter()
+            .cloned()
+            .map(|value| {
+                serde_json::from_value::<ToolSpecOut>(value)
+                    .map_err(|e| anyhow::anyhow!("Invalid Azure OpenAI tool specification: {e}"))
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        let tools = (!tools.is_empty()).then_some(tools);
+
+        let request = ChatRequest {
+            messages: Self::convert_messages(messages),
+            temperature,
+            tool_choice: tools.as_ref().map(|_| "auto"),
+            tools,
+            response_format: None,
+            stream: false,
+        };
+        self.send(model, &request).await
+    }
+
+    fn supports_structured_output(&self) -> bool {
+        true
+    }
+
+    async fn chat_with_schema(
+        &self,
+        messages: &[ChatMessage],
+        format: &JsonSchemaFormat,
+        model: &str,
+        temperature: f64,
+    ) -> anyhow::Result<String> {
+        let request = ChatRequest {
+            messages: Self::convert_messages(messages),
+            temperature,
+            tools: None,
+            tool_choice: None,
+            response_format: Some(ResponseFormat::json_schema(format)),
+            stream: false,
+        };
+        let response = self.send(model, &request).await?;
+        Ok(response.text.unwrap_or_default())
+    }
+
+    fn supports_streaming(&self) -> bool {
+        true
+    }
+
+    fn stream_chat_with_system(
+        &self,
+        system_prompt: Option<&str>,
+        message: &str,
+        model: &str,
+        temperature: f64,
+        options: StreamOptions,
+    ) -> stream::BoxStream<'static, StreamResult<StreamChunk>> {
+        let mut messages = Vec::new();
+        if let Some(sys) = system_prompt {
+            messages.push(ChatMessage::system(sys));
+        }
+        messages.push(ChatMessage::user(message));
+        self.stream_chat_with_history(&messages, model, temperature, options)
+    }
+
+    fn stream_chat_with_history(
+        &self,
+        messages: &[ChatMessage],
+        model: &str,
+        temperature: f64,
+        options: StreamOptions,
+    ) -> stream::BoxStream<'static, StreamResult<StreamChunk>> {
+        let url = match self
+            .auth()
+            .and_then(|_| self.deployment_url(model, "chat/completions"))
+        {
+            Ok(url) => url,
+            Err(e) => return sse::error_stream(e.to_string()),
+        };
+
+        let request = ChatRequest {
+            messages: Self::convert_messages(messages),
+            temperature,
+            tools: None,
+            tool_choice: None,
+            response_format: None,
+            stream: true,
+        };
+        let client = self.http_client();
+        match self.auth.as_ref() {
+            Some(Auth::EntraId(source)) => {
+                // The token may need fetching first, so the stream starts
+                // once it is available.
+                let source = Arc::clone(source);
+                stream::once(async move {
+                    match source.token(&client).await {
+                        Ok(token) => sse::stream_request(
+                            client.post(url).bearer_auth(token).json(&request),
+                            options.count_tokens,
+                            sse::chat_completions_delta,
+                        ),
+                        Err(e) => sse::error_stream(e.to_string()),
+                    }
+                })
+                .flatten()
+                .boxed()
+            }
+            Some(Auth::ApiKey(key)) => sse::stream_request(
+                client.post(url).header("api-key", key).json(&request),
+                options.count_tokens,
+                sse::chat_completions_delta,
+            ),
+            None => sse::error_stream("Azure OpenAI credentials not set"),
+        }
+    }
+
+    fn supports_embeddings(&self) -> bool {
+        true
+    }
+
+    async fn embed(&self, texts: &[&str], model: &str) -> anyhow::Result<Vec<Vec<f32>>> {
+        if texts.is_empty() {
+            return Ok(Vec::new());
+        }
+        let url = self.deployment_url(model, "embeddings")?;
+        let client = self.http_client();
+        let response = self
+            .authorize(
+                &client,
+                client
+                    .post(url)
+                    .json(&serde_json::json!({ "input": texts })),
+            )
+            .await?
+            .send()
+            .await?;
+        if !response.status().is_success() {
+            return Err(super::api_error("Azure OpenAI", response).await);
+        }
+
+        let body: serde_json::Value = response.json().await?;
+        parse_openai_embeddings(&body)
+    }
+
+    async fn warmup(&self) -> anyhow::Result<()> {
+        let (Some(endpoint), Some(_)) = (self.endpoint.as_deref(), self.auth.as_ref()) else {
+            return Ok(());
+        };
+        let client = self.http_client();
+        let url = format!(
+            "{endpoint}/openai/models?api-version={}",
+            urlencoding::encode(&self.api_version)
+        );
+        self.authorize(&client, client.get(url))
+            .await?
+            .send()
+            .await?
+            .error_for_status()?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(endpoint: Option<&str>) -> AzureOpenAiConfig {
+        AzureOpenAiConfig {
+            endpoint: endpoint.map(ToString::to_string),
+            ..AzureOpenAiConfig::default()
+        }
+    }
+
+    #[test]
+    fn deployment_url_uses_deployment_and_api_version() {
+        let provider = AzureOpenAiProvider::new(
+            &config(Some("https://contoso.openai.azure.com/")),
+            None,
+            Some("key"),
+        );
+        assert_eq!(
+            provider
+                .deployment_url("gpt-4o-prod", "chat/completions")
+                .unwrap(),
+            "https://contoso.openai.azure.com/openai/deployments/gpt-4o-prod/chat/completions?api-version=2024-10-21"
+        );
+        assert!(provider.deployment_url(" ", "chat/completions").is_err());
+    }
+
+    #[test]
+    fn endpoint_falls_back_to_api_url() {
+        let provider = AzureOpenAiProvider::new(
+            &config(None),
+            Some("https://fabrikam.openai.azure.com"),
+            Some("key"),
+        );
+        assert!(provider
+            .deployment_url("chat", "embeddings")
+            .unwrap()
+            .starts_with("https://fabrikam.openai.azure.com/openai/deployments/chat/embeddings?"));
+    }
+
+    #[test]
+    fn client_credentials_take_precedence_over_api_key() {
+        let provider = AzureOpenAiProvider::new(
+            &AzureOpenAiConfig {
+                tenant_id: Some("tenant".into()),
+                client_id: Some("client".into()),
+                client_secret: Some("secret".into()),
+                ..config(Some("https://contoso.openai.azure.com"))
+            },
+            None,
+            Some("key"),
+        );
+        assert!(matches!(provider.auth, Some(Auth::EntraId(_))));
+
+        let provider = AzureOpenAiProvider::new(
+            &config(Some("https://contoso.openai.azure.com")),
+            None,
+            Some("key"),
+        );
+        assert!(matches!(provider.auth, Some(Auth::ApiKey(_))));
+    }
+
+    #[tokio::test]
+    async fn chat_fails_without_credentials() {
+        let provider = AzureOpenAiProvider::new(
+            &config(Some("https://contoso.openai.azure.com")),
+            None,
+            None,
+        );
+        let err = provider
+            .simple_chat("hello", "gpt-4o-prod", 0.2)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("AZURE_OPENAI_API_KEY"));
+    }
+}
@@ -0,0 +1,411 @@
+//! Content-addressed response cache in front of a provider.
+//!
+//! Low-temperature calls with an identical model, prompt history and tool
+//! set (scheduled summaries, classification prompts) are answered from the
+//! `[memory]` response cache until its TTL expires. Higher-temperature calls
+//! and streams always reach the provider, and failures are never cached.
+//! Neither are responses requesting tool calls: replaying one would run the
+//! tools again on stale arguments instead of asking the model.
+
+use super::structured::JsonSchemaFormat;
+use super::traits::{
+    ChatMessage, ChatRequest, ChatResponse, ProviderCapabilities, StreamChunk, StreamOptions,
+    StreamResult, ToolsPayload,
+};
+use super::Provider;
+use crate::agent::context_window::estimate_tokens;
+use crate::memory::ResponseCache;
+use crate::tools::ToolSpec;
+use async_trait::async_trait;
+use futures_util::stream;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+/// Stored form of a structured `chat` response without tool calls.
+#[derive(Serialize, Deserialize)]
+struct CachedChat {
+    text: Option<String>,
+    #[serde(default)]
+    reasoning_content: Option<String>,
+}
+
+pub struct CachedProvider {
+    inner: Box<dyn Provider>,
+    cache: Arc<ResponseCache>,
+}
+
+impl CachedProvider {
+    pub fn new(inner: Box<dyn Provider>, cache: Arc<ResponseCache>) -> Self {
+        Self { inner, cache }
+    }
+
+    /// Key over the whole conversation and tool set, or `None` when the call
+    /// should bypass the cache.
+    fn history_key(
+        &self,
+        kind: &str,
+        messages: &[ChatMessage],
+        tools: Option<&[ToolSpec]>,
+        model: &str,
+        temperature: f64,
+    ) -> Option<String> {
+        if !self.cache.accepts_temperature(temperature) {
+            return None;
+        }
+        let payload = serde_json::json!({
+            "kind": kind,
+            "messages": messages,
+            "tools": tools,
+        });
+        Some(ResponseCache::cache_key(model, None, &payload.to_string()))
+    }
+
+    fn lookup(&self, key: &str) -> Option<String> {
+        match self.cache.get(key) {
+            Ok(hit) => {
+                if hit.is_some() {
+                    tracing::debug!("Response cache hit");
+                }
+                hit
+            }
+            Err(e) => {
+                tracing::warn!("Response cache lookup failed: {e}");
+                None
+            }
+        }
+    }
+
+    fn store(&self, key: &str, model: &str, response: &str, output_tokens: Option<u64>) {
+        let tokens = match output_tokens {
+            Some(tokens) => u32::try_from(tokens),
+            None => u32::try_from(estimate_tokens(response)),
+        }
+        .unwrap_or(u32::MAX);
+        if let Err(e) = self.cache.put(key, model, response, tokens) {
+            tracing::warn!("Response cache write failed: {e}");
+        }
+    }
+}
+
+#[async_trait]
+impl Provider for CachedProvider {
+    fn capabilities(&self) -> ProviderCapabilities {
+        self.inner.capabilities()
+    }
+
+    fn convert_tools(&self, tools: &[ToolSpec]) -> ToolsPayload {
+        self.inner.convert_tools(tools)
+    }
+
+    async fn warmup(&self) -> anyhow::Result<()> {
+        self.inner.warmup().await
+    }
+
+    async fn list_models(&self) -> anyhow::Result<Vec<String>> {
+        self.inner.list_models().await
+    }
+
+    async fn chat_with_system(
+        &self,
+        system_prompt: Option<&str>,
+        message: &str,
+        model: &str,
+        temperature: f64,
+    ) -> anyhow::Result<String> {
+        let key = self
+            .cache
+            .accepts_temperature(temperature)
+            .then(|| ResponseCache::cache_key(model, system_prompt, message));
+        if let Some(hit) = key.as_deref().and_then(|key| self.lookup(key)) {
+            return Ok(hit);
+        }
+
+        let response = self
+            .inner
+            .chat_with_system(system_prompt, message, model, temperature)
+            .await?;
+        if let Some(key) = key {
+            self.store(&key, model, &response, None);
+        }
+        Ok(response)
+    }
+
+    async fn chat_with_history(
+        &self,
+        messages: &[ChatMessage],
+        model: &str,
+        temperature: f64,
+    ) -> anyhow::Result<String> {
+        let key = self.history_key("history", messages, None, model, temperature);
+        if let Some(hit) = key.as_deref().and_then(|key| self.lookup(key)) {
+            return Ok(hit);
+        }
+
+        let response = self
+            .inner
+            .chat_with_history(messages, model, temperature)
+            .await?;
+        if let Some(key) = key {
+            self.store(&key, model, &response, None);
+        }
+        Ok(response)
+    }
+
+    async fn chat(
+        &self,
+        request: ChatRequest<'_>,
+        model: &str,
+        temperature: f64,
+    ) -> anyhow::Result<ChatResponse> {
+        let key = self.history_key("chat", request.messages, request.tools, model, temperature);
+        if let Some(hit) = key.as_deref().and_then(|key| self.lookup(key)) {
+            match serde_json::from_str::<CachedChat>(&hit) {
+                Ok(cached) => {
+                    return Ok(ChatResponse {
+                        text: cached.text,
+                        tool_calls: Vec::new(),
+                        usage: None,
+                        reasoning_content: cached.reasoning_content,
+                    })
+                }
+                Err(e) => tracing::warn!("Ignoring unreadable cached response: {e}"),
+            }
+        }
+
+        let response = self.inner.chat(request, model, temperature).await?;
+        if let Some(key) = key.filter(|_| response.tool_calls.is_empty()) {
+            let cached = CachedChat {
+                text: response.text.clone(),
+                reasoning_content: response.reasoning_content.clone(),
+            };
+            let output_tokens = response.usage.as_ref().and_then(|u| u.output_tokens);
+            match serde_json::to_string(&cached) {
+                Ok(serialized) => self.store(&key, model, &serialized, output_tokens),
+                Err(e) => tracing::warn!("Failed to serialize response for cache: {e}"),
+            }
+        }
+        Ok(response)
+    }
+
+    fn supports_native_tools(&self) -> bool {
+        self.inner.supports_native_tools()
+    }
+
+    fn supports_native_tools_for_model(&self, model: &str) -> bool {
+        self.inner.supports_native_tools_for_model(model)
+    }
+
+    fn supports_vision(&self) -> bool {
+        self.inner.supports_vision()
+    }
+
+    async fn chat_with_tools(
+        &self,
+        messages: &[ChatMessage],
+        tools: &[serde_json::Value],
+        model: &str,
+        temperature: f64,
+    ) -> anyhow::Result<ChatResponse> {
+        self.inner
+            .chat_with_tools(messages, tools, model, temperature)
+            .await
+    }
+
+    fn supports_streaming(&self) -> bool {
+        self.inner.supports_streaming()
+    }
+
+    fn supports_embeddings(&self) -> bool {
+        self.inner.supports_embeddings()
+    }
+
+    async fn embed(&self, texts: &[&str], model: &str) -> anyhow::Result<Vec<Vec<f32>>> {
+        self.inner.embed(texts, model).await
+    }
+
+    fn supports_structured_output(&self) -> bool {
+        self.inner.supports_structured_output()
+    }
+
+    async fn chat_with_schema(
+        &self,
+        messages: &[ChatMessage],
+        format: &JsonSchemaFormat,
+        model: &str,
+        temperature: f64,
+    ) -> anyhow::Result<String> {
+        self.inner
+            .chat_with_schema(messages, format, model, temperature)
+            .await
+    }
+
+    fn stream_chat_with_system(
+        &self,
+        system_prompt: Option<&str>,
+        message: &str,
+        model: &str,
+        temperature: f64,
+        options: StreamOptions,
+    ) -> stream::BoxStream<'static, StreamResult<StreamChunk>> {
+        self.inner
+            .stream_chat_with_system(system_prompt, message, model, temperature, options)
+    }
+
+    fn stream_chat_with_history(
+        &self,
+        messages: &[ChatMessage],
+        model: &str,
+        temperature: f64,
+        options: StreamOptions,
+    ) -> stream::BoxStream<'static, StreamResult<StreamChunk>> {
+        self.inner
+            .stream_chat_with_history(messages, model, temperature, options)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::providers::traits::ToolCall;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    struct CountingProvider {
+        calls: Arc<AtomicUsize>,
+        fail: bool,
+    }
+
+    #[async_trait]
+    impl Provider for CountingProvider {
+        async fn chat_with_system(
+            &self,
+            _system_prompt: Option<&str>,
+            message: &str,
+            _model: &str,
+            _temperature: f64,
+        ) -> anyhow::Result<String> {
+            let n = self.calls.fetch_add(1, Ordering::SeqCst);
+            if self.fail {
+                anyhow::bail!("upstream error");
+            }
+            Ok(format!("{message} #{n}"))
+        }
+
+        /// Calls a tool when offered any, answers in text otherwise.
+        async fn chat(
+            &self,
+            request: ChatRequest<'_>,
+            _model: &str,
+            _temperature: f64,
+        ) -> anyhow::Result<ChatResponse> {
+            let n = self.calls.fetch_add(1, Ordering::SeqCst);
+            let tool_calls = match request.tools {
+                Some(_) => vec![ToolCall {
+                    id: format!("call_{n}"),
+                    name: "weather".into(),
+                    arguments: "{}".into(),
+                }],
+                None => Vec::new(),
+            };
+            Ok(ChatResponse {
+                text: Some("checking".into()),
+                tool_calls,
+                usage: None,
+                reasoning_content: Some("thinking".into()),
+            })
+        }
+    }
+
+    fn cached(fail: bool) -> (tempfile::TempDir, Arc<AtomicUsize>, CachedProvider) {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let cache = ResponseCache::new(tmp.path(), 60, 100)
+            .unwrap()
+            .with_max_temperature(0.2);
+        let calls = Arc::new(AtomicUsize::new(0));
+        let inner = CountingProvider {
+            calls: calls.clone(),
+            fail,
+        };
+        let provider = CachedProvider::new(Box::new(inner), Arc::new(cache));
+        (tmp, calls, provider)
+    }
+
+    #[tokio::test]
+    async fn low_temperature_calls_are_served_from_cache() {
+        let (_tmp, calls, provider) = cached(false);
+
+        let first = provider.simple_chat("summary", "m", 0.0).await.unwrap();
+        let second = provider.simple_chat("summary", "m", 0.0).await.unwrap();
+        assert_eq!(first, second);
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+        provider
+            .simple_chat("summary", "other-model", 0.0)
+            .await
+            .unwrap();
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+
+        let history = [ChatMessage::system("sys"), ChatMessage::user("summary")];
+        provider
+            .chat_with_history(&history, "m", 0.1)
+            .await
+            .unwrap();
+        provider
+            .chat_with_history(&history, "m", 0.1)
+            .await
+            .unwrap();
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn high_temperature_and_failed_calls_bypass_cache() {
+        let (_tmp, calls, provider) = cached(false);
+        let first = provider.simple_chat("poem", "m", 0.7).await.unwrap();
+        let second = provider.simple_chat("poem", "m", 0.7).await.unwrap();
+        assert_ne!(first, second);
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+
+        let (_tmp, calls, provider) = cached(true);
+        assert!(provider.simple_chat("poem", "m", 0.0).await.is_err());
+        assert!(provider.simple_chat("poem", "m", 0.0).await.is_err());
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn structured_chat_caches_text_answers() {
+        let (_tmp, calls, provider) = cached(false);
+        let messages = [ChatMessage::user("weather?")];
+        let request = ChatRequest {
+            messages: &messages,
+            tools: None,
+        };
+
+        provider.chat(request, "m", 0.0).await.unwrap();
+        let hit = provider.chat(request, "m", 0.0).await.unwrap();
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+        assert_eq!(hit.text.as_deref(), Some("checking"));
+        assert_eq!(hit.reasoning_content.as_deref(), Some("thinking"));
+        assert!(hit.tool_calls.is_empty());
+        assert!(hit.usage.is_none());
+    }
+
+    #[tokio::test]
+    async fn tool_call_responses_are_not_cached() {
+        let (_tmp, calls, provider) = cached(false);
+        let messages = [ChatMessage::user("weather?")];
+        let tools = [ToolSpec {
+            name: "weather".into(),
+            description: "Current weather".into(),
+            parameters: serde_json::json!({"type": "object"}),
+        }];
+        let request = ChatRequest {
+            messages: &messages,
+            tools: Some(&tools),
+        };
+
+        let first = provider.chat(request, "m", 0.0).await.unwrap();
+        let second = provider.chat(request, "m", 0.0).await.unwrap();
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+        assert_eq!(first.tool_calls[0].id, "call_0");
+        assert_eq!(second.tool_calls[0].id, "call_1");
+    }
+}
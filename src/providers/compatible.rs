@@ -3,13 +3,14 @@
 //! This module provides a single implementation that works for all of them.
 
 use crate::multimodal;
+use crate::providers::sse;
 use crate::providers::traits::{
-    ChatMessage, ChatRequest as ProviderChatRequest, ChatResponse as ProviderChatResponse,
-    Provider, StreamChunk, StreamError, StreamOptions, StreamResult, TokenUsage,
-    ToolCall as ProviderToolCall,
+    parse_openai_embeddings, parse_openai_model_ids, ChatMessage,
+    ChatRequest as ProviderChatRequest, ChatResponse as ProviderChatResponse, Provider,
+    StreamChunk, StreamOptions, StreamResult, TokenUsage, ToolCall as ProviderToolCall,
 };
 use async_trait::async_trait;
-use futures_util::{stream, StreamExt};
+use futures_util::stream;
 use reqwest::{
     header::{HeaderMap, HeaderValue, USER_AGENT},
     Client,
@@ -215,8 +216,11 @@ impl OpenAiCompatibleProvider {
                 .timeout(std::time::Duration::from_secs(120))
                 .connect_timeout(std::time::Duration::from_secs(10))
                 .default_headers(headers);
-            let builder =
-                crate::config::apply_runtime_proxy_to_builder(builder, "provider.compatible");
+            let builder = crate::config::apply_runtime_provider_http_to_builder(
+                builder,
+                &self.http_profile(),
+                "provider.compatible",
+            );
 
             return builder.build().unwrap_or_else(|error| {
                 tracing::warn!("Failed to build proxied timeout client with user-agent: {error}");
@@ -224,7 +228,23 @@ impl OpenAiCompatibleProvider {
             });
         }
 
-        crate::config::build_runtime_proxy_client_with_timeouts("provider.compatible", 120, 10)
+        crate::config::build_provider_client_with_timeouts(
+            &self.http_profile(),
+            "provider.compatible",
+            120,
+            10,
+        )
+    }
+
+    /// `[provider_http]` key for this provider: its name in kebab-case, e.g.
+    /// `custom`, `together-ai`.
+    fn http_profile(&self) -> String {
+        self.name
+            .to_ascii_lowercase()
+            .split(|c: char| !c.is_ascii_alphanumeric())
+            .filter(|part| !part.is_empty())
+            .collect::<Vec<_>>()
+            .join("-")
     }
 
     /// Build the full URL for chat completions, detecting if base_url already includes the path.
@@ -289,6 +309,34 @@ impl OpenAiCompatibleProvider {
         }
     }
 
+    /// Build the full URL for the embeddings API, mirroring [`Self::responses_url`].
+    fn embeddings_url(&self) -> String {
+        self.sibling_url("/embeddings")
+    }
+
+    /// Build the full URL for the model catalog.
+    fn models_url(&self) -> String {
+        self.sibling_url("/models")
+    }
+
+    /// URL of an endpoint living next to chat completions, e.g. `/embeddings`.
+    fn sibling_url(&self, endpoint: &str) -> String {
+        if self.path_ends_with(endpoint) {
+            return self.base_url.clone();
+        }
+
+        let normalized_base = self.base_url.trim_end_matches('/');
+        if let Some(prefix) = normalized_base.strip_suffix("/chat/completions") {
+            return format!("{prefix}{endpoint}");
+        }
+
+        if self.has_explicit_api_path() {
+            format!("{normalized_base}{endpoint}")
+        } else {
+            format!("{normalized_base}/v1{endpoint}")
+        }
+    }
+
     fn tool_specs_to_openai_format(tools: &[crate::tools::ToolSpec]) -> Vec<serde_json::Value> {
         tools
             .iter()
@@ -563,153 +611,6 @@ struct ResponsesContent {
     text: Option<String>,
 }
 
-// ---------------------------------------------------------------
-// Streaming support (SSE parser)
-// ---------------------------------------------------------------
-
-/// Server-Sent Event stream chunk for OpenAI-compatible streaming.
-#[derive(Debug, Deserialize)]
-struct StreamChunkResponse {
-    choices: Vec<StreamChoice>,
-}
-
-#[derive(Debug, Deserialize)]
-struct StreamChoice {
-    delta: StreamDelta,
-    finish_reason: Option<String>,
-}
-
-#[derive(Debug, Deserialize)]
-struct StreamDelta {
-    #[serde(default)]
-    content: Option<String>,
-    /// Reasoning/thinking models may stream output via `reasoning_content`.
-    #[serde(default)]
-    reasoning_content: Option<String>,
-}
-
-/// Parse SSE (Server-Sent Events) stream from OpenAI-compatible providers.
-/// Handles the `data: {...}` format and `[DONE]` sentinel.
-fn parse_sse_line(line: &str) -> StreamResult<Option<String>> {
-    let line = line.trim();
-
-    // Skip empty lines and comments
-    if line.is_empty() || line.starts_with(':') {
-        return Ok(None);
-    }
-
-    // SSE format: "data: {...}"
-    if let Some(data) = line.strip_prefix("data:") {
-        let data = data.trim();
-
-        // Check for [DONE] sentinel
-        if data == "[DONE]" {
-            return Ok(None);
-        }
-
-        // Parse JSON delta
-        let chunk: StreamChunkResponse = serde_json::from_str(data).map_err(StreamError::Json)?;
-
-        // Extract content from delta
-        if let Some(choice) = chunk.choices.first() {
-            if let Some(content) = &choice.delta.content {
-                if !content.is_empty() {
-                    return Ok(Some(content.clone()));
-                }
-            }
-            // Fallback to reasoning_content for thinking models
-            if let Some(reasoning) = &choice.delta.reasoning_content {
-                return Ok(Some(reasoning.clone()));
-            }
-        }
-    }
-
-    Ok(None)
-}
-
-/// Convert SSE byte stream to text chunks.
-fn sse_bytes_to_chunks(
-    response: reqwest::Response,
-    count_tokens: bool,
-) -> stream::BoxStream<'static, StreamResult<StreamChunk>> {
-    // Create a channel to send chunks
-    let (tx, rx) = tokio::sync::mpsc::channel::<StreamResult<StreamChunk>>(100);
-
-    tokio::spawn(async move {
-        // Buffer for incomplete lines
-        let mut buffer = String::new();
-
-        // Get response body as bytes stream
-        match response.error_for_status_ref() {
-            Ok(_) => {}
-            Err(e) => {
-                let _ = tx.send(Err(StreamError::Http(e))).await;
-                return;
-            }
-        }
-
-        let mut bytes_stream = response.bytes_stream();
-
-        while let Some(item) = bytes_stream.next().await {
-            match item {
-                Ok(bytes) => {
-                    // Convert bytes to string and process line by line
-                    let text = match String::from_utf8(bytes.to_vec()) {
-                        Ok(t) => t,
-                        Err(e) => {
-                            let _ = tx
-                                .send(Err(StreamError::InvalidSse(format!(
-                                    "Invalid UTF-8: {}",
-                                    e
-                                ))))
-                                .await;
-                            break;
-                        }
-                    };
-
-                    buffer.push_str(&text);
-
-                    // Process complete lines
-                    while let Some(pos) = buffer.find('\n') {
-                        let line = buffer.drain(..=pos).collect::<String>();
-                        buffer = buffer[pos + 1..].to_string();
-
-                        match parse_sse_line(&line) {
-                            Ok(Some(content)) => {
-                                let mut chunk = StreamChunk::delta(content);
-                                if count_tokens {
-                                    chunk = chunk.with_token_estimate();
-                                }
-                                if tx.send(Ok(chunk)).await.is_err() {
-                                    return; // Receiver dropped
-                                }
-                            }
-                            Ok(None) => {}
-                            Err(e) => {
-                                let _ = tx.send(Err(e)).await;
-                                return;
-                            }
-                        }
-                    }
-                }
-                Err(e) => {
-                    let _ = tx.send(Err(StreamError::Http(e))).await;
-                    break;
-                }
-            }
-        }
-
-        // Send final chunk
-        let _ = tx.send(Ok(StreamChunk::final_chunk())).await;
-    });
-
-    // Convert channel receiver to stream
-    stream::unfold(rx, |mut rx| async {
-        rx.recv().await.map(|chunk| (chunk, rx))
-    })
-    .boxed()
-}
-
 fn first_nonempty(text: Option<&str>) -> Option<String> {
     text.and_then(|value| {
         let trimmed = value.trim();
@@ -1186,6 +1087,7 @@ impl Provider for OpenAiCompatibleProvider {
 
         if !response.status().is_success() {
             let status = response.status();
+            let retry_after = super::rate_limit_suffix(status, response.headers());
             let error = response.text().await?;
             let sanitized = super::sanitize_api_error(&error);
 
@@ -1201,7 +1103,10 @@ impl Provider for OpenAiCompatibleProvider {
                     });
             }
 
-            anyhow::bail!("{} API error ({status}): {sanitized}", self.name);
+            anyhow::bail!(
+                "{} API error ({status}): {sanitized}{retry_after}",
+                self.name
+            );
         }
 
         let body = response.text().await?;
@@ -1519,6 +1424,7 @@ impl Provider for OpenAiCompatibleProvider {
 
         if !response.status().is_success() {
             let status = response.status();
+            let retry_after = super::rate_limit_suffix(status, response.headers());
             let error = response.text().await?;
             let sanitized = super::sanitize_api_error(&error);
 
@@ -1554,7 +1460,10 @@ impl Provider for OpenAiCompatibleProvider {
                     });
             }
 
-            anyhow::bail!("{} API error ({status}): {sanitized}", self.name);
+            anyhow::bail!(
+                "{} API error ({status}): {sanitized}{retry_after}",
+                self.name
+            );
         }
 
         let native_response: ApiChatResponse = response.json().await?;
@@ -1590,100 +1499,100 @@ impl Provider for OpenAiCompatibleProvider {
         temperature: f64,
         options: StreamOptions,
     ) -> stream::BoxStream<'static, StreamResult<StreamChunk>> {
-        let credential = match self.credential.as_ref() {
-            Some(value) => value.clone(),
-            None => {
-                let provider_name = self.name.clone();
-                return stream::once(async move {
-                    Err(StreamError::Provider(format!(
-                        "{} API key not set",
-                        provider_name
-                    )))
-                })
-                .boxed();
-            }
-        };
-
         let mut messages = Vec::new();
         if let Some(sys) = system_prompt {
-            messages.push(Message {
-                role: "system".to_string(),
-                content: MessageContent::Text(sys.to_string()),
-            });
+            messages.push(ChatMessage::system(sys));
         }
-        messages.push(Message {
-            role: "user".to_string(),
-            content: Self::to_message_content("user", message, !self.merge_system_into_user),
-        });
+        messages.push(ChatMessage::user(message));
+        self.stream_chat_with_history(&messages, model, temperature, options)
+    }
+
+    fn stream_chat_with_history(
+        &self,
+        messages: &[ChatMessage],
+        model: &str,
+        temperature: f64,
+        options: StreamOptions,
+    ) -> stream::BoxStream<'static, StreamResult<StreamChunk>> {
+        let Some(credential) = self.credential.as_ref() else {
+            return sse::error_stream(format!("{} API key not set", self.name));
+        };
+
+        let effective_messages = if self.merge_system_into_user {
+            Self::flatten_system_messages(messages)
+        } else {
+            messages.to_vec()
+        };
+        let api_messages: Vec<Message> = effective_messages
+            .iter()
+            .map(|m| Message {
+                role: m.role.clone(),
+                content: Self::to_message_content(
+                    &m.role,
+                    &m.content,
+                    !self.merge_system_into_user,
+                ),
+            })
+            .collect();
 
         let request = ApiChatRequest {
             model: model.to_string(),
-            messages,
+            messages: api_messages,
             temperature,
-            stream: Some(options.enabled),
+            stream: Some(true),
             tools: None,
             tool_choice: None,
         };
 
         let url = self.chat_completions_url();
-        let client = self.http_client();
-        let auth_header = self.auth_header.clone();
-
-        // Use a channel to bridge the async HTTP response to the stream
-        let (tx, rx) = tokio::sync::mpsc::channel::<StreamResult<StreamChunk>>(100);
-
-        tokio::spawn(async move {
-            // Build request with auth
-            let mut req_builder = client.post(&url).json(&request);
+        let req = self.apply_auth_header(self.http_client().post(&url).json(&request), credential);
+        sse::stream_request(req, options.count_tokens, sse::chat_completions_delta)
+    }
 
-            // Apply auth header
-            req_builder = match &auth_header {
-                AuthStyle::Bearer => {
-                    req_builder.header("Authorization", format!("Bearer {}", credential))
-                }
-                AuthStyle::XApiKey => req_builder.header("x-api-key", &credential),
-                AuthStyle::Custom(header) => req_builder.header(header, &credential),
-            };
+    fn supports_embeddings(&self) -> bool {
+        true
+    }
 
-            // Set accept header for streaming
-            req_builder = req_builder.header("Accept", "text/event-stream");
+    async fn embed(&self, texts: &[&str], model: &str) -> anyhow::Result<Vec<Vec<f32>>> {
+        if texts.is_empty() {
+            return Ok(Vec::new());
+        }
+        let credential = self.credential.as_ref().ok_or_else(|| {
+            anyhow::anyhow!(
+                "{} API key not set. Run `zeroclaw onboard` or set the appropriate env var.",
+                self.name
+            )
+        })?;
 
-            // Send request
-            let response = match req_builder.send().await {
-                Ok(r) => r,
-                Err(e) => {
-                    let _ = tx.send(Err(StreamError::Http(e))).await;
-                    return;
-                }
-            };
+        let body = serde_json::json!({ "model": model, "input": texts });
+        let response = self
+            .apply_auth_header(
+                self.http_client().post(self.embeddings_url()).json(&body),
+                credential,
+            )
+            .send()
+            .await?;
+        if !response.status().is_success() {
+            return Err(super::api_error(&self.name, response).await);
+        }
 
-            // Check status
-            if !response.status().is_success() {
-                let status = response.status();
-                let error = match response.text().await {
-                    Ok(e) => e,
-                    Err(_) => format!("HTTP error: {}", status),
-                };
-                let _ = tx
-                    .send(Err(StreamError::Provider(format!("{}: {}", status, error))))
-                    .await;
-                return;
-            }
+        let body: serde_json::Value = response.json().await?;
+        parse_openai_embeddings(&body)
+    }
 
-            // Convert to chunk stream and forward to channel
-            let mut chunk_stream = sse_bytes_to_chunks(response, options.count_tokens);
-            while let Some(chunk) = chunk_stream.next().await {
-                if tx.send(chunk).await.is_err() {
-                    break; // Receiver dropped
-                }
-            }
-        });
+    /// Local servers (llama.cpp, vLLM, SGLang) list models without a key.
+    async fn list_models(&self) -> anyhow::Result<Vec<String>> {
+        let mut request = self.http_client().get(self.models_url());
+        if let Some(credential) = self.credential.as_ref() {
+            request = self.apply_auth_header(request, credential);
+        }
+        let response = request.send().await?;
+        if !response.status().is_success() {
+            return Err(super::api_error(&self.name, response).await);
+        }
 
-        // Convert channel receiver to stream
-        stream::unfold(rx, |mut rx| async move {
-            rx.recv().await.map(|chunk| (chunk, rx))
-        })
-        .boxed()
+        let body: serde_json::Value = response.json().await?;
+        Ok(parse_openai_model_ids(&body))
     }
 
     async fn warmup(&self) -> anyhow::Result<()> {
@@ -1721,6 +1630,16 @@ mod tests {
         assert_eq!(p.credential.as_deref(), Some("venice-test-credential"));
     }
 
+    #[test]
+    fn http_profile_is_kebab_case_name() {
+        let custom = make_provider("Custom", "https://gateway.example.com", None);
+        assert_eq!(custom.http_profile(), "custom");
+        let together = make_provider("Together AI", "https://api.together.xyz", None);
+        assert_eq!(together.http_profile(), "together-ai");
+        let zai = make_provider("Z.AI", "https://api.z.ai", None);
+        assert_eq!(zai.http_profile(), "z-ai");
+    }
+
     #[test]
     fn creates_without_key() {
         let p = make_provider("test", "https://example.com", None);
@@ -2091,6 +2010,50 @@ mod tests {
         assert_eq!(p.responses_url(), "https://api.example.com/v1/responses");
     }
 
+    #[test]
+    fn models_url_follows_base_path() {
+        let p = make_provider("test", "https://api.example.com", None);
+        assert_eq!(p.models_url(), "https://api.example.com/v1/models");
+
+        let p = make_provider("vllm", "http://localhost:8000/v1", None);
+        assert_eq!(p.models_url(), "http://localhost:8000/v1/models");
+
+        let p = make_provider(
+            "custom",
+            "https://my-api.example.com/api/v2/chat/completions",
+            None,
+        );
+        assert_eq!(p.models_url(), "https://my-api.example.com/api/v2/models");
+    }
+
+    #[test]
+    fn embeddings_url_follows_base_path() {
+        let p = make_provider("test", "https://api.example.com", None);
+        assert_eq!(p.embeddings_url(), "https://api.example.com/v1/embeddings");
+
+        let p = make_provider("test", "https://api.example.com/v1", None);
+        assert_eq!(p.embeddings_url(), "https://api.example.com/v1/embeddings");
+
+        let p = make_provider(
+            "custom",
+            "https://my-api.example.com/api/v2/chat/completions",
+            None,
+        );
+        assert_eq!(
+            p.embeddings_url(),
+            "https://my-api.example.com/api/v2/embeddings"
+        );
+    }
+
+    #[tokio::test]
+    async fn embed_requires_key() {
+        let p = make_provider("test", "https://example.com", None);
+        assert!(p.supports_embeddings());
+        assert!(p.embed(&[], "m").await.unwrap().is_empty());
+        let err = p.embed(&["hello"], "m").await.unwrap_err();
+        assert!(err.to_string().contains("API key not set"));
+    }
+
     #[test]
     fn responses_url_non_v1_api_path_uses_raw_suffix() {
         let p = make_provider("test", "https://api.example.com/api/coding/v3", None);
@@ -2717,46 +2680,6 @@ mod tests {
         assert_eq!(msg.effective_content(), "Hello from Venice!");
     }
 
-    // ----------------------------------------------------------
-    // SSE streaming reasoning_content fallback tests
-    // ----------------------------------------------------------
-
-    #[test]
-    fn parse_sse_line_with_content() {
-        let line = r#"data: {"choices":[{"delta":{"content":"hello"}}]}"#;
-        let result = parse_sse_line(line).unwrap();
-        assert_eq!(result, Some("hello".to_string()));
-    }
-
-    #[test]
-    fn parse_sse_line_with_reasoning_content() {
-        let line = r#"data: {"choices":[{"delta":{"reasoning_content":"thinking..."}}]}"#;
-        let result = parse_sse_line(line).unwrap();
-        assert_eq!(result, Some("thinking...".to_string()));
-    }
-
-    #[test]
-    fn parse_sse_line_with_both_prefers_content() {
-        let line = r#"data: {"choices":[{"delta":{"content":"real answer","reasoning_content":"thinking..."}}]}"#;
-        let result = parse_sse_line(line).unwrap();
-        assert_eq!(result, Some("real answer".to_string()));
-    }
-
-    #[test]
-    fn parse_sse_line_with_empty_content_falls_back_to_reasoning_content() {
-        let line =
-            r#"data: {"choices":[{"delta":{"content":"","reasoning_content":"thinking..."}}]}"#;
-        let result = parse_sse_line(line).unwrap();
-        assert_eq!(result, Some("thinking...".to_string()));
-    }
-
-    #[test]
-    fn parse_sse_line_done_sentinel() {
-        let line = "data: [DONE]";
-        let result = parse_sse_line(line).unwrap();
-        assert_eq!(result, None);
-    }
-
     #[test]
     fn api_response_parses_usage() {
         let json = r#"{
@@ -3,14 +3,23 @@
 //! - Gemini CLI OAuth tokens (reuse existing ~/.gemini/ authentication)
 //! - ZeroClaw auth-profiles OAuth tokens
 //! - Google Cloud ADC (`GOOGLE_APPLICATION_CREDENTIALS`)
+//! - Image input as `inlineData` parts and `[gemini]` safety settings
+//! - SSE streaming via `streamGenerateContent` (API-key auth only)
 
 use crate::auth::AuthService;
-use crate::providers::traits::{ChatMessage, ChatResponse, Provider, TokenUsage};
+use crate::multimodal;
+use crate::providers::sse::{self, SseEvent};
+use crate::providers::traits::{
+    ChatMessage, ChatResponse, Provider, StreamChunk, StreamError, StreamOptions, StreamResult,
+    TokenUsage,
+};
 use async_trait::async_trait;
 use base64::Engine;
 use directories::UserDirs;
+use futures_util::stream;
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
+use std::fmt::Write;
 use std::path::PathBuf;
 use std::sync::Arc;
 
@@ -24,6 +33,8 @@ pub struct GeminiProvider {
     auth_service: Option<AuthService>,
     /// Override profile name for managed auth.
     auth_profile_override: Option<String>,
+    /// `safetySettings` sent with every request (`[gemini]` config).
+    safety_settings: Vec<SafetySetting>,
 }
 
 /// Mutable OAuth token state — supports runtime refresh for long-lived processes.
@@ -89,6 +100,8 @@ struct GenerateContentRequest {
     system_instruction: Option<Content>,
     #[serde(rename = "generationConfig")]
     generation_config: GenerationConfig,
+    #[serde(rename = "safetySettings", skip_serializing_if = "Vec::is_empty")]
+    safety_settings: Vec<SafetySetting>,
 }
 
 /// Request envelope for the internal cloudcode-pa API.
@@ -125,6 +138,8 @@ struct InternalGenerateContentRequest {
     system_instruction: Option<Content>,
     #[serde(rename = "generationConfig", skip_serializing_if = "Option::is_none")]
     generation_config: Option<GenerationConfig>,
+    #[serde(rename = "safetySettings", skip_serializing_if = "Vec::is_empty")]
+    safety_settings: Vec<SafetySetting>,
 }
 
 #[derive(Debug, Serialize, Clone)]
@@ -136,7 +151,33 @@ struct Content {
 
 #[derive(Debug, Serialize, Clone)]
 struct Part {
-    text: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    text: Option<String>,
+    #[serde(rename = "inlineData", skip_serializing_if = "Option::is_none")]
+    inline_data: Option<InlineData>,
+}
+
+impl Part {
+    fn text(text: impl Into<String>) -> Self {
+        Self {
+            text: Some(text.into()),
+            inline_data: None,
+        }
+    }
+}
+
+/// Base64 media embedded in a request part (images).
+#[derive(Debug, Serialize, Clone)]
+struct InlineData {
+    #[serde(rename = "mimeType")]
+    mime_type: String,
+    data: String,
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq, Eq)]
+struct SafetySetting {
+    category: String,
+    threshold: String,
 }
 
 #[derive(Debug, Serialize, Clone)]
@@ -149,6 +190,8 @@ struct GenerationConfig {
 #[derive(Debug, Deserialize)]
 struct GenerateContentResponse {
     candidates: Option<Vec<Candidate>>,
+    #[serde(default, rename = "promptFeedback")]
+    prompt_feedback: Option<PromptFeedback>,
     error: Option<ApiError>,
     #[serde(default)]
     response: Option<Box<GenerateContentResponse>>,
@@ -171,10 +214,18 @@ struct InternalGenerateContentResponse {
     response: GenerateContentResponse,
 }
 
+#[derive(Debug, Deserialize)]
+struct PromptFeedback {
+    #[serde(default, rename = "blockReason")]
+    block_reason: Option<String>,
+}
+
 #[derive(Debug, Deserialize)]
 struct Candidate {
     #[serde(default)]
     content: Option<CandidateContent>,
+    #[serde(default, rename = "finishReason")]
+    finish_reason: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -449,6 +500,7 @@ impl GeminiProvider {
             oauth_index: Arc::new(tokio::sync::Mutex::new(0)),
             auth_service: None,
             auth_profile_override: None,
+            safety_settings: Vec::new(),
         }
     }
 
@@ -522,9 +574,61 @@ impl GeminiProvider {
                 None
             },
             auth_profile_override: profile_override,
+            safety_settings: Vec::new(),
         }
     }
 
+    /// Apply `[gemini]` safety settings to every request.
+    pub fn with_safety_settings(mut self, settings: &[crate::config::GeminiSafetySetting]) -> Self {
+        self.safety_settings = settings
+            .iter()
+            .map(|setting| SafetySetting {
+                category: setting.category.clone(),
+                threshold: setting.threshold.clone(),
+            })
+            .collect();
+        self
+    }
+
+    /// Split a user message into text and `inlineData` parts. Image markers
+    /// were normalized to data URIs by `multimodal::prepare_messages_for_provider`;
+    /// anything else is left in the text.
+    fn user_parts(content: &str) -> Vec<Part> {
+        let (cleaned_text, image_refs) = multimodal::parse_image_markers(content);
+        if image_refs.is_empty() {
+            return vec![Part::text(content)];
+        }
+
+        let mut text = cleaned_text;
+        let mut images = Vec::with_capacity(image_refs.len());
+        for image_ref in image_refs {
+            let inline = image_ref
+                .strip_prefix("data:")
+                .and_then(|rest| rest.split_once(";base64,"))
+                .map(|(mime_type, data)| InlineData {
+                    mime_type: mime_type.to_string(),
+                    data: data.to_string(),
+                });
+            match inline {
+                Some(inline_data) => images.push(Part {
+                    text: None,
+                    inline_data: Some(inline_data),
+                }),
+                None => {
+                    let _ = write!(text, "\n[IMAGE:{image_ref}]");
+                }
+            }
+        }
+
+        let mut parts = Vec::with_capacity(images.len() + 1);
+        let text = text.trim();
+        if !text.is_empty() {
+            parts.push(Part::text(text));
+        }
+        parts.extend(images);
+        parts
+    }
+
     fn normalize_non_empty(value: &str) -> Option<String> {
         let trimmed = value.trim();
         if trimmed.is_empty() {
@@ -898,6 +1002,7 @@ impl GeminiProvider {
                         } else {
                             None
                         },
+                        safety_settings: request.safety_settings.clone(),
                     },
                 };
                 self.http_client()
@@ -922,6 +1027,78 @@ impl GeminiProvider {
             || error_text.contains(r#"Unknown name \"generationConfig\""#)
     }
 
+    /// Split history into a `systemInstruction` and `contents`; Gemini uses
+    /// the `model` role for assistant turns.
+    fn convert_messages(messages: &[ChatMessage]) -> (Option<Content>, Vec<Content>) {
+        let mut system_parts: Vec<&str> = Vec::new();
+        let mut contents: Vec<Content> = Vec::new();
+
+        for msg in messages {
+            match msg.role.as_str() {
+                "system" => system_parts.push(&msg.content),
+                "user" => contents.push(Content {
+                    role: Some("user".to_string()),
+                    parts: Self::user_parts(&msg.content),
+                }),
+                "assistant" => contents.push(Content {
+                    role: Some("model".to_string()),
+                    parts: vec![Part::text(msg.content.clone())],
+                }),
+                _ => {}
+            }
+        }
+
+        let system_instruction = if system_parts.is_empty() {
+            None
+        } else {
+            Some(Content {
+                role: None,
+                parts: vec![Part::text(system_parts.join("\n\n"))],
+            })
+        };
+        (system_instruction, contents)
+    }
+
+    /// Text carried by one `streamGenerateContent` event. Thought parts are
+    /// skipped; API errors and safety blocks abort the stream.
+    fn parse_stream_event(event: &SseEvent) -> StreamResult<Option<String>> {
+        let chunk: GenerateContentResponse =
+            serde_json::from_str(&event.data).map_err(StreamError::Json)?;
+        if let Some(err) = chunk.error {
+            return Err(StreamError::Provider(format!(
+                "Gemini API error: {}",
+                err.message
+            )));
+        }
+        if let Some(reason) = chunk.prompt_feedback.and_then(|f| f.block_reason) {
+            return Err(StreamError::Provider(format!(
+                "Gemini blocked the prompt ({reason})"
+            )));
+        }
+        let Some(candidate) = chunk.candidates.and_then(|c| c.into_iter().next()) else {
+            return Ok(None);
+        };
+        if let Some(reason @ ("SAFETY" | "PROHIBITED_CONTENT" | "BLOCKLIST")) =
+            candidate.finish_reason.as_deref()
+        {
+            return Err(StreamError::Provider(format!(
+                "Gemini blocked the response ({reason})"
+            )));
+        }
+        let text: String = candidate
+            .content
+            .map(|content| {
+                content
+                    .parts
+                    .into_iter()
+                    .filter(|part| !part.thought)
+                    .filter_map(|part| part.text)
+                    .collect()
+            })
+            .unwrap_or_default();
+        Ok(Some(text).filter(|t| !t.is_empty()))
+    }
+
     fn should_rotate_oauth_on_error(status: reqwest::StatusCode, error_text: &str) -> bool {
         status == reqwest::StatusCode::TOO_MANY_REQUESTS
             || status == reqwest::StatusCode::SERVICE_UNAVAILABLE
@@ -987,6 +1164,7 @@ impl GeminiProvider {
                 temperature,
                 max_output_tokens: 8192,
             },
+            safety_settings: self.safety_settings.clone(),
         };
 
         let url = Self::build_generate_content_url(model, auth);
@@ -1130,19 +1308,62 @@ impl GeminiProvider {
             output_tokens: u.candidates_token_count,
         });
 
-        let text = result
-            .candidates
-            .and_then(|c| c.into_iter().next())
+        if let Some(reason) = result.prompt_feedback.and_then(|f| f.block_reason) {
+            anyhow::bail!(
+                "Gemini blocked the prompt ({reason}); adjust [gemini].safety_settings if this is a false positive"
+            );
+        }
+        let candidate = result.candidates.and_then(|c| c.into_iter().next());
+        let finish_reason = candidate.as_ref().and_then(|c| c.finish_reason.clone());
+        let text = candidate
             .and_then(|c| c.content)
             .and_then(|c| c.effective_text())
-            .ok_or_else(|| anyhow::anyhow!("No response from Gemini"))?;
+            .ok_or_else(|| match finish_reason.as_deref() {
+                Some(reason @ ("SAFETY" | "PROHIBITED_CONTENT" | "BLOCKLIST")) => anyhow::anyhow!(
+                    "Gemini blocked the response ({reason}); adjust [gemini].safety_settings if this is a false positive"
+                ),
+                _ => anyhow::anyhow!("No response from Gemini"),
+            })?;
 
         Ok((text, usage))
     }
 }
 
+/// Model IDs from a `v1beta/models` response, limited to models that
+/// support `generateContent`.
+fn parse_gemini_model_ids(body: &serde_json::Value) -> Vec<String> {
+    let Some(models) = body.get("models").and_then(serde_json::Value::as_array) else {
+        return Vec::new();
+    };
+    let mut ids: Vec<String> = models
+        .iter()
+        .filter(|model| {
+            model
+                .get("supportedGenerationMethods")
+                .and_then(serde_json::Value::as_array)
+                .is_none_or(|methods| {
+                    methods
+                        .iter()
+                        .any(|method| method.as_str() == Some("generateContent"))
+                })
+        })
+        .filter_map(|model| model.get("name").and_then(serde_json::Value::as_str))
+        .map(|name| name.trim_start_matches("models/").to_string())
+        .collect();
+    ids.sort();
+    ids.dedup();
+    ids
+}
+
 #[async_trait]
 impl Provider for GeminiProvider {
+    fn capabilities(&self) -> crate::providers::traits::ProviderCapabilities {
+        crate::providers::traits::ProviderCapabilities {
+            native_tool_calling: false,
+            vision: true,
+        }
+    }
+
     async fn chat_with_system(
         &self,
         system_prompt: Option<&str>,
@@ -1152,16 +1373,12 @@ impl Provider for GeminiProvider {
     ) -> anyhow::Result<String> {
         let system_instruction = system_prompt.map(|sys| Content {
             role: None,
-            parts: vec![Part {
-                text: sys.to_string(),
-            }],
+            parts: vec![Part::text(sys.to_string())],
         });
 
         let contents = vec![Content {
             role: Some("user".to_string()),
-            parts: vec![Part {
-                text: message.to_string(),
-            }],
+            parts: vec![Part::text(message.to_string())],
         }];
 
         let (text, _usage) = self
@@ -1176,46 +1393,7 @@ impl Provider for GeminiProvider {
         model: &str,
         temperature: f64,
     ) -> anyhow::Result<String> {
-        let mut system_parts: Vec<&str> = Vec::new();
-        let mut contents: Vec<Content> = Vec::new();
-
-        for msg in messages {
-            match msg.role.as_str() {
-                "system" => {
-                    system_parts.push(&msg.content);
-                }
-                "user" => {
-                    contents.push(Content {
-                        role: Some("user".to_string()),
-                        parts: vec![Part {
-                            text: msg.content.clone(),
-                        }],
-                    });
-                }
-                "assistant" => {
-                    // Gemini API uses "model" role instead of "assistant"
-                    contents.push(Content {
-                        role: Some("model".to_string()),
-                        parts: vec![Part {
-                            text: msg.content.clone(),
-                        }],
-                    });
-                }
-                _ => {}
-            }
-        }
-
-        let system_instruction = if system_parts.is_empty() {
-            None
-        } else {
-            Some(Content {
-                role: None,
-                parts: vec![Part {
-                    text: system_parts.join("\n\n"),
-                }],
-            })
-        };
-
+        let (system_instruction, contents) = Self::convert_messages(messages);
         let (text, _usage) = self
             .send_generate_content(contents, system_instruction, model, temperature)
             .await?;
@@ -1228,39 +1406,7 @@ impl Provider for GeminiProvider {
         model: &str,
         temperature: f64,
     ) -> anyhow::Result<ChatResponse> {
-        let mut system_parts: Vec<&str> = Vec::new();
-        let mut contents: Vec<Content> = Vec::new();
-
-        for msg in request.messages {
-            match msg.role.as_str() {
-                "system" => system_parts.push(&msg.content),
-                "user" => contents.push(Content {
-                    role: Some("user".to_string()),
-                    parts: vec![Part {
-                        text: msg.content.clone(),
-                    }],
-                }),
-                "assistant" => contents.push(Content {
-                    role: Some("model".to_string()),
-                    parts: vec![Part {
-                        text: msg.content.clone(),
-                    }],
-                }),
-                _ => {}
-            }
-        }
-
-        let system_instruction = if system_parts.is_empty() {
-            None
-        } else {
-            Some(Content {
-                role: None,
-                parts: vec![Part {
-                    text: system_parts.join("\n\n"),
-                }],
-            })
-        };
-
+        let (system_instruction, contents) = Self::convert_messages(request.messages);
         let (text, usage) = self
             .send_generate_content(contents, system_instruction, model, temperature)
             .await?;
@@ -1273,6 +1419,81 @@ impl Provider for GeminiProvider {
         })
     }
 
+    /// Streaming uses the public API, so it is limited to API-key auth;
+    /// OAuth credentials fall back to the non-streaming path.
+    fn supports_streaming(&self) -> bool {
+        self.auth.as_ref().is_some_and(GeminiAuth::is_api_key)
+    }
+
+    fn stream_chat_with_system(
+        &self,
+        system_prompt: Option<&str>,
+        message: &str,
+        model: &str,
+        temperature: f64,
+        options: StreamOptions,
+    ) -> stream::BoxStream<'static, StreamResult<StreamChunk>> {
+        let mut messages = Vec::new();
+        if let Some(sys) = system_prompt {
+            messages.push(ChatMessage::system(sys));
+        }
+        messages.push(ChatMessage::user(message));
+        self.stream_chat_with_history(&messages, model, temperature, options)
+    }
+
+    fn stream_chat_with_history(
+        &self,
+        messages: &[ChatMessage],
+        model: &str,
+        temperature: f64,
+        options: StreamOptions,
+    ) -> stream::BoxStream<'static, StreamResult<StreamChunk>> {
+        let Some(auth) = self.auth.as_ref().filter(|auth| auth.is_api_key()) else {
+            return sse::error_stream("Gemini streaming requires API key authentication");
+        };
+
+        let (system_instruction, contents) = Self::convert_messages(messages);
+        let request = GenerateContentRequest {
+            contents,
+            system_instruction,
+            generation_config: GenerationConfig {
+                temperature,
+                max_output_tokens: 8192,
+            },
+            safety_settings: self.safety_settings.clone(),
+        };
+        let url = format!(
+            "{PUBLIC_API_ENDPOINT}/{}:streamGenerateContent?alt=sse&key={}",
+            Self::format_model_name(model),
+            auth.api_key_credential()
+        );
+        sse::stream_request(
+            self.http_client().post(url).json(&request),
+            options.count_tokens,
+            Self::parse_stream_event,
+        )
+    }
+
+    /// Only API-key auth can read the public model catalog; the OAuth
+    /// (Code Assist) endpoint has no model list.
+    async fn list_models(&self) -> anyhow::Result<Vec<String>> {
+        let Some(auth) = self.auth.as_ref().filter(|auth| auth.is_api_key()) else {
+            anyhow::bail!("Gemini model listing requires an API key (GEMINI_API_KEY)");
+        };
+        let response = self
+            .http_client()
+            .get("https://generativelanguage.googleapis.com/v1beta/models")
+            .query(&[("key", auth.api_key_credential()), ("pageSize", "1000")])
+            .send()
+            .await?;
+        if !response.status().is_success() {
+            return Err(super::api_error("Gemini", response).await);
+        }
+
+        let body: serde_json::Value = response.json().await?;
+        Ok(parse_gemini_model_ids(&body))
+    }
+
     async fn warmup(&self) -> anyhow::Result<()> {
         if let Some(auth) = self.auth.as_ref() {
             match auth {
@@ -1348,6 +1569,7 @@ mod tests {
             oauth_index: Arc::new(tokio::sync::Mutex::new(0)),
             auth_service: None,
             auth_profile_override: None,
+            safety_settings: Vec::new(),
         }
     }
 
@@ -1542,11 +1764,10 @@ mod tests {
         let body = GenerateContentRequest {
             contents: vec![Content {
                 role: Some("user".into()),
-                parts: vec![Part {
-                    text: "hello".into(),
-                }],
+                parts: vec![Part::text("hello")],
             }],
             system_instruction: None,
+            safety_settings: Vec::new(),
             generation_config: GenerationConfig {
                 temperature: 0.7,
                 max_output_tokens: 8192,
@@ -1583,11 +1804,10 @@ mod tests {
         let body = GenerateContentRequest {
             contents: vec![Content {
                 role: Some("user".into()),
-                parts: vec![Part {
-                    text: "hello".into(),
-                }],
+                parts: vec![Part::text("hello")],
             }],
             system_instruction: None,
+            safety_settings: Vec::new(),
             generation_config: GenerationConfig {
                 temperature: 0.7,
                 max_output_tokens: 8192,
@@ -1627,11 +1847,10 @@ mod tests {
         let body = GenerateContentRequest {
             contents: vec![Content {
                 role: Some("user".into()),
-                parts: vec![Part {
-                    text: "hello".into(),
-                }],
+                parts: vec![Part::text("hello")],
             }],
             system_instruction: None,
+            safety_settings: Vec::new(),
             generation_config: GenerationConfig {
                 temperature: 0.7,
                 max_output_tokens: 8192,
@@ -1659,20 +1878,17 @@ mod tests {
         let request = GenerateContentRequest {
             contents: vec![Content {
                 role: Some("user".to_string()),
-                parts: vec![Part {
-                    text: "Hello".to_string(),
-                }],
+                parts: vec![Part::text("Hello")],
             }],
             system_instruction: Some(Content {
                 role: None,
-                parts: vec![Part {
-                    text: "You are helpful".to_string(),
-                }],
+                parts: vec![Part::text("You are helpful")],
             }),
             generation_config: GenerationConfig {
                 temperature: 0.7,
                 max_output_tokens: 8192,
             },
+            safety_settings: Vec::new(),
         };
 
         let json = serde_json::to_string(&request).unwrap();
@@ -1682,6 +1898,90 @@ mod tests {
         assert!(!json.contains("\"system_instruction\""));
         assert!(json.contains("\"temperature\":0.7"));
         assert!(json.contains("\"maxOutputTokens\":8192"));
+        assert!(!json.contains("safetySettings"));
+    }
+
+    #[test]
+    fn safety_settings_serialize_in_public_and_internal_requests() {
+        let provider =
+            test_provider(None).with_safety_settings(&[crate::config::GeminiSafetySetting {
+                category: "HARM_CATEGORY_DANGEROUS_CONTENT".into(),
+                threshold: "BLOCK_ONLY_HIGH".into(),
+            }]);
+        let request = GenerateContentRequest {
+            contents: vec![Content {
+                role: Some("user".to_string()),
+                parts: vec![Part::text("Hello")],
+            }],
+            system_instruction: None,
+            generation_config: GenerationConfig {
+                temperature: 0.7,
+                max_output_tokens: 8192,
+            },
+            safety_settings: provider.safety_settings.clone(),
+        };
+        let json = serde_json::to_value(&request).unwrap();
+        assert_eq!(
+            json["safetySettings"][0]["category"],
+            "HARM_CATEGORY_DANGEROUS_CONTENT"
+        );
+        assert_eq!(json["safetySettings"][0]["threshold"], "BLOCK_ONLY_HIGH");
+
+        let auth = test_oauth_auth("ya29.mock-token");
+        let url = GeminiProvider::build_generate_content_url("gemini-2.0-flash", &auth);
+        let built = provider
+            .build_generate_content_request(
+                &auth,
+                &url,
+                &request,
+                "gemini-2.0-flash",
+                true,
+                Some("test-project"),
+                Some("ya29.mock-token"),
+            )
+            .build()
+            .unwrap();
+        let body: serde_json::Value =
+            serde_json::from_slice(built.body().unwrap().as_bytes().unwrap()).unwrap();
+        assert_eq!(
+            body["request"]["safetySettings"][0]["threshold"],
+            "BLOCK_ONLY_HIGH"
+        );
+    }
+
+    #[test]
+    fn user_parts_convert_data_uri_images_to_inline_data() {
+        let parts = GeminiProvider::user_parts(
+            "What is this?\n\n[IMAGE:data:image/png;base64,iVBORw0KGgo=]",
+        );
+        let json = serde_json::to_value(&parts).unwrap();
+        assert_eq!(json[0]["text"], "What is this?");
+        assert_eq!(json[1]["inlineData"]["mimeType"], "image/png");
+        assert_eq!(json[1]["inlineData"]["data"], "iVBORw0KGgo=");
+        assert!(json[1].get("text").is_none());
+
+        let plain = GeminiProvider::user_parts("no images here");
+        assert_eq!(plain.len(), 1);
+        assert_eq!(plain[0].text.as_deref(), Some("no images here"));
+    }
+
+    #[test]
+    fn blocked_responses_deserialize_reasons() {
+        let json = r#"{
+            "promptFeedback": {"blockReason": "SAFETY"},
+            "candidates": [{"finishReason": "SAFETY"}]
+        }"#;
+        let response: GenerateContentResponse = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            response
+                .prompt_feedback
+                .and_then(|f| f.block_reason)
+                .as_deref(),
+            Some("SAFETY")
+        );
+        let candidate = response.candidates.unwrap().into_iter().next().unwrap();
+        assert_eq!(candidate.finish_reason.as_deref(), Some("SAFETY"));
+        assert!(candidate.content.is_none());
     }
 
     #[test]
@@ -1693,11 +1993,10 @@ mod tests {
             request: InternalGenerateContentRequest {
                 contents: vec![Content {
                     role: Some("user".to_string()),
-                    parts: vec![Part {
-                        text: "Hello".to_string(),
-                    }],
+                    parts: vec![Part::text("Hello")],
                 }],
                 system_instruction: None,
+                safety_settings: Vec::new(),
                 generation_config: Some(GenerationConfig {
                     temperature: 0.7,
                     max_output_tokens: 8192,
@@ -1725,11 +2024,10 @@ mod tests {
             request: InternalGenerateContentRequest {
                 contents: vec![Content {
                     role: Some("user".to_string()),
-                    parts: vec![Part {
-                        text: "Hello".to_string(),
-                    }],
+                    parts: vec![Part::text("Hello")],
                 }],
                 system_instruction: None,
+                safety_settings: Vec::new(),
                 generation_config: None,
             },
         };
@@ -1748,11 +2046,10 @@ mod tests {
             request: InternalGenerateContentRequest {
                 contents: vec![Content {
                     role: Some("user".to_string()),
-                    parts: vec![Part {
-                        text: "Hello".to_string(),
-                    }],
+                    parts: vec![Part::text("Hello")],
                 }],
                 system_instruction: None,
+                safety_settings: Vec::new(),
                 generation_config: None,
             },
         };
@@ -2060,6 +2357,32 @@ mod tests {
         assert_eq!(text, Some("final answer".to_string()));
     }
 
+    #[test]
+    fn model_ids_keep_generate_content_models() {
+        let body = serde_json::json!({"models": [
+            {
+                "name": "models/gemini-2.5-flash",
+                "supportedGenerationMethods": ["generateContent", "countTokens"]
+            },
+            {
+                "name": "models/text-embedding-004",
+                "supportedGenerationMethods": ["embedContent"]
+            },
+            {"name": "models/gemini-2.5-pro"}
+        ]});
+        assert_eq!(
+            parse_gemini_model_ids(&body),
+            vec!["gemini-2.5-flash", "gemini-2.5-pro"]
+        );
+    }
+
+    #[tokio::test]
+    async fn list_models_requires_api_key() {
+        let provider = test_provider(Some(test_oauth_auth("ya29.mock-token")));
+        let err = provider.list_models().await.unwrap_err();
+        assert!(err.to_string().contains("API key"));
+    }
+
     #[tokio::test]
     async fn warmup_without_key_is_noop() {
         let provider = test_provider(None);
@@ -2121,6 +2444,7 @@ mod tests {
             oauth_index: Arc::new(tokio::sync::Mutex::new(0)),
             auth_service: None, // Missing auth_service
             auth_profile_override: None,
+            safety_settings: Vec::new(),
         };
 
         let result = provider.warmup().await;
@@ -2139,4 +2463,35 @@ mod tests {
         // Should succeed without making HTTP requests
         assert!(result.is_ok());
     }
+
+    #[test]
+    fn streaming_requires_api_key_auth() {
+        let api_key = test_provider(Some(GeminiAuth::ExplicitKey("key".into())));
+        assert!(api_key.supports_streaming());
+        let oauth = test_provider(Some(test_oauth_auth("token")));
+        assert!(!oauth.supports_streaming());
+        assert!(!test_provider(None).supports_streaming());
+    }
+
+    #[test]
+    fn stream_events_skip_thoughts_and_surface_blocks() {
+        let event = |data: &str| SseEvent {
+            event: None,
+            data: data.to_string(),
+        };
+        let text = event(
+            r#"{"candidates":[{"content":{"parts":[{"thought":true,"text":"hmm"},{"text":"Hello"}]}}]}"#,
+        );
+        assert_eq!(
+            GeminiProvider::parse_stream_event(&text).unwrap(),
+            Some("Hello".to_string())
+        );
+
+        let done = event(r#"{"candidates":[{"finishReason":"STOP"}],"usageMetadata":{}}"#);
+        assert_eq!(GeminiProvider::parse_stream_event(&done).unwrap(), None);
+
+        let blocked = event(r#"{"candidates":[{"finishReason":"SAFETY"}]}"#);
+        let err = GeminiProvider::parse_stream_event(&blocked).unwrap_err();
+        assert!(err.to_string().contains("SAFETY"));
+    }
 }
@@ -0,0 +1,201 @@
+//! Groq LPU inference through its OpenAI-compatible endpoint.
+//!
+//! Requests go through [`OpenAiCompatibleProvider`]; this wrapper maps
+//! shorthand and retired model names onto Groq's current model IDs. Groq
+//! reports rate limits in `retry-after` and `x-ratelimit-reset-*` headers,
+//! which [`super::api_error`] turns into the retry hint
+//! [`ReliableProvider`](super::reliable::ReliableProvider) backs off by.
+//! Groq has no embeddings endpoint.
+
+use super::compatible::{AuthStyle, OpenAiCompatibleProvider};
+use super::traits::{
+    ChatMessage, ChatRequest, ChatResponse, ProviderCapabilities, StreamChunk, StreamOptions,
+    StreamResult, ToolsPayload,
+};
+use super::Provider;
+use crate::tools::ToolSpec;
+use async_trait::async_trait;
+use futures_util::stream;
+
+pub const BASE_URL: &str = "https://api.groq.com/openai/v1";
+
+/// Shorthand and retired model names, mapped to current Groq model IDs.
+const MODEL_ALIASES: &[(&str, &str)] = &[
+    ("llama-3.3-70b", "llama-3.3-70b-versatile"),
+    ("llama3-70b-8192", "llama-3.3-70b-versatile"),
+    ("llama-3.1-70b-versatile", "llama-3.3-70b-versatile"),
+    ("llama-3.1-8b", "llama-3.1-8b-instant"),
+    ("llama3-8b-8192", "llama-3.1-8b-instant"),
+    ("llama-4-scout", "meta-llama/llama-4-scout-17b-16e-instruct"),
+    (
+        "llama-4-maverick",
+        "meta-llama/llama-4-maverick-17b-128e-instruct",
+    ),
+    ("gpt-oss-120b", "openai/gpt-oss-120b"),
+    ("gpt-oss-20b", "openai/gpt-oss-20b"),
+    ("kimi-k2", "moonshotai/kimi-k2-instruct"),
+    ("kimi-k2-instruct", "moonshotai/kimi-k2-instruct"),
+    ("qwen3-32b", "qwen/qwen3-32b"),
+];
+
+/// Groq model ID for `model`. Unknown names pass through unchanged.
+pub fn resolve_model(model: &str) -> &str {
+    let trimmed = model.trim();
+    MODEL_ALIASES
+        .iter()
+        .find(|(alias, _)| alias.eq_ignore_ascii_case(trimmed))
+        .map_or(model, |(_, id)| id)
+}
+
+pub struct GroqProvider {
+    inner: OpenAiCompatibleProvider,
+}
+
+impl GroqProvider {
+    pub fn new(credential: Option<&str>) -> Self {
+        Self {
+            inner: OpenAiCompatibleProvider::new("Groq", BASE_URL, credential, AuthStyle::Bearer),
+        }
+    }
+}
+
+#[async_trait]
+impl Provider for GroqProvider {
+    fn capabilities(&self) -> ProviderCapabilities {
+        self.inner.capabilities()
+    }
+
+    fn convert_tools(&self, tools: &[ToolSpec]) -> ToolsPayload {
+        self.inner.convert_tools(tools)
+    }
+
+    async fn warmup(&self) -> anyhow::Result<()> {
+        self.inner.warmup().await
+    }
+
+    async fn list_models(&self) -> anyhow::Result<Vec<String>> {
+        self.inner.list_models().await
+    }
+
+    async fn chat_with_system(
+        &self,
+        system_prompt: Option<&str>,
+        message: &str,
+        model: &str,
+        temperature: f64,
+    ) -> anyhow::Result<String> {
+        self.inner
+            .chat_with_system(system_prompt, message, resolve_model(model), temperature)
+            .await
+    }
+
+    async fn chat_with_history(
+        &self,
+        messages: &[ChatMessage],
+        model: &str,
+        temperature: f64,
+    ) -> anyhow::Result<String> {
+        self.inner
+            .chat_with_history(messages, resolve_model(model), temperature)
+            .await
+    }
+
+    async fn chat(
+        &self,
+        request: ChatRequest<'_>,
+        model: &str,
+        temperature: f64,
+    ) -> anyhow::Result<ChatResponse> {
+        self.inner
+            .chat(request, resolve_model(model), temperature)
+            .await
+    }
+
+    fn supports_native_tools(&self) -> bool {
+        self.inner.supports_native_tools()
+    }
+
+    fn supports_native_tools_for_model(&self, model: &str) -> bool {
+        self.inner
+            .supports_native_tools_for_model(resolve_model(model))
+    }
+
+    async fn chat_with_tools(
+        &self,
+        messages: &[ChatMessage],
+        tools: &[serde_json::Value],
+        model: &str,
+        temperature: f64,
+    ) -> anyhow::Result<ChatResponse> {
+        self.inner
+            .chat_with_tools(messages, tools, resolve_model(model), temperature)
+            .await
+    }
+
+    fn supports_streaming(&self) -> bool {
+        self.inner.supports_streaming()
+    }
+
+    fn stream_chat_with_system(
+        &self,
+        system_prompt: Option<&str>,
+        message: &str,
+        model: &str,
+        temperature: f64,
+        options: StreamOptions,
+    ) -> stream::BoxStream<'static, StreamResult<StreamChunk>> {
+        self.inner.stream_chat_with_system(
+            system_prompt,
+            message,
+            resolve_model(model),
+            temperature,
+            options,
+        )
+    }
+
+    fn stream_chat_with_history(
+        &self,
+        messages: &[ChatMessage],
+        model: &str,
+        temperature: f64,
+        options: StreamOptions,
+    ) -> stream::BoxStream<'static, StreamResult<StreamChunk>> {
+        self.inner
+            .stream_chat_with_history(messages, resolve_model(model), temperature, options)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resolves_shorthand_and_retired_model_names() {
+        assert_eq!(resolve_model("llama3-70b-8192"), "llama-3.3-70b-versatile");
+        assert_eq!(resolve_model("Llama-3.1-8B"), "llama-3.1-8b-instant");
+        assert_eq!(resolve_model("gpt-oss-120b"), "openai/gpt-oss-120b");
+        assert_eq!(
+            resolve_model("llama-3.3-70b-versatile"),
+            "llama-3.3-70b-versatile"
+        );
+        assert_eq!(resolve_model("my-new-model"), "my-new-model");
+    }
+
+    #[test]
+    fn groq_has_no_embeddings_or_vision() {
+        let provider = GroqProvider::new(Some("gsk-test"));
+        assert!(!provider.supports_embeddings());
+        assert!(!provider.supports_vision());
+        assert!(provider.supports_native_tools());
+    }
+
+    #[tokio::test]
+    async fn chat_fails_without_key() {
+        let provider = GroqProvider::new(None);
+        let err = provider
+            .simple_chat("hello", "llama-3.1-8b", 0.0)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("Groq"));
+    }
+}
@@ -0,0 +1,570 @@
+//! On-device inference through llama.cpp.
+//!
+//! Loads a GGUF model from the workspace and generates replies (or embeddings)
+//! in-process, so the agent keeps working without network access. Only one
+//! model is kept in memory and requests are served one at a time; phones
+//! rarely have the RAM for more. Requires the `local-llm` feature; without it
+//! the provider is still registered but every request fails with a rebuild
+//! hint.
+
+use crate::config::LocalLlmConfig;
+use crate::providers::traits::{ChatMessage, Provider};
+use async_trait::async_trait;
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+
+/// Inference threads when `local_llm.threads = 0`. Most phone SoCs pair four
+/// performance cores with slower efficiency cores; spilling onto the latter
+/// makes token generation slower, not faster.
+const MAX_AUTO_THREADS: usize = 4;
+
+/// Workspace directories scanned for GGUF files by `list_models`.
+const MODEL_DIRS: &[&str] = &["", "models"];
+
+pub struct LlamaCppProvider {
+    workspace_dir: Option<PathBuf>,
+    settings: LocalLlmConfig,
+}
+
+/// A chat turn in the shape chat templates expect.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub(super) struct PromptMessage {
+    pub role: &'static str,
+    pub content: String,
+}
+
+/// Map history onto system/user/assistant turns, folding tool results into
+/// user turns and merging consecutive turns of the same role, which several
+/// chat templates reject.
+pub(super) fn prompt_messages(messages: &[ChatMessage]) -> Vec<PromptMessage> {
+    let mut prompt: Vec<PromptMessage> = Vec::new();
+    for message in messages {
+        let (role, content) = match message.role.as_str() {
+            "system" => ("system", message.content.clone()),
+            "assistant" => ("assistant", message.content.clone()),
+            "tool" => ("user", format!("[Tool results]\n{}", message.content)),
+            _ => ("user", message.content.clone()),
+        };
+        match prompt.last_mut() {
+            Some(last) if last.role == role => {
+                last.content.push_str("\n\n");
+                last.content.push_str(&content);
+            }
+            _ => prompt.push(PromptMessage { role, content }),
+        }
+    }
+    prompt
+}
+
+#[cfg(feature = "local-llm")]
+mod engine {
+    use super::PromptMessage;
+    use crate::config::LocalLlmConfig;
+    use llama_cpp_2::context::params::LlamaContextParams;
+    use llama_cpp_2::llama_backend::LlamaBackend;
+    use llama_cpp_2::llama_batch::LlamaBatch;
+    use llama_cpp_2::model::params::LlamaModelParams;
+    use llama_cpp_2::model::{AddBos, LlamaChatMessage, LlamaModel, Special};
+    use llama_cpp_2::sampling::LlamaSampler;
+    use std::num::NonZeroU32;
+    use std::path::{Path, PathBuf};
+    use std::sync::{Mutex, OnceLock};
+
+    struct LoadedModel {
+        path: PathBuf,
+        gpu_layers: u32,
+        model: LlamaModel,
+    }
+
+    static BACKEND: OnceLock<LlamaBackend> = OnceLock::new();
+    /// The resident model. Held for the whole generation so requests run
+    /// one at a time and only one KV cache is allocated.
+    static LOADED: Mutex<Option<LoadedModel>> = Mutex::new(None);
+
+    /// Callers must hold `LOADED`, which keeps initialization single-threaded.
+    fn backend() -> anyhow::Result<&'static LlamaBackend> {
+        if let Some(backend) = BACKEND.get() {
+            return Ok(backend);
+        }
+        let mut backend = LlamaBackend::init()?;
+        backend.void_logs();
+        Ok(BACKEND.get_or_init(|| backend))
+    }
+
+    fn ensure_loaded<'a>(
+        slot: &'a mut Option<LoadedModel>,
+        path: &Path,
+        settings: &LocalLlmConfig,
+    ) -> anyhow::Result<&'a LlamaModel> {
+        let reusable = slot
+            .as_ref()
+            .is_some_and(|m| m.path == path && m.gpu_layers == settings.gpu_layers);
+        if !reusable {
+            // Free the previous model before mapping the next one.
+            *slot = None;
+            let params = LlamaModelParams::default().with_n_gpu_layers(settings.gpu_layers);
+            let model = LlamaModel::load_from_file(backend()?, path, &params).map_err(|e| {
+                anyhow::anyhow!("Failed to load GGUF model {}: {e}", path.display())
+            })?;
+            tracing::info!(model = %path.display(), "Loaded local llama.cpp model");
+            *slot = Some(LoadedModel {
+                path: path.to_path_buf(),
+                gpu_layers: settings.gpu_layers,
+                model,
+            });
+        }
+        Ok(&slot.as_ref().expect("model loaded above").model)
+    }
+
+    pub(super) fn load(path: &Path, settings: &LocalLlmConfig) -> anyhow::Result<()> {
+        let mut slot = LOADED.lock().unwrap_or_else(|e| e.into_inner());
+        ensure_loaded(&mut slot, path, settings).map(|_| ())
+    }
+
+    pub(super) fn generate(
+        path: &Path,
+        settings: &LocalLlmConfig,
+        threads: usize,
+        messages: &[PromptMessage],
+        temperature: f64,
+    ) -> anyhow::Result<String> {
+        let mut slot = LOADED.lock().unwrap_or_else(|e| e.into_inner());
+        let model = ensure_loaded(&mut slot, path, settings)?;
+
+        let chat = messages
+            .iter()
+            .map(|m| LlamaChatMessage::new(m.role.to_string(), m.content.clone()))
+            .collect::<Result<Vec<_>, _>>()?;
+        let template = model.chat_template(None)?;
+        let prompt = model.apply_chat_template(&template, &chat, true)?;
+        let tokens = model.str_to_token(&prompt, AddBos::Always)?;
+
+        let n_ctx = settings.context_size as usize;
+        if tokens.len() >= n_ctx {
+            anyhow::bail!(
+                "Prompt is {} tokens but local_llm.context_size is {n_ctx}; shorten the conversation or raise context_size",
+                tokens.len()
+            );
+        }
+        let max_new = (settings.max_tokens as usize).min(n_ctx - tokens.len());
+
+        let threads = i32::try_from(threads)?;
+        let params = LlamaContextParams::default()
+            .with_n_ctx(NonZeroU32::new(settings.context_size))
+            .with_n_batch(settings.batch_size)
+            .with_n_threads(threads)
+            .with_n_threads_batch(threads);
+        let mut ctx = model.new_context(backend()?, params)?;
+
+        let n_batch = settings.batch_size.max(1) as usize;
+        let mut batch = LlamaBatch::new(n_batch, 1);
+        let last = tokens.len() - 1;
+        for (chunk_index, chunk) in tokens.chunks(n_batch).enumerate() {
+            batch.clear();
+            for (offset, token) in chunk.iter().enumerate() {
+                let pos = chunk_index * n_batch + offset;
+                batch.add(*token, i32::try_from(pos)?, &[0], pos == last)?;
+            }
+            ctx.decode(&mut batch)?;
+        }
+
+        let mut sampler = if temperature <= 0.0 {
+            LlamaSampler::greedy()
+        } else {
+            #[allow(clippy::cast_possible_truncation)]
+            let temperature = temperature as f32;
+            LlamaSampler::chain_simple([
+                LlamaSampler::temp(temperature),
+                LlamaSampler::dist(rand::random()),
+            ])
+        };
+
+        let mut output = Vec::new();
+        let mut pos = tokens.len();
+        for _ in 0..max_new {
+            let token = sampler.sample(&ctx, batch.n_tokens() - 1);
+            sampler.accept(token);
+            if model.is_eog_token(token) {
+                break;
+            }
+            output.extend(model.token_to_bytes(token, Special::Plaintext)?);
+            batch.clear();
+            batch.add(token, i32::try_from(pos)?, &[0], true)?;
+            pos += 1;
+            ctx.decode(&mut batch)?;
+        }
+
+        Ok(String::from_utf8_lossy(&output).trim().to_string())
+    }
+
+    pub(super) fn embed(
+        path: &Path,
+        settings: &LocalLlmConfig,
+        threads: usize,
+        texts: &[String],
+    ) -> anyhow::Result<Vec<Vec<f32>>> {
+        let mut slot = LOADED.lock().unwrap_or_else(|e| e.into_inner());
+        let model = ensure_loaded(&mut slot, path, settings)?;
+
+        // Embedding models see the whole input in one batch, so the batch
+        // spans the full context.
+        let threads = i32::try_from(threads)?;
+        let params = LlamaContextParams::default()
+            .with_n_ctx(NonZeroU32::new(settings.context_size))
+            .with_n_batch(settings.context_size)
+            .with_n_ubatch(settings.context_size)
+            .with_n_threads(threads)
+            .with_n_threads_batch(threads)
+            .with_embeddings(true);
+        let mut ctx = model.new_context(backend()?, params)?;
+
+        let n_ctx = settings.context_size as usize;
+        let mut batch = LlamaBatch::new(n_ctx, 1);
+        let mut vectors = Vec::with_capacity(texts.len());
+        for text in texts {
+            let tokens = model.str_to_token(text, AddBos::Always)?;
+            if tokens.len() > n_ctx {
+                anyhow::bail!(
+                    "Text is {} tokens but local_llm.context_size is {n_ctx}; split it before embedding",
+                    tokens.len()
+                );
+            }
+            batch.clear();
+            batch.add_sequence(&tokens, 0, false)?;
+            ctx.clear_kv_cache();
+            ctx.decode(&mut batch)?;
+            vectors.push(ctx.embeddings_seq_ith(0)?.to_vec());
+        }
+        Ok(vectors)
+    }
+}
+
+impl LlamaCppProvider {
+    pub fn new(workspace_dir: Option<PathBuf>, settings: LocalLlmConfig) -> Self {
+        Self {
+            workspace_dir,
+            settings,
+        }
+    }
+
+    /// Resolve the GGUF file for `model` (or `local_llm.model_path`), which
+    /// must live inside the workspace.
+    fn resolve_model_path(&self, model: &str) -> anyhow::Result<PathBuf> {
+        let requested = self
+            .settings
+            .model_path
+            .as_deref()
+            .map(str::trim)
+            .filter(|p| !p.is_empty())
+            .unwrap_or_else(|| model.trim());
+        self.resolve_workspace_model(requested)
+    }
+
+    fn resolve_workspace_model(&self, requested: &str) -> anyhow::Result<PathBuf> {
+        let is_gguf = Path::new(requested)
+            .extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("gguf"));
+        if !is_gguf {
+            anyhow::bail!(
+                "local-llm expects a workspace-relative .gguf model path (set local_llm.model_path or the model name), got '{requested}'"
+            );
+        }
+        let Some(workspace_dir) = self.workspace_dir.as_deref() else {
+            anyhow::bail!("local-llm needs a workspace directory to load models from");
+        };
+
+        let workspace = workspace_dir.canonicalize().map_err(|e| {
+            anyhow::anyhow!(
+                "Failed to resolve workspace {}: {e}",
+                workspace_dir.display()
+            )
+        })?;
+        let resolved = workspace
+            .join(requested)
+            .canonicalize()
+            .map_err(|_| anyhow::anyhow!("Model file not found in workspace: {requested}"))?;
+        if !resolved.starts_with(&workspace) {
+            anyhow::bail!("Model path must stay inside the workspace: {requested}");
+        }
+        if !resolved.is_file() {
+            anyhow::bail!("Model path is not a file: {requested}");
+        }
+        Ok(resolved)
+    }
+
+    /// Workspace-relative paths of the GGUF files in the workspace root and
+    /// its `models/` directory, plus the directory of `local_llm.model_path`.
+    fn workspace_models(&self) -> anyhow::Result<Vec<String>> {
+        let Some(workspace_dir) = self.workspace_dir.as_deref() else {
+            anyhow::bail!("local-llm needs a workspace directory to list models");
+        };
+        let mut dirs: Vec<PathBuf> = MODEL_DIRS.iter().map(PathBuf::from).collect();
+        if let Some(parent) = self
+            .settings
+            .model_path
+            .as_deref()
+            .and_then(|path| Path::new(path.trim()).parent())
+        {
+            dirs.push(parent.to_path_buf());
+        }
+
+        let mut models = Vec::new();
+        for dir in dirs {
+            let Ok(entries) = std::fs::read_dir(workspace_dir.join(&dir)) else {
+                continue;
+            };
+            for entry in entries.flatten() {
+                let path = entry.path();
+                let is_gguf = path
+                    .extension()
+                    .is_some_and(|ext| ext.eq_ignore_ascii_case("gguf"));
+                if is_gguf && path.is_file() {
+                    let relative = dir.join(entry.file_name());
+                    models.push(relative.to_string_lossy().replace('\\', "/"));
+                }
+            }
+        }
+        models.sort();
+        models.dedup();
+        Ok(models)
+    }
+
+    fn threads(&self) -> usize {
+        if self.settings.threads > 0 {
+            return self.settings.threads;
+        }
+        std::thread::available_parallelism()
+            .map_or(1, std::num::NonZeroUsize::get)
+            .min(MAX_AUTO_THREADS)
+    }
+
+    #[cfg(feature = "local-llm")]
+    async fn generate(
+        &self,
+        messages: Vec<PromptMessage>,
+        model: &str,
+        temperature: f64,
+    ) -> anyhow::Result<String> {
+        let path = self.resolve_model_path(model)?;
+        let settings = self.settings.clone();
+        let threads = self.threads();
+        tokio::task::spawn_blocking(move || {
+            engine::generate(&path, &settings, threads, &messages, temperature)
+        })
+        .await?
+    }
+
+    /// Embeddings come from `model` itself rather than `local_llm.model_path`,
+    /// since the chat model is rarely an embedding model.
+    #[cfg(feature = "local-llm")]
+    async fn embed_texts(&self, texts: Vec<String>, model: &str) -> anyhow::Result<Vec<Vec<f32>>> {
+        let path = self.resolve_workspace_model(model.trim())?;
+        let settings = self.settings.clone();
+        let threads = self.threads();
+        tokio::task::spawn_blocking(move || engine::embed(&path, &settings, threads, &texts))
+            .await?
+    }
+
+    #[cfg(not(feature = "local-llm"))]
+    #[allow(clippy::unused_async)]
+    async fn embed_texts(&self, _texts: Vec<String>, model: &str) -> anyhow::Result<Vec<Vec<f32>>> {
+        self.resolve_workspace_model(model.trim())?;
+        anyhow::bail!(
+            "On-device embeddings are not available in this build. Rebuild with `cargo build --features local-llm`."
+        )
+    }
+
+    #[cfg(not(feature = "local-llm"))]
+    #[allow(clippy::unused_async)]
+    async fn generate(
+        &self,
+        _messages: Vec<PromptMessage>,
+        model: &str,
+        _temperature: f64,
+    ) -> anyhow::Result<String> {
+        self.resolve_model_path(model)?;
+        anyhow::bail!(
+            "On-device inference is not available in this build. Rebuild with `cargo build --features local-llm`."
+        )
+    }
+}
+
+#[async_trait]
+impl Provider for LlamaCppProvider {
+    async fn chat_with_system(
+        &self,
+        system_prompt: Option<&str>,
+        message: &str,
+        model: &str,
+        temperature: f64,
+    ) -> anyhow::Result<String> {
+        let mut messages = Vec::new();
+        if let Some(system) = system_prompt {
+            messages.push(ChatMessage::system(system));
+        }
+        messages.push(ChatMessage::user(message));
+        self.chat_with_history(&messages, model, temperature).await
+    }
+
+    async fn chat_with_history(
+        &self,
+        messages: &[ChatMessage],
+        model: &str,
+        temperature: f64,
+    ) -> anyhow::Result<String> {
+        self.generate(prompt_messages(messages), model, temperature)
+            .await
+    }
+
+    fn supports_embeddings(&self) -> bool {
+        true
+    }
+
+    /// GGUF files available in the workspace; no model is loaded.
+    async fn list_models(&self) -> anyhow::Result<Vec<String>> {
+        self.workspace_models()
+    }
+
+    /// Embed with a GGUF embedding model (e.g. nomic-embed-text). Loading it
+    /// replaces the resident chat model until the next chat request.
+    async fn embed(&self, texts: &[&str], model: &str) -> anyhow::Result<Vec<Vec<f32>>> {
+        if texts.is_empty() {
+            return Ok(Vec::new());
+        }
+        let texts = texts.iter().map(|text| (*text).to_string()).collect();
+        self.embed_texts(texts, model).await
+    }
+
+    /// Load the configured model ahead of the first request; a multi-GB
+    /// GGUF can take several seconds to map on a phone.
+    #[cfg(feature = "local-llm")]
+    async fn warmup(&self) -> anyhow::Result<()> {
+        if self.settings.model_path.is_some() {
+            let path = self.resolve_model_path("")?;
+            let settings = self.settings.clone();
+            tokio::task::spawn_blocking(move || engine::load(&path, &settings)).await??;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn provider(workspace: &Path, model_path: Option<&str>) -> LlamaCppProvider {
+        LlamaCppProvider::new(
+            Some(workspace.to_path_buf()),
+            LocalLlmConfig {
+                model_path: model_path.map(str::to_string),
+                ..LocalLlmConfig::default()
+            },
+        )
+    }
+
+    #[test]
+    fn resolves_gguf_models_inside_workspace_only() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let workspace = tmp.path().join("workspace");
+        std::fs::create_dir_all(workspace.join("models")).unwrap();
+        std::fs::write(workspace.join("models/qwen.gguf"), b"GGUF").unwrap();
+        std::fs::write(tmp.path().join("outside.gguf"), b"GGUF").unwrap();
+
+        let by_name = provider(&workspace, None);
+        assert!(by_name
+            .resolve_model_path("models/qwen.gguf")
+            .unwrap()
+            .ends_with("models/qwen.gguf"));
+        for (model, expected) in [
+            ("anthropic/claude-sonnet-4", ".gguf model path"),
+            ("models/missing.gguf", "not found"),
+            ("../outside.gguf", "inside the workspace"),
+        ] {
+            let err = by_name.resolve_model_path(model).unwrap_err().to_string();
+            assert!(err.contains(expected), "{model}: {err}");
+        }
+
+        let configured = provider(&workspace, Some("models/qwen.gguf"));
+        assert!(configured.resolve_model_path("ignored").is_ok());
+    }
+
+    #[test]
+    fn lists_gguf_files_in_workspace_model_dirs() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let workspace = tmp.path();
+        std::fs::create_dir_all(workspace.join("models")).unwrap();
+        std::fs::create_dir_all(workspace.join("llm/chat")).unwrap();
+        std::fs::write(workspace.join("models/qwen.gguf"), b"GGUF").unwrap();
+        std::fs::write(workspace.join("models/notes.txt"), b"").unwrap();
+        std::fs::write(workspace.join("root.GGUF"), b"GGUF").unwrap();
+        std::fs::write(workspace.join("llm/chat/phi.gguf"), b"GGUF").unwrap();
+
+        assert_eq!(
+            provider(workspace, None).workspace_models().unwrap(),
+            vec!["models/qwen.gguf", "root.GGUF"]
+        );
+        assert_eq!(
+            provider(workspace, Some("llm/chat/phi.gguf"))
+                .workspace_models()
+                .unwrap(),
+            vec!["llm/chat/phi.gguf", "models/qwen.gguf", "root.GGUF"]
+        );
+    }
+
+    #[test]
+    fn auto_threads_stay_on_performance_cores() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let auto = provider(tmp.path(), None);
+        assert!((1..=MAX_AUTO_THREADS).contains(&auto.threads()));
+
+        let mut fixed = provider(tmp.path(), None);
+        fixed.settings.threads = 6;
+        assert_eq!(fixed.threads(), 6);
+    }
+
+    #[test]
+    fn prompt_messages_fold_tool_results_and_merge_roles() {
+        let messages = vec![
+            ChatMessage::system("Be brief."),
+            ChatMessage::user("Weather?"),
+            ChatMessage::assistant("Checking."),
+            ChatMessage::tool("{\"temp\": 21}"),
+            ChatMessage::user("And tomorrow?"),
+        ];
+        let prompt = prompt_messages(&messages);
+        let roles: Vec<&str> = prompt.iter().map(|m| m.role).collect();
+        assert_eq!(roles, vec!["system", "user", "assistant", "user"]);
+        assert_eq!(
+            prompt[3].content,
+            "[Tool results]\n{\"temp\": 21}\n\nAnd tomorrow?"
+        );
+    }
+
+    #[cfg(not(feature = "local-llm"))]
+    #[tokio::test]
+    async fn reports_missing_feature() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        std::fs::write(tmp.path().join("model.gguf"), b"GGUF").unwrap();
+        let err = provider(tmp.path(), None)
+            .chat_with_system(None, "hi", "model.gguf", 0.7)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("--features local-llm"));
+    }
+
+    #[cfg(not(feature = "local-llm"))]
+    #[tokio::test]
+    async fn embeddings_use_requested_model_not_chat_model() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        std::fs::write(tmp.path().join("chat.gguf"), b"GGUF").unwrap();
+        let provider = provider(tmp.path(), Some("chat.gguf"));
+        assert!(provider.supports_embeddings());
+
+        let err = provider.embed(&["hi"], "embed.gguf").await.unwrap_err();
+        assert!(err.to_string().contains("not found"), "{err}");
+
+        std::fs::write(tmp.path().join("embed.gguf"), b"GGUF").unwrap();
+        let err = provider.embed(&["hi"], "embed.gguf").await.unwrap_err();
+        assert!(err.to_string().contains("--features local-llm"));
+    }
+}
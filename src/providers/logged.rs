@@ -0,0 +1,347 @@
+//! Wire logging in front of a provider.
+//!
+//! Wraps each provider of a chain while `observability.wire_log` is on and
+//! hands every request, reply and error to
+//! [`wire_log`](crate::observability::wire_log), which redacts and stores
+//! them. Tool schemas are logged by name only; streamed replies are logged
+//! once the stream ends.
+
+use super::structured::JsonSchemaFormat;
+use super::traits::{
+    ChatMessage, ChatRequest, ChatResponse, ProviderCapabilities, StreamChunk, StreamOptions,
+    StreamResult, ToolsPayload,
+};
+use super::Provider;
+use crate::observability::wire_log;
+use crate::tools::ToolSpec;
+use async_trait::async_trait;
+use futures_util::{stream, StreamExt};
+use serde_json::{json, Value};
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Instant;
+
+pub struct WireLoggedProvider {
+    name: String,
+    inner: Box<dyn Provider>,
+}
+
+impl WireLoggedProvider {
+    pub fn new(name: &str, inner: Box<dyn Provider>) -> Self {
+        Self {
+            name: name.to_string(),
+            inner,
+        }
+    }
+
+    async fn logged<T>(
+        &self,
+        model: &str,
+        request: Value,
+        call: impl Future<Output = anyhow::Result<T>>,
+        reply: impl FnOnce(&T) -> Value,
+    ) -> anyhow::Result<T> {
+        let call_id = uuid::Uuid::new_v4().to_string();
+        wire_log::record(&call_id, "request", &self.name, model, request);
+        let started = Instant::now();
+        let result = call.await;
+        let duration_ms = u64::try_from(started.elapsed().as_millis()).unwrap_or(u64::MAX);
+        match &result {
+            Ok(value) => {
+                let mut payload = reply(value);
+                payload["duration_ms"] = json!(duration_ms);
+                wire_log::record(&call_id, "response", &self.name, model, payload);
+            }
+            Err(e) => wire_log::record(
+                &call_id,
+                "error",
+                &self.name,
+                model,
+                json!({"error": format!("{e:#}"), "duration_ms": duration_ms}),
+            ),
+        }
+        result
+    }
+}
+
+fn text_reply<T: AsRef<str>>(text: &T) -> Value {
+    json!({ "text": text.as_ref() })
+}
+
+fn chat_reply(response: &ChatResponse) -> Value {
+    json!({
+        "text": response.text,
+        "tool_calls": response.tool_calls,
+        "reasoning_content": response.reasoning_content,
+        "usage": response.usage.as_ref().map(|usage| json!({
+            "input_tokens": usage.input_tokens,
+            "output_tokens": usage.output_tokens,
+        })),
+    })
+}
+
+/// Names of OpenAI-style (`function.name`) or flat (`name`) tool schemas.
+fn tool_names(tools: &[Value]) -> Vec<&str> {
+    tools
+        .iter()
+        .filter_map(|tool| {
+            tool.pointer("/function/name")
+                .or_else(|| tool.get("name"))
+                .and_then(Value::as_str)
+        })
+        .collect()
+}
+
+#[async_trait]
+impl Provider for WireLoggedProvider {
+    fn capabilities(&self) -> ProviderCapabilities {
+        self.inner.capabilities()
+    }
+
+    fn convert_tools(&self, tools: &[ToolSpec]) -> ToolsPayload {
+        self.inner.convert_tools(tools)
+    }
+
+    async fn warmup(&self) -> anyhow::Result<()> {
+        self.inner.warmup().await
+    }
+
+    async fn list_models(&self) -> anyhow::Result<Vec<String>> {
+        self.inner.list_models().await
+    }
+
+    async fn chat_with_system(
+        &self,
+        system_prompt: Option<&str>,
+        message: &str,
+        model: &str,
+        temperature: f64,
+    ) -> anyhow::Result<String> {
+        self.logged(
+            model,
+            json!({
+                "method": "chat_with_system",
+                "temperature": temperature,
+                "system_prompt": system_prompt,
+                "message": message,
+            }),
+            self.inner
+                .chat_with_system(system_prompt, message, model, temperature),
+            text_reply,
+        )
+        .await
+    }
+
+    async fn chat_with_history(
+        &self,
+        messages: &[ChatMessage],
+        model: &str,
+        temperature: f64,
+    ) -> anyhow::Result<String> {
+        self.logged(
+            model,
+            json!({
+                "method": "chat_with_history",
+                "temperature": temperature,
+                "messages": messages,
+            }),
+            self.inner.chat_with_history(messages, model, temperature),
+            text_reply,
+        )
+        .await
+    }
+
+    async fn chat(
+        &self,
+        request: ChatRequest<'_>,
+        model: &str,
+        temperature: f64,
+    ) -> anyhow::Result<ChatResponse> {
+        let tools: Option<Vec<&str>> = request
+            .tools
+            .map(|tools| tools.iter().map(|tool| tool.name.as_str()).collect());
+        self.logged(
+            model,
+            json!({
+                "method": "chat",
+                "temperature": temperature,
+                "messages": request.messages,
+                "tools": tools,
+            }),
+            self.inner.chat(request, model, temperature),
+            chat_reply,
+        )
+        .await
+    }
+
+    fn supports_native_tools(&self) -> bool {
+        self.inner.supports_native_tools()
+    }
+
+    fn supports_native_tools_for_model(&self, model: &str) -> bool {
+        self.inner.supports_native_tools_for_model(model)
+    }
+
+    fn supports_vision(&self) -> bool {
+        self.inner.supports_vision()
+    }
+
+    async fn chat_with_tools(
+        &self,
+        messages: &[ChatMessage],
+        tools: &[Value],
+        model: &str,
+        temperature: f64,
+    ) -> anyhow::Result<ChatResponse> {
+        self.logged(
+            model,
+            json!({
+                "method": "chat_with_tools",
+                "temperature": temperature,
+                "messages": messages,
+                "tools": tool_names(tools),
+            }),
+            self.inner
+                .chat_with_tools(messages, tools, model, temperature),
+            chat_reply,
+        )
+        .await
+    }
+
+    fn supports_streaming(&self) -> bool {
+        self.inner.supports_streaming()
+    }
+
+    fn supports_embeddings(&self) -> bool {
+        self.inner.supports_embeddings()
+    }
+
+    async fn embed(&self, texts: &[&str], model: &str) -> anyhow::Result<Vec<Vec<f32>>> {
+        self.logged(
+            model,
+            json!({ "method": "embed", "texts": texts }),
+            self.inner.embed(texts, model),
+            |vectors: &Vec<Vec<f32>>| {
+                json!({
+                    "vectors": vectors.len(),
+                    "dimensions": vectors.first().map(Vec::len),
+                })
+            },
+        )
+        .await
+    }
+
+    fn supports_structured_output(&self) -> bool {
+        self.inner.supports_structured_output()
+    }
+
+    async fn chat_with_schema(
+        &self,
+        messages: &[ChatMessage],
+        format: &JsonSchemaFormat,
+        model: &str,
+        temperature: f64,
+    ) -> anyhow::Result<String> {
+        self.logged(
+            model,
+            json!({
+                "method": "chat_with_schema",
+                "temperature": temperature,
+                "messages": messages,
+                "schema": format.name,
+            }),
+            self.inner
+                .chat_with_schema(messages, format, model, temperature),
+            text_reply,
+        )
+        .await
+    }
+
+    fn stream_chat_with_system(
+        &self,
+        system_prompt: Option<&str>,
+        message: &str,
+        model: &str,
+        temperature: f64,
+        options: StreamOptions,
+    ) -> stream::BoxStream<'static, StreamResult<StreamChunk>> {
+        let mut messages = Vec::new();
+        if let Some(system_prompt) = system_prompt {
+            messages.push(ChatMessage::system(system_prompt));
+        }
+        messages.push(ChatMessage::user(message));
+        self.stream_chat_with_history(&messages, model, temperature, options)
+    }
+
+    fn stream_chat_with_history(
+        &self,
+        messages: &[ChatMessage],
+        model: &str,
+        temperature: f64,
+        options: StreamOptions,
+    ) -> stream::BoxStream<'static, StreamResult<StreamChunk>> {
+        let call_id = Arc::<str>::from(uuid::Uuid::new_v4().to_string());
+        let provider = Arc::<str>::from(self.name.as_str());
+        let model = Arc::<str>::from(model);
+        wire_log::record(
+            &call_id,
+            "request",
+            &provider,
+            &model,
+            json!({
+                "method": "stream_chat_with_history",
+                "temperature": temperature,
+                "messages": messages,
+            }),
+        );
+
+        let started = Instant::now();
+        let mut reply = String::new();
+        self.inner
+            .stream_chat_with_history(messages, &model, temperature, options)
+            .inspect(move |chunk| {
+                let duration_ms = u64::try_from(started.elapsed().as_millis()).unwrap_or(u64::MAX);
+                match chunk {
+                    Ok(chunk) => {
+                        reply.push_str(&chunk.delta);
+                        if chunk.is_final {
+                            wire_log::record(
+                                &call_id,
+                                "response",
+                                &provider,
+                                &model,
+                                json!({"text": reply, "duration_ms": duration_ms}),
+                            );
+                        }
+                    }
+                    Err(e) => wire_log::record(
+                        &call_id,
+                        "error",
+                        &provider,
+                        &model,
+                        json!({
+                            "error": e.to_string(),
+                            "partial_text": reply,
+                            "duration_ms": duration_ms,
+                        }),
+                    ),
+                }
+            })
+            .boxed()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tool_names_accept_openai_and_flat_schemas() {
+        let tools = vec![
+            json!({"type": "function", "function": {"name": "shell", "parameters": {}}}),
+            json!({"name": "memory_recall", "input_schema": {}}),
+            json!({"type": "function"}),
+        ];
+        assert_eq!(tool_names(&tools), vec!["shell", "memory_recall"]);
+    }
+}
@@ -0,0 +1,649 @@
+//! Mistral AI chat completions (`https://api.mistral.ai/v1`, hosted in the EU).
+//!
+//! The wire format is close to OpenAI's, with function-calling differences
+//! this provider handles natively: tool call IDs must be exactly nine ASCII
+//! letters or digits, tool results carry the function `name`, and call
+//! arguments may come back as a JSON object instead of a string.
+
+use crate::providers::sse;
+use crate::providers::structured::{JsonSchemaFormat, ResponseFormat};
+use crate::providers::traits::{
+    parse_openai_embeddings, parse_openai_model_ids, prompt_guided_messages, ChatMessage,
+    ChatRequest as ProviderChatRequest, ChatResponse as ProviderChatResponse, Provider,
+    ProviderCapabilities, StreamChunk, StreamOptions, StreamResult, TokenUsage,
+    ToolCall as ProviderToolCall, ToolsPayload,
+};
+use crate::tools::ToolSpec;
+use async_trait::async_trait;
+use futures_util::stream;
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+pub const BASE_URL: &str = "https://api.mistral.ai/v1";
+
+const TOOL_CALL_ID_LEN: usize = 9;
+const TOOL_CALL_ID_ALPHABET: &[u8] =
+    b"abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789";
+
+pub struct MistralProvider {
+    base_url: String,
+    credential: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+struct ChatRequest {
+    model: String,
+    messages: Vec<Message>,
+    temperature: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tools: Option<Vec<ToolSpecOut>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tool_choice: Option<&'static str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    response_format: Option<ResponseFormat>,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    stream: bool,
+}
+
+#[derive(Debug, Serialize)]
+struct Message {
+    role: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    content: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tool_calls: Option<Vec<ToolCallOut>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tool_call_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    name: Option<String>,
+}
+
+impl Message {
+    fn text(role: &str, content: impl Into<String>) -> Self {
+        Self {
+            role: role.to_string(),
+            content: Some(content.into()),
+            tool_calls: None,
+            tool_call_id: None,
+            name: None,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct ToolSpecOut {
+    #[serde(rename = "type")]
+    kind: String,
+    function: FunctionSpec,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct FunctionSpec {
+    name: String,
+    description: String,
+    parameters: serde_json::Value,
+}
+
+#[derive(Debug, Serialize)]
+struct ToolCallOut {
+    id: String,
+    #[serde(rename = "type")]
+    kind: &'static str,
+    function: FunctionCallOut,
+}
+
+#[derive(Debug, Serialize)]
+struct FunctionCallOut {
+    name: String,
+    arguments: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatResponse {
+    choices: Vec<Choice>,
+    #[serde(default)]
+    usage: Option<UsageInfo>,
+}
+
+#[derive(Debug, Deserialize)]
+struct UsageInfo {
+    #[serde(default)]
+    prompt_tokens: Option<u64>,
+    #[serde(default)]
+    completion_tokens: Option<u64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Choice {
+    message: ResponseMessage,
+}
+
+#[derive(Debug, Deserialize)]
+struct ResponseMessage {
+    #[serde(default)]
+    content: Option<String>,
+    #[serde(default)]
+    tool_calls: Option<Vec<ResponseToolCall>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ResponseToolCall {
+    #[serde(default)]
+    id: Option<String>,
+    function: ResponseFunctionCall,
+}
+
+#[derive(Debug, Deserialize)]
+struct ResponseFunctionCall {
+    name: String,
+    /// A JSON string, or (for some models) the arguments object itself.
+    #[serde(default)]
+    arguments: serde_json::Value,
+}
+
+/// Map any tool call ID onto Mistral's nine-character alphanumeric format.
+/// The mapping is deterministic, so an assistant call and its tool result
+/// still match after conversion.
+fn mistral_tool_call_id(id: &str) -> String {
+    if id.len() == TOOL_CALL_ID_LEN && id.bytes().all(|b| b.is_ascii_alphanumeric()) {
+        return id.to_string();
+    }
+
+    // FNV-1a; 62^9 fits in a u64, so every character draws on fresh bits.
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for byte in id.bytes() {
+        hash ^= u64::from(byte);
+        hash = hash.wrapping_mul(0x0100_0000_01b3);
+    }
+    let base = TOOL_CALL_ID_ALPHABET.len() as u64;
+    (0..TOOL_CALL_ID_LEN)
+        .map(|_| {
+            let index = usize::try_from(hash % base).unwrap_or_default();
+            hash /= base;
+            char::from(TOOL_CALL_ID_ALPHABET[index])
+        })
+        .collect()
+}
+
+fn normalize_arguments(arguments: serde_json::Value) -> String {
+    match arguments {
+        serde_json::Value::String(text) if !text.trim().is_empty() => text,
+        serde_json::Value::String(_) | serde_json::Value::Null => "{}".to_string(),
+        other => other.to_string(),
+    }
+}
+
+impl MistralProvider {
+    pub fn new(credential: Option<&str>) -> Self {
+        Self::with_base_url(None, credential)
+    }
+
+    /// Create a provider with an optional custom base URL (self-hosted or
+    /// regional deployments). Defaults to [`BASE_URL`].
+    pub fn with_base_url(base_url: Option<&str>, credential: Option<&str>) -> Self {
+        Self {
+            base_url: base_url
+                .map(|u| u.trim_end_matches('/').to_string())
+                .unwrap_or_else(|| BASE_URL.to_string()),
+            credential: credential.map(ToString::to_string),
+        }
+    }
+
+    fn credential(&self) -> anyhow::Result<&str> {
+        self.credential.as_deref().ok_or_else(|| {
+            anyhow::anyhow!("Mistral API key not set. Set MISTRAL_API_KEY or edit config.toml.")
+        })
+    }
+
+    fn convert_tools(tools: Option<&[ToolSpec]>) -> Option<Vec<ToolSpecOut>> {
+        let items = tools?;
+        if items.is_empty() {
+            return None;
+        }
+        Some(
+            items
+                .iter()
+                .map(|tool| ToolSpecOut {
+                    kind: "function".to_string(),
+                    function: FunctionSpec {
+                        name: tool.name.clone(),
+                        description: tool.description.clone(),
+                        parameters: tool.parameters.clone(),
+                    },
+                })
+                .collect(),
+        )
+    }
+
+    fn parse_assistant_tool_calls(
+        content: &str,
+        names: &mut HashMap<String, String>,
+    ) -> Option<Message> {
+        let value = serde_json::from_str::<serde_json::Value>(content).ok()?;
+        let calls = value
+            .get("tool_calls")
+            .and_then(|v| serde_json::from_value::<Vec<ProviderToolCall>>(v.clone()).ok())?;
+
+        let tool_calls = calls
+            .into_iter()
+            .map(|call| {
+                let id = mistral_tool_call_id(&call.id);
+                names.insert(id.clone(), call.name.clone());
+                ToolCallOut {
+                    id,
+                    kind: "function",
+                    function: FunctionCallOut {
+                        name: call.name,
+                        arguments: normalize_arguments(serde_json::Value::String(call.arguments)),
+                    },
+                }
+            })
+            .collect();
+        let text = value
+            .get("content")
+            .and_then(serde_json::Value::as_str)
+            .filter(|text| !text.trim().is_empty())
+            .map(ToString::to_string);
+
+        Some(Message {
+            role: "assistant".to_string(),
+            content: text,
+            tool_calls: Some(tool_calls),
+            tool_call_id: None,
+            name: None,
+        })
+    }
+
+    fn parse_tool_result(content: &str, names: &HashMap<String, String>) -> Option<Message> {
+        let value = serde_json::from_str::<serde_json::Value>(content).ok()?;
+        let id = mistral_tool_call_id(value.get("tool_call_id")?.as_str()?);
+        let result = match value.get("content") {
+            Some(serde_json::Value::String(text)) => text.clone(),
+            Some(serde_json::Value::Null) | None => String::new(),
+            Some(other) => other.to_string(),
+        };
+        Some(Message {
+            role: "tool".to_string(),
+            content: Some(result),
+            tool_calls: None,
+            name: names.get(&id).cloned(),
+            tool_call_id: Some(id),
+        })
+    }
+
+    fn convert_messages(messages: &[ChatMessage]) -> Vec<Message> {
+        let mut names = HashMap::new();
+        messages
+            .iter()
+            .map(|m| match m.role.as_str() {
+                "assistant" => Self::parse_assistant_tool_calls(&m.content, &mut names)
+                    .unwrap_or_else(|| Message::text("assistant", m.content.clone())),
+                "tool" => Self::parse_tool_result(&m.content, &names)
+                    .unwrap_or_else(|| Message::text("tool", m.content.clone())),
+                role => Message::text(role, m.content.clone()),
+            })
+            .collect()
+    }
+
+    fn parse_response(response: ChatResponse) -> anyhow::Result<ProviderChatResponse> {
+        let usage = response.usage.map(|u| TokenUsage {
+            input_tokens: u.prompt_tokens,
+            output_tokens: u.completion_tokens,
+        });
+        let message = response
+            .choices
+            .into_iter()
+            .next()
+            .map(|c| c.message)
+            .ok_or_else(|| anyhow::anyhow!("No response from Mistral"))?;
+        let tool_calls = message
+            .tool_calls
+            .unwrap_or_default()
+            .into_iter()
+            .map(|call| ProviderToolCall {
+                id: call
+                    .id
+                    .unwrap_or_else(|| mistral_tool_call_id(&uuid::Uuid::new_v4().to_string())),
+                name: call.function.name,
+                arguments: normalize_arguments(call.function.arguments),
+            })
+            .collect();
+
+        Ok(ProviderChatResponse {
+            text: message.content.filter(|text| !text.is_empty()),
+            tool_calls,
+            usage,
+            reasoning_content: None,
+        })
+    }
+
+    async fn send(&self, request: &ChatRequest) -> anyhow::Result<ProviderChatResponse> {
+        let credential = self.credential()?;
+        let response = self
+            .http_client()
+            .post(format!("{}/chat/completions", self.base_url))
+            .bearer_auth(credential)
+            .json(request)
+            .send()
+            .await?;
+        if !response.status().is_success() {
+            return Err(super::api_error("Mistral", response).await);
+        }
+        Self::parse_response(response.json().await?)
+    }
+
+    fn http_client(&self) -> Client {
+        crate::config::build_runtime_proxy_client_with_timeouts("provider.mistral", 120, 10)
+    }
+}
+
+#[async_trait]
+impl Provider for MistralProvider {
+    fn capabilities(&self) -> ProviderCapabilities {
+        ProviderCapabilities {
+            native_tool_calling: true,
+            vision: false,
+        }
+    }
+
+    fn convert_tools(&self, tools: &[ToolSpec]) -> ToolsPayload {
+        ToolsPayload::OpenAI {
+            tools: Self::convert_tools(Some(tools))
+                .unwrap_or_default()
+                .into_iter()
+                .filter_map(|spec| serde_json::to_value(spec).ok())
+                .collect(),
+        }
+    }
+
+    async fn chat_with_system(
+        &self,
+        system_prompt: Option<&str>,
+        message: &str,
+        model: &str,
+        temperature: f64,
+    ) -> anyhow::Result<String> {
+        let mut messages = Vec::new();
+        if let Some(sys) = system_prompt {
+            messages.push(ChatMessage::system(sys));
+        }
+        messages.push(ChatMessage::user(message));
+        self.chat_with_history(&messages, model, temperature).await
+    }
+
+    async fn chat_with_history(
+        &self,
+        messages: &[ChatMessage],
+        model: &str,
+        temperature: f64,
+    ) -> anyhow::Result<String> {
+        let request = ChatRequest {
+            model: model.to_string(),
+            messages: Self::convert_messages(messages),
+            temperature,
+            tools: None,
+            tool_choice: None,
+            response_format: None,
+            stream: false,
+        };
+        let response = self.send(&request).await?;
+        Ok(response.text.unwrap_or_default())
+    }
+
+    async fn chat(
+        &self,
+        request: ProviderChatRequest<'_>,
+        model: &str,
+        temperature: f64,
+    ) -> anyhow::Result<ProviderChatResponse> {
+        if let Some(messages) = prompt_guided_messages(self, &request, model) {
+            let request = ProviderChatRequest {
+                messages: &messages,
+                tools: None,
+            };
+            return self.chat(request, model, temperature).await;
+        }
+
+        let tools = Self::convert_tools(request.tools);
+        let request = ChatRequest {
+            model: model.to_string(),
+            messages: Self::convert_messages(request.messages),
+            temperature,
+            tool_choice: tools.as_ref().map(|_| "auto"),
+            tools,
+            response_format: None,
+            stream: false,
+        };
+        self.send(&request).await
+    }
+
+    async fn chat_with_tools(
+        &self,
+        messages: &[ChatMessage],
+        tools: &[serde_json::Value],
+        model: &str,
+        temperature: f64,
+    ) -> anyhow::Result<ProviderChatResponse> {
+        let tools = tools
+            .iter()
+            .cloned()
+            .map(|value| {
+                serde_json::from_value::<ToolSpecOut>(value)
+                    .map_err(|e| anyhow::anyhow!("Invalid Mistral tool specification: {e}"))
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        let tools = (!tools.is_empty()).then_some(tools);
+
+        let request = ChatRequest {
+            model: model.to_string(),
+            messages: Self::convert_messages(messages),
+            temperature,
+            tool_choice: tools.as_ref().map(|_| "auto"),
+            tools,
+            response_format: None,
+            stream: false,
+        };
+        self.send(&request).await
+    }
+
+    fn supports_structured_output(&self) -> bool {
+        true
+    }
+
+    async fn chat_with_schema(
+        &self,
+        messages: &[ChatMessage],
+        format: &JsonSchemaFormat,
+        model: &str,
+        temperature: f64,
+    ) -> anyhow::Result<String> {
+        let request = ChatRequest {
+            model: model.to_string(),
+            messages: Self::convert_messages(messages),
+            temperature,
+            tools: None,
+            tool_choice: None,
+            response_format: Some(ResponseFormat::json_schema(format)),
+            stream: false,
+        };
+        let response = self.send(&request).await?;
+        Ok(response.text.unwrap_or_default())
+    }
+
+    fn supports_streaming(&self) -> bool {
+        true
+    }
+
+    fn stream_chat_with_system(
+        &self,
+        system_prompt: Option<&str>,
+        message: &str,
+        model: &str,
+        temperature: f64,
+        options: StreamOptions,
+    ) -> stream::BoxStream<'static, StreamResult<StreamChunk>> {
+        let mut messages = Vec::new();
+        if let Some(sys) = system_prompt {
+            messages.push(ChatMessage::system(sys));
+        }
+        messages.push(ChatMessage::user(message));
+        self.stream_chat_with_history(&messages, model, temperature, options)
+    }
+
+    fn stream_chat_with_history(
+        &self,
+        messages: &[ChatMessage],
+        model: &str,
+        temperature: f64,
+        options: StreamOptions,
+    ) -> stream::BoxStream<'static, StreamResult<StreamChunk>> {
+        let Some(credential) = self.credential.as_ref() else {
+            return sse::error_stream("Mistral API key not set");
+        };
+
+        let request = ChatRequest {
+            model: model.to_string(),
+            messages: Self::convert_messages(messages),
+            temperature,
+            tools: None,
+            tool_choice: None,
+            response_format: None,
+            stream: true,
+        };
+        let req = self
+            .http_client()
+            .post(format!("{}/chat/completions", self.base_url))
+            .bearer_auth(credential)
+            .json(&request);
+        sse::stream_request(req, options.count_tokens, sse::chat_completions_delta)
+    }
+
+    fn supports_embeddings(&self) -> bool {
+        true
+    }
+
+    async fn embed(&self, texts: &[&str], model: &str) -> anyhow::Result<Vec<Vec<f32>>> {
+        if texts.is_empty() {
+            return Ok(Vec::new());
+        }
+        let credential = self.credential()?;
+
+        let response = self
+            .http_client()
+            .post(format!("{}/embeddings", self.base_url))
+            .bearer_auth(credential)
+            .json(&serde_json::json!({ "model": model, "input": texts }))
+            .send()
+            .await?;
+        if !response.status().is_success() {
+            return Err(super::api_error("Mistral", response).await);
+        }
+
+        let body: serde_json::Value = response.json().await?;
+        parse_openai_embeddings(&body)
+    }
+
+    async fn list_models(&self) -> anyhow::Result<Vec<String>> {
+        let response = self
+            .http_client()
+            .get(format!("{}/models", self.base_url))
+            .bearer_auth(self.credential()?)
+            .send()
+            .await?;
+        if !response.status().is_success() {
+            return Err(super::api_error("Mistral", response).await);
+        }
+
+        let body: serde_json::Value = response.json().await?;
+        Ok(parse_openai_model_ids(&body))
+    }
+
+    async fn warmup(&self) -> anyhow::Result<()> {
+        if let Some(credential) = self.credential.as_ref() {
+            self.http_client()
+                .get(format!("{}/models", self.base_url))
+                .bearer_auth(credential)
+                .send()
+                .await?
+                .error_for_status()?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tool_call_ids_are_nine_alphanumerics_and_stable() {
+        assert_eq!(mistral_tool_call_id("aB3dE6gH9"), "aB3dE6gH9");
+
+        let mapped = mistral_tool_call_id("call_8f2a9c1e-5b7d");
+        assert_eq!(mapped.len(), TOOL_CALL_ID_LEN);
+        assert!(mapped.bytes().all(|b| b.is_ascii_alphanumeric()));
+        assert_eq!(mapped, mistral_tool_call_id("call_8f2a9c1e-5b7d"));
+        assert_ne!(mapped, mistral_tool_call_id("call_8f2a9c1e-5b7e"));
+    }
+
+    #[test]
+    fn convert_messages_maps_tool_call_history() {
+        let assistant = serde_json::json!({
+            "content": "",
+            "tool_calls": [{ "id": "call_abc-123", "name": "shell", "arguments": "" }]
+        });
+        let tool = serde_json::json!({ "tool_call_id": "call_abc-123", "content": "ok" });
+        let messages = vec![
+            ChatMessage::system("sys"),
+            ChatMessage::user("list files"),
+            ChatMessage::assistant(assistant.to_string()),
+            ChatMessage::tool(tool.to_string()),
+        ];
+
+        let converted = serde_json::to_value(MistralProvider::convert_messages(&messages)).unwrap();
+        let call = &converted[2]["tool_calls"][0];
+        let id = call["id"].as_str().unwrap();
+        assert_eq!(id, mistral_tool_call_id("call_abc-123"));
+        assert_eq!(call["function"]["arguments"], "{}");
+        assert!(converted[2].get("content").is_none());
+
+        assert_eq!(converted[3]["role"], "tool");
+        assert_eq!(converted[3]["tool_call_id"], id);
+        assert_eq!(converted[3]["name"], "shell");
+        assert_eq!(converted[3]["content"], "ok");
+    }
+
+    #[test]
+    fn parse_response_accepts_object_arguments() {
+        let response: ChatResponse = serde_json::from_value(serde_json::json!({
+            "choices": [{
+                "message": {
+                    "content": "",
+                    "tool_calls": [{
+                        "id": "D681PevKs",
+                        "function": { "name": "weather", "arguments": { "city": "Paris" } }
+                    }]
+                }
+            }],
+            "usage": { "prompt_tokens": 12, "completion_tokens": 5 }
+        }))
+        .unwrap();
+
+        let parsed = MistralProvider::parse_response(response).unwrap();
+        assert!(parsed.text.is_none());
+        assert_eq!(parsed.tool_calls[0].id, "D681PevKs");
+        assert_eq!(parsed.tool_calls[0].arguments, r#"{"city":"Paris"}"#);
+        assert_eq!(parsed.usage.unwrap().input_tokens, Some(12));
+    }
+
+    #[tokio::test]
+    async fn chat_fails_without_key() {
+        let provider = MistralProvider::new(None);
+        let err = provider
+            .simple_chat("bonjour", "mistral-small-latest", 0.3)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("MISTRAL_API_KEY"));
+    }
+}
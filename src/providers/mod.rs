@@ -16,24 +16,35 @@
 //! To add a new provider, implement [`Provider`] in a new submodule and register it
 //! in [`create_provider_with_url`]. See `AGENTS.md` §7.1 for the full change playbook.
 
+pub mod accelerated;
 pub mod anthropic;
+pub mod azure_openai;
 pub mod bedrock;
+pub mod cached;
 pub mod compatible;
 pub mod copilot;
 pub mod gemini;
+pub mod groq;
+pub mod local_llm;
+pub mod logged;
+pub mod mistral;
 pub mod ollama;
 pub mod openai;
 pub mod openai_codex;
 pub mod openrouter;
+pub mod probe;
+pub mod rate_limit;
 pub mod reliable;
 pub mod router;
+pub mod sse;
+pub mod structured;
 pub mod telnyx;
 pub mod traits;
 
 #[allow(unused_imports)]
 pub use traits::{
-    ChatMessage, ChatRequest, ChatResponse, ConversationMessage, Provider, ProviderCapabilityError,
-    ToolCall, ToolResultMessage,
+    ChatMessage, ChatRequest, ChatResponse, ConversationMessage, ImagePart, Provider,
+    ProviderCapabilityError, ToolCall, ToolResultMessage,
 };
 
 use crate::auth::AuthService;
@@ -676,6 +687,18 @@ pub struct ProviderRuntimeOptions {
     pub zeroclaw_dir: Option<PathBuf>,
     pub secrets_encrypt: bool,
     pub reasoning_enabled: Option<bool>,
+    /// `[gemini]` safety settings forwarded to the Gemini provider.
+    pub gemini_safety_settings: Vec<crate::config::GeminiSafetySetting>,
+    /// `[azure_openai]` endpoint, API version and Entra ID credentials.
+    pub azure_openai: crate::config::AzureOpenAiConfig,
+    /// `[openrouter]` provider routing preferences.
+    pub openrouter: crate::config::OpenRouterConfig,
+    /// `[local_llm]` settings for the on-device llama.cpp provider.
+    pub local_llm: crate::config::LocalLlmConfig,
+    /// Workspace that local model paths are resolved against.
+    pub workspace_dir: Option<PathBuf>,
+    /// `[memory]` response cache consulted for low-temperature calls.
+    pub response_cache: Option<std::sync::Arc<crate::memory::ResponseCache>>,
 }
 
 impl Default for ProviderRuntimeOptions {
@@ -686,6 +709,12 @@ impl Default for ProviderRuntimeOptions {
             zeroclaw_dir: None,
             secrets_encrypt: true,
             reasoning_enabled: None,
+            gemini_safety_settings: Vec::new(),
+            azure_openai: crate::config::AzureOpenAiConfig::default(),
+            openrouter: crate::config::OpenRouterConfig::default(),
+            local_llm: crate::config::LocalLlmConfig::default(),
+            workspace_dir: None,
+            response_cache: None,
         }
     }
 }
@@ -767,12 +796,75 @@ pub fn sanitize_api_error(input: &str) -> String {
 /// Build a sanitized provider error from a failed HTTP response.
 pub async fn api_error(provider: &str, response: reqwest::Response) -> anyhow::Error {
     let status = response.status();
+    let retry_after = rate_limit_suffix(status, response.headers());
     let body = response
         .text()
         .await
         .unwrap_or_else(|_| "<failed to read provider error body>".to_string());
     let sanitized = sanitize_api_error(&body);
-    anyhow::anyhow!("{provider} API error ({status}): {sanitized}")
+    anyhow::anyhow!("{provider} API error ({status}): {sanitized}{retry_after}")
+}
+
+/// Seconds to wait before retrying a rate-limited (429) response, taken from
+/// `Retry-After` or, failing that, the later of the `x-ratelimit-reset-requests`
+/// and `x-ratelimit-reset-tokens` headers sent by Groq and OpenAI.
+pub fn rate_limit_retry_after(
+    status: reqwest::StatusCode,
+    headers: &reqwest::header::HeaderMap,
+) -> Option<f64> {
+    if status != reqwest::StatusCode::TOO_MANY_REQUESTS {
+        return None;
+    }
+    let header = |name: &str| headers.get(name).and_then(|value| value.to_str().ok());
+
+    if let Some(secs) = header("retry-after").and_then(|value| value.trim().parse::<f64>().ok()) {
+        return Some(secs).filter(|secs| secs.is_finite() && *secs >= 0.0);
+    }
+    ["x-ratelimit-reset-requests", "x-ratelimit-reset-tokens"]
+        .into_iter()
+        .filter_map(|name| header(name).and_then(parse_reset_duration))
+        .reduce(f64::max)
+}
+
+/// ` (retry-after: N)` for rate-limited responses, in the form
+/// [`ReliableProvider`] reads its backoff from; empty otherwise.
+pub fn rate_limit_suffix(
+    status: reqwest::StatusCode,
+    headers: &reqwest::header::HeaderMap,
+) -> String {
+    rate_limit_retry_after(status, headers)
+        .map(|secs| format!(" (retry-after: {secs})"))
+        .unwrap_or_default()
+}
+
+/// Parse rate-limit reset durations such as `7.66s`, `2m59.56s`, `1h0m0s` or
+/// `120ms` into seconds.
+fn parse_reset_duration(value: &str) -> Option<f64> {
+    let mut rest = value.trim();
+    if rest.is_empty() {
+        return None;
+    }
+    let mut total = 0.0;
+    while !rest.is_empty() {
+        let number_len = rest
+            .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+            .unwrap_or(rest.len());
+        let number: f64 = rest[..number_len].parse().ok()?;
+        rest = &rest[number_len..];
+        let unit_len = rest
+            .find(|c: char| c.is_ascii_digit() || c == '.')
+            .unwrap_or(rest.len());
+        let scale = match &rest[..unit_len] {
+            "h" => 3600.0,
+            "m" => 60.0,
+            "s" | "" => 1.0,
+            "ms" => 0.001,
+            _ => return None,
+        };
+        total += number * scale;
+        rest = &rest[unit_len..];
+    }
+    Some(total)
 }
 
 /// Resolve API key for a provider from config and environment variables.
@@ -812,6 +904,7 @@ fn resolve_provider_credential(name: &str, credential_override: Option<&str>) ->
         "anthropic" => vec!["ANTHROPIC_OAUTH_TOKEN", "ANTHROPIC_API_KEY"],
         "openrouter" => vec!["OPENROUTER_API_KEY"],
         "openai" => vec!["OPENAI_API_KEY"],
+        "azure-openai" | "azure" | "azure_openai" => vec!["AZURE_OPENAI_API_KEY"],
         "ollama" => vec!["OLLAMA_API_KEY"],
         "venice" => vec!["VENICE_API_KEY"],
         "groq" => vec!["GROQ_API_KEY"],
@@ -969,8 +1062,12 @@ fn create_provider_with_url_and_options(
             )?))
         }
         // ── Primary providers (custom implementations) ───────
-        "openrouter" => Ok(Box::new(openrouter::OpenRouterProvider::new(key))),
-        "anthropic" => Ok(Box::new(anthropic::AnthropicProvider::new(key))),
+        "openrouter" => Ok(Box::new(
+            openrouter::OpenRouterProvider::new(key).with_routing(&options.openrouter),
+        )),
+        "anthropic" => Ok(Box::new(anthropic::AnthropicProvider::with_base_url(
+            key, api_url,
+        ))),
         "openai" => Ok(Box::new(openai::OpenAiProvider::with_base_url(api_url, key))),
         // Ollama uses api_url for custom base URL (e.g. remote Ollama instance)
         "ollama" => Ok(Box::new(ollama::OllamaProvider::new_with_reasoning(
@@ -978,6 +1075,17 @@ fn create_provider_with_url_and_options(
             key,
             options.reasoning_enabled,
         ))),
+        // GPU/NPU inference delegated to the host app's runtime over JNI
+        "accelerated" | "mlc" | "executorch" => Ok(Box::new(
+            accelerated::AcceleratedProvider::new(options.local_llm.clone()),
+        )),
+        // In-process llama.cpp; the model name is a workspace GGUF path
+        "local-llm" | "llamacpp-local" | "on-device" => {
+            Ok(Box::new(local_llm::LlamaCppProvider::new(
+                options.workspace_dir.clone(),
+                options.local_llm.clone(),
+            )))
+        }
         "gemini" | "google" | "google-gemini" => {
             let state_dir = options
                 .zeroclaw_dir
@@ -989,11 +1097,14 @@ fn create_provider_with_url_and_options(
                     )
                 });
             let auth_service = AuthService::new(&state_dir, options.secrets_encrypt);
-            Ok(Box::new(gemini::GeminiProvider::new_with_auth(
-                key,
-                auth_service,
-                options.auth_profile_override.clone(),
-            )))
+            Ok(Box::new(
+                gemini::GeminiProvider::new_with_auth(
+                    key,
+                    auth_service,
+                    options.auth_profile_override.clone(),
+                )
+                .with_safety_settings(&options.gemini_safety_settings),
+            ))
         }
         "telnyx" => Ok(Box::new(telnyx::TelnyxProvider::new(key))),
         // ── OpenAI-compatible providers ──────────────────────
@@ -1056,6 +1167,9 @@ fn create_provider_with_url_and_options(
             )
         )),
         "bedrock" | "aws-bedrock" => Ok(Box::new(bedrock::BedrockProvider::new())),
+        "azure-openai" | "azure" | "azure_openai" => Ok(Box::new(
+            azure_openai::AzureOpenAiProvider::new(&options.azure_openai, api_url, key),
+        )),
         name if is_qwen_oauth_alias(name) => {
             let base_url = api_url
                 .map(str::trim)
@@ -1092,12 +1206,8 @@ fn create_provider_with_url_and_options(
         ))),
 
         // ── Extended ecosystem (community favorites) ─────────
-        "groq" => Ok(Box::new(OpenAiCompatibleProvider::new(
-            "Groq", "https://api.groq.com/openai/v1", key, AuthStyle::Bearer,
-        ))),
-        "mistral" => Ok(Box::new(OpenAiCompatibleProvider::new(
-            "Mistral", "https://api.mistral.ai/v1", key, AuthStyle::Bearer,
-        ))),
+        "groq" => Ok(Box::new(groq::GroqProvider::new(key))),
+        "mistral" => Ok(Box::new(mistral::MistralProvider::with_base_url(api_url, key))),
         "xai" | "grok" => Ok(Box::new(OpenAiCompatibleProvider::new(
             "xAI", "https://api.x.ai", key, AuthStyle::Bearer,
         ))),
@@ -1279,14 +1389,17 @@ pub fn create_resilient_provider(
     )
 }
 
-/// Create provider chain with retry/fallback behavior and auth runtime options.
-pub fn create_resilient_provider_with_options(
+/// Instantiate the primary provider followed by every usable entry of
+/// `reliability.fallback_providers`, in priority order. Each one is paced by
+/// [`rate_limit::RateLimitedProvider`] and, while the wire log is enabled,
+/// logged by [`logged::WireLoggedProvider`].
+pub fn create_provider_chain(
     primary_name: &str,
     api_key: Option<&str>,
     api_url: Option<&str>,
     reliability: &crate::config::ReliabilityConfig,
     options: &ProviderRuntimeOptions,
-) -> anyhow::Result<Box<dyn Provider>> {
+) -> anyhow::Result<Vec<(String, Box<dyn Provider>)>> {
     let mut providers: Vec<(String, Box<dyn Provider>)> = Vec::new();
 
     let primary_provider = match primary_name {
@@ -1333,14 +1446,51 @@ pub fn create_resilient_provider_with_options(
         }
     }
 
+    Ok(providers
+        .into_iter()
+        .map(|(name, provider)| {
+            let provider: Box<dyn Provider> = if crate::observability::wire_log::enabled() {
+                Box::new(logged::WireLoggedProvider::new(&name, provider))
+            } else {
+                provider
+            };
+            let paced = rate_limit::RateLimitedProvider::new(
+                &name,
+                provider,
+                reliability.provider_rate_limits.get(&name),
+            );
+            (name, Box::new(paced) as Box<dyn Provider>)
+        })
+        .collect())
+}
+
+/// Create provider chain with retry/fallback behavior and auth runtime options.
+pub fn create_resilient_provider_with_options(
+    primary_name: &str,
+    api_key: Option<&str>,
+    api_url: Option<&str>,
+    reliability: &crate::config::ReliabilityConfig,
+    options: &ProviderRuntimeOptions,
+) -> anyhow::Result<Box<dyn Provider>> {
+    let providers = create_provider_chain(primary_name, api_key, api_url, reliability, options)?;
+
     let reliable = ReliableProvider::new(
         providers,
         reliability.provider_retries,
         reliability.provider_backoff_ms,
     )
+    .with_max_backoff_ms(reliability.provider_max_backoff_ms)
+    .with_retry_policies(reliability.provider_retry_policies.clone())
     .with_api_keys(reliability.api_keys.clone())
-    .with_model_fallbacks(reliability.model_fallbacks.clone());
+    .with_model_fallbacks(reliability.model_fallbacks.clone())
+    .with_provider_models(reliability.fallback_models.clone());
 
+    if let Some(cache) = &options.response_cache {
+        return Ok(Box::new(cached::CachedProvider::new(
+            Box::new(reliable),
+            cache.clone(),
+        )));
+    }
     Ok(Box::new(reliable))
 }
 
@@ -1493,6 +1643,18 @@ pub fn list_providers() -> Vec<ProviderInfo> {
             aliases: &[],
             local: true,
         },
+        ProviderInfo {
+            name: "local-llm",
+            display_name: "On-device llama.cpp",
+            aliases: &["llamacpp-local", "on-device"],
+            local: true,
+        },
+        ProviderInfo {
+            name: "accelerated",
+            display_name: "On-device GPU/NPU (MLC-LLM, ExecuTorch)",
+            aliases: &["mlc", "executorch"],
+            local: true,
+        },
         ProviderInfo {
             name: "gemini",
             display_name: "Google Gemini",
@@ -1576,6 +1738,12 @@ pub fn list_providers() -> Vec<ProviderInfo> {
             aliases: &["aws-bedrock"],
             local: false,
         },
+        ProviderInfo {
+            name: "azure-openai",
+            display_name: "Azure OpenAI",
+            aliases: &["azure", "azure_openai"],
+            local: false,
+        },
         ProviderInfo {
             name: "qianfan",
             display_name: "Qianfan (Baidu)",
@@ -2237,11 +2405,32 @@ mod tests {
     #[test]
     fn factory_groq() {
         assert!(create_provider("groq", Some("key")).is_ok());
+        let provider = create_provider("groq", Some("key")).unwrap();
+        assert!(!provider.supports_embeddings());
     }
 
     #[test]
     fn factory_mistral() {
         assert!(create_provider("mistral", Some("key")).is_ok());
+        let provider = create_provider("mistral", Some("key")).unwrap();
+        assert!(provider.supports_native_tools());
+        assert!(provider.supports_embeddings());
+    }
+
+    #[test]
+    fn factory_azure_openai() {
+        let options = ProviderRuntimeOptions {
+            azure_openai: crate::config::AzureOpenAiConfig {
+                endpoint: Some("https://contoso.openai.azure.com".into()),
+                ..crate::config::AzureOpenAiConfig::default()
+            },
+            ..ProviderRuntimeOptions::default()
+        };
+        for name in ["azure-openai", "azure", "azure_openai"] {
+            let provider = create_provider_with_options(name, Some("key"), &options).unwrap();
+            assert!(provider.supports_native_tools());
+            assert!(provider.supports_embeddings());
+        }
     }
 
     #[test]
@@ -2442,6 +2631,9 @@ mod tests {
         let reliability = crate::config::ReliabilityConfig {
             provider_retries: 1,
             provider_backoff_ms: 100,
+            provider_max_backoff_ms: 10_000,
+            provider_retry_policies: std::collections::HashMap::new(),
+            provider_rate_limits: std::collections::HashMap::new(),
             fallback_providers: vec![
                 "openrouter".into(),
                 "nonexistent-provider".into(),
@@ -2450,10 +2642,12 @@ mod tests {
             ],
             api_keys: Vec::new(),
             model_fallbacks: std::collections::HashMap::new(),
+            fallback_models: std::collections::HashMap::new(),
             channel_initial_backoff_secs: 2,
             channel_max_backoff_secs: 60,
             scheduler_poll_secs: 15,
             scheduler_retries: 2,
+            provider_probe_interval_secs: 300,
         };
 
         let provider = create_resilient_provider(
@@ -2486,13 +2680,18 @@ mod tests {
         let reliability = crate::config::ReliabilityConfig {
             provider_retries: 1,
             provider_backoff_ms: 100,
+            provider_max_backoff_ms: 10_000,
+            provider_retry_policies: std::collections::HashMap::new(),
+            provider_rate_limits: std::collections::HashMap::new(),
             fallback_providers: vec!["lmstudio".into(), "ollama".into()],
             api_keys: Vec::new(),
             model_fallbacks: std::collections::HashMap::new(),
+            fallback_models: std::collections::HashMap::new(),
             channel_initial_backoff_secs: 2,
             channel_max_backoff_secs: 60,
             scheduler_poll_secs: 15,
             scheduler_retries: 2,
+            provider_probe_interval_secs: 300,
         };
 
         // Primary uses a ZAI key; fallbacks (lmstudio, ollama) should NOT
@@ -2508,13 +2707,18 @@ mod tests {
         let reliability = crate::config::ReliabilityConfig {
             provider_retries: 1,
             provider_backoff_ms: 100,
+            provider_max_backoff_ms: 10_000,
+            provider_retry_policies: std::collections::HashMap::new(),
+            provider_rate_limits: std::collections::HashMap::new(),
             fallback_providers: vec!["custom:http://host.docker.internal:1234/v1".into()],
             api_keys: Vec::new(),
             model_fallbacks: std::collections::HashMap::new(),
+            fallback_models: std::collections::HashMap::new(),
             channel_initial_backoff_secs: 2,
             channel_max_backoff_secs: 60,
             scheduler_poll_secs: 15,
             scheduler_retries: 2,
+            provider_probe_interval_secs: 300,
         };
 
         let provider =
@@ -2529,6 +2733,9 @@ mod tests {
         let reliability = crate::config::ReliabilityConfig {
             provider_retries: 1,
             provider_backoff_ms: 100,
+            provider_max_backoff_ms: 10_000,
+            provider_retry_policies: std::collections::HashMap::new(),
+            provider_rate_limits: std::collections::HashMap::new(),
             fallback_providers: vec![
                 "deepseek".into(),
                 "custom:http://localhost:8080/v1".into(),
@@ -2537,10 +2744,12 @@ mod tests {
             ],
             api_keys: Vec::new(),
             model_fallbacks: std::collections::HashMap::new(),
+            fallback_models: std::collections::HashMap::new(),
             channel_initial_backoff_secs: 2,
             channel_max_backoff_secs: 60,
             scheduler_poll_secs: 15,
             scheduler_retries: 2,
+            provider_probe_interval_secs: 300,
         };
 
         let provider = create_resilient_provider("zai", Some("zai-test-key"), None, &reliability);
@@ -2566,13 +2775,18 @@ mod tests {
         let reliability = crate::config::ReliabilityConfig {
             provider_retries: 1,
             provider_backoff_ms: 100,
+            provider_max_backoff_ms: 10_000,
+            provider_retry_policies: std::collections::HashMap::new(),
+            provider_rate_limits: std::collections::HashMap::new(),
             fallback_providers: vec!["osaurus".into(), "lmstudio".into()],
             api_keys: Vec::new(),
             model_fallbacks: std::collections::HashMap::new(),
+            fallback_models: std::collections::HashMap::new(),
             channel_initial_backoff_secs: 2,
             channel_max_backoff_secs: 60,
             scheduler_poll_secs: 15,
             scheduler_retries: 2,
+            provider_probe_interval_secs: 300,
         };
 
         let provider = create_resilient_provider("zai", Some("zai-test-key"), None, &reliability);
@@ -2604,6 +2818,7 @@ mod tests {
             "minimax",
             "minimax-cn",
             "bedrock",
+            "azure-openai",
             "qianfan",
             "doubao",
             "qwen",
@@ -2690,6 +2905,45 @@ mod tests {
 
     // ── API error sanitization ───────────────────────────────
 
+    #[test]
+    fn rate_limit_retry_after_reads_groq_headers() {
+        use reqwest::header::{HeaderMap, HeaderValue};
+        use reqwest::StatusCode;
+
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            "x-ratelimit-reset-requests",
+            HeaderValue::from_static("2m59.56s"),
+        );
+        headers.insert(
+            "x-ratelimit-reset-tokens",
+            HeaderValue::from_static("7.66s"),
+        );
+        let secs = rate_limit_retry_after(StatusCode::TOO_MANY_REQUESTS, &headers).unwrap();
+        assert!((secs - 179.56).abs() < 1e-9);
+        assert_eq!(
+            rate_limit_retry_after(StatusCode::BAD_REQUEST, &headers),
+            None
+        );
+
+        headers.insert("retry-after", HeaderValue::from_static("8"));
+        assert_eq!(
+            rate_limit_suffix(StatusCode::TOO_MANY_REQUESTS, &headers),
+            " (retry-after: 8)"
+        );
+        assert_eq!(rate_limit_suffix(StatusCode::OK, &headers), "");
+    }
+
+    #[test]
+    fn parse_reset_duration_handles_units() {
+        assert_eq!(parse_reset_duration("7.66s"), Some(7.66));
+        assert_eq!(parse_reset_duration("1h0m0s"), Some(3600.0));
+        assert_eq!(parse_reset_duration("120ms"), Some(0.12));
+        assert_eq!(parse_reset_duration("30"), Some(30.0));
+        assert_eq!(parse_reset_duration("soon"), None);
+        assert_eq!(parse_reset_duration(""), None);
+    }
+
     #[test]
     fn sanitize_scrubs_sk_prefix() {
         let input = "request failed: sk-1234567890abcdef";
@@ -2859,13 +3113,18 @@ mod tests {
         let reliability = crate::config::ReliabilityConfig {
             provider_retries: 1,
             provider_backoff_ms: 100,
+            provider_max_backoff_ms: 10_000,
+            provider_retry_policies: std::collections::HashMap::new(),
+            provider_rate_limits: std::collections::HashMap::new(),
             fallback_providers: vec!["openai-codex:second".into()],
             api_keys: Vec::new(),
             model_fallbacks: std::collections::HashMap::new(),
+            fallback_models: std::collections::HashMap::new(),
             channel_initial_backoff_secs: 2,
             channel_max_backoff_secs: 60,
             scheduler_poll_secs: 15,
             scheduler_retries: 2,
+            provider_probe_interval_secs: 300,
         };
 
         // openai-codex resolves its own OAuth credential; it should not
@@ -2883,6 +3142,9 @@ mod tests {
         let reliability = crate::config::ReliabilityConfig {
             provider_retries: 1,
             provider_backoff_ms: 100,
+            provider_max_backoff_ms: 10_000,
+            provider_retry_policies: std::collections::HashMap::new(),
+            provider_rate_limits: std::collections::HashMap::new(),
             fallback_providers: vec![
                 "openai-codex:second".into(),
                 "custom:http://localhost:8080/v1".into(),
@@ -2891,10 +3153,12 @@ mod tests {
             ],
             api_keys: Vec::new(),
             model_fallbacks: std::collections::HashMap::new(),
+            fallback_models: std::collections::HashMap::new(),
             channel_initial_backoff_secs: 2,
             channel_max_backoff_secs: 60,
             scheduler_poll_secs: 15,
             scheduler_retries: 2,
+            provider_probe_interval_secs: 300,
         };
 
         let provider = create_resilient_provider("ollama", None, None, &reliability);
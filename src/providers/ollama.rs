@@ -1,12 +1,33 @@
 use crate::multimodal;
 use crate::providers::traits::{
-    ChatMessage, ChatResponse, Provider, ProviderCapabilities, TokenUsage, ToolCall,
+    prompt_guided_messages, ChatMessage, ChatResponse, Provider, ProviderCapabilities, TokenUsage,
+    ToolCall,
 };
 use async_trait::async_trait;
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
+/// Model families whose Ollama templates have no tool support; they get the
+/// text tool protocol instead of a `tools` array (which Ollama rejects).
+const NO_TOOL_FAMILIES: &[&str] = &[
+    "llama2",
+    "llama3",
+    "codellama",
+    "gemma",
+    "gemma2",
+    "gemma3",
+    "phi",
+    "phi3",
+    "llava",
+    "bakllava",
+    "moondream",
+    "tinyllama",
+    "orca-mini",
+    "vicuna",
+    "starcoder2",
+];
+
 pub struct OllamaProvider {
     base_url: String,
     api_key: Option<String>,
@@ -80,6 +101,23 @@ struct ResponseMessage {
     thinking: Option<String>,
 }
 
+#[derive(Debug, Deserialize)]
+struct ApiEmbedResponse {
+    embeddings: Vec<Vec<f32>>,
+}
+
+/// `/api/tags` response: the models pulled on this server.
+#[derive(Debug, Deserialize)]
+struct ApiTagsResponse {
+    #[serde(default)]
+    models: Vec<ApiTag>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ApiTag {
+    name: String,
+}
+
 #[derive(Debug, Deserialize)]
 struct OllamaToolCall {
     id: Option<String>,
@@ -164,6 +202,13 @@ impl OllamaProvider {
         Ok((normalized_model, should_auth))
     }
 
+    /// Family of an Ollama model reference, e.g. `gemma3` for
+    /// `library/gemma3:4b-it-q4_K_M`.
+    fn model_family(model: &str) -> String {
+        let name = model.rsplit('/').next().unwrap_or(model);
+        name.split(':').next().unwrap_or(name).to_ascii_lowercase()
+    }
+
     fn parse_tool_arguments(arguments: &str) -> serde_json::Value {
         serde_json::from_str(arguments).unwrap_or_else(|_| serde_json::json!({}))
     }
@@ -677,12 +722,24 @@ impl Provider for OllamaProvider {
         true
     }
 
+    fn supports_native_tools_for_model(&self, model: &str) -> bool {
+        !NO_TOOL_FAMILIES.contains(&Self::model_family(model).as_str())
+    }
+
     async fn chat(
         &self,
         request: crate::providers::traits::ChatRequest<'_>,
         model: &str,
         temperature: f64,
     ) -> anyhow::Result<ChatResponse> {
+        if let Some(messages) = prompt_guided_messages(self, &request, model) {
+            let request = crate::providers::traits::ChatRequest {
+                messages: &messages,
+                tools: None,
+            };
+            return self.chat(request, model, temperature).await;
+        }
+
         // Convert ToolSpec to OpenAI-compatible JSON and delegate to chat_with_tools.
         if let Some(specs) = request.tools {
             if !specs.is_empty() {
@@ -716,6 +773,65 @@ impl Provider for OllamaProvider {
             reasoning_content: None,
         })
     }
+
+    fn supports_embeddings(&self) -> bool {
+        true
+    }
+
+    async fn embed(&self, texts: &[&str], model: &str) -> anyhow::Result<Vec<Vec<f32>>> {
+        if texts.is_empty() {
+            return Ok(Vec::new());
+        }
+        let (normalized_model, should_auth) = self.resolve_request_details(model)?;
+
+        let url = format!("{}/api/embed", self.base_url);
+        let mut request_builder = self
+            .http_client()
+            .post(&url)
+            .json(&serde_json::json!({ "model": normalized_model, "input": texts }));
+        if should_auth {
+            if let Some(key) = self.api_key.as_ref() {
+                request_builder = request_builder.bearer_auth(key);
+            }
+        }
+
+        let response = request_builder.send().await?;
+        if !response.status().is_success() {
+            return Err(super::api_error("Ollama", response).await);
+        }
+
+        let body: ApiEmbedResponse = response.json().await?;
+        if body.embeddings.len() != texts.len() {
+            anyhow::bail!(
+                "Ollama returned {} embeddings for {} inputs",
+                body.embeddings.len(),
+                texts.len()
+            );
+        }
+        Ok(body.embeddings)
+    }
+
+    /// Models pulled on the server (`/api/tags`).
+    async fn list_models(&self) -> anyhow::Result<Vec<String>> {
+        let mut request_builder = self
+            .http_client()
+            .get(format!("{}/api/tags", self.base_url));
+        if !self.is_local_endpoint() {
+            if let Some(key) = self.api_key.as_ref() {
+                request_builder = request_builder.bearer_auth(key);
+            }
+        }
+
+        let response = request_builder.send().await?;
+        if !response.status().is_success() {
+            return Err(super::api_error("Ollama", response).await);
+        }
+
+        let body: ApiTagsResponse = response.json().await?;
+        let mut names: Vec<String> = body.models.into_iter().map(|tag| tag.name).collect();
+        names.sort();
+        Ok(names)
+    }
 }
 
 // ─── Tests ────────────────────────────────────────────────────────────────────
@@ -754,6 +870,15 @@ mod tests {
         assert_eq!(p.base_url, "");
     }
 
+    #[test]
+    fn embed_response_deserializes() {
+        let body: ApiEmbedResponse = serde_json::from_str(
+            r#"{"model":"nomic-embed-text","embeddings":[[0.1,0.2],[0.3,0.4]]}"#,
+        )
+        .unwrap();
+        assert_eq!(body.embeddings, vec![vec![0.1, 0.2], vec![0.3, 0.4]]);
+    }
+
     #[test]
     fn cloud_suffix_strips_model_name() {
         let p = OllamaProvider::new(Some("https://ollama.com"), Some("ollama-key"));
@@ -1069,4 +1194,15 @@ mod tests {
         assert!(resp.prompt_eval_count.is_none());
         assert!(resp.eval_count.is_none());
     }
+
+    #[test]
+    fn tool_support_is_selected_per_model_family() {
+        let p = OllamaProvider::new(None, None);
+        assert!(p.supports_native_tools_for_model("qwen2.5:7b"));
+        assert!(p.supports_native_tools_for_model("llama3.1:8b"));
+        assert!(p.supports_native_tools_for_model("qwen3:cloud"));
+        assert!(!p.supports_native_tools_for_model("llama3:8b"));
+        assert!(!p.supports_native_tools_for_model("library/gemma3:4b-it-q4_K_M"));
+        assert!(!p.supports_native_tools_for_model("LLaVA"));
+    }
 }
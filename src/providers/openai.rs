@@ -1,11 +1,18 @@
+use crate::multimodal;
+use crate::providers::sse;
+use crate::providers::structured::{JsonSchemaFormat, ResponseFormat};
 use crate::providers::traits::{
-    ChatMessage, ChatRequest as ProviderChatRequest, ChatResponse as ProviderChatResponse,
-    Provider, TokenUsage, ToolCall as ProviderToolCall,
+    parse_openai_embeddings, parse_openai_model_ids, prompt_guided_messages, ChatMessage,
+    ChatRequest as ProviderChatRequest, ChatResponse as ProviderChatResponse, Provider,
+    ProviderCapabilities, StreamChunk, StreamOptions, StreamResult, TokenUsage,
+    ToolCall as ProviderToolCall, ToolsPayload,
 };
 use crate::tools::ToolSpec;
 use async_trait::async_trait;
+use futures_util::stream;
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 
 pub struct OpenAiProvider {
     base_url: String,
@@ -16,13 +23,33 @@ pub struct OpenAiProvider {
 struct ChatRequest {
     model: String,
     messages: Vec<Message>,
-    temperature: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    temperature: Option<f64>,
 }
 
 #[derive(Debug, Serialize)]
 struct Message {
     role: String,
-    content: String,
+    content: MessageContent,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(untagged)]
+enum MessageContent {
+    Text(String),
+    Parts(Vec<MessagePart>),
+}
+
+#[derive(Debug, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum MessagePart {
+    Text { text: String },
+    ImageUrl { image_url: ImageUrlPart },
+}
+
+#[derive(Debug, Serialize)]
+struct ImageUrlPart {
+    url: String,
 }
 
 #[derive(Debug, Deserialize)]
@@ -57,18 +84,34 @@ impl ResponseMessage {
 struct NativeChatRequest {
     model: String,
     messages: Vec<NativeMessage>,
-    temperature: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    temperature: Option<f64>,
     #[serde(skip_serializing_if = "Option::is_none")]
     tools: Option<Vec<NativeToolSpec>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     tool_choice: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    prompt_cache_key: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    response_format: Option<ResponseFormat>,
+}
+
+#[derive(Debug, Serialize)]
+struct StreamChatRequest {
+    model: String,
+    messages: Vec<NativeMessage>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    temperature: Option<f64>,
+    stream: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    prompt_cache_key: Option<String>,
 }
 
 #[derive(Debug, Serialize)]
 struct NativeMessage {
     role: String,
     #[serde(skip_serializing_if = "Option::is_none")]
-    content: Option<String>,
+    content: Option<MessageContent>,
     #[serde(skip_serializing_if = "Option::is_none")]
     tool_call_id: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -135,6 +178,30 @@ struct UsageInfo {
     prompt_tokens: Option<u64>,
     #[serde(default)]
     completion_tokens: Option<u64>,
+    #[serde(default)]
+    prompt_tokens_details: Option<PromptTokensDetails>,
+}
+
+#[derive(Debug, Deserialize)]
+struct PromptTokensDetails {
+    #[serde(default)]
+    cached_tokens: Option<u64>,
+}
+
+impl UsageInfo {
+    fn into_token_usage(self) -> TokenUsage {
+        if let Some(cached) = self
+            .prompt_tokens_details
+            .and_then(|details| details.cached_tokens)
+            .filter(|cached| *cached > 0)
+        {
+            tracing::debug!(cached_tokens = cached, "OpenAI prompt cache hit");
+        }
+        TokenUsage {
+            input_tokens: self.prompt_tokens,
+            output_tokens: self.completion_tokens,
+        }
+    }
 }
 
 #[derive(Debug, Deserialize)]
@@ -178,6 +245,64 @@ impl OpenAiProvider {
         }
     }
 
+    /// Reasoning models (o-series, gpt-5) reject any `temperature` other than
+    /// the default, so it is omitted for them.
+    fn request_temperature(model: &str, temperature: f64) -> Option<f64> {
+        let model = model
+            .rsplit('/')
+            .next()
+            .unwrap_or(model)
+            .to_ascii_lowercase();
+        let is_reasoning_model = model.starts_with("gpt-5")
+            || (model.starts_with('o') && model[1..].starts_with(|c: char| c.is_ascii_digit()));
+        (!is_reasoning_model).then_some(temperature)
+    }
+
+    /// Legacy completion, search-preview, and early o1 models reject
+    /// `tools`; they get the text tool protocol instead.
+    fn model_supports_tools(model: &str) -> bool {
+        let model = model
+            .rsplit('/')
+            .next()
+            .unwrap_or(model)
+            .to_ascii_lowercase();
+        !(model.starts_with("o1-mini")
+            || model.starts_with("o1-preview")
+            || model.starts_with("chatgpt-")
+            || model.contains("-instruct")
+            || model.contains("search-preview"))
+    }
+
+    /// Models occasionally emit an empty argument string for zero-arg calls;
+    /// the API rejects that when the call is echoed back in history.
+    /// `prompt_cache_key` shared by requests with the same system prompt and
+    /// tool schemas, so OpenAI routes them to the same prompt cache. Only
+    /// sent to OpenAI itself; other endpoints may reject unknown fields.
+    fn prompt_cache_key(
+        &self,
+        messages: &[NativeMessage],
+        tools: Option<&[NativeToolSpec]>,
+    ) -> Option<String> {
+        if !self.base_url.starts_with("https://api.openai.com") {
+            return None;
+        }
+        let system: Vec<&NativeMessage> = messages.iter().filter(|m| m.role == "system").collect();
+        if system.is_empty() && tools.is_none_or(<[NativeToolSpec]>::is_empty) {
+            return None;
+        }
+        let prefix = serde_json::json!({ "system": system, "tools": tools });
+        let digest = Sha256::digest(prefix.to_string().as_bytes());
+        Some(format!("zeroclaw-{}", &hex::encode(digest)[..16]))
+    }
+
+    fn normalize_arguments(arguments: String) -> String {
+        if arguments.trim().is_empty() {
+            "{}".to_string()
+        } else {
+            arguments
+        }
+    }
+
     fn convert_tools(tools: Option<&[ToolSpec]>) -> Option<Vec<NativeToolSpec>> {
         tools.map(|items| {
             items
@@ -194,6 +319,29 @@ impl OpenAiProvider {
         })
     }
 
+    /// Split `[IMAGE:...]` markers in user messages into `image_url` parts.
+    fn to_message_content(role: &str, content: &str) -> MessageContent {
+        if role != "user" {
+            return MessageContent::Text(content.to_string());
+        }
+
+        let (cleaned_text, image_refs) = multimodal::parse_image_markers(content);
+        if image_refs.is_empty() {
+            return MessageContent::Text(content.to_string());
+        }
+
+        let mut parts = Vec::with_capacity(image_refs.len() + 1);
+        if !cleaned_text.is_empty() {
+            parts.push(MessagePart::Text { text: cleaned_text });
+        }
+        for image_ref in image_refs {
+            parts.push(MessagePart::ImageUrl {
+                image_url: ImageUrlPart { url: image_ref },
+            });
+        }
+        MessageContent::Parts(parts)
+    }
+
     fn convert_messages(messages: &[ChatMessage]) -> Vec<NativeMessage> {
         messages
             .iter()
@@ -213,14 +361,14 @@ impl OpenAiProvider {
                                         kind: Some("function".to_string()),
                                         function: NativeFunctionCall {
                                             name: tc.name,
-                                            arguments: tc.arguments,
+                                            arguments: Self::normalize_arguments(tc.arguments),
                                         },
                                     })
                                     .collect::<Vec<_>>();
                                 let content = value
                                     .get("content")
                                     .and_then(serde_json::Value::as_str)
-                                    .map(ToString::to_string);
+                                    .map(|text| MessageContent::Text(text.to_string()));
                                 let reasoning_content = value
                                     .get("reasoning_content")
                                     .and_then(serde_json::Value::as_str)
@@ -243,13 +391,16 @@ impl OpenAiProvider {
                             .get("tool_call_id")
                             .and_then(serde_json::Value::as_str)
                             .map(ToString::to_string);
-                        let content = value
-                            .get("content")
-                            .and_then(serde_json::Value::as_str)
-                            .map(ToString::to_string);
+                        // Tool messages must carry content; keep non-string
+                        // payloads as JSON text instead of dropping them.
+                        let content = match value.get("content") {
+                            Some(serde_json::Value::String(text)) => text.clone(),
+                            Some(serde_json::Value::Null) | None => String::new(),
+                            Some(other) => other.to_string(),
+                        };
                         return NativeMessage {
                             role: "tool".to_string(),
-                            content,
+                            content: Some(MessageContent::Text(content)),
                             tool_call_id,
                             tool_calls: None,
                             reasoning_content: None,
@@ -259,7 +410,7 @@ impl OpenAiProvider {
 
                 NativeMessage {
                     role: m.role.clone(),
-                    content: Some(m.content.clone()),
+                    content: Some(Self::to_message_content(&m.role, &m.content)),
                     tool_call_id: None,
                     tool_calls: None,
                     reasoning_content: None,
@@ -278,7 +429,7 @@ impl OpenAiProvider {
             .map(|tc| ProviderToolCall {
                 id: tc.id.unwrap_or_else(|| uuid::Uuid::new_v4().to_string()),
                 name: tc.function.name,
-                arguments: tc.function.arguments,
+                arguments: Self::normalize_arguments(tc.function.arguments),
             })
             .collect::<Vec<_>>();
 
@@ -293,10 +444,61 @@ impl OpenAiProvider {
     fn http_client(&self) -> Client {
         crate::config::build_runtime_proxy_client_with_timeouts("provider.openai", 120, 10)
     }
+
+    async fn send_native(
+        &self,
+        credential: &str,
+        request: &NativeChatRequest,
+    ) -> anyhow::Result<ProviderChatResponse> {
+        let response = self
+            .http_client()
+            .post(format!("{}/chat/completions", self.base_url))
+            .header("Authorization", format!("Bearer {credential}"))
+            .json(request)
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            return Err(super::api_error("OpenAI", response).await);
+        }
+
+        let native_response: NativeChatResponse = response.json().await?;
+        let usage = native_response.usage.map(UsageInfo::into_token_usage);
+        let message = native_response
+            .choices
+            .into_iter()
+            .next()
+            .map(|c| c.message)
+            .ok_or_else(|| anyhow::anyhow!("No response from OpenAI"))?;
+        let mut result = Self::parse_native_response(message);
+        result.usage = usage;
+        Ok(result)
+    }
 }
 
 #[async_trait]
 impl Provider for OpenAiProvider {
+    fn capabilities(&self) -> ProviderCapabilities {
+        ProviderCapabilities {
+            native_tool_calling: true,
+            vision: true,
+        }
+    }
+
+    fn supports_native_tools_for_model(&self, model: &str) -> bool {
+        Self::model_supports_tools(model)
+    }
+
+    fn convert_tools(&self, tools: &[ToolSpec]) -> ToolsPayload {
+        ToolsPayload::OpenAI {
+            tools: Self::convert_tools(Some(tools))
+                .unwrap_or_default()
+                .into_iter()
+                .filter_map(|spec| serde_json::to_value(spec).ok())
+                .collect(),
+        }
+    }
+
     async fn chat_with_system(
         &self,
         system_prompt: Option<&str>,
@@ -313,19 +515,19 @@ impl Provider for OpenAiProvider {
         if let Some(sys) = system_prompt {
             messages.push(Message {
                 role: "system".to_string(),
-                content: sys.to_string(),
+                content: MessageContent::Text(sys.to_string()),
             });
         }
 
         messages.push(Message {
             role: "user".to_string(),
-            content: message.to_string(),
+            content: Self::to_message_content("user", message),
         });
 
         let request = ChatRequest {
             model: model.to_string(),
             messages,
-            temperature,
+            temperature: Self::request_temperature(model, temperature),
         };
 
         let response = self
@@ -360,45 +562,27 @@ impl Provider for OpenAiProvider {
             anyhow::anyhow!("OpenAI API key not set. Set OPENAI_API_KEY or edit config.toml.")
         })?;
 
+        if let Some(messages) = prompt_guided_messages(self, &request, model) {
+            let request = ProviderChatRequest {
+                messages: &messages,
+                tools: None,
+            };
+            return self.chat(request, model, temperature).await;
+        }
+
         let tools = Self::convert_tools(request.tools);
+        let messages = Self::convert_messages(request.messages);
         let native_request = NativeChatRequest {
             model: model.to_string(),
-            messages: Self::convert_messages(request.messages),
-            temperature,
+            prompt_cache_key: self.prompt_cache_key(&messages, tools.as_deref()),
+            messages,
+            temperature: Self::request_temperature(model, temperature),
             tool_choice: tools.as_ref().map(|_| "auto".to_string()),
             tools,
+            response_format: None,
         };
 
-        let response = self
-            .http_client()
-            .post(format!("{}/chat/completions", self.base_url))
-            .header("Authorization", format!("Bearer {credential}"))
-            .json(&native_request)
-            .send()
-            .await?;
-
-        if !response.status().is_success() {
-            return Err(super::api_error("OpenAI", response).await);
-        }
-
-        let native_response: NativeChatResponse = response.json().await?;
-        let usage = native_response.usage.map(|u| TokenUsage {
-            input_tokens: u.prompt_tokens,
-            output_tokens: u.completion_tokens,
-        });
-        let message = native_response
-            .choices
-            .into_iter()
-            .next()
-            .map(|c| c.message)
-            .ok_or_else(|| anyhow::anyhow!("No response from OpenAI"))?;
-        let mut result = Self::parse_native_response(message);
-        result.usage = usage;
-        Ok(result)
-    }
-
-    fn supports_native_tools(&self) -> bool {
-        true
+        self.send_native(credential, &native_request).await
     }
 
     async fn chat_with_tools(
@@ -424,40 +608,140 @@ impl Provider for OpenAiProvider {
             )
         };
 
+        let messages = Self::convert_messages(messages);
         let native_request = NativeChatRequest {
             model: model.to_string(),
-            messages: Self::convert_messages(messages),
-            temperature,
+            prompt_cache_key: self.prompt_cache_key(&messages, native_tools.as_deref()),
+            messages,
+            temperature: Self::request_temperature(model, temperature),
             tool_choice: native_tools.as_ref().map(|_| "auto".to_string()),
             tools: native_tools,
+            response_format: None,
         };
 
-        let response = self
+        self.send_native(credential, &native_request).await
+    }
+
+    fn supports_structured_output(&self) -> bool {
+        true
+    }
+
+    async fn chat_with_schema(
+        &self,
+        messages: &[ChatMessage],
+        format: &JsonSchemaFormat,
+        model: &str,
+        temperature: f64,
+    ) -> anyhow::Result<String> {
+        let credential = self.credential.as_ref().ok_or_else(|| {
+            anyhow::anyhow!("OpenAI API key not set. Set OPENAI_API_KEY or edit config.toml.")
+        })?;
+
+        let messages = Self::convert_messages(messages);
+        let native_request = NativeChatRequest {
+            model: model.to_string(),
+            prompt_cache_key: self.prompt_cache_key(&messages, None),
+            messages,
+            temperature: Self::request_temperature(model, temperature),
+            tools: None,
+            tool_choice: None,
+            response_format: Some(ResponseFormat::json_schema(format)),
+        };
+
+        let response = self.send_native(credential, &native_request).await?;
+        Ok(response.text.unwrap_or_default())
+    }
+
+    fn supports_streaming(&self) -> bool {
+        true
+    }
+
+    fn stream_chat_with_system(
+        &self,
+        system_prompt: Option<&str>,
+        message: &str,
+        model: &str,
+        temperature: f64,
+        options: StreamOptions,
+    ) -> stream::BoxStream<'static, StreamResult<StreamChunk>> {
+        let mut messages = Vec::new();
+        if let Some(sys) = system_prompt {
+            messages.push(ChatMessage::system(sys));
+        }
+        messages.push(ChatMessage::user(message));
+        self.stream_chat_with_history(&messages, model, temperature, options)
+    }
+
+    fn stream_chat_with_history(
+        &self,
+        messages: &[ChatMessage],
+        model: &str,
+        temperature: f64,
+        options: StreamOptions,
+    ) -> stream::BoxStream<'static, StreamResult<StreamChunk>> {
+        let Some(credential) = self.credential.as_ref() else {
+            return sse::error_stream("OpenAI API key not set");
+        };
+
+        let messages = Self::convert_messages(messages);
+        let request = StreamChatRequest {
+            model: model.to_string(),
+            prompt_cache_key: self.prompt_cache_key(&messages, None),
+            messages,
+            temperature: Self::request_temperature(model, temperature),
+            stream: true,
+        };
+        let req = self
             .http_client()
             .post(format!("{}/chat/completions", self.base_url))
             .header("Authorization", format!("Bearer {credential}"))
-            .json(&native_request)
+            .json(&request);
+        sse::stream_request(req, options.count_tokens, sse::chat_completions_delta)
+    }
+
+    fn supports_embeddings(&self) -> bool {
+        true
+    }
+
+    async fn embed(&self, texts: &[&str], model: &str) -> anyhow::Result<Vec<Vec<f32>>> {
+        if texts.is_empty() {
+            return Ok(Vec::new());
+        }
+        let credential = self.credential.as_ref().ok_or_else(|| {
+            anyhow::anyhow!("OpenAI API key not set. Set OPENAI_API_KEY or edit config.toml.")
+        })?;
+
+        let response = self
+            .http_client()
+            .post(format!("{}/embeddings", self.base_url))
+            .header("Authorization", format!("Bearer {credential}"))
+            .json(&serde_json::json!({ "model": model, "input": texts }))
             .send()
             .await?;
+        if !response.status().is_success() {
+            return Err(super::api_error("OpenAI", response).await);
+        }
 
+        let body: serde_json::Value = response.json().await?;
+        parse_openai_embeddings(&body)
+    }
+
+    async fn list_models(&self) -> anyhow::Result<Vec<String>> {
+        let credential = self.credential.as_ref().ok_or_else(|| {
+            anyhow::anyhow!("OpenAI API key not set. Set OPENAI_API_KEY or edit config.toml.")
+        })?;
+        let response = self
+            .http_client()
+            .get(format!("{}/models", self.base_url))
+            .header("Authorization", format!("Bearer {credential}"))
+            .send()
+            .await?;
         if !response.status().is_success() {
             return Err(super::api_error("OpenAI", response).await);
         }
 
-        let native_response: NativeChatResponse = response.json().await?;
-        let usage = native_response.usage.map(|u| TokenUsage {
-            input_tokens: u.prompt_tokens,
-            output_tokens: u.completion_tokens,
-        });
-        let message = native_response
-            .choices
-            .into_iter()
-            .next()
-            .map(|c| c.message)
-            .ok_or_else(|| anyhow::anyhow!("No response from OpenAI"))?;
-        let mut result = Self::parse_native_response(message);
-        result.usage = usage;
-        Ok(result)
+        let body: serde_json::Value = response.json().await?;
+        Ok(parse_openai_model_ids(&body))
     }
 
     async fn warmup(&self) -> anyhow::Result<()> {
@@ -519,14 +803,14 @@ mod tests {
             messages: vec![
                 Message {
                     role: "system".to_string(),
-                    content: "You are ZeroClaw".to_string(),
+                    content: MessageContent::Text("You are ZeroClaw".to_string()),
                 },
                 Message {
                     role: "user".to_string(),
-                    content: "hello".to_string(),
+                    content: MessageContent::Text("hello".to_string()),
                 },
             ],
-            temperature: 0.7,
+            temperature: Some(0.7),
         };
         let json = serde_json::to_string(&req).unwrap();
         assert!(json.contains("\"role\":\"system\""));
@@ -540,9 +824,9 @@ mod tests {
             model: "gpt-4o".to_string(),
             messages: vec![Message {
                 role: "user".to_string(),
-                content: "hello".to_string(),
+                content: MessageContent::Text("hello".to_string()),
             }],
-            temperature: 0.0,
+            temperature: Some(0.0),
         };
         let json = serde_json::to_string(&req).unwrap();
         assert!(!json.contains("system"));
@@ -600,6 +884,17 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    #[tokio::test]
+    async fn embed_fails_without_key() {
+        let provider = OpenAiProvider::new(None);
+        assert!(provider.supports_embeddings());
+        let err = provider
+            .embed(&["hello"], "text-embedding-3-small")
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("API key not set"));
+    }
+
     // ----------------------------------------------------------
     // Reasoning model fallback tests (reasoning_content)
     // ----------------------------------------------------------
@@ -726,6 +1021,84 @@ mod tests {
         assert_eq!(usage.completion_tokens, Some(50));
     }
 
+    #[test]
+    fn native_response_parses_cached_prompt_tokens() {
+        let json = r#"{
+            "choices": [{"message": {"content": "Hello"}}],
+            "usage": {
+                "prompt_tokens": 2048,
+                "completion_tokens": 10,
+                "prompt_tokens_details": {"cached_tokens": 1920}
+            }
+        }"#;
+        let resp: NativeChatResponse = serde_json::from_str(json).unwrap();
+        let usage = resp.usage.unwrap();
+        assert_eq!(
+            usage
+                .prompt_tokens_details
+                .as_ref()
+                .and_then(|d| d.cached_tokens),
+            Some(1920)
+        );
+        assert_eq!(usage.into_token_usage().input_tokens, Some(2048));
+    }
+
+    #[test]
+    fn prompt_cache_key_follows_system_prompt_and_tools() {
+        let provider = OpenAiProvider::new(Some("key"));
+        let tools = OpenAiProvider::convert_tools(Some(&[ToolSpec {
+            name: "shell".into(),
+            description: "Run a command".into(),
+            parameters: serde_json::json!({"type": "object"}),
+        }]));
+        let first = OpenAiProvider::convert_messages(&[
+            ChatMessage::system("static prompt"),
+            ChatMessage::user("one"),
+        ]);
+        let second = OpenAiProvider::convert_messages(&[
+            ChatMessage::system("static prompt"),
+            ChatMessage::user("two"),
+        ]);
+        let key = provider.prompt_cache_key(&first, tools.as_deref());
+        assert!(key.as_deref().is_some_and(|k| k.starts_with("zeroclaw-")));
+        assert_eq!(key, provider.prompt_cache_key(&second, tools.as_deref()));
+        assert_ne!(key, provider.prompt_cache_key(&second, None));
+
+        let user_only = OpenAiProvider::convert_messages(&[ChatMessage::user("hi")]);
+        assert!(provider.prompt_cache_key(&user_only, None).is_none());
+
+        let proxied =
+            OpenAiProvider::with_base_url(Some("https://gateway.example.com/v1"), Some("key"));
+        assert!(proxied.prompt_cache_key(&first, tools.as_deref()).is_none());
+    }
+
+    #[test]
+    fn schema_request_serializes_response_format() {
+        let format = JsonSchemaFormat::new(
+            "verdict",
+            serde_json::json!({"type": "object", "required": ["ok"]}),
+        )
+        .strict(true);
+        let request = NativeChatRequest {
+            model: "gpt-4o".into(),
+            messages: OpenAiProvider::convert_messages(&[ChatMessage::user("ok?")]),
+            temperature: None,
+            tools: None,
+            tool_choice: None,
+            prompt_cache_key: None,
+            response_format: Some(ResponseFormat::json_schema(&format)),
+        };
+        let json = serde_json::to_value(&request).unwrap();
+        assert_eq!(json["response_format"]["type"], "json_schema");
+        assert_eq!(json["response_format"]["json_schema"]["name"], "verdict");
+        assert_eq!(json["response_format"]["json_schema"]["strict"], true);
+        assert_eq!(
+            json["response_format"]["json_schema"]["schema"]["required"][0],
+            "ok"
+        );
+        assert!(json.get("tools").is_none());
+    }
+
     #[test]
     fn native_response_parses_without_usage() {
         let json = r#"{"choices": [{"message": {"content": "Hello"}}]}"#;
@@ -802,11 +1175,34 @@ mod tests {
         assert!(native[0].reasoning_content.is_none());
     }
 
+    #[test]
+    fn convert_messages_sends_user_images_as_image_url_parts() {
+        let messages = vec![
+            ChatMessage::system("[IMAGE:https://example.com/ignored.png]"),
+            ChatMessage::user("Describe [IMAGE:data:image/png;base64,iVBORw0KGgo=]"),
+        ];
+
+        let native = OpenAiProvider::convert_messages(&messages);
+        let system = serde_json::to_value(&native[0]).unwrap();
+        assert!(system["content"].is_string());
+
+        let user = serde_json::to_value(&native[1]).unwrap();
+        let parts = user["content"].as_array().unwrap();
+        assert_eq!(parts[0]["type"], "text");
+        assert_eq!(parts[0]["text"], "Describe");
+        assert_eq!(parts[1]["type"], "image_url");
+        assert_eq!(
+            parts[1]["image_url"]["url"],
+            "data:image/png;base64,iVBORw0KGgo="
+        );
+        assert!(OpenAiProvider::new(None).supports_vision());
+    }
+
     #[test]
     fn native_message_omits_reasoning_content_when_none() {
         let msg = NativeMessage {
             role: "assistant".to_string(),
-            content: Some("hi".to_string()),
+            content: Some(MessageContent::Text("hi".to_string())),
             tool_call_id: None,
             tool_calls: None,
             reasoning_content: None,
@@ -819,7 +1215,7 @@ mod tests {
     fn native_message_includes_reasoning_content_when_some() {
         let msg = NativeMessage {
             role: "assistant".to_string(),
-            content: Some("hi".to_string()),
+            content: Some(MessageContent::Text("hi".to_string())),
             tool_call_id: None,
             tool_calls: None,
             reasoning_content: Some("thinking...".to_string()),
@@ -828,4 +1224,133 @@ mod tests {
         assert!(json.contains("reasoning_content"));
         assert!(json.contains("thinking..."));
     }
+
+    #[test]
+    fn capabilities_advertise_native_tool_calling() {
+        let provider = OpenAiProvider::new(Some("openai-test-credential"));
+        assert!(provider.supports_native_tools());
+
+        let payload = Provider::convert_tools(
+            &provider,
+            &[ToolSpec {
+                name: "shell".to_string(),
+                description: "Run a command".to_string(),
+                parameters: serde_json::json!({"type": "object"}),
+            }],
+        );
+        match payload {
+            ToolsPayload::OpenAI { tools } => {
+                assert_eq!(tools.len(), 1);
+                assert_eq!(tools[0]["type"], "function");
+                assert_eq!(tools[0]["function"]["name"], "shell");
+            }
+            other => panic!("expected OpenAI tools payload, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn temperature_is_omitted_for_reasoning_models() {
+        assert_eq!(
+            OpenAiProvider::request_temperature("gpt-4o", 0.7),
+            Some(0.7)
+        );
+        assert_eq!(
+            OpenAiProvider::request_temperature("gpt-4.1-mini", 0.2),
+            Some(0.2)
+        );
+        for model in ["o1", "o3-mini", "o4-mini", "gpt-5", "openai/gpt-5-mini"] {
+            assert_eq!(
+                OpenAiProvider::request_temperature(model, 0.7),
+                None,
+                "{model}"
+            );
+        }
+
+        let req = ChatRequest {
+            model: "o3-mini".to_string(),
+            messages: Vec::new(),
+            temperature: OpenAiProvider::request_temperature("o3-mini", 0.7),
+        };
+        assert!(!serde_json::to_string(&req).unwrap().contains("temperature"));
+    }
+
+    #[test]
+    fn tool_call_history_is_normalized() {
+        use crate::providers::ChatMessage;
+
+        let assistant = serde_json::json!({
+            "content": null,
+            "tool_calls": [{ "id": "tc_1", "name": "battery_status", "arguments": "" }]
+        });
+        let tool = serde_json::json!({
+            "tool_call_id": "tc_1",
+            "content": { "level": 80 }
+        });
+        let messages = vec![
+            ChatMessage::assistant(assistant.to_string()),
+            ChatMessage::tool(tool.to_string()),
+        ];
+
+        let native = OpenAiProvider::convert_messages(&messages);
+        let calls = native[0].tool_calls.as_ref().unwrap();
+        assert_eq!(calls[0].function.arguments, "{}");
+        assert_eq!(native[1].tool_call_id.as_deref(), Some("tc_1"));
+        assert!(matches!(
+            native[1].content.as_ref(),
+            Some(MessageContent::Text(text)) if text == r#"{"level":80}"#
+        ));
+
+        let parsed = OpenAiProvider::parse_native_response(NativeResponseMessage {
+            content: None,
+            reasoning_content: None,
+            tool_calls: Some(vec![NativeToolCall {
+                id: None,
+                kind: Some("function".to_string()),
+                function: NativeFunctionCall {
+                    name: "battery_status".to_string(),
+                    arguments: String::new(),
+                },
+            }]),
+        });
+        assert_eq!(parsed.tool_calls[0].arguments, "{}");
+        assert!(!parsed.tool_calls[0].id.is_empty());
+    }
+
+    #[test]
+    fn stream_request_sets_stream_flag() {
+        let request = StreamChatRequest {
+            model: "o3-mini".to_string(),
+            messages: OpenAiProvider::convert_messages(&[ChatMessage::user("hi")]),
+            temperature: OpenAiProvider::request_temperature("o3-mini", 0.7),
+            stream: true,
+            prompt_cache_key: None,
+        };
+        let json = serde_json::to_value(&request).unwrap();
+        assert_eq!(json["stream"], true);
+        assert!(json.get("temperature").is_none());
+        assert_eq!(json["messages"][0]["content"], "hi");
+    }
+
+    #[tokio::test]
+    async fn stream_fails_without_key() {
+        use futures_util::StreamExt;
+
+        let p = OpenAiProvider::new(None);
+        let messages = [ChatMessage::user("hello")];
+        let mut stream =
+            p.stream_chat_with_history(&messages, "gpt-4o", 0.7, StreamOptions::new(true));
+        let err = stream.next().await.unwrap().unwrap_err();
+        assert!(err.to_string().contains("API key not set"));
+    }
+
+    #[test]
+    fn tool_support_is_selected_per_model() {
+        let provider = OpenAiProvider::new(Some("key"));
+        assert!(provider.supports_native_tools_for_model("gpt-4o"));
+        assert!(provider.supports_native_tools_for_model("o3-mini"));
+        assert!(!provider.supports_native_tools_for_model("o1-mini"));
+        assert!(!provider.supports_native_tools_for_model("chatgpt-4o-latest"));
+        assert!(!provider.supports_native_tools_for_model("gpt-4o-search-preview"));
+        assert!(!provider.supports_native_tools_for_model("gpt-3.5-turbo-instruct"));
+    }
 }
@@ -1018,6 +1018,12 @@ data: [DONE]
             secrets_encrypt: false,
             auth_profile_override: None,
             reasoning_enabled: None,
+            gemini_safety_settings: Vec::new(),
+            azure_openai: crate::config::AzureOpenAiConfig::default(),
+            openrouter: crate::config::OpenRouterConfig::default(),
+            local_llm: crate::config::LocalLlmConfig::default(),
+            workspace_dir: None,
+            response_cache: None,
         };
         let provider =
             OpenAiCodexProvider::new(&options, None).expect("provider should initialize");
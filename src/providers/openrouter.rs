@@ -1,15 +1,72 @@
 use crate::multimodal;
+use crate::providers::sse;
+use crate::providers::structured::{JsonSchemaFormat, ResponseFormat};
 use crate::providers::traits::{
-    ChatMessage, ChatRequest as ProviderChatRequest, ChatResponse as ProviderChatResponse,
-    Provider, ProviderCapabilities, TokenUsage, ToolCall as ProviderToolCall,
+    parse_openai_embeddings, parse_openai_model_ids, ChatMessage,
+    ChatRequest as ProviderChatRequest, ChatResponse as ProviderChatResponse, Provider,
+    ProviderCapabilities, StreamChunk, StreamOptions, StreamResult, TokenUsage,
+    ToolCall as ProviderToolCall,
 };
 use crate::tools::ToolSpec;
 use async_trait::async_trait;
+use futures_util::stream;
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
 
 pub struct OpenRouterProvider {
     credential: Option<String>,
+    routing: Option<ProviderPreferences>,
+}
+
+/// OpenRouter's `provider` request object, built from `[openrouter]`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+struct ProviderPreferences {
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    order: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    allow_fallbacks: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    max_price: Option<MaxPrice>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    data_collection: Option<&'static str>,
+    /// Only route to providers that honour every request parameter; set
+    /// for schema-constrained requests.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    require_parameters: Option<bool>,
+}
+
+/// Price caps in USD per million tokens.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+struct MaxPrice {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    prompt: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    completion: Option<f64>,
+}
+
+impl ProviderPreferences {
+    /// `None` when the config leaves routing entirely to OpenRouter.
+    fn from_config(config: &crate::config::OpenRouterConfig) -> Option<Self> {
+        let max_price = (config.max_prompt_price.is_some()
+            || config.max_completion_price.is_some())
+        .then(|| MaxPrice {
+            prompt: config.max_prompt_price,
+            completion: config.max_completion_price,
+        });
+        let preferences = Self {
+            order: config
+                .order
+                .iter()
+                .map(|slug| slug.trim().to_string())
+                .filter(|slug| !slug.is_empty())
+                .collect(),
+            allow_fallbacks: (!config.allow_fallbacks).then_some(false),
+            max_price,
+            data_collection: config.deny_data_collection.then_some("deny"),
+            require_parameters: None,
+        };
+        (preferences != Self::default()).then_some(preferences)
+    }
 }
 
 #[derive(Debug, Serialize)]
@@ -17,6 +74,18 @@ struct ChatRequest {
     model: String,
     messages: Vec<Message>,
     temperature: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    provider: Option<ProviderPreferences>,
+}
+
+#[derive(Debug, Serialize)]
+struct StreamChatRequest {
+    model: String,
+    messages: Vec<Message>,
+    temperature: f64,
+    stream: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    provider: Option<ProviderPreferences>,
 }
 
 #[derive(Debug, Serialize)]
@@ -35,10 +104,28 @@ enum MessageContent {
 #[derive(Debug, Serialize)]
 #[serde(tag = "type", rename_all = "snake_case")]
 enum MessagePart {
-    Text { text: String },
-    ImageUrl { image_url: ImageUrlPart },
+    Text {
+        text: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        cache_control: Option<CacheControl>,
+    },
+    ImageUrl {
+        image_url: ImageUrlPart,
+    },
 }
 
+/// Prompt-cache breakpoint, forwarded by OpenRouter to upstreams that need
+/// explicit markers (Anthropic, Gemini).
+#[derive(Debug, Serialize)]
+struct CacheControl {
+    #[serde(rename = "type")]
+    kind: &'static str,
+}
+
+/// System prompts shorter than this (~1024 tokens) are below the upstream
+/// minimum and are not marked.
+const MIN_CACHEABLE_SYSTEM_CHARS: usize = 3072;
+
 #[derive(Debug, Serialize)]
 struct ImageUrlPart {
     url: String,
@@ -68,6 +155,10 @@ struct NativeChatRequest {
     tools: Option<Vec<NativeToolSpec>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     tool_choice: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    provider: Option<ProviderPreferences>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    response_format: Option<ResponseFormat>,
 }
 
 #[derive(Debug, Serialize)]
@@ -149,9 +240,17 @@ impl OpenRouterProvider {
     pub fn new(credential: Option<&str>) -> Self {
         Self {
             credential: credential.map(ToString::to_string),
+            routing: None,
         }
     }
 
+    /// Apply `[openrouter]` provider routing preferences to every chat
+    /// request.
+    pub fn with_routing(mut self, config: &crate::config::OpenRouterConfig) -> Self {
+        self.routing = ProviderPreferences::from_config(config);
+        self
+    }
+
     fn convert_tools(tools: Option<&[ToolSpec]>) -> Option<Vec<NativeToolSpec>> {
         let items = tools?;
         if items.is_empty() {
@@ -262,6 +361,7 @@ impl OpenRouterProvider {
         if !trimmed_text.is_empty() {
             parts.push(MessagePart::Text {
                 text: trimmed_text.to_string(),
+                cache_control: None,
             });
         }
 
@@ -274,6 +374,42 @@ impl OpenRouterProvider {
         MessageContent::Parts(parts)
     }
 
+    /// Whether OpenRouter honours `cache_control` breakpoints for `model`.
+    /// OpenAI, DeepSeek and others cache automatically.
+    fn supports_cache_control(model: &str) -> bool {
+        model.starts_with("anthropic/") || model.starts_with("google/gemini")
+    }
+
+    /// Mark a long system prompt as a cache breakpoint. Upstreams cache the
+    /// tool schemas together with it, so the static prefix is reused.
+    fn mark_cacheable(model: &str, role: &str, content: &mut MessageContent) {
+        if role != "system" || !Self::supports_cache_control(model) {
+            return;
+        }
+        if let MessageContent::Text(text) = content {
+            if text.len() >= MIN_CACHEABLE_SYSTEM_CHARS {
+                *content = MessageContent::Parts(vec![MessagePart::Text {
+                    text: std::mem::take(text),
+                    cache_control: Some(CacheControl { kind: "ephemeral" }),
+                }]);
+            }
+        }
+    }
+
+    fn message_content(model: &str, role: &str, content: &str) -> MessageContent {
+        let mut content = Self::to_message_content(role, content);
+        Self::mark_cacheable(model, role, &mut content);
+        content
+    }
+
+    fn mark_cacheable_messages(model: &str, messages: &mut [NativeMessage]) {
+        for message in messages {
+            if let Some(content) = message.content.as_mut() {
+                Self::mark_cacheable(model, &message.role, content);
+            }
+        }
+    }
+
     fn parse_native_response(message: NativeResponseMessage) -> ProviderChatResponse {
         let reasoning_content = message.reasoning_content.clone();
         let tool_calls = message
@@ -298,6 +434,43 @@ impl OpenRouterProvider {
     fn http_client(&self) -> Client {
         crate::config::build_runtime_proxy_client_with_timeouts("provider.openrouter", 120, 10)
     }
+    async fn send_native(
+        &self,
+        credential: &str,
+        request: &NativeChatRequest,
+    ) -> anyhow::Result<ProviderChatResponse> {
+        let response = self
+            .http_client()
+            .post("https://openrouter.ai/api/v1/chat/completions")
+            .header("Authorization", format!("Bearer {credential}"))
+            .header(
+                "HTTP-Referer",
+                "https://github.com/theonlyhennygod/zeroclaw",
+            )
+            .header("X-Title", "ZeroClaw")
+            .json(request)
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            return Err(super::api_error("OpenRouter", response).await);
+        }
+
+        let native_response: NativeChatResponse = response.json().await?;
+        let usage = native_response.usage.map(|u| TokenUsage {
+            input_tokens: u.prompt_tokens,
+            output_tokens: u.completion_tokens,
+        });
+        let message = native_response
+            .choices
+            .into_iter()
+            .next()
+            .map(|c| c.message)
+            .ok_or_else(|| anyhow::anyhow!("No response from OpenRouter"))?;
+        let mut result = Self::parse_native_response(message);
+        result.usage = usage;
+        Ok(result)
+    }
 }
 
 #[async_trait]
@@ -309,6 +482,109 @@ impl Provider for OpenRouterProvider {
         }
     }
 
+    fn supports_streaming(&self) -> bool {
+        true
+    }
+
+    fn stream_chat_with_system(
+        &self,
+        system_prompt: Option<&str>,
+        message: &str,
+        model: &str,
+        temperature: f64,
+        options: StreamOptions,
+    ) -> stream::BoxStream<'static, StreamResult<StreamChunk>> {
+        let mut messages = Vec::new();
+        if let Some(sys) = system_prompt {
+            messages.push(ChatMessage::system(sys));
+        }
+        messages.push(ChatMessage::user(message));
+        self.stream_chat_with_history(&messages, model, temperature, options)
+    }
+
+    fn stream_chat_with_history(
+        &self,
+        messages: &[ChatMessage],
+        model: &str,
+        temperature: f64,
+        options: StreamOptions,
+    ) -> stream::BoxStream<'static, StreamResult<StreamChunk>> {
+        let Some(credential) = self.credential.as_ref() else {
+            return sse::error_stream("OpenRouter API key not set");
+        };
+
+        let request = StreamChatRequest {
+            model: model.to_string(),
+            messages: messages
+                .iter()
+                .map(|m| Message {
+                    role: m.role.clone(),
+                    content: Self::message_content(model, &m.role, &m.content),
+                })
+                .collect(),
+            temperature,
+            stream: true,
+            provider: self.routing.clone(),
+        };
+        let req = self
+            .http_client()
+            .post("https://openrouter.ai/api/v1/chat/completions")
+            .header("Authorization", format!("Bearer {credential}"))
+            .header(
+                "HTTP-Referer",
+                "https://github.com/theonlyhennygod/zeroclaw",
+            )
+            .header("X-Title", "ZeroClaw")
+            .json(&request);
+        sse::stream_request(req, options.count_tokens, sse::chat_completions_delta)
+    }
+
+    fn supports_embeddings(&self) -> bool {
+        true
+    }
+
+    async fn embed(&self, texts: &[&str], model: &str) -> anyhow::Result<Vec<Vec<f32>>> {
+        if texts.is_empty() {
+            return Ok(Vec::new());
+        }
+        let credential = self.credential.as_ref().ok_or_else(|| {
+            anyhow::anyhow!(
+                "OpenRouter API key not set. Run `zeroclaw onboard` or set OPENROUTER_API_KEY env var."
+            )
+        })?;
+
+        let response = self
+            .http_client()
+            .post("https://openrouter.ai/api/v1/embeddings")
+            .header("Authorization", format!("Bearer {credential}"))
+            .json(&serde_json::json!({ "model": model, "input": texts }))
+            .send()
+            .await?;
+        if !response.status().is_success() {
+            return Err(super::api_error("OpenRouter", response).await);
+        }
+
+        let body: serde_json::Value = response.json().await?;
+        parse_openai_embeddings(&body)
+    }
+
+    /// The catalog is public; the key is sent when configured.
+    async fn list_models(&self) -> anyhow::Result<Vec<String>> {
+        let mut request = self
+            .http_client()
+            .get("https://openrouter.ai/api/v1/models");
+        if let Some(credential) = self.credential.as_ref() {
+            request = request.header("Authorization", format!("Bearer {credential}"));
+        }
+        let response = request.send().await?;
+        if !response.status().is_success() {
+            return Err(super::api_error("OpenRouter", response).await);
+        }
+
+        let body: serde_json::Value = response.json().await?;
+        Ok(parse_openai_model_ids(&body))
+    }
+
     async fn warmup(&self) -> anyhow::Result<()> {
         // Hit a lightweight endpoint to establish TLS + HTTP/2 connection pool.
         // This prevents the first real chat request from timing out on cold start.
@@ -338,7 +614,7 @@ impl Provider for OpenRouterProvider {
         if let Some(sys) = system_prompt {
             messages.push(Message {
                 role: "system".to_string(),
-                content: MessageContent::Text(sys.to_string()),
+                content: Self::message_content(model, "system", sys),
             });
         }
 
@@ -351,6 +627,7 @@ impl Provider for OpenRouterProvider {
             model: model.to_string(),
             messages,
             temperature,
+            provider: self.routing.clone(),
         };
 
         let response = self
@@ -393,7 +670,7 @@ impl Provider for OpenRouterProvider {
             .iter()
             .map(|m| Message {
                 role: m.role.clone(),
-                content: Self::to_message_content(&m.role, &m.content),
+                content: Self::message_content(model, &m.role, &m.content),
             })
             .collect();
 
@@ -401,6 +678,7 @@ impl Provider for OpenRouterProvider {
             model: model.to_string(),
             messages: api_messages,
             temperature,
+            provider: self.routing.clone(),
         };
 
         let response = self
@@ -443,45 +721,19 @@ impl Provider for OpenRouterProvider {
         })?;
 
         let tools = Self::convert_tools(request.tools);
+        let mut messages = Self::convert_messages(request.messages);
+        Self::mark_cacheable_messages(model, &mut messages);
         let native_request = NativeChatRequest {
             model: model.to_string(),
-            messages: Self::convert_messages(request.messages),
+            messages,
             temperature,
             tool_choice: tools.as_ref().map(|_| "auto".to_string()),
             tools,
+            provider: self.routing.clone(),
+            response_format: None,
         };
 
-        let response = self
-            .http_client()
-            .post("https://openrouter.ai/api/v1/chat/completions")
-            .header("Authorization", format!("Bearer {credential}"))
-            .header(
-                "HTTP-Referer",
-                "https://github.com/theonlyhennygod/zeroclaw",
-            )
-            .header("X-Title", "ZeroClaw")
-            .json(&native_request)
-            .send()
-            .await?;
-
-        if !response.status().is_success() {
-            return Err(super::api_error("OpenRouter", response).await);
-        }
-
-        let native_response: NativeChatResponse = response.json().await?;
-        let usage = native_response.usage.map(|u| TokenUsage {
-            input_tokens: u.prompt_tokens,
-            output_tokens: u.completion_tokens,
-        });
-        let message = native_response
-            .choices
-            .into_iter()
-            .next()
-            .map(|c| c.message)
-            .ok_or_else(|| anyhow::anyhow!("No response from OpenRouter"))?;
-        let mut result = Self::parse_native_response(message);
-        result.usage = usage;
-        Ok(result)
+        self.send_native(credential, &native_request).await
     }
 
     fn supports_native_tools(&self) -> bool {
@@ -535,7 +787,8 @@ impl Provider for OpenRouterProvider {
 
         // Convert ChatMessage to NativeMessage, preserving structured assistant/tool entries
         // when history contains native tool-call metadata.
-        let native_messages = Self::convert_messages(messages);
+        let mut native_messages = Self::convert_messages(messages);
+        Self::mark_cacheable_messages(model, &mut native_messages);
 
         let native_request = NativeChatRequest {
             model: model.to_string(),
@@ -543,39 +796,50 @@ impl Provider for OpenRouterProvider {
             temperature,
             tool_choice: native_tools.as_ref().map(|_| "auto".to_string()),
             tools: native_tools,
+            provider: self.routing.clone(),
+            response_format: None,
         };
 
-        let response = self
-            .http_client()
-            .post("https://openrouter.ai/api/v1/chat/completions")
-            .header("Authorization", format!("Bearer {credential}"))
-            .header(
-                "HTTP-Referer",
-                "https://github.com/theonlyhennygod/zeroclaw",
+        self.send_native(credential, &native_request).await
+    }
+
+    /// OpenRouter forwards `response_format` to models that support it;
+    /// `require_parameters` keeps the request off upstreams that would
+    /// silently drop it.
+    fn supports_structured_output(&self) -> bool {
+        true
+    }
+
+    async fn chat_with_schema(
+        &self,
+        messages: &[ChatMessage],
+        format: &JsonSchemaFormat,
+        model: &str,
+        temperature: f64,
+    ) -> anyhow::Result<String> {
+        let credential = self.credential.as_ref().ok_or_else(|| {
+            anyhow::anyhow!(
+                "OpenRouter API key not set. Run `zeroclaw onboard` or set OPENROUTER_API_KEY env var."
             )
-            .header("X-Title", "ZeroClaw")
-            .json(&native_request)
-            .send()
-            .await?;
+        })?;
 
-        if !response.status().is_success() {
-            return Err(super::api_error("OpenRouter", response).await);
-        }
+        let mut native_messages = Self::convert_messages(messages);
+        Self::mark_cacheable_messages(model, &mut native_messages);
+        let mut routing = self.routing.clone().unwrap_or_default();
+        routing.require_parameters = Some(true);
 
-        let native_response: NativeChatResponse = response.json().await?;
-        let usage = native_response.usage.map(|u| TokenUsage {
-            input_tokens: u.prompt_tokens,
-            output_tokens: u.completion_tokens,
-        });
-        let message = native_response
-            .choices
-            .into_iter()
-            .next()
-            .map(|c| c.message)
-            .ok_or_else(|| anyhow::anyhow!("No response from OpenRouter"))?;
-        let mut result = Self::parse_native_response(message);
-        result.usage = usage;
-        Ok(result)
+        let native_request = NativeChatRequest {
+            model: model.to_string(),
+            messages: native_messages,
+            temperature,
+            tools: None,
+            tool_choice: None,
+            provider: Some(routing),
+            response_format: Some(ResponseFormat::json_schema(format)),
+        };
+
+        let response = self.send_native(credential, &native_request).await?;
+        Ok(response.text.unwrap_or_default())
     }
 }
 
@@ -662,6 +926,7 @@ mod tests {
                 },
             ],
             temperature: 0.5,
+            provider: None,
         };
 
         let json = serde_json::to_string(&request).unwrap();
@@ -672,6 +937,63 @@ mod tests {
         assert!(json.contains("\"temperature\":0.5"));
     }
 
+    #[test]
+    fn long_system_prompt_is_cacheable_for_anthropic_models() {
+        let prompt = "tool catalog ".repeat(400);
+        let messages = [ChatMessage::system(&prompt), ChatMessage::user("hi")];
+
+        let mut anthropic = OpenRouterProvider::convert_messages(&messages);
+        OpenRouterProvider::mark_cacheable_messages("anthropic/claude-sonnet-4", &mut anthropic);
+        let json = serde_json::to_value(&anthropic).unwrap();
+        assert_eq!(json[0]["content"][0]["cache_control"]["type"], "ephemeral");
+        assert_eq!(json[0]["content"][0]["text"], prompt.as_str());
+        assert_eq!(json[1]["content"], "hi");
+
+        let mut openai = OpenRouterProvider::convert_messages(&messages);
+        OpenRouterProvider::mark_cacheable_messages("openai/gpt-4o", &mut openai);
+        let json = serde_json::to_value(&openai).unwrap();
+        assert_eq!(json[0]["content"], prompt.as_str());
+
+        let short =
+            OpenRouterProvider::message_content("anthropic/claude-sonnet-4", "system", "hi");
+        assert!(matches!(short, MessageContent::Text(_)));
+    }
+
+    #[test]
+    fn default_routing_config_sends_no_provider_object() {
+        let provider = OpenRouterProvider::new(Some("key"))
+            .with_routing(&crate::config::OpenRouterConfig::default());
+        assert!(provider.routing.is_none());
+    }
+
+    #[test]
+    fn routing_preferences_serialize_to_provider_object() {
+        let config = crate::config::OpenRouterConfig {
+            order: vec!["deepinfra".into(), " together ".into()],
+            allow_fallbacks: false,
+            max_prompt_price: Some(0.5),
+            max_completion_price: None,
+            deny_data_collection: true,
+        };
+        let request = ChatRequest {
+            model: "meta-llama/llama-3.3-70b-instruct".into(),
+            messages: Vec::new(),
+            temperature: 0.2,
+            provider: ProviderPreferences::from_config(&config),
+        };
+
+        let json = serde_json::to_value(&request).unwrap();
+        assert_eq!(
+            json["provider"],
+            serde_json::json!({
+                "order": ["deepinfra", "together"],
+                "allow_fallbacks": false,
+                "max_price": { "prompt": 0.5 },
+                "data_collection": "deny",
+            })
+        );
+    }
+
     #[test]
     fn chat_request_serializes_history_messages() {
         let messages = [
@@ -695,6 +1017,7 @@ mod tests {
                 })
                 .collect(),
             temperature: 0.0,
+            provider: None,
         };
 
         let json = serde_json::to_string(&request).unwrap();
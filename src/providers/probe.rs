@@ -0,0 +1,177 @@
+//! Background reachability and latency probes for the provider chain.
+//!
+//! The daemon periodically times a `warmup()` call against the primary and
+//! every fallback provider and records the result in the health registry.
+//! `/health`, `/api/health` and the Android `getStatus` bridge report it, and
+//! [`ReliableProvider`](super::reliable::ReliableProvider) tries providers
+//! last while their latest probe found them unreachable.
+
+use super::reliable::is_unreachable;
+use super::{create_provider_chain, sanitize_api_error, Provider, ProviderRuntimeOptions};
+use crate::config::Config;
+use std::time::{Duration, Instant};
+
+/// Upper bound on a single probe; slower endpoints count as unreachable.
+const PROBE_TIMEOUT_SECS: u64 = 10;
+
+/// Shortest interval between probe rounds.
+const MIN_PROBE_INTERVAL_SECS: u64 = 30;
+
+const PROBE_COMPONENT: &str = "provider_probe";
+
+/// Time one `warmup()` call and record the outcome under `name`.
+///
+/// Providers without an explicit warmup (no credentials, local backends)
+/// return immediately and are reported as `ok`.
+pub async fn probe_provider(name: &str, provider: &dyn Provider, timeout: Duration) {
+    let started = Instant::now();
+    match tokio::time::timeout(timeout, provider.warmup()).await {
+        Ok(Ok(())) => crate::health::mark_provider_ok(name, started.elapsed()),
+        Ok(Err(e)) => {
+            let unreachable = is_unreachable(&e);
+            tracing::debug!(provider = name, unreachable, "Provider probe failed: {e}");
+            crate::health::mark_provider_error(
+                name,
+                sanitize_api_error(&format!("{e:#}")),
+                unreachable,
+            );
+        }
+        Err(_) => {
+            tracing::debug!(provider = name, "Provider probe timed out");
+            crate::health::mark_provider_error(
+                name,
+                format!("no response within {}s", timeout.as_secs()),
+                true,
+            );
+        }
+    }
+}
+
+/// Probe every provider in the chain concurrently.
+pub async fn probe_all(providers: &[(String, Box<dyn Provider>)], timeout: Duration) {
+    futures_util::future::join_all(
+        providers
+            .iter()
+            .map(|(name, provider)| probe_provider(name, provider.as_ref(), timeout)),
+    )
+    .await;
+}
+
+/// Probe the configured provider chain every
+/// `[reliability].provider_probe_interval_secs` seconds.
+pub async fn run(config: Config) -> anyhow::Result<()> {
+    let providers = create_provider_chain(
+        config.default_provider.as_deref().unwrap_or("openrouter"),
+        config.api_key.as_deref(),
+        config.api_url.as_deref(),
+        &config.reliability,
+        &ProviderRuntimeOptions {
+            auth_profile_override: None,
+            provider_api_url: config.api_url.clone(),
+            zeroclaw_dir: config.config_path.parent().map(std::path::PathBuf::from),
+            secrets_encrypt: config.secrets.encrypt,
+            reasoning_enabled: config.runtime.reasoning_enabled,
+            gemini_safety_settings: config.gemini.safety_settings.clone(),
+            azure_openai: config.azure_openai.clone(),
+            openrouter: config.openrouter.clone(),
+            local_llm: config.local_llm.clone(),
+            workspace_dir: Some(config.workspace_dir.clone()),
+            response_cache: None,
+        },
+    )?;
+
+    let interval_secs = config
+        .reliability
+        .provider_probe_interval_secs
+        .max(MIN_PROBE_INTERVAL_SECS);
+    let mut interval = tokio::time::interval(Duration::from_secs(interval_secs));
+    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+    let timeout = Duration::from_secs(PROBE_TIMEOUT_SECS);
+
+    loop {
+        interval.tick().await;
+        crate::health::mark_component_ok(PROBE_COMPONENT);
+        probe_all(&providers, timeout).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+
+    enum Warmup {
+        Ok,
+        Refused,
+        Rejected,
+        Hang,
+    }
+
+    struct MockProvider(Warmup);
+
+    #[async_trait]
+    impl Provider for MockProvider {
+        async fn warmup(&self) -> anyhow::Result<()> {
+            match self.0 {
+                Warmup::Ok => Ok(()),
+                Warmup::Refused => anyhow::bail!("error sending request: connection refused"),
+                Warmup::Rejected => anyhow::bail!("401 Unauthorized: invalid api key"),
+                Warmup::Hang => {
+                    tokio::time::sleep(Duration::from_secs(60)).await;
+                    Ok(())
+                }
+            }
+        }
+
+        async fn chat_with_system(
+            &self,
+            _system_prompt: Option<&str>,
+            _message: &str,
+            _model: &str,
+            _temperature: f64,
+        ) -> anyhow::Result<String> {
+            Ok("ok".into())
+        }
+    }
+
+    fn unique(prefix: &str) -> String {
+        format!("{prefix}-{}", uuid::Uuid::new_v4())
+    }
+
+    #[tokio::test]
+    async fn probes_record_status_per_provider() {
+        let providers: Vec<(String, Box<dyn Provider>)> = vec![
+            (unique("ok"), Box::new(MockProvider(Warmup::Ok))),
+            (unique("refused"), Box::new(MockProvider(Warmup::Refused))),
+            (unique("rejected"), Box::new(MockProvider(Warmup::Rejected))),
+        ];
+        probe_all(&providers, Duration::from_secs(5)).await;
+
+        let ok = crate::health::provider_health(&providers[0].0).unwrap();
+        assert_eq!(ok.status, "ok");
+        assert!(ok.latency_ms.is_some());
+
+        let refused = crate::health::provider_health(&providers[1].0).unwrap();
+        assert_eq!(refused.status, "unreachable");
+        assert!(crate::health::provider_unreachable(&providers[1].0));
+
+        let rejected = crate::health::provider_health(&providers[2].0).unwrap();
+        assert_eq!(rejected.status, "error");
+        assert_eq!(rejected.consecutive_failures, 1);
+    }
+
+    #[tokio::test]
+    async fn slow_probe_counts_as_unreachable() {
+        let name = unique("slow");
+        probe_provider(
+            &name,
+            &MockProvider(Warmup::Hang),
+            Duration::from_millis(20),
+        )
+        .await;
+
+        let entry = crate::health::provider_health(&name).unwrap();
+        assert_eq!(entry.status, "unreachable");
+        assert!(entry.last_error.unwrap().contains("no response"));
+    }
+}
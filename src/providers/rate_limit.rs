@@ -0,0 +1,423 @@
+//! Client-side request pacing per provider.
+//!
+//! Every provider in a chain sits behind a [`RateLimitedProvider`], which
+//! takes a slot from a process-wide queue keyed by provider name before each
+//! request, so the scheduler firing many jobs at once, channels and the
+//! gateway all draw from one budget. `[reliability.provider_rate_limits]`
+//! turns the queue into a token bucket. Independently of that, a 429 whose
+//! `Retry-After` / `x-ratelimit-reset-*` headers say when the limit resets
+//! (see [`super::rate_limit_retry_after`]) pauses the provider's queue until
+//! then, and the requests that piled up meanwhile are released one at a time
+//! instead of all at once.
+
+use super::reliable::{is_non_retryable_rate_limit, is_rate_limited, parse_retry_after_ms};
+use super::structured::JsonSchemaFormat;
+use super::traits::{
+    ChatMessage, ChatRequest, ChatResponse, ProviderCapabilities, StreamChunk, StreamOptions,
+    StreamResult, ToolsPayload,
+};
+use super::Provider;
+use crate::config::ProviderRateLimitConfig;
+use crate::tools::ToolSpec;
+use async_trait::async_trait;
+use futures_util::{stream, StreamExt};
+use parking_lot::Mutex;
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::{Arc, OnceLock};
+use std::time::{Duration, Instant};
+
+/// Gap between requests released together when a rate-limit pause ends.
+const RELEASE_SPACING: Duration = Duration::from_millis(250);
+
+/// Pause after a 429 that carried no reset hint.
+const DEFAULT_PAUSE: Duration = Duration::from_secs(1);
+
+/// Longest pause taken from a reset hint, matching the retry cap in
+/// [`ReliableProvider`](super::reliable::ReliableProvider).
+const MAX_PAUSE: Duration = Duration::from_secs(30);
+
+/// Sustained rate and burst size of a provider's token bucket.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RateLimit {
+    per_sec: f64,
+    burst: f64,
+}
+
+impl RateLimit {
+    /// `None` when `requests_per_minute` is 0.
+    pub fn from_config(config: &ProviderRateLimitConfig) -> Option<Self> {
+        if config.requests_per_minute == 0 {
+            return None;
+        }
+        let burst = config
+            .burst
+            .unwrap_or_else(|| config.requests_per_minute.div_ceil(6))
+            .max(1);
+        Some(Self {
+            per_sec: f64::from(config.requests_per_minute) / 60.0,
+            burst: f64::from(burst),
+        })
+    }
+}
+
+#[derive(Debug)]
+struct Bucket {
+    /// May go negative: each queued request reserves its token up front.
+    tokens: f64,
+    refilled_at: Instant,
+    paused_until: Option<Instant>,
+    /// Earliest start for the next request released after a pause.
+    next_release: Instant,
+}
+
+impl Bucket {
+    fn new(limit: Option<RateLimit>, now: Instant) -> Self {
+        Self {
+            tokens: limit.map_or(0.0, |limit| limit.burst),
+            refilled_at: now,
+            paused_until: None,
+            next_release: now,
+        }
+    }
+
+    /// Reserve a slot and return when the request may start.
+    fn reserve(&mut self, limit: Option<RateLimit>, now: Instant) -> Instant {
+        let mut start = now;
+        if let Some(until) = self.paused_until {
+            if until > now {
+                start = until.max(self.next_release);
+                self.next_release = start + RELEASE_SPACING;
+            } else {
+                self.paused_until = None;
+            }
+        }
+
+        if let Some(limit) = limit {
+            let elapsed = now.saturating_duration_since(self.refilled_at);
+            self.tokens = (self.tokens + elapsed.as_secs_f64() * limit.per_sec).min(limit.burst);
+            self.refilled_at = now;
+            self.tokens -= 1.0;
+            if self.tokens < 0.0 {
+                let wait = Duration::from_secs_f64(-self.tokens / limit.per_sec);
+                start = start.max(now + wait);
+            }
+        }
+        start
+    }
+
+    fn pause(&mut self, duration: Duration, now: Instant) {
+        let until = now + duration.min(MAX_PAUSE);
+        if self.paused_until.is_none_or(|current| current < until) {
+            self.paused_until = Some(until);
+            self.next_release = self.next_release.max(until);
+        }
+    }
+}
+
+static QUEUES: OnceLock<Mutex<HashMap<String, Bucket>>> = OnceLock::new();
+
+fn queues() -> &'static Mutex<HashMap<String, Bucket>> {
+    QUEUES.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// Wait for `provider`'s next request slot.
+pub async fn acquire(provider: &str, limit: Option<RateLimit>) {
+    let now = Instant::now();
+    let start = queues()
+        .lock()
+        .entry(provider.to_string())
+        .or_insert_with(|| Bucket::new(limit, now))
+        .reserve(limit, now);
+    let wait = start.saturating_duration_since(now);
+    if !wait.is_zero() {
+        tracing::debug!(
+            provider,
+            wait_ms = u64::try_from(wait.as_millis()).unwrap_or(u64::MAX),
+            "Queued provider request"
+        );
+        tokio::time::sleep(wait).await;
+    }
+}
+
+/// Pause `provider`'s queue when `err` is a retryable 429, for as long as
+/// its reset hint says.
+pub fn report_error(provider: &str, err: &anyhow::Error) {
+    if !is_rate_limited(err) || is_non_retryable_rate_limit(err) {
+        return;
+    }
+    let pause = parse_retry_after_ms(err).map_or(DEFAULT_PAUSE, Duration::from_millis);
+    tracing::info!(
+        provider,
+        pause_ms = u64::try_from(pause.min(MAX_PAUSE).as_millis()).unwrap_or(u64::MAX),
+        "Rate limited; pausing provider queue"
+    );
+    let now = Instant::now();
+    queues()
+        .lock()
+        .entry(provider.to_string())
+        .or_insert_with(|| Bucket::new(None, now))
+        .pause(pause, now);
+}
+
+/// Provider wrapper that paces requests through the shared queue.
+pub struct RateLimitedProvider {
+    name: String,
+    inner: Arc<dyn Provider>,
+    limit: Option<RateLimit>,
+}
+
+impl RateLimitedProvider {
+    pub fn new(
+        name: &str,
+        inner: Box<dyn Provider>,
+        config: Option<&ProviderRateLimitConfig>,
+    ) -> Self {
+        Self {
+            name: name.to_string(),
+            inner: Arc::from(inner),
+            limit: config.and_then(RateLimit::from_config),
+        }
+    }
+
+    async fn paced<T>(&self, call: impl Future<Output = anyhow::Result<T>>) -> anyhow::Result<T> {
+        acquire(&self.name, self.limit).await;
+        let result = call.await;
+        if let Err(e) = &result {
+            report_error(&self.name, e);
+        }
+        result
+    }
+}
+
+#[async_trait]
+impl Provider for RateLimitedProvider {
+    fn capabilities(&self) -> ProviderCapabilities {
+        self.inner.capabilities()
+    }
+
+    fn convert_tools(&self, tools: &[ToolSpec]) -> ToolsPayload {
+        self.inner.convert_tools(tools)
+    }
+
+    async fn warmup(&self) -> anyhow::Result<()> {
+        self.inner.warmup().await
+    }
+
+    async fn list_models(&self) -> anyhow::Result<Vec<String>> {
+        self.inner.list_models().await
+    }
+
+    async fn chat_with_system(
+        &self,
+        system_prompt: Option<&str>,
+        message: &str,
+        model: &str,
+        temperature: f64,
+    ) -> anyhow::Result<String> {
+        self.paced(
+            self.inner
+                .chat_with_system(system_prompt, message, model, temperature),
+        )
+        .await
+    }
+
+    async fn chat_with_history(
+        &self,
+        messages: &[ChatMessage],
+        model: &str,
+        temperature: f64,
+    ) -> anyhow::Result<String> {
+        self.paced(self.inner.chat_with_history(messages, model, temperature))
+            .await
+    }
+
+    async fn chat(
+        &self,
+        request: ChatRequest<'_>,
+        model: &str,
+        temperature: f64,
+    ) -> anyhow::Result<ChatResponse> {
+        self.paced(self.inner.chat(request, model, temperature))
+            .await
+    }
+
+    fn supports_native_tools(&self) -> bool {
+        self.inner.supports_native_tools()
+    }
+
+    fn supports_native_tools_for_model(&self, model: &str) -> bool {
+        self.inner.supports_native_tools_for_model(model)
+    }
+
+    fn supports_vision(&self) -> bool {
+        self.inner.supports_vision()
+    }
+
+    async fn chat_with_tools(
+        &self,
+        messages: &[ChatMessage],
+        tools: &[serde_json::Value],
+        model: &str,
+        temperature: f64,
+    ) -> anyhow::Result<ChatResponse> {
+        self.paced(
+            self.inner
+                .chat_with_tools(messages, tools, model, temperature),
+        )
+        .await
+    }
+
+    fn supports_streaming(&self) -> bool {
+        self.inner.supports_streaming()
+    }
+
+    fn supports_embeddings(&self) -> bool {
+        self.inner.supports_embeddings()
+    }
+
+    async fn embed(&self, texts: &[&str], model: &str) -> anyhow::Result<Vec<Vec<f32>>> {
+        self.paced(self.inner.embed(texts, model)).await
+    }
+
+    fn supports_structured_output(&self) -> bool {
+        self.inner.supports_structured_output()
+    }
+
+    async fn chat_with_schema(
+        &self,
+        messages: &[ChatMessage],
+        format: &JsonSchemaFormat,
+        model: &str,
+        temperature: f64,
+    ) -> anyhow::Result<String> {
+        self.paced(
+            self.inner
+                .chat_with_schema(messages, format, model, temperature),
+        )
+        .await
+    }
+
+    fn stream_chat_with_system(
+        &self,
+        system_prompt: Option<&str>,
+        message: &str,
+        model: &str,
+        temperature: f64,
+        options: StreamOptions,
+    ) -> stream::BoxStream<'static, StreamResult<StreamChunk>> {
+        let mut messages = Vec::new();
+        if let Some(system_prompt) = system_prompt {
+            messages.push(ChatMessage::system(system_prompt));
+        }
+        messages.push(ChatMessage::user(message));
+        self.stream_chat_with_history(&messages, model, temperature, options)
+    }
+
+    /// Provider streams send their request as soon as they are created, so
+    /// the inner stream is only opened once the slot is granted.
+    fn stream_chat_with_history(
+        &self,
+        messages: &[ChatMessage],
+        model: &str,
+        temperature: f64,
+        options: StreamOptions,
+    ) -> stream::BoxStream<'static, StreamResult<StreamChunk>> {
+        let name = self.name.clone();
+        let limit = self.limit;
+        let inner = Arc::clone(&self.inner);
+        let messages = messages.to_vec();
+        let model = model.to_string();
+        stream::once(async move {
+            acquire(&name, limit).await;
+            inner.stream_chat_with_history(&messages, &model, temperature, options)
+        })
+        .flatten()
+        .boxed()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn limit(requests_per_minute: u32, burst: Option<u32>) -> Option<RateLimit> {
+        RateLimit::from_config(&ProviderRateLimitConfig {
+            requests_per_minute,
+            burst,
+        })
+    }
+
+    #[test]
+    fn bucket_allows_burst_then_paces_at_configured_rate() {
+        let limit = limit(60, Some(2));
+        let now = Instant::now();
+        let mut bucket = Bucket::new(limit, now);
+
+        let starts: Vec<Duration> = (0..4)
+            .map(|_| bucket.reserve(limit, now).duration_since(now))
+            .collect();
+        assert_eq!(starts[0], Duration::ZERO);
+        assert_eq!(starts[1], Duration::ZERO);
+        assert_eq!(starts[2], Duration::from_secs(1));
+        assert_eq!(starts[3], Duration::from_secs(2));
+
+        // Idle time refills the bucket up to the burst size only.
+        let later = now + Duration::from_secs(60);
+        assert_eq!(bucket.reserve(limit, later), later);
+        assert_eq!(bucket.reserve(limit, later), later);
+        assert!(bucket.reserve(limit, later) > later);
+
+        assert_eq!(self::limit(0, None), None);
+        assert_eq!(
+            self::limit(30, None),
+            Some(RateLimit {
+                per_sec: 0.5,
+                burst: 5.0
+            })
+        );
+    }
+
+    #[test]
+    fn pause_releases_queued_requests_one_at_a_time() {
+        let now = Instant::now();
+        let mut bucket = Bucket::new(None, now);
+        assert_eq!(bucket.reserve(None, now), now);
+
+        bucket.pause(Duration::from_secs(5), now);
+        let reset = now + Duration::from_secs(5);
+        assert_eq!(bucket.reserve(None, now), reset);
+        assert_eq!(bucket.reserve(None, now), reset + RELEASE_SPACING);
+        assert_eq!(bucket.reserve(None, now), reset + RELEASE_SPACING * 2);
+
+        // A shorter pause never cuts an existing one short.
+        bucket.pause(Duration::from_secs(1), now);
+        assert_eq!(bucket.paused_until, Some(reset));
+
+        let after = reset + Duration::from_secs(1);
+        assert_eq!(bucket.reserve(None, after), after);
+        assert_eq!(bucket.paused_until, None);
+
+        bucket.pause(Duration::from_secs(3600), after);
+        assert_eq!(bucket.paused_until, Some(after + MAX_PAUSE));
+    }
+
+    #[test]
+    fn only_retryable_rate_limits_pause_the_queue() {
+        let name = format!("rate-limit-{}", uuid::Uuid::new_v4());
+        report_error(&name, &anyhow::anyhow!("500 Internal Server Error"));
+        report_error(
+            &name,
+            &anyhow::anyhow!("429 Too Many Requests: insufficient quota"),
+        );
+        assert!(queues().lock().get(&name).is_none());
+
+        report_error(
+            &name,
+            &anyhow::anyhow!("API error (429 Too Many Requests): slow down (retry-after: 2)"),
+        );
+        let paused_until = queues().lock()[&name].paused_until.unwrap();
+        let remaining = paused_until.saturating_duration_since(Instant::now());
+        assert!(remaining > Duration::from_secs(1) && remaining <= Duration::from_secs(2));
+    }
+}
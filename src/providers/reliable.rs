@@ -1,10 +1,15 @@
+use super::structured::JsonSchemaFormat;
 use super::traits::{
-    ChatMessage, ChatRequest, ChatResponse, StreamChunk, StreamOptions, StreamResult,
+    ChatMessage, ChatRequest, ChatResponse, ProviderCapabilityError, StreamChunk, StreamOptions,
+    StreamResult,
 };
 use super::Provider;
+use crate::config::ProviderRetryPolicyConfig;
+use crate::multimodal;
 use async_trait::async_trait;
 use futures_util::{stream, StreamExt};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::future::Future;
 use std::sync::atomic::{AtomicUsize, Ordering};
 use std::time::Duration;
 
@@ -87,8 +92,35 @@ fn is_context_window_exceeded(err: &anyhow::Error) -> bool {
     hints.iter().any(|hint| lower.contains(hint))
 }
 
+/// Check if the provider could not be reached at all (device offline, DNS
+/// failure, refused connection). Retrying the same endpoint only burns the
+/// backoff budget, so the chain moves straight to the next provider.
+pub(crate) fn is_unreachable(err: &anyhow::Error) -> bool {
+    if err
+        .chain()
+        .filter_map(|cause| cause.downcast_ref::<reqwest::Error>())
+        .any(reqwest::Error::is_connect)
+    {
+        return true;
+    }
+
+    let lower = format!("{err:#}").to_lowercase();
+    let hints = [
+        "dns error",
+        "failed to lookup address",
+        "name or service not known",
+        "no address associated with hostname",
+        "connection refused",
+        "network is unreachable",
+        "no route to host",
+        "host is unreachable",
+    ];
+
+    hints.iter().any(|hint| lower.contains(hint))
+}
+
 /// Check if an error is a rate-limit (429) error.
-fn is_rate_limited(err: &anyhow::Error) -> bool {
+pub(super) fn is_rate_limited(err: &anyhow::Error) -> bool {
     if let Some(reqwest_err) = err.downcast_ref::<reqwest::Error>() {
         if let Some(status) = reqwest_err.status() {
             return status.as_u16() == 429;
@@ -105,7 +137,7 @@ fn is_rate_limited(err: &anyhow::Error) -> bool {
 /// - plan does not include requested model
 /// - insufficient balance / package not active
 /// - known provider business codes (e.g. Z.AI: 1311, 1113)
-fn is_non_retryable_rate_limit(err: &anyhow::Error) -> bool {
+pub(super) fn is_non_retryable_rate_limit(err: &anyhow::Error) -> bool {
     if !is_rate_limited(err) {
         return false;
     }
@@ -147,7 +179,7 @@ fn is_non_retryable_rate_limit(err: &anyhow::Error) -> bool {
 
 /// Try to extract a Retry-After value (in milliseconds) from an error message.
 /// Looks for patterns like `Retry-After: 5` or `retry_after: 2.5` in the error string.
-fn parse_retry_after_ms(err: &anyhow::Error) -> Option<u64> {
+pub(super) fn parse_retry_after_ms(err: &anyhow::Error) -> Option<u64> {
     let msg = err.to_string();
     let lower = msg.to_lowercase();
 
@@ -178,8 +210,10 @@ fn parse_retry_after_ms(err: &anyhow::Error) -> Option<u64> {
     None
 }
 
-fn failure_reason(rate_limited: bool, non_retryable: bool) -> &'static str {
-    if rate_limited && non_retryable {
+fn failure_reason(unreachable: bool, rate_limited: bool, non_retryable: bool) -> &'static str {
+    if unreachable {
+        "unreachable"
+    } else if rate_limited && non_retryable {
         "rate_limited_non_retryable"
     } else if rate_limited {
         "rate_limited"
@@ -215,22 +249,51 @@ fn push_failure(
 // Three-level failover strategy: model chain → provider chain → retry loop.
 //   Outer loop:  iterate model fallback chain (original model first, then
 //                configured alternatives).
-//   Middle loop: iterate registered providers in priority order.
+//   Middle loop: iterate registered providers in priority order. Providers
+//                with a pinned model (`provider_models`) run that model, and
+//                each (provider, model) pair is attempted at most once.
 //   Inner loop:  retry the same (provider, model) pair with exponential
-//                backoff, rotating API keys on rate-limit errors.
+//                backoff, rotating API keys on rate-limit errors. Unreachable
+//                providers are skipped for the rest of the request.
 // Loop invariant: `failures` accumulates every failed attempt so the final
 // error message gives operators a complete diagnostic trail.
 
+/// Retry budget and backoff bounds applied to one provider.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct RetryPolicy {
+    max_retries: u32,
+    base_backoff_ms: u64,
+    max_backoff_ms: u64,
+}
+
+impl RetryPolicy {
+    /// Delay before retry `attempt` (0-based). The base doubles per attempt up
+    /// to `max_backoff_ms`, then a random part of the upper half is dropped
+    /// (equal jitter) so clients that failed together do not retry in
+    /// lockstep.
+    fn backoff_ms(&self, attempt: u32) -> u64 {
+        let ceiling = self
+            .base_backoff_ms
+            .saturating_mul(1 << attempt.min(20))
+            .min(self.max_backoff_ms.max(self.base_backoff_ms));
+        let half = ceiling / 2;
+        ceiling - half + rand::random::<u64>() % (half + 1)
+    }
+}
+
 /// Provider wrapper with retry, fallback, auth rotation, and model failover.
 pub struct ReliableProvider {
     providers: Vec<(String, Box<dyn Provider>)>,
-    max_retries: u32,
-    base_backoff_ms: u64,
+    retry_policy: RetryPolicy,
+    /// Per-provider retry overrides; unset fields fall back to `retry_policy`.
+    retry_overrides: HashMap<String, ProviderRetryPolicyConfig>,
     /// Extra API keys for rotation (index tracks round-robin position).
     api_keys: Vec<String>,
     key_index: AtomicUsize,
     /// Per-model fallback chains: model_name → [fallback_model_1, fallback_model_2, ...]
     model_fallbacks: HashMap<String, Vec<String>>,
+    /// Per-provider model overrides: provider_name → model to request.
+    provider_models: HashMap<String, String>,
 }
 
 impl ReliableProvider {
@@ -241,11 +304,46 @@ impl ReliableProvider {
     ) -> Self {
         Self {
             providers,
-            max_retries,
-            base_backoff_ms: base_backoff_ms.max(50),
+            retry_policy: RetryPolicy {
+                max_retries,
+                base_backoff_ms: base_backoff_ms.max(50),
+                max_backoff_ms: 10_000,
+            },
+            retry_overrides: HashMap::new(),
             api_keys: Vec::new(),
             key_index: AtomicUsize::new(0),
             model_fallbacks: HashMap::new(),
+            provider_models: HashMap::new(),
+        }
+    }
+
+    /// Cap the exponential retry delay.
+    pub fn with_max_backoff_ms(mut self, max_backoff_ms: u64) -> Self {
+        self.retry_policy.max_backoff_ms = max_backoff_ms;
+        self
+    }
+
+    /// Override the retry budget or backoff for specific providers, e.g. no
+    /// retries for a local model that either answers or is not running.
+    pub fn with_retry_policies(
+        mut self,
+        overrides: HashMap<String, ProviderRetryPolicyConfig>,
+    ) -> Self {
+        self.retry_overrides = overrides;
+        self
+    }
+
+    fn retry_policy(&self, provider_name: &str) -> RetryPolicy {
+        let base = self.retry_policy;
+        let Some(overrides) = self.retry_overrides.get(provider_name) else {
+            return base;
+        };
+        RetryPolicy {
+            max_retries: overrides.max_retries.unwrap_or(base.max_retries),
+            base_backoff_ms: overrides
+                .backoff_ms
+                .map_or(base.base_backoff_ms, |ms| ms.max(50)),
+            max_backoff_ms: overrides.max_backoff_ms.unwrap_or(base.max_backoff_ms),
         }
     }
 
@@ -261,75 +359,79 @@ impl ReliableProvider {
         self
     }
 
-    /// Build the list of models to try: [original, fallback1, fallback2, ...]
-    fn model_chain<'a>(&'a self, model: &'a str) -> Vec<&'a str> {
-        let mut chain = vec![model];
-        if let Some(fallbacks) = self.model_fallbacks.get(model) {
-            chain.extend(fallbacks.iter().map(|s| s.as_str()));
-        }
-        chain
-    }
-
-    /// Advance to the next API key and return it, or None if no extra keys configured.
-    fn rotate_key(&self) -> Option<&str> {
-        if self.api_keys.is_empty() {
-            return None;
-        }
-        let idx = self.key_index.fetch_add(1, Ordering::Relaxed) % self.api_keys.len();
-        Some(&self.api_keys[idx])
-    }
-
-    /// Compute backoff duration, respecting Retry-After if present.
-    fn compute_backoff(&self, base: u64, err: &anyhow::Error) -> u64 {
-        if let Some(retry_after) = parse_retry_after_ms(err) {
-            // Use Retry-After but cap at 30s to avoid indefinite waits
-            retry_after.min(30_000).max(base)
-        } else {
-            base
-        }
+    /// Pin the model requested from specific providers, e.g. a local model
+    /// for an `ollama` fallback behind a cloud primary.
+    pub fn with_provider_models(mut self, models: HashMap<String, String>) -> Self {
+        self.provider_models = models;
+        self
     }
-}
 
-#[async_trait]
-impl Provider for ReliableProvider {
-    async fn warmup(&self) -> anyhow::Result<()> {
-        for (name, provider) in &self.providers {
-            tracing::info!(provider = name, "Warming up provider connection pool");
-            if provider.warmup().await.is_err() {
-                tracing::warn!(provider = name, "Warmup failed (non-fatal)");
-            }
+    /// Model to request from `provider_name` while trying `model`.
+    fn provider_model<'a>(&'a self, provider_name: &str, model: &'a str) -> &'a str {
+        self.provider_models
+            .get(provider_name)
+            .map_or(model, String::as_str)
+    }
+
+    /// Error returned once every provider/model attempt has failed, out of
+    /// `candidates` providers able to take the request.
+    fn exhausted_error(
+        failures: &[String],
+        unreachable: usize,
+        candidates: usize,
+    ) -> anyhow::Error {
+        if unreachable == candidates {
+            return anyhow::anyhow!(
+                "No provider is reachable (network down or device offline). Attempts:\n{}",
+                failures.join("\n")
+            );
         }
-        Ok(())
+        anyhow::anyhow!(
+            "All providers/models failed. Attempts:\n{}",
+            failures.join("\n")
+        )
     }
 
-    async fn chat_with_system(
-        &self,
-        system_prompt: Option<&str>,
-        message: &str,
-        model: &str,
-        temperature: f64,
-    ) -> anyhow::Result<String> {
+    /// Run `call` through the failover chain: every model of the fallback
+    /// chain on every provider able to take the request, each retried per its
+    /// policy. Returns the first success, or an error listing every attempt.
+    async fn with_failover<'a, T, F, Fut>(
+        &'a self,
+        model: &'a str,
+        needs_vision: bool,
+        mut call: F,
+    ) -> anyhow::Result<T>
+    where
+        F: FnMut(&'a dyn Provider, &'a str) -> Fut,
+        Fut: Future<Output = anyhow::Result<T>>,
+    {
         let models = self.model_chain(model);
+        let candidates = self.providers_by_health(needs_vision).len();
         let mut failures = Vec::new();
+        let mut attempted = HashSet::new();
+        let mut unreachable = HashSet::new();
 
         // Outer: model fallback chain. Middle: provider priority. Inner: retries.
         // Each iteration: attempt one (provider, model) call. On success, return
         // immediately. On non-retryable error, break to next provider. On
         // retryable error, sleep with exponential backoff and retry.
-        for current_model in &models {
-            for (provider_name, provider) in &self.providers {
-                let mut backoff_ms = self.base_backoff_ms;
-
-                for attempt in 0..=self.max_retries {
-                    match provider
-                        .chat_with_system(system_prompt, message, current_model, temperature)
-                        .await
-                    {
+        for current_model in models {
+            for (provider_name, provider) in self.providers_by_health(needs_vision) {
+                let provider_model = self.provider_model(provider_name, current_model);
+                if unreachable.contains(provider_name.as_str())
+                    || !attempted.insert((provider_name.as_str(), provider_model))
+                {
+                    continue;
+                }
+                let policy = self.retry_policy(provider_name);
+
+                for attempt in 0..=policy.max_retries {
+                    match call(provider.as_ref(), provider_model).await {
                         Ok(resp) => {
-                            if attempt > 0 || *current_model != model {
+                            if attempt > 0 || provider_model != model {
                                 tracing::info!(
                                     provider = provider_name,
-                                    model = *current_model,
+                                    model = provider_model,
                                     attempt,
                                     original_model = model,
                                     "Provider recovered (failover/retry)"
@@ -338,18 +440,20 @@ impl Provider for ReliableProvider {
                             return Ok(resp);
                         }
                         Err(e) => {
+                            let provider_unreachable = is_unreachable(&e);
                             let non_retryable_rate_limit = is_non_retryable_rate_limit(&e);
                             let non_retryable = is_non_retryable(&e) || non_retryable_rate_limit;
                             let rate_limited = is_rate_limited(&e);
-                            let failure_reason = failure_reason(rate_limited, non_retryable);
+                            let failure_reason =
+                                failure_reason(provider_unreachable, rate_limited, non_retryable);
                             let error_detail = compact_error_detail(&e);
 
                             push_failure(
                                 &mut failures,
                                 provider_name,
-                                current_model,
+                                provider_model,
                                 attempt + 1,
-                                self.max_retries + 1,
+                                policy.max_retries + 1,
                                 failure_reason,
                                 &error_detail,
                             );
@@ -369,10 +473,20 @@ impl Provider for ReliableProvider {
                                 }
                             }
 
+                            if provider_unreachable {
+                                tracing::warn!(
+                                    provider = provider_name,
+                                    error = %error_detail,
+                                    "Provider unreachable, failing over"
+                                );
+                                unreachable.insert(provider_name.as_str());
+                                break;
+                            }
+
                             if non_retryable {
                                 tracing::warn!(
                                     provider = provider_name,
-                                    model = *current_model,
+                                    model = provider_model,
                                     error = %error_detail,
                                     "Non-retryable error, moving on"
                                 );
@@ -387,11 +501,11 @@ impl Provider for ReliableProvider {
                                 break;
                             }
 
-                            if attempt < self.max_retries {
-                                let wait = self.compute_backoff(backoff_ms, &e);
+                            if attempt < policy.max_retries {
+                                let wait = self.compute_backoff(policy.backoff_ms(attempt), &e);
                                 tracing::warn!(
                                     provider = provider_name,
-                                    model = *current_model,
+                                    model = provider_model,
                                     attempt = attempt + 1,
                                     backoff_ms = wait,
                                     reason = failure_reason,
@@ -399,7 +513,6 @@ impl Provider for ReliableProvider {
                                     "Provider call failed, retrying"
                                 );
                                 tokio::time::sleep(Duration::from_millis(wait)).await;
-                                backoff_ms = (backoff_ms.saturating_mul(2)).min(10_000);
                             }
                         }
                     }
@@ -407,134 +520,140 @@ impl Provider for ReliableProvider {
 
                 tracing::warn!(
                     provider = provider_name,
-                    model = *current_model,
+                    model = provider_model,
                     "Exhausted retries, trying next provider/model"
                 );
             }
 
-            if *current_model != model {
+            if current_model != model {
                 tracing::warn!(
                     original_model = model,
-                    fallback_model = *current_model,
+                    fallback_model = current_model,
                     "Model fallback exhausted all providers, trying next fallback model"
                 );
             }
         }
 
-        anyhow::bail!(
-            "All providers/models failed. Attempts:\n{}",
-            failures.join("\n")
-        )
+        Err(Self::exhausted_error(
+            &failures,
+            unreachable.len(),
+            candidates,
+        ))
     }
 
-    async fn chat_with_history(
-        &self,
-        messages: &[ChatMessage],
-        model: &str,
-        temperature: f64,
-    ) -> anyhow::Result<String> {
-        let models = self.model_chain(model);
-        let mut failures = Vec::new();
-
-        for current_model in &models {
-            for (provider_name, provider) in &self.providers {
-                let mut backoff_ms = self.base_backoff_ms;
-
-                for attempt in 0..=self.max_retries {
-                    match provider
-                        .chat_with_history(messages, current_model, temperature)
-                        .await
-                    {
-                        Ok(resp) => {
-                            if attempt > 0 || *current_model != model {
-                                tracing::info!(
-                                    provider = provider_name,
-                                    model = *current_model,
-                                    attempt,
-                                    original_model = model,
-                                    "Provider recovered (failover/retry)"
-                                );
-                            }
-                            return Ok(resp);
-                        }
-                        Err(e) => {
-                            let non_retryable_rate_limit = is_non_retryable_rate_limit(&e);
-                            let non_retryable = is_non_retryable(&e) || non_retryable_rate_limit;
-                            let rate_limited = is_rate_limited(&e);
-                            let failure_reason = failure_reason(rate_limited, non_retryable);
-                            let error_detail = compact_error_detail(&e);
-
-                            push_failure(
-                                &mut failures,
-                                provider_name,
-                                current_model,
-                                attempt + 1,
-                                self.max_retries + 1,
-                                failure_reason,
-                                &error_detail,
-                            );
+    /// Build the list of models to try: [original, fallback1, fallback2, ...]
+    fn model_chain<'a>(&'a self, model: &'a str) -> Vec<&'a str> {
+        let mut chain = vec![model];
+        if let Some(fallbacks) = self.model_fallbacks.get(model) {
+            chain.extend(fallbacks.iter().map(|s| s.as_str()));
+        }
+        chain
+    }
 
-                            if rate_limited && !non_retryable_rate_limit {
-                                if let Some(new_key) = self.rotate_key() {
-                                    tracing::warn!(
-                                        provider = provider_name,
-                                        error = %error_detail,
-                                        "Rate limited; key rotation selected key ending ...{} \
-                                         but cannot apply (Provider trait has no set_api_key). \
-                                         Retrying with original key.",
-                                        &new_key[new_key.len().saturating_sub(4)..]
-                                    );
-                                }
-                            }
+    /// Providers in priority order, except that those the background prober
+    /// last found unreachable are tried after the rest. Requests carrying
+    /// images only go to providers with vision support.
+    fn providers_by_health(&self, needs_vision: bool) -> Vec<&(String, Box<dyn Provider>)> {
+        let (reachable, unreachable): (Vec<_>, Vec<_>) = self
+            .providers
+            .iter()
+            .filter(|(_, provider)| !needs_vision || provider.supports_vision())
+            .partition(|(name, _)| !crate::health::provider_unreachable(name));
+        reachable.into_iter().chain(unreachable).collect()
+    }
+
+    /// Whether a request with `image_count` images needs vision support.
+    /// Fails when no provider in the chain accepts images.
+    fn require_vision(&self, image_count: usize) -> anyhow::Result<bool> {
+        if image_count > 0 && !self.supports_vision() {
+            return Err(ProviderCapabilityError {
+                provider: self
+                    .providers
+                    .first()
+                    .map(|(name, _)| name.clone())
+                    .unwrap_or_default(),
+                capability: "vision".to_string(),
+                message: format!(
+                    "received {image_count} image(s), but no configured provider supports vision input"
+                ),
+            }
+            .into());
+        }
+        Ok(image_count > 0)
+    }
 
-                            if non_retryable {
-                                tracing::warn!(
-                                    provider = provider_name,
-                                    model = *current_model,
-                                    error = %error_detail,
-                                    "Non-retryable error, moving on"
-                                );
+    /// Advance to the next API key and return it, or None if no extra keys configured.
+    fn rotate_key(&self) -> Option<&str> {
+        if self.api_keys.is_empty() {
+            return None;
+        }
+        let idx = self.key_index.fetch_add(1, Ordering::Relaxed) % self.api_keys.len();
+        Some(&self.api_keys[idx])
+    }
 
-                                if is_context_window_exceeded(&e) {
-                                    anyhow::bail!(
-                                        "Request exceeds model context window; retries and fallbacks were skipped. Attempts:\n{}",
-                                        failures.join("\n")
-                                    );
-                                }
+    /// Compute backoff duration, respecting Retry-After if present.
+    fn compute_backoff(&self, base: u64, err: &anyhow::Error) -> u64 {
+        if let Some(retry_after) = parse_retry_after_ms(err) {
+            // Use Retry-After but cap at 30s to avoid indefinite waits
+            retry_after.min(30_000).max(base)
+        } else {
+            base
+        }
+    }
+}
 
-                                break;
-                            }
+#[async_trait]
+impl Provider for ReliableProvider {
+    async fn warmup(&self) -> anyhow::Result<()> {
+        for (name, provider) in &self.providers {
+            tracing::info!(provider = name, "Warming up provider connection pool");
+            if provider.warmup().await.is_err() {
+                tracing::warn!(provider = name, "Warmup failed (non-fatal)");
+            }
+        }
+        Ok(())
+    }
 
-                            if attempt < self.max_retries {
-                                let wait = self.compute_backoff(backoff_ms, &e);
-                                tracing::warn!(
-                                    provider = provider_name,
-                                    model = *current_model,
-                                    attempt = attempt + 1,
-                                    backoff_ms = wait,
-                                    reason = failure_reason,
-                                    error = %error_detail,
-                                    "Provider call failed, retrying"
-                                );
-                                tokio::time::sleep(Duration::from_millis(wait)).await;
-                                backoff_ms = (backoff_ms.saturating_mul(2)).min(10_000);
-                            }
-                        }
-                    }
+    /// Models of the first provider in the chain that can list them.
+    async fn list_models(&self) -> anyhow::Result<Vec<String>> {
+        let mut last_error = None;
+        for (name, provider) in &self.providers {
+            match provider.list_models().await {
+                Ok(models) => return Ok(models),
+                Err(e) => {
+                    tracing::debug!(provider = name, "Model listing failed: {e}");
+                    last_error = Some(e);
                 }
-
-                tracing::warn!(
-                    provider = provider_name,
-                    model = *current_model,
-                    "Exhausted retries, trying next provider/model"
-                );
             }
         }
+        Err(last_error.unwrap_or_else(|| anyhow::anyhow!("No providers configured")))
+    }
 
-        anyhow::bail!(
-            "All providers/models failed. Attempts:\n{}",
-            failures.join("\n")
-        )
+    async fn chat_with_system(
+        &self,
+        system_prompt: Option<&str>,
+        message: &str,
+        model: &str,
+        temperature: f64,
+    ) -> anyhow::Result<String> {
+        let needs_vision = self.require_vision(multimodal::parse_image_markers(message).1.len())?;
+        self.with_failover(model, needs_vision, |provider, model| {
+            provider.chat_with_system(system_prompt, message, model, temperature)
+        })
+        .await
+    }
+
+    async fn chat_with_history(
+        &self,
+        messages: &[ChatMessage],
+        model: &str,
+        temperature: f64,
+    ) -> anyhow::Result<String> {
+        let needs_vision = self.require_vision(multimodal::count_image_markers(messages))?;
+        self.with_failover(model, needs_vision, |provider, model| {
+            provider.chat_with_history(messages, model, temperature)
+        })
+        .await
     }
 
     fn supports_native_tools(&self) -> bool {
@@ -544,6 +663,13 @@ impl Provider for ReliableProvider {
             .unwrap_or(false)
     }
 
+    fn supports_native_tools_for_model(&self, model: &str) -> bool {
+        self.providers
+            .first()
+            .map(|(_, p)| p.supports_native_tools_for_model(model))
+            .unwrap_or(false)
+    }
+
     fn supports_vision(&self) -> bool {
         self.providers
             .iter()
@@ -557,108 +683,34 @@ impl Provider for ReliableProvider {
         model: &str,
         temperature: f64,
     ) -> anyhow::Result<ChatResponse> {
-        let models = self.model_chain(model);
-        let mut failures = Vec::new();
-
-        for current_model in &models {
-            for (provider_name, provider) in &self.providers {
-                let mut backoff_ms = self.base_backoff_ms;
-
-                for attempt in 0..=self.max_retries {
-                    match provider
-                        .chat_with_tools(messages, tools, current_model, temperature)
-                        .await
-                    {
-                        Ok(resp) => {
-                            if attempt > 0 || *current_model != model {
-                                tracing::info!(
-                                    provider = provider_name,
-                                    model = *current_model,
-                                    attempt,
-                                    original_model = model,
-                                    "Provider recovered (failover/retry)"
-                                );
-                            }
-                            return Ok(resp);
-                        }
-                        Err(e) => {
-                            let non_retryable_rate_limit = is_non_retryable_rate_limit(&e);
-                            let non_retryable = is_non_retryable(&e) || non_retryable_rate_limit;
-                            let rate_limited = is_rate_limited(&e);
-                            let failure_reason = failure_reason(rate_limited, non_retryable);
-                            let error_detail = compact_error_detail(&e);
-
-                            push_failure(
-                                &mut failures,
-                                provider_name,
-                                current_model,
-                                attempt + 1,
-                                self.max_retries + 1,
-                                failure_reason,
-                                &error_detail,
-                            );
-
-                            if rate_limited && !non_retryable_rate_limit {
-                                if let Some(new_key) = self.rotate_key() {
-                                    tracing::warn!(
-                                        provider = provider_name,
-                                        error = %error_detail,
-                                        "Rate limited; key rotation selected key ending ...{} \
-                                         but cannot apply (Provider trait has no set_api_key). \
-                                         Retrying with original key.",
-                                        &new_key[new_key.len().saturating_sub(4)..]
-                                    );
-                                }
-                            }
-
-                            if non_retryable {
-                                tracing::warn!(
-                                    provider = provider_name,
-                                    model = *current_model,
-                                    error = %error_detail,
-                                    "Non-retryable error, moving on"
-                                );
-
-                                if is_context_window_exceeded(&e) {
-                                    anyhow::bail!(
-                                        "Request exceeds model context window; retries and fallbacks were skipped. Attempts:\n{}",
-                                        failures.join("\n")
-                                    );
-                                }
-
-                                break;
-                            }
-
-                            if attempt < self.max_retries {
-                                let wait = self.compute_backoff(backoff_ms, &e);
-                                tracing::warn!(
-                                    provider = provider_name,
-                                    model = *current_model,
-                                    attempt = attempt + 1,
-                                    backoff_ms = wait,
-                                    reason = failure_reason,
-                                    error = %error_detail,
-                                    "Provider call failed, retrying"
-                                );
-                                tokio::time::sleep(Duration::from_millis(wait)).await;
-                                backoff_ms = (backoff_ms.saturating_mul(2)).min(10_000);
-                            }
-                        }
-                    }
-                }
+        let needs_vision = self.require_vision(multimodal::count_image_markers(messages))?;
+        self.with_failover(model, needs_vision, |provider, model| {
+            provider.chat_with_tools(messages, tools, model, temperature)
+        })
+        .await
+    }
 
-                tracing::warn!(
-                    provider = provider_name,
-                    model = *current_model,
-                    "Exhausted retries, trying next provider/model"
-                );
-            }
-        }
+    fn supports_structured_output(&self) -> bool {
+        self.providers
+            .iter()
+            .any(|(_, provider)| provider.supports_structured_output())
+    }
 
-        anyhow::bail!(
-            "All providers/models failed. Attempts:\n{}",
-            failures.join("\n")
-        )
+    /// Schema requests fail over like any other call; validation and
+    /// re-asking happen above this layer in
+    /// [`chat_structured`](super::structured::chat_structured).
+    async fn chat_with_schema(
+        &self,
+        messages: &[ChatMessage],
+        format: &JsonSchemaFormat,
+        model: &str,
+        temperature: f64,
+    ) -> anyhow::Result<String> {
+        let needs_vision = self.require_vision(multimodal::count_image_markers(messages))?;
+        self.with_failover(model, needs_vision, |provider, model| {
+            provider.chat_with_schema(messages, format, model, temperature)
+        })
+        .await
     }
 
     async fn chat(
@@ -667,117 +719,59 @@ impl Provider for ReliableProvider {
         model: &str,
         temperature: f64,
     ) -> anyhow::Result<ChatResponse> {
-        let models = self.model_chain(model);
-        let mut failures = Vec::new();
-
-        for current_model in &models {
-            for (provider_name, provider) in &self.providers {
-                let mut backoff_ms = self.base_backoff_ms;
-
-                for attempt in 0..=self.max_retries {
-                    let req = ChatRequest {
-                        messages: request.messages,
-                        tools: request.tools,
-                    };
-                    match provider.chat(req, current_model, temperature).await {
-                        Ok(resp) => {
-                            if attempt > 0 || *current_model != model {
-                                tracing::info!(
-                                    provider = provider_name,
-                                    model = *current_model,
-                                    attempt,
-                                    original_model = model,
-                                    "Provider recovered (failover/retry)"
-                                );
-                            }
-                            return Ok(resp);
-                        }
-                        Err(e) => {
-                            let non_retryable_rate_limit = is_non_retryable_rate_limit(&e);
-                            let non_retryable = is_non_retryable(&e) || non_retryable_rate_limit;
-                            let rate_limited = is_rate_limited(&e);
-                            let failure_reason = failure_reason(rate_limited, non_retryable);
-                            let error_detail = compact_error_detail(&e);
-
-                            push_failure(
-                                &mut failures,
-                                provider_name,
-                                current_model,
-                                attempt + 1,
-                                self.max_retries + 1,
-                                failure_reason,
-                                &error_detail,
-                            );
-
-                            if rate_limited && !non_retryable_rate_limit {
-                                if let Some(new_key) = self.rotate_key() {
-                                    tracing::warn!(
-                                        provider = provider_name,
-                                        error = %error_detail,
-                                        "Rate limited; key rotation selected key ending ...{} \
-                                         but cannot apply (Provider trait has no set_api_key). \
-                                         Retrying with original key.",
-                                        &new_key[new_key.len().saturating_sub(4)..]
-                                    );
-                                }
-                            }
-
-                            if non_retryable {
-                                tracing::warn!(
-                                    provider = provider_name,
-                                    model = *current_model,
-                                    error = %error_detail,
-                                    "Non-retryable error, moving on"
-                                );
-
-                                if is_context_window_exceeded(&e) {
-                                    anyhow::bail!(
-                                        "Request exceeds model context window; retries and fallbacks were skipped. Attempts:\n{}",
-                                        failures.join("\n")
-                                    );
-                                }
+        let needs_vision = self.require_vision(request.image_count())?;
+        self.with_failover(model, needs_vision, |provider, model| {
+            let request = ChatRequest {
+                messages: request.messages,
+                tools: request.tools,
+            };
+            provider.chat(request, model, temperature)
+        })
+        .await
+    }
 
-                                break;
-                            }
+    fn supports_embeddings(&self) -> bool {
+        self.providers.iter().any(|(_, p)| p.supports_embeddings())
+    }
 
-                            if attempt < self.max_retries {
-                                let wait = self.compute_backoff(backoff_ms, &e);
-                                tracing::warn!(
-                                    provider = provider_name,
-                                    model = *current_model,
-                                    attempt = attempt + 1,
-                                    backoff_ms = wait,
-                                    reason = failure_reason,
-                                    error = %error_detail,
-                                    "Provider call failed, retrying"
-                                );
-                                tokio::time::sleep(Duration::from_millis(wait)).await;
-                                backoff_ms = (backoff_ms.saturating_mul(2)).min(10_000);
-                            }
-                        }
+    /// Embeddings go to the first provider that supports them, with retries
+    /// but without failover: vectors from different models are not
+    /// comparable, so silently switching providers would corrupt stored
+    /// embeddings.
+    async fn embed(&self, texts: &[&str], model: &str) -> anyhow::Result<Vec<Vec<f32>>> {
+        let Some((provider_name, provider)) =
+            self.providers.iter().find(|(_, p)| p.supports_embeddings())
+        else {
+            anyhow::bail!("No configured provider supports embeddings");
+        };
+        let policy = self.retry_policy(provider_name);
+
+        let mut attempt = 0;
+        loop {
+            match provider.embed(texts, model).await {
+                Ok(vectors) => return Ok(vectors),
+                Err(e) => {
+                    if attempt >= policy.max_retries
+                        || is_unreachable(&e)
+                        || is_non_retryable(&e)
+                        || is_non_retryable_rate_limit(&e)
+                    {
+                        return Err(e);
                     }
+                    let wait = self.compute_backoff(policy.backoff_ms(attempt), &e);
+                    tracing::warn!(
+                        provider = provider_name,
+                        model,
+                        attempt = attempt + 1,
+                        backoff_ms = wait,
+                        error = %compact_error_detail(&e),
+                        "Embedding call failed, retrying"
+                    );
+                    tokio::time::sleep(Duration::from_millis(wait)).await;
+                    attempt += 1;
                 }
-
-                tracing::warn!(
-                    provider = provider_name,
-                    model = *current_model,
-                    "Exhausted retries, trying next provider/model"
-                );
-            }
-
-            if *current_model != model {
-                tracing::warn!(
-                    original_model = model,
-                    fallback_model = *current_model,
-                    "Model fallback exhausted all providers, trying next fallback model"
-                );
             }
         }
-
-        anyhow::bail!(
-            "All providers/models failed. Attempts:\n{}",
-            failures.join("\n")
-        )
     }
 
     fn supports_streaming(&self) -> bool {
@@ -792,9 +786,29 @@ impl Provider for ReliableProvider {
         temperature: f64,
         options: StreamOptions,
     ) -> stream::BoxStream<'static, StreamResult<StreamChunk>> {
+        let mut messages = Vec::new();
+        if let Some(sys) = system_prompt {
+            messages.push(ChatMessage::system(sys));
+        }
+        messages.push(ChatMessage::user(message));
+        self.stream_chat_with_history(&messages, model, temperature, options)
+    }
+
+    fn stream_chat_with_history(
+        &self,
+        messages: &[ChatMessage],
+        model: &str,
+        temperature: f64,
+        options: StreamOptions,
+    ) -> stream::BoxStream<'static, StreamResult<StreamChunk>> {
+        let needs_vision = match self.require_vision(multimodal::count_image_markers(messages)) {
+            Ok(needs_vision) => needs_vision,
+            Err(e) => return super::sse::error_stream(e.to_string()),
+        };
+
         // Try each provider/model combination for streaming
         // For streaming, we use the first provider that supports it and has streaming enabled
-        for (provider_name, provider) in &self.providers {
+        for (provider_name, provider) in self.providers_by_health(needs_vision) {
             if !provider.supports_streaming() || !options.enabled {
                 continue;
             }
@@ -802,21 +816,13 @@ impl Provider for ReliableProvider {
             // Clone provider data for the stream
             let provider_clone = provider_name.clone();
 
-            // Try the first model in the chain for streaming
-            let current_model = match self.model_chain(model).first() {
-                Some(m) => m.to_string(),
-                None => model.to_string(),
-            };
+            // Fallback providers may pin their own model
+            let current_model = self.provider_model(provider_name, model).to_string();
 
             // For streaming, we attempt once and propagate errors
             // The caller can retry the entire request if needed
-            let stream = provider.stream_chat_with_system(
-                system_prompt,
-                message,
-                &current_model,
-                temperature,
-                options,
-            );
+            let stream =
+                provider.stream_chat_with_history(messages, &current_model, temperature, options);
 
             // Use a channel to bridge the stream with logging
             let (tx, rx) = tokio::sync::mpsc::channel::<StreamResult<StreamChunk>>(100);
@@ -857,6 +863,7 @@ impl Provider for ReliableProvider {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::providers::traits::ProviderCapabilities;
     use std::sync::Arc;
 
     struct MockProvider {
@@ -892,37 +899,222 @@ mod tests {
             if attempt <= self.fail_until_attempt {
                 anyhow::bail!(self.error);
             }
-            Ok(self.response.to_string())
+            Ok(self.response.to_string())
+        }
+    }
+
+    /// Mock that records which model was used for each call.
+    struct ModelAwareMock {
+        calls: Arc<AtomicUsize>,
+        models_seen: parking_lot::Mutex<Vec<String>>,
+        fail_models: Vec<&'static str>,
+        response: &'static str,
+    }
+
+    #[async_trait]
+    impl Provider for ModelAwareMock {
+        async fn chat_with_system(
+            &self,
+            _system_prompt: Option<&str>,
+            _message: &str,
+            model: &str,
+            _temperature: f64,
+        ) -> anyhow::Result<String> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            self.models_seen.lock().push(model.to_string());
+            if self.fail_models.contains(&model) {
+                anyhow::bail!("500 model {} unavailable", model);
+            }
+            Ok(self.response.to_string())
+        }
+    }
+
+    // ── Existing tests (preserved) ──
+
+    struct EmbeddingMock {
+        calls: Arc<AtomicUsize>,
+        fail_until_attempt: usize,
+        error: &'static str,
+    }
+
+    #[async_trait]
+    impl Provider for EmbeddingMock {
+        async fn chat_with_system(
+            &self,
+            _system_prompt: Option<&str>,
+            _message: &str,
+            _model: &str,
+            _temperature: f64,
+        ) -> anyhow::Result<String> {
+            Ok("ok".into())
+        }
+
+        fn supports_embeddings(&self) -> bool {
+            true
+        }
+
+        async fn embed(&self, texts: &[&str], _model: &str) -> anyhow::Result<Vec<Vec<f32>>> {
+            let attempt = self.calls.fetch_add(1, Ordering::SeqCst) + 1;
+            if attempt <= self.fail_until_attempt {
+                anyhow::bail!(self.error);
+            }
+            Ok(texts.iter().map(|_| vec![1.0, 0.0]).collect())
         }
     }
 
-    /// Mock that records which model was used for each call.
-    struct ModelAwareMock {
+    fn embedding_chain(
+        error: &'static str,
+    ) -> (ReliableProvider, Arc<AtomicUsize>, Arc<AtomicUsize>) {
+        let embedder_calls = Arc::new(AtomicUsize::new(0));
+        let other_calls = Arc::new(AtomicUsize::new(0));
+        let provider = ReliableProvider::new(
+            vec![
+                (
+                    "chat-only".into(),
+                    Box::new(MockProvider {
+                        calls: Arc::new(AtomicUsize::new(0)),
+                        fail_until_attempt: 0,
+                        response: "ok",
+                        error: "boom",
+                    }),
+                ),
+                (
+                    "embedder".into(),
+                    Box::new(EmbeddingMock {
+                        calls: Arc::clone(&embedder_calls),
+                        fail_until_attempt: 1,
+                        error,
+                    }),
+                ),
+                (
+                    "other-embedder".into(),
+                    Box::new(EmbeddingMock {
+                        calls: Arc::clone(&other_calls),
+                        fail_until_attempt: 0,
+                        error: "boom",
+                    }),
+                ),
+            ],
+            2,
+            1,
+        );
+        (provider, embedder_calls, other_calls)
+    }
+
+    #[tokio::test]
+    async fn embed_retries_first_capable_provider_without_failover() {
+        let (provider, embedder_calls, other_calls) = embedding_chain("500 Internal Server Error");
+        assert!(provider.supports_embeddings());
+        let vectors = provider.embed(&["a", "b"], "embed-model").await.unwrap();
+        assert_eq!(vectors.len(), 2);
+        assert_eq!(embedder_calls.load(Ordering::SeqCst), 2);
+        assert_eq!(other_calls.load(Ordering::SeqCst), 0);
+
+        let (provider, embedder_calls, other_calls) = embedding_chain("401 Unauthorized");
+        assert!(provider.embed(&["a"], "embed-model").await.is_err());
+        assert_eq!(embedder_calls.load(Ordering::SeqCst), 1);
+        assert_eq!(other_calls.load(Ordering::SeqCst), 0);
+    }
+
+    /// Mock that accepts images and counts its calls, failing with `error`
+    /// when set.
+    struct VisionMock {
         calls: Arc<AtomicUsize>,
-        models_seen: parking_lot::Mutex<Vec<String>>,
-        fail_models: Vec<&'static str>,
-        response: &'static str,
+        error: Option<&'static str>,
     }
 
     #[async_trait]
-    impl Provider for ModelAwareMock {
+    impl Provider for VisionMock {
+        fn capabilities(&self) -> ProviderCapabilities {
+            ProviderCapabilities {
+                native_tool_calling: false,
+                vision: true,
+            }
+        }
+
         async fn chat_with_system(
             &self,
             _system_prompt: Option<&str>,
             _message: &str,
-            model: &str,
+            _model: &str,
             _temperature: f64,
         ) -> anyhow::Result<String> {
             self.calls.fetch_add(1, Ordering::SeqCst);
-            self.models_seen.lock().push(model.to_string());
-            if self.fail_models.contains(&model) {
-                anyhow::bail!("500 model {} unavailable", model);
+            if let Some(error) = self.error {
+                anyhow::bail!(error);
             }
-            Ok(self.response.to_string())
+            Ok("vision".into())
         }
     }
 
-    // ── Existing tests (preserved) ──
+    #[tokio::test]
+    async fn image_requests_skip_providers_without_vision() {
+        let text_calls = Arc::new(AtomicUsize::new(0));
+        let vision_calls = Arc::new(AtomicUsize::new(0));
+        let provider = ReliableProvider::new(
+            vec![
+                (
+                    "text-only".into(),
+                    Box::new(MockProvider {
+                        calls: Arc::clone(&text_calls),
+                        fail_until_attempt: 0,
+                        response: "text",
+                        error: "boom",
+                    }),
+                ),
+                (
+                    "vision".into(),
+                    Box::new(VisionMock {
+                        calls: Arc::clone(&vision_calls),
+                        error: None,
+                    }),
+                ),
+            ],
+            1,
+            1,
+        );
+
+        let messages = [ChatMessage::user(
+            "What is this? [IMAGE:data:image/png;base64,iVBORw0KGgo=]",
+        )];
+        let reply = provider
+            .chat_with_history(&messages, "test", 0.0)
+            .await
+            .unwrap();
+        assert_eq!(reply, "vision");
+        assert_eq!(text_calls.load(Ordering::SeqCst), 0);
+
+        let reply = provider.simple_chat("no image", "test", 0.0).await.unwrap();
+        assert_eq!(reply, "text");
+        assert_eq!(vision_calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn image_requests_fail_clearly_without_vision_provider() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let provider = ReliableProvider::new(
+            vec![(
+                "text-only".into(),
+                Box::new(MockProvider {
+                    calls: Arc::clone(&calls),
+                    fail_until_attempt: 0,
+                    response: "text",
+                    error: "boom",
+                }),
+            )],
+            1,
+            1,
+        );
+
+        let err = provider
+            .simple_chat("[IMAGE:https://example.com/cat.png] describe", "test", 0.0)
+            .await
+            .unwrap_err();
+        let capability = err.downcast_ref::<ProviderCapabilityError>().unwrap();
+        assert_eq!(capability.provider, "text-only");
+        assert_eq!(capability.capability, "vision");
+        assert_eq!(calls.load(Ordering::SeqCst), 0);
+    }
 
     #[tokio::test]
     async fn succeeds_without_retry() {
@@ -1335,6 +1527,308 @@ mod tests {
         assert_eq!(calls.load(Ordering::SeqCst), 1);
     }
 
+    // ── Provider fallback chains ──
+
+    const OFFLINE_ERROR: &str = "error sending request for url (https://openrouter.ai/api/v1/chat/completions): dns error: failed to lookup address information";
+
+    #[test]
+    fn unreachable_detects_offline_errors() {
+        for msg in [
+            OFFLINE_ERROR,
+            "tcp connect error: Connection refused (os error 111)",
+            "Network is unreachable (os error 101)",
+        ] {
+            assert!(is_unreachable(&anyhow::anyhow!(msg)), "{msg}");
+        }
+        assert!(!is_unreachable(&anyhow::anyhow!(
+            "500 Internal Server Error"
+        )));
+        assert!(!is_unreachable(&anyhow::anyhow!("operation timed out")));
+    }
+
+    #[tokio::test]
+    async fn unreachable_provider_fails_over_without_retries() {
+        let primary_calls = Arc::new(AtomicUsize::new(0));
+        let fallback_calls = Arc::new(AtomicUsize::new(0));
+        let provider = ReliableProvider::new(
+            vec![
+                (
+                    "openrouter".into(),
+                    Box::new(MockProvider {
+                        calls: Arc::clone(&primary_calls),
+                        fail_until_attempt: usize::MAX,
+                        response: "never",
+                        error: OFFLINE_ERROR,
+                    }),
+                ),
+                (
+                    "ollama".into(),
+                    Box::new(MockProvider {
+                        calls: Arc::clone(&fallback_calls),
+                        fail_until_attempt: 0,
+                        response: "from local model",
+                        error: "",
+                    }),
+                ),
+            ],
+            3,
+            1,
+        );
+
+        let result = provider.simple_chat("hello", "test", 0.0).await.unwrap();
+        assert_eq!(result, "from local model");
+        assert_eq!(primary_calls.load(Ordering::SeqCst), 1);
+        assert_eq!(fallback_calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn probed_unreachable_provider_is_tried_last() {
+        let primary_name = format!("probe-primary-{}", uuid::Uuid::new_v4());
+        crate::health::mark_provider_error(&primary_name, "dns error", true);
+        let primary_calls = Arc::new(AtomicUsize::new(0));
+        let fallback_calls = Arc::new(AtomicUsize::new(0));
+        let provider = ReliableProvider::new(
+            vec![
+                (
+                    primary_name.clone(),
+                    Box::new(MockProvider {
+                        calls: Arc::clone(&primary_calls),
+                        fail_until_attempt: 0,
+                        response: "from primary",
+                        error: "",
+                    }),
+                ),
+                (
+                    "fallback".into(),
+                    Box::new(MockProvider {
+                        calls: Arc::clone(&fallback_calls),
+                        fail_until_attempt: 0,
+                        response: "from fallback",
+                        error: "",
+                    }),
+                ),
+            ],
+            1,
+            1,
+        );
+
+        let result = provider.simple_chat("hello", "test", 0.0).await.unwrap();
+        assert_eq!(result, "from fallback");
+        assert_eq!(primary_calls.load(Ordering::SeqCst), 0);
+
+        crate::health::mark_provider_ok(&primary_name, Duration::from_millis(5));
+        let result = provider.simple_chat("hello", "test", 0.0).await.unwrap();
+        assert_eq!(result, "from primary");
+    }
+
+    #[tokio::test]
+    async fn fallback_provider_uses_pinned_model_once() {
+        let primary = Arc::new(ModelAwareMock {
+            calls: Arc::new(AtomicUsize::new(0)),
+            models_seen: parking_lot::Mutex::new(Vec::new()),
+            fail_models: vec!["anthropic/claude-sonnet-4", "openai/gpt-4o"],
+            response: "never",
+        });
+        let local = Arc::new(ModelAwareMock {
+            calls: Arc::new(AtomicUsize::new(0)),
+            models_seen: parking_lot::Mutex::new(Vec::new()),
+            fail_models: vec!["llama3.2:3b"],
+            response: "never",
+        });
+
+        let provider = ReliableProvider::new(
+            vec![
+                (
+                    "openrouter".into(),
+                    Box::new(primary.clone()) as Box<dyn Provider>,
+                ),
+                (
+                    "ollama".into(),
+                    Box::new(local.clone()) as Box<dyn Provider>,
+                ),
+            ],
+            0,
+            1,
+        )
+        .with_model_fallbacks(HashMap::from([(
+            "anthropic/claude-sonnet-4".to_string(),
+            vec!["openai/gpt-4o".to_string()],
+        )]))
+        .with_provider_models(HashMap::from([(
+            "ollama".to_string(),
+            "llama3.2:3b".to_string(),
+        )]));
+
+        let err = provider
+            .simple_chat("hello", "anthropic/claude-sonnet-4", 0.0)
+            .await
+            .expect_err("all models fail");
+        assert!(err
+            .to_string()
+            .contains("provider=ollama model=llama3.2:3b attempt 1/1"));
+        assert_eq!(
+            *primary.models_seen.lock(),
+            vec!["anthropic/claude-sonnet-4", "openai/gpt-4o"]
+        );
+        // The pinned model is tried once, not once per model in the chain.
+        assert_eq!(*local.models_seen.lock(), vec!["llama3.2:3b"]);
+    }
+
+    #[tokio::test]
+    async fn all_providers_unreachable_reports_offline() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let mock = |calls: &Arc<AtomicUsize>| {
+            Box::new(MockProvider {
+                calls: Arc::clone(calls),
+                fail_until_attempt: usize::MAX,
+                response: "never",
+                error: OFFLINE_ERROR,
+            }) as Box<dyn Provider>
+        };
+        let provider = ReliableProvider::new(
+            vec![
+                ("openrouter".into(), mock(&calls)),
+                ("openai".into(), mock(&calls)),
+            ],
+            2,
+            1,
+        );
+
+        let messages = vec![ChatMessage::user("hello")];
+        let err = provider
+            .chat_with_history(&messages, "test", 0.0)
+            .await
+            .expect_err("offline");
+        assert!(err.to_string().starts_with("No provider is reachable"));
+        assert!(err.to_string().contains("unreachable"));
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn unreachable_vision_providers_report_offline() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let provider = ReliableProvider::new(
+            vec![
+                (
+                    "text-only".into(),
+                    Box::new(MockProvider {
+                        calls: Arc::clone(&calls),
+                        fail_until_attempt: 0,
+                        response: "text",
+                        error: "boom",
+                    }),
+                ),
+                (
+                    "vision".into(),
+                    Box::new(VisionMock {
+                        calls: Arc::clone(&calls),
+                        error: Some(OFFLINE_ERROR),
+                    }),
+                ),
+            ],
+            2,
+            1,
+        );
+
+        // Only the vision provider can take the request, and it is offline.
+        let err = provider
+            .simple_chat("[IMAGE:https://example.com/cat.png] describe", "test", 0.0)
+            .await
+            .expect_err("offline");
+        assert!(err.to_string().starts_with("No provider is reachable"));
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    // ── Retry policies ──
+
+    #[test]
+    fn backoff_grows_exponentially_with_jitter_and_cap() {
+        let policy = RetryPolicy {
+            max_retries: 5,
+            base_backoff_ms: 100,
+            max_backoff_ms: 1_000,
+        };
+        for _ in 0..50 {
+            assert!((50..=100).contains(&policy.backoff_ms(0)));
+            assert!((200..=400).contains(&policy.backoff_ms(2)));
+            assert!((500..=1_000).contains(&policy.backoff_ms(6)));
+            assert!((500..=1_000).contains(&policy.backoff_ms(u32::MAX)));
+        }
+    }
+
+    #[test]
+    fn retry_overrides_inherit_unset_fields() {
+        let provider = ReliableProvider::new(vec![], 3, 200)
+            .with_max_backoff_ms(5_000)
+            .with_retry_policies(HashMap::from([(
+                "ollama".to_string(),
+                ProviderRetryPolicyConfig {
+                    max_retries: Some(0),
+                    backoff_ms: Some(1),
+                    max_backoff_ms: None,
+                },
+            )]));
+
+        assert_eq!(
+            provider.retry_policy("openrouter"),
+            RetryPolicy {
+                max_retries: 3,
+                base_backoff_ms: 200,
+                max_backoff_ms: 5_000,
+            }
+        );
+        assert_eq!(
+            provider.retry_policy("ollama"),
+            RetryPolicy {
+                max_retries: 0,
+                base_backoff_ms: 50,
+                max_backoff_ms: 5_000,
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn per_provider_retry_budget_applies() {
+        let primary_calls = Arc::new(AtomicUsize::new(0));
+        let fallback_calls = Arc::new(AtomicUsize::new(0));
+        let provider = ReliableProvider::new(
+            vec![
+                (
+                    "ollama".into(),
+                    Box::new(MockProvider {
+                        calls: Arc::clone(&primary_calls),
+                        fail_until_attempt: usize::MAX,
+                        response: "never",
+                        error: "503 Service Unavailable",
+                    }),
+                ),
+                (
+                    "openrouter".into(),
+                    Box::new(MockProvider {
+                        calls: Arc::clone(&fallback_calls),
+                        fail_until_attempt: 1,
+                        response: "recovered",
+                        error: "502 Bad Gateway",
+                    }),
+                ),
+            ],
+            2,
+            1,
+        )
+        .with_retry_policies(HashMap::from([(
+            "ollama".to_string(),
+            ProviderRetryPolicyConfig {
+                max_retries: Some(0),
+                ..ProviderRetryPolicyConfig::default()
+            },
+        )]));
+
+        let result = provider.simple_chat("hello", "test", 0.0).await.unwrap();
+        assert_eq!(result, "recovered");
+        assert_eq!(primary_calls.load(Ordering::SeqCst), 1);
+        assert_eq!(fallback_calls.load(Ordering::SeqCst), 2);
+    }
+
     // ── New tests: auth rotation ──
 
     #[tokio::test]
@@ -1,6 +1,10 @@
-use super::traits::{ChatMessage, ChatRequest, ChatResponse};
+use super::structured::JsonSchemaFormat;
+use super::traits::{
+    ChatMessage, ChatRequest, ChatResponse, StreamChunk, StreamOptions, StreamResult,
+};
 use super::Provider;
 use async_trait::async_trait;
+use futures_util::stream;
 use std::collections::HashMap;
 
 /// A single route: maps a task hint to a provider + model combo.
@@ -74,10 +78,10 @@ impl RouterProvider {
     /// If the model starts with "hint:", look up the hint in the route table.
     /// Otherwise, use the default provider with the given model name.
     /// Resolve a model parameter to a (provider_index, actual_model) pair.
-    fn resolve(&self, model: &str) -> (usize, String) {
+    fn resolve<'a>(&'a self, model: &'a str) -> (usize, &'a str) {
         if let Some(hint) = model.strip_prefix("hint:") {
             if let Some((idx, resolved_model)) = self.routes.get(hint) {
-                return (*idx, resolved_model.clone());
+                return (*idx, resolved_model);
             }
             tracing::warn!(
                 hint = hint,
@@ -86,7 +90,7 @@ impl RouterProvider {
         }
 
         // Not a hint or hint not found — use default provider with the model as-is
-        (self.default_index, model.to_string())
+        (self.default_index, model)
     }
 }
 
@@ -104,12 +108,12 @@ impl Provider for RouterProvider {
         let (provider_name, provider) = &self.providers[provider_idx];
         tracing::info!(
             provider = provider_name.as_str(),
-            model = resolved_model.as_str(),
+            model = resolved_model,
             "Router dispatching request"
         );
 
         provider
-            .chat_with_system(system_prompt, message, &resolved_model, temperature)
+            .chat_with_system(system_prompt, message, resolved_model, temperature)
             .await
     }
 
@@ -122,7 +126,7 @@ impl Provider for RouterProvider {
         let (provider_idx, resolved_model) = self.resolve(model);
         let (_, provider) = &self.providers[provider_idx];
         provider
-            .chat_with_history(messages, &resolved_model, temperature)
+            .chat_with_history(messages, resolved_model, temperature)
             .await
     }
 
@@ -134,7 +138,7 @@ impl Provider for RouterProvider {
     ) -> anyhow::Result<ChatResponse> {
         let (provider_idx, resolved_model) = self.resolve(model);
         let (_, provider) = &self.providers[provider_idx];
-        provider.chat(request, &resolved_model, temperature).await
+        provider.chat(request, resolved_model, temperature).await
     }
 
     async fn chat_with_tools(
@@ -147,7 +151,7 @@ impl Provider for RouterProvider {
         let (provider_idx, resolved_model) = self.resolve(model);
         let (_, provider) = &self.providers[provider_idx];
         provider
-            .chat_with_tools(messages, tools, &resolved_model, temperature)
+            .chat_with_tools(messages, tools, resolved_model, temperature)
             .await
     }
 
@@ -158,12 +162,102 @@ impl Provider for RouterProvider {
             .unwrap_or(false)
     }
 
+    fn supports_native_tools_for_model(&self, model: &str) -> bool {
+        let (provider_idx, resolved_model) = self.resolve(model);
+        let (_, provider) = &self.providers[provider_idx];
+        provider.supports_native_tools_for_model(resolved_model)
+    }
+
     fn supports_vision(&self) -> bool {
         self.providers
             .iter()
             .any(|(_, provider)| provider.supports_vision())
     }
 
+    fn supports_embeddings(&self) -> bool {
+        self.providers
+            .iter()
+            .any(|(_, provider)| provider.supports_embeddings())
+    }
+
+    async fn embed(&self, texts: &[&str], model: &str) -> anyhow::Result<Vec<Vec<f32>>> {
+        let (provider_idx, resolved_model) = self.resolve(model);
+        let (_, provider) = &self.providers[provider_idx];
+        provider.embed(texts, resolved_model).await
+    }
+
+    fn supports_structured_output(&self) -> bool {
+        self.providers
+            .get(self.default_index)
+            .map(|(_, p)| p.supports_structured_output())
+            .unwrap_or(false)
+    }
+
+    async fn chat_with_schema(
+        &self,
+        messages: &[ChatMessage],
+        format: &JsonSchemaFormat,
+        model: &str,
+        temperature: f64,
+    ) -> anyhow::Result<String> {
+        let (provider_idx, resolved_model) = self.resolve(model);
+        let (_, provider) = &self.providers[provider_idx];
+        provider
+            .chat_with_schema(messages, format, resolved_model, temperature)
+            .await
+    }
+
+    fn supports_streaming(&self) -> bool {
+        self.providers
+            .iter()
+            .any(|(_, provider)| provider.supports_streaming())
+    }
+
+    fn stream_chat_with_system(
+        &self,
+        system_prompt: Option<&str>,
+        message: &str,
+        model: &str,
+        temperature: f64,
+        options: StreamOptions,
+    ) -> stream::BoxStream<'static, StreamResult<StreamChunk>> {
+        let (provider_idx, resolved_model) = self.resolve(model);
+        let (_, provider) = &self.providers[provider_idx];
+        provider.stream_chat_with_system(
+            system_prompt,
+            message,
+            resolved_model,
+            temperature,
+            options,
+        )
+    }
+
+    fn stream_chat_with_history(
+        &self,
+        messages: &[ChatMessage],
+        model: &str,
+        temperature: f64,
+        options: StreamOptions,
+    ) -> stream::BoxStream<'static, StreamResult<StreamChunk>> {
+        let (provider_idx, resolved_model) = self.resolve(model);
+        let (_, provider) = &self.providers[provider_idx];
+        provider.stream_chat_with_history(messages, resolved_model, temperature, options)
+    }
+
+    /// Streams through the routed provider, which falls back to a single
+    /// delta itself when it cannot stream.
+    fn chat_stream<'a>(
+        &'a self,
+        messages: &'a [ChatMessage],
+        model: &'a str,
+        temperature: f64,
+        options: StreamOptions,
+    ) -> stream::BoxStream<'a, StreamResult<StreamChunk>> {
+        let (provider_idx, resolved_model) = self.resolve(model);
+        let (_, provider) = &self.providers[provider_idx];
+        provider.chat_stream(messages, resolved_model, temperature, options)
+    }
+
     async fn warmup(&self) -> anyhow::Result<()> {
         for (name, provider) in &self.providers {
             tracing::info!(provider = name, "Warming up routed provider");
@@ -173,6 +267,14 @@ impl Provider for RouterProvider {
         }
         Ok(())
     }
+
+    /// Models of the default provider; routed hints name their own models.
+    async fn list_models(&self) -> anyhow::Result<Vec<String>> {
+        let Some((_, provider)) = self.providers.get(self.default_index) else {
+            anyhow::bail!("Router has no default provider");
+        };
+        provider.list_models().await
+    }
 }
 
 #[cfg(test)]
@@ -461,4 +563,114 @@ mod tests {
         assert_eq!(mocks[1].last_model(), "claude-opus");
         assert_eq!(mocks[0].call_count(), 0);
     }
+
+    #[test]
+    fn native_tool_support_follows_the_routed_model() {
+        let router = RouterProvider::new(
+            vec![
+                (
+                    "openai".to_string(),
+                    Box::new(crate::providers::openai::OpenAiProvider::new(Some("key")))
+                        as Box<dyn Provider>,
+                ),
+                (
+                    "ollama".to_string(),
+                    Box::new(crate::providers::ollama::OllamaProvider::new(None, None)),
+                ),
+            ],
+            vec![(
+                "local".to_string(),
+                Route {
+                    provider_name: "ollama".to_string(),
+                    model: "gemma3:4b".to_string(),
+                },
+            )],
+            "gpt-4o".to_string(),
+        );
+
+        assert!(router.supports_native_tools_for_model("gpt-4o"));
+        assert!(!router.supports_native_tools_for_model("hint:local"));
+    }
+
+    struct StreamingProvider;
+
+    #[async_trait]
+    impl Provider for StreamingProvider {
+        async fn chat_with_system(
+            &self,
+            _system_prompt: Option<&str>,
+            _message: &str,
+            _model: &str,
+            _temperature: f64,
+        ) -> anyhow::Result<String> {
+            Ok("unstreamed".to_string())
+        }
+
+        fn supports_streaming(&self) -> bool {
+            true
+        }
+
+        fn stream_chat_with_system(
+            &self,
+            _system_prompt: Option<&str>,
+            _message: &str,
+            model: &str,
+            _temperature: f64,
+            _options: StreamOptions,
+        ) -> stream::BoxStream<'static, StreamResult<StreamChunk>> {
+            let chunks = vec![
+                Ok(StreamChunk::delta(format!("{model}:"))),
+                Ok(StreamChunk::delta("streamed")),
+                Ok(StreamChunk::final_chunk()),
+            ];
+            Box::pin(stream::iter(chunks))
+        }
+    }
+
+    #[tokio::test]
+    async fn chat_stream_streams_through_the_routed_provider() {
+        use futures_util::StreamExt;
+
+        let router = RouterProvider::new(
+            vec![
+                (
+                    "plain".to_string(),
+                    Box::new(MockProvider::new("plain-response")) as Box<dyn Provider>,
+                ),
+                ("streaming".to_string(), Box::new(StreamingProvider)),
+            ],
+            vec![(
+                "fast".to_string(),
+                Route {
+                    provider_name: "streaming".to_string(),
+                    model: "stream-model".to_string(),
+                },
+            )],
+            "default-model".to_string(),
+        );
+        assert!(router.supports_streaming());
+        let messages = [ChatMessage::user("hi")];
+
+        let deltas: Vec<String> = router
+            .chat_stream(&messages, "hint:fast", 0.5, StreamOptions::new(true))
+            .map(|chunk| chunk.unwrap().delta)
+            .collect()
+            .await;
+        assert_eq!(deltas, ["stream-model:", "streamed", ""]);
+
+        let history: Vec<String> = router
+            .stream_chat_with_history(&messages, "hint:fast", 0.5, StreamOptions::new(true))
+            .map(|chunk| chunk.unwrap().delta)
+            .collect()
+            .await;
+        assert_eq!(history, deltas);
+
+        // A route whose provider cannot stream still answers with one delta.
+        let deltas: Vec<String> = router
+            .chat_stream(&messages, "default-model", 0.5, StreamOptions::new(true))
+            .map(|chunk| chunk.unwrap().delta)
+            .collect()
+            .await;
+        assert_eq!(deltas, ["plain-response", ""]);
+    }
 }
@@ -0,0 +1,294 @@
+//! Server-Sent Events decoding shared by the streaming providers.
+//!
+//! [`SseDecoder`] turns raw response bytes into events, buffering partial
+//! lines (and partial UTF-8 sequences) across network chunks.
+//! [`stream_request`] sends a request and maps each event to text through a
+//! provider-specific extractor, producing the [`StreamChunk`] stream returned
+//! by `Provider::stream_chat_with_history`.
+
+use crate::providers::traits::{StreamChunk, StreamError, StreamResult};
+use futures_util::{stream, StreamExt};
+use serde::Deserialize;
+
+/// One dispatched event: the optional `event:` name and its joined `data:`
+/// lines.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SseEvent {
+    pub event: Option<String>,
+    pub data: String,
+}
+
+#[derive(Debug, Default)]
+pub struct SseDecoder {
+    buffer: Vec<u8>,
+    event: Option<String>,
+    data: Vec<String>,
+}
+
+impl SseDecoder {
+    /// Feed response bytes and return every event they complete.
+    pub fn push(&mut self, bytes: &[u8]) -> Vec<SseEvent> {
+        self.buffer.extend_from_slice(bytes);
+        let mut events = Vec::new();
+        while let Some(pos) = self.buffer.iter().position(|b| *b == b'\n') {
+            let line: Vec<u8> = self.buffer.drain(..=pos).collect();
+            let line = String::from_utf8_lossy(&line);
+            if let Some(event) = self.process_line(line.trim_end_matches(['\n', '\r'])) {
+                events.push(event);
+            }
+        }
+        events
+    }
+
+    /// Flush an event left open when the body ends without a blank line.
+    pub fn finish(&mut self) -> Option<SseEvent> {
+        if !self.buffer.is_empty() {
+            let line = String::from_utf8_lossy(&std::mem::take(&mut self.buffer)).into_owned();
+            if let Some(event) = self.process_line(line.trim_end_matches('\r')) {
+                return Some(event);
+            }
+        }
+        self.dispatch()
+    }
+
+    fn process_line(&mut self, line: &str) -> Option<SseEvent> {
+        if line.is_empty() {
+            return self.dispatch();
+        }
+        if line.starts_with(':') {
+            return None;
+        }
+        let (field, value) = line.split_once(':').unwrap_or((line, ""));
+        let value = value.strip_prefix(' ').unwrap_or(value);
+        match field {
+            "event" => self.event = Some(value.to_string()),
+            "data" => self.data.push(value.to_string()),
+            _ => {}
+        }
+        None
+    }
+
+    fn dispatch(&mut self) -> Option<SseEvent> {
+        if self.data.is_empty() {
+            self.event = None;
+            return None;
+        }
+        Some(SseEvent {
+            event: self.event.take(),
+            data: std::mem::take(&mut self.data).join("\n"),
+        })
+    }
+}
+
+/// A stream that yields a single error.
+pub fn error_stream(
+    message: impl Into<String>,
+) -> stream::BoxStream<'static, StreamResult<StreamChunk>> {
+    let error = StreamError::Provider(message.into());
+    stream::once(async move { Err(error) }).boxed()
+}
+
+/// Send `request` and stream the text `extract` pulls out of each event.
+///
+/// `extract` returns `Ok(None)` for events without text (keep-alives, role
+/// headers, `[DONE]`) and an error to abort the stream. A final chunk is
+/// emitted once the body ends.
+pub fn stream_request<F>(
+    request: reqwest::RequestBuilder,
+    count_tokens: bool,
+    mut extract: F,
+) -> stream::BoxStream<'static, StreamResult<StreamChunk>>
+where
+    F: FnMut(&SseEvent) -> StreamResult<Option<String>> + Send + 'static,
+{
+    let (tx, rx) = tokio::sync::mpsc::channel::<StreamResult<StreamChunk>>(100);
+
+    tokio::spawn(async move {
+        let response = match request.header("Accept", "text/event-stream").send().await {
+            Ok(response) => response,
+            Err(e) => {
+                let _ = tx.send(Err(StreamError::Http(e))).await;
+                return;
+            }
+        };
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            let _ = tx
+                .send(Err(StreamError::Provider(format!(
+                    "{status}: {}",
+                    super::sanitize_api_error(&body)
+                ))))
+                .await;
+            return;
+        }
+
+        let mut decoder = SseDecoder::default();
+        let mut bytes = response.bytes_stream();
+        let mut pending = Vec::new();
+        loop {
+            for event in pending.drain(..) {
+                match extract(&event) {
+                    Ok(Some(text)) if !text.is_empty() => {
+                        let mut chunk = StreamChunk::delta(text);
+                        if count_tokens {
+                            chunk = chunk.with_token_estimate();
+                        }
+                        if tx.send(Ok(chunk)).await.is_err() {
+                            return; // Receiver dropped
+                        }
+                    }
+                    Ok(_) => {}
+                    Err(e) => {
+                        let _ = tx.send(Err(e)).await;
+                        return;
+                    }
+                }
+            }
+            match bytes.next().await {
+                Some(Ok(data)) => pending = decoder.push(&data),
+                Some(Err(e)) => {
+                    let _ = tx.send(Err(StreamError::Http(e))).await;
+                    return;
+                }
+                None => match decoder.finish() {
+                    Some(event) => pending = vec![event],
+                    None => break,
+                },
+            }
+        }
+
+        let _ = tx.send(Ok(StreamChunk::final_chunk())).await;
+    });
+
+    stream::unfold(rx, |mut rx| async move {
+        rx.recv().await.map(|chunk| (chunk, rx))
+    })
+    .boxed()
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatCompletionChunk {
+    #[serde(default)]
+    choices: Vec<ChatCompletionChoice>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatCompletionChoice {
+    delta: ChatCompletionDelta,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatCompletionDelta {
+    #[serde(default)]
+    content: Option<String>,
+    /// Reasoning/thinking models may stream output via `reasoning_content`.
+    #[serde(default)]
+    reasoning_content: Option<String>,
+}
+
+/// Extract the text delta from an OpenAI-style `chat.completion.chunk`
+/// event, handling the `[DONE]` sentinel.
+pub fn chat_completions_delta(event: &SseEvent) -> StreamResult<Option<String>> {
+    let data = event.data.trim();
+    if data.is_empty() || data == "[DONE]" {
+        return Ok(None);
+    }
+
+    let chunk: ChatCompletionChunk = serde_json::from_str(data).map_err(StreamError::Json)?;
+    let Some(choice) = chunk.choices.into_iter().next() else {
+        return Ok(None);
+    };
+    match choice.delta.content {
+        Some(content) if !content.is_empty() => Ok(Some(content)),
+        // Fallback to reasoning_content for thinking models
+        _ => Ok(choice.delta.reasoning_content),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn data(payload: &str) -> SseEvent {
+        SseEvent {
+            event: None,
+            data: payload.to_string(),
+        }
+    }
+
+    #[test]
+    fn decoder_buffers_partial_lines_and_utf8() {
+        let mut decoder = SseDecoder::default();
+        let payload = "event: delta\ndata: {\"t\":\"héllo\"}\n\n".as_bytes();
+        // Split inside the two-byte 'é'.
+        let split = payload.iter().position(|b| *b == 0xC3).unwrap() + 1;
+        assert!(decoder.push(&payload[..split]).is_empty());
+        let events = decoder.push(&payload[split..]);
+        assert_eq!(
+            events,
+            vec![SseEvent {
+                event: Some("delta".into()),
+                data: "{\"t\":\"héllo\"}".into(),
+            }]
+        );
+    }
+
+    #[test]
+    fn decoder_joins_data_lines_skips_comments_and_flushes_tail() {
+        let mut decoder = SseDecoder::default();
+        let events = decoder.push(b": keep-alive\r\ndata: a\r\ndata: b\r\n\r\ndata: tail");
+        assert_eq!(events, vec![data("a\nb")]);
+        assert_eq!(decoder.finish(), Some(data("tail")));
+        assert_eq!(decoder.finish(), None);
+    }
+
+    #[test]
+    fn chat_completions_delta_with_content() {
+        let event = data(r#"{"choices":[{"delta":{"content":"hello"}}]}"#);
+        assert_eq!(
+            chat_completions_delta(&event).unwrap(),
+            Some("hello".to_string())
+        );
+    }
+
+    #[test]
+    fn chat_completions_delta_with_reasoning_content() {
+        let event = data(r#"{"choices":[{"delta":{"reasoning_content":"thinking..."}}]}"#);
+        assert_eq!(
+            chat_completions_delta(&event).unwrap(),
+            Some("thinking...".to_string())
+        );
+    }
+
+    #[test]
+    fn chat_completions_delta_with_both_prefers_content() {
+        let event = data(
+            r#"{"choices":[{"delta":{"content":"real answer","reasoning_content":"thinking..."}}]}"#,
+        );
+        assert_eq!(
+            chat_completions_delta(&event).unwrap(),
+            Some("real answer".to_string())
+        );
+    }
+
+    #[test]
+    fn chat_completions_delta_with_empty_content_falls_back_to_reasoning_content() {
+        let event =
+            data(r#"{"choices":[{"delta":{"content":"","reasoning_content":"thinking..."}}]}"#);
+        assert_eq!(
+            chat_completions_delta(&event).unwrap(),
+            Some("thinking...".to_string())
+        );
+    }
+
+    #[test]
+    fn chat_completions_delta_done_sentinel_and_bad_json() {
+        assert_eq!(chat_completions_delta(&data("[DONE]")).unwrap(), None);
+        assert_eq!(
+            chat_completions_delta(&data(r#"{"choices":[]}"#)).unwrap(),
+            None
+        );
+        assert!(chat_completions_delta(&data("{not json")).is_err());
+    }
+}
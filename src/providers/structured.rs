@@ -0,0 +1,448 @@
+//! Schema-constrained JSON answers.
+//!
+//! [`chat_structured`] asks a provider for a reply matching a JSON schema and
+//! returns the parsed value. Providers that can constrain decoding
+//! server-side (OpenAI-style `response_format: json_schema`) override
+//! [`Provider::chat_with_schema`]; everywhere else the schema is described in
+//! the system prompt. Either way the reply is validated locally and, on a
+//! mismatch, the model is shown the error and asked again.
+
+use crate::providers::traits::{inject_tool_instructions, ChatMessage, Provider};
+use serde::Serialize;
+use serde_json::Value;
+
+/// Attempts made before giving up on a reply that does not match the schema.
+const MAX_ATTEMPTS: usize = 3;
+
+/// A named JSON schema the reply must satisfy. Serializes to the
+/// `json_schema` object of an OpenAI `response_format`.
+#[derive(Debug, Clone, Serialize)]
+pub struct JsonSchemaFormat {
+    pub name: String,
+    pub schema: Value,
+    /// Ask the backend for exact schema adherence. OpenAI only accepts
+    /// strict schemas that list every property in `required` and set
+    /// `additionalProperties: false`.
+    pub strict: bool,
+}
+
+impl JsonSchemaFormat {
+    pub fn new(name: impl Into<String>, schema: Value) -> Self {
+        Self {
+            name: name.into(),
+            schema,
+            strict: false,
+        }
+    }
+
+    pub fn strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+}
+
+/// OpenAI-style `response_format` request field.
+#[derive(Debug, Clone, Serialize)]
+pub struct ResponseFormat {
+    #[serde(rename = "type")]
+    kind: &'static str,
+    json_schema: JsonSchemaFormat,
+}
+
+impl ResponseFormat {
+    pub fn json_schema(format: &JsonSchemaFormat) -> Self {
+        Self {
+            kind: "json_schema",
+            json_schema: format.clone(),
+        }
+    }
+}
+
+/// System prompt addition for providers without native schema support.
+pub fn schema_instructions(format: &JsonSchemaFormat) -> String {
+    let schema = serde_json::to_string(&format.schema).unwrap_or_else(|_| "{}".to_string());
+    format!(
+        "## Response Format\n\n\
+         Reply with a single JSON value named `{}` that validates against this JSON schema, \
+         with no prose or code fences around it:\n\n{schema}",
+        format.name
+    )
+}
+
+/// `messages` with [`schema_instructions`] folded into the system prompt.
+pub fn prompt_guided_messages(
+    messages: &[ChatMessage],
+    format: &JsonSchemaFormat,
+) -> Vec<ChatMessage> {
+    inject_tool_instructions(messages, &schema_instructions(format))
+}
+
+/// Ask `provider` for a reply matching `format` and return it parsed.
+///
+/// Provider errors are returned as-is. Replies that are not valid JSON or do
+/// not match the schema are sent back with the validation error, up to
+/// [`MAX_ATTEMPTS`] requests in total.
+pub async fn chat_structured<P: Provider + ?Sized>(
+    provider: &P,
+    messages: &[ChatMessage],
+    format: &JsonSchemaFormat,
+    model: &str,
+    temperature: f64,
+) -> anyhow::Result<Value> {
+    let mut history = messages.to_vec();
+    let mut last_error = String::new();
+
+    for attempt in 1..=MAX_ATTEMPTS {
+        let reply = provider
+            .chat_with_schema(&history, format, model, temperature)
+            .await?;
+        match parse_and_validate(&reply, &format.schema) {
+            Ok(value) => return Ok(value),
+            Err(error) => {
+                tracing::debug!(
+                    schema = format.name.as_str(),
+                    attempt,
+                    "Structured reply rejected: {error}"
+                );
+                history.push(ChatMessage::assistant(reply));
+                history.push(ChatMessage::user(format!(
+                    "That reply does not match the required schema: {error}. \
+                     Reply again with only the corrected JSON."
+                )));
+                last_error = error;
+            }
+        }
+    }
+
+    anyhow::bail!(
+        "No reply matching schema `{}` after {MAX_ATTEMPTS} attempts: {last_error}",
+        format.name
+    )
+}
+
+/// Parse the JSON value in `reply` and check it against `schema`.
+pub fn parse_and_validate(reply: &str, schema: &Value) -> Result<Value, String> {
+    let value = extract_json(reply).ok_or_else(|| "reply is not valid JSON".to_string())?;
+    validate(&value, schema)?;
+    Ok(value)
+}
+
+/// The JSON value in `text`, tolerating code fences and surrounding prose.
+pub fn extract_json(text: &str) -> Option<Value> {
+    let trimmed = text.trim();
+    let unfenced = trimmed
+        .strip_prefix("```json")
+        .or_else(|| trimmed.strip_prefix("```"))
+        .and_then(|rest| rest.trim_end().strip_suffix("```"))
+        .map_or(trimmed, str::trim);
+    if let Ok(value) = serde_json::from_str(unfenced) {
+        return Some(value);
+    }
+
+    let start = unfenced.find(['{', '['])?;
+    let close = if unfenced[start..].starts_with('{') {
+        '}'
+    } else {
+        ']'
+    };
+    let end = unfenced.rfind(close)?;
+    serde_json::from_str(unfenced.get(start..=end)?).ok()
+}
+
+/// Check `value` against the commonly used subset of JSON Schema: `type`,
+/// `enum`, `const`, `properties`, `required`, `additionalProperties: false`,
+/// `items`, and length and range bounds. Unknown keywords are ignored.
+pub fn validate(value: &Value, schema: &Value) -> Result<(), String> {
+    validate_at(value, schema, "$")
+}
+
+fn validate_at(value: &Value, schema: &Value, path: &str) -> Result<(), String> {
+    let Some(schema) = schema.as_object() else {
+        return Ok(());
+    };
+
+    if let Some(expected) = schema.get("type") {
+        let allowed: Vec<&str> = match expected {
+            Value::String(kind) => vec![kind.as_str()],
+            Value::Array(kinds) => kinds.iter().filter_map(Value::as_str).collect(),
+            _ => Vec::new(),
+        };
+        if !allowed.is_empty() && !allowed.iter().any(|kind| type_matches(value, kind)) {
+            return Err(format!(
+                "{path} should be {}, got {}",
+                allowed.join(" or "),
+                type_name(value)
+            ));
+        }
+    }
+
+    if let Some(options) = schema.get("enum").and_then(Value::as_array) {
+        if !options.contains(value) {
+            return Err(format!(
+                "{path} should be one of {}",
+                Value::from(options.clone())
+            ));
+        }
+    }
+    if let Some(expected) = schema.get("const") {
+        if value != expected {
+            return Err(format!("{path} should be {expected}"));
+        }
+    }
+
+    match value {
+        Value::Object(map) => {
+            let properties = schema.get("properties").and_then(Value::as_object);
+            if let Some(required) = schema.get("required").and_then(Value::as_array) {
+                for key in required.iter().filter_map(Value::as_str) {
+                    if !map.contains_key(key) {
+                        return Err(format!("{path} is missing required field `{key}`"));
+                    }
+                }
+            }
+            for (key, field) in map {
+                let field_path = format!("{path}.{key}");
+                match properties.and_then(|properties| properties.get(key)) {
+                    Some(field_schema) => validate_at(field, field_schema, &field_path)?,
+                    None => match schema.get("additionalProperties") {
+                        Some(Value::Bool(false)) => {
+                            return Err(format!("{field_path} is not an allowed field"));
+                        }
+                        Some(extra @ Value::Object(_)) => validate_at(field, extra, &field_path)?,
+                        _ => {}
+                    },
+                }
+            }
+        }
+        Value::Array(items) => {
+            check_bound(path, "items", items.len(), schema)?;
+            if let Some(item_schema) = schema.get("items") {
+                for (index, item) in items.iter().enumerate() {
+                    validate_at(item, item_schema, &format!("{path}[{index}]"))?;
+                }
+            }
+        }
+        Value::String(text) => check_bound(path, "characters", text.chars().count(), schema)?,
+        Value::Number(number) => {
+            if let Some(n) = number.as_f64() {
+                if let Some(min) = schema.get("minimum").and_then(Value::as_f64) {
+                    if n < min {
+                        return Err(format!("{path} should be at least {min}"));
+                    }
+                }
+                if let Some(max) = schema.get("maximum").and_then(Value::as_f64) {
+                    if n > max {
+                        return Err(format!("{path} should be at most {max}"));
+                    }
+                }
+            }
+        }
+        _ => {}
+    }
+
+    Ok(())
+}
+
+/// Enforce `minItems`/`maxItems` or `minLength`/`maxLength`.
+fn check_bound(
+    path: &str,
+    unit: &str,
+    len: usize,
+    schema: &serde_json::Map<String, Value>,
+) -> Result<(), String> {
+    let (min_key, max_key) = if unit == "items" {
+        ("minItems", "maxItems")
+    } else {
+        ("minLength", "maxLength")
+    };
+    let bound = |key: &str| {
+        schema
+            .get(key)
+            .and_then(Value::as_u64)
+            .and_then(|n| usize::try_from(n).ok())
+    };
+    if let Some(min) = bound(min_key) {
+        if len < min {
+            return Err(format!("{path} should have at least {min} {unit}"));
+        }
+    }
+    if let Some(max) = bound(max_key) {
+        if len > max {
+            return Err(format!("{path} should have at most {max} {unit}"));
+        }
+    }
+    Ok(())
+}
+
+fn type_matches(value: &Value, kind: &str) -> bool {
+    match kind {
+        "object" => value.is_object(),
+        "array" => value.is_array(),
+        "string" => value.is_string(),
+        "number" => value.is_number(),
+        "integer" => value.is_i64() || value.is_u64(),
+        "boolean" => value.is_boolean(),
+        "null" => value.is_null(),
+        _ => true,
+    }
+}
+
+fn type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Object(_) => "object",
+        Value::Array(_) => "array",
+        Value::String(_) => "string",
+        Value::Number(_) => "number",
+        Value::Bool(_) => "boolean",
+        Value::Null => "null",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+    use serde_json::json;
+    use std::sync::Mutex;
+
+    fn verdict_schema() -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "verdict": {"type": "string", "enum": ["pass", "fail"]},
+                "score": {"type": "integer", "minimum": 0, "maximum": 10},
+                "notes": {"type": "array", "items": {"type": "string"}, "maxItems": 2}
+            },
+            "required": ["verdict", "score"],
+            "additionalProperties": false
+        })
+    }
+
+    #[test]
+    fn extract_json_strips_fences_and_prose() {
+        assert_eq!(extract_json(r#"{"a":1}"#), Some(json!({"a": 1})));
+        assert_eq!(
+            extract_json("```json\n{\"a\": 1}\n```"),
+            Some(json!({"a": 1}))
+        );
+        assert_eq!(
+            extract_json("Here you go: [1, 2] as requested."),
+            Some(json!([1, 2]))
+        );
+        assert_eq!(extract_json("no json here"), None);
+    }
+
+    #[test]
+    fn validate_reports_the_first_mismatch_with_its_path() {
+        let schema = verdict_schema();
+        assert!(validate(&json!({"verdict": "pass", "score": 7}), &schema).is_ok());
+
+        let cases = [
+            (json!({"verdict": "pass"}), "missing required field `score`"),
+            (
+                json!({"verdict": "maybe", "score": 1}),
+                "$.verdict should be one of",
+            ),
+            (
+                json!({"verdict": "pass", "score": 1.5}),
+                "$.score should be integer",
+            ),
+            (
+                json!({"verdict": "pass", "score": 11}),
+                "$.score should be at most 10",
+            ),
+            (
+                json!({"verdict": "fail", "score": 0, "extra": true}),
+                "$.extra is not an allowed field",
+            ),
+            (
+                json!({"verdict": "fail", "score": 0, "notes": ["a", 2]}),
+                "$.notes[1] should be string",
+            ),
+            (
+                json!({"verdict": "fail", "score": 0, "notes": ["a", "b", "c"]}),
+                "at most 2 items",
+            ),
+        ];
+        for (value, expected) in cases {
+            let error = validate(&value, &schema).unwrap_err();
+            assert!(
+                error.contains(expected),
+                "{error} should mention {expected}"
+            );
+        }
+    }
+
+    struct ScriptedProvider {
+        replies: Mutex<Vec<&'static str>>,
+        seen: Mutex<Vec<Vec<ChatMessage>>>,
+    }
+
+    #[async_trait]
+    impl Provider for ScriptedProvider {
+        async fn chat_with_system(
+            &self,
+            _system_prompt: Option<&str>,
+            _message: &str,
+            _model: &str,
+            _temperature: f64,
+        ) -> anyhow::Result<String> {
+            unreachable!("structured chat goes through chat_with_history")
+        }
+
+        async fn chat_with_history(
+            &self,
+            messages: &[ChatMessage],
+            _model: &str,
+            _temperature: f64,
+        ) -> anyhow::Result<String> {
+            self.seen.lock().unwrap().push(messages.to_vec());
+            Ok(self.replies.lock().unwrap().remove(0).to_string())
+        }
+    }
+
+    fn scripted(replies: Vec<&'static str>) -> ScriptedProvider {
+        ScriptedProvider {
+            replies: Mutex::new(replies),
+            seen: Mutex::new(Vec::new()),
+        }
+    }
+
+    #[tokio::test]
+    async fn fallback_describes_schema_and_retries_with_the_error() {
+        let provider = scripted(vec![
+            "Sure! The verdict is pass.",
+            r#"{"verdict": "pass", "score": 12}"#,
+            "```json\n{\"verdict\": \"pass\", \"score\": 8}\n```",
+        ]);
+        let format = JsonSchemaFormat::new("verdict", verdict_schema());
+        let value = chat_structured(
+            &provider,
+            &[ChatMessage::user("grade this")],
+            &format,
+            "m",
+            0.0,
+        )
+        .await
+        .unwrap();
+        assert_eq!(value, json!({"verdict": "pass", "score": 8}));
+
+        let seen = provider.seen.lock().unwrap();
+        assert_eq!(seen.len(), 3);
+        assert_eq!(seen[0][0].role, "system");
+        assert!(seen[0][0].content.contains("\"verdict\""));
+        let feedback = &seen[2].last().unwrap().content;
+        assert!(feedback.contains("$.score should be at most 10"));
+    }
+
+    #[tokio::test]
+    async fn gives_up_after_max_attempts() {
+        let provider = scripted(vec!["nope"; MAX_ATTEMPTS]);
+        let format = JsonSchemaFormat::new("verdict", verdict_schema());
+        let error = chat_structured(&provider, &[ChatMessage::user("grade")], &format, "m", 0.0)
+            .await
+            .unwrap_err();
+        assert!(error.to_string().contains("after 3 attempts"));
+        assert!(provider.replies.lock().unwrap().is_empty());
+    }
+}
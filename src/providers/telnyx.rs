@@ -67,30 +67,6 @@ impl TelnyxProvider {
         Self::new(api_key)
     }
 
-    /// List available models from Telnyx AI.
-    ///
-    /// Returns a list of model IDs that can be used with the chat API.
-    pub async fn list_models(&self) -> anyhow::Result<Vec<String>> {
-        let api_key = self.api_key.as_ref().ok_or_else(|| {
-            anyhow::anyhow!("Telnyx API key not set. Set TELNYX_API_KEY environment variable.")
-        })?;
-
-        let response = self
-            .client
-            .get(format!("{}/models", Self::BASE_URL))
-            .header("Authorization", format!("Bearer {}", api_key))
-            .send()
-            .await?;
-
-        if !response.status().is_success() {
-            let error = response.text().await?;
-            anyhow::bail!("Failed to list Telnyx models: {}", error);
-        }
-
-        let models_response: ModelsResponse = response.json().await?;
-        Ok(models_response.data.into_iter().map(|m| m.id).collect())
-    }
-
     /// Build the chat completions URL
     fn chat_url(&self) -> String {
         format!("{}/chat/completions", Self::BASE_URL)
@@ -278,6 +254,32 @@ impl Provider for TelnyxProvider {
             .ok_or_else(|| anyhow::anyhow!("No response from Telnyx"))
     }
 
+    /// List available models from Telnyx AI.
+    ///
+    /// Returns a list of model IDs that can be used with the chat API.
+    async fn list_models(&self) -> anyhow::Result<Vec<String>> {
+        let api_key = self.api_key.as_ref().ok_or_else(|| {
+            anyhow::anyhow!("Telnyx API key not set. Set TELNYX_API_KEY environment variable.")
+        })?;
+
+        let response = self
+            .client
+            .get(format!("{}/models", Self::BASE_URL))
+            .header("Authorization", format!("Bearer {}", api_key))
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            let error = response.text().await?;
+            anyhow::bail!("Failed to list Telnyx models: {}", error);
+        }
+
+        let models_response: ModelsResponse = response.json().await?;
+        let mut models: Vec<String> = models_response.data.into_iter().map(|m| m.id).collect();
+        models.sort();
+        Ok(models)
+    }
+
     async fn warmup(&self) -> anyhow::Result<()> {
         // Pre-warm the connection pool
         let _ = self
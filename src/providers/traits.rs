@@ -1,3 +1,4 @@
+use super::structured::JsonSchemaFormat;
 use crate::tools::ToolSpec;
 use async_trait::async_trait;
 use futures_util::{stream, StreamExt};
@@ -39,6 +40,75 @@ impl ChatMessage {
             content: content.into(),
         }
     }
+
+    /// User message with attached images. Images travel as `[IMAGE:<ref>]`
+    /// markers in `content`; vision-capable providers turn them into native
+    /// image parts.
+    pub fn user_with_images(text: impl Into<String>, images: &[ImagePart]) -> Self {
+        let references: Vec<String> = images.iter().map(ImagePart::reference).collect();
+        Self::user(crate::multimodal::compose_multimodal_message(
+            &text.into(),
+            &references,
+        ))
+    }
+
+    /// Images attached to this message. Markers that are neither data URIs
+    /// nor http(s) URLs (e.g. local paths not yet normalized) are skipped.
+    pub fn images(&self) -> Vec<ImagePart> {
+        crate::multimodal::parse_image_markers(&self.content)
+            .1
+            .iter()
+            .filter_map(|reference| ImagePart::from_reference(reference))
+            .collect()
+    }
+}
+
+/// An image attached to a chat message.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ImagePart {
+    /// Inline image data, base64-encoded.
+    Base64 { mime_type: String, data: String },
+    /// Image the provider fetches itself.
+    Url(String),
+}
+
+impl ImagePart {
+    pub fn base64(mime_type: impl Into<String>, data: impl Into<String>) -> Self {
+        Self::Base64 {
+            mime_type: mime_type.into(),
+            data: data.into(),
+        }
+    }
+
+    pub fn url(url: impl Into<String>) -> Self {
+        Self::Url(url.into())
+    }
+
+    /// Parse an `[IMAGE:...]` reference: a `data:<mime>;base64,<data>` URI or
+    /// an http(s) URL.
+    pub fn from_reference(reference: &str) -> Option<Self> {
+        let reference = reference.trim();
+        if let Some(rest) = reference.strip_prefix("data:") {
+            let (header, data) = rest.split_once(',')?;
+            let mime_type = header.strip_suffix(";base64")?;
+            if mime_type.is_empty() || data.trim().is_empty() {
+                return None;
+            }
+            return Some(Self::base64(mime_type, data.trim()));
+        }
+        if reference.starts_with("http://") || reference.starts_with("https://") {
+            return Some(Self::url(reference));
+        }
+        None
+    }
+
+    /// The reference carried in the message marker.
+    pub fn reference(&self) -> String {
+        match self {
+            Self::Base64 { mime_type, data } => format!("data:{mime_type};base64,{data}"),
+            Self::Url(url) => url.clone(),
+        }
+    }
 }
 
 /// A tool call requested by the LLM.
@@ -91,6 +161,13 @@ pub struct ChatRequest<'a> {
     pub tools: Option<&'a [ToolSpec]>,
 }
 
+impl ChatRequest<'_> {
+    /// Number of images attached to user messages.
+    pub fn image_count(&self) -> usize {
+        crate::multimodal::count_image_markers(self.messages)
+    }
+}
+
 /// A tool result to feed back to the LLM.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ToolResultMessage {
@@ -328,32 +405,22 @@ pub trait Provider: Send + Sync {
         model: &str,
         temperature: f64,
     ) -> anyhow::Result<ChatResponse> {
-        // If tools are provided but provider doesn't support native tools,
+        // If tools are provided but the model can't take them natively,
         // inject tool instructions into system prompt as fallback.
         if let Some(tools) = request.tools {
-            if !tools.is_empty() && !self.supports_native_tools() {
+            if !tools.is_empty() && !self.supports_native_tools_for_model(model) {
                 let tool_instructions = match self.convert_tools(tools) {
                     ToolsPayload::PromptGuided { instructions } => instructions,
+                    // Native provider, but this model takes the text protocol.
+                    _ if self.supports_native_tools() => build_tool_instructions_text(tools),
                     payload => {
                         anyhow::bail!(
                             "Provider returned non-prompt-guided tools payload ({payload:?}) while supports_native_tools() is false"
                         )
                     }
                 };
-                let mut modified_messages = request.messages.to_vec();
-
-                // Inject tool instructions into an existing system message.
-                // If none exists, prepend one to the conversation.
-                if let Some(system_message) =
-                    modified_messages.iter_mut().find(|m| m.role == "system")
-                {
-                    if !system_message.content.is_empty() {
-                        system_message.content.push_str("\n\n");
-                    }
-                    system_message.content.push_str(&tool_instructions);
-                } else {
-                    modified_messages.insert(0, ChatMessage::system(tool_instructions));
-                }
+                let modified_messages =
+                    inject_tool_instructions(request.messages, &tool_instructions);
 
                 let text = self
                     .chat_with_history(&modified_messages, model, temperature)
@@ -383,6 +450,14 @@ pub trait Provider: Send + Sync {
         self.capabilities().native_tool_calling
     }
 
+    /// Whether `model` should receive tools as structured function
+    /// definitions. Providers whose catalogs mix function-calling and plain
+    /// models override this so callers fall back to the text tool protocol
+    /// per model. Defaults to `supports_native_tools()`.
+    fn supports_native_tools_for_model(&self, _model: &str) -> bool {
+        self.supports_native_tools()
+    }
+
     /// Whether provider supports multimodal vision input.
     fn supports_vision(&self) -> bool {
         self.capabilities().vision
@@ -438,21 +513,187 @@ pub trait Provider: Send + Sync {
     /// Default implementation falls back to stream_chat_with_system with last user message.
     fn stream_chat_with_history(
         &self,
-        _messages: &[ChatMessage],
-        _model: &str,
-        _temperature: f64,
-        _options: StreamOptions,
+        messages: &[ChatMessage],
+        model: &str,
+        temperature: f64,
+        options: StreamOptions,
     ) -> stream::BoxStream<'static, StreamResult<StreamChunk>> {
-        // For default implementation, we need to convert to owned strings
-        // This is a limitation of the default implementation
-        let provider_name = "unknown".to_string();
+        let system = messages
+            .iter()
+            .find(|m| m.role == "system")
+            .map(|m| m.content.as_str());
+        let last_user = messages
+            .iter()
+            .rfind(|m| m.role == "user")
+            .map(|m| m.content.as_str())
+            .unwrap_or("");
+        self.stream_chat_with_system(system, last_user, model, temperature, options)
+    }
+
+    /// Multi-turn chat as a stream of text deltas ending with a final chunk.
+    ///
+    /// This is the entry point for streaming surfaces (gateway, FFI, JNI).
+    /// Streams token-by-token when the provider supports it and
+    /// `options.enabled` is set; otherwise the complete `chat_with_history`
+    /// reply is emitted as a single delta.
+    fn chat_stream<'a>(
+        &'a self,
+        messages: &'a [ChatMessage],
+        model: &'a str,
+        temperature: f64,
+        options: StreamOptions,
+    ) -> stream::BoxStream<'a, StreamResult<StreamChunk>> {
+        if options.enabled && self.supports_streaming() {
+            return self.stream_chat_with_history(messages, model, temperature, options);
+        }
+
+        stream::once(async move {
+            self.chat_with_history(messages, model, temperature)
+                .await
+                .map_err(|e| StreamError::Provider(e.to_string()))
+        })
+        .flat_map(move |result| {
+            let chunks = match result {
+                Ok(text) => {
+                    let mut chunk = StreamChunk::delta(text);
+                    if options.count_tokens {
+                        chunk = chunk.with_token_estimate();
+                    }
+                    vec![Ok(chunk), Ok(StreamChunk::final_chunk())]
+                }
+                Err(e) => vec![Err(e)],
+            };
+            stream::iter(chunks)
+        })
+        .boxed()
+    }
+
+    /// Whether [`Provider::embed`] is implemented.
+    /// Default implementation returns false.
+    fn supports_embeddings(&self) -> bool {
+        false
+    }
+
+    /// Embed a batch of texts with `model`, returning one vector per input
+    /// in input order. Default implementation fails.
+    async fn embed(&self, _texts: &[&str], _model: &str) -> anyhow::Result<Vec<Vec<f32>>> {
+        anyhow::bail!("This provider does not support embeddings")
+    }
+
+    /// Model IDs the backend currently offers, for model pickers.
+    /// Default implementation fails.
+    async fn list_models(&self) -> anyhow::Result<Vec<String>> {
+        anyhow::bail!("This provider does not support model listing")
+    }
 
-        // Create a single empty chunk to indicate not supported
-        let chunk = StreamChunk::error(format!("{} does not support streaming", provider_name));
-        stream::once(async move { Ok(chunk) }).boxed()
+    /// Whether [`Provider::chat_with_schema`] constrains decoding to the
+    /// schema server-side. Default implementation returns false.
+    fn supports_structured_output(&self) -> bool {
+        false
+    }
+
+    /// One request for a reply matching `format`, returned as raw text.
+    /// Default implementation describes the schema in the system prompt.
+    ///
+    /// Callers should go through [`chat_structured`](super::structured::chat_structured),
+    /// which validates the reply and retries on a mismatch.
+    async fn chat_with_schema(
+        &self,
+        messages: &[ChatMessage],
+        format: &JsonSchemaFormat,
+        model: &str,
+        temperature: f64,
+    ) -> anyhow::Result<String> {
+        let messages = super::structured::prompt_guided_messages(messages, format);
+        self.chat_with_history(&messages, model, temperature).await
     }
 }
 
+/// Parse an OpenAI-style `/embeddings` response body, ordering vectors by
+/// their `index` so they line up with the request inputs.
+pub fn parse_openai_embeddings(body: &serde_json::Value) -> anyhow::Result<Vec<Vec<f32>>> {
+    let data = body
+        .get("data")
+        .and_then(serde_json::Value::as_array)
+        .ok_or_else(|| anyhow::anyhow!("Invalid embedding response: missing 'data'"))?;
+
+    let mut indexed = Vec::with_capacity(data.len());
+    for (position, item) in data.iter().enumerate() {
+        let values = item
+            .get("embedding")
+            .and_then(serde_json::Value::as_array)
+            .ok_or_else(|| anyhow::anyhow!("Invalid embedding item"))?;
+        let index = item
+            .get("index")
+            .and_then(serde_json::Value::as_u64)
+            .and_then(|index| usize::try_from(index).ok())
+            .unwrap_or(position);
+        #[allow(clippy::cast_possible_truncation)]
+        let vector: Vec<f32> = values
+            .iter()
+            .filter_map(|v| v.as_f64().map(|f| f as f32))
+            .collect();
+        indexed.push((index, vector));
+    }
+    indexed.sort_by_key(|(index, _)| *index);
+    Ok(indexed.into_iter().map(|(_, vector)| vector).collect())
+}
+
+/// Model IDs from an OpenAI-style `/models` response (`{"data": [{"id"}]}`
+/// or a bare array), deduplicated and sorted.
+pub fn parse_openai_model_ids(body: &serde_json::Value) -> Vec<String> {
+    let entries = body
+        .get("data")
+        .and_then(serde_json::Value::as_array)
+        .or_else(|| body.as_array());
+    let mut ids: Vec<String> = entries
+        .into_iter()
+        .flatten()
+        .filter_map(|entry| entry.get("id").and_then(serde_json::Value::as_str))
+        .map(str::trim)
+        .filter(|id| !id.is_empty())
+        .map(str::to_string)
+        .collect();
+    ids.sort();
+    ids.dedup();
+    ids
+}
+
+/// Fold prompt-guided tool instructions into the first system message,
+/// prepending one when the conversation has none.
+pub fn inject_tool_instructions(messages: &[ChatMessage], instructions: &str) -> Vec<ChatMessage> {
+    let mut modified = messages.to_vec();
+    if let Some(system_message) = modified.iter_mut().find(|m| m.role == "system") {
+        if !system_message.content.is_empty() {
+            system_message.content.push_str("\n\n");
+        }
+        system_message.content.push_str(instructions);
+    } else {
+        modified.insert(0, ChatMessage::system(instructions));
+    }
+    modified
+}
+
+/// History to send instead of `request` when it carries tools that `model`
+/// cannot take natively: the tools are rendered into the system prompt using
+/// the text protocol. Returns `None` when the request can go out as-is.
+///
+/// For providers that override `chat()` with a native tool path.
+pub fn prompt_guided_messages<P: Provider + ?Sized>(
+    provider: &P,
+    request: &ChatRequest<'_>,
+    model: &str,
+) -> Option<Vec<ChatMessage>> {
+    let tools = request.tools.filter(|tools| !tools.is_empty())?;
+    if provider.supports_native_tools_for_model(model) {
+        return None;
+    }
+    Some(inject_tool_instructions(
+        request.messages,
+        &build_tool_instructions_text(tools),
+    ))
+}
+
 /// Build tool instructions text for prompt-guided tool calling.
 ///
 /// Generates a formatted text block describing available tools and how to
@@ -528,6 +769,36 @@ mod tests {
         assert_eq!(tool.role, "tool");
     }
 
+    #[test]
+    fn user_with_images_round_trips_image_parts() {
+        let images = [
+            ImagePart::base64("image/png", "iVBORw0KGgo="),
+            ImagePart::url("https://example.com/cat.jpg"),
+        ];
+        let message = ChatMessage::user_with_images("What is this?", &images);
+        assert_eq!(message.role, "user");
+        assert!(message.content.starts_with("What is this?"));
+        assert_eq!(message.images(), images);
+
+        let messages = [ChatMessage::system("sys"), message];
+        let request = ChatRequest {
+            messages: &messages,
+            tools: None,
+        };
+        assert_eq!(request.image_count(), 2);
+    }
+
+    #[test]
+    fn image_part_rejects_unsupported_references() {
+        assert!(ImagePart::from_reference("/tmp/photo.png").is_none());
+        assert!(ImagePart::from_reference("data:image/png,abc").is_none());
+        assert!(ImagePart::from_reference("data:;base64,abc").is_none());
+        assert_eq!(
+            ImagePart::from_reference(" data:image/jpeg;base64,/9j/ ").unwrap(),
+            ImagePart::base64("image/jpeg", "/9j/")
+        );
+    }
+
     #[test]
     fn chat_response_helpers() {
         let empty = ChatResponse {
@@ -575,6 +846,29 @@ mod tests {
         assert_eq!(resp.usage.as_ref().unwrap().output_tokens, Some(50));
     }
 
+    #[test]
+    fn parse_openai_model_ids_accepts_data_and_bare_arrays() {
+        let body = serde_json::json!({
+            "data": [
+                {"id": "gpt-4o"},
+                {"id": " gpt-4o-mini "},
+                {"id": "gpt-4o"},
+                {"object": "model"}
+            ]
+        });
+        assert_eq!(parse_openai_model_ids(&body), vec!["gpt-4o", "gpt-4o-mini"]);
+
+        let bare = serde_json::json!([{"id": "llama-3"}]);
+        assert_eq!(parse_openai_model_ids(&bare), vec!["llama-3"]);
+        assert!(parse_openai_model_ids(&serde_json::json!({})).is_empty());
+    }
+
+    #[tokio::test]
+    async fn list_models_is_unsupported_by_default() {
+        let err = CapabilityMockProvider.list_models().await.unwrap_err();
+        assert!(err.to_string().contains("model listing"));
+    }
+
     #[test]
     fn tool_call_serialization() {
         let tc = ToolCall {
@@ -948,4 +1242,102 @@ mod tests {
 
         assert!(message.contains("non-prompt-guided"));
     }
+
+    #[tokio::test]
+    async fn chat_stream_falls_back_to_single_delta() {
+        let provider = EchoSystemProvider {
+            supports_native: false,
+        };
+        let messages = [ChatMessage::system("SYSTEM"), ChatMessage::user("Hello")];
+
+        for enabled in [true, false] {
+            let chunks: Vec<_> = provider
+                .chat_stream(&messages, "model", 0.7, StreamOptions::new(enabled))
+                .collect()
+                .await;
+            assert_eq!(chunks.len(), 2);
+            let first = chunks[0].as_ref().unwrap();
+            assert_eq!(first.delta, "SYSTEM");
+            assert!(!first.is_final);
+            assert!(chunks[1].as_ref().unwrap().is_final);
+        }
+    }
+
+    // Native provider whose "plain-*" models lack function calling.
+    struct PerModelProvider;
+
+    #[async_trait]
+    impl Provider for PerModelProvider {
+        fn supports_native_tools(&self) -> bool {
+            true
+        }
+
+        fn supports_native_tools_for_model(&self, model: &str) -> bool {
+            !model.starts_with("plain-")
+        }
+
+        fn convert_tools(&self, _tools: &[ToolSpec]) -> ToolsPayload {
+            ToolsPayload::OpenAI { tools: Vec::new() }
+        }
+
+        async fn chat_with_system(
+            &self,
+            system: Option<&str>,
+            _message: &str,
+            _model: &str,
+            _temperature: f64,
+        ) -> anyhow::Result<String> {
+            Ok(system.unwrap_or_default().to_string())
+        }
+    }
+
+    #[tokio::test]
+    async fn native_provider_uses_text_protocol_for_models_without_tools() {
+        let tools = vec![ToolSpec {
+            name: "shell".to_string(),
+            description: "Run commands".to_string(),
+            parameters: serde_json::json!({"type": "object"}),
+        }];
+        let messages = [ChatMessage::system("BASE"), ChatMessage::user("Hello")];
+        let request = ChatRequest {
+            messages: &messages,
+            tools: Some(&tools),
+        };
+
+        assert!(prompt_guided_messages(&PerModelProvider, &request, "native-1").is_none());
+        let guided = prompt_guided_messages(&PerModelProvider, &request, "plain-1").unwrap();
+        assert_eq!(guided.len(), 2);
+        assert!(guided[0]
+            .content
+            .starts_with("BASE\n\n## Tool Use Protocol"));
+
+        let response = PerModelProvider
+            .chat(request, "plain-1", 0.7)
+            .await
+            .unwrap();
+        assert!(response.text.unwrap_or_default().contains("shell"));
+    }
+
+    #[tokio::test]
+    async fn embeddings_are_unsupported_by_default() {
+        let provider = CapabilityMockProvider;
+        assert!(!provider.supports_embeddings());
+        let err = provider.embed(&["hello"], "model").await.unwrap_err();
+        assert!(err.to_string().contains("does not support embeddings"));
+    }
+
+    #[test]
+    fn parse_openai_embeddings_orders_by_index() {
+        let body = serde_json::json!({
+            "data": [
+                { "index": 1, "embedding": [0.5, 0.25] },
+                { "index": 0, "embedding": [1.0, 0.0] }
+            ]
+        });
+        let vectors = parse_openai_embeddings(&body).unwrap();
+        assert_eq!(vectors, vec![vec![1.0, 0.0], vec![0.5, 0.25]]);
+
+        assert!(parse_openai_embeddings(&serde_json::json!({ "error": "bad" })).is_err());
+        assert!(parse_openai_embeddings(&serde_json::json!({ "data": [{}] })).is_err());
+    }
 }
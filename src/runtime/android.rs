@@ -1,7 +1,10 @@
 use super::traits::RuntimeAdapter;
-use crate::config::AndroidRuntimeConfig;
+use crate::config::{AndroidRuntimeConfig, AndroidStorageLocation};
 use std::path::{Path, PathBuf};
 
+/// Root of the primary shared storage volume on Android.
+const SHARED_STORAGE_ROOT: &str = "/storage/emulated/0";
+
 /// Android runtime adapter for app-hosted deployments.
 #[derive(Debug, Clone)]
 pub struct AndroidRuntime {
@@ -16,6 +19,82 @@ impl AndroidRuntime {
     fn default_storage_path() -> PathBuf {
         PathBuf::from("/data/user/0/com.zeroclaw.app/files/zeroclaw")
     }
+
+    fn default_external_storage_path() -> PathBuf {
+        PathBuf::from(SHARED_STORAGE_ROOT).join("Android/data/com.zeroclaw.app/files/zeroclaw")
+    }
+
+    fn internal_storage_path(&self) -> PathBuf {
+        self.config
+            .app_data_dir
+            .as_ref()
+            .map_or_else(Self::default_storage_path, PathBuf::from)
+    }
+
+    fn external_storage_path(&self) -> PathBuf {
+        self.config
+            .external_files_dir
+            .as_ref()
+            .map_or_else(Self::default_external_storage_path, PathBuf::from)
+    }
+
+    /// Storage locations in preference order; the last entry is the fallback.
+    fn storage_candidates(&self) -> Vec<PathBuf> {
+        match self.config.storage_location {
+            AndroidStorageLocation::Internal => vec![self.internal_storage_path()],
+            AndroidStorageLocation::External => {
+                vec![self.external_storage_path(), self.internal_storage_path()]
+            }
+        }
+    }
+
+    /// Resolve the directory for a shared media collection name.
+    fn media_collection_dir(name: &str) -> Option<PathBuf> {
+        let dir = match name.trim().to_ascii_lowercase().as_str() {
+            "pictures" | "images" => "Pictures",
+            "music" | "audio" => "Music",
+            "movies" | "video" => "Movies",
+            "documents" => "Documents",
+            "download" | "downloads" => "Download",
+            "dcim" => "DCIM",
+            _ => return None,
+        };
+        Some(PathBuf::from(SHARED_STORAGE_ROOT).join(dir))
+    }
+
+    /// Shared media collection directories the app is currently permitted to read.
+    ///
+    /// Unknown collection names and directories that cannot be listed (missing
+    /// `READ_MEDIA_*` / storage permission, or volume unmounted) are skipped.
+    pub fn media_collection_paths(&self) -> Vec<PathBuf> {
+        self.config
+            .media_collections
+            .iter()
+            .filter_map(|name| {
+                let dir = Self::media_collection_dir(name);
+                if dir.is_none() {
+                    tracing::warn!("Ignoring unknown android media collection '{name}'");
+                }
+                dir
+            })
+            .filter(|dir| is_readable_dir(dir))
+            .collect()
+    }
+}
+
+/// A directory is usable for storage when it exists (or can be created) and
+/// is not read-only.
+fn is_usable_storage_dir(path: &Path) -> bool {
+    if std::fs::create_dir_all(path).is_err() {
+        return false;
+    }
+    std::fs::metadata(path).is_ok_and(|meta| meta.is_dir() && !meta.permissions().readonly())
+}
+
+/// Listing a directory is the cheapest reliable permission probe on Android:
+/// it fails with `EACCES` when the storage permission has not been granted.
+fn is_readable_dir(path: &Path) -> bool {
+    std::fs::read_dir(path).is_ok()
 }
 
 impl RuntimeAdapter for AndroidRuntime {
@@ -32,16 +111,46 @@ impl RuntimeAdapter for AndroidRuntime {
     }
 
     fn storage_path(&self) -> PathBuf {
-        self.config
-            .app_data_dir
-            .as_ref()
-            .map_or_else(Self::default_storage_path, PathBuf::from)
+        let candidates = self.storage_candidates();
+        let (fallback, preferred) = candidates
+            .split_last()
+            .expect("storage candidates are never empty");
+        for candidate in preferred {
+            if is_usable_storage_dir(candidate) {
+                return candidate.clone();
+            }
+            tracing::warn!(
+                "Android storage path {} is unavailable; falling back",
+                candidate.display()
+            );
+        }
+        fallback.clone()
     }
 
     fn supports_long_running(&self) -> bool {
         self.config.use_foreground_service
     }
 
+    fn is_background_restricted(&self) -> bool {
+        super::power::background_state().restricted
+    }
+
+    fn next_allowed_wakeup(&self) -> Option<chrono::DateTime<chrono::Utc>> {
+        let state = super::power::background_state();
+        state
+            .restricted
+            .then_some(state.next_allowed_wakeup)
+            .flatten()
+    }
+
+    fn connection_type(&self) -> super::network::ConnectionType {
+        super::network::network_state().connection
+    }
+
+    fn is_metered(&self) -> bool {
+        super::network::network_state().metered
+    }
+
     fn build_shell_command(
         &self,
         _command: &str,
@@ -71,4 +180,45 @@ mod tests {
         let storage = runtime.storage_path();
         assert!(storage.to_string_lossy().contains("zeroclaw"));
     }
+
+    #[test]
+    fn android_runtime_uses_writable_external_dir() {
+        let tmp = tempfile::tempdir().unwrap();
+        let external = tmp.path().join("external");
+        let runtime = AndroidRuntime::new(AndroidRuntimeConfig {
+            app_data_dir: Some(tmp.path().join("internal").display().to_string()),
+            storage_location: AndroidStorageLocation::External,
+            external_files_dir: Some(external.display().to_string()),
+            ..AndroidRuntimeConfig::default()
+        });
+        assert_eq!(runtime.storage_path(), external);
+    }
+
+    #[test]
+    fn android_runtime_falls_back_to_internal_when_external_unavailable() {
+        let tmp = tempfile::tempdir().unwrap();
+        let blocker = tmp.path().join("not-a-dir");
+        std::fs::write(&blocker, "file").unwrap();
+        let internal = tmp.path().join("internal");
+        let runtime = AndroidRuntime::new(AndroidRuntimeConfig {
+            app_data_dir: Some(internal.display().to_string()),
+            storage_location: AndroidStorageLocation::External,
+            external_files_dir: Some(blocker.join("zeroclaw").display().to_string()),
+            ..AndroidRuntimeConfig::default()
+        });
+        assert_eq!(runtime.storage_path(), internal);
+    }
+
+    #[test]
+    fn android_runtime_skips_unknown_media_collections() {
+        let runtime = AndroidRuntime::new(AndroidRuntimeConfig {
+            media_collections: vec!["holograms".into()],
+            ..AndroidRuntimeConfig::default()
+        });
+        assert!(runtime.media_collection_paths().is_empty());
+        assert_eq!(
+            AndroidRuntime::media_collection_dir("Downloads"),
+            Some(PathBuf::from("/storage/emulated/0/Download"))
+        );
+    }
 }
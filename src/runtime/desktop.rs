@@ -0,0 +1,122 @@
+use super::traits::RuntimeAdapter;
+use std::path::{Path, PathBuf};
+
+/// Marker files present inside the ChromeOS Crostini (Linux development
+/// environment) container.
+const CROSTINI_MARKERS: &[&str] = &["/dev/.cros_milestone", "/opt/google/cros-containers"];
+
+/// Desktop runtime — ChromeOS Crostini containers and desktop Linux sessions.
+///
+/// Grants shell and full filesystem access like the native runtime, but keeps
+/// persistent state in XDG-compliant locations so developers can test the
+/// same binary on a desktop before deploying it to a phone.
+#[derive(Debug, Clone)]
+pub struct DesktopRuntime {
+    crostini: bool,
+}
+
+impl DesktopRuntime {
+    pub fn new() -> Self {
+        Self {
+            crostini: Self::is_crostini(),
+        }
+    }
+
+    /// Return `true` when running inside the ChromeOS Linux container.
+    pub fn is_crostini() -> bool {
+        cfg!(target_os = "linux") && CROSTINI_MARKERS.iter().any(|m| Path::new(m).exists())
+    }
+
+    /// Resolve `$XDG_DATA_HOME/zeroclaw`, defaulting to `~/.local/share/zeroclaw`.
+    fn xdg_storage_path(xdg_data_home: Option<String>, home: Option<PathBuf>) -> PathBuf {
+        let base = xdg_data_home
+            .map(PathBuf::from)
+            .filter(|p| p.is_absolute())
+            .or_else(|| home.map(|h| h.join(".local").join("share")))
+            .unwrap_or_else(|| PathBuf::from(".local/share"));
+        base.join("zeroclaw")
+    }
+}
+
+impl RuntimeAdapter for DesktopRuntime {
+    fn name(&self) -> &str {
+        if self.crostini {
+            "crostini"
+        } else {
+            "desktop"
+        }
+    }
+
+    fn has_shell_access(&self) -> bool {
+        true
+    }
+
+    fn has_filesystem_access(&self) -> bool {
+        true
+    }
+
+    fn storage_path(&self) -> PathBuf {
+        Self::xdg_storage_path(
+            std::env::var("XDG_DATA_HOME").ok(),
+            directories::UserDirs::new().map(|u| u.home_dir().to_path_buf()),
+        )
+    }
+
+    fn supports_long_running(&self) -> bool {
+        true
+    }
+
+    fn build_shell_command(
+        &self,
+        command: &str,
+        workspace_dir: &Path,
+    ) -> anyhow::Result<tokio::process::Command> {
+        let mut process = tokio::process::Command::new("sh");
+        process.arg("-c").arg(command).current_dir(workspace_dir);
+        Ok(process)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn desktop_reports_full_capabilities() {
+        let rt = DesktopRuntime { crostini: false };
+        assert_eq!(rt.name(), "desktop");
+        assert!(rt.has_shell_access());
+        assert!(rt.has_filesystem_access());
+        assert!(rt.supports_long_running());
+    }
+
+    #[test]
+    fn crostini_name() {
+        assert_eq!(DesktopRuntime { crostini: true }.name(), "crostini");
+    }
+
+    #[test]
+    fn xdg_data_home_takes_precedence() {
+        let path = DesktopRuntime::xdg_storage_path(
+            Some("/data/xdg".into()),
+            Some(PathBuf::from("/home/dev")),
+        );
+        assert_eq!(path, PathBuf::from("/data/xdg/zeroclaw"));
+    }
+
+    #[test]
+    fn relative_xdg_data_home_is_ignored() {
+        let path =
+            DesktopRuntime::xdg_storage_path(Some("rel".into()), Some(PathBuf::from("/home/dev")));
+        assert_eq!(path, PathBuf::from("/home/dev/.local/share/zeroclaw"));
+    }
+
+    #[test]
+    fn desktop_builds_shell_command() {
+        let cwd = std::env::temp_dir();
+        let command = DesktopRuntime { crostini: false }
+            .build_shell_command("echo hello", &cwd)
+            .unwrap();
+        assert!(format!("{command:?}").contains("echo hello"));
+    }
+}
@@ -0,0 +1,172 @@
+//! Startup runtime detection.
+//!
+//! `runtime.kind = "auto"` (the default) inspects the host environment and
+//! picks the matching [`RuntimeAdapter`], so the same binary gets correct
+//! capabilities whether it is loaded by the Android app over JNI, run from a
+//! Termux shell, or started on a developer desktop.
+
+use super::{AndroidRuntime, DesktopRuntime, NativeRuntime, RuntimeAdapter};
+use crate::config::RuntimeConfig;
+use std::path::Path;
+
+/// Host platform classification used to select a runtime adapter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DetectedPlatform {
+    /// App-hosted Android process (no shell, scoped storage).
+    Android,
+    /// Termux on Android — a full Linux userland with shell access.
+    Termux,
+    /// iOS app bundle.
+    Ios,
+    /// ChromeOS Crostini container or graphical desktop Linux session.
+    Desktop,
+    /// Anything else (servers, macOS, Raspberry Pi, containers).
+    Native,
+}
+
+/// Classify the current process environment.
+pub fn detect_platform() -> DetectedPlatform {
+    detect_platform_with(
+        std::env::consts::OS,
+        &|name| std::env::var(name).ok().filter(|v| !v.is_empty()),
+        &|path| Path::new(path).exists(),
+    )
+}
+
+/// Testable core of [`detect_platform`].
+fn detect_platform_with(
+    target_os: &str,
+    env: &dyn Fn(&str) -> Option<String>,
+    path_exists: &dyn Fn(&str) -> bool,
+) -> DetectedPlatform {
+    let termux = env("TERMUX_VERSION").is_some()
+        || env("PREFIX").is_some_and(|prefix| prefix.contains("/com.termux/"));
+    if termux {
+        return DetectedPlatform::Termux;
+    }
+
+    let android = target_os == "android"
+        || (env("ANDROID_ROOT").is_some() && env("ANDROID_DATA").is_some())
+        || path_exists("/system/build.prop");
+    if android {
+        return DetectedPlatform::Android;
+    }
+
+    if target_os == "ios" || env("SIMULATOR_DEVICE_NAME").is_some() {
+        return DetectedPlatform::Ios;
+    }
+
+    if target_os == "linux" {
+        let crostini =
+            path_exists("/dev/.cros_milestone") || path_exists("/opt/google/cros-containers");
+        let desktop = ["XDG_CURRENT_DESKTOP", "WAYLAND_DISPLAY", "DISPLAY"]
+            .iter()
+            .any(|var| env(var).is_some());
+        if crostini || desktop {
+            return DetectedPlatform::Desktop;
+        }
+    }
+
+    DetectedPlatform::Native
+}
+
+/// Pick the runtime adapter for the current environment.
+pub fn detect(config: &RuntimeConfig) -> anyhow::Result<Box<dyn RuntimeAdapter>> {
+    let platform = detect_platform();
+    tracing::info!("Auto-detected runtime platform: {platform:?}");
+    adapter_for(platform, config)
+}
+
+fn adapter_for(
+    platform: DetectedPlatform,
+    config: &RuntimeConfig,
+) -> anyhow::Result<Box<dyn RuntimeAdapter>> {
+    match platform {
+        DetectedPlatform::Android => Ok(Box::new(AndroidRuntime::new(config.android.clone()))),
+        DetectedPlatform::Termux | DetectedPlatform::Native => Ok(Box::new(NativeRuntime::new())),
+        DetectedPlatform::Desktop => Ok(Box::new(DesktopRuntime::new())),
+        DetectedPlatform::Ios => anyhow::bail!(
+            "iOS was detected but no iOS runtime adapter exists yet. Set runtime.kind explicitly."
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn detect_with(target_os: &str, vars: &[(&str, &str)], paths: &[&str]) -> DetectedPlatform {
+        let vars: HashMap<String, String> = vars
+            .iter()
+            .map(|(k, v)| ((*k).to_string(), (*v).to_string()))
+            .collect();
+        detect_platform_with(target_os, &|name| vars.get(name).cloned(), &|path| {
+            paths.contains(&path)
+        })
+    }
+
+    #[test]
+    fn android_target_is_android() {
+        assert_eq!(detect_with("android", &[], &[]), DetectedPlatform::Android);
+    }
+
+    #[test]
+    fn android_props_on_linux_target_are_android() {
+        assert_eq!(
+            detect_with(
+                "linux",
+                &[("ANDROID_ROOT", "/system"), ("ANDROID_DATA", "/data")],
+                &[]
+            ),
+            DetectedPlatform::Android
+        );
+    }
+
+    #[test]
+    fn termux_prefix_wins_over_android() {
+        assert_eq!(
+            detect_with(
+                "android",
+                &[("PREFIX", "/data/data/com.termux/files/usr")],
+                &["/system/build.prop"]
+            ),
+            DetectedPlatform::Termux
+        );
+    }
+
+    #[test]
+    fn crostini_marker_is_desktop() {
+        assert_eq!(
+            detect_with("linux", &[], &["/dev/.cros_milestone"]),
+            DetectedPlatform::Desktop
+        );
+    }
+
+    #[test]
+    fn headless_linux_is_native() {
+        assert_eq!(detect_with("linux", &[], &[]), DetectedPlatform::Native);
+        assert_eq!(
+            detect_with("macos", &[("DISPLAY", ":0")], &[]),
+            DetectedPlatform::Native
+        );
+    }
+
+    #[test]
+    fn ios_is_reported_and_rejected() {
+        assert_eq!(detect_with("ios", &[], &[]), DetectedPlatform::Ios);
+        assert!(adapter_for(DetectedPlatform::Ios, &RuntimeConfig::default()).is_err());
+    }
+
+    #[test]
+    fn adapters_match_platform_capabilities() {
+        let cfg = RuntimeConfig::default();
+        let android = adapter_for(DetectedPlatform::Android, &cfg).unwrap();
+        assert_eq!(android.name(), "android");
+        assert!(!android.has_shell_access());
+
+        let termux = adapter_for(DetectedPlatform::Termux, &cfg).unwrap();
+        assert_eq!(termux.name(), "native");
+        assert!(termux.has_shell_access());
+    }
+}
@@ -0,0 +1,260 @@
+//! Geofences registered with the host's platform geofencing API.
+//!
+//! The `android_geofence` tool persists each fence here and asks the host to
+//! register it. When the host reports an enter/exit transition over JNI, the
+//! fence's prompt is scheduled as a one-shot agent cron job so it runs
+//! through the normal scheduler (and its delivery) a few seconds later.
+
+use crate::config::Config;
+use crate::cron::{self, CronJob, DeliveryConfig, Schedule, SessionTarget};
+use crate::tools::android_bridge::AndroidBridge;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+const STATE_FILE: &str = "geofences.json";
+/// Delay between the transition and the scheduled prompt run.
+const TRIGGER_DELAY_SECS: i64 = 5;
+const ANDROID_REMINDER_CHANNEL: &str = "reminders";
+
+/// Serializes read-modify-write cycles on the state file.
+static STORE_LOCK: Mutex<()> = Mutex::new(());
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum GeofenceTransition {
+    Enter,
+    Exit,
+}
+
+impl GeofenceTransition {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Enter => "enter",
+            Self::Exit => "exit",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Geofence {
+    pub id: String,
+    pub name: String,
+    pub latitude: f64,
+    pub longitude: f64,
+    pub radius_m: f64,
+    pub transitions: Vec<GeofenceTransition>,
+    /// Prompt handed to the agent when the fence triggers.
+    pub prompt: String,
+    /// Remove the fence after its first trigger.
+    #[serde(default)]
+    pub once: bool,
+    pub created_at: DateTime<Utc>,
+}
+
+/// JSON payload sent by the host when a fence triggers.
+#[derive(Debug, Deserialize)]
+struct EventPayload {
+    id: String,
+    transition: GeofenceTransition,
+}
+
+fn state_path(workspace_dir: &Path) -> PathBuf {
+    workspace_dir.join("state").join(STATE_FILE)
+}
+
+fn read_all(workspace_dir: &Path) -> anyhow::Result<Vec<Geofence>> {
+    let path = state_path(workspace_dir);
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let raw = std::fs::read_to_string(&path)?;
+    if raw.trim().is_empty() {
+        return Ok(Vec::new());
+    }
+    Ok(serde_json::from_str(&raw)?)
+}
+
+fn write_all(workspace_dir: &Path, fences: &[Geofence]) -> anyhow::Result<()> {
+    let path = state_path(workspace_dir);
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(&path, serde_json::to_string_pretty(fences)?)?;
+    Ok(())
+}
+
+pub fn list(workspace_dir: &Path) -> anyhow::Result<Vec<Geofence>> {
+    let _guard = STORE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    read_all(workspace_dir)
+}
+
+/// Insert `fence`, replacing any existing fence with the same id.
+pub fn upsert(workspace_dir: &Path, fence: Geofence) -> anyhow::Result<()> {
+    let _guard = STORE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let mut fences = read_all(workspace_dir)?;
+    fences.retain(|f| f.id != fence.id);
+    fences.push(fence);
+    write_all(workspace_dir, &fences)
+}
+
+/// Remove the fence with `id`, returning it if it existed.
+pub fn remove(workspace_dir: &Path, id: &str) -> anyhow::Result<Option<Geofence>> {
+    let _guard = STORE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let mut fences = read_all(workspace_dir)?;
+    let Some(index) = fences.iter().position(|f| f.id == id) else {
+        return Ok(None);
+    };
+    let removed = fences.remove(index);
+    write_all(workspace_dir, &fences)?;
+    Ok(Some(removed))
+}
+
+/// Handle a transition reported by the host. Returns the scheduled agent job,
+/// or `None` when the fence is unknown or does not watch this transition.
+pub async fn handle_event(config: &Config, payload_json: &str) -> anyhow::Result<Option<CronJob>> {
+    let payload: EventPayload = serde_json::from_str(payload_json)?;
+    let Some(fence) = list(&config.workspace_dir)?
+        .into_iter()
+        .find(|f| f.id == payload.id)
+    else {
+        tracing::debug!(id = %payload.id, "Ignoring event for unknown geofence");
+        return Ok(None);
+    };
+    if !fence.transitions.contains(&payload.transition) {
+        return Ok(None);
+    }
+    if !config.cron.enabled {
+        anyhow::bail!("cron is disabled by config (cron.enabled=false)");
+    }
+
+    let transition = payload.transition.as_str();
+    let verb = match payload.transition {
+        GeofenceTransition::Enter => "arrived at",
+        GeofenceTransition::Exit => "left",
+    };
+    let prompt = format!(
+        "[geofence:{} {transition}] The user just {verb} '{}'. {}",
+        fence.name, fence.name, fence.prompt
+    );
+    let delivery =
+        (config.android.enabled && config.android.capabilities.notifications).then(|| {
+            DeliveryConfig {
+                mode: "announce".into(),
+                channel: Some("android".into()),
+                to: Some(ANDROID_REMINDER_CHANNEL.into()),
+                best_effort: true,
+            }
+        });
+    let job = cron::add_agent_job(
+        config,
+        Some(format!("geofence {} ({transition})", fence.name)),
+        Schedule::At {
+            at: Utc::now() + chrono::Duration::seconds(TRIGGER_DELAY_SECS),
+        },
+        &prompt,
+        None,
+        SessionTarget::Isolated,
+        None,
+        delivery,
+        true,
+    )?;
+
+    if fence.once {
+        remove(&config.workspace_dir, &fence.id)?;
+        let bridge = AndroidBridge::new(config.android.bridge.clone());
+        if let Err(e) = bridge
+            .call("unregister_geofence", serde_json::json!({ "id": fence.id }))
+            .await
+        {
+            tracing::warn!(id = %fence.id, "Failed to unregister one-shot geofence: {e}");
+        }
+    }
+    Ok(Some(job))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cron::JobType;
+    use tempfile::TempDir;
+
+    fn fence(id: &str, transitions: Vec<GeofenceTransition>, once: bool) -> Geofence {
+        Geofence {
+            id: id.into(),
+            name: "Home".into(),
+            latitude: 52.52,
+            longitude: 13.405,
+            radius_m: 150.0,
+            transitions,
+            prompt: "Remind me to water the plants.".into(),
+            once,
+            created_at: Utc::now(),
+        }
+    }
+
+    fn test_config(tmp: &TempDir) -> Config {
+        let config = Config {
+            workspace_dir: tmp.path().join("workspace"),
+            config_path: tmp.path().join("config.toml"),
+            ..Config::default()
+        };
+        std::fs::create_dir_all(&config.workspace_dir).unwrap();
+        config
+    }
+
+    #[test]
+    fn store_upserts_and_removes() {
+        let tmp = TempDir::new().unwrap();
+        let (enter, exit) = (GeofenceTransition::Enter, GeofenceTransition::Exit);
+        upsert(tmp.path(), fence("a", vec![enter], false)).unwrap();
+        upsert(tmp.path(), fence("b", vec![exit], false)).unwrap();
+        upsert(tmp.path(), fence("a", vec![exit], false)).unwrap();
+
+        let fences = list(tmp.path()).unwrap();
+        assert_eq!(fences.len(), 2);
+        assert!(fences
+            .iter()
+            .any(|f| f.id == "a" && f.transitions == vec![exit]));
+
+        assert!(remove(tmp.path(), "a").unwrap().is_some());
+        assert!(remove(tmp.path(), "a").unwrap().is_none());
+        assert_eq!(list(tmp.path()).unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn enter_event_schedules_agent_prompt_and_drops_one_shot_fence() {
+        let tmp = TempDir::new().unwrap();
+        let config = test_config(&tmp);
+        upsert(
+            &config.workspace_dir,
+            fence("home", vec![GeofenceTransition::Enter], true),
+        )
+        .unwrap();
+
+        let ignored = handle_event(&config, r#"{"id":"home","transition":"exit"}"#)
+            .await
+            .unwrap();
+        assert!(ignored.is_none());
+
+        let job = handle_event(&config, r#"{"id":"home","transition":"enter"}"#)
+            .await
+            .unwrap()
+            .expect("job scheduled");
+        assert_eq!(job.job_type, JobType::Agent);
+        assert!(job.delete_after_run);
+        let prompt = job.prompt.unwrap_or_default();
+        assert!(prompt.contains("arrived at 'Home'"));
+        assert!(prompt.contains("water the plants"));
+        assert!(list(&config.workspace_dir).unwrap().is_empty());
+
+        assert!(
+            handle_event(&config, r#"{"id":"home","transition":"enter"}"#)
+                .await
+                .unwrap()
+                .is_none()
+        );
+        assert!(handle_event(&config, "not json").await.is_err());
+    }
+}
@@ -0,0 +1,75 @@
+//! Host-app callback registry.
+//!
+//! When ZeroClaw runs inside a mobile host app (the Android cdylib build),
+//! some platform APIs have no Rust equivalent and must be reached through
+//! the host process. The host registers a single [`HostBridge`] at startup;
+//! runtime helpers resolve it lazily on every call so registration order
+//! relative to tool construction does not matter.
+
+use serde_json::Value;
+use std::sync::{Arc, RwLock};
+
+/// Synchronous request/response channel into the host application.
+///
+/// `action` is a stable snake_case identifier (e.g. `"saf_read"`) and
+/// `payload` a JSON object. Implementations return the host's JSON reply
+/// or an error when the host rejects or cannot service the call.
+pub trait HostBridge: Send + Sync {
+    fn call(&self, action: &str, payload: Value) -> anyhow::Result<Value>;
+}
+
+static HOST_BRIDGE: RwLock<Option<Arc<dyn HostBridge>>> = RwLock::new(None);
+
+/// Install the process-wide host bridge, replacing any previous one.
+pub fn register_host_bridge(bridge: Arc<dyn HostBridge>) {
+    let mut slot = HOST_BRIDGE.write().unwrap_or_else(|e| e.into_inner());
+    *slot = Some(bridge);
+}
+
+/// Remove the process-wide host bridge (e.g. when the host agent stops).
+pub fn clear_host_bridge() {
+    let mut slot = HOST_BRIDGE.write().unwrap_or_else(|e| e.into_inner());
+    *slot = None;
+}
+
+/// Return the currently registered host bridge, if any.
+pub fn host_bridge() -> Option<Arc<dyn HostBridge>> {
+    HOST_BRIDGE
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .as_ref()
+        .map(Arc::clone)
+}
+
+/// Call the registered host bridge, failing with a descriptive error when
+/// no host is attached (CLI and daemon processes outside the mobile app).
+pub fn call_host(action: &str, payload: Value) -> anyhow::Result<Value> {
+    let Some(bridge) = host_bridge() else {
+        anyhow::bail!(
+            "No host bridge registered; '{action}' is only available inside the mobile app"
+        );
+    };
+    bridge.call(action, payload)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    struct EchoBridge;
+
+    impl HostBridge for EchoBridge {
+        fn call(&self, action: &str, payload: Value) -> anyhow::Result<Value> {
+            Ok(json!({ "action": action, "payload": payload }))
+        }
+    }
+
+    #[test]
+    fn echo_bridge_round_trips_payload() {
+        let bridge: Arc<dyn HostBridge> = Arc::new(EchoBridge);
+        let reply = bridge.call("ping", json!({ "n": 1 })).unwrap();
+        assert_eq!(reply["action"], "ping");
+        assert_eq!(reply["payload"]["n"], 1);
+    }
+}
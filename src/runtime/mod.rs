@@ -1,11 +1,25 @@
 pub mod android;
+pub mod desktop;
+pub mod detect;
 pub mod docker;
+pub mod geofences;
+pub mod host_bridge;
 pub mod native;
+pub mod network;
+pub mod notifications;
+pub mod power;
+pub mod saf;
+pub mod shizuku;
 pub mod traits;
 
 pub use android::AndroidRuntime;
+pub use desktop::DesktopRuntime;
+pub use detect::{detect, detect_platform, DetectedPlatform};
 pub use docker::DockerRuntime;
+pub use host_bridge::HostBridge;
 pub use native::NativeRuntime;
+pub use network::{ConnectionType, LargeTransfer};
+pub use saf::SafStorage;
 pub use traits::RuntimeAdapter;
 
 use crate::config::RuntimeConfig;
@@ -13,17 +27,21 @@ use crate::config::RuntimeConfig;
 /// Factory: create the right runtime from config
 pub fn create_runtime(config: &RuntimeConfig) -> anyhow::Result<Box<dyn RuntimeAdapter>> {
     match config.kind.as_str() {
+        "auto" => detect(config),
         "native" => Ok(Box::new(NativeRuntime::new())),
         "docker" => Ok(Box::new(DockerRuntime::new(config.docker.clone()))),
         "android" => Ok(Box::new(AndroidRuntime::new(config.android.clone()))),
+        "desktop" | "crostini" => Ok(Box::new(DesktopRuntime::new())),
         "cloudflare" => anyhow::bail!(
             "runtime.kind='cloudflare' is not implemented yet. Use runtime.kind='native' for now."
         ),
         other if other.trim().is_empty() => {
-            anyhow::bail!("runtime.kind cannot be empty. Supported values: native, docker, android")
+            anyhow::bail!(
+                "runtime.kind cannot be empty. Supported values: auto, native, docker, android, desktop"
+            )
         }
         other => anyhow::bail!(
-            "Unknown runtime kind '{other}'. Supported values: native, docker, android"
+            "Unknown runtime kind '{other}'. Supported values: auto, native, docker, android, desktop"
         ),
     }
 }
@@ -32,6 +50,21 @@ pub fn create_runtime(config: &RuntimeConfig) -> anyhow::Result<Box<dyn RuntimeA
 mod tests {
     use super::*;
 
+    #[test]
+    fn factory_auto_detects_runtime() {
+        let cfg = RuntimeConfig {
+            kind: "auto".into(),
+            ..RuntimeConfig::default()
+        };
+        let rt = create_runtime(&cfg).unwrap();
+        let expected = match detect_platform() {
+            DetectedPlatform::Android => "android",
+            DetectedPlatform::Desktop => rt.name(),
+            _ => "native",
+        };
+        assert_eq!(rt.name(), expected);
+    }
+
     #[test]
     fn factory_native() {
         let cfg = RuntimeConfig {
@@ -65,6 +98,18 @@ mod tests {
         assert!(!rt.has_shell_access());
     }
 
+    #[test]
+    fn factory_desktop() {
+        let cfg = RuntimeConfig {
+            kind: "desktop".into(),
+            ..RuntimeConfig::default()
+        };
+        let rt = create_runtime(&cfg).unwrap();
+        assert!(matches!(rt.name(), "desktop" | "crostini"));
+        assert!(rt.has_shell_access());
+        assert!(rt.storage_path().to_string_lossy().contains("zeroclaw"));
+    }
+
     #[test]
     fn factory_cloudflare_errors() {
         let cfg = RuntimeConfig {
@@ -0,0 +1,195 @@
+//! Host-reported network state (connection type and metered status).
+//!
+//! The host app observes `ConnectivityManager` callbacks and pushes the
+//! active network here. Runtime adapters expose it through
+//! [`RuntimeAdapter::connection_type`](super::RuntimeAdapter::connection_type),
+//! and `[runtime.network]` decides which large transfers are deferred to
+//! unmetered networks.
+
+use crate::config::RuntimeNetworkConfig;
+use std::sync::RwLock;
+use std::time::Duration;
+
+/// Transport of the currently active network.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ConnectionType {
+    /// No report from the host (non-mobile platforms).
+    #[default]
+    Unknown,
+    /// No network connection.
+    None,
+    Wifi,
+    Cellular,
+    Ethernet,
+}
+
+impl ConnectionType {
+    pub fn parse(raw: &str) -> Self {
+        match raw.trim().to_ascii_lowercase().as_str() {
+            "none" | "offline" => Self::None,
+            "wifi" | "wi-fi" => Self::Wifi,
+            "cellular" | "mobile" => Self::Cellular,
+            "ethernet" => Self::Ethernet,
+            _ => Self::Unknown,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Unknown => "unknown",
+            Self::None => "none",
+            Self::Wifi => "wifi",
+            Self::Cellular => "cellular",
+            Self::Ethernet => "ethernet",
+        }
+    }
+}
+
+/// Snapshot of the host's active network.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct NetworkState {
+    pub connection: ConnectionType,
+    /// `true` when the OS reports the active network as metered.
+    pub metered: bool,
+}
+
+/// Large operations that `[runtime.network]` can restrict to unmetered networks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LargeTransfer {
+    ModelDownload,
+    ImageUpload,
+}
+
+impl LargeTransfer {
+    pub fn parse(raw: &str) -> Option<Self> {
+        match raw.trim().to_ascii_lowercase().as_str() {
+            "model_download" => Some(Self::ModelDownload),
+            "image_upload" => Some(Self::ImageUpload),
+            _ => None,
+        }
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::ModelDownload => "model_download",
+            Self::ImageUpload => "image_upload",
+        }
+    }
+}
+
+static NETWORK_STATE: RwLock<NetworkState> = RwLock::new(NetworkState {
+    connection: ConnectionType::Unknown,
+    metered: false,
+});
+
+static NETWORK_POLICY: RwLock<RuntimeNetworkConfig> = RwLock::new(RuntimeNetworkConfig {
+    unmetered_only_downloads: false,
+    unmetered_only_image_uploads: false,
+    metered_poll_interval_secs: 0,
+});
+
+/// Record the latest network state reported by the host app.
+pub fn update_network_state(state: NetworkState) {
+    let mut slot = NETWORK_STATE.write().unwrap_or_else(|e| e.into_inner());
+    if *slot != state {
+        tracing::info!(
+            connection = state.connection.as_str(),
+            metered = state.metered,
+            "Host network changed"
+        );
+    }
+    *slot = state;
+}
+
+/// Return the most recent network state reported by the host app.
+pub fn network_state() -> NetworkState {
+    *NETWORK_STATE.read().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Install the `[runtime.network]` policy. Called when config is loaded.
+pub fn set_network_policy(policy: RuntimeNetworkConfig) {
+    *NETWORK_POLICY.write().unwrap_or_else(|e| e.into_inner()) = policy;
+}
+
+fn network_policy() -> RuntimeNetworkConfig {
+    NETWORK_POLICY
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .clone()
+}
+
+fn check_transfer(
+    transfer: LargeTransfer,
+    state: NetworkState,
+    policy: &RuntimeNetworkConfig,
+) -> anyhow::Result<()> {
+    let gated = match transfer {
+        LargeTransfer::ModelDownload => policy.unmetered_only_downloads,
+        LargeTransfer::ImageUpload => policy.unmetered_only_image_uploads,
+    };
+    if gated && state.metered {
+        anyhow::bail!(
+            "{} deferred: active {} network is metered (runtime.network policy)",
+            transfer.as_str(),
+            state.connection.as_str()
+        );
+    }
+    Ok(())
+}
+
+/// Fail when `transfer` is restricted to unmetered networks and the active
+/// network is metered.
+pub fn ensure_transfer_allowed(transfer: LargeTransfer) -> anyhow::Result<()> {
+    check_transfer(transfer, network_state(), &network_policy())
+}
+
+/// Extra delay to insert between long-poll cycles while on a metered network.
+pub fn metered_poll_delay() -> Option<Duration> {
+    let secs = network_policy().metered_poll_interval_secs;
+    (secs > 0 && network_state().metered).then(|| Duration::from_secs(secs))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn metered_cellular() -> NetworkState {
+        NetworkState {
+            connection: ConnectionType::Cellular,
+            metered: true,
+        }
+    }
+
+    #[test]
+    fn connection_type_parses_host_labels() {
+        assert_eq!(ConnectionType::parse("WIFI"), ConnectionType::Wifi);
+        assert_eq!(ConnectionType::parse("mobile"), ConnectionType::Cellular);
+        assert_eq!(ConnectionType::parse("bluetooth"), ConnectionType::Unknown);
+    }
+
+    #[test]
+    fn default_policy_allows_everything() {
+        let policy = RuntimeNetworkConfig::default();
+        assert!(check_transfer(LargeTransfer::ModelDownload, metered_cellular(), &policy).is_ok());
+        assert!(check_transfer(LargeTransfer::ImageUpload, metered_cellular(), &policy).is_ok());
+    }
+
+    #[test]
+    fn gated_transfers_blocked_only_on_metered() {
+        let policy = RuntimeNetworkConfig {
+            unmetered_only_downloads: true,
+            ..RuntimeNetworkConfig::default()
+        };
+        let err = check_transfer(LargeTransfer::ModelDownload, metered_cellular(), &policy)
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("metered"));
+
+        let wifi = NetworkState {
+            connection: ConnectionType::Wifi,
+            metered: false,
+        };
+        assert!(check_transfer(LargeTransfer::ModelDownload, wifi, &policy).is_ok());
+        assert!(check_transfer(LargeTransfer::ImageUpload, metered_cellular(), &policy).is_ok());
+    }
+}
@@ -0,0 +1,191 @@
+//! Host-fed feed of device notifications.
+//!
+//! The host app's `NotificationListenerService` pushes every posted and
+//! removed notification here over JNI. The feed keeps a bounded, recent
+//! history so the agent can answer "what pinged me in the last hour" even
+//! for notifications the user already dismissed.
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+
+/// Oldest entries are dropped beyond this many notifications.
+const MAX_FEED_ENTRIES: usize = 500;
+/// Entries older than this are pruned on every update.
+const FEED_RETENTION_HOURS: i64 = 24;
+const MAX_FIELD_CHARS: usize = 1000;
+
+/// One notification as reported by the host.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DeviceNotification {
+    /// Android `StatusBarNotification` key; stable across updates.
+    pub key: String,
+    pub package: String,
+    pub app_name: Option<String>,
+    pub title: String,
+    pub text: String,
+    pub category: Option<String>,
+    pub posted_at: DateTime<Utc>,
+    /// Set once the notification is dismissed or cancelled.
+    pub removed_at: Option<DateTime<Utc>>,
+}
+
+impl DeviceNotification {
+    pub fn is_active(&self) -> bool {
+        self.removed_at.is_none()
+    }
+}
+
+/// JSON payload sent by the host for a posted notification.
+#[derive(Debug, Deserialize)]
+struct PostedPayload {
+    key: String,
+    package: String,
+    #[serde(default)]
+    app_name: Option<String>,
+    #[serde(default)]
+    title: String,
+    #[serde(default)]
+    text: String,
+    #[serde(default)]
+    category: Option<String>,
+    #[serde(default)]
+    posted_at_ms: Option<i64>,
+    /// Ongoing notifications (media players, foreground services) are noise.
+    #[serde(default)]
+    ongoing: bool,
+}
+
+static FEED: Mutex<VecDeque<DeviceNotification>> = Mutex::new(VecDeque::new());
+static LISTENER_CONNECTED: AtomicBool = AtomicBool::new(false);
+
+fn clip(value: &str) -> String {
+    value.trim().chars().take(MAX_FIELD_CHARS).collect()
+}
+
+fn prune(feed: &mut VecDeque<DeviceNotification>, now: DateTime<Utc>) {
+    let cutoff = now - Duration::hours(FEED_RETENTION_HOURS);
+    feed.retain(|n| n.posted_at >= cutoff);
+    while feed.len() > MAX_FEED_ENTRIES {
+        feed.pop_front();
+    }
+}
+
+/// Record whether the host's notification listener is bound (the user has
+/// granted notification access).
+pub fn set_listener_connected(connected: bool) {
+    if LISTENER_CONNECTED.swap(connected, Ordering::Relaxed) != connected {
+        tracing::info!(connected, "Notification listener state changed");
+    }
+}
+
+pub fn listener_connected() -> bool {
+    LISTENER_CONNECTED.load(Ordering::Relaxed)
+}
+
+/// Record a posted (or updated) notification from its host JSON payload.
+pub fn record_posted(payload_json: &str) -> anyhow::Result<()> {
+    let payload: PostedPayload = serde_json::from_str(payload_json)?;
+    if payload.ongoing {
+        return Ok(());
+    }
+    if payload.key.trim().is_empty() || payload.package.trim().is_empty() {
+        anyhow::bail!("notification payload requires 'key' and 'package'");
+    }
+
+    let now = Utc::now();
+    let posted_at = payload
+        .posted_at_ms
+        .and_then(DateTime::<Utc>::from_timestamp_millis)
+        .unwrap_or(now);
+    let notification = DeviceNotification {
+        key: payload.key,
+        package: payload.package.trim().to_string(),
+        app_name: payload
+            .app_name
+            .map(|name| clip(&name))
+            .filter(|name| !name.is_empty()),
+        title: clip(&payload.title),
+        text: clip(&payload.text),
+        category: payload.category,
+        posted_at,
+        removed_at: None,
+    };
+
+    let mut feed = FEED.lock().unwrap_or_else(|e| e.into_inner());
+    feed.retain(|n| n.key != notification.key);
+    feed.push_back(notification);
+    prune(&mut feed, now);
+    Ok(())
+}
+
+/// Mark the notification with `key` as removed.
+pub fn record_removed(key: &str) {
+    let now = Utc::now();
+    let mut feed = FEED.lock().unwrap_or_else(|e| e.into_inner());
+    if let Some(entry) = feed
+        .iter_mut()
+        .rev()
+        .find(|n| n.key == key && n.removed_at.is_none())
+    {
+        entry.removed_at = Some(now);
+    }
+    prune(&mut feed, now);
+}
+
+/// Notifications posted at or after `since`, newest first.
+pub fn notifications_since(since: DateTime<Utc>) -> Vec<DeviceNotification> {
+    let feed = FEED.lock().unwrap_or_else(|e| e.into_inner());
+    feed.iter()
+        .rev()
+        .filter(|n| n.posted_at >= since)
+        .cloned()
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn posted_updates_replace_and_removal_marks_inactive() {
+        let key = "0|com.example.feedtest|1|null|10001";
+        record_posted(&format!(
+            r#"{{"key":"{key}","package":"com.example.feedtest","title":"Alice","text":"Lunch?"}}"#
+        ))
+        .unwrap();
+        record_posted(&format!(
+            r#"{{"key":"{key}","package":"com.example.feedtest","title":"Alice","text":"Lunch at 1?"}}"#
+        ))
+        .unwrap();
+
+        let since = Utc::now() - Duration::minutes(5);
+        let ours = |all: Vec<DeviceNotification>| -> Vec<DeviceNotification> {
+            all.into_iter().filter(|n| n.key == key).collect()
+        };
+        let entries = ours(notifications_since(since));
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].text, "Lunch at 1?");
+        assert!(entries[0].is_active());
+
+        record_removed(key);
+        let entries = ours(notifications_since(since));
+        assert!(!entries[0].is_active());
+    }
+
+    #[test]
+    fn ongoing_and_malformed_payloads() {
+        record_posted(
+            r#"{"key":"media-ongoing-test","package":"com.example.player","title":"Playing","ongoing":true}"#,
+        )
+        .unwrap();
+        assert!(notifications_since(Utc::now() - Duration::minutes(5))
+            .iter()
+            .all(|n| n.key != "media-ongoing-test"));
+
+        assert!(record_posted(r#"{"key":"","package":"com.example"}"#).is_err());
+        assert!(record_posted("not json").is_err());
+    }
+}
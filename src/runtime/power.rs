@@ -0,0 +1,131 @@
+//! Host-reported background execution state (Android Doze / App Standby).
+//!
+//! The host app observes `PowerManager.isDeviceIdleMode()` and App Standby
+//! buckets and pushes the result here. Runtime adapters expose it through
+//! [`RuntimeAdapter::is_background_restricted`](super::RuntimeAdapter::is_background_restricted)
+//! and the scheduler and channel supervisors defer network work until the
+//! next maintenance window instead of fighting the OS.
+//!
+//! The host also pushes the battery level, which heartbeat runs check
+//! against `heartbeat.min_battery_percent`.
+
+use chrono::{DateTime, Utc};
+use std::sync::RwLock;
+use std::time::Duration;
+
+/// Upper bound on a single wait so state updates from the host are noticed.
+const MAX_RESTRICTED_WAIT: Duration = Duration::from_secs(60);
+
+/// Snapshot of the host's background execution restrictions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct BackgroundState {
+    /// `true` while the OS is restricting background network/CPU work.
+    pub restricted: bool,
+    /// Start of the next maintenance window, when the host knows it.
+    pub next_allowed_wakeup: Option<DateTime<Utc>>,
+}
+
+static BACKGROUND_STATE: RwLock<BackgroundState> = RwLock::new(BackgroundState {
+    restricted: false,
+    next_allowed_wakeup: None,
+});
+
+/// Record the latest background state reported by the host app.
+pub fn update_background_state(state: BackgroundState) {
+    let mut slot = BACKGROUND_STATE.write().unwrap_or_else(|e| e.into_inner());
+    if slot.restricted != state.restricted {
+        tracing::info!(
+            restricted = state.restricted,
+            "Host background restriction changed"
+        );
+    }
+    *slot = state;
+}
+
+/// Return the most recent background state reported by the host app.
+pub fn background_state() -> BackgroundState {
+    *BACKGROUND_STATE.read().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Battery level reported by the host app.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct BatteryState {
+    /// Charge in percent; `None` until the host reports it.
+    pub percent: Option<u8>,
+    pub charging: bool,
+}
+
+static BATTERY_STATE: RwLock<BatteryState> = RwLock::new(BatteryState {
+    percent: None,
+    charging: false,
+});
+
+/// Record the latest battery level reported by the host app.
+pub fn update_battery_state(state: BatteryState) {
+    *BATTERY_STATE.write().unwrap_or_else(|e| e.into_inner()) = state;
+}
+
+/// Return the most recent battery level reported by the host app.
+pub fn battery_state() -> BatteryState {
+    *BATTERY_STATE.read().unwrap_or_else(|e| e.into_inner())
+}
+
+/// How long to wait before re-checking a restricted state.
+fn restricted_wait(state: BackgroundState, now: DateTime<Utc>) -> Duration {
+    state
+        .next_allowed_wakeup
+        .map_or(MAX_RESTRICTED_WAIT, |at| {
+            (at - now).to_std().unwrap_or(Duration::ZERO)
+        })
+        .clamp(Duration::from_secs(1), MAX_RESTRICTED_WAIT)
+}
+
+/// Sleep until the host reports that background work is allowed again.
+pub async fn wait_until_unrestricted() {
+    loop {
+        let state = background_state();
+        if !state.restricted {
+            return;
+        }
+        tokio::time::sleep(restricted_wait(state, Utc::now())).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_state_is_unrestricted() {
+        let state = BackgroundState::default();
+        assert!(!state.restricted);
+        assert!(state.next_allowed_wakeup.is_none());
+    }
+
+    #[test]
+    fn restricted_wait_targets_next_window() {
+        let now = Utc::now();
+        let state = BackgroundState {
+            restricted: true,
+            next_allowed_wakeup: Some(now + chrono::Duration::seconds(10)),
+        };
+        let wait = restricted_wait(state, now);
+        assert!(wait <= Duration::from_secs(10) && wait >= Duration::from_secs(9));
+    }
+
+    #[test]
+    fn restricted_wait_is_bounded() {
+        let now = Utc::now();
+        let far = BackgroundState {
+            restricted: true,
+            next_allowed_wakeup: Some(now + chrono::Duration::hours(3)),
+        };
+        assert_eq!(restricted_wait(far, now), MAX_RESTRICTED_WAIT);
+
+        let past = BackgroundState {
+            restricted: true,
+            next_allowed_wakeup: Some(now - chrono::Duration::seconds(5)),
+        };
+        assert_eq!(restricted_wait(past, now), Duration::from_secs(1));
+    }
+}
@@ -0,0 +1,215 @@
+//! Storage Access Framework (SAF) document backend.
+//!
+//! Android apps cannot open user folders such as Downloads or a synced notes
+//! directory with plain filesystem paths. Instead the user grants a *tree*
+//! through the system document picker and the app addresses files by
+//! `content://` URIs. This module resolves those URIs through the host
+//! bridge (`saf_read` / `saf_write` actions), restricted to the tree URIs
+//! listed in `runtime.android.saf_tree_uris`.
+
+use super::host_bridge::{self, HostBridge};
+use base64::Engine;
+use serde_json::json;
+use std::sync::Arc;
+
+const CONTENT_SCHEME: &str = "content://";
+
+/// Return `true` when `path` is a `content://` URI rather than a filesystem path.
+pub fn is_content_uri(path: &str) -> bool {
+    path.trim_start().starts_with(CONTENT_SCHEME)
+}
+
+/// SAF-backed document storage scoped to user-granted tree URIs.
+pub struct SafStorage {
+    granted_trees: Vec<String>,
+    bridge: Option<Arc<dyn HostBridge>>,
+}
+
+impl SafStorage {
+    /// Create a backend limited to `granted_trees`. Empty entries are dropped.
+    pub fn new(granted_trees: Vec<String>) -> Self {
+        Self {
+            granted_trees: granted_trees
+                .into_iter()
+                .map(|tree| tree.trim().trim_end_matches('/').to_string())
+                .filter(|tree| is_content_uri(tree))
+                .collect(),
+            bridge: None,
+        }
+    }
+
+    /// Use an explicit bridge instead of the process-wide registered one.
+    pub fn with_bridge(mut self, bridge: Arc<dyn HostBridge>) -> Self {
+        self.bridge = Some(bridge);
+        self
+    }
+
+    /// Validate that `uri` addresses a document inside a granted tree.
+    pub fn ensure_allowed(&self, uri: &str) -> anyhow::Result<()> {
+        let uri = uri.trim();
+        if !is_content_uri(uri) {
+            anyhow::bail!("Not a content:// URI: {uri}");
+        }
+
+        let decoded = urlencoding::decode(uri)
+            .map(std::borrow::Cow::into_owned)
+            .unwrap_or_else(|_| uri.to_string());
+        if decoded.split('/').any(|segment| segment == "..") {
+            anyhow::bail!("Path traversal is not allowed in content URIs");
+        }
+
+        let granted = self.granted_trees.iter().any(|tree| {
+            uri == tree
+                || uri
+                    .strip_prefix(tree.as_str())
+                    .is_some_and(|rest| rest.starts_with('/'))
+        });
+        if !granted {
+            anyhow::bail!(
+                "Content URI is outside the granted folders in runtime.android.saf_tree_uris"
+            );
+        }
+        Ok(())
+    }
+
+    /// Read the full contents of the document at `uri`.
+    pub async fn read(&self, uri: &str) -> anyhow::Result<Vec<u8>> {
+        self.ensure_allowed(uri)?;
+        let reply = self.call("saf_read", json!({ "uri": uri.trim() })).await?;
+        let encoded = reply
+            .get("data_base64")
+            .and_then(serde_json::Value::as_str)
+            .ok_or_else(|| anyhow::anyhow!("Host bridge saf_read reply is missing data_base64"))?;
+        Ok(base64::engine::general_purpose::STANDARD.decode(encoded)?)
+    }
+
+    /// Replace the contents of the document at `uri`, creating it if needed.
+    pub async fn write(&self, uri: &str, data: &[u8]) -> anyhow::Result<()> {
+        self.ensure_allowed(uri)?;
+        self.call(
+            "saf_write",
+            json!({
+                "uri": uri.trim(),
+                "data_base64": base64::engine::general_purpose::STANDARD.encode(data),
+            }),
+        )
+        .await?;
+        Ok(())
+    }
+
+    async fn call(
+        &self,
+        action: &'static str,
+        payload: serde_json::Value,
+    ) -> anyhow::Result<serde_json::Value> {
+        let bridge = self.bridge.clone();
+        let reply = tokio::task::spawn_blocking(move || match bridge {
+            Some(bridge) => bridge.call(action, payload),
+            None => host_bridge::call_host(action, payload),
+        })
+        .await??;
+
+        if reply
+            .get("ok")
+            .and_then(serde_json::Value::as_bool)
+            .is_some_and(|ok| !ok)
+        {
+            let detail = reply
+                .get("error")
+                .and_then(serde_json::Value::as_str)
+                .unwrap_or("document provider error");
+            anyhow::bail!("{action} failed: {detail}");
+        }
+        Ok(reply)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use parking_lot::Mutex;
+    use std::collections::HashMap;
+
+    const TREE: &str = "content://com.android.externalstorage.documents/tree/primary%3ADownload";
+
+    #[derive(Default)]
+    struct FakeDocuments {
+        docs: Mutex<HashMap<String, String>>,
+    }
+
+    impl HostBridge for FakeDocuments {
+        fn call(
+            &self,
+            action: &str,
+            payload: serde_json::Value,
+        ) -> anyhow::Result<serde_json::Value> {
+            let uri = payload["uri"].as_str().unwrap_or_default().to_string();
+            match action {
+                "saf_write" => {
+                    let data = payload["data_base64"].as_str().unwrap_or_default();
+                    self.docs.lock().insert(uri, data.to_string());
+                    Ok(json!({ "ok": true }))
+                }
+                "saf_read" => match self.docs.lock().get(&uri) {
+                    Some(data) => Ok(json!({ "ok": true, "data_base64": data })),
+                    None => Ok(json!({ "ok": false, "error": "not found" })),
+                },
+                other => anyhow::bail!("unexpected action {other}"),
+            }
+        }
+    }
+
+    fn storage() -> SafStorage {
+        SafStorage::new(vec![format!("{TREE}/")]).with_bridge(Arc::new(FakeDocuments::default()))
+    }
+
+    #[test]
+    fn detects_content_uris() {
+        assert!(is_content_uri(TREE));
+        assert!(!is_content_uri("/sdcard/Download/a.txt"));
+        assert!(!is_content_uri("notes/today.md"));
+    }
+
+    #[test]
+    fn rejects_uris_outside_granted_trees() {
+        let saf = storage();
+        assert!(saf
+            .ensure_allowed(&format!("{TREE}/document/primary%3ADownload%2Fa.txt"))
+            .is_ok());
+        assert!(saf
+            .ensure_allowed(
+                "content://com.android.externalstorage.documents/tree/primary%3ADCIM/document/x"
+            )
+            .is_err());
+        assert!(saf
+            .ensure_allowed(&format!("{TREE}Other/document/x"))
+            .is_err());
+    }
+
+    #[test]
+    fn rejects_encoded_traversal() {
+        let saf = storage();
+        let err = saf
+            .ensure_allowed(&format!("{TREE}/document/primary%3ADownload%2F..%2Fsecret"))
+            .unwrap_err();
+        assert!(err.to_string().contains("traversal"));
+    }
+
+    #[tokio::test]
+    async fn write_then_read_round_trips_through_bridge() {
+        let saf = storage();
+        let uri = format!("{TREE}/document/primary%3ADownload%2Fnotes.md");
+        saf.write(&uri, b"hello saf").await.unwrap();
+        assert_eq!(saf.read(&uri).await.unwrap(), b"hello saf");
+    }
+
+    #[tokio::test]
+    async fn host_error_is_surfaced() {
+        let saf = storage();
+        let err = saf
+            .read(&format!("{TREE}/document/primary%3ADownload%2Fmissing.md"))
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("not found"));
+    }
+}
@@ -0,0 +1,240 @@
+//! Shizuku-backed privileged execution for non-rooted Android devices.
+//!
+//! [Shizuku](https://shizuku.rikka.app/) lets an app run commands with the
+//! ADB shell identity once the user has started its service. The host app
+//! exposes it through the host bridge (`shizuku_exec` action); this module
+//! only ever sends a fixed set of typed operations, never free-form shell.
+
+use super::host_bridge;
+use serde_json::json;
+
+/// Android settings table targeted by a `settings put`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SettingsNamespace {
+    System,
+    Secure,
+    Global,
+}
+
+impl SettingsNamespace {
+    pub fn parse(raw: &str) -> anyhow::Result<Self> {
+        match raw.trim().to_ascii_lowercase().as_str() {
+            "system" => Ok(Self::System),
+            "secure" => Ok(Self::Secure),
+            "global" => Ok(Self::Global),
+            other => {
+                anyhow::bail!("Unknown settings namespace '{other}'. Use system, secure, or global")
+            }
+        }
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::System => "system",
+            Self::Secure => "secure",
+            Self::Global => "global",
+        }
+    }
+}
+
+/// Privileged operation executed through Shizuku.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PrivilegedOp {
+    SettingsPut {
+        namespace: SettingsNamespace,
+        key: String,
+        value: String,
+    },
+    InputTap {
+        x: u32,
+        y: u32,
+    },
+    InputSwipe {
+        x1: u32,
+        y1: u32,
+        x2: u32,
+        y2: u32,
+        duration_ms: u32,
+    },
+    InputText {
+        text: String,
+    },
+    InputKeyEvent {
+        keycode: String,
+    },
+}
+
+fn is_settings_token(value: &str) -> bool {
+    !value.is_empty()
+        && value.len() <= 128
+        && value
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '.' | '-'))
+}
+
+impl PrivilegedOp {
+    /// Validate arguments and render the argv passed to the Shizuku process.
+    pub fn to_argv(&self) -> anyhow::Result<Vec<String>> {
+        let argv = match self {
+            Self::SettingsPut {
+                namespace,
+                key,
+                value,
+            } => {
+                if !is_settings_token(key) {
+                    anyhow::bail!("Invalid settings key '{key}'");
+                }
+                if value.len() > 256 || value.chars().any(char::is_control) {
+                    anyhow::bail!("Invalid settings value");
+                }
+                vec![
+                    "settings".into(),
+                    "put".into(),
+                    namespace.as_str().into(),
+                    key.clone(),
+                    value.clone(),
+                ]
+            }
+            Self::InputTap { x, y } => {
+                vec!["input".into(), "tap".into(), x.to_string(), y.to_string()]
+            }
+            Self::InputSwipe {
+                x1,
+                y1,
+                x2,
+                y2,
+                duration_ms,
+            } => vec![
+                "input".into(),
+                "swipe".into(),
+                x1.to_string(),
+                y1.to_string(),
+                x2.to_string(),
+                y2.to_string(),
+                duration_ms.to_string(),
+            ],
+            Self::InputText { text } => {
+                if text.is_empty() || text.len() > 1000 || text.chars().any(char::is_control) {
+                    anyhow::bail!("input text must be 1-1000 printable characters");
+                }
+                // `input text` treats spaces as argument separators.
+                vec!["input".into(), "text".into(), text.replace(' ', "%s")]
+            }
+            Self::InputKeyEvent { keycode } => {
+                let valid = keycode.starts_with("KEYCODE_") && is_settings_token(keycode)
+                    || keycode.chars().all(|c| c.is_ascii_digit()) && !keycode.is_empty();
+                if !valid {
+                    anyhow::bail!("Invalid keycode '{keycode}'");
+                }
+                vec!["input".into(), "keyevent".into(), keycode.clone()]
+            }
+        };
+        Ok(argv)
+    }
+}
+
+/// Result of a Shizuku-executed operation.
+#[derive(Debug, Clone)]
+pub struct ShizukuOutput {
+    pub exit_code: i64,
+    pub stdout: String,
+    pub stderr: String,
+}
+
+/// Run `op` with Shizuku privileges through the host bridge.
+pub async fn execute(op: &PrivilegedOp) -> anyhow::Result<ShizukuOutput> {
+    let argv = op.to_argv()?;
+    let reply = tokio::task::spawn_blocking(move || {
+        host_bridge::call_host("shizuku_exec", json!({ "argv": argv }))
+    })
+    .await??;
+
+    if reply
+        .get("ok")
+        .and_then(serde_json::Value::as_bool)
+        .is_some_and(|ok| !ok)
+    {
+        let detail = reply
+            .get("error")
+            .and_then(serde_json::Value::as_str)
+            .unwrap_or("Shizuku is not running or permission was not granted");
+        anyhow::bail!("shizuku_exec failed: {detail}");
+    }
+
+    let text = |field: &str| {
+        reply
+            .get(field)
+            .and_then(serde_json::Value::as_str)
+            .unwrap_or_default()
+            .to_string()
+    };
+    Ok(ShizukuOutput {
+        exit_code: reply
+            .get("exit_code")
+            .and_then(serde_json::Value::as_i64)
+            .unwrap_or(-1),
+        stdout: text("stdout"),
+        stderr: text("stderr"),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn settings_put_renders_argv() {
+        let op = PrivilegedOp::SettingsPut {
+            namespace: SettingsNamespace::System,
+            key: "screen_brightness".into(),
+            value: "128".into(),
+        };
+        assert_eq!(
+            op.to_argv().unwrap(),
+            vec!["settings", "put", "system", "screen_brightness", "128"]
+        );
+    }
+
+    #[test]
+    fn settings_key_injection_is_rejected() {
+        let op = PrivilegedOp::SettingsPut {
+            namespace: SettingsNamespace::Global,
+            key: "adb_enabled; reboot".into(),
+            value: "1".into(),
+        };
+        assert!(op.to_argv().is_err());
+    }
+
+    #[test]
+    fn input_text_escapes_spaces() {
+        let op = PrivilegedOp::InputText {
+            text: "hello world".into(),
+        };
+        assert_eq!(op.to_argv().unwrap(), vec!["input", "text", "hello%sworld"]);
+    }
+
+    #[test]
+    fn keycodes_are_validated() {
+        let ok = PrivilegedOp::InputKeyEvent {
+            keycode: "KEYCODE_HOME".into(),
+        };
+        assert!(ok.to_argv().is_ok());
+        let numeric = PrivilegedOp::InputKeyEvent {
+            keycode: "3".into(),
+        };
+        assert!(numeric.to_argv().is_ok());
+        let bad = PrivilegedOp::InputKeyEvent {
+            keycode: "HOME && rm".into(),
+        };
+        assert!(bad.to_argv().is_err());
+    }
+
+    #[test]
+    fn namespace_parse_rejects_unknown() {
+        assert_eq!(
+            SettingsNamespace::parse("Secure").unwrap(),
+            SettingsNamespace::Secure
+        );
+        assert!(SettingsNamespace::parse("vendor").is_err());
+    }
+}
@@ -52,6 +52,40 @@ pub trait RuntimeAdapter: Send + Sync {
         0
     }
 
+    /// Report whether the host OS is currently restricting background work.
+    ///
+    /// Mobile runtimes return `true` while Android Doze or App Standby is
+    /// active. Schedulers and channel supervisors should defer network work
+    /// until [`next_allowed_wakeup`](Self::next_allowed_wakeup) instead of
+    /// retrying against a blocked network. Defaults to `false`.
+    fn is_background_restricted(&self) -> bool {
+        false
+    }
+
+    /// Return the start of the next maintenance window, if known.
+    ///
+    /// Only meaningful while [`is_background_restricted`](Self::is_background_restricted)
+    /// returns `true`. Defaults to `None` (unknown or unrestricted).
+    fn next_allowed_wakeup(&self) -> Option<chrono::DateTime<chrono::Utc>> {
+        None
+    }
+
+    /// Return the transport of the active network.
+    ///
+    /// Mobile runtimes report what the host app observed; other runtimes
+    /// default to [`ConnectionType::Unknown`](super::network::ConnectionType::Unknown).
+    fn connection_type(&self) -> super::network::ConnectionType {
+        super::network::ConnectionType::Unknown
+    }
+
+    /// Report whether the active network is metered (cellular data, hotspot).
+    ///
+    /// Callers use this to defer large transfers; see `[runtime.network]`.
+    /// Defaults to `false`.
+    fn is_metered(&self) -> bool {
+        false
+    }
+
     /// Build a shell command process configured for this runtime.
     ///
     /// Constructs a [`tokio::process::Command`] that will execute `command`
@@ -115,6 +149,13 @@ mod tests {
         assert_eq!(runtime.memory_budget(), 0);
     }
 
+    #[test]
+    fn default_background_state_is_unrestricted() {
+        let runtime = DummyRuntime;
+        assert!(!runtime.is_background_restricted());
+        assert!(runtime.next_allowed_wakeup().is_none());
+    }
+
     #[test]
     fn runtime_reports_capabilities() {
         let runtime = DummyRuntime;
@@ -6,13 +6,15 @@
 // that must be sent on all subsequent requests via `Authorization: Bearer <token>`.
 //
 // Already-paired tokens are persisted in config so restarts don't require
-// re-pairing.
+// re-pairing. Further codes can be issued on demand (e.g. shown as a QR code);
+// those expire after a short TTL. Paired tokens can be revoked by id. The gateway's per-install API token is accepted alongside
+// paired tokens and makes authentication mandatory even without pairing.
 
 use parking_lot::Mutex;
 use sha2::{Digest, Sha256};
 use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 
 /// Maximum failed pairing attempts before lockout.
 const MAX_PAIR_ATTEMPTS: u32 = 5;
@@ -20,6 +22,8 @@ const MAX_PAIR_ATTEMPTS: u32 = 5;
 const PAIR_LOCKOUT_SECS: u64 = 300; // 5 minutes
 /// Maximum number of tracked client entries to bound memory usage.
 const MAX_TRACKED_CLIENTS: usize = 10_000;
+/// Length of a paired token's id (a prefix of its SHA-256 hash).
+const TOKEN_ID_LEN: usize = 12;
 /// Retention period for failed-attempt entries with no activity.
 const FAILED_ATTEMPT_RETENTION_SECS: u64 = 900; // 15 min
 /// Minimum interval between full sweeps of the failed-attempt map.
@@ -33,6 +37,19 @@ struct FailedAttemptState {
     last_attempt: Instant,
 }
 
+/// A pairing code and, for codes issued on demand, when it expires.
+#[derive(Debug, Clone)]
+struct PairingCode {
+    code: String,
+    expires_at: Option<Instant>,
+}
+
+impl PairingCode {
+    fn is_expired(&self, now: Instant) -> bool {
+        self.expires_at.is_some_and(|at| now >= at)
+    }
+}
+
 /// Manages pairing state for the gateway.
 ///
 /// Bearer tokens are stored as SHA-256 hashes to prevent plaintext exposure
@@ -43,10 +60,13 @@ struct FailedAttemptState {
 pub struct PairingGuard {
     /// Whether pairing is required at all.
     require_pairing: bool,
-    /// One-time pairing code (generated on startup, consumed on first pair).
-    pairing_code: Arc<Mutex<Option<String>>>,
+    /// One-time pairing code (generated on startup or issued on demand,
+    /// consumed on first pair).
+    pairing_code: Arc<Mutex<Option<PairingCode>>>,
     /// Set of SHA-256 hashed bearer tokens (persisted across restarts).
     paired_tokens: Arc<Mutex<HashSet<String>>>,
+    /// SHA-256 hash of the gateway's per-install API token, if any.
+    api_token: Arc<Mutex<Option<String>>>,
     /// Brute-force protection: per-client failed attempt state + last sweep timestamp.
     failed_attempts: Arc<Mutex<(HashMap<String, FailedAttemptState>, Instant)>>,
 }
@@ -72,7 +92,10 @@ impl PairingGuard {
             })
             .collect();
         let code = if require_pairing && tokens.is_empty() {
-            Some(generate_code())
+            Some(PairingCode {
+                code: generate_code(),
+                expires_at: None,
+            })
         } else {
             None
         };
@@ -80,13 +103,42 @@ impl PairingGuard {
             require_pairing,
             pairing_code: Arc::new(Mutex::new(code)),
             paired_tokens: Arc::new(Mutex::new(tokens)),
+            api_token: Arc::new(Mutex::new(None)),
             failed_attempts: Arc::new(Mutex::new((HashMap::new(), Instant::now()))),
         }
     }
 
-    /// The one-time pairing code (only set when no tokens exist yet).
+    /// Also accept `token`, the gateway's per-install API token. Requests
+    /// then need a valid token even when pairing is not required.
+    pub fn with_api_token(self, token: &str) -> Self {
+        self.set_api_token(token);
+        self
+    }
+
+    /// Replace the API token. The previous one stops working immediately.
+    pub fn set_api_token(&self, token: &str) {
+        *self.api_token.lock() = Some(hash_token(token));
+    }
+
+    /// The current one-time pairing code: the startup code (only set when no
+    /// tokens exist yet) or an unexpired code from [`Self::issue_code`].
     pub fn pairing_code(&self) -> Option<String> {
-        self.pairing_code.lock().clone()
+        self.pairing_code
+            .lock()
+            .as_ref()
+            .filter(|code| !code.is_expired(Instant::now()))
+            .map(|code| code.code.clone())
+    }
+
+    /// Issue a fresh pairing code valid for `ttl`, replacing any earlier one.
+    /// Works whether or not clients are already paired.
+    pub fn issue_code(&self, ttl: Duration) -> String {
+        let code = generate_code();
+        *self.pairing_code.lock() = Some(PairingCode {
+            code: code.clone(),
+            expires_at: Some(Instant::now() + ttl),
+        });
+        code
     }
 
     /// Whether pairing is required at all.
@@ -94,6 +146,12 @@ impl PairingGuard {
         self.require_pairing
     }
 
+    /// Whether requests must carry a bearer token: pairing is required or
+    /// an API token is set.
+    pub fn requires_auth(&self) -> bool {
+        self.require_pairing || self.api_token.lock().is_some()
+    }
+
     fn try_pair_blocking(&self, code: &str, client_id: &str) -> Result<Option<String>, u64> {
         let client_id = normalize_client_key(client_id);
         let now = Instant::now();
@@ -124,8 +182,14 @@ impl PairingGuard {
 
         {
             let mut pairing_code = self.pairing_code.lock();
+            if pairing_code
+                .as_ref()
+                .is_some_and(|expected| expected.is_expired(now))
+            {
+                *pairing_code = None;
+            }
             if let Some(ref expected) = *pairing_code {
-                if constant_time_eq(code.trim(), expected.trim()) {
+                if constant_time_eq(code.trim(), expected.code.trim()) {
                     // Reset failed attempts for this client on success
                     {
                         let mut guard = self.failed_attempts.lock();
@@ -197,10 +261,18 @@ impl PairingGuard {
 
     /// Check if a bearer token is valid (compares against stored hashes).
     pub fn is_authenticated(&self, token: &str) -> bool {
-        if !self.require_pairing {
+        if !self.requires_auth() {
             return true;
         }
         let hashed = hash_token(token);
+        if self
+            .api_token
+            .lock()
+            .as_deref()
+            .is_some_and(|api_token| constant_time_eq(&hashed, api_token))
+        {
+            return true;
+        }
         let tokens = self.paired_tokens.lock();
         tokens.contains(&hashed)
     }
@@ -211,6 +283,39 @@ impl PairingGuard {
         !tokens.is_empty()
     }
 
+    /// Ids of the paired tokens, sorted. An id is a prefix of the token's
+    /// hash, so it can be listed without exposing anything usable.
+    pub fn token_ids(&self) -> Vec<String> {
+        let tokens = self.paired_tokens.lock();
+        let mut ids: Vec<String> = tokens.iter().map(|hash| token_id(hash)).collect();
+        ids.sort();
+        ids
+    }
+
+    /// Id of `token` if it is a paired token. The API token has none.
+    pub fn paired_token_id(&self, token: &str) -> Option<String> {
+        let hashed = hash_token(token);
+        let tokens = self.paired_tokens.lock();
+        tokens.contains(&hashed).then(|| token_id(&hashed))
+    }
+
+    /// Revoke the paired token with id `id`. Returns false if none matched.
+    pub fn revoke(&self, id: &str) -> bool {
+        let id = id.trim().to_ascii_lowercase();
+        let mut tokens = self.paired_tokens.lock();
+        let before = tokens.len();
+        tokens.retain(|hash| token_id(hash) != id);
+        tokens.len() != before
+    }
+
+    /// Revoke every paired token. Returns how many were revoked.
+    pub fn revoke_all(&self) -> usize {
+        let mut tokens = self.paired_tokens.lock();
+        let revoked = tokens.len();
+        tokens.clear();
+        revoked
+    }
+
     /// Get all paired token hashes (for persisting to config).
     pub fn tokens(&self) -> Vec<String> {
         let tokens = self.paired_tokens.lock();
@@ -265,7 +370,7 @@ fn generate_code() -> String {
 /// (/dev/urandom on Linux, BCryptGenRandom on Windows, SecRandomCopyBytes
 /// on macOS). The 32 random bytes (256 bits) are hex-encoded for a
 /// 64-character token, providing 256 bits of entropy.
-fn generate_token() -> String {
+pub(crate) fn generate_token() -> String {
     let bytes: [u8; 32] = rand::random();
     format!("zc_{}", hex::encode(bytes))
 }
@@ -275,6 +380,11 @@ fn hash_token(token: &str) -> String {
     format!("{:x}", Sha256::digest(token.as_bytes()))
 }
 
+/// Id of a paired token, from its stored hash.
+fn token_id(hash: &str) -> String {
+    hash.chars().take(TOKEN_ID_LEN).collect()
+}
+
 /// Check if a stored value looks like a SHA-256 hash (64 hex chars)
 /// rather than a plaintext token.
 fn is_token_hash(value: &str) -> bool {
@@ -392,6 +502,68 @@ mod tests {
         assert!(guard.is_authenticated(""));
     }
 
+    #[test]
+    async fn issued_code_pairs_even_when_already_paired() {
+        let guard = PairingGuard::new(true, &["zc_existing".into()]);
+        assert!(guard.pairing_code().is_none());
+        let code = guard.issue_code(Duration::from_secs(60));
+        assert_eq!(guard.pairing_code(), Some(code.clone()));
+
+        let token = guard.try_pair(&code, "qr").await.unwrap().unwrap();
+        assert!(guard.is_authenticated(&token));
+        assert!(guard.pairing_code().is_none());
+        assert_eq!(guard.token_ids().len(), 2);
+    }
+
+    #[test]
+    async fn expired_code_is_rejected() {
+        let guard = PairingGuard::new(true, &["zc_existing".into()]);
+        let code = guard.issue_code(Duration::ZERO);
+        assert!(guard.pairing_code().is_none());
+        assert!(guard.try_pair(&code, "qr").await.unwrap().is_none());
+    }
+
+    #[test]
+    async fn revoked_token_is_no_longer_accepted() {
+        let guard = PairingGuard::new(true, &["zc_one".into(), "zc_two".into()]);
+        let id = token_id(&hash_token("zc_one"));
+        assert_eq!(id.len(), TOKEN_ID_LEN);
+        assert!(guard.token_ids().contains(&id));
+        assert_eq!(guard.paired_token_id("zc_one"), Some(id.clone()));
+        assert!(guard.paired_token_id("zc_unknown").is_none());
+
+        assert!(guard.revoke(&id));
+        assert!(!guard.revoke(&id));
+        assert!(!guard.is_authenticated("zc_one"));
+        assert!(guard.is_authenticated("zc_two"));
+        assert_eq!(guard.tokens().len(), 1);
+    }
+
+    #[test]
+    async fn api_token_is_required_even_when_pairing_disabled() {
+        let guard = PairingGuard::new(false, &["zc_paired".into()]).with_api_token("zc_api");
+        assert!(guard.requires_auth());
+        assert!(guard.pairing_code().is_none());
+        assert!(guard.is_authenticated("zc_api"));
+        assert!(guard.is_authenticated("zc_paired"));
+        assert!(!guard.is_authenticated("zc_other"));
+        assert!(!guard.is_authenticated(""));
+    }
+
+    #[test]
+    async fn rotated_api_token_replaces_old_and_revoke_all_clears_paired() {
+        let guard =
+            PairingGuard::new(true, &["zc_a".into(), "zc_b".into()]).with_api_token("zc_old");
+        guard.set_api_token("zc_new");
+        assert!(!guard.is_authenticated("zc_old"));
+        assert!(guard.is_authenticated("zc_new"));
+
+        assert_eq!(guard.revoke_all(), 2);
+        assert!(!guard.is_authenticated("zc_a"));
+        assert!(!guard.is_paired());
+        assert!(guard.is_authenticated("zc_new"));
+    }
+
     #[test]
     async fn tokens_returns_hashes() {
         let guard = PairingGuard::new(true, &["zc_a".into(), "zc_b".into()]);
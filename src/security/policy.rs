@@ -9,6 +9,7 @@ use std::time::Instant;
 #[serde(rename_all = "lowercase")]
 pub enum AutonomyLevel {
     /// Read-only: can observe but not act
+    #[serde(alias = "read_only")]
     ReadOnly,
     /// Supervised: acts but requires approval for risky operations
     #[default]
@@ -91,6 +92,10 @@ pub struct SecurityPolicy {
     pub require_approval_for_medium_risk: bool,
     pub block_high_risk_commands: bool,
     pub shell_env_passthrough: Vec<String>,
+    pub allowed_sms_numbers: Vec<String>,
+    pub allow_location: bool,
+    pub denied_domains: Vec<String>,
+    pub max_scheduled_jobs: usize,
     pub tracker: ActionTracker,
 }
 
@@ -143,6 +148,10 @@ impl Default for SecurityPolicy {
             require_approval_for_medium_risk: true,
             block_high_risk_commands: true,
             shell_env_passthrough: vec![],
+            allowed_sms_numbers: vec![],
+            allow_location: false,
+            denied_domains: vec![],
+            max_scheduled_jobs: 20,
             tracker: ActionTracker::new(),
         }
     }
@@ -1042,6 +1051,50 @@ impl SecurityPolicy {
         self.tracker.count() >= self.max_actions_per_hour as usize
     }
 
+    /// Check a phone number against the SMS allowlist.
+    ///
+    /// Numbers are compared after stripping formatting (spaces, dashes,
+    /// parentheses). An empty allowlist denies every number.
+    pub fn is_sms_number_allowed(&self, number: &str) -> bool {
+        let normalize = |raw: &str| {
+            raw.chars()
+                .filter(|c| c.is_ascii_digit() || *c == '+')
+                .collect::<String>()
+        };
+        let number = normalize(number);
+        !number.is_empty()
+            && self
+                .allowed_sms_numbers
+                .iter()
+                .any(|allowed| normalize(allowed) == number)
+    }
+
+    /// Check a host against the outbound domain denylist.
+    ///
+    /// Entries match the domain itself and any subdomain. Network tools apply
+    /// this before their own allowlists, so a denied domain always wins.
+    pub fn is_domain_denied(&self, host: &str) -> bool {
+        let host = host.trim_end_matches('.').to_ascii_lowercase();
+        self.denied_domains.iter().any(|domain| {
+            host == *domain
+                || host
+                    .strip_suffix(domain.as_str())
+                    .is_some_and(|prefix| prefix.ends_with('.'))
+        })
+    }
+
+    /// Check whether another scheduled job may be created when `existing`
+    /// jobs are already registered.
+    pub fn check_scheduled_job_capacity(&self, existing: usize) -> Result<(), String> {
+        if existing >= self.max_scheduled_jobs {
+            return Err(format!(
+                "Scheduled job limit reached ({existing}/{}); remove an existing job or raise autonomy.max_scheduled_jobs",
+                self.max_scheduled_jobs
+            ));
+        }
+        Ok(())
+    }
+
     /// Build from config sections
     pub fn from_config(
         autonomy_config: &crate::config::AutonomyConfig,
@@ -1070,6 +1123,15 @@ impl SecurityPolicy {
             require_approval_for_medium_risk: autonomy_config.require_approval_for_medium_risk,
             block_high_risk_commands: autonomy_config.block_high_risk_commands,
             shell_env_passthrough: autonomy_config.shell_env_passthrough.clone(),
+            allowed_sms_numbers: autonomy_config.allowed_sms_numbers.clone(),
+            allow_location: autonomy_config.allow_location,
+            denied_domains: autonomy_config
+                .denied_domains
+                .iter()
+                .map(|d| d.trim().trim_start_matches("*.").to_ascii_lowercase())
+                .filter(|d| !d.is_empty())
+                .collect(),
+            max_scheduled_jobs: autonomy_config.max_scheduled_jobs,
             tracker: ActionTracker::new(),
         }
     }
@@ -1450,6 +1512,50 @@ mod tests {
         assert_eq!(policy.workspace_dir, PathBuf::from("/tmp/test-workspace"));
     }
 
+    #[test]
+    fn sms_allowlist_normalizes_and_denies_by_default() {
+        assert!(!default_policy().is_sms_number_allowed("+15551234567"));
+
+        let policy = SecurityPolicy {
+            allowed_sms_numbers: vec!["+1 (555) 123-4567".into()],
+            ..SecurityPolicy::default()
+        };
+        assert!(policy.is_sms_number_allowed("+15551234567"));
+        assert!(!policy.is_sms_number_allowed("+15550000000"));
+        assert!(!policy.is_sms_number_allowed("not a number"));
+    }
+
+    #[test]
+    fn domain_denylist_matches_subdomains() {
+        let autonomy_config = crate::config::AutonomyConfig {
+            denied_domains: vec!["*.Tracker.example".into(), " ads.test ".into()],
+            ..crate::config::AutonomyConfig::default()
+        };
+        let policy =
+            SecurityPolicy::from_config(&autonomy_config, Path::new("/tmp/test-workspace"));
+        assert!(policy.is_domain_denied("tracker.example"));
+        assert!(policy.is_domain_denied("api.tracker.example"));
+        assert!(policy.is_domain_denied("ADS.test"));
+        assert!(!policy.is_domain_denied("nottracker.example"));
+        assert!(!default_policy().is_domain_denied("tracker.example"));
+    }
+
+    #[test]
+    fn scheduled_job_capacity_is_enforced() {
+        let policy = SecurityPolicy {
+            max_scheduled_jobs: 2,
+            ..SecurityPolicy::default()
+        };
+        assert!(policy.check_scheduled_job_capacity(1).is_ok());
+        assert!(policy.check_scheduled_job_capacity(2).is_err());
+
+        let disabled = SecurityPolicy {
+            max_scheduled_jobs: 0,
+            ..SecurityPolicy::default()
+        };
+        assert!(disabled.check_scheduled_job_capacity(0).is_err());
+    }
+
     #[test]
     fn from_config_normalizes_allowed_roots() {
         let autonomy_config = crate::config::AutonomyConfig {
@@ -19,12 +19,21 @@
 // Migration: values with the legacy `enc:` prefix (XOR cipher) are decrypted
 // using the old algorithm for backward compatibility. New encryptions always
 // produce `enc2:` (ChaCha20-Poly1305).
+//
+// Keystore backend: inside the mobile app, `secrets.backend = "keystore"`
+// hands encryption to the host (e.g. an Android Keystore key that never
+// leaves the device's secure hardware) through the host bridge actions
+// `keystore_encrypt` / `keystore_decrypt`. Values are stored as `ks1:<host
+// ciphertext>`, so a copied config or backup is useless without the device.
 
+use crate::runtime::host_bridge::{self, HostBridge};
 use anyhow::{Context, Result};
 use chacha20poly1305::aead::{Aead, KeyInit, OsRng};
 use chacha20poly1305::{AeadCore, ChaCha20Poly1305, Key, Nonce};
+use serde_json::json;
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
 
 /// Length of the random encryption key in bytes (256-bit, matches `ChaCha20`).
 const KEY_LEN: usize = 32;
@@ -32,13 +41,33 @@ const KEY_LEN: usize = 32;
 /// ChaCha20-Poly1305 nonce length in bytes.
 const NONCE_LEN: usize = 12;
 
+/// Prefix of values encrypted by the host keystore.
+const KEYSTORE_PREFIX: &str = "ks1:";
+
+/// Keystore key alias the host encrypts config secrets with.
+const KEYSTORE_ALIAS: &str = "zeroclaw_secrets";
+
 /// Manages encrypted storage of secrets (API keys, tokens, etc.)
-#[derive(Debug, Clone)]
+#[derive(Clone)]
 pub struct SecretStore {
     /// Path to the key file (`~/.zeroclaw/.secret_key`)
     key_path: PathBuf,
     /// Whether encryption is enabled
     enabled: bool,
+    /// Encrypt new values with the host keystore instead of the key file
+    keystore: bool,
+    /// Explicit host bridge; the process-wide one is used when unset
+    bridge: Option<Arc<dyn HostBridge>>,
+}
+
+impl std::fmt::Debug for SecretStore {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SecretStore")
+            .field("key_path", &self.key_path)
+            .field("enabled", &self.enabled)
+            .field("keystore", &self.keystore)
+            .finish_non_exhaustive()
+    }
 }
 
 impl SecretStore {
@@ -47,16 +76,45 @@ impl SecretStore {
         Self {
             key_path: zeroclaw_dir.join(".secret_key"),
             enabled,
+            keystore: false,
+            bridge: None,
         }
     }
 
+    /// Create the store for config secrets as set in `[secrets]`.
+    pub fn from_config(zeroclaw_dir: &Path, config: &crate::config::SecretsConfig) -> Self {
+        let store = Self::new(zeroclaw_dir, config.encrypt);
+        if config.backend == crate::config::SecretsBackend::Keystore {
+            store.with_keystore()
+        } else {
+            store
+        }
+    }
+
+    /// Encrypt new values with the host keystore (`ks1:`) instead of the
+    /// local key file.
+    pub fn with_keystore(mut self) -> Self {
+        self.keystore = true;
+        self
+    }
+
+    /// Use an explicit host bridge instead of the process-wide registered one.
+    pub fn with_bridge(mut self, bridge: Arc<dyn HostBridge>) -> Self {
+        self.bridge = Some(bridge);
+        self
+    }
+
     /// Encrypt a plaintext secret. Returns hex-encoded ciphertext prefixed with `enc2:`.
     /// Format: `enc2:<hex(nonce ‖ ciphertext ‖ tag)>` (12 + N + 16 bytes).
+    /// With the keystore backend the host encrypts it instead (`ks1:`).
     /// If encryption is disabled, returns the plaintext as-is.
     pub fn encrypt(&self, plaintext: &str) -> Result<String> {
         if !self.enabled || plaintext.is_empty() {
             return Ok(plaintext.to_string());
         }
+        if self.keystore {
+            return self.encrypt_keystore(plaintext);
+        }
 
         let key_bytes = self.load_or_create_key()?;
         let key = Key::from_slice(&key_bytes);
@@ -76,6 +134,7 @@ impl SecretStore {
     }
 
     /// Decrypt a secret.
+    /// - `ks1:` prefix → host keystore
     /// - `enc2:` prefix → ChaCha20-Poly1305 (current format)
     /// - `enc:` prefix → legacy XOR cipher (backward compatibility for migration)
     /// - No prefix → returned as-is (plaintext config)
@@ -83,7 +142,9 @@ impl SecretStore {
     /// **Warning**: Legacy `enc:` values are insecure. Use `decrypt_and_migrate` to
     /// automatically upgrade them to the secure `enc2:` format.
     pub fn decrypt(&self, value: &str) -> Result<String> {
-        if let Some(hex_str) = value.strip_prefix("enc2:") {
+        if let Some(ciphertext) = value.strip_prefix(KEYSTORE_PREFIX) {
+            self.decrypt_keystore(ciphertext)
+        } else if let Some(hex_str) = value.strip_prefix("enc2:") {
             self.decrypt_chacha20(hex_str)
         } else if let Some(hex_str) = value.strip_prefix("enc:") {
             self.decrypt_legacy_xor(hex_str)
@@ -99,7 +160,9 @@ impl SecretStore {
     ///
     /// This allows callers to persist the upgraded value back to config.
     pub fn decrypt_and_migrate(&self, value: &str) -> Result<(String, Option<String>)> {
-        if let Some(hex_str) = value.strip_prefix("enc2:") {
+        if let Some(ciphertext) = value.strip_prefix(KEYSTORE_PREFIX) {
+            Ok((self.decrypt_keystore(ciphertext)?, None))
+        } else if let Some(hex_str) = value.strip_prefix("enc2:") {
             // Already using secure format — no migration needed
             let plaintext = self.decrypt_chacha20(hex_str)?;
             Ok((plaintext, None))
@@ -157,9 +220,53 @@ impl SecretStore {
             .context("Decrypted legacy secret is not valid UTF-8 — wrong key or corrupt data")
     }
 
+    /// Encrypt through the host keystore.
+    fn encrypt_keystore(&self, plaintext: &str) -> Result<String> {
+        let reply = self
+            .call_host(
+                "keystore_encrypt",
+                json!({ "alias": KEYSTORE_ALIAS, "plaintext": plaintext }),
+            )
+            .context("Keystore encryption failed")?;
+        let ciphertext = reply
+            .get("ciphertext")
+            .and_then(serde_json::Value::as_str)
+            .filter(|ciphertext| !ciphertext.is_empty())
+            .context("Keystore encryption reply is missing 'ciphertext'")?;
+        anyhow::ensure!(
+            ciphertext != plaintext,
+            "Keystore returned the secret unencrypted"
+        );
+        Ok(format!("{KEYSTORE_PREFIX}{ciphertext}"))
+    }
+
+    /// Decrypt a `ks1:` value through the host keystore.
+    fn decrypt_keystore(&self, ciphertext: &str) -> Result<String> {
+        let reply = self
+            .call_host(
+                "keystore_decrypt",
+                json!({ "alias": KEYSTORE_ALIAS, "ciphertext": ciphertext }),
+            )
+            .context("Keystore decryption failed (encrypted on another device or key reset?)")?;
+        reply
+            .get("plaintext")
+            .and_then(serde_json::Value::as_str)
+            .map(ToString::to_string)
+            .context("Keystore decryption reply is missing 'plaintext'")
+    }
+
+    fn call_host(&self, action: &str, payload: serde_json::Value) -> Result<serde_json::Value> {
+        match &self.bridge {
+            Some(bridge) => bridge.call(action, payload),
+            None => host_bridge::call_host(action, payload),
+        }
+    }
+
     /// Check if a value is already encrypted (current or legacy format).
     pub fn is_encrypted(value: &str) -> bool {
-        value.starts_with("enc2:") || value.starts_with("enc:")
+        value.starts_with(KEYSTORE_PREFIX)
+            || value.starts_with("enc2:")
+            || value.starts_with("enc:")
     }
 
     /// Check if a value uses the secure `enc2:` format.
@@ -488,6 +595,88 @@ mod tests {
         assert!(!SecretStore::is_secure_encrypted(""));
     }
 
+    // ── Keystore backend ───────────────────────────────────────
+
+    /// Stand-in for the Android Keystore: "encrypts" by hex-encoding the
+    /// reversed plaintext and only accepts its own alias.
+    struct FakeKeystore;
+
+    impl HostBridge for FakeKeystore {
+        fn call(&self, action: &str, payload: serde_json::Value) -> Result<serde_json::Value> {
+            anyhow::ensure!(payload["alias"] == KEYSTORE_ALIAS, "unknown key alias");
+            match action {
+                "keystore_encrypt" => {
+                    let plaintext = payload["plaintext"].as_str().unwrap_or_default();
+                    let reversed: Vec<u8> = plaintext.bytes().rev().collect();
+                    Ok(json!({ "ciphertext": hex_encode(&reversed) }))
+                }
+                "keystore_decrypt" => {
+                    let mut bytes = hex_decode(payload["ciphertext"].as_str().unwrap_or_default())?;
+                    bytes.reverse();
+                    Ok(json!({ "plaintext": String::from_utf8(bytes)? }))
+                }
+                other => anyhow::bail!("unexpected action {other}"),
+            }
+        }
+    }
+
+    #[test]
+    fn keystore_backend_encrypts_through_host() {
+        let tmp = TempDir::new().unwrap();
+        let store = SecretStore::new(tmp.path(), true)
+            .with_keystore()
+            .with_bridge(Arc::new(FakeKeystore));
+
+        let encrypted = store.encrypt("123456:telegram-bot-token").unwrap();
+        assert!(encrypted.starts_with("ks1:"));
+        assert!(!encrypted.contains("telegram-bot-token"));
+        assert!(SecretStore::is_encrypted(&encrypted));
+        assert_eq!(
+            store.decrypt(&encrypted).unwrap(),
+            "123456:telegram-bot-token"
+        );
+        assert!(
+            !tmp.path().join(".secret_key").exists(),
+            "Keystore backend must not create a local key file"
+        );
+    }
+
+    #[test]
+    fn local_store_still_decrypts_keystore_values() {
+        let tmp = TempDir::new().unwrap();
+        let keystore = SecretStore::new(tmp.path(), true)
+            .with_keystore()
+            .with_bridge(Arc::new(FakeKeystore));
+        let encrypted = keystore.encrypt("sk-switch-backends").unwrap();
+
+        let local = SecretStore::new(tmp.path(), true).with_bridge(Arc::new(FakeKeystore));
+        assert_eq!(local.decrypt(&encrypted).unwrap(), "sk-switch-backends");
+        assert!(local
+            .encrypt("sk-switch-backends")
+            .unwrap()
+            .starts_with("enc2:"));
+    }
+
+    #[test]
+    fn keystore_errors_surface_as_decrypt_failures() {
+        struct RejectingKeystore;
+
+        impl HostBridge for RejectingKeystore {
+            fn call(
+                &self,
+                _action: &str,
+                _payload: serde_json::Value,
+            ) -> Result<serde_json::Value> {
+                anyhow::bail!("KeyPermanentlyInvalidatedException")
+            }
+        }
+
+        let tmp = TempDir::new().unwrap();
+        let store = SecretStore::new(tmp.path(), true).with_bridge(Arc::new(RejectingKeystore));
+        let err = store.decrypt("ks1:deadbeef").unwrap_err();
+        assert!(format!("{err:#}").contains("Keystore decryption failed"));
+    }
+
     #[test]
     fn decrypt_and_migrate_returns_none_for_enc2() {
         let tmp = TempDir::new().unwrap();
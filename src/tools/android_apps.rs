@@ -0,0 +1,189 @@
+use super::android_bridge::{bridge_tool_result, AndroidBridge};
+use super::traits::{Tool, ToolResult};
+use crate::config::AndroidConfig;
+use async_trait::async_trait;
+use serde_json::json;
+
+const MAX_QUERY_CHARS: usize = 100;
+const DEFAULT_USAGE_HOURS: u64 = 24;
+/// UsageStatsManager keeps daily buckets for about a week.
+const MAX_USAGE_HOURS: u64 = 7 * 24;
+const DEFAULT_LIMIT: u64 = 20;
+const MAX_LIMIT: u64 = 200;
+
+/// Query installed apps and their recent usage through the host app
+/// (`PackageManager` / `UsageStatsManager`).
+pub struct AndroidAppsTool {
+    config: AndroidConfig,
+    bridge: AndroidBridge,
+}
+
+impl AndroidAppsTool {
+    pub fn new(config: AndroidConfig) -> Self {
+        Self {
+            bridge: AndroidBridge::new(config.bridge.clone()),
+            config,
+        }
+    }
+
+    fn limit(args: &serde_json::Value) -> u64 {
+        args.get("limit")
+            .and_then(serde_json::Value::as_u64)
+            .map_or(DEFAULT_LIMIT, |v| v.clamp(1, MAX_LIMIT))
+    }
+
+    /// Validate arguments and return the bridge action and payload.
+    fn build_call(
+        &self,
+        args: &serde_json::Value,
+    ) -> anyhow::Result<(&'static str, serde_json::Value)> {
+        match args.get("action").and_then(serde_json::Value::as_str) {
+            Some("list") => {
+                let query = args
+                    .get("query")
+                    .and_then(serde_json::Value::as_str)
+                    .map(str::trim)
+                    .filter(|v| !v.is_empty());
+                if query.is_some_and(|q| q.chars().count() > MAX_QUERY_CHARS) {
+                    anyhow::bail!("'query' exceeds {MAX_QUERY_CHARS} characters");
+                }
+                let include_system = args
+                    .get("include_system")
+                    .and_then(serde_json::Value::as_bool)
+                    .unwrap_or(false);
+                Ok((
+                    "list_installed_apps",
+                    json!({
+                        "query": query,
+                        "include_system": include_system,
+                        "limit": Self::limit(args),
+                    }),
+                ))
+            }
+            Some("usage") => {
+                if !self.config.capabilities.usage_stats {
+                    anyhow::bail!("android capability usage_stats is disabled");
+                }
+                let since_hours = args
+                    .get("since_hours")
+                    .and_then(serde_json::Value::as_u64)
+                    .map_or(DEFAULT_USAGE_HOURS, |v| v.clamp(1, MAX_USAGE_HOURS));
+                Ok((
+                    "app_usage_stats",
+                    json!({ "since_hours": since_hours, "limit": Self::limit(args) }),
+                ))
+            }
+            Some(other) => anyhow::bail!("Unknown action '{other}'. Supported: list, usage"),
+            None => anyhow::bail!("Missing 'action' parameter"),
+        }
+    }
+}
+
+#[async_trait]
+impl Tool for AndroidAppsTool {
+    fn name(&self) -> &str {
+        "android_apps"
+    }
+
+    fn description(&self) -> &str {
+        "Query apps on the user's Android device. action=list returns installed apps (label and package), optionally filtered by a name query — use it to find the package for android_intent open_app. action=usage returns per-app foreground time, launch count, last-used time and battery share (when the device reports it) for the last N hours, most used first. Read-only."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "action": {
+                    "type": "string",
+                    "enum": ["list", "usage"],
+                    "description": "list installed apps or report recent usage"
+                },
+                "query": {
+                    "type": "string",
+                    "description": "App label or package fragment to match (list)"
+                },
+                "include_system": {
+                    "type": "boolean",
+                    "description": "Include system apps without a launcher icon (list)",
+                    "default": false
+                },
+                "since_hours": {
+                    "type": "integer",
+                    "description": "Usage window in hours, max 168 (usage)",
+                    "default": DEFAULT_USAGE_HOURS
+                },
+                "limit": {
+                    "type": "integer",
+                    "description": "Maximum apps to return",
+                    "default": DEFAULT_LIMIT
+                }
+            },
+            "required": ["action"]
+        })
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        if !self.config.capabilities.app_launch {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some("android capability app_launch is disabled".into()),
+            });
+        }
+
+        let (action, payload) = match self.build_call(&args) {
+            Ok(call) => call,
+            Err(error) => {
+                return Ok(ToolResult {
+                    success: false,
+                    output: String::new(),
+                    error: Some(error.to_string()),
+                });
+            }
+        };
+
+        Ok(bridge_tool_result(self.bridge.call(action, payload).await))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_config(usage_stats: bool) -> AndroidConfig {
+        let mut config = AndroidConfig {
+            enabled: true,
+            ..AndroidConfig::default()
+        };
+        config.capabilities.usage_stats = usage_stats;
+        config
+    }
+
+    #[tokio::test]
+    async fn list_forwards_query_to_bridge() {
+        let result = AndroidAppsTool::new(test_config(false))
+            .execute(json!({ "action": "list", "query": "spotify" }))
+            .await
+            .unwrap();
+        assert!(result.success, "{:?}", result.error);
+        assert!(result.output.contains("list_installed_apps"));
+        assert!(result.output.contains("spotify"));
+    }
+
+    #[tokio::test]
+    async fn usage_requires_capability_and_clamps_window() {
+        let blocked = AndroidAppsTool::new(test_config(false))
+            .execute(json!({ "action": "usage" }))
+            .await
+            .unwrap();
+        assert!(!blocked.success);
+        assert!(blocked.error.unwrap().contains("usage_stats"));
+
+        let tool = AndroidAppsTool::new(test_config(true));
+        let (action, payload) = tool
+            .build_call(&json!({ "action": "usage", "since_hours": 10_000 }))
+            .unwrap();
+        assert_eq!(action, "app_usage_stats");
+        assert_eq!(payload["since_hours"], MAX_USAGE_HOURS);
+    }
+}
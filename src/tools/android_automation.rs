@@ -0,0 +1,312 @@
+use super::android_bridge::{bridge_tool_result, AndroidBridge};
+use super::traits::{Tool, ToolResult};
+use crate::config::AndroidConfig;
+use crate::security::policy::ToolOperation;
+use crate::security::SecurityPolicy;
+use async_trait::async_trait;
+use serde_json::json;
+use std::sync::Arc;
+
+/// Tasker's external task API accepts `%par1`..`%par9`.
+const MAX_TASKER_PARAMS: usize = 9;
+const MAX_EXTRAS: usize = 32;
+const MAX_VALUE_CHARS: usize = 2000;
+
+/// Hook into on-device automation: run Tasker tasks or send custom
+/// broadcast intents through the host app.
+pub struct AndroidAutomationTool {
+    security: Arc<SecurityPolicy>,
+    config: AndroidConfig,
+    bridge: AndroidBridge,
+}
+
+impl AndroidAutomationTool {
+    pub fn new(security: Arc<SecurityPolicy>, config: AndroidConfig) -> Self {
+        Self {
+            security,
+            bridge: AndroidBridge::new(config.bridge.clone()),
+            config,
+        }
+    }
+
+    fn required_str<'a>(
+        args: &'a serde_json::Value,
+        name: &str,
+        action: &str,
+    ) -> anyhow::Result<&'a str> {
+        args.get(name)
+            .and_then(serde_json::Value::as_str)
+            .map(str::trim)
+            .filter(|v| !v.is_empty())
+            .ok_or_else(|| anyhow::anyhow!("{action} requires '{name}'"))
+    }
+
+    fn is_valid_action_name(value: &str) -> bool {
+        !value.starts_with('.')
+            && !value.ends_with('.')
+            && value.contains('.')
+            && value
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '_'))
+    }
+
+    fn tasker_payload(&self, args: &serde_json::Value) -> anyhow::Result<serde_json::Value> {
+        let task = Self::required_str(args, "task", "run_tasker_task")?;
+        let allowed = &self.config.policy.allowed_tasker_tasks;
+        if !allowed.is_empty() && !allowed.iter().any(|t| t == task) {
+            anyhow::bail!("Tasker task '{task}' is not in android.policy.allowed_tasker_tasks");
+        }
+
+        let params = match args.get("params") {
+            None | Some(serde_json::Value::Null) => Vec::new(),
+            Some(serde_json::Value::Array(items)) => {
+                if items.len() > MAX_TASKER_PARAMS {
+                    anyhow::bail!("Tasker accepts at most {MAX_TASKER_PARAMS} params");
+                }
+                items
+                    .iter()
+                    .map(|item| match item {
+                        serde_json::Value::String(s) => s.clone(),
+                        other => other.to_string(),
+                    })
+                    .collect()
+            }
+            Some(_) => anyhow::bail!("'params' must be an array of strings"),
+        };
+        if params.iter().any(|p| p.chars().count() > MAX_VALUE_CHARS) {
+            anyhow::bail!("Tasker params are limited to {MAX_VALUE_CHARS} characters each");
+        }
+
+        Ok(json!({ "task": task, "params": params }))
+    }
+
+    fn broadcast_payload(&self, args: &serde_json::Value) -> anyhow::Result<serde_json::Value> {
+        let intent_action = Self::required_str(args, "intent_action", "send_broadcast")?;
+        if !Self::is_valid_action_name(intent_action) {
+            anyhow::bail!("'{intent_action}' is not a valid intent action name");
+        }
+        // Broadcasts can reach any receiver on the device, so they are
+        // deny-by-default rather than allow-when-empty.
+        if !self
+            .config
+            .policy
+            .allowed_broadcast_actions
+            .iter()
+            .any(|allowed| allowed == intent_action)
+        {
+            anyhow::bail!(
+                "Broadcast action '{intent_action}' is not in android.policy.allowed_broadcast_actions"
+            );
+        }
+
+        let package = args
+            .get("package")
+            .and_then(serde_json::Value::as_str)
+            .map(str::trim)
+            .filter(|v| !v.is_empty());
+        if package.is_some_and(|p| !Self::is_valid_action_name(p)) {
+            anyhow::bail!("'package' is not a valid Android package id");
+        }
+
+        let extras = match args.get("extras") {
+            None | Some(serde_json::Value::Null) => serde_json::Map::new(),
+            Some(serde_json::Value::Object(map)) => {
+                if map.len() > MAX_EXTRAS {
+                    anyhow::bail!("At most {MAX_EXTRAS} extras are allowed");
+                }
+                for (key, value) in map {
+                    match value {
+                        serde_json::Value::String(s) if s.chars().count() > MAX_VALUE_CHARS => {
+                            anyhow::bail!("Extra '{key}' exceeds {MAX_VALUE_CHARS} characters")
+                        }
+                        serde_json::Value::String(_)
+                        | serde_json::Value::Number(_)
+                        | serde_json::Value::Bool(_) => {}
+                        _ => anyhow::bail!("Extra '{key}' must be a string, number, or boolean"),
+                    }
+                }
+                map.clone()
+            }
+            Some(_) => anyhow::bail!("'extras' must be an object"),
+        };
+
+        Ok(json!({
+            "intent_action": intent_action,
+            "package": package,
+            "extras": extras,
+        }))
+    }
+
+    /// Validate arguments and return the bridge action and payload.
+    fn build_call(
+        &self,
+        args: &serde_json::Value,
+    ) -> anyhow::Result<(&'static str, serde_json::Value)> {
+        match args.get("action").and_then(serde_json::Value::as_str) {
+            Some("run_tasker_task") => Ok(("tasker_run_task", self.tasker_payload(args)?)),
+            Some("send_broadcast") => Ok(("send_broadcast", self.broadcast_payload(args)?)),
+            Some(other) => anyhow::bail!(
+                "Unknown action '{other}'. Supported: run_tasker_task, send_broadcast"
+            ),
+            None => anyhow::bail!("Missing 'action' parameter"),
+        }
+    }
+}
+
+#[async_trait]
+impl Tool for AndroidAutomationTool {
+    fn name(&self) -> &str {
+        "android_automation"
+    }
+
+    fn description(&self) -> &str {
+        "Trigger the user's existing on-device automations: run_tasker_task runs a named Tasker task (optional params become %par1..%par9); send_broadcast sends a custom broadcast intent (action name, optional target package, string/number/bool extras) for apps like Automate or MacroDroid. Broadcast actions must be listed in android.policy.allowed_broadcast_actions."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "action": {
+                    "type": "string",
+                    "enum": ["run_tasker_task", "send_broadcast"],
+                    "description": "Automation to trigger"
+                },
+                "task": {
+                    "type": "string",
+                    "description": "Tasker task name (run_tasker_task)"
+                },
+                "params": {
+                    "type": "array",
+                    "items": { "type": "string" },
+                    "description": "Up to 9 values passed as %par1..%par9 (run_tasker_task)"
+                },
+                "intent_action": {
+                    "type": "string",
+                    "description": "Broadcast intent action, e.g. com.example.automation.LIGHTS_ON (send_broadcast)"
+                },
+                "package": {
+                    "type": "string",
+                    "description": "Optional receiver package to make the broadcast explicit (send_broadcast)"
+                },
+                "extras": {
+                    "type": "object",
+                    "description": "Intent extras; values must be strings, numbers, or booleans (send_broadcast)"
+                }
+            },
+            "required": ["action"]
+        })
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        if !self.config.capabilities.automation {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some("android capability automation is disabled".into()),
+            });
+        }
+
+        let (action, payload) = match self.build_call(&args) {
+            Ok(call) => call,
+            Err(error) => {
+                return Ok(ToolResult {
+                    success: false,
+                    output: String::new(),
+                    error: Some(error.to_string()),
+                });
+            }
+        };
+
+        if let Err(error) = self
+            .security
+            .enforce_tool_operation(ToolOperation::Act, "android_automation")
+        {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(error),
+            });
+        }
+
+        Ok(bridge_tool_result(self.bridge.call(action, payload).await))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_tool() -> AndroidAutomationTool {
+        let mut config = AndroidConfig {
+            enabled: true,
+            ..AndroidConfig::default()
+        };
+        config.capabilities.automation = true;
+        config.policy.allowed_tasker_tasks = vec!["Lights On".into()];
+        config.policy.allowed_broadcast_actions = vec!["com.example.automation.RUN".into()];
+        AndroidAutomationTool::new(Arc::new(SecurityPolicy::default()), config)
+    }
+
+    #[tokio::test]
+    async fn runs_allowlisted_tasker_task() {
+        let result = test_tool()
+            .execute(json!({
+                "action": "run_tasker_task",
+                "task": "Lights On",
+                "params": ["living room", 40]
+            }))
+            .await
+            .unwrap();
+        assert!(result.success, "{:?}", result.error);
+        assert!(result.output.contains("tasker_run_task"));
+        assert!(result.output.contains("living room"));
+
+        let blocked = test_tool()
+            .execute(json!({ "action": "run_tasker_task", "task": "Wipe Phone" }))
+            .await
+            .unwrap();
+        assert!(!blocked.success);
+        assert!(blocked.error.unwrap().contains("allowed_tasker_tasks"));
+    }
+
+    #[test]
+    fn broadcast_validation() {
+        let tool = test_tool();
+        let (action, payload) = tool
+            .build_call(&json!({
+                "action": "send_broadcast",
+                "intent_action": "com.example.automation.RUN",
+                "extras": { "scene": "movie", "level": 3 }
+            }))
+            .unwrap();
+        assert_eq!(action, "send_broadcast");
+        assert_eq!(payload["extras"]["scene"], "movie");
+
+        for bad in [
+            json!({ "action": "send_broadcast", "intent_action": "android.intent.action.FACTORY_RESET" }),
+            json!({ "action": "send_broadcast", "intent_action": "not an action" }),
+            json!({
+                "action": "send_broadcast",
+                "intent_action": "com.example.automation.RUN",
+                "extras": { "nested": { "a": 1 } }
+            }),
+        ] {
+            assert!(tool.build_call(&bad).is_err(), "{bad}");
+        }
+    }
+
+    #[tokio::test]
+    async fn requires_automation_capability() {
+        let tool = AndroidAutomationTool::new(
+            Arc::new(SecurityPolicy::default()),
+            AndroidConfig::default(),
+        );
+        let result = tool
+            .execute(json!({ "action": "run_tasker_task", "task": "Lights On" }))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("automation"));
+    }
+}
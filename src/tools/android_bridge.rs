@@ -0,0 +1,255 @@
+//! Transport shared by the Android tools.
+//!
+//! `android.bridge.mode` selects how actions reach the host app:
+//! - `mock`: echo the action and payload (tests, desktop development)
+//! - `http`: POST `{action, payload}` to a local bridge endpoint
+//! - `jni`: call the host callback registered over JNI (in-app builds)
+
+use super::traits::ToolResult;
+use crate::config::AndroidBridgeConfig;
+use serde_json::json;
+use std::net::IpAddr;
+use std::time::Duration;
+
+#[derive(Debug, Clone)]
+pub struct AndroidBridge {
+    config: AndroidBridgeConfig,
+}
+
+impl AndroidBridge {
+    pub fn new(config: AndroidBridgeConfig) -> Self {
+        Self { config }
+    }
+
+    pub fn mode(&self) -> &str {
+        self.config.mode.trim()
+    }
+
+    fn validate_endpoint(&self) -> anyhow::Result<&str> {
+        let endpoint = self.config.endpoint.trim();
+        if endpoint.is_empty() {
+            anyhow::bail!("android.bridge.endpoint cannot be empty");
+        }
+
+        if !endpoint.starts_with("http://") && !endpoint.starts_with("https://") {
+            anyhow::bail!("android.bridge.endpoint must start with http:// or https://");
+        }
+
+        if !self.config.allow_remote_endpoint {
+            let host = extract_host(endpoint)?;
+            if !is_local_host(&host) {
+                anyhow::bail!(
+                    "android.bridge.allow_remote_endpoint=false blocks non-local endpoint: {host}"
+                );
+            }
+        }
+
+        Ok(endpoint)
+    }
+
+    /// Send `action` with `payload` to the host app and return its reply.
+    pub async fn call(
+        &self,
+        action: &str,
+        payload: serde_json::Value,
+    ) -> anyhow::Result<serde_json::Value> {
+        match self.mode() {
+            "mock" => Ok(json!({
+                "ok": true,
+                "action": action,
+                "mode": "mock",
+                "result": payload
+            })),
+            "http" => {
+                let endpoint = self.validate_endpoint()?;
+                let client = reqwest::Client::builder()
+                    .timeout(Duration::from_millis(self.config.timeout_ms))
+                    .build()?;
+
+                let mut request = client.post(endpoint).json(&json!({
+                    "action": action,
+                    "payload": payload,
+                }));
+
+                if let Some(api_key) = self
+                    .config
+                    .api_key
+                    .as_deref()
+                    .map(str::trim)
+                    .filter(|v| !v.is_empty())
+                {
+                    request = request.bearer_auth(api_key);
+                }
+
+                let response = request.send().await?;
+                if !response.status().is_success() {
+                    anyhow::bail!("android bridge returned {}", response.status().as_u16());
+                }
+
+                Ok(response.json::<serde_json::Value>().await?)
+            }
+            "jni" => {
+                let action = action.to_string();
+                let timeout = Duration::from_millis(self.config.timeout_ms);
+                let call = tokio::task::spawn_blocking(move || {
+                    crate::runtime::host_bridge::call_host(&action, payload)
+                });
+                match tokio::time::timeout(timeout, call).await {
+                    Ok(joined) => joined?,
+                    Err(_) => anyhow::bail!(
+                        "android host callback timed out after {}ms",
+                        timeout.as_millis()
+                    ),
+                }
+            }
+            other => anyhow::bail!(
+                "Unsupported android.bridge.mode '{other}'. Supported: mock, http, jni"
+            ),
+        }
+    }
+}
+
+/// Convert a bridge reply into a [`ToolResult`], treating `"ok": false`
+/// replies as failures.
+pub fn bridge_tool_result(result: anyhow::Result<serde_json::Value>) -> ToolResult {
+    let result = match result {
+        Ok(value) => value,
+        Err(error) => {
+            return ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(error.to_string()),
+            };
+        }
+    };
+
+    if result
+        .get("ok")
+        .and_then(serde_json::Value::as_bool)
+        .is_some_and(|ok| !ok)
+    {
+        let detail = result
+            .get("error")
+            .and_then(serde_json::Value::as_str)
+            .unwrap_or("android_bridge_error")
+            .to_string();
+        return ToolResult {
+            success: false,
+            output: serde_json::to_string_pretty(&result).unwrap_or_else(|_| result.to_string()),
+            error: Some(detail),
+        };
+    }
+
+    ToolResult {
+        success: true,
+        output: serde_json::to_string_pretty(&result).unwrap_or_else(|_| result.to_string()),
+        error: None,
+    }
+}
+
+fn extract_host(url: &str) -> anyhow::Result<String> {
+    let rest = url
+        .strip_prefix("http://")
+        .or_else(|| url.strip_prefix("https://"))
+        .ok_or_else(|| anyhow::anyhow!("invalid scheme"))?;
+    let authority = rest
+        .split(['/', '?', '#'])
+        .next()
+        .ok_or_else(|| anyhow::anyhow!("invalid endpoint"))?;
+    if authority.is_empty() {
+        anyhow::bail!("endpoint host missing");
+    }
+    let without_userinfo = authority
+        .split_once('@')
+        .map_or(authority, |(_, value)| value);
+    let host = without_userinfo
+        .split_once(':')
+        .map_or(without_userinfo, |(value, _)| value);
+
+    Ok(host.to_ascii_lowercase())
+}
+
+fn is_local_host(host: &str) -> bool {
+    if host == "localhost" {
+        return true;
+    }
+    if let Ok(ip) = host.parse::<IpAddr>() {
+        return ip.is_loopback();
+    }
+    false
+}
+
+/// Fixtures shared by the Android tool tests.
+#[cfg(test)]
+pub(crate) mod test_support {
+    use crate::config::{AndroidCapabilitiesConfig, AndroidConfig};
+    use crate::tools::traits::Tool;
+
+    /// An enabled `[android]` config on the mock bridge, with the capabilities
+    /// switched on by `enable`.
+    pub fn enabled_config(enable: impl FnOnce(&mut AndroidCapabilitiesConfig)) -> AndroidConfig {
+        let mut config = AndroidConfig {
+            enabled: true,
+            ..AndroidConfig::default()
+        };
+        enable(&mut config.capabilities);
+        config
+    }
+
+    /// Assert that `tool` refuses `args` because `capability` is switched off.
+    pub async fn assert_capability_required(
+        tool: &dyn Tool,
+        args: serde_json::Value,
+        capability: &str,
+    ) {
+        let result = tool.execute(args).await.unwrap();
+        assert!(!result.success);
+        assert_eq!(
+            result.error.as_deref(),
+            Some(format!("android capability {capability} is disabled").as_str())
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn http_mode_rejects_remote_endpoint() {
+        let bridge = AndroidBridge::new(AndroidBridgeConfig {
+            mode: "http".into(),
+            endpoint: "https://bridge.example.com/v1/android/actions".into(),
+            ..AndroidBridgeConfig::default()
+        });
+        let err = bridge.call("get_battery", json!({})).await.unwrap_err();
+        assert!(err.to_string().contains("allow_remote_endpoint"));
+    }
+
+    #[tokio::test]
+    async fn jni_mode_without_host_fails() {
+        let bridge = AndroidBridge::new(AndroidBridgeConfig {
+            mode: "jni".into(),
+            ..AndroidBridgeConfig::default()
+        });
+        let err = bridge.call("get_battery", json!({})).await.unwrap_err();
+        assert!(err.to_string().contains("host bridge"));
+    }
+
+    #[test]
+    fn not_ok_reply_is_failure() {
+        let result = bridge_tool_result(Ok(json!({ "ok": false, "error": "denied" })));
+        assert!(!result.success);
+        assert_eq!(result.error.as_deref(), Some("denied"));
+    }
+
+    #[test]
+    fn extract_host_strips_userinfo_and_port() {
+        assert_eq!(
+            extract_host("http://user@LOCALHOST:9797/path").unwrap(),
+            "localhost"
+        );
+        assert!(is_local_host("127.0.0.1"));
+        assert!(!is_local_host("10.0.0.2"));
+    }
+}
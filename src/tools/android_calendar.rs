@@ -0,0 +1,265 @@
+use super::android_bridge::{bridge_tool_result, AndroidBridge};
+use super::traits::{Tool, ToolResult};
+use crate::config::AndroidConfig;
+use crate::security::policy::ToolOperation;
+use crate::security::SecurityPolicy;
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, FixedOffset, Utc};
+use serde_json::json;
+use std::sync::Arc;
+
+const DEFAULT_LIST_DAYS: i64 = 1;
+const MAX_LIST_DAYS: i64 = 31;
+const MAX_TITLE_CHARS: usize = 200;
+
+/// Read and create calendar events through the host's CalendarContract bridge.
+pub struct AndroidCalendarTool {
+    security: Arc<SecurityPolicy>,
+    config: AndroidConfig,
+    bridge: AndroidBridge,
+}
+
+impl AndroidCalendarTool {
+    pub fn new(security: Arc<SecurityPolicy>, config: AndroidConfig) -> Self {
+        Self {
+            security,
+            bridge: AndroidBridge::new(config.bridge.clone()),
+            config,
+        }
+    }
+
+    fn parse_time(
+        args: &serde_json::Value,
+        name: &str,
+    ) -> anyhow::Result<Option<DateTime<FixedOffset>>> {
+        match args.get(name).and_then(serde_json::Value::as_str) {
+            None => Ok(None),
+            Some(raw) => DateTime::parse_from_rfc3339(raw.trim())
+                .map(Some)
+                .map_err(|e| anyhow::anyhow!("Invalid '{name}' (expected RFC 3339): {e}")),
+        }
+    }
+
+    async fn list(&self, args: &serde_json::Value) -> anyhow::Result<serde_json::Value> {
+        let start =
+            Self::parse_time(args, "start")?.map_or_else(Utc::now, |t| t.with_timezone(&Utc));
+        let days = args
+            .get("days")
+            .and_then(serde_json::Value::as_i64)
+            .map_or(DEFAULT_LIST_DAYS, |d| d.clamp(1, MAX_LIST_DAYS));
+        let end = start + Duration::days(days);
+
+        self.bridge
+            .call(
+                "read_calendar",
+                json!({
+                    "start_ms": start.timestamp_millis(),
+                    "end_ms": end.timestamp_millis(),
+                }),
+            )
+            .await
+    }
+
+    async fn create(&self, args: &serde_json::Value) -> anyhow::Result<serde_json::Value> {
+        let title = args
+            .get("title")
+            .and_then(serde_json::Value::as_str)
+            .map(str::trim)
+            .unwrap_or_default();
+        if title.is_empty() {
+            anyhow::bail!("create requires 'title'");
+        }
+        if title.chars().count() > MAX_TITLE_CHARS {
+            anyhow::bail!("'title' exceeds {MAX_TITLE_CHARS} characters");
+        }
+        let start = Self::parse_time(args, "start")?
+            .ok_or_else(|| anyhow::anyhow!("create requires 'start'"))?;
+        let end = Self::parse_time(args, "end")?.unwrap_or(start + Duration::hours(1));
+        if end <= start {
+            anyhow::bail!("'end' must be after 'start'");
+        }
+
+        self.security
+            .enforce_tool_operation(ToolOperation::Act, "android_calendar.create")
+            .map_err(anyhow::Error::msg)?;
+
+        let optional = |name: &str| {
+            args.get(name)
+                .and_then(serde_json::Value::as_str)
+                .map(str::trim)
+                .filter(|v| !v.is_empty())
+        };
+        self.bridge
+            .call(
+                "create_calendar_event",
+                json!({
+                    "title": title,
+                    "start_ms": start.timestamp_millis(),
+                    "end_ms": end.timestamp_millis(),
+                    "location": optional("location"),
+                    "description": optional("description"),
+                }),
+            )
+            .await
+    }
+}
+
+#[async_trait]
+impl Tool for AndroidCalendarTool {
+    fn name(&self) -> &str {
+        "android_calendar"
+    }
+
+    fn description(&self) -> &str {
+        "Read the phone's calendar (action=list, e.g. \"what's on tomorrow\") or add an event (action=create). Times are RFC 3339 with a UTC offset, e.g. 2025-03-14T09:00:00+01:00."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "action": {
+                    "type": "string",
+                    "enum": ["list", "create"],
+                    "description": "list upcoming events or create a new one"
+                },
+                "start": {
+                    "type": "string",
+                    "description": "RFC 3339 start time (list: window start, defaults to now; create: event start)"
+                },
+                "days": {
+                    "type": "integer",
+                    "description": "Number of days to list from start",
+                    "default": DEFAULT_LIST_DAYS
+                },
+                "end": {
+                    "type": "string",
+                    "description": "RFC 3339 end time for create; defaults to one hour after start"
+                },
+                "title": {
+                    "type": "string",
+                    "description": "Event title (create)"
+                },
+                "location": {
+                    "type": "string",
+                    "description": "Optional event location (create)"
+                },
+                "description": {
+                    "type": "string",
+                    "description": "Optional event notes (create)"
+                }
+            },
+            "required": ["action"]
+        })
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        if !self.config.capabilities.calendar {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some("android capability calendar is disabled".into()),
+            });
+        }
+
+        let result = match args.get("action").and_then(serde_json::Value::as_str) {
+            Some("list") => self.list(&args).await,
+            Some("create") => self.create(&args).await,
+            Some(other) => Err(anyhow::anyhow!(
+                "Unknown action '{other}'. Supported: list, create"
+            )),
+            None => Err(anyhow::anyhow!("Missing 'action' parameter")),
+        };
+        Ok(bridge_tool_result(result))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::security::AutonomyLevel;
+    use crate::tools::android_bridge::test_support;
+
+    fn enabled_config() -> AndroidConfig {
+        test_support::enabled_config(|caps| caps.calendar = true)
+    }
+
+    #[tokio::test]
+    async fn disabled_capability_blocks_access() {
+        let tool = AndroidCalendarTool::new(
+            Arc::new(SecurityPolicy::default()),
+            test_support::enabled_config(|_| {}),
+        );
+        test_support::assert_capability_required(&tool, json!({ "action": "list" }), "calendar")
+            .await;
+    }
+
+    #[tokio::test]
+    async fn list_sends_time_window() {
+        let tool = AndroidCalendarTool::new(Arc::new(SecurityPolicy::default()), enabled_config());
+        let result = tool
+            .execute(json!({ "action": "list", "start": "2025-03-14T00:00:00Z", "days": 2 }))
+            .await
+            .unwrap();
+        assert!(result.success, "{:?}", result.error);
+        assert!(result.output.contains("1741910400000"));
+        assert!(result.output.contains("1742083200000"));
+    }
+
+    #[tokio::test]
+    async fn create_defaults_to_one_hour() {
+        let tool = AndroidCalendarTool::new(Arc::new(SecurityPolicy::default()), enabled_config());
+        let result = tool
+            .execute(json!({
+                "action": "create",
+                "title": "Dentist",
+                "start": "2025-03-14T09:00:00Z"
+            }))
+            .await
+            .unwrap();
+        assert!(result.success, "{:?}", result.error);
+        assert!(result.output.contains("create_calendar_event"));
+        assert!(result.output.contains("1741946400000"));
+    }
+
+    #[tokio::test]
+    async fn read_only_autonomy_blocks_create() {
+        let security = Arc::new(SecurityPolicy {
+            autonomy: AutonomyLevel::ReadOnly,
+            ..SecurityPolicy::default()
+        });
+        let tool = AndroidCalendarTool::new(security, enabled_config());
+
+        assert!(
+            tool.execute(json!({ "action": "list" }))
+                .await
+                .unwrap()
+                .success
+        );
+        let result = tool
+            .execute(json!({
+                "action": "create",
+                "title": "Dentist",
+                "start": "2025-03-14T09:00:00Z"
+            }))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("read-only"));
+    }
+
+    #[tokio::test]
+    async fn create_rejects_end_before_start() {
+        let tool = AndroidCalendarTool::new(Arc::new(SecurityPolicy::default()), enabled_config());
+        let result = tool
+            .execute(json!({
+                "action": "create",
+                "title": "Dentist",
+                "start": "2025-03-14T09:00:00Z",
+                "end": "2025-03-14T08:00:00Z"
+            }))
+            .await
+            .unwrap();
+        assert!(!result.success);
+    }
+}
@@ -0,0 +1,141 @@
+use super::android_bridge::{bridge_tool_result, AndroidBridge};
+use super::traits::{Tool, ToolResult};
+use crate::config::AndroidConfig;
+use async_trait::async_trait;
+use serde_json::json;
+
+/// Capture a photo through the host app and hand back the saved image path.
+pub struct AndroidCameraTool {
+    config: AndroidConfig,
+    bridge: AndroidBridge,
+}
+
+impl AndroidCameraTool {
+    pub fn new(config: AndroidConfig) -> Self {
+        Self {
+            bridge: AndroidBridge::new(config.bridge.clone()),
+            config,
+        }
+    }
+
+    /// `[IMAGE:<path>]` marker for the captured photo, so the next turn
+    /// carries it to a vision-capable provider.
+    fn image_marker(reply: &serde_json::Value) -> Option<String> {
+        reply
+            .get("path")
+            .and_then(serde_json::Value::as_str)
+            .map(str::trim)
+            .filter(|path| !path.is_empty())
+            .map(|path| format!("[IMAGE:{path}]"))
+    }
+}
+
+#[async_trait]
+impl Tool for AndroidCameraTool {
+    fn name(&self) -> &str {
+        "android_camera"
+    }
+
+    fn description(&self) -> &str {
+        "Take a photo with the phone camera and return the saved image path. Set describe=true to attach the photo to your next turn so you can look at it (requires a vision-capable model)."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "lens": {
+                    "type": "string",
+                    "enum": ["rear", "front"],
+                    "description": "Camera lens to use",
+                    "default": "rear"
+                },
+                "describe": {
+                    "type": "boolean",
+                    "description": "Attach the captured photo for vision analysis",
+                    "default": false
+                }
+            }
+        })
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        if !self.config.capabilities.camera {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some("android capability camera is disabled".into()),
+            });
+        }
+
+        let lens = match args.get("lens").and_then(serde_json::Value::as_str) {
+            None | Some("rear") => "rear",
+            Some("front") => "front",
+            Some(other) => {
+                return Ok(ToolResult {
+                    success: false,
+                    output: String::new(),
+                    error: Some(format!("Unknown lens '{other}'. Supported: rear, front")),
+                });
+            }
+        };
+        let describe = args
+            .get("describe")
+            .and_then(serde_json::Value::as_bool)
+            .unwrap_or(false);
+
+        let reply = self
+            .bridge
+            .call("take_photo", json!({ "lens": lens }))
+            .await;
+        let marker = if describe {
+            reply.as_ref().ok().and_then(Self::image_marker)
+        } else {
+            None
+        };
+        let mut result = bridge_tool_result(reply);
+        if let Some(marker) = marker.filter(|_| result.success) {
+            result.output.push('\n');
+            result.output.push_str(&marker);
+        }
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tools::android_bridge::test_support;
+
+    fn enabled_config() -> AndroidConfig {
+        test_support::enabled_config(|caps| caps.camera = true)
+    }
+
+    #[tokio::test]
+    async fn disabled_capability_blocks_capture() {
+        let tool = AndroidCameraTool::new(test_support::enabled_config(|_| {}));
+        test_support::assert_capability_required(&tool, json!({}), "camera").await;
+    }
+
+    #[tokio::test]
+    async fn capture_forwards_lens_to_bridge() {
+        let result = AndroidCameraTool::new(enabled_config())
+            .execute(json!({ "lens": "front" }))
+            .await
+            .unwrap();
+        assert!(result.success, "{:?}", result.error);
+        assert!(result.output.contains("take_photo"));
+        assert!(result.output.contains("front"));
+        assert!(!result.output.contains("[IMAGE:"));
+    }
+
+    #[test]
+    fn image_marker_uses_reported_path() {
+        assert_eq!(
+            AndroidCameraTool::image_marker(&json!({ "ok": true, "path": "/data/photos/p1.jpg" }))
+                .as_deref(),
+            Some("[IMAGE:/data/photos/p1.jpg]")
+        );
+        assert!(AndroidCameraTool::image_marker(&json!({ "ok": true })).is_none());
+    }
+}
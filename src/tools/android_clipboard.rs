@@ -0,0 +1,152 @@
+use super::android_bridge::{bridge_tool_result, AndroidBridge};
+use super::traits::{Tool, ToolResult};
+use crate::config::AndroidConfig;
+use crate::security::policy::ToolOperation;
+use crate::security::SecurityPolicy;
+use async_trait::async_trait;
+use serde_json::json;
+use std::sync::Arc;
+
+/// Keep clipboard payloads well below Binder transaction limits.
+const MAX_CLIPBOARD_CHARS: usize = 100_000;
+
+/// Read and write the device clipboard through the host app.
+pub struct AndroidClipboardTool {
+    security: Arc<SecurityPolicy>,
+    config: AndroidConfig,
+    bridge: AndroidBridge,
+}
+
+impl AndroidClipboardTool {
+    pub fn new(security: Arc<SecurityPolicy>, config: AndroidConfig) -> Self {
+        Self {
+            security,
+            bridge: AndroidBridge::new(config.bridge.clone()),
+            config,
+        }
+    }
+
+    async fn set(&self, args: &serde_json::Value) -> anyhow::Result<serde_json::Value> {
+        let text = args
+            .get("text")
+            .and_then(serde_json::Value::as_str)
+            .ok_or_else(|| anyhow::anyhow!("set requires 'text'"))?;
+        if text.chars().count() > MAX_CLIPBOARD_CHARS {
+            anyhow::bail!("Clipboard text exceeds {MAX_CLIPBOARD_CHARS} characters");
+        }
+        self.security
+            .enforce_tool_operation(ToolOperation::Act, "android_clipboard.set")
+            .map_err(anyhow::Error::msg)?;
+        self.bridge
+            .call("set_clipboard", json!({ "text": text }))
+            .await
+    }
+}
+
+#[async_trait]
+impl Tool for AndroidClipboardTool {
+    fn name(&self) -> &str {
+        "android_clipboard"
+    }
+
+    fn description(&self) -> &str {
+        "Read what the user last copied on their phone (action=get) or place text on the clipboard so they can paste it (action=set)."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "action": {
+                    "type": "string",
+                    "enum": ["get", "set"],
+                    "description": "get the current clipboard text or set new text"
+                },
+                "text": {
+                    "type": "string",
+                    "description": "Text to copy (set)"
+                }
+            },
+            "required": ["action"]
+        })
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        if !self.config.capabilities.clipboard {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some("android capability clipboard is disabled".into()),
+            });
+        }
+
+        let result = match args.get("action").and_then(serde_json::Value::as_str) {
+            Some("get") => self.bridge.call("read_clipboard", json!({})).await,
+            Some("set") => self.set(&args).await,
+            Some(other) => Err(anyhow::anyhow!(
+                "Unknown action '{other}'. Supported: get, set"
+            )),
+            None => Err(anyhow::anyhow!("Missing 'action' parameter")),
+        };
+        Ok(bridge_tool_result(result))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::security::AutonomyLevel;
+    use crate::tools::android_bridge::test_support;
+
+    fn enabled_config() -> AndroidConfig {
+        test_support::enabled_config(|caps| caps.clipboard = true)
+    }
+
+    #[tokio::test]
+    async fn set_then_get_reach_bridge() {
+        let tool = AndroidClipboardTool::new(Arc::new(SecurityPolicy::default()), enabled_config());
+
+        let set = tool
+            .execute(json!({ "action": "set", "text": "summary" }))
+            .await
+            .unwrap();
+        assert!(set.success, "{:?}", set.error);
+        assert!(set.output.contains("set_clipboard"));
+
+        let get = tool.execute(json!({ "action": "get" })).await.unwrap();
+        assert!(get.success);
+        assert!(get.output.contains("read_clipboard"));
+    }
+
+    #[tokio::test]
+    async fn read_only_autonomy_can_get_but_not_set() {
+        let security = Arc::new(SecurityPolicy {
+            autonomy: AutonomyLevel::ReadOnly,
+            ..SecurityPolicy::default()
+        });
+        let tool = AndroidClipboardTool::new(security, enabled_config());
+
+        assert!(
+            tool.execute(json!({ "action": "get" }))
+                .await
+                .unwrap()
+                .success
+        );
+        let set = tool
+            .execute(json!({ "action": "set", "text": "x" }))
+            .await
+            .unwrap();
+        assert!(!set.success);
+        assert!(set.error.unwrap().contains("read-only"));
+    }
+
+    #[tokio::test]
+    async fn disabled_capability_blocks_access() {
+        let tool = AndroidClipboardTool::new(
+            Arc::new(SecurityPolicy::default()),
+            test_support::enabled_config(|_| {}),
+        );
+        test_support::assert_capability_required(&tool, json!({ "action": "get" }), "clipboard")
+            .await;
+    }
+}
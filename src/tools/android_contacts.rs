@@ -0,0 +1,182 @@
+use super::android_bridge::{bridge_tool_result, AndroidBridge};
+use super::traits::{Tool, ToolResult};
+use crate::config::{AndroidConfig, AndroidDistribution};
+use async_trait::async_trait;
+use serde_json::json;
+
+const MAX_QUERY_CHARS: usize = 100;
+
+/// Read-only lookup against the device contacts provider.
+pub struct AndroidContactsTool {
+    config: AndroidConfig,
+    bridge: AndroidBridge,
+}
+
+impl AndroidContactsTool {
+    pub fn new(config: AndroidConfig) -> Self {
+        Self {
+            bridge: AndroidBridge::new(config.bridge.clone()),
+            config,
+        }
+    }
+
+    fn ensure_available(&self) -> anyhow::Result<()> {
+        if !self.config.capabilities.contacts {
+            anyhow::bail!("android capability contacts is disabled");
+        }
+        if self.config.distribution == AndroidDistribution::Play {
+            anyhow::bail!(
+                "contact actions are disabled in play distribution. Use enterprise/full distribution."
+            );
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl Tool for AndroidContactsTool {
+    fn name(&self) -> &str {
+        "android_contacts"
+    }
+
+    fn description(&self) -> &str {
+        "Search the phone's contacts by name, phone number, or email. Use this to resolve requests like \"text Maria\" to a phone number before calling android_sms. Read-only."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "query": {
+                    "type": "string",
+                    "description": "Name, phone number, or email fragment to search for"
+                },
+                "field": {
+                    "type": "string",
+                    "enum": ["any", "name", "number", "email"],
+                    "description": "Restrict matching to one field",
+                    "default": "any"
+                },
+                "limit": {
+                    "type": "integer",
+                    "description": "Maximum contacts to return",
+                    "default": 5
+                }
+            },
+            "required": ["query"]
+        })
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        if let Err(error) = self.ensure_available() {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(error.to_string()),
+            });
+        }
+
+        let query = args
+            .get("query")
+            .and_then(serde_json::Value::as_str)
+            .map(str::trim)
+            .unwrap_or_default();
+        if query.is_empty() {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some("Missing 'query' parameter".into()),
+            });
+        }
+        if query.chars().count() > MAX_QUERY_CHARS {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(format!("'query' exceeds {MAX_QUERY_CHARS} characters")),
+            });
+        }
+
+        let field = match args.get("field").and_then(serde_json::Value::as_str) {
+            None => "any",
+            Some(field @ ("any" | "name" | "number" | "email")) => field,
+            Some(other) => {
+                return Ok(ToolResult {
+                    success: false,
+                    output: String::new(),
+                    error: Some(format!(
+                        "Unknown field '{other}'. Supported: any, name, number, email"
+                    )),
+                });
+            }
+        };
+        let limit = args
+            .get("limit")
+            .and_then(serde_json::Value::as_u64)
+            .map_or(5, |v| v.clamp(1, 50));
+
+        Ok(bridge_tool_result(
+            self.bridge
+                .call(
+                    "search_contacts",
+                    json!({ "query": query, "field": field, "limit": limit }),
+                )
+                .await,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tools::android_bridge::test_support;
+
+    fn enabled_config() -> AndroidConfig {
+        AndroidConfig {
+            distribution: AndroidDistribution::Full,
+            ..test_support::enabled_config(|caps| caps.contacts = true)
+        }
+    }
+
+    #[tokio::test]
+    async fn disabled_capability_blocks_search() {
+        let tool = AndroidContactsTool::new(AndroidConfig {
+            distribution: AndroidDistribution::Full,
+            ..test_support::enabled_config(|_| {})
+        });
+        test_support::assert_capability_required(&tool, json!({ "query": "Maria" }), "contacts")
+            .await;
+    }
+
+    #[tokio::test]
+    async fn search_forwards_query_to_bridge() {
+        let tool = AndroidContactsTool::new(enabled_config());
+        let result = tool
+            .execute(json!({ "query": "Maria", "field": "name" }))
+            .await
+            .unwrap();
+        assert!(result.success, "{:?}", result.error);
+        assert!(result.output.contains("search_contacts"));
+        assert!(result.output.contains("Maria"));
+    }
+
+    #[tokio::test]
+    async fn play_distribution_blocks_contacts() {
+        let mut config = enabled_config();
+        config.distribution = AndroidDistribution::Play;
+        let result = AndroidContactsTool::new(config)
+            .execute(json!({ "query": "Maria" }))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("play distribution"));
+    }
+
+    #[tokio::test]
+    async fn rejects_unknown_field() {
+        let result = AndroidContactsTool::new(enabled_config())
+            .execute(json!({ "query": "Maria", "field": "birthday" }))
+            .await
+            .unwrap();
+        assert!(!result.success);
+    }
+}
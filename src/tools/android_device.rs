@@ -1,17 +1,20 @@
+use super::android_bridge::{bridge_tool_result, AndroidBridge};
 use super::traits::{Tool, ToolResult};
 use crate::config::{AndroidConfig, AndroidDistribution};
+use crate::runtime::shizuku::{self, PrivilegedOp, SettingsNamespace};
 use crate::security::SecurityPolicy;
 use async_trait::async_trait;
 use parking_lot::Mutex;
 use serde_json::json;
+use std::sync::Arc;
 use std::time::{Duration, Instant};
-use std::{net::IpAddr, sync::Arc};
 
 const RATE_LIMIT_WINDOW_SECS: u64 = 3600;
 
 pub struct AndroidDeviceTool {
     security: Arc<SecurityPolicy>,
     config: AndroidConfig,
+    bridge: AndroidBridge,
     sms_events: Mutex<Vec<Instant>>,
     call_events: Mutex<Vec<Instant>>,
 }
@@ -20,38 +23,13 @@ impl AndroidDeviceTool {
     pub fn new(security: Arc<SecurityPolicy>, config: AndroidConfig) -> Self {
         Self {
             security,
+            bridge: AndroidBridge::new(config.bridge.clone()),
             config,
             sms_events: Mutex::new(Vec::new()),
             call_events: Mutex::new(Vec::new()),
         }
     }
 
-    fn bridge_mode(&self) -> &str {
-        self.config.bridge.mode.trim()
-    }
-
-    fn validate_bridge_endpoint(&self) -> anyhow::Result<&str> {
-        let endpoint = self.config.bridge.endpoint.trim();
-        if endpoint.is_empty() {
-            anyhow::bail!("android.bridge.endpoint cannot be empty");
-        }
-
-        if !endpoint.starts_with("http://") && !endpoint.starts_with("https://") {
-            anyhow::bail!("android.bridge.endpoint must start with http:// or https://");
-        }
-
-        if !self.config.bridge.allow_remote_endpoint {
-            let host = extract_host(endpoint)?;
-            if !is_local_host(&host) {
-                anyhow::bail!(
-                    "android.bridge.allow_remote_endpoint=false blocks non-local endpoint: {host}"
-                );
-            }
-        }
-
-        Ok(endpoint)
-    }
-
     fn ensure_capability_enabled(&self, action: &str) -> anyhow::Result<()> {
         match action {
             "launch_app" | "list_apps" | "open_url" | "open_settings" => {
@@ -158,8 +136,13 @@ impl AndroidDeviceTool {
                     anyhow::bail!("android capability event_hooks is disabled");
                 }
             }
+            "settings_put" | "input_inject" => {
+                if !self.config.shizuku.enabled {
+                    anyhow::bail!("android.shizuku.enabled is false");
+                }
+            }
             other => anyhow::bail!(
-                "Unknown android action '{other}'. Supported: launch_app, list_apps, open_url, open_settings, sensor_read, vibrate, get_location, take_photo, record_audio, set_clipboard, read_clipboard, post_notification, get_network, get_battery, get_device_info, get_android_version, read_contacts, read_calendar, send_sms, read_sms, place_call, read_call_log, ui_automation_*, browser_*, *_files*, hook_*"
+                "Unknown android action '{other}'. Supported: launch_app, list_apps, open_url, open_settings, sensor_read, vibrate, get_location, take_photo, record_audio, set_clipboard, read_clipboard, post_notification, get_network, get_battery, get_device_info, get_android_version, read_contacts, read_calendar, send_sms, read_sms, place_call, read_call_log, ui_automation_*, browser_*, *_files*, hook_*, settings_put, input_inject"
             ),
         }
 
@@ -216,7 +199,7 @@ impl AndroidDeviceTool {
 
     fn ensure_approved_if_required(&self, approved: bool, action: &str) -> anyhow::Result<()> {
         let needs_approval = self.config.policy.require_explicit_approval
-            && matches!(action, "send_sms" | "place_call");
+            && matches!(action, "send_sms" | "place_call" | "settings_put");
 
         if needs_approval && !approved {
             anyhow::bail!(
@@ -227,54 +210,46 @@ impl AndroidDeviceTool {
         Ok(())
     }
 
+    fn ensure_settings_key_allowed(&self, key: &str) -> anyhow::Result<()> {
+        if self
+            .config
+            .shizuku
+            .allowed_settings_keys
+            .iter()
+            .any(|k| k == key)
+        {
+            Ok(())
+        } else {
+            anyhow::bail!("Settings key '{key}' is not in android.shizuku.allowed_settings_keys")
+        }
+    }
+
+    /// Run a privileged operation through Shizuku. Mock and HTTP bridges
+    /// receive the rendered argv as a `shizuku_exec` call.
+    async fn execute_privileged(&self, op: &PrivilegedOp) -> anyhow::Result<serde_json::Value> {
+        if self.bridge.mode() != "jni" {
+            let argv = op.to_argv()?;
+            return self
+                .execute_bridge_call("shizuku_exec", json!({ "argv": argv }))
+                .await;
+        }
+
+        let output = shizuku::execute(op).await?;
+        Ok(json!({
+            "ok": output.exit_code == 0,
+            "exit_code": output.exit_code,
+            "stdout": output.stdout,
+            "stderr": output.stderr,
+            "error": (output.exit_code != 0).then_some(output.stderr.trim()),
+        }))
+    }
+
     async fn execute_bridge_call(
         &self,
         action: &str,
         payload: serde_json::Value,
     ) -> anyhow::Result<serde_json::Value> {
-        match self.bridge_mode() {
-            "mock" => Ok(json!({
-                "ok": true,
-                "action": action,
-                "mode": "mock",
-                "result": payload
-            })),
-            "http" => {
-                let endpoint = self.validate_bridge_endpoint()?;
-                let client = reqwest::Client::builder()
-                    .timeout(Duration::from_millis(self.config.bridge.timeout_ms))
-                    .build()?;
-
-                let mut request = client.post(endpoint).json(&json!({
-                    "action": action,
-                    "payload": payload,
-                }));
-
-                if let Some(api_key) = self
-                    .config
-                    .bridge
-                    .api_key
-                    .as_deref()
-                    .map(str::trim)
-                    .filter(|v| !v.is_empty())
-                {
-                    request = request.bearer_auth(api_key);
-                }
-
-                let response = request.send().await?;
-                if !response.status().is_success() {
-                    anyhow::bail!("android bridge returned {}", response.status().as_u16());
-                }
-
-                Ok(response.json::<serde_json::Value>().await?)
-            }
-            "jni" => anyhow::bail!(
-                "android.bridge.mode='jni' requires in-app Rust/Java binding and is not available in CLI process"
-            ),
-            other => anyhow::bail!(
-                "Unsupported android.bridge.mode '{other}'. Supported: mock, http, jni"
-            ),
-        }
+        self.bridge.call(action, payload).await
     }
 }
 
@@ -294,7 +269,7 @@ impl Tool for AndroidDeviceTool {
             "properties": {
                 "action": {
                     "type": "string",
-                    "description": "Action to perform. Real-time event hooks (register Android BroadcastReceiver — fire immediately, never miss an event): hook_incoming_call, hook_incoming_sms, hook_notifications, read_notifications. Device info/sensors: get_battery, get_network, get_location, get_device_info, get_android_version, sensor_read. Telephony: send_sms, read_sms, place_call, read_call_log. Contacts/calendar: read_contacts, read_calendar. UI: launch_app, list_apps, open_url, open_settings, vibrate, post_notification, set_clipboard, read_clipboard, take_photo, record_audio, ui_automation_enable, ui_automation_tap, ui_automation_swipe, ui_automation_click_text, ui_automation_back, ui_automation_home, ui_automation_recents. Browser: browser_open_session, browser_navigate, browser_get_state, browser_fetch_page, browser_close_session. Files: request_all_files_access, pick_document, manage_files."
+                    "description": "Action to perform. Real-time event hooks (register Android BroadcastReceiver — fire immediately, never miss an event): hook_incoming_call, hook_incoming_sms, hook_notifications, read_notifications. Device info/sensors: get_battery, get_network, get_location, get_device_info, get_android_version, sensor_read. Telephony: send_sms, read_sms, place_call, read_call_log. Contacts/calendar: read_contacts, read_calendar. UI: launch_app, list_apps, open_url, open_settings, vibrate, post_notification, set_clipboard, read_clipboard, take_photo, record_audio, ui_automation_enable, ui_automation_tap, ui_automation_swipe, ui_automation_click_text, ui_automation_back, ui_automation_home, ui_automation_recents. Browser: browser_open_session, browser_navigate, browser_get_state, browser_fetch_page, browser_close_session. Files: request_all_files_access, pick_document, manage_files. Privileged (requires Shizuku): settings_put, input_inject."
                 },
                 "package": {
                     "type": "string",
@@ -388,6 +363,29 @@ impl Tool for AndroidDeviceTool {
                     "type": "boolean",
                     "description": "Enable/disable event hook",
                     "default": true
+                },
+                "namespace": {
+                    "type": "string",
+                    "enum": ["system", "secure", "global"],
+                    "description": "Settings table for settings_put",
+                    "default": "system"
+                },
+                "key": {
+                    "type": "string",
+                    "description": "Settings key for settings_put"
+                },
+                "value": {
+                    "type": "string",
+                    "description": "Settings value for settings_put"
+                },
+                "input": {
+                    "type": "string",
+                    "enum": ["tap", "swipe", "text", "keyevent"],
+                    "description": "Input event kind for input_inject (uses x/y, x1/y1/x2/y2, text, or keycode)"
+                },
+                "keycode": {
+                    "type": "string",
+                    "description": "Android keycode for input_inject keyevent (e.g. KEYCODE_HOME)"
                 }
             },
             "required": ["action"]
@@ -884,85 +882,116 @@ impl Tool for AndroidDeviceTool {
                 self.execute_bridge_call("read_notifications", json!({ "limit": limit }))
                     .await
             }
-            _ => anyhow::bail!("Unsupported action"),
-        };
-
-        let result = match result {
-            Ok(value) => value,
-            Err(error) => {
-                return Ok(ToolResult {
-                    success: false,
-                    output: String::new(),
-                    error: Some(error.to_string()),
-                });
+            // ── Privileged (Shizuku) ─────────────────────────────────
+            "settings_put" => {
+                let str_arg = |name: &str| {
+                    args.get(name)
+                        .and_then(serde_json::Value::as_str)
+                        .unwrap_or("")
+                        .trim()
+                        .to_string()
+                };
+                let key = str_arg("key");
+                if key.is_empty() {
+                    return Ok(ToolResult {
+                        success: false,
+                        output: String::new(),
+                        error: Some("settings_put requires 'key'".into()),
+                    });
+                }
+                if let Err(error) = self.ensure_settings_key_allowed(&key) {
+                    return Ok(ToolResult {
+                        success: false,
+                        output: String::new(),
+                        error: Some(error.to_string()),
+                    });
+                }
+                let namespace = args
+                    .get("namespace")
+                    .and_then(serde_json::Value::as_str)
+                    .unwrap_or("system");
+                match SettingsNamespace::parse(namespace) {
+                    Ok(namespace) => {
+                        self.execute_privileged(&PrivilegedOp::SettingsPut {
+                            namespace,
+                            key,
+                            value: str_arg("value"),
+                        })
+                        .await
+                    }
+                    Err(error) => Err(error),
+                }
             }
+            "input_inject" => match input_op(&args) {
+                Ok(op) => self.execute_privileged(&op).await,
+                Err(error) => Err(error),
+            },
+            _ => anyhow::bail!("Unsupported action"),
         };
 
-        if result
-            .get("ok")
-            .and_then(serde_json::Value::as_bool)
-            .is_some_and(|ok| !ok)
-        {
-            let detail = result
-                .get("error")
-                .and_then(serde_json::Value::as_str)
-                .unwrap_or("android_bridge_error")
-                .to_string();
-            return Ok(ToolResult {
-                success: false,
-                output: serde_json::to_string_pretty(&result)
-                    .unwrap_or_else(|_| result.to_string()),
-                error: Some(detail),
-            });
-        }
-
-        Ok(ToolResult {
-            success: true,
-            output: serde_json::to_string_pretty(&result).unwrap_or_else(|_| result.to_string()),
-            error: None,
-        })
+        Ok(bridge_tool_result(result))
     }
 }
 
-fn extract_host(url: &str) -> anyhow::Result<String> {
-    let rest = url
-        .strip_prefix("http://")
-        .or_else(|| url.strip_prefix("https://"))
-        .ok_or_else(|| anyhow::anyhow!("invalid scheme"))?;
-    let authority = rest
-        .split(['/', '?', '#'])
-        .next()
-        .ok_or_else(|| anyhow::anyhow!("invalid endpoint"))?;
-    if authority.is_empty() {
-        anyhow::bail!("endpoint host missing");
-    }
-    let without_userinfo = authority
-        .split_once('@')
-        .map_or(authority, |(_, value)| value);
-    let host = without_userinfo
-        .split_once(':')
-        .map_or(without_userinfo, |(value, _)| value);
-
-    Ok(host.to_ascii_lowercase())
-}
-
-fn is_local_host(host: &str) -> bool {
-    if host == "localhost" {
-        return true;
-    }
-    if let Ok(ip) = host.parse::<IpAddr>() {
-        return ip.is_loopback();
+/// The privileged operation an `input_inject` call describes. Coordinates
+/// must fit in a `u32`.
+fn input_op(args: &serde_json::Value) -> anyhow::Result<PrivilegedOp> {
+    let coord = |name: &str| {
+        let value = args
+            .get(name)
+            .and_then(serde_json::Value::as_u64)
+            .unwrap_or(0);
+        u32::try_from(value)
+            .map_err(|_| anyhow::anyhow!("input_inject '{name}' is out of range (got {value})"))
+    };
+    match args
+        .get("input")
+        .and_then(serde_json::Value::as_str)
+        .unwrap_or("")
+    {
+        "tap" => Ok(PrivilegedOp::InputTap {
+            x: coord("x")?,
+            y: coord("y")?,
+        }),
+        "swipe" => Ok(PrivilegedOp::InputSwipe {
+            x1: coord("x1")?,
+            y1: coord("y1")?,
+            x2: coord("x2")?,
+            y2: coord("y2")?,
+            duration_ms: args
+                .get("duration_ms")
+                .and_then(serde_json::Value::as_u64)
+                .map_or(300, |v| v.clamp(50, 5000))
+                .try_into()?,
+        }),
+        "text" => Ok(PrivilegedOp::InputText {
+            text: args
+                .get("text")
+                .and_then(serde_json::Value::as_str)
+                .unwrap_or("")
+                .to_string(),
+        }),
+        "keyevent" => Ok(PrivilegedOp::InputKeyEvent {
+            keycode: args
+                .get("keycode")
+                .and_then(serde_json::Value::as_str)
+                .unwrap_or("")
+                .trim()
+                .to_string(),
+        }),
+        other => Err(anyhow::anyhow!(
+            "input_inject requires input=tap|swipe|text|keyevent (got '{other}')"
+        )),
     }
-    false
 }
 
-fn normalize_phone(raw: &str) -> String {
+pub(crate) fn normalize_phone(raw: &str) -> String {
     raw.chars()
         .filter(|c| c.is_ascii_digit() || *c == '+')
         .collect::<String>()
 }
 
-fn redact_phone(raw: &str) -> String {
+pub(crate) fn redact_phone(raw: &str) -> String {
     let normalized = normalize_phone(raw);
     let count = normalized.chars().count();
     if count <= 4 {
@@ -998,6 +1027,11 @@ mod tests {
                 browser_automation: true,
                 file_system_access: true,
                 event_hooks: true,
+                notification_access: true,
+                usage_stats: true,
+                automation: true,
+                audio_playback: true,
+                geofencing: true,
             },
             bridge: crate::config::AndroidBridgeConfig::default(),
             policy: crate::config::AndroidPolicyConfig {
@@ -1006,6 +1040,13 @@ mod tests {
                 allowed_phone_numbers: vec!["+15551234567".into()],
                 max_sms_per_hour: 5,
                 max_calls_per_hour: 5,
+                allowed_intents: vec!["open_app".into()],
+                allowed_tasker_tasks: Vec::new(),
+                allowed_broadcast_actions: Vec::new(),
+            },
+            shizuku: crate::config::AndroidShizukuConfig {
+                enabled: true,
+                allowed_settings_keys: vec!["screen_brightness".into()],
             },
         }
     }
@@ -1085,4 +1126,100 @@ mod tests {
         assert!(result.success);
         assert!(result.output.contains("set_clipboard"));
     }
+
+    #[tokio::test]
+    async fn settings_put_requires_shizuku_enabled() {
+        let mut cfg = test_config();
+        cfg.shizuku.enabled = false;
+        let tool = AndroidDeviceTool::new(Arc::new(SecurityPolicy::default()), cfg);
+
+        let result = tool
+            .execute(json!({
+                "action": "settings_put",
+                "key": "screen_brightness",
+                "value": "128",
+                "approved": true
+            }))
+            .await
+            .unwrap();
+
+        assert!(!result.success);
+        assert!(result.error.unwrap_or_default().contains("shizuku"));
+    }
+
+    #[tokio::test]
+    async fn settings_put_enforces_key_allowlist_and_approval() {
+        let tool = AndroidDeviceTool::new(Arc::new(SecurityPolicy::default()), test_config());
+
+        let unapproved = tool
+            .execute(json!({
+                "action": "settings_put",
+                "key": "screen_brightness",
+                "value": "128"
+            }))
+            .await
+            .unwrap();
+        assert!(!unapproved.success);
+        assert!(unapproved.error.unwrap_or_default().contains("approval"));
+
+        let blocked = tool
+            .execute(json!({
+                "action": "settings_put",
+                "namespace": "global",
+                "key": "adb_enabled",
+                "value": "1",
+                "approved": true
+            }))
+            .await
+            .unwrap();
+        assert!(!blocked.success);
+        assert!(blocked
+            .error
+            .unwrap_or_default()
+            .contains("allowed_settings_keys"));
+
+        let allowed = tool
+            .execute(json!({
+                "action": "settings_put",
+                "key": "screen_brightness",
+                "value": "128",
+                "approved": true
+            }))
+            .await
+            .unwrap();
+        assert!(allowed.success);
+        assert!(allowed.output.contains("screen_brightness"));
+    }
+
+    #[tokio::test]
+    async fn input_inject_sends_rendered_argv() {
+        let tool = AndroidDeviceTool::new(Arc::new(SecurityPolicy::default()), test_config());
+
+        let result = tool
+            .execute(json!({
+                "action": "input_inject",
+                "input": "keyevent",
+                "keycode": "KEYCODE_HOME"
+            }))
+            .await
+            .unwrap();
+
+        assert!(result.success);
+        assert!(result.output.contains("KEYCODE_HOME"));
+
+        let result = tool
+            .execute(json!({
+                "action": "input_inject",
+                "input": "tap",
+                "x": u64::from(u32::MAX) + 1,
+                "y": 10
+            }))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(result
+            .error
+            .unwrap_or_default()
+            .contains("'x' is out of range"));
+    }
 }
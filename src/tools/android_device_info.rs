@@ -0,0 +1,143 @@
+use super::android_bridge::AndroidBridge;
+use super::traits::{Tool, ToolResult};
+use crate::config::AndroidConfig;
+use crate::runtime::{network, power};
+use async_trait::async_trait;
+use serde_json::json;
+
+/// Device health summary: host-reported battery, storage, and memory merged
+/// with the network and background state the runtime already tracks.
+pub struct AndroidDeviceInfoTool {
+    config: AndroidConfig,
+    bridge: AndroidBridge,
+}
+
+impl AndroidDeviceInfoTool {
+    pub fn new(config: AndroidConfig) -> Self {
+        Self {
+            bridge: AndroidBridge::new(config.bridge.clone()),
+            config,
+        }
+    }
+
+    /// Fields known on the Rust side without a host round-trip.
+    fn local_status() -> serde_json::Value {
+        let net = network::network_state();
+        json!({
+            "network": {
+                "type": net.connection.as_str(),
+                "metered": net.metered,
+            },
+            "background_restricted": power::background_state().restricted,
+            "agent_uptime_secs": crate::health::snapshot().uptime_seconds,
+        })
+    }
+
+    /// Merge the host reply into `status`. A failed host call is reported as
+    /// `host_error` so heartbeat reports still get the local fields.
+    fn merge_host_reply(status: &mut serde_json::Value, reply: anyhow::Result<serde_json::Value>) {
+        let Some(status) = status.as_object_mut() else {
+            return;
+        };
+        match reply {
+            Ok(reply) if reply.get("ok").and_then(serde_json::Value::as_bool) != Some(false) => {
+                if let Some(fields) = reply.as_object() {
+                    for (key, value) in fields {
+                        if key != "ok" {
+                            status.entry(key.clone()).or_insert_with(|| value.clone());
+                        }
+                    }
+                }
+            }
+            Ok(reply) => {
+                let detail = reply
+                    .get("error")
+                    .and_then(serde_json::Value::as_str)
+                    .unwrap_or("android_bridge_error");
+                status.insert("host_error".into(), json!(detail));
+            }
+            Err(error) => {
+                status.insert("host_error".into(), json!(error.to_string()));
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl Tool for AndroidDeviceInfoTool {
+    fn name(&self) -> &str {
+        "device_info"
+    }
+
+    fn description(&self) -> &str {
+        "Report device health: battery level and charging state, free storage, available RAM, network type (and whether it is metered), device and agent uptime. Use it in heartbeat reports and before starting heavy work such as large downloads."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {}
+        })
+    }
+
+    async fn execute(&self, _args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        if !self.config.capabilities.battery {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some("android capability battery is disabled".into()),
+            });
+        }
+
+        let mut status = Self::local_status();
+        let reply = self.bridge.call("get_device_status", json!({})).await;
+        Self::merge_host_reply(&mut status, reply);
+
+        Ok(ToolResult {
+            success: true,
+            output: serde_json::to_string_pretty(&status)?,
+            error: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn reports_local_and_host_fields() {
+        let result = AndroidDeviceInfoTool::new(AndroidConfig::default())
+            .execute(json!({}))
+            .await
+            .unwrap();
+        assert!(result.success);
+        let status: serde_json::Value = serde_json::from_str(&result.output).unwrap();
+        assert!(status["network"]["type"].is_string());
+        assert!(status["agent_uptime_secs"].is_u64());
+        assert_eq!(status["action"], "get_device_status");
+    }
+
+    #[test]
+    fn host_failure_keeps_local_fields() {
+        let mut status = json!({ "background_restricted": false });
+        AndroidDeviceInfoTool::merge_host_reply(
+            &mut status,
+            Ok(json!({ "ok": false, "error": "battery_unavailable" })),
+        );
+        assert_eq!(status["host_error"], "battery_unavailable");
+        assert_eq!(status["background_restricted"], false);
+    }
+
+    #[test]
+    fn host_fields_do_not_override_local_state() {
+        let mut status = json!({ "background_restricted": true });
+        AndroidDeviceInfoTool::merge_host_reply(
+            &mut status,
+            Ok(json!({ "ok": true, "battery_percent": 81, "background_restricted": false })),
+        );
+        assert_eq!(status["battery_percent"], 81);
+        assert_eq!(status["background_restricted"], true);
+        assert!(status.get("ok").is_none());
+    }
+}
@@ -0,0 +1,328 @@
+use super::android_bridge::{bridge_tool_result, AndroidBridge};
+use super::traits::{Tool, ToolResult};
+use crate::config::AndroidConfig;
+use crate::runtime::geofences::{self, Geofence, GeofenceTransition};
+use crate::security::policy::ToolOperation;
+use crate::security::SecurityPolicy;
+use async_trait::async_trait;
+use chrono::Utc;
+use serde_json::json;
+use std::sync::Arc;
+
+/// Android's geofencing API becomes unreliable below ~100 m.
+const MIN_RADIUS_M: f64 = 100.0;
+const MAX_RADIUS_M: f64 = 10_000.0;
+const DEFAULT_RADIUS_M: f64 = 150.0;
+/// Google Play services allows at most 100 active geofences per app.
+const MAX_GEOFENCES: usize = 100;
+const MAX_PROMPT_CHARS: usize = 1000;
+
+/// Register enter/exit geofences with the host; when one triggers, its
+/// prompt runs through the agent as a scheduled job.
+pub struct AndroidGeofenceTool {
+    security: Arc<SecurityPolicy>,
+    config: AndroidConfig,
+    bridge: AndroidBridge,
+}
+
+impl AndroidGeofenceTool {
+    pub fn new(security: Arc<SecurityPolicy>, config: AndroidConfig) -> Self {
+        Self {
+            security,
+            bridge: AndroidBridge::new(config.bridge.clone()),
+            config,
+        }
+    }
+
+    fn required_str<'a>(args: &'a serde_json::Value, name: &str) -> anyhow::Result<&'a str> {
+        args.get(name)
+            .and_then(serde_json::Value::as_str)
+            .map(str::trim)
+            .filter(|v| !v.is_empty())
+            .ok_or_else(|| anyhow::anyhow!("Missing '{name}' parameter"))
+    }
+
+    /// Validate `add` arguments into a new fence.
+    fn build_fence(args: &serde_json::Value) -> anyhow::Result<Geofence> {
+        let name = Self::required_str(args, "name")?;
+        let prompt = Self::required_str(args, "prompt")?;
+        if prompt.chars().count() > MAX_PROMPT_CHARS {
+            anyhow::bail!("'prompt' exceeds {MAX_PROMPT_CHARS} characters");
+        }
+
+        let latitude = args
+            .get("latitude")
+            .and_then(serde_json::Value::as_f64)
+            .filter(|v| (-90.0..=90.0).contains(v))
+            .ok_or_else(|| anyhow::anyhow!("'latitude' must be a number from -90 to 90"))?;
+        let longitude = args
+            .get("longitude")
+            .and_then(serde_json::Value::as_f64)
+            .filter(|v| (-180.0..=180.0).contains(v))
+            .ok_or_else(|| anyhow::anyhow!("'longitude' must be a number from -180 to 180"))?;
+        let radius_m = match args.get("radius_m") {
+            None | Some(serde_json::Value::Null) => DEFAULT_RADIUS_M,
+            Some(value) => value
+                .as_f64()
+                .filter(|v| (MIN_RADIUS_M..=MAX_RADIUS_M).contains(v))
+                .ok_or_else(|| {
+                    anyhow::anyhow!("'radius_m' must be between {MIN_RADIUS_M} and {MAX_RADIUS_M}")
+                })?,
+        };
+        let transitions = match args.get("trigger").and_then(serde_json::Value::as_str) {
+            None | Some("enter") => vec![GeofenceTransition::Enter],
+            Some("exit") => vec![GeofenceTransition::Exit],
+            Some("both") => vec![GeofenceTransition::Enter, GeofenceTransition::Exit],
+            Some(other) => anyhow::bail!("Unknown trigger '{other}'. Supported: enter, exit, both"),
+        };
+        let once = args
+            .get("once")
+            .and_then(serde_json::Value::as_bool)
+            .unwrap_or(true);
+
+        Ok(Geofence {
+            id: uuid::Uuid::new_v4().to_string(),
+            name: name.to_string(),
+            latitude,
+            longitude,
+            radius_m,
+            transitions,
+            prompt: prompt.to_string(),
+            once,
+            created_at: Utc::now(),
+        })
+    }
+
+    async fn add(&self, args: &serde_json::Value) -> anyhow::Result<ToolResult> {
+        let fence = Self::build_fence(args)?;
+        let existing = geofences::list(&self.security.workspace_dir)?;
+        if existing.len() >= MAX_GEOFENCES {
+            anyhow::bail!("At most {MAX_GEOFENCES} geofences can be active; remove one first");
+        }
+
+        let transitions: Vec<&str> = fence.transitions.iter().map(|t| t.as_str()).collect();
+        let result = bridge_tool_result(
+            self.bridge
+                .call(
+                    "register_geofence",
+                    json!({
+                        "id": fence.id,
+                        "latitude": fence.latitude,
+                        "longitude": fence.longitude,
+                        "radius_m": fence.radius_m,
+                        "transitions": transitions,
+                    }),
+                )
+                .await,
+        );
+        if result.success {
+            geofences::upsert(&self.security.workspace_dir, fence.clone())?;
+            return Ok(ToolResult {
+                success: true,
+                output: serde_json::to_string_pretty(&fence)?,
+                error: None,
+            });
+        }
+        Ok(result)
+    }
+
+    async fn remove(&self, args: &serde_json::Value) -> anyhow::Result<ToolResult> {
+        let id = Self::required_str(args, "id")?;
+        let Some(fence) = geofences::remove(&self.security.workspace_dir, id)? else {
+            anyhow::bail!("No geofence with id '{id}'");
+        };
+        let result = bridge_tool_result(
+            self.bridge
+                .call("unregister_geofence", json!({ "id": fence.id }))
+                .await,
+        );
+        if !result.success {
+            tracing::warn!(id = %fence.id, "Host failed to unregister geofence: {:?}", result.error);
+        }
+        Ok(ToolResult {
+            success: true,
+            output: format!("Removed geofence '{}' ({})", fence.name, fence.id),
+            error: None,
+        })
+    }
+}
+
+#[async_trait]
+impl Tool for AndroidGeofenceTool {
+    fn name(&self) -> &str {
+        "android_geofence"
+    }
+
+    fn description(&self) -> &str {
+        "Location-triggered tasks: add a geofence (name, latitude, longitude, radius_m, trigger enter/exit/both) with a prompt that you will be given when the user arrives at or leaves the place, e.g. 'when I get home, remind me to call mom' becomes trigger=enter with prompt 'Remind the user to call mom'. Fences fire once unless once=false. Use list to show fences and remove (id) to delete one."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "action": {
+                    "type": "string",
+                    "enum": ["add", "list", "remove"]
+                },
+                "name": {
+                    "type": "string",
+                    "description": "Place label, e.g. Home (add)"
+                },
+                "latitude": { "type": "number" },
+                "longitude": { "type": "number" },
+                "radius_m": {
+                    "type": "number",
+                    "description": "Fence radius in meters, 100-10000",
+                    "default": 150
+                },
+                "trigger": {
+                    "type": "string",
+                    "enum": ["enter", "exit", "both"],
+                    "default": "enter"
+                },
+                "prompt": {
+                    "type": "string",
+                    "description": "Instruction to run when the fence triggers (add)"
+                },
+                "once": {
+                    "type": "boolean",
+                    "description": "Remove the fence after it first triggers",
+                    "default": true
+                },
+                "id": {
+                    "type": "string",
+                    "description": "Geofence id (remove)"
+                }
+            },
+            "required": ["action"]
+        })
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        if !self.config.capabilities.geofencing {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some("android capability geofencing is disabled".into()),
+            });
+        }
+
+        let action = args.get("action").and_then(serde_json::Value::as_str);
+        if matches!(action, Some("add" | "remove")) {
+            if let Err(error) = self
+                .security
+                .enforce_tool_operation(ToolOperation::Act, "android_geofence")
+            {
+                return Ok(ToolResult {
+                    success: false,
+                    output: String::new(),
+                    error: Some(error),
+                });
+            }
+        }
+
+        let outcome = match action {
+            Some("add") => self.add(&args).await,
+            Some("remove") => self.remove(&args).await,
+            Some("list") => geofences::list(&self.security.workspace_dir).and_then(|fences| {
+                Ok(ToolResult {
+                    success: true,
+                    output: serde_json::to_string_pretty(&fences)?,
+                    error: None,
+                })
+            }),
+            Some(other) => Err(anyhow::anyhow!(
+                "Unknown action '{other}'. Supported: add, list, remove"
+            )),
+            None => Err(anyhow::anyhow!("Missing 'action' parameter")),
+        };
+        Ok(outcome.unwrap_or_else(|error| ToolResult {
+            success: false,
+            output: String::new(),
+            error: Some(error.to_string()),
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_tool(workspace: &std::path::Path) -> AndroidGeofenceTool {
+        let security = Arc::new(SecurityPolicy {
+            workspace_dir: workspace.to_path_buf(),
+            ..SecurityPolicy::default()
+        });
+        let mut config = AndroidConfig {
+            enabled: true,
+            ..AndroidConfig::default()
+        };
+        config.capabilities.geofencing = true;
+        AndroidGeofenceTool::new(security, config)
+    }
+
+    #[tokio::test]
+    async fn add_list_remove_round_trip() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let tool = test_tool(tmp.path());
+
+        let added = tool
+            .execute(json!({
+                "action": "add",
+                "name": "Home",
+                "latitude": 52.52,
+                "longitude": 13.405,
+                "prompt": "Remind the user to call mom"
+            }))
+            .await
+            .unwrap();
+        assert!(added.success, "{:?}", added.error);
+        let fence: Geofence = serde_json::from_str(&added.output).unwrap();
+        assert_eq!(fence.radius_m, DEFAULT_RADIUS_M);
+        assert_eq!(fence.transitions, vec![GeofenceTransition::Enter]);
+        assert!(fence.once);
+
+        let listed = tool.execute(json!({ "action": "list" })).await.unwrap();
+        assert!(listed.output.contains(&fence.id));
+
+        let removed = tool
+            .execute(json!({ "action": "remove", "id": fence.id }))
+            .await
+            .unwrap();
+        assert!(removed.success, "{:?}", removed.error);
+        assert!(geofences::list(tmp.path()).unwrap().is_empty());
+    }
+
+    #[test]
+    fn add_validation() {
+        let base = json!({
+            "name": "Office",
+            "latitude": 40.0,
+            "longitude": -3.7,
+            "prompt": "Log arrival"
+        });
+        assert!(AndroidGeofenceTool::build_fence(&base).is_ok());
+        for (key, value) in [
+            ("latitude", json!(91.0)),
+            ("longitude", json!("east")),
+            ("radius_m", json!(20)),
+            ("trigger", json!("dwell")),
+            ("prompt", json!("  ")),
+        ] {
+            let mut args = base.clone();
+            args[key] = value;
+            assert!(AndroidGeofenceTool::build_fence(&args).is_err(), "{key}");
+        }
+    }
+
+    #[tokio::test]
+    async fn requires_geofencing_capability() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let mut tool = test_tool(tmp.path());
+        tool.config.capabilities.geofencing = false;
+        let result = tool.execute(json!({ "action": "list" })).await.unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("geofencing"));
+    }
+}
@@ -0,0 +1,275 @@
+use super::android_bridge::{bridge_tool_result, AndroidBridge};
+use super::android_device::normalize_phone;
+use super::traits::{Tool, ToolResult};
+use crate::config::AndroidConfig;
+use crate::security::policy::ToolOperation;
+use crate::security::SecurityPolicy;
+use async_trait::async_trait;
+use serde_json::json;
+use std::sync::Arc;
+
+const SUPPORTED_INTENTS: &[&str] = &["open_app", "view_url", "navigate", "share_text", "dial"];
+
+/// Fire Android intents (launch apps, open links, navigation, share sheet,
+/// dialer) through the host app.
+pub struct AndroidIntentTool {
+    security: Arc<SecurityPolicy>,
+    config: AndroidConfig,
+    bridge: AndroidBridge,
+}
+
+impl AndroidIntentTool {
+    pub fn new(security: Arc<SecurityPolicy>, config: AndroidConfig) -> Self {
+        Self {
+            security,
+            bridge: AndroidBridge::new(config.bridge.clone()),
+            config,
+        }
+    }
+
+    fn ensure_intent_allowed(&self, intent: &str) -> anyhow::Result<()> {
+        if !SUPPORTED_INTENTS.contains(&intent) {
+            anyhow::bail!(
+                "Unknown intent '{intent}'. Supported: {}",
+                SUPPORTED_INTENTS.join(", ")
+            );
+        }
+        if !self
+            .config
+            .policy
+            .allowed_intents
+            .iter()
+            .any(|allowed| allowed == intent)
+        {
+            anyhow::bail!("Intent '{intent}' is not in android.policy.allowed_intents");
+        }
+        Ok(())
+    }
+
+    fn required_str<'a>(
+        args: &'a serde_json::Value,
+        name: &str,
+        intent: &str,
+    ) -> anyhow::Result<&'a str> {
+        args.get(name)
+            .and_then(serde_json::Value::as_str)
+            .map(str::trim)
+            .filter(|v| !v.is_empty())
+            .ok_or_else(|| anyhow::anyhow!("{intent} requires '{name}'"))
+    }
+
+    /// Validate arguments and build the host payload for `intent`.
+    fn build_payload(
+        &self,
+        intent: &str,
+        args: &serde_json::Value,
+    ) -> anyhow::Result<serde_json::Value> {
+        match intent {
+            "open_app" => {
+                let package = Self::required_str(args, "package", intent)?;
+                let allowed = &self.config.policy.allowed_packages;
+                if !allowed.is_empty() && !allowed.iter().any(|p| p == package) {
+                    anyhow::bail!("Package '{package}' is not in android.policy.allowed_packages");
+                }
+                Ok(json!({ "package": package }))
+            }
+            "view_url" => {
+                let url = Self::required_str(args, "url", intent)?;
+                if !url.starts_with("https://") {
+                    anyhow::bail!("view_url only supports https:// URLs");
+                }
+                Ok(json!({ "url": url }))
+            }
+            "navigate" => {
+                let destination = Self::required_str(args, "destination", intent)?;
+                let mode = match args.get("travel_mode").and_then(serde_json::Value::as_str) {
+                    None => "driving",
+                    Some(mode @ ("driving" | "walking" | "bicycling" | "transit")) => mode,
+                    Some(other) => anyhow::bail!(
+                        "Unknown travel_mode '{other}'. Supported: driving, walking, bicycling, transit"
+                    ),
+                };
+                Ok(json!({ "destination": destination, "travel_mode": mode }))
+            }
+            "share_text" => {
+                let text = Self::required_str(args, "text", intent)?;
+                Ok(json!({ "text": text }))
+            }
+            "dial" => {
+                let number = normalize_phone(Self::required_str(args, "number", intent)?);
+                if number.is_empty() {
+                    anyhow::bail!("dial requires a valid 'number'");
+                }
+                // ACTION_DIAL only pre-fills the dialer; the user still places the call.
+                Ok(json!({ "number": number }))
+            }
+            other => anyhow::bail!("Unknown intent '{other}'"),
+        }
+    }
+}
+
+#[async_trait]
+impl Tool for AndroidIntentTool {
+    fn name(&self) -> &str {
+        "android_intent"
+    }
+
+    fn description(&self) -> &str {
+        "Fire an Android intent on the user's phone: open_app (package), view_url (https url), navigate (destination in the maps app), share_text (share sheet), dial (pre-fill the dialer; the user places the call)."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "intent": {
+                    "type": "string",
+                    "enum": SUPPORTED_INTENTS,
+                    "description": "Intent to fire"
+                },
+                "package": {
+                    "type": "string",
+                    "description": "Android package id (open_app)"
+                },
+                "url": {
+                    "type": "string",
+                    "description": "HTTPS URL or deep link (view_url)"
+                },
+                "destination": {
+                    "type": "string",
+                    "description": "Address or place name (navigate)"
+                },
+                "travel_mode": {
+                    "type": "string",
+                    "enum": ["driving", "walking", "bicycling", "transit"],
+                    "default": "driving"
+                },
+                "text": {
+                    "type": "string",
+                    "description": "Text to share (share_text)"
+                },
+                "number": {
+                    "type": "string",
+                    "description": "Phone number (dial)"
+                }
+            },
+            "required": ["intent"]
+        })
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        if !self.config.capabilities.app_launch {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some("android capability app_launch is disabled".into()),
+            });
+        }
+
+        let intent = args
+            .get("intent")
+            .and_then(serde_json::Value::as_str)
+            .unwrap_or_default()
+            .trim();
+
+        let payload = self
+            .ensure_intent_allowed(intent)
+            .and_then(|()| self.build_payload(intent, &args));
+        let payload = match payload {
+            Ok(payload) => payload,
+            Err(error) => {
+                return Ok(ToolResult {
+                    success: false,
+                    output: String::new(),
+                    error: Some(error.to_string()),
+                });
+            }
+        };
+
+        if let Err(error) = self
+            .security
+            .enforce_tool_operation(ToolOperation::Act, "android_intent")
+        {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(error),
+            });
+        }
+
+        Ok(bridge_tool_result(
+            self.bridge
+                .call(
+                    "fire_intent",
+                    json!({ "intent": intent, "params": payload }),
+                )
+                .await,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_tool() -> AndroidIntentTool {
+        let config = AndroidConfig {
+            enabled: true,
+            ..AndroidConfig::default()
+        };
+        AndroidIntentTool::new(Arc::new(SecurityPolicy::default()), config)
+    }
+
+    #[tokio::test]
+    async fn navigate_is_allowed_by_default() {
+        let result = test_tool()
+            .execute(json!({ "intent": "navigate", "destination": "Central Station" }))
+            .await
+            .unwrap();
+        assert!(result.success, "{:?}", result.error);
+        assert!(result.output.contains("fire_intent"));
+        assert!(result.output.contains("driving"));
+    }
+
+    #[tokio::test]
+    async fn dial_requires_allowlisting() {
+        let result = test_tool()
+            .execute(json!({ "intent": "dial", "number": "+1 555 123 4567" }))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("allowed_intents"));
+
+        let mut config = AndroidConfig::default();
+        config.policy.allowed_intents.push("dial".into());
+        let tool = AndroidIntentTool::new(Arc::new(SecurityPolicy::default()), config);
+        let result = tool
+            .execute(json!({ "intent": "dial", "number": "+1 555 123 4567" }))
+            .await
+            .unwrap();
+        assert!(result.success);
+        assert!(result.output.contains("+15551234567"));
+    }
+
+    #[tokio::test]
+    async fn open_app_respects_package_allowlist() {
+        let mut config = AndroidConfig::default();
+        config.policy.allowed_packages = vec!["com.example.maps".into()];
+        let tool = AndroidIntentTool::new(Arc::new(SecurityPolicy::default()), config);
+        let result = tool
+            .execute(json!({ "intent": "open_app", "package": "com.example.bank" }))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("allowed_packages"));
+    }
+
+    #[tokio::test]
+    async fn view_url_requires_https() {
+        let result = test_tool()
+            .execute(json!({ "intent": "view_url", "url": "http://example.com" }))
+            .await
+            .unwrap();
+        assert!(!result.success);
+    }
+}
@@ -0,0 +1,177 @@
+use super::android_bridge::{bridge_tool_result, AndroidBridge};
+use super::traits::{Tool, ToolResult};
+use crate::config::AndroidConfig;
+use crate::security::SecurityPolicy;
+use async_trait::async_trait;
+use serde_json::json;
+use std::sync::Arc;
+
+/// Fixes older than this are flagged `stale` unless the caller overrides it.
+const DEFAULT_MAX_AGE_SECS: u64 = 600;
+
+/// Device location (last known or fresh fix) from the host app.
+pub struct AndroidLocationTool {
+    security: Arc<SecurityPolicy>,
+    config: AndroidConfig,
+    bridge: AndroidBridge,
+}
+
+impl AndroidLocationTool {
+    pub fn new(security: Arc<SecurityPolicy>, config: AndroidConfig) -> Self {
+        Self {
+            security,
+            bridge: AndroidBridge::new(config.bridge.clone()),
+            config,
+        }
+    }
+
+    /// Add `age_secs` and `stale` derived from the fix timestamp.
+    fn annotate_staleness(reply: &mut serde_json::Value, max_age_secs: u64, now_ms: i64) {
+        let Some(fix_ms) = reply
+            .get("timestamp_ms")
+            .and_then(serde_json::Value::as_i64)
+        else {
+            return;
+        };
+        let age_secs = u64::try_from(now_ms.saturating_sub(fix_ms) / 1000).unwrap_or(0);
+        if let Some(obj) = reply.as_object_mut() {
+            obj.insert("age_secs".into(), json!(age_secs));
+            obj.insert("stale".into(), json!(age_secs > max_age_secs));
+        }
+    }
+}
+
+#[async_trait]
+impl Tool for AndroidLocationTool {
+    fn name(&self) -> &str {
+        "android_location"
+    }
+
+    fn description(&self) -> &str {
+        "Get the phone's location (latitude, longitude, accuracy in meters, and how old the fix is). mode=last_known is instant and battery-friendly; mode=current requests a fresh fix."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "mode": {
+                    "type": "string",
+                    "enum": ["last_known", "current"],
+                    "description": "Use the cached fix or request a fresh one",
+                    "default": "last_known"
+                },
+                "max_age_secs": {
+                    "type": "integer",
+                    "description": "Fixes older than this are reported as stale",
+                    "default": DEFAULT_MAX_AGE_SECS
+                }
+            }
+        })
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        if !self.security.allow_location {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(
+                    "Location access is disabled by security policy (autonomy.allow_location)"
+                        .into(),
+                ),
+            });
+        }
+        if !self.config.capabilities.location {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some("android capability location is disabled".into()),
+            });
+        }
+
+        let mode = match args.get("mode").and_then(serde_json::Value::as_str) {
+            None | Some("last_known") => "last_known",
+            Some("current") => "current",
+            Some(other) => {
+                return Ok(ToolResult {
+                    success: false,
+                    output: String::new(),
+                    error: Some(format!(
+                        "Unknown mode '{other}'. Supported: last_known, current"
+                    )),
+                });
+            }
+        };
+        let max_age_secs = args
+            .get("max_age_secs")
+            .and_then(serde_json::Value::as_u64)
+            .unwrap_or(DEFAULT_MAX_AGE_SECS);
+
+        let result = self
+            .bridge
+            .call("get_location", json!({ "mode": mode }))
+            .await
+            .map(|mut reply| {
+                Self::annotate_staleness(
+                    &mut reply,
+                    max_age_secs,
+                    chrono::Utc::now().timestamp_millis(),
+                );
+                reply
+            });
+        Ok(bridge_tool_result(result))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tools::android_bridge::test_support;
+
+    fn allowed_security() -> Arc<SecurityPolicy> {
+        Arc::new(SecurityPolicy {
+            allow_location: true,
+            ..SecurityPolicy::default()
+        })
+    }
+
+    fn enabled_config() -> AndroidConfig {
+        test_support::enabled_config(|caps| caps.location = true)
+    }
+
+    #[tokio::test]
+    async fn denied_without_security_permission() {
+        let tool = AndroidLocationTool::new(Arc::new(SecurityPolicy::default()), enabled_config());
+        let result = tool.execute(json!({})).await.unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("allow_location"));
+    }
+
+    #[tokio::test]
+    async fn disabled_capability_blocks_access() {
+        let tool =
+            AndroidLocationTool::new(allowed_security(), test_support::enabled_config(|_| {}));
+        test_support::assert_capability_required(&tool, json!({}), "location").await;
+    }
+
+    #[tokio::test]
+    async fn current_mode_reaches_bridge() {
+        let tool = AndroidLocationTool::new(allowed_security(), enabled_config());
+        let result = tool.execute(json!({ "mode": "current" })).await.unwrap();
+        assert!(result.success, "{:?}", result.error);
+        assert!(result.output.contains("\"current\""));
+    }
+
+    #[test]
+    fn staleness_is_derived_from_fix_timestamp() {
+        let now_ms = 1_700_000_000_000;
+        let mut fresh = json!({ "latitude": 1.0, "timestamp_ms": now_ms - 30_000 });
+        AndroidLocationTool::annotate_staleness(&mut fresh, 600, now_ms);
+        assert_eq!(fresh["age_secs"], 30);
+        assert_eq!(fresh["stale"], false);
+
+        let mut old = json!({ "latitude": 1.0, "timestamp_ms": now_ms - 3_600_000 });
+        AndroidLocationTool::annotate_staleness(&mut old, 600, now_ms);
+        assert_eq!(old["stale"], true);
+    }
+}
@@ -0,0 +1,256 @@
+use super::android_bridge::{bridge_tool_result, AndroidBridge};
+use super::traits::{Tool, ToolResult};
+use crate::config::AndroidConfig;
+use crate::security::SecurityPolicy;
+use async_trait::async_trait;
+use serde_json::json;
+use std::sync::Arc;
+
+/// Android caps notifications at three action buttons.
+const MAX_NOTIFICATION_ACTIONS: usize = 3;
+const MAX_TITLE_CHARS: usize = 120;
+const MAX_BODY_CHARS: usize = 4000;
+const DEFAULT_CHANNEL: &str = "agent";
+
+/// Post a local notification on the device through the host app.
+pub struct AndroidNotificationTool {
+    security: Arc<SecurityPolicy>,
+    config: AndroidConfig,
+    bridge: AndroidBridge,
+}
+
+impl AndroidNotificationTool {
+    pub fn new(security: Arc<SecurityPolicy>, config: AndroidConfig) -> Self {
+        Self {
+            security,
+            bridge: AndroidBridge::new(config.bridge.clone()),
+            config,
+        }
+    }
+
+    fn parse_actions(args: &serde_json::Value) -> anyhow::Result<Vec<serde_json::Value>> {
+        let Some(raw) = args.get("actions") else {
+            return Ok(Vec::new());
+        };
+        let items = raw
+            .as_array()
+            .ok_or_else(|| anyhow::anyhow!("'actions' must be an array"))?;
+        if items.len() > MAX_NOTIFICATION_ACTIONS {
+            anyhow::bail!("At most {MAX_NOTIFICATION_ACTIONS} notification actions are supported");
+        }
+
+        items
+            .iter()
+            .map(|item| {
+                let title = item
+                    .get("title")
+                    .and_then(serde_json::Value::as_str)
+                    .map(str::trim)
+                    .filter(|v| !v.is_empty())
+                    .ok_or_else(|| anyhow::anyhow!("Each action requires a 'title'"))?;
+                let id = item
+                    .get("id")
+                    .and_then(serde_json::Value::as_str)
+                    .map(str::trim)
+                    .filter(|v| !v.is_empty())
+                    .unwrap_or(title);
+                Ok(json!({ "id": id, "title": title }))
+            })
+            .collect()
+    }
+}
+
+#[async_trait]
+impl Tool for AndroidNotificationTool {
+    fn name(&self) -> &str {
+        "android_notification"
+    }
+
+    fn description(&self) -> &str {
+        "Post a local notification on the user's Android device (title, body, optional channel and up to 3 action buttons). Use this to alert the user on-device, e.g. when a scheduled task finishes."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "title": {
+                    "type": "string",
+                    "description": "Notification title"
+                },
+                "body": {
+                    "type": "string",
+                    "description": "Notification text"
+                },
+                "channel": {
+                    "type": "string",
+                    "description": "Notification channel id (e.g. agent, reminders, alerts)",
+                    "default": DEFAULT_CHANNEL
+                },
+                "actions": {
+                    "type": "array",
+                    "description": "Optional action buttons; the chosen id is reported back to the agent as a message",
+                    "items": {
+                        "type": "object",
+                        "properties": {
+                            "id": { "type": "string" },
+                            "title": { "type": "string" }
+                        },
+                        "required": ["title"]
+                    }
+                }
+            },
+            "required": ["title", "body"]
+        })
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        if !self.config.capabilities.notifications {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some("android capability notifications is disabled".into()),
+            });
+        }
+
+        if !self.security.can_act() {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some("Action blocked: autonomy is read-only".into()),
+            });
+        }
+
+        if !self.security.record_action() {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some("Action blocked: rate limit exceeded".into()),
+            });
+        }
+
+        let title = args
+            .get("title")
+            .and_then(serde_json::Value::as_str)
+            .map(str::trim)
+            .unwrap_or_default();
+        let body = args
+            .get("body")
+            .and_then(serde_json::Value::as_str)
+            .map(str::trim)
+            .unwrap_or_default();
+        if title.is_empty() || body.is_empty() {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some("Both 'title' and 'body' are required".into()),
+            });
+        }
+
+        let channel = args
+            .get("channel")
+            .and_then(serde_json::Value::as_str)
+            .map(str::trim)
+            .filter(|v| !v.is_empty())
+            .unwrap_or(DEFAULT_CHANNEL);
+
+        let actions = match Self::parse_actions(&args) {
+            Ok(actions) => actions,
+            Err(error) => {
+                return Ok(ToolResult {
+                    success: false,
+                    output: String::new(),
+                    error: Some(error.to_string()),
+                });
+            }
+        };
+
+        let payload = json!({
+            "title": title.chars().take(MAX_TITLE_CHARS).collect::<String>(),
+            "body": body.chars().take(MAX_BODY_CHARS).collect::<String>(),
+            "channel": channel,
+            "actions": actions,
+        });
+
+        Ok(bridge_tool_result(
+            self.bridge.call("post_notification", payload).await,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::security::AutonomyLevel;
+    use crate::tools::android_bridge::test_support;
+
+    fn enabled_config() -> AndroidConfig {
+        test_support::enabled_config(|caps| caps.notifications = true)
+    }
+
+    #[tokio::test]
+    async fn posts_notification_with_actions() {
+        let tool =
+            AndroidNotificationTool::new(Arc::new(SecurityPolicy::default()), enabled_config());
+        let result = tool
+            .execute(json!({
+                "title": "Build finished",
+                "body": "All tests passed",
+                "actions": [{ "id": "open", "title": "Open report" }]
+            }))
+            .await
+            .unwrap();
+
+        assert!(result.success, "{:?}", result.error);
+        assert!(result.output.contains("post_notification"));
+        assert!(result.output.contains("Open report"));
+        assert!(result.output.contains("\"channel\": \"agent\""));
+    }
+
+    #[tokio::test]
+    async fn blocked_when_capability_disabled() {
+        let tool = AndroidNotificationTool::new(
+            Arc::new(SecurityPolicy::default()),
+            test_support::enabled_config(|_| {}),
+        );
+        test_support::assert_capability_required(
+            &tool,
+            json!({ "title": "t", "body": "b" }),
+            "notifications",
+        )
+        .await;
+    }
+
+    #[tokio::test]
+    async fn rejects_too_many_actions() {
+        let tool =
+            AndroidNotificationTool::new(Arc::new(SecurityPolicy::default()), enabled_config());
+        let result = tool
+            .execute(json!({
+                "title": "t",
+                "body": "b",
+                "actions": [
+                    { "title": "a" }, { "title": "b" }, { "title": "c" }, { "title": "d" }
+                ]
+            }))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("At most 3"));
+    }
+
+    #[tokio::test]
+    async fn blocked_in_read_only_mode() {
+        let security = Arc::new(SecurityPolicy {
+            autonomy: AutonomyLevel::ReadOnly,
+            ..SecurityPolicy::default()
+        });
+        let tool = AndroidNotificationTool::new(security, enabled_config());
+        let result = tool
+            .execute(json!({ "title": "t", "body": "b" }))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("read-only"));
+    }
+}
@@ -0,0 +1,144 @@
+use super::android_bridge::{bridge_tool_result, AndroidBridge};
+use super::traits::{Tool, ToolResult};
+use crate::config::AndroidConfig;
+use async_trait::async_trait;
+use serde_json::json;
+
+/// Sensors the host can sample in a single snapshot.
+const SUPPORTED_SENSORS: &[&str] = &["accelerometer", "light", "step_counter", "barometer"];
+
+/// One-shot readings from the device's motion and environment sensors.
+pub struct AndroidSensorsTool {
+    config: AndroidConfig,
+    bridge: AndroidBridge,
+}
+
+impl AndroidSensorsTool {
+    pub fn new(config: AndroidConfig) -> Self {
+        Self {
+            bridge: AndroidBridge::new(config.bridge.clone()),
+            config,
+        }
+    }
+
+    fn requested_sensors(args: &serde_json::Value) -> anyhow::Result<Vec<String>> {
+        let Some(requested) = args.get("sensors") else {
+            return Ok(SUPPORTED_SENSORS.iter().map(|s| (*s).to_string()).collect());
+        };
+        let requested = requested
+            .as_array()
+            .ok_or_else(|| anyhow::anyhow!("'sensors' must be an array of sensor names"))?;
+
+        let mut sensors = Vec::with_capacity(requested.len());
+        for sensor in requested {
+            let name = sensor.as_str().unwrap_or_default().trim();
+            if !SUPPORTED_SENSORS.contains(&name) {
+                anyhow::bail!(
+                    "Unknown sensor '{name}'. Supported: {}",
+                    SUPPORTED_SENSORS.join(", ")
+                );
+            }
+            if !sensors.iter().any(|s| s == name) {
+                sensors.push(name.to_string());
+            }
+        }
+        if sensors.is_empty() {
+            anyhow::bail!("'sensors' must name at least one sensor");
+        }
+        Ok(sensors)
+    }
+}
+
+#[async_trait]
+impl Tool for AndroidSensorsTool {
+    fn name(&self) -> &str {
+        "android_sensors"
+    }
+
+    fn description(&self) -> &str {
+        "Take a snapshot of the phone's sensors: accelerometer (m/s²), ambient light (lux), step_counter (steps since boot and today), barometer (hPa). Useful for questions like \"did I walk today?\" or \"is it dark in the room?\"."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "sensors": {
+                    "type": "array",
+                    "items": { "type": "string", "enum": SUPPORTED_SENSORS },
+                    "description": "Sensors to read; defaults to all supported sensors"
+                }
+            }
+        })
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        if !self.config.capabilities.sensors {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some("android capability sensors is disabled".into()),
+            });
+        }
+
+        let sensors = match Self::requested_sensors(&args) {
+            Ok(sensors) => sensors,
+            Err(error) => {
+                return Ok(ToolResult {
+                    success: false,
+                    output: String::new(),
+                    error: Some(error.to_string()),
+                });
+            }
+        };
+
+        Ok(bridge_tool_result(
+            self.bridge
+                .call("sensor_snapshot", json!({ "sensors": sensors }))
+                .await,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn snapshot_defaults_to_all_sensors() {
+        let result = AndroidSensorsTool::new(AndroidConfig::default())
+            .execute(json!({}))
+            .await
+            .unwrap();
+        assert!(result.success, "{:?}", result.error);
+        assert!(result.output.contains("sensor_snapshot"));
+        for sensor in SUPPORTED_SENSORS {
+            assert!(result.output.contains(sensor));
+        }
+    }
+
+    #[test]
+    fn requested_sensors_are_validated_and_deduplicated() {
+        let sensors = AndroidSensorsTool::requested_sensors(
+            &json!({ "sensors": ["step_counter", "light", "step_counter"] }),
+        )
+        .unwrap();
+        assert_eq!(sensors, vec!["step_counter", "light"]);
+
+        assert!(
+            AndroidSensorsTool::requested_sensors(&json!({ "sensors": ["gyroscope"] })).is_err()
+        );
+        assert!(AndroidSensorsTool::requested_sensors(&json!({ "sensors": [] })).is_err());
+    }
+
+    #[tokio::test]
+    async fn disabled_capability_blocks_snapshot() {
+        let mut config = AndroidConfig::default();
+        config.capabilities.sensors = false;
+        let result = AndroidSensorsTool::new(config)
+            .execute(json!({}))
+            .await
+            .unwrap();
+        assert!(!result.success);
+    }
+}
@@ -0,0 +1,305 @@
+use super::android_bridge::{bridge_tool_result, AndroidBridge};
+use super::android_device::{normalize_phone, redact_phone};
+use super::traits::{Tool, ToolResult};
+use crate::config::{AndroidConfig, AndroidDistribution};
+use crate::security::policy::ToolOperation;
+use crate::security::SecurityPolicy;
+use async_trait::async_trait;
+use parking_lot::Mutex;
+use serde_json::json;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+const RATE_LIMIT_WINDOW_SECS: u64 = 3600;
+const MAX_SMS_BODY_CHARS: usize = 1600;
+
+/// Send and read SMS through the host app, restricted to the numbers in
+/// `autonomy.allowed_sms_numbers`.
+pub struct AndroidSmsTool {
+    security: Arc<SecurityPolicy>,
+    config: AndroidConfig,
+    bridge: AndroidBridge,
+    sent: Mutex<Vec<Instant>>,
+}
+
+impl AndroidSmsTool {
+    pub fn new(security: Arc<SecurityPolicy>, config: AndroidConfig) -> Self {
+        Self {
+            security,
+            bridge: AndroidBridge::new(config.bridge.clone()),
+            config,
+            sent: Mutex::new(Vec::new()),
+        }
+    }
+
+    fn ensure_available(&self) -> anyhow::Result<()> {
+        if !self.config.capabilities.sms {
+            anyhow::bail!("android capability sms is disabled");
+        }
+        if self.config.distribution == AndroidDistribution::Play {
+            anyhow::bail!(
+                "sms actions are disabled in play distribution. Use enterprise/full distribution."
+            );
+        }
+        if self.security.allowed_sms_numbers.is_empty() {
+            anyhow::bail!("No numbers in autonomy.allowed_sms_numbers; SMS access is disabled");
+        }
+        Ok(())
+    }
+
+    fn ensure_number_allowed(&self, number: &str) -> anyhow::Result<()> {
+        if self.security.is_sms_number_allowed(number) {
+            Ok(())
+        } else {
+            anyhow::bail!(
+                "Number {} is not in autonomy.allowed_sms_numbers",
+                redact_phone(number)
+            )
+        }
+    }
+
+    fn enforce_send_budget(&self) -> anyhow::Result<()> {
+        let limit = self.config.policy.max_sms_per_hour;
+        let now = Instant::now();
+        let cutoff = now
+            .checked_sub(Duration::from_secs(RATE_LIMIT_WINDOW_SECS))
+            .unwrap_or(now);
+        let mut sent = self.sent.lock();
+        sent.retain(|ts| *ts >= cutoff);
+        if sent.len() >= limit as usize {
+            anyhow::bail!("send blocked: hourly SMS limit reached ({limit})");
+        }
+        sent.push(now);
+        Ok(())
+    }
+
+    async fn send(&self, args: &serde_json::Value) -> anyhow::Result<serde_json::Value> {
+        let to = args
+            .get("to")
+            .and_then(serde_json::Value::as_str)
+            .unwrap_or("")
+            .trim();
+        let body = args
+            .get("body")
+            .and_then(serde_json::Value::as_str)
+            .unwrap_or("")
+            .trim();
+        if to.is_empty() || body.is_empty() {
+            anyhow::bail!("send requires 'to' and 'body'");
+        }
+        if body.chars().count() > MAX_SMS_BODY_CHARS {
+            anyhow::bail!("SMS body exceeds {MAX_SMS_BODY_CHARS} characters");
+        }
+        self.ensure_number_allowed(to)?;
+
+        let approved = args
+            .get("approved")
+            .and_then(serde_json::Value::as_bool)
+            .unwrap_or(false);
+        if self.config.policy.require_explicit_approval && !approved {
+            anyhow::bail!(
+                "send requires explicit approval. Retry with approved=true after user confirmation"
+            );
+        }
+
+        self.security
+            .enforce_tool_operation(ToolOperation::Act, "android_sms.send")
+            .map_err(anyhow::Error::msg)?;
+        self.enforce_send_budget()?;
+
+        tracing::warn!(target: "android_sms", to = %redact_phone(to), "sms send requested");
+        self.bridge
+            .call(
+                "send_sms",
+                json!({ "to": normalize_phone(to), "body": body }),
+            )
+            .await
+    }
+
+    async fn read(&self, args: &serde_json::Value) -> anyhow::Result<serde_json::Value> {
+        let limit = args
+            .get("limit")
+            .and_then(serde_json::Value::as_u64)
+            .map_or(10, |v| v.clamp(1, 100));
+
+        let numbers: Vec<String> = match args.get("from").and_then(serde_json::Value::as_str) {
+            Some(from) if !from.trim().is_empty() => {
+                self.ensure_number_allowed(from)?;
+                vec![normalize_phone(from)]
+            }
+            _ => self
+                .security
+                .allowed_sms_numbers
+                .iter()
+                .map(|n| normalize_phone(n))
+                .collect(),
+        };
+
+        let mut reply = self
+            .bridge
+            .call("read_sms", json!({ "limit": limit, "numbers": numbers }))
+            .await?;
+
+        // Defense in depth: never surface messages from numbers outside the
+        // allowlist, even if the host ignored the filter.
+        if let Some(messages) = reply
+            .get_mut("messages")
+            .and_then(serde_json::Value::as_array_mut)
+        {
+            messages.retain(|message| {
+                message
+                    .get("address")
+                    .and_then(serde_json::Value::as_str)
+                    .is_some_and(|address| self.security.is_sms_number_allowed(address))
+            });
+        }
+        Ok(reply)
+    }
+}
+
+#[async_trait]
+impl Tool for AndroidSmsTool {
+    fn name(&self) -> &str {
+        "android_sms"
+    }
+
+    fn description(&self) -> &str {
+        "Send SMS and read recent messages on the user's Android phone. Only numbers in the user's SMS allowlist can be messaged or read. Sending requires approved=true after the user confirms the recipient and text."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "action": {
+                    "type": "string",
+                    "enum": ["send", "read"],
+                    "description": "send an SMS or read recent messages"
+                },
+                "to": {
+                    "type": "string",
+                    "description": "Recipient phone number (send)"
+                },
+                "body": {
+                    "type": "string",
+                    "description": "Message text (send)"
+                },
+                "from": {
+                    "type": "string",
+                    "description": "Only read messages from this number (read); defaults to all allowlisted numbers"
+                },
+                "limit": {
+                    "type": "integer",
+                    "description": "Maximum messages to return (read)",
+                    "default": 10
+                },
+                "approved": {
+                    "type": "boolean",
+                    "description": "Set true after the user explicitly confirms the send",
+                    "default": false
+                }
+            },
+            "required": ["action"]
+        })
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        if let Err(error) = self.ensure_available() {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(error.to_string()),
+            });
+        }
+
+        let result = match args.get("action").and_then(serde_json::Value::as_str) {
+            Some("send") => self.send(&args).await,
+            Some("read") => self.read(&args).await,
+            Some(other) => Err(anyhow::anyhow!(
+                "Unknown action '{other}'. Supported: send, read"
+            )),
+            None => Err(anyhow::anyhow!("Missing 'action' parameter")),
+        };
+        Ok(bridge_tool_result(result))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_tool(allowed: &[&str]) -> AndroidSmsTool {
+        let security = Arc::new(SecurityPolicy {
+            allowed_sms_numbers: allowed.iter().map(|n| (*n).to_string()).collect(),
+            ..SecurityPolicy::default()
+        });
+        let mut config = AndroidConfig {
+            enabled: true,
+            distribution: AndroidDistribution::Full,
+            ..AndroidConfig::default()
+        };
+        config.capabilities.sms = true;
+        AndroidSmsTool::new(security, config)
+    }
+
+    #[tokio::test]
+    async fn empty_allowlist_disables_sms() {
+        let result = test_tool(&[])
+            .execute(json!({ "action": "read" }))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("allowed_sms_numbers"));
+    }
+
+    #[tokio::test]
+    async fn send_rejects_unlisted_number() {
+        let result = test_tool(&["+15551234567"])
+            .execute(json!({
+                "action": "send",
+                "to": "+15559999999",
+                "body": "hi",
+                "approved": true
+            }))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("not in"));
+    }
+
+    #[tokio::test]
+    async fn send_requires_approval() {
+        let result = test_tool(&["+15551234567"])
+            .execute(json!({ "action": "send", "to": "+1 555 123 4567", "body": "hi" }))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("approval"));
+    }
+
+    #[tokio::test]
+    async fn approved_send_reaches_bridge() {
+        let result = test_tool(&["+15551234567"])
+            .execute(json!({
+                "action": "send",
+                "to": "+1 555 123 4567",
+                "body": "On my way",
+                "approved": true
+            }))
+            .await
+            .unwrap();
+        assert!(result.success, "{:?}", result.error);
+        assert!(result.output.contains("+15551234567"));
+    }
+
+    #[tokio::test]
+    async fn read_defaults_to_allowlisted_numbers() {
+        let result = test_tool(&["+15551234567"])
+            .execute(json!({ "action": "read", "limit": 5 }))
+            .await
+            .unwrap();
+        assert!(result.success);
+        assert!(result.output.contains("read_sms"));
+        assert!(result.output.contains("+15551234567"));
+    }
+}
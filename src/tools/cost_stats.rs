@@ -0,0 +1,129 @@
+use super::traits::{Tool, ToolResult};
+use crate::config::Config;
+use crate::cost::{CostTracker, MAX_USAGE_DAYS};
+use async_trait::async_trait;
+use chrono::{Datelike, Utc};
+use serde_json::json;
+use std::sync::Arc;
+
+const DEFAULT_DAYS: u32 = 7;
+
+/// Report LLM spend from the cost ledger, per day and per provider/model.
+pub struct CostStatsTool {
+    config: Arc<Config>,
+}
+
+impl CostStatsTool {
+    pub fn new(config: Arc<Config>) -> Self {
+        Self { config }
+    }
+
+    fn stats(&self, days: u32) -> anyhow::Result<serde_json::Value> {
+        let tracker = CostTracker::new(self.config.cost.clone(), &self.config.workspace_dir)?;
+        let now = Utc::now();
+        Ok(json!({
+            "month_cost_usd": tracker.get_monthly_cost(now.year(), now.month())?,
+            "daily_limit_usd": self.config.cost.daily_limit_usd,
+            "monthly_limit_usd": self.config.cost.monthly_limit_usd,
+            "days": tracker.daily_usage(days)?,
+        }))
+    }
+}
+
+#[async_trait]
+impl Tool for CostStatsTool {
+    fn name(&self) -> &str {
+        "cost_stats"
+    }
+
+    fn description(&self) -> &str {
+        "Show what LLM usage has cost: per-day spend, tokens and request counts broken down by provider and model, plus month-to-date spend and the configured budget limits."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "days": {
+                    "type": "integer",
+                    "description": "Number of days to report, newest first",
+                    "default": DEFAULT_DAYS,
+                    "minimum": 1,
+                    "maximum": MAX_USAGE_DAYS
+                }
+            }
+        })
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        if !self.config.cost.enabled {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some("cost tracking is disabled by config (cost.enabled=false)".into()),
+            });
+        }
+
+        let days = args
+            .get("days")
+            .and_then(serde_json::Value::as_u64)
+            .map_or(DEFAULT_DAYS, |v| u32::try_from(v).unwrap_or(MAX_USAGE_DAYS));
+
+        match self.stats(days) {
+            Ok(stats) => Ok(ToolResult {
+                success: true,
+                output: serde_json::to_string_pretty(&stats)?,
+                error: None,
+            }),
+            Err(e) => Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(format!("Failed to read cost ledger: {e}")),
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_config(workspace: &std::path::Path, enabled: bool) -> Arc<Config> {
+        let mut config = Config {
+            workspace_dir: workspace.to_path_buf(),
+            ..Config::default()
+        };
+        config.cost.enabled = enabled;
+        Arc::new(config)
+    }
+
+    #[tokio::test]
+    async fn reports_recorded_usage_per_day() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let config = test_config(tmp.path(), true);
+        let tracker = CostTracker::new(config.cost.clone(), tmp.path()).unwrap();
+        tracker
+            .record_usage(tracker.price_usage("anthropic", "claude-sonnet-4-20250514", 1_000, 200))
+            .unwrap();
+
+        let tool = CostStatsTool::new(config);
+        let result = tool.execute(json!({ "days": 2 })).await.unwrap();
+        assert!(result.success, "{:?}", result.error);
+
+        let stats: serde_json::Value = serde_json::from_str(&result.output).unwrap();
+        let days = stats["days"].as_array().unwrap();
+        assert_eq!(days.len(), 2);
+        assert_eq!(days[0]["request_count"], 1);
+        assert_eq!(days[0]["by_model"][0]["provider"], "anthropic");
+        assert!(stats["month_cost_usd"].as_f64().unwrap() > 0.0);
+    }
+
+    #[tokio::test]
+    async fn disabled_cost_tracking_is_reported() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let tool = CostStatsTool::new(test_config(tmp.path(), false));
+        let result = tool.execute(json!({})).await.unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("cost.enabled"));
+    }
+}
@@ -27,6 +27,15 @@ impl CronAddTool {
             });
         }
 
+        let existing = cron::list_jobs(&self.config).map_or(0, |jobs| jobs.len());
+        if let Err(reason) = self.security.check_scheduled_job_capacity(existing) {
+            return Some(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(reason),
+            });
+        }
+
         if self.security.is_rate_limited() {
             return Some(ToolResult {
                 success: false,
@@ -88,8 +97,8 @@ impl Tool for CronAddTool {
                     "description": "Delivery config to send job output to a channel. Example: {\"mode\":\"announce\",\"channel\":\"discord\",\"to\":\"<channel_id>\"}",
                     "properties": {
                         "mode": { "type": "string", "enum": ["none", "announce"], "description": "Set to 'announce' to deliver output to a channel" },
-                        "channel": { "type": "string", "enum": ["telegram", "discord", "slack", "mattermost"], "description": "Channel type to deliver to" },
-                        "to": { "type": "string", "description": "Target: Discord channel ID, Telegram chat ID, Slack channel, etc." },
+                        "channel": { "type": "string", "enum": ["telegram", "discord", "slack", "mattermost", "android", "gateway"], "description": "Channel type to deliver to (android posts a phone notification, gateway pushes to connected /ws clients)" },
+                        "to": { "type": "string", "description": "Target: Discord channel ID, Telegram chat ID, Slack channel, Android notification channel, etc." },
                         "best_effort": { "type": "boolean", "description": "If true, delivery failure does not fail the job" }
                     }
                 },
@@ -261,6 +270,13 @@ impl Tool for CronAddTool {
                     delete_after_run,
                 )
             }
+            JobType::Reminder => {
+                return Ok(ToolResult {
+                    success: false,
+                    output: String::new(),
+                    error: Some("Use set_reminder to create reminders".to_string()),
+                });
+            }
         };
 
         match result {
@@ -329,6 +345,30 @@ mod tests {
         assert!(result.output.contains("next_run"));
     }
 
+    #[tokio::test]
+    async fn enforces_scheduled_job_cap() {
+        let tmp = TempDir::new().unwrap();
+        let cfg = test_config(&tmp).await;
+        let security = Arc::new(SecurityPolicy {
+            max_scheduled_jobs: 1,
+            ..SecurityPolicy::from_config(&cfg.autonomy, &cfg.workspace_dir)
+        });
+        let tool = CronAddTool::new(cfg.clone(), security);
+        let args = json!({
+            "schedule": { "kind": "cron", "expr": "0 9 * * *" },
+            "job_type": "agent",
+            "prompt": "Check the feed"
+        });
+
+        assert!(tool.execute(args.clone()).await.unwrap().success);
+        let result = tool.execute(args).await.unwrap();
+        assert!(!result.success);
+        assert!(result
+            .error
+            .unwrap_or_default()
+            .contains("max_scheduled_jobs"));
+    }
+
     #[tokio::test]
     async fn blocks_disallowed_shell_command() {
         let tmp = TempDir::new().unwrap();
@@ -2,6 +2,7 @@ use super::traits::{Tool, ToolResult};
 use crate::agent::loop_::run_tool_call_loop;
 use crate::config::DelegateAgentConfig;
 use crate::observability::traits::{Observer, ObserverEvent, ObserverMetric};
+use crate::providers::structured::{chat_structured, JsonSchemaFormat};
 use crate::providers::{self, ChatMessage, Provider};
 use crate::security::policy::ToolOperation;
 use crate::security::SecurityPolicy;
@@ -15,6 +16,8 @@ use std::time::Duration;
 const DELEGATE_TIMEOUT_SECS: u64 = 120;
 /// Default timeout for agentic sub-agent runs.
 const DELEGATE_AGENTIC_TIMEOUT_SECS: u64 = 300;
+/// Schema name sent with `output_schema` requests.
+const DELEGATE_SCHEMA_NAME: &str = "delegate_result";
 
 /// Tool that delegates a subtask to a named agent with a different
 /// provider/model configuration. Enables multi-agent workflows where
@@ -33,6 +36,8 @@ pub struct DelegateTool {
     parent_tools: Arc<Vec<Arc<dyn Tool>>>,
     /// Inherited multimodal handling config for sub-agent loops.
     multimodal_config: crate::config::MultimodalConfig,
+    /// Inherited provider retry settings (fallback providers are not applied).
+    reliability: crate::config::ReliabilityConfig,
 }
 
 impl DelegateTool {
@@ -63,6 +68,7 @@ impl DelegateTool {
             depth: 0,
             parent_tools: Arc::new(Vec::new()),
             multimodal_config: crate::config::MultimodalConfig::default(),
+            reliability: crate::config::ReliabilityConfig::default(),
         }
     }
 
@@ -99,6 +105,7 @@ impl DelegateTool {
             depth,
             parent_tools: Arc::new(Vec::new()),
             multimodal_config: crate::config::MultimodalConfig::default(),
+            reliability: crate::config::ReliabilityConfig::default(),
         }
     }
 
@@ -113,6 +120,16 @@ impl DelegateTool {
         self.multimodal_config = config;
         self
     }
+
+    /// Attach retry settings for sub-agent provider calls. The fallback chain
+    /// is dropped so a sub-agent always runs on its configured provider.
+    pub fn with_reliability(mut self, reliability: crate::config::ReliabilityConfig) -> Self {
+        self.reliability = crate::config::ReliabilityConfig {
+            fallback_providers: Vec::new(),
+            ..reliability
+        };
+        self
+    }
 }
 
 #[async_trait]
@@ -124,7 +141,8 @@ impl Tool for DelegateTool {
     fn description(&self) -> &str {
         "Delegate a subtask to a specialized agent. Use when: a task benefits from a different model \
          (e.g. fast summarization, deep reasoning, code generation). The sub-agent runs a single \
-         prompt by default; with agentic=true it can iterate with a filtered tool-call loop."
+         prompt by default; with agentic=true it can iterate with a filtered tool-call loop. \
+         Pass output_schema to get back only a JSON value matching that schema."
     }
 
     fn parameters_schema(&self) -> serde_json::Value {
@@ -153,6 +171,10 @@ impl Tool for DelegateTool {
                 "context": {
                     "type": "string",
                     "description": "Optional context to prepend (e.g. relevant code, prior findings)"
+                },
+                "output_schema": {
+                    "type": "object",
+                    "description": "Optional JSON schema the answer must match; the result is then the bare JSON value (not supported for agentic agents)"
                 }
             },
             "required": ["agent", "prompt"]
@@ -194,6 +216,20 @@ impl Tool for DelegateTool {
             .map(str::trim)
             .unwrap_or("");
 
+        let output_format = match args.get("output_schema") {
+            None | Some(serde_json::Value::Null) => None,
+            Some(schema @ serde_json::Value::Object(_)) => {
+                Some(JsonSchemaFormat::new(DELEGATE_SCHEMA_NAME, schema.clone()))
+            }
+            Some(_) => {
+                return Ok(ToolResult {
+                    success: false,
+                    output: String::new(),
+                    error: Some("'output_schema' must be a JSON schema object".into()),
+                });
+            }
+        };
+
         // Look up agent config
         let agent_config = match self.agents.get(agent_name) {
             Some(cfg) => cfg,
@@ -248,9 +284,11 @@ impl Tool for DelegateTool {
         #[allow(clippy::option_as_ref_deref)]
         let provider_credential = provider_credential_owned.as_ref().map(String::as_str);
 
-        let provider: Box<dyn Provider> = match providers::create_provider_with_options(
+        let provider: Box<dyn Provider> = match providers::create_resilient_provider_with_options(
             &agent_config.provider,
             provider_credential,
+            None,
+            &self.reliability,
             &self.provider_runtime_options,
         ) {
             Ok(p) => p,
@@ -277,6 +315,15 @@ impl Tool for DelegateTool {
 
         // Agentic mode: run full tool-call loop with allowlisted tools.
         if agent_config.agentic {
+            if output_format.is_some() {
+                return Ok(ToolResult {
+                    success: false,
+                    output: String::new(),
+                    error: Some(format!(
+                        "Agent '{agent_name}' is agentic; 'output_schema' is only supported for single-prompt agents"
+                    )),
+                });
+            }
             return self
                 .execute_agentic(
                     agent_name,
@@ -288,17 +335,35 @@ impl Tool for DelegateTool {
                 .await;
         }
 
-        // Wrap the provider call in a timeout to prevent indefinite blocking
-        let result = tokio::time::timeout(
-            Duration::from_secs(DELEGATE_TIMEOUT_SECS),
-            provider.chat_with_system(
-                agent_config.system_prompt.as_deref(),
-                &full_prompt,
+        let call = async {
+            let Some(format) = &output_format else {
+                return provider
+                    .chat_with_system(
+                        agent_config.system_prompt.as_deref(),
+                        &full_prompt,
+                        &agent_config.model,
+                        temperature,
+                    )
+                    .await;
+            };
+            let mut messages = Vec::new();
+            if let Some(system_prompt) = agent_config.system_prompt.as_deref() {
+                messages.push(ChatMessage::system(system_prompt));
+            }
+            messages.push(ChatMessage::user(full_prompt.as_str()));
+            chat_structured(
+                &*provider,
+                &messages,
+                format,
                 &agent_config.model,
                 temperature,
-            ),
-        )
-        .await;
+            )
+            .await
+            .map(|value| value.to_string())
+        };
+
+        // Wrap the provider call in a timeout to prevent indefinite blocking
+        let result = tokio::time::timeout(Duration::from_secs(DELEGATE_TIMEOUT_SECS), call).await;
 
         let result = match result {
             Ok(inner) => inner,
@@ -314,6 +379,12 @@ impl Tool for DelegateTool {
         };
 
         match result {
+            // Schema-constrained answers are returned bare so callers can parse them.
+            Ok(response) if output_format.is_some() => Ok(ToolResult {
+                success: true,
+                output: response,
+                error: None,
+            }),
             Ok(response) => {
                 let mut rendered = response;
                 if rendered.trim().is_empty() {
@@ -407,6 +478,7 @@ impl DelegateTool {
                 "delegate",
                 &self.multimodal_config,
                 agent_config.max_iterations,
+                crate::agent::context_window::model_context_window(&agent_config.model),
                 None,
                 None,
                 None,
@@ -697,6 +769,7 @@ mod tests {
         assert!(schema["properties"]["agent"].is_object());
         assert!(schema["properties"]["prompt"].is_object());
         assert!(schema["properties"]["context"].is_object());
+        assert!(schema["properties"]["output_schema"].is_object());
         let required = schema["required"].as_array().unwrap();
         assert!(required.contains(&json!("agent")));
         assert!(required.contains(&json!("prompt")));
@@ -1014,6 +1087,38 @@ mod tests {
             .contains("no executable tools"));
     }
 
+    #[tokio::test]
+    async fn output_schema_must_be_an_object() {
+        let tool = DelegateTool::new(sample_agents(), None, test_security());
+        let result = tool
+            .execute(json!({
+                "agent": "researcher",
+                "prompt": "classify this",
+                "output_schema": "a string"
+            }))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("'output_schema'"));
+    }
+
+    #[tokio::test]
+    async fn output_schema_is_rejected_for_agentic_agents() {
+        let mut agents = HashMap::new();
+        agents.insert("agentic".to_string(), agentic_config(Vec::new(), 4));
+        let tool = DelegateTool::new(agents, None, test_security());
+        let result = tool
+            .execute(json!({
+                "agent": "agentic",
+                "prompt": "classify this",
+                "output_schema": {"type": "object"}
+            }))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("single-prompt agents"));
+    }
+
     #[tokio::test]
     async fn execute_agentic_runs_tool_call_loop_with_filtered_tools() {
         let config = agentic_config(vec!["echo_tool".to_string()], 10);
@@ -0,0 +1,337 @@
+use super::traits::{Tool, ToolResult};
+use crate::config::DiscordConfig;
+use crate::security::SecurityPolicy;
+use async_trait::async_trait;
+use reqwest::Client;
+use serde_json::json;
+use std::sync::Arc;
+use std::time::Duration;
+
+const DISCORD_API_TIMEOUT_SECS: u64 = 15;
+const DISCORD_MAX_CONTENT_CHARS: usize = 2000;
+const DISCORD_MAX_EMBEDS: usize = 10;
+
+/// Where proactive Discord notifications are delivered.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DiscordNotifyTarget {
+    /// Incoming webhook URL; no bot membership required.
+    Webhook(String),
+    /// Channel message posted with the bot token.
+    Channel {
+        bot_token: String,
+        channel_id: String,
+    },
+}
+
+impl DiscordNotifyTarget {
+    /// Resolve the notify target from config, preferring `notify_webhook_url`
+    /// over `notify_chat_id`.
+    pub fn from_config(config: &DiscordConfig) -> Option<Self> {
+        let non_empty = |v: &Option<String>| {
+            v.as_deref()
+                .map(str::trim)
+                .filter(|v| !v.is_empty())
+                .map(str::to_string)
+        };
+
+        if let Some(url) = non_empty(&config.notify_webhook_url) {
+            return Some(Self::Webhook(url));
+        }
+        let channel_id = non_empty(&config.notify_chat_id)?;
+        if config.bot_token.trim().is_empty() {
+            return None;
+        }
+        Some(Self::Channel {
+            bot_token: config.bot_token.clone(),
+            channel_id,
+        })
+    }
+}
+
+pub struct DiscordNotifyTool {
+    client: Client,
+    security: Arc<SecurityPolicy>,
+    target: DiscordNotifyTarget,
+}
+
+impl DiscordNotifyTool {
+    pub fn new(security: Arc<SecurityPolicy>, target: DiscordNotifyTarget) -> Self {
+        let client = Client::builder()
+            .timeout(Duration::from_secs(DISCORD_API_TIMEOUT_SECS))
+            .build()
+            .unwrap_or_else(|_| Client::new());
+
+        Self {
+            client,
+            security,
+            target,
+        }
+    }
+
+    /// Build the message payload. Mentions are never parsed so a notification
+    /// cannot ping `@everyone` or arbitrary users.
+    fn build_payload(args: &serde_json::Value) -> Result<serde_json::Value, String> {
+        let message = args
+            .get("message")
+            .and_then(|v| v.as_str())
+            .map(str::trim)
+            .filter(|v| !v.is_empty());
+
+        let embeds = match args.get("embeds") {
+            None | Some(serde_json::Value::Null) => Vec::new(),
+            Some(serde_json::Value::Array(items)) => {
+                if items.len() > DISCORD_MAX_EMBEDS {
+                    return Err(format!("Too many embeds (max {DISCORD_MAX_EMBEDS})"));
+                }
+                if items.iter().any(|item| !item.is_object()) {
+                    return Err("Each embed must be an object".into());
+                }
+                items.clone()
+            }
+            Some(_) => return Err("'embeds' must be an array of objects".into()),
+        };
+
+        if message.is_none() && embeds.is_empty() {
+            return Err("Missing 'message' parameter (or at least one embed)".into());
+        }
+        if let Some(message) = message {
+            if message.chars().count() > DISCORD_MAX_CONTENT_CHARS {
+                return Err(format!(
+                    "Message exceeds Discord's {DISCORD_MAX_CONTENT_CHARS}-character limit"
+                ));
+            }
+        }
+
+        let mut payload = json!({ "allowed_mentions": { "parse": [] } });
+        if let Some(message) = message {
+            payload["content"] = json!(message);
+        }
+        if !embeds.is_empty() {
+            payload["embeds"] = json!(embeds);
+        }
+        Ok(payload)
+    }
+
+    fn destination(&self) -> &'static str {
+        match self.target {
+            DiscordNotifyTarget::Webhook(_) => "webhook",
+            DiscordNotifyTarget::Channel { .. } => "channel",
+        }
+    }
+}
+
+#[async_trait]
+impl Tool for DiscordNotifyTool {
+    fn name(&self) -> &str {
+        "discord_notify"
+    }
+
+    fn description(&self) -> &str {
+        "Send a Discord message to the configured notify channel or webhook. Use this to proactively notify the user via Discord; optional embeds render rich cards."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "message": {
+                    "type": "string",
+                    "description": "The message text to send (max 2000 characters)"
+                },
+                "embeds": {
+                    "type": "array",
+                    "description": "Optional Discord embeds (max 10)",
+                    "items": {
+                        "type": "object",
+                        "properties": {
+                            "title": { "type": "string" },
+                            "description": { "type": "string" },
+                            "url": { "type": "string" },
+                            "color": {
+                                "type": "integer",
+                                "description": "RGB color as an integer, e.g. 5814783"
+                            },
+                            "fields": {
+                                "type": "array",
+                                "items": {
+                                    "type": "object",
+                                    "properties": {
+                                        "name": { "type": "string" },
+                                        "value": { "type": "string" },
+                                        "inline": { "type": "boolean" }
+                                    },
+                                    "required": ["name", "value"]
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        })
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        if !self.security.can_act() {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some("Action blocked: autonomy is read-only".into()),
+            });
+        }
+
+        if !self.security.record_action() {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some("Action blocked: rate limit exceeded".into()),
+            });
+        }
+
+        let payload = match Self::build_payload(&args) {
+            Ok(payload) => payload,
+            Err(error) => {
+                return Ok(ToolResult {
+                    success: false,
+                    output: String::new(),
+                    error: Some(error),
+                })
+            }
+        };
+
+        let request = match &self.target {
+            DiscordNotifyTarget::Webhook(url) => self.client.post(url).query(&[("wait", "true")]),
+            DiscordNotifyTarget::Channel {
+                bot_token,
+                channel_id,
+            } => self
+                .client
+                .post(format!(
+                    "https://discord.com/api/v10/channels/{channel_id}/messages"
+                ))
+                .header("Authorization", format!("Bot {bot_token}")),
+        };
+
+        let response = request.json(&payload).send().await?;
+        let status = response.status();
+        let response_text = response.text().await.unwrap_or_default();
+
+        if !status.is_success() {
+            return Ok(ToolResult {
+                success: false,
+                output: response_text,
+                error: Some(format!("Discord API returned status {status}")),
+            });
+        }
+
+        let message_id = serde_json::from_str::<serde_json::Value>(&response_text)
+            .ok()
+            .and_then(|json| json.get("id").and_then(|v| v.as_str()).map(String::from));
+
+        Ok(ToolResult {
+            success: true,
+            output: match message_id {
+                Some(id) => format!("Discord message {id} sent to {}.", self.destination()),
+                None => format!("Discord message sent to {}.", self.destination()),
+            },
+            error: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::security::AutonomyLevel;
+
+    fn test_security(level: AutonomyLevel, max_actions_per_hour: u32) -> Arc<SecurityPolicy> {
+        Arc::new(SecurityPolicy {
+            autonomy: level,
+            max_actions_per_hour,
+            workspace_dir: std::env::temp_dir(),
+            ..SecurityPolicy::default()
+        })
+    }
+
+    fn test_tool(level: AutonomyLevel, max_actions_per_hour: u32) -> DiscordNotifyTool {
+        DiscordNotifyTool::new(
+            test_security(level, max_actions_per_hour),
+            DiscordNotifyTarget::Channel {
+                bot_token: "token".into(),
+                channel_id: "123456789".into(),
+            },
+        )
+    }
+
+    fn discord_config(chat_id: Option<&str>, webhook: Option<&str>) -> DiscordConfig {
+        DiscordConfig {
+            bot_token: "token".into(),
+            guild_id: None,
+            allowed_users: vec![],
+            listen_to_bots: false,
+            mention_only: false,
+            notify_chat_id: chat_id.map(String::from),
+            notify_webhook_url: webhook.map(String::from),
+        }
+    }
+
+    #[test]
+    fn tool_name() {
+        assert_eq!(test_tool(AutonomyLevel::Full, 100).name(), "discord_notify");
+    }
+
+    #[test]
+    fn target_prefers_webhook_over_channel() {
+        let target = DiscordNotifyTarget::from_config(&discord_config(
+            Some("123"),
+            Some("https://discord.com/api/webhooks/1/abc"),
+        ));
+        assert_eq!(
+            target,
+            Some(DiscordNotifyTarget::Webhook(
+                "https://discord.com/api/webhooks/1/abc".into()
+            ))
+        );
+
+        let target = DiscordNotifyTarget::from_config(&discord_config(Some("123"), Some(" ")));
+        assert!(matches!(target, Some(DiscordNotifyTarget::Channel { .. })));
+
+        assert!(DiscordNotifyTarget::from_config(&discord_config(None, None)).is_none());
+    }
+
+    #[test]
+    fn payload_disables_mentions_and_keeps_embeds() {
+        let payload = DiscordNotifyTool::build_payload(&json!({
+            "message": "@everyone build done",
+            "embeds": [{"title": "CI", "color": 5814783}]
+        }))
+        .unwrap();
+        assert_eq!(payload["content"], "@everyone build done");
+        assert_eq!(payload["allowed_mentions"]["parse"], json!([]));
+        assert_eq!(payload["embeds"][0]["title"], "CI");
+    }
+
+    #[test]
+    fn payload_requires_message_or_embed() {
+        assert!(DiscordNotifyTool::build_payload(&json!({"message": "  "})).is_err());
+        assert!(DiscordNotifyTool::build_payload(&json!({"embeds": [{"title": "only"}]})).is_ok());
+        assert!(DiscordNotifyTool::build_payload(&json!({"embeds": ["text"]})).is_err());
+        let too_long = "x".repeat(DISCORD_MAX_CONTENT_CHARS + 1);
+        assert!(DiscordNotifyTool::build_payload(&json!({ "message": too_long })).is_err());
+    }
+
+    #[tokio::test]
+    async fn execute_blocks_readonly_mode() {
+        let tool = test_tool(AutonomyLevel::ReadOnly, 100);
+        let result = tool.execute(json!({"message": "hello"})).await.unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("read-only"));
+    }
+
+    #[tokio::test]
+    async fn execute_blocks_rate_limit() {
+        let tool = test_tool(AutonomyLevel::Full, 0);
+        let result = tool.execute(json!({"message": "hello"})).await.unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("rate limit"));
+    }
+}
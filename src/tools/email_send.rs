@@ -0,0 +1,243 @@
+use super::traits::{Tool, ToolResult};
+use crate::channels::email_channel::{EmailChannel, EmailConfig};
+use crate::channels::traits::{Channel, SendMessage};
+use crate::security::policy::ToolOperation;
+use crate::security::SecurityPolicy;
+use async_trait::async_trait;
+use serde_json::json;
+use std::fmt::Write;
+use std::sync::Arc;
+
+const MAX_RECIPIENTS: usize = 10;
+const MAX_SUBJECT_CHARS: usize = 200;
+const MAX_BODY_CHARS: usize = 100_000;
+
+/// Send plain-text email over the `[channels_config.email]` SMTP account,
+/// restricted to `allowed_recipients`.
+pub struct EmailSendTool {
+    security: Arc<SecurityPolicy>,
+    channel: EmailChannel,
+}
+
+impl EmailSendTool {
+    pub fn new(security: Arc<SecurityPolicy>, config: EmailConfig) -> Self {
+        Self {
+            security,
+            channel: EmailChannel::new(config),
+        }
+    }
+
+    fn parse_recipients(args: &serde_json::Value) -> anyhow::Result<Vec<String>> {
+        let recipients: Vec<String> = match args.get("to") {
+            Some(serde_json::Value::String(to)) => to
+                .split(',')
+                .map(|addr| addr.trim().to_string())
+                .filter(|addr| !addr.is_empty())
+                .collect(),
+            Some(serde_json::Value::Array(items)) => items
+                .iter()
+                .filter_map(serde_json::Value::as_str)
+                .map(|addr| addr.trim().to_string())
+                .filter(|addr| !addr.is_empty())
+                .collect(),
+            _ => anyhow::bail!("Missing 'to' parameter"),
+        };
+        if recipients.is_empty() {
+            anyhow::bail!("'to' must contain at least one address");
+        }
+        if recipients.len() > MAX_RECIPIENTS {
+            anyhow::bail!("Too many recipients (max {MAX_RECIPIENTS})");
+        }
+        Ok(recipients)
+    }
+}
+
+#[async_trait]
+impl Tool for EmailSendTool {
+    fn name(&self) -> &str {
+        "email_send"
+    }
+
+    fn description(&self) -> &str {
+        "Send a plain-text email (reports, reminders, notifications) from the configured account. Only addresses in the email allowlist can receive mail."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "to": {
+                    "description": "Recipient address, comma-separated addresses, or an array of addresses",
+                    "oneOf": [
+                        { "type": "string" },
+                        { "type": "array", "items": { "type": "string" } }
+                    ]
+                },
+                "subject": {
+                    "type": "string",
+                    "description": "Subject line"
+                },
+                "body": {
+                    "type": "string",
+                    "description": "Plain-text message body"
+                }
+            },
+            "required": ["to", "subject", "body"]
+        })
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let recipients = Self::parse_recipients(&args)?;
+
+        let subject = args
+            .get("subject")
+            .and_then(|v| v.as_str())
+            .map(str::trim)
+            .ok_or_else(|| anyhow::anyhow!("Missing 'subject' parameter"))?;
+        if subject.chars().count() > MAX_SUBJECT_CHARS || subject.contains(['\r', '\n']) {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(format!(
+                    "Subject must be a single line of at most {MAX_SUBJECT_CHARS} characters"
+                )),
+            });
+        }
+
+        let body = args
+            .get("body")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| anyhow::anyhow!("Missing 'body' parameter"))?;
+        if body.chars().count() > MAX_BODY_CHARS {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(format!("Body exceeds {MAX_BODY_CHARS} characters")),
+            });
+        }
+
+        if let Some(blocked) = recipients
+            .iter()
+            .find(|addr| !self.channel.is_recipient_allowed(addr))
+        {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(format!(
+                    "Recipient '{blocked}' is not in channels_config.email.allowed_recipients"
+                )),
+            });
+        }
+
+        if let Err(error) = self
+            .security
+            .enforce_tool_operation(ToolOperation::Act, "email_send")
+        {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(error),
+            });
+        }
+
+        let mut sent = Vec::with_capacity(recipients.len());
+        for recipient in &recipients {
+            let message = SendMessage::with_subject(body, recipient, subject);
+            if let Err(e) = self.channel.send(&message).await {
+                let mut error = format!("Failed to send email to {recipient}: {e}");
+                if !sent.is_empty() {
+                    let _ = write!(error, " (already sent to: {})", sent.join(", "));
+                }
+                return Ok(ToolResult {
+                    success: false,
+                    output: String::new(),
+                    error: Some(error),
+                });
+            }
+            sent.push(recipient.as_str());
+        }
+
+        Ok(ToolResult {
+            success: true,
+            output: format!("Email sent to {}", sent.join(", ")),
+            error: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::security::AutonomyLevel;
+
+    fn test_config() -> EmailConfig {
+        EmailConfig {
+            smtp_host: "smtp.invalid".into(),
+            from_address: "agent@example.com".into(),
+            allowed_recipients: vec!["me@example.com".into(), "@family.example".into()],
+            ..EmailConfig::default()
+        }
+    }
+
+    #[test]
+    fn parses_string_and_array_recipients() {
+        let parsed =
+            EmailSendTool::parse_recipients(&json!({"to": "a@example.com, b@example.com"}))
+                .unwrap();
+        assert_eq!(parsed, vec!["a@example.com", "b@example.com"]);
+
+        let parsed = EmailSendTool::parse_recipients(&json!({"to": ["c@example.com"]})).unwrap();
+        assert_eq!(parsed, vec!["c@example.com"]);
+
+        assert!(EmailSendTool::parse_recipients(&json!({"to": " "})).is_err());
+    }
+
+    #[tokio::test]
+    async fn rejects_recipient_outside_allowlist() {
+        let tool = EmailSendTool::new(Arc::new(SecurityPolicy::default()), test_config());
+        let result = tool
+            .execute(json!({
+                "to": ["me@example.com", "stranger@other.example"],
+                "subject": "Report",
+                "body": "Done."
+            }))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("stranger@other.example"));
+    }
+
+    #[tokio::test]
+    async fn rejects_multiline_subject() {
+        let tool = EmailSendTool::new(Arc::new(SecurityPolicy::default()), test_config());
+        let result = tool
+            .execute(json!({
+                "to": "me@example.com",
+                "subject": "Report\r\nBcc: someone@else.example",
+                "body": "Done."
+            }))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("single line"));
+    }
+
+    #[tokio::test]
+    async fn read_only_autonomy_blocks_send() {
+        let security = Arc::new(SecurityPolicy {
+            autonomy: AutonomyLevel::ReadOnly,
+            ..SecurityPolicy::default()
+        });
+        let tool = EmailSendTool::new(security, test_config());
+        let result = tool
+            .execute(json!({
+                "to": "kid@family.example",
+                "subject": "Dinner",
+                "body": "At 7."
+            }))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("read-only"));
+    }
+}
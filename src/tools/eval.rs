@@ -0,0 +1,280 @@
+use super::traits::{Tool, ToolResult};
+use crate::config::EvalConfig;
+use async_trait::async_trait;
+use chrono::{Datelike, Duration, NaiveDate};
+use parking_lot::Mutex;
+use rhai::{Dynamic, Engine, EvalAltResult, Scope};
+use serde_json::json;
+use std::sync::Arc;
+use std::time::Instant;
+
+const MAX_CODE_CHARS: usize = 20_000;
+const MAX_PRINT_LINES: usize = 200;
+const MAX_CALL_LEVELS: usize = 64;
+const MAX_EXPR_DEPTH: usize = 64;
+
+/// Evaluate Rhai scripts in a resource-limited sandbox for calculations,
+/// date math, and data munging.
+pub struct EvalTool {
+    config: EvalConfig,
+}
+
+/// Outcome of one script run.
+#[derive(Debug)]
+struct EvalOutput {
+    result: serde_json::Value,
+    printed: Vec<String>,
+}
+
+fn parse_date(value: &str) -> Result<NaiveDate, Box<EvalAltResult>> {
+    NaiveDate::parse_from_str(value.trim(), "%Y-%m-%d")
+        .map_err(|_| format!("expected a YYYY-MM-DD date, got '{value}'").into())
+}
+
+impl EvalTool {
+    pub fn new(config: EvalConfig) -> Self {
+        Self { config }
+    }
+
+    /// Build an engine with limits applied and every escape hatch removed.
+    fn engine(config: &EvalConfig, printed: &Arc<Mutex<Vec<String>>>) -> Engine {
+        let mut engine = Engine::new();
+        engine.set_module_resolver(rhai::module_resolvers::DummyModuleResolver::new());
+        engine.disable_symbol("eval");
+
+        engine.set_max_operations(config.max_operations.max(1));
+        engine.set_max_string_size(config.max_string_bytes.max(1));
+        engine.set_max_array_size(config.max_collection_len.max(1));
+        engine.set_max_map_size(config.max_collection_len.max(1));
+        engine.set_max_call_levels(MAX_CALL_LEVELS);
+        engine.set_max_expr_depths(MAX_EXPR_DEPTH, MAX_EXPR_DEPTH);
+
+        let started = Instant::now();
+        let timeout = std::time::Duration::from_millis(config.timeout_ms.max(1));
+        engine.on_progress(move |_| (started.elapsed() > timeout).then_some(Dynamic::UNIT));
+
+        let sink = printed.clone();
+        engine.on_print(move |line| {
+            let mut lines = sink.lock();
+            if lines.len() < MAX_PRINT_LINES {
+                lines.push(line.to_string());
+            }
+        });
+        let sink = printed.clone();
+        engine.on_debug(move |line, _, _| {
+            let mut lines = sink.lock();
+            if lines.len() < MAX_PRINT_LINES {
+                lines.push(line.to_string());
+            }
+        });
+
+        // Date helpers; dates are YYYY-MM-DD strings.
+        engine.register_fn("today", || {
+            chrono::Local::now()
+                .date_naive()
+                .format("%Y-%m-%d")
+                .to_string()
+        });
+        engine.register_fn("now", || chrono::Utc::now().to_rfc3339());
+        engine.register_fn(
+            "add_days",
+            |date: &str, days: i64| -> Result<String, Box<EvalAltResult>> {
+                let date = parse_date(date)?;
+                date.checked_add_signed(Duration::days(days))
+                    .map(|d| d.format("%Y-%m-%d").to_string())
+                    .ok_or_else(|| "date out of range".into())
+            },
+        );
+        engine.register_fn(
+            "days_between",
+            |from: &str, to: &str| -> Result<i64, Box<EvalAltResult>> {
+                Ok((parse_date(to)? - parse_date(from)?).num_days())
+            },
+        );
+        engine.register_fn(
+            "weekday",
+            |date: &str| -> Result<String, Box<EvalAltResult>> {
+                Ok(parse_date(date)?.weekday().to_string())
+            },
+        );
+
+        engine
+    }
+
+    fn run(
+        config: &EvalConfig,
+        code: &str,
+        input: Option<serde_json::Value>,
+    ) -> anyhow::Result<EvalOutput> {
+        let printed = Arc::new(Mutex::new(Vec::new()));
+        let engine = Self::engine(config, &printed);
+
+        let mut scope = Scope::new();
+        if let Some(input) = input {
+            let input = rhai::serde::to_dynamic(input)
+                .map_err(|e| anyhow::anyhow!("'input' cannot be converted: {e}"))?;
+            scope.push_constant_dynamic("input", input);
+        }
+
+        let value = engine
+            .eval_with_scope::<Dynamic>(&mut scope, code)
+            .map_err(|error| match *error {
+                EvalAltResult::ErrorTerminated(..) => {
+                    anyhow::anyhow!("Script exceeded the {} ms time limit", config.timeout_ms)
+                }
+                EvalAltResult::ErrorTooManyOperations(_) => anyhow::anyhow!(
+                    "Script exceeded the {} operation limit",
+                    config.max_operations
+                ),
+                other => anyhow::anyhow!("{other}"),
+            })?;
+
+        let result = if value.is_unit() {
+            serde_json::Value::Null
+        } else {
+            rhai::serde::from_dynamic::<serde_json::Value>(&value)
+                .unwrap_or_else(|_| serde_json::Value::String(value.to_string()))
+        };
+        let printed = std::mem::take(&mut *printed.lock());
+        Ok(EvalOutput { result, printed })
+    }
+}
+
+#[async_trait]
+impl Tool for EvalTool {
+    fn name(&self) -> &str {
+        "eval"
+    }
+
+    fn description(&self) -> &str {
+        "Evaluate a Rhai script in a sandbox and return the value of its last expression. Use it for arithmetic, unit conversions, date math, and reshaping data instead of computing in your head. Rhai syntax is Rust-like: `let x = 2.0 * 3.5; x / 7`. Integers are i64 (write 1.0 for floats). Helpers: today(), now(), add_days(\"2025-03-14\", 30), days_between(a, b), weekday(date). Pass JSON via `input` and read it as the `input` variable. No files, network, or imports."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "code": {
+                    "type": "string",
+                    "description": "Rhai script; the last expression is the result"
+                },
+                "input": {
+                    "description": "Optional JSON value exposed to the script as the constant `input`"
+                }
+            },
+            "required": ["code"]
+        })
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let code = args
+            .get("code")
+            .and_then(serde_json::Value::as_str)
+            .map(str::trim)
+            .filter(|v| !v.is_empty());
+        let Some(code) = code else {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some("Missing 'code' parameter".into()),
+            });
+        };
+        if code.chars().count() > MAX_CODE_CHARS {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(format!("'code' exceeds {MAX_CODE_CHARS} characters")),
+            });
+        }
+
+        let config = self.config.clone();
+        let code = code.to_string();
+        let input = args.get("input").cloned();
+        let outcome = tokio::task::spawn_blocking(move || Self::run(&config, &code, input))
+            .await
+            .map_err(|e| anyhow::anyhow!("eval task failed: {e}"))?;
+
+        match outcome {
+            Ok(EvalOutput { result, printed }) => {
+                let mut report = json!({ "result": result });
+                if !printed.is_empty() {
+                    report["printed"] = json!(printed);
+                }
+                Ok(ToolResult {
+                    success: true,
+                    output: serde_json::to_string_pretty(&report)?,
+                    error: None,
+                })
+            }
+            Err(error) => Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(error.to_string()),
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(code: &str) -> anyhow::Result<EvalOutput> {
+        EvalTool::run(&EvalConfig::default(), code, None)
+    }
+
+    #[test]
+    fn evaluates_arithmetic_and_dates() {
+        assert_eq!(run("let x = 17; x * 3 + 1").unwrap().result, json!(52));
+        assert_eq!(run("1.5 * 4.0").unwrap().result, json!(6.0));
+        assert_eq!(
+            run(r#"add_days("2024-02-27", 3)"#).unwrap().result,
+            json!("2024-03-01")
+        );
+        assert_eq!(
+            run(r#"days_between("2025-01-01", "2025-12-25")"#)
+                .unwrap()
+                .result,
+            json!(358)
+        );
+        assert_eq!(
+            run(r#"weekday("2025-03-14")"#).unwrap().result,
+            json!("Fri")
+        );
+        assert!(run(r#"add_days("14/03/2025", 1)"#).is_err());
+    }
+
+    #[test]
+    fn input_is_bound_and_output_converts_to_json() {
+        let output = EvalTool::run(
+            &EvalConfig::default(),
+            r#"let total = 0; for item in input.items { total += item.qty * item.price; } print(`n=${input.items.len()}`); #{ total: total }"#,
+            Some(json!({ "items": [{ "qty": 2, "price": 3 }, { "qty": 1, "price": 10 }] })),
+        )
+        .unwrap();
+        assert_eq!(output.result, json!({ "total": 16 }));
+        assert_eq!(output.printed, vec!["n=2".to_string()]);
+    }
+
+    #[test]
+    fn limits_and_sandbox_are_enforced() {
+        let config = EvalConfig {
+            max_operations: 10_000,
+            ..EvalConfig::default()
+        };
+        let err = EvalTool::run(&config, "loop {}", None).unwrap_err();
+        assert!(err.to_string().contains("operation limit"));
+
+        let config = EvalConfig {
+            timeout_ms: 50,
+            max_operations: u64::MAX,
+            ..EvalConfig::default()
+        };
+        let err = EvalTool::run(&config, "loop {}", None).unwrap_err();
+        assert!(err.to_string().contains("time limit"));
+
+        assert!(run(r#"import "fs" as fs; 1"#).is_err());
+        assert!(run(r#"eval("1 + 1")"#).is_err());
+        assert!(run(r#"let s = "x"; loop { s += s; }"#).is_err());
+    }
+}
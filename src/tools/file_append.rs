@@ -0,0 +1,268 @@
+use super::traits::{Tool, ToolResult};
+use crate::security::SecurityPolicy;
+use async_trait::async_trait;
+use serde_json::json;
+use std::sync::Arc;
+use tokio::io::AsyncWriteExt;
+
+/// Append to a file with path sandboxing, creating it if missing
+pub struct FileAppendTool {
+    security: Arc<SecurityPolicy>,
+}
+
+impl FileAppendTool {
+    pub fn new(security: Arc<SecurityPolicy>) -> Self {
+        Self { security }
+    }
+}
+
+#[async_trait]
+impl Tool for FileAppendTool {
+    fn name(&self) -> &str {
+        "file_append"
+    }
+
+    fn description(&self) -> &str {
+        "Append text to the end of a file in the workspace, creating it if it does not exist. Use for running notes, logs, and todo lists instead of rewriting the whole file."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "path": {
+                    "type": "string",
+                    "description": "Path to the file. Relative paths resolve from workspace; outside paths require policy allowlist."
+                },
+                "content": {
+                    "type": "string",
+                    "description": "Text to append"
+                },
+                "newline": {
+                    "type": "boolean",
+                    "description": "Start on a new line if the file does not already end with one",
+                    "default": true
+                }
+            },
+            "required": ["path", "content"]
+        })
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let path = args
+            .get("path")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| anyhow::anyhow!("Missing 'path' parameter"))?;
+
+        let content = args
+            .get("content")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| anyhow::anyhow!("Missing 'content' parameter"))?;
+
+        let ensure_newline = args
+            .get("newline")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(true);
+
+        if !self.security.can_act() {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some("Action blocked: autonomy is read-only".into()),
+            });
+        }
+
+        if self.security.is_rate_limited() {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some("Rate limit exceeded: too many actions in the last hour".into()),
+            });
+        }
+
+        // Security check: validate path is within workspace
+        if !self.security.is_path_allowed(path) {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(format!("Path not allowed by security policy: {path}")),
+            });
+        }
+
+        let full_path = self.security.workspace_dir.join(path);
+
+        let Some(parent) = full_path.parent() else {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some("Invalid path: missing parent directory".into()),
+            });
+        };
+
+        tokio::fs::create_dir_all(parent).await?;
+
+        // Resolve parent AFTER creation to block symlink escapes.
+        let resolved_parent = match tokio::fs::canonicalize(parent).await {
+            Ok(p) => p,
+            Err(e) => {
+                return Ok(ToolResult {
+                    success: false,
+                    output: String::new(),
+                    error: Some(format!("Failed to resolve file path: {e}")),
+                });
+            }
+        };
+
+        if !self.security.is_resolved_path_allowed(&resolved_parent) {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(
+                    self.security
+                        .resolved_path_violation_message(&resolved_parent),
+                ),
+            });
+        }
+
+        let Some(file_name) = full_path.file_name() else {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some("Invalid path: missing file name".into()),
+            });
+        };
+
+        let resolved_target = resolved_parent.join(file_name);
+
+        let needs_separator = match tokio::fs::symlink_metadata(&resolved_target).await {
+            Ok(meta) if meta.file_type().is_symlink() => {
+                return Ok(ToolResult {
+                    success: false,
+                    output: String::new(),
+                    error: Some(format!(
+                        "Refusing to write through symlink: {}",
+                        resolved_target.display()
+                    )),
+                });
+            }
+            Ok(meta) if meta.len() > 0 && ensure_newline => {
+                let existing = tokio::fs::read(&resolved_target).await.unwrap_or_default();
+                existing.last().is_some_and(|byte| *byte != b'\n')
+            }
+            _ => false,
+        };
+
+        if !self.security.record_action() {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some("Rate limit exceeded: action budget exhausted".into()),
+            });
+        }
+
+        let mut file = match tokio::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&resolved_target)
+            .await
+        {
+            Ok(f) => f,
+            Err(e) => {
+                return Ok(ToolResult {
+                    success: false,
+                    output: String::new(),
+                    error: Some(format!("Failed to open file: {e}")),
+                });
+            }
+        };
+
+        let mut payload = String::with_capacity(content.len() + 1);
+        if needs_separator {
+            payload.push('\n');
+        }
+        payload.push_str(content);
+
+        match file.write_all(payload.as_bytes()).await {
+            Ok(()) => Ok(ToolResult {
+                success: true,
+                output: format!("Appended {} bytes to {path}", payload.len()),
+                error: None,
+            }),
+            Err(e) => Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(format!("Failed to append to file: {e}")),
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::security::AutonomyLevel;
+
+    fn test_security(workspace: std::path::PathBuf) -> Arc<SecurityPolicy> {
+        Arc::new(SecurityPolicy {
+            autonomy: AutonomyLevel::Supervised,
+            workspace_dir: workspace,
+            ..SecurityPolicy::default()
+        })
+    }
+
+    #[tokio::test]
+    async fn file_append_creates_then_appends_on_new_line() {
+        let dir = std::env::temp_dir().join("zeroclaw_test_file_append");
+        let _ = tokio::fs::remove_dir_all(&dir).await;
+        tokio::fs::create_dir_all(&dir).await.unwrap();
+
+        let tool = FileAppendTool::new(test_security(dir.clone()));
+        for item in ["- buy milk", "- call mum"] {
+            let result = tool
+                .execute(json!({"path": "notes/todo.md", "content": item}))
+                .await
+                .unwrap();
+            assert!(result.success, "{:?}", result.error);
+        }
+
+        let content = tokio::fs::read_to_string(dir.join("notes/todo.md"))
+            .await
+            .unwrap();
+        assert_eq!(content, "- buy milk\n- call mum");
+
+        let _ = tokio::fs::remove_dir_all(&dir).await;
+    }
+
+    #[tokio::test]
+    async fn file_append_blocks_path_traversal() {
+        let dir = std::env::temp_dir().join("zeroclaw_test_file_append_traversal");
+        let _ = tokio::fs::remove_dir_all(&dir).await;
+        tokio::fs::create_dir_all(&dir).await.unwrap();
+
+        let tool = FileAppendTool::new(test_security(dir.clone()));
+        let result = tool
+            .execute(json!({"path": "../escape.txt", "content": "x"}))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("not allowed"));
+
+        let _ = tokio::fs::remove_dir_all(&dir).await;
+    }
+
+    #[tokio::test]
+    async fn file_append_blocks_readonly_mode() {
+        let dir = std::env::temp_dir().join("zeroclaw_test_file_append_readonly");
+        let tool = FileAppendTool::new(Arc::new(SecurityPolicy {
+            autonomy: AutonomyLevel::ReadOnly,
+            workspace_dir: dir,
+            ..SecurityPolicy::default()
+        }));
+        let result = tool
+            .execute(json!({"path": "log.txt", "content": "x"}))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("read-only"));
+    }
+}
@@ -0,0 +1,217 @@
+use super::traits::{Tool, ToolResult};
+use crate::security::SecurityPolicy;
+use async_trait::async_trait;
+use serde_json::json;
+use std::sync::Arc;
+
+/// Delete a single file with path sandboxing
+pub struct FileDeleteTool {
+    security: Arc<SecurityPolicy>,
+}
+
+impl FileDeleteTool {
+    pub fn new(security: Arc<SecurityPolicy>) -> Self {
+        Self { security }
+    }
+}
+
+#[async_trait]
+impl Tool for FileDeleteTool {
+    fn name(&self) -> &str {
+        "file_delete"
+    }
+
+    fn description(&self) -> &str {
+        "Delete a single file from the workspace. Directories are not removed."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "path": {
+                    "type": "string",
+                    "description": "Path to the file. Relative paths resolve from workspace; outside paths require policy allowlist."
+                }
+            },
+            "required": ["path"]
+        })
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let path = args
+            .get("path")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| anyhow::anyhow!("Missing 'path' parameter"))?;
+
+        if !self.security.can_act() {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some("Action blocked: autonomy is read-only".into()),
+            });
+        }
+
+        if self.security.is_rate_limited() {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some("Rate limit exceeded: too many actions in the last hour".into()),
+            });
+        }
+
+        // Security check: validate path is within workspace
+        if !self.security.is_path_allowed(path) {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(format!("Path not allowed by security policy: {path}")),
+            });
+        }
+
+        // Record action before canonicalization so path-probing still consumes budget.
+        if !self.security.record_action() {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some("Rate limit exceeded: action budget exhausted".into()),
+            });
+        }
+
+        let full_path = self.security.workspace_dir.join(path);
+
+        let (Some(parent), Some(file_name)) = (full_path.parent(), full_path.file_name()) else {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some("Invalid path: missing file name".into()),
+            });
+        };
+
+        // Resolve only the parent so a symlink at the target is removed as a
+        // link instead of deleting whatever it points to.
+        let resolved_parent = match tokio::fs::canonicalize(parent).await {
+            Ok(p) => p,
+            Err(e) => {
+                return Ok(ToolResult {
+                    success: false,
+                    output: String::new(),
+                    error: Some(format!("Failed to resolve file path: {e}")),
+                });
+            }
+        };
+
+        if !self.security.is_resolved_path_allowed(&resolved_parent) {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(
+                    self.security
+                        .resolved_path_violation_message(&resolved_parent),
+                ),
+            });
+        }
+
+        let resolved_target = resolved_parent.join(file_name);
+
+        match tokio::fs::symlink_metadata(&resolved_target).await {
+            Ok(meta) if meta.is_dir() => {
+                return Ok(ToolResult {
+                    success: false,
+                    output: String::new(),
+                    error: Some(format!("Refusing to delete directory: {path}")),
+                });
+            }
+            Ok(_) => {}
+            Err(e) => {
+                return Ok(ToolResult {
+                    success: false,
+                    output: String::new(),
+                    error: Some(format!("Failed to read file metadata: {e}")),
+                });
+            }
+        }
+
+        match tokio::fs::remove_file(&resolved_target).await {
+            Ok(()) => Ok(ToolResult {
+                success: true,
+                output: format!("Deleted {path}"),
+                error: None,
+            }),
+            Err(e) => Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(format!("Failed to delete file: {e}")),
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::security::AutonomyLevel;
+    use tempfile::TempDir;
+
+    fn test_security(workspace: std::path::PathBuf) -> Arc<SecurityPolicy> {
+        Arc::new(SecurityPolicy {
+            autonomy: AutonomyLevel::Supervised,
+            workspace_dir: workspace,
+            ..SecurityPolicy::default()
+        })
+    }
+
+    #[tokio::test]
+    async fn file_delete_removes_file() {
+        let tmp = TempDir::new().unwrap();
+        tokio::fs::write(tmp.path().join("draft.md"), "old")
+            .await
+            .unwrap();
+
+        let tool = FileDeleteTool::new(test_security(tmp.path().to_path_buf()));
+        let result = tool.execute(json!({"path": "draft.md"})).await.unwrap();
+        assert!(result.success, "{:?}", result.error);
+        assert!(!tmp.path().join("draft.md").exists());
+    }
+
+    #[tokio::test]
+    async fn file_delete_refuses_directories() {
+        let tmp = TempDir::new().unwrap();
+        tokio::fs::create_dir(tmp.path().join("notes"))
+            .await
+            .unwrap();
+
+        let tool = FileDeleteTool::new(test_security(tmp.path().to_path_buf()));
+        let result = tool.execute(json!({"path": "notes"})).await.unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("directory"));
+        assert!(tmp.path().join("notes").is_dir());
+    }
+
+    #[tokio::test]
+    async fn file_delete_blocks_path_traversal() {
+        let tmp = TempDir::new().unwrap();
+        let tool = FileDeleteTool::new(test_security(tmp.path().to_path_buf()));
+        let result = tool
+            .execute(json!({"path": "../../etc/passwd"}))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("not allowed"));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn file_delete_removes_symlink_not_target() {
+        let tmp = TempDir::new().unwrap();
+        let outside = TempDir::new().unwrap();
+        let target = outside.path().join("keep.txt");
+        tokio::fs::write(&target, "keep").await.unwrap();
+        std::os::unix::fs::symlink(&target, tmp.path().join("link.txt")).unwrap();
+
+        let tool = FileDeleteTool::new(test_security(tmp.path().to_path_buf()));
+        let result = tool.execute(json!({"path": "link.txt"})).await.unwrap();
+        assert!(result.success, "{:?}", result.error);
+        assert!(target.exists());
+    }
+}
@@ -0,0 +1,269 @@
+use super::traits::{Tool, ToolResult};
+use crate::security::SecurityPolicy;
+use async_trait::async_trait;
+use serde_json::json;
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+const DEFAULT_MAX_ENTRIES: usize = 200;
+const MAX_ENTRIES_LIMIT: usize = 1000;
+
+/// List directory contents with path sandboxing
+pub struct FileListTool {
+    security: Arc<SecurityPolicy>,
+}
+
+impl FileListTool {
+    pub fn new(security: Arc<SecurityPolicy>) -> Self {
+        Self { security }
+    }
+}
+
+/// Walk `root`, returning one line per entry relative to `root`. Symlinked
+/// directories are listed but never descended into.
+async fn collect_entries(
+    root: &Path,
+    recursive: bool,
+    max_entries: usize,
+) -> std::io::Result<(Vec<String>, bool)> {
+    let mut lines = Vec::new();
+    let mut pending: Vec<PathBuf> = vec![root.to_path_buf()];
+
+    while let Some(dir) = pending.pop() {
+        let mut reader = tokio::fs::read_dir(&dir).await?;
+        let mut batch = Vec::new();
+        while let Some(entry) = reader.next_entry().await? {
+            batch.push(entry);
+        }
+        batch.sort_by_key(tokio::fs::DirEntry::file_name);
+
+        let mut subdirs = Vec::new();
+        for entry in batch {
+            if lines.len() >= max_entries {
+                return Ok((lines, true));
+            }
+            let entry_path = entry.path();
+            let relative = entry_path
+                .strip_prefix(root)
+                .unwrap_or(&entry_path)
+                .display()
+                .to_string();
+            let file_type = entry.file_type().await?;
+            if file_type.is_dir() {
+                lines.push(format!("{relative}/"));
+                if recursive {
+                    subdirs.push(entry_path);
+                }
+            } else if file_type.is_symlink() {
+                lines.push(format!("{relative} -> (symlink)"));
+            } else {
+                let size = entry.metadata().await.map(|m| m.len()).unwrap_or(0);
+                lines.push(format!("{relative} ({size} bytes)"));
+            }
+        }
+        pending.extend(subdirs.into_iter().rev());
+    }
+
+    Ok((lines, false))
+}
+
+#[async_trait]
+impl Tool for FileListTool {
+    fn name(&self) -> &str {
+        "file_list"
+    }
+
+    fn description(&self) -> &str {
+        "List files and directories in the workspace with their sizes. Directories end with '/'."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "path": {
+                    "type": "string",
+                    "description": "Directory to list. Relative paths resolve from workspace; defaults to the workspace root.",
+                    "default": "."
+                },
+                "recursive": {
+                    "type": "boolean",
+                    "description": "Include the contents of subdirectories",
+                    "default": false
+                },
+                "max_entries": {
+                    "type": "integer",
+                    "description": "Maximum number of entries to return",
+                    "default": DEFAULT_MAX_ENTRIES
+                }
+            }
+        })
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let path = args.get("path").and_then(|v| v.as_str()).unwrap_or(".");
+        let recursive = args
+            .get("recursive")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false);
+        let max_entries = args
+            .get("max_entries")
+            .and_then(|v| v.as_u64())
+            .map_or(DEFAULT_MAX_ENTRIES, |n| {
+                usize::try_from(n).unwrap_or(MAX_ENTRIES_LIMIT)
+            })
+            .clamp(1, MAX_ENTRIES_LIMIT);
+
+        if self.security.is_rate_limited() {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some("Rate limit exceeded: too many actions in the last hour".into()),
+            });
+        }
+
+        // Security check: validate path is within workspace
+        if !self.security.is_path_allowed(path) {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(format!("Path not allowed by security policy: {path}")),
+            });
+        }
+
+        // Record action before canonicalization so path-probing still consumes budget.
+        if !self.security.record_action() {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some("Rate limit exceeded: action budget exhausted".into()),
+            });
+        }
+
+        let full_path = self.security.workspace_dir.join(path);
+        let resolved_path = match tokio::fs::canonicalize(&full_path).await {
+            Ok(p) => p,
+            Err(e) => {
+                return Ok(ToolResult {
+                    success: false,
+                    output: String::new(),
+                    error: Some(format!("Failed to resolve directory path: {e}")),
+                });
+            }
+        };
+
+        if !self.security.is_resolved_path_allowed(&resolved_path) {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(
+                    self.security
+                        .resolved_path_violation_message(&resolved_path),
+                ),
+            });
+        }
+
+        if !resolved_path.is_dir() {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(format!("Not a directory: {path}")),
+            });
+        }
+
+        match collect_entries(&resolved_path, recursive, max_entries).await {
+            Ok((lines, _)) if lines.is_empty() => Ok(ToolResult {
+                success: true,
+                output: format!("{path} is empty"),
+                error: None,
+            }),
+            Ok((lines, truncated)) => {
+                let mut output = lines.join("\n");
+                if truncated {
+                    let _ = write!(
+                        output,
+                        "\n[truncated after {max_entries} entries; narrow the path or raise max_entries]"
+                    );
+                }
+                Ok(ToolResult {
+                    success: true,
+                    output,
+                    error: None,
+                })
+            }
+            Err(e) => Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(format!("Failed to list directory: {e}")),
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::security::AutonomyLevel;
+    use tempfile::TempDir;
+
+    fn test_security(workspace: std::path::PathBuf) -> Arc<SecurityPolicy> {
+        Arc::new(SecurityPolicy {
+            autonomy: AutonomyLevel::Supervised,
+            workspace_dir: workspace,
+            ..SecurityPolicy::default()
+        })
+    }
+
+    async fn workspace_with_notes() -> TempDir {
+        let tmp = TempDir::new().unwrap();
+        tokio::fs::create_dir_all(tmp.path().join("notes"))
+            .await
+            .unwrap();
+        tokio::fs::write(tmp.path().join("todo.md"), "abc")
+            .await
+            .unwrap();
+        tokio::fs::write(tmp.path().join("notes/day.md"), "hello")
+            .await
+            .unwrap();
+        tmp
+    }
+
+    #[tokio::test]
+    async fn file_list_shows_top_level_by_default() {
+        let tmp = workspace_with_notes().await;
+        let tool = FileListTool::new(test_security(tmp.path().to_path_buf()));
+        let result = tool.execute(json!({})).await.unwrap();
+        assert!(result.success, "{:?}", result.error);
+        assert_eq!(result.output, "notes/\ntodo.md (3 bytes)");
+    }
+
+    #[tokio::test]
+    async fn file_list_recurses_when_asked() {
+        let tmp = workspace_with_notes().await;
+        let tool = FileListTool::new(test_security(tmp.path().to_path_buf()));
+        let result = tool.execute(json!({"recursive": true})).await.unwrap();
+        assert!(result.success, "{:?}", result.error);
+        assert!(result.output.contains("notes/day.md (5 bytes)"));
+    }
+
+    #[tokio::test]
+    async fn file_list_truncates_at_max_entries() {
+        let tmp = workspace_with_notes().await;
+        let tool = FileListTool::new(test_security(tmp.path().to_path_buf()));
+        let result = tool.execute(json!({"max_entries": 1})).await.unwrap();
+        assert!(result.success);
+        assert!(result
+            .output
+            .starts_with("notes/\n[truncated after 1 entries"));
+    }
+
+    #[tokio::test]
+    async fn file_list_blocks_path_traversal() {
+        let tmp = TempDir::new().unwrap();
+        let tool = FileListTool::new(test_security(tmp.path().to_path_buf()));
+        let result = tool.execute(json!({"path": "../"})).await.unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("not allowed"));
+    }
+}
@@ -1,4 +1,5 @@
 use super::traits::{Tool, ToolResult};
+use crate::runtime::saf::{self, SafStorage};
 use crate::security::SecurityPolicy;
 use async_trait::async_trait;
 use serde_json::json;
@@ -9,11 +10,59 @@ const MAX_FILE_SIZE_BYTES: u64 = 10 * 1024 * 1024;
 /// Read file contents with path sandboxing
 pub struct FileReadTool {
     security: Arc<SecurityPolicy>,
+    saf: Option<Arc<SafStorage>>,
 }
 
 impl FileReadTool {
     pub fn new(security: Arc<SecurityPolicy>) -> Self {
-        Self { security }
+        Self {
+            security,
+            saf: None,
+        }
+    }
+
+    /// Allow `content://` paths to be read through the SAF document backend.
+    pub fn with_saf(mut self, saf: Arc<SafStorage>) -> Self {
+        self.saf = Some(saf);
+        self
+    }
+
+    async fn read_content_uri(&self, uri: &str, args: &serde_json::Value) -> ToolResult {
+        let Some(saf) = self.saf.as_ref() else {
+            return ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(
+                    "content:// paths require runtime.android.saf_tree_uris to be configured"
+                        .into(),
+                ),
+            };
+        };
+
+        if !self.security.record_action() {
+            return ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some("Rate limit exceeded: action budget exhausted".into()),
+            };
+        }
+
+        match saf.read(uri).await {
+            Ok(bytes) if bytes.len() as u64 > MAX_FILE_SIZE_BYTES => ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(format!(
+                    "File too large: {} bytes (limit: {MAX_FILE_SIZE_BYTES} bytes)",
+                    bytes.len()
+                )),
+            },
+            Ok(bytes) => render_numbered_lines(&String::from_utf8_lossy(&bytes), args),
+            Err(e) => ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(format!("Failed to read document: {e}")),
+            },
+        }
     }
 }
 
@@ -33,7 +82,7 @@ impl Tool for FileReadTool {
             "properties": {
                 "path": {
                     "type": "string",
-                    "description": "Path to the file. Relative paths resolve from workspace; outside paths require policy allowlist."
+                    "description": "Path to the file. Relative paths resolve from workspace; outside paths require policy allowlist. content:// URIs read from user-granted Android folders."
                 },
                 "offset": {
                     "type": "integer",
@@ -62,6 +111,10 @@ impl Tool for FileReadTool {
             });
         }
 
+        if saf::is_content_uri(path) {
+            return Ok(self.read_content_uri(path, &args).await);
+        }
+
         // Security check: validate path is within workspace
         if !self.security.is_path_allowed(path) {
             return Ok(ToolResult {
@@ -131,65 +184,7 @@ impl Tool for FileReadTool {
         }
 
         match tokio::fs::read_to_string(&resolved_path).await {
-            Ok(contents) => {
-                let lines: Vec<&str> = contents.lines().collect();
-                let total = lines.len();
-
-                if total == 0 {
-                    return Ok(ToolResult {
-                        success: true,
-                        output: String::new(),
-                        error: None,
-                    });
-                }
-
-                let offset = args
-                    .get("offset")
-                    .and_then(|v| v.as_u64())
-                    .map(|v| {
-                        usize::try_from(v.max(1))
-                            .unwrap_or(usize::MAX)
-                            .saturating_sub(1)
-                    })
-                    .unwrap_or(0);
-                let start = offset.min(total);
-
-                let end = match args.get("limit").and_then(|v| v.as_u64()) {
-                    Some(l) => {
-                        let limit = usize::try_from(l).unwrap_or(usize::MAX);
-                        (start.saturating_add(limit)).min(total)
-                    }
-                    None => total,
-                };
-
-                if start >= end {
-                    return Ok(ToolResult {
-                        success: true,
-                        output: format!("[No lines in range, file has {total} lines]"),
-                        error: None,
-                    });
-                }
-
-                let numbered: String = lines[start..end]
-                    .iter()
-                    .enumerate()
-                    .map(|(i, line)| format!("{}: {}", start + i + 1, line))
-                    .collect::<Vec<_>>()
-                    .join("\n");
-
-                let partial = start > 0 || end < total;
-                let summary = if partial {
-                    format!("\n[Lines {}-{} of {total}]", start + 1, end)
-                } else {
-                    format!("\n[{total} lines total]")
-                };
-
-                Ok(ToolResult {
-                    success: true,
-                    output: format!("{numbered}{summary}"),
-                    error: None,
-                })
-            }
+            Ok(contents) => Ok(render_numbered_lines(&contents, &args)),
             Err(_) => {
                 // Not valid UTF-8 — read raw bytes and try to extract text
                 let bytes = tokio::fs::read(&resolved_path)
@@ -216,6 +211,67 @@ impl Tool for FileReadTool {
     }
 }
 
+/// Render `contents` with 1-based line numbers, honoring `offset`/`limit` args.
+fn render_numbered_lines(contents: &str, args: &serde_json::Value) -> ToolResult {
+    let lines: Vec<&str> = contents.lines().collect();
+    let total = lines.len();
+
+    if total == 0 {
+        return ToolResult {
+            success: true,
+            output: String::new(),
+            error: None,
+        };
+    }
+
+    let offset = args
+        .get("offset")
+        .and_then(|v| v.as_u64())
+        .map(|v| {
+            usize::try_from(v.max(1))
+                .unwrap_or(usize::MAX)
+                .saturating_sub(1)
+        })
+        .unwrap_or(0);
+    let start = offset.min(total);
+
+    let end = match args.get("limit").and_then(|v| v.as_u64()) {
+        Some(l) => {
+            let limit = usize::try_from(l).unwrap_or(usize::MAX);
+            (start.saturating_add(limit)).min(total)
+        }
+        None => total,
+    };
+
+    if start >= end {
+        return ToolResult {
+            success: true,
+            output: format!("[No lines in range, file has {total} lines]"),
+            error: None,
+        };
+    }
+
+    let numbered: String = lines[start..end]
+        .iter()
+        .enumerate()
+        .map(|(i, line)| format!("{}: {}", start + i + 1, line))
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    let partial = start > 0 || end < total;
+    let summary = if partial {
+        format!("\n[Lines {}-{} of {total}]", start + 1, end)
+    } else {
+        format!("\n[{total} lines total]")
+    };
+
+    ToolResult {
+        success: true,
+        output: format!("{numbered}{summary}"),
+        error: None,
+    }
+}
+
 #[cfg(feature = "rag-pdf")]
 fn try_extract_pdf_text(bytes: &[u8]) -> Option<String> {
     if bytes.len() < 5 || &bytes[..5] != b"%PDF-" {
@@ -316,6 +372,17 @@ mod tests {
         let _ = tokio::fs::remove_dir_all(&dir).await;
     }
 
+    #[tokio::test]
+    async fn file_read_content_uri_requires_saf_grant() {
+        let tool = FileReadTool::new(test_security(std::env::temp_dir()));
+        let result = tool
+            .execute(json!({"path": "content://com.android.externalstorage.documents/tree/primary%3ADownload/document/x"}))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("saf_tree_uris"));
+    }
+
     #[tokio::test]
     async fn file_read_blocks_path_traversal() {
         let dir = std::env::temp_dir().join("zeroclaw_test_file_read_traversal");
@@ -1,4 +1,5 @@
 use super::traits::{Tool, ToolResult};
+use crate::runtime::saf::{self, SafStorage};
 use crate::security::SecurityPolicy;
 use async_trait::async_trait;
 use serde_json::json;
@@ -7,11 +8,55 @@ use std::sync::Arc;
 /// Write file contents with path sandboxing
 pub struct FileWriteTool {
     security: Arc<SecurityPolicy>,
+    saf: Option<Arc<SafStorage>>,
 }
 
 impl FileWriteTool {
     pub fn new(security: Arc<SecurityPolicy>) -> Self {
-        Self { security }
+        Self {
+            security,
+            saf: None,
+        }
+    }
+
+    /// Allow `content://` paths to be written through the SAF document backend.
+    pub fn with_saf(mut self, saf: Arc<SafStorage>) -> Self {
+        self.saf = Some(saf);
+        self
+    }
+
+    async fn write_content_uri(&self, uri: &str, content: &str) -> ToolResult {
+        let Some(saf) = self.saf.as_ref() else {
+            return ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(
+                    "content:// paths require runtime.android.saf_tree_uris to be configured"
+                        .into(),
+                ),
+            };
+        };
+
+        if !self.security.record_action() {
+            return ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some("Rate limit exceeded: action budget exhausted".into()),
+            };
+        }
+
+        match saf.write(uri, content.as_bytes()).await {
+            Ok(()) => ToolResult {
+                success: true,
+                output: format!("Written {} bytes to {uri}", content.len()),
+                error: None,
+            },
+            Err(e) => ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(format!("Failed to write document: {e}")),
+            },
+        }
     }
 }
 
@@ -31,7 +76,7 @@ impl Tool for FileWriteTool {
             "properties": {
                 "path": {
                     "type": "string",
-                    "description": "Path to the file. Relative paths resolve from workspace; outside paths require policy allowlist."
+                    "description": "Path to the file. Relative paths resolve from workspace; outside paths require policy allowlist. content:// URIs write into user-granted Android folders."
                 },
                 "content": {
                     "type": "string",
@@ -69,6 +114,10 @@ impl Tool for FileWriteTool {
             });
         }
 
+        if saf::is_content_uri(path) {
+            return Ok(self.write_content_uri(path, content).await);
+        }
+
         // Security check: validate path is within workspace
         if !self.security.is_path_allowed(path) {
             return Ok(ToolResult {
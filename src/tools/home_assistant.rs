@@ -0,0 +1,537 @@
+use super::traits::{Tool, ToolResult};
+use crate::config::HomeAssistantConfig;
+use crate::security::SecurityPolicy;
+use anyhow::Context;
+use async_trait::async_trait;
+use futures_util::{SinkExt, StreamExt};
+use serde_json::json;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio_tungstenite::tungstenite::Message;
+
+const DEFAULT_LIST_LIMIT: usize = 100;
+const MAX_LIST_LIMIT: usize = 500;
+const HA_CONNECT_TIMEOUT_SECS: u64 = 10;
+
+/// Control a Home Assistant instance: read entity states over the REST API,
+/// call services, and list areas over the WebSocket API.
+pub struct HomeAssistantTool {
+    security: Arc<SecurityPolicy>,
+    base_url: String,
+    token: String,
+    allowed_domains: Vec<String>,
+    timeout_secs: u64,
+}
+
+impl HomeAssistantTool {
+    pub fn new(
+        security: Arc<SecurityPolicy>,
+        base_url: &str,
+        token: &str,
+        allowed_domains: Vec<String>,
+        timeout_secs: u64,
+    ) -> Self {
+        Self {
+            security,
+            base_url: base_url.trim().trim_end_matches('/').to_string(),
+            token: token.trim().to_string(),
+            allowed_domains: allowed_domains
+                .into_iter()
+                .map(|d| d.trim().to_ascii_lowercase())
+                .filter(|d| !d.is_empty())
+                .collect(),
+            timeout_secs: timeout_secs.max(1),
+        }
+    }
+
+    /// Build the tool when `[home_assistant]` is enabled with a base URL and token.
+    pub fn from_config(
+        security: Arc<SecurityPolicy>,
+        config: &HomeAssistantConfig,
+    ) -> Option<Self> {
+        if !config.enabled {
+            return None;
+        }
+        let base_url = config
+            .base_url
+            .as_deref()
+            .map(str::trim)
+            .unwrap_or_default();
+        let token = config.token.as_deref().map(str::trim).unwrap_or_default();
+        if token.is_empty()
+            || !(base_url.starts_with("http://") || base_url.starts_with("https://"))
+        {
+            tracing::warn!(
+                "home_assistant enabled but base_url/token missing or invalid; tool not registered"
+            );
+            return None;
+        }
+        Some(Self::new(
+            security,
+            base_url,
+            token,
+            config.allowed_domains.clone(),
+            config.timeout_secs,
+        ))
+    }
+
+    fn websocket_url(&self) -> String {
+        let ws_base = if let Some(rest) = self.base_url.strip_prefix("https://") {
+            format!("wss://{rest}")
+        } else if let Some(rest) = self.base_url.strip_prefix("http://") {
+            format!("ws://{rest}")
+        } else {
+            self.base_url.clone()
+        };
+        format!("{ws_base}/api/websocket")
+    }
+
+    fn client(&self) -> reqwest::Client {
+        crate::config::build_runtime_proxy_client_with_timeouts(
+            "tool.home_assistant",
+            self.timeout_secs,
+            HA_CONNECT_TIMEOUT_SECS,
+        )
+    }
+
+    /// Home Assistant identifiers (domains, services, object ids) are
+    /// lowercase snake_case.
+    fn is_slug(value: &str) -> bool {
+        !value.is_empty()
+            && value
+                .chars()
+                .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+    }
+
+    fn is_entity_id(value: &str) -> bool {
+        value
+            .split_once('.')
+            .is_some_and(|(domain, object)| Self::is_slug(domain) && Self::is_slug(object))
+    }
+
+    fn required_str<'a>(
+        args: &'a serde_json::Value,
+        name: &str,
+        action: &str,
+    ) -> anyhow::Result<&'a str> {
+        args.get(name)
+            .and_then(serde_json::Value::as_str)
+            .map(str::trim)
+            .filter(|v| !v.is_empty())
+            .ok_or_else(|| anyhow::anyhow!("{action} requires '{name}'"))
+    }
+
+    fn ensure_domain_allowed(&self, domain: &str) -> anyhow::Result<()> {
+        if !self.allowed_domains.is_empty() && !self.allowed_domains.iter().any(|d| d == domain) {
+            anyhow::bail!("Domain '{domain}' is not in home_assistant.allowed_domains");
+        }
+        Ok(())
+    }
+
+    /// Validate a `call_service` request and return `(domain, service, body)`.
+    fn service_call(
+        &self,
+        args: &serde_json::Value,
+    ) -> anyhow::Result<(String, String, serde_json::Value)> {
+        let domain = Self::required_str(args, "domain", "call_service")?.to_ascii_lowercase();
+        let service = Self::required_str(args, "service", "call_service")?.to_ascii_lowercase();
+        if !Self::is_slug(&domain) || !Self::is_slug(&service) {
+            anyhow::bail!(
+                "'domain' and 'service' must be lowercase identifiers like light / turn_on"
+            );
+        }
+        self.ensure_domain_allowed(&domain)?;
+
+        let mut body = match args.get("data") {
+            None | Some(serde_json::Value::Null) => serde_json::Map::new(),
+            Some(serde_json::Value::Object(map)) => map.clone(),
+            Some(_) => anyhow::bail!("'data' must be an object"),
+        };
+        if let Some(entity_id) = args
+            .get("entity_id")
+            .and_then(serde_json::Value::as_str)
+            .map(str::trim)
+            .filter(|v| !v.is_empty())
+        {
+            if !Self::is_entity_id(entity_id) {
+                anyhow::bail!("'{entity_id}' is not a valid entity id");
+            }
+            body.insert("entity_id".into(), json!(entity_id));
+        }
+        Ok((domain, service, serde_json::Value::Object(body)))
+    }
+
+    async fn rest_get(&self, path: &str) -> anyhow::Result<serde_json::Value> {
+        let response = self
+            .client()
+            .get(format!("{}{path}", self.base_url))
+            .bearer_auth(&self.token)
+            .send()
+            .await
+            .context("Home Assistant request failed")?;
+        Self::read_json(response).await
+    }
+
+    async fn read_json(response: reqwest::Response) -> anyhow::Result<serde_json::Value> {
+        let status = response.status();
+        let body = response.text().await.unwrap_or_default();
+        if !status.is_success() {
+            let detail = crate::util::truncate_with_ellipsis(body.trim(), 300);
+            anyhow::bail!("Home Assistant returned {status}: {detail}");
+        }
+        serde_json::from_str(&body).context("Home Assistant returned invalid JSON")
+    }
+
+    /// Keep only the fields the model needs from a state object.
+    fn compact_state(state: &serde_json::Value) -> serde_json::Value {
+        json!({
+            "entity_id": state["entity_id"],
+            "state": state["state"],
+            "name": state["attributes"]["friendly_name"],
+            "unit": state["attributes"]["unit_of_measurement"],
+            "last_changed": state["last_changed"],
+        })
+    }
+
+    fn filter_states(
+        states: &[serde_json::Value],
+        domain: Option<&str>,
+        query: Option<&str>,
+        limit: usize,
+    ) -> serde_json::Value {
+        let query = query.map(str::to_lowercase);
+        let matched: Vec<serde_json::Value> = states
+            .iter()
+            .filter(|s| {
+                let entity_id = s["entity_id"].as_str().unwrap_or_default();
+                domain.is_none_or(|d| entity_id.split('.').next() == Some(d))
+            })
+            .filter(|s| {
+                query.as_deref().is_none_or(|q| {
+                    s["entity_id"].as_str().unwrap_or_default().contains(q)
+                        || s["attributes"]["friendly_name"]
+                            .as_str()
+                            .is_some_and(|name| name.to_lowercase().contains(q))
+                })
+            })
+            .map(Self::compact_state)
+            .collect();
+        let total = matched.len();
+        let entities: Vec<_> = matched.into_iter().take(limit).collect();
+        json!({ "total": total, "returned": entities.len(), "entities": entities })
+    }
+
+    /// Run one command over the WebSocket API and return its `result`.
+    async fn ws_command(&self, command: &str) -> anyhow::Result<serde_json::Value> {
+        let exchange = async {
+            let (ws, _) = tokio_tungstenite::connect_async(self.websocket_url())
+                .await
+                .context("Home Assistant WebSocket connect failed")?;
+            let (mut write, mut read) = ws.split();
+
+            let hello = next_json(&mut read).await?;
+            if hello["type"] != "auth_required" {
+                anyhow::bail!("unexpected WebSocket greeting: {}", hello["type"]);
+            }
+            write
+                .send(Message::Text(
+                    json!({ "type": "auth", "access_token": self.token })
+                        .to_string()
+                        .into(),
+                ))
+                .await?;
+            let auth = next_json(&mut read).await?;
+            if auth["type"] != "auth_ok" {
+                anyhow::bail!("Home Assistant rejected the access token");
+            }
+
+            write
+                .send(Message::Text(
+                    json!({ "id": 1, "type": command }).to_string().into(),
+                ))
+                .await?;
+            loop {
+                let message = next_json(&mut read).await?;
+                if message["id"] != 1 || message["type"] != "result" {
+                    continue;
+                }
+                if message["success"] != true {
+                    anyhow::bail!(
+                        "{command} failed: {}",
+                        message["error"]["message"]
+                            .as_str()
+                            .unwrap_or("unknown error")
+                    );
+                }
+                let _ = write.send(Message::Close(None)).await;
+                return Ok(message["result"].clone());
+            }
+        };
+        tokio::time::timeout(Duration::from_secs(self.timeout_secs), exchange)
+            .await
+            .map_err(|_| anyhow::anyhow!("Home Assistant WebSocket timed out"))?
+    }
+
+    async fn run(&self, args: &serde_json::Value) -> anyhow::Result<serde_json::Value> {
+        match args.get("action").and_then(serde_json::Value::as_str) {
+            Some("get_state") => {
+                let entity_id = Self::required_str(args, "entity_id", "get_state")?;
+                if !Self::is_entity_id(entity_id) {
+                    anyhow::bail!("'{entity_id}' is not a valid entity id");
+                }
+                let state = self.rest_get(&format!("/api/states/{entity_id}")).await?;
+                Ok(json!({
+                    "entity_id": state["entity_id"],
+                    "state": state["state"],
+                    "attributes": state["attributes"],
+                    "last_changed": state["last_changed"],
+                }))
+            }
+            Some("list_states") => {
+                let domain = args
+                    .get("domain")
+                    .and_then(serde_json::Value::as_str)
+                    .map(str::trim)
+                    .filter(|v| !v.is_empty());
+                let query = args
+                    .get("query")
+                    .and_then(serde_json::Value::as_str)
+                    .map(str::trim)
+                    .filter(|v| !v.is_empty());
+                let limit = args
+                    .get("limit")
+                    .and_then(serde_json::Value::as_u64)
+                    .map_or(DEFAULT_LIST_LIMIT, |v| {
+                        usize::try_from(v).unwrap_or(MAX_LIST_LIMIT)
+                    })
+                    .clamp(1, MAX_LIST_LIMIT);
+                let states = self.rest_get("/api/states").await?;
+                let states = states.as_array().map(Vec::as_slice).unwrap_or_default();
+                Ok(Self::filter_states(states, domain, query, limit))
+            }
+            Some("list_areas") => {
+                let areas = self.ws_command("config/area_registry/list").await?;
+                let areas: Vec<serde_json::Value> = areas
+                    .as_array()
+                    .map(Vec::as_slice)
+                    .unwrap_or_default()
+                    .iter()
+                    .map(|a| json!({ "area_id": a["area_id"], "name": a["name"] }))
+                    .collect();
+                Ok(json!({ "areas": areas }))
+            }
+            Some("call_service") => {
+                let (domain, service, body) = self.service_call(args)?;
+                if !self.security.can_act() {
+                    anyhow::bail!("Action blocked: autonomy is read-only");
+                }
+                if !self.security.record_action() {
+                    anyhow::bail!("Action blocked: rate limit exceeded");
+                }
+                let response = self
+                    .client()
+                    .post(format!("{}/api/services/{domain}/{service}", self.base_url))
+                    .bearer_auth(&self.token)
+                    .json(&body)
+                    .send()
+                    .await
+                    .context("Home Assistant request failed")?;
+                let changed = Self::read_json(response).await?;
+                let changed: Vec<serde_json::Value> = changed
+                    .as_array()
+                    .map(Vec::as_slice)
+                    .unwrap_or_default()
+                    .iter()
+                    .map(Self::compact_state)
+                    .collect();
+                Ok(json!({ "called": format!("{domain}.{service}"), "changed": changed }))
+            }
+            Some(other) => anyhow::bail!(
+                "Unknown action '{other}'. Supported: get_state, list_states, list_areas, call_service"
+            ),
+            None => anyhow::bail!("Missing 'action' parameter"),
+        }
+    }
+}
+
+/// Next JSON text frame from the WebSocket, skipping pings and binary frames.
+async fn next_json<S>(read: &mut S) -> anyhow::Result<serde_json::Value>
+where
+    S: futures_util::Stream<Item = Result<Message, tokio_tungstenite::tungstenite::Error>> + Unpin,
+{
+    loop {
+        match read.next().await {
+            Some(Ok(Message::Text(text))) => {
+                return serde_json::from_str(&text).context("invalid WebSocket message");
+            }
+            Some(Ok(Message::Close(_))) | None => {
+                anyhow::bail!("Home Assistant closed the WebSocket")
+            }
+            Some(Ok(_)) => {}
+            Some(Err(e)) => return Err(e.into()),
+        }
+    }
+}
+
+#[async_trait]
+impl Tool for HomeAssistantTool {
+    fn name(&self) -> &str {
+        "home_assistant"
+    }
+
+    fn description(&self) -> &str {
+        "Control the user's smart home through Home Assistant. list_states finds entities (filter by domain such as light/switch/climate/sensor or a name query); get_state reads one entity with attributes; list_areas lists rooms; call_service runs a service such as light.turn_on with entity_id and optional data (brightness_pct, temperature, ...)."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "action": {
+                    "type": "string",
+                    "enum": ["get_state", "list_states", "list_areas", "call_service"],
+                    "description": "Operation to perform"
+                },
+                "entity_id": {
+                    "type": "string",
+                    "description": "Entity id such as light.kitchen (get_state, call_service)"
+                },
+                "domain": {
+                    "type": "string",
+                    "description": "Entity domain filter (list_states) or service domain (call_service)"
+                },
+                "service": {
+                    "type": "string",
+                    "description": "Service name such as turn_on, turn_off, toggle, set_temperature (call_service)"
+                },
+                "data": {
+                    "type": "object",
+                    "description": "Extra service data, e.g. {\"brightness_pct\": 40} (call_service)"
+                },
+                "query": {
+                    "type": "string",
+                    "description": "Match entity id or friendly name (list_states)"
+                },
+                "limit": {
+                    "type": "integer",
+                    "description": "Maximum entities to return (list_states)",
+                    "default": DEFAULT_LIST_LIMIT
+                }
+            },
+            "required": ["action"]
+        })
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        match self.run(&args).await {
+            Ok(output) => Ok(ToolResult {
+                success: true,
+                output: serde_json::to_string_pretty(&output)?,
+                error: None,
+            }),
+            Err(error) => Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(error.to_string()),
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::security::AutonomyLevel;
+
+    fn test_tool(security: SecurityPolicy) -> HomeAssistantTool {
+        HomeAssistantTool::new(
+            Arc::new(security),
+            "http://homeassistant.local:8123/",
+            "token",
+            vec!["light".into(), "Climate".into()],
+            15,
+        )
+    }
+
+    #[test]
+    fn from_config_requires_url_and_token() {
+        let security = Arc::new(SecurityPolicy::default());
+        let mut config = HomeAssistantConfig {
+            enabled: true,
+            base_url: Some("https://ha.example.com".into()),
+            ..HomeAssistantConfig::default()
+        };
+        assert!(HomeAssistantTool::from_config(security.clone(), &config).is_none());
+
+        config.token = Some("llat".into());
+        let tool = HomeAssistantTool::from_config(security.clone(), &config).unwrap();
+        assert_eq!(tool.websocket_url(), "wss://ha.example.com/api/websocket");
+
+        config.base_url = Some("homeassistant.local:8123".into());
+        assert!(HomeAssistantTool::from_config(security, &config).is_none());
+    }
+
+    #[test]
+    fn service_call_validation() {
+        let tool = test_tool(SecurityPolicy::default());
+        assert_eq!(
+            tool.websocket_url(),
+            "ws://homeassistant.local:8123/api/websocket"
+        );
+
+        let (domain, service, body) = tool
+            .service_call(&json!({
+                "domain": "light",
+                "service": "turn_on",
+                "entity_id": "light.kitchen",
+                "data": { "brightness_pct": 40 }
+            }))
+            .unwrap();
+        assert_eq!((domain.as_str(), service.as_str()), ("light", "turn_on"));
+        assert_eq!(body["entity_id"], "light.kitchen");
+        assert_eq!(body["brightness_pct"], 40);
+
+        let err = tool
+            .service_call(&json!({ "domain": "lock", "service": "unlock" }))
+            .unwrap_err();
+        assert!(err.to_string().contains("allowed_domains"));
+        assert!(tool
+            .service_call(
+                &json!({ "domain": "light", "service": "turn_on", "entity_id": "../api" })
+            )
+            .is_err());
+        assert!(tool
+            .service_call(&json!({ "domain": "light/x", "service": "turn_on" }))
+            .is_err());
+    }
+
+    #[test]
+    fn filter_states_by_domain_and_query() {
+        let states = vec![
+            json!({"entity_id": "light.kitchen", "state": "on", "attributes": {"friendly_name": "Kitchen Light"}}),
+            json!({"entity_id": "light.bedroom", "state": "off", "attributes": {"friendly_name": "Bedroom"}}),
+            json!({"entity_id": "sensor.kitchen_temp", "state": "21.5", "attributes": {"unit_of_measurement": "°C"}}),
+        ];
+        let lights = HomeAssistantTool::filter_states(&states, Some("light"), None, 10);
+        assert_eq!(lights["total"], 2);
+
+        let kitchen = HomeAssistantTool::filter_states(&states, None, Some("Kitchen"), 1);
+        assert_eq!(kitchen["total"], 2);
+        assert_eq!(kitchen["returned"], 1);
+        assert_eq!(kitchen["entities"][0]["name"], "Kitchen Light");
+    }
+
+    #[tokio::test]
+    async fn read_only_autonomy_blocks_service_calls() {
+        let tool = test_tool(SecurityPolicy {
+            autonomy: AutonomyLevel::ReadOnly,
+            ..SecurityPolicy::default()
+        });
+        let result = tool
+            .execute(json!({ "action": "call_service", "domain": "light", "service": "toggle" }))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("read-only"));
+    }
+}
@@ -51,6 +51,10 @@ impl HttpRequestTool {
             anyhow::bail!("Blocked local/private host: {host}");
         }
 
+        if self.security.is_domain_denied(&host) {
+            anyhow::bail!("Host '{host}' is blocked by autonomy.denied_domains");
+        }
+
         // If allowlist is empty or contains "*", allow all public domains
         let allow_all =
             self.allowed_domains.is_empty() || self.allowed_domains.iter().any(|d| d == "*");
@@ -185,7 +189,7 @@ impl Tool for HttpRequestTool {
 
     fn description(&self) -> &str {
         "Make HTTP requests to external APIs. Supports GET, POST, PUT, DELETE, PATCH, HEAD, OPTIONS methods. \
-        Security constraints: allowlist-only domains, no local/private hosts, configurable timeout and response size limits."
+        Security constraints: allowlist-only domains, denylisted domains always blocked, no local/private hosts, configurable timeout and response size limits."
     }
 
     fn parameters_schema(&self) -> serde_json::Value {
@@ -526,6 +530,22 @@ mod tests {
         assert!(tool.validate_url("https://news.ycombinator.com").is_ok());
     }
 
+    #[test]
+    fn validate_denylist_overrides_wildcard_allowlist() {
+        let security = Arc::new(SecurityPolicy {
+            autonomy: AutonomyLevel::Supervised,
+            denied_domains: vec!["tracker.example".into()],
+            ..SecurityPolicy::default()
+        });
+        let tool = HttpRequestTool::new(security, vec!["*".into()], 1_000_000, 30);
+        let err = tool
+            .validate_url("https://cdn.tracker.example/pixel")
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("denied_domains"));
+        assert!(tool.validate_url("https://example.com").is_ok());
+    }
+
     #[test]
     fn validate_wildcard_allowlist_still_rejects_private_host() {
         let tool = test_tool(vec!["*"]);
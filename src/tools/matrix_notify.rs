@@ -0,0 +1,319 @@
+use super::traits::{Tool, ToolResult};
+use crate::config::MatrixConfig;
+use crate::security::SecurityPolicy;
+use async_trait::async_trait;
+use reqwest::Client;
+use serde_json::json;
+use std::sync::Arc;
+use std::time::Duration;
+
+const MATRIX_API_TIMEOUT_SECS: u64 = 15;
+
+/// Post plain-text notifications to a Matrix room over the Client-Server API.
+///
+/// Encrypted rooms are only supported through a pantalaimon-compatible proxy
+/// (`notify_proxy_url`); without one the tool refuses to post plaintext there.
+pub struct MatrixNotifyTool {
+    client: Client,
+    security: Arc<SecurityPolicy>,
+    base_url: String,
+    access_token: String,
+    room: String,
+    via_proxy: bool,
+}
+
+impl MatrixNotifyTool {
+    pub fn new(
+        security: Arc<SecurityPolicy>,
+        base_url: String,
+        access_token: String,
+        room: String,
+        via_proxy: bool,
+    ) -> Self {
+        let client = Client::builder()
+            .timeout(Duration::from_secs(MATRIX_API_TIMEOUT_SECS))
+            .build()
+            .unwrap_or_else(|_| Client::new());
+
+        Self {
+            client,
+            security,
+            base_url: base_url.trim_end_matches('/').to_string(),
+            access_token,
+            room,
+            via_proxy,
+        }
+    }
+
+    /// Build the tool from `[channels_config.matrix]` when `notify_room_id` is set.
+    pub fn from_config(security: Arc<SecurityPolicy>, config: &MatrixConfig) -> Option<Self> {
+        let room = config
+            .notify_room_id
+            .as_deref()
+            .map(str::trim)
+            .filter(|r| !r.is_empty())?;
+        if config.access_token.trim().is_empty() {
+            return None;
+        }
+        let proxy = config
+            .notify_proxy_url
+            .as_deref()
+            .map(str::trim)
+            .filter(|p| !p.is_empty());
+        let base_url = proxy.unwrap_or(&config.homeserver).to_string();
+        if base_url.is_empty() {
+            return None;
+        }
+        Some(Self::new(
+            security,
+            base_url,
+            config.access_token.clone(),
+            room.to_string(),
+            proxy.is_some(),
+        ))
+    }
+
+    fn room_url(&self, room_id: &str, suffix: &str) -> String {
+        format!(
+            "{}/_matrix/client/v3/rooms/{}/{suffix}",
+            self.base_url,
+            urlencoding::encode(room_id)
+        )
+    }
+
+    async fn resolve_room_id(&self) -> anyhow::Result<String> {
+        if self.room.starts_with('!') {
+            return Ok(self.room.clone());
+        }
+        if !self.room.starts_with('#') {
+            anyhow::bail!(
+                "Matrix notify_room_id must start with '!' (room ID) or '#' (room alias), got: {}",
+                self.room
+            );
+        }
+
+        let url = format!(
+            "{}/_matrix/client/v3/directory/room/{}",
+            self.base_url,
+            urlencoding::encode(&self.room)
+        );
+        let response = self
+            .client
+            .get(&url)
+            .bearer_auth(&self.access_token)
+            .send()
+            .await?;
+        if !response.status().is_success() {
+            let err = response.text().await.unwrap_or_default();
+            anyhow::bail!(
+                "Matrix room alias resolution failed for '{}': {err}",
+                self.room
+            );
+        }
+        let body: serde_json::Value = response.json().await?;
+        body.get("room_id")
+            .and_then(|v| v.as_str())
+            .map(String::from)
+            .ok_or_else(|| anyhow::anyhow!("Matrix alias response missing room_id"))
+    }
+
+    async fn room_is_encrypted(&self, room_id: &str) -> anyhow::Result<bool> {
+        let response = self
+            .client
+            .get(self.room_url(room_id, "state/m.room.encryption"))
+            .bearer_auth(&self.access_token)
+            .send()
+            .await?;
+        if response.status().is_success() {
+            return Ok(true);
+        }
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            return Ok(false);
+        }
+        let err = response.text().await.unwrap_or_default();
+        anyhow::bail!("Matrix room encryption check failed for '{room_id}': {err}");
+    }
+
+    async fn send(&self, message: &str) -> anyhow::Result<ToolResult> {
+        let room_id = self.resolve_room_id().await?;
+
+        if !self.via_proxy && self.room_is_encrypted(&room_id).await? {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(format!(
+                    "Matrix room {room_id} is end-to-end encrypted; set channels_config.matrix.notify_proxy_url to a pantalaimon instance to notify it"
+                )),
+            });
+        }
+
+        let txn_id = uuid::Uuid::new_v4().simple().to_string();
+        let response = self
+            .client
+            .put(self.room_url(&room_id, &format!("send/m.room.message/{txn_id}")))
+            .bearer_auth(&self.access_token)
+            .json(&json!({ "msgtype": "m.text", "body": message }))
+            .send()
+            .await?;
+
+        let status = response.status();
+        let response_text = response.text().await.unwrap_or_default();
+        if !status.is_success() {
+            return Ok(ToolResult {
+                success: false,
+                output: response_text,
+                error: Some(format!("Matrix API returned status {status}")),
+            });
+        }
+
+        Ok(ToolResult {
+            success: true,
+            output: format!("Matrix message sent to room {room_id}."),
+            error: None,
+        })
+    }
+}
+
+#[async_trait]
+impl Tool for MatrixNotifyTool {
+    fn name(&self) -> &str {
+        "matrix_notify"
+    }
+
+    fn description(&self) -> &str {
+        "Send a Matrix message to the configured notify room. Use this to proactively notify the user via Matrix."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "message": {
+                    "type": "string",
+                    "description": "The message text to send"
+                }
+            },
+            "required": ["message"]
+        })
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        if !self.security.can_act() {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some("Action blocked: autonomy is read-only".into()),
+            });
+        }
+
+        if !self.security.record_action() {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some("Action blocked: rate limit exceeded".into()),
+            });
+        }
+
+        let message = args
+            .get("message")
+            .and_then(|v| v.as_str())
+            .map(str::trim)
+            .filter(|v| !v.is_empty())
+            .ok_or_else(|| anyhow::anyhow!("Missing 'message' parameter"))?;
+
+        match self.send(message).await {
+            Ok(result) => Ok(result),
+            Err(e) => Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(e.to_string()),
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::security::AutonomyLevel;
+
+    fn test_security(level: AutonomyLevel, max_actions_per_hour: u32) -> Arc<SecurityPolicy> {
+        Arc::new(SecurityPolicy {
+            autonomy: level,
+            max_actions_per_hour,
+            workspace_dir: std::env::temp_dir(),
+            ..SecurityPolicy::default()
+        })
+    }
+
+    fn matrix_config(notify_room_id: Option<&str>, proxy: Option<&str>) -> MatrixConfig {
+        MatrixConfig {
+            homeserver: "https://matrix.example.com".into(),
+            access_token: "syt_token".into(),
+            user_id: None,
+            device_id: None,
+            room_id: "!listen:matrix.example.com".into(),
+            allowed_users: vec![],
+            notify_room_id: notify_room_id.map(String::from),
+            notify_proxy_url: proxy.map(String::from),
+        }
+    }
+
+    #[test]
+    fn from_config_requires_notify_room() {
+        let security = test_security(AutonomyLevel::Full, 100);
+        assert!(
+            MatrixNotifyTool::from_config(security.clone(), &matrix_config(None, None)).is_none()
+        );
+
+        let tool = MatrixNotifyTool::from_config(
+            security,
+            &matrix_config(Some("#alerts:matrix.example.com"), None),
+        )
+        .unwrap();
+        assert_eq!(tool.name(), "matrix_notify");
+        assert_eq!(tool.base_url, "https://matrix.example.com");
+        assert!(!tool.via_proxy);
+    }
+
+    #[test]
+    fn proxy_url_replaces_homeserver() {
+        let tool = MatrixNotifyTool::from_config(
+            test_security(AutonomyLevel::Full, 100),
+            &matrix_config(
+                Some("!alerts:matrix.example.com"),
+                Some("http://127.0.0.1:8009/"),
+            ),
+        )
+        .unwrap();
+        assert!(tool.via_proxy);
+        assert_eq!(
+            tool.room_url("!alerts:matrix.example.com", "send/m.room.message/1"),
+            "http://127.0.0.1:8009/_matrix/client/v3/rooms/%21alerts%3Amatrix.example.com/send/m.room.message/1"
+        );
+    }
+
+    #[tokio::test]
+    async fn execute_blocks_readonly_mode() {
+        let tool = MatrixNotifyTool::from_config(
+            test_security(AutonomyLevel::ReadOnly, 100),
+            &matrix_config(Some("!alerts:matrix.example.com"), None),
+        )
+        .unwrap();
+        let result = tool.execute(json!({"message": "hello"})).await.unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("read-only"));
+    }
+
+    #[tokio::test]
+    async fn execute_blocks_rate_limit() {
+        let tool = MatrixNotifyTool::from_config(
+            test_security(AutonomyLevel::Full, 0),
+            &matrix_config(Some("!alerts:matrix.example.com"), None),
+        )
+        .unwrap();
+        let result = tool.execute(json!({"message": "hello"})).await.unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("rate limit"));
+    }
+}
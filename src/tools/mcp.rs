@@ -0,0 +1,749 @@
+//! MCP (Model Context Protocol) client.
+//!
+//! Connects to the servers listed under `[mcp]`, imports their tools and
+//! registers each one as an [`McpTool`] that proxies `execute()` to the
+//! server's `tools/call`. Two transports are supported:
+//!
+//! - `stdio`: spawn a local command and exchange newline-delimited JSON-RPC.
+//! - `http`: streamable HTTP; responses may be plain JSON or SSE-framed.
+
+use super::traits::{Tool, ToolResult};
+use crate::config::{McpConfig, McpServerConfig};
+use crate::security::SecurityPolicy;
+use async_trait::async_trait;
+use reqwest::Client;
+use serde_json::{json, Value};
+use std::collections::{HashMap, HashSet};
+use std::process::Stdio;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::process::{Child, ChildStdin, ChildStdout};
+use tokio::sync::Mutex;
+
+const MCP_PROTOCOL_VERSION: &str = "2025-03-26";
+/// Upper bound on `tools/list` pages fetched from one server.
+const MCP_MAX_LIST_PAGES: usize = 20;
+/// Provider APIs reject function names longer than this.
+const MCP_MAX_TOOL_NAME_LEN: usize = 64;
+/// Environment variables forwarded to stdio servers besides the configured `env`.
+/// Only functional variables are included — never API keys or secrets.
+const MCP_SAFE_ENV_VARS: &[&str] = &[
+    "PATH",
+    "HOME",
+    "LANG",
+    "LC_ALL",
+    "USER",
+    "TMPDIR",
+    "SYSTEMROOT",
+];
+
+enum McpTransport {
+    Stdio {
+        // Held so the server is killed when the client is dropped.
+        _child: Child,
+        stdin: ChildStdin,
+        stdout: BufReader<ChildStdout>,
+    },
+    Http {
+        client: Client,
+        url: String,
+        headers: HashMap<String, String>,
+        session_id: Option<String>,
+    },
+}
+
+impl McpTransport {
+    async fn send(&mut self, message: &Value) -> anyhow::Result<()> {
+        match self {
+            Self::Stdio { stdin, .. } => {
+                let mut line = serde_json::to_string(message)?;
+                line.push('\n');
+                stdin.write_all(line.as_bytes()).await?;
+                stdin.flush().await?;
+                Ok(())
+            }
+            Self::Http { .. } => {
+                self.post(message).await?;
+                Ok(())
+            }
+        }
+    }
+
+    /// Send a request and wait for the response carrying the same id.
+    async fn round_trip(&mut self, id: u64, message: &Value) -> anyhow::Result<Value> {
+        match self {
+            Self::Stdio { .. } => {
+                self.send(message).await?;
+                self.read_stdio_response(id).await
+            }
+            Self::Http { .. } => {
+                let body = self.post(message).await?;
+                find_response(&body, id)
+                    .ok_or_else(|| anyhow::anyhow!("MCP server sent no response for request {id}"))
+            }
+        }
+    }
+
+    async fn read_stdio_response(&mut self, id: u64) -> anyhow::Result<Value> {
+        loop {
+            let Self::Stdio { stdin, stdout, .. } = self else {
+                unreachable!("read_stdio_response on a non-stdio transport");
+            };
+            let mut line = String::new();
+            if stdout.read_line(&mut line).await? == 0 {
+                anyhow::bail!("MCP server closed its stdout");
+            }
+            let Ok(message) = serde_json::from_str::<Value>(line.trim()) else {
+                // Servers occasionally print log lines to stdout; skip them.
+                continue;
+            };
+
+            if message.get("method").is_some() {
+                // Server-initiated request: answer pings, decline everything else.
+                if let Some(request_id) = message.get("id") {
+                    let reply = if message["method"] == "ping" {
+                        json!({"jsonrpc": "2.0", "id": request_id, "result": {}})
+                    } else {
+                        json!({
+                            "jsonrpc": "2.0",
+                            "id": request_id,
+                            "error": {"code": -32601, "message": "Method not supported by client"}
+                        })
+                    };
+                    let mut reply_line = serde_json::to_string(&reply)?;
+                    reply_line.push('\n');
+                    stdin.write_all(reply_line.as_bytes()).await?;
+                    stdin.flush().await?;
+                }
+                continue;
+            }
+
+            if message.get("id").and_then(Value::as_u64) == Some(id) {
+                return Ok(message);
+            }
+        }
+    }
+
+    /// POST one JSON-RPC message and return the raw response body.
+    async fn post(&mut self, message: &Value) -> anyhow::Result<String> {
+        let Self::Http {
+            client,
+            url,
+            headers,
+            session_id,
+        } = self
+        else {
+            unreachable!("post on a non-http transport");
+        };
+
+        let mut request = client
+            .post(url.as_str())
+            .header("Accept", "application/json, text/event-stream")
+            .json(message);
+        for (name, value) in headers.iter() {
+            request = request.header(name.as_str(), value.as_str());
+        }
+        if let Some(session) = session_id.as_deref() {
+            request = request.header("Mcp-Session-Id", session);
+        }
+
+        let response = request.send().await?;
+        let status = response.status();
+        if let Some(session) = response
+            .headers()
+            .get("mcp-session-id")
+            .and_then(|v| v.to_str().ok())
+        {
+            *session_id = Some(session.to_string());
+        }
+        let body = response.text().await.unwrap_or_default();
+        if !status.is_success() {
+            anyhow::bail!("MCP server returned status {status}: {body}");
+        }
+        Ok(body)
+    }
+}
+
+/// Locate the JSON-RPC response with `id` in an HTTP body, which is either a
+/// JSON object, a JSON batch, or an SSE stream of `data:` events.
+fn find_response(body: &str, id: u64) -> Option<Value> {
+    let matches = |message: &Value| message.get("id").and_then(Value::as_u64) == Some(id);
+    let pick = |value: Value| match value {
+        Value::Array(items) => items.into_iter().find(|m| matches(m)),
+        other => matches(&other).then_some(other),
+    };
+
+    if let Ok(value) = serde_json::from_str::<Value>(body.trim()) {
+        return pick(value);
+    }
+
+    let mut data = String::new();
+    for line in body.lines().chain(std::iter::once("")) {
+        if let Some(chunk) = line.strip_prefix("data:") {
+            if !data.is_empty() {
+                data.push('\n');
+            }
+            data.push_str(chunk.strip_prefix(' ').unwrap_or(chunk));
+        } else if line.trim().is_empty() && !data.is_empty() {
+            let event = std::mem::take(&mut data);
+            if let Some(found) = serde_json::from_str::<Value>(&event).ok().and_then(pick) {
+                return Some(found);
+            }
+        }
+    }
+    None
+}
+
+/// A connected MCP server session.
+pub struct McpClient {
+    server: String,
+    timeout: Duration,
+    next_id: AtomicU64,
+    transport: Mutex<McpTransport>,
+}
+
+impl McpClient {
+    /// Start the transport and perform the `initialize` handshake.
+    pub async fn connect(config: &McpServerConfig) -> anyhow::Result<Self> {
+        validate_server_name(&config.name)?;
+        let transport = match config.transport.trim() {
+            "stdio" => Self::spawn_stdio(config)?,
+            "http" => {
+                let url = config
+                    .url
+                    .as_deref()
+                    .map(str::trim)
+                    .filter(|u| !u.is_empty())
+                    .ok_or_else(|| {
+                        anyhow::anyhow!("MCP server '{}' uses http but has no url", config.name)
+                    })?;
+                if !url.starts_with("http://") && !url.starts_with("https://") {
+                    anyhow::bail!("MCP server '{}' url must be http(s)", config.name);
+                }
+                McpTransport::Http {
+                    client: Client::new(),
+                    url: url.to_string(),
+                    headers: config.headers.clone(),
+                    session_id: None,
+                }
+            }
+            other => anyhow::bail!(
+                "MCP server '{}' has unknown transport '{other}' (expected stdio or http)",
+                config.name
+            ),
+        };
+
+        let client = Self {
+            server: config.name.clone(),
+            timeout: Duration::from_secs(config.timeout_secs.max(1)),
+            next_id: AtomicU64::new(1),
+            transport: Mutex::new(transport),
+        };
+        client.initialize().await?;
+        Ok(client)
+    }
+
+    fn spawn_stdio(config: &McpServerConfig) -> anyhow::Result<McpTransport> {
+        let command = config
+            .command
+            .as_deref()
+            .map(str::trim)
+            .filter(|c| !c.is_empty())
+            .ok_or_else(|| {
+                anyhow::anyhow!("MCP server '{}' uses stdio but has no command", config.name)
+            })?;
+
+        let mut cmd = tokio::process::Command::new(command);
+        cmd.args(&config.args)
+            .env_clear()
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .kill_on_drop(true);
+        for var in MCP_SAFE_ENV_VARS {
+            if let Ok(value) = std::env::var(var) {
+                cmd.env(var, value);
+            }
+        }
+        cmd.envs(&config.env);
+
+        let mut child = cmd.spawn().map_err(|e| {
+            anyhow::anyhow!(
+                "Failed to start MCP server '{}' ({command}): {e}",
+                config.name
+            )
+        })?;
+        let stdin = child
+            .stdin
+            .take()
+            .ok_or_else(|| anyhow::anyhow!("MCP server '{}' has no stdin", config.name))?;
+        let stdout = child
+            .stdout
+            .take()
+            .ok_or_else(|| anyhow::anyhow!("MCP server '{}' has no stdout", config.name))?;
+
+        Ok(McpTransport::Stdio {
+            _child: child,
+            stdin,
+            stdout: BufReader::new(stdout),
+        })
+    }
+
+    async fn initialize(&self) -> anyhow::Result<()> {
+        self.request(
+            "initialize",
+            json!({
+                "protocolVersion": MCP_PROTOCOL_VERSION,
+                "capabilities": {},
+                "clientInfo": {
+                    "name": "zeroclaw",
+                    "version": env!("CARGO_PKG_VERSION")
+                }
+            }),
+        )
+        .await?;
+        self.notify("notifications/initialized").await
+    }
+
+    async fn request(&self, method: &str, params: Value) -> anyhow::Result<Value> {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let message = json!({"jsonrpc": "2.0", "id": id, "method": method, "params": params});
+
+        let mut transport = self.transport.lock().await;
+        let response = tokio::time::timeout(self.timeout, transport.round_trip(id, &message))
+            .await
+            .map_err(|_| {
+                anyhow::anyhow!(
+                    "MCP server '{}' timed out on {method} after {}s",
+                    self.server,
+                    self.timeout.as_secs()
+                )
+            })?
+            .map_err(|e| anyhow::anyhow!("MCP server '{}' {method} failed: {e}", self.server))?;
+
+        if let Some(error) = response.get("error") {
+            let message = error
+                .get("message")
+                .and_then(Value::as_str)
+                .unwrap_or("unknown error");
+            anyhow::bail!("MCP server '{}' rejected {method}: {message}", self.server);
+        }
+        Ok(response.get("result").cloned().unwrap_or(Value::Null))
+    }
+
+    async fn notify(&self, method: &str) -> anyhow::Result<()> {
+        let message = json!({"jsonrpc": "2.0", "method": method});
+        let mut transport = self.transport.lock().await;
+        tokio::time::timeout(self.timeout, transport.send(&message))
+            .await
+            .map_err(|_| anyhow::anyhow!("MCP server '{}' timed out on {method}", self.server))?
+    }
+
+    /// Fetch every tool the server advertises, following `nextCursor` pages.
+    pub async fn list_tools(&self) -> anyhow::Result<Vec<Value>> {
+        let mut tools = Vec::new();
+        let mut cursor: Option<String> = None;
+        for _ in 0..MCP_MAX_LIST_PAGES {
+            let params = match &cursor {
+                Some(cursor) => json!({ "cursor": cursor }),
+                None => json!({}),
+            };
+            let result = self.request("tools/list", params).await?;
+            if let Some(items) = result.get("tools").and_then(Value::as_array) {
+                tools.extend(items.iter().cloned());
+            }
+            cursor = result
+                .get("nextCursor")
+                .and_then(Value::as_str)
+                .filter(|c| !c.is_empty())
+                .map(String::from);
+            if cursor.is_none() {
+                break;
+            }
+        }
+        Ok(tools)
+    }
+
+    pub async fn call_tool(&self, name: &str, arguments: Value) -> anyhow::Result<Value> {
+        self.request(
+            "tools/call",
+            json!({ "name": name, "arguments": arguments }),
+        )
+        .await
+    }
+}
+
+fn validate_server_name(name: &str) -> anyhow::Result<()> {
+    if name.is_empty()
+        || !name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+    {
+        anyhow::bail!(
+            "MCP server name must be non-empty and use only [A-Za-z0-9_-], got: {name:?}"
+        );
+    }
+    Ok(())
+}
+
+/// Registry name for a remote tool: `mcp_<server>_<tool>`, restricted to the
+/// characters and length every provider accepts for function names.
+fn local_tool_name(server: &str, remote: &str) -> String {
+    let mut name: String = format!("mcp_{server}_{remote}")
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '_' || c == '-' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    name.truncate(MCP_MAX_TOOL_NAME_LEN);
+    name
+}
+
+/// Make sure the advertised input schema is an object schema with properties.
+fn normalize_schema(schema: Option<&Value>) -> Value {
+    let mut schema = match schema {
+        Some(Value::Object(map)) => Value::Object(map.clone()),
+        _ => json!({}),
+    };
+    schema["type"] = json!("object");
+    if !schema.get("properties").is_some_and(Value::is_object) {
+        schema["properties"] = json!({});
+    }
+    schema
+}
+
+/// Flatten a `tools/call` result into text for the model.
+fn render_call_result(result: &Value) -> String {
+    let mut parts: Vec<String> = Vec::new();
+    for item in result
+        .get("content")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+    {
+        let part = match item.get("type").and_then(Value::as_str) {
+            Some("text") => item
+                .get("text")
+                .and_then(Value::as_str)
+                .unwrap_or_default()
+                .to_string(),
+            Some("image" | "audio") => format!(
+                "[{} content: {}]",
+                item["type"].as_str().unwrap_or_default(),
+                item.get("mimeType")
+                    .and_then(Value::as_str)
+                    .unwrap_or("unknown type")
+            ),
+            Some("resource") => {
+                let resource = &item["resource"];
+                match resource.get("text").and_then(Value::as_str) {
+                    Some(text) => text.to_string(),
+                    None => format!(
+                        "[resource: {}]",
+                        resource.get("uri").and_then(Value::as_str).unwrap_or("?")
+                    ),
+                }
+            }
+            _ => item.to_string(),
+        };
+        parts.push(part);
+    }
+
+    if parts.is_empty() {
+        if let Some(structured) = result.get("structuredContent") {
+            return serde_json::to_string_pretty(structured).unwrap_or_default();
+        }
+    }
+    parts.join("\n")
+}
+
+/// A tool imported from an MCP server; `execute()` is proxied to `tools/call`.
+pub struct McpTool {
+    client: Arc<McpClient>,
+    security: Arc<SecurityPolicy>,
+    name: String,
+    remote_name: String,
+    description: String,
+    schema: Value,
+}
+
+impl McpTool {
+    fn from_definition(
+        client: Arc<McpClient>,
+        security: Arc<SecurityPolicy>,
+        definition: &Value,
+    ) -> Option<Self> {
+        let remote_name = definition.get("name").and_then(Value::as_str)?.to_string();
+        let description = definition
+            .get("description")
+            .and_then(Value::as_str)
+            .map(str::trim)
+            .filter(|d| !d.is_empty())
+            .map_or_else(
+                || format!("[MCP {}] {remote_name}", client.server),
+                |d| format!("[MCP {}] {d}", client.server),
+            );
+        Some(Self {
+            name: local_tool_name(&client.server, &remote_name),
+            schema: normalize_schema(definition.get("inputSchema")),
+            client,
+            security,
+            remote_name,
+            description,
+        })
+    }
+}
+
+#[async_trait]
+impl Tool for McpTool {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn description(&self) -> &str {
+        &self.description
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        self.schema.clone()
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        // Remote tools can have arbitrary side effects; gate them like any action.
+        if !self.security.can_act() {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some("Action blocked: autonomy is read-only".into()),
+            });
+        }
+
+        if !self.security.record_action() {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some("Action blocked: rate limit exceeded".into()),
+            });
+        }
+
+        let arguments = if args.is_object() { args } else { json!({}) };
+        match self.client.call_tool(&self.remote_name, arguments).await {
+            Ok(result) => {
+                let output = render_call_result(&result);
+                if result.get("isError").and_then(Value::as_bool) == Some(true) {
+                    Ok(ToolResult {
+                        success: false,
+                        output: String::new(),
+                        error: Some(if output.is_empty() {
+                            format!("MCP tool {} reported an error", self.remote_name)
+                        } else {
+                            output
+                        }),
+                    })
+                } else {
+                    Ok(ToolResult {
+                        success: true,
+                        output,
+                        error: None,
+                    })
+                }
+            }
+            Err(e) => Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(e.to_string()),
+            }),
+        }
+    }
+}
+
+/// Connect to every configured MCP server and wrap its tools.
+///
+/// A server that fails to start or handshake is logged and skipped so one
+/// broken entry cannot take the whole agent down.
+pub async fn create_mcp_tools(
+    config: &McpConfig,
+    security: &Arc<SecurityPolicy>,
+) -> Vec<Box<dyn Tool>> {
+    if !config.enabled || config.servers.is_empty() {
+        return Vec::new();
+    }
+
+    let mut tools: Vec<Box<dyn Tool>> = Vec::new();
+    let mut seen = HashSet::new();
+    for server in &config.servers {
+        let client = match McpClient::connect(server).await {
+            Ok(client) => Arc::new(client),
+            Err(e) => {
+                tracing::warn!("Skipping MCP server {}: {e}", server.name);
+                continue;
+            }
+        };
+        let definitions = match client.list_tools().await {
+            Ok(definitions) => definitions,
+            Err(e) => {
+                tracing::warn!("Skipping MCP server {}: {e}", server.name);
+                continue;
+            }
+        };
+
+        let mut imported = 0usize;
+        for definition in &definitions {
+            let Some(tool) = McpTool::from_definition(client.clone(), security.clone(), definition)
+            else {
+                continue;
+            };
+            if !server.allowed_tools.is_empty()
+                && !server.allowed_tools.iter().any(|t| t == &tool.remote_name)
+            {
+                continue;
+            }
+            if !seen.insert(tool.name.clone()) {
+                tracing::warn!(tool = %tool.name, "Duplicate MCP tool name skipped");
+                continue;
+            }
+            tools.push(Box::new(tool));
+            imported += 1;
+        }
+        tracing::info!(server = %server.name, count = imported, "MCP tools imported");
+    }
+    tools
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::security::AutonomyLevel;
+
+    #[test]
+    fn local_tool_name_is_prefixed_and_sanitized() {
+        assert_eq!(
+            local_tool_name("github", "search.issues"),
+            "mcp_github_search_issues"
+        );
+        let long = local_tool_name("srv", &"x".repeat(100));
+        assert_eq!(long.len(), MCP_MAX_TOOL_NAME_LEN);
+    }
+
+    #[test]
+    fn normalize_schema_always_has_object_properties() {
+        assert_eq!(
+            normalize_schema(None),
+            json!({"type": "object", "properties": {}})
+        );
+        let schema = normalize_schema(Some(&json!({
+            "type": "object",
+            "properties": {"q": {"type": "string"}},
+            "required": ["q"]
+        })));
+        assert_eq!(schema["properties"]["q"]["type"], "string");
+        assert_eq!(schema["required"], json!(["q"]));
+    }
+
+    #[test]
+    fn find_response_handles_json_and_sse_bodies() {
+        let json_body = r#"{"jsonrpc":"2.0","id":3,"result":{"ok":true}}"#;
+        assert_eq!(find_response(json_body, 3).unwrap()["result"]["ok"], true);
+        assert!(find_response(json_body, 4).is_none());
+
+        let sse_body = "event: message\ndata: {\"jsonrpc\":\"2.0\",\"method\":\"notifications/progress\"}\n\nevent: message\ndata: {\"jsonrpc\":\"2.0\",\"id\":7,\"result\":{}}\n\n";
+        assert!(find_response(sse_body, 7).is_some());
+    }
+
+    #[test]
+    fn render_call_result_joins_content_blocks() {
+        let result = json!({
+            "content": [
+                {"type": "text", "text": "first"},
+                {"type": "image", "data": "AAAA", "mimeType": "image/png"},
+                {"type": "resource", "resource": {"uri": "file:///a.txt", "text": "inline"}}
+            ]
+        });
+        assert_eq!(
+            render_call_result(&result),
+            "first\n[image content: image/png]\ninline"
+        );
+        assert_eq!(
+            render_call_result(&json!({"content": [], "structuredContent": {"n": 1}})),
+            "{\n  \"n\": 1\n}"
+        );
+    }
+
+    #[tokio::test]
+    async fn connect_rejects_incomplete_server_config() {
+        let missing_command = McpServerConfig {
+            name: "local".into(),
+            ..McpServerConfig::default()
+        };
+        let err = McpClient::connect(&missing_command).await.err().unwrap();
+        assert!(err.to_string().contains("no command"));
+
+        let bad_name = McpServerConfig {
+            name: "has space".into(),
+            command: Some("true".into()),
+            ..McpServerConfig::default()
+        };
+        assert!(McpClient::connect(&bad_name).await.is_err());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn stdio_server_tools_are_imported_and_called() {
+        // Minimal scripted server: initialize (id 1), initialized notification,
+        // tools/list (id 2), tools/call (id 3).
+        let script = r#"read l; echo '{"jsonrpc":"2.0","id":1,"result":{"protocolVersion":"2025-03-26","capabilities":{"tools":{}},"serverInfo":{"name":"fake","version":"0"}}}'
+read l
+read l; echo '{"jsonrpc":"2.0","id":2,"result":{"tools":[{"name":"echo","description":"Echo text","inputSchema":{"type":"object","properties":{"text":{"type":"string"}}}}]}}'
+read l; echo '{"jsonrpc":"2.0","id":3,"result":{"content":[{"type":"text","text":"pong"}]}}'
+"#;
+        let config = McpConfig {
+            enabled: true,
+            servers: vec![McpServerConfig {
+                name: "fake".into(),
+                command: Some("sh".into()),
+                args: vec!["-c".into(), script.into()],
+                timeout_secs: 10,
+                ..McpServerConfig::default()
+            }],
+        };
+        let security = Arc::new(SecurityPolicy::default());
+
+        let tools = create_mcp_tools(&config, &security).await;
+        assert_eq!(tools.len(), 1);
+        assert_eq!(tools[0].name(), "mcp_fake_echo");
+        assert_eq!(tools[0].description(), "[MCP fake] Echo text");
+
+        let result = tools[0].execute(json!({"text": "ping"})).await.unwrap();
+        assert!(result.success, "{:?}", result.error);
+        assert_eq!(result.output, "pong");
+    }
+
+    #[tokio::test]
+    async fn execute_blocks_readonly_mode() {
+        let client = Arc::new(McpClient {
+            server: "fake".into(),
+            timeout: Duration::from_secs(1),
+            next_id: AtomicU64::new(1),
+            transport: Mutex::new(McpTransport::Http {
+                client: Client::new(),
+                url: "http://127.0.0.1:9/mcp".into(),
+                headers: HashMap::new(),
+                session_id: None,
+            }),
+        });
+        let security = Arc::new(SecurityPolicy {
+            autonomy: AutonomyLevel::ReadOnly,
+            ..SecurityPolicy::default()
+        });
+        let tool = McpTool::from_definition(client, security, &json!({"name": "echo"})).unwrap();
+        let result = tool.execute(json!({})).await.unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("read-only"));
+    }
+}
@@ -1,5 +1,5 @@
 use super::traits::{Tool, ToolResult};
-use crate::memory::Memory;
+use crate::memory::{Memory, MemoryCategory, MemoryEntry};
 use async_trait::async_trait;
 use serde_json::json;
 use std::fmt::Write;
@@ -14,6 +14,21 @@ impl MemoryRecallTool {
     pub fn new(memory: Arc<dyn Memory>) -> Self {
         Self { memory }
     }
+
+    fn format_entries(entries: &[MemoryEntry]) -> String {
+        let mut output = format!("Found {} memories:\n", entries.len());
+        for entry in entries {
+            let score = entry
+                .score
+                .map_or_else(String::new, |s| format!(" [{s:.0}%]"));
+            let _ = writeln!(
+                output,
+                "- [{}] {}: {}{score}",
+                entry.category, entry.key, entry.content
+            );
+        }
+        output
+    }
 }
 
 #[async_trait]
@@ -23,7 +38,7 @@ impl Tool for MemoryRecallTool {
     }
 
     fn description(&self) -> &str {
-        "Search long-term memory for relevant facts, preferences, or context. Returns scored results ranked by relevance."
+        "Search long-term memory for relevant facts, preferences, or context. Returns scored results ranked by relevance. Pass 'key' to fetch one memory exactly, or 'category' to narrow (or, without a query, list) a category."
     }
 
     fn parameters_schema(&self) -> serde_json::Value {
@@ -34,20 +49,55 @@ impl Tool for MemoryRecallTool {
                     "type": "string",
                     "description": "Keywords or phrase to search for in memory"
                 },
+                "key": {
+                    "type": "string",
+                    "description": "Exact key of a stored memory to fetch instead of searching"
+                },
+                "category": {
+                    "type": "string",
+                    "description": "Only return memories in this category ('core', 'daily', 'conversation', or a custom name)"
+                },
                 "limit": {
                     "type": "integer",
                     "description": "Max results to return (default: 5)"
                 }
-            },
-            "required": ["query"]
+            }
         })
     }
 
     async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
-        let query = args
-            .get("query")
-            .and_then(|v| v.as_str())
-            .ok_or_else(|| anyhow::anyhow!("Missing 'query' parameter"))?;
+        let text_arg = |name: &str| {
+            args.get(name)
+                .and_then(|v| v.as_str())
+                .map(str::trim)
+                .filter(|v| !v.is_empty())
+        };
+
+        if let Some(key) = text_arg("key") {
+            return match self.memory.get(key).await {
+                Ok(Some(entry)) => Ok(ToolResult {
+                    success: true,
+                    output: Self::format_entries(std::slice::from_ref(&entry)),
+                    error: None,
+                }),
+                Ok(None) => Ok(ToolResult {
+                    success: true,
+                    output: format!("No memory stored under key '{key}'."),
+                    error: None,
+                }),
+                Err(e) => Ok(ToolResult {
+                    success: false,
+                    output: String::new(),
+                    error: Some(format!("Memory recall failed: {e}")),
+                }),
+            };
+        }
+
+        let category = text_arg("category").map(MemoryCategory::from_name);
+        let query = text_arg("query");
+        if query.is_none() && category.is_none() {
+            anyhow::bail!("Missing 'query' parameter");
+        }
 
         #[allow(clippy::cast_possible_truncation)]
         let limit = args
@@ -55,30 +105,38 @@ impl Tool for MemoryRecallTool {
             .and_then(serde_json::Value::as_u64)
             .map_or(5, |v| v as usize);
 
-        match self.memory.recall(query, limit, None).await {
+        let found = match (query, &category) {
+            (Some(query), None) => self.memory.recall(query, limit, None).await,
+            // Over-fetch so filtering by category still fills the limit.
+            (Some(query), Some(category)) => self
+                .memory
+                .recall(query, limit.saturating_mul(4), None)
+                .await
+                .map(|entries| {
+                    entries
+                        .into_iter()
+                        .filter(|entry| &entry.category == category)
+                        .take(limit)
+                        .collect()
+                }),
+            (None, category) => self
+                .memory
+                .list(category.as_ref(), None)
+                .await
+                .map(|entries| entries.into_iter().take(limit).collect()),
+        };
+
+        match found {
             Ok(entries) if entries.is_empty() => Ok(ToolResult {
                 success: true,
                 output: "No memories found matching that query.".into(),
                 error: None,
             }),
-            Ok(entries) => {
-                let mut output = format!("Found {} memories:\n", entries.len());
-                for entry in &entries {
-                    let score = entry
-                        .score
-                        .map_or_else(String::new, |s| format!(" [{s:.0}%]"));
-                    let _ = writeln!(
-                        output,
-                        "- [{}] {}: {}{score}",
-                        entry.category, entry.key, entry.content
-                    );
-                }
-                Ok(ToolResult {
-                    success: true,
-                    output,
-                    error: None,
-                })
-            }
+            Ok(entries) => Ok(ToolResult {
+                success: true,
+                output: Self::format_entries(&entries),
+                error: None,
+            }),
             Err(e) => Ok(ToolResult {
                 success: false,
                 output: String::new(),
@@ -91,7 +149,7 @@ impl Tool for MemoryRecallTool {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::memory::{MemoryCategory, SqliteMemory};
+    use crate::memory::SqliteMemory;
     use tempfile::TempDir;
 
     fn seeded_mem() -> (TempDir, Arc<dyn Memory>) {
@@ -157,6 +215,48 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[tokio::test]
+    async fn recall_by_exact_key() {
+        let (_tmp, mem) = seeded_mem();
+        mem.store("tz", "Timezone is EST", MemoryCategory::Core, None)
+            .await
+            .unwrap();
+
+        let tool = MemoryRecallTool::new(mem);
+        let result = tool.execute(json!({"key": "tz"})).await.unwrap();
+        assert!(result.success);
+        assert!(result.output.contains("tz: Timezone is EST"));
+
+        let result = tool.execute(json!({"key": "missing"})).await.unwrap();
+        assert!(result.success);
+        assert!(result
+            .output
+            .contains("No memory stored under key 'missing'"));
+    }
+
+    #[tokio::test]
+    async fn recall_filters_by_category() {
+        let (_tmp, mem) = seeded_mem();
+        mem.store("lang", "User prefers Rust", MemoryCategory::Core, None)
+            .await
+            .unwrap();
+        mem.store("today", "Rust workshop at 3pm", MemoryCategory::Daily, None)
+            .await
+            .unwrap();
+
+        let tool = MemoryRecallTool::new(mem);
+        let result = tool
+            .execute(json!({"query": "Rust", "category": "daily"}))
+            .await
+            .unwrap();
+        assert!(result.output.contains("Found 1"));
+        assert!(result.output.contains("today"));
+
+        let result = tool.execute(json!({"category": "core"})).await.unwrap();
+        assert!(result.output.contains("Found 1"));
+        assert!(result.output.contains("lang"));
+    }
+
     #[test]
     fn name_and_schema() {
         let (_tmp, mem) = seeded_mem();
@@ -0,0 +1,456 @@
+use super::traits::{Tool, ToolResult};
+use crate::memory::chunker::chunk_markdown;
+use crate::memory::{Memory, MemoryCategory, MemoryEntry};
+use crate::security::policy::ToolOperation;
+use crate::security::SecurityPolicy;
+use async_trait::async_trait;
+use serde_json::json;
+use std::collections::HashSet;
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// Memory category holding indexed workspace file chunks.
+const WORKSPACE_CATEGORY: &str = "workspace";
+/// Key prefix for workspace chunks: `workspace:<relative path>#<chunk index>`.
+const WORKSPACE_KEY_PREFIX: &str = "workspace:";
+const INDEXED_EXTENSIONS: &[&str] = &["md", "markdown", "txt"];
+const MAX_INDEX_FILES: usize = 500;
+const MAX_INDEX_FILE_BYTES: u64 = 512 * 1024;
+const DEFAULT_LIMIT: usize = 5;
+
+/// Semantic search over long-term memory and indexed workspace notes.
+///
+/// Workspace files are chunked and stored in the memory backend under the
+/// `workspace` category, so they share its embeddings and hybrid ranking.
+pub struct MemorySearchTool {
+    memory: Arc<dyn Memory>,
+    security: Arc<SecurityPolicy>,
+    chunk_max_tokens: usize,
+}
+
+impl MemorySearchTool {
+    pub fn new(
+        memory: Arc<dyn Memory>,
+        security: Arc<SecurityPolicy>,
+        chunk_max_tokens: usize,
+    ) -> Self {
+        Self {
+            memory,
+            security,
+            chunk_max_tokens,
+        }
+    }
+
+    fn workspace_category() -> MemoryCategory {
+        MemoryCategory::Custom(WORKSPACE_CATEGORY.into())
+    }
+
+    fn chunk_key(relative: &str, index: usize) -> String {
+        format!("{WORKSPACE_KEY_PREFIX}{relative}#{index}")
+    }
+
+    async fn search(&self, args: &serde_json::Value) -> anyhow::Result<ToolResult> {
+        let query = args
+            .get("query")
+            .and_then(|v| v.as_str())
+            .map(str::trim)
+            .filter(|q| !q.is_empty())
+            .ok_or_else(|| anyhow::anyhow!("Missing 'query' parameter"))?;
+
+        #[allow(clippy::cast_possible_truncation)]
+        let limit = args
+            .get("limit")
+            .and_then(serde_json::Value::as_u64)
+            .map_or(DEFAULT_LIMIT, |v| (v as usize).max(1));
+
+        let scope = args.get("scope").and_then(|v| v.as_str()).unwrap_or("all");
+        if !matches!(scope, "all" | "memory" | "workspace") {
+            anyhow::bail!("Unknown scope '{scope}'. Supported: all, memory, workspace");
+        }
+        let workspace = Self::workspace_category();
+
+        // Over-fetch so scoping still fills the limit.
+        let entries = match self
+            .memory
+            .recall(query, limit.saturating_mul(4), None)
+            .await
+        {
+            Ok(entries) => entries,
+            Err(e) => {
+                return Ok(ToolResult {
+                    success: false,
+                    output: String::new(),
+                    error: Some(format!("Memory search failed: {e}")),
+                });
+            }
+        };
+        let entries: Vec<MemoryEntry> = entries
+            .into_iter()
+            .filter(|entry| match scope {
+                "memory" => entry.category != workspace,
+                "workspace" => entry.category == workspace,
+                _ => true,
+            })
+            .take(limit)
+            .collect();
+
+        if entries.is_empty() {
+            return Ok(ToolResult {
+                success: true,
+                output:
+                    "No results found. If you expected workspace notes, run action=index first."
+                        .into(),
+                error: None,
+            });
+        }
+
+        let mut output = format!("Found {} results:\n", entries.len());
+        for entry in &entries {
+            let score = entry
+                .score
+                .map_or_else(String::new, |s| format!(" [{s:.0}%]"));
+            let source = entry
+                .key
+                .strip_prefix(WORKSPACE_KEY_PREFIX)
+                .map_or_else(|| format!("memory:{}", entry.key), str::to_string);
+            let _ = writeln!(output, "- {source}{score}\n  {}", entry.content.trim());
+        }
+        Ok(ToolResult {
+            success: true,
+            output,
+            error: None,
+        })
+    }
+
+    async fn index(&self, args: &serde_json::Value) -> anyhow::Result<ToolResult> {
+        let path = args.get("path").and_then(|v| v.as_str()).unwrap_or(".");
+
+        if let Err(error) = self
+            .security
+            .enforce_tool_operation(ToolOperation::Act, "memory_search.index")
+        {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(error),
+            });
+        }
+
+        if !self.security.is_path_allowed(path) {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(format!("Path not allowed by security policy: {path}")),
+            });
+        }
+
+        let workspace_root = match tokio::fs::canonicalize(&self.security.workspace_dir).await {
+            Ok(p) => p,
+            Err(e) => {
+                return Ok(ToolResult {
+                    success: false,
+                    output: String::new(),
+                    error: Some(format!("Failed to resolve workspace: {e}")),
+                });
+            }
+        };
+        let resolved = match tokio::fs::canonicalize(self.security.workspace_dir.join(path)).await {
+            Ok(p) => p,
+            Err(e) => {
+                return Ok(ToolResult {
+                    success: false,
+                    output: String::new(),
+                    error: Some(format!("Failed to resolve index path: {e}")),
+                });
+            }
+        };
+
+        // Keys are workspace-relative, so indexing stays inside the workspace.
+        let Ok(relative_root) = resolved.strip_prefix(&workspace_root) else {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(self.security.resolved_path_violation_message(&resolved)),
+            });
+        };
+        let relative_root = relative_root.to_string_lossy().replace('\\', "/");
+
+        let (files, truncated) = collect_indexable_files(&resolved).await?;
+
+        let mut live_keys = HashSet::new();
+        let mut chunk_count = 0usize;
+        for file in &files {
+            let Ok(text) = tokio::fs::read_to_string(file).await else {
+                continue;
+            };
+            let relative = file
+                .strip_prefix(&workspace_root)
+                .unwrap_or(file)
+                .to_string_lossy()
+                .replace('\\', "/");
+            // `Chunk` holds an `Rc`, so keep only owned parts across awaits.
+            let chunks: Vec<(usize, String)> = chunk_markdown(&text, self.chunk_max_tokens)
+                .into_iter()
+                .map(|chunk| (chunk.index, chunk.content))
+                .collect();
+            for (index, content) in chunks {
+                let key = Self::chunk_key(&relative, index);
+                self.memory
+                    .store(&key, &content, Self::workspace_category(), None)
+                    .await?;
+                live_keys.insert(key);
+                chunk_count += 1;
+            }
+        }
+
+        // Drop chunks for files that were deleted or shrank since the last run.
+        let scope_prefix = if relative_root.is_empty() {
+            WORKSPACE_KEY_PREFIX.to_string()
+        } else if resolved.is_file() {
+            format!("{WORKSPACE_KEY_PREFIX}{relative_root}#")
+        } else {
+            format!("{WORKSPACE_KEY_PREFIX}{relative_root}/")
+        };
+        let mut removed = 0usize;
+        if !truncated {
+            for entry in self
+                .memory
+                .list(Some(&Self::workspace_category()), None)
+                .await?
+            {
+                if entry.key.starts_with(&scope_prefix)
+                    && !live_keys.contains(&entry.key)
+                    && self.memory.forget(&entry.key).await?
+                {
+                    removed += 1;
+                }
+            }
+        }
+
+        let mut output = format!(
+            "Indexed {chunk_count} chunks from {} files; removed {removed} stale chunks.",
+            files.len()
+        );
+        if truncated {
+            let _ = write!(
+                output,
+                " Stopped after {MAX_INDEX_FILES} files; index a narrower path to cover the rest."
+            );
+        }
+        Ok(ToolResult {
+            success: true,
+            output,
+            error: None,
+        })
+    }
+}
+
+/// Collect text files under `root`, skipping hidden entries, symlinks, and
+/// files over [`MAX_INDEX_FILE_BYTES`]. Returns whether the file cap was hit.
+async fn collect_indexable_files(root: &Path) -> anyhow::Result<(Vec<PathBuf>, bool)> {
+    if root.is_file() {
+        return Ok((vec![root.to_path_buf()], false));
+    }
+
+    let mut files = Vec::new();
+    let mut pending = vec![root.to_path_buf()];
+    while let Some(dir) = pending.pop() {
+        let mut reader = tokio::fs::read_dir(&dir).await?;
+        while let Some(entry) = reader.next_entry().await? {
+            if entry.file_name().to_string_lossy().starts_with('.') {
+                continue;
+            }
+            let file_type = entry.file_type().await?;
+            let path = entry.path();
+            if file_type.is_dir() {
+                pending.push(path);
+            } else if file_type.is_file()
+                && path
+                    .extension()
+                    .and_then(|ext| ext.to_str())
+                    .is_some_and(|ext| {
+                        INDEXED_EXTENSIONS.contains(&ext.to_ascii_lowercase().as_str())
+                    })
+                && entry
+                    .metadata()
+                    .await
+                    .is_ok_and(|meta| meta.len() <= MAX_INDEX_FILE_BYTES)
+            {
+                if files.len() >= MAX_INDEX_FILES {
+                    return Ok((files, true));
+                }
+                files.push(path);
+            }
+        }
+    }
+    files.sort();
+    Ok((files, false))
+}
+
+#[async_trait]
+impl Tool for MemorySearchTool {
+    fn name(&self) -> &str {
+        "memory_search"
+    }
+
+    fn description(&self) -> &str {
+        "Semantic search across long-term memory and indexed workspace notes (Markdown and text files). Run action=index after creating or editing notes, then action=search with a natural-language query."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "action": {
+                    "type": "string",
+                    "enum": ["search", "index"],
+                    "description": "search (default) or index workspace files",
+                    "default": "search"
+                },
+                "query": {
+                    "type": "string",
+                    "description": "Natural-language query (search)"
+                },
+                "scope": {
+                    "type": "string",
+                    "enum": ["all", "memory", "workspace"],
+                    "description": "Restrict search to stored memories or workspace files",
+                    "default": "all"
+                },
+                "limit": {
+                    "type": "integer",
+                    "description": "Max results to return",
+                    "default": DEFAULT_LIMIT
+                },
+                "path": {
+                    "type": "string",
+                    "description": "File or directory to index, relative to the workspace (index). Defaults to the whole workspace.",
+                    "default": "."
+                }
+            }
+        })
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        match args
+            .get("action")
+            .and_then(|v| v.as_str())
+            .unwrap_or("search")
+        {
+            "search" => self.search(&args).await,
+            "index" => self.index(&args).await,
+            other => Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(format!(
+                    "Unknown action '{other}'. Supported: search, index"
+                )),
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::memory::SqliteMemory;
+    use crate::security::AutonomyLevel;
+    use tempfile::TempDir;
+
+    fn test_tool(workspace: &Path) -> (Arc<dyn Memory>, MemorySearchTool) {
+        let memory: Arc<dyn Memory> = Arc::new(SqliteMemory::new(workspace).unwrap());
+        let security = Arc::new(SecurityPolicy {
+            autonomy: AutonomyLevel::Supervised,
+            workspace_dir: workspace.to_path_buf(),
+            ..SecurityPolicy::default()
+        });
+        (memory.clone(), MemorySearchTool::new(memory, security, 512))
+    }
+
+    #[tokio::test]
+    async fn index_then_search_workspace_notes() {
+        let tmp = TempDir::new().unwrap();
+        tokio::fs::create_dir_all(tmp.path().join("notes"))
+            .await
+            .unwrap();
+        tokio::fs::write(
+            tmp.path().join("notes/garden.md"),
+            "# Garden\nWater the tomatoes every evening.",
+        )
+        .await
+        .unwrap();
+        tokio::fs::write(tmp.path().join("notes/photo.jpg"), b"\xff\xd8")
+            .await
+            .unwrap();
+
+        let (_memory, tool) = test_tool(tmp.path());
+        let result = tool.execute(json!({"action": "index"})).await.unwrap();
+        assert!(result.success, "{:?}", result.error);
+        assert!(result.output.contains("from 1 files"));
+
+        let result = tool
+            .execute(json!({"query": "tomatoes", "scope": "workspace"}))
+            .await
+            .unwrap();
+        assert!(result.success);
+        assert!(result.output.contains("notes/garden.md#0"));
+    }
+
+    #[tokio::test]
+    async fn reindex_drops_deleted_files() {
+        let tmp = TempDir::new().unwrap();
+        tokio::fs::write(tmp.path().join("todo.md"), "Buy milk")
+            .await
+            .unwrap();
+
+        let (memory, tool) = test_tool(tmp.path());
+        tool.execute(json!({"action": "index"})).await.unwrap();
+        assert!(memory.get("workspace:todo.md#0").await.unwrap().is_some());
+
+        tokio::fs::remove_file(tmp.path().join("todo.md"))
+            .await
+            .unwrap();
+        let result = tool.execute(json!({"action": "index"})).await.unwrap();
+        assert!(result.output.contains("removed 1 stale"));
+        assert!(memory.get("workspace:todo.md#0").await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn memory_scope_excludes_workspace_chunks() {
+        let tmp = TempDir::new().unwrap();
+        let (memory, tool) = test_tool(tmp.path());
+        memory
+            .store("lang", "User prefers Rust", MemoryCategory::Core, None)
+            .await
+            .unwrap();
+        memory
+            .store(
+                "workspace:rust.md#0",
+                "Rust notes",
+                MemorySearchTool::workspace_category(),
+                None,
+            )
+            .await
+            .unwrap();
+
+        let result = tool
+            .execute(json!({"query": "Rust", "scope": "memory"}))
+            .await
+            .unwrap();
+        assert!(result.output.contains("memory:lang"));
+        assert!(!result.output.contains("rust.md"));
+    }
+
+    #[tokio::test]
+    async fn index_rejects_path_traversal() {
+        let tmp = TempDir::new().unwrap();
+        let (_memory, tool) = test_tool(tmp.path());
+        let result = tool
+            .execute(json!({"action": "index", "path": "../"}))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("not allowed"));
+    }
+}
@@ -60,12 +60,10 @@ impl Tool for MemoryStoreTool {
             .and_then(|v| v.as_str())
             .ok_or_else(|| anyhow::anyhow!("Missing 'content' parameter"))?;
 
-        let category = match args.get("category").and_then(|v| v.as_str()) {
-            Some("core") | None => MemoryCategory::Core,
-            Some("daily") => MemoryCategory::Daily,
-            Some("conversation") => MemoryCategory::Conversation,
-            Some(other) => MemoryCategory::Custom(other.to_string()),
-        };
+        let category = args
+            .get("category")
+            .and_then(|v| v.as_str())
+            .map_or(MemoryCategory::Core, MemoryCategory::from_name);
 
         if let Err(error) = self
             .security
@@ -9,18 +9,35 @@
 //! and [`all_tools`] (full set including memory, browser, cron, HTTP, delegation,
 //! and optional integrations). Security policy enforcement is injected via
 //! [`SecurityPolicy`](crate::security::SecurityPolicy) at construction time.
+//! `[tools.<name>]` policies from the config are applied by [`policy::apply`].
 //!
 //! # Extension
 //!
 //! To add a new tool, implement [`Tool`] in a new submodule and register it in
 //! [`all_tools_with_runtime`]. See `AGENTS.md` §7.3 for the full change playbook.
 
+pub mod android_apps;
+pub mod android_automation;
+pub mod android_bridge;
+pub mod android_calendar;
+pub mod android_camera;
+pub mod android_clipboard;
+pub mod android_contacts;
 pub mod android_device;
+pub mod android_device_info;
+pub mod android_geofence;
+pub mod android_intent;
+pub mod android_location;
+pub mod android_notification;
+pub mod android_notifications_read;
+pub mod android_sensors;
+pub mod android_sms;
 pub mod browser;
 pub mod browser_open;
 pub mod cli_discovery;
 pub mod composio;
 pub mod content_search;
+pub mod cost_stats;
 pub mod cron_add;
 pub mod cron_list;
 pub mod cron_remove;
@@ -28,7 +45,15 @@ pub mod cron_run;
 pub mod cron_runs;
 pub mod cron_update;
 pub mod delegate;
+pub mod discord_notify;
+pub mod email_fetch;
+pub mod email_send;
+#[cfg(feature = "eval-rhai")]
+pub mod eval;
+pub mod file_append;
+pub mod file_delete;
 pub mod file_edit;
+pub mod file_list;
 pub mod file_read;
 pub mod file_write;
 pub mod git_operations;
@@ -39,31 +64,58 @@ pub mod hardware_board_info;
 pub mod hardware_memory_map;
 #[cfg(feature = "hardware")]
 pub mod hardware_memory_read;
+pub mod home_assistant;
 pub mod http_request;
 pub mod image_info;
+pub mod matrix_notify;
+pub mod mcp;
 pub mod memory_forget;
 pub mod memory_recall;
+pub mod memory_search;
 pub mod memory_store;
 pub mod model_routing_config;
+pub mod ntfy;
 pub mod pdf_read;
+pub mod play_audio;
+pub mod policy;
 pub mod proxy_config;
 pub mod pushover;
+pub mod readability;
 pub mod rules;
 pub mod schedule;
 pub mod schema;
 pub mod screenshot;
+pub mod set_reminder;
 pub mod shell;
+pub mod slack_notify;
 pub mod telegram_notify;
 pub mod traits;
+pub mod transcribe_audio;
+pub mod wasm_plugin;
 pub mod web_fetch;
 pub mod web_search;
 pub mod web_search_tool;
 
+pub use android_apps::AndroidAppsTool;
+pub use android_automation::AndroidAutomationTool;
+pub use android_calendar::AndroidCalendarTool;
+pub use android_camera::AndroidCameraTool;
+pub use android_clipboard::AndroidClipboardTool;
+pub use android_contacts::AndroidContactsTool;
 pub use android_device::AndroidDeviceTool;
+pub use android_device_info::AndroidDeviceInfoTool;
+pub use android_geofence::AndroidGeofenceTool;
+pub use android_intent::AndroidIntentTool;
+pub use android_location::AndroidLocationTool;
+pub use android_notification::AndroidNotificationTool;
+pub use android_notifications_read::AndroidNotificationsReadTool;
+pub use android_sensors::AndroidSensorsTool;
+pub use android_sms::AndroidSmsTool;
 pub use browser::{BrowserTool, ComputerUseConfig};
 pub use browser_open::BrowserOpenTool;
 pub use composio::ComposioTool;
 pub use content_search::ContentSearchTool;
+pub use cost_stats::CostStatsTool;
 pub use cron_add::CronAddTool;
 pub use cron_list::CronListTool;
 pub use cron_remove::CronRemoveTool;
@@ -71,7 +123,15 @@ pub use cron_run::CronRunTool;
 pub use cron_runs::CronRunsTool;
 pub use cron_update::CronUpdateTool;
 pub use delegate::DelegateTool;
+pub use discord_notify::{DiscordNotifyTarget, DiscordNotifyTool};
+pub use email_fetch::EmailFetchTool;
+pub use email_send::EmailSendTool;
+#[cfg(feature = "eval-rhai")]
+pub use eval::EvalTool;
+pub use file_append::FileAppendTool;
+pub use file_delete::FileDeleteTool;
 pub use file_edit::FileEditTool;
+pub use file_list::FileListTool;
 pub use file_read::FileReadTool;
 pub use file_write::FileWriteTool;
 pub use git_operations::GitOperationsTool;
@@ -82,13 +142,18 @@ pub use hardware_board_info::HardwareBoardInfoTool;
 pub use hardware_memory_map::HardwareMemoryMapTool;
 #[cfg(feature = "hardware")]
 pub use hardware_memory_read::HardwareMemoryReadTool;
+pub use home_assistant::HomeAssistantTool;
 pub use http_request::HttpRequestTool;
 pub use image_info::ImageInfoTool;
+pub use matrix_notify::MatrixNotifyTool;
 pub use memory_forget::MemoryForgetTool;
 pub use memory_recall::MemoryRecallTool;
+pub use memory_search::MemorySearchTool;
 pub use memory_store::MemoryStoreTool;
 pub use model_routing_config::ModelRoutingConfigTool;
+pub use ntfy::NtfyTool;
 pub use pdf_read::PdfReadTool;
+pub use play_audio::PlayAudioTool;
 pub use proxy_config::ProxyConfigTool;
 pub use pushover::PushoverTool;
 pub use rules::RulesTool;
@@ -96,18 +161,21 @@ pub use schedule::ScheduleTool;
 #[allow(unused_imports)]
 pub use schema::{CleaningStrategy, SchemaCleanr};
 pub use screenshot::ScreenshotTool;
+pub use set_reminder::SetReminderTool;
 pub use shell::ShellTool;
+pub use slack_notify::{SlackNotifyTarget, SlackNotifyTool};
 pub use telegram_notify::TelegramNotifyTool;
 pub use traits::Tool;
 #[allow(unused_imports)]
 pub use traits::{ToolResult, ToolSpec};
+pub use transcribe_audio::TranscribeAudioTool;
 pub use web_fetch::WebFetchTool;
 pub use web_search::WebSearchTool as WebSearchToolLegacy;
 pub use web_search_tool::WebSearchTool;
 
 use crate::config::{Config, DelegateAgentConfig};
 use crate::memory::Memory;
-use crate::runtime::{NativeRuntime, RuntimeAdapter};
+use crate::runtime::{NativeRuntime, RuntimeAdapter, SafStorage};
 use crate::security::SecurityPolicy;
 use async_trait::async_trait;
 use std::collections::HashMap;
@@ -217,11 +285,24 @@ pub fn all_tools_with_runtime(
     fallback_api_key: Option<&str>,
     root_config: &crate::config::Config,
 ) -> Vec<Box<dyn Tool>> {
+    let mut file_read = FileReadTool::new(security.clone());
+    let mut file_write = FileWriteTool::new(security.clone());
+    if !root_config.runtime.android.saf_tree_uris.is_empty() {
+        let saf = Arc::new(SafStorage::new(
+            root_config.runtime.android.saf_tree_uris.clone(),
+        ));
+        file_read = file_read.with_saf(saf.clone());
+        file_write = file_write.with_saf(saf);
+    }
+
     let mut tool_arcs: Vec<Arc<dyn Tool>> = vec![
         Arc::new(ShellTool::new(security.clone(), runtime)),
-        Arc::new(FileReadTool::new(security.clone())),
-        Arc::new(FileWriteTool::new(security.clone())),
+        Arc::new(file_read),
+        Arc::new(file_write),
         Arc::new(FileEditTool::new(security.clone())),
+        Arc::new(FileAppendTool::new(security.clone())),
+        Arc::new(FileDeleteTool::new(security.clone())),
+        Arc::new(FileListTool::new(security.clone())),
         Arc::new(GlobSearchTool::new(security.clone())),
         Arc::new(ContentSearchTool::new(security.clone())),
         Arc::new(CronAddTool::new(config.clone(), security.clone())),
@@ -230,8 +311,15 @@ pub fn all_tools_with_runtime(
         Arc::new(CronUpdateTool::new(config.clone(), security.clone())),
         Arc::new(CronRunTool::new(config.clone(), security.clone())),
         Arc::new(CronRunsTool::new(config.clone())),
+        Arc::new(CostStatsTool::new(config.clone())),
+        Arc::new(SetReminderTool::new(config.clone(), security.clone())),
         Arc::new(MemoryStoreTool::new(memory.clone(), security.clone())),
         Arc::new(MemoryRecallTool::new(memory.clone())),
+        Arc::new(MemorySearchTool::new(
+            memory.clone(),
+            security.clone(),
+            root_config.memory.chunk_max_tokens,
+        )),
         Arc::new(MemoryForgetTool::new(memory, security.clone())),
         Arc::new(ScheduleTool::new(security.clone(), root_config.clone())),
         Arc::new(ModelRoutingConfigTool::new(
@@ -247,6 +335,7 @@ pub fn all_tools_with_runtime(
             security.clone(),
             workspace_dir.to_path_buf(),
         )),
+        Arc::new(NtfyTool::new(security.clone(), workspace_dir.to_path_buf())),
     ];
 
     if root_config.android.enabled {
@@ -254,6 +343,86 @@ pub fn all_tools_with_runtime(
             security.clone(),
             root_config.android.clone(),
         )));
+        if root_config.android.capabilities.battery {
+            tool_arcs.push(Arc::new(AndroidDeviceInfoTool::new(
+                root_config.android.clone(),
+            )));
+        }
+        if root_config.android.capabilities.app_launch {
+            tool_arcs.push(Arc::new(AndroidIntentTool::new(
+                security.clone(),
+                root_config.android.clone(),
+            )));
+            tool_arcs.push(Arc::new(AndroidAppsTool::new(root_config.android.clone())));
+        }
+        if root_config.android.capabilities.audio_playback {
+            tool_arcs.push(Arc::new(PlayAudioTool::new(
+                security.clone(),
+                root_config.android.clone(),
+            )));
+        }
+        if root_config.android.capabilities.automation {
+            tool_arcs.push(Arc::new(AndroidAutomationTool::new(
+                security.clone(),
+                root_config.android.clone(),
+            )));
+        }
+        if root_config.android.capabilities.location && security.allow_location {
+            tool_arcs.push(Arc::new(AndroidLocationTool::new(
+                security.clone(),
+                root_config.android.clone(),
+            )));
+        }
+        if root_config.android.capabilities.geofencing && security.allow_location {
+            tool_arcs.push(Arc::new(AndroidGeofenceTool::new(
+                security.clone(),
+                root_config.android.clone(),
+            )));
+        }
+        if root_config.android.capabilities.notifications {
+            tool_arcs.push(Arc::new(AndroidNotificationTool::new(
+                security.clone(),
+                root_config.android.clone(),
+            )));
+        }
+        if root_config.android.capabilities.notification_access {
+            tool_arcs.push(Arc::new(AndroidNotificationsReadTool::new(
+                root_config.android.clone(),
+            )));
+        }
+        if root_config.android.capabilities.camera {
+            tool_arcs.push(Arc::new(AndroidCameraTool::new(
+                root_config.android.clone(),
+            )));
+        }
+        if root_config.android.capabilities.clipboard {
+            tool_arcs.push(Arc::new(AndroidClipboardTool::new(
+                security.clone(),
+                root_config.android.clone(),
+            )));
+        }
+        if root_config.android.capabilities.sensors {
+            tool_arcs.push(Arc::new(AndroidSensorsTool::new(
+                root_config.android.clone(),
+            )));
+        }
+        if root_config.android.capabilities.calendar {
+            tool_arcs.push(Arc::new(AndroidCalendarTool::new(
+                security.clone(),
+                root_config.android.clone(),
+            )));
+        }
+        if root_config.android.capabilities.contacts {
+            tool_arcs.push(Arc::new(AndroidContactsTool::new(
+                root_config.android.clone(),
+            )));
+        }
+        if root_config.android.capabilities.sms {
+            tool_arcs.push(Arc::new(AndroidSmsTool::new(
+                security.clone(),
+                root_config.android.clone(),
+            )));
+        }
     }
 
     if browser_config.enabled {
@@ -293,36 +462,68 @@ pub fn all_tools_with_runtime(
     }
 
     if web_fetch_config.enabled {
-        tool_arcs.push(Arc::new(WebFetchTool::new(
-            security.clone(),
-            web_fetch_config.allowed_domains.clone(),
-            web_fetch_config.blocked_domains.clone(),
-            web_fetch_config.max_response_size,
-            web_fetch_config.timeout_secs,
-        )));
+        tool_arcs.push(Arc::new(
+            WebFetchTool::new(
+                security.clone(),
+                web_fetch_config.allowed_domains.clone(),
+                web_fetch_config.blocked_domains.clone(),
+                web_fetch_config.max_response_size,
+                web_fetch_config.timeout_secs,
+            )
+            .with_readability(web_fetch_config.readability)
+            .with_cache_ttl_secs(web_fetch_config.cache_ttl_secs),
+        ));
     }
 
     // Web search tool (enabled by default for GLM and other models)
     if root_config.web_search.enabled {
-        tool_arcs.push(Arc::new(WebSearchTool::new(
-            root_config.web_search.provider.clone(),
-            root_config.web_search.brave_api_key.clone(),
-            root_config.web_search.max_results,
-            root_config.web_search.timeout_secs,
-        )));
+        tool_arcs.push(Arc::new(
+            WebSearchTool::new(
+                root_config.web_search.provider.clone(),
+                root_config.web_search.brave_api_key.clone(),
+                root_config.web_search.max_results,
+                root_config.web_search.timeout_secs,
+            )
+            .with_searxng_url(root_config.web_search.searxng_url.clone())
+            .with_tavily_api_key(root_config.web_search.tavily_api_key.clone()),
+        ));
     }
 
     // PDF extraction (feature-gated at compile time via rag-pdf)
     tool_arcs.push(Arc::new(PdfReadTool::new(security.clone())));
 
+    if root_config.transcription.enabled {
+        tool_arcs.push(Arc::new(TranscribeAudioTool::new(
+            security.clone(),
+            root_config.transcription.clone(),
+        )));
+    }
+
     // Vision tools are always available
     tool_arcs.push(Arc::new(ScreenshotTool::new(security.clone())));
     tool_arcs.push(Arc::new(ImageInfoTool::new(security.clone())));
 
+    // Sandboxed script evaluation for calculations and data munging
+    #[cfg(feature = "eval-rhai")]
+    if root_config.eval.enabled {
+        tool_arcs.push(Arc::new(EvalTool::new(root_config.eval.clone())));
+    }
+    #[cfg(not(feature = "eval-rhai"))]
+    if root_config.eval.enabled {
+        tracing::warn!(
+            "Eval tool is enabled but this build was compiled without `eval-rhai`; skipping the eval tool. Rebuild with `--features eval-rhai` or set `[eval] enabled = false`."
+        );
+    }
+
     // Rules tool for automation rules
     let rules_db_path = workspace_dir.join("rules").join("rules.db");
     tool_arcs.push(Arc::new(RulesTool::with_db_path(rules_db_path)));
 
+    // Third-party WASM plugins from <workspace>/<plugins.dir>
+    for plugin in wasm_plugin::load_plugins(&root_config.plugins, workspace_dir, security) {
+        tool_arcs.push(Arc::new(plugin));
+    }
+
     if let Some(key) = composio_key {
         if !key.is_empty() {
             tool_arcs.push(Arc::new(ComposioTool::new(
@@ -333,24 +534,69 @@ pub fn all_tools_with_runtime(
         }
     }
 
-    // Add Telegram notification tool when bot token + notify_chat_id are configured
+    // Home Assistant smart-home control when [home_assistant] is configured
+    if let Some(tool) =
+        HomeAssistantTool::from_config(security.clone(), &root_config.home_assistant)
+    {
+        tool_arcs.push(Arc::new(tool));
+    }
+
+    // Add Telegram notification tool when a bot token and notify chat(s) are configured
     if let Some(tg_cfg) = &root_config.channels_config.telegram {
-        if let Some(chat_id) = &tg_cfg.notify_chat_id {
-            if !tg_cfg.bot_token.is_empty() && !chat_id.is_empty() {
-                tool_arcs.push(Arc::new(TelegramNotifyTool::new(
-                    security.clone(),
-                    tg_cfg.bot_token.clone(),
-                    chat_id.clone(),
-                )));
-            }
+        if let Some(tool) = TelegramNotifyTool::from_config(security.clone(), tg_cfg) {
+            tool_arcs.push(Arc::new(tool));
         }
     }
 
+    // Add Discord notification tool when a notify webhook or channel is configured
+    if let Some(dc_cfg) = &root_config.channels_config.discord {
+        if let Some(target) = DiscordNotifyTarget::from_config(dc_cfg) {
+            tool_arcs.push(Arc::new(DiscordNotifyTool::new(security.clone(), target)));
+        }
+    }
+
+    // Add Slack notification tool when a notify webhook or channel is configured
+    if let Some(sl_cfg) = &root_config.channels_config.slack {
+        if let Some(target) = SlackNotifyTarget::from_config(sl_cfg) {
+            tool_arcs.push(Arc::new(SlackNotifyTool::new(security.clone(), target)));
+        }
+    }
+
+    // Add Matrix notification tool when notify_room_id is configured
+    if let Some(mx_cfg) = &root_config.channels_config.matrix {
+        if let Some(tool) = MatrixNotifyTool::from_config(security.clone(), mx_cfg) {
+            tool_arcs.push(Arc::new(tool));
+        }
+    }
+
+    // Add email tools: send needs SMTP plus a recipient allowlist, fetch needs IMAP
+    if let Some(email_cfg) = &root_config.channels_config.email {
+        if !email_cfg.smtp_host.is_empty() && !email_cfg.allowed_recipients.is_empty() {
+            tool_arcs.push(Arc::new(EmailSendTool::new(
+                security.clone(),
+                email_cfg.clone(),
+            )));
+        }
+        if !email_cfg.imap_host.is_empty() {
+            tool_arcs.push(Arc::new(EmailFetchTool::new(
+                security.clone(),
+                email_cfg.clone(),
+            )));
+        }
+    }
+
+    // Per-tool [tools.<name>] policies; delegated sub-agents see the result too
+    let mut tool_arcs = policy::apply(tool_arcs, &root_config.tools);
+
     // Add delegation tool when agents are configured
     if !agents.is_empty() {
         let delegate_agents: HashMap<String, DelegateAgentConfig> = agents
             .iter()
-            .map(|(name, cfg)| (name.clone(), cfg.clone()))
+            .map(|(name, cfg)| {
+                let mut cfg = cfg.clone();
+                cfg.model = root_config.resolve_model_alias(&cfg.model);
+                (name.clone(), cfg)
+            })
             .collect();
         let delegate_fallback_credential = fallback_api_key.and_then(|value| {
             let trimmed_value = value.trim();
@@ -370,11 +616,25 @@ pub fn all_tools_with_runtime(
                     .map(std::path::PathBuf::from),
                 secrets_encrypt: root_config.secrets.encrypt,
                 reasoning_enabled: root_config.runtime.reasoning_enabled,
+                gemini_safety_settings: root_config.gemini.safety_settings.clone(),
+                azure_openai: root_config.azure_openai.clone(),
+                openrouter: root_config.openrouter.clone(),
+                local_llm: root_config.local_llm.clone(),
+                workspace_dir: Some(root_config.workspace_dir.clone()),
+                response_cache: crate::memory::create_response_cache(
+                    &root_config.memory,
+                    &root_config.workspace_dir,
+                )
+                .map(Arc::new),
             },
         )
         .with_parent_tools(parent_tools)
-        .with_multimodal_config(root_config.multimodal.clone());
-        tool_arcs.push(Arc::new(delegate_tool));
+        .with_multimodal_config(root_config.multimodal.clone())
+        .with_reliability(root_config.reliability.clone());
+        tool_arcs.extend(policy::apply(
+            vec![Arc::new(delegate_tool)],
+            &root_config.tools,
+        ));
     }
 
     boxed_registry_from_arcs(tool_arcs)
@@ -441,6 +701,11 @@ mod tests {
         assert!(names.contains(&"model_routing_config"));
         assert!(names.contains(&"pushover"));
         assert!(names.contains(&"proxy_config"));
+        assert!(names.contains(&"file_append"));
+        assert!(names.contains(&"file_delete"));
+        assert!(names.contains(&"file_list"));
+        assert!(names.contains(&"memory_search"));
+        assert!(names.contains(&"ntfy"));
     }
 
     #[test]
@@ -517,6 +782,13 @@ mod tests {
         );
         let names: Vec<&str> = tools.iter().map(|t| t.name()).collect();
         assert!(names.contains(&"android_device"));
+        assert!(names.contains(&"android_intent"));
+        assert!(names.contains(&"android_apps"));
+        assert!(names.contains(&"play_audio"));
+        assert!(names.contains(&"device_info"));
+        assert!(names.contains(&"android_sensors"));
+        // Notifications capability is opt-in.
+        assert!(!names.contains(&"android_notification"));
     }
 
     #[test]
@@ -1,5 +1,7 @@
 use super::traits::{Tool, ToolResult};
-use crate::config::{ClassificationRule, Config, DelegateAgentConfig, ModelRouteConfig};
+use crate::config::{
+    ChangeSource, ClassificationRule, Config, DelegateAgentConfig, ModelRouteConfig,
+};
 use crate::security::SecurityPolicy;
 use crate::util::MaybeSet;
 use async_trait::async_trait;
@@ -414,7 +416,7 @@ impl ModelRoutingConfigTool {
             MaybeSet::Unset => {}
         }
 
-        cfg.save().await?;
+        cfg.save_from(ChangeSource::Agent).await?;
 
         Ok(ToolResult {
             success: true,
@@ -546,7 +548,7 @@ impl ModelRoutingConfigTool {
         Self::normalize_and_sort_rules(&mut cfg.query_classification.rules);
         cfg.query_classification.enabled = !cfg.query_classification.rules.is_empty();
 
-        cfg.save().await?;
+        cfg.save_from(ChangeSource::Agent).await?;
 
         Ok(ToolResult {
             success: true,
@@ -589,7 +591,7 @@ impl ModelRoutingConfigTool {
         Self::normalize_and_sort_rules(&mut cfg.query_classification.rules);
         cfg.query_classification.enabled = !cfg.query_classification.rules.is_empty();
 
-        cfg.save().await?;
+        cfg.save_from(ChangeSource::Agent).await?;
 
         Ok(ToolResult {
             success: true,
@@ -701,7 +703,7 @@ impl ModelRoutingConfigTool {
         }
 
         cfg.agents.insert(name.clone(), next_agent);
-        cfg.save().await?;
+        cfg.save_from(ChangeSource::Agent).await?;
 
         Ok(ToolResult {
             success: true,
@@ -722,7 +724,7 @@ impl ModelRoutingConfigTool {
             anyhow::bail!("No delegate agent found with name '{name}'");
         }
 
-        cfg.save().await?;
+        cfg.save_from(ChangeSource::Agent).await?;
 
         Ok(ToolResult {
             success: true,
@@ -873,11 +875,11 @@ impl Tool for ModelRoutingConfigTool {
                 }
 
                 match action.as_str() {
-                    "set_default" => self.handle_set_default(&args).await,
-                    "upsert_scenario" => self.handle_upsert_scenario(&args).await,
-                    "remove_scenario" => self.handle_remove_scenario(&args).await,
-                    "upsert_agent" => self.handle_upsert_agent(&args).await,
-                    "remove_agent" => self.handle_remove_agent(&args).await,
+                    "set_default" => Box::pin(self.handle_set_default(&args)).await,
+                    "upsert_scenario" => Box::pin(self.handle_upsert_scenario(&args)).await,
+                    "remove_scenario" => Box::pin(self.handle_remove_scenario(&args)).await,
+                    "upsert_agent" => Box::pin(self.handle_upsert_agent(&args)).await,
+                    "remove_agent" => Box::pin(self.handle_remove_agent(&args)).await,
                     _ => unreachable!("validated above"),
                 }
             }
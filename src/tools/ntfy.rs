@@ -0,0 +1,410 @@
+use super::pushover::PushoverTool;
+use super::traits::{Tool, ToolResult};
+use crate::security::SecurityPolicy;
+use async_trait::async_trait;
+use serde_json::json;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+const NTFY_DEFAULT_SERVER: &str = "https://ntfy.sh";
+const NTFY_REQUEST_TIMEOUT_SECS: u64 = 15;
+const NTFY_MAX_TAGS: usize = 10;
+
+#[derive(Debug, PartialEq, Eq)]
+struct NtfySettings {
+    server: String,
+    topic: String,
+    token: Option<String>,
+}
+
+pub struct NtfyTool {
+    security: Arc<SecurityPolicy>,
+    workspace_dir: PathBuf,
+}
+
+impl NtfyTool {
+    pub fn new(security: Arc<SecurityPolicy>, workspace_dir: PathBuf) -> Self {
+        Self {
+            security,
+            workspace_dir,
+        }
+    }
+
+    fn is_valid_topic(topic: &str) -> bool {
+        !topic.is_empty()
+            && topic.len() <= 64
+            && topic
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    }
+
+    async fn get_settings(&self) -> anyhow::Result<NtfySettings> {
+        let env_path = self.workspace_dir.join(".env");
+        let content = tokio::fs::read_to_string(&env_path)
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to read {}: {}", env_path.display(), e))?;
+
+        let mut server = None;
+        let mut topic = None;
+        let mut token = None;
+
+        for line in content.lines() {
+            let line = line.trim();
+            if line.starts_with('#') || line.is_empty() {
+                continue;
+            }
+            let line = line.strip_prefix("export ").map(str::trim).unwrap_or(line);
+            if let Some((key, value)) = line.split_once('=') {
+                let key = key.trim();
+                let value = PushoverTool::parse_env_value(value);
+
+                if key.eq_ignore_ascii_case("NTFY_URL") {
+                    server = Some(value);
+                } else if key.eq_ignore_ascii_case("NTFY_TOPIC") {
+                    topic = Some(value);
+                } else if key.eq_ignore_ascii_case("NTFY_TOKEN") {
+                    token = Some(value);
+                }
+            }
+        }
+
+        let topic = topic
+            .filter(|t| !t.is_empty())
+            .ok_or_else(|| anyhow::anyhow!("NTFY_TOPIC not found in .env"))?;
+        if !Self::is_valid_topic(&topic) {
+            anyhow::bail!("NTFY_TOPIC must be 1-64 characters of letters, digits, '-' or '_'");
+        }
+
+        let server = server
+            .filter(|s| !s.is_empty())
+            .unwrap_or_else(|| NTFY_DEFAULT_SERVER.to_string())
+            .trim_end_matches('/')
+            .to_string();
+        if !server.starts_with("https://") && !server.starts_with("http://") {
+            anyhow::bail!("NTFY_URL must start with http:// or https://");
+        }
+
+        Ok(NtfySettings {
+            server,
+            topic,
+            token: token.filter(|t| !t.is_empty()),
+        })
+    }
+
+    fn parse_tags(args: &serde_json::Value) -> Result<Vec<String>, String> {
+        let tags: Vec<String> = match args.get("tags") {
+            None | Some(serde_json::Value::Null) => Vec::new(),
+            Some(serde_json::Value::String(tags)) => tags
+                .split(',')
+                .map(|t| t.trim().to_string())
+                .filter(|t| !t.is_empty())
+                .collect(),
+            Some(serde_json::Value::Array(items)) => items
+                .iter()
+                .filter_map(serde_json::Value::as_str)
+                .map(|t| t.trim().to_string())
+                .filter(|t| !t.is_empty())
+                .collect(),
+            Some(_) => return Err("'tags' must be a string or an array of strings".into()),
+        };
+        if tags.len() > NTFY_MAX_TAGS {
+            return Err(format!("Too many tags (max {NTFY_MAX_TAGS})"));
+        }
+        Ok(tags)
+    }
+}
+
+#[async_trait]
+impl Tool for NtfyTool {
+    fn name(&self) -> &str {
+        "ntfy"
+    }
+
+    fn description(&self) -> &str {
+        "Send a push notification to an ntfy topic (ntfy.sh or self-hosted). Requires NTFY_TOPIC (and optionally NTFY_URL, NTFY_TOKEN) in .env file."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "message": {
+                    "type": "string",
+                    "description": "The notification message to send"
+                },
+                "title": {
+                    "type": "string",
+                    "description": "Optional notification title"
+                },
+                "priority": {
+                    "type": "integer",
+                    "description": "Message priority: 1 (min), 2 (low), 3 (default), 4 (high), 5 (max/urgent)"
+                },
+                "tags": {
+                    "description": "Optional tags or emoji shortcodes (e.g., 'warning', 'white_check_mark'), as an array or comma-separated string",
+                    "oneOf": [
+                        { "type": "string" },
+                        { "type": "array", "items": { "type": "string" } }
+                    ]
+                },
+                "click": {
+                    "type": "string",
+                    "description": "Optional http(s) URL to open when the notification is tapped"
+                }
+            },
+            "required": ["message"]
+        })
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        if !self.security.can_act() {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some("Action blocked: autonomy is read-only".into()),
+            });
+        }
+
+        if !self.security.record_action() {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some("Action blocked: rate limit exceeded".into()),
+            });
+        }
+
+        let message = args
+            .get("message")
+            .and_then(|v| v.as_str())
+            .map(str::trim)
+            .filter(|v| !v.is_empty())
+            .ok_or_else(|| anyhow::anyhow!("Missing 'message' parameter"))?
+            .to_string();
+
+        let priority = match args.get("priority").and_then(|v| v.as_i64()) {
+            Some(value) if (1..=5).contains(&value) => Some(value),
+            Some(value) => {
+                return Ok(ToolResult {
+                    success: false,
+                    output: String::new(),
+                    error: Some(format!(
+                        "Invalid 'priority': {value}. Expected integer in range 1..=5"
+                    )),
+                })
+            }
+            None => None,
+        };
+
+        let tags = match Self::parse_tags(&args) {
+            Ok(tags) => tags,
+            Err(error) => {
+                return Ok(ToolResult {
+                    success: false,
+                    output: String::new(),
+                    error: Some(error),
+                })
+            }
+        };
+
+        let click = args
+            .get("click")
+            .and_then(|v| v.as_str())
+            .map(str::trim)
+            .filter(|v| !v.is_empty());
+        if let Some(click) = click {
+            if !click.starts_with("https://") && !click.starts_with("http://") {
+                return Ok(ToolResult {
+                    success: false,
+                    output: String::new(),
+                    error: Some("Invalid 'click': expected an http(s) URL".into()),
+                });
+            }
+        }
+
+        let settings = self.get_settings().await?;
+
+        let mut payload = json!({
+            "topic": settings.topic,
+            "message": message,
+        });
+        if let Some(title) = args.get("title").and_then(|v| v.as_str()) {
+            payload["title"] = json!(title);
+        }
+        if let Some(priority) = priority {
+            payload["priority"] = json!(priority);
+        }
+        if !tags.is_empty() {
+            payload["tags"] = json!(tags);
+        }
+        if let Some(click) = click {
+            payload["click"] = json!(click);
+        }
+
+        let client = crate::config::build_runtime_proxy_client_with_timeouts(
+            "tool.ntfy",
+            NTFY_REQUEST_TIMEOUT_SECS,
+            10,
+        );
+        let mut request = client.post(&settings.server).json(&payload);
+        if let Some(token) = &settings.token {
+            request = request.bearer_auth(token);
+        }
+        let response = request.send().await?;
+
+        let status = response.status();
+        let body = response.text().await.unwrap_or_default();
+
+        if !status.is_success() {
+            return Ok(ToolResult {
+                success: false,
+                output: body,
+                error: Some(format!("ntfy server returned status {}", status)),
+            });
+        }
+
+        let message_id = serde_json::from_str::<serde_json::Value>(&body)
+            .ok()
+            .and_then(|json| json.get("id").and_then(|v| v.as_str()).map(String::from));
+
+        Ok(ToolResult {
+            success: true,
+            output: match message_id {
+                Some(id) => format!(
+                    "ntfy notification {id} published to topic {}.",
+                    settings.topic
+                ),
+                None => format!("ntfy notification published to topic {}.", settings.topic),
+            },
+            error: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::security::AutonomyLevel;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn test_security(level: AutonomyLevel, max_actions_per_hour: u32) -> Arc<SecurityPolicy> {
+        Arc::new(SecurityPolicy {
+            autonomy: level,
+            max_actions_per_hour,
+            workspace_dir: std::env::temp_dir(),
+            ..SecurityPolicy::default()
+        })
+    }
+
+    #[test]
+    fn ntfy_tool_name_and_schema() {
+        let tool = NtfyTool::new(
+            test_security(AutonomyLevel::Full, 100),
+            PathBuf::from("/tmp"),
+        );
+        assert_eq!(tool.name(), "ntfy");
+        let schema = tool.parameters_schema();
+        assert!(schema["properties"].get("click").is_some());
+        assert!(schema["properties"].get("tags").is_some());
+    }
+
+    #[tokio::test]
+    async fn settings_default_to_ntfy_sh() {
+        let tmp = TempDir::new().unwrap();
+        fs::write(tmp.path().join(".env"), "NTFY_TOPIC=phone-alerts_42\n").unwrap();
+
+        let tool = NtfyTool::new(
+            test_security(AutonomyLevel::Full, 100),
+            tmp.path().to_path_buf(),
+        );
+        let settings = tool.get_settings().await.unwrap();
+        assert_eq!(
+            settings,
+            NtfySettings {
+                server: "https://ntfy.sh".into(),
+                topic: "phone-alerts_42".into(),
+                token: None,
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn settings_support_self_hosted_server_and_token() {
+        let tmp = TempDir::new().unwrap();
+        fs::write(
+            tmp.path().join(".env"),
+            "export NTFY_URL=\"https://ntfy.example.com/\"\nNTFY_TOPIC=home\nNTFY_TOKEN='tk_abc'\n",
+        )
+        .unwrap();
+
+        let tool = NtfyTool::new(
+            test_security(AutonomyLevel::Full, 100),
+            tmp.path().to_path_buf(),
+        );
+        let settings = tool.get_settings().await.unwrap();
+        assert_eq!(settings.server, "https://ntfy.example.com");
+        assert_eq!(settings.token.as_deref(), Some("tk_abc"));
+    }
+
+    #[tokio::test]
+    async fn settings_reject_missing_or_invalid_topic() {
+        let tmp = TempDir::new().unwrap();
+        let tool = NtfyTool::new(
+            test_security(AutonomyLevel::Full, 100),
+            tmp.path().to_path_buf(),
+        );
+
+        fs::write(tmp.path().join(".env"), "NTFY_URL=https://ntfy.sh\n").unwrap();
+        assert!(tool.get_settings().await.is_err());
+
+        fs::write(tmp.path().join(".env"), "NTFY_TOPIC=../admin\n").unwrap();
+        assert!(tool.get_settings().await.is_err());
+    }
+
+    #[test]
+    fn tags_accept_string_or_array() {
+        assert_eq!(
+            NtfyTool::parse_tags(&json!({"tags": "warning, robot"})).unwrap(),
+            vec!["warning", "robot"]
+        );
+        assert_eq!(
+            NtfyTool::parse_tags(&json!({"tags": ["tada"]})).unwrap(),
+            vec!["tada"]
+        );
+        assert!(NtfyTool::parse_tags(&json!({"tags": 3})).is_err());
+    }
+
+    #[tokio::test]
+    async fn execute_blocks_readonly_mode() {
+        let tool = NtfyTool::new(
+            test_security(AutonomyLevel::ReadOnly, 100),
+            PathBuf::from("/tmp"),
+        );
+
+        let result = tool.execute(json!({"message": "hello"})).await.unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("read-only"));
+    }
+
+    #[tokio::test]
+    async fn execute_rejects_invalid_priority_and_click() {
+        let tool = NtfyTool::new(
+            test_security(AutonomyLevel::Full, 100),
+            PathBuf::from("/tmp"),
+        );
+
+        let result = tool
+            .execute(json!({"message": "hello", "priority": 9}))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("1..=5"));
+
+        let result = tool
+            .execute(json!({"message": "hello", "click": "javascript:alert(1)"}))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("click"));
+    }
+}
@@ -0,0 +1,290 @@
+use super::android_bridge::{bridge_tool_result, AndroidBridge};
+use super::traits::{Tool, ToolResult};
+use crate::config::AndroidConfig;
+use crate::security::policy::ToolOperation;
+use crate::security::SecurityPolicy;
+use async_trait::async_trait;
+use serde_json::json;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+const AUDIO_EXTENSIONS: &[&str] = &["mp3", "wav", "ogg", "oga", "opus", "m4a", "aac", "flac"];
+const MAX_AUDIO_BYTES: u64 = 50 * 1024 * 1024;
+/// Android `TextToSpeech.getMaxSpeechInputLength()` is 4000 characters.
+const MAX_TTS_CHARS: usize = 4000;
+const MAX_REPEAT: u64 = 10;
+
+/// Play a workspace audio file or spoken text on the device speaker through
+/// the host app.
+pub struct PlayAudioTool {
+    security: Arc<SecurityPolicy>,
+    config: AndroidConfig,
+    bridge: AndroidBridge,
+}
+
+impl PlayAudioTool {
+    pub fn new(security: Arc<SecurityPolicy>, config: AndroidConfig) -> Self {
+        Self {
+            security,
+            bridge: AndroidBridge::new(config.bridge.clone()),
+            config,
+        }
+    }
+
+    /// Resolve a workspace audio file, applying the same sandbox checks as
+    /// the file tools.
+    async fn resolve_file(&self, path: &str) -> anyhow::Result<PathBuf> {
+        if !self.security.is_path_allowed(path) {
+            anyhow::bail!("Path not allowed by security policy: {path}");
+        }
+        let full_path = self.security.workspace_dir.join(path);
+        let resolved = tokio::fs::canonicalize(&full_path)
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to resolve audio file: {e}"))?;
+        if !self.security.is_resolved_path_allowed(&resolved) {
+            anyhow::bail!(self.security.resolved_path_violation_message(&resolved));
+        }
+
+        let extension = resolved
+            .extension()
+            .and_then(|e| e.to_str())
+            .map(str::to_ascii_lowercase)
+            .unwrap_or_default();
+        if !AUDIO_EXTENSIONS.contains(&extension.as_str()) {
+            anyhow::bail!(
+                "Unsupported audio format '{extension}'. Supported: {}",
+                AUDIO_EXTENSIONS.join(", ")
+            );
+        }
+        let metadata = tokio::fs::metadata(&resolved).await?;
+        if !metadata.is_file() {
+            anyhow::bail!("Audio path is not a file: {path}");
+        }
+        if metadata.len() > MAX_AUDIO_BYTES {
+            anyhow::bail!(
+                "Audio file too large: {} bytes (limit: {MAX_AUDIO_BYTES} bytes)",
+                metadata.len()
+            );
+        }
+        Ok(resolved)
+    }
+
+    /// Validate arguments and build the host payload for `play_audio`.
+    async fn build_payload(&self, args: &serde_json::Value) -> anyhow::Result<serde_json::Value> {
+        let file = args
+            .get("file")
+            .and_then(serde_json::Value::as_str)
+            .map(str::trim)
+            .filter(|v| !v.is_empty());
+        let text = args
+            .get("text")
+            .and_then(serde_json::Value::as_str)
+            .map(str::trim)
+            .filter(|v| !v.is_empty());
+
+        let stream = match args.get("stream").and_then(serde_json::Value::as_str) {
+            None => "media",
+            Some(stream @ ("media" | "alarm" | "notification")) => stream,
+            Some(other) => {
+                anyhow::bail!("Unknown stream '{other}'. Supported: media, alarm, notification")
+            }
+        };
+        let volume = match args.get("volume") {
+            None | Some(serde_json::Value::Null) => None,
+            Some(value) => match value.as_u64() {
+                Some(v) if v <= 100 => Some(v),
+                _ => anyhow::bail!("'volume' must be an integer from 0 to 100"),
+            },
+        };
+        let repeat = args
+            .get("repeat")
+            .and_then(serde_json::Value::as_u64)
+            .map_or(1, |v| v.clamp(1, MAX_REPEAT));
+
+        let mut payload = match (file, text) {
+            (Some(_), Some(_)) => anyhow::bail!("Pass either 'file' or 'text', not both"),
+            (None, None) => anyhow::bail!("play_audio requires 'file' or 'text'"),
+            (Some(file), None) => {
+                let path = self.resolve_file(file).await?;
+                json!({ "source": "file", "path": path.to_string_lossy() })
+            }
+            (None, Some(text)) => {
+                if text.chars().count() > MAX_TTS_CHARS {
+                    anyhow::bail!("'text' exceeds {MAX_TTS_CHARS} characters");
+                }
+                let language = args
+                    .get("language")
+                    .and_then(serde_json::Value::as_str)
+                    .map(str::trim)
+                    .filter(|v| !v.is_empty());
+                json!({ "source": "tts", "text": text, "language": language })
+            }
+        };
+        payload["stream"] = json!(stream);
+        payload["volume"] = json!(volume);
+        payload["repeat"] = json!(repeat);
+        Ok(payload)
+    }
+}
+
+#[async_trait]
+impl Tool for PlayAudioTool {
+    fn name(&self) -> &str {
+        "play_audio"
+    }
+
+    fn description(&self) -> &str {
+        "Play sound out loud on the user's phone: a workspace audio file (mp3, wav, ogg, m4a, ...) or text spoken with the device's text-to-speech. Use stream=alarm for wake-up alarms and reminders that must be heard, media for briefings. action=stop stops playback."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "action": {
+                    "type": "string",
+                    "enum": ["play", "stop"],
+                    "default": "play"
+                },
+                "file": {
+                    "type": "string",
+                    "description": "Workspace-relative audio file to play"
+                },
+                "text": {
+                    "type": "string",
+                    "description": "Text to speak with text-to-speech (instead of file)"
+                },
+                "language": {
+                    "type": "string",
+                    "description": "BCP-47 language for text, e.g. en-US (default: device locale)"
+                },
+                "stream": {
+                    "type": "string",
+                    "enum": ["media", "alarm", "notification"],
+                    "default": "media",
+                    "description": "Audio stream; alarm plays even when media volume is low"
+                },
+                "volume": {
+                    "type": "integer",
+                    "description": "Stream volume 0-100 for this playback (default: current volume)"
+                },
+                "repeat": {
+                    "type": "integer",
+                    "description": "Times to play, max 10",
+                    "default": 1
+                }
+            }
+        })
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        if !self.config.capabilities.audio_playback {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some("android capability audio_playback is disabled".into()),
+            });
+        }
+
+        let (action, payload) = match args.get("action").and_then(serde_json::Value::as_str) {
+            None | Some("play") => match self.build_payload(&args).await {
+                Ok(payload) => ("play_audio", payload),
+                Err(error) => {
+                    return Ok(ToolResult {
+                        success: false,
+                        output: String::new(),
+                        error: Some(error.to_string()),
+                    });
+                }
+            },
+            Some("stop") => ("stop_audio", json!({})),
+            Some(other) => {
+                return Ok(ToolResult {
+                    success: false,
+                    output: String::new(),
+                    error: Some(format!("Unknown action '{other}'. Supported: play, stop")),
+                });
+            }
+        };
+
+        if let Err(error) = self
+            .security
+            .enforce_tool_operation(ToolOperation::Act, "play_audio")
+        {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(error),
+            });
+        }
+
+        Ok(bridge_tool_result(self.bridge.call(action, payload).await))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_tool(workspace: &std::path::Path) -> PlayAudioTool {
+        let security = Arc::new(SecurityPolicy {
+            workspace_dir: workspace.to_path_buf(),
+            ..SecurityPolicy::default()
+        });
+        let config = AndroidConfig {
+            enabled: true,
+            ..AndroidConfig::default()
+        };
+        PlayAudioTool::new(security, config)
+    }
+
+    #[tokio::test]
+    async fn plays_workspace_file_on_alarm_stream() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        std::fs::write(tmp.path().join("alarm.mp3"), b"ID3").unwrap();
+        let result = test_tool(tmp.path())
+            .execute(json!({ "file": "alarm.mp3", "stream": "alarm", "repeat": 50 }))
+            .await
+            .unwrap();
+        assert!(result.success, "{:?}", result.error);
+        assert!(result.output.contains("play_audio"));
+        assert!(result.output.contains("alarm.mp3"));
+        assert!(result.output.contains("\"repeat\": 10"));
+    }
+
+    #[tokio::test]
+    async fn speaks_text_and_validates_sources() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let tool = test_tool(tmp.path());
+
+        let result = tool
+            .execute(json!({ "text": "Good morning. It is 7 o'clock." }))
+            .await
+            .unwrap();
+        assert!(result.success, "{:?}", result.error);
+        assert!(result.output.contains("tts"));
+
+        std::fs::write(tmp.path().join("notes.txt"), b"hi").unwrap();
+        for (args, expected) in [
+            (json!({}), "requires 'file' or 'text'"),
+            (json!({ "file": "a.mp3", "text": "hi" }), "not both"),
+            (json!({ "file": "notes.txt" }), "Unsupported audio format"),
+            (json!({ "file": "../outside.mp3" }), "not allowed"),
+            (json!({ "text": "hi", "volume": 150 }), "volume"),
+        ] {
+            let result = tool.execute(args).await.unwrap();
+            assert!(!result.success);
+            assert!(result.error.unwrap().contains(expected), "{expected}");
+        }
+    }
+
+    #[tokio::test]
+    async fn requires_audio_playback_capability() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let mut tool = test_tool(tmp.path());
+        tool.config.capabilities.audio_playback = false;
+        let result = tool.execute(json!({ "text": "hi" })).await.unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("audio_playback"));
+    }
+}
@@ -0,0 +1,265 @@
+//! `[tools.<name>]` policies applied while the registry is built.
+//!
+//! A policy can drop a tool, change the name and description the model sees,
+//! and reject calls whose arguments break a parameter constraint before the
+//! tool runs. Tools without a policy are registered unchanged.
+
+use super::traits::{Tool, ToolResult};
+use crate::config::{ToolParamConstraint, ToolPolicyConfig};
+use async_trait::async_trait;
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+use std::hash::BuildHasher;
+use std::sync::Arc;
+
+/// Apply `policies` to a registry.
+pub fn apply<S: BuildHasher>(
+    tools: Vec<Arc<dyn Tool>>,
+    policies: &HashMap<String, ToolPolicyConfig, S>,
+) -> Vec<Arc<dyn Tool>> {
+    if policies.is_empty() {
+        return tools;
+    }
+    let existing: HashSet<String> = tools.iter().map(|tool| tool.name().to_string()).collect();
+
+    tools
+        .into_iter()
+        .filter_map(|tool| {
+            let Some(policy) = policies.get(tool.name()) else {
+                return Some(tool);
+            };
+            if !policy.enabled {
+                tracing::info!(tool = tool.name(), "Tool disabled by [tools] config");
+                return None;
+            }
+            let mut name = policy.name.clone();
+            if let Some(rename) = name.as_deref() {
+                if rename != tool.name() && existing.contains(rename) {
+                    tracing::warn!(
+                        "tools.{}.name {rename:?} is already a tool name; keeping the original",
+                        tool.name()
+                    );
+                    name = None;
+                }
+            }
+            Some(Arc::new(PolicyTool {
+                name: name.unwrap_or_else(|| tool.name().to_string()),
+                description: policy.description.clone(),
+                params: policy.params.clone(),
+                inner: tool,
+            }) as Arc<dyn Tool>)
+        })
+        .collect()
+}
+
+/// [`apply`] for a boxed registry, such as imported MCP tools.
+pub fn apply_boxed<S: BuildHasher>(
+    tools: Vec<Box<dyn Tool>>,
+    policies: &HashMap<String, ToolPolicyConfig, S>,
+) -> Vec<Box<dyn Tool>> {
+    if policies.is_empty() {
+        return tools;
+    }
+    super::boxed_registry_from_arcs(apply(tools.into_iter().map(Arc::from).collect(), policies))
+}
+
+struct PolicyTool {
+    inner: Arc<dyn Tool>,
+    name: String,
+    description: Option<String>,
+    params: HashMap<String, ToolParamConstraint>,
+}
+
+#[async_trait]
+impl Tool for PolicyTool {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn description(&self) -> &str {
+        self.description
+            .as_deref()
+            .unwrap_or_else(|| self.inner.description())
+    }
+
+    fn parameters_schema(&self) -> Value {
+        self.inner.parameters_schema()
+    }
+
+    async fn execute(&self, args: Value) -> anyhow::Result<ToolResult> {
+        for (param, constraint) in &self.params {
+            if let Some(value) = args.get(param) {
+                if let Err(reason) = check(value, constraint) {
+                    return Ok(ToolResult {
+                        success: false,
+                        output: String::new(),
+                        error: Some(format!("Blocked by tool policy: {param} {reason}")),
+                    });
+                }
+            }
+        }
+        self.inner.execute(args).await
+    }
+}
+
+fn check(value: &Value, constraint: &ToolParamConstraint) -> Result<(), String> {
+    match value {
+        Value::Array(items) => items.iter().try_for_each(|item| check(item, constraint)),
+        Value::String(text) => {
+            if let Some(max) = constraint.max_bytes {
+                if text.len() > max {
+                    return Err(format!(
+                        "is {} bytes, over the {max}-byte limit",
+                        text.len()
+                    ));
+                }
+            }
+            text.split(',')
+                .map(str::trim)
+                .filter(|item| !item.is_empty())
+                .try_for_each(|item| check_allowed(item, constraint))
+        }
+        Value::Number(number) => {
+            if let Some(number) = number.as_f64() {
+                if let Some(min) = constraint.min.filter(|min| number < *min) {
+                    return Err(format!("must be at least {min}"));
+                }
+                if let Some(max) = constraint.max.filter(|max| number > *max) {
+                    return Err(format!("must be at most {max}"));
+                }
+            }
+            check_allowed(&number.to_string(), constraint)
+        }
+        Value::Bool(flag) => check_allowed(&flag.to_string(), constraint),
+        Value::Null | Value::Object(_) => Ok(()),
+    }
+}
+
+fn check_allowed(value: &str, constraint: &ToolParamConstraint) -> Result<(), String> {
+    if constraint.allowed_values.is_empty()
+        || constraint
+            .allowed_values
+            .iter()
+            .any(|allowed| allowed.eq_ignore_ascii_case(value))
+    {
+        Ok(())
+    } else {
+        Err(format!("value {value:?} is not allowed"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    struct EchoTool(&'static str);
+
+    #[async_trait]
+    impl Tool for EchoTool {
+        fn name(&self) -> &str {
+            self.0
+        }
+
+        fn description(&self) -> &str {
+            "Echo arguments"
+        }
+
+        fn parameters_schema(&self) -> Value {
+            json!({ "type": "object" })
+        }
+
+        async fn execute(&self, args: Value) -> anyhow::Result<ToolResult> {
+            Ok(ToolResult {
+                success: true,
+                output: args.to_string(),
+                error: None,
+            })
+        }
+    }
+
+    fn registry() -> Vec<Arc<dyn Tool>> {
+        vec![
+            Arc::new(EchoTool("shell")),
+            Arc::new(EchoTool("file_write")),
+            Arc::new(EchoTool("email_send")),
+            Arc::new(EchoTool("file_read")),
+        ]
+    }
+
+    fn policies() -> HashMap<String, ToolPolicyConfig> {
+        toml::from_str(
+            r#"
+[shell]
+enabled = false
+
+[file_write]
+name = "save_file"
+description = "Save a note"
+params.content.max_bytes = 8
+
+[email_send.params.to]
+allowed_values = ["me@example.com", "team@example.com"]
+
+[file_read]
+name = "email_send"
+"#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn policies_disable_and_rename_tools() {
+        let tools = apply(registry(), &policies());
+        let names: Vec<&str> = tools.iter().map(|tool| tool.name()).collect();
+        // file_read keeps its name: the rename would shadow email_send.
+        assert_eq!(names, vec!["save_file", "email_send", "file_read"]);
+        assert_eq!(tools[0].description(), "Save a note");
+        assert_eq!(tools[1].description(), "Echo arguments");
+    }
+
+    #[tokio::test]
+    async fn parameter_constraints_block_calls() {
+        let tools = apply(registry(), &policies());
+
+        let ok = tools[0]
+            .execute(json!({ "content": "short" }))
+            .await
+            .unwrap();
+        assert!(ok.success);
+        let blocked = tools[0]
+            .execute(json!({ "content": "far too long" }))
+            .await
+            .unwrap();
+        assert!(!blocked.success);
+        assert!(blocked.error.unwrap().contains("8-byte limit"));
+
+        let email = &tools[1];
+        assert!(
+            email
+                .execute(json!({ "to": "Me@Example.com, team@example.com" }))
+                .await
+                .unwrap()
+                .success
+        );
+        assert!(
+            !email
+                .execute(json!({ "to": ["me@example.com", "boss@example.com"] }))
+                .await
+                .unwrap()
+                .success
+        );
+    }
+
+    #[test]
+    fn numeric_bounds_are_checked() {
+        let constraint = ToolParamConstraint {
+            min: Some(1.0),
+            max: Some(10.0),
+            ..ToolParamConstraint::default()
+        };
+        assert!(check(&json!(5), &constraint).is_ok());
+        assert!(check(&json!(0), &constraint).is_err());
+        assert!(check(&json!([3, 11]), &constraint).is_err());
+    }
+}
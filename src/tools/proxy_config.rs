@@ -1,6 +1,6 @@
 use super::traits::{Tool, ToolResult};
 use crate::config::{
-    runtime_proxy_config, set_runtime_proxy_config, Config, ProxyConfig, ProxyScope,
+    runtime_proxy_config, set_runtime_proxy_config, ChangeSource, Config, ProxyConfig, ProxyScope,
 };
 use crate::security::SecurityPolicy;
 use crate::util::MaybeSet;
@@ -245,7 +245,7 @@ impl ProxyConfigTool {
         proxy.validate()?;
 
         cfg.proxy = proxy.clone();
-        cfg.save().await?;
+        cfg.save_from(ChangeSource::Agent).await?;
         set_runtime_proxy_config(proxy.clone());
 
         if proxy.enabled && proxy.scope == ProxyScope::Environment {
@@ -269,7 +269,7 @@ impl ProxyConfigTool {
         let mut cfg = self.load_config_without_env()?;
         let clear_env_default = cfg.proxy.scope == ProxyScope::Environment;
         cfg.proxy.enabled = false;
-        cfg.save().await?;
+        cfg.save_from(ChangeSource::Agent).await?;
 
         set_runtime_proxy_config(cfg.proxy.clone());
 
@@ -21,7 +21,7 @@ impl PushoverTool {
         }
     }
 
-    fn parse_env_value(raw: &str) -> String {
+    pub(super) fn parse_env_value(raw: &str) -> String {
         let raw = raw.trim();
 
         let unquoted = if raw.len() >= 2
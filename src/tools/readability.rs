@@ -0,0 +1,227 @@
+//! Lightweight readability extraction: strip page chrome from HTML and render
+//! the main content as Markdown for `web_fetch`.
+//!
+//! This is deliberately regex-based rather than a full DOM parser. It keeps
+//! headings, paragraphs, lists, links, emphasis, and code blocks, and drops
+//! scripts, styles, navigation, headers/footers, sidebars, and forms.
+
+use regex::Regex;
+use std::sync::OnceLock;
+
+/// Elements removed together with their content before extraction.
+const BOILERPLATE_TAGS: &[&str] = &[
+    "script", "style", "noscript", "svg", "nav", "header", "footer", "aside", "form", "iframe",
+    "template", "button",
+];
+
+/// Sentinel wrapping preserved `<pre>` block indices during conversion.
+const PRE_SENTINEL: char = '\u{0}';
+
+struct Patterns {
+    comment: Regex,
+    boilerplate: Vec<Regex>,
+    title: Regex,
+    article: Regex,
+    main: Regex,
+    body: Regex,
+    pre: Regex,
+    whitespace: Regex,
+    strong: Regex,
+    em: Regex,
+    code: Regex,
+    link: Regex,
+    heading: Regex,
+    list_item: Regex,
+    line_break: Regex,
+    block: Regex,
+    tag: Regex,
+    numeric_entity: Regex,
+    blank_lines: Regex,
+}
+
+fn patterns() -> &'static Patterns {
+    static PATTERNS: OnceLock<Patterns> = OnceLock::new();
+    PATTERNS.get_or_init(|| {
+        let re = |pattern: &str| Regex::new(pattern).expect("readability regex must compile");
+        Patterns {
+            comment: re(r"(?s)<!--.*?-->"),
+            boilerplate: BOILERPLATE_TAGS
+                .iter()
+                .map(|tag| re(&format!(r"(?is)<{tag}\b[^>]*>.*?</{tag}\s*>")))
+                .collect(),
+            title: re(r"(?is)<title\b[^>]*>(.*?)</title\s*>"),
+            article: re(r"(?is)<article\b[^>]*>(.*)</article\s*>"),
+            main: re(r"(?is)<main\b[^>]*>(.*)</main\s*>"),
+            body: re(r"(?is)<body\b[^>]*>(.*)</body\s*>"),
+            pre: re(r"(?is)<pre\b[^>]*>(.*?)</pre\s*>"),
+            whitespace: re(r"\s+"),
+            strong: re(r"(?is)<(?:strong|b)\b[^>]*>(.*?)</(?:strong|b)\s*>"),
+            em: re(r"(?is)<(?:em|i)\b[^>]*>(.*?)</(?:em|i)\s*>"),
+            code: re(r"(?is)<code\b[^>]*>(.*?)</code\s*>"),
+            link: re(r#"(?is)<a\b[^>]*?href\s*=\s*["']([^"']*)["'][^>]*>(.*?)</a\s*>"#),
+            heading: re(r"(?is)<h([1-6])\b[^>]*>(.*?)</h[1-6]\s*>"),
+            list_item: re(r"(?i)<li\b[^>]*>"),
+            line_break: re(r"(?i)<br\s*/?>"),
+            block: re(
+                r"(?i)</?(?:p|div|section|article|main|ul|ol|table|tr|blockquote|figure|dl|dd|dt)\b[^>]*>",
+            ),
+            tag: re(r"(?s)<[^>]*>"),
+            numeric_entity: re(r"&#(x[0-9a-fA-F]+|[0-9]+);"),
+            blank_lines: re(r"\n[ \t]*\n(?:[ \t]*\n)+"),
+        }
+    })
+}
+
+/// Convert an HTML page to readable Markdown. Relative links are resolved
+/// against `base_url`.
+pub fn html_to_markdown(html: &str, base_url: &str) -> String {
+    let p = patterns();
+
+    let mut html = p.comment.replace_all(html, "").into_owned();
+    for pattern in &p.boilerplate {
+        html = pattern.replace_all(&html, "").into_owned();
+    }
+
+    let title = p
+        .title
+        .captures(&html)
+        .map(|caps| normalize_inline(&caps[1]))
+        .unwrap_or_default();
+
+    let content = [&p.article, &p.main, &p.body]
+        .iter()
+        .find_map(|pattern| pattern.captures(&html).map(|caps| caps[1].to_string()))
+        .unwrap_or(html);
+
+    // Keep preformatted blocks verbatim while everything else is reflowed.
+    let mut pre_blocks = Vec::new();
+    let content = p.pre.replace_all(&content, |caps: &regex::Captures<'_>| {
+        let code = decode_entities(&p.tag.replace_all(&caps[1], ""));
+        pre_blocks.push(format!("\n\n```\n{}\n```\n\n", code.trim_matches('\n')));
+        format!("{PRE_SENTINEL}{}{PRE_SENTINEL}", pre_blocks.len() - 1)
+    });
+
+    let text = p.whitespace.replace_all(&content, " ");
+    let text = p.strong.replace_all(&text, "**$1**");
+    let text = p.em.replace_all(&text, "_${1}_");
+    let text = p.code.replace_all(&text, "`$1`");
+    let text = p.link.replace_all(&text, |caps: &regex::Captures<'_>| {
+        let label = normalize_inline(&caps[2]);
+        match resolve_link(base_url, caps[1].trim()) {
+            Some(href) if !label.is_empty() => format!("[{label}]({href})"),
+            _ => label,
+        }
+    });
+    let text = p.heading.replace_all(&text, |caps: &regex::Captures<'_>| {
+        let level: usize = caps[1].parse().unwrap_or(1);
+        format!(
+            "\n\n{} {}\n\n",
+            "#".repeat(level),
+            normalize_inline(&caps[2])
+        )
+    });
+    let text = p.list_item.replace_all(&text, "\n- ");
+    let text = p.line_break.replace_all(&text, "\n");
+    let text = p.block.replace_all(&text, "\n\n");
+    let text = p.tag.replace_all(&text, "");
+    let text = decode_entities(&text);
+
+    let mut markdown = text.lines().map(str::trim).collect::<Vec<_>>().join("\n");
+    for (index, block) in pre_blocks.iter().enumerate() {
+        markdown = markdown.replace(&format!("{PRE_SENTINEL}{index}{PRE_SENTINEL}"), block);
+    }
+    let markdown = p.blank_lines.replace_all(&markdown, "\n\n");
+    let markdown = markdown.trim();
+
+    if title.is_empty() || markdown.starts_with(&format!("# {title}")) {
+        markdown.to_string()
+    } else {
+        format!("# {title}\n\n{markdown}")
+    }
+}
+
+/// Strip tags and collapse whitespace for single-line contexts.
+fn normalize_inline(fragment: &str) -> String {
+    let p = patterns();
+    let text = p.tag.replace_all(fragment, "");
+    let text = decode_entities(&text);
+    p.whitespace.replace_all(&text, " ").trim().to_string()
+}
+
+fn resolve_link(base_url: &str, href: &str) -> Option<String> {
+    if href.is_empty() || href.starts_with('#') || href.starts_with("javascript:") {
+        return None;
+    }
+    reqwest::Url::parse(base_url)
+        .and_then(|base| base.join(href))
+        .ok()
+        .filter(|url| matches!(url.scheme(), "http" | "https" | "mailto"))
+        .map(String::from)
+}
+
+fn decode_entities(text: &str) -> String {
+    let text = patterns()
+        .numeric_entity
+        .replace_all(text, |caps: &regex::Captures<'_>| {
+            let raw = &caps[1];
+            let code = match raw.strip_prefix('x') {
+                Some(hex) => u32::from_str_radix(hex, 16).ok(),
+                None => raw.parse().ok(),
+            };
+            code.and_then(char::from_u32)
+                .map_or_else(|| caps[0].to_string(), String::from)
+        });
+    text.replace("&nbsp;", " ")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn drops_page_chrome_and_keeps_article() {
+        let html = r#"<html><head><title>Release notes</title><style>p{}</style></head>
+            <body><nav><a href="/">Home</a></nav>
+            <article><h2>What's new</h2><p>Faster <b>startup</b> and
+            <a href="/docs/install">install docs</a>.</p>
+            <ul><li>One</li><li>Two &amp; three</li></ul></article>
+            <footer>© Example</footer><script>track()</script></body></html>"#;
+        let md = html_to_markdown(html, "https://example.com/blog/post");
+
+        assert!(md.starts_with("# Release notes"));
+        assert!(md.contains("## What's new"));
+        assert!(
+            md.contains("Faster **startup** and [install docs](https://example.com/docs/install).")
+        );
+        assert!(md.contains("- One\n- Two & three"));
+        assert!(!md.contains("Home"));
+        assert!(!md.contains("track()"));
+        assert!(!md.contains("© Example"));
+    }
+
+    #[test]
+    fn preserves_preformatted_blocks() {
+        let html =
+            "<body><p>Run:</p><pre><code>cargo build\n  --release &lt;x&gt;</code></pre></body>";
+        let md = html_to_markdown(html, "https://example.com");
+        assert!(md.contains("```\ncargo build\n  --release <x>\n```"));
+    }
+
+    #[test]
+    fn skips_anchor_and_script_links() {
+        let html =
+            r##"<body><a href="#top">Top</a> <a href="javascript:void(0)">Click</a></body>"##;
+        assert_eq!(html_to_markdown(html, "https://example.com"), "Top Click");
+    }
+
+    #[test]
+    fn decodes_numeric_entities() {
+        assert_eq!(decode_entities("caf&#233; &#x2014; ok"), "café — ok");
+    }
+}
@@ -261,6 +261,15 @@ impl ScheduleTool {
             });
         }
 
+        let existing = cron::list_jobs(&self.config)?.len();
+        if let Err(reason) = self.security.check_scheduled_job_capacity(existing) {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(reason),
+            });
+        }
+
         let expression = args.get("expression").and_then(|value| value.as_str());
         let delay = args.get("delay").and_then(|value| value.as_str());
         let run_at = args.get("run_at").and_then(|value| value.as_str());
@@ -475,6 +484,30 @@ mod tests {
         assert!(cancel.success);
     }
 
+    #[tokio::test]
+    async fn create_respects_scheduled_job_cap() {
+        let (_tmp, mut config, _) = test_setup().await;
+        config.autonomy.max_scheduled_jobs = 1;
+        let security = Arc::new(SecurityPolicy::from_config(
+            &config.autonomy,
+            &config.workspace_dir,
+        ));
+        let tool = ScheduleTool::new(security, config);
+        let args = json!({
+            "action": "once",
+            "delay": "1h",
+            "command": "echo remind"
+        });
+
+        assert!(tool.execute(args.clone()).await.unwrap().success);
+        let blocked = tool.execute(args).await.unwrap();
+        assert!(!blocked.success);
+        assert!(blocked
+            .error
+            .unwrap()
+            .contains("Scheduled job limit reached"));
+    }
+
     #[tokio::test]
     async fn once_and_pause_resume_aliases_work() {
         let (_tmp, config, security) = test_setup().await;
@@ -0,0 +1,326 @@
+use super::traits::{Tool, ToolResult};
+use crate::config::Config;
+use crate::cron::{self, DeliveryConfig, Schedule};
+use crate::security::SecurityPolicy;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde_json::json;
+use std::sync::Arc;
+
+const MAX_MESSAGE_CHARS: usize = 1000;
+/// One year ahead is plenty for a personal reminder.
+const MAX_IN_MINUTES: i64 = 365 * 24 * 60;
+const ANDROID_REMINDER_CHANNEL: &str = "reminders";
+
+/// Persist a reminder in the cron store and deliver it as a native Android
+/// notification (or a Telegram message) when due.
+pub struct SetReminderTool {
+    config: Arc<Config>,
+    security: Arc<SecurityPolicy>,
+}
+
+impl SetReminderTool {
+    pub fn new(config: Arc<Config>, security: Arc<SecurityPolicy>) -> Self {
+        Self { config, security }
+    }
+
+    fn android_available(&self) -> bool {
+        self.config.android.enabled && self.config.android.capabilities.notifications
+    }
+
+    fn telegram_chat(&self) -> Option<&str> {
+        self.config
+            .channels_config
+            .telegram
+            .as_ref()
+            .filter(|tg| !tg.bot_token.is_empty())
+            .and_then(|tg| tg.notify_chat_id.as_deref())
+            .map(str::trim)
+            .filter(|chat| !chat.is_empty())
+    }
+
+    /// Pick where the reminder is delivered: Android notification first,
+    /// Telegram `notify_chat_id` as the fallback.
+    fn resolve_delivery(&self, requested: &str) -> anyhow::Result<DeliveryConfig> {
+        let android = || DeliveryConfig {
+            mode: "announce".into(),
+            channel: Some("android".into()),
+            to: Some(ANDROID_REMINDER_CHANNEL.into()),
+            best_effort: false,
+        };
+        let telegram = |chat: &str| DeliveryConfig {
+            mode: "announce".into(),
+            channel: Some("telegram".into()),
+            to: Some(chat.to_string()),
+            best_effort: false,
+        };
+
+        match requested {
+            "auto" => {
+                if self.android_available() {
+                    Ok(android())
+                } else if let Some(chat) = self.telegram_chat() {
+                    Ok(telegram(chat))
+                } else {
+                    anyhow::bail!(
+                        "No reminder delivery available: enable android notifications or set channels_config.telegram.notify_chat_id"
+                    )
+                }
+            }
+            "android" if self.android_available() => Ok(android()),
+            "android" => anyhow::bail!("android notifications are not enabled"),
+            "telegram" => self
+                .telegram_chat()
+                .map(telegram)
+                .ok_or_else(|| anyhow::anyhow!("Telegram notify_chat_id is not configured")),
+            other => anyhow::bail!("Unknown channel '{other}'. Supported: auto, android, telegram"),
+        }
+    }
+
+    fn resolve_schedule(args: &serde_json::Value, now: DateTime<Utc>) -> anyhow::Result<Schedule> {
+        let at = args
+            .get("at")
+            .and_then(serde_json::Value::as_str)
+            .map(str::trim)
+            .filter(|v| !v.is_empty());
+        let in_minutes = args.get("in_minutes").and_then(serde_json::Value::as_i64);
+        let cron_expr = args
+            .get("cron")
+            .and_then(serde_json::Value::as_str)
+            .map(str::trim)
+            .filter(|v| !v.is_empty());
+
+        match (at, in_minutes, cron_expr) {
+            (Some(at), None, None) => {
+                let at = DateTime::parse_from_rfc3339(at)
+                    .map_err(|e| anyhow::anyhow!("'at' must be RFC 3339 with an offset: {e}"))?
+                    .with_timezone(&Utc);
+                if at <= now {
+                    anyhow::bail!("'at' is in the past");
+                }
+                Ok(Schedule::At { at })
+            }
+            (None, Some(minutes), None) => {
+                if !(1..=MAX_IN_MINUTES).contains(&minutes) {
+                    anyhow::bail!("'in_minutes' must be between 1 and {MAX_IN_MINUTES}");
+                }
+                Ok(Schedule::At {
+                    at: now + chrono::Duration::minutes(minutes),
+                })
+            }
+            (None, None, Some(expr)) => Ok(Schedule::Cron {
+                expr: expr.to_string(),
+                tz: args
+                    .get("tz")
+                    .and_then(serde_json::Value::as_str)
+                    .map(str::trim)
+                    .filter(|v| !v.is_empty())
+                    .map(str::to_string),
+            }),
+            (None, None, None) => anyhow::bail!("Provide one of 'at', 'in_minutes', or 'cron'"),
+            _ => anyhow::bail!("Provide only one of 'at', 'in_minutes', or 'cron'"),
+        }
+    }
+
+    fn enforce_mutation_allowed(&self) -> Result<(), String> {
+        if !self.security.can_act() {
+            return Err("Security policy: read-only mode, cannot perform 'set_reminder'".into());
+        }
+        let existing = cron::list_jobs(&self.config).map_or(0, |jobs| jobs.len());
+        self.security.check_scheduled_job_capacity(existing)?;
+        if self.security.is_rate_limited() {
+            return Err("Rate limit exceeded: too many actions in the last hour".into());
+        }
+        if !self.security.record_action() {
+            return Err("Rate limit exceeded: action budget exhausted".into());
+        }
+        Ok(())
+    }
+
+    fn create(&self, args: &serde_json::Value) -> anyhow::Result<serde_json::Value> {
+        if !self.config.cron.enabled {
+            anyhow::bail!("cron is disabled by config (cron.enabled=false)");
+        }
+        let message = args
+            .get("message")
+            .and_then(serde_json::Value::as_str)
+            .map(str::trim)
+            .filter(|v| !v.is_empty())
+            .ok_or_else(|| anyhow::anyhow!("Missing 'message' parameter"))?;
+        if message.chars().count() > MAX_MESSAGE_CHARS {
+            anyhow::bail!("'message' exceeds {MAX_MESSAGE_CHARS} characters");
+        }
+
+        let schedule = Self::resolve_schedule(args, Utc::now())?;
+        let channel = args
+            .get("channel")
+            .and_then(serde_json::Value::as_str)
+            .map(str::trim)
+            .filter(|v| !v.is_empty())
+            .unwrap_or("auto");
+        let delivery = self.resolve_delivery(channel)?;
+
+        if let Err(reason) = self.enforce_mutation_allowed() {
+            anyhow::bail!(reason);
+        }
+
+        let name = message.chars().take(60).collect::<String>();
+        let job = cron::add_reminder_job(&self.config, Some(name), schedule, message, delivery)?;
+        Ok(json!({
+            "id": job.id,
+            "message": message,
+            "schedule": job.schedule,
+            "next_run": job.next_run,
+            "delivery": job.delivery.channel,
+        }))
+    }
+}
+
+#[async_trait]
+impl Tool for SetReminderTool {
+    fn name(&self) -> &str {
+        "set_reminder"
+    }
+
+    fn description(&self) -> &str {
+        "Set a reminder that pops up as a phone notification (or a Telegram message when the phone bridge is unavailable) at the requested time, even if the app restarts. Use 'at' for an exact time (RFC 3339 with the user's UTC offset), 'in_minutes' for relative times, or 'cron' (+ optional 'tz') for repeating reminders. List or cancel reminders with cron_list / cron_remove."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "message": {
+                    "type": "string",
+                    "description": "What to remind the user about"
+                },
+                "at": {
+                    "type": "string",
+                    "description": "Exact time, RFC 3339 with offset, e.g. 2025-03-14T09:00:00+01:00"
+                },
+                "in_minutes": {
+                    "type": "integer",
+                    "description": "Minutes from now"
+                },
+                "cron": {
+                    "type": "string",
+                    "description": "Cron expression for repeating reminders, e.g. '0 8 * * 1-5'"
+                },
+                "tz": {
+                    "type": "string",
+                    "description": "IANA timezone for 'cron', e.g. Europe/Madrid"
+                },
+                "channel": {
+                    "type": "string",
+                    "enum": ["auto", "android", "telegram"],
+                    "default": "auto"
+                }
+            },
+            "required": ["message"]
+        })
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        match self.create(&args) {
+            Ok(output) => Ok(ToolResult {
+                success: true,
+                output: serde_json::to_string_pretty(&output)?,
+                error: None,
+            }),
+            Err(error) => Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(error.to_string()),
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cron::JobType;
+    use crate::security::AutonomyLevel;
+    use tempfile::TempDir;
+
+    fn test_config(tmp: &TempDir, android: bool) -> Arc<Config> {
+        let mut config = Config {
+            workspace_dir: tmp.path().join("workspace"),
+            config_path: tmp.path().join("config.toml"),
+            ..Config::default()
+        };
+        config.android.enabled = android;
+        config.android.capabilities.notifications = android;
+        std::fs::create_dir_all(&config.workspace_dir).unwrap();
+        Arc::new(config)
+    }
+
+    fn test_tool(config: &Arc<Config>) -> SetReminderTool {
+        let security = SecurityPolicy::from_config(&config.autonomy, &config.workspace_dir);
+        SetReminderTool::new(config.clone(), Arc::new(security))
+    }
+
+    #[tokio::test]
+    async fn creates_android_reminder_job() {
+        let tmp = TempDir::new().unwrap();
+        let config = test_config(&tmp, true);
+        let result = test_tool(&config)
+            .execute(json!({ "message": "Take the laundry out", "in_minutes": 45 }))
+            .await
+            .unwrap();
+        assert!(result.success, "{:?}", result.error);
+
+        let jobs = cron::list_jobs(&config).unwrap();
+        assert_eq!(jobs.len(), 1);
+        assert_eq!(jobs[0].job_type, JobType::Reminder);
+        assert_eq!(jobs[0].delivery.channel.as_deref(), Some("android"));
+        assert!(jobs[0].delete_after_run);
+    }
+
+    #[test]
+    fn schedule_requires_exactly_one_time_spec() {
+        let now = Utc::now();
+        assert!(SetReminderTool::resolve_schedule(&json!({}), now).is_err());
+        assert!(SetReminderTool::resolve_schedule(
+            &json!({ "in_minutes": 5, "cron": "0 8 * * *" }),
+            now
+        )
+        .is_err());
+        assert!(
+            SetReminderTool::resolve_schedule(&json!({ "at": "2000-01-01T00:00:00Z" }), now)
+                .is_err()
+        );
+        assert!(matches!(
+            SetReminderTool::resolve_schedule(
+                &json!({ "cron": "0 8 * * 1-5", "tz": "Europe/Madrid" }),
+                now
+            ),
+            Ok(Schedule::Cron { tz: Some(_), .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn fails_without_delivery_path_or_in_read_only_mode() {
+        let tmp = TempDir::new().unwrap();
+        let config = test_config(&tmp, false);
+        let result = test_tool(&config)
+            .execute(json!({ "message": "Stand up", "in_minutes": 30 }))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("No reminder delivery"));
+
+        let config = test_config(&tmp, true);
+        let security = Arc::new(SecurityPolicy {
+            autonomy: AutonomyLevel::ReadOnly,
+            ..SecurityPolicy::from_config(&config.autonomy, &config.workspace_dir)
+        });
+        let result = SetReminderTool::new(config.clone(), security)
+            .execute(json!({ "message": "Stand up", "in_minutes": 30 }))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("read-only"));
+        assert!(cron::list_jobs(&config).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,320 @@
+use super::traits::{Tool, ToolResult};
+use crate::config::SlackConfig;
+use crate::security::SecurityPolicy;
+use async_trait::async_trait;
+use reqwest::Client;
+use serde_json::json;
+use std::sync::Arc;
+use std::time::Duration;
+
+const SLACK_API_TIMEOUT_SECS: u64 = 15;
+const SLACK_MAX_TEXT_CHARS: usize = 40_000;
+const SLACK_MAX_BLOCKS: usize = 50;
+
+/// Where proactive Slack notifications are delivered.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SlackNotifyTarget {
+    /// Incoming webhook URL bound to a single channel.
+    Webhook(String),
+    /// `chat.postMessage` with the bot token.
+    Channel {
+        bot_token: String,
+        channel_id: String,
+    },
+}
+
+impl SlackNotifyTarget {
+    /// Resolve the notify target from config, preferring `notify_webhook_url`
+    /// over `notify_channel_id`.
+    pub fn from_config(config: &SlackConfig) -> Option<Self> {
+        let non_empty = |v: &Option<String>| {
+            v.as_deref()
+                .map(str::trim)
+                .filter(|v| !v.is_empty())
+                .map(str::to_string)
+        };
+
+        if let Some(url) = non_empty(&config.notify_webhook_url) {
+            return Some(Self::Webhook(url));
+        }
+        let channel_id = non_empty(&config.notify_channel_id)?;
+        if config.bot_token.trim().is_empty() {
+            return None;
+        }
+        Some(Self::Channel {
+            bot_token: config.bot_token.clone(),
+            channel_id,
+        })
+    }
+}
+
+pub struct SlackNotifyTool {
+    client: Client,
+    security: Arc<SecurityPolicy>,
+    target: SlackNotifyTarget,
+}
+
+impl SlackNotifyTool {
+    pub fn new(security: Arc<SecurityPolicy>, target: SlackNotifyTarget) -> Self {
+        let client = Client::builder()
+            .timeout(Duration::from_secs(SLACK_API_TIMEOUT_SECS))
+            .build()
+            .unwrap_or_else(|_| Client::new());
+
+        Self {
+            client,
+            security,
+            target,
+        }
+    }
+
+    /// Build the message payload. `text` doubles as the notification fallback
+    /// when blocks are present.
+    fn build_payload(args: &serde_json::Value) -> Result<serde_json::Value, String> {
+        let message = args
+            .get("message")
+            .and_then(|v| v.as_str())
+            .map(str::trim)
+            .filter(|v| !v.is_empty());
+
+        let blocks = match args.get("blocks") {
+            None | Some(serde_json::Value::Null) => Vec::new(),
+            Some(serde_json::Value::Array(items)) => {
+                if items.len() > SLACK_MAX_BLOCKS {
+                    return Err(format!("Too many blocks (max {SLACK_MAX_BLOCKS})"));
+                }
+                if items
+                    .iter()
+                    .any(|item| item.get("type").and_then(|t| t.as_str()).is_none())
+                {
+                    return Err("Each block must be an object with a 'type'".into());
+                }
+                items.clone()
+            }
+            Some(_) => return Err("'blocks' must be an array of Block Kit objects".into()),
+        };
+
+        if message.is_none() && blocks.is_empty() {
+            return Err("Missing 'message' parameter (or at least one block)".into());
+        }
+        if let Some(message) = message {
+            if message.chars().count() > SLACK_MAX_TEXT_CHARS {
+                return Err(format!(
+                    "Message exceeds Slack's {SLACK_MAX_TEXT_CHARS}-character limit"
+                ));
+            }
+        }
+
+        let mut payload = json!({});
+        if let Some(message) = message {
+            payload["text"] = json!(message);
+        }
+        if !blocks.is_empty() {
+            payload["blocks"] = json!(blocks);
+        }
+        Ok(payload)
+    }
+}
+
+#[async_trait]
+impl Tool for SlackNotifyTool {
+    fn name(&self) -> &str {
+        "slack_notify"
+    }
+
+    fn description(&self) -> &str {
+        "Send a Slack message to the configured notify channel or webhook. Use this to push alerts into a team channel; optional Block Kit blocks render rich layouts."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "message": {
+                    "type": "string",
+                    "description": "The message text (mrkdwn). Also used as the notification fallback when blocks are sent"
+                },
+                "blocks": {
+                    "type": "array",
+                    "description": "Optional Slack Block Kit blocks (max 50), e.g. [{\"type\":\"section\",\"text\":{\"type\":\"mrkdwn\",\"text\":\"*Done*\"}}]",
+                    "items": { "type": "object" }
+                }
+            }
+        })
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        if !self.security.can_act() {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some("Action blocked: autonomy is read-only".into()),
+            });
+        }
+
+        if !self.security.record_action() {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some("Action blocked: rate limit exceeded".into()),
+            });
+        }
+
+        let mut payload = match Self::build_payload(&args) {
+            Ok(payload) => payload,
+            Err(error) => {
+                return Ok(ToolResult {
+                    success: false,
+                    output: String::new(),
+                    error: Some(error),
+                })
+            }
+        };
+
+        let request = match &self.target {
+            SlackNotifyTarget::Webhook(url) => self.client.post(url),
+            SlackNotifyTarget::Channel {
+                bot_token,
+                channel_id,
+            } => {
+                payload["channel"] = json!(channel_id);
+                self.client
+                    .post("https://slack.com/api/chat.postMessage")
+                    .bearer_auth(bot_token)
+            }
+        };
+
+        let response = request.json(&payload).send().await?;
+        let status = response.status();
+        let response_text = response.text().await.unwrap_or_default();
+
+        if !status.is_success() {
+            return Ok(ToolResult {
+                success: false,
+                output: response_text,
+                error: Some(format!("Slack API returned status {status}")),
+            });
+        }
+
+        // Webhooks answer a bare "ok"; chat.postMessage reports errors in a 200 body.
+        if let SlackNotifyTarget::Channel { channel_id, .. } = &self.target {
+            let parsed = serde_json::from_str::<serde_json::Value>(&response_text).ok();
+            let ok = parsed
+                .as_ref()
+                .and_then(|json| json.get("ok").and_then(|v| v.as_bool()))
+                .unwrap_or(false);
+            if !ok {
+                let reason = parsed
+                    .as_ref()
+                    .and_then(|json| json.get("error").and_then(|v| v.as_str()))
+                    .unwrap_or("unknown error");
+                return Ok(ToolResult {
+                    success: false,
+                    output: response_text.clone(),
+                    error: Some(format!("Slack API returned ok=false: {reason}")),
+                });
+            }
+            return Ok(ToolResult {
+                success: true,
+                output: format!("Slack message sent to channel {channel_id}."),
+                error: None,
+            });
+        }
+
+        Ok(ToolResult {
+            success: true,
+            output: "Slack message sent via webhook.".into(),
+            error: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::security::AutonomyLevel;
+
+    fn test_security(level: AutonomyLevel, max_actions_per_hour: u32) -> Arc<SecurityPolicy> {
+        Arc::new(SecurityPolicy {
+            autonomy: level,
+            max_actions_per_hour,
+            workspace_dir: std::env::temp_dir(),
+            ..SecurityPolicy::default()
+        })
+    }
+
+    fn test_tool(level: AutonomyLevel, max_actions_per_hour: u32) -> SlackNotifyTool {
+        SlackNotifyTool::new(
+            test_security(level, max_actions_per_hour),
+            SlackNotifyTarget::Channel {
+                bot_token: "xoxb-test".into(),
+                channel_id: "C1234567890".into(),
+            },
+        )
+    }
+
+    fn slack_config(channel: Option<&str>, webhook: Option<&str>) -> SlackConfig {
+        SlackConfig {
+            bot_token: "xoxb-test".into(),
+            app_token: None,
+            channel_id: None,
+            allowed_users: vec![],
+            notify_channel_id: channel.map(String::from),
+            notify_webhook_url: webhook.map(String::from),
+        }
+    }
+
+    #[test]
+    fn tool_name() {
+        assert_eq!(test_tool(AutonomyLevel::Full, 100).name(), "slack_notify");
+    }
+
+    #[test]
+    fn target_prefers_webhook_over_channel() {
+        let target = SlackNotifyTarget::from_config(&slack_config(
+            Some("C1"),
+            Some("https://hooks.slack.com/services/T/B/x"),
+        ));
+        assert_eq!(
+            target,
+            Some(SlackNotifyTarget::Webhook(
+                "https://hooks.slack.com/services/T/B/x".into()
+            ))
+        );
+
+        let target = SlackNotifyTarget::from_config(&slack_config(Some("C1"), None));
+        assert!(matches!(target, Some(SlackNotifyTarget::Channel { .. })));
+
+        assert!(SlackNotifyTarget::from_config(&slack_config(None, None)).is_none());
+    }
+
+    #[test]
+    fn payload_accepts_blocks_and_validates_shape() {
+        let payload = SlackNotifyTool::build_payload(&json!({
+            "message": "Deploy finished",
+            "blocks": [{"type": "section", "text": {"type": "mrkdwn", "text": "*Deploy finished*"}}]
+        }))
+        .unwrap();
+        assert_eq!(payload["text"], "Deploy finished");
+        assert_eq!(payload["blocks"][0]["type"], "section");
+
+        assert!(SlackNotifyTool::build_payload(&json!({"blocks": [{"text": "no type"}]})).is_err());
+        assert!(SlackNotifyTool::build_payload(&json!({"message": " "})).is_err());
+    }
+
+    #[tokio::test]
+    async fn execute_blocks_readonly_mode() {
+        let tool = test_tool(AutonomyLevel::ReadOnly, 100);
+        let result = tool.execute(json!({"message": "hello"})).await.unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("read-only"));
+    }
+
+    #[tokio::test]
+    async fn execute_blocks_rate_limit() {
+        let tool = test_tool(AutonomyLevel::Full, 0);
+        let result = tool.execute(json!({"message": "hello"})).await.unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("rate limit"));
+    }
+}
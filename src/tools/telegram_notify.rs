@@ -1,18 +1,223 @@
 use super::traits::{Tool, ToolResult};
+use crate::config::TelegramConfig;
 use crate::security::SecurityPolicy;
 use async_trait::async_trait;
+use reqwest::multipart::{Form, Part};
 use reqwest::Client;
 use serde_json::json;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::time::Duration;
 
 const TELEGRAM_API_TIMEOUT_SECS: u64 = 15;
+const TELEGRAM_UPLOAD_TIMEOUT_SECS: u64 = 120;
+const TELEGRAM_MAX_CAPTION_CHARS: usize = 1024;
+const TELEGRAM_MAX_PHOTO_BYTES: u64 = 10 * 1024 * 1024;
+const TELEGRAM_MAX_FILE_BYTES: u64 = 50 * 1024 * 1024;
+const TELEGRAM_MAX_BUTTONS: usize = 100;
+const TELEGRAM_MAX_CALLBACK_DATA_BYTES: usize = 64;
+const MARKDOWN_V2_SPECIAL: &str = "_*[]()~`>#+-=|{}.!\\";
+
+/// How a workspace file is delivered to Telegram.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum AttachmentKind {
+    Photo,
+    Document,
+    Audio,
+}
+
+impl AttachmentKind {
+    fn from_name(name: &str) -> Option<Self> {
+        match name.trim().to_ascii_lowercase().as_str() {
+            "photo" | "image" => Some(Self::Photo),
+            "document" | "file" => Some(Self::Document),
+            "audio" => Some(Self::Audio),
+            _ => None,
+        }
+    }
+
+    /// Guess from the file extension; anything unrecognised goes as a document.
+    fn infer(path: &Path) -> Self {
+        let ext = path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .map(str::to_ascii_lowercase)
+            .unwrap_or_default();
+        match ext.as_str() {
+            "png" | "jpg" | "jpeg" | "webp" => Self::Photo,
+            "mp3" | "m4a" | "ogg" | "oga" | "wav" | "flac" | "aac" | "opus" => Self::Audio,
+            _ => Self::Document,
+        }
+    }
+
+    /// Bot API method and multipart field name.
+    fn method_and_field(self) -> (&'static str, &'static str) {
+        match self {
+            Self::Photo => ("sendPhoto", "photo"),
+            Self::Document => ("sendDocument", "document"),
+            Self::Audio => ("sendAudio", "audio"),
+        }
+    }
+
+    fn max_bytes(self) -> u64 {
+        match self {
+            Self::Photo => TELEGRAM_MAX_PHOTO_BYTES,
+            Self::Document | Self::Audio => TELEGRAM_MAX_FILE_BYTES,
+        }
+    }
+}
+
+/// Formatting applied to the message text (or caption).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+enum ParseMode {
+    #[default]
+    Plain,
+    /// Common Markdown, converted to escaped MarkdownV2.
+    Markdown,
+    /// Caller-escaped MarkdownV2, sent as-is.
+    MarkdownV2,
+    Html,
+}
+
+impl ParseMode {
+    fn from_name(name: &str) -> Option<Self> {
+        match name.trim().to_ascii_lowercase().as_str() {
+            "plain" | "none" | "text" => Some(Self::Plain),
+            "markdown" => Some(Self::Markdown),
+            "markdownv2" | "markdown_v2" => Some(Self::MarkdownV2),
+            "html" => Some(Self::Html),
+            _ => None,
+        }
+    }
+
+    /// Text to send and the Bot API `parse_mode` value.
+    fn render(self, text: &str) -> (String, Option<&'static str>) {
+        match self {
+            Self::Plain => (text.to_string(), None),
+            Self::Markdown => (markdown_to_markdown_v2(text), Some("MarkdownV2")),
+            Self::MarkdownV2 => (text.to_string(), Some("MarkdownV2")),
+            Self::Html => (text.to_string(), Some("HTML")),
+        }
+    }
+}
+
+/// Escape every character MarkdownV2 treats as markup.
+fn escape_markdown_v2(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        if MARKDOWN_V2_SPECIAL.contains(c) {
+            out.push('\\');
+        }
+        out.push(c);
+    }
+    out
+}
+
+/// Escape text inside `code` / ```pre``` entities, where only ` and \ are special.
+fn escape_markdown_v2_code(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('`', "\\`")
+}
+
+/// Find `pat` in `chars` at or after `from`, on the same line unless `multiline`.
+fn find_closing(chars: &[char], from: usize, pat: &[char], multiline: bool) -> Option<usize> {
+    let mut i = from;
+    while i + pat.len() <= chars.len() {
+        if !multiline && chars[i] == '\n' {
+            return None;
+        }
+        if chars[i..i + pat.len()] == *pat {
+            return Some(i);
+        }
+        i += 1;
+    }
+    None
+}
+
+/// Convert common Markdown (`**bold**`, `*italic*`, `_italic_`, `` `code` ``,
+/// fenced code, `[label](url)`) to MarkdownV2, escaping everything else so
+/// identifiers like `file_name.txt` survive intact.
+fn markdown_to_markdown_v2(text: &str) -> String {
+    let chars: Vec<char> = text.chars().collect();
+    let collect = |from: usize, to: usize| chars[from..to].iter().collect::<String>();
+    let mut out = String::with_capacity(text.len() + 16);
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        let prev_is_word = i > 0 && chars[i - 1].is_alphanumeric();
+
+        if chars[i..].starts_with(&['`', '`', '`']) {
+            if let Some(end) = find_closing(&chars, i + 3, &['`', '`', '`'], true) {
+                out.push_str("```");
+                out.push_str(&escape_markdown_v2_code(&collect(i + 3, end)));
+                out.push_str("```");
+                i = end + 3;
+                continue;
+            }
+        } else if c == '`' {
+            if let Some(end) = find_closing(&chars, i + 1, &['`'], false) {
+                out.push('`');
+                out.push_str(&escape_markdown_v2_code(&collect(i + 1, end)));
+                out.push('`');
+                i = end + 1;
+                continue;
+            }
+        } else if chars[i..].starts_with(&['*', '*']) {
+            if let Some(end) = find_closing(&chars, i + 2, &['*', '*'], false) {
+                if end > i + 2 {
+                    out.push('*');
+                    out.push_str(&escape_markdown_v2(&collect(i + 2, end)));
+                    out.push('*');
+                    i = end + 2;
+                    continue;
+                }
+            }
+        } else if (c == '*' || c == '_') && !prev_is_word {
+            if let Some(end) = find_closing(&chars, i + 1, &[c], false) {
+                let next_is_word = chars.get(end + 1).is_some_and(|n| n.is_alphanumeric());
+                let inner_ok = end > i + 1 && !chars[i + 1].is_whitespace();
+                if inner_ok && !next_is_word {
+                    out.push('_');
+                    out.push_str(&escape_markdown_v2(&collect(i + 1, end)));
+                    out.push('_');
+                    i = end + 1;
+                    continue;
+                }
+            }
+        } else if c == '[' {
+            if let Some(mid) = find_closing(&chars, i + 1, &[']', '('], false) {
+                if let Some(end) = find_closing(&chars, mid + 2, &[')'], false) {
+                    let url = collect(mid + 2, end);
+                    out.push('[');
+                    out.push_str(&escape_markdown_v2(&collect(i + 1, mid)));
+                    out.push_str("](");
+                    out.push_str(&url.replace('\\', "\\\\").replace(')', "\\)"));
+                    out.push(')');
+                    i = end + 1;
+                    continue;
+                }
+            }
+        }
+
+        if MARKDOWN_V2_SPECIAL.contains(c) {
+            out.push('\\');
+        }
+        out.push(c);
+        i += 1;
+    }
+
+    out
+}
 
 pub struct TelegramNotifyTool {
     client: Client,
     security: Arc<SecurityPolicy>,
     bot_token: String,
-    chat_id: String,
+    /// Default chat used when the caller does not pick one.
+    chat_id: Option<String>,
+    /// Named chats the caller may target in addition to the default.
+    targets: HashMap<String, String>,
 }
 
 impl TelegramNotifyTool {
@@ -26,12 +231,375 @@ impl TelegramNotifyTool {
             client,
             security,
             bot_token,
-            chat_id,
+            chat_id: Some(chat_id).filter(|id| !id.is_empty()),
+            targets: HashMap::new(),
+        }
+    }
+
+    pub fn with_targets(mut self, targets: HashMap<String, String>) -> Self {
+        self.targets = targets
+            .into_iter()
+            .map(|(name, id)| (name.trim().to_string(), id.trim().to_string()))
+            .filter(|(name, id)| !name.is_empty() && !id.is_empty())
+            .collect();
+        self
+    }
+
+    /// Build the tool from `[channels_config.telegram]` when a bot token and at
+    /// least one notify chat (`notify_chat_id` or `notify_targets`) are configured.
+    pub fn from_config(security: Arc<SecurityPolicy>, config: &TelegramConfig) -> Option<Self> {
+        if config.bot_token.is_empty() {
+            return None;
+        }
+        let tool = Self::new(
+            security,
+            config.bot_token.clone(),
+            config.notify_chat_id.clone().unwrap_or_default(),
+        )
+        .with_targets(config.notify_targets.clone());
+        (tool.chat_id.is_some() || !tool.targets.is_empty()).then_some(tool)
+    }
+
+    /// Pick the destination chat. `chat_id` may be a configured target name or
+    /// one of the allowlisted chat IDs; anything else is refused.
+    fn resolve_chat(&self, args: &serde_json::Value) -> Result<String, String> {
+        let requested = args
+            .get("chat_id")
+            .and_then(|v| match v {
+                serde_json::Value::String(s) => Some(s.trim().to_string()),
+                serde_json::Value::Number(n) => Some(n.to_string()),
+                _ => None,
+            })
+            .filter(|v| !v.is_empty());
+
+        let Some(requested) = requested else {
+            return self.chat_id.clone().ok_or_else(|| {
+                format!(
+                    "No default notify chat configured; pass chat_id as one of: {}",
+                    self.target_names().join(", ")
+                )
+            });
+        };
+
+        if let Some(id) = self.targets.get(&requested) {
+            return Ok(id.clone());
+        }
+        if self.chat_id.as_deref() == Some(requested.as_str())
+            || self.targets.values().any(|id| id == &requested)
+        {
+            return Ok(requested);
         }
+        Err(format!(
+            "Chat '{requested}' is not an allowed notify target. Configure it in channels_config.telegram.notify_targets{}",
+            if self.targets.is_empty() {
+                String::new()
+            } else {
+                format!(" (available: {})", self.target_names().join(", "))
+            }
+        ))
     }
 
-    fn send_message_url(&self) -> String {
-        format!("https://api.telegram.org/bot{}/sendMessage", self.bot_token)
+    fn target_names(&self) -> Vec<&str> {
+        let mut names: Vec<&str> = self.targets.keys().map(String::as_str).collect();
+        names.sort_unstable();
+        names
+    }
+
+    fn api_url(&self, method: &str) -> String {
+        format!("https://api.telegram.org/bot{}/{method}", self.bot_token)
+    }
+
+    /// Build `inline_keyboard` reply markup from the `buttons` argument.
+    ///
+    /// Accepts a flat list (one row) or a list of rows. Each button is either a
+    /// string (label doubles as callback data), `{text, data}`, or `{text, url}`.
+    fn parse_buttons(args: &serde_json::Value) -> Result<Option<serde_json::Value>, String> {
+        let items = match args.get("buttons") {
+            None | Some(serde_json::Value::Null) => return Ok(None),
+            Some(serde_json::Value::Array(items)) if items.is_empty() => return Ok(None),
+            Some(serde_json::Value::Array(items)) => items,
+            Some(_) => return Err("'buttons' must be an array".into()),
+        };
+
+        let rows: Vec<&[serde_json::Value]> = if items.iter().all(|item| item.is_array()) {
+            items
+                .iter()
+                .filter_map(|row| row.as_array().map(Vec::as_slice))
+                .collect()
+        } else if items.iter().any(|item| item.is_array()) {
+            return Err("'buttons' must be either a list of buttons or a list of rows".into());
+        } else {
+            vec![items.as_slice()]
+        };
+
+        let total: usize = rows.iter().map(|row| row.len()).sum();
+        if total > TELEGRAM_MAX_BUTTONS {
+            return Err(format!("Too many buttons (max {TELEGRAM_MAX_BUTTONS})"));
+        }
+
+        let mut keyboard = Vec::with_capacity(rows.len());
+        for row in rows {
+            let mut out_row = Vec::with_capacity(row.len());
+            for button in row {
+                out_row.push(Self::parse_button(button)?);
+            }
+            if !out_row.is_empty() {
+                keyboard.push(out_row);
+            }
+        }
+
+        Ok(Some(json!({ "inline_keyboard": keyboard })))
+    }
+
+    fn parse_button(button: &serde_json::Value) -> Result<serde_json::Value, String> {
+        let (text, data, url) = match button {
+            serde_json::Value::String(label) => (label.as_str(), Some(label.as_str()), None),
+            serde_json::Value::Object(obj) => (
+                obj.get("text").and_then(|v| v.as_str()).unwrap_or_default(),
+                obj.get("data").and_then(|v| v.as_str()),
+                obj.get("url").and_then(|v| v.as_str()),
+            ),
+            _ => return Err("Each button must be a string or an object".into()),
+        };
+
+        let text = text.trim();
+        if text.is_empty() {
+            return Err("Each button needs a non-empty 'text'".into());
+        }
+
+        match (data.map(str::trim), url.map(str::trim)) {
+            (_, Some(url)) if !url.is_empty() => {
+                if !url.starts_with("https://") && !url.starts_with("http://") {
+                    return Err(format!("Button '{text}' url must be an http(s) URL"));
+                }
+                Ok(json!({ "text": text, "url": url }))
+            }
+            (data, _) => {
+                let data = data.filter(|d| !d.is_empty()).unwrap_or(text);
+                if data.len() > TELEGRAM_MAX_CALLBACK_DATA_BYTES {
+                    return Err(format!(
+                        "Button '{text}' callback data exceeds {TELEGRAM_MAX_CALLBACK_DATA_BYTES} bytes"
+                    ));
+                }
+                Ok(json!({ "text": text, "callback_data": data }))
+            }
+        }
+    }
+
+    /// Resolve a workspace attachment path, applying the same sandbox checks
+    /// as the file tools.
+    async fn resolve_attachment(&self, path: &str) -> Result<PathBuf, String> {
+        if !self.security.is_path_allowed(path) {
+            return Err(format!("Path not allowed by security policy: {path}"));
+        }
+
+        let full_path = self.security.workspace_dir.join(path);
+        let resolved = tokio::fs::canonicalize(&full_path)
+            .await
+            .map_err(|e| format!("Failed to resolve attachment path: {e}"))?;
+
+        if !self.security.is_resolved_path_allowed(&resolved) {
+            return Err(self.security.resolved_path_violation_message(&resolved));
+        }
+        if !resolved.is_file() {
+            return Err(format!("Attachment is not a file: {path}"));
+        }
+        Ok(resolved)
+    }
+
+    async fn send_attachment(
+        &self,
+        chat_id: &str,
+        path: &Path,
+        kind: AttachmentKind,
+        caption: Option<&str>,
+        parse_mode: ParseMode,
+        reply_markup: Option<&serde_json::Value>,
+    ) -> anyhow::Result<ToolResult> {
+        let size = tokio::fs::metadata(path).await?.len();
+        if size > kind.max_bytes() {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(format!(
+                    "Attachment too large: {size} bytes (limit: {} bytes)",
+                    kind.max_bytes()
+                )),
+            });
+        }
+
+        let file_name = path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or("file")
+            .to_string();
+        let (_, field) = kind.method_and_field();
+        let success_output = format!("Telegram {field} {file_name} sent to chat {chat_id}.");
+
+        let rendered = caption.map(|caption| parse_mode.render(caption));
+        let (status, body) = self
+            .post_attachment(
+                chat_id,
+                path,
+                &file_name,
+                kind,
+                rendered.as_ref().map(|(text, mode)| (text.as_str(), *mode)),
+                reply_markup,
+            )
+            .await?;
+
+        let formatted = rendered.as_ref().is_some_and(|(_, mode)| mode.is_some());
+        if formatted && Self::is_entity_parse_error(status, &body) {
+            tracing::warn!("Telegram rejected caption formatting; retrying as plain text");
+            let (status, body) = self
+                .post_attachment(
+                    chat_id,
+                    path,
+                    &file_name,
+                    kind,
+                    caption.map(|caption| (caption, None)),
+                    reply_markup,
+                )
+                .await?;
+            return Ok(Self::interpret_response(
+                status,
+                body,
+                format!("{success_output} Caption sent as plain text because Telegram rejected the formatting."),
+            ));
+        }
+
+        Ok(Self::interpret_response(status, body, success_output))
+    }
+
+    async fn post_attachment(
+        &self,
+        chat_id: &str,
+        path: &Path,
+        file_name: &str,
+        kind: AttachmentKind,
+        caption: Option<(&str, Option<&'static str>)>,
+        reply_markup: Option<&serde_json::Value>,
+    ) -> anyhow::Result<(reqwest::StatusCode, String)> {
+        let file_bytes = tokio::fs::read(path).await?;
+        let (method, field) = kind.method_and_field();
+
+        let mut form = Form::new().text("chat_id", chat_id.to_string()).part(
+            field,
+            Part::bytes(file_bytes).file_name(file_name.to_string()),
+        );
+        if let Some((caption, parse_mode)) = caption {
+            form = form.text("caption", caption.to_string());
+            if let Some(parse_mode) = parse_mode {
+                form = form.text("parse_mode", parse_mode);
+            }
+        }
+        if let Some(markup) = reply_markup {
+            form = form.text("reply_markup", markup.to_string());
+        }
+
+        let response = self
+            .client
+            .post(self.api_url(method))
+            .timeout(Duration::from_secs(TELEGRAM_UPLOAD_TIMEOUT_SECS))
+            .multipart(form)
+            .send()
+            .await?;
+        let status = response.status();
+        Ok((status, response.text().await.unwrap_or_default()))
+    }
+
+    async fn send_text(
+        &self,
+        chat_id: &str,
+        message: &str,
+        parse_mode: ParseMode,
+        reply_markup: Option<&serde_json::Value>,
+    ) -> anyhow::Result<ToolResult> {
+        let success_output = format!("Telegram message sent to chat {chat_id}.");
+        let (text, mode) = parse_mode.render(message);
+        let (status, body) = self.post_text(chat_id, &text, mode, reply_markup).await?;
+
+        if mode.is_some() && Self::is_entity_parse_error(status, &body) {
+            tracing::warn!("Telegram rejected message formatting; retrying as plain text");
+            let (status, body) = self.post_text(chat_id, message, None, reply_markup).await?;
+            return Ok(Self::interpret_response(
+                status,
+                body,
+                format!(
+                    "{success_output} Sent as plain text because Telegram rejected the formatting."
+                ),
+            ));
+        }
+
+        Ok(Self::interpret_response(status, body, success_output))
+    }
+
+    async fn post_text(
+        &self,
+        chat_id: &str,
+        text: &str,
+        parse_mode: Option<&'static str>,
+        reply_markup: Option<&serde_json::Value>,
+    ) -> anyhow::Result<(reqwest::StatusCode, String)> {
+        let mut body = json!({
+            "chat_id": chat_id,
+            "text": text,
+        });
+        if let Some(parse_mode) = parse_mode {
+            body["parse_mode"] = json!(parse_mode);
+        }
+        if let Some(markup) = reply_markup {
+            body["reply_markup"] = markup.clone();
+        }
+
+        let response = self
+            .client
+            .post(self.api_url("sendMessage"))
+            .header("Content-Type", "application/json")
+            .body(body.to_string())
+            .send()
+            .await?;
+        let status = response.status();
+        Ok((status, response.text().await.unwrap_or_default()))
+    }
+
+    /// Telegram answers 400 "can't parse entities" when formatting is malformed.
+    fn is_entity_parse_error(status: reqwest::StatusCode, body: &str) -> bool {
+        status == reqwest::StatusCode::BAD_REQUEST
+            && body.to_ascii_lowercase().contains("can't parse entities")
+    }
+
+    fn interpret_response(
+        status: reqwest::StatusCode,
+        response_text: String,
+        success_output: String,
+    ) -> ToolResult {
+        if !status.is_success() {
+            return ToolResult {
+                success: false,
+                output: response_text,
+                error: Some(format!("Telegram API returned status {}", status)),
+            };
+        }
+
+        let ok = serde_json::from_str::<serde_json::Value>(&response_text)
+            .ok()
+            .and_then(|json| json.get("ok").and_then(|v| v.as_bool()))
+            .unwrap_or(false);
+
+        if ok {
+            ToolResult {
+                success: true,
+                output: success_output,
+                error: None,
+            }
+        } else {
+            ToolResult {
+                success: false,
+                output: response_text,
+                error: Some("Telegram API returned ok=false".into()),
+            }
+        }
     }
 }
 
@@ -42,7 +610,7 @@ impl Tool for TelegramNotifyTool {
     }
 
     fn description(&self) -> &str {
-        "Send a Telegram message to the configured notify chat. Use this to proactively notify the user via Telegram."
+        "Send a Telegram message to the configured notify chat (or a named notify target via chat_id). Use this to proactively notify the user via Telegram, optionally attaching a workspace file (photo, document, or audio) such as a generated chart or export."
     }
 
     fn parameters_schema(&self) -> serde_json::Value {
@@ -51,10 +619,60 @@ impl Tool for TelegramNotifyTool {
             "properties": {
                 "message": {
                     "type": "string",
-                    "description": "The message text to send"
+                    "description": "The message text to send; used as the caption when an attachment is sent"
+                },
+                "parse_mode": {
+                    "type": "string",
+                    "enum": ["plain", "markdown", "markdown_v2", "html"],
+                    "description": "Formatting: plain (default); markdown converts **bold**, _italic_, `code` and [links](url) with escaping handled for you; markdown_v2 and html are sent as-is. Falls back to plain text if Telegram rejects the formatting"
+                },
+                "chat_id": {
+                    "type": "string",
+                    "description": "Optional notify target name or allowlisted chat ID (default: the configured notify chat)"
+                },
+                "attachment": {
+                    "type": "string",
+                    "description": "Optional workspace-relative path of a file to send"
+                },
+                "buttons": {
+                    "type": "array",
+                    "description": "Optional inline keyboard. A list of buttons (one row) or a list of rows. Each button is a label string, {\"text\", \"data\"} (pressing it sends the data back to you as a message), or {\"text\", \"url\"}. Example: [\"Approve\", \"Deny\"]",
+                    "items": {
+                        "anyOf": [
+                            { "type": "string" },
+                            {
+                                "type": "object",
+                                "properties": {
+                                    "text": { "type": "string" },
+                                    "data": { "type": "string" },
+                                    "url": { "type": "string" }
+                                }
+                            },
+                            {
+                                "type": "array",
+                                "items": {
+                                    "anyOf": [
+                                        { "type": "string" },
+                                        {
+                                            "type": "object",
+                                            "properties": {
+                                                "text": { "type": "string" },
+                                                "data": { "type": "string" },
+                                                "url": { "type": "string" }
+                                            }
+                                        }
+                                    ]
+                                }
+                            }
+                        ]
+                    }
+                },
+                "attachment_type": {
+                    "type": "string",
+                    "enum": ["photo", "document", "audio"],
+                    "description": "How to send the attachment (default: inferred from the file extension)"
                 }
-            },
-            "required": ["message"]
+            }
         })
     }
 
@@ -79,52 +697,104 @@ impl Tool for TelegramNotifyTool {
             .get("message")
             .and_then(|v| v.as_str())
             .map(str::trim)
-            .filter(|v| !v.is_empty())
-            .ok_or_else(|| anyhow::anyhow!("Missing 'message' parameter"))?
-            .to_string();
+            .filter(|v| !v.is_empty());
 
-        let body = json!({
-            "chat_id": self.chat_id,
-            "text": message,
-        });
+        let chat_id = match self.resolve_chat(&args) {
+            Ok(chat_id) => chat_id,
+            Err(error) => {
+                return Ok(ToolResult {
+                    success: false,
+                    output: String::new(),
+                    error: Some(error),
+                })
+            }
+        };
 
-        let response = self
-            .client
-            .post(&self.send_message_url())
-            .header("Content-Type", "application/json")
-            .body(body.to_string())
-            .send()
-            .await?;
+        let parse_mode = match args.get("parse_mode").and_then(|v| v.as_str()) {
+            Some(name) => match ParseMode::from_name(name) {
+                Some(mode) => mode,
+                None => {
+                    return Ok(ToolResult {
+                        success: false,
+                        output: String::new(),
+                        error: Some(format!(
+                            "Invalid 'parse_mode': {name}. Expected plain, markdown, markdown_v2, or html"
+                        )),
+                    })
+                }
+            },
+            None => ParseMode::default(),
+        };
 
-        let status = response.status();
-        let response_text = response.text().await.unwrap_or_default();
+        let reply_markup = match Self::parse_buttons(&args) {
+            Ok(markup) => markup,
+            Err(error) => {
+                return Ok(ToolResult {
+                    success: false,
+                    output: String::new(),
+                    error: Some(error),
+                })
+            }
+        };
 
-        if !status.is_success() {
+        let Some(attachment) = args
+            .get("attachment")
+            .and_then(|v| v.as_str())
+            .map(str::trim)
+            .filter(|v| !v.is_empty())
+        else {
+            let message = message.ok_or_else(|| anyhow::anyhow!("Missing 'message' parameter"))?;
+            return self
+                .send_text(&chat_id, message, parse_mode, reply_markup.as_ref())
+                .await;
+        };
+
+        if message.is_some_and(|m| m.chars().count() > TELEGRAM_MAX_CAPTION_CHARS) {
             return Ok(ToolResult {
                 success: false,
-                output: response_text.clone(),
-                error: Some(format!("Telegram API returned status {}", status)),
+                output: String::new(),
+                error: Some(format!(
+                    "Caption exceeds {TELEGRAM_MAX_CAPTION_CHARS} characters; send the text separately"
+                )),
             });
         }
 
-        let ok = serde_json::from_str::<serde_json::Value>(&response_text)
-            .ok()
-            .and_then(|json| json.get("ok").and_then(|v| v.as_bool()))
-            .unwrap_or(false);
+        let path = match self.resolve_attachment(attachment).await {
+            Ok(path) => path,
+            Err(error) => {
+                return Ok(ToolResult {
+                    success: false,
+                    output: String::new(),
+                    error: Some(error),
+                })
+            }
+        };
 
-        if ok {
-            Ok(ToolResult {
-                success: true,
-                output: format!("Telegram message sent to chat {}.", self.chat_id),
-                error: None,
-            })
-        } else {
-            Ok(ToolResult {
-                success: false,
-                output: response_text.clone(),
-                error: Some("Telegram API returned ok=false".into()),
-            })
-        }
+        let kind = match args.get("attachment_type").and_then(|v| v.as_str()) {
+            Some(name) => match AttachmentKind::from_name(name) {
+                Some(kind) => kind,
+                None => {
+                    return Ok(ToolResult {
+                        success: false,
+                        output: String::new(),
+                        error: Some(format!(
+                            "Invalid 'attachment_type': {name}. Expected photo, document, or audio"
+                        )),
+                    })
+                }
+            },
+            None => AttachmentKind::infer(&path),
+        };
+
+        self.send_attachment(
+            &chat_id,
+            &path,
+            kind,
+            message,
+            parse_mode,
+            reply_markup.as_ref(),
+        )
+        .await
     }
 }
 
@@ -162,16 +832,229 @@ mod tests {
         assert!(!tool.description().is_empty());
     }
 
+    #[tokio::test]
+    async fn tool_requires_message_without_attachment() {
+        let tool = TelegramNotifyTool::new(
+            test_security(AutonomyLevel::Full, 100),
+            "123:ABC".into(),
+            "987654321".into(),
+        );
+        let result = tool.execute(json!({})).await;
+        assert!(result.unwrap_err().to_string().contains("message"));
+    }
+
+    fn targeted_tool() -> TelegramNotifyTool {
+        TelegramNotifyTool::new(
+            test_security(AutonomyLevel::Full, 100),
+            "123:ABC".into(),
+            "987654321".into(),
+        )
+        .with_targets(HashMap::from([
+            ("family".to_string(), "-1001234567890".to_string()),
+            ("work".to_string(), "-1009876543210".to_string()),
+        ]))
+    }
+
+    #[test]
+    fn resolve_chat_uses_default_names_and_allowlisted_ids() {
+        let tool = targeted_tool();
+        assert_eq!(tool.resolve_chat(&json!({})).unwrap(), "987654321");
+        assert_eq!(
+            tool.resolve_chat(&json!({"chat_id": "family"})).unwrap(),
+            "-1001234567890"
+        );
+        assert_eq!(
+            tool.resolve_chat(&json!({"chat_id": -1009876543210_i64}))
+                .unwrap(),
+            "-1009876543210"
+        );
+        let err = tool
+            .resolve_chat(&json!({"chat_id": "-100555"}))
+            .unwrap_err();
+        assert!(err.contains("not an allowed notify target"));
+        assert!(err.contains("family, work"));
+    }
+
+    #[test]
+    fn from_config_accepts_targets_without_default_chat() {
+        let config = TelegramConfig {
+            bot_token: "123:ABC".into(),
+            allowed_users: vec![],
+            stream_mode: crate::config::StreamMode::default(),
+            draft_update_interval_ms: 1000,
+            interrupt_on_new_message: false,
+            mention_only: false,
+            notify_chat_id: None,
+            notify_targets: HashMap::from([("family".to_string(), "-100123".to_string())]),
+        };
+        let tool =
+            TelegramNotifyTool::from_config(test_security(AutonomyLevel::Full, 100), &config)
+                .unwrap();
+        let err = tool.resolve_chat(&json!({})).unwrap_err();
+        assert!(err.contains("family"));
+
+        let empty = TelegramConfig {
+            notify_targets: HashMap::new(),
+            ..config
+        };
+        assert!(
+            TelegramNotifyTool::from_config(test_security(AutonomyLevel::Full, 100), &empty)
+                .is_none()
+        );
+    }
+
+    #[tokio::test]
+    async fn execute_rejects_unlisted_chat() {
+        let result = targeted_tool()
+            .execute(json!({"message": "hi", "chat_id": "42"}))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(result
+            .error
+            .unwrap()
+            .contains("not an allowed notify target"));
+    }
+
+    #[test]
+    fn markdown_conversion_escapes_plain_text() {
+        assert_eq!(
+            markdown_to_markdown_v2("Saved report_2024.csv (3 rows) - done!"),
+            r"Saved report\_2024\.csv \(3 rows\) \- done\!"
+        );
+        assert_eq!(
+            markdown_to_markdown_v2("**Backup** finished for my_db"),
+            r"*Backup* finished for my\_db"
+        );
+        assert_eq!(
+            markdown_to_markdown_v2("_note_: see `a_b.rs` and [docs](https://x.io/a_b)"),
+            "_note_: see `a_b.rs` and [docs](https://x.io/a_b)"
+        );
+        assert_eq!(
+            markdown_to_markdown_v2("```\nlet x = a*b;\n```"),
+            "```\nlet x = a*b;\n```"
+        );
+        assert_eq!(markdown_to_markdown_v2("2 * 3 = 6"), r"2 \* 3 \= 6");
+    }
+
+    #[test]
+    fn parse_mode_render_and_entity_error_detection() {
+        assert_eq!(ParseMode::Plain.render("a_b"), ("a_b".to_string(), None));
+        assert_eq!(
+            ParseMode::Markdown.render("a_b"),
+            (r"a\_b".to_string(), Some("MarkdownV2"))
+        );
+        assert_eq!(
+            ParseMode::from_name("MarkdownV2"),
+            Some(ParseMode::MarkdownV2)
+        );
+        assert!(ParseMode::from_name("bbcode").is_none());
+
+        assert!(TelegramNotifyTool::is_entity_parse_error(
+            reqwest::StatusCode::BAD_REQUEST,
+            r#"{"ok":false,"error_code":400,"description":"Bad Request: can't parse entities: Can't find end of the entity starting at byte offset 5"}"#
+        ));
+        assert!(!TelegramNotifyTool::is_entity_parse_error(
+            reqwest::StatusCode::BAD_REQUEST,
+            r#"{"ok":false,"description":"Bad Request: chat not found"}"#
+        ));
+    }
+
+    #[tokio::test]
+    async fn execute_rejects_unknown_parse_mode() {
+        let result = targeted_tool()
+            .execute(json!({"message": "hi", "parse_mode": "bbcode"}))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("parse_mode"));
+    }
+
+    #[test]
+    fn buttons_flat_list_becomes_single_row() {
+        let markup = TelegramNotifyTool::parse_buttons(&json!({
+            "buttons": ["Approve", {"text": "Deny", "data": "deny:42"}, {"text": "Open", "url": "https://example.com"}]
+        }))
+        .unwrap()
+        .unwrap();
+        let keyboard = markup["inline_keyboard"].as_array().unwrap();
+        assert_eq!(keyboard.len(), 1);
+        assert_eq!(keyboard[0][0]["callback_data"], "Approve");
+        assert_eq!(keyboard[0][1]["callback_data"], "deny:42");
+        assert_eq!(keyboard[0][2]["url"], "https://example.com");
+    }
+
+    #[test]
+    fn buttons_rows_and_validation() {
+        let markup = TelegramNotifyTool::parse_buttons(&json!({
+            "buttons": [["Yes", "No"], ["Later"]]
+        }))
+        .unwrap()
+        .unwrap();
+        assert_eq!(markup["inline_keyboard"].as_array().unwrap().len(), 2);
+
+        assert!(TelegramNotifyTool::parse_buttons(&json!({}))
+            .unwrap()
+            .is_none());
+        assert!(TelegramNotifyTool::parse_buttons(&json!({"buttons": ["a", ["b"]]})).is_err());
+        assert!(TelegramNotifyTool::parse_buttons(&json!({"buttons": [{"text": " "}]})).is_err());
+        let long = "x".repeat(TELEGRAM_MAX_CALLBACK_DATA_BYTES + 1);
+        assert!(TelegramNotifyTool::parse_buttons(&json!({ "buttons": [long] })).is_err());
+        assert!(TelegramNotifyTool::parse_buttons(
+            &json!({"buttons": [{"text": "x", "url": "javascript:alert(1)"}]})
+        )
+        .is_err());
+    }
+
     #[test]
-    fn tool_requires_message_param() {
+    fn attachment_kind_inferred_from_extension() {
+        assert_eq!(
+            AttachmentKind::infer(Path::new("charts/weekly.PNG")),
+            AttachmentKind::Photo
+        );
+        assert_eq!(
+            AttachmentKind::infer(Path::new("memo.m4a")),
+            AttachmentKind::Audio
+        );
+        assert_eq!(
+            AttachmentKind::infer(Path::new("export.csv")),
+            AttachmentKind::Document
+        );
+        assert_eq!(
+            AttachmentKind::Audio.method_and_field(),
+            ("sendAudio", "audio")
+        );
+    }
+
+    #[tokio::test]
+    async fn attachment_outside_workspace_is_rejected() {
         let tool = TelegramNotifyTool::new(
             test_security(AutonomyLevel::Full, 100),
             "123:ABC".into(),
             "987654321".into(),
         );
-        let schema = tool.parameters_schema();
-        let required = schema["required"].as_array().unwrap();
-        assert!(required.contains(&serde_json::Value::String("message".to_string())));
+        let result = tool
+            .execute(json!({"message": "here", "attachment": "../etc/passwd"}))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("not allowed"));
+    }
+
+    #[tokio::test]
+    async fn attachment_must_exist() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let security = Arc::new(SecurityPolicy {
+            workspace_dir: tmp.path().to_path_buf(),
+            ..SecurityPolicy::default()
+        });
+        let tool = TelegramNotifyTool::new(security, "123:ABC".into(), "987654321".into());
+        let result = tool
+            .execute(json!({"attachment": "missing.png"}))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("Failed to resolve"));
     }
 
     #[tokio::test]
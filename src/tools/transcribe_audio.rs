@@ -0,0 +1,211 @@
+use super::traits::{Tool, ToolResult};
+use crate::channels::transcription::{self, MAX_AUDIO_BYTES};
+use crate::config::TranscriptionConfig;
+use crate::security::SecurityPolicy;
+use async_trait::async_trait;
+use serde_json::json;
+use std::sync::Arc;
+
+/// Speech-to-text for audio files in the workspace, using the backend
+/// selected in `[transcription]`.
+pub struct TranscribeAudioTool {
+    security: Arc<SecurityPolicy>,
+    config: TranscriptionConfig,
+}
+
+impl TranscribeAudioTool {
+    pub fn new(security: Arc<SecurityPolicy>, config: TranscriptionConfig) -> Self {
+        Self { security, config }
+    }
+}
+
+#[async_trait]
+impl Tool for TranscribeAudioTool {
+    fn name(&self) -> &str {
+        "transcribe_audio"
+    }
+
+    fn description(&self) -> &str {
+        "Transcribe an audio file (flac, mp3, m4a, ogg, opus, wav, webm) from the workspace to text. Use it on voice messages and recordings."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "path": {
+                    "type": "string",
+                    "description": "Path to the audio file. Relative paths resolve from workspace; outside paths require policy allowlist."
+                }
+            },
+            "required": ["path"]
+        })
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let path = args
+            .get("path")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| anyhow::anyhow!("Missing 'path' parameter"))?;
+
+        if self.security.is_rate_limited() {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some("Rate limit exceeded: too many actions in the last hour".into()),
+            });
+        }
+
+        if !self.security.is_path_allowed(path) {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(format!("Path not allowed by security policy: {path}")),
+            });
+        }
+
+        // Record action before canonicalization so path-probing still consumes budget.
+        if !self.security.record_action() {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some("Rate limit exceeded: action budget exhausted".into()),
+            });
+        }
+
+        let full_path = self.security.workspace_dir.join(path);
+        let resolved_path = match tokio::fs::canonicalize(&full_path).await {
+            Ok(p) => p,
+            Err(e) => {
+                return Ok(ToolResult {
+                    success: false,
+                    output: String::new(),
+                    error: Some(format!("Failed to resolve file path: {e}")),
+                });
+            }
+        };
+
+        if !self.security.is_resolved_path_allowed(&resolved_path) {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(
+                    self.security
+                        .resolved_path_violation_message(&resolved_path),
+                ),
+            });
+        }
+
+        let transcript = if self.config.backend == "on_device" {
+            transcription::transcribe_on_device(&resolved_path, &self.config).await
+        } else {
+            match tokio::fs::metadata(&resolved_path).await {
+                Ok(meta) if meta.len() > MAX_AUDIO_BYTES as u64 => {
+                    return Ok(ToolResult {
+                        success: false,
+                        output: String::new(),
+                        error: Some(format!(
+                            "Audio file too large: {} bytes (limit: {MAX_AUDIO_BYTES} bytes)",
+                            meta.len()
+                        )),
+                    });
+                }
+                Ok(_) => {}
+                Err(e) => {
+                    return Ok(ToolResult {
+                        success: false,
+                        output: String::new(),
+                        error: Some(format!("Failed to read file metadata: {e}")),
+                    });
+                }
+            }
+            let file_name = resolved_path
+                .file_name()
+                .map(|n| n.to_string_lossy().into_owned())
+                .unwrap_or_default();
+            match tokio::fs::read(&resolved_path).await {
+                Ok(bytes) => transcription::transcribe_audio(bytes, &file_name, &self.config).await,
+                Err(e) => Err(anyhow::anyhow!("Failed to read audio file: {e}")),
+            }
+        };
+
+        match transcript {
+            Ok(text) if text.trim().is_empty() => Ok(ToolResult {
+                success: true,
+                output: "No speech detected in audio".into(),
+                error: None,
+            }),
+            Ok(text) => Ok(ToolResult {
+                success: true,
+                output: text,
+                error: None,
+            }),
+            Err(e) => Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(e.to_string()),
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::security::AutonomyLevel;
+    use tempfile::TempDir;
+
+    fn test_security(workspace: std::path::PathBuf) -> Arc<SecurityPolicy> {
+        Arc::new(SecurityPolicy {
+            autonomy: AutonomyLevel::Supervised,
+            workspace_dir: workspace,
+            ..SecurityPolicy::default()
+        })
+    }
+
+    #[tokio::test]
+    async fn rejects_path_outside_workspace() {
+        let tmp = TempDir::new().unwrap();
+        let tool = TranscribeAudioTool::new(
+            test_security(tmp.path().to_path_buf()),
+            TranscriptionConfig::default(),
+        );
+        let result = tool
+            .execute(json!({ "path": "/etc/passwd" }))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("not allowed"));
+    }
+
+    #[tokio::test]
+    async fn on_device_backend_requires_host_bridge() {
+        let tmp = TempDir::new().unwrap();
+        tokio::fs::write(tmp.path().join("note.ogg"), b"OggS")
+            .await
+            .unwrap();
+        let config = TranscriptionConfig {
+            backend: "on_device".into(),
+            ..TranscriptionConfig::default()
+        };
+        let tool = TranscribeAudioTool::new(test_security(tmp.path().to_path_buf()), config);
+        let result = tool.execute(json!({ "path": "note.ogg" })).await.unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("host bridge"));
+    }
+
+    #[tokio::test]
+    async fn api_backend_rejects_unsupported_format() {
+        let tmp = TempDir::new().unwrap();
+        tokio::fs::write(tmp.path().join("note.txt"), b"not audio")
+            .await
+            .unwrap();
+        let tool = TranscribeAudioTool::new(
+            test_security(tmp.path().to_path_buf()),
+            TranscriptionConfig::default(),
+        );
+        let result = tool.execute(json!({ "path": "note.txt" })).await.unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("Unsupported audio format"));
+    }
+}
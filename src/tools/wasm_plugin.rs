@@ -0,0 +1,567 @@
+//! WASM plugin tools — third-party tools loaded from the workspace.
+//!
+//! Each plugin is a directory under `<workspace>/<plugins.dir>/`:
+//!
+//! ```text
+//! plugins/word_count/
+//!   plugin.json   # manifest: name, description, parameters, capabilities, limits
+//!   plugin.wasm   # WASI (preview 1) command module
+//! ```
+//!
+//! A call runs the module's `_start` with the JSON arguments on stdin; stdout
+//! becomes the tool output and a non-zero exit code marks the call failed.
+//! Modules get no network, no environment and no filesystem unless the
+//! manifest requests workspace access and `[plugins]` allows it. Fuel, memory
+//! and wall-clock limits are the manifest's request capped by `[plugins]`.
+//!
+//! # Feature gate
+//! Execution needs `--features plugins-wasm` (wasmtime). Without it, plugins
+//! are not registered.
+
+use super::traits::{Tool, ToolResult};
+use crate::config::PluginsConfig;
+use crate::security::policy::ToolOperation;
+use crate::security::SecurityPolicy;
+use async_trait::async_trait;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+const MANIFEST_FILE: &str = "plugin.json";
+const MODULE_FILE: &str = "plugin.wasm";
+/// Refuse modules larger than this before handing them to the compiler.
+const MAX_MODULE_BYTES: u64 = 50 * 1024 * 1024;
+
+/// `plugin.json` contents.
+#[derive(Debug, Clone, Deserialize)]
+pub struct PluginManifest {
+    /// Plugin name; registered as `plugin_<name>`
+    pub name: String,
+    /// Description shown to the model
+    pub description: String,
+    /// JSON schema for the call arguments
+    #[serde(default = "default_parameters")]
+    pub parameters: Value,
+    #[serde(default)]
+    pub capabilities: PluginCapabilities,
+    #[serde(default)]
+    pub limits: PluginLimits,
+}
+
+fn default_parameters() -> Value {
+    json!({ "type": "object", "properties": {} })
+}
+
+/// Capabilities a plugin asks for.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct PluginCapabilities {
+    /// Mount the workspace read-only at `/workspace`
+    #[serde(default)]
+    pub workspace_read: bool,
+    /// Mount the workspace read-write at `/workspace`
+    #[serde(default)]
+    pub workspace_write: bool,
+}
+
+/// Resource limits a plugin asks for; `None` means the configured maximum.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct PluginLimits {
+    #[serde(default)]
+    pub fuel: Option<u64>,
+    #[serde(default)]
+    pub memory_mb: Option<u64>,
+    #[serde(default)]
+    pub timeout_secs: Option<u64>,
+}
+
+/// Limits actually applied to a call.
+#[cfg_attr(not(feature = "plugins-wasm"), allow(dead_code))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct EffectiveLimits {
+    fuel: u64,
+    memory_bytes: u64,
+    timeout_secs: u64,
+}
+
+impl EffectiveLimits {
+    fn resolve(requested: &PluginLimits, config: &PluginsConfig) -> Self {
+        let cap = |req: Option<u64>, max: u64| req.filter(|v| *v > 0).map_or(max, |v| v.min(max));
+        Self {
+            fuel: cap(requested.fuel, config.max_fuel),
+            memory_bytes: cap(requested.memory_mb, config.max_memory_mb)
+                .saturating_mul(1024 * 1024),
+            timeout_secs: cap(requested.timeout_secs, config.max_timeout_secs).max(1),
+        }
+    }
+}
+
+fn validate_plugin_name(name: &str) -> anyhow::Result<()> {
+    if name.is_empty()
+        || name.len() > 48
+        || !name
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+    {
+        anyhow::bail!("plugin name must be 1-48 characters of [a-z0-9_], got: {name:?}");
+    }
+    Ok(())
+}
+
+/// Read and validate one plugin directory against the `[plugins]` policy.
+fn load_manifest(plugin_dir: &Path, config: &PluginsConfig) -> anyhow::Result<PluginManifest> {
+    let raw = std::fs::read_to_string(plugin_dir.join(MANIFEST_FILE))?;
+    let manifest: PluginManifest =
+        serde_json::from_str(&raw).map_err(|e| anyhow::anyhow!("invalid {MANIFEST_FILE}: {e}"))?;
+
+    validate_plugin_name(&manifest.name)?;
+    if manifest.description.trim().is_empty() {
+        anyhow::bail!("plugin '{}' has an empty description", manifest.name);
+    }
+    if manifest.parameters.get("type").and_then(Value::as_str) != Some("object") {
+        anyhow::bail!(
+            "plugin '{}' parameters must be a JSON schema with \"type\": \"object\"",
+            manifest.name
+        );
+    }
+    if manifest.capabilities.workspace_write && !config.allow_workspace_write {
+        anyhow::bail!(
+            "plugin '{}' requests workspace_write but plugins.allow_workspace_write = false",
+            manifest.name
+        );
+    }
+    if manifest.capabilities.workspace_read
+        && !(config.allow_workspace_read || config.allow_workspace_write)
+    {
+        anyhow::bail!(
+            "plugin '{}' requests workspace_read but plugins.allow_workspace_read = false",
+            manifest.name
+        );
+    }
+
+    let module_len = std::fs::metadata(plugin_dir.join(MODULE_FILE))
+        .map_err(|e| anyhow::anyhow!("missing {MODULE_FILE}: {e}"))?
+        .len();
+    if module_len > MAX_MODULE_BYTES {
+        anyhow::bail!(
+            "plugin '{}' module is {} MB — exceeds the 50 MB limit",
+            manifest.name,
+            module_len / (1024 * 1024)
+        );
+    }
+    Ok(manifest)
+}
+
+/// Plugin directories (sorted) under the configured plugin root.
+fn discover_plugin_dirs(root: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = std::fs::read_dir(root) else {
+        return Vec::new();
+    };
+    let mut dirs: Vec<PathBuf> = entries
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| path.join(MANIFEST_FILE).is_file())
+        .collect();
+    dirs.sort();
+    dirs
+}
+
+/// Output of one plugin run.
+#[cfg_attr(not(feature = "plugins-wasm"), allow(dead_code))]
+#[derive(Debug)]
+struct PluginRun {
+    stdout: String,
+    stderr: String,
+    exit_code: i32,
+}
+
+/// A loaded WASM plugin exposed as a tool.
+#[cfg_attr(not(feature = "plugins-wasm"), allow(dead_code))]
+pub struct WasmPluginTool {
+    name: String,
+    manifest: PluginManifest,
+    limits: EffectiveLimits,
+    workspace_dir: PathBuf,
+    security: Arc<SecurityPolicy>,
+    #[cfg(feature = "plugins-wasm")]
+    engine: wasmtime::Engine,
+    #[cfg(feature = "plugins-wasm")]
+    module: wasmtime::Module,
+}
+
+#[cfg(feature = "plugins-wasm")]
+mod host {
+    use super::{EffectiveLimits, PluginRun};
+    use std::path::Path;
+    use std::time::Duration;
+    use wasmtime::{Config, Engine, Linker, Module, Store, StoreLimits, StoreLimitsBuilder, Trap};
+    use wasmtime_wasi::pipe::{MemoryInputPipe, MemoryOutputPipe};
+    use wasmtime_wasi::preview1::{self, WasiP1Ctx};
+    use wasmtime_wasi::{DirPerms, FilePerms, I32Exit, WasiCtxBuilder};
+
+    /// Epoch ticks are used for wall-clock timeouts.
+    const EPOCH_TICK_MS: u64 = 100;
+    /// Captured stdout/stderr are truncated to this many bytes.
+    const MAX_OUTPUT_BYTES: usize = 1_048_576;
+    /// Guest path the workspace is mounted at when a plugin is granted access.
+    const GUEST_WORKSPACE_DIR: &str = "/workspace";
+
+    struct PluginState {
+        wasi: WasiP1Ctx,
+        limits: StoreLimits,
+    }
+
+    /// Engine with fuel metering and a background epoch ticker that stops
+    /// once the engine is dropped.
+    pub(super) fn new_engine() -> anyhow::Result<Engine> {
+        let mut config = Config::new();
+        config.consume_fuel(true).epoch_interruption(true);
+        let engine = Engine::new(&config)?;
+
+        let weak = engine.weak();
+        std::thread::Builder::new()
+            .name("wasm-plugin-epoch".into())
+            .spawn(move || loop {
+                std::thread::sleep(Duration::from_millis(EPOCH_TICK_MS));
+                match weak.upgrade() {
+                    Some(engine) => engine.increment_epoch(),
+                    None => break,
+                }
+            })?;
+        Ok(engine)
+    }
+
+    pub(super) fn compile(engine: &Engine, path: &Path) -> anyhow::Result<Module> {
+        Module::from_file(engine, path)
+    }
+
+    pub(super) fn run(
+        engine: &Engine,
+        module: &Module,
+        program: &str,
+        input: &[u8],
+        limits: EffectiveLimits,
+        workspace: Option<(&Path, bool)>,
+    ) -> anyhow::Result<PluginRun> {
+        let stdout = MemoryOutputPipe::new(MAX_OUTPUT_BYTES);
+        let stderr = MemoryOutputPipe::new(MAX_OUTPUT_BYTES);
+        let mut builder = WasiCtxBuilder::new();
+        builder
+            .stdin(MemoryInputPipe::new(input.to_vec()))
+            .stdout(stdout.clone())
+            .stderr(stderr.clone())
+            .arg(program);
+        if let Some((dir, writable)) = workspace {
+            let (dir_perms, file_perms) = if writable {
+                (DirPerms::all(), FilePerms::all())
+            } else {
+                (DirPerms::READ, FilePerms::READ)
+            };
+            builder.preopened_dir(dir, GUEST_WORKSPACE_DIR, dir_perms, file_perms)?;
+        }
+
+        let memory_size = usize::try_from(limits.memory_bytes).unwrap_or(usize::MAX);
+        let mut store = Store::new(
+            engine,
+            PluginState {
+                wasi: builder.build_p1(),
+                limits: StoreLimitsBuilder::new()
+                    .memory_size(memory_size)
+                    .instances(1)
+                    .build(),
+            },
+        );
+        store.limiter(|state| &mut state.limits);
+        store.set_fuel(limits.fuel)?;
+        store.set_epoch_deadline((limits.timeout_secs * 1000).div_ceil(EPOCH_TICK_MS));
+
+        let mut linker: Linker<PluginState> = Linker::new(engine);
+        preview1::add_to_linker_sync(&mut linker, |state| &mut state.wasi)?;
+        let instance = linker.instantiate(&mut store, module)?;
+        let start = instance.get_typed_func::<(), ()>(&mut store, "_start")?;
+
+        let exit_code = match start.call(&mut store, ()) {
+            Ok(()) => 0,
+            Err(e) => {
+                if let Some(exit) = e.downcast_ref::<I32Exit>() {
+                    exit.0
+                } else {
+                    match e.downcast_ref::<Trap>() {
+                        Some(Trap::OutOfFuel) => {
+                            anyhow::bail!("plugin exceeded its fuel limit ({})", limits.fuel)
+                        }
+                        Some(Trap::Interrupt) => anyhow::bail!(
+                            "plugin exceeded its time limit ({}s)",
+                            limits.timeout_secs
+                        ),
+                        _ => anyhow::bail!("plugin trapped: {e}"),
+                    }
+                }
+            }
+        };
+
+        drop(store);
+        Ok(PluginRun {
+            stdout: String::from_utf8_lossy(&stdout.contents()).into_owned(),
+            stderr: String::from_utf8_lossy(&stderr.contents()).into_owned(),
+            exit_code,
+        })
+    }
+}
+
+impl WasmPluginTool {
+    #[cfg(feature = "plugins-wasm")]
+    async fn run(&self, input: Vec<u8>) -> anyhow::Result<PluginRun> {
+        let engine = self.engine.clone();
+        let module = self.module.clone();
+        let program = self.name.clone();
+        let limits = self.limits;
+        let caps = self.manifest.capabilities.clone();
+        let workspace_dir = self.workspace_dir.clone();
+        tokio::task::spawn_blocking(move || {
+            let workspace = (caps.workspace_read || caps.workspace_write)
+                .then_some((workspace_dir.as_path(), caps.workspace_write));
+            host::run(&engine, &module, &program, &input, limits, workspace)
+        })
+        .await?
+    }
+
+    #[cfg(not(feature = "plugins-wasm"))]
+    #[allow(clippy::unused_async)]
+    async fn run(&self, _input: Vec<u8>) -> anyhow::Result<PluginRun> {
+        anyhow::bail!(
+            "WASM plugins are not available in this build. Rebuild with `cargo build --features plugins-wasm`."
+        )
+    }
+}
+
+#[async_trait]
+impl Tool for WasmPluginTool {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn description(&self) -> &str {
+        &self.manifest.description
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        self.manifest.parameters.clone()
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        if self.manifest.capabilities.workspace_write {
+            if let Err(error) = self
+                .security
+                .enforce_tool_operation(ToolOperation::Act, &self.name)
+            {
+                return Ok(ToolResult {
+                    success: false,
+                    output: String::new(),
+                    error: Some(error),
+                });
+            }
+        } else if self.security.is_rate_limited() {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some("Rate limit exceeded: too many actions in the last hour".into()),
+            });
+        }
+
+        let input = serde_json::to_vec(&args)?;
+        match self.run(input).await {
+            Ok(run) if run.exit_code == 0 => Ok(ToolResult {
+                success: true,
+                output: run.stdout,
+                error: None,
+            }),
+            Ok(run) => Ok(ToolResult {
+                success: false,
+                output: run.stdout,
+                error: Some(if run.stderr.trim().is_empty() {
+                    format!("plugin exited with code {}", run.exit_code)
+                } else {
+                    run.stderr.trim().to_string()
+                }),
+            }),
+            Err(e) => Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(e.to_string()),
+            }),
+        }
+    }
+}
+
+/// Load every valid plugin under `<workspace_dir>/<config.dir>`.
+///
+/// Invalid plugins are logged and skipped. Returns nothing when plugins are
+/// disabled or the build lacks the `plugins-wasm` feature.
+pub fn load_plugins(
+    config: &PluginsConfig,
+    workspace_dir: &Path,
+    security: &Arc<SecurityPolicy>,
+) -> Vec<WasmPluginTool> {
+    if !config.enabled {
+        return Vec::new();
+    }
+    if config.dir.contains("..") {
+        tracing::warn!("plugins.dir must not contain '..'; WASM plugins disabled");
+        return Vec::new();
+    }
+    if !cfg!(feature = "plugins-wasm") {
+        tracing::warn!(
+            "plugins.enabled is set but this build lacks the plugins-wasm feature; WASM plugins disabled"
+        );
+        return Vec::new();
+    }
+
+    #[cfg(feature = "plugins-wasm")]
+    let engine = match host::new_engine() {
+        Ok(engine) => engine,
+        Err(e) => {
+            tracing::warn!("Failed to start WASM plugin engine: {e}");
+            return Vec::new();
+        }
+    };
+
+    let mut tools = Vec::new();
+    for plugin_dir in discover_plugin_dirs(&workspace_dir.join(&config.dir)) {
+        let manifest = match load_manifest(&plugin_dir, config) {
+            Ok(manifest) => manifest,
+            Err(e) => {
+                tracing::warn!("Skipping WASM plugin {}: {e}", plugin_dir.display());
+                continue;
+            }
+        };
+
+        #[cfg(feature = "plugins-wasm")]
+        let module = match host::compile(&engine, &plugin_dir.join(MODULE_FILE)) {
+            Ok(module) => module,
+            Err(e) => {
+                tracing::warn!("Skipping WASM plugin {}: {e}", manifest.name);
+                continue;
+            }
+        };
+
+        tracing::info!(plugin = %manifest.name, "WASM plugin loaded");
+        tools.push(WasmPluginTool {
+            name: format!("plugin_{}", manifest.name),
+            limits: EffectiveLimits::resolve(&manifest.limits, config),
+            manifest,
+            workspace_dir: workspace_dir.to_path_buf(),
+            security: security.clone(),
+            #[cfg(feature = "plugins-wasm")]
+            engine: engine.clone(),
+            #[cfg(feature = "plugins-wasm")]
+            module,
+        });
+    }
+    tools
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn write_plugin(root: &Path, dir: &str, manifest: &Value) -> PathBuf {
+        let plugin_dir = root.join(dir);
+        std::fs::create_dir_all(&plugin_dir).unwrap();
+        std::fs::write(plugin_dir.join(MANIFEST_FILE), manifest.to_string()).unwrap();
+        std::fs::write(plugin_dir.join(MODULE_FILE), b"\0asm\x01\0\0\0").unwrap();
+        plugin_dir
+    }
+
+    #[test]
+    fn manifest_defaults_and_validation() {
+        let tmp = TempDir::new().unwrap();
+        let config = PluginsConfig::default();
+
+        let dir = write_plugin(
+            tmp.path(),
+            "word_count",
+            &json!({"name": "word_count", "description": "Count words"}),
+        );
+        let manifest = load_manifest(&dir, &config).unwrap();
+        assert_eq!(manifest.parameters, default_parameters());
+        assert!(!manifest.capabilities.workspace_read);
+
+        let dir = write_plugin(
+            tmp.path(),
+            "bad_name",
+            &json!({"name": "Bad Name", "description": "x"}),
+        );
+        assert!(load_manifest(&dir, &config).is_err());
+
+        let dir = write_plugin(
+            tmp.path(),
+            "bad_schema",
+            &json!({"name": "bad_schema", "description": "x", "parameters": {"type": "string"}}),
+        );
+        assert!(load_manifest(&dir, &config).is_err());
+    }
+
+    #[test]
+    fn capabilities_must_be_allowed_by_config() {
+        let tmp = TempDir::new().unwrap();
+        let dir = write_plugin(
+            tmp.path(),
+            "indexer",
+            &json!({
+                "name": "indexer",
+                "description": "Index notes",
+                "capabilities": {"workspace_read": true}
+            }),
+        );
+
+        let err = load_manifest(&dir, &PluginsConfig::default()).unwrap_err();
+        assert!(err.to_string().contains("allow_workspace_read"));
+
+        let config = PluginsConfig {
+            allow_workspace_read: true,
+            ..PluginsConfig::default()
+        };
+        assert!(load_manifest(&dir, &config).is_ok());
+    }
+
+    #[test]
+    fn limits_are_capped_by_config() {
+        let config = PluginsConfig {
+            max_fuel: 1_000,
+            max_memory_mb: 16,
+            max_timeout_secs: 5,
+            ..PluginsConfig::default()
+        };
+        let limits = EffectiveLimits::resolve(
+            &PluginLimits {
+                fuel: Some(500),
+                memory_mb: Some(1024),
+                timeout_secs: None,
+            },
+            &config,
+        );
+        assert_eq!(
+            limits,
+            EffectiveLimits {
+                fuel: 500,
+                memory_bytes: 16 * 1024 * 1024,
+                timeout_secs: 5,
+            }
+        );
+    }
+
+    #[test]
+    fn load_plugins_is_empty_when_disabled() {
+        let tmp = TempDir::new().unwrap();
+        write_plugin(
+            &tmp.path().join("plugins"),
+            "word_count",
+            &json!({"name": "word_count", "description": "Count words"}),
+        );
+        let security = Arc::new(SecurityPolicy::default());
+        assert!(load_plugins(&PluginsConfig::default(), tmp.path(), &security).is_empty());
+        assert_eq!(discover_plugin_dirs(&tmp.path().join("plugins")).len(), 1);
+    }
+}
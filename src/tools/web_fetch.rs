@@ -1,18 +1,23 @@
+use super::readability;
 use super::traits::{Tool, ToolResult};
 use crate::security::SecurityPolicy;
 use async_trait::async_trait;
 use futures_util::StreamExt;
 use serde_json::json;
+use sha2::{Digest, Sha256};
+use std::path::PathBuf;
 use std::sync::Arc;
 use std::time::Duration;
 
-/// Web fetch tool: fetches a web page and converts HTML to plain text for LLM consumption.
+/// Web fetch tool: fetches a web page and converts HTML to readable text for LLM consumption.
 ///
 /// Unlike `http_request` (an API client returning raw responses), this tool:
 /// - Only supports GET
 /// - Follows redirects (up to 10)
-/// - Converts HTML to clean plain text via `nanohtml2text`
+/// - Extracts the main content of HTML pages as Markdown (or plain text via
+///   `nanohtml2text` when readability is disabled)
 /// - Passes through text/plain, text/markdown, and application/json as-is
+/// - Caches extracted pages under `<workspace>/cache/web_fetch`
 /// - Sets a descriptive User-Agent
 pub struct WebFetchTool {
     security: Arc<SecurityPolicy>,
@@ -20,6 +25,8 @@ pub struct WebFetchTool {
     blocked_domains: Vec<String>,
     max_response_size: usize,
     timeout_secs: u64,
+    readability: bool,
+    cache_ttl_secs: u64,
 }
 
 impl WebFetchTool {
@@ -30,12 +37,76 @@ impl WebFetchTool {
         max_response_size: usize,
         timeout_secs: u64,
     ) -> Self {
+        // The policy-wide denylist applies here too, including redirect targets.
+        let blocked_domains = blocked_domains
+            .into_iter()
+            .chain(security.denied_domains.iter().cloned())
+            .collect();
         Self {
             security,
             allowed_domains: normalize_allowed_domains(allowed_domains),
             blocked_domains: normalize_allowed_domains(blocked_domains),
             max_response_size,
             timeout_secs,
+            readability: true,
+            cache_ttl_secs: 0,
+        }
+    }
+
+    /// Render HTML as Markdown of the main content instead of flat text.
+    pub fn with_readability(mut self, readability: bool) -> Self {
+        self.readability = readability;
+        self
+    }
+
+    /// Serve pages fetched within the last `cache_ttl_secs` from the
+    /// workspace cache (0 disables caching).
+    pub fn with_cache_ttl_secs(mut self, cache_ttl_secs: u64) -> Self {
+        self.cache_ttl_secs = cache_ttl_secs;
+        self
+    }
+
+    fn cache_path(&self, url: &str) -> PathBuf {
+        let mode = if self.readability { "md" } else { "txt" };
+        let key = hex::encode(Sha256::digest(url.as_bytes()));
+        self.security
+            .workspace_dir
+            .join("cache")
+            .join("web_fetch")
+            .join(format!("{key}.{mode}"))
+    }
+
+    async fn read_cache(&self, url: &str) -> Option<String> {
+        if self.cache_ttl_secs == 0 {
+            return None;
+        }
+        let path = self.cache_path(url);
+        let age = tokio::fs::metadata(&path)
+            .await
+            .ok()?
+            .modified()
+            .ok()?
+            .elapsed()
+            .ok()?;
+        if age > Duration::from_secs(self.cache_ttl_secs) {
+            return None;
+        }
+        tokio::fs::read_to_string(&path).await.ok()
+    }
+
+    async fn write_cache(&self, url: &str, text: &str) {
+        if self.cache_ttl_secs == 0 {
+            return;
+        }
+        let path = self.cache_path(url);
+        if let Some(parent) = path.parent() {
+            if let Err(e) = tokio::fs::create_dir_all(parent).await {
+                tracing::debug!("web_fetch: failed to create cache dir: {e}");
+                return;
+            }
+        }
+        if let Err(e) = tokio::fs::write(&path, text).await {
+            tracing::debug!("web_fetch: failed to write cache entry: {e}");
         }
     }
 
@@ -87,10 +158,10 @@ impl Tool for WebFetchTool {
     }
 
     fn description(&self) -> &str {
-        "Fetch a web page and return its content as clean plain text. \
-         HTML pages are automatically converted to readable text. \
+        "Fetch a web page and return its readable content. \
+         HTML pages are reduced to their main content as Markdown (navigation, ads and scripts removed). \
          JSON and plain text responses are returned as-is. \
-         Only GET requests; follows redirects. \
+         Only GET requests; follows redirects; recently fetched pages are served from cache. \
          Security: allowlist-only domains, no local/private hosts."
     }
 
@@ -140,6 +211,14 @@ impl Tool for WebFetchTool {
             }
         };
 
+        if let Some(cached) = self.read_cache(&url).await {
+            return Ok(ToolResult {
+                success: true,
+                output: self.truncate_response(&cached),
+                error: None,
+            });
+        }
+
         // Build client: follow redirects, set timeout, set User-Agent
         let timeout_secs = if self.timeout_secs == 0 {
             tracing::warn!("web_fetch: timeout_secs is 0, using safe default of 30s");
@@ -198,6 +277,7 @@ impl Tool for WebFetchTool {
             }
         };
 
+        let final_url = response.url().to_string();
         let status = response.status();
         if !status.is_success() {
             return Ok(ToolResult {
@@ -248,11 +328,12 @@ impl Tool for WebFetchTool {
             }
         };
 
-        let text = if body_mode == "html" {
-            nanohtml2text::html2text(&body)
-        } else {
-            body
+        let text = match body_mode {
+            "html" if self.readability => readability::html_to_markdown(&body, &final_url),
+            "html" => nanohtml2text::html2text(&body),
+            _ => body,
         };
+        self.write_cache(&url, &text).await;
 
         let output = self.truncate_response(&text);
 
@@ -817,6 +898,42 @@ mod tests {
         assert!(tool.validate_url("https://example.com").is_ok());
     }
 
+    #[test]
+    fn policy_denylist_is_merged_into_blocklist() {
+        let security = Arc::new(SecurityPolicy {
+            autonomy: AutonomyLevel::Supervised,
+            denied_domains: vec!["tracker.example".into()],
+            ..SecurityPolicy::default()
+        });
+        let tool = WebFetchTool::new(security, vec!["*".into()], vec![], 500_000, 30);
+        let err = tool
+            .validate_url("https://cdn.tracker.example/page")
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("blocked_domains"));
+    }
+
+    #[tokio::test]
+    async fn cache_roundtrip_respects_ttl_setting() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let security = Arc::new(SecurityPolicy {
+            autonomy: AutonomyLevel::Supervised,
+            workspace_dir: tmp.path().to_path_buf(),
+            ..SecurityPolicy::default()
+        });
+        let tool = WebFetchTool::new(security.clone(), vec!["*".into()], vec![], 500_000, 30)
+            .with_cache_ttl_secs(60);
+        tool.write_cache("https://example.com/a", "# Cached").await;
+        assert_eq!(
+            tool.read_cache("https://example.com/a").await.as_deref(),
+            Some("# Cached")
+        );
+        assert!(tool.read_cache("https://example.com/b").await.is_none());
+
+        let uncached = WebFetchTool::new(security, vec!["*".into()], vec![], 500_000, 30);
+        assert!(uncached.read_cache("https://example.com/a").await.is_none());
+    }
+
     #[test]
     fn append_chunk_with_cap_truncates_and_stops() {
         let mut buffer = Vec::new();
@@ -5,10 +5,13 @@ use serde_json::json;
 use std::time::Duration;
 
 /// Web search tool for searching the internet.
-/// Supports multiple providers: DuckDuckGo (free), Brave (requires API key).
+/// Supports multiple providers: DuckDuckGo (free), Brave and Tavily (require
+/// API keys), and self-hosted SearXNG instances.
 pub struct WebSearchTool {
     provider: String,
     brave_api_key: Option<String>,
+    searxng_url: Option<String>,
+    tavily_api_key: Option<String>,
     max_results: usize,
     timeout_secs: u64,
 }
@@ -23,11 +26,27 @@ impl WebSearchTool {
         Self {
             provider: provider.trim().to_lowercase(),
             brave_api_key,
+            searxng_url: None,
+            tavily_api_key: None,
             max_results: max_results.clamp(1, 10),
             timeout_secs: timeout_secs.max(1),
         }
     }
 
+    /// Base URL of the SearXNG instance used by the `searxng` provider.
+    pub fn with_searxng_url(mut self, searxng_url: Option<String>) -> Self {
+        self.searxng_url = searxng_url
+            .map(|url| url.trim().trim_end_matches('/').to_string())
+            .filter(|url| !url.is_empty());
+        self
+    }
+
+    /// API key used by the `tavily` provider.
+    pub fn with_tavily_api_key(mut self, tavily_api_key: Option<String>) -> Self {
+        self.tavily_api_key = tavily_api_key.filter(|key| !key.trim().is_empty());
+        self
+    }
+
     async fn search_duckduckgo(&self, query: &str) -> anyhow::Result<String> {
         let encoded_query = urlencoding::encode(query);
         let search_url = format!("https://html.duckduckgo.com/html/?q={}", encoded_query);
@@ -162,6 +181,107 @@ impl WebSearchTool {
 
         Ok(lines.join("\n"))
     }
+
+    async fn search_searxng(&self, query: &str) -> anyhow::Result<String> {
+        let base_url = self
+            .searxng_url
+            .as_ref()
+            .ok_or_else(|| anyhow::anyhow!("SearXNG URL not configured"))?;
+        if !base_url.starts_with("http://") && !base_url.starts_with("https://") {
+            anyhow::bail!("SearXNG URL must start with http:// or https://");
+        }
+
+        let search_url = format!(
+            "{}/search?q={}&format=json",
+            base_url,
+            urlencoding::encode(query)
+        );
+
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(self.timeout_secs))
+            .build()?;
+
+        let response = client
+            .get(&search_url)
+            .header("Accept", "application/json")
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            anyhow::bail!("SearXNG search failed with status: {}", response.status());
+        }
+
+        let json: serde_json::Value = response.json().await?;
+        self.parse_json_results(&json, query, "SearXNG")
+    }
+
+    async fn search_tavily(&self, query: &str) -> anyhow::Result<String> {
+        let api_key = self
+            .tavily_api_key
+            .as_ref()
+            .ok_or_else(|| anyhow::anyhow!("Tavily API key not configured"))?;
+
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(self.timeout_secs))
+            .build()?;
+
+        let response = client
+            .post("https://api.tavily.com/search")
+            .bearer_auth(api_key)
+            .json(&json!({
+                "query": query,
+                "max_results": self.max_results,
+            }))
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            anyhow::bail!("Tavily search failed with status: {}", response.status());
+        }
+
+        let json: serde_json::Value = response.json().await?;
+        self.parse_json_results(&json, query, "Tavily")
+    }
+
+    /// Parse the `results: [{title, url, content}]` shape shared by SearXNG
+    /// and Tavily.
+    fn parse_json_results(
+        &self,
+        json: &serde_json::Value,
+        query: &str,
+        provider_label: &str,
+    ) -> anyhow::Result<String> {
+        let results = json
+            .get("results")
+            .and_then(|r| r.as_array())
+            .ok_or_else(|| anyhow::anyhow!("Invalid {provider_label} API response"))?;
+
+        if results.is_empty() {
+            return Ok(format!("No results found for: {}", query));
+        }
+
+        let mut lines = vec![format!(
+            "Search results for: {} (via {})",
+            query, provider_label
+        )];
+
+        for (i, result) in results.iter().take(self.max_results).enumerate() {
+            let title = result
+                .get("title")
+                .and_then(|t| t.as_str())
+                .unwrap_or("No title");
+            let url = result.get("url").and_then(|u| u.as_str()).unwrap_or("");
+            let content = result.get("content").and_then(|c| c.as_str()).unwrap_or("");
+
+            lines.push(format!("{}. {}", i + 1, title));
+            lines.push(format!("   {}", url));
+            if !content.is_empty() {
+                lines.push(format!("   {}", content.trim()));
+            }
+        }
+
+        Ok(lines.join("\n"))
+    }
 }
 
 fn decode_ddg_redirect_url(raw_url: &str) -> String {
@@ -219,8 +339,10 @@ impl Tool for WebSearchTool {
         let result = match self.provider.as_str() {
             "duckduckgo" | "ddg" => self.search_duckduckgo(query).await?,
             "brave" => self.search_brave(query).await?,
+            "searxng" => self.search_searxng(query).await?,
+            "tavily" => self.search_tavily(query).await?,
             _ => anyhow::bail!(
-                "Unknown search provider: '{}'. Set tools.web_search.provider to 'duckduckgo' or 'brave' in config.toml",
+                "Unknown search provider: '{}'. Set web_search.provider to 'duckduckgo', 'brave', 'searxng', or 'tavily' in config.toml",
                 self.provider
             ),
         };
@@ -328,4 +450,43 @@ mod tests {
         assert!(result.is_err());
         assert!(result.unwrap_err().to_string().contains("API key"));
     }
+
+    #[test]
+    fn test_parse_json_results_for_searxng_and_tavily() {
+        let tool = WebSearchTool::new("searxng".to_string(), None, 1, 15);
+        let json = json!({
+            "results": [
+                { "title": "First", "url": "https://a.example", "content": " Snippet A " },
+                { "title": "Second", "url": "https://b.example", "content": "Snippet B" }
+            ]
+        });
+        let result = tool.parse_json_results(&json, "test", "SearXNG").unwrap();
+        assert!(result.contains("(via SearXNG)"));
+        assert!(result.contains("1. First"));
+        assert!(result.contains("   Snippet A"));
+        assert!(!result.contains("Second"));
+
+        let empty = tool
+            .parse_json_results(&json!({ "results": [] }), "test", "Tavily")
+            .unwrap();
+        assert!(empty.contains("No results found"));
+        assert!(tool
+            .parse_json_results(&json!({}), "test", "Tavily")
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_execute_searxng_without_url() {
+        let tool = WebSearchTool::new("searxng".to_string(), None, 5, 15)
+            .with_searxng_url(Some("  ".into()));
+        let result = tool.execute(json!({"query": "test"})).await;
+        assert!(result.unwrap_err().to_string().contains("SearXNG URL"));
+    }
+
+    #[tokio::test]
+    async fn test_execute_tavily_without_api_key() {
+        let tool = WebSearchTool::new("tavily".to_string(), None, 5, 15);
+        let result = tool.execute(json!({"query": "test"})).await;
+        assert!(result.unwrap_err().to_string().contains("Tavily API key"));
+    }
 }
@@ -2,6 +2,7 @@ mod cloudflare;
 mod custom;
 mod ngrok;
 mod none;
+mod relay;
 mod tailscale;
 
 pub use cloudflare::CloudflareTunnel;
@@ -9,10 +10,12 @@ pub use custom::CustomTunnel;
 pub use ngrok::NgrokTunnel;
 #[allow(unused_imports)]
 pub use none::NoneTunnel;
+pub use relay::{relayed_client_key, RelayTunnel};
 pub use tailscale::TailscaleTunnel;
 
 use crate::config::schema::{TailscaleTunnelConfig, TunnelConfig};
 use anyhow::{bail, Result};
+use rustls::pki_types::CertificateDer;
 use std::sync::Arc;
 use tokio::sync::Mutex;
 
@@ -21,7 +24,7 @@ use tokio::sync::Mutex;
 /// Agnostic tunnel abstraction — bring your own tunnel provider.
 ///
 /// Implementations wrap an external tunnel binary (cloudflared, tailscale,
-/// ngrok, etc.) or a custom command. The gateway calls `start()` after
+/// ngrok, etc.), a custom command, or an outbound connection to a relay. The gateway calls `start()` after
 /// binding its local port and `stop()` on shutdown.
 #[async_trait::async_trait]
 pub trait Tunnel: Send + Sync {
@@ -70,7 +73,13 @@ pub(crate) async fn kill_shared(proc: &SharedProcess) -> Result<()> {
 // ── Factory ──────────────────────────────────────────────────────
 
 /// Create a tunnel from config. Returns `None` for provider "none".
-pub fn create_tunnel(config: &TunnelConfig) -> Result<Option<Box<dyn Tunnel>>> {
+///
+/// `local_tls` is the certificate of a gateway serving HTTPS, for tunnels
+/// that connect to it from this process.
+pub fn create_tunnel(
+    config: &TunnelConfig,
+    local_tls: Option<CertificateDer<'static>>,
+) -> Result<Option<Box<dyn Tunnel>>> {
     match config.provider.as_str() {
         "none" | "" => Ok(None),
 
@@ -104,6 +113,21 @@ pub fn create_tunnel(config: &TunnelConfig) -> Result<Option<Box<dyn Tunnel>>> {
             ))))
         }
 
+        "relay" => {
+            let relay = config
+                .relay
+                .as_ref()
+                .ok_or_else(|| anyhow::anyhow!("tunnel.provider = \"relay\" but [tunnel.relay] section is missing"))?;
+            Ok(Some(Box::new(
+                RelayTunnel::new(
+                    relay.url.clone(),
+                    relay.token.clone(),
+                    relay.public_url.clone(),
+                )
+                .with_local_tls(local_tls),
+            )))
+        }
+
         "custom" => {
             let cu = config
                 .custom
@@ -116,7 +140,7 @@ pub fn create_tunnel(config: &TunnelConfig) -> Result<Option<Box<dyn Tunnel>>> {
             ))))
         }
 
-        other => bail!("Unknown tunnel provider: \"{other}\". Valid: none, cloudflare, tailscale, ngrok, custom"),
+        other => bail!("Unknown tunnel provider: \"{other}\". Valid: none, cloudflare, tailscale, ngrok, relay, custom"),
     }
 }
 
@@ -126,13 +150,14 @@ pub fn create_tunnel(config: &TunnelConfig) -> Result<Option<Box<dyn Tunnel>>> {
 mod tests {
     use super::*;
     use crate::config::schema::{
-        CloudflareTunnelConfig, CustomTunnelConfig, NgrokTunnelConfig, TunnelConfig,
+        CloudflareTunnelConfig, CustomTunnelConfig, NgrokTunnelConfig, RelayTunnelConfig,
+        TunnelConfig,
     };
     use tokio::process::Command;
 
     /// Helper: assert `create_tunnel` returns an error containing `needle`.
     fn assert_tunnel_err(cfg: &TunnelConfig, needle: &str) {
-        match create_tunnel(cfg) {
+        match create_tunnel(cfg, None) {
             Err(e) => assert!(
                 e.to_string().contains(needle),
                 "Expected error containing \"{needle}\", got: {e}"
@@ -144,7 +169,7 @@ mod tests {
     #[test]
     fn factory_none_returns_none() {
         let cfg = TunnelConfig::default();
-        let t = create_tunnel(&cfg).unwrap();
+        let t = create_tunnel(&cfg, None).unwrap();
         assert!(t.is_none());
     }
 
@@ -154,7 +179,7 @@ mod tests {
             provider: String::new(),
             ..TunnelConfig::default()
         };
-        let t = create_tunnel(&cfg).unwrap();
+        let t = create_tunnel(&cfg, None).unwrap();
         assert!(t.is_none());
     }
 
@@ -185,7 +210,7 @@ mod tests {
             }),
             ..TunnelConfig::default()
         };
-        let t = create_tunnel(&cfg).unwrap();
+        let t = create_tunnel(&cfg, None).unwrap();
         assert!(t.is_some());
         assert_eq!(t.unwrap().name(), "cloudflare");
     }
@@ -196,7 +221,7 @@ mod tests {
             provider: "tailscale".into(),
             ..TunnelConfig::default()
         };
-        let t = create_tunnel(&cfg).unwrap();
+        let t = create_tunnel(&cfg, None).unwrap();
         assert!(t.is_some());
         assert_eq!(t.unwrap().name(), "tailscale");
     }
@@ -220,11 +245,38 @@ mod tests {
             }),
             ..TunnelConfig::default()
         };
-        let t = create_tunnel(&cfg).unwrap();
+        let t = create_tunnel(&cfg, None).unwrap();
         assert!(t.is_some());
         assert_eq!(t.unwrap().name(), "ngrok");
     }
 
+    #[test]
+    fn factory_relay_missing_config_errors() {
+        let cfg = TunnelConfig {
+            provider: "relay".into(),
+            ..TunnelConfig::default()
+        };
+        assert_tunnel_err(&cfg, "[tunnel.relay]");
+    }
+
+    #[test]
+    fn factory_relay_with_config_ok() {
+        let cfg = TunnelConfig {
+            provider: "relay".into(),
+            relay: Some(RelayTunnelConfig {
+                url: "wss://relay.example.com/connect".into(),
+                token: "relay-token".into(),
+                public_url: None,
+            }),
+            ..TunnelConfig::default()
+        };
+        let t = create_tunnel(&cfg, None).unwrap();
+        assert!(t.is_some());
+        let t = t.unwrap();
+        assert_eq!(t.name(), "relay");
+        assert!(t.public_url().is_none());
+    }
+
     #[test]
     fn factory_custom_missing_config_errors() {
         let cfg = TunnelConfig {
@@ -245,7 +297,7 @@ mod tests {
             }),
             ..TunnelConfig::default()
         };
-        let t = create_tunnel(&cfg).unwrap();
+        let t = create_tunnel(&cfg, None).unwrap();
         assert!(t.is_some());
         assert_eq!(t.unwrap().name(), "custom");
     }
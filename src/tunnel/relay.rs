@@ -0,0 +1,814 @@
+use super::Tunnel;
+use anyhow::{bail, Context, Result};
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use futures_util::{SinkExt, StreamExt};
+use parking_lot::Mutex;
+use rustls::pki_types::CertificateDer;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::net::{IpAddr, SocketAddr};
+use std::sync::{Arc, OnceLock};
+use std::time::Duration;
+use tokio::sync::{mpsc, oneshot};
+use tokio::task::{AbortHandle, JoinHandle};
+use tokio_tungstenite::tungstenite::client::IntoClientRequest;
+use tokio_tungstenite::tungstenite::http::HeaderValue;
+use tokio_tungstenite::tungstenite::Message;
+
+/// How long `start()` waits for the relay to accept the first connection.
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(15);
+/// Keepalive pings hold NAT and carrier-grade NAT mappings open.
+const PING_INTERVAL: Duration = Duration::from_secs(25);
+const RECONNECT_BACKOFF_INITIAL: Duration = Duration::from_secs(1);
+const RECONNECT_BACKOFF_MAX: Duration = Duration::from_secs(60);
+
+/// Address of the remote client as reported by the relay, on replayed
+/// requests.
+const RELAY_CLIENT_HEADER: &str = "x-zeroclaw-relay-client";
+/// Per-process secret proving that a request was replayed by the relay
+/// tunnel and its client header can be trusted.
+const RELAY_SECRET_HEADER: &str = "x-zeroclaw-relay-secret";
+
+/// Headers that describe one hop and are not forwarded in either direction.
+const HOP_BY_HOP_HEADERS: &[&str] = &[
+    "connection",
+    "content-length",
+    "host",
+    "keep-alive",
+    "proxy-connection",
+    "te",
+    "trailer",
+    "transfer-encoding",
+    "upgrade",
+];
+
+/// Outbound relay tunnel — keeps a WebSocket open to a user-hosted relay
+/// server and serves the gateway requests it forwards.
+///
+/// Because the phone dials out, the gateway is reachable from the internet
+/// behind NAT and carrier-grade NAT without opening ports. Every frame is a
+/// JSON text message tagged by `type`:
+///
+/// - relay → phone: `hello` (first, with an optional `public_url`),
+///   `request` (`id`, `method`, `path`, `headers`, base64 `body`, optional
+///   `client` address) and `cancel` (`id`) when the remote client goes away;
+/// - phone → relay: `response` (`id`, `status`, `headers`), then `data`
+///   frames with base64 `body` chunks as the gateway streams them, then `end`
+///   (`id`, plus `error` if the request failed).
+///
+/// Requests are served concurrently and their frames interleave by `id`, so
+/// SSE streams stay open while other requests complete. They reach the
+/// gateway over loopback, so they carry the remote client's address for
+/// [`relayed_client_key`] to keep them out of the loopback exemptions. A
+/// gateway serving `[gateway.tls]` is reached over HTTPS, trusting only its
+/// own certificate.
+pub struct RelayTunnel {
+    url: String,
+    token: String,
+    public_url: Option<String>,
+    local_tls: Option<CertificateDer<'static>>,
+    state: Arc<Mutex<RelayState>>,
+    task: Mutex<Option<JoinHandle<()>>>,
+}
+
+#[derive(Default)]
+struct RelayState {
+    connected: bool,
+    public_url: Option<String>,
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum RelayFrame {
+    Hello {
+        #[serde(default)]
+        public_url: Option<String>,
+    },
+    Request {
+        id: String,
+        method: String,
+        path: String,
+        #[serde(default)]
+        headers: Vec<(String, String)>,
+        #[serde(default)]
+        body: String,
+        #[serde(default)]
+        client: Option<String>,
+    },
+    Cancel {
+        id: String,
+    },
+}
+
+#[derive(Debug, Serialize, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum PhoneFrame {
+    Response {
+        id: String,
+        status: u16,
+        headers: Vec<(String, String)>,
+    },
+    Data {
+        id: String,
+        body: String,
+    },
+    End {
+        id: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        error: Option<String>,
+    },
+}
+
+type ReadySender = Option<oneshot::Sender<Result<String>>>;
+
+impl RelayTunnel {
+    pub fn new(url: String, token: String, public_url: Option<String>) -> Self {
+        Self {
+            url,
+            token,
+            public_url: public_url.filter(|url| !url.trim().is_empty()),
+            local_tls: None,
+            state: Arc::new(Mutex::new(RelayState::default())),
+            task: Mutex::new(None),
+        }
+    }
+
+    /// Reach the gateway over HTTPS, pinning `certificate`.
+    pub fn with_local_tls(mut self, certificate: Option<CertificateDer<'static>>) -> Self {
+        self.local_tls = certificate;
+        self
+    }
+}
+
+impl Drop for RelayTunnel {
+    fn drop(&mut self) {
+        // Like the process-backed tunnels, the relay goes away with the gateway.
+        if let Some(task) = self.task.get_mut().take() {
+            task.abort();
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl Tunnel for RelayTunnel {
+    fn name(&self) -> &str {
+        "relay"
+    }
+
+    async fn start(&self, local_host: &str, local_port: u16) -> Result<String> {
+        self.stop().await?;
+
+        let local = local_base_url(local_host, local_port, self.local_tls.is_some());
+        let mut client = reqwest::Client::builder()
+            .connect_timeout(Duration::from_secs(10))
+            .redirect(reqwest::redirect::Policy::none());
+        if let Some(certificate) = &self.local_tls {
+            client = client.use_preconfigured_tls(crate::gateway::tls::pinned_client_config(
+                certificate.clone(),
+            )?);
+        }
+        let client = client.build()?;
+        let (ready_tx, ready_rx) = oneshot::channel();
+        let task = tokio::spawn(supervise(
+            self.url.clone(),
+            self.token.clone(),
+            self.public_url.clone(),
+            local,
+            client,
+            Arc::clone(&self.state),
+            ready_tx,
+        ));
+
+        let ready = tokio::time::timeout(CONNECT_TIMEOUT, ready_rx).await;
+        let public_url = match ready {
+            Ok(Ok(Ok(public_url))) => public_url,
+            Ok(Ok(Err(e))) => {
+                task.abort();
+                return Err(e);
+            }
+            Ok(Err(_)) | Err(_) => {
+                task.abort();
+                bail!(
+                    "Relay {} did not accept the connection within {}s",
+                    self.url,
+                    CONNECT_TIMEOUT.as_secs()
+                );
+            }
+        };
+
+        *self.task.lock() = Some(task);
+        Ok(public_url)
+    }
+
+    async fn stop(&self) -> Result<()> {
+        if let Some(task) = self.task.lock().take() {
+            task.abort();
+        }
+        *self.state.lock() = RelayState::default();
+        Ok(())
+    }
+
+    async fn health_check(&self) -> bool {
+        self.state.lock().connected
+    }
+
+    fn public_url(&self) -> Option<String> {
+        self.state.lock().public_url.clone()
+    }
+}
+
+/// The gateway as seen from this process; wildcard binds are reached over
+/// loopback.
+fn local_base_url(local_host: &str, local_port: u16, tls: bool) -> String {
+    let host = match local_host {
+        "0.0.0.0" | "" => "127.0.0.1",
+        "::" | "[::]" => "[::1]",
+        host => host,
+    };
+    let scheme = if tls { "https" } else { "http" };
+    format!("{scheme}://{host}:{local_port}")
+}
+
+fn is_hop_by_hop(name: &str) -> bool {
+    HOP_BY_HOP_HEADERS
+        .iter()
+        .any(|header| name.eq_ignore_ascii_case(header))
+}
+
+fn relay_secret() -> &'static str {
+    static SECRET: OnceLock<String> = OnceLock::new();
+    SECRET.get_or_init(|| uuid::Uuid::new_v4().simple().to_string())
+}
+
+/// Rate-limit key of a request replayed by the relay tunnel:
+/// `relay:<client ip>` (`relay:unknown` when the relay did not report one).
+/// `None` for requests that did not come through this process's relay, so a
+/// remote client cannot forge the header.
+pub fn relayed_client_key(headers: &axum::http::HeaderMap) -> Option<String> {
+    let secret = headers.get(RELAY_SECRET_HEADER)?.to_str().ok()?;
+    if !crate::security::pairing::constant_time_eq(secret, relay_secret()) {
+        return None;
+    }
+    let client = headers
+        .get(RELAY_CLIENT_HEADER)
+        .and_then(|v| v.to_str().ok())
+        .and_then(client_ip)
+        .map_or_else(|| "unknown".to_string(), |ip| ip.to_string());
+    Some(format!("relay:{client}"))
+}
+
+/// IP of a client address as relays report it, with or without a port.
+fn client_ip(value: &str) -> Option<IpAddr> {
+    let value = value.trim();
+    value
+        .parse::<SocketAddr>()
+        .map(|addr| addr.ip())
+        .or_else(|_| value.trim_matches(['[', ']']).parse::<IpAddr>())
+        .ok()
+}
+
+/// Keep a relay connection up, reconnecting with backoff once the first
+/// connection has succeeded. `ready` reports the first connection's outcome.
+async fn supervise(
+    url: String,
+    token: String,
+    public_url: Option<String>,
+    local: String,
+    client: reqwest::Client,
+    state: Arc<Mutex<RelayState>>,
+    ready_tx: oneshot::Sender<Result<String>>,
+) {
+    let mut ready = Some(ready_tx);
+    let mut backoff = RECONNECT_BACKOFF_INITIAL;
+
+    loop {
+        let result = connect_and_serve(
+            &url,
+            &token,
+            public_url.as_deref(),
+            &local,
+            &client,
+            &state,
+            &mut ready,
+        )
+        .await;
+        state.lock().connected = false;
+
+        if let Some(ready) = ready.take() {
+            let error = result
+                .err()
+                .unwrap_or_else(|| anyhow::anyhow!("Relay closed the connection before hello"));
+            let _ = ready.send(Err(error));
+            return;
+        }
+        match result {
+            Ok(()) => {
+                tracing::warn!("Relay connection closed; reconnecting");
+                backoff = RECONNECT_BACKOFF_INITIAL;
+            }
+            Err(e) => tracing::warn!(
+                "Relay connection failed: {e:#}; retrying in {}s",
+                backoff.as_secs()
+            ),
+        }
+
+        tokio::time::sleep(backoff).await;
+        backoff = backoff.saturating_mul(2).min(RECONNECT_BACKOFF_MAX);
+    }
+}
+
+async fn connect_and_serve(
+    url: &str,
+    token: &str,
+    public_url: Option<&str>,
+    local: &str,
+    client: &reqwest::Client,
+    state: &Mutex<RelayState>,
+    ready: &mut ReadySender,
+) -> Result<()> {
+    let mut request = url
+        .into_client_request()
+        .with_context(|| format!("Invalid relay URL: {url}"))?;
+    request.headers_mut().insert(
+        "Authorization",
+        HeaderValue::from_str(&format!("Bearer {token}"))
+            .context("Relay token contains invalid characters")?,
+    );
+    let (ws, _) = tokio_tungstenite::connect_async(request)
+        .await
+        .with_context(|| format!("Failed to connect to relay {url}"))?;
+    let (mut write, mut read) = ws.split();
+
+    let (out_tx, mut out_rx) = mpsc::unbounded_channel::<PhoneFrame>();
+    let mut in_flight: HashMap<String, AbortHandle> = HashMap::new();
+    let mut ping = tokio::time::interval(PING_INTERVAL);
+    ping.tick().await;
+
+    let result = loop {
+        tokio::select! {
+            Some(frame) = out_rx.recv() => {
+                let text = serde_json::to_string(&frame)?;
+                if let Err(e) = write.send(Message::Text(text.into())).await {
+                    break Err(e.into());
+                }
+            }
+            _ = ping.tick() => {
+                if let Err(e) = write.send(Message::Ping(Vec::new().into())).await {
+                    break Err(e.into());
+                }
+            }
+            msg = read.next() => {
+                let text = match msg {
+                    Some(Ok(Message::Text(text))) => text,
+                    Some(Ok(Message::Close(_))) | None => break Ok(()),
+                    Some(Ok(_)) => continue,
+                    Some(Err(e)) => break Err(e.into()),
+                };
+                let frame = match serde_json::from_str::<RelayFrame>(text.as_str()) {
+                    Ok(frame) => frame,
+                    Err(e) => {
+                        tracing::debug!("Ignoring malformed relay frame: {e}");
+                        continue;
+                    }
+                };
+                match frame {
+                    RelayFrame::Hello { public_url: announced } => {
+                        let Some(url) = public_url.map(str::to_string).or(announced) else {
+                            break Err(anyhow::anyhow!(
+                                "Relay did not report a public URL; set [tunnel.relay] public_url"
+                            ));
+                        };
+                        {
+                            let mut state = state.lock();
+                            state.connected = true;
+                            state.public_url = Some(url.clone());
+                        }
+                        tracing::info!("Relay connected; gateway reachable at {url}");
+                        if let Some(ready) = ready.take() {
+                            let _ = ready.send(Ok(url));
+                        }
+                    }
+                    RelayFrame::Request { id, method, path, headers, body, client: remote } => {
+                        in_flight.retain(|_, handle| !handle.is_finished());
+                        let task = tokio::spawn(forward(
+                            client.clone(),
+                            local.to_string(),
+                            id.clone(),
+                            method,
+                            path,
+                            headers,
+                            body,
+                            remote,
+                            out_tx.clone(),
+                        ));
+                        in_flight.insert(id, task.abort_handle());
+                    }
+                    RelayFrame::Cancel { id } => {
+                        if let Some(handle) = in_flight.remove(&id) {
+                            handle.abort();
+                        }
+                    }
+                }
+            }
+        }
+    };
+
+    for handle in in_flight.values() {
+        handle.abort();
+    }
+    result
+}
+
+/// Replay one relayed request against the local gateway and stream its
+/// response back as `response`, `data` and `end` frames.
+async fn forward(
+    client: reqwest::Client,
+    local: String,
+    id: String,
+    method: String,
+    path: String,
+    headers: Vec<(String, String)>,
+    body: String,
+    remote: Option<String>,
+    out: mpsc::UnboundedSender<PhoneFrame>,
+) {
+    let result = stream_response(
+        &client,
+        &local,
+        &id,
+        &method,
+        &path,
+        headers,
+        &body,
+        remote.as_deref(),
+        &out,
+    )
+    .await;
+    let error = result.err().map(|e| {
+        tracing::debug!(id = %id, "Relayed request failed: {e:#}");
+        format!("{e:#}")
+    });
+    let _ = out.send(PhoneFrame::End { id, error });
+}
+
+async fn stream_response(
+    client: &reqwest::Client,
+    local: &str,
+    id: &str,
+    method: &str,
+    path: &str,
+    headers: Vec<(String, String)>,
+    body: &str,
+    remote: Option<&str>,
+    out: &mpsc::UnboundedSender<PhoneFrame>,
+) -> Result<()> {
+    // Only paths on the local gateway, never another host.
+    if !path.starts_with('/') {
+        bail!("Relayed path must start with '/': {path}");
+    }
+    let method = reqwest::Method::from_bytes(method.as_bytes())
+        .with_context(|| format!("Invalid method: {method}"))?;
+    let body = BASE64
+        .decode(body)
+        .context("Request body is not valid base64")?;
+
+    let remote = remote
+        .and_then(client_ip)
+        .map_or_else(|| "unknown".to_string(), |ip| ip.to_string());
+    let mut request = client
+        .request(method, format!("{local}{path}"))
+        .header(RELAY_SECRET_HEADER, relay_secret())
+        .header(RELAY_CLIENT_HEADER, remote);
+    for (name, value) in headers {
+        let is_relay_header = name.eq_ignore_ascii_case(RELAY_SECRET_HEADER)
+            || name.eq_ignore_ascii_case(RELAY_CLIENT_HEADER);
+        if !is_hop_by_hop(&name) && !is_relay_header {
+            request = request.header(name, value);
+        }
+    }
+    let response = request
+        .body(body)
+        .send()
+        .await
+        .context("Gateway request failed")?;
+
+    let headers = response
+        .headers()
+        .iter()
+        .filter(|(name, _)| !is_hop_by_hop(name.as_str()))
+        .filter_map(|(name, value)| Some((name.to_string(), value.to_str().ok()?.to_string())))
+        .collect();
+    out.send(PhoneFrame::Response {
+        id: id.to_string(),
+        status: response.status().as_u16(),
+        headers,
+    })?;
+
+    let mut body = response.bytes_stream();
+    while let Some(chunk) = body.next().await {
+        let chunk = chunk.context("Gateway response body failed")?;
+        out.send(PhoneFrame::Data {
+            id: id.to_string(),
+            body: BASE64.encode(chunk),
+        })?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::extract::ws::{Message as AxumMessage, WebSocketUpgrade};
+    use axum::http::HeaderMap;
+    use axum::routing::{get, post};
+    use axum::Router;
+
+    async fn serve(app: Router) -> u16 {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+        port
+    }
+
+    #[test]
+    fn frames_use_the_documented_wire_format() {
+        let frame: RelayFrame =
+            serde_json::from_str(r#"{"type":"request","id":"7","method":"GET","path":"/health"}"#)
+                .unwrap();
+        assert_eq!(
+            frame,
+            RelayFrame::Request {
+                id: "7".into(),
+                method: "GET".into(),
+                path: "/health".into(),
+                headers: Vec::new(),
+                body: String::new(),
+                client: None,
+            }
+        );
+
+        let end = serde_json::to_value(PhoneFrame::End {
+            id: "7".into(),
+            error: None,
+        })
+        .unwrap();
+        assert_eq!(end, serde_json::json!({"type": "end", "id": "7"}));
+    }
+
+    #[test]
+    fn local_url_reaches_wildcard_binds_over_loopback() {
+        assert_eq!(
+            local_base_url("0.0.0.0", 42617, false),
+            "http://127.0.0.1:42617"
+        );
+        assert_eq!(local_base_url("::", 8080, false), "http://[::1]:8080");
+        assert_eq!(
+            local_base_url("100.101.102.103", 8080, false),
+            "http://100.101.102.103:8080"
+        );
+        assert_eq!(
+            local_base_url("0.0.0.0", 8443, true),
+            "https://127.0.0.1:8443"
+        );
+    }
+
+    #[test]
+    fn relayed_client_key_requires_the_relay_secret() {
+        let mut headers = HeaderMap::new();
+        headers.insert(RELAY_CLIENT_HEADER, "127.0.0.1".parse().unwrap());
+        assert_eq!(relayed_client_key(&headers), None);
+
+        headers.insert(RELAY_SECRET_HEADER, "forged".parse().unwrap());
+        assert_eq!(relayed_client_key(&headers), None);
+
+        headers.insert(RELAY_SECRET_HEADER, relay_secret().parse().unwrap());
+        assert_eq!(
+            relayed_client_key(&headers).as_deref(),
+            Some("relay:127.0.0.1")
+        );
+        headers.insert(RELAY_CLIENT_HEADER, "[2001:db8::1]:443".parse().unwrap());
+        assert_eq!(
+            relayed_client_key(&headers).as_deref(),
+            Some("relay:2001:db8::1")
+        );
+        headers.remove(RELAY_CLIENT_HEADER);
+        assert_eq!(
+            relayed_client_key(&headers).as_deref(),
+            Some("relay:unknown")
+        );
+    }
+
+    /// A relay that sends `request` after `hello` and reports the frames it
+    /// gets back.
+    async fn fake_relay(
+        request: serde_json::Value,
+    ) -> (u16, mpsc::UnboundedReceiver<serde_json::Value>) {
+        let (frames_tx, frames_rx) = mpsc::unbounded_channel::<serde_json::Value>();
+        let relay = Router::new().route(
+            "/connect",
+            get(move |headers: HeaderMap, ws: WebSocketUpgrade| {
+                let frames_tx = frames_tx.clone();
+                let request = request.clone();
+                async move {
+                    assert_eq!(headers["authorization"], "Bearer relay-secret");
+                    ws.on_upgrade(move |mut socket| async move {
+                        let hello = r#"{"type":"hello","public_url":"https://relay.example/p/1"}"#;
+                        socket.send(AxumMessage::Text(hello.into())).await.unwrap();
+                        socket
+                            .send(AxumMessage::Text(request.to_string().into()))
+                            .await
+                            .unwrap();
+                        while let Some(Ok(msg)) = socket.recv().await {
+                            if let AxumMessage::Text(text) = msg {
+                                let frame: serde_json::Value =
+                                    serde_json::from_str(text.as_str()).unwrap();
+                                let done = frame["type"] == "end";
+                                frames_tx.send(frame).unwrap();
+                                if done {
+                                    break;
+                                }
+                            }
+                        }
+                    })
+                }
+            }),
+        );
+        (serve(relay).await, frames_rx)
+    }
+
+    /// Frames of one relayed request, through `end`, and its decoded body.
+    async fn collect_frames(
+        frames_rx: &mut mpsc::UnboundedReceiver<serde_json::Value>,
+    ) -> (Vec<serde_json::Value>, String) {
+        let mut frames = Vec::new();
+        while let Some(frame) = frames_rx.recv().await {
+            let done = frame["type"] == "end";
+            frames.push(frame);
+            if done {
+                break;
+            }
+        }
+        let body: Vec<u8> = frames
+            .iter()
+            .filter(|frame| frame["type"] == "data")
+            .flat_map(|frame| BASE64.decode(frame["body"].as_str().unwrap()).unwrap())
+            .collect();
+        (frames, String::from_utf8(body).unwrap())
+    }
+
+    #[tokio::test]
+    async fn relayed_requests_reach_the_gateway_and_stream_back() {
+        let gateway = Router::new().route(
+            "/agent/message",
+            post(|headers: HeaderMap, body: String| async move {
+                let auth = headers
+                    .get("authorization")
+                    .and_then(|v| v.to_str().ok())
+                    .unwrap_or_default()
+                    .to_string();
+                let client = relayed_client_key(&headers).unwrap_or_default();
+                format!("{auth}|{client}|{body}")
+            }),
+        );
+        let gateway_port = serve(gateway).await;
+
+        let (relay_port, mut frames_rx) = fake_relay(serde_json::json!({
+            "type": "request",
+            "id": "r1",
+            "method": "POST",
+            "path": "/agent/message",
+            "headers": [
+                ["Authorization", "Bearer zc_token"],
+                ["Host", "relay.example"],
+                ["X-ZeroClaw-Relay-Client", "127.0.0.1"],
+            ],
+            "body": BASE64.encode("hi"),
+            "client": "203.0.113.7:50123",
+        }))
+        .await;
+
+        let tunnel = RelayTunnel::new(
+            format!("ws://127.0.0.1:{relay_port}/connect"),
+            "relay-secret".into(),
+            None,
+        );
+        let url = tunnel.start("0.0.0.0", gateway_port).await.unwrap();
+        assert_eq!(url, "https://relay.example/p/1");
+        assert!(tunnel.health_check().await);
+
+        let (frames, body) = collect_frames(&mut frames_rx).await;
+        assert_eq!(frames[0]["type"], "response");
+        assert_eq!(frames[0]["status"], 200);
+        assert_eq!(body, "Bearer zc_token|relay:203.0.113.7|hi");
+        assert_eq!(
+            frames.last().unwrap(),
+            &serde_json::json!({"type": "end", "id": "r1"})
+        );
+
+        tunnel.stop().await.unwrap();
+        assert!(tunnel.public_url().is_none());
+    }
+
+    #[tokio::test]
+    async fn relayed_requests_reach_a_tls_gateway() {
+        use crate::gateway::tls;
+
+        let tmp = tempfile::TempDir::new().unwrap();
+        let mut config = crate::config::Config {
+            config_path: tmp.path().join("config.toml"),
+            workspace_dir: tmp.path().join("workspace"),
+            ..crate::config::Config::default()
+        };
+        config.gateway.tls.enabled = true;
+        // Named for another host, so only pinning lets loopback through.
+        config.gateway.host = "gateway.lan".into();
+        let gateway_tls = tls::load(&config).unwrap();
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let gateway_port = listener.local_addr().unwrap().port();
+        let listener = tls::TlsListener::new(listener, gateway_tls.acceptor).unwrap();
+        let gateway = Router::new().route("/health", get(|| async { "ok" }));
+        tokio::spawn(async move { axum::serve(listener, gateway).await.unwrap() });
+
+        let (relay_port, mut frames_rx) = fake_relay(serde_json::json!({
+            "type": "request",
+            "id": "r1",
+            "method": "GET",
+            "path": "/health",
+        }))
+        .await;
+        let tunnel = RelayTunnel::new(
+            format!("ws://127.0.0.1:{relay_port}/connect"),
+            "relay-secret".into(),
+            None,
+        )
+        .with_local_tls(Some(gateway_tls.certificate));
+        tunnel.start("127.0.0.1", gateway_port).await.unwrap();
+
+        let (frames, body) = collect_frames(&mut frames_rx).await;
+        assert_eq!(frames[0]["status"], 200, "{frames:?}");
+        assert_eq!(body, "ok");
+        assert_eq!(
+            frames.last().unwrap(),
+            &serde_json::json!({"type": "end", "id": "r1"})
+        );
+        tunnel.stop().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn relay_refuses_a_gateway_with_another_certificate() {
+        use crate::gateway::tls;
+
+        let tmp = tempfile::TempDir::new().unwrap();
+        let mut config = crate::config::Config {
+            config_path: tmp.path().join("config.toml"),
+            workspace_dir: tmp.path().join("workspace"),
+            ..crate::config::Config::default()
+        };
+        config.gateway.tls.enabled = true;
+        config.gateway.host = "gateway.lan".into();
+        let pinned = tls::load(&config).unwrap().certificate;
+        // Replaced for another host: the pinned certificate is stale.
+        config.gateway.host = "10.0.0.5".into();
+        let gateway_tls = tls::load(&config).unwrap();
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let gateway_port = listener.local_addr().unwrap().port();
+        let listener = tls::TlsListener::new(listener, gateway_tls.acceptor).unwrap();
+        let gateway = Router::new().route("/health", get(|| async { "ok" }));
+        tokio::spawn(async move { axum::serve(listener, gateway).await.unwrap() });
+
+        let (relay_port, mut frames_rx) = fake_relay(serde_json::json!({
+            "type": "request",
+            "id": "r1",
+            "method": "GET",
+            "path": "/health",
+        }))
+        .await;
+        let tunnel = RelayTunnel::new(
+            format!("ws://127.0.0.1:{relay_port}/connect"),
+            "relay-secret".into(),
+            None,
+        )
+        .with_local_tls(Some(pinned));
+        tunnel.start("127.0.0.1", gateway_port).await.unwrap();
+
+        let (frames, _) = collect_frames(&mut frames_rx).await;
+        assert_eq!(frames.len(), 1, "{frames:?}");
+        assert!(frames[0]["error"].is_string());
+        tunnel.stop().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn start_fails_when_relay_is_unreachable() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        drop(listener);
+
+        let tunnel = RelayTunnel::new(format!("ws://127.0.0.1:{port}"), "t".into(), None);
+        let err = tunnel.start("127.0.0.1", 1).await.unwrap_err();
+        assert!(err.to_string().contains("Failed to connect to relay"));
+        assert!(!tunnel.health_check().await);
+    }
+}
@@ -151,6 +151,12 @@ async fn openai_codex_second_vision_support() -> Result<()> {
         zeroclaw_dir: None,
         secrets_encrypt: false,
         reasoning_enabled: None,
+        gemini_safety_settings: Vec::new(),
+        azure_openai: zeroclaw::config::AzureOpenAiConfig::default(),
+        openrouter: zeroclaw::config::OpenRouterConfig::default(),
+        local_llm: zeroclaw::config::LocalLlmConfig::default(),
+        workspace_dir: None,
+        response_cache: None,
     };
 
     let provider = zeroclaw::providers::create_provider_with_options("openai-codex", None, &opts)?;
@@ -0,0 +1,241 @@
+// Minimal chat + status UI served by the gateway at `/`.
+//
+// Talks to the same endpoints as any other client: `POST /pair` to obtain a
+// token, `GET /api/status` for the status panel and `GET /ws` for agent turns
+// and notifications.
+"use strict";
+
+const TOKEN_KEY = "mobileclaw.token";
+const SESSION_KEY = "mobileclaw.session";
+const STATUS_INTERVAL_MS = 15000;
+
+const $ = (id) => document.getElementById(id);
+
+let socket = null;
+let reconnectDelay = 1000;
+let reconnectTimer = null;
+let statusTimer = null;
+let reply = null;
+
+function token() {
+  return localStorage.getItem(TOKEN_KEY);
+}
+
+function sessionId() {
+  let id = localStorage.getItem(SESSION_KEY);
+  if (!id) {
+    id = "web-" + Math.random().toString(36).slice(2, 10);
+    localStorage.setItem(SESSION_KEY, id);
+  }
+  return id;
+}
+
+function show(signedIn) {
+  $("login").hidden = signedIn;
+  $("app").hidden = !signedIn;
+  $("logout").hidden = !signedIn;
+}
+
+function signOut(message) {
+  localStorage.removeItem(TOKEN_KEY);
+  clearInterval(statusTimer);
+  clearTimeout(reconnectTimer);
+  if (socket) {
+    socket.onclose = null;
+    socket.close();
+    socket = null;
+  }
+  setOnline(false);
+  show(false);
+  const error = $("login-error");
+  error.hidden = !message;
+  error.textContent = message || "";
+}
+
+function signIn() {
+  show(true);
+  refreshStatus();
+  statusTimer = setInterval(refreshStatus, STATUS_INTERVAL_MS);
+  connect();
+}
+
+// ── Pairing ─────────────────────────────────────────────────────
+
+$("pair-form").addEventListener("submit", async (event) => {
+  event.preventDefault();
+  const code = $("pair-code").value.trim();
+  const apiToken = $("api-token").value.trim();
+  try {
+    if (apiToken) {
+      localStorage.setItem(TOKEN_KEY, apiToken);
+    } else if (code) {
+      const response = await fetch("/pair", {
+        method: "POST",
+        headers: { "X-Pairing-Code": code },
+      });
+      const body = await response.json().catch(() => ({}));
+      if (!response.ok || !body.token) {
+        throw new Error(body.error || "Pairing failed");
+      }
+      localStorage.setItem(TOKEN_KEY, body.token);
+    } else {
+      return;
+    }
+    $("pair-code").value = "";
+    $("api-token").value = "";
+    $("login-error").hidden = true;
+    signIn();
+  } catch (error) {
+    signOut(error.message);
+  }
+});
+
+$("logout").addEventListener("click", () => signOut());
+
+// ── Status ──────────────────────────────────────────────────────
+
+function formatUptime(seconds) {
+  const h = Math.floor(seconds / 3600);
+  const m = Math.floor((seconds % 3600) / 60);
+  return h ? `${h}h ${m}m` : `${m}m`;
+}
+
+async function refreshStatus() {
+  let status;
+  try {
+    const response = await fetch("/api/status", {
+      headers: { Authorization: `Bearer ${token()}` },
+    });
+    if (response.status === 401) {
+      signOut("The saved token is no longer valid. Pair again.");
+      return;
+    }
+    status = await response.json();
+  } catch {
+    return;
+  }
+
+  const channels = Object.entries(status.channels || {})
+    .filter(([, enabled]) => enabled)
+    .map(([name]) => name);
+  const fields = {
+    Provider: status.provider || "—",
+    Model: status.model || "—",
+    Uptime: formatUptime(status.uptime_seconds || 0),
+    Memory: status.memory_backend || "—",
+    Channels: channels.length ? channels.join(", ") : "none",
+  };
+  const list = $("status-fields");
+  list.replaceChildren();
+  for (const [label, value] of Object.entries(fields)) {
+    const dt = document.createElement("dt");
+    dt.textContent = label;
+    const dd = document.createElement("dd");
+    dd.textContent = value;
+    list.append(dt, dd);
+  }
+
+  const components = $("components");
+  components.replaceChildren();
+  for (const [name, health] of Object.entries(status.health?.components || {})) {
+    const item = document.createElement("li");
+    item.textContent = name;
+    item.title = health.last_error || health.status;
+    item.classList.toggle("ok", health.status === "ok");
+    components.append(item);
+  }
+}
+
+// ── Chat ────────────────────────────────────────────────────────
+
+function addMessage(kind, text) {
+  const element = document.createElement("div");
+  element.className = `message ${kind}`;
+  element.textContent = text;
+  const messages = $("messages");
+  messages.append(element);
+  messages.scrollTop = messages.scrollHeight;
+  return element;
+}
+
+function setOnline(online) {
+  const dot = $("connection");
+  dot.classList.toggle("online", online);
+  dot.title = online ? "Connected" : "Disconnected";
+  $("send").disabled = !online;
+}
+
+function connect() {
+  const scheme = location.protocol === "https:" ? "wss" : "ws";
+  const query = new URLSearchParams({ token: token(), session: sessionId() });
+  socket = new WebSocket(`${scheme}://${location.host}/ws?${query}`);
+
+  socket.onopen = () => {
+    reconnectDelay = 1000;
+    setOnline(true);
+  };
+
+  socket.onmessage = (event) => {
+    let frame;
+    try {
+      frame = JSON.parse(event.data);
+    } catch {
+      return;
+    }
+    switch (frame.type) {
+      case "chunk":
+        reply = reply || addMessage("agent", "");
+        reply.textContent += frame.content;
+        break;
+      case "tool_call":
+        addMessage("event", `🔧 ${frame.name}`);
+        break;
+      case "done":
+        if (!reply) {
+          reply = addMessage("agent", "");
+        }
+        reply.textContent = frame.full_response;
+        reply = null;
+        break;
+      case "error":
+        addMessage("error", frame.message);
+        reply = null;
+        break;
+      case "notification":
+        addMessage("event", `🔔 ${frame.topic}: ${frame.content}`);
+        break;
+    }
+  };
+
+  socket.onclose = () => {
+    setOnline(false);
+    reply = null;
+    reconnectTimer = setTimeout(connect, reconnectDelay);
+    reconnectDelay = Math.min(reconnectDelay * 2, 30000);
+  };
+}
+
+$("chat-form").addEventListener("submit", (event) => {
+  event.preventDefault();
+  const prompt = $("prompt");
+  const content = prompt.value.trim();
+  if (!content || !socket || socket.readyState !== WebSocket.OPEN) {
+    return;
+  }
+  socket.send(JSON.stringify({ type: "message", content }));
+  addMessage("user", content);
+  prompt.value = "";
+});
+
+$("prompt").addEventListener("keydown", (event) => {
+  if (event.key === "Enter" && !event.shiftKey) {
+    event.preventDefault();
+    $("chat-form").requestSubmit();
+  }
+});
+
+if (token()) {
+  signIn();
+} else {
+  show(false);
+}